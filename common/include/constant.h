@@ -48,6 +48,9 @@ enum ExceptionErrorCode : int32_t {
     E_TASK_NOT_FOUND = 21900006,
     E_TASK_STATE = 21900007,
     E_GROUP_NOT_FOUND = 21900008,
+    E_CONSTRUCT_THROTTLED = 21900009,
+    E_TOO_MANY_TASKS = 21900010,
+    E_TOO_MANY_CHANNELS = 21900011,
 };
 
 struct ExceptionError {
@@ -68,6 +71,9 @@ static constexpr const char *E_TASK_MODE_INFO = "Operation with wrong task mode"
 static constexpr const char *E_TASK_NOT_FOUND_INFO = "Task removed or not found";
 static constexpr const char *E_TASK_STATE_INFO = "Operation with wrong task state";
 static constexpr const char *E_GROUP_NOT_FOUND_INFO = "Group deleted or not found";
+static constexpr const char *E_CONSTRUCT_THROTTLED_INFO = "Too many tasks created in a short time";
+static constexpr const char *E_TOO_MANY_TASKS_INFO = "The application has too many tasks";
+static constexpr const char *E_TOO_MANY_CHANNELS_INFO = "The service has too many open channels";
 
 static constexpr const char *FUNCTION_PAUSE = "pause";
 static constexpr const char *FUNCTION_QUERY = "query";
@@ -70,6 +70,26 @@ pub struct TaskConfig {
     pub saveas: String,
     pub overwrite: bool,
     pub notification: Notification,
+    /// Per-task options sent after the notification of the task.
+    pub options: TaskOptions,
+}
+
+impl TaskConfig {
+    /// Adds an HTTP header to the request, replacing any header of the same
+    /// name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use request_core::config::{TaskConfigBuilder, Version};
+    ///
+    /// let mut config = TaskConfigBuilder::new(Version::API10).build();
+    /// config.add_header("User-Agent", "MyApp/1.0");
+    /// assert_eq!(config.headers["User-Agent"], "MyApp/1.0");
+    /// ```
+    pub fn add_header(&mut self, key: &str, value: &str) {
+        self.headers.insert(key.to_string(), value.to_string());
+    }
 }
 
 /// Builder for creating a `TaskConfig` with a fluent interface.
@@ -80,12 +100,17 @@ pub struct TaskConfig {
 /// # Examples
 ///
 /// ```rust
-/// let config = TaskConfigBuilder::new(Version::API10)
+/// use request_core::config::{TaskConfigBuilder, Version};
+///
+/// let mut builder = TaskConfigBuilder::new(Version::API10);
+/// builder
 ///     .url("https://example.com".to_string())
 ///     .title("Example Download".to_string())
 ///     .description("Download example file".to_string())
-///     .background(true)
-///     .build();
+///     .header("User-Agent", "MyApp/1.0")
+///     .background(true);
+/// let config = builder.build();
+/// assert_eq!(config.headers["User-Agent"], "MyApp/1.0");
 /// ```
 pub struct TaskConfigBuilder {
     version: Version,
@@ -115,6 +140,7 @@ pub struct TaskConfigBuilder {
     data: Option<Vec<FormItem>>,
     action: Action,
     // notification: Option<Notification>,
+    options: TaskOptions,
 }
 
 impl TaskConfigBuilder {
@@ -139,6 +165,7 @@ impl TaskConfigBuilder {
             data: None,
             action: Action::Download,
             // notification: None,
+            options: TaskOptions::default(),
         }
     }
 
@@ -154,6 +181,15 @@ impl TaskConfigBuilder {
         self
     }
 
+    /// Adds an HTTP header to the request, replacing any header of the same
+    /// name set before.
+    pub fn header(&mut self, key: &str, value: &str) -> &mut Self {
+        self.headers
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
     /// Sets whether the task should run on metered connections.
     pub fn metered(&mut self, enable: bool) -> &mut Self {
         self.enable_metered = Some(enable);
@@ -231,6 +267,250 @@ impl TaskConfigBuilder {
         self
     }
 
+    /// Sets the number of concurrent range connections of a download.
+    pub fn segments(&mut self, segments: u32) -> &mut Self {
+        self.options.segments = Some(segments);
+        self
+    }
+
+    /// Sets how many bytes of the upload response body are kept, 0 disables it.
+    pub fn response_body_limit(&mut self, limit: u32) -> &mut Self {
+        self.options.response_body_limit = Some(limit);
+        self
+    }
+
+    /// Sets how the current network is treated regardless of its reported
+    /// metered flag.
+    pub fn treat_network_as(&mut self, treat_as: MeteredOverride) -> &mut Self {
+        self.options.treat_network_as = Some(treat_as);
+        self
+    }
+
+    /// Sets whether a resumed download whose range the server rejects fails
+    /// instead of downloading the file again from the beginning.
+    pub fn strict_resume(&mut self, strict_resume: bool) -> &mut Self {
+        self.options.strict_resume = Some(strict_resume);
+        self
+    }
+
+    /// Sets whether disk space for the whole file is reserved once the
+    /// download size is known.
+    pub fn preallocate(&mut self, preallocate: bool) -> &mut Self {
+        self.options.preallocate = Some(preallocate);
+        self
+    }
+
+    /// Sets the longest delay in milliseconds the task waits when the server
+    /// rate limits it with `Retry-After`, 0 fails the task instead.
+    pub fn retry_after_cap_ms(&mut self, retry_after_cap_ms: u64) -> &mut Self {
+        self.options.retry_after_cap_ms = Some(retry_after_cap_ms);
+        self
+    }
+
+    /// Sets whether state changes, headers and retry decisions of the task
+    /// are logged in detail.
+    pub fn debug(&mut self, debug: bool) -> &mut Self {
+        self.options.debug = Some(debug);
+        self
+    }
+
+    /// Sets whether a download is synced to disk and its size checked before
+    /// it is reported completed.
+    pub fn durable_completion(&mut self, durable_completion: bool) -> &mut Self {
+        self.options.durable_completion = Some(durable_completion);
+        self
+    }
+
+    /// Sets how creating a download that is already active is handled.
+    pub fn duplicate_policy(&mut self, duplicate_policy: DuplicatePolicy) -> &mut Self {
+        self.options.duplicate_policy = Some(duplicate_policy);
+        self
+    }
+
+    /// Sets the address families the task connects to and their order.
+    pub fn ip_preference(&mut self, ip_preference: IpPreference) -> &mut Self {
+        self.options.ip_preference = Some(ip_preference);
+        self
+    }
+
+    /// Sets the lowest TLS version the task negotiates.
+    pub fn min_tls_version(&mut self, version: TlsProtocol) -> &mut Self {
+        self.options.min_tls_version = Some(version);
+        self
+    }
+
+    /// Sets the highest TLS version the task negotiates.
+    pub fn max_tls_version(&mut self, version: TlsProtocol) -> &mut Self {
+        self.options.max_tls_version = Some(version);
+        self
+    }
+
+    /// Sets the ciphers allowed for TLS 1.2 connections, as an OpenSSL
+    /// cipher list.
+    pub fn tls_ciphers(&mut self, ciphers: &str) -> &mut Self {
+        self.options.tls_ciphers = Some(ciphers.to_string());
+        self
+    }
+
+    /// Sets the opaque tag of the application returned with every
+    /// notification of the task, at most 256 bytes.
+    pub fn client_tag(&mut self, client_tag: &str) -> &mut Self {
+        self.options.client_tag = Some(client_tag.to_string());
+        self
+    }
+
+    /// Sets whether each redirect followed by the task is reported to the
+    /// client.
+    pub fn report_redirects(&mut self, report_redirects: bool) -> &mut Self {
+        self.options.report_redirects = Some(report_redirects);
+        self
+    }
+
+    /// Adds a content type the download accepts, such as `image/png` or
+    /// `image/*`.
+    pub fn expected_mime_type(&mut self, mime_type: &str) -> &mut Self {
+        self.options
+            .expected_mime_types
+            .get_or_insert_with(Vec::new)
+            .push(mime_type.to_string());
+        self
+    }
+
+    /// Sets the ETag the remote object must still have for an upload to
+    /// replace it, sent as `If-Match`.
+    pub fn if_match(&mut self, etag: &str) -> &mut Self {
+        self.options.if_match = Some(etag.to_string());
+        self
+    }
+
+    /// Sets whether certificate verification is skipped, for test servers
+    /// with self-signed certificates. Ignored unless the caller is a system
+    /// application.
+    pub fn insecure_skip_verify(&mut self, skip: bool) -> &mut Self {
+        self.options.insecure_skip_verify = Some(skip);
+        self
+    }
+
+    /// Sets how the download resumes from the bytes it already has.
+    pub fn resume_strategy(&mut self, strategy: ResumeStrategy) -> &mut Self {
+        self.options.resume_strategy = Some(strategy);
+        self
+    }
+
+    /// Sets the time in milliseconds since the epoch after which the task
+    /// fails instead of retrying, 0 retries until the retry budget is used.
+    pub fn retry_deadline_ms(&mut self, deadline_ms: u64) -> &mut Self {
+        self.options.retry_deadline_ms = Some(deadline_ms);
+        self
+    }
+
+    /// Caps the size of each file of the task.
+    pub fn max_size_bytes(&mut self, bytes: u64) -> &mut Self {
+        self.options.max_size_bytes = Some(bytes);
+        self
+    }
+
+    /// Caps the size of all files of the task together.
+    pub fn max_total_bytes(&mut self, bytes: u64) -> &mut Self {
+        self.options.max_total_bytes = Some(bytes);
+        self
+    }
+
+    /// Sets the content codings advertised in `Accept-Encoding`, replacing
+    /// the header of the task. `identity` disables compression.
+    pub fn accept_encoding(&mut self, encoding: &str) -> &mut Self {
+        self.options.accept_encoding = Some(encoding.to_string());
+        self
+    }
+
+    /// Binds the task to the network of the given handle instead of the
+    /// default network.
+    pub fn net_id(&mut self, net_id: i32) -> &mut Self {
+        self.options.net_id = Some(net_id);
+        self
+    }
+
+    /// Sets what happens to the files of the task when it is removed.
+    pub fn on_remove_partial(&mut self, policy: PartialFilePolicy) -> &mut Self {
+        self.options.on_remove_partial = Some(policy);
+        self
+    }
+
+    /// Sets whether a download named after its URL takes the file name of
+    /// the `Content-Disposition` header of the response instead.
+    pub fn content_disposition_name(&mut self, enabled: bool) -> &mut Self {
+        self.options.content_disposition_name = Some(enabled);
+        self
+    }
+
+    /// Caps the redirects to another host a request of the task follows.
+    pub fn max_cross_host_redirects(&mut self, count: u32) -> &mut Self {
+        self.options.max_cross_host_redirects = Some(count);
+        self
+    }
+
+    /// Accepts addresses of hosts cached up to `ms` milliseconds ago instead
+    /// of the lifetime of the shared DNS cache, 0 resolves on every
+    /// connection.
+    pub fn dns_ttl_override_ms(&mut self, ms: u64) -> &mut Self {
+        self.options.dns_ttl_override_ms = Some(ms);
+        self
+    }
+
+    /// Propagates a W3C trace context with every request of the task. The
+    /// `traceparent` must be valid.
+    pub fn trace_context(&mut self, traceparent: &str, tracestate: Option<&str>) -> &mut Self {
+        let mut context = traceparent.to_string();
+        if let Some(state) = tracestate {
+            context.push('\n');
+            context.push_str(state);
+        }
+        self.options.trace_context = Some(context);
+        self
+    }
+
+    /// Sets whether a completed download records the size and content type
+    /// of its file in the progress `extras`.
+    pub fn record_final_metadata(&mut self, enabled: bool) -> &mut Self {
+        self.options.record_final_metadata = Some(enabled);
+        self
+    }
+
+    /// Sets whether an upload sends its files in order as the body of one
+    /// request, without framing.
+    pub fn concatenated(&mut self, concatenated: bool) -> &mut Self {
+        self.options.concatenated = Some(concatenated);
+        self
+    }
+
+    /// Makes the task wait while the thermal level of the device is `level`
+    /// or higher.
+    pub fn pause_on_thermal_level(&mut self, level: u8) -> &mut Self {
+        self.options.pause_on_thermal_level = Some(level);
+        self
+    }
+
+    /// Sets whether the task waits while the device is not charging,
+    /// whatever its mode.
+    pub fn require_charging_while_running(&mut self, required: bool) -> &mut Self {
+        self.options.require_charging_while_running = Some(required);
+        self
+    }
+
+    /// Sets whether the device is kept awake while the task runs, for
+    /// callers holding the running lock permission.
+    pub fn keep_awake(&mut self, keep_awake: bool) -> &mut Self {
+        self.options.keep_awake = Some(keep_awake);
+        self
+    }
+
+    /// Sets the priority of each file of the task, in the order of the
+    /// files. Files of higher priority are processed first.
+    pub fn file_priorities(&mut self, priorities: Vec<u32>) -> &mut Self {
+        self.options.file_priorities = Some(priorities);
+        self
+    }
+
     // pub fn notification(&mut self, notification: Notification) -> &mut Self {
     //     self.notification = Some(notification);
     //     self
@@ -296,6 +576,7 @@ impl TaskConfigBuilder {
                 title: None,
                 text: None,
             },
+            options: self.options,
             // notification: self.notification.unwrap_or(Notification {
             //     title: "".to_string(),
             //     text: "".to_string(),
@@ -472,6 +753,8 @@ pub enum Action {
     Download = 0,
     /// Upload content to a remote server.
     Upload,
+    /// Write generated data locally, reserved to system callers.
+    LocalLoopback = 3,
 }
 
 impl From<u32> for Action {
@@ -484,6 +767,7 @@ impl From<u32> for Action {
         match value {
             0 => Action::Download,
             1 => Action::Upload,
+            3 => Action::LocalLoopback,
             _ => unimplemented!(),
         }
     }
@@ -680,6 +964,13 @@ impl ipc::parcel::Deserialize for TaskConfig {
         let min_speed_speed = parcel.read::<i64>()?;
         let min_speed_duration = parcel.read::<i64>()?;
 
+        // deserialize options, absent in replies of older services
+        let options = if parcel.readable() > 0 {
+            parcel.read::<TaskOptions>()?
+        } else {
+            TaskOptions::default()
+        };
+
         Ok(TaskConfig {
             bundle,
             bundle_type,
@@ -712,12 +1003,505 @@ impl ipc::parcel::Deserialize for TaskConfig {
                 title: None,
                 text: None,
             },
+            options,
         })
     }
 }
 
+/// Per-task options beyond the fields every client sends.
+///
+/// Options travel as name and value pairs, so a service ignores the options
+/// it does not know. Unset options keep the default of the service.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TaskOptions {
+    /// Number of concurrent range connections used for a download.
+    pub segments: Option<u32>,
+    /// Maximum bytes of the upload response body kept for the client, 0
+    /// disables keeping it.
+    pub response_body_limit: Option<u32>,
+    /// How the current network is treated regardless of its reported
+    /// metered flag.
+    pub treat_network_as: Option<MeteredOverride>,
+    /// Whether a resumed download whose range the server rejects fails
+    /// instead of downloading the file again from the beginning.
+    pub strict_resume: Option<bool>,
+    /// Whether disk space for the whole file is reserved once the download
+    /// size is known.
+    pub preallocate: Option<bool>,
+    /// Longest delay in milliseconds the task waits when the server rate
+    /// limits it with `Retry-After`, 0 fails the task instead.
+    pub retry_after_cap_ms: Option<u64>,
+    /// Whether state changes, headers and retry decisions of the task are
+    /// logged in detail.
+    pub debug: Option<bool>,
+    /// Whether a download is synced to disk and its size checked before it is
+    /// reported completed.
+    pub durable_completion: Option<bool>,
+    /// How creating a download that is already active is handled.
+    pub duplicate_policy: Option<DuplicatePolicy>,
+    /// Opaque tag of the application returned with every notification of the
+    /// task, at most 256 bytes.
+    pub client_tag: Option<String>,
+    /// Address families the task connects to and their order.
+    pub ip_preference: Option<IpPreference>,
+    /// Lowest TLS version the task negotiates.
+    pub min_tls_version: Option<TlsProtocol>,
+    /// Highest TLS version the task negotiates.
+    pub max_tls_version: Option<TlsProtocol>,
+    /// Ciphers allowed for TLS 1.2 connections, as an OpenSSL cipher list.
+    pub tls_ciphers: Option<String>,
+    /// Whether each redirect followed by the task is reported to the client.
+    pub report_redirects: Option<bool>,
+    /// Content types a download accepts, `type/*` matching any subtype.
+    pub expected_mime_types: Option<Vec<String>>,
+    /// ETag the remote object must still have for an upload to replace it,
+    /// sent as `If-Match`.
+    pub if_match: Option<String>,
+    /// Whether certificate verification is skipped, honoured for system
+    /// applications only.
+    pub insecure_skip_verify: Option<bool>,
+    /// How the download resumes from the bytes it already has.
+    pub resume_strategy: Option<ResumeStrategy>,
+    /// Time in milliseconds since the epoch after which the task fails
+    /// instead of retrying, 0 retries until the retry budget is used.
+    pub retry_deadline_ms: Option<u64>,
+    /// Largest size in bytes of each file of the task.
+    pub max_size_bytes: Option<u64>,
+    /// Largest size in bytes of all files of the task.
+    pub max_total_bytes: Option<u64>,
+    /// Content codings advertised in `Accept-Encoding`.
+    pub accept_encoding: Option<String>,
+    /// Handle of the network the task goes out of, 0 for the default network.
+    pub net_id: Option<i32>,
+    /// What happens to the files of the task when it is removed.
+    pub on_remove_partial: Option<PartialFilePolicy>,
+    /// Whether a download named after its URL takes the file name of the
+    /// `Content-Disposition` header of the response instead.
+    pub content_disposition_name: Option<bool>,
+    /// Largest number of redirects to another host a request of the task
+    /// follows.
+    pub max_cross_host_redirects: Option<u32>,
+    /// Longest time in milliseconds the task accepts a cached address of a
+    /// host.
+    pub dns_ttl_override_ms: Option<u64>,
+    /// W3C trace context sent with every request of the task, its
+    /// `traceparent` followed by a line feed and its `tracestate` if any.
+    pub trace_context: Option<String>,
+    /// Whether a completed download records the size and content type of its
+    /// file in the progress `extras`.
+    pub record_final_metadata: Option<bool>,
+    /// Whether an upload sends its files one after another as the body of a
+    /// single request.
+    pub concatenated: Option<bool>,
+    /// Thermal level at which the task waits until the device cools down.
+    pub pause_on_thermal_level: Option<u8>,
+    /// Whether the task waits while the device is not charging.
+    pub require_charging_while_running: Option<bool>,
+    /// Whether the device is kept awake while the task runs.
+    pub keep_awake: Option<bool>,
+    /// Priority of each file of the task, in the order of the files.
+    pub file_priorities: Option<Vec<u32>>,
+}
+
+impl TaskOptions {
+    /// Returns the options that are set as `(name, value)` pairs, the form
+    /// they are sent to the service in.
+    pub fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![];
+        if let Some(segments) = self.segments {
+            pairs.push(("segments", segments.to_string()));
+        }
+        if let Some(limit) = self.response_body_limit {
+            pairs.push(("response_body_limit", limit.to_string()));
+        }
+        if let Some(treat_as) = self.treat_network_as {
+            pairs.push(("treat_network_as", (treat_as as u8).to_string()));
+        }
+        if let Some(strict_resume) = self.strict_resume {
+            pairs.push(("strict_resume", strict_resume.to_string()));
+        }
+        if let Some(preallocate) = self.preallocate {
+            pairs.push(("preallocate", preallocate.to_string()));
+        }
+        if let Some(retry_after_cap_ms) = self.retry_after_cap_ms {
+            pairs.push(("retry_after_cap_ms", retry_after_cap_ms.to_string()));
+        }
+        if let Some(debug) = self.debug {
+            pairs.push(("debug", debug.to_string()));
+        }
+        if let Some(durable_completion) = self.durable_completion {
+            pairs.push(("durable_completion", durable_completion.to_string()));
+        }
+        if let Some(policy) = self.duplicate_policy {
+            pairs.push(("duplicate_policy", (policy as u8).to_string()));
+        }
+        if let Some(preference) = self.ip_preference {
+            pairs.push(("ip_preference", (preference as u8).to_string()));
+        }
+        if let Some(version) = self.min_tls_version {
+            pairs.push(("min_tls_version", (version as u8).to_string()));
+        }
+        if let Some(version) = self.max_tls_version {
+            pairs.push(("max_tls_version", (version as u8).to_string()));
+        }
+        if let Some(ciphers) = &self.tls_ciphers {
+            pairs.push(("tls_ciphers", ciphers.clone()));
+        }
+        if let Some(tag) = &self.client_tag {
+            pairs.push(("client_tag", tag.clone()));
+        }
+        if let Some(report_redirects) = self.report_redirects {
+            pairs.push(("report_redirects", report_redirects.to_string()));
+        }
+        if let Some(types) = &self.expected_mime_types {
+            pairs.push(("expected_mime_types", types.join(",")));
+        }
+        if let Some(etag) = &self.if_match {
+            pairs.push(("if_match", etag.clone()));
+        }
+        if let Some(skip) = self.insecure_skip_verify {
+            pairs.push(("insecure_skip_verify", skip.to_string()));
+        }
+        if let Some(strategy) = &self.resume_strategy {
+            pairs.push(("resume_strategy", strategy.to_option()));
+        }
+        if let Some(deadline_ms) = self.retry_deadline_ms {
+            pairs.push(("retry_deadline_ms", deadline_ms.to_string()));
+        }
+        if let Some(bytes) = self.max_size_bytes {
+            pairs.push(("max_size_bytes", bytes.to_string()));
+        }
+        if let Some(bytes) = self.max_total_bytes {
+            pairs.push(("max_total_bytes", bytes.to_string()));
+        }
+        if let Some(encoding) = &self.accept_encoding {
+            pairs.push(("accept_encoding", encoding.clone()));
+        }
+        if let Some(net_id) = self.net_id {
+            pairs.push(("net_id", net_id.to_string()));
+        }
+        if let Some(policy) = self.on_remove_partial {
+            pairs.push(("on_remove_partial", (policy as u8).to_string()));
+        }
+        if let Some(enabled) = self.content_disposition_name {
+            pairs.push(("content_disposition_name", enabled.to_string()));
+        }
+        if let Some(count) = self.max_cross_host_redirects {
+            pairs.push(("max_cross_host_redirects", count.to_string()));
+        }
+        if let Some(ms) = self.dns_ttl_override_ms {
+            pairs.push(("dns_ttl_override_ms", ms.to_string()));
+        }
+        if let Some(context) = &self.trace_context {
+            pairs.push(("trace_context", context.clone()));
+        }
+        if let Some(enabled) = self.record_final_metadata {
+            pairs.push(("record_final_metadata", enabled.to_string()));
+        }
+        if let Some(concatenated) = self.concatenated {
+            pairs.push(("concatenated", concatenated.to_string()));
+        }
+        if let Some(level) = self.pause_on_thermal_level {
+            pairs.push(("pause_on_thermal_level", level.to_string()));
+        }
+        if let Some(required) = self.require_charging_while_running {
+            pairs.push(("require_charging_while_running", required.to_string()));
+        }
+        if let Some(keep_awake) = self.keep_awake {
+            pairs.push(("keep_awake", keep_awake.to_string()));
+        }
+        if let Some(priorities) = &self.file_priorities {
+            let priorities = priorities
+                .iter()
+                .map(|priority| priority.to_string())
+                .collect::<Vec<_>>();
+            pairs.push(("file_priorities", priorities.join(",")));
+        }
+        pairs
+    }
+
+    /// Sets the option `name` from the value [`pairs`] gives for it.
+    ///
+    /// Unknown names are ignored, `None` if the value is not valid.
+    ///
+    /// [`pairs`]: TaskOptions::pairs
+    pub fn set(&mut self, name: &str, value: &str) -> Option<()> {
+        match name {
+            "segments" => self.segments = Some(value.parse().ok()?),
+            "response_body_limit" => self.response_body_limit = Some(value.parse().ok()?),
+            "treat_network_as" => {
+                self.treat_network_as = Some(MeteredOverride::from_repr(value.parse().ok()?)?)
+            }
+            "strict_resume" => self.strict_resume = Some(value.parse().ok()?),
+            "preallocate" => self.preallocate = Some(value.parse().ok()?),
+            "retry_after_cap_ms" => self.retry_after_cap_ms = Some(value.parse().ok()?),
+            "debug" => self.debug = Some(value.parse().ok()?),
+            "durable_completion" => self.durable_completion = Some(value.parse().ok()?),
+            "duplicate_policy" => {
+                self.duplicate_policy = Some(DuplicatePolicy::from_repr(value.parse().ok()?)?)
+            }
+            "ip_preference" => {
+                self.ip_preference = Some(IpPreference::from_repr(value.parse().ok()?)?)
+            }
+            "min_tls_version" => {
+                self.min_tls_version = Some(TlsProtocol::from_repr(value.parse().ok()?)?)
+            }
+            "max_tls_version" => {
+                self.max_tls_version = Some(TlsProtocol::from_repr(value.parse().ok()?)?)
+            }
+            "tls_ciphers" => self.tls_ciphers = Some(value.to_string()),
+            "client_tag" => self.client_tag = Some(value.to_string()),
+            "report_redirects" => self.report_redirects = Some(value.parse().ok()?),
+            "expected_mime_types" => {
+                self.expected_mime_types = Some(value.split(',').map(str::to_string).collect())
+            }
+            "if_match" => self.if_match = Some(value.to_string()),
+            "insecure_skip_verify" => self.insecure_skip_verify = Some(value.parse().ok()?),
+            "resume_strategy" => self.resume_strategy = Some(ResumeStrategy::from_option(value)?),
+            "retry_deadline_ms" => self.retry_deadline_ms = Some(value.parse().ok()?),
+            "max_size_bytes" => self.max_size_bytes = Some(value.parse().ok()?),
+            "max_total_bytes" => self.max_total_bytes = Some(value.parse().ok()?),
+            "accept_encoding" => self.accept_encoding = Some(value.to_string()),
+            "net_id" => self.net_id = Some(value.parse().ok()?),
+            "on_remove_partial" => {
+                self.on_remove_partial = Some(PartialFilePolicy::from_repr(value.parse().ok()?)?)
+            }
+            "content_disposition_name" => self.content_disposition_name = Some(value.parse().ok()?),
+            "max_cross_host_redirects" => self.max_cross_host_redirects = Some(value.parse().ok()?),
+            "dns_ttl_override_ms" => self.dns_ttl_override_ms = Some(value.parse().ok()?),
+            "trace_context" => self.trace_context = Some(value.to_string()),
+            "record_final_metadata" => self.record_final_metadata = Some(value.parse().ok()?),
+            "concatenated" => self.concatenated = Some(value.parse().ok()?),
+            "pause_on_thermal_level" => self.pause_on_thermal_level = Some(value.parse().ok()?),
+            "require_charging_while_running" => {
+                self.require_charging_while_running = Some(value.parse().ok()?)
+            }
+            "keep_awake" => self.keep_awake = Some(value.parse().ok()?),
+            "file_priorities" => {
+                let priorities = value
+                    .split(',')
+                    .filter(|priority| !priority.is_empty())
+                    .map(|priority| priority.parse().ok())
+                    .collect::<Option<Vec<_>>>()?;
+                self.file_priorities = Some(priorities);
+            }
+            // Options of newer services are ignored.
+            _ => {}
+        }
+        Some(())
+    }
+}
+
+impl ipc::parcel::Serialize for TaskOptions {
+    fn serialize(&self, parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<()> {
+        let pairs = self.pairs();
+        parcel.write(&(pairs.len() as u32))?;
+        for (name, value) in pairs.iter() {
+            parcel.write(&name.to_string())?;
+            parcel.write(value)?;
+        }
+        Ok(())
+    }
+}
+
+impl ipc::parcel::Deserialize for TaskOptions {
+    fn deserialize(parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<Self> {
+        let mut options = TaskOptions::default();
+        let len = parcel.read::<u32>()?;
+        for _ in 0..len {
+            let name = parcel.read::<String>()?;
+            let value = parcel.read::<String>()?;
+            if options.set(&name, &value).is_none() {
+                return Err(ipc::IpcStatusCode::Failed);
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Per-task override of the metered flag reported by the platform.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum MeteredOverride {
+    /// Treat the network as metered.
+    Metered = 1,
+    /// Treat the network as unmetered.
+    Unmetered,
+}
+
+impl MeteredOverride {
+    fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(MeteredOverride::Metered),
+            2 => Some(MeteredOverride::Unmetered),
+            _ => None,
+        }
+    }
+}
+
+/// What happens to the files of a task when it is removed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum PartialFilePolicy {
+    /// Keep the files.
+    Keep = 1,
+    /// Delete the files, even of a finished download.
+    Delete,
+    /// Delete the files unless the download finished.
+    DeleteIfIncomplete,
+}
+
+impl PartialFilePolicy {
+    fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(PartialFilePolicy::Keep),
+            2 => Some(PartialFilePolicy::Delete),
+            3 => Some(PartialFilePolicy::DeleteIfIncomplete),
+            _ => None,
+        }
+    }
+}
+
+/// How creating a download that is already active is handled.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum DuplicatePolicy {
+    /// Create the task regardless of active duplicates.
+    Allow = 0,
+    /// Return the active task instead of creating another one.
+    Warn,
+    /// Fail the creation of a duplicate.
+    Reject,
+}
+
+impl DuplicatePolicy {
+    fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(DuplicatePolicy::Allow),
+            1 => Some(DuplicatePolicy::Warn),
+            2 => Some(DuplicatePolicy::Reject),
+            _ => None,
+        }
+    }
+}
+
+/// Address families a task connects to and their order.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum IpPreference {
+    /// Connect to the addresses in the order they are resolved.
+    Default = 0,
+    /// Connect to IPv4 addresses only.
+    V4Only,
+    /// Connect to IPv6 addresses only.
+    V6Only,
+    /// Try IPv4 first and fall back to IPv6.
+    V4Preferred,
+    /// Try IPv6 first and fall back to IPv4.
+    V6Preferred,
+}
+
+impl IpPreference {
+    fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(IpPreference::Default),
+            1 => Some(IpPreference::V4Only),
+            2 => Some(IpPreference::V6Only),
+            3 => Some(IpPreference::V4Preferred),
+            4 => Some(IpPreference::V6Preferred),
+            _ => None,
+        }
+    }
+}
+
+/// TLS protocol versions a task may restrict its connections to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum TlsProtocol {
+    /// TLS 1.2.
+    Tls12 = 0,
+    /// TLS 1.3.
+    Tls13,
+}
+
+impl TlsProtocol {
+    fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(TlsProtocol::Tls12),
+            1 => Some(TlsProtocol::Tls13),
+            _ => None,
+        }
+    }
+}
+
+/// How a download resumes from the bytes it already has.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ResumeStrategy {
+    /// Asks for the remaining bytes with a `Range` header.
+    RangeHeader,
+    /// Asks for the remaining bytes with the query parameter `name` set to
+    /// the byte offset, for servers ignoring `Range`.
+    QueryParam {
+        /// Name of the query parameter carrying the offset.
+        name: String,
+    },
+    /// Restarts from zero on every resume.
+    None,
+}
+
+impl ResumeStrategy {
+    fn to_option(&self) -> String {
+        match self {
+            ResumeStrategy::RangeHeader => "range_header".to_string(),
+            ResumeStrategy::QueryParam { name } => format!("query_param:{}", name),
+            ResumeStrategy::None => "none".to_string(),
+        }
+    }
+
+    fn from_option(value: &str) -> Option<Self> {
+        match value {
+            "range_header" => Some(ResumeStrategy::RangeHeader),
+            "none" => Some(ResumeStrategy::None),
+            _ => {
+                let name = value.strip_prefix("query_param:")?;
+                if name.is_empty() {
+                    return None;
+                }
+                Some(ResumeStrategy::QueryParam {
+                    name: name.to_string(),
+                })
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Notification {
     pub title: Option<String>,
     pub text: Option<String>,
 }
+
+/// Progress style of a notification group's gauge.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[repr(u32)]
+pub enum GroupProgressStyle {
+    /// Shows the share of the group's work that is done.
+    #[default]
+    Determinate = 0,
+    /// Shows activity without a completion percentage.
+    Indeterminate,
+    /// Starts indeterminate and switches to determinate once every member's
+    /// size is known.
+    AutoSwitch,
+}
+
+/// Operations a signed task token grants to the process holding it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[repr(u32)]
+pub enum TokenScope {
+    /// Touches the task only.
+    #[default]
+    Query = 0,
+    /// Touches and gets the task, controlling it stays with its owner.
+    Full,
+}
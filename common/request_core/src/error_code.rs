@@ -52,3 +52,12 @@ pub const TASK_STATE_ERR: i32 = 21900007;
 
 /// Requested task group not found.
 pub const GROUP_NOT_FOUND: i32 = 21900008;
+
+/// Too many tasks constructed by the application in a short time.
+pub const CONSTRUCT_THROTTLED: i32 = 21900009;
+
+/// The application has as many live tasks as it may have.
+pub const TOO_MANY_TASKS: i32 = 21900010;
+
+/// The service has as many open client channels as it may have.
+pub const TOO_MANY_CHANNELS: i32 = 21900011;
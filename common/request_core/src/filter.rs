@@ -22,7 +22,7 @@ use crate::info::State;
 /// Filter criteria for searching network tasks.
 ///
 /// A set of optional filtering parameters used to narrow down task searches based on
-/// bundle ownership, time ranges, state, action type, and mode.
+/// bundle ownership, time ranges, state, action type, mode, and client tag.
 ///
 /// # Examples
 ///
@@ -40,6 +40,7 @@ use crate::info::State;
 /// recent_uploads_filter.action = Some(Action::Upload);
 /// recent_uploads_filter.after = Some(1628092800); // Unix timestamp for a specific date
 /// ```
+#[derive(Clone)]
 pub struct SearchFilter {
     /// The bundle name of the task owner.
     pub bundle_name: Option<String>,
@@ -53,6 +54,15 @@ pub struct SearchFilter {
     pub action: Option<Action>,
     /// Operating mode of the task.
     pub mode: Option<Mode>,
+    /// Client tag the task was created with, matched exactly.
+    pub client_tag: Option<String>,
+    /// Tasks created at or after this timestamp.
+    ///
+    /// Setting either creation time bound replaces `before` and `after`, the
+    /// bound left unset is open.
+    pub ctime_after: Option<i64>,
+    /// Tasks created at or before this timestamp.
+    pub ctime_before: Option<i64>,
 }
 
 impl SearchFilter {
@@ -70,6 +80,27 @@ impl SearchFilter {
             state: None,
             action: None,
             mode: None,
+            client_tag: None,
+            ctime_after: None,
+            ctime_before: None,
+        }
+    }
+
+    /// Returns the creation time window searched, as `(after, before)` with
+    /// both ends inclusive.
+    ///
+    /// Without creation time bounds, `before` defaults to `now` and `after` to
+    /// one day before `now`.
+    pub fn time_window(&self, now: i64) -> (i64, i64) {
+        if self.ctime_after.is_some() || self.ctime_before.is_some() {
+            return (
+                self.ctime_after.unwrap_or(0),
+                self.ctime_before.unwrap_or(i64::MAX),
+            );
         }
+        (
+            self.after.unwrap_or(now - 24 * 60 * 60 * 1000),
+            self.before.unwrap_or(now),
+        )
     }
 }
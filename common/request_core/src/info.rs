@@ -48,6 +48,8 @@ pub enum State {
     Failed = 0x41,
     /// Task has been removed from the system.
     Removed = 0x50,
+    /// Task has been removed but can still be restored for a grace period.
+    PendingRemoval = 0x51,
     /// Special value representing any state in filter operations.
     Any = 0x61,
 }
@@ -69,6 +71,7 @@ impl From<u32> for State {
             0x40 => State::Completed,
             0x41 => State::Failed,
             0x50 => State::Removed,
+            0x51 => State::PendingRemoval,
             _ => State::Any,
         }
     }
@@ -259,8 +262,13 @@ pub struct FaultOccur {
     pub task_id: i32,
     pub subscribe_type: SubscribeType,
     pub faults: Faults,
+    /// Tag set by the client at creation, empty if none.
+    pub client_tag: String,
 }
 
+/// Pseudo-header the service appends when it drops response headers.
+pub const HEADERS_TRUNCATED: &str = "x-request-headers-truncated";
+
 #[derive(Debug)]
 pub struct Response {
     /// Unique identifier of the task associated with this response.
@@ -273,6 +281,8 @@ pub struct Response {
     pub reason: String,
     /// HTTP headers returned by the server.
     pub headers: HashMap<String, Vec<String>>,
+    /// Whether the service dropped headers to fit the response message.
+    pub truncated: bool,
 }
 
 /// Status information for a specific task file.
@@ -304,6 +314,9 @@ pub struct Progress {
     /// Additional progress-related metadata.
     pub extras: HashMap<String, String>,
     pub body_bytes: Vec<u8>,
+    /// Estimated remaining time in milliseconds, `None` if the task is not
+    /// running or its total size is unknown.
+    pub eta_ms: Option<u64>,
 }
 
 /// Data structure for task notifications.
@@ -324,6 +337,381 @@ pub struct NotifyData {
     pub version: Version,
     /// Status information for each file in the task.
     pub task_states: Vec<TaskState>,
+    /// Tag set by the client at creation, empty if none.
+    pub client_tag: String,
+}
+
+/// Compact description of a task in a subscribed task list.
+#[derive(Clone, Debug)]
+pub struct TaskBrief {
+    /// Unique identifier of the task.
+    pub task_id: u32,
+    /// Action type of the task.
+    pub action: Action,
+    /// Execution mode of the task.
+    pub mode: Mode,
+    /// Current state of the task.
+    pub state: State,
+}
+
+/// Changes of a subscribed task list since the previous delta.
+///
+/// The first delta of a subscription lists all matching tasks as `added`.
+#[derive(Clone, Debug, Default)]
+pub struct TaskListDelta {
+    /// Tasks that started matching the filter.
+    pub added: Vec<TaskBrief>,
+    /// Identifiers of tasks that were removed or stopped matching the filter.
+    pub removed: Vec<u32>,
+    /// Identifiers and new states of tasks still matching the filter.
+    pub state_changed: Vec<(u32, State)>,
+}
+
+/// Number of tasks of one application per state.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateCounts {
+    /// Tasks created but not started.
+    pub initialized: u32,
+    /// Tasks waiting to run.
+    pub waiting: u32,
+    /// Tasks currently running.
+    pub running: u32,
+    /// Tasks retrying after a failure.
+    pub retrying: u32,
+    /// Paused tasks.
+    pub paused: u32,
+    /// Stopped tasks.
+    pub stopped: u32,
+    /// Completed tasks.
+    pub completed: u32,
+    /// Failed tasks.
+    pub failed: u32,
+    /// Removed tasks.
+    pub removed: u32,
+}
+
+impl Deserialize for StateCounts {
+    /// Deserializes `StateCounts` from an IPC parcel, in the order of the
+    /// state values.
+    fn deserialize(parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<Self> {
+        Ok(StateCounts {
+            initialized: parcel.read()?,
+            waiting: parcel.read()?,
+            running: parcel.read()?,
+            retrying: parcel.read()?,
+            paused: parcel.read()?,
+            stopped: parcel.read()?,
+            completed: parcel.read()?,
+            failed: parcel.read()?,
+            removed: parcel.read()?,
+        })
+    }
+}
+
+/// Membership and aggregate progress of a task group.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupInfo {
+    /// Identifier of the group.
+    pub group_id: String,
+    /// Identifiers of the tasks of the group that were not removed.
+    pub member_task_ids: Vec<String>,
+    /// Bytes processed by the members.
+    pub aggregate_processed: u64,
+    /// Total bytes of the members, -1 if the size of a member is unknown.
+    pub aggregate_total: i64,
+    /// Number of tasks of the group per state, removed tasks included.
+    pub counts_by_state: StateCounts,
+    /// Whether the group notification shows the progress.
+    pub gauge: bool,
+    /// Customized title of the group notification.
+    pub title: Option<String>,
+}
+
+impl Deserialize for GroupInfo {
+    /// Deserializes `GroupInfo` from an IPC parcel.
+    fn deserialize(parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<Self> {
+        let group_id = parcel.read()?;
+        let len = parcel.read::<u32>()? as usize;
+        let mut member_task_ids = Vec::with_capacity(len);
+        for _ in 0..len {
+            member_task_ids.push(parcel.read()?);
+        }
+        let aggregate_processed = parcel.read()?;
+        let aggregate_total = parcel.read()?;
+        let counts_by_state = parcel.read()?;
+        let gauge = parcel.read()?;
+        let title = if parcel.read::<bool>()? {
+            Some(parcel.read()?)
+        } else {
+            None
+        };
+        Ok(GroupInfo {
+            group_id,
+            member_task_ids,
+            aggregate_processed,
+            aggregate_total,
+            counts_by_state,
+            gauge,
+            title,
+        })
+    }
+}
+
+/// Configuration a task runs with after the adjustments of the service.
+///
+/// Secret header values and proxy passwords are redacted, their names are
+/// kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EffectiveConfig {
+    /// URL requested after rewrite rules.
+    pub url: String,
+    /// Headers sent with the requests, default headers included.
+    pub headers: Vec<(String, String)>,
+    /// Proxy the requests go through, empty if none.
+    pub proxy: String,
+    /// Speed limit in bytes per second when the task started, 0 for unlimited.
+    pub speed_limit: u64,
+    /// How a download resumes: `range`, `query:<name>` or `none`.
+    pub resume_strategy: String,
+    /// HTTP version of the first response, empty until one arrived.
+    pub protocol: String,
+}
+
+impl Deserialize for EffectiveConfig {
+    /// Deserializes `EffectiveConfig` from an IPC parcel.
+    fn deserialize(parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<Self> {
+        let url = parcel.read()?;
+        let len = parcel.read::<u32>()? as usize;
+        let mut headers = Vec::with_capacity(len);
+        for _ in 0..len {
+            headers.push((parcel.read()?, parcel.read()?));
+        }
+        Ok(EffectiveConfig {
+            url,
+            headers,
+            proxy: parcel.read()?,
+            speed_limit: parcel.read()?,
+            resume_strategy: parcel.read()?,
+            protocol: parcel.read()?,
+        })
+    }
+}
+
+/// Processed bytes of a task at a point in time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SpeedSample {
+    /// Timestamp of the sample in milliseconds.
+    pub time: u64,
+    /// Total bytes processed at `time`.
+    pub processed: u64,
+}
+
+impl Deserialize for SpeedSample {
+    /// Deserializes `SpeedSample` from an IPC parcel.
+    fn deserialize(parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<Self> {
+        Ok(SpeedSample {
+            time: parcel.read()?,
+            processed: parcel.read()?,
+        })
+    }
+}
+
+/// Condition a waiting task waits for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum WaitingCause {
+    /// Waiting in the queue for its turn.
+    TaskQueue = 0,
+    /// Waiting for a suitable network.
+    Network,
+    /// Waiting for its application to be in the foreground.
+    AppState,
+    /// Waiting for its user account to be active.
+    UserState,
+    /// Waiting for the delay requested by a rate limiting server.
+    RateLimit,
+    /// Background task waiting for the device to be charging.
+    Battery,
+    /// Waiting for the user to sign in to a captive portal.
+    CaptivePortal,
+    /// Waiting for the device to cool down or to be charging.
+    DeviceCondition,
+}
+
+impl From<u32> for WaitingCause {
+    /// Converts a u32 value to a `WaitingCause`, unknown values wait in the
+    /// queue.
+    fn from(value: u32) -> Self {
+        match value {
+            1 => WaitingCause::Network,
+            2 => WaitingCause::AppState,
+            3 => WaitingCause::UserState,
+            4 => WaitingCause::RateLimit,
+            5 => WaitingCause::Battery,
+            6 => WaitingCause::CaptivePortal,
+            7 => WaitingCause::DeviceCondition,
+            _ => WaitingCause::TaskQueue,
+        }
+    }
+}
+
+/// Why a task is in its current state.
+///
+/// The conditions, retry count and next action are only reported to the
+/// application owning the task, `detailed` is `false` and they keep their
+/// default values for other callers.
+#[derive(Clone, Debug)]
+pub struct TaskExplanation {
+    /// Current state of the task.
+    pub state: State,
+    /// Code of the reason of the current state.
+    pub reason: u32,
+    /// Description of the reason of the current state.
+    pub reason_message: String,
+    /// What the task waits for, `None` if it is not waiting.
+    pub waiting_cause: Option<WaitingCause>,
+    /// Whether the details below are reported.
+    pub detailed: bool,
+    /// Whether the current network suits the task.
+    pub network_satisfied: bool,
+    /// Whether the task may run given the state of its application.
+    pub foreground_satisfied: bool,
+    /// Whether the task may run given the charging state.
+    pub battery_satisfied: bool,
+    /// Whether the task has not run out of storage.
+    pub storage_satisfied: bool,
+    /// Number of times the task was retried.
+    pub tries: u32,
+    /// Time in milliseconds the task is next scheduled to act at, 0 if none.
+    pub next_action_time: u64,
+}
+
+impl Deserialize for TaskExplanation {
+    /// Deserializes `TaskExplanation` from an IPC parcel.
+    fn deserialize(parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<Self> {
+        let state = State::from(parcel.read::<u32>()?);
+        let reason = parcel.read()?;
+        let reason_message = parcel.read()?;
+        let waiting_cause = match parcel.read::<bool>()? {
+            true => Some(WaitingCause::from(parcel.read::<u32>()?)),
+            false => None,
+        };
+        let mut explanation = TaskExplanation {
+            state,
+            reason,
+            reason_message,
+            waiting_cause,
+            detailed: parcel.read()?,
+            network_satisfied: false,
+            foreground_satisfied: false,
+            battery_satisfied: false,
+            storage_satisfied: false,
+            tries: 0,
+            next_action_time: 0,
+        };
+        if explanation.detailed {
+            explanation.network_satisfied = parcel.read()?;
+            explanation.foreground_satisfied = parcel.read()?;
+            explanation.battery_satisfied = parcel.read()?;
+            explanation.storage_satisfied = parcel.read()?;
+            explanation.tries = parcel.read()?;
+            explanation.next_action_time = parcel.read()?;
+        }
+        Ok(explanation)
+    }
+}
+
+/// Tasks of the calling application changed since a revision.
+#[derive(Clone, Debug, Default)]
+pub struct ChangedSet {
+    /// Revision to pass to the next query.
+    pub revision: u64,
+    /// Whether removals newer than the queried revision were forgotten, in
+    /// which case all tasks have to be fetched again.
+    pub resync: bool,
+    /// Identifiers and revisions of the tasks created or changed.
+    pub changed: Vec<(String, u64)>,
+    /// Identifiers and revisions of the tasks removed.
+    pub removed: Vec<(String, u64)>,
+}
+
+impl Deserialize for ChangedSet {
+    /// Deserializes `ChangedSet` from an IPC parcel.
+    fn deserialize(parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<Self> {
+        let revision = parcel.read()?;
+        let resync = parcel.read()?;
+        let mut tasks = || -> ipc::IpcResult<Vec<(String, u64)>> {
+            let len = parcel.read::<u32>()? as usize;
+            let mut tasks = Vec::with_capacity(len);
+            for _ in 0..len {
+                tasks.push((parcel.read()?, parcel.read()?));
+            }
+            Ok(tasks)
+        };
+        let changed = tasks()?;
+        let removed = tasks()?;
+        Ok(ChangedSet {
+            revision,
+            resync,
+            changed,
+            removed,
+        })
+    }
+}
+
+/// Why a legacy task was not migrated to an API10 task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum MigrationSkip {
+    /// The task is an upload, only downloads are migrated.
+    Upload = 1,
+    /// The task writes to a file opened by the user.
+    UserFile,
+    /// The task was removed.
+    Removed,
+    /// The configuration of the task could not be read.
+    Unreadable,
+}
+
+impl From<u32> for MigrationSkip {
+    /// Converts a u32 value to a `MigrationSkip`, unknown values are
+    /// unreadable tasks.
+    fn from(value: u32) -> Self {
+        match value {
+            1 => MigrationSkip::Upload,
+            2 => MigrationSkip::UserFile,
+            3 => MigrationSkip::Removed,
+            _ => MigrationSkip::Unreadable,
+        }
+    }
+}
+
+/// Outcome of migrating the legacy API9 downloads of an application, every
+/// legacy task is either migrated or skipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Identifiers of the tasks now controllable as API10 tasks.
+    pub migrated: Vec<String>,
+    /// Identifiers of the tasks left untouched, with the reason.
+    pub skipped: Vec<(String, MigrationSkip)>,
+}
+
+impl Deserialize for MigrationReport {
+    /// Deserializes `MigrationReport` from an IPC parcel.
+    fn deserialize(parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<Self> {
+        let len = parcel.read::<u32>()? as usize;
+        let mut migrated = Vec::with_capacity(len);
+        for _ in 0..len {
+            migrated.push(parcel.read()?);
+        }
+        let len = parcel.read::<u32>()? as usize;
+        let mut skipped = Vec::with_capacity(len);
+        for _ in 0..len {
+            let task_id = parcel.read()?;
+            skipped.push((task_id, MigrationSkip::from(parcel.read::<u32>()?)));
+        }
+        Ok(MigrationReport { migrated, skipped })
+    }
 }
 
 /// Detailed progress information for a task.
@@ -428,6 +816,23 @@ pub struct TaskInfo {
     pub common_data: CommonTaskInfo,
     /// Maximum allowed transfer speed (bytes per second).
     pub max_speed: i64,
+    /// Speed limit actually applied to the task in bytes per second, the
+    /// lower of its QoS tier and `max_speed`. 0 means unlimited.
+    pub effective_speed_limit: u64,
+    /// Upload server response body kept by the service, if any.
+    pub response_body: Option<ResponseBody>,
+    /// Tag set by the client at creation, empty if none.
+    pub client_tag: String,
+}
+
+/// Upload server response body kept for the client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseBody {
+    /// Kept bytes of the response body, at most the response body limit of
+    /// the task.
+    pub data: Vec<u8>,
+    /// Whether the server sent more than `data` holds.
+    pub truncated: bool,
 }
 
 impl Deserialize for TaskInfo {
@@ -530,6 +935,20 @@ impl Deserialize for TaskInfo {
             });
         }
 
+        // Read the speed limit, older services end the parcel before it.
+        let effective_speed_limit = parcel.read::<u64>().unwrap_or(0);
+
+        // Read the upload response body, if the service kept one.
+        let response_body = match parcel.read::<bool>() {
+            Ok(true) => {
+                let truncated = parcel.read::<bool>()?;
+                let data = parcel.read::<Vec<u8>>()?;
+                Some(ResponseBody { data, truncated })
+            }
+            _ => None,
+        };
+        let client_tag = parcel.read::<String>().unwrap_or_default();
+
         // Construct common task information
         let common_data = CommonTaskInfo {
             task_id,
@@ -573,6 +992,9 @@ impl Deserialize for TaskInfo {
             extras, // Extras are not serialized in this context
             common_data,
             max_speed: 0, // Max speed is not serialized in this context
+            effective_speed_limit,
+            response_body,
+            client_tag,
         })
     }
 }
@@ -76,6 +76,19 @@ enum DfxErrorCode : uint32_t {
     INVALID_IPC_MESSAGE_A44 = 0x001F1501,
     INVALID_IPC_MESSAGE_A45 = 0x001F1600,
     INVALID_IPC_MESSAGE_A46 = 0x001F1601,
+    INVALID_IPC_MESSAGE_A47 = 0x001F1700,
+    INVALID_IPC_MESSAGE_A48 = 0x001F1701,
+    INVALID_IPC_MESSAGE_A49 = 0x001F1800,
+    INVALID_IPC_MESSAGE_A50 = 0x001F1900,
+    INVALID_IPC_MESSAGE_A51 = 0x001F1A00,
+    INVALID_IPC_MESSAGE_A52 = 0x001F1B00,
+    INVALID_IPC_MESSAGE_A53 = 0x001F1C00,
+    INVALID_IPC_MESSAGE_A54 = 0x001F1D00,
+    INVALID_IPC_MESSAGE_A55 = 0x001F1E00,
+    INVALID_IPC_MESSAGE_A56 = 0x001F1F00,
+    INVALID_IPC_MESSAGE_A57 = 0x001F2000,
+    INVALID_IPC_MESSAGE_A58 = 0x001F2100,
+    INVALID_IPC_MESSAGE_A59 = 0x001F2200,
     INVALID_IPC_MESSAGE_00 = 0x00100000,
     INVALID_IPC_MESSAGE_01 = 0x00100100,
     INVALID_IPC_MESSAGE_02 = 0x00100200,
@@ -114,6 +127,7 @@ enum DfxErrorCode : uint32_t {
     UDS_FAULT_02 = 0x00300002,
     UDS_FAULT_03 = 0x003F0000,
     UDS_FAULT_04 = 0x003F0001,
+    UDS_FAULT_05 = 0x003F0002,
     SA_ERROR_00 = 0x004F0000,
     SA_ERROR_01 = 0x004F0001,
     SA_ERROR_02 = 0x004F0002,
@@ -37,6 +37,10 @@ rust::string GetBaseDir();
 
 rust::string SHA256(rust::str input);
 
+rust::string Sha256Bytes(rust::Slice<const uint8_t> input);
+
+rust::Vec<uint8_t> HmacSha256(rust::Slice<const uint8_t> key, rust::Slice<const uint8_t> data);
+
 bool IsStageContext(AniEnv *env, AniObject *obj);
 
 std::shared_ptr<Context> GetStageModeContext(AniEnv **env, AniObject *obj);
@@ -20,6 +20,8 @@
 
 #include "ani.h"
 #include "ani_base_context.h"
+#include "openssl/evp.h"
+#include "openssl/hmac.h"
 #include "openssl/sha.h"
 #include "data_ability_helper.h"
 #include "network_security_config.h"
@@ -47,11 +49,17 @@ rust::string GetBaseDir()
 }
 
 rust::string SHA256(rust::str input)
+{
+    auto bytes = reinterpret_cast<const uint8_t *>(input.data());
+    return Sha256Bytes(rust::Slice<const uint8_t>(bytes, input.length()));
+}
+
+rust::string Sha256Bytes(rust::Slice<const uint8_t> input)
 {
     unsigned char hash[SHA256_DIGEST_LENGTH];
     SHA256_CTX sha256;
     SHA256_Init(&sha256);
-    SHA256_Update(&sha256, input.data(), input.length());
+    SHA256_Update(&sha256, input.data(), input.size());
     SHA256_Final(hash, &sha256);
     std::stringstream ss;
     for (int i = 0; i < SHA256_DIGEST_LENGTH; i++) {
@@ -61,6 +69,20 @@ rust::string SHA256(rust::str input)
     return ss.str();
 }
 
+rust::Vec<uint8_t> HmacSha256(rust::Slice<const uint8_t> key, rust::Slice<const uint8_t> data)
+{
+    rust::Vec<uint8_t> mac;
+    unsigned char out[EVP_MAX_MD_SIZE];
+    unsigned int len = 0;
+    if (HMAC(EVP_sha256(), key.data(), key.size(), data.data(), data.size(), out, &len) == nullptr) {
+        return mac;
+    }
+    for (unsigned int i = 0; i < len; i++) {
+        mac.push_back(out[i]);
+    }
+    return mac;
+}
+
 bool IsStageContext(AniEnv *env, AniObject *obj)
 {
     ani_boolean stageMode;
@@ -0,0 +1,79 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+cfg_ohos! {
+    use crate::wrapper::HmacSha256;
+}
+
+cfg_not_ohos! {
+    use std::ffi::{c_int, c_uint, c_void};
+
+    #[link(name = "crypto")]
+    extern "C" {
+        fn EVP_sha256() -> *const c_void;
+        fn HMAC(
+            md: *const c_void,
+            key: *const c_void,
+            key_len: c_int,
+            data: *const u8,
+            data_len: usize,
+            out: *mut u8,
+            out_len: *mut c_uint,
+        ) -> *mut u8;
+    }
+}
+
+/// Size of an HMAC-SHA256 in bytes.
+pub const HMAC_SHA256_LEN: usize = 32;
+
+/// Returns the HMAC-SHA256 of `data` under `key`, computed by OpenSSL.
+///
+/// # Returns
+///
+/// `None` if OpenSSL fails to compute it.
+#[cfg(feature = "ohos")]
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Option<Vec<u8>> {
+    let mac = HmacSha256(key, data);
+    (mac.len() == HMAC_SHA256_LEN).then_some(mac)
+}
+
+/// Returns the HMAC-SHA256 of `data` under `key`, computed by OpenSSL.
+///
+/// # Returns
+///
+/// `None` if OpenSSL fails to compute it.
+#[cfg(not(feature = "ohos"))]
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Option<Vec<u8>> {
+    let key_len = c_int::try_from(key.len()).ok()?;
+    let mut mac = vec![0; HMAC_SHA256_LEN];
+    let mut len: c_uint = 0;
+    // Safety: the digest is static, the input slices outlive the call and
+    // `mac` has room for a SHA-256 digest.
+    let ret = unsafe {
+        HMAC(
+            EVP_sha256(),
+            key.as_ptr().cast(),
+            key_len,
+            data.as_ptr(),
+            data.len(),
+            mac.as_mut_ptr(),
+            &mut len,
+        )
+    };
+    (!ret.is_null() && len as usize == HMAC_SHA256_LEN).then_some(mac)
+}
+
+#[cfg(test)]
+mod ut_hmac {
+    include!("../../tests/ut/hash/ut_hmac.rs");
+}
@@ -11,9 +11,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-cfg_ohos! {
-    mod sha256;
-}
+mod sha256;
+pub use sha256::{sha256, sha256_bytes};
+
+mod hmac;
+pub use hmac::{hmac_sha256, HMAC_SHA256_LEN};
 
 mod url;
 pub use url::url_hash;
@@ -11,8 +11,46 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::wrapper::SHA256;
+cfg_ohos! {
+    use crate::wrapper::{Sha256Bytes, SHA256};
+}
+
+cfg_not_ohos! {
+    #[link(name = "crypto")]
+    extern "C" {
+        fn SHA256(data: *const u8, len: usize, md: *mut u8) -> *mut u8;
+    }
+}
 
+/// Returns the lowercase hex SHA-256 of `input`.
+#[cfg(feature = "ohos")]
 pub fn sha256(input: &str) -> String {
     SHA256(input)
 }
+
+/// Returns the lowercase hex SHA-256 of `input`.
+#[cfg(feature = "ohos")]
+pub fn sha256_bytes(input: &[u8]) -> String {
+    Sha256Bytes(input)
+}
+
+/// Returns the lowercase hex SHA-256 of `input`.
+#[cfg(not(feature = "ohos"))]
+pub fn sha256(input: &str) -> String {
+    sha256_bytes(input.as_bytes())
+}
+
+/// Returns the lowercase hex SHA-256 of `input`.
+#[cfg(not(feature = "ohos"))]
+pub fn sha256_bytes(input: &[u8]) -> String {
+    let mut md = [0u8; 32];
+    // Safety: the input slice outlives the call and `md` has room for a
+    // SHA-256 digest.
+    unsafe { SHA256(input.as_ptr(), input.len(), md.as_mut_ptr()) };
+    md.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod ut_sha256 {
+    include!("../../tests/ut/hash/ut_sha256.rs");
+}
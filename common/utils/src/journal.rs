@@ -0,0 +1,219 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compact binary journal of task events.
+//!
+//! A journal is a pair of files holding fixed size records: the active file
+//! and the file it was rotated to. Once the active file would grow past half
+//! the size cap, it replaces the rotated one and a new active file is started,
+//! so the journal keeps the latest events within the cap like a ring buffer.
+//!
+//! The journal is read back by [`parse_journal`] without any database.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Size of an encoded record in bytes.
+pub const RECORD_LEN: usize = 22;
+
+/// Suffix of the file an active journal is rotated to.
+const ROTATED_SUFFIX: &str = ".1";
+
+/// Event recorded in a journal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum JournalEvent {
+    /// The task made progress, sampled.
+    Progress = 0,
+    /// The task was paused.
+    Pause = 1,
+    /// The task was resumed.
+    Resume = 2,
+    /// The task is waiting, `reason` holds the waiting cause.
+    Waiting = 3,
+    /// The task completed.
+    Complete = 4,
+    /// The task failed.
+    Fail = 5,
+    /// An error occurred, `reason` holds the failure reason.
+    Fault = 6,
+    /// The task was removed.
+    Remove = 7,
+}
+
+impl JournalEvent {
+    /// Returns the event encoded as `repr`, `None` for unknown events.
+    pub fn from_repr(repr: u8) -> Option<Self> {
+        match repr {
+            0 => Some(Self::Progress),
+            1 => Some(Self::Pause),
+            2 => Some(Self::Resume),
+            3 => Some(Self::Waiting),
+            4 => Some(Self::Complete),
+            5 => Some(Self::Fail),
+            6 => Some(Self::Fault),
+            7 => Some(Self::Remove),
+            _ => None,
+        }
+    }
+
+    /// Whether no event of the task is expected after this one. A failure is
+    /// recorded as `Fail` followed by the `Fault` carrying its reason.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Complete | Self::Fault | Self::Remove)
+    }
+
+    /// Returns the name of the event.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Progress => "progress",
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+            Self::Waiting => "waiting",
+            Self::Complete => "complete",
+            Self::Fail => "fail",
+            Self::Fault => "fault",
+            Self::Remove => "remove",
+        }
+    }
+}
+
+/// A journaled event of a task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JournalRecord {
+    /// ID of the task.
+    pub task_id: u32,
+    /// Time of the event in milliseconds since the epoch.
+    pub timestamp: u64,
+    /// What happened.
+    pub event: JournalEvent,
+    /// Bytes processed by the task so far.
+    pub bytes: u64,
+    /// Failure reason or waiting cause, 0 if none.
+    pub reason: u8,
+}
+
+impl JournalRecord {
+    /// Encodes the record in little endian.
+    pub fn encode(&self) -> [u8; RECORD_LEN] {
+        let mut buf = [0; RECORD_LEN];
+        buf[0..4].copy_from_slice(&self.task_id.to_le_bytes());
+        buf[4..12].copy_from_slice(&self.timestamp.to_le_bytes());
+        buf[12] = self.event as u8;
+        buf[13..21].copy_from_slice(&self.bytes.to_le_bytes());
+        buf[21] = self.reason;
+        buf
+    }
+
+    /// Decodes a record, `None` if its event is unknown.
+    pub fn decode(buf: &[u8; RECORD_LEN]) -> Option<Self> {
+        Some(Self {
+            task_id: u32::from_le_bytes(buf[0..4].try_into().ok()?),
+            timestamp: u64::from_le_bytes(buf[4..12].try_into().ok()?),
+            event: JournalEvent::from_repr(buf[12])?,
+            bytes: u64::from_le_bytes(buf[13..21].try_into().ok()?),
+            reason: buf[21],
+        })
+    }
+}
+
+/// Appends records to a journal, rotating it at its size cap.
+pub struct JournalWriter {
+    path: PathBuf,
+    file: BufWriter<File>,
+    len: u64,
+    segment_cap: u64,
+}
+
+impl JournalWriter {
+    /// Opens the journal at `path` for appending, keeping at most `cap` bytes
+    /// over the active and the rotated file.
+    pub fn open(path: impl AsRef<Path>, cap: u64) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file: BufWriter::new(file),
+            len,
+            // A segment holds at least one record.
+            segment_cap: (cap / 2).max(RECORD_LEN as u64),
+        })
+    }
+
+    /// Appends a record, rotating the journal first if it is full.
+    pub fn append(&mut self, record: &JournalRecord) -> io::Result<()> {
+        if self.len + RECORD_LEN as u64 > self.segment_cap {
+            self.rotate()?;
+        }
+        self.file.write_all(&record.encode())?;
+        self.len += RECORD_LEN as u64;
+        Ok(())
+    }
+
+    /// Writes buffered records to the file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        fs::rename(&self.path, rotated_path(&self.path))?;
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.file = BufWriter::new(file);
+        self.len = 0;
+        Ok(())
+    }
+}
+
+impl Drop for JournalWriter {
+    fn drop(&mut self) {
+        let _ = self.file.flush();
+    }
+}
+
+/// Returns the file the journal at `path` is rotated to.
+pub fn rotated_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut rotated = path.as_ref().as_os_str().to_owned();
+    rotated.push(ROTATED_SUFFIX);
+    PathBuf::from(rotated)
+}
+
+/// Reads the records of the journal at `path`, oldest first.
+///
+/// Records of the rotated file come before those of the active one. Missing
+/// files are empty, a trailing partial record and records of unknown events
+/// are skipped.
+pub fn parse_journal(path: impl AsRef<Path>) -> Vec<JournalRecord> {
+    let mut records = Vec::new();
+    for file in [rotated_path(&path), path.as_ref().to_path_buf()] {
+        let Ok(data) = fs::read(&file) else {
+            continue;
+        };
+        records.extend(
+            data.chunks_exact(RECORD_LEN)
+                .filter_map(|chunk| JournalRecord::decode(chunk.try_into().ok()?)),
+        );
+    }
+    records
+}
+
+#[cfg(test)]
+mod ut_journal {
+    include!("../tests/ut/ut_journal.rs");
+}
@@ -37,6 +37,9 @@ pub mod hash;
 /// Internal logging module for HarmonyOS.
 mod hilog;
 
+/// Binary journal of task events.
+pub mod journal;
+
 /// Least Recently Used (LRU) cache implementation.
 pub mod lru;
 
@@ -46,7 +49,7 @@ pub mod task_id;
 // Conditional compilation for non-OHOS platforms
 // Provides standard logging macros from the log crate
 cfg_not_ohos! {
-    pub use log::{debug, error, info};
+    pub use log::{debug, error, info, warn};
 }
 
 // Conditional compilation for OHOS platform
@@ -15,7 +15,7 @@
 //! 
 //! This module provides convenient logging macros that wrap the `hilog_rust` crate,
 //! offering a simplified interface for logging at different severity levels
-//! (debug, info, and error) with consistent formatting and label usage.
+//! (debug, info, warn and error) with consistent formatting and label usage.
 
 /// Logs a debug-level message using HarmonyOS logging.
 ///
@@ -75,6 +75,32 @@ macro_rules! info {
     }}
 }
 
+/// Logs a warn-level message using HarmonyOS logging.
+///
+/// Uses the crate's configured `LOG_LABEL` for consistent log identification.
+/// Supports formatted strings with the same syntax as standard formatting macros.
+/// All arguments are logged as public information.
+///
+/// # Examples
+///
+/// ```rust
+/// use request_utils::warn;
+///
+/// let task_id = 1;
+/// warn!("Certificate verification disabled for task {}", task_id);
+/// ```
+#[macro_export]
+macro_rules! warn {
+    ($fmt: literal $(, $args:expr)* $(,)?) => {{
+        use std::ffi::{CString, c_char};
+        use hilog_rust::{warn, hilog};
+        use crate::LOG_LABEL;
+
+        // Log warn message with public visibility
+        hilog_rust::warn!(LOG_LABEL, $fmt $(, @public($args))*);
+    }}
+}
+
 /// Logs an error-level message using HarmonyOS logging.
 ///
 /// Uses the crate's configured `LOG_LABEL` for consistent log identification.
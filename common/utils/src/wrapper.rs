@@ -147,6 +147,20 @@ mod ffi {
         /// The SHA-256 hash of the input as a string.
         fn SHA256(input: &str) -> String;
 
+        /// Computes the SHA-256 hash of a byte slice.
+        ///
+        /// # Returns
+        ///
+        /// The SHA-256 hash of the input as a lowercase hex string.
+        fn Sha256Bytes(input: &[u8]) -> String;
+
+        /// Computes the HMAC-SHA256 of `data` under `key`.
+        ///
+        /// # Returns
+        ///
+        /// The 32 bytes of the MAC, empty if it could not be computed.
+        fn HmacSha256(key: &[u8], data: &[u8]) -> Vec<u8>;
+
         /// Checks if the given environment is a stage context.
         ///
         /// # Safety
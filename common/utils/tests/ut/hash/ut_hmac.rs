@@ -0,0 +1,47 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// @tc.name: ut_hmac_sha256_rfc4231
+// @tc.desc: Test HMAC-SHA256 against RFC 4231 test cases
+// @tc.precon: NA
+// @tc.step: 1. Compute the MAC for test cases 1, 2 and 6
+//           2. Compare with the published values
+// @tc.expect: All MACs match, including the one with a key longer than a block
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+// @tc.level: Level 1
+#[test]
+fn ut_hmac_sha256_rfc4231() {
+    let mac = hmac_sha256(&[0x0b; 20], b"Hi There").unwrap();
+    assert_eq!(
+        hex(&mac),
+        "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+    );
+    let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?").unwrap();
+    assert_eq!(
+        hex(&mac),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+    let data = b"Test Using Larger Than Block-Size Key - Hash Key First";
+    let mac = hmac_sha256(&[0xaa; 131], data).unwrap();
+    assert_eq!(
+        hex(&mac),
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+    );
+}
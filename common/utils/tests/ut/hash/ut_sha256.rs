@@ -0,0 +1,39 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+// @tc.name: ut_sha256_known_answer
+// @tc.desc: Test SHA-256 of strings and byte slices against known digests
+// @tc.precon: NA
+// @tc.step: 1. Hash an empty input, a string and a binary slice
+// @tc.expect: The digests match the published values
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+// @tc.level: Level 1
+#[test]
+fn ut_sha256_known_answer() {
+    assert_eq!(
+        sha256(""),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        sha256("abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(sha256_bytes(b"abc"), sha256("abc"));
+    assert_eq!(
+        sha256_bytes(&[0xff; 4]),
+        "ad95131bc0b799c0b1af477fb14fcf26a6a9f76079e48bf090acb7e8367bfd0e"
+    );
+}
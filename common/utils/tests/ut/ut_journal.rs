@@ -0,0 +1,128 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+
+use super::*;
+
+fn journal_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(name);
+    let _ = fs::remove_file(&path);
+    let _ = fs::remove_file(rotated_path(&path));
+    path
+}
+
+fn record(task_id: u32, timestamp: u64, event: JournalEvent) -> JournalRecord {
+    JournalRecord {
+        task_id,
+        timestamp,
+        event,
+        bytes: timestamp * 1024,
+        reason: 0,
+    }
+}
+
+// @tc.name: ut_journal_record_codec
+// @tc.desc: Test a journal record is decoded as it was encoded
+// @tc.precon: NA
+// @tc.step: 1. Encode and decode a record of every event
+//           2. Decode a record of an unknown event
+// @tc.expect: Every record is decoded unchanged, the unknown event is not
+// decoded
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_journal_record_codec() {
+    for repr in 0..=7 {
+        let record = JournalRecord {
+            task_id: u32::MAX - repr as u32,
+            timestamp: u64::MAX - repr as u64,
+            event: JournalEvent::from_repr(repr).unwrap(),
+            bytes: repr as u64 * 1000,
+            reason: repr,
+        };
+        assert_eq!(JournalRecord::decode(&record.encode()), Some(record));
+    }
+    let mut buf = record(1, 1, JournalEvent::Pause).encode();
+    buf[12] = 8;
+    assert_eq!(JournalRecord::decode(&buf), None);
+}
+
+// @tc.name: ut_journal_rotation
+// @tc.desc: Test the reader reconstructs records written across a rotation
+// @tc.precon: NA
+// @tc.step: 1. Open a journal holding four records per file
+//           2. Write six records of a task lifecycle and reopen the journal
+//           3. Write four more records
+// @tc.expect: The six records are read back in order across the rotation,
+// after the second rotation only the latest records within the cap remain
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_journal_rotation() {
+    let path = journal_path("ut_journal_rotation");
+    let cap = 8 * RECORD_LEN as u64;
+    let events = [
+        JournalEvent::Resume,
+        JournalEvent::Progress,
+        JournalEvent::Waiting,
+        JournalEvent::Progress,
+        JournalEvent::Fail,
+        JournalEvent::Fault,
+    ];
+    let written: Vec<_> = events
+        .iter()
+        .enumerate()
+        .map(|(i, event)| record(7, i as u64, *event))
+        .collect();
+
+    let mut writer = JournalWriter::open(&path, cap).unwrap();
+    for record in written.iter() {
+        writer.append(record).unwrap();
+    }
+    writer.flush().unwrap();
+    assert_eq!(fs::metadata(&path).unwrap().len(), 2 * RECORD_LEN as u64);
+    assert_eq!(parse_journal(&path), written);
+    drop(writer);
+
+    let mut writer = JournalWriter::open(&path, cap).unwrap();
+    let more: Vec<_> = (6..10)
+        .map(|i| record(8, i, JournalEvent::Progress))
+        .collect();
+    for record in more.iter() {
+        writer.append(record).unwrap();
+    }
+    drop(writer);
+    let expected: Vec<_> = written[4..].iter().chain(more.iter()).copied().collect();
+    assert_eq!(parse_journal(&path), expected);
+}
+
+// @tc.name: ut_journal_partial_record
+// @tc.desc: Test the reader skips a trailing partial record
+// @tc.precon: NA
+// @tc.step: 1. Write a record and append half a record to the file
+//           2. Parse the journal, and a journal that does not exist
+// @tc.expect: Only the whole record is read, the missing journal is empty
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_journal_partial_record() {
+    let path = journal_path("ut_journal_partial_record");
+    let whole = record(3, 1, JournalEvent::Complete);
+    let mut data = whole.encode().to_vec();
+    data.extend_from_slice(&whole.encode()[..RECORD_LEN / 2]);
+    fs::write(&path, data).unwrap();
+
+    assert_eq!(parse_journal(&path), vec![whole]);
+    assert!(parse_journal(journal_path("ut_journal_missing")).is_empty());
+}
@@ -32,3 +32,21 @@ pub struct CacheDownloadOptions {
     pub cache_strategy: Option<CacheStrategy>,
     pub caPath: Option<String>,
 }
+
+#[ani_rs::ani(path = "L@ohos/request/cacheDownload/cacheDownload/PreloadEventType")]
+pub enum PreloadEventType {
+    START,
+    PROGRESS,
+    SUCCESS,
+    FAIL,
+    CANCEL,
+}
+
+#[ani_rs::ani(path = "L@ohos/request/cacheDownload/cacheDownload/PreloadEventInner")]
+pub struct PreloadEvent {
+    pub event_type: PreloadEventType,
+    pub downloaded: i64,
+    pub total: i64,
+    pub code: i32,
+    pub message: String,
+}
@@ -18,8 +18,12 @@
 //! cache download service.
 
 use ani_rs::business_error::BusinessError;
-use preload_native_rlib::{CacheDownloadService, DownloadRequest, PreloadCallback, Downloader};
-use crate::bridge::CacheDownloadOptions;
+use ani_rs::objects::{AniFnObject, GlobalRefCallback};
+use ani_rs::AniEnv;
+use preload_native_rlib::{
+    CacheDownloadService, DownloadRequest, Downloader, PreloadCallback, PreloadObserver,
+};
+use crate::bridge::{CacheDownloadOptions, PreloadEvent, PreloadEventType};
 
 /// Empty callback implementation for preload operations.
 ///
@@ -28,6 +32,53 @@ struct Callback;
 
 impl PreloadCallback for Callback {}
 
+/// Observer forwarding the lifecycle of a download to an ETS callback.
+struct Observer {
+    callback: GlobalRefCallback<(PreloadEvent,)>,
+}
+
+impl Observer {
+    /// Sends one event to the ETS callback.
+    fn notify(
+        &self,
+        event_type: PreloadEventType,
+        downloaded: u64,
+        total: u64,
+        code: i32,
+        msg: &str,
+    ) {
+        self.callback.execute((PreloadEvent {
+            event_type,
+            downloaded: downloaded as i64,
+            total: total as i64,
+            code,
+            message: msg.to_string(),
+        },));
+    }
+}
+
+impl PreloadObserver for Observer {
+    fn on_start(&self) {
+        self.notify(PreloadEventType::START, 0, 0, 0, "");
+    }
+
+    fn on_progress(&self, downloaded: u64, total: u64) {
+        self.notify(PreloadEventType::PROGRESS, downloaded, total, 0, "");
+    }
+
+    fn on_success(&self) {
+        self.notify(PreloadEventType::SUCCESS, 0, 0, 0, "");
+    }
+
+    fn on_fail(&self, code: i32, msg: &str) {
+        self.notify(PreloadEventType::FAIL, 0, 0, code, msg);
+    }
+
+    fn on_cancel(&self) {
+        self.notify(PreloadEventType::CANCEL, 0, 0, 0, "");
+    }
+}
+
 const MAX_FILE_SIZE: i64 = 4294967296;
 const MAX_MEM_SIZE: i64 = 1073741824;
 const MAX_UTL_LENGTH: usize = 8192;
@@ -128,6 +179,47 @@ pub fn cancel(url: String) -> Result<(), BusinessError> {
     Ok(())
 }
 
+/// Observes the lifecycle of the running download of a URL.
+///
+/// Every caller preloading the same URL may register its own observer. The
+/// callback receives a start event first, then progress events, and finally
+/// one success, fail or cancel event.
+///
+/// # Parameters
+///
+/// * `env` - The animation environment reference
+/// * `url` - The URL of the resource download to observe
+/// * `callback` - The callback function receiving each event
+///
+/// # Returns
+///
+/// * `Ok(true)` if the observer was registered
+/// * `Ok(false)` if no download of the URL is running
+/// * `Err(BusinessError)` if the URL is too long
+///
+/// # Examples
+///
+/// ```rust
+/// use ani_cache_download::cache_download::observe;
+/// use ani_rs::objects::AniFnObject;
+/// use ani_rs::AniEnv;
+///
+/// // Assuming env and callback_fn are properly initialized
+/// let result = observe(&env, "https://example.com/resource.mp4".to_string(), callback_fn);
+/// ```
+#[ani_rs::native]
+pub fn observe(env: &AniEnv, url: String, callback: AniFnObject) -> Result<bool, BusinessError> {
+    if url.len() > MAX_UTL_LENGTH {
+        return Err(BusinessError::new(
+            401,
+            "url exceeds the maximum length".to_string()
+        ));
+    }
+    let callback = callback.into_global_callback(env).unwrap();
+    let observer = Box::new(Observer { callback });
+    Ok(CacheDownloadService::get_instance().observe(&url, observer))
+}
+
 /// Sets the maximum memory (RAM) cache size in bytes.
 ///
 /// Configures the RAM cache size for the cache download service.
@@ -18,6 +18,7 @@ ani_rs::ani_constructor! {
     [
         "download" : cache_download::download,
         "cancel" : cache_download::cancel,
+        "observe" : cache_download::observe,
         "setMemoryCacheSize" : cache_download::set_memory_cache_size,
         "setFileCacheSize" : cache_download::set_file_cache_size,
     ]
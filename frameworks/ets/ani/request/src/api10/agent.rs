@@ -29,7 +29,7 @@ use request_core::filter::SearchFilter;
 use request_utils::context::Context;
 use request_core::config::TaskConfig;
 
-use crate::api10::bridge::{Config, Filter, Task, TaskInfo};
+use crate::api10::bridge::{Config, Filter, MigrationReport, Task, TaskInfo};
 use crate::seq::TaskSeq;
 use crate::constant::*;
 
@@ -386,3 +386,32 @@ pub fn query(id: String) -> Result<TaskInfo, BusinessError> {
         })
         .map_err(|e| BusinessError::new_static(e, "Failed to query task"))
 }
+
+/// Migrates the legacy API9 downloads of an application to API10 tasks.
+///
+/// # Parameters
+///
+/// * `uid` - The uid of the application whose legacy tasks are migrated
+///
+/// # Returns
+///
+/// * `Ok(MigrationReport)` - The migrated tasks and the reason each other
+///   legacy task was skipped
+/// * `Err(BusinessError)` - If the caller is not a system application or the
+///   uid is invalid
+#[ani_rs::native]
+pub fn migrate_tasks(uid: i64) -> Result<MigrationReport, BusinessError> {
+    if uid < 0 {
+        return Err(BusinessError::new(
+            ExceptionErrorCode::E_PARAMETER_CHECK as i32,
+            "Invalid uid".to_string(),
+        ));
+    }
+    RequestClient::get_instance()
+        .migrate_legacy_tasks(uid as u64)
+        .map(|report| {
+            info!("Api10 migrate tasks: {:?}", report);
+            MigrationReport::from(report)
+        })
+        .map_err(|e| BusinessError::new_static(e, "Failed to migrate tasks"))
+}
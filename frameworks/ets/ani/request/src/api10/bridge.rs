@@ -47,6 +47,8 @@ impl From<config::Action> for Action {
         match value {
             config::Action::Download => Action::Download,
             config::Action::Upload => Action::Upload,
+            // Loopback tasks are reported as the downloads they imitate.
+            config::Action::LocalLoopback => Action::Download,
         }
     }
 }
@@ -317,7 +319,10 @@ impl From<request_core::info::State> for State {
             request_core::info::State::Stopped => State::Stopped,
             request_core::info::State::Completed => State::Completed,
             request_core::info::State::Failed => State::Failed,
-            request_core::info::State::Removed => State::Removed,
+            // Tasks pending removal are hidden like removed ones.
+            request_core::info::State::Removed | request_core::info::State::PendingRemoval => {
+                State::Removed
+            }
             _ => unimplemented!(),
         }
     }
@@ -352,7 +357,7 @@ impl From<u8> for State {
             0x31 => State::Stopped,
             0x40 => State::Completed,
             0x41 => State::Failed,
-            0x50 => State::Removed,
+            0x50 | 0x51 => State::Removed,
             _ => unimplemented!(),
         }
     }
@@ -468,6 +473,9 @@ impl From<Filter> for request_core::filter::SearchFilter {
             state: value.state.map(|s| s.into()),
             action: value.action.map(|a| a.into()),
             mode: value.mode.map(|m| m.into()),
+            client_tag: None,
+            ctime_after: None,
+            ctime_before: None,
         }
     }
 }
@@ -565,6 +573,8 @@ pub struct HttpResponse {
     reason: String,
     /// Response headers.
     headers: HashMap<String, Vec<String>>,
+    /// Whether headers were dropped to fit the response message.
+    truncated: bool,
 }
 
 /// Converts from core Response to API HttpResponse.
@@ -575,6 +585,7 @@ impl From<&request_core::info::Response> for HttpResponse {
             status_code: value.status_code as i32,
             reason: value.reason.clone(),
             headers: value.headers.clone(),
+            truncated: value.truncated,
         }
     }
 }
@@ -587,15 +598,123 @@ pub struct Task {
     pub config: Config,
 }
 
+/// Defines how the gauge of a task group renders its progress.
+#[derive(Clone)]
+#[ani_rs::ani(path = "L@ohos/request/request/agent/GroupProgressStyle")]
+pub enum GroupProgressStyle {
+    /// Shows the share of the group's work that is done.
+    Determinate,
+    /// Shows activity without a completion percentage.
+    Indeterminate,
+    /// Switches to determinate once every member's size is known.
+    AutoSwitch,
+}
+
+/// Converts from API GroupProgressStyle to core GroupProgressStyle.
+impl From<GroupProgressStyle> for config::GroupProgressStyle {
+    fn from(value: GroupProgressStyle) -> Self {
+        match value {
+            GroupProgressStyle::Determinate => config::GroupProgressStyle::Determinate,
+            GroupProgressStyle::Indeterminate => config::GroupProgressStyle::Indeterminate,
+            GroupProgressStyle::AutoSwitch => config::GroupProgressStyle::AutoSwitch,
+        }
+    }
+}
+
 /// Represents configuration for a task group.
 #[ani_rs::ani(path = "L@ohos/request/request/agent/GroupConfigInner")]
 pub struct GroupConfig {
     /// Optional gauge flag for the group.
     pub gauge: Option<bool>,
+    /// Optional progress style of the gauge, determinate by default.
+    pub progress_style: Option<GroupProgressStyle>,
     /// Notification details for the group.
     pub notification: Notification,
 }
 
+/// Represents the membership and aggregate progress of a task group.
+#[ani_rs::ani(path = "L@ohos/request/request/agent/GroupInfoInner")]
+pub struct GroupInfo {
+    /// Group ID.
+    pub gid: String,
+    /// IDs of the tasks of the group that were not removed.
+    pub tids: Vec<String>,
+    /// Bytes processed by the members.
+    pub processed: i64,
+    /// Total bytes of the members, -1 if the size of a member is unknown.
+    pub total: i64,
+    /// Number of tasks of the group per state name, removed tasks included.
+    pub counts: HashMap<String, i32>,
+    /// Whether the group notification shows the progress.
+    pub gauge: bool,
+    /// Customized title of the group notification.
+    pub title: Option<String>,
+}
+
+/// Converts from core GroupInfo to API GroupInfo.
+impl From<request_core::info::GroupInfo> for GroupInfo {
+    fn from(value: request_core::info::GroupInfo) -> Self {
+        let counts = &value.counts_by_state;
+        let counts = [
+            ("initialized", counts.initialized),
+            ("waiting", counts.waiting),
+            ("running", counts.running),
+            ("retrying", counts.retrying),
+            ("paused", counts.paused),
+            ("stopped", counts.stopped),
+            ("completed", counts.completed),
+            ("failed", counts.failed),
+            ("removed", counts.removed),
+        ]
+        .into_iter()
+        .map(|(state, count)| (state.to_string(), count as i32))
+        .collect();
+        GroupInfo {
+            gid: value.group_id,
+            tids: value.member_task_ids,
+            processed: value.aggregate_processed as i64,
+            total: value.aggregate_total,
+            counts,
+            gauge: value.gauge,
+            title: value.title,
+        }
+    }
+}
+
+/// Represents the outcome of migrating the legacy tasks of an application.
+#[ani_rs::ani(path = "L@ohos/request/request/agent/MigrationReportInner")]
+pub struct MigrationReport {
+    /// IDs of the tasks migrated to API10 tasks.
+    pub migrated: Vec<String>,
+    /// Reason each skipped task was left untouched, keyed by task ID.
+    pub skipped: HashMap<String, String>,
+}
+
+/// Converts from core MigrationReport to API MigrationReport.
+impl From<request_core::info::MigrationReport> for MigrationReport {
+    fn from(value: request_core::info::MigrationReport) -> Self {
+        use request_core::info::MigrationSkip;
+
+        let skipped = value
+            .skipped
+            .into_iter()
+            .map(|(task_id, reason)| {
+                let reason = match reason {
+                    MigrationSkip::Upload => "upload",
+                    MigrationSkip::UserFile => "userFile",
+                    MigrationSkip::Removed => "removed",
+                    MigrationSkip::Unreadable => "unreadable",
+                };
+                (task_id, reason.to_string())
+            })
+            .collect();
+        MigrationReport {
+            migrated: value.migrated,
+            skipped,
+        }
+    }
+}
+
 impl From<request_core::config::TaskConfig> for Config {
     fn from(value: request_core::config::TaskConfig) -> Self {
         Config {
@@ -736,6 +855,7 @@ impl From<Config> for TaskConfig {
                 title: None,
                 text: None,
             }),
+            options: config::TaskOptions::default(),
         }
     }
 }
@@ -13,7 +13,7 @@
 
 use ani_rs::business_error::BusinessError;
 
-use crate::api10::bridge::GroupConfig;
+use crate::api10::bridge::{GroupConfig, GroupInfo};
 
 use request_client::RequestClient;
 use crate::constant::*;
@@ -54,8 +54,15 @@ fn ParseGid(gid: &str) -> Result<(), BusinessError> {
 #[ani_rs::native]
 pub fn create_group(config: GroupConfig) -> Result<String, BusinessError> {
     ParseTitleText(&config.notification.title, &config.notification.text)?;
+    let progress_style = config.progress_style.map(Into::into);
     RequestClient::get_instance()
-        .create_group(config.gauge, config.notification.title, config.notification.text, None)
+        .create_group(
+            config.gauge,
+            config.notification.title,
+            config.notification.text,
+            None,
+            progress_style,
+        )
         .map(|info| {
             info!("create_group: {:?}", info);
             info
@@ -78,3 +85,12 @@ pub fn delete_group(gid: String) -> Result<(), BusinessError> {
         .delete_group(gid)
         .map_err(|e| BusinessError::new_static(e, "Failed to delete group"))
 }
+
+#[ani_rs::native]
+pub fn query_group(gid: String) -> Result<GroupInfo, BusinessError> {
+    ParseGid(&gid)?;
+    RequestClient::get_instance()
+        .query_group(gid)
+        .map(GroupInfo::from)
+        .map_err(|e| BusinessError::new_static(e, "Failed to query group"))
+}
@@ -32,4 +32,7 @@ pub enum ExceptionErrorCode {
     E_TASK_NOT_FOUND = 21900006,
     E_TASK_STATE = 21900007,
     E_GROUP_NOT_FOUND = 21900008,
+    E_CONSTRUCT_THROTTLED = 21900009,
+    E_TOO_MANY_TASKS = 21900010,
+    E_TOO_MANY_CHANNELS = 21900011,
 }
\ No newline at end of file
@@ -96,6 +96,8 @@ ani_constructor!(
         "createGroupSync": api10::notification::create_group, // Create notification group
         "attachGroupSync": api10::notification::attach_group, // Attach task to notification group
         "deleteGroupSync": api10::notification::delete_group, // Delete notification group
+        "queryGroupSync": api10::notification::query_group, // Query notification group
+        "migrateTasksSync": api10::agent::migrate_tasks,     // Migrate legacy API9 tasks
     ]
     // API 10 TaskInner class method bindings
     class "L@ohos/request/request/agent/TaskInner"
@@ -26,10 +26,11 @@
 
 use std::collections::hash_map::Entry;
 use std::fs::{self, DirEntry, File, OpenOptions};
-use std::io::{self, Seek, Write};
+use std::io::{self, Write};
 use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, Once, OnceLock, Weak};
 use std::time::SystemTime;
 
@@ -46,6 +47,14 @@ use crate::spawn;
 /// invalid.
 const FINISH_SUFFIX: &str = "_F";
 
+/// Suffix of the temporary files cache files are written to before the rename.
+///
+/// Temporary files are never restored, the startup scan removes them.
+const TEMP_SUFFIX: &str = "_T";
+
+/// Distinguishes the temporary files of concurrent writes for the same task.
+static TEMP_SEQ: AtomicUsize = AtomicUsize::new(0);
+
 /// Global file store directory manager.
 ///
 /// This static variable manages the directories used for storing cache files. It is
@@ -461,6 +470,8 @@ impl FileCache {
     /// Attempts to create a new file cache from RAM cache data.
     ///
     /// Writes the contents of the RAM cache to a file and creates a new FileCache instance.
+    /// The caller records the returned cache only once the file is in place, so a
+    /// crash can lose the entry but never leave one pointing to a partial file.
     ///
     /// # Parameters
     /// - `task_id`: ID of the task to create the cache for
@@ -488,7 +499,8 @@ impl FileCache {
         }
 
         // Try to create the file cache
-        if let Err(e) = Self::create_file(&task_id, cache) {
+        let durable = handle.durable.load(Ordering::Relaxed);
+        if let Err(e) = Self::create_file(&task_id, cache, durable) {
             error!("create file cache error: {}", e);
             // Release memory if creation fails
             handle.file_handle.lock().unwrap().release(size as u64);
@@ -505,32 +517,23 @@ impl FileCache {
     /// # Parameters
     /// - `task_id`: ID of the task to create the file for
     /// - `cache`: RAM cache to write to disk
+    /// - `durable`: Whether to sync the file and the directory
     ///
     /// # Returns
     /// `Ok(())` if successful, `Err(io::Error)` if any file operation fails
-    fn create_file(task_id: &TaskId, cache: Arc<RamCache>) -> Result<(), io::Error> {
-        if let Some(path) = Self::path(task_id) {
-            // Create the file and write cache contents
-            let mut file = OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open(path.as_path())?;
-            io::copy(&mut cache.cursor(), &mut file)?;
-            file.flush()?;
-            file.rewind()?;
-            
-            // Rename to indicate the file is complete
-            let file_name = format!("{}{}", task_id, FINISH_SUFFIX);
-            if let Some(new_path) = unsafe { FILE_STORE_DIR.join(file_name) } {
-                fs::rename(path, new_path)?;
-                return Ok(());
-            }
-        }
-        Err(io::Error::new(
-            io::ErrorKind::NotFound,
-            "cache store dir not created.",
-        ))
+    fn create_file(task_id: &TaskId, cache: Arc<RamCache>, durable: bool) -> Result<(), io::Error> {
+        // SAFETY: This is a read-only operation to get the path
+        let Some(dir) = (unsafe { FILE_STORE_DIR.as_path() }) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "cache store dir not created.",
+            ));
+        };
+        let temp = write_temp(dir, task_id, &cache, durable)?;
+        commit_temp(dir, &temp, task_id, durable).map_err(|e| {
+            let _ = fs::remove_file(&temp);
+            e
+        })
     }
 
     /// Opens the cache file for reading.
@@ -561,6 +564,65 @@ impl FileCache {
     }
 }
 
+/// Writes the contents of a RAM cache to a new temporary file in `dir`.
+///
+/// # Parameters
+/// - `dir`: Directory of the cache files
+/// - `task_id`: ID of the task the file is written for
+/// - `cache`: RAM cache to write to disk
+/// - `durable`: Whether to sync the file before returning
+///
+/// # Returns
+/// Path to the temporary file, removed again if the write fails
+pub(crate) fn write_temp(
+    dir: &Path,
+    task_id: &TaskId,
+    cache: &RamCache,
+    durable: bool,
+) -> Result<PathBuf, io::Error> {
+    let seq = TEMP_SEQ.fetch_add(1, Ordering::Relaxed);
+    let temp = dir.join(format!("{}_{}{}", task_id, seq, TEMP_SUFFIX));
+    let write = || -> Result<(), io::Error> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(temp.as_path())?;
+        io::copy(&mut cache.cursor(), &mut file)?;
+        file.flush()?;
+        if durable {
+            file.sync_all()?;
+        }
+        Ok(())
+    };
+    write().map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        e
+    })?;
+    Ok(temp)
+}
+
+/// Publishes a temporary file as the finished cache file of a task.
+///
+/// The rename is atomic, so the finished file is either absent or complete.
+///
+/// # Parameters
+/// - `dir`: Directory of the cache files
+/// - `temp`: Temporary file written by `write_temp`
+/// - `task_id`: ID of the task the file is written for
+/// - `durable`: Whether to sync the directory after the rename
+pub(crate) fn commit_temp(
+    dir: &Path,
+    temp: &Path,
+    task_id: &TaskId,
+    durable: bool,
+) -> Result<(), io::Error> {
+    fs::rename(temp, dir.join(format!("{}{}", task_id, FINISH_SUFFIX)))?;
+    if durable {
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
 /// Restores all valid cache files from the current directory.
 ///
 /// Scans the current cache directory for valid cache files and returns an iterator
@@ -639,7 +701,7 @@ fn filter_map_entry(
     
     // Check for the finish suffix to ensure the file is complete
     if !file_name.ends_with(FINISH_SUFFIX) {
-        // Remove incomplete files
+        // Remove incomplete files, including temporary files never renamed
         let _ = fs::remove_file(path.join(file_name));
         return Err(io::Error::new(
             io::ErrorKind::InvalidData,
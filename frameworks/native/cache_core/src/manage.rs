@@ -19,6 +19,7 @@
 
 use std::collections::{HashMap, HashSet};
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, OnceLock, Weak};
 
 use request_utils::lru::LRUCache;
@@ -71,6 +72,9 @@ pub struct CacheManager {
 
     /// Manages file cache resource allocation and capacity
     pub(crate) file_handle: Mutex<data::ResourceManager>,
+
+    /// Whether file cache writes are synced to storage before being published
+    pub(crate) durable: AtomicBool,
 }
 
 impl CacheManager {
@@ -89,6 +93,7 @@ impl CacheManager {
 
             ram_handle: Mutex::new(data::ResourceManager::new(DEFAULT_RAM_CACHE_SIZE)),
             file_handle: Mutex::new(data::ResourceManager::new(DEFAULT_FILE_CACHE_SIZE)),
+            durable: AtomicBool::new(false),
         }
     }
 
@@ -116,10 +121,24 @@ impl CacheManager {
         CacheManager::apply_cache(&self.file_handle, &self.files, 0);
     }
 
+    /// Sets whether file cache writes are synced to storage.
+    ///
+    /// Off by default: a file cache is written to a temporary file and renamed
+    /// into place without fsync, so a crash may lose the entry but never leaves
+    /// a partial one. When on, the file and its directory are synced so the
+    /// entry also survives power loss.
+    ///
+    /// # Parameters
+    /// - `durable`: Whether to sync each file cache write
+    pub fn set_file_durable(&self, durable: bool) {
+        self.durable.store(durable, Ordering::Relaxed);
+    }
+
     /// Restores cached files from persistent storage.
     ///
     /// Initializes the current storage directory and restores all previously cached files
-    /// into the manager's file cache.
+    /// into the manager's file cache. Temporary files left by writes interrupted
+    /// before their rename are discarded.
     ///
     /// # Safety
     /// Must be called with a `'static self` reference as it may spawn background tasks
@@ -243,3 +243,47 @@ fn ut_cache_file_update_ram_from_file() {
         assert!(j.join().unwrap());
     }
 }
+
+// @tc.name: ut_cache_file_crash_before_rename
+// @tc.desc: Test the startup scan recovers from a write interrupted before rename
+// @tc.precon: NA
+// @tc.step: 1. Write and commit the file cache of one task
+//           2. Write the file cache of another task without the rename
+//           3. Call restore_files_inner function as on startup
+// @tc.expect: Only the committed task is restored with its content, the
+// temporary file of the other task is removed and it has no finished file
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_cache_file_crash_before_rename() {
+    init();
+    const TEST_DIR: &str = "crash_test";
+    static CACHE_MANAGER: LazyLock<CacheManager> = LazyLock::new(CacheManager::new);
+
+    init_curr_store_dir();
+    let path = unsafe { FILE_STORE_DIR.join(String::from(TEST_DIR)).unwrap() };
+    fs::create_dir_all(&path).unwrap();
+
+    let committed = TaskId::new(fast_random().to_string());
+    let crashed = TaskId::new(fast_random().to_string());
+    let mut temps = vec![];
+    for (task_id, durable) in [(&committed, true), (&crashed, false)] {
+        let mut ram_cache = RamCache::new(task_id.clone(), &CACHE_MANAGER, Some(TEST_STRING_SIZE));
+        ram_cache.write_all(TEST_STRING.as_bytes()).unwrap();
+        temps.push(write_temp(&path, task_id, &ram_cache, durable).unwrap());
+    }
+    commit_temp(&path, &temps[0], &committed, true).unwrap();
+    // Crash: the second temporary file is never renamed
+    assert!(temps[1].is_file());
+
+    let restored = restore_files_inner(path.as_path())
+        .map(|task_id| task_id.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(restored, vec![committed.to_string()]);
+    assert!(!temps[1].exists());
+    assert!(!path.join(format!("{}{}", crashed, FINISH_SUFFIX)).exists());
+
+    let finished = path.join(format!("{}{}", committed, FINISH_SUFFIX));
+    assert_eq!(fs::read_to_string(finished).unwrap(), TEST_STRING);
+    fs::remove_dir_all(&path).unwrap();
+}
@@ -22,9 +22,11 @@ use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 use super::common::{CommonError, CommonResponse};
+use super::sink::DataSink;
 use super::{CacheDownloadError, RUNNING};
 use crate::download::{CANCEL, FAIL, SUCCESS};
 use crate::info::RustDownloadInfo;
+use crate::observe::PreloadObservers;
 use crate::services::{CacheDownloadService, PreloadCallback};
 use cache_core::{CacheManager, Updater};
 use netstack_rs::info::DownloadInfo;
@@ -55,6 +57,12 @@ pub(crate) struct PrimeCallback {
     progress_restriction: ProgressRestriction,
     /// Sequence number for task ordering
     seq: usize,
+    /// Callback-only delivery of received data, bypassing the cache
+    sink: Option<DataSink>,
+    /// Observers of the download lifecycle
+    observers: Arc<Mutex<PreloadObservers>>,
+    /// Set once `on_ready` was signalled, when the request asked for it
+    ready: Option<Arc<AtomicBool>>,
 }
 
 /// Restricts the frequency of progress updates.
@@ -110,12 +118,43 @@ impl PrimeCallback {
             callbacks,
             progress_restriction: ProgressRestriction::new(),
             seq,
+            sink: None,
+            observers: Arc::new(Mutex::new(PreloadObservers::new())),
+            ready: None,
         }
     }
 
+    /// Notifies the given observers of the download lifecycle.
+    ///
+    /// # Parameters
+    /// - `observers`: Observers shared with the handles of the download
+    pub(crate) fn with_observers(mut self, observers: Arc<Mutex<PreloadObservers>>) -> Self {
+        self.observers = observers;
+        self
+    }
+
+    /// Delivers received data to `on_data` callbacks instead of the cache.
+    ///
+    /// # Parameters
+    /// - `max_in_flight`: Maximum number of undelivered bytes before reception blocks
+    pub(crate) fn with_sink(mut self, max_in_flight: usize) -> Self {
+        self.sink = Some(DataSink::new(max_in_flight));
+        self
+    }
+
+    /// Signals `on_ready` to the callbacks once the first bytes arrive.
+    ///
+    /// # Parameters
+    /// - `ready`: Flag shared with the task handle, set once signalled
+    pub(crate) fn with_ready(mut self, ready: Arc<AtomicBool>) -> Self {
+        self.ready = Some(ready);
+        self
+    }
+
     /// Sets the download state to running.
     pub(crate) fn set_running(&self) {
         self.state.store(RUNNING, Ordering::Release);
+        self.observers.lock().unwrap().start();
     }
 
     /// Gets the task ID associated with this callback.
@@ -145,9 +184,21 @@ impl PrimeCallback {
     {
         let code = response.code();
         info!("{} status {}", self.task_id.brief(), code);
+        // An empty body still signals readiness before success
+        self.signal_ready();
 
-        // Finalize cache storage
+        // Make sure sink consumers have seen every byte before reporting success
+        let delivered = self.sink.as_ref().map(DataSink::flush);
+        // Finalize cache storage, left empty when data went to the sink
         let cache = self.cache_handle.cache_finish();
+        let size = delivered.unwrap_or(cache.size()) as u64;
+
+        // Observers get the same final progress, in order on this thread
+        let mut observers = self.observers.lock().unwrap();
+        observers.progress(size, size);
+        observers.finish(|observer| observer.on_success());
+        drop(observers);
+
         // Update task state to success
         self.state.store(SUCCESS, Ordering::Release);
         self.finish.store(true, Ordering::Release);
@@ -161,7 +212,7 @@ impl PrimeCallback {
             // Spawn in separate tasks to avoid blocking
             crate::spawn(move || {
                 // Report 100% progress before success
-                callback.on_progress(clone_cache.size() as u64, clone_cache.size() as u64);
+                callback.on_progress(size, size);
                 callback.on_success(clone_cache, &task_id)
             });
         }
@@ -187,6 +238,15 @@ impl PrimeCallback {
         E: CommonError,
     {
         info!("{} download failed {}", self.task_id.brief(), error.code());
+        if let Some(sink) = self.sink.as_ref() {
+            sink.flush();
+        }
+        let msg = error.msg();
+        self.observers
+            .lock()
+            .unwrap()
+            .finish(|observer| observer.on_fail(error.code(), &msg));
+
         // Update task state to failed
         self.state.store(FAIL, Ordering::Release);
         self.finish.store(true, Ordering::Release);
@@ -215,6 +275,14 @@ impl PrimeCallback {
     /// of the cancellation.
     pub(crate) fn common_cancel(&mut self) {
         info!("{} is cancel", self.task_id.brief());
+        if let Some(sink) = self.sink.as_ref() {
+            sink.flush();
+        }
+        self.observers
+            .lock()
+            .unwrap()
+            .finish(|observer| observer.on_cancel());
+
         // Update task state to canceled
         self.state.store(CANCEL, Ordering::Release);
         self.finish.store(true, Ordering::Release);
@@ -272,6 +340,8 @@ impl PrimeCallback {
         // Reset counter for next interval
         self.progress_restriction.count = 1;
 
+        self.observers.lock().unwrap().progress(dl_now, dl_total);
+
         // Notify all registered callbacks of progress
         let mut callbacks = self.callbacks.lock().unwrap();
         for callback in callbacks.iter_mut() {
@@ -281,7 +351,8 @@ impl PrimeCallback {
 
     /// Processes received data and updates the cache.
     ///
-    /// Marks that data reception has started and forwards the data to the cache handler.
+    /// Marks that data reception has started and forwards the data to the cache handler,
+    /// or to the `on_data` callbacks when the task delivers through a sink.
     ///
     /// # Type Parameters
     /// - `F`: Function type that returns the content length when called
@@ -295,6 +366,11 @@ impl PrimeCallback {
     {
         // Mark that data reception has started
         self.progress_restriction.data_receive = true;
+        self.signal_ready();
+        if let Some(sink) = self.sink.as_mut() {
+            sink.push(data, &self.task_id, &self.callbacks);
+            return;
+        }
         // Forward data to cache storage
         self.cache_handle.cache_receive(data, content_length);
     }
@@ -305,9 +381,30 @@ impl PrimeCallback {
     /// Only available when the `netstack` feature is enabled.
     #[cfg(feature = "netstack")]
     pub(crate) fn common_restart(&mut self) {
+        if let Some(sink) = self.sink.as_mut() {
+            sink.restart();
+        }
         self.cache_handle.reset_cache();
     }
 
+    /// Notifies the callbacks that the task is ready, at most once.
+    ///
+    /// Callbacks joining later are notified when they are added.
+    fn signal_ready(&self) {
+        let Some(ready) = self.ready.as_ref() else {
+            return;
+        };
+        if ready.load(Ordering::Acquire) {
+            return;
+        }
+        let mut callbacks = self.callbacks.lock().unwrap();
+        ready.store(true, Ordering::Release);
+        info!("{} is ready", self.task_id.brief());
+        for callback in callbacks.iter_mut() {
+            callback.on_ready(self.task_id.brief());
+        }
+    }
+
     /// Notifies the cache download service that the task has finished.
     ///
     /// Used to trigger any necessary cleanup or notification operations in the service.
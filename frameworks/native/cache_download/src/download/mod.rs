@@ -29,6 +29,7 @@ cfg_ylong! {
 
 pub(crate) mod common;
 mod error;
+mod sink;
 
 pub(crate) use error::CacheDownloadError;
 pub(crate) mod task;
@@ -0,0 +1,153 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Callback-only delivery of downloaded data.
+//!
+//! When a request opts into sink mode, received chunks bypass the cache and are
+//! handed to each registered `PreloadCallback::on_data` in arrival order. Chunks
+//! are delivered on a separate task so a slow consumer never runs on the network
+//! thread, and the receiving side blocks once the undelivered bytes reach the
+//! configured bound.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+
+use request_utils::task_id::TaskId;
+
+use crate::services::PreloadCallback;
+
+/// Chunks received but not yet handed to the callbacks.
+struct Pending {
+    /// Queued chunks in arrival order
+    chunks: VecDeque<Vec<u8>>,
+    /// Total bytes queued or being delivered
+    bytes: usize,
+    /// Whether a delivery task is currently running
+    draining: bool,
+}
+
+/// Bounded buffer forwarding downloaded chunks to `on_data` callbacks.
+pub(crate) struct DataSink {
+    /// Pending chunks and the condition signalled whenever bytes are released
+    pending: Arc<(Mutex<Pending>, Condvar)>,
+    /// Maximum number of undelivered bytes before the receiver is blocked
+    max_in_flight: usize,
+    /// Bytes of the response body accepted so far
+    received: usize,
+    /// Bytes of a restarted response to drop because they were already delivered
+    skip: usize,
+}
+
+impl DataSink {
+    /// Creates a sink allowing at most `max_in_flight` undelivered bytes.
+    pub(crate) fn new(max_in_flight: usize) -> Self {
+        Self {
+            pending: Arc::new((
+                Mutex::new(Pending {
+                    chunks: VecDeque::new(),
+                    bytes: 0,
+                    draining: false,
+                }),
+                Condvar::new(),
+            )),
+            max_in_flight,
+            received: 0,
+            skip: 0,
+        }
+    }
+
+    /// Queues a chunk for delivery, blocking while the buffer is full.
+    ///
+    /// A single chunk larger than the bound is still accepted once the buffer
+    /// has fully drained, so delivery always makes progress.
+    pub(crate) fn push(
+        &mut self,
+        data: &[u8],
+        task_id: &TaskId,
+        callbacks: &Arc<Mutex<VecDeque<Box<dyn PreloadCallback>>>>,
+    ) {
+        let skipped = self.skip.min(data.len());
+        self.skip -= skipped;
+        let data = &data[skipped..];
+        if data.is_empty() {
+            return;
+        }
+        self.received += data.len();
+
+        let (lock, cvar) = &*self.pending;
+        let mut pending = lock.lock().unwrap();
+        while pending.bytes != 0 && pending.bytes + data.len() > self.max_in_flight {
+            pending = cvar.wait(pending).unwrap();
+        }
+        pending.bytes += data.len();
+        pending.chunks.push_back(data.to_vec());
+        if !pending.draining {
+            pending.draining = true;
+            let pending = self.pending.clone();
+            let callbacks = callbacks.clone();
+            let task_id = task_id.brief().to_string();
+            crate::spawn(move || drain(pending, callbacks, task_id));
+        }
+    }
+
+    /// Prepares for the response being restarted from the beginning.
+    ///
+    /// Bytes already handed out cannot be taken back, so the same prefix of the
+    /// new response is dropped instead of being delivered twice.
+    #[cfg(feature = "netstack")]
+    pub(crate) fn restart(&mut self) {
+        info!("sink restart after {} bytes", self.received);
+        self.skip = self.received;
+        self.received = 0;
+    }
+
+    /// Waits until every queued chunk has been delivered.
+    ///
+    /// # Returns
+    /// The total number of bytes delivered.
+    pub(crate) fn flush(&self) -> usize {
+        let (lock, cvar) = &*self.pending;
+        let mut pending = lock.lock().unwrap();
+        while pending.draining {
+            pending = cvar.wait(pending).unwrap();
+        }
+        self.received
+    }
+}
+
+/// Delivers queued chunks until the buffer is empty.
+fn drain(
+    pending: Arc<(Mutex<Pending>, Condvar)>,
+    callbacks: Arc<Mutex<VecDeque<Box<dyn PreloadCallback>>>>,
+    task_id: String,
+) {
+    let (lock, cvar) = &*pending;
+    loop {
+        let chunk = {
+            let mut pending = lock.lock().unwrap();
+            match pending.chunks.pop_front() {
+                Some(chunk) => chunk,
+                None => {
+                    pending.draining = false;
+                    cvar.notify_all();
+                    return;
+                }
+            }
+        };
+        for callback in callbacks.lock().unwrap().iter_mut() {
+            callback.on_data(&task_id, &chunk);
+        }
+        lock.lock().unwrap().bytes -= chunk.len();
+        cvar.notify_all();
+    }
+}
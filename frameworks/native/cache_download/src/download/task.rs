@@ -37,6 +37,7 @@ cfg_netstack! {
     use crate::download::netstack;
 }
 
+use crate::observe::{PreloadObserver, PreloadObservers};
 use crate::services::{DownloadRequest, PreloadCallback};
 
 /// Enum representing available download backends.
@@ -144,7 +145,7 @@ impl DownloadTask {
     /// `Ok(())` if the callback was successfully added, otherwise returns the callback in `Err`.
     pub(crate) fn try_add_callback(
         &mut self,
-        callback: Box<dyn PreloadCallback>,
+        mut callback: Box<dyn PreloadCallback>,
     ) -> Result<(), Box<dyn PreloadCallback>> {
         self.handle.try_add_callback(callback)
     }
@@ -165,6 +166,12 @@ pub struct TaskHandle {
     finish: Arc<AtomicBool>,
     /// Queue of callbacks to notify about download events.
     callbacks: Arc<Mutex<VecDeque<Box<dyn PreloadCallback>>>>,
+    /// Whether data is delivered to callbacks only, leaving nothing to share.
+    sink: bool,
+    /// Observers of the download lifecycle.
+    observers: Arc<Mutex<PreloadObservers>>,
+    /// Set once `on_ready` was signalled, if the request asked for it.
+    ready: Option<Arc<AtomicBool>>,
 }
 
 impl TaskHandle {
@@ -179,6 +186,9 @@ impl TaskHandle {
             handle: None,
             finish: Arc::new(AtomicBool::new(false)),
             callbacks: Arc::new(Mutex::new(VecDeque::with_capacity(1))),
+            sink: false,
+            observers: Arc::new(Mutex::new(PreloadObservers::new())),
+            ready: None,
         }
     }
     
@@ -242,10 +252,33 @@ impl TaskHandle {
     pub(crate) fn set_completed(&self) {
         self.state.store(SUCCESS, Ordering::Relaxed);
         self.finish.store(true, Ordering::Relaxed);
+        self.observers
+            .lock()
+            .unwrap()
+            .finish(|observer| observer.on_success());
+    }
+
+    /// Registers an observer of the download lifecycle.
+    ///
+    /// Every handle of the download shares its observers, so each caller
+    /// preloading the same URL may register its own.
+    ///
+    /// # Parameters
+    /// - `observer`: Observer to notify of the download events.
+    ///
+    /// # Returns
+    /// `true` if the observer was registered, `false` if the download
+    /// already finished.
+    pub fn add_observer(&self, observer: Box<dyn PreloadObserver>) -> bool {
+        self.observers.lock().unwrap().add(observer).is_ok()
     }
 
     /// Attempts to add a callback to the task if it hasn't finished.
     ///
+    /// Sink tasks never accept additional callbacks, since bytes delivered before
+    /// joining would be missed and no cache is produced to fill the gap. A
+    /// callback joining a task that is already ready gets `on_ready` at once.
+    ///
     /// # Parameters
     /// - `callback`: Callback to add to the task.
     ///
@@ -255,9 +288,16 @@ impl TaskHandle {
         &mut self,
         callback: Box<dyn PreloadCallback>,
     ) -> Result<(), Box<dyn PreloadCallback>> {
+        if self.sink {
+            return Err(callback);
+        }
         let mut callbacks = self.callbacks.lock().unwrap();
         if !self.finish.load(Ordering::Acquire) {
             info!("add callback to task {}", self.task_id.brief());
+            let ready = self.ready.as_ref();
+            if ready.is_some_and(|ready| ready.load(Ordering::Acquire)) {
+                callback.on_ready(self.task_id.brief());
+            }
             callbacks.push_back(callback);
             if let Some(handle) = self.handle.as_ref() {
                 handle.add_count();
@@ -338,7 +378,7 @@ where
         handle.callbacks.lock().unwrap().push_back(callback);
     }
 
-    let callback = PrimeCallback::new(
+    let mut callback = PrimeCallback::new(
         task_id,
         cache_manager,
         handle.finish_flag(),
@@ -346,6 +386,16 @@ where
         handle.callbacks(),
         seq,
     );
+    if let Some(max_in_flight) = request.sink {
+        handle.sink = true;
+        callback = callback.with_sink(max_in_flight);
+    }
+    if request.ready {
+        let ready = Arc::new(AtomicBool::new(false));
+        handle.ready = Some(ready.clone());
+        callback = callback.with_ready(ready);
+    }
+    callback = callback.with_observers(handle.observers.clone());
     downloader(request, callback, info_mgr).map(move |command| {
         handle.set_handle(command);
         handle
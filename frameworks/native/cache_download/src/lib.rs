@@ -44,6 +44,7 @@ pub mod info;    // Download information and metrics
 pub mod observe; // Observation and monitoring functionality
 pub mod services; // Service interfaces and types
 
+pub use observe::PreloadObserver;
 pub use services::{CacheDownloadService, DownloadRequest, PreloadCallback};
 
 // Re-export downloader enum for public API use
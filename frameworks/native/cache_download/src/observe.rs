@@ -14,7 +14,8 @@
 //! Network observation functionality for cache download operations.
 //! 
 //! This module provides observers for monitoring network state changes and
-//! triggering appropriate actions in the cache download system.
+//! triggering appropriate actions in the cache download system, and the
+//! observers of the lifecycle of each cache download.
 
 // Import network observation trait from request_utils
 use request_utils::observe::network;
@@ -42,3 +43,112 @@ impl network::Observer for NetObserver {
         CacheDownloadService::get_instance().reset_all_tasks();
     }
 }
+
+/// Trait for observing the lifecycle of a cache download.
+///
+/// Unlike `PreloadCallback`, observers do not receive the downloaded data.
+/// Events are delivered in order on the download thread, so implementations
+/// should return quickly: `on_start` first, any number of `on_progress`,
+/// then exactly one of `on_success`, `on_fail` or `on_cancel`.
+#[allow(unused_variables)]
+pub trait PreloadObserver: Send {
+    /// Called when the download starts, or on registration if it already
+    /// started.
+    fn on_start(&self) {}
+
+    /// Called to report download progress.
+    ///
+    /// # Parameters
+    /// - `downloaded`: Number of bytes downloaded so far
+    /// - `total`: Total number of bytes to download, 0 if unknown
+    fn on_progress(&self, downloaded: u64, total: u64) {}
+
+    /// Called when the download completes successfully.
+    fn on_success(&self) {}
+
+    /// Called when the download fails.
+    ///
+    /// # Parameters
+    /// - `code`: Error code of the failure
+    /// - `msg`: Human-readable description of the failure
+    fn on_fail(&self, code: i32, msg: &str) {}
+
+    /// Called when the download is cancelled.
+    fn on_cancel(&self) {}
+}
+
+/// Observers registered to one cache download.
+///
+/// Shared between the handles of the download, so every caller preloading
+/// the same URL can register its own observers.
+pub(crate) struct PreloadObservers {
+    /// Registered observers, notified in registration order
+    observers: Vec<Box<dyn PreloadObserver>>,
+    /// Whether the download has started
+    started: bool,
+    /// Whether the download has finished, releasing the observers
+    finished: bool,
+}
+
+impl PreloadObservers {
+    /// Creates an empty set of observers for a download not started yet.
+    pub(crate) fn new() -> Self {
+        Self {
+            observers: Vec::new(),
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Registers an observer, telling it at once if the download started.
+    ///
+    /// # Returns
+    /// `Ok(())` if the observer was registered, otherwise returns the
+    /// observer in `Err` when the download already finished.
+    pub(crate) fn add(
+        &mut self,
+        observer: Box<dyn PreloadObserver>,
+    ) -> Result<(), Box<dyn PreloadObserver>> {
+        if self.finished {
+            return Err(observer);
+        }
+        if self.started {
+            observer.on_start();
+        }
+        self.observers.push(observer);
+        Ok(())
+    }
+
+    /// Notifies the observers that the download started.
+    pub(crate) fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        for observer in self.observers.iter() {
+            observer.on_start();
+        }
+    }
+
+    /// Notifies the observers of the download progress.
+    pub(crate) fn progress(&self, downloaded: u64, total: u64) {
+        for observer in self.observers.iter() {
+            observer.on_progress(downloaded, total);
+        }
+    }
+
+    /// Notifies the observers of the end of the download and releases them.
+    ///
+    /// # Parameters
+    /// - `notify`: Delivers the final event to one observer
+    pub(crate) fn finish<F>(&mut self, notify: F)
+    where
+        F: Fn(&dyn PreloadObserver),
+    {
+        self.start();
+        self.finished = true;
+        for observer in self.observers.drain(..) {
+            notify(observer.as_ref());
+        }
+    }
+}
@@ -32,7 +32,7 @@ use request_utils::task_id::TaskId;
 use crate::download::task::{DownloadTask, Downloader, TaskHandle};
 use crate::download::CacheDownloadError;
 use crate::info::RustDownloadInfo;
-use crate::observe::NetObserver;
+use crate::observe::{NetObserver, PreloadObserver};
 
 /// Trait defining callback methods for preload operations.
 ///
@@ -64,6 +64,26 @@ pub trait PreloadCallback: Send {
     /// - `progress`: Number of bytes downloaded so far
     /// - `total`: Total number of bytes to download
     fn on_progress(&mut self, progress: u64, total: u64) {}
+
+    /// Called with each received chunk when the request delivers through a sink.
+    ///
+    /// Chunks arrive in order and are not written to the cache, so the data
+    /// passed to `on_success` is empty for such requests.
+    ///
+    /// # Parameters
+    /// - `task_id`: Brief identifier for the task
+    /// - `chunk`: The next part of the response body
+    fn on_data(&mut self, task_id: &str, chunk: &[u8]) {}
+
+    /// Called once the response headers and first bytes arrived, when the
+    /// request signals readiness.
+    ///
+    /// Always precedes `on_success` and the first `on_data` call, while the
+    /// download goes on in the background.
+    ///
+    /// # Parameters
+    /// - `task_id`: Brief identifier for the task
+    fn on_ready(&mut self, task_id: &str) {}
 }
 
 /// Main service for managing cache downloads.
@@ -94,6 +114,10 @@ pub struct DownloadRequest<'a> {
     pub ssl_type: Option<&'a str>,
     /// Optional path to CA certificates.
     pub ca_path: Option<&'a str>,
+    /// Bound on undelivered bytes when data goes to `on_data` instead of the cache.
+    pub sink: Option<usize>,
+    /// Whether `on_ready` is signalled once the first bytes arrive.
+    pub ready: bool,
 }
 
 impl<'a> DownloadRequest<'a> {
@@ -113,6 +137,8 @@ impl<'a> DownloadRequest<'a> {
             headers: None,
             ssl_type: None,
             ca_path: None,
+            sink: None,
+            ready: false,
         }
     }
 
@@ -161,6 +187,32 @@ impl<'a> DownloadRequest<'a> {
         self.ca_path = Some(ca_path);
         self
     }
+
+    /// Delivers the response body to `PreloadCallback::on_data` only.
+    ///
+    /// Nothing is written to the memory or file cache. At most `max_in_flight`
+    /// bytes wait for the callbacks at any time; beyond that, reception pauses
+    /// until the callbacks catch up.
+    ///
+    /// # Parameters
+    /// - `max_in_flight`: Maximum number of undelivered bytes
+    ///
+    /// # Returns
+    /// A mutable reference to self for method chaining
+    pub fn sink_to_callback(&mut self, max_in_flight: usize) -> &mut Self {
+        self.sink = Some(max_in_flight);
+        self
+    }
+
+    /// Signals `PreloadCallback::on_ready` as soon as the headers and first
+    /// bytes are available, so streaming consumers can start before completion.
+    ///
+    /// # Returns
+    /// A mutable reference to self for method chaining
+    pub fn ready_on_first_byte(&mut self) -> &mut Self {
+        self.ready = true;
+        self
+    }
 }
 
 impl CacheDownloadService {
@@ -220,6 +272,24 @@ impl CacheDownloadService {
         }
     }
 
+    /// Registers an observer of the running download of a URL.
+    ///
+    /// # Parameters
+    /// - `url`: URL of the download to observe
+    /// - `observer`: Observer to notify of the download events
+    ///
+    /// # Returns
+    /// `true` if the observer was registered, `false` if no download of the
+    /// URL is running
+    pub fn observe(&self, url: &str, observer: Box<dyn PreloadObserver>) -> bool {
+        let task_id = TaskId::from_url(url);
+        let Some(task) = self.running_tasks.lock().unwrap().get(&task_id).cloned() else {
+            return false;
+        };
+        let handle = task.lock().unwrap().task_handle();
+        handle.add_observer(observer)
+    }
+
     /// Resets all currently running download tasks.
     ///
     /// Called when network connectivity is restored to resume paused downloads.
@@ -274,9 +344,11 @@ impl CacheDownloadService {
         let url = request.url;
         let task_id = TaskId::from_url(url);
         info!("preload {}", task_id.brief());
+        // Sink requests always fetch from the network and never share a task
+        let sink = request.sink.is_some();
 
         // Try to fetch from cache first if not updating
-        if !update {
+        if !update && !sink {
             if let Err(ret) = self.fetch_with_callback(&task_id, callback) {
                 callback = ret;
             } else {
@@ -315,10 +387,15 @@ impl CacheDownloadService {
             };
 
             let mut updater = updater.lock().unwrap();
-            match updater.try_add_callback(callback) {
+            let added = if sink {
+                Err(callback)
+            } else {
+                updater.try_add_callback(callback)
+            };
+            match added {
                 Ok(()) => return Some(updater.task_handle()),
                 Err(mut cb) => {
-                    if update {
+                    if update || sink {
                         info!("add callback failed, update task {}", task_id.brief());
                     } else if let Err(callback) = self.fetch_with_callback(&task_id, cb) {
                         error!("{} fetch fail after update", task_id.brief());
@@ -12,10 +12,10 @@
 // limitations under the License.
 
 use std::collections::HashSet;
-use std::io::{BufReader, Lines};
-use std::net::TcpStream;
+use std::io::{BufRead, BufReader, Lines, Write};
+use std::net::{TcpListener, TcpStream};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, LazyLock};
+use std::sync::{mpsc, Arc, LazyLock, Mutex};
 use std::thread;
 use std::time::Duration;
 
@@ -152,3 +152,169 @@ fn ut_download_headers() {
     }
     assert!(flag.load(Ordering::SeqCst));
 }
+
+struct SinkCallback {
+    data: Arc<Mutex<Vec<u8>>>,
+    cached: Arc<Mutex<Option<usize>>>,
+}
+
+impl PreloadCallback for SinkCallback {
+    fn on_data(&mut self, _task_id: &str, chunk: &[u8]) {
+        // Slow consumer, so the receiving side has to wait for buffer space
+        thread::sleep(Duration::from_millis(1));
+        self.data.lock().unwrap().extend_from_slice(chunk);
+    }
+
+    fn on_success(&mut self, data: Arc<RamCache>, _task_id: &str) {
+        *self.cached.lock().unwrap() = Some(data.size());
+    }
+}
+
+// @tc.name: ut_download_sink_to_callback
+// @tc.desc: Test sink mode delivers the body through on_data only
+// @tc.precon: NA
+// @tc.step: 1. Start a server answering with a known body
+//           2. Create a download request in sink mode with a small in-flight bound
+//           3. Call download_inner function with a slow on_data callback
+//           4. Wait for task completion
+// @tc.expect: The concatenated chunks equal the body and nothing is cached
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_download_sink_to_callback() {
+    init();
+    static CACHE_MANAGER: LazyLock<CacheManager> = LazyLock::new(CacheManager::new);
+    let body = (0..64 * 1024).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let server = format!("http://{}", listener.local_addr().unwrap());
+    let response = body.clone();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let reader = BufReader::new(&mut stream);
+        for line in reader.lines() {
+            if line.unwrap().is_empty() {
+                break;
+            }
+        }
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+            response.len()
+        );
+        stream.write_all(head.as_bytes()).unwrap();
+        stream.write_all(&response).unwrap();
+    });
+
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let cached = Arc::new(Mutex::new(None));
+    let mut request = DownloadRequest::new(&server);
+    request.sink_to_callback(4096);
+    let info_mgr = Arc::new(DownloadInfoMgr::new());
+    let handle = download_inner(
+        TaskId::from_url(&server),
+        &CACHE_MANAGER,
+        info_mgr,
+        request,
+        Some(Box::new(SinkCallback {
+            data: data.clone(),
+            cached: cached.clone(),
+        })),
+        DOWNLOADER,
+        0,
+    );
+    assert!(handle.is_some());
+    let handle = handle.unwrap();
+    while !handle.is_finish() {
+        thread::sleep(Duration::from_millis(500));
+    }
+    while cached.lock().unwrap().is_none() {
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert_eq!(*data.lock().unwrap(), body);
+    assert_eq!(*cached.lock().unwrap(), Some(0));
+    assert!(CACHE_MANAGER.fetch(&TaskId::from_url(&server)).is_none());
+}
+
+struct ReadyCallback {
+    events: Arc<Mutex<Vec<&'static str>>>,
+    ready_tx: mpsc::Sender<()>,
+}
+
+impl PreloadCallback for ReadyCallback {
+    fn on_ready(&mut self, _task_id: &str) {
+        self.events.lock().unwrap().push("ready");
+        self.ready_tx.send(()).unwrap();
+    }
+
+    fn on_success(&mut self, _data: Arc<RamCache>, _task_id: &str) {
+        self.events.lock().unwrap().push("complete");
+    }
+}
+
+// @tc.name: ut_download_ready_on_first_byte
+// @tc.desc: Test on_ready fires once the first bytes arrive, before completion
+// @tc.precon: NA
+// @tc.step: 1. Start a server sending the headers and the first half of the body
+//           2. Create a download request signalling readiness
+//           3. Let the server send the second half only after on_ready
+//           4. Wait for task completion
+// @tc.expect: on_ready is called once, before on_success
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_download_ready_on_first_byte() {
+    init();
+    static CACHE_MANAGER: LazyLock<CacheManager> = LazyLock::new(CacheManager::new);
+    let body = (0..8 * 1024).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let server = format!("http://{}", listener.local_addr().unwrap());
+    let (ready_tx, ready_rx) = mpsc::channel();
+    let response = body.clone();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let reader = BufReader::new(&mut stream);
+        for line in reader.lines() {
+            if line.unwrap().is_empty() {
+                break;
+            }
+        }
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+            response.len()
+        );
+        let (first, rest) = response.split_at(response.len() / 2);
+        stream.write_all(head.as_bytes()).unwrap();
+        stream.write_all(first).unwrap();
+        stream.flush().unwrap();
+        // The task cannot complete before the consumer was told it is ready
+        ready_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        stream.write_all(rest).unwrap();
+    });
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut request = DownloadRequest::new(&server);
+    request.ready_on_first_byte();
+    let info_mgr = Arc::new(DownloadInfoMgr::new());
+    let handle = download_inner(
+        TaskId::from_url(&server),
+        &CACHE_MANAGER,
+        info_mgr,
+        request,
+        Some(Box::new(ReadyCallback {
+            events: events.clone(),
+            ready_tx,
+        })),
+        DOWNLOADER,
+        0,
+    );
+    assert!(handle.is_some());
+    let handle = handle.unwrap();
+    while !handle.is_finish() {
+        thread::sleep(Duration::from_millis(100));
+    }
+    while events.lock().unwrap().len() < 2 {
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert_eq!(*events.lock().unwrap(), vec!["ready", "complete"]);
+    let cached = CACHE_MANAGER.fetch(&TaskId::from_url(&server)).unwrap();
+    assert_eq!(cached.size(), body.len());
+}
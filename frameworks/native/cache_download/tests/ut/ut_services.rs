@@ -80,6 +80,33 @@ impl PreloadCallback for TestCallbackC {
     }
 }
 
+struct TestObserver {
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl PreloadObserver for TestObserver {
+    fn on_start(&self) {
+        self.events.lock().unwrap().push("start".to_string());
+    }
+
+    fn on_progress(&self, downloaded: u64, total: u64) {
+        let event = format!("progress {}/{}", downloaded, total);
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn on_success(&self) {
+        self.events.lock().unwrap().push("success".to_string());
+    }
+
+    fn on_fail(&self, code: i32, _msg: &str) {
+        self.events.lock().unwrap().push(format!("fail {}", code));
+    }
+
+    fn on_cancel(&self) {
+        self.events.lock().unwrap().push("cancel".to_string());
+    }
+}
+
 // @tc.name: ut_preload_success
 // @tc.desc: Test successful preload operation
 // @tc.precon: NA
@@ -406,6 +433,64 @@ fn ut_preload_local_headers() {
     assert_eq!(success_flag.load(Ordering::SeqCst), NO_DATA);
 }
 
+// @tc.name: ut_preload_observer_success
+// @tc.desc: Test observers receive the lifecycle of a successful preload
+// @tc.precon: NA
+// @tc.step: 1. Initialize CacheDownloadService
+//           2. Preload from a test server holding its response
+//           3. Register one observer by URL and one on the task handle
+//           4. Let the server respond and wait for task completion
+// @tc.expect: Both observers receive start, the final progress and success,
+//             in this order
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+// @tc.level: level1
+#[test]
+fn ut_preload_observer_success() {
+    init();
+    static SERVICE: LazyLock<CacheDownloadService> = LazyLock::new(CacheDownloadService::new);
+
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    let test_f = move |mut lines: Lines<BufReader<&mut TcpStream>>| {
+        for line in lines.by_ref() {
+            if line.unwrap().is_empty() {
+                break;
+            }
+        }
+        // Respond only once the observers are registered
+        rx.recv().unwrap();
+    };
+    let server = test_server(test_f);
+    let callback = Box::new(TestCallbackN);
+    let handle = SERVICE.preload(DownloadRequest::new(&server), callback, true, DOWNLOADER);
+    assert!(handle.is_some());
+    let handle = handle.unwrap();
+
+    let by_url = Arc::new(Mutex::new(vec![]));
+    let by_handle = Arc::new(Mutex::new(vec![]));
+    let observer = Box::new(TestObserver {
+        events: by_url.clone(),
+    });
+    assert!(SERVICE.observe(&server, observer));
+    let observer = Box::new(TestObserver {
+        events: by_handle.clone(),
+    });
+    assert!(handle.add_observer(observer));
+    tx.send(()).unwrap();
+
+    while !handle.is_finish() {
+        thread::sleep(Duration::from_millis(500));
+    }
+    let expected = vec!["start", "progress 0/0", "success"];
+    assert_eq!(*by_url.lock().unwrap(), expected);
+    assert_eq!(*by_handle.lock().unwrap(), expected);
+
+    let observer = Box::new(TestObserver {
+        events: Arc::new(Mutex::new(vec![])),
+    });
+    assert!(!handle.add_observer(observer));
+}
+
 // @tc.name: ut_preload_fetch
 // @tc.desc: Test fetching cached data after preload
 // @tc.precon: NA
@@ -43,8 +43,28 @@ enum class RequestInterfaceCode {
     CMD_DELETE_GROUP,
     CMD_SET_MAX_SPEED,
     CMD_SHOW_PROGRESS,
+    CMD_SUBSCRIBE_TASK_LIST,
+    CMD_UNSUBSCRIBE_TASK_LIST,
+    CMD_COUNT_BY_STATE,
     CMD_SET_MODE = 100,
     CMD_DISABLE_TASK_NOTIFICATIONS,
+    CMD_SET_GLOBAL_MAX_SPEED,
+    CMD_SET_DESTINATION,
+    CMD_QUERY_GROUP,
+    CMD_SUBSCRIBE_BATCH,
+    CMD_REMOVE_WITH_GRACE,
+    CMD_UNDO_REMOVE,
+    CMD_PURGE_REMOVED,
+    CMD_ENABLE_JOURNAL,
+    CMD_QUERY_EFFECTIVE_CONFIG,
+    CMD_SOFT_STOP,
+    CMD_QUERY_SPEED_HISTORY,
+    CMD_CANCEL_GROUP,
+    CMD_EXPLAIN_TASK,
+    CMD_CHANGED_SINCE,
+    CMD_SEARCH_SYSTEM,
+    CMD_MIGRATE_LEGACY_TASKS,
+    CMD_ISSUE_TASK_TOKEN,
 };
 
 enum class RequestNotifyInterfaceCode {
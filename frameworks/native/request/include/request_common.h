@@ -30,6 +30,7 @@ enum class Action : uint32_t {
     DOWNLOAD = 0,
     UPLOAD,
     ANY,
+    LOCAL_LOOPBACK,
 };
 
 enum class Mode : uint32_t {
@@ -90,6 +91,17 @@ enum Reason : uint32_t {
     APP_ACCOUNT,
     NETWORK_APP_ACCOUNT,
     LOW_SPEED,
+    RATE_LIMITED,
+    DURABILITY_CHECK_FAILED,
+    UNEXPECTED_CONTENT_TYPE,
+    PRECONDITION_FAILED,
+    NOT_CHARGING,
+    RETRY_DEADLINE_EXCEEDED,
+    SIZE_LIMIT_EXCEEDED,
+    CAPTIVE_PORTAL,
+    BOUND_NETWORK_UNAVAILABLE,
+    CROSS_HOST_REDIRECT_LIMIT,
+    DEVICE_CONDITION,
 };
 
 enum WaitingReason : uint32_t {
@@ -97,6 +109,10 @@ enum WaitingReason : uint32_t {
     NetworkNotMatch = 0x01,
     AppBackground = 0x02,
     UserInactivated = 0x03,
+    RateLimited = 0x04,
+    Battery = 0x05,
+    CaptivePortal = 0x06,
+    DeviceCondition = 0x07,
 };
 
 enum class SubscribeType : uint32_t {
@@ -204,6 +220,7 @@ enum class State : uint32_t {
     COMPLETED = 0x40,
     FAILED = 0x41,
     REMOVED = 0x50,
+    PENDING_REMOVAL = 0x51,
     DEFAULT = 0x60,
     ANY = 0x61,
 };
@@ -291,6 +308,9 @@ struct TaskInfo {
     uint64_t taskTime = 0;
     std::map<std::string, std::string> extras;
     std::vector<TaskState> taskStates;
+    uint64_t effectiveSpeedLimit = 0;
+    std::vector<uint8_t> responseBody;
+    bool responseBodyTruncated = false;
 };
 
 struct TaskInfoRet {
@@ -39,6 +39,11 @@ void ParcelHelper::UnMarshal(MessageParcel &data, TaskInfo &info)
     if (!UnMarshalTaskState(data, info)) {
         return;
     }
+    info.effectiveSpeedLimit = data.ReadUint64();
+    if (data.ReadBool()) {
+        info.responseBodyTruncated = data.ReadBool();
+        data.ReadUInt8Vector(&info.responseBody);
+    }
 }
 
 void ParcelHelper::UnMarshalBase(MessageParcel &data, TaskInfo &info)
@@ -0,0 +1,94 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recovery of the notification channel for calls to the service.
+//!
+//! The service answers `CHANNEL_NOT_OPEN` when the notification channel of
+//! the client is gone, for instance after the service restarted. Only calls
+//! subscribing the client to tasks depend on the channel: creating a task,
+//! getting a task and subscribing to tasks. The service checks the channel
+//! before creating tasks and subscribing again has no further effect, so
+//! these calls are safe to send again once the channel is reopened.
+//! [`ChannelGuard`] reopens the channel once and retries the call, callers
+//! failing on the same closed channel at the same time reopen it only once.
+
+use std::sync::Mutex;
+
+use request_core::error_code::CHANNEL_NOT_OPEN;
+
+use crate::client::error::CreateTaskError;
+
+/// Errors telling whether a call failed because the channel was not open.
+pub(crate) trait ChannelError: From<i32> {
+    /// Returns `true` if the service answered `CHANNEL_NOT_OPEN`.
+    fn channel_not_open(&self) -> bool;
+}
+
+impl ChannelError for i32 {
+    fn channel_not_open(&self) -> bool {
+        *self == CHANNEL_NOT_OPEN
+    }
+}
+
+impl ChannelError for CreateTaskError {
+    fn channel_not_open(&self) -> bool {
+        matches!(self, CreateTaskError::Code(CHANNEL_NOT_OPEN))
+    }
+}
+
+/// Reopens the channel at most once per closure and retries calls.
+#[derive(Default)]
+pub(crate) struct ChannelGuard {
+    /// Number of times the channel was opened, guarded so only one caller
+    /// reopens it at a time.
+    generation: Mutex<u64>,
+}
+
+impl ChannelGuard {
+    /// Records that the channel was opened outside of the guard.
+    pub(crate) fn opened(&self) {
+        *self.generation.lock().unwrap() += 1;
+    }
+
+    /// Runs `call`, reopening the channel with `reopen` and running `call`
+    /// once more if the channel was not open.
+    ///
+    /// If another caller reopened the channel since `call` was sent, the
+    /// channel is not reopened again and `call` is retried right away. An
+    /// error of `reopen` is returned without retrying.
+    pub(crate) fn run<T, E: ChannelError>(
+        &self,
+        mut call: impl FnMut() -> Result<T, E>,
+        reopen: impl FnOnce() -> Result<(), i32>,
+    ) -> Result<T, E> {
+        let sent = *self.generation.lock().unwrap();
+        match call() {
+            Err(e) if e.channel_not_open() => {}
+            res => return res,
+        }
+        {
+            let mut generation = self.generation.lock().unwrap();
+            if *generation == sent {
+                info!("channel not open, reopening it");
+                reopen().map_err(E::from)?;
+                *generation += 1;
+            }
+        }
+        call()
+    }
+}
+
+#[cfg(test)]
+mod ut_channel {
+    include!("../../tests/ut/client/ut_channel.rs");
+}
@@ -0,0 +1,70 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Preparation of a task configuration for a copy of the task.
+//!
+//! A clone keeps what describes the transfer, such as the URL, method, body
+//! and headers. What grants access to resources, such as the task token,
+//! file descriptors and resolved paths, is dropped and derived again when
+//! the clone is created, exactly as for a new task.
+
+use std::collections::HashMap;
+
+use request_core::config::{Action, TaskConfig};
+
+/// Changes applied to a cloned task configuration.
+#[derive(Clone, Debug, Default)]
+pub struct TaskOverrides {
+    /// Save path of the clone, for downloads.
+    pub saveas: Option<String>,
+    /// Headers replacing those of the original task.
+    pub headers: Option<HashMap<String, String>>,
+}
+
+/// Turns the configuration of an existing task into one for a new task.
+pub(crate) fn prepare_clone(mut config: TaskConfig, overrides: TaskOverrides) -> TaskConfig {
+    // Downloads are recreated from their save path, the file specs are
+    // resolved from it again.
+    if matches!(config.common_data.action, Action::Download) {
+        if let Some(spec) = config.file_specs.first() {
+            config.saveas = spec.path.clone();
+        }
+        config.file_specs.clear();
+    }
+    for spec in config.file_specs.iter_mut() {
+        spec.is_user_file = false;
+        spec.fd = None;
+    }
+    config.token.clear();
+    config.body_file_paths.clear();
+    config.certs_path.clear();
+    config.bundle.clear();
+    config.bundle_type = 0;
+    config.atomic_account.clear();
+    config.common_data.task_id = 0;
+    config.common_data.uid = 0;
+    config.common_data.token_id = 0;
+
+    if let Some(saveas) = overrides.saveas {
+        config.saveas = saveas;
+    }
+    if let Some(headers) = overrides.headers {
+        config.headers = headers;
+    }
+    config
+}
+
+#[cfg(test)]
+mod ut_clone {
+    include!("../../tests/ut/client/ut_clone.rs");
+}
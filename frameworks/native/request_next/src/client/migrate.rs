@@ -0,0 +1,444 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export format of task state for device migration.
+//!
+//! A blob starts with [`EXPORT_MAGIC`] and the format version, followed by
+//! the number of tasks and one length prefixed entry per task. Integers are
+//! little endian, strings are UTF-8 prefixed by their byte length. Readers
+//! skip bytes left at the end of an entry, so later versions may append
+//! fields without breaking older readers; the task options were appended
+//! this way and are empty for entries written before. Partial files are not
+//! part of the blob; an imported download resumes from the file found at its
+//! `saveas` path, cut back to the exported progress.
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io;
+use std::path::Path;
+
+use request_core::config::{
+    Action, CommonTaskConfig, FormItem, MinSpeed, Mode, NetworkConfig, Notification, TaskConfig,
+    TaskOptions, Timeout, Version,
+};
+use request_core::error_code::PARAMETER_CHECK;
+use request_core::file::FileSpec;
+
+/// Leading bytes of an export blob.
+pub(crate) const EXPORT_MAGIC: [u8; 4] = *b"RQEX";
+
+/// Format version written by this client.
+pub(crate) const EXPORT_VERSION: u32 = 1;
+
+/// Headers dropped on export unless explicitly included.
+const SENSITIVE_HEADERS: [&str; 4] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+];
+
+/// Configuration and progress of an exported task.
+#[derive(Clone, Debug)]
+pub(crate) struct ExportedTask {
+    pub(crate) config: TaskConfig,
+    pub(crate) total_processed: u64,
+    pub(crate) sizes: Vec<i64>,
+    pub(crate) processed: Vec<u64>,
+}
+
+/// Removes sensitive headers except those named in `include`, compared
+/// case-insensitively.
+pub(crate) fn redact_headers(headers: &mut HashMap<String, String>, include: &[String]) {
+    headers.retain(|name, _| {
+        let name = name.to_ascii_lowercase();
+        !SENSITIVE_HEADERS.contains(&name.as_str())
+            || include.iter().any(|kept| kept.eq_ignore_ascii_case(&name))
+    });
+}
+
+/// Cuts the partial file of an imported download back to the exported
+/// progress.
+///
+/// Bytes past the exported progress were never counted and may be torn, so
+/// they are downloaded again. A shorter file is a valid prefix and resumes
+/// from its own end.
+pub(crate) fn reconcile_partial_file(path: &Path, total_processed: u64) -> io::Result<()> {
+    let file = OpenOptions::new().write(true).open(path)?;
+    if file.metadata()?.len() > total_processed {
+        file.set_len(total_processed)?;
+    }
+    Ok(())
+}
+
+/// Serializes `tasks` into an export blob.
+pub(crate) fn encode(tasks: &[ExportedTask]) -> Vec<u8> {
+    let mut blob = EXPORT_MAGIC.to_vec();
+    put_u32(&mut blob, EXPORT_VERSION);
+    put_u32(&mut blob, tasks.len() as u32);
+    for task in tasks {
+        let entry = encode_task(task);
+        put_u32(&mut blob, entry.len() as u32);
+        blob.extend(entry);
+    }
+    blob
+}
+
+/// Parses an export blob.
+///
+/// Returns `PARAMETER_CHECK` if the blob is truncated, holds unknown values
+/// or was written by a newer format version.
+pub(crate) fn decode(blob: &[u8]) -> Result<Vec<ExportedTask>, i32> {
+    let mut reader = Reader { inner: blob };
+    if reader.bytes(EXPORT_MAGIC.len())? != EXPORT_MAGIC {
+        error!("import blob has no export header");
+        return Err(PARAMETER_CHECK);
+    }
+    let version = reader.u32()?;
+    if version == 0 || version > EXPORT_VERSION {
+        error!("import blob version {} unsupported", version);
+        return Err(PARAMETER_CHECK);
+    }
+    let count = reader.u32()?;
+    let mut tasks = vec![];
+    for _ in 0..count {
+        let len = reader.u32()? as usize;
+        let mut entry = Reader {
+            inner: reader.bytes(len)?,
+        };
+        tasks.push(decode_task(&mut entry)?);
+    }
+    Ok(tasks)
+}
+
+fn encode_task(task: &ExportedTask) -> Vec<u8> {
+    let config = &task.config;
+    let common = &config.common_data;
+    let mut entry = vec![];
+    put_u32(&mut entry, common.action.clone() as u32);
+    put_u32(&mut entry, common.mode as u32);
+    put_u32(&mut entry, config.version as u32);
+    for value in [
+        &config.url,
+        &config.title,
+        &config.description,
+        &config.method,
+        &config.data,
+        &config.saveas,
+        &config.proxy,
+        &config.certificate_pins,
+    ] {
+        put_str(&mut entry, value);
+    }
+    put_map(&mut entry, &config.headers);
+    put_map(&mut entry, &config.extras);
+    for flag in [
+        config.overwrite,
+        common.metered,
+        common.roaming,
+        common.retry,
+        common.redirect,
+        common.gauge,
+        common.precise,
+        common.background,
+        common.multipart,
+    ] {
+        entry.push(flag as u8);
+    }
+    put_u32(&mut entry, common.network_config as u32);
+    put_u32(&mut entry, common.index);
+    put_u64(&mut entry, common.begins);
+    put_u64(&mut entry, common.ends as u64);
+    put_u32(&mut entry, common.priority);
+    put_u64(&mut entry, common.min_speed.speed as u64);
+    put_u64(&mut entry, common.min_speed.duration as u64);
+    put_u64(&mut entry, common.timeout.connection_timeout);
+    put_u64(&mut entry, common.timeout.total_timeout);
+    for text in [&config.notification.title, &config.notification.text] {
+        entry.push(text.is_some() as u8);
+        put_str(&mut entry, text.as_deref().unwrap_or_default());
+    }
+    put_u32(&mut entry, config.form_items.len() as u32);
+    for item in config.form_items.iter() {
+        put_str(&mut entry, &item.name);
+        put_str(&mut entry, &item.value);
+    }
+    put_u32(&mut entry, config.file_specs.len() as u32);
+    for spec in config.file_specs.iter() {
+        put_str(&mut entry, &spec.name);
+        put_str(&mut entry, &spec.path);
+        put_str(&mut entry, &spec.file_name);
+        put_str(&mut entry, &spec.mime_type);
+    }
+
+    put_u64(&mut entry, task.total_processed);
+    put_u32(&mut entry, task.sizes.len() as u32);
+    for size in task.sizes.iter() {
+        put_u64(&mut entry, *size as u64);
+    }
+    put_u32(&mut entry, task.processed.len() as u32);
+    for processed in task.processed.iter() {
+        put_u64(&mut entry, *processed);
+    }
+
+    let options = config.options.pairs();
+    put_u32(&mut entry, options.len() as u32);
+    for (name, value) in options {
+        put_str(&mut entry, name);
+        put_str(&mut entry, &value);
+    }
+    entry
+}
+
+fn decode_task(entry: &mut Reader) -> Result<ExportedTask, i32> {
+    let action = match entry.u32()? {
+        0 => Action::Download,
+        1 => Action::Upload,
+        _ => return Err(PARAMETER_CHECK),
+    };
+    let mode = match entry.u32()? {
+        0 => Mode::BackGround,
+        1 => Mode::FrontEnd,
+        _ => return Err(PARAMETER_CHECK),
+    };
+    let version = match entry.u32()? {
+        1 => Version::API9,
+        2 => Version::API10,
+        _ => return Err(PARAMETER_CHECK),
+    };
+    let url = entry.string()?;
+    let title = entry.string()?;
+    let description = entry.string()?;
+    let method = entry.string()?;
+    let data = entry.string()?;
+    let saveas = entry.string()?;
+    let proxy = entry.string()?;
+    let certificate_pins = entry.string()?;
+    let headers = entry.map()?;
+    let extras = entry.map()?;
+    let overwrite = entry.bool()?;
+    let metered = entry.bool()?;
+    let roaming = entry.bool()?;
+    let retry = entry.bool()?;
+    let redirect = entry.bool()?;
+    let gauge = entry.bool()?;
+    let precise = entry.bool()?;
+    let background = entry.bool()?;
+    let multipart = entry.bool()?;
+    let network_config = match entry.u32()? {
+        0 => NetworkConfig::Any,
+        1 => NetworkConfig::Wifi,
+        2 => NetworkConfig::Cellular,
+        _ => return Err(PARAMETER_CHECK),
+    };
+    let index = entry.u32()?;
+    let begins = entry.u64()?;
+    let ends = entry.u64()? as i64;
+    let priority = entry.u32()?;
+    let min_speed = MinSpeed {
+        speed: entry.u64()? as i64,
+        duration: entry.u64()? as i64,
+    };
+    let timeout = Timeout {
+        connection_timeout: entry.u64()?,
+        total_timeout: entry.u64()?,
+    };
+    let notification = Notification {
+        title: entry.optional_string()?,
+        text: entry.optional_string()?,
+    };
+    let mut form_items = vec![];
+    for _ in 0..entry.u32()? {
+        form_items.push(FormItem {
+            name: entry.string()?,
+            value: entry.string()?,
+        });
+    }
+    let mut file_specs = vec![];
+    for _ in 0..entry.u32()? {
+        file_specs.push(FileSpec {
+            name: entry.string()?,
+            path: entry.string()?,
+            file_name: entry.string()?,
+            mime_type: entry.string()?,
+            is_user_file: false,
+            fd: None,
+        });
+    }
+
+    let total_processed = entry.u64()?;
+    let mut sizes = vec![];
+    for _ in 0..entry.u32()? {
+        sizes.push(entry.u64()? as i64);
+    }
+    let mut processed = vec![];
+    for _ in 0..entry.u32()? {
+        processed.push(entry.u64()?);
+    }
+
+    let mut options = TaskOptions::default();
+    if !entry.is_empty() {
+        for _ in 0..entry.u32()? {
+            let name = entry.string()?;
+            let value = entry.string()?;
+            if options.set(&name, &value).is_none() {
+                error!("import blob option {} not valid", name);
+                return Err(PARAMETER_CHECK);
+            }
+        }
+    }
+
+    let config = TaskConfig {
+        bundle: String::new(),
+        bundle_type: 0,
+        atomic_account: String::new(),
+        url,
+        title,
+        description,
+        method,
+        headers,
+        data,
+        token: String::new(),
+        proxy,
+        certificate_pins,
+        extras,
+        version,
+        form_items,
+        file_specs,
+        body_file_paths: vec![],
+        certs_path: vec![],
+        common_data: CommonTaskConfig {
+            task_id: 0,
+            uid: 0,
+            token_id: 0,
+            action,
+            mode,
+            cover: overwrite,
+            network_config,
+            metered,
+            roaming,
+            retry,
+            redirect,
+            index,
+            begins,
+            ends,
+            gauge,
+            precise,
+            priority,
+            background,
+            multipart,
+            min_speed,
+            timeout,
+        },
+        saveas,
+        overwrite,
+        notification,
+        options,
+    };
+    Ok(ExportedTask {
+        config,
+        total_processed,
+        sizes,
+        processed,
+    })
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u64(buf: &mut Vec<u8>, value: u64) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_str(buf: &mut Vec<u8>, value: &str) {
+    put_u32(buf, value.len() as u32);
+    buf.extend_from_slice(value.as_bytes());
+}
+
+fn put_map(buf: &mut Vec<u8>, map: &HashMap<String, String>) {
+    // Sorted, so equal maps give equal blobs.
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort();
+    put_u32(buf, entries.len() as u32);
+    for (key, value) in entries {
+        put_str(buf, key);
+        put_str(buf, value);
+    }
+}
+
+/// Bounds checked reader of an export blob.
+struct Reader<'a> {
+    inner: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], i32> {
+        if self.inner.len() < len {
+            error!("import blob truncated");
+            return Err(PARAMETER_CHECK);
+        }
+        let (bytes, rest) = self.inner.split_at(len);
+        self.inner = rest;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, i32> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.bytes(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> Result<u64, i32> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.bytes(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn bool(&mut self) -> Result<bool, i32> {
+        match self.bytes(1)?[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(PARAMETER_CHECK),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, i32> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.bytes(len)?.to_vec()).map_err(|_| PARAMETER_CHECK)
+    }
+
+    fn optional_string(&mut self) -> Result<Option<String>, i32> {
+        let present = self.bool()?;
+        let value = self.string()?;
+        Ok(present.then_some(value))
+    }
+
+    fn map(&mut self) -> Result<HashMap<String, String>, i32> {
+        let mut map = HashMap::new();
+        for _ in 0..self.u32()? {
+            let key = self.string()?;
+            map.insert(key, self.string()?);
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod ut_migrate {
+    include!("../../tests/ut/client/ut_migrate.rs");
+}
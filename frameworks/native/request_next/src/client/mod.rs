@@ -21,111 +21,110 @@
 //!
 //! ```rust
 //! use std::sync::Arc;
+//!
+//! use request_client::client::error::CreateTaskError;
+//! use request_client::{Callback, RequestClient};
+//! use request_core::config::{TaskConfigBuilder, Version};
+//! use request_core::info::Progress;
 //! use request_utils::context::Context;
-//! use request_core::config::{TaskConfig, Version};
-//! use request_core::filter::SearchFilter;
-//! use request_next::client::RequestClient;
-//! use request_next::Callback;
 //!
 //! // Define a callback to receive download notifications
 //! struct MyCallback;
 //!
 //! impl Callback for MyCallback {
-//!     fn on_progress(&self, task_id: i64, current: i64, total: i64) {
-//!         println!("Download progress: {}/{} bytes", current, total);
+//!     fn on_progress(&self, progress: &Progress) {
+//!         println!("Download progress: {} bytes", progress.total_processed);
 //!     }
 //!
-//!     fn on_complete(&self, task_id: i64) {
+//!     fn on_completed(&self, _progress: &Progress) {
 //!         println!("Download completed!");
 //!     }
 //!
-//!     fn on_failed(&self, task_id: i64, error_code: i32) {
+//!     fn on_failed(&self, _progress: &Progress, error_code: i32) {
 //!         println!("Download failed with code: {}", error_code);
 //!     }
 //! }
 //!
-//! fn manage_downloads() {
+//! fn download(context: Context, seq: u64) -> Result<i64, CreateTaskError> {
 //!     // Get the singleton client instance
 //!     let client = RequestClient::get_instance();
 //!
-//!     // Create and configure a download task
-//!     let context = Context::new();
-//!     let mut config = TaskConfig::new();
-//!     config.url = "https://example.com/large-file.iso".to_string();
-//!     config.add_header("User-Agent", "MyApp/1.0");
-//!
-//!     // Create the task
-//!     match client.crate_task(
-//!         context,
-//!         Version::Api10,
-//!         config,
-//!         "/data/data/com.example.app/downloads/file.iso",
-//!         false
-//!     ) {
-//!         Ok(task_id) => {
-//!             println!("Created task with ID: {}", task_id);
-//!
-//!             // Register for progress updates
-//!             client.register_callback(task_id, Arc::new(MyCallback));
+//!     // Configure a download task
+//!     let mut builder = TaskConfigBuilder::new(Version::API10);
+//!     builder
+//!         .url("https://example.com/large-file.iso".to_string())
+//!         .file_path("./large-file.iso".to_string())
+//!         .header("User-Agent", "MyApp/1.0");
 //!
-//!             // Start the download
-//!             if let Err(err) = client.start(task_id) {
-//!                 println!("Failed to start task: {}", err);
-//!                 return;
-//!             }
+//!     // Check the config, then create the task from it
+//!     let check_context = Context {
+//!         inner: context.inner.clone(),
+//!     };
+//!     client.check_config(check_context, seq, builder.build())?;
+//!     let task_id = client.create_task(context, seq)?;
 //!
-//!             // ... later, if needed
-//!             // Pause the download
-//!             // client.pause(task_id).unwrap();
+//!     // Register for progress updates and start the download
+//!     client.register_callback(task_id, Arc::new(MyCallback));
+//!     client.start(task_id).map_err(CreateTaskError::Code)?;
 //!
-//!             // Resume the download
-//!             // client.resume(task_id).unwrap();
-//!
-//!             // Limit download speed to 1MB/s
-//!             // client.set_max_speed(task_id, 1_048_576).unwrap();
-//!
-//!             // Get task information
-//!             if let Ok(info) = client.show_task(task_id) {
-//!                 println!("Task info: {:?}", info);
-//!             }
-//!         },
-//!         Err(e) => {
-//!             println!("Failed to create task: {:?}", e);
-//!         }
-//!     }
-//!
-//!     // Search for tasks
-//!     let filter = SearchFilter::new();
-//!     if let Ok(task_ids) = client.search(filter) {
-//!         println!("Found {} tasks", task_ids.len());
-//!     }
+//!     // Limit download speed to 1MB/s
+//!     client
+//!         .set_max_speed(task_id, 1_048_576)
+//!         .map_err(CreateTaskError::Code)?;
+//!     Ok(task_id)
 //! }
 //! ```
 
 // Public module exports
+mod channel;
+mod clone;
 pub mod error;
+mod migrate;
 mod native_task;
-use std::path::PathBuf;
+mod probe;
+use std::path::{Path, PathBuf};
 
 // Standard library imports
-use std::sync::{Arc, OnceLock};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::Duration;
 
 // External dependencies
-use request_core::config::{Action, TaskConfig, Version};
-use request_core::error_code::{CHANNEL_NOT_OPEN, OTHER};
+use request_core::config::{Action, GroupProgressStyle, TaskConfig, TokenScope, Version};
+use request_core::error_code::{OTHER, PARAMETER_CHECK, TASK_NOT_FOUND};
 use request_core::file::FileSpec;
 use request_core::filter::SearchFilter;
-use request_core::info::TaskInfo;
+use request_core::info::{
+    ChangedSet, EffectiveConfig, GroupInfo, MigrationReport, SpeedSample, State, StateCounts,
+    TaskExplanation, TaskInfo, TaskListDelta,
+};
 use request_utils::context::Context;
 
 // Internal dependencies
+use crate::client::channel::{ChannelError, ChannelGuard};
+use crate::client::clone::prepare_clone;
+pub use crate::client::clone::TaskOverrides;
 use crate::client::error::CreateTaskError;
+use crate::client::migrate::{redact_headers, ExportedTask};
 use crate::client::native_task::{NativeTask, NativeTaskManager};
+pub use crate::client::native_task::ListedTask;
+pub use crate::client::probe::{ProbeOutcome, ProbeResult};
 use crate::file::FileManager;
 use crate::listen::Observer;
+pub use crate::proxy::ChannelStats;
 use crate::proxy::RequestProxy;
 use crate::verify::TaskConfigVerifier;
-use crate::{check, Callback};
+use crate::{check, Callback, CallbackRegistration};
+
+/// Returns a sequence number for a task created by the client itself.
+///
+/// Sequence numbers of the upper half are never handed out to callers of
+/// `check_config`.
+fn internal_seq() -> u64 {
+    static NEXT_SEQ: AtomicU64 = AtomicU64::new(1 << 63);
+    NEXT_SEQ.fetch_add(1, Ordering::Relaxed)
+}
 
 /// Client for interacting with the download service.
 ///
@@ -137,6 +136,8 @@ pub struct RequestClient<'a> {
     pub task_manager: NativeTaskManager,
     /// Proxy for communicating with the download service
     proxy: &'a RequestProxy,
+    /// Reopens the notification channel for calls failing on it
+    channel: ChannelGuard,
 }
 
 impl<'a> RequestClient<'a> {
@@ -151,7 +152,7 @@ impl<'a> RequestClient<'a> {
     /// # Examples
     ///
     /// ```rust
-    /// use request_next::client::RequestClient;
+    /// use request_client::RequestClient;
     ///
     /// // Get the singleton instance
     /// let client1 = RequestClient::get_instance();
@@ -159,9 +160,6 @@ impl<'a> RequestClient<'a> {
     ///
     /// // Both references point to the same instance
     /// assert!(std::ptr::eq(client1, client2));
-    ///
-    /// // Use the client for operations
-    /// // let result = client1.start(123);
     /// ```
     pub fn get_instance() -> &'static Self {
         static INSTANCE: OnceLock<RequestClient> = OnceLock::new();
@@ -172,6 +170,7 @@ impl<'a> RequestClient<'a> {
                 listener,
                 task_manager: NativeTaskManager::default(),
                 proxy: RequestProxy::get_instance(),
+                channel: ChannelGuard::default(),
             };
             // Initialize communication channel on first creation
             res.open_channel();
@@ -188,131 +187,119 @@ impl<'a> RequestClient<'a> {
         debug!("Creating task with config: {:?}", config);
         // todo: errcode and errmsg
         TaskConfigVerifier::get_instance().verify(&config)?;
-        let token = FileManager::get_instance().apply(context, &mut config)?;
+        let (token, certs) = FileManager::get_instance().apply(context, &mut config)?;
+        let body_files = config.body_file_paths.iter().map(PathBuf::from).collect();
         let task = NativeTask {
             config,
-            token,
+            token: Mutex::new(token),
+            certs: Mutex::new(certs),
+            body_files: Mutex::new(body_files),
         };
         self.task_manager.insert(seq, task);
 
         Ok(())
     }
 
-    /// Creates a new download task with the specified configuration.
+    /// Creates a task from the configuration checked under `seq`.
     ///
-    /// Validates the download path, creates a file specification, and sends the task
-    /// creation request to the service. Automatically reopens the channel if needed.
+    /// Sends the task creation request to the service, automatically reopening
+    /// the channel if needed. The task is created but not started.
     ///
     /// # Parameters
-    /// - `context`: Application context for path validation
-    /// - `version`: API version to determine path handling
-    /// - `config`: Task configuration including URL, headers, etc.
-    /// - `save_as`: Path where the downloaded file should be saved
-    /// - `overwrite`: Whether to overwrite existing files
+    /// - `context`: Application context of the caller
+    /// - `seq`: Sequence number the configuration was checked under by
+    ///   [`check_config`](Self::check_config)
     ///
     /// # Returns
     /// A task ID on success, or a `CreateTaskError` on failure
     ///
     /// # Errors
-    /// - `CreateTaskError::DownloadPath`: If path validation fails
+    /// - `CreateTaskError::Code(OTHER)`: If no configuration was checked under `seq`
     /// - `CreateTaskError::Code`: If task creation fails for other reasons
     ///
-    /// # Notes
-    /// The function name contains a typo (`crate_task` instead of `create_task`).
-    ///
     /// # Examples
     ///
     /// ```rust
-    /// use std::sync::Arc;
+    /// use request_client::client::error::CreateTaskError;
+    /// use request_client::RequestClient;
+    /// use request_core::config::{TaskConfigBuilder, Version};
     /// use request_utils::context::Context;
-    /// use request_core::config::{TaskConfig, Version};
-    /// use request_next::client::RequestClient;
-    /// use request_next::Callback;
-    ///
-    /// // Create a simple callback implementation
-    /// struct DownloadCallback;
-    /// impl Callback for DownloadCallback {
-    ///     fn on_progress(&self, task_id: i64, bytes_downloaded: i64, total_bytes: i64) {
-    ///         println!("Task {} progress: {}/{} bytes", task_id, bytes_downloaded, total_bytes);
-    ///     }
-    ///
-    ///     fn on_complete(&self, task_id: i64) {
-    ///         println!("Task {} completed successfully", task_id);
-    ///     }
-    ///
-    ///     fn on_failed(&self, task_id: i64, error_code: i32) {
-    ///         println!("Task {} failed with error: {}", task_id, error_code);
-    ///     }
-    /// }
     ///
-    /// // Example usage
-    /// fn create_and_start_download() {
-    ///     // Get the client instance
+    /// fn create_and_start(context: Context, seq: u64) -> Result<i64, CreateTaskError> {
     ///     let client = RequestClient::get_instance();
     ///
-    ///     // Create context and task configuration
-    ///     let context = Context::new();
-    ///     let mut config = TaskConfig::new();
+    ///     let mut config = TaskConfigBuilder::new(Version::API10).build();
     ///     config.url = "https://example.com/file.zip".to_string();
+    ///     config.saveas = "./download.zip".to_string();
+    ///     config.add_header("Accept", "application/zip");
     ///
-    ///     // Create the download task
-    ///     match client.crate_task(
-    ///         context,
-    ///         Version::Api10,
-    ///         config,
-    ///         "/data/data/com.example.app/files/download.zip",
-    ///         true
-    ///     ) {
-    ///         Ok(task_id) => {
-    ///             println!("Task created with ID: {}", task_id);
-    ///
-    ///             // Register a callback for status updates
-    ///             client.register_callback(task_id, Arc::new(DownloadCallback));
-    ///
-    ///             // Start the download
-    ///             if let Err(err) = client.start(task_id) {
-    ///                 println!("Failed to start task: {}", err);
-    ///             }
-    ///         },
-    ///         Err(e) => {
-    ///             println!("Failed to create task: {:?}", e);
-    ///         }
-    ///     }
+    ///     let check_context = Context {
+    ///         inner: context.inner.clone(),
+    ///     };
+    ///     client.check_config(check_context, seq, config)?;
+    ///     let task_id = client.create_task(context, seq)?;
+    ///     client.start(task_id).map_err(CreateTaskError::Code)?;
+    ///     Ok(task_id)
     /// }
     /// ```
-    pub fn create_task(
+    pub fn create_task(&self, context: Context, seq: u64) -> Result<i64, CreateTaskError> {
+        self.create_task_reporting_duplicate(context, seq)
+            .map(|created| created.task_id)
+    }
+
+    /// Creates a task like [`create_task`](Self::create_task), telling
+    /// whether the service returned an active task instead.
+    ///
+    /// With [`DuplicatePolicy::Warn`], creating a download of the same URL
+    /// and destination as an active download of the caller returns that
+    /// download, flagged as a duplicate. The duplicate keeps the callbacks
+    /// and tokens it was created with.
+    ///
+    /// [`DuplicatePolicy::Warn`]: request_core::config::DuplicatePolicy::Warn
+    ///
+    /// # Parameters
+    /// - `context`: Application context of the caller
+    /// - `seq`: Sequence number the configuration was checked under
+    ///
+    /// # Returns
+    /// The created or returned task on success, or a `CreateTaskError` on
+    /// failure
+    pub fn create_task_reporting_duplicate(
         &self,
-        context: Context,
-        seq: u64
-    ) -> Result<i64, CreateTaskError> {
-        let task = self.task_manager.get_by_seq(&seq).ok_or(CreateTaskError::Code(OTHER))?;
-
-        // Retry loop for channel reconnection
-        loop {
-            let res = match self.proxy.create(&task.config) {
-                Err(e) => {
-                    error!("Failed to create task: {:?}", e);
-                    // Attempt to reopen channel if it's closed
-                    if matches!(e, CreateTaskError::Code(CHANNEL_NOT_OPEN)) {
-                        self.open_channel();
-                        continue;
-                    }
+        _context: Context,
+        seq: u64,
+    ) -> Result<CreatedTask, CreateTaskError> {
+        let task = self
+            .task_manager
+            .get_by_seq(&seq)
+            .ok_or(CreateTaskError::Code(OTHER))?;
+
+        match self.guarded(|| self.proxy.create(&task.config)) {
+            Err(e) => {
+                error!("Failed to create task: {:?}", e);
+                self.task_manager.remove(&seq);
+                Err(e)
+            }
+            Ok(created) if created.duplicate => {
+                info!("Task {} returned as an active duplicate", created.task_id);
+                if self.task_manager.get_by_id(&created.task_id).is_some() {
                     self.task_manager.remove(&seq);
-                    Err(e)
+                } else {
+                    self.task_manager.bind(created.task_id, seq);
                 }
-                Ok(task_id) => {
-                    info!("Task created successfully with ID: {}", task_id);
-                    self.task_manager.bind(task_id, seq);
-                    Ok(task_id)
-                }
-            };
-            break res;
+                Ok(created)
+            }
+            Ok(created) => {
+                info!("Task created successfully with ID: {}", created.task_id);
+                self.task_manager.bind(created.task_id, seq);
+                Ok(created)
+            }
         }
     }
 
     pub fn get_task(&self, task_id: i64, token: Option<String>) -> Result<TaskConfig, i32> {
         
-        self.proxy.get_task(task_id, token)
+        self.guarded(|| self.proxy.get_task(task_id, token.clone()))
     }
 
     /// Starts a download task with the specified ID.
@@ -322,6 +309,24 @@ impl<'a> RequestClient<'a> {
     ///
     /// # Returns
     /// `Ok(())` on success, or an error code on failure
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use request_client::RequestClient;
+    /// use request_core::config::{TaskConfigBuilder, Version};
+    /// use request_core::error_code::TASK_STATE_ERR;
+    ///
+    /// # let task_id = request_client::add_task(TaskConfigBuilder::new(Version::API10).build());
+    /// let client = RequestClient::get_instance();
+    /// client.start(task_id).unwrap();
+    ///
+    /// // A running task cannot be started again
+    /// assert_eq!(client.start(task_id), Err(TASK_STATE_ERR));
+    ///
+    /// client.pause(task_id).unwrap();
+    /// client.resume(task_id).unwrap();
+    /// ```
     pub fn start(&self, task_id: i64) -> Result<(), i32> {
         self.proxy.start(task_id)
     }
@@ -360,6 +365,45 @@ impl<'a> RequestClient<'a> {
         self.proxy.remove(task_id)
     }
 
+    /// Removes a task, keeping it restorable for a grace period.
+    ///
+    /// The task stops and is hidden from searches, but keeps its progress
+    /// and partial file until [`purge_removed`](Self::purge_removed) is
+    /// called or the grace period expires.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task to remove
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error code on failure
+    pub fn remove_with_grace(&self, task_id: i64) -> Result<(), i32> {
+        self.proxy.remove_with_grace(task_id)
+    }
+
+    /// Restores a task removed with a grace period, it becomes paused.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task pending removal
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error code on failure
+    pub fn undo_remove(&self, task_id: i64) -> Result<(), i32> {
+        self.proxy.undo_remove(task_id)
+    }
+
+    /// Removes a task pending removal without waiting for its grace period.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task pending removal
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error code on failure
+    pub fn purge_removed(&self, task_id: i64) -> Result<(), i32> {
+        self.proxy.purge_removed(task_id)?;
+        self.task_manager.remove_task(&task_id);
+        Ok(())
+    }
+
     /// Stops a running download task without removing files.
     ///
     /// # Parameters
@@ -368,7 +412,9 @@ impl<'a> RequestClient<'a> {
     /// # Returns
     /// `Ok(())` on success, or an error code on failure
     pub fn stop(&self, task_id: i64) -> Result<(), i32> {
-        self.proxy.stop(task_id)
+        self.proxy.stop(task_id)?;
+        self.task_manager.revoke(&task_id);
+        Ok(())
     }
 
     /// Sets the maximum download speed for a task.
@@ -383,12 +429,96 @@ impl<'a> RequestClient<'a> {
         self.proxy.set_max_speed(task_id, speed)
     }
 
+    /// Moves the file of a paused download task to a new destination path.
+    ///
+    /// The task continues downloading into the new path once resumed.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the paused download task
+    /// - `path`: New destination path inside the application sandbox
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error code on failure
+    pub fn set_destination(&self, task_id: i64, path: &str) -> Result<(), i32> {
+        self.proxy.set_destination(task_id, path)
+    }
+
     pub fn query_mime_type(&self, task_id: i64) -> Result<String, i32> {
         self.proxy.query_mime_type(task_id)
     }
 
+    /// Retrieves the configuration a task runs with after the adjustments of
+    /// the service, with secret values redacted.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task to query
+    ///
+    /// # Returns
+    /// The effective configuration on success, or an error code if the task
+    /// is not found or never ran
+    pub fn effective_config(&self, task_id: i64) -> Result<EffectiveConfig, i32> {
+        self.proxy.effective_config(task_id)
+    }
+
+    /// Retrieves the recent processed bytes samples of a task for graphing
+    /// its speed.
+    ///
+    /// Samples are taken about once per second and only the latest minute
+    /// is kept. A task that stopped running keeps its last window for a
+    /// while.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task to query
+    /// - `points`: Maximum number of samples to return, 0 for all
+    ///
+    /// # Returns
+    /// The newest samples oldest first on success, or an error code if the
+    /// task is neither running nor recently stopped
+    pub fn speed_history(&self, task_id: i64, points: u32) -> Result<Vec<SpeedSample>, i32> {
+        self.proxy.speed_history(task_id, points)
+    }
+
+    /// Explains why a task is in its current state.
+    ///
+    /// Reports the state and reason of the task, what it waits for and
+    /// whether the network, foreground, battery and storage conditions of
+    /// the task are satisfied now, with its retry count and the time it is
+    /// next scheduled to act at. The conditions, retry count and next action
+    /// are only reported to the application owning the task.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task to explain
+    ///
+    /// # Returns
+    /// The explanation on success, or an error code if the task is not found
+    pub fn explain(&self, task_id: i64) -> Result<TaskExplanation, i32> {
+        self.proxy.explain(task_id)
+    }
+
+    /// Checks whether the host of a URL is reachable, without creating a
+    /// task.
+    ///
+    /// The host is resolved and connected to, and for `http` URLs a `HEAD`
+    /// request is sent. A failed resolution, a refused connection and a
+    /// timeout are reported as distinct outcomes.
+    ///
+    /// # Parameters
+    /// - `url`: `http` or `https` URL of the host to probe
+    /// - `timeout`: Longest time the probe may take, the name resolution
+    ///   itself is not cut short
+    ///
+    /// # Returns
+    /// The outcome, latency and address of the probe, or `PARAMETER_CHECK` if
+    /// the URL is not supported or the timeout is zero
+    pub fn probe(&self, url: &str, timeout: Duration) -> Result<ProbeResult, i32> {
+        probe::probe(url, timeout)
+    }
+
     /// Registers a callback for task status updates.
     ///
+    /// The callback is kept until `unregister_callback` is called or another
+    /// callback is registered for the task.
+    ///
     /// # Parameters
     /// - `task_id`: ID of the task to monitor
     /// - `callback`: Callback to receive status updates
@@ -400,6 +530,88 @@ impl<'a> RequestClient<'a> {
         self.listener.register_callback(task_id, callback);
     }
 
+    /// Registers a callback for task status updates until the returned
+    /// registration is dropped.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task to monitor
+    /// - `callback`: Callback to receive status updates
+    pub fn register_scoped_callback(
+        &self,
+        task_id: i64,
+        callback: Arc<dyn Callback + Send + Sync + 'static>,
+    ) -> CallbackRegistration {
+        self.listener.register_scoped_callback(task_id, callback)
+    }
+
+    /// Registers a callback for task status updates without keeping it alive.
+    ///
+    /// Updates stop once the callback is dropped by its owner or the returned
+    /// registration is dropped.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task to monitor
+    /// - `callback`: Weak reference to the callback to receive status updates
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use request_client::{Callback, RequestClient};
+    /// use request_core::config::{TaskConfigBuilder, Version};
+    /// use request_core::info::Progress;
+    ///
+    /// struct Page;
+    ///
+    /// impl Callback for Page {
+    ///     fn on_progress(&self, progress: &Progress) {
+    ///         println!("{} bytes processed", progress.total_processed);
+    ///     }
+    /// }
+    ///
+    /// # let task_id = request_client::add_task(TaskConfigBuilder::new(Version::API10).build());
+    /// let client = RequestClient::get_instance();
+    /// let page: Arc<dyn Callback + Send + Sync> = Arc::new(Page);
+    /// let registration = client.register_weak_callback(task_id, Arc::downgrade(&page));
+    /// assert_eq!(registration.task_id(), task_id);
+    ///
+    /// // Dropping the page stops the updates, the client does not keep it alive
+    /// drop(page);
+    /// ```
+    pub fn register_weak_callback(
+        &self,
+        task_id: i64,
+        callback: Weak<dyn Callback + Send + Sync + 'static>,
+    ) -> CallbackRegistration {
+        self.listener.register_weak_callback(task_id, callback)
+    }
+
+    /// Subscribes to updates for many tasks in a single call.
+    ///
+    /// Each subscribed task immediately reports its current state to the
+    /// callback registered for it, so the state of restored tasks is known
+    /// without querying them one by one.
+    ///
+    /// # Parameters
+    /// - `task_ids`: IDs of the tasks to monitor
+    ///
+    /// # Returns
+    /// The result of each task in order, or an error code if the request
+    /// itself failed
+    pub fn subscribe_batch(&self, task_ids: &[i64]) -> Result<Vec<Result<(), i32>>, i32> {
+        let task_ids = task_ids.iter().map(|task_id| task_id.to_string()).collect();
+        self.guarded(|| self.proxy.subscribe_batch(task_ids.clone()))
+    }
+
+    /// Unregisters the callback of a task, no further updates are delivered.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task to stop monitoring
+    pub fn unregister_callback(&self, task_id: i64) {
+        self.listener.unregister_callback(task_id);
+    }
+
     /// Opens the communication channel with the download service.
     ///
     /// Initializes the listener with a file descriptor from the proxy.
@@ -407,6 +619,49 @@ impl<'a> RequestClient<'a> {
         // Unwrap is safe as the proxy handles error conditions
         let file = self.proxy.open_channel().unwrap();
         self.listener.set_listenr(file);
+        self.channel.opened();
+    }
+
+    /// Reopens the communication channel after the service reported it was
+    /// not open.
+    fn reopen_channel(&self) -> Result<(), i32> {
+        let file = self.proxy.open_channel()?;
+        self.listener.set_listenr(file);
+        Ok(())
+    }
+
+    /// Sends a call subscribing the client to tasks, reopening the channel
+    /// once and sending the call again if the service reported the channel
+    /// was not open.
+    ///
+    /// Other calls do not depend on the channel and are never answered
+    /// `CHANNEL_NOT_OPEN`. The service checks the channel before creating
+    /// tasks, so a retried creation leaves no task behind.
+    fn guarded<T, E: ChannelError>(&self, call: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+        self.channel.run(call, || self.reopen_channel())
+    }
+
+    /// Returns the state of the connection to the download service.
+    ///
+    /// After failed loads of the service, this reports how many failed in a
+    /// row and how long it is until the next load is attempted.
+    pub fn channel_stats(&self) -> ChannelStats {
+        self.proxy.channel_stats()
+    }
+
+    /// Reconnects to the download service immediately, skipping any backoff.
+    ///
+    /// Meant for callers that know the service just restarted. The
+    /// communication channel is reopened on success.
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error code if the service or channel could
+    /// not be opened
+    pub fn force_reconnect(&self) -> Result<(), i32> {
+        self.proxy.force_reconnect()?;
+        self.reopen_channel()?;
+        self.channel.opened();
+        Ok(())
     }
 
     /// Retrieves information about a specific task.
@@ -431,23 +686,497 @@ impl<'a> RequestClient<'a> {
         self.proxy.search(keyword)
     }
 
+    /// Searches for tasks matching the specified filter, one page at a time.
+    ///
+    /// Pages split the result of [`search`](Self::search) in order, a page
+    /// past the last task is empty.
+    ///
+    /// # Parameters
+    /// - `filter`: Search filter defining the search criteria
+    /// - `page`: Index of the page, starting at 0
+    /// - `page_size`: Maximum number of task IDs per page
+    ///
+    /// # Returns
+    /// The task IDs of the page on success, `PARAMETER_CHECK` if `page_size`
+    /// is 0, or another error code on failure
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use request_client::RequestClient;
+    /// use request_core::config::{Action, TaskConfigBuilder, Version};
+    /// use request_core::filter::SearchFilter;
+    ///
+    /// # for _ in 0..5 {
+    /// #     request_client::add_task(TaskConfigBuilder::new(Version::API10).build());
+    /// # }
+    /// let client = RequestClient::get_instance();
+    /// let filter = || {
+    ///     let mut filter = SearchFilter::new();
+    ///     filter.action = Some(Action::Download);
+    ///     filter
+    /// };
+    ///
+    /// let all = client.search(filter()).unwrap();
+    /// assert_eq!(all.len(), 5);
+    ///
+    /// let first = client.search_page(filter(), 0, 2).unwrap();
+    /// assert_eq!(first, all[..2]);
+    /// let last = client.search_page(filter(), 2, 2).unwrap();
+    /// assert_eq!(last, all[4..]);
+    /// assert!(client.search_page(filter(), 3, 2).unwrap().is_empty());
+    /// ```
+    pub fn search_page(
+        &self,
+        filter: SearchFilter,
+        page: usize,
+        page_size: usize,
+    ) -> Result<Vec<String>, i32> {
+        if page_size == 0 {
+            return Err(PARAMETER_CHECK);
+        }
+        let task_ids = self.search(filter)?;
+        Ok(task_ids
+            .into_iter()
+            .skip(page.saturating_mul(page_size))
+            .take(page_size)
+            .collect())
+    }
+
+    /// Returns the configs of tasks passed to [`check_config`](Self::check_config)
+    /// but not yet bound to a task ID, keyed by sequence number.
+    pub fn pending_tasks(&self) -> Vec<(u64, TaskConfig)> {
+        self.task_manager
+            .pending()
+            .into_iter()
+            .map(|(seq, task)| (seq, task.config.clone()))
+            .collect()
+    }
+
+    /// Lists the tasks matching the specified filter, including tasks still
+    /// being constructed.
+    ///
+    /// Tasks being constructed are flagged as pending until they are bound
+    /// to a task ID, after which they are listed once by their ID.
+    ///
+    /// # Parameters
+    /// - `filter`: Search filter defining the search criteria
+    ///
+    /// # Returns
+    /// The matching tasks on success, or an error code on failure
+    pub fn list_all(&self, filter: SearchFilter) -> Result<Vec<ListedTask>, i32> {
+        // Snapshot before searching, so a task bound during the search is
+        // still listed.
+        let pending = self
+            .task_manager
+            .pending()
+            .into_iter()
+            .filter(|(_, task)| task.matches(&filter))
+            .map(|(seq, _)| seq)
+            .collect();
+        let task_ids = self.search(filter)?;
+        Ok(self.task_manager.merge_pending(pending, task_ids))
+    }
+
+    /// Counts tasks per state, e.g. for quota and settings pages.
+    ///
+    /// # Parameters
+    /// - `uid`: Application whose tasks are counted, `None` for the caller.
+    ///   Only system applications may count the tasks of other applications.
+    ///
+    /// # Returns
+    /// The counts keyed by uid on success, or an error code on failure
+    pub fn count_by_state(&self, uid: Option<u64>) -> Result<HashMap<u64, StateCounts>, i32> {
+        self.proxy.count_by_state(false, uid)
+    }
+
+    /// Counts the tasks of all applications per state.
+    ///
+    /// This is a system API.
+    ///
+    /// # Returns
+    /// The counts keyed by uid on success, or an error code on failure
+    pub fn count_all_by_state(&self) -> Result<HashMap<u64, StateCounts>, i32> {
+        self.proxy.count_by_state(true, None)
+    }
+
+    /// Rewrites the legacy API9 downloads of an application as API10 tasks.
+    ///
+    /// Migrated tasks keep their progress and files and can be queried,
+    /// started and paused through the API10 interface. Uploads, downloads to
+    /// files opened by the user and removed tasks are left untouched and
+    /// reported with the reason. Migrating again only reports the tasks left.
+    ///
+    /// This is a system API.
+    ///
+    /// # Parameters
+    /// - `uid`: Application whose legacy tasks are migrated
+    ///
+    /// # Returns
+    /// The migrated and skipped tasks on success, or an error code on failure
+    pub fn migrate_legacy_tasks(&self, uid: u64) -> Result<MigrationReport, i32> {
+        self.proxy.migrate_legacy_tasks(uid)
+    }
+
+    /// Returns the tasks of the calling application changed since a
+    /// revision.
+    ///
+    /// Every creation, state or progress change, mode change and removal of
+    /// a task gives it a new revision. Keep the `revision` of the result and
+    /// pass it to the next call to learn only about later changes instead of
+    /// querying every task. Removals are remembered for a week; if an older
+    /// revision is passed, `resync` is set and all tasks have to be fetched
+    /// again.
+    ///
+    /// # Parameters
+    /// - `revision`: Revision returned by the previous call, 0 for all tasks
+    ///
+    /// # Returns
+    /// The changed and removed tasks on success, or an error code on failure
+    pub fn changed_since(&self, revision: u64) -> Result<ChangedSet, i32> {
+        self.proxy.changed_since(revision)
+    }
+
+    /// Exports the configuration and progress of tasks for migration to
+    /// another device.
+    ///
+    /// Partial files are not exported. Sensitive headers such as
+    /// `Authorization` and `Cookie` are dropped unless named in
+    /// `include_headers`, task tokens are never exported.
+    ///
+    /// # Parameters
+    /// - `task_ids`: Tasks to export
+    /// - `include_headers`: Sensitive headers to keep, case-insensitive
+    ///
+    /// # Returns
+    /// The export blob on success, or an error code on failure
+    pub fn export_tasks(
+        &self,
+        task_ids: &[i64],
+        include_headers: &[String],
+    ) -> Result<Vec<u8>, i32> {
+        let mut tasks = vec![];
+        for task_id in task_ids {
+            let mut config = self.get_task(*task_id, None)?;
+            let progress = self.show_task(*task_id)?.progress;
+            // Downloads are recreated from their save path.
+            if matches!(config.common_data.action, Action::Download) {
+                if let Some(spec) = config.file_specs.first() {
+                    config.saveas = spec.path.clone();
+                }
+                config.file_specs.clear();
+            }
+            redact_headers(&mut config.headers, include_headers);
+            tasks.push(ExportedTask {
+                config,
+                total_processed: progress.common_data.total_processed as u64,
+                sizes: progress.sizes,
+                processed: progress.processed.iter().map(|p| *p as u64).collect(),
+            });
+        }
+        Ok(migrate::encode(&tasks))
+    }
+
+    /// Recreates tasks from a blob written by [`export_tasks`](Self::export_tasks).
+    ///
+    /// Tasks are created but not started. A download resumes from the file
+    /// found at its save path once started, so partial files copied along
+    /// with the blob are continued instead of downloaded again. The file is
+    /// cut back to the exported progress first, uploads start over.
+    ///
+    /// # Parameters
+    /// - `context`: Application context for path validation
+    /// - `blob`: Export blob
+    ///
+    /// # Returns
+    /// The IDs of the created tasks on success. On failure tasks created
+    /// before the failing one are kept.
+    pub fn import_tasks(&self, context: Context, blob: &[u8]) -> Result<Vec<i64>, CreateTaskError> {
+        let mut task_ids = vec![];
+        for task in migrate::decode(blob)? {
+            let mut config = task.config;
+            if matches!(config.common_data.action, Action::Download) {
+                // Keep a partial file at the save path instead of rejecting it.
+                config.overwrite = true;
+                config.common_data.cover = true;
+            }
+            let download = matches!(config.common_data.action, Action::Download);
+            let seq = internal_seq();
+            let task_context = Context {
+                inner: context.inner.clone(),
+            };
+            self.check_config(task_context, seq, config)?;
+            let path = self.task_manager.get_by_seq(&seq).and_then(|native| {
+                native
+                    .config
+                    .file_specs
+                    .first()
+                    .map(|spec| spec.path.clone())
+            });
+            if let Some(path) = path.filter(|_| download) {
+                let total_processed = task.total_processed;
+                if let Err(e) = migrate::reconcile_partial_file(Path::new(&path), total_processed) {
+                    error!("import reconcile partial file failed {:?}", e);
+                }
+            }
+            let task_context = Context {
+                inner: context.inner.clone(),
+            };
+            task_ids.push(self.create_task(task_context, seq)?);
+        }
+        Ok(task_ids)
+    }
+
+    /// Creates a new task from the configuration of an existing one.
+    ///
+    /// The clone keeps the URL, method, body and headers of the task. Its
+    /// token, file descriptors and resolved paths are derived again instead
+    /// of copied. The clone is created but not started.
+    ///
+    /// # Parameters
+    /// - `context`: Application context for path validation
+    /// - `task_id`: ID of the task to clone
+    /// - `overrides`: Changes applied to the cloned configuration
+    ///
+    /// # Returns
+    /// The ID of the new task on success, or a `CreateTaskError` on failure
+    pub fn clone_task(
+        &self,
+        context: Context,
+        task_id: i64,
+        overrides: TaskOverrides,
+    ) -> Result<i64, CreateTaskError> {
+        let config = self.get_task(task_id, None)?;
+        let config = prepare_clone(config, overrides);
+        let seq = internal_seq();
+        let task_context = Context {
+            inner: context.inner.clone(),
+        };
+        self.check_config(task_context, seq, config)?;
+        self.create_task(context, seq)
+    }
+
+    /// Subscribes to changes of the caller's tasks matching a filter.
+    ///
+    /// The callback first receives all matching tasks as added, then the
+    /// tasks created, removed or changing state, at most every 500 ms.
+    ///
+    /// # Parameters
+    /// - `filter`: Filter selecting the listed tasks
+    /// - `callback`: Callback receiving the changes of the task list
+    ///
+    /// # Returns
+    /// A handle ending the subscription on success, or an error code on failure
+    pub fn subscribe_task_list<F>(
+        &self,
+        filter: SearchFilter,
+        callback: F,
+    ) -> Result<TaskListSubscription, i32>
+    where
+        F: Fn(&TaskListDelta) + Send + Sync + 'static,
+    {
+        static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        // Register first, the initial delta may arrive before the reply.
+        self.listener.register_task_list(id, Arc::new(callback));
+        if let Err(code) = self.proxy.subscribe_task_list(&filter, id) {
+            self.listener.unregister_task_list(id);
+            return Err(code);
+        }
+        Ok(TaskListSubscription { id })
+    }
+
+    /// Revokes permission tokens of tasks the service no longer runs.
+    ///
+    /// Tasks the service reports as not found are forgotten, while tasks that
+    /// ended in a terminal state only lose their grants. Any other error, such
+    /// as a dead service, says nothing about the task, so it is kept for the
+    /// next sweep.
+    ///
+    /// # Returns
+    /// The number of tasks whose tokens were revoked
+    pub fn sweep_permissions(&self) -> usize {
+        self.task_manager.sweep(|task_id| match self.show_task(task_id) {
+            Ok(info) => {
+                let state = State::from(info.progress.common_data.state as u32);
+                if matches!(
+                    state,
+                    State::Completed | State::Failed | State::Removed | State::Stopped
+                ) {
+                    self.task_manager.revoke(&task_id);
+                }
+                true
+            }
+            Err(TASK_NOT_FOUND) => false,
+            Err(_) => true,
+        })
+    }
+
     pub fn touch(&self, task_id: i64, token: String) -> Result<TaskInfo, i32> {
         self.proxy.touch(task_id, token)
     }
 
+    /// Issues a signed token sharing a task of the caller with another
+    /// process.
+    ///
+    /// The token may be passed in place of the task token to [`touch`] with
+    /// any scope, and to [`get_task`] with [`TokenScope::Full`], by processes
+    /// other than the owner of the task. It is rejected once expired, when
+    /// altered, or a day after the service restarted.
+    ///
+    /// [`touch`]: RequestClient::touch
+    /// [`get_task`]: RequestClient::get_task
+    ///
+    /// # Parameters
+    /// - `task_id`: Task to share, owned by the caller
+    /// - `scope`: Operations the token grants
+    /// - `ttl_ms`: Lifetime of the token in milliseconds, 0 for an hour and
+    ///   at most a week
+    ///
+    /// # Returns
+    /// The token on success, or an error code on failure
+    pub fn issue_task_token(
+        &self,
+        task_id: i64,
+        scope: TokenScope,
+        ttl_ms: u64,
+    ) -> Result<String, i32> {
+        self.proxy.issue_task_token(task_id, scope, ttl_ms)
+    }
+
     pub fn query(&self, task_id: i64) -> Result<TaskInfo, i32> {
         self.proxy.query(task_id)
     }
 
-    pub fn create_group(&self, gauge: Option<bool>, title: Option<String>, text: Option<String>, disable: Option<bool>) -> Result<String, i32> {
-        self.proxy.create_group(gauge, title, text, disable)
+    /// Creates a notification group that tasks can be attached to.
+    ///
+    /// # Parameters
+    /// - `gauge`: Whether the group notification shows the progress
+    /// - `title`: Customized title of the group notification
+    /// - `text`: Customized text of the group notification
+    /// - `disable`: Whether the group notification is disabled
+    /// - `progress_style`: How the gauge renders the group progress, determinate
+    ///   when `None`
+    ///
+    /// # Returns
+    /// The ID of the new group on success, or an error code on failure
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use request_client::RequestClient;
+    /// use request_core::config::{TaskConfigBuilder, Version};
+    /// use request_core::error_code::GROUP_NOT_FOUND;
+    ///
+    /// # let first = request_client::add_task(TaskConfigBuilder::new(Version::API10).build());
+    /// # let second = request_client::add_task(TaskConfigBuilder::new(Version::API10).build());
+    /// let client = RequestClient::get_instance();
+    /// let title = Some("Photos".to_string());
+    /// let group_id = client.create_group(Some(true), title, None, None, None).unwrap();
+    ///
+    /// let members = vec![first.to_string(), second.to_string()];
+    /// client.attach_group(group_id.clone(), members).unwrap();
+    /// client.start(first).unwrap();
+    ///
+    /// let info = client.query_group(group_id.clone()).unwrap();
+    /// assert_eq!(info.member_task_ids.len(), 2);
+    /// assert_eq!(info.counts_by_state.running, 1);
+    ///
+    /// // Stop the running member, the other one cannot be stopped
+    /// let results = client.cancel_group(group_id.clone(), false).unwrap();
+    /// assert_eq!(results[0], (first.to_string(), 0));
+    /// assert_ne!(results[1].1, 0);
+    ///
+    /// client.delete_group(group_id.clone()).unwrap();
+    /// assert_eq!(client.query_group(group_id).err(), Some(GROUP_NOT_FOUND));
+    /// ```
+    pub fn create_group(
+        &self,
+        gauge: Option<bool>,
+        title: Option<String>,
+        text: Option<String>,
+        disable: Option<bool>,
+        progress_style: Option<GroupProgressStyle>,
+    ) -> Result<String, i32> {
+        self.proxy.create_group(gauge, title, text, disable, progress_style)
     }
 
+    /// Attaches tasks to a notification group.
+    ///
+    /// # Parameters
+    /// - `group_id`: ID of the group
+    /// - `task_ids`: IDs of the tasks to attach
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error code on failure
     pub fn attach_group(&self, group_id: String, task_ids: Vec<String>) -> Result<(), i32> {
         self.proxy.attach_group(group_id, task_ids)
     }
 
+    /// Deletes a notification group, no more tasks can be attached to it.
+    ///
+    /// # Parameters
+    /// - `group_id`: ID of the group
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error code on failure
     pub fn delete_group(&self, group_id: String) -> Result<(), i32> {
         self.proxy.delete_group(group_id)
     }
+
+    /// Retrieves the members and aggregate progress of a notification group.
+    ///
+    /// # Parameters
+    /// - `group_id`: ID of the group
+    ///
+    /// # Returns
+    /// The group information on success, or an error code on failure
+    pub fn query_group(&self, group_id: String) -> Result<GroupInfo, i32> {
+        self.proxy.query_group(group_id)
+    }
+
+    /// Stops all tasks of a notification group, or removes them if
+    /// `remove_members` is set.
+    ///
+    /// # Parameters
+    /// - `group_id`: ID of the group
+    /// - `remove_members`: Whether the members are removed instead of stopped
+    ///
+    /// # Returns
+    /// The result code of each member on success, or an error code on failure
+    pub fn cancel_group(
+        &self,
+        group_id: String,
+        remove_members: bool,
+    ) -> Result<Vec<(String, i32)>, i32> {
+        self.proxy.cancel_group(group_id, remove_members)
+    }
+}
+
+/// Task a creation resulted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CreatedTask {
+    /// ID of the task.
+    pub task_id: i64,
+    /// Whether the service returned an active duplicate instead of creating
+    /// a task.
+    pub duplicate: bool,
+}
+
+/// Handle of a task list subscription created by
+/// `RequestClient::subscribe_task_list`.
+pub struct TaskListSubscription {
+    id: u32,
+}
+
+impl TaskListSubscription {
+    /// Ends the subscription, no further changes are delivered.
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error code on failure
+    pub fn unsubscribe(self) -> Result<(), i32> {
+        let client = RequestClient::get_instance();
+        client.listener.unregister_task_list(self.id);
+        client.proxy.unsubscribe_task_list(self.id)
+    }
 }
@@ -11,9 +11,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::file::PermissionToken;
+use crate::file::{CertsDir, PermissionToken};
 use request_core::config::TaskConfig;
-use std::collections::HashMap;
+use request_core::filter::SearchFilter;
+use request_core::info::State;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
 use std::sync::Mutex;
 use std::sync::Arc;
 
@@ -30,7 +34,61 @@ pub(crate) struct NativeTaskManagerInner {
 
 pub struct NativeTask {
     pub config: TaskConfig,
-    pub token: Vec<PermissionToken>,
+    pub token: Mutex<Vec<PermissionToken>>,
+    /// Certificate directory of the task, removed once no task uses it.
+    pub certs: Mutex<Option<CertsDir>>,
+    /// Body files created for the task, deleted on drop unless the task was
+    /// bound to a task ID and the service took them over.
+    pub body_files: Mutex<Vec<PathBuf>>,
+}
+
+/// A task listed by [`RequestClient::list_all`](crate::RequestClient::list_all).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListedTask {
+    /// Task ID assigned by the service, `None` while the task is pending.
+    pub task_id: Option<String>,
+    /// Sequence number of a pending task, `None` once it is bound.
+    pub seq: Option<u64>,
+    /// Whether the task is still being constructed.
+    pub pending: bool,
+}
+
+impl NativeTask {
+    /// Returns whether a task being constructed matches `filter`.
+    ///
+    /// A pending task counts as initialized; the bundle and time criteria
+    /// are left to the service.
+    pub fn matches(&self, filter: &SearchFilter) -> bool {
+        let common = &self.config.common_data;
+        let state = filter
+            .state
+            .as_ref()
+            .map_or(true, |s| matches!(s, State::Initialized));
+        let action = filter.action.as_ref().map_or(true, |a| *a == common.action);
+        let mode = filter.mode.map_or(true, |m| m == common.mode);
+        state && action && mode
+    }
+
+    /// Drops every permission token held by the task, revoking its grants,
+    /// and releases its certificate directory.
+    pub fn revoke_tokens(&self) {
+        let tokens = std::mem::take(&mut *self.token.lock().unwrap());
+        if !tokens.is_empty() {
+            debug!("revoke {} permission tokens", tokens.len());
+        }
+        drop(tokens);
+        self.certs.lock().unwrap().take();
+    }
+}
+
+impl Drop for NativeTask {
+    fn drop(&mut self) {
+        for path in self.body_files.get_mut().unwrap().drain(..) {
+            if let Err(e) = fs::remove_file(&path) {
+                error!("Failed to remove body file {:?}: {}", path, e);
+            }
+        }
+    }
 }
 
 impl NativeTaskManager {
@@ -43,7 +101,11 @@ impl NativeTaskManager {
     }
 
     pub fn bind(&self, task_id: i64, seq: u64) {
-        self.inner.lock().unwrap().tids.insert(task_id, seq);
+        let mut task_map = self.inner.lock().unwrap();
+        if let Some(task) = task_map.tasks.get(&seq) {
+            task.body_files.lock().unwrap().clear();
+        }
+        task_map.tids.insert(task_id, seq);
     }
 
     pub fn remove_task(&self, task_id: &i64) {
@@ -53,6 +115,92 @@ impl NativeTaskManager {
         }
     }
 
+    /// Revokes the permission tokens of a task that reached a terminal state.
+    ///
+    /// The task config is kept, since notifications may still arrive for it.
+    pub fn revoke(&self, task_id: &i64) {
+        if let Some(task) = self.get_by_id(task_id) {
+            task.revoke_tokens();
+        }
+    }
+
+    /// Revokes tokens of every bound task for which `alive` returns `false`
+    /// and forgets those tasks.
+    ///
+    /// Returns the number of tasks swept.
+    pub fn sweep<F: Fn(i64) -> bool>(&self, alive: F) -> usize {
+        let task_ids: Vec<i64> = self.inner.lock().unwrap().tids.keys().copied().collect();
+        let mut count = 0;
+        for task_id in task_ids {
+            if alive(task_id) {
+                continue;
+            }
+            self.revoke(&task_id);
+            self.remove_task(&task_id);
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns the tasks inserted but not yet bound to a task ID, ordered
+    /// by sequence number.
+    pub fn pending(&self) -> Vec<(u64, Arc<NativeTask>)> {
+        let task_map = self.inner.lock().unwrap();
+        let bound: HashSet<&u64> = task_map.tids.values().collect();
+        let mut pending: Vec<(u64, Arc<NativeTask>)> = task_map
+            .tasks
+            .iter()
+            .filter(|(seq, _)| !bound.contains(seq))
+            .map(|(seq, task)| (*seq, task.clone()))
+            .collect();
+        pending.sort_by_key(|(seq, _)| *seq);
+        pending
+    }
+
+    /// Merges service search results with the tasks that were pending
+    /// before the search.
+    ///
+    /// A task bound since `pending` was taken is listed once by its task ID,
+    /// whether or not the search already saw it, and a task whose creation
+    /// failed in the meantime is dropped.
+    pub fn merge_pending(&self, pending: Vec<u64>, task_ids: Vec<String>) -> Vec<ListedTask> {
+        let task_map = self.inner.lock().unwrap();
+        let bound: HashMap<u64, i64> = task_map
+            .tids
+            .iter()
+            .map(|(tid, seq)| (*seq, *tid))
+            .collect();
+        let mut listed: Vec<ListedTask> = task_ids
+            .iter()
+            .map(|task_id| ListedTask {
+                task_id: Some(task_id.clone()),
+                seq: None,
+                pending: false,
+            })
+            .collect();
+        for seq in pending {
+            match bound.get(&seq) {
+                Some(tid) => {
+                    let task_id = tid.to_string();
+                    if !task_ids.contains(&task_id) {
+                        listed.push(ListedTask {
+                            task_id: Some(task_id),
+                            seq: None,
+                            pending: false,
+                        });
+                    }
+                }
+                None if task_map.tasks.contains_key(&seq) => listed.push(ListedTask {
+                    task_id: None,
+                    seq: Some(seq),
+                    pending: true,
+                }),
+                None => {}
+            }
+        }
+        listed
+    }
+
     pub fn get_by_seq(&self, seq: &u64) -> Option<Arc<NativeTask>> {
         self.inner.lock().unwrap().tasks.get(seq).cloned()
     }
@@ -66,3 +214,8 @@ impl NativeTaskManager {
         }
     }
 }
+
+#[cfg(test)]
+mod ut_native_task {
+    include!("../../tests/ut/client/ut_native_task.rs");
+}
@@ -0,0 +1,205 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reachability checks of hosts before creating tasks.
+//!
+//! A probe resolves the host of a URL and connects to it, it creates no task
+//! and leaves nothing behind. For `http` URLs it also sends a `HEAD` request
+//! over the connection and reports the status of the answer. `https` URLs are
+//! only connected to, the handshake is left to the tasks.
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use request_core::error_code::PARAMETER_CHECK;
+
+/// Outcome of a reachability probe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProbeOutcome {
+    /// A connection was established. `status` is the status of the answer to
+    /// the `HEAD` request, `None` if none was sent or no answer arrived.
+    Reachable { status: Option<u16> },
+    /// The host name could not be resolved.
+    DnsFailure,
+    /// Every resolved address refused the connection.
+    ConnectRefused,
+    /// No connection was established before the timeout.
+    Timeout,
+    /// The connection failed for another reason, such as no route to host.
+    ConnectFailed,
+}
+
+/// Result of a reachability probe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProbeResult {
+    /// What the probe found.
+    pub outcome: ProbeOutcome,
+    /// Time the probe took.
+    pub latency: Duration,
+    /// Address connected to, or the last one tried if none accepted. `None`
+    /// if the host name could not be resolved.
+    pub address: Option<SocketAddr>,
+}
+
+impl ProbeResult {
+    /// Whether a connection to the host was established.
+    pub fn reachable(&self) -> bool {
+        matches!(self.outcome, ProbeOutcome::Reachable { .. })
+    }
+}
+
+/// Target of a probe parsed from its URL.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Target {
+    pub(crate) http: bool,
+    pub(crate) host: String,
+    pub(crate) port: u16,
+    pub(crate) path: String,
+}
+
+/// Splits an `http` or `https` URL into the parts a probe needs.
+pub(crate) fn parse_target(url: &str) -> Option<Target> {
+    let (scheme, rest) = url.split_once("://")?;
+    let http = match scheme.to_ascii_lowercase().as_str() {
+        "http" => true,
+        "https" => false,
+        _ => return None,
+    };
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let authority = &rest[..end];
+    let path = match &rest[end..] {
+        "" => "/".to_string(),
+        path if path.starts_with('/') => path.split('#').next().unwrap_or("/").to_string(),
+        query => format!("/{}", query.split('#').next().unwrap_or_default()),
+    };
+    // User info is not sent with a probe.
+    let authority = match authority.rsplit_once('@') {
+        Some((_, host)) => host,
+        None => authority,
+    };
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(v6) => {
+            let (host, rest) = v6.split_once(']')?;
+            (host, rest.strip_prefix(':'))
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+    if host.is_empty() {
+        return None;
+    }
+    let port = match port {
+        Some(port) => port.parse::<u16>().ok()?,
+        None if http => 80,
+        None => 443,
+    };
+    Some(Target {
+        http,
+        host: host.to_string(),
+        port,
+        path,
+    })
+}
+
+/// Probes the host of `url`, giving up after `timeout`.
+///
+/// # Errors
+///
+/// Returns `PARAMETER_CHECK` if `url` is not an `http` or `https` URL or
+/// `timeout` is zero.
+pub(crate) fn probe(url: &str, timeout: Duration) -> Result<ProbeResult, i32> {
+    let Some(target) = parse_target(url) else {
+        return Err(PARAMETER_CHECK);
+    };
+    if timeout.is_zero() {
+        return Err(PARAMETER_CHECK);
+    }
+    let start = Instant::now();
+    let result = |outcome, address| ProbeResult {
+        outcome,
+        latency: start.elapsed(),
+        address,
+    };
+    let addresses = match (target.host.as_str(), target.port).to_socket_addrs() {
+        Ok(addresses) => addresses.collect::<Vec<_>>(),
+        Err(_) => return Ok(result(ProbeOutcome::DnsFailure, None)),
+    };
+    if addresses.is_empty() {
+        return Ok(result(ProbeOutcome::DnsFailure, None));
+    }
+    let mut outcome = ProbeOutcome::ConnectFailed;
+    let mut last = None;
+    for address in addresses {
+        let remaining = timeout.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            outcome = ProbeOutcome::Timeout;
+            break;
+        }
+        last = Some(address);
+        match TcpStream::connect_timeout(&address, remaining) {
+            Ok(mut stream) => {
+                let remaining = timeout.saturating_sub(start.elapsed());
+                let status = if target.http {
+                    head(&mut stream, &target, remaining)
+                } else {
+                    None
+                };
+                return Ok(result(ProbeOutcome::Reachable { status }, Some(address)));
+            }
+            Err(e) => {
+                outcome = match e.kind() {
+                    ErrorKind::ConnectionRefused => ProbeOutcome::ConnectRefused,
+                    ErrorKind::TimedOut | ErrorKind::WouldBlock => ProbeOutcome::Timeout,
+                    _ => ProbeOutcome::ConnectFailed,
+                };
+            }
+        }
+    }
+    Ok(result(outcome, last))
+}
+
+/// Sends a `HEAD` request over `stream` and returns the status of the answer.
+fn head(stream: &mut TcpStream, target: &Target, timeout: Duration) -> Option<u16> {
+    if timeout.is_zero() {
+        return None;
+    }
+    stream.set_read_timeout(Some(timeout)).ok()?;
+    stream.set_write_timeout(Some(timeout)).ok()?;
+    let host = if target.host.contains(':') {
+        format!("[{}]:{}", target.host, target.port)
+    } else {
+        format!("{}:{}", target.host, target.port)
+    };
+    let request = format!(
+        "HEAD {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        target.path, host
+    );
+    stream.write_all(request.as_bytes()).ok()?;
+    // The status line fits in the first read of any sane server.
+    let mut buf = [0; 64];
+    let size = stream.read(&mut buf).ok()?;
+    let line = std::str::from_utf8(&buf[..size]).ok()?;
+    let mut parts = line.split_whitespace();
+    if !parts.next()?.starts_with("HTTP/") {
+        return None;
+    }
+    parts.next()?.parse::<u16>().ok()
+}
+
+#[cfg(test)]
+mod ut_probe {
+    include!("../../tests/ut/client/ut_probe.rs");
+}
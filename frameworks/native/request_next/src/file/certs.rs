@@ -0,0 +1,223 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Certificate directories handed to the service.
+//!
+//! The trust anchors of a task are copied into their own subdirectory of the
+//! certs root, so the service only trusts the anchors configured for the
+//! task's host. Directories are content addressed: files are named by the
+//! SHA-256 of their content and a directory by the SHA-256 of its file set, so
+//! tasks with identical anchors share one directory and identical files are
+//! stored once per directory. A directory is removed once the last task
+//! using it releases its [`CertsDir`].
+
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use request_utils::hash::sha256_bytes;
+
+const E_FILE_IO: i32 = 13400001;
+
+/// Creates and reference counts the certificate directories of a certs root.
+///
+/// All directory changes happen under one in-process lock, so concurrent
+/// task creations never observe a partially copied directory.
+pub(crate) struct CertsStore {
+    root: PathBuf,
+    dirs: Mutex<HashMap<PathBuf, usize>>,
+}
+
+/// A certificate directory in use by a task, released on drop.
+pub struct CertsDir {
+    store: Arc<CertsStore>,
+    path: PathBuf,
+}
+
+impl CertsDir {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the certificate files of the directory.
+    pub(crate) fn files(&self) -> Vec<PathBuf> {
+        fs::read_dir(&self.path)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for CertsDir {
+    fn drop(&mut self) {
+        self.store.release(&self.path);
+    }
+}
+
+struct CertFile {
+    name: String,
+    content: Vec<u8>,
+}
+
+impl CertsStore {
+    pub(crate) fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            dirs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Copies the files of `folders` into a certificate directory.
+    ///
+    /// Returns `None` if `folders` is empty, or an error if one of them is
+    /// not a directory or copying fails.
+    pub(crate) fn acquire(self: &Arc<Self>, folders: &[String]) -> Result<Option<CertsDir>, i32> {
+        if folders.is_empty() {
+            return Ok(None);
+        }
+        let files = Self::read_folders(folders)?;
+        let mut names = Vec::new();
+        for file in files.iter() {
+            names.extend_from_slice(file.name.as_bytes());
+            names.push(0);
+        }
+        let dir_name = sha256_bytes(&names);
+
+        let mut dirs = self.dirs.lock().unwrap();
+        // A hash collision between different file sets gets a suffixed name.
+        let mut suffix = 0;
+        let path = loop {
+            let mut path = self.root.join(&dir_name);
+            if suffix != 0 {
+                path.set_file_name(format!("{}-{}", dir_name, suffix));
+            }
+            if !dirs.contains_key(&path) || Self::holds(&path, &files) {
+                break path;
+            }
+            suffix += 1;
+        };
+
+        match dirs.get_mut(&path) {
+            Some(count) => *count += 1,
+            None => {
+                Self::create(&path, &files)?;
+                dirs.insert(path.clone(), 1);
+            }
+        }
+        Ok(Some(CertsDir {
+            store: self.clone(),
+            path,
+        }))
+    }
+
+    fn release(&self, path: &Path) {
+        let mut dirs = self.dirs.lock().unwrap();
+        let Some(count) = dirs.get_mut(path) else {
+            return;
+        };
+        *count -= 1;
+        if *count == 0 {
+            dirs.remove(path);
+            if let Err(e) = fs::remove_dir_all(path) {
+                error!("Failed to remove certs directory {:?}: {}", path, e);
+            }
+        }
+    }
+
+    /// Reads the files of all folders, dropping files with identical content.
+    fn read_folders(folders: &[String]) -> Result<Vec<CertFile>, i32> {
+        let mut files = HashMap::new();
+        for folder in folders {
+            let folder = PathBuf::from(folder);
+            if !folder.is_dir() {
+                error!("bad certs_path");
+                return Err(E_FILE_IO);
+            }
+            let Ok(entries) = fs::read_dir(&folder) else {
+                continue;
+            };
+            for path in entries.flatten().map(|entry| entry.path()) {
+                let content = match fs::read(&path) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        error!("Failed to read cert file {:?}: {}", path, e);
+                        continue;
+                    }
+                };
+                let mut name = sha256_bytes(&content);
+                if let Some(extension) = path.extension() {
+                    name.push('.');
+                    name.push_str(&extension.to_string_lossy());
+                }
+                files
+                    .entry(name.clone())
+                    .or_insert(CertFile { name, content });
+            }
+        }
+        let mut files: Vec<CertFile> = files.into_values().collect();
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(files)
+    }
+
+    /// Returns whether the directory at `path` holds exactly `files`.
+    fn holds(path: &Path, files: &[CertFile]) -> bool {
+        let count = fs::read_dir(path)
+            .map(|entries| entries.count())
+            .unwrap_or(0);
+        count == files.len()
+            && files
+                .iter()
+                .all(|file| fs::read(path.join(&file.name)).is_ok_and(|c| c == file.content))
+    }
+
+    /// Writes `files` into a temporary directory moved to `path` once
+    /// complete, replacing leftovers of an earlier process.
+    fn create(path: &Path, files: &[CertFile]) -> Result<(), i32> {
+        let mut tmp = path.to_path_buf();
+        tmp.set_file_name(format!(
+            ".tmp-{}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        for dir in [tmp.as_path(), path] {
+            if dir.exists() {
+                let _ = fs::remove_dir_all(dir);
+            }
+        }
+        fs::create_dir_all(&tmp).map_err(|e| {
+            error!("Failed to create directory {:?}: {}", tmp, e);
+            E_FILE_IO
+        })?;
+        for file in files {
+            let file_path = tmp.join(&file.name);
+            if let Err(e) = fs::write(&file_path, &file.content) {
+                error!("Failed to copy cert file to {:?}: {}", file_path, e);
+                let _ = fs::remove_dir_all(&tmp);
+                return Err(E_FILE_IO);
+            }
+            if let Err(e) = fs::set_permissions(&file_path, fs::Permissions::from_mode(0o755)) {
+                error!("Failed to set permissions for {:?}: {}", file_path, e);
+            }
+        }
+        fs::rename(&tmp, path).map_err(|e| {
+            error!("Failed to move certs directory to {:?}: {}", path, e);
+            let _ = fs::remove_dir_all(&tmp);
+            E_FILE_IO
+        })
+    }
+}
+
+#[cfg(test)]
+mod ut_certs {
+    include!("../../tests/ut/file/ut_certs.rs");
+}
@@ -11,6 +11,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod certs;
 mod permission;
 
 use cxx::let_cxx_string;
@@ -26,9 +27,12 @@ use std::{
     collections::HashMap,
     fs::{self, File, OpenOptions},
     path::PathBuf,
-    sync::{Mutex, OnceLock},
+    sync::{Arc, Mutex, OnceLock},
 };
+pub use certs::CertsDir;
+use certs::CertsStore;
 pub use permission::{PermissionManager, PermissionToken};
+pub(crate) use permission::Granter;
 
 const DOCS_PREFIX: &str = "file://docs/";
 const MEDIA_PREFIX: &str = "file://media/";
@@ -45,6 +49,7 @@ const CERTS_PATH: &str = "/data/storage/el2/base/.ohos/.request/.certs";
 
 pub struct FileManager {
     pub permission_manager: PermissionManager,
+    certs: Arc<CertsStore>,
 }
 
 impl FileManager {
@@ -52,11 +57,19 @@ impl FileManager {
         static INSTANCE: OnceLock<FileManager> = OnceLock::new();
         INSTANCE.get_or_init(|| FileManager {
             permission_manager: PermissionManager::new(),
+            certs: Arc::new(CertsStore::new(CERTS_PATH)),
         })
     }
 
-    pub fn apply(&self, context: Context, config: &mut TaskConfig) -> Result<Vec<PermissionToken>, i32> {
-        let mut tokens = if matches!(config.common_data.action, Action::Download) {
+    pub fn apply(
+        &self,
+        context: Context,
+        config: &mut TaskConfig,
+    ) -> Result<(Vec<PermissionToken>, Option<CertsDir>), i32> {
+        let mut tokens = if matches!(
+            config.common_data.action,
+            Action::Download | Action::LocalLoopback
+        ) {
             let mut tokens = vec![];
             if let Some(token) = self.apply_download_path(config, &context)? {
                 tokens.push(token);
@@ -68,8 +81,8 @@ impl FileManager {
         Self::get_cert_path(config);
         // test
         Self::get_certificate_pins(config);
-        self.apply_cert_path(&mut config.certs_path, &mut tokens)?;
-        Ok(tokens)
+        let certs = self.apply_cert_path(&mut config.certs_path, &mut tokens)?;
+        Ok((tokens, certs))
     }
 
     fn apply_download_path(
@@ -203,60 +216,25 @@ impl FileManager {
         Ok(tokens)
     }
 
-    // todo: check
+    /// Copies the trust anchors of the task into its own certs directory and
+    /// points `certs_path` at it.
+    ///
+    /// The directory is removed once the returned `CertsDir` and those of all
+    /// tasks sharing it are dropped.
     fn apply_cert_path(
         &self,
         certs_path: &mut Vec<String>,
         tokens: &mut Vec<PermissionToken>,
-    ) -> Result<(), i32> {
-        let new_path = PathBuf::from(CERTS_PATH);
-        if !new_path.exists() {
-            fs::create_dir_all(new_path.as_path()).map_err(|e| {
-                error!("Failed to create directory {:?}: {}", new_path, e);
-                13400001
-            })?;
-        }
-
-        for folder_path in certs_path.as_slice() {
-            let folder = PathBuf::from(folder_path);
-            if !folder.exists() || !folder.is_dir() {
-                error!("bad certs_path");
-                return Err(13400001);
-            }
-
-            if let Ok(entries) = fs::read_dir(&folder) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    let exist_file_path = folder.join(path.file_name().unwrap_or_default());
-                    let new_file_path = new_path.join(path.file_name().unwrap_or_default());
-
-                    if !new_file_path.exists() {
-                        if let Err(e) = fs::copy(&exist_file_path, &new_file_path) {
-                            error!(
-                                "Failed to copy file from {:?} to {:?}: {}",
-                                exist_file_path, new_file_path, e
-                            );
-                            continue;
-                        }
-                    }
-
-                    if let Err(e) =
-                        fs::set_permissions(&new_file_path, fs::Permissions::from_mode(0o755))
-                    {
-                        error!("Failed to set permissions for {:?}: {}", new_file_path, e);
-                    }
-
-                    tokens.push(self.permission_manager.grant(&new_file_path)?);
-                }
-            }
-        }
-
-        if !certs_path.is_empty() {
-            certs_path.clear();
-            certs_path.push(CERTS_PATH.to_string());
+    ) -> Result<Option<CertsDir>, i32> {
+        let Some(certs) = self.certs.acquire(certs_path)? else {
+            return Ok(None);
+        };
+        for file in certs.files() {
+            tokens.push(self.permission_manager.grant(&file)?);
         }
-
-        Ok(())
+        certs_path.clear();
+        certs_path.push(certs.path().to_string_lossy().to_string());
+        Ok(Some(certs))
     }
 
     fn check_download_user_file(config: &mut TaskConfig) -> Result<(), i32> {
@@ -266,7 +244,10 @@ impl FileManager {
         if matches!(config.common_data.mode, Mode::BackGround) {
             return Err(401);
         }
-        if matches!(config.common_data.action, Action::Download) {
+        if matches!(
+            config.common_data.action,
+            Action::Download | Action::LocalLoopback
+        ) {
             if !config.overwrite {
                 return Err(401);
             }
@@ -12,9 +12,10 @@
 // limitations under the License.
 
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock};
 
 use request_utils::storage;
 
@@ -25,26 +26,57 @@ const SA_PERMISSION_RWX: &str = "g:3815:rwx";
 const SA_PERMISSION_X: &str = "g:3815:x";
 const SA_PERMISSION_CLEAN: &str = "g:3815:---";
 
+/// Default largest number of tokens held at once. Tokens are revoked with
+/// their tasks, so reaching it means some were never revoked.
+const MAX_TOKENS: usize = 1024;
+
+/// Identifies tokens across all managers.
+static NEXT_TOKEN_ID: AtomicU64 = AtomicU64::new(0);
 
 pub struct PermissionManager {
     paths: Mutex<HashMap<String, i32>>,
-    granter: Box<dyn Granter>,
+    /// Live tokens by ID with their paths, least recently used first.
+    tokens: Mutex<VecDeque<(u64, PathBuf)>>,
+    max_tokens: AtomicUsize,
+    granter: RwLock<Box<dyn Granter>>,
 }
 
 impl PermissionManager {
     pub(crate) fn new() -> Self {
         Self {
             paths: Mutex::new(HashMap::new()),
-            granter: Box::new(AclGranter {}),
+            tokens: Mutex::new(VecDeque::new()),
+            max_tokens: AtomicUsize::new(MAX_TOKENS),
+            granter: RwLock::new(Box::new(AclGranter {})),
         }
     }
 
-    pub(crate) fn set_granter(&mut self, granter: Box<dyn Granter>) {
-        self.granter = granter;
+    pub(crate) fn set_granter(&self, granter: Box<dyn Granter>) {
+        *self.granter.write().unwrap() = granter;
+    }
+
+    /// Sets the largest number of tokens held at once, the least recently
+    /// used ones beyond it are revoked on the next grant.
+    pub(crate) fn set_max_tokens(&self, max_tokens: usize) {
+        self.max_tokens.store(max_tokens, Ordering::Relaxed);
+    }
+
+    /// Returns the number of tokens currently held.
+    pub fn token_count(&self) -> usize {
+        self.tokens.lock().unwrap().len()
+    }
+
+    /// Returns whether `path` still holds a grant issued by this manager.
+    pub(crate) fn is_granted(&self, path: &PathBuf) -> bool {
+        self.paths
+            .lock()
+            .unwrap()
+            .contains_key(&path.to_string_lossy().to_string())
     }
 
     pub(crate) fn grant(&self, path: &PathBuf) -> Result<PermissionToken, i32> {
         let mut paths = self.paths.lock().unwrap();
+        let granter = self.granter.read().unwrap();
         let mut path_clone = path.clone();
         let mut completed_path: Vec<String> = vec![];
 
@@ -55,7 +87,7 @@ impl PermissionManager {
             debug!("Current path: {:?}", path_clone);
             let temp_path = path_clone.to_string_lossy().to_string();
 
-            if let Err(e) = self.granter.grant(&temp_path, SA_PERMISSION_X) {
+            if let Err(e) = granter.grant(&temp_path, SA_PERMISSION_X) {
                 // for path in &completed_path {
                 //     if let Some(count) = paths.get_mut(path) {
                 //         *count -= 1;
@@ -83,7 +115,7 @@ impl PermissionManager {
 
         debug!("Setting ACL access for path: {:?}", path);
         if let Err(e) =
-            self.granter.grant(&path.to_string_lossy().to_string(), SA_PERMISSION_RWX)
+            granter.grant(&path.to_string_lossy().to_string(), SA_PERMISSION_RWX)
         {
             error!("grant file: {}, error: {}", path.to_string_lossy().to_string(), e);
             return Err(13400001);
@@ -96,11 +128,60 @@ impl PermissionManager {
                 entry.insert(1);
             }
         }
-        Ok(PermissionToken::new(path.clone()))
+        drop(granter);
+        drop(paths);
+
+        let id = NEXT_TOKEN_ID.fetch_add(1, Ordering::Relaxed);
+        for (evicted, evicted_path) in self.track(id, path) {
+            warn!(
+                "token cap reached, revoke token {} of path: {:?}",
+                evicted, evicted_path
+            );
+            self.revoke_path(&evicted_path);
+        }
+        Ok(PermissionToken::new(path.clone(), id))
     }
 
-    pub(crate) fn revoke(&self, path: &PathBuf) {
+    /// Adds a token as the most recently used one, along with the other
+    /// tokens of its path, and returns the tokens evicted beyond the cap.
+    fn track(&self, id: u64, path: &PathBuf) -> Vec<(u64, PathBuf)> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let mut used = vec![];
+        tokens.retain(|token| {
+            if token.1 == *path {
+                used.push(token.clone());
+                return false;
+            }
+            true
+        });
+        tokens.extend(used);
+        tokens.push_back((id, path.clone()));
+
+        let max_tokens = self.max_tokens.load(Ordering::Relaxed);
+        let mut evicted = vec![];
+        while tokens.len() > max_tokens {
+            evicted.extend(tokens.pop_front());
+        }
+        evicted
+    }
+
+    /// Revokes the grant of token `id`, unless it was already evicted.
+    pub(crate) fn revoke(&self, id: u64, path: &PathBuf) {
+        {
+            let mut tokens = self.tokens.lock().unwrap();
+            match tokens.iter().position(|(token, _)| *token == id) {
+                Some(index) => {
+                    tokens.remove(index);
+                }
+                None => return,
+            }
+        }
+        self.revoke_path(path);
+    }
+
+    fn revoke_path(&self, path: &PathBuf) {
         let mut paths = self.paths.lock().unwrap();
+        let granter = self.granter.read().unwrap();
         let mut path_clone = path.clone();
         while true {
             let temp_path = path_clone.to_string_lossy().to_string();
@@ -108,7 +189,7 @@ impl PermissionManager {
                 *count -= 1;
                 if *count == 0 {
                     info!("revoke, path: {}", temp_path);
-                    self.granter.grant(&temp_path, SA_PERMISSION_CLEAN);
+                    granter.grant(&temp_path, SA_PERMISSION_CLEAN);
                     paths.remove(&temp_path);
                 }
             }
@@ -133,16 +214,24 @@ impl Granter for AclGranter {
 
 pub struct PermissionToken {
     path: PathBuf,
+    id: u64,
 }
 
 impl PermissionToken {
-    pub fn new(path: PathBuf) -> Self {
-        Self { path }
+    pub fn new(path: PathBuf, id: u64) -> Self {
+        Self { path, id }
     }
 }
 
 impl Drop for PermissionToken {
     fn drop(&mut self) {
-        FileManager::get_instance().permission_manager.revoke(&self.path);
+        FileManager::get_instance()
+            .permission_manager
+            .revoke(self.id, &self.path);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod ut_permission {
+    include!("../../tests/ut/file/ut_permission.rs");
+}
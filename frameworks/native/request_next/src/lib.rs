@@ -16,6 +16,10 @@
 //! The `request_next` crate provides a native Rust interface for interacting with the
 //! download/upload service, enabling efficient task management, state observation,
 //! and proxy communication.
+//!
+//! The examples run against an in-memory service when the crate is built with
+//! the `doc-test-stubs` feature:
+//! `cargo test --doc -p request_client --features doc-test-stubs`.
 
 #![feature(lazy_cell)]
 
@@ -34,11 +38,16 @@ mod proxy;
 /// Re-export of the main client interface.
 pub use client::RequestClient;
 
+/// Adds a task to the in-memory service of the `doc-test-stubs` feature.
+#[cfg(feature = "doc-test-stubs")]
+#[doc(hidden)]
+pub use proxy::add_task;
+
 /// Callback and observation functionality for tracking request state changes.
 mod listen;
 
 /// Re-export of the callback trait for request state monitoring.
-pub use listen::Callback;
+pub use listen::{Callback, CallbackRegistration};
 
 // Import utility macros
 #[macro_use]
@@ -15,4 +15,4 @@ mod observe;
 mod ser;
 mod uds;
 
-pub use observe::{Callback, Observer};
+pub use observe::{Callback, CallbackRegistration, Observer, TaskListCallback};
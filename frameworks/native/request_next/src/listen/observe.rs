@@ -21,17 +21,19 @@
 // Standard library imports
 use std::collections::HashMap;
 use std::fs::File;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 
 // External dependencies
 use request_core::config::{Action, Version};
-use request_core::info::{Faults, Progress, Response, SubscribeType, TaskState, NotifyData};
+use request_core::info::{
+    Faults, NotifyData, Progress, Response, SubscribeType, TaskInfo, TaskListDelta, TaskState,
+};
 use ylong_runtime::task::JoinHandle;
 use crate::client::RequestClient;
 use crate::file::FileManager;
 
 // Internal dependencies
-use crate::listen::uds::{Message, UdsListener};
+use crate::listen::uds::{InlineBody, Message, ResponseBody, UdsListener};
 
 /// Manages callbacks and dispatches task events to registered observers.
 ///
@@ -40,11 +42,111 @@ use crate::listen::uds::{Message, UdsListener};
 /// they are dispatched to the appropriate callback based on the task ID and event type.
 pub struct Observer {
     /// Registry mapping task IDs to their corresponding callback implementations
-    callbacks: Arc<Mutex<HashMap<i64, Arc<dyn Callback + Send + Sync + 'static>>>>,
+    callbacks: Arc<Mutex<CallbackMap>>,
+    /// Registry mapping task list subscription IDs to their callbacks
+    task_lists: Arc<Mutex<HashMap<u32, TaskListCallback>>>,
     /// Handle to the background task listening for events
     listener: Mutex<Option<JoinHandle<()>>>,
 }
 
+/// Callback receiving the changes of a subscribed task list.
+pub type TaskListCallback = Arc<dyn Fn(&TaskListDelta) + Send + Sync + 'static>;
+
+/// Callback registered for a task, held strongly or weakly.
+enum Registered {
+    Strong(Arc<dyn Callback + Send + Sync + 'static>),
+    Weak(Weak<dyn Callback + Send + Sync + 'static>),
+}
+
+/// Registry of the callbacks of tasks.
+///
+/// Each registration gets an ID, so a `CallbackRegistration` only removes
+/// its own registration and not one that replaced it.
+#[derive(Default)]
+struct CallbackMap {
+    /// ID of the next registration
+    next_id: u64,
+    /// Registration ID and callback of each task
+    entries: HashMap<i64, (u64, Registered)>,
+}
+
+impl CallbackMap {
+    /// Registers the callback of a task, replacing any previous one.
+    ///
+    /// # Returns
+    /// The ID of the registration
+    fn insert(&mut self, task_id: i64, callback: Registered) -> u64 {
+        self.next_id += 1;
+        self.entries.insert(task_id, (self.next_id, callback));
+        self.next_id
+    }
+
+    /// Removes the callback of a task if it is still the given registration.
+    fn remove_registration(&mut self, task_id: i64, id: u64) {
+        if self
+            .entries
+            .get(&task_id)
+            .is_some_and(|(current, _)| *current == id)
+        {
+            self.entries.remove(&task_id);
+        }
+    }
+
+    /// Returns the callback of a task, pruning it if it was dropped.
+    fn get(&mut self, task_id: i64) -> Option<Arc<dyn Callback + Send + Sync + 'static>> {
+        let callback = match &self.entries.get(&task_id)?.1 {
+            Registered::Strong(callback) => Some(callback.clone()),
+            Registered::Weak(callback) => callback.upgrade(),
+        };
+        if callback.is_none() {
+            self.entries.remove(&task_id);
+        }
+        callback
+    }
+}
+
+/// Looks up the callback of a task for dispatching an event.
+///
+/// The registry is unlocked before the callback is invoked, so callbacks may
+/// register or unregister callbacks while handling an event. A callback
+/// unregistered during a dispatch still receives the event in flight.
+fn lookup(
+    callbacks: &Mutex<CallbackMap>,
+    task_id: i64,
+) -> Option<Arc<dyn Callback + Send + Sync + 'static>> {
+    callbacks.lock().unwrap().get(task_id)
+}
+
+/// Registration of a task callback, unregistering it when dropped.
+///
+/// Returned by `RequestClient::register_scoped_callback` and
+/// `RequestClient::register_weak_callback`, keep it as long as the events
+/// should be received, for example in the page that displays them.
+#[must_use = "the callback is unregistered when the registration is dropped"]
+pub struct CallbackRegistration {
+    task_id: i64,
+    id: u64,
+    callbacks: Weak<Mutex<CallbackMap>>,
+}
+
+impl CallbackRegistration {
+    /// Returns the ID of the task the callback is registered for.
+    pub fn task_id(&self) -> i64 {
+        self.task_id
+    }
+}
+
+impl Drop for CallbackRegistration {
+    fn drop(&mut self) {
+        if let Some(callbacks) = self.callbacks.upgrade() {
+            callbacks
+                .lock()
+                .unwrap()
+                .remove_registration(self.task_id, self.id);
+        }
+    }
+}
+
 /// Trait defining the interface for handling download task events.
 ///
 /// Implementations of this trait can receive notifications about various download
@@ -55,32 +157,34 @@ pub struct Observer {
 /// # Examples
 ///
 /// ```rust
-/// use request_core::info::{Progress, Response};
-/// use request_next::listen::observe::Callback;
 /// use std::sync::Arc;
 ///
+/// use request_client::Callback;
+/// use request_core::info::Progress;
+///
 /// // Custom callback implementation that logs download progress
 /// struct ProgressLogger;
 ///
 /// impl Callback for ProgressLogger {
 ///     fn on_progress(&self, progress: &Progress) {
-///         println!("Download progress: {} bytes downloaded of {} total",
-///                  progress.download_size, progress.total_size);
+///         let total: i64 = progress.sizes.iter().sum();
+///         println!("Download progress: {} of {} bytes", progress.total_processed, total);
 ///     }
 ///
 ///     fn on_completed(&self, progress: &Progress) {
-///         println!("Download completed: {} bytes downloaded", progress.download_size);
+///         println!("Download completed: {} bytes", progress.total_processed);
 ///     }
 ///
 ///     fn on_failed(&self, progress: &Progress, error_code: i32) {
-///         println!("Download failed with error code {} after {} bytes",
-///                  error_code, progress.download_size);
+///         println!(
+///             "Download failed with error code {} after {} bytes",
+///             error_code, progress.total_processed
+///         );
 ///     }
 /// }
 ///
-/// // Create and use the callback
-/// let callback = Arc::new(ProgressLogger);
-/// // observer.register_callback(task_id, callback); // Register with Observer
+/// // Register it with `RequestClient::register_callback`
+/// let callback: Arc<dyn Callback + Send + Sync> = Arc::new(ProgressLogger);
 /// ```
 pub trait Callback {
     /// Called when download progress is updated.
@@ -95,6 +199,17 @@ pub trait Callback {
     /// - `progress`: Final progress information with complete download details
     fn on_completed(&self, progress: &Progress) {}
 
+    /// Called when an upload completes and the server response body is available.
+    ///
+    /// Defaults to `on_completed`, so callbacks that ignore the body need no change.
+    ///
+    /// # Parameters
+    /// - `progress`: Final progress information
+    /// - `body`: Beginning of the response body, see `ResponseBody::truncated`
+    fn on_complete_with_response(&self, progress: &Progress, body: &ResponseBody) {
+        self.on_completed(progress)
+    }
+
     /// Called when a download fails.
     ///
     /// # Parameters
@@ -131,23 +246,26 @@ pub trait Callback {
     fn on_fault(&self, faults: Faults) {}
     fn on_complete_upload(&self, task_states: Vec<TaskState>) {}
     fn on_fail_upload(&self, task_states: Vec<TaskState>) {}
+
+    /// Called for each redirect followed by a task configured to report them,
+    /// in the order the redirects were followed.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task that followed the redirect
+    /// - `from_url`: URL that answered with the redirect
+    /// - `to_url`: URL the redirect pointed to
+    /// - `status`: Status code of the redirect response
+    fn on_redirect(&self, task_id: i64, from_url: &str, to_url: &str, status: u32) {}
 }
 
 impl Observer {
     /// Creates a new `Observer` instance.
     ///
     /// Initializes empty collections for callbacks and the listener handle.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use request_next::listen::observe::Observer;
-    ///
-    /// let observer = Observer::new();
-    /// ```
     pub fn new() -> Self {
         Observer {
-            callbacks: Arc::new(Mutex::new(HashMap::new())),
+            callbacks: Arc::new(Mutex::new(CallbackMap::default())),
+            task_lists: Arc::new(Mutex::new(HashMap::new())),
             listener: Mutex::new(None),
         }
     }
@@ -161,24 +279,12 @@ impl Observer {
     /// # Parameters
     /// - `file`: File descriptor connected to the download service's event stream
     ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use request_next::listen::observe::Observer;
-    /// use std::fs::File;
-    ///
-    /// // In a real application, this file would be obtained from a service connection
-    /// // let file = ...; // File descriptor connected to UDS socket
-    ///
-    /// // let observer = Observer::new();
-    /// // observer.set_listenr(file); // Start listening for events
-    /// ```
-    ///
     /// # Notes
     /// The function name contains a typo (`set_listenr` instead of `set_listener`).
     pub fn set_listenr(&self, file: File) {
         let mut listener = UdsListener::new(file);
         let callbacks = self.callbacks.clone();
+        let task_lists = self.task_lists.clone();
 
         // Spawn background task to process incoming messages
         let handle = ylong_runtime::spawn(async move {
@@ -188,26 +294,31 @@ impl Observer {
                         Message::HttpResponse(response) => {
                             // Convert task_id from string to i64 for lookup
                             let task_id = response.task_id.parse().unwrap();
-                            if let Some(callback) = callbacks.lock().unwrap().get(&task_id) {
+                            if let Some(callback) = lookup(&callbacks, task_id) {
                                 callback.on_response(&response);
                             }
                         }
-                        Message::NotifyData(data) => {
+                        Message::NotifyData(data, response_body) => {
                             let task_id = data.task_id as i64;
                             Observer::process_header_receive(data);
                             let mut progress = &data.progress;
 
                             // Find the appropriate callback for the task
-                            if let Some(callback) = callbacks.lock().unwrap().get(&task_id) {
+                            if let Some(callback) = lookup(&callbacks, task_id) {
                                 // Dispatch to the appropriate callback method based on event type
                                 match data.version {
                                     Version::API10 => match data.subscribe_type {
                                         SubscribeType::Progress => {
                                             callback.on_progress(&progress);
                                         }
-                                        SubscribeType::Completed => {
-                                            callback.on_completed(&progress);
-                                        }
+                                        SubscribeType::Completed => match response_body.take() {
+                                            Some(inline) => {
+                                                let body = Observer::complete_body(task_id, inline);
+                                                callback
+                                                    .on_complete_with_response(&progress, &body);
+                                            }
+                                            None => callback.on_completed(&progress),
+                                        },
                                         SubscribeType::Failed => {
                                             callback.on_failed(
                                                 &progress,
@@ -226,29 +337,31 @@ impl Observer {
                                         _ => {}
                                     },
                                     Version::API9 => match data.action {
-                                        Action::Download => match data.subscribe_type {
-                                            SubscribeType::Completed => {
-                                                callback.on_completed(&progress);
-                                            }
-                                            SubscribeType::Pause => {
-                                                callback.on_pause(&progress);
-                                            }
-                                            SubscribeType::Remove => {
-                                                callback.on_remove(&progress);
-                                            }
-                                            SubscribeType::Failed => {
-                                                callback.on_failed(
-                                                    &progress,
-                                                    data.task_states[0].response_code as i32,
-                                                );
-                                            }
-                                            SubscribeType::Progress => {
-                                                callback.on_progress(&progress);
+                                        Action::Download | Action::LocalLoopback => {
+                                            match data.subscribe_type {
+                                                SubscribeType::Completed => {
+                                                    callback.on_completed(&progress);
+                                                }
+                                                SubscribeType::Pause => {
+                                                    callback.on_pause(&progress);
+                                                }
+                                                SubscribeType::Remove => {
+                                                    callback.on_remove(&progress);
+                                                }
+                                                SubscribeType::Failed => {
+                                                    callback.on_failed(
+                                                        &progress,
+                                                        data.task_states[0].response_code as i32,
+                                                    );
+                                                }
+                                                SubscribeType::Progress => {
+                                                    callback.on_progress(&progress);
+                                                }
+                                                _ => {
+                                                    error!("bad subscribeType ");
+                                                }
                                             }
-                                            _ => {
-                                                error!("bad subscribeType ");
-                                            }
-                                        },
+                                        }
                                         Action::Upload => match data.subscribe_type {
                                             SubscribeType::Progress => {
                                                 callback.on_progress(&progress);
@@ -270,13 +383,39 @@ impl Observer {
                                 }
 
                             }
+                            // Grants are no longer needed once the task has ended.
+                            if matches!(
+                                data.subscribe_type,
+                                SubscribeType::Completed
+                                    | SubscribeType::Failed
+                                    | SubscribeType::Remove
+                            ) {
+                                RequestClient::get_instance().task_manager.revoke(&task_id);
+                            }
                         }
                         Message::Faults(faultOccur) => {
                             let task_id = faultOccur.task_id as i64;
-                            if let Some(callback) = callbacks.lock().unwrap().get(&task_id) {
+                            if let Some(callback) = lookup(&callbacks, task_id) {
                                 callback.on_fault(faultOccur.faults);
                             }
                         }
+                        Message::Redirect(redirect) => {
+                            let task_id = redirect.task_id as i64;
+                            if let Some(callback) = lookup(&callbacks, task_id) {
+                                callback.on_redirect(
+                                    task_id,
+                                    &redirect.from_url,
+                                    &redirect.to_url,
+                                    redirect.status,
+                                );
+                            }
+                        }
+                        Message::TaskListDelta(id, delta) => {
+                            let callback = task_lists.lock().unwrap().get(id).cloned();
+                            if let Some(callback) = callback {
+                                callback(delta);
+                            }
+                        }
                     },
                     Err(e) => error!("Error receiving message: {}", e),
                 }
@@ -297,35 +436,55 @@ impl Observer {
     /// # Parameters
     /// - `task_id`: ID of the task to monitor
     /// - `callback`: Callback implementation to receive events
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use request_core::info::{Progress, Response};
-    /// use request_next::listen::observe::{Callback, Observer};
-    /// use std::sync::Arc;
-    ///
-    /// struct SimpleCallback;
-    ///
-    /// impl Callback for SimpleCallback {
-    ///     fn on_progress(&self, progress: &Progress) {
-    ///         println!("Progress: {}/{} bytes", progress.download_size, progress.total_size);
-    ///     }
-    /// }
-    ///
-    /// let observer = Observer::new();
-    /// let task_id = 12345;
-    /// let callback = Arc::new(SimpleCallback);
-    ///
-    /// // Register the callback for the specific task
-    /// observer.register_callback(task_id, callback);
-    /// ```
     pub fn register_callback(
         &self,
         task_id: i64,
         callback: Arc<dyn Callback + Send + Sync + 'static>,
     ) {
-        self.callbacks.lock().unwrap().insert(task_id, callback);
+        self.callbacks
+            .lock()
+            .unwrap()
+            .insert(task_id, Registered::Strong(callback));
+    }
+
+    /// Registers a callback for a task, unregistering it when the returned
+    /// registration is dropped.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task to monitor
+    /// - `callback`: Callback implementation to receive events
+    pub fn register_scoped_callback(
+        &self,
+        task_id: i64,
+        callback: Arc<dyn Callback + Send + Sync + 'static>,
+    ) -> CallbackRegistration {
+        self.register(task_id, Registered::Strong(callback))
+    }
+
+    /// Registers a callback for a task without keeping it alive.
+    ///
+    /// Events are dispatched while the callback has another strong reference,
+    /// the registration is pruned at the first event after it was dropped and
+    /// removed when the returned registration is dropped.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task to monitor
+    /// - `callback`: Weak reference to the callback receiving events
+    pub fn register_weak_callback(
+        &self,
+        task_id: i64,
+        callback: Weak<dyn Callback + Send + Sync + 'static>,
+    ) -> CallbackRegistration {
+        self.register(task_id, Registered::Weak(callback))
+    }
+
+    fn register(&self, task_id: i64, callback: Registered) -> CallbackRegistration {
+        let id = self.callbacks.lock().unwrap().insert(task_id, callback);
+        CallbackRegistration {
+            task_id,
+            id,
+            callbacks: Arc::downgrade(&self.callbacks),
+        }
     }
 
     /// Unregisters a callback for a specific task.
@@ -335,20 +494,49 @@ impl Observer {
     ///
     /// # Parameters
     /// - `task_id`: ID of the task to stop monitoring
+    pub fn unregister_callback(&self, task_id: i64) {
+        self.callbacks.lock().unwrap().entries.remove(&task_id);
+    }
+
+    /// Registers a callback for a task list subscription.
     ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use request_next::listen::observe::Observer;
-    ///
-    /// let observer = Observer::new();
-    /// let task_id = 12345;
+    /// # Parameters
+    /// - `id`: ID of the subscription
+    /// - `callback`: Callback receiving the changes of the task list
+    pub fn register_task_list(&self, id: u32, callback: TaskListCallback) {
+        self.task_lists.lock().unwrap().insert(id, callback);
+    }
+
+    /// Unregisters the callback of a task list subscription.
     ///
-    /// // Unregister any callback associated with the task
-    /// observer.unregister_callback(task_id);
-    /// ```
-    pub fn unregister_callback(&self, task_id: i64) {
-        self.callbacks.lock().unwrap().remove(&task_id);
+    /// # Parameters
+    /// - `id`: ID of the subscription
+    pub fn unregister_task_list(&self, id: u32) {
+        self.task_lists.lock().unwrap().remove(&id);
+    }
+
+    /// Returns the whole response body of a completed upload.
+    ///
+    /// A completion message carries only the beginning of a long body, the
+    /// rest is fetched from the service. If that fails the beginning is
+    /// reported as truncated.
+    fn complete_body(task_id: i64, inline: InlineBody) -> ResponseBody {
+        if !inline.partial {
+            return inline.body;
+        }
+        match RequestClient::get_instance().show_task(task_id) {
+            Ok(TaskInfo {
+                response_body: Some(body),
+                ..
+            }) => body,
+            _ => {
+                error!("Task {} full response body unavailable", task_id);
+                ResponseBody {
+                    data: inline.body.data,
+                    truncated: true,
+                }
+            }
+        }
     }
 
     pub fn process_header_receive(notify_data: &mut NotifyData) {
@@ -376,3 +564,8 @@ impl Observer {
         // Waiting for "complete" to read and delete.
     }
 }
+
+#[cfg(test)]
+mod ut_observe {
+    include!("../../tests/ut/listen/ut_observe.rs");
+}
@@ -23,11 +23,14 @@ use std::collections::HashMap;
 use std::io::Read;
 
 // External dependencies
-use request_core::config::{Action, Version};
+use request_core::config::{Action, Mode, Version};
 use request_core::info::{
-    FaultOccur, Faults, NotifyData, Progress, Reason, Response, State, SubscribeType, TaskState,
+    FaultOccur, Faults, NotifyData, Progress, Reason, Response, State, SubscribeType, TaskBrief,
+    TaskListDelta, TaskState, HEADERS_TRUNCATED,
 };
 
+use crate::listen::uds::{InlineBody, Redirect, ResponseBody};
+
 /// Binary deserializer for Unix Domain Socket communications.
 ///
 /// Provides methods to read and deserialize various data types from a byte buffer.
@@ -48,16 +51,6 @@ impl UdsSer<'_> {
     ///
     /// # Returns
     /// A new `UdsSer` instance ready to deserialize data
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use request_next::listen::ser::UdsSer;
-    ///
-    /// let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]; // Example bytes
-    /// let mut serializer = UdsSer::new(&data);
-    /// // let value: i64 = serializer.read(); // Deserialize data
-    /// ```
     pub fn new(inner: &[u8]) -> UdsSer {
         UdsSer { inner }
     }
@@ -72,20 +65,31 @@ impl UdsSer<'_> {
     ///
     /// # Returns
     /// Deserialized value of type `S`
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use request_next::listen::ser::UdsSer;
-    ///
-    /// // Example with i32
-    /// let data = [0x01, 0x02, 0x03, 0x04];
-    /// let mut serializer = UdsSer::new(&data);
-    /// // let value: i32 = serializer.read(); // 67305985 in little-endian
-    /// ```
     pub fn read<S: Serialize>(&mut self) -> S {
         S::read(self)
     }
+
+    /// Returns `true` if every byte of the buffer has been consumed.
+    ///
+    /// Used to detect optional trailing sections that older services omit.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Reads a client tag, its length (u32) followed by its bytes.
+    ///
+    /// Returns an empty tag if the message ends before the tag, as messages
+    /// of older services do.
+    pub fn read_client_tag(&mut self) -> String {
+        if self.inner.len() < 4 {
+            return String::new();
+        }
+        let length: u32 = self.read();
+        let length = (length as usize).min(self.inner.len());
+        let tag = String::from_utf8_lossy(&self.inner[..length]).to_string();
+        self.inner = &self.inner[length..];
+        tag
+    }
 }
 
 /// Trait for types that can be deserialized from a `UdsSer` buffer.
@@ -155,6 +159,17 @@ impl Serialize for u32 {
     }
 }
 
+/// Deserializes a `u8` from the binary stream.
+///
+/// Reads exactly 1 byte.
+impl Serialize for u8 {
+    fn read(ser: &mut UdsSer) -> Self {
+        let byte = ser.inner[0];
+        ser.inner = &ser.inner[1..];
+        byte
+    }
+}
+
 /// Deserializes an `i16` from the binary stream.
 ///
 /// Reads exactly 2 bytes and interprets them as a little-endian i16 value.
@@ -187,6 +202,16 @@ impl Serialize for Action {
     }
 }
 
+/// Deserializes a `Mode` enum from the binary stream.
+///
+/// Reads a u32 value and converts it to the corresponding `Mode` variant.
+impl Serialize for Mode {
+    fn read(ser: &mut UdsSer) -> Self {
+        let mode: u32 = ser.read();
+        Mode::from(mode)
+    }
+}
+
 /// Deserializes a `Version` enum from the binary stream.
 ///
 /// Reads a u32 value and converts it to the corresponding `Version` variant.
@@ -213,10 +238,12 @@ impl Serialize for FaultOccur {
         let task_id = ser.read::<i32>();
         let subscribe_type = ser.read::<SubscribeType>();
         let faults: Faults = ser.read::<Reason>().into();
+        let client_tag = ser.read_client_tag();
         FaultOccur {
             task_id,
             subscribe_type,
             faults,
+            client_tag,
         }
     }
 }
@@ -302,7 +329,8 @@ impl Serialize for Vec<i64> {
 /// Deserializes a `Response` from the binary stream.
 ///
 /// Reads all fields of a Response sequentially: task_id, version, status_code, reason, and headers.
-/// The task_id is converted from i32 to String as part of the deserialization process.
+/// The task_id is converted from i32 to String as part of the deserialization process, and the
+/// truncation pseudo-header is removed from the headers and reported through `truncated`.
 impl Serialize for Response {
     fn read(ser: &mut UdsSer) -> Self {
         let task_id = ser.read::<i32>();
@@ -310,9 +338,10 @@ impl Serialize for Response {
         let status_code: i32 = ser.read();
 
         let reason = ser.read::<String>();
-        let headers: HashMap<String, Vec<String>> = ser.read();
+        let mut headers: HashMap<String, Vec<String>> = ser.read();
+        let truncated = headers.remove(HEADERS_TRUNCATED).is_some();
 
-        info!("headers {:?}", headers);
+        info!("headers {:?}, truncated {}", headers, truncated);
 
         Response {
             task_id: task_id.to_string(),
@@ -320,6 +349,7 @@ impl Serialize for Response {
             status_code,
             reason,
             headers,
+            truncated,
         }
     }
 }
@@ -370,6 +400,7 @@ impl Serialize for Progress {
             sizes,
             extras,
             body_bytes: Vec::new(),
+            eta_ms: None,
         }
     }
 }
@@ -397,6 +428,87 @@ impl Serialize for NotifyData {
             action,
             version,
             task_states,
+            client_tag: String::new(),
+        }
+    }
+}
+
+/// Flag of an inline body, the server sent more than the task keeps.
+const BODY_TRUNCATED: u8 = 1;
+
+/// Flag of an inline body, the message carries only the beginning of the
+/// body kept by the service.
+const BODY_PARTIAL: u8 = 1 << 1;
+
+/// Deserializes an `InlineBody` from the binary stream.
+///
+/// Reads the body length (u32), the flags (u8) and then the body bytes,
+/// never reading past the end of the buffer.
+impl Serialize for InlineBody {
+    fn read(ser: &mut UdsSer) -> Self {
+        let length: u32 = ser.read();
+        let flags: u8 = ser.read();
+        let length = (length as usize).min(ser.inner.len());
+        let data = ser.inner[..length].to_vec();
+        ser.inner = &ser.inner[length..];
+        InlineBody {
+            body: ResponseBody {
+                data,
+                truncated: flags & BODY_TRUNCATED != 0,
+            },
+            partial: flags & BODY_PARTIAL != 0,
+        }
+    }
+}
+
+/// Deserializes a `Redirect` from the binary stream.
+///
+/// Reads the task ID (u32) and the status code (u32), followed by the source
+/// and the target URL as null-terminated strings.
+impl Serialize for Redirect {
+    fn read(ser: &mut UdsSer) -> Self {
+        Redirect {
+            task_id: ser.read(),
+            status: ser.read(),
+            from_url: ser.read(),
+            to_url: ser.read(),
+        }
+    }
+}
+
+/// Deserializes a `TaskListDelta` from the binary stream.
+///
+/// Reads the added tasks as (task ID, action, mode, state), the removed task
+/// IDs and the (task ID, state) pairs of changed tasks, each list preceded
+/// by its length (u32).
+impl Serialize for TaskListDelta {
+    fn read(ser: &mut UdsSer) -> Self {
+        let length: u32 = ser.read();
+        let mut added = Vec::with_capacity(length as usize);
+        for _ in 0..length {
+            added.push(TaskBrief {
+                task_id: ser.read(),
+                action: ser.read(),
+                mode: ser.read(),
+                state: ser.read(),
+            });
+        }
+        let length: u32 = ser.read();
+        let mut removed = Vec::with_capacity(length as usize);
+        for _ in 0..length {
+            removed.push(ser.read());
+        }
+        let length: u32 = ser.read();
+        let mut state_changed = Vec::with_capacity(length as usize);
+        for _ in 0..length {
+            let task_id: u32 = ser.read();
+            let state: State = ser.read();
+            state_changed.push((task_id, state));
+        }
+        TaskListDelta {
+            added,
+            removed,
+            state_changed,
         }
     }
 }
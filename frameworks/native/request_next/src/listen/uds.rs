@@ -23,7 +23,8 @@ use std::io;
 use std::os::fd::{FromRawFd, IntoRawFd};
 use std::os::unix;
 
-use request_core::info::{FaultOccur, Faults, NotifyData, Response};
+pub use request_core::info::ResponseBody;
+use request_core::info::{FaultOccur, Faults, NotifyData, Response, TaskListDelta};
 use ylong_runtime::net::UnixDatagram;
 
 // Local dependencies
@@ -46,6 +47,16 @@ const HTTP_RESPONSE: i16 = 0;
 const NOTIFY_DATA: i16 = 1;
 const FAULTS: i16 = 2;
 
+/// Message type identifier for task list changes.
+///
+/// Indicates that the message contains a delta of a subscribed task list.
+const TASK_LIST_DELTA: i16 = 4;
+
+/// Message type identifier for redirects.
+///
+/// Indicates that the message contains a redirect followed by a download task.
+const REDIRECT: i16 = 5;
+
 /// Listener for Unix Domain Socket messages.
 ///
 /// Provides methods to receive and process messages from the download service.
@@ -99,34 +110,9 @@ impl UdsListener {
     /// # Errors
     /// - Returns `io::ErrorKind::InvalidData` if message validation fails or if the message type is unknown
     /// - Returns other `io::Error` variants for socket operation failures
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use std::fs::File;
-    /// use request_next::listen::uds::UdsListener;
-    /// 
-    /// async fn example() -> Result<(), std::io::Error> {
-    ///     // Assuming socket_file is a valid file descriptor
-    ///     let socket_file = File::open("/path/to/socket")?;
-    ///     let mut listener = UdsListener::new(socket_file);
-    ///     
-    ///     // Receive a message
-    ///     match listener.recv().await? {
-    ///         request_next::listen::uds::Message::HttpResponse(response) => {
-    ///             println!("Received HTTP response for task: {}", response.task_id);
-    ///         }
-    ///         request_next::listen::uds::Message::NotifyData(notify_data) => {
-    ///             println!("Received notification for task: {}", notify_data.task_id);
-    ///         }
-    ///     }
-    ///     
-    ///     Ok(())
-    /// }
-    /// ```
     pub async fn recv(&mut self) -> Result<Message, io::Error> {
         // Buffer for receiving data
-        let mut buf = [0u8; 4096];
+        let mut buf = [0u8; 8 * 1024];
         // Receive data from socket
         let size = self.socket.recv(&mut buf).await?;
         // Send acknowledgment with received size
@@ -157,11 +143,18 @@ impl UdsListener {
             let response: Response = uds.read();
             Ok(Message::HttpResponse(response))
         } else if msg_type == NOTIFY_DATA {
-            let notify_data: NotifyData = uds.read();
-            Ok(Message::NotifyData(notify_data))
+            let (notify_data, response_body) = read_notify_data(&mut uds);
+            Ok(Message::NotifyData(notify_data, response_body))
         } else if msg_type == FAULTS {
             let fault_occur: FaultOccur = uds.read();
             Ok(Message::Faults(fault_occur))
+        } else if msg_type == TASK_LIST_DELTA {
+            let id: u32 = uds.read();
+            let delta: TaskListDelta = uds.read();
+            Ok(Message::TaskListDelta(id, delta))
+        } else if msg_type == REDIRECT {
+            let redirect: Redirect = uds.read();
+            Ok(Message::Redirect(redirect))
         } else {
             Err(io::Error::new(
                 io::ErrorKind::InvalidData,
@@ -177,9 +170,68 @@ impl UdsListener {
 pub enum Message {
     /// HTTP response message containing response data for a download task
     HttpResponse(Response),
-    /// Notification data message containing status updates for download tasks
-    NotifyData(NotifyData),
+    /// Notification data message containing status updates for download tasks,
+    /// with the upload response body if the service attached one
+    NotifyData(NotifyData, Option<InlineBody>),
     Faults(FaultOccur),
+    /// Changes of the task list subscribed with the given ID
+    TaskListDelta(u32, TaskListDelta),
+    /// Redirect followed by a task reporting its redirects
+    Redirect(Redirect),
+}
+
+/// Redirect followed by a task, sent for each hop of its redirect chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Redirect {
+    /// ID of the task that followed the redirect
+    pub task_id: u32,
+    /// Status code of the redirect response
+    pub status: u32,
+    /// URL that answered with the redirect
+    pub from_url: String,
+    /// URL the redirect pointed to
+    pub to_url: String,
+}
+
+/// Upload server response body attached to a completion message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InlineBody {
+    /// Body as carried by the message
+    pub body: ResponseBody,
+    /// Whether the message carries only the beginning of the body kept by the
+    /// service, the whole body is returned by `RequestClient::show_task`
+    pub partial: bool,
+}
+
+/// Reads the body of a notification data message.
+///
+/// Newer services append an extension block starting with its version (u32).
+/// Version 1 holds the estimated remaining time as a flag (u8) and the
+/// milliseconds (u64), then a flag (u8) followed by the upload response body
+/// if the service attached one. Later versions only append to the block.
+///
+/// # Returns
+/// The notification data and the upload response body, if any
+fn read_notify_data(uds: &mut UdsSer) -> (NotifyData, Option<InlineBody>) {
+    let mut notify_data: NotifyData = uds.read();
+    if uds.is_empty() {
+        return (notify_data, None);
+    }
+    let version: u32 = uds.read();
+    if version < 1 {
+        return (notify_data, None);
+    }
+    let has_eta: u8 = uds.read();
+    let eta_ms: u64 = uds.read();
+    if has_eta != 0 {
+        notify_data.progress.eta_ms = Some(eta_ms);
+    }
+    let has_body: u8 = uds.read();
+    let response_body = (has_body != 0).then(|| uds.read::<InlineBody>());
+    if version >= 2 {
+        notify_data.client_tag = uds.read_client_tag();
+    }
+    (notify_data, response_body)
 }
 
 /// Validates the header of a received message.
@@ -226,3 +278,8 @@ fn message_check(uds: &mut UdsSer, size: i16, message_id: i32, msg_type: &mut i1
     }
     true
 }
+
+#[cfg(test)]
+mod ut_uds {
+    include!("../../tests/ut/listen/ut_uds.rs");
+}
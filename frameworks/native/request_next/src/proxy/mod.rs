@@ -18,37 +18,59 @@
 //! objects, and serves as the foundation for all service interactions.
 
 // Submodules
+#[cfg(not(feature = "doc-test-stubs"))]
 mod notification; // Handles notification-related functionality
+#[cfg(not(feature = "doc-test-stubs"))]
 mod query; // Provides task query capabilities
 mod state; // Manages service state tracking
+#[cfg(feature = "doc-test-stubs")]
+mod stub; // In-memory service used by the doctests
+#[cfg(not(feature = "doc-test-stubs"))]
 mod task; // Implements task management operations
+#[cfg(not(feature = "doc-test-stubs"))]
 mod uds; // Handles Unix Domain Socket communication
 
 /// Service token identifier for the download request service.
 ///
 /// Used to identify and connect to the download service through the IPC mechanism.
+#[cfg(not(feature = "doc-test-stubs"))]
 const SERVICE_TOKEN: &str = "OHOS.Download.RequestServiceInterface";
 
 // Standard library imports
-use std::sync::{Arc, LazyLock, Mutex};
+#[cfg(not(feature = "doc-test-stubs"))]
+use std::sync::{Arc, LazyLock};
 
 // External dependencies
+#[cfg(not(feature = "doc-test-stubs"))]
 use ipc::remote::RemoteObj;
+#[cfg(not(feature = "doc-test-stubs"))]
 use request_core::error_code::EXCEPTION_SERVICE;
+#[cfg(not(feature = "doc-test-stubs"))]
+use samgr::definition::DOWNLOAD_SERVICE_ID;
+#[cfg(not(feature = "doc-test-stubs"))]
+use samgr::manage::SystemAbilityManager;
 
 // Local dependencies
-use state::SaState;
+pub use state::ChannelStats;
+#[cfg(not(feature = "doc-test-stubs"))]
+use state::SaKeeper;
+#[cfg(feature = "doc-test-stubs")]
+pub use stub::add_task;
+#[cfg(feature = "doc-test-stubs")]
+pub(crate) use stub::RequestProxy;
 
 /// Proxy for interacting with the download service through IPC.
 ///
 /// Implements the singleton pattern to provide a single point of access to the
 /// download service. Manages connection state and provides methods to obtain
 /// the remote service object for IPC calls.
+#[cfg(not(feature = "doc-test-stubs"))]
 pub struct RequestProxy {
-    /// Service state protected by a mutex for thread safety
-    remote: Mutex<SaState>,
+    /// Service state, reloaded with backoff after failures
+    remote: SaKeeper<Arc<RemoteObj>>,
 }
 
+#[cfg(not(feature = "doc-test-stubs"))]
 impl RequestProxy {
     /// Returns the singleton instance of `RequestProxy`.
     ///
@@ -57,30 +79,19 @@ impl RequestProxy {
     ///
     /// # Returns
     /// A static reference to the singleton `RequestProxy` instance
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// // Get the singleton instance
-    /// let proxy = RequestProxy::get_instance();
-    ///
-    /// // Verify pointer equality (both references point to the same instance)
-    /// let proxy2 = RequestProxy::get_instance();
-    /// assert!(std::ptr::eq(proxy, proxy2));
-    /// ```
     pub fn get_instance() -> &'static Self {
         static REQUEST_PROXY: LazyLock<RequestProxy> = LazyLock::new(|| RequestProxy {
-            remote: Mutex::new(SaState::update()),
+            remote: SaKeeper::new(Box::new(|| {
+                SystemAbilityManager::load_system_ability(DOWNLOAD_SERVICE_ID, 1000).map(Arc::new)
+            })),
         });
         &REQUEST_PROXY
     }
 
     /// Retrieves the remote service object for IPC communication.
     ///
-    /// Checks if the service state is ready. If not, attempts to reconnect if the
-    /// last failure occurred more than 5 seconds ago.
+    /// Loads the service if it is not ready. After a failed load, further loads
+    /// are only attempted once an exponentially growing backoff has elapsed.
     ///
     /// # Returns
     /// A `Result` containing either:
@@ -93,23 +104,25 @@ impl RequestProxy {
     /// # Safety
     /// This method is marked as `pub(crate)` to restrict access to the module's internal API.
     pub(crate) fn remote(&self) -> Result<Arc<RemoteObj>, i32> {
-        let mut remote = self.remote.lock().unwrap();
-        match *remote {
-            // If service is ready, return the remote object
-            SaState::Ready(ref obj) => return Ok(obj.clone()),
-            // If service is invalid, attempt to reconnect after a delay
-            SaState::Invalid(ref time) => {
-                // Only attempt reconnection after 5 seconds to prevent excessive reconnection attempts
-                if time.elapsed().as_secs() > 5 {
-                    *remote = SaState::update();
-                    if let SaState::Ready(ref obj) = *remote {
-                        return Ok(obj.clone());
-                    }
-                }
-            }
-        }
-        // Log error and return exception code
-        error!("request systemAbility load failed");
-        Err(EXCEPTION_SERVICE)
+        self.remote.get().ok_or_else(|| {
+            error!("request systemAbility load failed");
+            EXCEPTION_SERVICE
+        })
+    }
+
+    /// Reloads the service right away, ignoring any pending backoff.
+    ///
+    /// # Errors
+    /// - Returns `EXCEPTION_SERVICE` if the service cannot be loaded
+    pub(crate) fn force_reconnect(&self) -> Result<Arc<RemoteObj>, i32> {
+        self.remote.force_reconnect().ok_or_else(|| {
+            error!("request systemAbility reload failed");
+            EXCEPTION_SERVICE
+        })
+    }
+
+    /// Returns a snapshot of the connection to the service.
+    pub(crate) fn channel_stats(&self) -> ChannelStats {
+        self.remote.stats()
     }
 }
@@ -21,20 +21,25 @@
 use ipc::parcel::MsgParcel;
 use ipc::remote;
 use crate::proxy::{RequestProxy, SERVICE_TOKEN};
+use request_core::config::GroupProgressStyle;
+use request_core::info::GroupInfo;
 use request_core::interface;
 
+const VISIBILITY_BANNER: u32 = 0b01;
+
 impl RequestProxy {
     /// Creates a new notification group for download tasks.
     ///
     /// # Returns
-    /// - `Ok(())` on success
+    /// - `Ok(String)` with the new group ID on success
     /// - `Err(i32)` with an error code on failure
     ///
     /// # Notes
-    /// This method is currently not implemented. It will remain as a placeholder until
-    /// the notification grouping functionality is fully developed.
+    /// The progress style is written after the visibility level, services that
+    /// predate it stop reading before it and keep a determinate gauge.
     pub(crate) fn create_group(&self, gauge: Option<bool>, title: Option<String>,
-        text: Option<String>, disable: Option<bool>) -> Result<String, i32> {
+        text: Option<String>, disable: Option<bool>,
+        progress_style: Option<GroupProgressStyle>) -> Result<String, i32> {
 
         let remote = self.remote()?;
         let mut data = MsgParcel::new();
@@ -58,15 +63,21 @@ impl RequestProxy {
             }
             None => data.write(&false).unwrap(),
         }
+        // No want agent is supported by this client
+        data.write(&false).unwrap();
         match disable {
             Some(d) => data.write(&d).unwrap(),
             None => data.write(&false).unwrap(),
         }
+        // Banner visibility only, as the native client defaults it
+        data.write(&VISIBILITY_BANNER).unwrap();
+        let progress_style = progress_style.unwrap_or_default() as u32;
+        data.write(&progress_style).unwrap();
 
         let mut reply = remote.send_request(interface::CREATE_GROUP, &mut data).unwrap();
 
-        let group_id = reply.read::<u32>().unwrap();
-        Ok(group_id.to_string())
+        let group_id = reply.read::<String>().unwrap();
+        Ok(group_id)
     }
 
     /// Deletes an existing notification group.
@@ -127,4 +138,71 @@ impl RequestProxy {
         }
         Ok(())
     }
+
+    /// Queries the membership and aggregate progress of a notification group.
+    ///
+    /// # Parameters
+    /// - `group_id`: Unique identifier of the notification group to query
+    ///
+    /// # Returns
+    /// - `Ok(GroupInfo)` on success
+    /// - `Err(i32)` with an error code on failure, the group is not found if
+    ///   the caller did not create it
+    pub(crate) fn query_group(&self, group_id: String) -> Result<GroupInfo, i32> {
+        let remote = self.remote()?;
+        let mut data = MsgParcel::new();
+
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&group_id).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::QUERY_GROUP, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap();
+        if code != 0 {
+            return Err(code);
+        }
+        Ok(reply.read::<GroupInfo>().unwrap())
+    }
+
+    /// Stops or removes all tasks of a notification group and deletes the group.
+    ///
+    /// # Parameters
+    /// - `group_id`: Unique identifier of the notification group to cancel
+    /// - `remove_members`: Whether the tasks are removed instead of stopped
+    ///
+    /// # Returns
+    /// - `Ok(Vec<(String, i32)>)` with the ID and result code of each task on success
+    /// - `Err(i32)` with an error code on failure, the group is not found if
+    ///   the caller did not create it
+    pub(crate) fn cancel_group(
+        &self,
+        group_id: String,
+        remove_members: bool,
+    ) -> Result<Vec<(String, i32)>, i32> {
+        let remote = self.remote()?;
+        let mut data = MsgParcel::new();
+
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&group_id).unwrap();
+        data.write(&remove_members).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::CANCEL_GROUP, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap();
+        if code != 0 {
+            return Err(code);
+        }
+        let len = reply.read::<u32>().unwrap();
+        let mut results = Vec::with_capacity(len as usize);
+        for _ in 0..len {
+            let task_id = reply.read::<String>().unwrap();
+            let code = reply.read::<i32>().unwrap();
+            results.push((task_id, code));
+        }
+        Ok(results)
+    }
 }
@@ -24,8 +24,11 @@ use ipc::remote;
 // Download core dependencies
 use request_core::config::{Action,TaskConfig};
 use request_core::filter::SearchFilter;
-use request_core::info::{State, TaskInfo};
+use request_core::info::{
+    ChangedSet, EffectiveConfig, SpeedSample, State, StateCounts, TaskExplanation, TaskInfo,
+};
 use request_core::interface;
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 // Local dependencies
@@ -72,24 +75,6 @@ impl RequestProxy {
     /// A `Result` containing either:
     /// - `Ok(String)` with the MIME type of the download task
     /// - `Err(i32)` with an error code if the task doesn't exist or cannot be accessed
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example() -> Result<(), i32> {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     let task_id = 12345;
-    ///     
-    ///     match proxy.query_mime_type(task_id) {
-    ///         Ok(mime_type) => println!("Task MIME type: {}", mime_type),
-    ///         Err(error) => println!("Failed to get MIME type: {}", error),
-    ///     }
-    ///     
-    ///     Ok(())
-    /// }
-    /// ```
     pub(crate) fn query_mime_type(&self, task_id: i64) -> Result<String, i32> {
         let remote = self.remote()?;
 
@@ -120,24 +105,6 @@ impl RequestProxy {
     /// A `Result` containing either:
     /// - `Ok(TaskInfo)` with detailed information about the task
     /// - `Err(i32)` with an error code if the task doesn't exist or cannot be accessed
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example() -> Result<(), i32> {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     let task_id = 12345;
-    ///     
-    ///     match proxy.show(task_id) {
-    ///         Ok(task_info) => println!("Task URL: {}", task_info.url),
-    ///         Err(error) => println!("Failed to retrieve task info: {}", error),
-    ///     }
-    ///     
-    ///     Ok(())
-    /// }
-    /// ```
     pub(crate) fn show(&self, task_id: i64) -> Result<TaskInfo, i32> {
         let remote = self.remote()?;
 
@@ -201,41 +168,13 @@ impl RequestProxy {
     /// Searches for download tasks based on specified filter criteria.
     ///
     /// # Parameters
-    /// - `filter`: Search criteria to filter tasks by bundle name, time range, state, action, and mode
+    /// - `filter`: Search criteria to filter tasks by bundle name, time range, state, action, mode
+    ///   and client tag
     ///
     /// # Returns
     /// A `Result` containing either:
     /// - `Ok(Vec<String>)` with a list of matching task IDs
     /// - `Err(i32)` with an error code if the search fails
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    /// use request_core::filter::SearchFilter;
-    /// use request_core::info::State;
-    ///
-    /// fn example() -> Result<(), i32> {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     
-    ///     // Search for completed tasks
-    ///     let filter = SearchFilter {
-    ///         state: Some(State::Completed),
-    ///         bundle_name: None,
-    ///         before: None,
-    ///         after: None,
-    ///         action: None,
-    ///         mode: None,
-    ///     };
-    ///     
-    ///     match proxy.search(filter) {
-    ///         Ok(task_ids) => println!("Found {} completed tasks", task_ids.len()),
-    ///         Err(error) => println!("Search failed: {}", error),
-    ///     }
-    ///     
-    ///     Ok(())
-    /// }
-    /// ```
     pub(crate) fn search(&self, filter: SearchFilter) -> Result<Vec<String>, i32> {
         let remote = self.remote()?;
 
@@ -248,24 +187,14 @@ impl RequestProxy {
             None => data.write(&"*".to_string()).unwrap(),
         }
 
-        // Serialize the filter parameters into the parcel
-        match filter.before {
-            Some(before) => data.write(&before).unwrap(),
-            None => match SystemTime::now().duration_since(UNIX_EPOCH) {
-                Ok(n) => data.write(&(n.as_millis() as i64)).unwrap(),
-                Err(_) => data.write(&(0i64)).unwrap(),
-            },
-        }
-
-        match filter.after {
-            Some(after) => data.write(&after).unwrap(),
-            None => match SystemTime::now().duration_since(UNIX_EPOCH) {
-                Ok(n) => data
-                    .write(&(n.as_millis() as i64 - 24 * 60 * 60 * 1000))
-                    .unwrap(),
-                Err(_) => data.write(&(0i64)).unwrap(),
-            },
-        }
+        // Serialize the creation time window into the parcel
+        let now = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(n) => n.as_millis() as i64,
+            Err(_) => 0,
+        };
+        let (after, before) = filter.time_window(now);
+        data.write(&before).unwrap();
+        data.write(&after).unwrap();
 
         match filter.state {
             Some(state) => data.write(&(state as u32)).unwrap(),
@@ -282,6 +211,9 @@ impl RequestProxy {
             None => data.write(&02u32).unwrap(), // Default mode value
         }
 
+        // Serialize the client tag filter, an empty tag matches any task
+        data.write(&filter.client_tag.unwrap_or_default()).unwrap();
+
         let mut reply = remote.send_request(interface::SEARCH, &mut data).map_err(|_| 13400003)?;
 
         // First value in reply is the number of results
@@ -296,6 +228,47 @@ impl RequestProxy {
         Ok(ids)
     }
 
+    /// Counts tasks per application and state.
+    ///
+    /// # Parameters
+    /// - `all`: Whether the tasks of all applications are counted
+    /// - `uid`: Application to count if not `all`, `None` for the caller
+    ///
+    /// # Returns
+    /// - `Ok(HashMap)` with the counts per uid on success
+    /// - `Err(i32)` with an error code on failure
+    pub(crate) fn count_by_state(
+        &self,
+        all: bool,
+        uid: Option<u64>,
+    ) -> Result<HashMap<u64, StateCounts>, i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+
+        data.write(&all).unwrap();
+        data.write(&uid.is_some()).unwrap();
+        data.write(&uid.unwrap_or_default()).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::COUNT_BY_STATE, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap(); // error code
+        if code != 0 {
+            return Err(code);
+        }
+
+        let len = reply.read::<u32>().unwrap();
+        let mut counts = HashMap::with_capacity(len as usize);
+        for _ in 0..len {
+            let uid = reply.read::<u64>().unwrap();
+            counts.insert(uid, reply.read::<StateCounts>().unwrap());
+        }
+        Ok(counts)
+    }
+
     /// Retrieves a download task with authentication.
     ///
     /// # Parameters
@@ -331,4 +304,118 @@ impl RequestProxy {
         let task_config = reply.read::<TaskConfig>().unwrap();
         Ok(task_config)
     }
+
+    /// Retrieves the effective configuration a task runs with.
+    ///
+    /// # Parameters
+    /// - `task_id`: Unique identifier of the task to query
+    ///
+    /// # Returns
+    /// - `Ok(EffectiveConfig)` on success
+    /// - `Err(i32)` with an error code on failure, the task is not found if
+    ///   it never ran
+    pub(crate) fn effective_config(&self, task_id: i64) -> Result<EffectiveConfig, i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&task_id.to_string()).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::QUERY_EFFECTIVE_CONFIG, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap();
+        if code != 0 {
+            return Err(code);
+        }
+        Ok(reply.read::<EffectiveConfig>().unwrap())
+    }
+
+    /// Retrieves the recent speed samples of a task.
+    ///
+    /// # Parameters
+    /// - `task_id`: Unique identifier of the task to query
+    /// - `points`: Maximum number of samples to return, 0 for all
+    ///
+    /// # Returns
+    /// - `Ok(Vec<SpeedSample>)` with the newest samples, oldest first
+    /// - `Err(i32)` with an error code on failure, the task is not found if
+    ///   it is neither running nor recently stopped
+    pub(crate) fn speed_history(&self, task_id: i64, points: u32) -> Result<Vec<SpeedSample>, i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&task_id.to_string()).unwrap();
+        data.write(&points).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::QUERY_SPEED_HISTORY, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap();
+        if code != 0 {
+            return Err(code);
+        }
+        let len = reply.read::<u32>().unwrap() as usize;
+        let mut samples = Vec::with_capacity(len);
+        for _ in 0..len {
+            samples.push(reply.read::<SpeedSample>().unwrap());
+        }
+        Ok(samples)
+    }
+
+    /// Explains why a task is in its current state.
+    ///
+    /// # Parameters
+    /// - `task_id`: Unique identifier of the task to explain
+    ///
+    /// # Returns
+    /// - `Ok(TaskExplanation)` with the details left out unless the caller
+    ///   owns the task
+    /// - `Err(i32)` with an error code on failure
+    pub(crate) fn explain(&self, task_id: i64) -> Result<TaskExplanation, i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&task_id.to_string()).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::EXPLAIN_TASK, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap();
+        if code != 0 {
+            return Err(code);
+        }
+        Ok(reply.read::<TaskExplanation>().unwrap())
+    }
+
+    /// Queries the tasks of the caller changed since a revision.
+    ///
+    /// # Parameters
+    /// - `revision`: Revision of the last sync, 0 for all tasks
+    ///
+    /// # Returns
+    /// - `Ok(ChangedSet)` with the changed and removed tasks
+    /// - `Err(i32)` with an error code on failure
+    pub(crate) fn changed_since(&self, revision: u64) -> Result<ChangedSet, i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&revision).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::CHANGED_SINCE, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap();
+        if code != 0 {
+            return Err(code);
+        }
+        Ok(reply.read::<ChangedSet>().unwrap())
+    }
 }
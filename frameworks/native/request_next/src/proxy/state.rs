@@ -12,73 +12,185 @@
 // limitations under the License.
 
 //! System Ability state management for download service.
-//! 
+//!
 //! This module defines the state management for the download service's System Ability (SA).
-//! It provides functionality to track whether the service is ready or invalid, and to
-//! attempt to load the System Ability with automatic retry logic.
+//! It tracks whether the service is ready, being loaded or invalid, and retries failed
+//! loads with an exponential backoff so a service that keeps failing is not hammered.
 
 // Standard library dependencies
-use std::sync::Arc;
-use std::time::{self, Instant};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
 
-// IPC and service management dependencies
-use ipc::remote::RemoteObj;
-use samgr::definition::DOWNLOAD_SERVICE_ID;
-use samgr::manage::SystemAbilityManager;
+use ylong_runtime::fastrand::fast_random;
 
-pub(crate) enum SaState {
+/// Delay before reloading after the first failed load.
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound of the delay between two loads, before jitter is applied.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Percentage by which a delay is randomly lengthened or shortened.
+const JITTER_PERCENT: u64 = 20;
+
+/// Snapshot of the connection to the download service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChannelStats {
+    /// Whether the service is currently loaded.
+    pub connected: bool,
+    /// Number of loads that failed in a row since the last success.
+    pub consecutive_failures: u32,
+    /// Delay applied after the last failed load, zero when connected.
+    pub backoff: Duration,
+    /// Time left before the next load is allowed, zero if allowed now.
+    pub retry_in: Duration,
+}
+
+pub(crate) enum SaState<T> {
     /// The System Ability is ready to use with the provided remote object.
-    Ready(Arc<RemoteObj>),
-    
-    /// The System Ability is invalid, with the timestamp when it became invalid.
-    Invalid(time::Instant),
+    Ready(T),
+
+    /// A caller is loading the System Ability, after `failures` failed loads.
+    Loading { failures: u32 },
+
+    /// The last `failures` loads failed, the next one is allowed at `retry_at`.
+    Invalid {
+        failures: u32,
+        backoff: Duration,
+        retry_at: Instant,
+    },
 }
 
-impl SaState {
-    /// Attempts to load the download service System Ability with retry logic.
-    ///
-    /// Tries to load the System Ability up to 5 times with a 5-second delay between attempts.
-    /// Returns `SaState::Ready` if successful, or `SaState::Invalid` if all attempts fail.
-    ///
-    /// # Returns
-    /// - `SaState::Ready` with an `Arc<RemoteObj>` if the System Ability is successfully loaded
-    /// - `SaState::Invalid` with the current time if all 5 attempts to load fail
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::state::SaState;
-    ///
-    /// fn example() {
-    ///     // Attempt to load the download service System Ability
-    ///     let state = SaState::update();
-    ///     
-    ///     match state {
-    ///         SaState::Ready(remote) => println!("System Ability loaded successfully"),
-    ///         SaState::Invalid(timestamp) => println!("Failed to load System Ability"),
-    ///     }
-    /// }
-    /// ```
+/// Keeps the System Ability loaded, backing off between failed loads.
+///
+/// The state mutex is never held while loading, callers arriving during a
+/// load wait for its outcome instead of starting another one.
+pub(crate) struct SaKeeper<T> {
+    state: Mutex<SaState<T>>,
+    loaded: Condvar,
+    load: Box<dyn Fn() -> Option<T> + Send + Sync>,
+}
+
+impl<T: Clone> SaKeeper<T> {
+    /// Creates a keeper that loads the System Ability with `load` on first use.
+    pub(crate) fn new(load: Box<dyn Fn() -> Option<T> + Send + Sync>) -> Self {
+        Self {
+            state: Mutex::new(SaState::Invalid {
+                failures: 0,
+                backoff: Duration::ZERO,
+                retry_at: Instant::now(),
+            }),
+            loaded: Condvar::new(),
+            load,
+        }
+    }
+
+    /// Returns the loaded object, loading it if the current backoff has elapsed.
     ///
-    /// # Notes
-    /// This method uses a fixed retry count of 5 and a 5-second delay between retries. If all
-    /// attempts fail, it records the current time as the point when the state became invalid.
-    pub(crate) fn update() -> Self {
-        // Try to load the System Ability up to 5 times with retries
-        for _ in 0..5 {
-            match SystemAbilityManager::load_system_ability(DOWNLOAD_SERVICE_ID, 1000) {
-                Some(remote) => {
-                    // Successfully loaded, return Ready state with the remote object
-                    return SaState::Ready(Arc::new(remote));
+    /// Returns `None` if the load fails or the backoff has not elapsed yet.
+    pub(crate) fn get(&self) -> Option<T> {
+        let mut state = self.state.lock().unwrap();
+        let failures = loop {
+            match *state {
+                SaState::Ready(ref obj) => return Some(obj.clone()),
+                SaState::Loading { .. } => state = self.loaded.wait(state).unwrap(),
+                SaState::Invalid {
+                    failures, retry_at, ..
+                } => {
+                    if Instant::now() < retry_at {
+                        return None;
+                    }
+                    *state = SaState::Loading { failures };
+                    break failures;
                 }
-                None => {
-                    // Failed to load, wait 5 seconds before retrying
-                    std::thread::sleep(std::time::Duration::from_millis(5000));
-                    error!("request systemAbility load failed, retrying...");
+            }
+        };
+        drop(state);
+
+        let obj = (self.load)();
+
+        let mut state = self.state.lock().unwrap();
+        *state = match obj {
+            Some(ref obj) => SaState::Ready(obj.clone()),
+            None => {
+                let failures = failures.saturating_add(1);
+                let backoff = jitter(backoff(failures), fast_random());
+                error!(
+                    "request systemAbility load failed {} times, retry in {:?}",
+                    failures, backoff
+                );
+                SaState::Invalid {
+                    failures,
+                    backoff,
+                    retry_at: Instant::now() + backoff,
                 }
             }
+        };
+        self.loaded.notify_all();
+        obj
+    }
+
+    /// Drops the loaded object and any backoff, then loads immediately.
+    ///
+    /// If a load is already in progress, its outcome is reused.
+    pub(crate) fn force_reconnect(&self) -> Option<T> {
+        let mut state = self.state.lock().unwrap();
+        if !matches!(*state, SaState::Loading { .. }) {
+            *state = SaState::Invalid {
+                failures: 0,
+                backoff: Duration::ZERO,
+                retry_at: Instant::now(),
+            };
+        }
+        drop(state);
+        self.get()
+    }
+
+    /// Returns a snapshot of the current state.
+    pub(crate) fn stats(&self) -> ChannelStats {
+        match *self.state.lock().unwrap() {
+            SaState::Ready(_) => ChannelStats {
+                connected: true,
+                consecutive_failures: 0,
+                backoff: Duration::ZERO,
+                retry_in: Duration::ZERO,
+            },
+            SaState::Loading { failures } => ChannelStats {
+                connected: false,
+                consecutive_failures: failures,
+                backoff: Duration::ZERO,
+                retry_in: Duration::ZERO,
+            },
+            SaState::Invalid {
+                failures,
+                backoff,
+                retry_at,
+            } => ChannelStats {
+                connected: false,
+                consecutive_failures: failures,
+                backoff,
+                retry_in: retry_at.saturating_duration_since(Instant::now()),
+            },
         }
-        // All retries failed, return Invalid state with current timestamp
-        SaState::Invalid(Instant::now())
     }
 }
+
+/// Returns the delay before reloading after `failures` failed loads in a row.
+///
+/// The delay starts at one second and doubles with each failure, up to a minute.
+fn backoff(failures: u32) -> Duration {
+    let doublings = failures.saturating_sub(1).min(6);
+    (BASE_BACKOFF * (1 << doublings)).min(MAX_BACKOFF)
+}
+
+/// Lengthens or shortens `delay` by up to `JITTER_PERCENT`, as picked by `random`.
+///
+/// Spreading the reloads keeps clients that failed together from retrying together.
+fn jitter(delay: Duration, random: u64) -> Duration {
+    let percent = 100 - JITTER_PERCENT + random % (2 * JITTER_PERCENT + 1);
+    delay * percent as u32 / 100
+}
+
+#[cfg(test)]
+mod ut_state {
+    include!("../../tests/ut/proxy/ut_state.rs");
+}
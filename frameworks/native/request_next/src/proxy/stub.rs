@@ -0,0 +1,557 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! In-memory stand-in for the download service.
+//!
+//! Enabled by the `doc-test-stubs` feature so the documentation examples run
+//! without the service. Tasks are kept in memory and never transfer anything,
+//! state changes follow the rules of the service.
+
+// Standard library dependencies
+use std::collections::HashMap;
+use std::fs::File;
+use std::os::fd::OwnedFd;
+use std::os::unix::net::UnixDatagram;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Request core dependencies
+use request_core::config::{Action, GroupProgressStyle, Mode, TaskConfig, TokenScope, Version};
+use request_core::error_code::{
+    EXCEPTION_SERVICE, GROUP_NOT_FOUND, TASK_NOT_FOUND, TASK_STATE_ERR,
+};
+use request_core::filter::SearchFilter;
+use request_core::info::{
+    ChangedSet, CommonProgress, CommonTaskInfo, EffectiveConfig, GroupInfo, InfoProgress,
+    MigrationReport, MigrationSkip, SpeedSample, State, StateCounts, TaskExplanation, TaskInfo,
+    WaitingCause,
+};
+
+// Local dependencies
+use super::state::{ChannelStats, SaKeeper};
+use crate::client::error::CreateTaskError;
+use crate::client::CreatedTask;
+
+/// Task kept by the stub service.
+struct StubTask {
+    config: TaskConfig,
+    state: State,
+    max_speed: i64,
+    ctime: u64,
+    /// Signed tokens issued for the task
+    issued: Vec<String>,
+}
+
+/// Notification group kept by the stub service.
+struct StubGroup {
+    gauge: bool,
+    title: Option<String>,
+    members: Vec<i64>,
+}
+
+/// In-memory proxy used in place of the IPC proxy by the doctests.
+pub struct RequestProxy {
+    /// Always loads, so the connection statistics behave as when connected
+    remote: SaKeeper<()>,
+    tasks: Mutex<HashMap<i64, StubTask>>,
+    groups: Mutex<HashMap<String, StubGroup>>,
+    next_id: AtomicI64,
+    /// Service end of the channel, kept open so the listener does not see EOF
+    peer: Mutex<Option<UnixDatagram>>,
+}
+
+/// Adds a task to the stub service as if created by a client, returning its
+/// ID.
+pub fn add_task(config: TaskConfig) -> i64 {
+    RequestProxy::get_instance().insert(config)
+}
+
+impl RequestProxy {
+    /// Returns the singleton instance of the stub proxy.
+    pub fn get_instance() -> &'static Self {
+        static REQUEST_PROXY: LazyLock<RequestProxy> = LazyLock::new(|| RequestProxy {
+            remote: SaKeeper::new(Box::new(|| Some(()))),
+            tasks: Mutex::new(HashMap::new()),
+            groups: Mutex::new(HashMap::new()),
+            next_id: AtomicI64::new(1),
+            peer: Mutex::new(None),
+        });
+        &REQUEST_PROXY
+    }
+
+    fn insert(&self, mut config: TaskConfig) -> i64 {
+        let task_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        config.common_data.task_id = task_id as u32;
+        let ctime = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        let task = StubTask {
+            config,
+            state: State::Initialized,
+            max_speed: 0,
+            ctime,
+            issued: Vec::new(),
+        };
+        self.tasks.lock().unwrap().insert(task_id, task);
+        task_id
+    }
+
+    /// Moves a task from one of the `from` states to `to`.
+    fn transit(&self, task_id: i64, from: &[State], to: State) -> Result<(), i32> {
+        let mut tasks = self.tasks.lock().unwrap();
+        let task = tasks.get_mut(&task_id).ok_or(TASK_NOT_FOUND)?;
+        let state = task.state.clone() as u32;
+        if !from.iter().any(|s| s.clone() as u32 == state) {
+            return Err(TASK_STATE_ERR);
+        }
+        task.state = to;
+        Ok(())
+    }
+
+    fn with_task<T>(&self, task_id: i64, f: impl FnOnce(&mut StubTask) -> T) -> Result<T, i32> {
+        let mut tasks = self.tasks.lock().unwrap();
+        let task = tasks.get_mut(&task_id).ok_or(TASK_NOT_FOUND)?;
+        if matches!(task.state, State::PendingRemoval) {
+            return Err(TASK_NOT_FOUND);
+        }
+        Ok(f(task))
+    }
+
+    pub(crate) fn create(&self, config: &TaskConfig) -> Result<CreatedTask, CreateTaskError> {
+        Ok(CreatedTask {
+            task_id: self.insert(config.clone()),
+            duplicate: false,
+        })
+    }
+
+    pub(crate) fn get_task(&self, task_id: i64, _token: Option<String>) -> Result<TaskConfig, i32> {
+        self.with_task(task_id, |task| task.config.clone())
+    }
+
+    pub(crate) fn start(&self, task_id: i64) -> Result<(), i32> {
+        let from = [State::Initialized, State::Stopped, State::Failed];
+        self.transit(task_id, &from, State::Running)
+    }
+
+    pub(crate) fn pause(&self, task_id: i64) -> Result<(), i32> {
+        let from = [State::Waiting, State::Running, State::Retrying];
+        self.transit(task_id, &from, State::Paused)
+    }
+
+    pub(crate) fn resume(&self, task_id: i64) -> Result<(), i32> {
+        self.transit(task_id, &[State::Paused], State::Running)
+    }
+
+    pub(crate) fn stop(&self, task_id: i64) -> Result<(), i32> {
+        let from = [State::Waiting, State::Running, State::Retrying];
+        self.transit(task_id, &from, State::Stopped)
+    }
+
+    pub(crate) fn remove(&self, task_id: i64) -> Result<(), i32> {
+        self.tasks
+            .lock()
+            .unwrap()
+            .remove(&task_id)
+            .map(|_| ())
+            .ok_or(TASK_NOT_FOUND)
+    }
+
+    pub(crate) fn remove_with_grace(&self, task_id: i64) -> Result<(), i32> {
+        self.with_task(task_id, |task| task.state = State::PendingRemoval)
+    }
+
+    pub(crate) fn undo_remove(&self, task_id: i64) -> Result<(), i32> {
+        self.transit(task_id, &[State::PendingRemoval], State::Paused)
+    }
+
+    pub(crate) fn purge_removed(&self, task_id: i64) -> Result<(), i32> {
+        self.transit(task_id, &[State::PendingRemoval], State::Removed)?;
+        self.remove(task_id)
+    }
+
+    pub(crate) fn set_max_speed(&self, task_id: i64, speed: i64) -> Result<(), i32> {
+        self.with_task(task_id, |task| task.max_speed = speed)
+    }
+
+    pub(crate) fn set_destination(&self, task_id: i64, path: &str) -> Result<(), i32> {
+        self.with_task(task_id, |task| match task.state {
+            State::Paused => {
+                task.config.saveas = path.to_string();
+                Ok(())
+            }
+            _ => Err(TASK_STATE_ERR),
+        })?
+    }
+
+    pub(crate) fn migrate_legacy_tasks(&self, uid: u64) -> Result<MigrationReport, i32> {
+        let mut tasks = self.tasks.lock().unwrap();
+        let mut legacy: Vec<_> = tasks
+            .iter_mut()
+            .filter(|(_, task)| {
+                task.config.common_data.uid == uid && task.config.version == Version::API9
+            })
+            .collect();
+        legacy.sort_by_key(|(task_id, _)| **task_id);
+
+        let mut report = MigrationReport::default();
+        for (task_id, task) in legacy {
+            let skip = if task.config.common_data.action != Action::Download {
+                Some(MigrationSkip::Upload)
+            } else if task.config.file_specs.iter().any(|spec| spec.is_user_file) {
+                Some(MigrationSkip::UserFile)
+            } else if matches!(task.state, State::Removed | State::PendingRemoval) {
+                Some(MigrationSkip::Removed)
+            } else {
+                None
+            };
+            if let Some(skip) = skip {
+                report.skipped.push((task_id.to_string(), skip));
+                continue;
+            }
+            let common = &mut task.config.common_data;
+            task.config.version = Version::API10;
+            common.mode = if common.background {
+                Mode::BackGround
+            } else {
+                Mode::FrontEnd
+            };
+            common.gauge = false;
+            common.priority = 0;
+            common.retry = true;
+            report.migrated.push(task_id.to_string());
+        }
+        Ok(report)
+    }
+
+    pub(crate) fn query_mime_type(&self, task_id: i64) -> Result<String, i32> {
+        self.with_task(task_id, |_| String::new())
+    }
+
+    pub(crate) fn effective_config(&self, task_id: i64) -> Result<EffectiveConfig, i32> {
+        self.with_task(task_id, |task| {
+            let mut headers: Vec<_> = task.config.headers.clone().into_iter().collect();
+            headers.sort();
+            EffectiveConfig {
+                url: task.config.url.clone(),
+                headers,
+                proxy: task.config.proxy.clone(),
+                ..Default::default()
+            }
+        })
+    }
+
+    pub(crate) fn speed_history(
+        &self,
+        task_id: i64,
+        _points: u32,
+    ) -> Result<Vec<SpeedSample>, i32> {
+        self.with_task(task_id, |_| vec![])
+    }
+
+    pub(crate) fn explain(&self, task_id: i64) -> Result<TaskExplanation, i32> {
+        self.with_task(task_id, |task| TaskExplanation {
+            state: task.state.clone(),
+            reason: 0,
+            reason_message: String::new(),
+            waiting_cause: matches!(task.state, State::Waiting).then_some(WaitingCause::TaskQueue),
+            detailed: true,
+            network_satisfied: true,
+            foreground_satisfied: true,
+            battery_satisfied: true,
+            storage_satisfied: true,
+            tries: 0,
+            next_action_time: 0,
+        })
+    }
+
+    pub(crate) fn open_channel(&self) -> Result<File, i32> {
+        let (local, peer) = UnixDatagram::pair().map_err(|_| EXCEPTION_SERVICE)?;
+        *self.peer.lock().unwrap() = Some(peer);
+        Ok(File::from(OwnedFd::from(local)))
+    }
+
+    pub(crate) fn force_reconnect(&self) -> Result<(), i32> {
+        self.remote.force_reconnect().ok_or(EXCEPTION_SERVICE)
+    }
+
+    pub(crate) fn channel_stats(&self) -> ChannelStats {
+        self.remote.stats()
+    }
+
+    pub(crate) fn subscribe_batch(
+        &self,
+        task_ids: Vec<String>,
+    ) -> Result<Vec<Result<(), i32>>, i32> {
+        let tasks = self.tasks.lock().unwrap();
+        Ok(task_ids
+            .iter()
+            .map(|id| match id.parse().ok().and_then(|id| tasks.get(&id)) {
+                Some(_) => Ok(()),
+                None => Err(TASK_NOT_FOUND),
+            })
+            .collect())
+    }
+
+    pub(crate) fn subscribe_task_list(&self, _filter: &SearchFilter, _id: u32) -> Result<(), i32> {
+        Ok(())
+    }
+
+    pub(crate) fn unsubscribe_task_list(&self, _id: u32) -> Result<(), i32> {
+        Ok(())
+    }
+
+    pub(crate) fn show(&self, task_id: i64) -> Result<TaskInfo, i32> {
+        self.with_task(task_id, |task| task_info(task))
+    }
+
+    pub(crate) fn query(&self, task_id: i64) -> Result<TaskInfo, i32> {
+        self.show(task_id)
+    }
+
+    pub(crate) fn issue_task_token(
+        &self,
+        task_id: i64,
+        scope: TokenScope,
+        ttl_ms: u64,
+    ) -> Result<String, i32> {
+        self.with_task(task_id, |task| {
+            let count = task.issued.len();
+            let token = format!("rt1.{}.{}.{}.{}", task_id, scope as u32, ttl_ms, count);
+            task.issued.push(token.clone());
+            token
+        })
+    }
+
+    pub(crate) fn touch(&self, task_id: i64, token: String) -> Result<TaskInfo, i32> {
+        self.with_task(task_id, |task| {
+            (task.config.token == token || task.issued.contains(&token))
+                .then(|| task_info(task))
+                .ok_or(TASK_NOT_FOUND)
+        })?
+    }
+
+    /// Lists matching tasks in creation order, tasks pending removal are
+    /// hidden.
+    pub(crate) fn search(&self, filter: SearchFilter) -> Result<Vec<String>, i32> {
+        let tasks = self.tasks.lock().unwrap();
+        let mut task_ids: Vec<_> = tasks
+            .iter()
+            .filter(|(_, task)| !matches!(task.state, State::PendingRemoval))
+            .filter(|(_, task)| match &filter.state {
+                None | Some(State::Any) => true,
+                Some(state) => state.clone() as u32 == task.state.clone() as u32,
+            })
+            .filter(|(_, task)| match &filter.action {
+                Some(action) => *action == task.config.common_data.action,
+                None => true,
+            })
+            .filter(|(_, task)| match filter.mode {
+                Some(mode) => mode == task.config.common_data.mode,
+                None => true,
+            })
+            .map(|(task_id, _)| *task_id)
+            .collect();
+        task_ids.sort();
+        Ok(task_ids.iter().map(|task_id| task_id.to_string()).collect())
+    }
+
+    pub(crate) fn count_by_state(
+        &self,
+        all: bool,
+        uid: Option<u64>,
+    ) -> Result<HashMap<u64, StateCounts>, i32> {
+        let mut counts = HashMap::new();
+        for task in self.tasks.lock().unwrap().values() {
+            let owner = task.config.common_data.uid;
+            if all || uid.map_or(true, |uid| uid == owner) {
+                count_state(counts.entry(owner).or_default(), &task.state);
+            }
+        }
+        Ok(counts)
+    }
+
+    pub(crate) fn changed_since(&self, revision: u64) -> Result<ChangedSet, i32> {
+        // The stub keeps no revisions, all its tasks exist since revision 1.
+        let changed = match revision {
+            0 => self
+                .tasks
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, task)| !matches!(task.state, State::PendingRemoval))
+                .map(|(task_id, _)| (task_id.to_string(), 1))
+                .collect(),
+            _ => vec![],
+        };
+        Ok(ChangedSet {
+            revision: 1,
+            resync: false,
+            changed,
+            removed: vec![],
+        })
+    }
+
+    pub(crate) fn create_group(
+        &self,
+        gauge: Option<bool>,
+        title: Option<String>,
+        _text: Option<String>,
+        _disable: Option<bool>,
+        _progress_style: Option<GroupProgressStyle>,
+    ) -> Result<String, i32> {
+        let group_id = self.next_id.fetch_add(1, Ordering::Relaxed).to_string();
+        let group = StubGroup {
+            gauge: gauge.unwrap_or(false),
+            title,
+            members: vec![],
+        };
+        self.groups.lock().unwrap().insert(group_id.clone(), group);
+        Ok(group_id)
+    }
+
+    pub(crate) fn attach_group(&self, group_id: String, task_ids: Vec<String>) -> Result<(), i32> {
+        let tasks = self.tasks.lock().unwrap();
+        let mut groups = self.groups.lock().unwrap();
+        let group = groups.get_mut(&group_id).ok_or(GROUP_NOT_FOUND)?;
+        let mut members = Vec::with_capacity(task_ids.len());
+        for task_id in task_ids {
+            match task_id.parse() {
+                Ok(task_id) if tasks.contains_key(&task_id) => members.push(task_id),
+                _ => return Err(TASK_NOT_FOUND),
+            }
+        }
+        group.members.extend(members);
+        Ok(())
+    }
+
+    pub(crate) fn delete_group(&self, group_id: String) -> Result<(), i32> {
+        self.groups
+            .lock()
+            .unwrap()
+            .remove(&group_id)
+            .map(|_| ())
+            .ok_or(GROUP_NOT_FOUND)
+    }
+
+    pub(crate) fn query_group(&self, group_id: String) -> Result<GroupInfo, i32> {
+        let tasks = self.tasks.lock().unwrap();
+        let groups = self.groups.lock().unwrap();
+        let group = groups.get(&group_id).ok_or(GROUP_NOT_FOUND)?;
+        let mut counts_by_state = StateCounts::default();
+        let mut member_task_ids = vec![];
+        for task_id in group.members.iter() {
+            match tasks.get(task_id) {
+                Some(task) => {
+                    count_state(&mut counts_by_state, &task.state);
+                    member_task_ids.push(task_id.to_string());
+                }
+                None => counts_by_state.removed += 1,
+            }
+        }
+        Ok(GroupInfo {
+            group_id,
+            member_task_ids,
+            aggregate_processed: 0,
+            aggregate_total: -1,
+            counts_by_state,
+            gauge: group.gauge,
+            title: group.title.clone(),
+        })
+    }
+
+    pub(crate) fn cancel_group(
+        &self,
+        group_id: String,
+        remove_members: bool,
+    ) -> Result<Vec<(String, i32)>, i32> {
+        let members = match self.groups.lock().unwrap().get(&group_id) {
+            Some(group) => group.members.clone(),
+            None => return Err(GROUP_NOT_FOUND),
+        };
+        let results = members
+            .into_iter()
+            .map(|task_id| {
+                let res = match remove_members {
+                    true => self.remove(task_id),
+                    false => self.stop(task_id),
+                };
+                (task_id.to_string(), res.err().unwrap_or(0))
+            })
+            .collect();
+        Ok(results)
+    }
+}
+
+/// Adds a task in `state` to `counts`.
+fn count_state(counts: &mut StateCounts, state: &State) {
+    match state {
+        State::Initialized => counts.initialized += 1,
+        State::Waiting => counts.waiting += 1,
+        State::Running => counts.running += 1,
+        State::Retrying => counts.retrying += 1,
+        State::Paused => counts.paused += 1,
+        State::Stopped => counts.stopped += 1,
+        State::Completed => counts.completed += 1,
+        State::Failed => counts.failed += 1,
+        State::Removed | State::PendingRemoval => counts.removed += 1,
+        State::Any => {}
+    }
+}
+
+/// Builds the information the service reports about a task.
+fn task_info(task: &StubTask) -> TaskInfo {
+    let config = &task.config;
+    let sizes = vec![-1; config.file_specs.len().max(1)];
+    TaskInfo {
+        bundle: config.bundle.clone(),
+        url: config.url.clone(),
+        data: config.data.clone(),
+        token: config.token.clone(),
+        form_items: config.form_items.clone(),
+        file_specs: config.file_specs.clone(),
+        title: config.title.clone(),
+        description: config.description.clone(),
+        mime_type: String::new(),
+        progress: InfoProgress {
+            common_data: CommonProgress {
+                state: task.state.clone() as u32 as u8,
+                index: 0,
+                total_processed: 0,
+            },
+            processed: vec![0; sizes.len()],
+            sizes,
+            extras: HashMap::new(),
+        },
+        extras: config.extras.clone(),
+        common_data: CommonTaskInfo {
+            task_id: config.common_data.task_id,
+            uid: config.common_data.uid,
+            action: config.common_data.action.clone() as u8,
+            mode: config.common_data.mode as u8,
+            ctime: task.ctime,
+            mtime: task.ctime,
+            reason: 0,
+            gauge: config.common_data.gauge,
+            retry: config.common_data.retry,
+            tries: 0,
+            version: config.version as u8,
+            priority: config.common_data.priority,
+        },
+        max_speed: task.max_speed,
+        effective_speed_limit: task.max_speed.max(0) as u64,
+        response_body: None,
+        client_tag: String::new(),
+    }
+}
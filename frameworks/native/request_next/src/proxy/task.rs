@@ -20,56 +20,42 @@
 // IPC and parcel dependencies
 use ipc::parcel::MsgParcel;
 // Download core dependencies
-use request_core::config::TaskConfig;
+use request_core::config::{TaskConfig, TokenScope};
+use request_core::info::MigrationReport;
 use request_core::interface;
 
 // Local dependencies
 use super::{RequestProxy, SERVICE_TOKEN};
 use crate::client::error::CreateTaskError;
+use crate::client::CreatedTask;
 
 impl RequestProxy {
     /// Creates a new download task with the provided configuration.
     ///
     /// Sends a request to the download service to create a new task based on the provided
-    /// `TaskConfig`. Returns the unique task ID if successful.
+    /// `TaskConfig`. Returns the unique task ID if successful, and whether the
+    /// service returned an active duplicate instead.
     ///
     /// # Parameters
     /// - `config`: The task configuration containing download parameters
     ///
     /// # Returns
-    /// - `Ok(i64)` with the task ID if the task was created successfully
+    /// - `Ok(CreatedTask)` with the task ID if the task was created successfully
     /// - `Err(CreateTaskError)` if an error occurred during task creation
     ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_core::config::TaskConfig;
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     let config = TaskConfig::default();
-    ///     
-    ///     match proxy.create(&config) {
-    ///         Ok(task_id) => println!("Task created with ID: {}", task_id),
-    ///         Err(e) => eprintln!("Failed to create task: {:?}", e),
-    ///     }
-    ///     
-    ///     Ok(())
-    /// }
-    /// ```
-    ///
     /// # Panics
     /// - Panics if parcel operations fail due to IPC errors
-    pub(crate) fn create(&self, config: &TaskConfig) -> Result<i64, CreateTaskError> {
+    pub(crate) fn create(&self, config: &TaskConfig) -> Result<CreatedTask, CreateTaskError> {
         let remote = self.remote()?;
         let mut data = MsgParcel::new();
         // Write interface token to identify the service
         data.write_interface_token(SERVICE_TOKEN).unwrap();
 
-        // Write version information and task configuration
-        data.write(&1u32).unwrap();
+        // Write the task count, the task configuration and its options
+        data.write(&(1u32 | interface::CONSTRUCT_WITH_OPTIONS))
+            .unwrap();
         data.write(config).unwrap();
+        data.write(&config.options).unwrap();
 
         // Send request to construct the task
         let mut reply = remote
@@ -89,8 +75,13 @@ impl RequestProxy {
         }
         // Read and return the task ID
         let task_id = reply.read::<u32>().unwrap();
+        // Services without duplicate detection send no flag
+        let duplicate = reply.read::<bool>().unwrap_or(false);
 
-        Ok(task_id as i64)
+        Ok(CreatedTask {
+            task_id: task_id as i64,
+            duplicate,
+        })
     }
 
     /// Starts a download task identified by the given task ID.
@@ -102,21 +93,6 @@ impl RequestProxy {
     /// - `Ok(())` if the task started successfully
     /// - `Err(i32)` with the error code if starting the task failed
     ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example(task_id: i64) {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     
-    ///     match proxy.start(task_id) {
-    ///         Ok(_) => println!("Task {} started successfully", task_id),
-    ///         Err(code) => eprintln!("Failed to start task {} with error code: {}", task_id, code),
-    ///     }
-    /// }
-    /// ```
-    ///
     /// # Panics
     /// - Panics if parcel operations fail due to IPC errors
     pub(crate) fn start(&self, task_id: i64) -> Result<(), i32> {
@@ -152,21 +128,6 @@ impl RequestProxy {
     /// - `Ok(())` if the task was paused successfully
     /// - `Err(i32)` with the error code if pausing the task failed
     ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example(task_id: i64) {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     
-    ///     match proxy.pause(task_id) {
-    ///         Ok(_) => println!("Task {} paused successfully", task_id),
-    ///         Err(code) => eprintln!("Failed to pause task {} with error code: {}", task_id, code),
-    ///     }
-    /// }
-    /// ```
-    ///
     /// # Panics
     /// - Panics if parcel operations fail due to IPC errors
     pub(crate) fn pause(&self, task_id: i64) -> Result<(), i32> {
@@ -206,21 +167,6 @@ impl RequestProxy {
     /// - `Ok(())` if the task was resumed successfully
     /// - `Err(i32)` with the error code if resuming the task failed
     ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example(task_id: i64) {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     
-    ///     match proxy.resume(task_id) {
-    ///         Ok(_) => println!("Task {} resumed successfully", task_id),
-    ///         Err(code) => eprintln!("Failed to resume task {} with error code: {}", task_id, code),
-    ///     }
-    /// }
-    /// ```
-    ///
     /// # Panics
     /// - Panics if parcel operations fail due to IPC errors
     pub(crate) fn resume(&self, task_id: i64) -> Result<(), i32> {
@@ -259,24 +205,28 @@ impl RequestProxy {
     /// - `Ok(())` if the task was removed successfully
     /// - `Err(i32)` with the error code if removing the task failed
     ///
-    /// # Examples
+    /// # Panics
+    /// - Panics if parcel operations fail due to IPC errors
+    pub(crate) fn remove(&self, task_id: i64) -> Result<(), i32> {
+        self.remove_by(interface::REMOVE, task_id)
+    }
+
+    /// Removes a task, keeping it restorable for the removal grace period.
     ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
+    /// # Parameters
+    /// - `task_id`: The unique identifier of the task to remove
     ///
-    /// fn example(task_id: i64) {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     
-    ///     match proxy.remove(task_id) {
-    ///         Ok(_) => println!("Task {} removed successfully", task_id),
-    ///         Err(code) => eprintln!("Failed to remove task {} with error code: {}", task_id, code),
-    ///     }
-    /// }
-    /// ```
+    /// # Returns
+    /// - `Ok(())` if the task is pending removal
+    /// - `Err(i32)` with the error code if removing the task failed
     ///
     /// # Panics
     /// - Panics if parcel operations fail due to IPC errors
-    pub(crate) fn remove(&self, task_id: i64) -> Result<(), i32> {
+    pub(crate) fn remove_with_grace(&self, task_id: i64) -> Result<(), i32> {
+        self.remove_by(interface::REMOVE_WITH_GRACE, task_id)
+    }
+
+    fn remove_by(&self, code: u32, task_id: i64) -> Result<(), i32> {
         let remote = self.remote()?;
 
         let mut data = MsgParcel::new();
@@ -287,7 +237,7 @@ impl RequestProxy {
         data.write(&task_id.to_string()).unwrap();
 
         // Send remove request
-        let mut reply = remote.send_request(interface::REMOVE, &mut data).map_err(|_| 13400003)?;
+        let mut reply = remote.send_request(code, &mut data).map_err(|_| 13400003)?;
 
         // Check first error code
         let code = reply.read::<i32>().unwrap(); // error code
@@ -313,21 +263,6 @@ impl RequestProxy {
     /// - `Ok(())` if the task was stopped successfully
     /// - `Err(i32)` with the error code if stopping the task failed
     ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example(task_id: i64) {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     
-    ///     match proxy.stop(task_id) {
-    ///         Ok(_) => println!("Task {} stopped successfully", task_id),
-    ///         Err(code) => eprintln!("Failed to stop task {} with error code: {}", task_id, code),
-    ///     }
-    /// }
-    /// ```
-    ///
     /// # Panics
     /// - Panics if parcel operations fail due to IPC errors
     pub(crate) fn stop(&self, task_id: i64) -> Result<(), i32> {
@@ -367,23 +302,6 @@ impl RequestProxy {
     /// - `Ok(())` if the speed limit was set successfully
     /// - `Err(i32)` with the error code if setting the speed limit failed
     ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example(task_id: i64) {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     // Set maximum speed to 1024000 bytes per second (1MB/s)
-    ///     let max_speed = 1024000i64;
-    ///     
-    ///     match proxy.set_max_speed(task_id, max_speed) {
-    ///         Ok(_) => println!("Speed limit set for task {}", task_id),
-    ///         Err(code) => eprintln!("Failed to set speed limit with error code: {}", code),
-    ///     }
-    /// }
-    /// ```
-    ///
     /// # Panics
     /// - Panics if parcel operations fail due to IPC errors
     pub(crate) fn set_max_speed(&self, task_id: i64, speed: i64) -> Result<(), i32> {
@@ -414,4 +332,150 @@ impl RequestProxy {
         }
         Ok(())
     }
+
+    /// Moves the file of a paused download task to a new destination path.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the paused download task
+    /// - `path`: New destination path inside the application sandbox
+    ///
+    /// # Returns
+    /// - `Ok(())` if the file was moved and the task will resume into it
+    /// - `Err(i32)` with an error code if the task is not paused, the path is
+    ///   invalid or the file could not be moved
+    ///
+    /// # Panics
+    /// - Panics if parcel operations fail due to IPC errors
+    pub(crate) fn set_destination(&self, task_id: i64, path: &str) -> Result<(), i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+
+        data.write(&task_id.to_string()).unwrap();
+        data.write(&path.to_string()).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::SET_DESTINATION, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap(); // error code
+        if code != 0 {
+            return Err(code);
+        }
+        Ok(())
+    }
+
+    /// Restores a task pending removal to the paused state.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task pending removal
+    ///
+    /// # Returns
+    /// - `Ok(())` if the task was restored
+    /// - `Err(i32)` with an error code if the task is not pending removal
+    ///
+    /// # Panics
+    /// - Panics if parcel operations fail due to IPC errors
+    pub(crate) fn undo_remove(&self, task_id: i64) -> Result<(), i32> {
+        self.trash_request(interface::UNDO_REMOVE, task_id)
+    }
+
+    /// Removes a task pending removal without waiting for its grace period.
+    ///
+    /// # Parameters
+    /// - `task_id`: ID of the task pending removal
+    ///
+    /// # Returns
+    /// - `Ok(())` if the task was removed
+    /// - `Err(i32)` with an error code if the task is not pending removal
+    ///
+    /// # Panics
+    /// - Panics if parcel operations fail due to IPC errors
+    pub(crate) fn purge_removed(&self, task_id: i64) -> Result<(), i32> {
+        self.trash_request(interface::PURGE_REMOVED, task_id)
+    }
+
+    /// Rewrites the legacy API9 downloads of an application as API10 tasks.
+    ///
+    /// # Parameters
+    /// - `uid`: Application whose legacy tasks are migrated
+    ///
+    /// # Returns
+    /// - `Ok(MigrationReport)` with the migrated and skipped tasks
+    /// - `Err(i32)` with an error code on failure
+    ///
+    /// # Panics
+    /// - Panics if parcel operations fail due to IPC errors
+    pub(crate) fn migrate_legacy_tasks(&self, uid: u64) -> Result<MigrationReport, i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&uid).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::MIGRATE_LEGACY_TASKS, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap(); // error code
+        if code != 0 {
+            return Err(code);
+        }
+        Ok(reply.read::<MigrationReport>().unwrap())
+    }
+
+    /// Issues a signed token sharing a task of the caller with another process.
+    ///
+    /// # Parameters
+    /// - `task_id`: Unique identifier of the task to share
+    /// - `scope`: Operations the token grants
+    /// - `ttl_ms`: Lifetime of the token in milliseconds, 0 for an hour
+    ///
+    /// # Returns
+    /// - `Ok(String)` with the token
+    /// - `Err(i32)` with an error code on failure
+    ///
+    /// # Panics
+    /// - Panics if parcel operations fail due to IPC errors
+    pub(crate) fn issue_task_token(
+        &self,
+        task_id: i64,
+        scope: TokenScope,
+        ttl_ms: u64,
+    ) -> Result<String, i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&task_id.to_string()).unwrap();
+        data.write(&(scope as u32)).unwrap();
+        data.write(&ttl_ms).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::ISSUE_TASK_TOKEN, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap(); // error code
+        if code != 0 {
+            return Err(code);
+        }
+        Ok(reply.read::<String>().unwrap())
+    }
+
+    fn trash_request(&self, code: u32, task_id: i64) -> Result<(), i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&task_id.to_string()).unwrap();
+
+        let mut reply = remote.send_request(code, &mut data).map_err(|_| 13400003)?;
+
+        let code = reply.read::<i32>().unwrap(); // error code
+        if code != 0 {
+            return Err(code);
+        }
+        Ok(())
+    }
 }
@@ -23,6 +23,8 @@ use std::os::fd::{IntoRawFd, RawFd};
 
 // IPC and download core dependencies
 use ipc::parcel::MsgParcel;
+use request_core::filter::SearchFilter;
+use request_core::info::State;
 use request_core::interface;
 
 // Local dependencies
@@ -39,21 +41,6 @@ impl RequestProxy {
     /// - `Ok(File)` with the file descriptor for the communication channel if successful
     /// - `Err(i32)` with the error code if opening the channel failed
     ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example() {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     
-    ///     match proxy.open_channel() {
-    ///         Ok(file) => println!("Communication channel opened successfully"),
-    ///         Err(code) => eprintln!("Failed to open channel with error code: {}", code),
-    ///     }
-    /// }
-    /// ```
-    ///
     /// # Errors
     /// Returns an error with code from the download service if the channel cannot be opened
     ///
@@ -94,22 +81,6 @@ impl RequestProxy {
     /// - `Ok(())` if subscription was successful
     /// - `Err(i32)` with the error code if subscription failed
     ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example() {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     let task_id = "12345678".to_string();
-    ///     
-    ///     match proxy.subscribe(task_id) {
-    ///         Ok(_) => println!("Successfully subscribed to task updates"),
-    ///         Err(code) => eprintln!("Failed to subscribe with error code: {}", code),
-    ///     }
-    /// }
-    /// ```
-    ///
     /// # Errors
     /// Returns an error with code from the download service if subscription fails
     ///
@@ -140,6 +111,47 @@ impl RequestProxy {
         Ok(())
     }
 
+    /// Subscribes to updates for many download tasks in a single call.
+    ///
+    /// Each subscribed task immediately reports its current state through the
+    /// channel.
+    ///
+    /// # Parameters
+    /// - `task_ids`: The unique identifiers of the tasks to subscribe to
+    ///
+    /// # Returns
+    /// - `Ok(Vec<Result<(), i32>>)` with the result of each task, in order
+    /// - `Err(i32)` with the error code if the request itself failed
+    ///
+    /// # Panics
+    /// - Panics if parcel operations fail due to IPC errors
+    pub(crate) fn subscribe_batch(
+        &self,
+        task_ids: Vec<String>,
+    ) -> Result<Vec<Result<(), i32>>, i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&task_ids).unwrap();
+
+        let mut reply = remote
+            .send_request(interface::SUBSCRIBE_BATCH, &mut data)
+            .map_err(|_| 13400003)?;
+
+        let mut results = Vec::with_capacity(task_ids.len());
+        for task_id in task_ids.iter() {
+            let code = reply.read::<i32>().unwrap();
+            if code != 0 {
+                error!("subscribe task {} failed: {}", task_id, code);
+                results.push(Err(code));
+            } else {
+                results.push(Ok(()));
+            }
+        }
+        Ok(results)
+    }
+
     /// Unsubscribes from updates for a specific download task.
     ///
     /// Cancels the registration to receive status updates for the specified task ID.
@@ -151,22 +163,6 @@ impl RequestProxy {
     /// - `Ok(())` if unsubscription was successful
     /// - `Err(i32)` with the error code if unsubscription failed
     ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use request_next::proxy::RequestProxy;
-    ///
-    /// fn example() {
-    ///     let proxy = RequestProxy::get_instance();
-    ///     let task_id = 12345678i64;
-    ///     
-    ///     match proxy.Unsubscribe(task_id) {
-    ///         Ok(_) => println!("Successfully unsubscribed from task updates"),
-    ///         Err(code) => eprintln!("Failed to unsubscribe with error code: {}", code),
-    ///     }
-    /// }
-    /// ```
-    ///
     /// # Errors
     /// Returns an error with code from the download service if unsubscription fails
     ///
@@ -199,4 +195,91 @@ impl RequestProxy {
         }
         Ok(())
     }
+
+    /// Subscribes to changes of the caller's tasks matching a filter.
+    ///
+    /// Changes are delivered over the communication channel as task list
+    /// deltas carrying `id`. Unset time bounds of the filter are left open,
+    /// the bundle name is ignored as only the caller's tasks are listed.
+    ///
+    /// # Parameters
+    /// - `filter`: Filter selecting the listed tasks
+    /// - `id`: Subscription ID chosen by the caller
+    ///
+    /// # Returns
+    /// - `Ok(())` if subscription was successful
+    /// - `Err(i32)` with the error code if subscription failed
+    ///
+    /// # Panics
+    /// - Panics if parcel operations fail due to IPC errors
+    pub(crate) fn subscribe_task_list(&self, filter: &SearchFilter, id: u32) -> Result<(), i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        // Write interface token to identify the service
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+
+        // Write the filter followed by the subscription ID
+        data.write(&filter.before.unwrap_or(i64::MAX)).unwrap();
+        data.write(&filter.after.unwrap_or(0)).unwrap();
+        match filter.state {
+            Some(ref state) => data.write(&(state.clone() as u32)).unwrap(),
+            None => data.write(&(State::Any as u32)).unwrap(),
+        }
+        match filter.action {
+            Some(ref action) => data.write(&(action.clone() as u32)).unwrap(),
+            None => data.write(&2u32).unwrap(), // Any action
+        }
+        match filter.mode {
+            Some(mode) => data.write(&(mode as u32)).unwrap(),
+            None => data.write(&2u32).unwrap(), // Any mode
+        }
+        data.write(&id).unwrap();
+
+        // Send subscription request
+        let mut reply = remote
+            .send_request(interface::SUBSCRIBE_TASK_LIST, &mut data)
+            .map_err(|_| 13400003)?;
+
+        // Check subscription result
+        let code = reply.read::<i32>().unwrap();
+        if code != 0 {
+            error!("subscribe task list failed: {}", code);
+            return Err(code);
+        }
+        Ok(())
+    }
+
+    /// Ends a task list subscription.
+    ///
+    /// # Parameters
+    /// - `id`: Subscription ID passed to `subscribe_task_list`
+    ///
+    /// # Returns
+    /// - `Ok(())` if unsubscription was successful
+    /// - `Err(i32)` with the error code if unsubscription failed
+    ///
+    /// # Panics
+    /// - Panics if parcel operations fail due to IPC errors
+    pub(crate) fn unsubscribe_task_list(&self, id: u32) -> Result<(), i32> {
+        let remote = self.remote()?;
+
+        let mut data = MsgParcel::new();
+        // Write interface token to identify the service
+        data.write_interface_token(SERVICE_TOKEN).unwrap();
+        data.write(&id).unwrap();
+
+        // Send unsubscription request
+        let mut reply = remote
+            .send_request(interface::UNSUBSCRIBE_TASK_LIST, &mut data)
+            .map_err(|_| 13400003)?;
+
+        // Check unsubscription result
+        let code = reply.read::<i32>().unwrap();
+        if code != 0 {
+            error!("unsubscribe task list failed: {}", code);
+            return Err(code);
+        }
+        Ok(())
+    }
 }
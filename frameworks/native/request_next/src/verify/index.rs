@@ -19,7 +19,10 @@ pub struct IndexVerifier {}
 
 impl ConfigVerifier for IndexVerifier {
     fn verify(&self, config: &TaskConfig) -> Result<(), i32> {
-        if matches!(config.common_data.action, Action::Download) {
+        if matches!(
+            config.common_data.action,
+            Action::Download | Action::LocalLoopback
+        ) {
             if config.common_data.index != 0 {
                 error!("index must be 0 for download action");
                 return Err(401);
@@ -19,7 +19,10 @@ pub struct MethodVerifier {}
 
 impl ConfigVerifier for MethodVerifier {
     fn verify(&self, config: &TaskConfig) -> Result<(), i32> {
-        if matches!(config.common_data.action, Action::Download) {
+        if matches!(
+            config.common_data.action,
+            Action::Download | Action::LocalLoopback
+        ) {
             if config.method != "GET" && config.method != "POST" {
                 error!("method must be GET or POST for download action");
                 return Err(401);
@@ -0,0 +1,155 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use request_core::error_code::EXCEPTION_SERVICE;
+
+use super::*;
+
+/// Service whose channel to the client can be closed.
+#[derive(Default)]
+struct ClosableService {
+    open: AtomicBool,
+    created: AtomicU32,
+    calls: AtomicU32,
+    reopens: AtomicU32,
+}
+
+impl ClosableService {
+    fn closed() -> Self {
+        Self::default()
+    }
+
+    fn reopen(&self) -> Result<(), i32> {
+        self.reopens.fetch_add(1, Ordering::SeqCst);
+        self.open.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Creates a task, checking the channel first like the service does.
+    fn create(&self) -> Result<u32, CreateTaskError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if !self.open.load(Ordering::SeqCst) {
+            return Err(CreateTaskError::Code(CHANNEL_NOT_OPEN));
+        }
+        Ok(self.created.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    /// Subscribes to a task, which only needs the channel to be open.
+    fn subscribe(&self) -> Result<(), i32> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        match self.open.load(Ordering::SeqCst) {
+            true => Ok(()),
+            false => Err(CHANNEL_NOT_OPEN),
+        }
+    }
+}
+
+// @tc.name: ut_channel_create_recovers
+// @tc.desc: Test a task creation on a closed channel reopens it
+// @tc.precon: NA
+// @tc.step: 1. Create a task through the guard while the channel is closed
+// @tc.expect: The channel is reopened once and only the retried creation
+//             creates a task
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_channel_create_recovers() {
+    let guard = ChannelGuard::default();
+    let service = ClosableService::closed();
+    assert!(matches!(
+        guard.run(|| service.create(), || service.reopen()),
+        Ok(1)
+    ));
+    assert_eq!(service.reopens.load(Ordering::SeqCst), 1);
+    assert_eq!(service.calls.load(Ordering::SeqCst), 2);
+    assert_eq!(service.created.load(Ordering::SeqCst), 1);
+}
+
+// @tc.name: ut_channel_subscribe_recovers
+// @tc.desc: Test a subscription after the channel closed reopens it
+// @tc.precon: NA
+// @tc.step: 1. Subscribe through the guard
+//           2. Close the channel and subscribe through the guard again
+// @tc.expect: The channel is reopened for the closure only and both
+//             subscriptions succeed
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_channel_subscribe_recovers() {
+    let guard = ChannelGuard::default();
+    let service = ClosableService::closed();
+    service.reopen().unwrap();
+    guard.opened();
+    assert_eq!(
+        guard.run(|| service.subscribe(), || service.reopen()),
+        Ok(())
+    );
+    assert_eq!(service.calls.load(Ordering::SeqCst), 1);
+
+    service.open.store(false, Ordering::SeqCst);
+    assert_eq!(
+        guard.run(|| service.subscribe(), || service.reopen()),
+        Ok(())
+    );
+    assert_eq!(service.reopens.load(Ordering::SeqCst), 2);
+    assert_eq!(service.calls.load(Ordering::SeqCst), 3);
+}
+
+// @tc.name: ut_channel_reopen_failed
+// @tc.desc: Test the error of a failed reopen is returned without retrying
+// @tc.precon: NA
+// @tc.step: 1. Create a task through the guard with a failing reopen
+// @tc.expect: The reopen error is returned, the call is sent once and no
+//             task is created
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_channel_reopen_failed() {
+    let guard = ChannelGuard::default();
+    let service = ClosableService::closed();
+    assert!(matches!(
+        guard.run(|| service.create(), || Err(EXCEPTION_SERVICE)),
+        Err(CreateTaskError::Code(EXCEPTION_SERVICE))
+    ));
+    assert_eq!(service.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(service.created.load(Ordering::SeqCst), 0);
+}
+
+// @tc.name: ut_channel_reopen_once
+// @tc.desc: Test concurrent calls on a closed channel reopen it once
+// @tc.precon: NA
+// @tc.step: 1. Subscribe through the guard from several threads while the
+//              channel is closed
+// @tc.expect: Every call succeeds and the channel is reopened once
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_channel_reopen_once() {
+    let guard = Arc::new(ChannelGuard::default());
+    let service = Arc::new(ClosableService::closed());
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let guard = guard.clone();
+            let service = service.clone();
+            thread::spawn(move || guard.run(|| service.subscribe(), || service.reopen()))
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Ok(()));
+    }
+    assert_eq!(service.reopens.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,119 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use request_core::config::{TaskConfigBuilder, Version};
+use request_core::file::FileSpec;
+
+use super::*;
+
+/// Configuration of a completed download as returned by the service.
+fn completed_download() -> TaskConfig {
+    let mut config = TaskConfigBuilder::new(Version::API10).build();
+    config.url = "https://example.com/file.zip".to_string();
+    config.saveas = "./file.zip".to_string();
+    config.token = "secret".to_string();
+    config.headers = HashMap::from([("Accept".to_string(), "*/*".to_string())]);
+    config.certs_path = vec!["/data/storage/el2/base/certs".to_string()];
+    config.common_data.task_id = 1;
+    config.common_data.uid = 20020000;
+    config.common_data.token_id = 537;
+    config.file_specs = vec![FileSpec {
+        name: "file".to_string(),
+        path: "/data/storage/el2/base/files/file.zip".to_string(),
+        file_name: "file.zip".to_string(),
+        mime_type: "zip".to_string(),
+        is_user_file: false,
+        fd: Some(3),
+    }];
+    config
+}
+
+// @tc.name: ut_clone_download_saveas
+// @tc.desc: Test cloning a completed download with a new save path
+// @tc.precon: NA
+// @tc.step: 1. Clone the configuration of a completed download
+//           2. Override the save path
+// @tc.expect: The clone keeps the URL and headers, uses the new path and
+//             drops the token, file specs and ids of the original task
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_clone_download_saveas() {
+    let overrides = TaskOverrides {
+        saveas: Some("./copy.zip".to_string()),
+        headers: None,
+    };
+    let clone = prepare_clone(completed_download(), overrides);
+    assert_eq!(clone.url, "https://example.com/file.zip");
+    assert_eq!(clone.saveas, "./copy.zip");
+    assert_eq!(clone.headers.get("Accept").unwrap(), "*/*");
+    assert!(clone.token.is_empty());
+    assert!(clone.file_specs.is_empty());
+    assert!(clone.certs_path.is_empty());
+    assert_eq!(clone.common_data.task_id, 0);
+    assert_eq!(clone.common_data.uid, 0);
+    assert_eq!(clone.common_data.token_id, 0);
+}
+
+// @tc.name: ut_clone_download_defaults
+// @tc.desc: Test cloning a download without overrides
+// @tc.precon: NA
+// @tc.step: 1. Clone the configuration of a completed download
+// @tc.expect: The clone saves to the resolved path of the original task
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_clone_download_defaults() {
+    let clone = prepare_clone(completed_download(), TaskOverrides::default());
+    assert_eq!(clone.saveas, "/data/storage/el2/base/files/file.zip");
+}
+
+// @tc.name: ut_clone_headers
+// @tc.desc: Test the header override replaces the original headers
+// @tc.precon: NA
+// @tc.step: 1. Clone a download overriding its headers
+// @tc.expect: Only the overriding headers are kept
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_clone_headers() {
+    let headers = HashMap::from([("Range".to_string(), "bytes=0-".to_string())]);
+    let overrides = TaskOverrides {
+        saveas: None,
+        headers: Some(headers.clone()),
+    };
+    let clone = prepare_clone(completed_download(), overrides);
+    assert_eq!(clone.headers, headers);
+}
+
+// @tc.name: ut_clone_upload_fds
+// @tc.desc: Test cloning an upload drops the file descriptors
+// @tc.precon: NA
+// @tc.step: 1. Clone an upload of a user file opened by descriptor
+// @tc.expect: The file is kept by path without its descriptor
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_clone_upload_fds() {
+    let mut config = completed_download();
+    config.common_data.action = Action::Upload;
+    config.file_specs[0].is_user_file = true;
+    let clone = prepare_clone(config, TaskOverrides::default());
+    assert_eq!(clone.file_specs.len(), 1);
+    assert_eq!(
+        clone.file_specs[0].path,
+        "/data/storage/el2/base/files/file.zip"
+    );
+    assert_eq!(clone.file_specs[0].fd, None);
+    assert!(!clone.file_specs[0].is_user_file);
+}
@@ -0,0 +1,240 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use request_core::config::TaskConfigBuilder;
+
+use super::*;
+
+fn download() -> ExportedTask {
+    let mut builder = TaskConfigBuilder::new(Version::API10);
+    builder.segments(4).if_match("\"v1\"").keep_awake(true);
+    let mut config = builder.build();
+    config.url = "https://example.com/file.zip".to_string();
+    config.title = "file".to_string();
+    config.saveas = "/data/storage/el2/base/files/file.zip".to_string();
+    config.headers = HashMap::from([
+        ("Accept".to_string(), "*/*".to_string()),
+        ("Authorization".to_string(), "Bearer secret".to_string()),
+    ]);
+    config.extras = HashMap::from([("key".to_string(), "value".to_string())]);
+    config.common_data.mode = Mode::BackGround;
+    config.common_data.network_config = NetworkConfig::Wifi;
+    config.common_data.ends = -1;
+    config.common_data.priority = 7;
+    config.notification.title = Some("Downloading".to_string());
+    ExportedTask {
+        config,
+        total_processed: 4096,
+        sizes: vec![10_000],
+        processed: vec![4096],
+    }
+}
+
+fn upload() -> ExportedTask {
+    let mut config = TaskConfigBuilder::new(Version::API9).build();
+    config.url = "https://example.com/upload".to_string();
+    config.method = "PUT".to_string();
+    config.common_data.action = Action::Upload;
+    config.common_data.multipart = true;
+    config.form_items = vec![FormItem {
+        name: "user".to_string(),
+        value: "alice".to_string(),
+    }];
+    config.file_specs = vec![FileSpec {
+        name: "file".to_string(),
+        path: "/data/storage/el2/base/files/a.txt".to_string(),
+        file_name: "a.txt".to_string(),
+        mime_type: "text/plain".to_string(),
+        is_user_file: false,
+        fd: None,
+    }];
+    ExportedTask {
+        config,
+        total_processed: 0,
+        sizes: vec![-1],
+        processed: vec![],
+    }
+}
+
+fn assert_same(a: &ExportedTask, b: &ExportedTask) {
+    assert_eq!(a.config.url, b.config.url);
+    assert_eq!(a.config.title, b.config.title);
+    assert_eq!(a.config.method, b.config.method);
+    assert_eq!(a.config.saveas, b.config.saveas);
+    assert_eq!(a.config.headers, b.config.headers);
+    assert_eq!(a.config.extras, b.config.extras);
+    assert_eq!(a.config.version, b.config.version);
+    assert_eq!(a.config.common_data.action, b.config.common_data.action);
+    assert_eq!(a.config.common_data.mode, b.config.common_data.mode);
+    assert_eq!(
+        a.config.common_data.network_config,
+        b.config.common_data.network_config
+    );
+    assert_eq!(a.config.common_data.ends, b.config.common_data.ends);
+    assert_eq!(a.config.common_data.priority, b.config.common_data.priority);
+    assert_eq!(
+        a.config.common_data.multipart,
+        b.config.common_data.multipart
+    );
+    assert_eq!(a.config.notification.title, b.config.notification.title);
+    assert_eq!(a.config.notification.text, b.config.notification.text);
+    assert_eq!(a.config.form_items.len(), b.config.form_items.len());
+    for (x, y) in a.config.form_items.iter().zip(b.config.form_items.iter()) {
+        assert_eq!((&x.name, &x.value), (&y.name, &y.value));
+    }
+    assert_eq!(a.config.file_specs.len(), b.config.file_specs.len());
+    for (x, y) in a.config.file_specs.iter().zip(b.config.file_specs.iter()) {
+        assert_eq!((&x.name, &x.path), (&y.name, &y.path));
+        assert_eq!((&x.file_name, &x.mime_type), (&y.file_name, &y.mime_type));
+    }
+    assert_eq!(a.config.options.pairs(), b.config.options.pairs());
+    assert_eq!(a.total_processed, b.total_processed);
+    assert_eq!(a.sizes, b.sizes);
+    assert_eq!(a.processed, b.processed);
+}
+
+// @tc.name: ut_migrate_round_trip
+// @tc.desc: Test exported tasks are read back unchanged
+// @tc.precon: NA
+// @tc.step: 1. Encode a download and an upload task
+//           2. Decode the blob
+// @tc.expect: Configs, options and progress of both tasks equal the
+// originals and equal tasks give equal blobs
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_migrate_round_trip() {
+    let tasks = vec![download(), upload()];
+    let blob = encode(&tasks);
+    assert_eq!(blob[..4], EXPORT_MAGIC);
+
+    let decoded = decode(&blob).unwrap();
+    assert_eq!(decoded.len(), 2);
+    assert_same(&tasks[0], &decoded[0]);
+    assert_same(&tasks[1], &decoded[1]);
+    assert_eq!(encode(&decoded), blob);
+    assert!(decode(&encode(&[])).unwrap().is_empty());
+}
+
+// @tc.name: ut_migrate_without_options
+// @tc.desc: Test entries written before the options were exported
+// @tc.precon: NA
+// @tc.step: 1. Encode a task and cut its entry before the options
+//           2. Decode the blob
+// @tc.expect: The task is read back with no options
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_migrate_without_options() {
+    let task = upload();
+    let mut blob = encode(&[task.clone()]);
+    // The entry of a task without options ends with the option count 0.
+    blob.truncate(blob.len() - 4);
+    let len = u32::from_le_bytes(blob[12..16].try_into().unwrap()) - 4;
+    blob[12..16].copy_from_slice(&len.to_le_bytes());
+
+    let decoded = decode(&blob).unwrap();
+    assert_same(&task, &decoded[0]);
+    assert!(decoded[0].config.options.pairs().is_empty());
+}
+
+// @tc.name: ut_migrate_redact_headers
+// @tc.desc: Test sensitive headers are dropped unless included
+// @tc.precon: NA
+// @tc.step: 1. Redact headers without and with an included sensitive header
+// @tc.expect: Sensitive headers are dropped case-insensitively, included
+// ones and ordinary headers are kept
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_migrate_redact_headers() {
+    let headers = HashMap::from([
+        ("Accept".to_string(), "*/*".to_string()),
+        ("authorization".to_string(), "Bearer secret".to_string()),
+        ("COOKIE".to_string(), "session=1".to_string()),
+    ]);
+
+    let mut redacted = headers.clone();
+    redact_headers(&mut redacted, &[]);
+    assert_eq!(redacted.len(), 1);
+    assert!(redacted.contains_key("Accept"));
+
+    let mut redacted = headers.clone();
+    redact_headers(&mut redacted, &["Cookie".to_string()]);
+    assert_eq!(redacted.len(), 2);
+    assert!(redacted.contains_key("COOKIE"));
+}
+
+// @tc.name: ut_migrate_invalid_blob
+// @tc.desc: Test malformed blobs are rejected and unknown trailing fields
+// skipped
+// @tc.precon: NA
+// @tc.step: 1. Decode blobs with a wrong header, a newer version, truncated
+//              content and an unknown action
+//           2. Decode a blob whose entry carries extra trailing bytes
+// @tc.expect: Malformed blobs fail with a parameter error, the extra bytes
+// are ignored
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_migrate_invalid_blob() {
+    let blob = encode(&[download()]);
+
+    assert_eq!(decode(b"XXXX").unwrap_err(), PARAMETER_CHECK);
+    let mut newer = blob.clone();
+    newer[4..8].copy_from_slice(&(EXPORT_VERSION + 1).to_le_bytes());
+    assert_eq!(decode(&newer).unwrap_err(), PARAMETER_CHECK);
+    for len in [3, 10, blob.len() - 1] {
+        assert_eq!(decode(&blob[..len]).unwrap_err(), PARAMETER_CHECK);
+    }
+    let mut action = blob.clone();
+    // Header, count and entry length precede the action.
+    action[16..20].copy_from_slice(&9u32.to_le_bytes());
+    assert_eq!(decode(&action).unwrap_err(), PARAMETER_CHECK);
+
+    let mut entry = encode_task(&download());
+    entry.extend_from_slice(b"future");
+    let mut extended = EXPORT_MAGIC.to_vec();
+    put_u32(&mut extended, EXPORT_VERSION);
+    put_u32(&mut extended, 1);
+    put_u32(&mut extended, entry.len() as u32);
+    extended.extend(entry);
+    let decoded = decode(&extended).unwrap();
+    assert_same(&download(), &decoded[0]);
+}
+
+// @tc.name: ut_migrate_reconcile_partial_file
+// @tc.desc: Test the partial file of an imported download is cut back to the
+// exported progress
+// @tc.precon: NA
+// @tc.step: 1. Reconcile a file longer and a file shorter than the progress
+// @tc.expect: The longer file is cut to the progress, the shorter one is kept
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_migrate_reconcile_partial_file() {
+    let dir = std::env::temp_dir().join("ut_migrate_reconcile_partial_file");
+    std::fs::create_dir_all(&dir).unwrap();
+    let longer = dir.join("longer");
+    let shorter = dir.join("shorter");
+    std::fs::write(&longer, vec![1u8; 8192]).unwrap();
+    std::fs::write(&shorter, vec![1u8; 1024]).unwrap();
+
+    reconcile_partial_file(&longer, 4096).unwrap();
+    reconcile_partial_file(&shorter, 4096).unwrap();
+
+    assert_eq!(std::fs::metadata(&longer).unwrap().len(), 4096);
+    assert_eq!(std::fs::metadata(&shorter).unwrap().len(), 1024);
+    assert!(reconcile_partial_file(&dir.join("missing"), 4096).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
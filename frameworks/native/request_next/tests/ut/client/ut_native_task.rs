@@ -0,0 +1,231 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use request_core::config::{Action, Mode, TaskConfigBuilder, Version};
+
+use super::*;
+use crate::file::FileManager;
+
+struct NopGranter;
+
+impl crate::file::Granter for NopGranter {
+    fn grant(&self, _path: &str, _permission: &str) -> Result<(), i32> {
+        Ok(())
+    }
+}
+
+fn granted_task(path: &PathBuf) -> NativeTask {
+    let manager = &FileManager::get_instance().permission_manager;
+    manager.set_granter(Box::new(NopGranter));
+    let token = manager.grant(path).unwrap();
+    NativeTask {
+        config: TaskConfigBuilder::new(Version::API10).build(),
+        token: Mutex::new(vec![token]),
+        certs: Mutex::new(None),
+        body_files: Mutex::new(vec![]),
+    }
+}
+
+// @tc.name: ut_native_task_revoke_on_complete
+// @tc.desc: Test permission tokens are revoked once a task completes
+// @tc.precon: NA
+// @tc.step: 1. Grant a path and bind the token to a task
+//           2. Revoke the task as done on the complete notification
+// @tc.expect: The path is no longer granted while the task is still known
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_native_task_revoke_on_complete() {
+    let path = PathBuf::from("/data/storage/el2/base/ut_native_task_revoke_on_complete");
+    let manager = NativeTaskManager::default();
+    manager.insert(1, granted_task(&path));
+    manager.bind(100, 1);
+    assert!(FileManager::get_instance().permission_manager.is_granted(&path));
+
+    manager.revoke(&100);
+    assert!(!FileManager::get_instance().permission_manager.is_granted(&path));
+    assert!(manager.get_by_id(&100).is_some());
+}
+
+// @tc.name: ut_native_task_sweep
+// @tc.desc: Test the sweep revokes tokens of tasks no longer present
+// @tc.precon: NA
+// @tc.step: 1. Grant paths for two bound tasks
+//           2. Sweep with only one task reported alive
+// @tc.expect: Only the missing task is swept and its path revoked
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_native_task_sweep() {
+    let alive = PathBuf::from("/data/storage/el2/base/ut_native_task_sweep_alive");
+    let gone = PathBuf::from("/data/storage/el2/base/ut_native_task_sweep_gone");
+    let manager = NativeTaskManager::default();
+    manager.insert(1, granted_task(&alive));
+    manager.bind(200, 1);
+    manager.insert(2, granted_task(&gone));
+    manager.bind(201, 2);
+
+    assert_eq!(manager.sweep(|task_id| task_id == 200), 1);
+    assert!(FileManager::get_instance().permission_manager.is_granted(&alive));
+    assert!(!FileManager::get_instance().permission_manager.is_granted(&gone));
+    assert!(manager.get_by_id(&201).is_none());
+}
+
+fn pending_task(action: Action) -> NativeTask {
+    let mut config = TaskConfigBuilder::new(Version::API10).build();
+    config.common_data.action = action;
+    NativeTask {
+        config,
+        token: Mutex::new(vec![]),
+        certs: Mutex::new(None),
+        body_files: Mutex::new(vec![]),
+    }
+}
+
+fn listed(task_id: &str) -> ListedTask {
+    ListedTask {
+        task_id: Some(task_id.to_string()),
+        seq: None,
+        pending: false,
+    }
+}
+
+fn pending_seqs(manager: &NativeTaskManager) -> Vec<u64> {
+    manager.pending().into_iter().map(|(seq, _)| seq).collect()
+}
+
+// @tc.name: ut_native_task_list_pending
+// @tc.desc: Test tasks being constructed are listed before, during and after
+// they are bound
+// @tc.precon: NA
+// @tc.step: 1. Insert a task and merge search results before it is bound
+//           2. Bind the task between the snapshot and the search
+//           3. Bind the task before the search
+// @tc.expect: The task is listed once, as pending before the bind and by its
+// task ID afterwards, whether or not the search saw it
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_native_task_list_pending() {
+    let manager = NativeTaskManager::default();
+    manager.insert(1, pending_task(Action::Download));
+    let pending = pending_seqs(&manager);
+    assert_eq!(pending, vec![1]);
+
+    let before = manager.merge_pending(pending.clone(), vec!["5".to_string()]);
+    assert_eq!(
+        before,
+        vec![
+            listed("5"),
+            ListedTask {
+                task_id: None,
+                seq: Some(1),
+                pending: true,
+            }
+        ]
+    );
+
+    manager.bind(300, 1);
+    assert!(pending_seqs(&manager).is_empty());
+    let during = manager.merge_pending(pending.clone(), vec!["5".to_string()]);
+    assert_eq!(during, vec![listed("5"), listed("300")]);
+
+    let after = manager.merge_pending(pending, vec!["5".to_string(), "300".to_string()]);
+    assert_eq!(after, vec![listed("5"), listed("300")]);
+}
+
+// @tc.name: ut_native_task_list_failed
+// @tc.desc: Test a task whose creation failed is no longer listed
+// @tc.precon: NA
+// @tc.step: 1. Insert a task and take the pending snapshot
+//           2. Remove the task as done on a failed creation and merge
+// @tc.expect: The task is neither pending nor listed
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_native_task_list_failed() {
+    let manager = NativeTaskManager::default();
+    manager.insert(2, pending_task(Action::Download));
+    let pending = pending_seqs(&manager);
+    manager.remove(&2);
+    assert!(manager.pending().is_empty());
+    assert!(manager.merge_pending(pending, vec![]).is_empty());
+}
+
+// @tc.name: ut_native_task_pending_filter
+// @tc.desc: Test pending tasks are matched against the search filter
+// @tc.precon: NA
+// @tc.step: 1. Match a pending download against filters on action, mode and
+//              state
+// @tc.expect: Only filters an initialized download matches are accepted
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_native_task_pending_filter() {
+    let task = pending_task(Action::Download);
+    let mut filter = SearchFilter::new();
+    assert!(task.matches(&filter));
+
+    filter.action = Some(Action::Upload);
+    assert!(!task.matches(&filter));
+    filter.action = Some(Action::Download);
+    assert!(task.matches(&filter));
+
+    filter.mode = Some(task.config.common_data.mode);
+    filter.state = Some(State::Initialized);
+    assert!(task.matches(&filter));
+    filter.state = Some(State::Running);
+    assert!(!task.matches(&filter));
+
+    filter.state = None;
+    filter.mode = Some(match task.config.common_data.mode {
+        Mode::BackGround => Mode::FrontEnd,
+        Mode::FrontEnd => Mode::BackGround,
+    });
+    assert!(!task.matches(&filter));
+}
+
+// @tc.name: ut_native_task_body_files
+// @tc.desc: Test body files are deleted with tasks that were never bound
+// @tc.precon: NA
+// @tc.step: 1. Insert two tasks owning a body file each
+//           2. Bind the first task, remove both tasks
+// @tc.expect: The body file of the unbound task is deleted, the one of the
+// bound task is kept for the service
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_native_task_body_files() {
+    let dir = std::env::temp_dir().join("ut_native_task_body_files");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let bound = dir.join("tmp_body_0_1");
+    let unbound = dir.join("tmp_body_0_2");
+
+    let manager = NativeTaskManager::default();
+    for (seq, path) in [(3, &bound), (4, &unbound)] {
+        std::fs::write(path, "body").unwrap();
+        let task = pending_task(Action::Upload);
+        task.body_files.lock().unwrap().push(path.clone());
+        manager.insert(seq, task);
+    }
+    manager.bind(400, 3);
+
+    manager.remove(&4);
+    assert!(!unbound.exists());
+    manager.remove_task(&400);
+    assert!(bound.exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
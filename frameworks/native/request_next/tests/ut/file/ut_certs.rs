@@ -0,0 +1,141 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::thread;
+
+use super::*;
+
+/// Creates a fresh test directory and a certs store rooted inside it.
+fn store(name: &str) -> (PathBuf, Arc<CertsStore>) {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let store = Arc::new(CertsStore::new(dir.join("certs")));
+    (dir, store)
+}
+
+/// Creates a source folder holding `files` as `(name, content)` pairs.
+fn source(dir: &Path, name: &str, files: &[(&str, &str)]) -> String {
+    let folder = dir.join(name);
+    fs::create_dir_all(&folder).unwrap();
+    for (file, content) in files {
+        fs::write(folder.join(file), content).unwrap();
+    }
+    folder.to_string_lossy().to_string()
+}
+
+fn contents(certs: &CertsDir) -> Vec<String> {
+    let mut contents: Vec<String> = certs
+        .files()
+        .iter()
+        .map(|file| fs::read_to_string(file).unwrap())
+        .collect();
+    contents.sort();
+    contents
+}
+
+// @tc.name: ut_certs_concurrent_isolation
+// @tc.desc: Test concurrent applies for different hosts get separate
+// directories that are removed after use
+// @tc.precon: NA
+// @tc.step: 1. Acquire certs directories for two sources with the same file
+//              names but different anchors from two threads
+//           2. Drop both directories
+// @tc.expect: Each directory holds only the anchors of its own source and
+// both are removed once dropped
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_certs_concurrent_isolation() {
+    let (dir, store) = store("ut_certs_concurrent_isolation");
+    let a = source(&dir, "a", &[("ca.pem", "anchor a")]);
+    let b = source(&dir, "b", &[("ca.pem", "anchor b")]);
+
+    let handles: Vec<_> = [a, b]
+        .into_iter()
+        .map(|folder| {
+            let store = store.clone();
+            thread::spawn(move || store.acquire(&[folder]).unwrap().unwrap())
+        })
+        .collect();
+    let certs: Vec<CertsDir> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    assert_ne!(certs[0].path(), certs[1].path());
+    assert_eq!(contents(&certs[0]), vec!["anchor a"]);
+    assert_eq!(contents(&certs[1]), vec!["anchor b"]);
+
+    let paths: Vec<PathBuf> = certs.iter().map(|c| c.path().to_path_buf()).collect();
+    drop(certs);
+    assert!(paths.iter().all(|path| !path.exists()));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+// @tc.name: ut_certs_dedupe
+// @tc.desc: Test identical anchors are stored once and shared directories
+// live until their last user is dropped
+// @tc.precon: NA
+// @tc.step: 1. Acquire a directory for two sources holding the same anchor
+//           2. Acquire again for the same anchor and drop the directories
+//              one after the other
+// @tc.expect: The anchor is stored once, both tasks share the directory and
+// it is removed only after the second drop
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_certs_dedupe() {
+    let (dir, store) = store("ut_certs_dedupe");
+    let a = source(&dir, "a", &[("ca.pem", "anchor"), ("other.pem", "other")]);
+    let b = source(&dir, "b", &[("copy.pem", "anchor")]);
+
+    let first = store.acquire(&[a.clone(), b]).unwrap().unwrap();
+    assert_eq!(contents(&first), vec!["anchor", "other"]);
+
+    let second = store.acquire(&[a]).unwrap().unwrap();
+    assert_eq!(first.path(), second.path());
+
+    let path = first.path().to_path_buf();
+    drop(first);
+    assert!(path.exists());
+    drop(second);
+    assert!(!path.exists());
+
+    assert!(store.acquire(&[]).unwrap().is_none());
+    assert!(store
+        .acquire(&[dir.join("missing").to_string_lossy().to_string()])
+        .is_err());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+// @tc.name: ut_certs_stable_names
+// @tc.desc: Test certificate files and directories get the same names in
+// every release
+// @tc.precon: NA
+// @tc.step: 1. Acquire a directory for a source holding one anchor
+// @tc.expect: The file and the directory are named by the SHA-256 of the
+// anchor content and of the file set
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_certs_stable_names() {
+    let (dir, store) = store("ut_certs_stable_names");
+    let a = source(&dir, "a", &[("ca.pem", "a")]);
+
+    let certs = store.acquire(&[a]).unwrap().unwrap();
+    assert!(certs
+        .path()
+        .ends_with("4bda7863cd784a7927e6ccd078526b051b576c6623fe22b1ca82f4e59c9938f1"));
+    assert!(certs.files()[0]
+        .ends_with("ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb.pem"));
+    drop(certs);
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,85 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use super::*;
+
+struct NopGranter;
+
+impl Granter for NopGranter {
+    fn grant(&self, _path: &str, _permission: &str) -> Result<(), i32> {
+        Ok(())
+    }
+}
+
+fn capped_manager(max_tokens: usize) -> PermissionManager {
+    let manager = PermissionManager::new();
+    manager.set_granter(Box::new(NopGranter));
+    manager.set_max_tokens(max_tokens);
+    manager
+}
+
+fn path(name: &str) -> PathBuf {
+    PathBuf::from(format!("/data/storage/el2/base/{}", name))
+}
+
+// @tc.name: ut_permission_cap_evicts_oldest
+// @tc.desc: Test granting beyond the cap revokes the oldest token
+// @tc.precon: NA
+// @tc.step: 1. Create a manager capped at two tokens
+//           2. Grant three paths
+// @tc.expect: The first path is revoked, the other two stay granted and two
+// tokens are counted
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_permission_cap_evicts_oldest() {
+    let manager = capped_manager(2);
+    let _first = manager.grant(&path("ut_permission_cap_first")).unwrap();
+    let _second = manager.grant(&path("ut_permission_cap_second")).unwrap();
+    let _third = manager.grant(&path("ut_permission_cap_third")).unwrap();
+
+    assert!(!manager.is_granted(&path("ut_permission_cap_first")));
+    assert!(manager.is_granted(&path("ut_permission_cap_second")));
+    assert!(manager.is_granted(&path("ut_permission_cap_third")));
+    assert_eq!(manager.token_count(), 2);
+}
+
+// @tc.name: ut_permission_cap_least_recently_used
+// @tc.desc: Test granting a path again keeps its tokens from eviction
+// @tc.precon: NA
+// @tc.step: 1. Create a manager capped at two tokens
+//           2. Grant a path, another path, then the first path again
+// @tc.expect: The second path is revoked as the least recently used one and
+// a revoked token no longer changes the grants once dropped
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_permission_cap_least_recently_used() {
+    let manager = capped_manager(2);
+    let used = path("ut_permission_lru_used");
+    let idle = path("ut_permission_lru_idle");
+    let first = manager.grant(&used).unwrap();
+    let evicted = manager.grant(&idle).unwrap();
+    let second = manager.grant(&used).unwrap();
+
+    assert!(manager.is_granted(&used));
+    assert!(!manager.is_granted(&idle));
+    manager.revoke(evicted.id, &evicted.path);
+    manager.revoke(first.id, &first.path);
+    assert!(manager.is_granted(&used));
+    manager.revoke(second.id, &second.path);
+    assert!(!manager.is_granted(&used));
+    assert_eq!(manager.token_count(), 0);
+}
@@ -0,0 +1,150 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use super::*;
+
+/// Callback counting the redirects it receives.
+#[derive(Default)]
+struct Counter {
+    count: AtomicUsize,
+}
+
+impl Callback for Counter {
+    fn on_redirect(&self, _task_id: i64, _from_url: &str, _to_url: &str, _status: u32) {
+        self.count.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Dispatches a redirect of a task the way the listener does.
+fn dispatch(observer: &Observer, task_id: i64) -> bool {
+    match lookup(&observer.callbacks, task_id) {
+        Some(callback) => {
+            callback.on_redirect(task_id, "http://a", "http://b", 302);
+            true
+        }
+        None => false,
+    }
+}
+
+// @tc.name: ut_observe_registration_drop
+// @tc.desc: Test dropping a registration unregisters its callback only
+// @tc.precon: NA
+// @tc.step: 1. Register a scoped callback and dispatch an event
+//           2. Drop the registration and dispatch an event
+//           3. Replace a scoped callback and drop the first registration
+// @tc.expect: Events are dispatched until the registration is dropped, a
+// stale registration leaves the callback that replaced it registered
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_observe_registration_drop() {
+    let observer = Observer::new();
+    let counter = Arc::new(Counter::default());
+
+    let registration = observer.register_scoped_callback(1, counter.clone());
+    assert_eq!(registration.task_id(), 1);
+    assert!(dispatch(&observer, 1));
+    drop(registration);
+    assert!(!dispatch(&observer, 1));
+    assert_eq!(counter.count.load(Ordering::SeqCst), 1);
+
+    let stale = observer.register_scoped_callback(2, counter.clone());
+    let current = observer.register_scoped_callback(2, counter.clone());
+    drop(stale);
+    assert!(dispatch(&observer, 2));
+    drop(current);
+    assert!(!dispatch(&observer, 2));
+}
+
+// @tc.name: ut_observe_weak_prune
+// @tc.desc: Test weak callbacks are pruned once their owner drops them
+// @tc.precon: NA
+// @tc.step: 1. Register a weak callback and dispatch an event
+//           2. Drop the callback and dispatch an event
+// @tc.expect: The callback receives the first event, the second one finds
+// no callback and prunes the registration
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_observe_weak_prune() {
+    let observer = Observer::new();
+    let counter = Arc::new(Counter::default());
+    let weak: Weak<dyn Callback + Send + Sync> = Arc::downgrade(&counter) as _;
+    let registration = observer.register_weak_callback(1, weak);
+
+    assert!(dispatch(&observer, 1));
+    assert_eq!(counter.count.load(Ordering::SeqCst), 1);
+    drop(counter);
+    assert!(!dispatch(&observer, 1));
+    assert!(observer.callbacks.lock().unwrap().entries.is_empty());
+    drop(registration);
+}
+
+/// Callback unregistering itself while it handles an event.
+struct Unregistering {
+    observer: Arc<Observer>,
+}
+
+impl Callback for Unregistering {
+    fn on_redirect(&self, task_id: i64, _from_url: &str, _to_url: &str, _status: u32) {
+        self.observer.unregister_callback(task_id);
+    }
+}
+
+// @tc.name: ut_observe_concurrent_unregister
+// @tc.desc: Test unregistering during dispatch neither deadlocks nor panics
+// @tc.precon: NA
+// @tc.step: 1. Dispatch to a callback unregistering itself
+//           2. Dispatch events while other threads register and unregister
+//              the callbacks of the same tasks
+// @tc.expect: The self unregistering callback is removed, all threads finish
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_observe_concurrent_unregister() {
+    let observer = Arc::new(Observer::new());
+    observer.register_callback(
+        1,
+        Arc::new(Unregistering {
+            observer: observer.clone(),
+        }),
+    );
+    assert!(dispatch(&observer, 1));
+    assert!(!dispatch(&observer, 1));
+
+    let counter = Arc::new(Counter::default());
+    let mut handles = vec![];
+    for task_id in 0..4 {
+        let observer = observer.clone();
+        handles.push(thread::spawn(move || {
+            for _ in 0..1000 {
+                dispatch(&observer, task_id);
+            }
+        }));
+        let observer = observer.clone();
+        let counter = counter.clone();
+        handles.push(thread::spawn(move || {
+            for _ in 0..1000 {
+                let registration = observer.register_scoped_callback(task_id, counter.clone());
+                observer.unregister_callback(task_id);
+                drop(registration);
+            }
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,288 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use request_core::info::{State, HEADERS_TRUNCATED};
+
+use super::*;
+
+/// Serializes a running progress notification of task 7 the way the
+/// service does, up to the end of the file statuses.
+fn notify_data_body() -> Vec<u8> {
+    let mut message = vec![];
+    // Subscribe type, task ID, state and index
+    for value in [4u32, 7, State::Running as u32, 0] {
+        message.extend_from_slice(&value.to_le_bytes());
+    }
+    // Processed and total processed
+    message.extend_from_slice(&100u64.to_le_bytes());
+    message.extend_from_slice(&100u64.to_le_bytes());
+    // Sizes
+    message.extend_from_slice(&1u32.to_le_bytes());
+    message.extend_from_slice(&1000i64.to_le_bytes());
+    // Extras
+    message.extend_from_slice(&1u32.to_le_bytes());
+    message.extend_from_slice(b"key\0value\0");
+    // Action and version
+    message.extend_from_slice(&0u32.to_le_bytes());
+    message.extend_from_slice(&1u32.to_le_bytes());
+    // File statuses
+    message.extend_from_slice(&1u32.to_le_bytes());
+    message.push(b'\0');
+    message.extend_from_slice(&0u32.to_le_bytes());
+    message.extend_from_slice(b"ok\0");
+    message
+}
+
+fn extension(eta_ms: Option<u64>, body: Option<(&[u8], u8)>) -> Vec<u8> {
+    let mut message = 1u32.to_le_bytes().to_vec();
+    message.push(eta_ms.is_some() as u8);
+    message.extend_from_slice(&eta_ms.unwrap_or(0).to_le_bytes());
+    match body {
+        Some((data, flags)) => {
+            message.push(1);
+            message.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            message.push(flags);
+            message.extend_from_slice(data);
+        }
+        None => message.push(0),
+    }
+    message
+}
+
+fn client_tag(tag: &str) -> Vec<u8> {
+    let mut message = (tag.len() as u32).to_le_bytes().to_vec();
+    message.extend_from_slice(tag.as_bytes());
+    message
+}
+
+// @tc.name: ut_uds_notify_data_eta
+// @tc.desc: Test notification data messages carrying the extension block
+// @tc.precon: NA
+// @tc.step: 1. Serialize notification data followed by extension blocks with
+//              and without remaining time and response body
+//           2. Read the messages
+// @tc.expect: The remaining time and the response body with its flags are
+// read from the extension block and the whole message is consumed
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_uds_notify_data_eta() {
+    let mut message = notify_data_body();
+    message.extend(extension(Some(180_000), None));
+    let mut uds = UdsSer::new(&message);
+    let (notify_data, body) = read_notify_data(&mut uds);
+    assert_eq!(notify_data.task_id, 7);
+    assert_eq!(notify_data.progress.total_processed, 100);
+    assert_eq!(notify_data.progress.sizes, vec![1000]);
+    assert_eq!(notify_data.task_states[0].message, "ok");
+    assert_eq!(notify_data.progress.eta_ms, Some(180_000));
+    assert!(body.is_none());
+    assert!(uds.is_empty());
+
+    let mut message = notify_data_body();
+    message.extend(extension(None, Some((b"done", 1))));
+    let mut uds = UdsSer::new(&message);
+    let (notify_data, body) = read_notify_data(&mut uds);
+    assert_eq!(notify_data.progress.eta_ms, None);
+    assert_eq!(
+        body,
+        Some(InlineBody {
+            body: ResponseBody {
+                data: b"done".to_vec(),
+                truncated: true,
+            },
+            partial: false,
+        })
+    );
+    assert!(uds.is_empty());
+
+    let mut message = notify_data_body();
+    message.extend(extension(None, Some((b"do", 2))));
+    let mut uds = UdsSer::new(&message);
+    let (_, body) = read_notify_data(&mut uds);
+    let body = body.unwrap();
+    assert!(body.partial);
+    assert!(!body.body.truncated);
+    assert_eq!(body.body.data, b"do");
+}
+
+// @tc.name: ut_uds_notify_data_legacy
+// @tc.desc: Test notification data messages without the extension block
+// @tc.precon: NA
+// @tc.step: 1. Serialize notification data without an extension block
+//           2. Read the message
+// @tc.expect: The message is read without remaining time or response body
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_uds_notify_data_legacy() {
+    let message = notify_data_body();
+    let mut uds = UdsSer::new(&message);
+    let (notify_data, body) = read_notify_data(&mut uds);
+    assert_eq!(notify_data.task_id, 7);
+    assert_eq!(notify_data.progress.eta_ms, None);
+    assert!(body.is_none());
+}
+
+// @tc.name: ut_uds_notify_data_client_tag
+// @tc.desc: Test notification data messages carrying the client tag
+// @tc.precon: NA
+// @tc.step: 1. Serialize notification data followed by a version 2
+//              extension block ending with a client tag
+//           2. Read the message
+// @tc.expect: The client tag is read and the whole message is consumed,
+// version 1 blocks are read with an empty tag
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_uds_notify_data_client_tag() {
+    let mut message = notify_data_body();
+    let mut ext = extension(None, Some((b"done", 0)));
+    ext[..4].copy_from_slice(&2u32.to_le_bytes());
+    message.extend(ext);
+    message.extend(client_tag("order-42"));
+    let mut uds = UdsSer::new(&message);
+    let (notify_data, body) = read_notify_data(&mut uds);
+    assert_eq!(notify_data.client_tag, "order-42");
+    assert_eq!(body.unwrap().body.data, b"done".to_vec());
+    assert!(uds.is_empty());
+
+    let mut message = notify_data_body();
+    message.extend(extension(Some(1000), None));
+    let mut uds = UdsSer::new(&message);
+    let (notify_data, _) = read_notify_data(&mut uds);
+    assert_eq!(notify_data.client_tag, "");
+    assert!(uds.is_empty());
+}
+
+// @tc.name: ut_uds_faults_client_tag
+// @tc.desc: Test fault messages with and without the trailing client tag
+// @tc.precon: NA
+// @tc.step: 1. Serialize a fault followed by a client tag
+//           2. Serialize a fault without a client tag
+//           3. Read both messages
+// @tc.expect: The tag is read when present and empty otherwise
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_uds_faults_client_tag() {
+    let mut message = vec![];
+    for value in [7u32, 3, 0] {
+        message.extend_from_slice(&value.to_le_bytes());
+    }
+    let legacy = message.clone();
+    message.extend(client_tag("order-42"));
+
+    let mut uds = UdsSer::new(&message);
+    let fault: FaultOccur = uds.read();
+    assert_eq!(fault.task_id, 7);
+    assert_eq!(fault.client_tag, "order-42");
+    assert!(uds.is_empty());
+
+    let mut uds = UdsSer::new(&legacy);
+    let fault: FaultOccur = uds.read();
+    assert_eq!(fault.task_id, 7);
+    assert_eq!(fault.client_tag, "");
+}
+
+fn redirect(task_id: u32, status: u32, from_url: &str, to_url: &str) -> Vec<u8> {
+    let mut message = vec![];
+    message.extend_from_slice(&task_id.to_le_bytes());
+    message.extend_from_slice(&status.to_le_bytes());
+    message.extend_from_slice(from_url.as_bytes());
+    message.push(b'\0');
+    message.extend_from_slice(to_url.as_bytes());
+    message.push(b'\0');
+    message
+}
+
+// @tc.name: ut_uds_redirect_chain
+// @tc.desc: Test the redirect messages of a multi-hop chain are read in order
+// @tc.precon: NA
+// @tc.step: 1. Serialize the redirects of a three hop chain
+//           2. Read the redirects one after the other
+// @tc.expect: Each hop is read with its URLs and status in the chain order
+// and the whole message is consumed
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_uds_redirect_chain() {
+    let chain = [
+        (301, "http://example.com/a", "https://example.com/a"),
+        (302, "https://example.com/a", "https://cdn.example.com/a"),
+        (307, "https://cdn.example.com/a", "https://edge.example/a"),
+    ];
+    let mut message = vec![];
+    for (status, from_url, to_url) in chain {
+        message.extend(redirect(7, status, from_url, to_url));
+    }
+
+    let mut uds = UdsSer::new(&message);
+    for (status, from_url, to_url) in chain {
+        let redirect: Redirect = uds.read();
+        assert_eq!(
+            redirect,
+            Redirect {
+                task_id: 7,
+                status,
+                from_url: from_url.to_string(),
+                to_url: to_url.to_string(),
+            }
+        );
+    }
+    assert!(uds.is_empty());
+}
+
+fn response_message(headers: &str) -> Vec<u8> {
+    let mut message = vec![];
+    message.extend_from_slice(&7u32.to_le_bytes());
+    message.extend_from_slice(b"HTTP/1.1\0");
+    message.extend_from_slice(&200u32.to_le_bytes());
+    message.extend_from_slice(b"OK\0");
+    message.extend_from_slice(headers.as_bytes());
+    message
+}
+
+// @tc.name: ut_uds_response_truncated
+// @tc.desc: Test the truncation marker of a response is reported as a flag
+// @tc.precon: NA
+// @tc.step: 1. Serialize a response keeping 5 of 200 headers with the
+//              truncation marker
+//           2. Serialize a response without the marker
+//           3. Read both responses
+// @tc.expect: The first response is truncated with the kept headers only,
+// the second is not truncated
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_uds_response_truncated() {
+    let mut headers = String::from("content-type:text/plain\ncontent-length:1024\n");
+    for i in 0..3 {
+        headers.push_str(&format!("x-custom-{:03}:{}\n", i, "v".repeat(64)));
+    }
+    let truncated = format!("{}x-request-headers-truncated:195\n", headers);
+
+    let mut uds = UdsSer::new(&response_message(&truncated));
+    let response: Response = uds.read();
+    assert_eq!(response.task_id, "7");
+    assert_eq!(response.status_code, 200);
+    assert!(response.truncated);
+    assert_eq!(response.headers.len(), 5);
+    assert_eq!(response.headers["content-length"], vec!["1024"]);
+    assert!(!response.headers.contains_key(HEADERS_TRUNCATED));
+
+    let mut uds = UdsSer::new(&response_message(&headers));
+    let response: Response = uds.read();
+    assert!(!response.truncated);
+    assert_eq!(response.headers.len(), 5);
+}
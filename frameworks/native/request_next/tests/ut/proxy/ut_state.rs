@@ -0,0 +1,179 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::*;
+
+/// Loader stub whose loads fail until it is told to succeed.
+#[derive(Default)]
+struct Stub {
+    succeed: AtomicBool,
+    loads: AtomicUsize,
+}
+
+/// Creates a keeper loading through `stub`.
+fn keeper(stub: &Arc<Stub>) -> SaKeeper<u32> {
+    let stub = stub.clone();
+    SaKeeper::new(Box::new(move || {
+        stub.loads.fetch_add(1, Ordering::SeqCst);
+        stub.succeed.load(Ordering::SeqCst).then_some(7)
+    }))
+}
+
+/// Lets the next load happen without waiting for the backoff.
+fn expire(keeper: &SaKeeper<u32>) {
+    if let SaState::Invalid { retry_at, .. } = &mut *keeper.state.lock().unwrap() {
+        *retry_at = Instant::now();
+    }
+}
+
+/// Asserts `delay` is within the jitter bounds of `nominal`.
+fn assert_jittered(delay: Duration, nominal: Duration) {
+    assert!(
+        delay >= nominal * 80 / 100,
+        "{:?} < 0.8 * {:?}",
+        delay,
+        nominal
+    );
+    assert!(
+        delay <= nominal * 120 / 100,
+        "{:?} > 1.2 * {:?}",
+        delay,
+        nominal
+    );
+}
+
+// @tc.name: ut_state_backoff_schedule
+// @tc.desc: Test the backoff doubles from one second and caps at a minute
+// @tc.precon: NA
+// @tc.step: 1. Compute the backoff for an increasing number of failures
+// @tc.expect: The backoff is 1s, 2s, 4s ... and never exceeds 60s
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_state_backoff_schedule() {
+    let expected = [1, 2, 4, 8, 16, 32, 60, 60];
+    for (i, secs) in expected.iter().enumerate() {
+        assert_eq!(backoff(i as u32 + 1), Duration::from_secs(*secs));
+    }
+    assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+}
+
+// @tc.name: ut_state_jitter_bounds
+// @tc.desc: Test the jitter stays within 20 percent of the delay
+// @tc.precon: NA
+// @tc.step: 1. Apply the jitter with the extreme and random values
+// @tc.expect: The jittered delay spans exactly 0.8x to 1.2x of the delay
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_state_jitter_bounds() {
+    let delay = Duration::from_secs(10);
+    assert_eq!(jitter(delay, 0), Duration::from_secs(8));
+    assert_eq!(jitter(delay, 40), Duration::from_secs(12));
+    assert_eq!(jitter(delay, 20), delay);
+    for _ in 0..100 {
+        assert_jittered(jitter(delay, fast_random()), delay);
+    }
+}
+
+// @tc.name: ut_state_repeated_failures
+// @tc.desc: Test repeated load failures back off exponentially
+// @tc.precon: NA
+// @tc.step: 1. Fail the load repeatedly, expiring the backoff each time
+//           2. Query the keeper before the backoff expires
+// @tc.expect: Each failure grows the backoff, loads are skipped while it runs
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_state_repeated_failures() {
+    let stub = Arc::new(Stub::default());
+    let keeper = keeper(&stub);
+
+    for (i, secs) in [1, 2, 4, 8, 16, 32, 60, 60].iter().enumerate() {
+        expire(&keeper);
+        assert_eq!(keeper.get(), None);
+        let stats = keeper.stats();
+        assert!(!stats.connected);
+        assert_eq!(stats.consecutive_failures, i as u32 + 1);
+        assert_jittered(stats.backoff, Duration::from_secs(*secs));
+        assert!(stats.retry_in <= stats.backoff);
+    }
+    assert_eq!(stub.loads.load(Ordering::SeqCst), 8);
+
+    assert_eq!(keeper.get(), None);
+    assert_eq!(stub.loads.load(Ordering::SeqCst), 8);
+}
+
+// @tc.name: ut_state_reset_on_success
+// @tc.desc: Test a successful load resets the backoff
+// @tc.precon: NA
+// @tc.step: 1. Fail the load three times
+//           2. Let the load succeed, then fail it again after a reconnect
+// @tc.expect: The success clears the failures, the next failure backs off 1s
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_state_reset_on_success() {
+    let stub = Arc::new(Stub::default());
+    let keeper = keeper(&stub);
+    for _ in 0..3 {
+        expire(&keeper);
+        assert_eq!(keeper.get(), None);
+    }
+    assert_eq!(keeper.stats().consecutive_failures, 3);
+
+    stub.succeed.store(true, Ordering::SeqCst);
+    expire(&keeper);
+    assert_eq!(keeper.get(), Some(7));
+    let stats = keeper.stats();
+    assert!(stats.connected);
+    assert_eq!(stats.consecutive_failures, 0);
+    assert_eq!(stats.backoff, Duration::ZERO);
+
+    assert_eq!(keeper.get(), Some(7));
+    assert_eq!(stub.loads.load(Ordering::SeqCst), 4);
+
+    stub.succeed.store(false, Ordering::SeqCst);
+    assert_eq!(keeper.force_reconnect(), None);
+    let stats = keeper.stats();
+    assert_eq!(stats.consecutive_failures, 1);
+    assert_jittered(stats.backoff, BASE_BACKOFF);
+}
+
+// @tc.name: ut_state_force_reconnect
+// @tc.desc: Test a forced reconnect skips the pending backoff
+// @tc.precon: NA
+// @tc.step: 1. Fail the load several times so a long backoff is pending
+//           2. Let the load succeed and force a reconnect
+// @tc.expect: The load happens at once and the keeper is connected
+// @tc.type: FUNC
+// @tc.require: issue#ICN31I
+#[test]
+fn ut_state_force_reconnect() {
+    let stub = Arc::new(Stub::default());
+    let keeper = keeper(&stub);
+    for _ in 0..5 {
+        expire(&keeper);
+        keeper.get();
+    }
+    assert!(keeper.stats().retry_in > Duration::from_secs(1));
+
+    stub.succeed.store(true, Ordering::SeqCst);
+    assert_eq!(keeper.get(), None);
+    assert_eq!(keeper.force_reconnect(), Some(7));
+    assert!(keeper.stats().connected);
+    assert_eq!(stub.loads.load(Ordering::SeqCst), 6);
+}
@@ -112,6 +112,19 @@ constexpr const char *REQUEST_TASK_TABLE_ADD_ATOMIC_ACCOUNT = "ALTER TABLE reque
                                                               "TEXT";
 
 constexpr const char *REQUEST_TASK_TABLE_ADD_UID_INDEX = "CREATE INDEX uid_index on request_task(uid)";
+constexpr const char *REQUEST_TASK_TABLE_ADD_UID_STATE_INDEX = "CREATE INDEX IF NOT EXISTS uid_state_index on "
+                                                               "request_task(uid, state)";
+constexpr const char *REQUEST_TASK_TABLE_ADD_UID_URL_INDEX = "CREATE INDEX IF NOT EXISTS uid_url_index on "
+                                                             "request_task(uid, url)";
+constexpr const char *REQUEST_TASK_TABLE_ADD_UID_CTIME_INDEX = "CREATE INDEX IF NOT EXISTS uid_ctime_index on "
+                                                               "request_task(uid, ctime)";
+constexpr const char *REQUEST_TASK_TABLE_ADD_CTIME_INDEX = "CREATE INDEX IF NOT EXISTS ctime_index on "
+                                                           "request_task(ctime)";
+
+constexpr const char *REQUEST_DB_QUERY_AUTO_VACUUM = "PRAGMA auto_vacuum";
+constexpr const char *REQUEST_DB_SET_INCREMENTAL_VACUUM = "PRAGMA auto_vacuum = INCREMENTAL";
+constexpr const char *REQUEST_DB_VACUUM = "VACUUM";
+constexpr int REQUEST_DB_AUTO_VACUUM_INCREMENTAL = 2;
 
 constexpr const char *REQUEST_TASK_TABLE_ADD_MAX_SPEED = "ALTER TABLE request_task ADD COLUMN max_speed INTEGER";
 constexpr const char *REQUEST_TASK_TABLE_ADD_MULTIPART = "ALTER TABLE request_task ADD COLUMN multipart INTEGER";
@@ -124,6 +137,34 @@ constexpr const char *REQUEST_TASK_TABLE_ADD_TOTAL_TIMEOUT = "ALTER TABLE reques
                                                              "INTEGER";
 constexpr const char *REQUEST_TASK_TABLE_ADD_TASK_TIME = "ALTER TABLE request_task ADD COLUMN task_time "
                                                          "INTEGER";
+constexpr const char *REQUEST_TASK_TABLE_ADD_RESPONSE_BODY = "ALTER TABLE request_task ADD COLUMN response_body "
+                                                             "BLOB";
+constexpr const char *REQUEST_TASK_TABLE_ADD_RESPONSE_TRUNCATED = "ALTER TABLE request_task ADD COLUMN "
+                                                                  "response_truncated INTEGER";
+constexpr const char *REQUEST_TASK_TABLE_ADD_METERED_OVERRIDE = "ALTER TABLE request_task ADD COLUMN "
+                                                                "metered_override INTEGER";
+constexpr const char *REQUEST_TASK_TABLE_ADD_CLIENT_TAG = "ALTER TABLE request_task ADD COLUMN client_tag BLOB";
+constexpr const char *REQUEST_TASK_TABLE_ADD_DESTINATION = "ALTER TABLE request_task ADD COLUMN destination BLOB";
+constexpr const char *REQUEST_TASK_TABLE_ADD_UNWRITTEN_RANGES = "ALTER TABLE request_task ADD COLUMN "
+                                                                "unwritten_ranges BLOB";
+
+constexpr const char *REQUEST_TASK_TABLE_ADD_REVISION = "ALTER TABLE request_task ADD COLUMN "
+                                                        "revision INTEGER";
+
+constexpr const char *REQUEST_TASK_TABLE_ADD_PAUSE_ON_THERMAL_LEVEL = "ALTER TABLE request_task ADD COLUMN "
+                                                                      "pause_on_thermal_level INTEGER";
+
+constexpr const char *REQUEST_TASK_TABLE_ADD_REQUIRE_CHARGING_WHILE_RUNNING =
+    "ALTER TABLE request_task ADD COLUMN "
+    "require_charging_while_running INTEGER";
+
+constexpr const char *REQUEST_TASK_TABLE_ADD_DEBUG =
+    "ALTER TABLE request_task ADD COLUMN "
+    "debug INTEGER";
+
+constexpr const char *REQUEST_TASK_TABLE_ADD_OPTIONS = "ALTER TABLE request_task ADD COLUMN options BLOB";
+constexpr const char *REQUEST_TASK_TABLE_ADD_PRIVILEGED = "ALTER TABLE request_task ADD COLUMN "
+                                                          "privileged INTEGER";
 
 constexpr const char *REQUEST_TASK_TABLE_COL_PROXY = "proxy";
 constexpr const char *REQUEST_TASK_TABLE_COL_CERTIFICATE_PINS = "certificate_pins";
@@ -136,6 +177,18 @@ constexpr const char *REQUEST_TASK_TABLE_COL_MIN_SPEED_DURATION = "min_speed_dur
 constexpr const char *REQUEST_TASK_TABLE_COL_CONNECTION_TIMEOUT = "connection_timeout";
 constexpr const char *REQUEST_TASK_TABLE_COL_TOTAL_TIMEOUT = "total_timeout";
 constexpr const char *REQUEST_TASK_TABLE_COL_TASK_TIME = "task_time";
+constexpr const char *REQUEST_TASK_TABLE_COL_RESPONSE_BODY = "response_body";
+constexpr const char *REQUEST_TASK_TABLE_COL_RESPONSE_TRUNCATED = "response_truncated";
+constexpr const char *REQUEST_TASK_TABLE_COL_METERED_OVERRIDE = "metered_override";
+constexpr const char *REQUEST_TASK_TABLE_COL_CLIENT_TAG = "client_tag";
+constexpr const char *REQUEST_TASK_TABLE_COL_DESTINATION = "destination";
+constexpr const char *REQUEST_TASK_TABLE_COL_UNWRITTEN_RANGES = "unwritten_ranges";
+constexpr const char *REQUEST_TASK_TABLE_COL_REVISION = "revision";
+constexpr const char *REQUEST_TASK_TABLE_COL_PAUSE_ON_THERMAL_LEVEL = "pause_on_thermal_level";
+constexpr const char *REQUEST_TASK_TABLE_COL_REQUIRE_CHARGING_WHILE_RUNNING = "require_charging_while_running";
+constexpr const char *REQUEST_TASK_TABLE_COL_DEBUG = "debug";
+constexpr const char *REQUEST_TASK_TABLE_COL_OPTIONS = "options";
+constexpr const char *REQUEST_TASK_TABLE_COL_PRIVILEGED = "privileged";
 
 struct TaskFilter;
 struct NetworkInfo;
@@ -152,6 +205,8 @@ public:
     bool Delete(const OHOS::NativeRdb::AbsRdbPredicates &predicates);
     int ExecuteSql(rust::str sql);
     int QueryInteger(rust::str sql, rust::vec<rust::i64> &res);
+    int QueryIntegerRows(rust::str sql, size_t columns, rust::vec<rust::i64> &res);
+    int QueryBlob(rust::str sql, rust::vec<uint8_t> &res);
     int QueryText(rust::str sql, rust::vec<rust::string> &res);
     int GetAppTaskQosInfos(rust::str sql, rust::vec<TaskQosInfo> &res);
     int GetTaskQosInfo(rust::str sql, TaskQosInfo &res);
@@ -189,7 +244,6 @@ struct CVectorWrapper {
 
 // Request Database Modify.
 bool RecordRequestTask(CTaskInfo *taskInfo, CTaskConfig *taskConfig);
-bool UpdateRequestTask(uint32_t taskId, CUpdateInfo *updateInfo);
 bool UpdateRequestTaskTime(uint32_t taskId, uint64_t taskTime);
 bool UpdateRequestTaskState(uint32_t taskId, CUpdateStateInfo *updateStateInfo);
 void RequestDBRemoveRecordsFromTime(uint64_t time);
@@ -73,14 +73,6 @@ struct TaskInfo {
     uint64_t taskTime;
 };
 
-struct CUpdateInfo {
-    uint64_t mtime;
-    uint8_t reason;
-    uint32_t tries;
-    CStringWrapper mimeType;
-    CProgress progress;
-};
-
 struct CUpdateStateInfo {
     uint64_t mtime;
     uint8_t state;
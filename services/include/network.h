@@ -69,5 +69,8 @@ std::unique_ptr<NetworkRegistry> RegisterNetworkChange(rust::box<NetworkInner> n
     rust::fn<void(const NetworkTaskManagerTx &task_manager)> notifyTaskManagerOnline,
     rust::fn<void(const NetworkTaskManagerTx &task_manager)> notifyTaskManagerOffline);
 
+bool IsNetAvailable(int32_t netId);
+rust::vec<rust::string> GetAddressesByNetId(int32_t netId, rust::str host);
+
 } // namespace OHOS::Request
 #endif
\ No newline at end of file
@@ -44,7 +44,8 @@ private:
     rust::Box<TaskManagerWrapper> _taskManager;
 };
 
-void SubscribeNotification(rust::Box<TaskManagerWrapper> taskManager);
+int SubscribeNotification(rust::Box<TaskManagerWrapper> taskManager);
+bool IsServiceUnavailable(int32_t errCode);
 
 inline int32_t CancelNotification(uint32_t notificationId)
 {
@@ -27,6 +27,11 @@ bool CheckPermission(uint64_t tokenId, rust::str permission);
 bool PublishStateChangeEvent(rust::str bundleName, uint32_t taskId, int32_t state, int32_t uid);
 int32_t UpdatePolicy(bool result);
 bool IsCalledByHAP(uint32_t tokenId);
+int64_t GetIntParameter(rust::str key, int64_t defaultValue);
+int32_t GetThermalLevel();
+bool IsCharging();
+bool AcquireRunningLock(int64_t timeoutMs);
+void ReleaseRunningLock();
 
 } // namespace OHOS::Request
 
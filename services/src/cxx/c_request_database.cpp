@@ -161,6 +161,63 @@ int RequestDataBase::QueryInteger(rust::str sql, rust::vec<rust::i64> &res)
     return 0;
 }
 
+int RequestDataBase::QueryIntegerRows(rust::str sql, size_t columns, rust::vec<rust::i64> &res)
+{
+    if (store_ == nullptr) {
+        return -1;
+    }
+    auto queryRet = store_->QueryByStep(std::string(sql));
+    if (queryRet == nullptr) {
+        REQUEST_HILOGE("Search failed with reason: result set is nullptr");
+        return -1;
+    }
+    int rowCount = 0;
+
+    int errCode = queryRet->GetRowCount(rowCount);
+    if (errCode != OHOS::NativeRdb::E_OK) {
+        REQUEST_HILOGE("GetRowCount failed: %{public}d", errCode);
+        CheckAndRebuildDataBase(errCode);
+        return -1;
+    }
+    for (int i = 0; i < rowCount; i++) {
+        auto code = queryRet->GoToRow(i);
+        if (code != OHOS::NativeRdb::E_OK) {
+            REQUEST_HILOGE("result set go to %{public}d row failed %{public}d", i, code);
+            return -1;
+        }
+        for (size_t column = 0; column < columns; column++) {
+            int64_t value = 0;
+            queryRet->GetLong(static_cast<int>(column), value);
+            res.push_back(rust::i64(value));
+        }
+    }
+    return 0;
+}
+
+int RequestDataBase::QueryBlob(rust::str sql, rust::vec<uint8_t> &res)
+{
+    if (store_ == nullptr) {
+        return -1;
+    }
+    auto queryRet = store_->QueryByStep(std::string(sql));
+    if (queryRet == nullptr) {
+        REQUEST_HILOGE("Search failed with reason: result set is nullptr");
+        return -1;
+    }
+    auto code = queryRet->GoToFirstRow();
+    if (code != OHOS::NativeRdb::E_OK) {
+        REQUEST_HILOGE("result set go to first row failed %{public}d", code);
+        CheckAndRebuildDataBase(code);
+        return -1;
+    }
+    std::vector<uint8_t> value;
+    queryRet->GetBlob(0, value);
+    for (auto byte : value) {
+        res.push_back(byte);
+    }
+    return 0;
+}
+
 int RequestDataBase::QueryText(rust::str sql, rust::vec<rust::String> &res)
 {
     if (store_ == nullptr) {
@@ -572,6 +629,36 @@ int RequestDBUpgradeFrom51(OHOS::NativeRdb::RdbStore &store)
     }
     return OHOS::NativeRdb::E_OK;
 }
+// Free pages are only returned by `incremental_vacuum` in incremental
+// auto-vacuum mode, switching an existing database to it takes a full VACUUM.
+void RequestDBEnableIncrementalVacuum(OHOS::NativeRdb::RdbStore &store)
+{
+    auto resultSet = store.QuerySql(REQUEST_DB_QUERY_AUTO_VACUUM);
+    if (resultSet == nullptr) {
+        REQUEST_HILOGE("Query auto vacuum failed: result set is nullptr");
+        return;
+    }
+    int mode = 0;
+    if (resultSet->GoToNextRow() == NativeRdb::E_OK) {
+        NativeRdb::ValueObject valObject;
+        if (resultSet->Get(0, valObject) == NativeRdb::E_OK) {
+            valObject.GetInt(mode);
+        }
+    }
+    resultSet->Close();
+    if (mode == REQUEST_DB_AUTO_VACUUM_INCREMENTAL) {
+        return;
+    }
+    REQUEST_HILOGI("Enables incremental auto vacuum, mode %{public}d", mode);
+    if (store.ExecuteSql(REQUEST_DB_SET_INCREMENTAL_VACUUM) != OHOS::NativeRdb::E_OK) {
+        REQUEST_HILOGE("set incremental auto vacuum failed");
+        return;
+    }
+    if (store.ExecuteSql(REQUEST_DB_VACUUM) != OHOS::NativeRdb::E_OK) {
+        REQUEST_HILOGE("vacuum failed");
+    }
+}
+
 // This function is used to adapt beta version, remove it later.
 void RequestDBUpgradeFrom60(OHOS::NativeRdb::RdbStore &store)
 {
@@ -590,6 +677,55 @@ void RequestDBUpgradeFrom60(OHOS::NativeRdb::RdbStore &store)
     if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_TASK_TIME)) {
         store.ExecuteSql(REQUEST_TASK_TABLE_ADD_TASK_TIME);
     }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_RESPONSE_BODY)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_RESPONSE_BODY);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_RESPONSE_TRUNCATED)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_RESPONSE_TRUNCATED);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_METERED_OVERRIDE)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_METERED_OVERRIDE);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_CLIENT_TAG)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_CLIENT_TAG);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_DESTINATION)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_DESTINATION);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_UNWRITTEN_RANGES)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_UNWRITTEN_RANGES);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_REVISION)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_REVISION);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_PAUSE_ON_THERMAL_LEVEL)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_PAUSE_ON_THERMAL_LEVEL);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_REQUIRE_CHARGING_WHILE_RUNNING)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_REQUIRE_CHARGING_WHILE_RUNNING);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_DEBUG)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_DEBUG);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_OPTIONS)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_OPTIONS);
+    }
+    if (!ColumnExists(store, REQUEST_TASK_TABLE_COL_PRIVILEGED)) {
+        store.ExecuteSql(REQUEST_TASK_TABLE_ADD_PRIVILEGED);
+    }
+    if (store.ExecuteSql(REQUEST_TASK_TABLE_ADD_UID_STATE_INDEX) != OHOS::NativeRdb::E_OK) {
+        REQUEST_HILOGE("add uid state index failed");
+    }
+    if (store.ExecuteSql(REQUEST_TASK_TABLE_ADD_UID_URL_INDEX) != OHOS::NativeRdb::E_OK) {
+        REQUEST_HILOGE("add uid url index failed");
+    }
+    if (store.ExecuteSql(REQUEST_TASK_TABLE_ADD_UID_CTIME_INDEX) != OHOS::NativeRdb::E_OK) {
+        REQUEST_HILOGE("add uid ctime index failed");
+    }
+    if (store.ExecuteSql(REQUEST_TASK_TABLE_ADD_CTIME_INDEX) != OHOS::NativeRdb::E_OK) {
+        REQUEST_HILOGE("add ctime index failed");
+    }
+    RequestDBEnableIncrementalVacuum(store);
 }
 
 int RequestDBUpgrade(OHOS::NativeRdb::RdbStore &store)
@@ -1134,28 +1270,6 @@ bool RecordRequestTask(CTaskInfo *taskInfo, CTaskConfig *taskConfig)
     return true;
 }
 
-bool UpdateRequestTask(uint32_t taskId, CUpdateInfo *updateInfo)
-{
-    REQUEST_HILOGD("update request_task");
-    OHOS::NativeRdb::ValuesBucket values;
-    values.PutLong("mtime", updateInfo->mtime);
-    values.PutLong("tries", updateInfo->tries);
-    // write to insertValues
-    values.PutString("mime_type", std::string(updateInfo->mimeType.cStr, updateInfo->mimeType.len));
-    values.PutLong("idx", updateInfo->progress.commonData.index);
-    values.PutLong("total_processed", updateInfo->progress.commonData.totalProcessed);
-    values.PutString("processed", std::string(updateInfo->progress.processed.cStr, updateInfo->progress.processed.len));
-    values.PutString("extras", std::string(updateInfo->progress.extras.cStr, updateInfo->progress.extras.len));
-
-    OHOS::NativeRdb::RdbPredicates rdbPredicates("request_task");
-    rdbPredicates.EqualTo("task_id", std::to_string(taskId));
-    if (!OHOS::Request::RequestDataBase::GetInstance(OHOS::Request::DB_NAME, true).Update(values, rdbPredicates)) {
-        REQUEST_HILOGE("update table1 failed, task_id: %{public}d", taskId);
-        return false;
-    }
-    return true;
-}
-
 bool UpdateRequestTaskTime(uint32_t taskId, uint64_t taskTime)
 {
     REQUEST_HILOGD("update request task time");
@@ -18,6 +18,7 @@
 #include <cstdint>
 
 #include "cxx.h"
+#include "inet_addr.h"
 #include "log.h"
 #include "manage/network.rs.h"
 #include "net_all_capabilities.h"
@@ -275,4 +276,36 @@ NetworkRegistry::~NetworkRegistry()
     }
 }
 
+bool IsNetAvailable(int32_t netId)
+{
+    std::list<sptr<NetHandle>> netList;
+    int32_t ret = NetConnClient::GetInstance().GetAllNets(netList);
+    if (ret != 0) {
+        REQUEST_HILOGE("GetAllNets failed: %{public}d", ret);
+        return false;
+    }
+    for (auto netHandle : netList) {
+        if (netHandle->GetNetId() == netId) {
+            return true;
+        }
+    }
+    return false;
+}
+
+rust::vec<rust::string> GetAddressesByNetId(int32_t netId, rust::str host)
+{
+    rust::vec<rust::string> addresses;
+    NetHandle netHandle(netId);
+    std::vector<INetAddr> addrList;
+    int32_t ret = netHandle.GetAddressesByName(std::string(host), addrList);
+    if (ret != 0) {
+        REQUEST_HILOGE("GetAddressesByName on net %{public}d failed: %{public}d", netId, ret);
+        return addresses;
+    }
+    for (const auto &addr : addrList) {
+        addresses.push_back(rust::string(addr.address_));
+    }
+    return addresses;
+}
+
 } // namespace OHOS::Request
\ No newline at end of file
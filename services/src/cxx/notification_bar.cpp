@@ -19,6 +19,7 @@
 #include <cstdint>
 #include <string>
 
+#include "ans_inner_errors.h"
 #include "cxx.h"
 #include "image_source.h"
 #include "locale_config.h"
@@ -46,6 +47,8 @@ static constexpr int32_t REQUEST_STYLE_SIMPLE = 8;
 // static constexpr size_t PLACEHOLDER_LENGTH = 2;
 
 static const std::string CLOSE_ICON_PATH = "/etc/request/xmark.svg";
+static const std::string EXPAND_ICON_PATH = "/etc/request/expand.svg";
+static const std::string COLLAPSE_ICON_PATH = "/etc/request/collapse.svg";
 
 rust::string GetSystemResourceString(const rust::str name)
 {
@@ -77,30 +80,37 @@ rust::string GetSystemLanguage()
     return rust::string(I18n::LocaleConfig::GetSystemLanguage().c_str());
 }
 
+std::shared_ptr<Media::PixelMap> LoadPixelMap(const std::string &path)
+{
+    uint32_t errorCode = 0;
+    Media::SourceOptions opts;
+    auto source = Media::ImageSource::CreateImageSource(path, opts, errorCode);
+    if (source == nullptr) {
+        REQUEST_HILOGE("create image source failed");
+        return nullptr;
+    }
+    Media::DecodeOptions decodeOpts;
+    std::unique_ptr<Media::PixelMap> pixel = source->CreatePixelMap(decodeOpts, errorCode);
+    if (pixel == nullptr) {
+        REQUEST_HILOGE("create pixel map failed");
+        return nullptr;
+    }
+    return std::move(pixel);
+}
+
 std::shared_ptr<Media::PixelMap> CreatePixelMap()
 {
-    static std::shared_ptr<Media::PixelMap> pixelMap = nullptr;
-    static std::once_flag flag;
-
-    std::call_once(flag, []() {
-        uint32_t errorCode = 0;
-        Media::SourceOptions opts;
-        auto source = Media::ImageSource::CreateImageSource(CLOSE_ICON_PATH, opts, errorCode);
-        if (source == nullptr) {
-            REQUEST_HILOGE("create image source failed");
-            return;
-        }
-        Media::DecodeOptions decodeOpts;
-        std::unique_ptr<Media::PixelMap> pixel = source->CreatePixelMap(decodeOpts, errorCode);
-        if (pixel == nullptr) {
-            REQUEST_HILOGE("create pixel map failed");
-            return;
-        }
-        pixelMap = std::move(pixel);
-    });
+    static std::shared_ptr<Media::PixelMap> pixelMap = LoadPixelMap(CLOSE_ICON_PATH);
     return pixelMap;
 }
 
+std::shared_ptr<Media::PixelMap> AttentionPixelMap(bool attended)
+{
+    static std::shared_ptr<Media::PixelMap> expand = LoadPixelMap(EXPAND_ICON_PATH);
+    static std::shared_ptr<Media::PixelMap> collapse = LoadPixelMap(COLLAPSE_ICON_PATH);
+    return attended ? collapse : expand;
+}
+
 void BasicRequestSettings(Notification::NotificationRequest &request, int32_t uid)
 {
     request.SetCreatorUid(REQUEST_SERVICE_ID);
@@ -126,7 +136,7 @@ std::shared_ptr<OHOS::Notification::NotificationContent> LiveViewContent(const N
     liveViewContent->SetText(std::string(content.text));
     liveViewContent->SetTitle(std::string(content.title));
 
-    if (content.x_mark || content.progress_circle.open) {
+    if (content.x_mark || content.attention_button || content.progress_circle.open) {
         liveViewContent->addFlag(Notification::NotificationLocalLiveViewContent::LiveViewContentInner::BUTTON);
     }
 
@@ -140,6 +150,16 @@ std::shared_ptr<OHOS::Notification::NotificationContent> LiveViewContent(const N
         }
     }
 
+    if (content.attention_button) {
+        auto button = liveViewContent->GetButton();
+        auto icon = AttentionPixelMap(content.attended);
+        if (icon != nullptr) {
+            button.addSingleButtonName(content.attended ? "collapse" : "expand");
+            button.addSingleButtonIcon(icon);
+            liveViewContent->SetButton(button);
+        }
+    }
+
     if (content.progress_circle.open) {
         liveViewContent->addFlag(Notification::NotificationLocalLiveViewContent::LiveViewContentInner::PROGRESS);
         Notification::NotificationProgress progress;
@@ -190,13 +210,24 @@ void NotificationSubscriber::OnResponse(
     } else if (buttonOption->GetButtonName() == "cancel") {
         this->_taskManager->stop_task(static_cast<uint32_t>(notificationId));
         Notification::NotificationHelper::CancelNotification(notificationId);
+    } else if (buttonOption->GetButtonName() == "expand") {
+        this->_taskManager->user_attention(static_cast<uint32_t>(notificationId), true);
+    } else if (buttonOption->GetButtonName() == "collapse") {
+        this->_taskManager->user_attention(static_cast<uint32_t>(notificationId), false);
     }
 };
 
-void SubscribeNotification(rust::Box<TaskManagerWrapper> taskManager)
+int SubscribeNotification(rust::Box<TaskManagerWrapper> taskManager)
 {
+    // Later calls retry with the subscriber created first.
     static auto subscriber = std::make_unique<NotificationSubscriber>(std::move(taskManager));
-    Notification::NotificationHelper::SubscribeLocalLiveViewNotification(*subscriber);
+    return Notification::NotificationHelper::SubscribeLocalLiveViewNotification(*subscriber);
+}
+
+bool IsServiceUnavailable(int32_t errCode)
+{
+    return errCode == Notification::ERR_ANS_SERVICE_NOT_READY
+        || errCode == Notification::ERR_ANS_SERVICE_NOT_CONNECTED;
 }
 
 } // namespace OHOS::Request
\ No newline at end of file
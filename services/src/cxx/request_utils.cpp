@@ -29,12 +29,26 @@
 #include "int_wrapper.h"
 #include "iservice_registry.h"
 #include "log.h"
+#include "parameters.h"
 #include "string_wrapper.h"
 #include "sys_event.h"
 #include "system_ability_definition.h"
 #include "tokenid_kit.h"
 #include "utils/mod.rs.h"
 
+#ifdef REQUEST_BATTERY_MANAGER
+#include "battery_srv_client.h"
+#endif
+#ifdef REQUEST_THERMAL_MANAGER
+#include "thermal_mgr_client.h"
+#endif
+#ifdef REQUEST_POWER_MANAGER
+#include <mutex>
+
+#include "power_mgr_client.h"
+#include "running_lock.h"
+#endif
+
 namespace OHOS::Request {
 using namespace OHOS::Security::AccessToken;
 using namespace OHOS::EventFwk;
@@ -175,4 +189,71 @@ bool IsCalledByHAP(uint32_t tokenId)
     return AccessTokenKit::GetTokenTypeFlag(tokenId) == ATokenTypeEnum::TOKEN_HAP;
 }
 
+int64_t GetIntParameter(rust::str key, int64_t defaultValue)
+{
+    return OHOS::system::GetIntParameter(std::string(key), defaultValue);
+}
+
+int32_t GetThermalLevel()
+{
+#ifdef REQUEST_THERMAL_MANAGER
+    return static_cast<int32_t>(PowerMgr::ThermalMgrClient::GetInstance().GetThermalLevel());
+#else
+    return 0;
+#endif
+}
+
+bool IsCharging()
+{
+#ifdef REQUEST_BATTERY_MANAGER
+    auto status = PowerMgr::BatterySrvClient::GetInstance().GetChargingStatus();
+    return status == PowerMgr::BatteryChargeState::CHARGE_STATE_ENABLE
+           || status == PowerMgr::BatteryChargeState::CHARGE_STATE_FULL;
+#else
+    return true;
+#endif
+}
+
+#ifdef REQUEST_POWER_MANAGER
+static std::mutex g_runningLockMutex;
+static std::shared_ptr<PowerMgr::RunningLock> g_runningLock = nullptr;
+#endif
+
+bool AcquireRunningLock(int64_t timeoutMs)
+{
+#ifdef REQUEST_POWER_MANAGER
+    std::lock_guard<std::mutex> lock(g_runningLockMutex);
+    if (g_runningLock == nullptr) {
+        g_runningLock = PowerMgr::PowerMgrClient::GetInstance().CreateRunningLock(
+            "request_keep_awake", PowerMgr::RunningLockType::RUNNINGLOCK_BACKGROUND_TASK);
+    }
+    if (g_runningLock == nullptr) {
+        REQUEST_HILOGE("AcquireRunningLock failed, create running lock failed");
+        return false;
+    }
+    ErrCode ret = g_runningLock->Lock(static_cast<int32_t>(timeoutMs));
+    if (ret != ERR_OK) {
+        REQUEST_HILOGE("AcquireRunningLock failed, ret: %{public}d", ret);
+        return false;
+    }
+    return true;
+#else
+    return false;
+#endif
+}
+
+void ReleaseRunningLock()
+{
+#ifdef REQUEST_POWER_MANAGER
+    std::lock_guard<std::mutex> lock(g_runningLockMutex);
+    if (g_runningLock == nullptr) {
+        return;
+    }
+    ErrCode ret = g_runningLock->UnLock();
+    if (ret != ERR_OK) {
+        REQUEST_HILOGE("ReleaseRunningLock failed, ret: %{public}d", ret);
+    }
+#endif
+}
+
 } // namespace OHOS::Request
\ No newline at end of file
@@ -0,0 +1,302 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::config::TaskConfig;
+use crate::error::ErrorCode;
+use crate::info::State;
+use crate::manage::database::RequestDb;
+use crate::manage::events::TaskManagerEvent;
+use crate::manage::task_manager::TaskManagerTx;
+use crate::task::notify::{NotifyData, Progress, SubscribeType};
+use crate::task::reason::Reason;
+use crate::utils::Recv;
+
+/// Progress of a task as reported to an [`EmbeddedCallback`].
+#[derive(Clone, Debug)]
+pub struct TaskProgress {
+    /// ID of the task.
+    pub task_id: i64,
+    /// State of the task.
+    pub state: State,
+    /// Index of the file being processed.
+    pub index: usize,
+    /// Bytes processed across all files.
+    pub processed: u64,
+    /// Size of each file in bytes, -1 if unknown.
+    pub sizes: Vec<i64>,
+}
+
+impl TaskProgress {
+    fn new(task_id: u32, progress: &Progress) -> Self {
+        Self {
+            task_id: task_id as i64,
+            state: State::from(progress.common_data.state),
+            index: progress.common_data.index,
+            processed: progress.common_data.total_processed as u64,
+            sizes: progress.sizes.clone(),
+        }
+    }
+}
+
+/// Callbacks receiving the notifications of a task.
+///
+/// Callbacks run on a dispatcher thread of the embedded service, one at a
+/// time and in the order the notifications were sent. They may call back
+/// into the [`EmbeddedClient`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use download_server::embedded::{EmbeddedCallback, TaskProgress};
+///
+/// struct Logger;
+///
+/// impl EmbeddedCallback for Logger {
+///     fn on_progress(&self, progress: &TaskProgress) {
+///         println!("task {}: {} bytes", progress.task_id, progress.processed);
+///     }
+///
+///     fn on_fault(&self, task_id: i64, reason: i32) {
+///         println!("task {} fault {}", task_id, reason);
+///     }
+/// }
+/// ```
+pub trait EmbeddedCallback: Send + Sync {
+    /// Called when the task made progress.
+    fn on_progress(&self, _progress: &TaskProgress) {}
+
+    /// Called when the task completed.
+    fn on_completed(&self, _progress: &TaskProgress) {}
+
+    /// Called when the task failed, [`on_fault`](Self::on_fault) reports the
+    /// reason.
+    fn on_failed(&self, _progress: &TaskProgress) {}
+
+    /// Called when the task was paused.
+    fn on_pause(&self, _progress: &TaskProgress) {}
+
+    /// Called when the task was resumed.
+    fn on_resume(&self, _progress: &TaskProgress) {}
+
+    /// Called when the task was removed.
+    fn on_remove(&self, _progress: &TaskProgress) {}
+
+    /// Called when the response headers of the task arrived.
+    fn on_header_receive(&self, _progress: &TaskProgress) {}
+
+    /// Called with the reason code of a fault of the task.
+    fn on_fault(&self, _task_id: i64, _reason: i32) {}
+}
+
+/// Notification waiting to be handed to a callback.
+enum Notice {
+    Notify(SubscribeType, TaskProgress),
+    Fault(u32, Reason),
+}
+
+/// Callbacks registered for tasks and the thread calling them.
+struct Dispatcher {
+    callbacks: Mutex<HashMap<u32, Arc<dyn EmbeddedCallback>>>,
+    tx: Mutex<Sender<Notice>>,
+}
+
+impl Dispatcher {
+    fn get_instance() -> &'static Self {
+        static DISPATCHER: OnceLock<Dispatcher> = OnceLock::new();
+
+        DISPATCHER.get_or_init(|| {
+            let (tx, rx) = channel();
+            std::thread::spawn(move || {
+                while let Ok(notice) = rx.recv() {
+                    Dispatcher::get_instance().deliver(notice);
+                }
+            });
+            Dispatcher {
+                callbacks: Mutex::new(HashMap::new()),
+                tx: Mutex::new(tx),
+            }
+        })
+    }
+
+    fn callback(&self, task_id: u32) -> Option<Arc<dyn EmbeddedCallback>> {
+        self.callbacks.lock().unwrap().get(&task_id).cloned()
+    }
+
+    fn send(&self, task_id: u32, notice: Notice) {
+        if self.callbacks.lock().unwrap().contains_key(&task_id) {
+            let _ = self.tx.lock().unwrap().send(notice);
+        }
+    }
+
+    fn deliver(&self, notice: Notice) {
+        match notice {
+            Notice::Notify(subscribe_type, progress) => {
+                let Some(callback) = self.callback(progress.task_id as u32) else {
+                    return;
+                };
+                match subscribe_type {
+                    SubscribeType::Complete => callback.on_completed(&progress),
+                    SubscribeType::Fail => callback.on_failed(&progress),
+                    SubscribeType::HeaderReceive => callback.on_header_receive(&progress),
+                    SubscribeType::Pause => callback.on_pause(&progress),
+                    SubscribeType::Progress => callback.on_progress(&progress),
+                    SubscribeType::Remove => callback.on_remove(&progress),
+                    SubscribeType::Resume => callback.on_resume(&progress),
+                    SubscribeType::FaultOccur => {}
+                }
+            }
+            Notice::Fault(task_id, reason) => {
+                if let Some(callback) = self.callback(task_id) {
+                    callback.on_fault(task_id as i64, reason.repr as i32);
+                }
+            }
+        }
+    }
+}
+
+/// Hands a notification the client manager is sending to the callback of
+/// its task.
+pub(crate) fn dispatch_notify(subscribe_type: SubscribeType, notify_data: &NotifyData) {
+    let progress = TaskProgress::new(notify_data.task_id, &notify_data.progress);
+    Dispatcher::get_instance().send(
+        notify_data.task_id,
+        Notice::Notify(subscribe_type, progress),
+    );
+}
+
+/// Hands a fault the client manager is sending to the callback of its task.
+pub(crate) fn dispatch_fault(task_id: u32, reason: Reason) {
+    Dispatcher::get_instance().send(task_id, Notice::Fault(task_id, reason));
+}
+
+/// Client driving the tasks of one application in the embedded service.
+///
+/// Offers the task operations of `request_next::RequestClient`, answered
+/// by the task manager of the process instead of the system ability.
+///
+/// # Examples
+///
+/// ```no_run
+/// use download_server::embedded::EmbeddedRequestService;
+///
+/// let client = EmbeddedRequestService::new("/tmp/request").client(100);
+/// # let task_id = 1;
+/// client.pause(task_id).unwrap();
+/// client.resume(task_id).unwrap();
+/// println!("{:?}", client.query(task_id).unwrap().state);
+/// ```
+#[derive(Clone)]
+pub struct EmbeddedClient {
+    uid: u64,
+    task_manager: TaskManagerTx,
+}
+
+impl EmbeddedClient {
+    pub(crate) fn new(uid: u64, task_manager: TaskManagerTx) -> Self {
+        Self { uid, task_manager }
+    }
+
+    /// Creates a task from `config` for the application of this client.
+    ///
+    /// # Returns
+    /// The ID of the new task, or the error code of the service
+    pub fn create_task(&self, mut config: TaskConfig) -> Result<i64, i32> {
+        config.common_data.uid = self.uid;
+        let (event, rx) = TaskManagerEvent::construct(config);
+        if !self.task_manager.send_event(event) {
+            return Err(ErrorCode::Other as i32);
+        }
+        match rx.get() {
+            Some(Ok(created)) => Ok(created.task_id as i64),
+            Some(Err(code)) => Err(code as i32),
+            None => Err(ErrorCode::Other as i32),
+        }
+    }
+
+    /// Starts a task.
+    pub fn start(&self, task_id: i64) -> Result<(), i32> {
+        self.control(task_id, TaskManagerEvent::start)
+    }
+
+    /// Pauses a task.
+    pub fn pause(&self, task_id: i64) -> Result<(), i32> {
+        self.control(task_id, TaskManagerEvent::pause)
+    }
+
+    /// Resumes a paused task.
+    pub fn resume(&self, task_id: i64) -> Result<(), i32> {
+        self.control(task_id, TaskManagerEvent::resume)
+    }
+
+    /// Stops a task.
+    pub fn stop(&self, task_id: i64) -> Result<(), i32> {
+        self.control(task_id, TaskManagerEvent::stop)
+    }
+
+    /// Removes a task.
+    pub fn remove(&self, task_id: i64) -> Result<(), i32> {
+        self.control(task_id, |uid, task_id| {
+            TaskManagerEvent::remove(uid, task_id, false)
+        })
+    }
+
+    /// Returns the current progress of a task of this client.
+    pub fn query(&self, task_id: i64) -> Result<TaskProgress, i32> {
+        let task_id = u32::try_from(task_id).map_err(|_| ErrorCode::TaskNotFound as i32)?;
+        let db = RequestDb::get_instance();
+        if db.query_task_uid(task_id) != Some(self.uid) {
+            return Err(ErrorCode::TaskNotFound as i32);
+        }
+        match db.get_task_info(task_id) {
+            Some(info) => Ok(TaskProgress::new(task_id, &info.progress)),
+            None => Err(ErrorCode::TaskNotFound as i32),
+        }
+    }
+
+    /// Registers `callback` for the notifications of a task, replacing the
+    /// callback registered before.
+    pub fn register_callback(&self, task_id: i64, callback: Arc<dyn EmbeddedCallback>) {
+        if let Ok(task_id) = u32::try_from(task_id) {
+            let callbacks = &Dispatcher::get_instance().callbacks;
+            callbacks.lock().unwrap().insert(task_id, callback);
+        }
+    }
+
+    /// Unregisters the callback of a task.
+    pub fn unregister_callback(&self, task_id: i64) {
+        if let Ok(task_id) = u32::try_from(task_id) {
+            let callbacks = &Dispatcher::get_instance().callbacks;
+            callbacks.lock().unwrap().remove(&task_id);
+        }
+    }
+
+    fn control<F>(&self, task_id: i64, event: F) -> Result<(), i32>
+    where
+        F: FnOnce(u64, u32) -> (TaskManagerEvent, Recv<ErrorCode>),
+    {
+        let task_id = u32::try_from(task_id).map_err(|_| ErrorCode::TaskNotFound as i32)?;
+        let (event, rx) = event(self.uid, task_id);
+        if !self.task_manager.send_event(event) {
+            return Err(ErrorCode::Other as i32);
+        }
+        match rx.get() {
+            Some(ErrorCode::ErrOk) => Ok(()),
+            Some(code) => Err(code as i32),
+            None => Err(ErrorCode::Other as i32),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! In-process request service for platforms without system abilities.
+//!
+//! Test rigs and ports outside OpenHarmony can run the task manager, the
+//! scheduler and the database inside their own process.
+//! [`EmbeddedRequestService`] boots them, and an [`EmbeddedClient`] drives
+//! tasks by sending events to the task manager directly, where applications
+//! on a device go through IPC. Notifications are handed to the registered
+//! [`EmbeddedCallback`]s without going through the UDS channel.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use std::sync::Arc;
+//!
+//! use download_server::config::{Action, ConfigBuilder, Mode};
+//! use download_server::embedded::{EmbeddedCallback, EmbeddedRequestService, TaskProgress};
+//!
+//! struct Logger;
+//!
+//! impl EmbeddedCallback for Logger {
+//!     fn on_completed(&self, progress: &TaskProgress) {
+//!         println!("task {} completed", progress.task_id);
+//!     }
+//! }
+//!
+//! let handle = EmbeddedRequestService::new("/tmp/request");
+//! let client = handle.client(100);
+//! let file = File::create("/tmp/request/file.bin").unwrap();
+//! let config = ConfigBuilder::new()
+//!     .action(Action::Download)
+//!     .mode(Mode::BackGround)
+//!     .version(2)
+//!     .url("http://example.com/file.bin")
+//!     .file_spec(file)
+//!     .build();
+//! let task_id = client.create_task(config).unwrap();
+//! client.register_callback(task_id, Arc::new(Logger));
+//! client.start(task_id).unwrap();
+//! ```
+
+mod client;
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub(crate) use client::{dispatch_fault, dispatch_notify};
+pub use client::{EmbeddedCallback, EmbeddedClient, TaskProgress};
+
+use crate::manage::database::RequestDb;
+use crate::manage::events::TaskManagerEvent;
+use crate::manage::network::{NetworkInfo, NetworkType};
+use crate::manage::network_manager::NetworkManager;
+use crate::manage::task_manager::TaskManagerTx;
+use crate::manage::TaskManager;
+use crate::service::active_counter::ActiveCounter;
+use crate::service::client::ClientManager;
+use crate::service::run_count::RunCountManager;
+
+/// File of the database in the configuration directory.
+const DATABASE_FILE: &str = "request.db";
+
+/// Entry point booting the request service inside the calling process.
+pub struct EmbeddedRequestService;
+
+impl EmbeddedRequestService {
+    /// Boots the task manager, client manager and run count manager, keeping
+    /// the database in `config_dir`, and reports an unmetered network.
+    ///
+    /// The service runs once per process: later calls return a handle to the
+    /// running service and ignore `config_dir`. Tasks left in the database
+    /// by a previous run are restored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config_dir` cannot be created.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use download_server::embedded::EmbeddedRequestService;
+    ///
+    /// let handle = EmbeddedRequestService::new("/tmp/request");
+    /// let again = EmbeddedRequestService::new("/tmp/ignored");
+    /// ```
+    pub fn new(config_dir: impl AsRef<Path>) -> EmbeddedHandle {
+        static SERVICE: OnceLock<EmbeddedHandle> = OnceLock::new();
+
+        SERVICE
+            .get_or_init(|| {
+                let config_dir = config_dir.as_ref();
+                std::fs::create_dir_all(config_dir).unwrap();
+                RequestDb::set_path(config_dir.join(DATABASE_FILE));
+                let network = NetworkManager::get_instance()
+                    .lock()
+                    .unwrap()
+                    .network
+                    .clone();
+                let task_manager = TaskManager::init(
+                    RunCountManager::init(),
+                    ClientManager::init(),
+                    ActiveCounter::new(),
+                    network,
+                );
+                let handle = EmbeddedHandle { task_manager };
+                handle.set_online(true);
+                handle
+            })
+            .clone()
+    }
+}
+
+/// Handle to the request service running in the process.
+#[derive(Clone)]
+pub struct EmbeddedHandle {
+    task_manager: TaskManagerTx,
+}
+
+impl EmbeddedHandle {
+    /// Returns a client acting for the application of `uid`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use download_server::embedded::EmbeddedRequestService;
+    ///
+    /// let client = EmbeddedRequestService::new("/tmp/request").client(100);
+    /// ```
+    pub fn client(&self, uid: u64) -> EmbeddedClient {
+        EmbeddedClient::new(uid, self.task_manager.clone())
+    }
+
+    /// Reports the network as an unmetered wifi connection if `online`, or
+    /// as offline otherwise. Tasks wait while the network is offline.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use download_server::embedded::EmbeddedRequestService;
+    ///
+    /// let handle = EmbeddedRequestService::new("/tmp/request");
+    /// handle.set_online(false);
+    /// ```
+    pub fn set_online(&self, online: bool) {
+        let network_manager = NetworkManager::get_instance().lock().unwrap();
+        let notified = if online {
+            network_manager.network.inner.notify_online(NetworkInfo {
+                network_type: NetworkType::Wifi,
+                is_metered: false,
+                is_roaming: false,
+            })
+        } else {
+            network_manager.network.inner.notify_offline();
+            true
+        };
+        drop(network_manager);
+        if notified {
+            self.task_manager.send_event(TaskManagerEvent::network());
+        }
+    }
+}
@@ -30,6 +30,9 @@ pub(crate) enum ErrorCode {
     TaskNotFound = 21900006,
     TaskStateErr = 21900007,
     GroupNotFound = 21900008,
+    ConstructThrottled = 21900009,
+    TooManyTasks = 21900010,
+    TooManyChannels = 21900011,
 }
 
 impl From<ServiceError> for ErrorCode {
@@ -40,7 +40,6 @@ mod macros;
 extern crate request_utils;
 
 cfg_oh! {
-    mod trace;
     pub mod ability;
     mod sys_event;
     pub use service::interface;
@@ -48,12 +47,18 @@ cfg_oh! {
 }
 
 mod database;
+#[cfg(all(feature = "embedded", not(feature = "oh")))]
+pub mod embedded;
 mod error;
 mod manage;
 mod service;
 mod task;
+#[cfg(any(feature = "oh", feature = "spans"))]
+mod trace;
 mod utils;
 pub use task::{config, info};
+#[cfg(feature = "spans")]
+pub use trace::span;
 
 use hilog_rust::{HiLogLabel, LogType};
 
@@ -115,3 +120,25 @@ mod tests {
         fn SetAccessTokenPermission();
     }
 }
+
+#[cfg(test)]
+mod fixtures {
+    include!("../tests/ut/fixtures.rs");
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod it {
+    pub(crate) mod harness {
+        include!("../tests/it/harness.rs");
+    }
+    mod it_download {
+        include!("../tests/it/it_download.rs");
+    }
+    mod it_loopback {
+        include!("../tests/it/it_loopback.rs");
+    }
+    mod it_upload {
+        include!("../tests/it/it_upload.rs");
+    }
+}
@@ -57,3 +57,14 @@ macro_rules! sys_event {
         }
     };
 }
+
+/// Logs a lifecycle detail of a task configured with `debug`, formatted only
+/// if the task is debugged.
+macro_rules! task_debug {
+    ($log:expr, $fmt:literal $(, $args:expr)* $(,)?) => {{
+        let log: &crate::task::debug_log::TaskLog = &$log;
+        if log.enabled() {
+            log.write(&format!($fmt $(, $args)*));
+        }
+    }};
+}
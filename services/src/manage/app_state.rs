@@ -162,6 +162,12 @@ extern "C" fn process_died_callback(uid: i32, state: i32, pid: i32, bundle_name:
                 .client_manager
                 .notify_process_terminate(pid as u64)
         };
+        unsafe {
+            APP_STATE_LISTENER
+                .assume_init_ref()
+                .task_manager
+                .notify_process_terminate(pid as u64)
+        };
     }
 }
 
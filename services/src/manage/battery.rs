@@ -0,0 +1,93 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Charging and thermal state of the device.
+//!
+//! Background tasks may be required to run only while the device is charging,
+//! and single tasks may wait while the device is too hot. Both states come
+//! from the common events of the system.
+
+use crate::manage::task_manager::TaskManagerTx;
+use crate::utils::{CommonEventSubscriber, CommonEventWant};
+
+/// Common event sent when the device starts charging.
+pub(crate) const CHARGING_EVENT: &str = "usual.event.CHARGING";
+/// Common event sent when the device stops charging.
+pub(crate) const DISCHARGING_EVENT: &str = "usual.event.DISCHARGING";
+/// Common event sent when the thermal level of the device changes.
+pub(crate) const THERMAL_LEVEL_EVENT: &str = "usual.event.THERMAL_LEVEL_CHANGED";
+
+/// Parameter of the thermal event carrying the new thermal level.
+const THERMAL_LEVEL_KEY: &str = "0";
+
+/// Subscriber forwarding one charging common event to the task manager.
+pub(crate) struct ChargingSubscriber {
+    /// Task manager transmitter for sending charging state events.
+    task_manager: TaskManagerTx,
+    /// Charging state the subscribed event reports.
+    charging: bool,
+}
+
+impl ChargingSubscriber {
+    /// Creates a subscriber for the event reporting `charging`.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_manager` - Transmitter for sending events to the task manager.
+    /// * `charging` - Whether the subscribed event means the device charges.
+    pub(crate) fn new(task_manager: TaskManagerTx, charging: bool) -> Self {
+        Self {
+            task_manager,
+            charging,
+        }
+    }
+}
+
+impl CommonEventSubscriber for ChargingSubscriber {
+    /// Forwards the charging state of the subscribed event.
+    fn on_receive_event(&self, _code: i32, _data: String, _want: CommonEventWant) {
+        info!("Receive charging event, charging: {}", self.charging);
+        self.task_manager.notify_charging_change(self.charging);
+    }
+}
+
+/// Subscriber forwarding thermal level changes to the task manager.
+pub(crate) struct ThermalSubscriber {
+    /// Task manager transmitter for sending thermal level events.
+    task_manager: TaskManagerTx,
+}
+
+impl ThermalSubscriber {
+    /// Creates a subscriber for the thermal level event.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_manager` - Transmitter for sending events to the task manager.
+    pub(crate) fn new(task_manager: TaskManagerTx) -> Self {
+        Self { task_manager }
+    }
+}
+
+impl CommonEventSubscriber for ThermalSubscriber {
+    /// Forwards the thermal level carried by the event.
+    fn on_receive_event(&self, _code: i32, _data: String, want: CommonEventWant) {
+        match want.get_int_param(THERMAL_LEVEL_KEY) {
+            Some(level) => {
+                info!("Receive thermal level event, level: {}", level);
+                self.task_manager
+                    .notify_thermal_change(level.clamp(0, u8::MAX as i32) as u8);
+            }
+            None => error!("Thermal level event without level"),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cleanup of abandoned upload body files.
+//!
+//! Clients write the bodies of upload tasks into `tmp_body_<index>_<nanos>`
+//! files in the app cache and record their paths in `body_file_paths`. Files
+//! of clients that died before constructing their task are referenced by no
+//! task row. On startup such files older than [`BODY_FILE_MAX_AGE`] are
+//! deleted, looking only into the cache directories known from the body files
+//! of recorded tasks.
+//!
+//! The cache of an application with no recorded upload task is therefore
+//! left unswept: its abandoned body files are only deleted once it records
+//! an upload again, or by the client removing the files of tasks it failed
+//! to construct.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::database::RequestDb;
+use crate::config::Action;
+use crate::task::files::convert_path;
+
+/// Unreferenced body files younger than this may still belong to a task
+/// being constructed.
+pub(crate) const BODY_FILE_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+const BODY_FILE_PREFIX: &str = "tmp_body_";
+const CACHE_DIR_NAME: &str = "cache";
+
+/// Returns whether `name` is exactly a body file name created by clients.
+pub(crate) fn is_body_file_name(name: &str) -> bool {
+    let Some((index, nanos)) = name
+        .strip_prefix(BODY_FILE_PREFIX)
+        .and_then(|rest| rest.split_once('_'))
+    else {
+        return false;
+    };
+    [index, nanos]
+        .iter()
+        .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Deletes body files referenced by no task and last modified more than
+/// `max_age` before `now`.
+///
+/// Only the cache directories holding `referenced` body files are searched.
+/// Returns the number of files deleted.
+pub(crate) fn sweep_body_files(
+    referenced: &HashSet<PathBuf>,
+    max_age: Duration,
+    now: SystemTime,
+) -> usize {
+    let dirs: HashSet<&Path> = referenced
+        .iter()
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| is_body_file_name(&name.to_string_lossy()))
+        })
+        .filter_map(|path| path.parent())
+        .filter(|dir| dir.file_name().is_some_and(|name| name == CACHE_DIR_NAME))
+        .collect();
+
+    let mut deleted = 0;
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if !is_body_file_name(&entry.file_name().to_string_lossy()) {
+                continue;
+            }
+            let path = entry.path();
+            if referenced.contains(&path) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let stale = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age >= max_age);
+            if !stale {
+                continue;
+            }
+            match fs::remove_file(&path) {
+                Ok(()) => deleted += 1,
+                Err(e) => error!("Failed to remove body file {:?}: {}", path, e),
+            }
+        }
+    }
+    deleted
+}
+
+/// Returns the service side paths of the body files of all upload tasks.
+fn referenced_body_files(db: &RequestDb) -> HashSet<PathBuf> {
+    let sql = format!(
+        "SELECT task_id FROM request_task WHERE action = {}",
+        Action::Upload.repr
+    );
+    let mut referenced = HashSet::new();
+    for task_id in db.query_integer::<u32>(&sql) {
+        let Some(config) = db.get_task_config(task_id) else {
+            continue;
+        };
+        if config.bundle.is_empty() {
+            continue;
+        }
+        for path in config.body_file_paths.iter() {
+            referenced.insert(PathBuf::from(convert_path(
+                config.common_data.uid,
+                &config.bundle,
+                path,
+            )));
+        }
+    }
+    referenced
+}
+
+/// Deletes the stale body files of abandoned uploads.
+///
+/// Reads every upload task and walks the cache directories, so it is run on
+/// a blocking thread.
+pub(crate) fn clear_stale_body_files() {
+    let referenced = referenced_body_files(RequestDb::get_instance());
+    let deleted = sweep_body_files(&referenced, BODY_FILE_MAX_AGE, SystemTime::now());
+    if deleted != 0 {
+        info!("removed {} stale body files", deleted);
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_body_files {
+    include!("../../tests/ut/manage/ut_body_files.rs");
+}
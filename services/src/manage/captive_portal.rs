@@ -0,0 +1,177 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Captive portal detection.
+//!
+//! Networks behind a captive portal claim connectivity, but answer every
+//! request with a sign-in page until the user signs in. Tasks then fail with
+//! TLS errors, or with the HTML page rejected by `expected_mime_types`.
+//!
+//! Before such a task fails while the network is online, the scheduler probes
+//! a `generate_204` url. Any answer other than `204 No Content` means the
+//! traffic is intercepted, so the task waits with
+//! `WaitingCause::CaptivePortal` instead. The url is probed again
+//! periodically, and the waiting tasks return to the queue once it answers
+//! normally.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use ylong_http_client::async_impl::{Body, Client, RequestBuilder};
+use ylong_http_client::{Redirect, Timeout};
+
+use crate::info::State;
+use crate::manage::database::RequestDb;
+use crate::manage::task_manager::TaskManagerTx;
+use crate::task::content_type::UNEXPECTED_CONTENT_TYPE;
+use crate::task::reason::Reason;
+use crate::utils::runtime_spawn;
+
+/// Url answering `204 No Content` to clients with direct internet access.
+pub(crate) const DEFAULT_PROBE_URL: &str =
+    "http://connectivitycheck.platform.hicloud.com/generate_204";
+
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+/// Probe url overriding `DEFAULT_PROBE_URL`, empty for the default.
+static PROBE_URL: Mutex<String> = Mutex::new(String::new());
+
+/// Interval between probes while a captive portal is detected.
+static REPROBE_INTERVAL_MS: AtomicU64 = AtomicU64::new(30 * 1000);
+
+/// Replaces the url probed for a captive portal, empty restores the default.
+pub(crate) fn set_probe_url(url: &str) {
+    *PROBE_URL.lock().unwrap() = url.to_string();
+}
+
+/// Changes the interval between probes while a captive portal is detected.
+pub(crate) fn set_reprobe_interval(interval: Duration) {
+    REPROBE_INTERVAL_MS.store(interval.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Checks whether a task failure may be caused by a captive portal.
+///
+/// # Arguments
+///
+/// * `reason` - The reason the task failed with.
+/// * `extras` - The progress `extras` of the task.
+///
+/// # Returns
+///
+/// `true` for TLS errors and for HTML responses rejected by the content type
+/// allowlist of the task.
+pub(crate) fn is_portal_symptom(reason: Reason, extras: &HashMap<String, String>) -> bool {
+    match reason {
+        Reason::Ssl => true,
+        Reason::UnexpectedContentType => extras
+            .get(UNEXPECTED_CONTENT_TYPE)
+            .is_some_and(|content_type| is_html(content_type)),
+        _ => false,
+    }
+}
+
+fn is_html(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    mime.eq_ignore_ascii_case("text/html") || mime.eq_ignore_ascii_case("application/xhtml+xml")
+}
+
+/// Probes whether a captive portal intercepts the traffic of the device.
+///
+/// # Returns
+///
+/// `true` if the probe url answers with anything but `204 No Content`.
+/// Probes that get no answer at all detect no portal, the network is rather
+/// unreachable then.
+pub(crate) async fn probe() -> bool {
+    let url = match PROBE_URL.lock().unwrap().as_str() {
+        "" => DEFAULT_PROBE_URL.to_string(),
+        url => url.to_string(),
+    };
+    let client = match Client::builder()
+        .connect_timeout(Timeout::from_secs(PROBE_TIMEOUT_SECS))
+        .total_timeout(Timeout::from_secs(2 * PROBE_TIMEOUT_SECS))
+        .redirect(Redirect::none())
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            error!("build captive portal probe client failed, {:?}", e);
+            return false;
+        }
+    };
+    let request = match RequestBuilder::new()
+        .method("GET")
+        .url(url.as_str())
+        .body(Body::empty())
+    {
+        Ok(request) => request,
+        Err(e) => {
+            error!("build captive portal probe {} failed, {:?}", url, e);
+            return false;
+        }
+    };
+    match client.request(request).await {
+        Ok(response) => {
+            let status = response.status().as_u16();
+            info!("captive portal probe answered {}", status);
+            status != 204
+        }
+        Err(e) => {
+            info!("captive portal probe failed, {:?}", e);
+            false
+        }
+    }
+}
+
+/// Probes once and reports the result to the task manager.
+pub(crate) fn spawn_probe(task_manager: TaskManagerTx) {
+    runtime_spawn(async move {
+        let portal = probe().await;
+        task_manager.notify_captive_portal(portal);
+    });
+}
+
+/// Probes periodically, reporting every result to the task manager, until
+/// no captive portal is detected anymore.
+pub(crate) fn spawn_reprobe(task_manager: TaskManagerTx) {
+    runtime_spawn(async move {
+        loop {
+            let interval = REPROBE_INTERVAL_MS.load(Ordering::Relaxed);
+            ylong_runtime::time::sleep(Duration::from_millis(interval)).await;
+            let portal = probe().await;
+            task_manager.notify_captive_portal(portal);
+            if !portal {
+                break;
+            }
+        }
+    });
+}
+
+impl RequestDb {
+    /// Checks whether any task waits for a captive portal.
+    pub(crate) fn has_captive_portal_tasks(&self) -> bool {
+        let sql = format!(
+            "SELECT task_id FROM request_task WHERE state = {} AND reason = {} LIMIT 1",
+            State::Waiting.repr,
+            Reason::CaptivePortal.repr,
+        );
+        !self.query_integer::<u32>(&sql).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod ut_captive_portal {
+    include!("../../tests/ut/manage/ut_captive_portal.rs");
+}
@@ -76,6 +76,17 @@ impl SystemConfigManager {
             certs,
         }
     }
+
+    /// Returns the URL of the system proxy, empty if none is set.
+    pub(crate) fn proxy_url(&self) -> String {
+        let host = self.proxy.host();
+        let port = self.proxy.port();
+        if host.is_empty() || port.is_empty() {
+            host
+        } else {
+            format!("{}:{}", host, port)
+        }
+    }
 }
 
 /// Holds system configuration parameters for network requests.
@@ -24,17 +24,33 @@ cfg_oh! {
 }
 
 cfg_not_oh! {
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+    use std::sync::OnceLock;
+
     use rusqlite::Connection;
-    const CREATE_TABLE: &'static str = "CREATE TABLE IF NOT EXISTS request_task (task_id INTEGER PRIMARY KEY, uid INTEGER, token_id INTEGER, action INTEGER, mode INTEGER, cover INTEGER, network INTEGER, metered INTEGER, roaming INTEGER, ctime INTEGER, mtime INTEGER, reason INTEGER, gauge INTEGER, retry INTEGER, redirect INTEGER, tries INTEGER, version INTEGER, config_idx INTEGER, begins INTEGER, ends INTEGER, precise INTEGER, priority INTEGER, background INTEGER, bundle TEXT, url TEXT, data TEXT, token TEXT, title TEXT, description TEXT, method TEXT, headers TEXT, config_extras TEXT, mime_type TEXT, state INTEGER, idx INTEGER, total_processed INTEGER, sizes TEXT, processed TEXT, extras TEXT, form_items BLOB, file_specs BLOB, each_file_status BLOB, body_file_names BLOB, certs_paths BLOB)";
+
+    /// File the database is kept in, in memory if unset.
+    static DATABASE_PATH: OnceLock<PathBuf> = OnceLock::new();
+    const CREATE_TABLE: &'static str = "CREATE TABLE IF NOT EXISTS request_task (task_id INTEGER PRIMARY KEY, uid INTEGER, token_id INTEGER, action INTEGER, mode INTEGER, cover INTEGER, network INTEGER, metered INTEGER, roaming INTEGER, ctime INTEGER, mtime INTEGER, reason INTEGER, gauge INTEGER, retry INTEGER, redirect INTEGER, tries INTEGER, version INTEGER, config_idx INTEGER, begins INTEGER, ends INTEGER, precise INTEGER, priority INTEGER, background INTEGER, bundle TEXT, url TEXT, data TEXT, token TEXT, title TEXT, description TEXT, method TEXT, headers TEXT, config_extras TEXT, mime_type TEXT, state INTEGER, idx INTEGER, total_processed INTEGER, sizes TEXT, processed TEXT, extras TEXT, form_items BLOB, file_specs BLOB, each_file_status BLOB, body_file_names BLOB, certs_paths BLOB, response_body BLOB, response_truncated INTEGER, metered_override INTEGER, client_tag BLOB, destination BLOB, unwritten_ranges BLOB, revision INTEGER, pause_on_thermal_level INTEGER, require_charging_while_running INTEGER, debug INTEGER, options BLOB, privileged INTEGER)";
+    const CREATE_UID_STATE_INDEX: &'static str = "CREATE INDEX IF NOT EXISTS uid_state_index ON request_task (uid, state)";
+    const CREATE_UID_URL_INDEX: &'static str = "CREATE INDEX IF NOT EXISTS uid_url_index ON request_task (uid, url)";
+    const CREATE_UID_CTIME_INDEX: &'static str = "CREATE INDEX IF NOT EXISTS uid_ctime_index ON request_task (uid, ctime)";
+    const CREATE_CTIME_INDEX: &'static str = "CREATE INDEX IF NOT EXISTS ctime_index ON request_task (ctime)";
+    /// `PRAGMA auto_vacuum` value of incremental auto-vacuum.
+    const AUTO_VACUUM_INCREMENTAL: i64 = 2;
 }
 use crate::config::Action;
 use crate::error::ErrorCode;
 use crate::service::client::ClientManagerEntry;
 use crate::task::config::TaskConfig;
-use crate::task::ffi::{CTaskConfig, CTaskInfo, CUpdateInfo};
-use crate::task::info::{State, TaskInfo, UpdateInfo};
+use crate::task::ffi::{CTaskConfig, CTaskInfo};
+use crate::task::info::{State, TaskInfo};
+use crate::task::notify::ResponseBody;
 use crate::task::reason::Reason;
 use crate::task::request_task::RequestTask;
+use crate::utils::form_item::FileSpec;
+use crate::utils::url_rewrite::UrlRewriter;
 use crate::utils::{call_once, get_current_timestamp, hashmap_to_string};
 
 pub(crate) struct RequestDb {
@@ -75,8 +91,23 @@ impl RequestDb {
         static ONCE: Once = Once::new();
 
         call_once(&ONCE, || {
-            let inner = Connection::open_in_memory().unwrap();
+            let inner = match DATABASE_PATH.get() {
+                Some(path) => Connection::open(path).unwrap(),
+                None => Connection::open_in_memory().unwrap(),
+            };
             inner.execute(&CREATE_TABLE, ()).unwrap();
+            add_missing_columns(&inner).unwrap();
+            inner.execute(&CREATE_UID_STATE_INDEX, ()).unwrap();
+            inner.execute(&CREATE_UID_URL_INDEX, ()).unwrap();
+            inner.execute(&CREATE_UID_CTIME_INDEX, ()).unwrap();
+            inner.execute(&CREATE_CTIME_INDEX, ()).unwrap();
+            let auto_vacuum: i64 = inner
+                .query_row("PRAGMA auto_vacuum", (), |row| row.get(0))
+                .unwrap_or(0);
+            if auto_vacuum != AUTO_VACUUM_INCREMENTAL {
+                // Switching an existing database takes a full vacuum.
+                let _ = inner.execute_batch("PRAGMA auto_vacuum = INCREMENTAL; VACUUM;");
+            }
             unsafe {
                 DATABASE.write(RequestDb {
                     inner,
@@ -88,6 +119,15 @@ impl RequestDb {
         unsafe { DATABASE.assume_init_ref() }
     }
 
+    /// Keeps the database in the file at `path` instead of in memory, it has
+    /// no effect once the database is in use.
+    ///
+    /// Returns `false` if a path was already set.
+    #[cfg(not(feature = "oh"))]
+    pub(crate) fn set_path(path: PathBuf) -> bool {
+        DATABASE_PATH.set(path).is_ok()
+    }
+
     #[cfg(feature = "oh")]
     pub(crate) fn execute(&self, sql: &str) -> Result<(), i32> {
         let ret = unsafe { Pin::new_unchecked(&mut *self.inner).ExecuteSql(sql) };
@@ -165,6 +205,68 @@ impl RequestDb {
             .collect()
     }
 
+    /// Queries rows of `columns` integer columns.
+    ///
+    /// # Returns
+    ///
+    /// The rows in the order the query returns them, each holding the values
+    /// of its columns.
+    #[cfg(feature = "oh")]
+    pub(crate) fn query_integer_rows(&self, sql: &str, columns: usize) -> Vec<Vec<i64>> {
+        let mut v = vec![];
+        let ret =
+            unsafe { Pin::new_unchecked(&mut *self.inner).QueryIntegerRows(sql, columns, &mut v) };
+        if ret != 0 {
+            error!("query integer rows err:{}", ret);
+            sys_event!(
+                ExecFault,
+                DfxCode::RDB_FAULT_06,
+                &format!("query integer rows err:{}", ret)
+            );
+        }
+        v.chunks_exact(columns).map(|row| row.to_vec()).collect()
+    }
+
+    /// Queries rows of `columns` integer columns.
+    ///
+    /// # Returns
+    ///
+    /// The rows in the order the query returns them, each holding the values
+    /// of its columns.
+    #[cfg(not(feature = "oh"))]
+    pub(crate) fn query_integer_rows(&self, sql: &str, columns: usize) -> Vec<Vec<i64>> {
+        let mut stmt = self.inner.prepare(sql).unwrap();
+        let rows = stmt
+            .query_map([], |row| {
+                (0..columns).map(|i| row.get::<_, i64>(i)).collect()
+            })
+            .unwrap();
+        rows.into_iter().map(|row| row.unwrap()).collect()
+    }
+
+    #[cfg(feature = "oh")]
+    pub(crate) fn query_blob(&self, sql: &str) -> Option<Vec<u8>> {
+        let mut v = vec![];
+        let ret = unsafe { Pin::new_unchecked(&mut *self.inner).QueryBlob(sql, &mut v) };
+        if ret != 0 {
+            error!("query blob err:{}", ret);
+            sys_event!(
+                ExecFault,
+                DfxCode::RDB_FAULT_06,
+                &format!("query blob err:{}", ret)
+            );
+            return None;
+        }
+        Some(v)
+    }
+
+    #[cfg(not(feature = "oh"))]
+    pub(crate) fn query_blob(&self, sql: &str) -> Option<Vec<u8>> {
+        self.inner
+            .query_row(sql, [], |row| row.get::<_, Vec<u8>>(0))
+            .ok()
+    }
+
     pub(crate) fn contains_task(&self, task_id: u32) -> bool {
         let sql = format!(
             "SELECT COUNT(*) FROM request_task WHERE task_id = {}",
@@ -225,6 +327,7 @@ impl RequestDb {
         if !unsafe { RecordRequestTask(&c_task_info, &c_task_config) } {
             info!("task {} insert database fail", task_id);
         }
+        self.update_options(task_id, task_config);
 
         // For some tasks contains user_file, we must save it to map first.
         if task.conf.contains_user_file() {
@@ -287,6 +390,7 @@ impl RequestDb {
             State::Initialized.repr,
         );
         self.execute(&sql).unwrap();
+        self.update_options(task_id, config);
 
         // For some tasks contains user_file, we must save it to map first.
         if task.conf.contains_user_file() {
@@ -298,26 +402,16 @@ impl RequestDb {
         true
     }
 
+    pub(crate) fn get_user_file_task(&self, task_id: u32) -> Option<Arc<RequestTask>> {
+        self.user_file_tasks.lock().unwrap().get(&task_id).cloned()
+    }
+
     pub(crate) fn remove_user_file_task(&self, task_id: u32) {
         let mut task_map = self.user_file_tasks.lock().unwrap();
         task_map.remove(&task_id);
         debug!("Remove completed user file task, task_id: {}", task_id);
     }
 
-    #[cfg(feature = "oh")]
-    pub(crate) fn update_task(&self, task_id: u32, update_info: UpdateInfo) {
-        debug!("Update task in database, task_id: {}", task_id);
-        if !self.contains_task(task_id) {
-            return;
-        }
-        let sizes = format!("{:?}", update_info.progress.sizes);
-        let processed = format!("{:?}", update_info.progress.processed);
-        let extras = hashmap_to_string(&update_info.progress.extras);
-        let c_update_info = update_info.to_c_struct(&sizes, &processed, &extras);
-        let ret = unsafe { UpdateRequestTask(task_id, &c_update_info) };
-        debug!("Update task in database, ret is {}", ret);
-    }
-
     pub(crate) fn update_task_time(&self, task_id: u32, task_time: u64) {
         let ret = unsafe { UpdateRequestTaskTime(task_id, task_time) };
         debug!("Update task time in database, ret is {}", ret);
@@ -348,20 +442,22 @@ impl RequestDb {
         })
     }
 
-    #[cfg(not(feature = "oh"))]
-    pub(crate) fn update_task(&self, task_id: u32, update_info: UpdateInfo) {
-        if !self.contains_task(task_id) {
-            return;
-        }
-        let sql = format!(
-            "UPDATE request_task SET sizes = {:?}, processed = {:?}, extras = {} WHERE task_id = {}",
-            update_info.progress.sizes, update_info.progress.processed, hashmap_to_string(&update_info.progress.extras),
-            task_id,
-        );
-        self.execute(&sql).unwrap();
+    pub(crate) fn update_task_state(&self, task_id: u32, state: State, reason: Reason) {
+        let _ = self.commit_task_state(task_id, state, reason);
     }
 
-    pub(crate) fn update_task_state(&self, task_id: u32, state: State, reason: Reason) {
+    /// Writes the state and reason of a task.
+    ///
+    /// # Returns
+    ///
+    /// `Err(ErrorCode::SystemApi)` if the write failed, in which case the
+    /// change must not be reported to clients.
+    pub(crate) fn commit_task_state(
+        &self,
+        task_id: u32,
+        state: State,
+        reason: Reason,
+    ) -> Result<(), ErrorCode> {
         let sql = format!(
             "UPDATE request_task SET state = {}, mtime = {}, reason = {} WHERE task_id = {}",
             state.repr,
@@ -369,7 +465,7 @@ impl RequestDb {
             reason.repr,
             task_id
         );
-        let _ = self.execute(&sql);
+        self.execute(&sql).map_err(|_| ErrorCode::SystemApi)
     }
 
     pub(crate) fn update_task_max_speed(&self, task_id: u32, max_speed: i64) {
@@ -380,6 +476,196 @@ impl RequestDb {
         let _ = self.execute(&sql);
     }
 
+    pub(crate) fn update_task_response_body(&self, task_id: u32, body: &ResponseBody) {
+        let sql = format!(
+            "UPDATE request_task SET response_body = X'{}', response_truncated = {} WHERE task_id = {}",
+            hex(&body.data),
+            body.truncated as u8,
+            task_id
+        );
+        let _ = self.execute(&sql);
+    }
+
+    pub(crate) fn query_task_response_body(&self, task_id: u32) -> Option<ResponseBody> {
+        let sql = format!(
+            "SELECT response_truncated FROM request_task WHERE task_id = {} AND response_body IS NOT NULL",
+            task_id
+        );
+        let truncated = *self.query_integer::<u8>(&sql).first()? == 1;
+        let sql = format!(
+            "SELECT response_body FROM request_task WHERE task_id = {}",
+            task_id
+        );
+        let data = self.query_blob(&sql)?;
+        Some(ResponseBody { data, truncated })
+    }
+
+    /// Stores the options of a task, they are written once at creation.
+    ///
+    /// The options are kept serialized in one column, the client tag and the
+    /// options the scheduler filters on are also copied to columns of their
+    /// own. Whether the caller was privileged is kept along, options are
+    /// checked against it again on restore.
+    fn update_options(&self, task_id: u32, config: &TaskConfig) {
+        let sql = format!(
+            "UPDATE request_task SET privileged = {}, options = X'{}', client_tag = X'{}', metered_override = {}, debug = {}, pause_on_thermal_level = {}, require_charging_while_running = {} WHERE task_id = {}",
+            config.privileged as u8,
+            hex(config.encode_options()),
+            hex(&config.client_tag),
+            config.treat_network_as.map_or(0, |treat_as| treat_as as u8),
+            config.debug as u8,
+            config
+                .pause_on_thermal_level
+                .map_or("NULL".to_string(), |level| level.to_string()),
+            config.require_charging_while_running as u8,
+            task_id
+        );
+        let _ = self.execute(&sql);
+    }
+
+    /// Restores the stored options of a task into its configuration.
+    fn apply_options(&self, task_id: u32, config: &mut TaskConfig) {
+        let sql = format!(
+            "SELECT COUNT(*) FROM request_task WHERE task_id = {} AND privileged = 1",
+            task_id
+        );
+        config.privileged =
+            matches!(self.query_integer::<u32>(&sql).first(), Some(count) if *count > 0);
+        // Tasks stored by older versions have no options.
+        let sql = format!(
+            "SELECT length(options) FROM request_task WHERE task_id = {} AND options IS NOT NULL",
+            task_id
+        );
+        if self.query_integer::<u32>(&sql).first().is_none() {
+            return;
+        }
+        let sql = format!(
+            "SELECT options FROM request_task WHERE task_id = {}",
+            task_id
+        );
+        if let Some(options) = self.query_blob(&sql) {
+            config.decode_options(&options);
+        }
+    }
+
+    /// Returns the client tag of a task, empty if it has none.
+    pub(crate) fn query_client_tag(&self, task_id: u32) -> String {
+        // Most tasks have no tag, check first so the blob query finds a row.
+        let sql = format!(
+            "SELECT length(client_tag) FROM request_task WHERE task_id = {} AND client_tag IS NOT NULL",
+            task_id
+        );
+        match self.query_integer::<u32>(&sql).first() {
+            Some(len) if *len > 0 => {}
+            _ => return String::new(),
+        }
+        let sql = format!(
+            "SELECT client_tag FROM request_task WHERE task_id = {}",
+            task_id
+        );
+        self.query_blob(&sql)
+            .and_then(|tag| String::from_utf8(tag).ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether the task logs in detail.
+    pub(crate) fn query_debug(&self, task_id: u32) -> bool {
+        let sql = format!(
+            "SELECT IFNULL(debug, 0) FROM request_task WHERE task_id = {}",
+            task_id
+        );
+        self.query_integer::<u8>(&sql)
+            .first()
+            .is_some_and(|value| *value != 0)
+    }
+
+    /// Stores the path a paused download was moved to.
+    pub(crate) fn update_destination(&self, task_id: u32, path: &str) -> Result<(), ErrorCode> {
+        let sql = format!(
+            "UPDATE request_task SET destination = X'{}' WHERE task_id = {}",
+            hex(path),
+            task_id
+        );
+        self.execute(&sql).map_err(|_| ErrorCode::SystemApi)
+    }
+
+    /// Returns the path a download was moved to, `None` if it was never moved.
+    pub(crate) fn query_destination(&self, task_id: u32) -> Option<String> {
+        let sql = format!(
+            "SELECT length(destination) FROM request_task WHERE task_id = {} AND destination IS NOT NULL",
+            task_id
+        );
+        self.query_integer::<u32>(&sql).first()?;
+        let sql = format!(
+            "SELECT destination FROM request_task WHERE task_id = {}",
+            task_id
+        );
+        self.query_blob(&sql)
+            .and_then(|path| String::from_utf8(path).ok())
+    }
+
+    /// Replaces the path of the downloaded file by the one it was moved to.
+    pub(crate) fn apply_destination(&self, task_id: u32, file_specs: &mut [FileSpec]) {
+        let Some(spec) = file_specs.first_mut() else {
+            return;
+        };
+        if let Some(path) = self.query_destination(task_id) {
+            spec.path = path;
+        }
+    }
+
+    /// Records the byte ranges of a download left unwritten, empty once the
+    /// file is complete or cleared.
+    pub(crate) fn update_unwritten_ranges(&self, task_id: u32, ranges: &[(u64, u64)]) {
+        let sql = if ranges.is_empty() {
+            format!(
+                "UPDATE request_task SET unwritten_ranges = NULL WHERE task_id = {}",
+                task_id
+            )
+        } else {
+            let value = ranges
+                .iter()
+                .map(|(begin, end)| format!("{}-{}", begin, end))
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(
+                "UPDATE request_task SET unwritten_ranges = X'{}' WHERE task_id = {}",
+                hex(value),
+                task_id
+            )
+        };
+        let _ = self.execute(&sql);
+    }
+
+    /// Returns the inclusive byte ranges of a download left unwritten, empty
+    /// if the file has no holes.
+    pub(crate) fn query_unwritten_ranges(&self, task_id: u32) -> Vec<(u64, u64)> {
+        let sql = format!(
+            "SELECT length(unwritten_ranges) FROM request_task WHERE task_id = {} AND unwritten_ranges IS NOT NULL",
+            task_id
+        );
+        if self.query_integer::<u32>(&sql).first().is_none() {
+            return Vec::new();
+        }
+        let sql = format!(
+            "SELECT unwritten_ranges FROM request_task WHERE task_id = {}",
+            task_id
+        );
+        let Some(value) = self
+            .query_blob(&sql)
+            .and_then(|value| String::from_utf8(value).ok())
+        else {
+            return Vec::new();
+        };
+        value
+            .split(',')
+            .filter_map(|range| {
+                let (begin, end) = range.split_once('-')?;
+                Some((begin.parse().ok()?, end.parse().ok()?))
+            })
+            .collect()
+    }
+
     pub(crate) fn update_task_sizes(&self, task_id: u32, sizes: &Vec<i64>) {
         let sql = format!(
             "UPDATE request_task SET sizes = '{:?}' WHERE task_id = {}",
@@ -397,8 +683,10 @@ impl RequestDb {
             return None;
         }
         let c_task_info = unsafe { &*c_task_info };
-        let task_info = TaskInfo::from_c_struct(c_task_info);
+        let mut task_info = TaskInfo::from_c_struct(c_task_info);
         unsafe { DeleteCTaskInfo(c_task_info) };
+        task_info.client_tag = self.query_client_tag(task_id);
+        self.apply_destination(task_id, &mut task_info.file_specs);
         Some(task_info)
     }
 
@@ -421,11 +709,15 @@ impl RequestDb {
     pub(crate) fn get_task_info(&self, task_id: u32) -> Option<TaskInfo> {
         use crate::info::CommonTaskInfo;
         use crate::task::notify::Progress;
+        use crate::utils::string_to_hashmap;
 
-        let sql = format!("SELECT task_id, uid, action, mode, mtime, reason, gauge, retry, version, priority, ctime, tries, url, data, token, state, idx from request_task where task_id = {}", task_id);
+        let sql = format!("SELECT task_id, uid, action, mode, mtime, reason, gauge, retry, version, priority, ctime, tries, url, data, token, state, idx, IFNULL(extras, '') from request_task where task_id = {}", task_id);
         let mut stmt = self.inner.prepare(&sql).unwrap();
         let mut row = stmt
             .query_map([], |row| {
+                let mut progress = Progress::new(vec![]);
+                let mut extras: String = row.get(17).unwrap();
+                progress.extras = string_to_hashmap(&mut extras);
                 Ok(TaskInfo {
                     common_data: CommonTaskInfo {
                         task_id: row.get(0).unwrap(),
@@ -452,11 +744,18 @@ impl RequestDb {
                     each_file_status: vec![],
                     form_items: vec![],
                     file_specs: vec![],
-                    progress: Progress::new(vec![]),
+                    progress,
+                    effective_speed_limit: 0,
+                    response_body: None,
+                    client_tag: String::new(),
                 })
             })
             .unwrap();
-        row.next().map(|info| info.unwrap())
+        row.next().map(|info| {
+            let mut info = info.unwrap();
+            info.client_tag = self.query_client_tag(task_id);
+            info
+        })
     }
 
     #[cfg(feature = "oh")]
@@ -472,8 +771,12 @@ impl RequestDb {
             );
             None
         } else {
-            let task_config = TaskConfig::from_c_struct(unsafe { &*c_task_config });
+            let mut task_config = TaskConfig::from_c_struct(unsafe { &*c_task_config });
             unsafe { DeleteCTaskConfig(c_task_config) };
+            // The effective URL is not stored, rules are applied again on restore.
+            UrlRewriter::get_instance().apply(&mut task_config);
+            self.apply_options(task_id, &mut task_config);
+            self.apply_destination(task_id, &mut task_config.file_specs);
             Some(task_config)
         }
     }
@@ -528,10 +831,17 @@ impl RequestDb {
                     proxy: Default::default(),
                     certificate_pins: Default::default(),
                     atomic_account: Default::default(),
+                    ..Default::default()
                 })
             })
             .unwrap();
-        row.next().map(|config| config.unwrap())
+        row.next().map(|config| {
+            let mut config = config.unwrap();
+            UrlRewriter::get_instance().apply(&mut config);
+            self.apply_options(task_id, &mut config);
+            self.apply_destination(task_id, &mut config.file_specs);
+            config
+        })
     }
 
     #[cfg(feature = "oh")]
@@ -652,6 +962,10 @@ impl RequestDb {
             );
             return Err(ErrorCode::TaskStateErr);
         }
+        if state == State::PendingRemoval {
+            error!("get_task state is PendingRemoval, {}", task_id);
+            return Err(ErrorCode::TaskStateErr);
+        }
 
         match RequestTask::new_by_info(
             config,
@@ -675,6 +989,37 @@ impl RequestDb {
     }
 }
 
+/// Returns `bytes` in hexadecimal, the way SQL blob literals take them.
+pub(crate) fn hex(bytes: impl AsRef<[u8]>) -> String {
+    bytes
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Adds the columns of `CREATE_TABLE` a database file created by an older
+/// version lacks.
+#[cfg(not(feature = "oh"))]
+fn add_missing_columns(inner: &Connection) -> rusqlite::Result<()> {
+    let mut stmt = inner.prepare("PRAGMA table_info(request_task)")?;
+    let existing = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<HashSet<_>>>()?;
+    let columns = CREATE_TABLE
+        .split_once('(')
+        .map_or("", |(_, columns)| columns)
+        .trim_end_matches(')');
+    for column in columns.split(", ") {
+        let name = column.split(' ').next().unwrap_or_default();
+        if !existing.contains(name) {
+            let sql = format!("ALTER TABLE request_task ADD COLUMN {}", column);
+            inner.execute(&sql, ())?;
+        }
+    }
+    Ok(())
+}
+
 unsafe impl Send for RequestDb {}
 unsafe impl Sync for RequestDb {}
 
@@ -686,7 +1031,6 @@ extern "C" {
     fn GetTaskInfo(task_id: u32) -> *const CTaskInfo;
     fn QueryTaskConfig(task_id: u32) -> *const CTaskConfig;
     fn RecordRequestTask(info: *const CTaskInfo, config: *const CTaskConfig) -> bool;
-    fn UpdateRequestTask(id: u32, info: *const CUpdateInfo) -> bool;
     fn UpdateRequestTaskTime(task_id: u32, taskTime: u64) -> bool;
 }
 
@@ -707,6 +1051,13 @@ mod ffi {
         fn GetDatabaseInstance(path: &str, encrypt: bool) -> *mut RequestDataBase;
         fn ExecuteSql(self: Pin<&mut RequestDataBase>, sql: &str) -> i32;
         fn QueryInteger(self: Pin<&mut RequestDataBase>, sql: &str, v: &mut Vec<i64>) -> i32;
+        fn QueryIntegerRows(
+            self: Pin<&mut RequestDataBase>,
+            sql: &str,
+            columns: usize,
+            v: &mut Vec<i64>,
+        ) -> i32;
+        fn QueryBlob(self: Pin<&mut RequestDataBase>, sql: &str, v: &mut Vec<u8>) -> i32;
         fn GetAppTaskQosInfos(
             self: Pin<&mut RequestDataBase>,
             sql: &str,
@@ -722,3 +1073,9 @@ mod ffi {
 mod ut_database {
     include!("../../tests/ut/manage/ut_database.rs");
 }
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_database_migrate {
+    include!("../../tests/ut/manage/ut_database_migrate.rs");
+}
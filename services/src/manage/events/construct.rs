@@ -21,14 +21,27 @@ cfg_oh! {
     use crate::ability::SYSTEM_CONFIG_MANAGER;
 }
 
-use crate::config::Mode;
+use crate::config::{Action, DuplicatePolicy, Mode};
 use crate::error::ErrorCode;
+use crate::info::State;
 use crate::manage::database::RequestDb;
+use crate::manage::scheduler::Scheduler;
 use crate::manage::TaskManager;
 use crate::task::config::TaskConfig;
 use crate::task::request_task::{check_config, get_rest_time, RequestTask};
+use crate::task::trace_context;
+#[cfg(feature = "spans")]
+use crate::trace::span;
 use crate::utils::task_id_generator::TaskIdGenerator;
 
+/// Largest number of live tasks of one application, unless overridden by
+/// [`MAX_LIVE_TASKS_PARAM`].
+pub(crate) const DEFAULT_MAX_LIVE_TASKS: usize = 4096;
+
+/// System parameter overriding the largest number of live tasks of one
+/// application.
+pub(crate) const MAX_LIVE_TASKS_PARAM: &str = "const.request.max_live_tasks";
+
 /// Maximum number of background tasks allowed per user ID.
 ///
 /// Includes tasks with mode `Mode::Background` and starts counting from 0.
@@ -39,6 +52,26 @@ const MAX_BACKGROUND_TASK: usize = 1001;
 /// Includes tasks with mode `Mode::FrontEnd` and starts counting from 0.
 const MAX_FRONTEND_TASK: usize = 2001;
 
+/// Returns the largest number of live tasks of one application.
+pub(crate) fn max_live_tasks() -> usize {
+    #[cfg(feature = "oh")]
+    let limit =
+        crate::utils::get_int_parameter(MAX_LIVE_TASKS_PARAM, DEFAULT_MAX_LIVE_TASKS as i64);
+    #[cfg(not(feature = "oh"))]
+    let limit = DEFAULT_MAX_LIVE_TASKS as i64;
+    usize::try_from(limit).unwrap_or(DEFAULT_MAX_LIVE_TASKS)
+}
+
+/// Task a creation resulted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct CreatedTask {
+    /// ID of the task.
+    pub(crate) task_id: u32,
+    /// Whether an active duplicate was returned instead of a new task, see
+    /// `DuplicatePolicy::Warn`.
+    pub(crate) duplicate: bool,
+}
+
 impl TaskManager {
     /// Creates a new request task with the provided configuration.
     ///
@@ -60,14 +93,89 @@ impl TaskManager {
     /// # Notes
     ///
     /// This method enforces task limits based on mode and user ID, generates a unique task ID,
-    /// validates the configuration, and initializes a new task.
-    pub(crate) fn create(&mut self, mut config: TaskConfig) -> Result<u32, ErrorCode> {
-        // Generate a unique task ID and assign it to the configuration
-        let task_id = TaskIdGenerator::generate();
-        config.common_data.task_id = task_id;
+    /// validates the configuration, and initializes a new task. Depending on the duplicate
+    /// policy of the configuration, an active download of the same URL and destination is
+    /// returned or fails the creation instead.
+    pub(crate) fn create(&mut self, config: TaskConfig) -> Result<u32, ErrorCode> {
+        self.create_task(config).map(|created| created.task_id)
+    }
+
+    /// Creates a new request task, telling whether an active duplicate was
+    /// returned instead.
+    pub(crate) fn create_task(&mut self, config: TaskConfig) -> Result<CreatedTask, ErrorCode> {
+        self.create_with_ids(config, std::iter::repeat_with(TaskIdGenerator::generate))
+    }
 
-        // Extract user ID and version for logging and validation
+    /// Creates a new request task, trying the task IDs of `candidates`.
+    ///
+    /// Candidates used by a stored task are skipped. Only the task manager
+    /// inserts tasks, so the ID found free is still free when the task is
+    /// inserted.
+    ///
+    /// # Errors
+    ///
+    /// * `ErrorCode::TooManyTasks` - The application has as many live tasks
+    ///   as `max_live_tasks`, checked before the database is used.
+    /// * `ErrorCode::Other` - No candidate is free after `MAX_ID_ATTEMPTS`.
+    pub(crate) fn create_with_ids(
+        &mut self,
+        mut config: TaskConfig,
+        candidates: impl IntoIterator<Item = u32>,
+    ) -> Result<CreatedTask, ErrorCode> {
         let uid = config.common_data.uid;
+        let (frontend, background) = self.task_count.get(&uid).copied().unwrap_or_default();
+        if frontend + background >= self.max_live_tasks {
+            error!(
+                "{} live task count {} reaches the limit {}",
+                uid,
+                frontend + background,
+                self.max_live_tasks
+            );
+            return Err(ErrorCode::TooManyTasks);
+        }
+
+        // Files are stored and processed by descending priority
+        config.order_file_specs();
+
+        // Handle an active download of the same URL and destination
+        if config.duplicate_policy != DuplicatePolicy::Allow {
+            if let Some(task_id) = RequestDb::get_instance().find_duplicate(&config) {
+                if config.duplicate_policy == DuplicatePolicy::Reject {
+                    error!(
+                        "{} duplicates active task {}",
+                        config.common_data.uid, task_id
+                    );
+                    return Err(ErrorCode::TaskEnqueueErr);
+                }
+                info!(
+                    "{} duplicates active task {}, returned",
+                    config.common_data.uid, task_id
+                );
+                return Ok(CreatedTask {
+                    task_id,
+                    duplicate: true,
+                });
+            }
+        }
+
+        // Allocate a task ID no stored task uses and assign it to the configuration
+        let db = RequestDb::get_instance();
+        let Some(task_id) =
+            TaskIdGenerator::allocate_from(candidates, |task_id| db.contains_task(task_id))
+        else {
+            error!("{} found no free task id", uid);
+            #[cfg(feature = "oh")]
+            sys_event!(
+                ExecFault,
+                DfxCode::SA_ERROR_00,
+                &format!("{} found no free task id", uid)
+            );
+            return Err(ErrorCode::Other);
+        };
+        config.common_data.task_id = task_id;
+
+        // Extract mode and version for logging and validation
+        let mode = config.common_data.mode;
         let version = config.version;
 
         debug!(
@@ -103,26 +211,89 @@ impl TaskManager {
         #[cfg(feature = "oh")]
         let system_config = unsafe { SYSTEM_CONFIG_MANAGER.assume_init_ref().system_config() };
 
+        // Generate a trace context if the tracing policy of the app asks for one
+        trace_context::apply_policy(&mut config);
+
         // Calculate remaining time and validate task configuration
         let rest_time = get_rest_time(&config, 0);
-        let (files, client) = check_config(
+        let (files, client, used_family) = check_config(
             &config,
             rest_time,
             #[cfg(feature = "oh")]
             system_config,
         )?;
+        #[cfg(feature = "spans")]
+        span::task_created(
+            task_id,
+            &config.url,
+            config
+                .trace_context
+                .as_ref()
+                .map(|context| context.traceparent.as_str()),
+        );
         // Create a new request task with validated configuration and resources
         let task = RequestTask::new(
             config,
             files,
             client,
+            used_family,
             self.client_manager.clone(),
             false,
             rest_time,
         );
         // New task: State::Initialized, Reason::Default
         // Insert the new task into the database for persistence
-        RequestDb::get_instance().insert_task(task);
-        Ok(task_id)
+        if !db.insert_task(task) {
+            error!("{} task id {} is in use, task not inserted", uid, task_id);
+            Scheduler::reduce_task_count(uid, mode, &mut self.task_count);
+            return Err(ErrorCode::Other);
+        }
+        Ok(CreatedTask {
+            task_id,
+            duplicate: false,
+        })
     }
 }
+
+/// Returns the destination of a download, empty if it has none.
+fn destination(config: &TaskConfig) -> &str {
+    config
+        .file_specs
+        .first()
+        .map_or("", |spec| spec.path.as_str())
+}
+
+impl RequestDb {
+    /// Finds an active download of an application with the same URL and
+    /// destination as the given configuration.
+    ///
+    /// Candidates are looked up by the `(uid, url)` index, only their
+    /// destinations are compared by loading their configurations.
+    pub(crate) fn find_duplicate(&self, config: &TaskConfig) -> Option<u32> {
+        if config.common_data.action != Action::Download {
+            return None;
+        }
+        let sql = format!(
+            "SELECT task_id FROM request_task WHERE uid = {} AND url = '{}' AND action = {} AND state IN ({}, {}, {}, {}, {})",
+            config.common_data.uid,
+            config.url.replace('\'', "''"),
+            Action::Download.repr,
+            State::Initialized.repr,
+            State::Waiting.repr,
+            State::Running.repr,
+            State::Retrying.repr,
+            State::Paused.repr,
+        );
+        let saveas = destination(config);
+        self.query_integer::<u32>(&sql).into_iter().find(|task_id| {
+            self.get_task_config(*task_id)
+                .is_some_and(|active| destination(&active) == saveas)
+        })
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_construct {
+    include!("../../../tests/ut/manage/events/ut_construct.rs");
+}
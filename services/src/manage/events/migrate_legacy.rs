@@ -0,0 +1,148 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Migration of legacy download tasks for the task manager.
+//!
+//! Applications moving from the API9 download interface to the API10 agent
+//! want their existing downloads to be controllable through the new
+//! interface. Migrating rewrites the rows of those tasks as API10 tasks in
+//! place: the mode follows the legacy background flag, the progress gauge is
+//! off and the priority is the default, while progress and file paths are
+//! kept. Tasks that can not be migrated are left untouched and reported with
+//! the reason. Migrated tasks are API10 tasks, so migrating again does not
+//! change anything.
+
+use crate::config::{Action, Mode, Version};
+use crate::info::State;
+use crate::manage::database::RequestDb;
+use crate::manage::TaskManager;
+
+/// Why a legacy task was not migrated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum MigrationSkip {
+    /// The task is an upload, only downloads are migrated.
+    Upload = 1,
+    /// The task writes to a file opened by the user, which is only handed to
+    /// the task it was created for.
+    UserFile,
+    /// The task was removed.
+    Removed,
+    /// The configuration of the task could not be read.
+    Unreadable,
+}
+
+/// Outcome of migrating the legacy tasks of an application, every legacy
+/// task is either migrated or skipped.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct MigrationReport {
+    /// Tasks rewritten as API10 tasks.
+    pub(crate) migrated: Vec<u32>,
+    /// Tasks left untouched, with the reason.
+    pub(crate) skipped: Vec<(u32, MigrationSkip)>,
+}
+
+impl TaskManager {
+    /// Rewrites the legacy downloads of an application as API10 tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The application whose legacy tasks are migrated.
+    ///
+    /// # Returns
+    ///
+    /// The migrated tasks and the skipped ones with the reason.
+    pub(crate) fn migrate_legacy_tasks(&mut self, uid: u64) -> MigrationReport {
+        let db = RequestDb::get_instance();
+        let mut report = MigrationReport::default();
+        for task_id in db.legacy_tasks(uid) {
+            match db.migrate_legacy_task(task_id) {
+                Ok(mode) => {
+                    // Queued tasks are ordered by mode, move them to their new
+                    // mode as well.
+                    if mode == Mode::BackGround {
+                        if let Err(e) = self.scheduler.task_set_mode(uid, task_id, mode) {
+                            error!("migrate legacy task {} set mode failed {:?}", task_id, e);
+                        }
+                    }
+                    report.migrated.push(task_id);
+                }
+                Err(reason) => report.skipped.push((task_id, reason)),
+            }
+        }
+        info!(
+            "migrate legacy tasks of uid {}, {} migrated, {} skipped",
+            uid,
+            report.migrated.len(),
+            report.skipped.len()
+        );
+        report
+    }
+}
+
+impl RequestDb {
+    /// Returns the legacy tasks of an application.
+    fn legacy_tasks(&self, uid: u64) -> Vec<u32> {
+        let sql = format!(
+            "SELECT task_id FROM request_task WHERE uid = {} AND version = {} ORDER BY task_id",
+            uid,
+            Version::API9 as u8
+        );
+        self.query_integer(&sql)
+    }
+
+    /// Rewrites the row of a legacy task as an API10 task, returning its new
+    /// mode.
+    fn migrate_legacy_task(&self, task_id: u32) -> Result<Mode, MigrationSkip> {
+        let Some(config) = self.get_task_config(task_id) else {
+            return Err(MigrationSkip::Unreadable);
+        };
+        if config.common_data.action != Action::Download {
+            return Err(MigrationSkip::Upload);
+        }
+        if config.contains_user_file() {
+            return Err(MigrationSkip::UserFile);
+        }
+        match self.query_task_state(task_id).map(State::from) {
+            Some(State::Removed | State::PendingRemoval) => return Err(MigrationSkip::Removed),
+            Some(_) => {}
+            None => return Err(MigrationSkip::Unreadable),
+        }
+
+        let mode = if config.common_data.background {
+            Mode::BackGround
+        } else {
+            Mode::FrontEnd
+        };
+        // Legacy downloads wait for the network again after a restart, which
+        // API10 tasks only do when they retry.
+        let sql = format!(
+            "UPDATE request_task SET version = {}, mode = {}, gauge = 0, priority = 0, retry = 1 WHERE task_id = {} AND version = {}",
+            Version::API10 as u8,
+            mode.repr,
+            task_id,
+            Version::API9 as u8
+        );
+        if let Err(e) = self.execute(&sql) {
+            error!("migrate legacy task {} failed {}", task_id, e);
+            return Err(MigrationSkip::Unreadable);
+        }
+        Ok(mode)
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_migrate_legacy {
+    include!("../../../tests/ut/manage/events/ut_migrate_legacy.rs");
+}
@@ -17,7 +17,9 @@
 //! `TaskManager`. It includes various event types, message structures, and factory methods
 //! for creating events that trigger different task management operations.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::time::Duration;
 
 use ylong_runtime::sync::oneshot::{channel, Sender};
 
@@ -25,21 +27,39 @@ use super::account::AccountEvent;
 use crate::config::{Action, Mode};
 use crate::error::ErrorCode;
 use crate::info::TaskInfo;
+use crate::manage::events::construct::CreatedTask;
+use crate::manage::group_info::GroupInfo;
+use crate::manage::maintenance::MaintenanceReport;
+use crate::manage::query::TaskFilter;
+use crate::manage::revision::ChangedSet;
+use crate::manage::scheduler::{Explanation, WaitingBreakdown};
+use crate::manage::state_counts::StateCounts;
+use crate::manage::system_search::{SystemSearchFilter, TaskSummary};
+use crate::service::task_token::TokenScope;
 use crate::task::config::TaskConfig;
 use crate::task::info::{DumpAllInfo, DumpOneInfo};
+use crate::task::notify::NotifyData;
 use crate::task::reason::Reason;
+use crate::task::speed_history::SpeedSample;
 use crate::utils::Recv;
 
 // Event handling implementations for specific operations
 mod construct;
 mod dump;
+mod migrate_legacy;
 mod pause;
 mod remove;
 mod resume;
+mod set_destination;
 mod set_max_speed;
 mod set_mode;
 mod start;
 mod stop;
+mod user_attention;
+
+pub(crate) use construct::max_live_tasks;
+pub(crate) use migrate_legacy::{MigrationReport, MigrationSkip};
+pub(crate) use remove::{REMOVAL_GRACE_PERIOD, REMOVAL_SWEEP_INTERVAL};
 
 /// The main event type for the task manager.
 ///
@@ -74,10 +94,10 @@ impl TaskManagerEvent {
     ///
     /// # Returns
     ///
-    /// A tuple containing the event and a receiver for the task ID result.
-    pub(crate) fn construct(config: TaskConfig) -> (Self, Recv<Result<u32, ErrorCode>>) {
+    /// A tuple containing the event and a receiver for the created task.
+    pub(crate) fn construct(config: TaskConfig) -> (Self, Recv<Result<CreatedTask, ErrorCode>>) {
         // Create channel for async response
-        let (tx, rx) = channel::<Result<u32, ErrorCode>>();
+        let (tx, rx) = channel::<Result<CreatedTask, ErrorCode>>();
         (
             Self::Service(ServiceEvent::Construct(
                 Box::new(ConstructMessage { config }),
@@ -136,7 +156,26 @@ impl TaskManagerEvent {
     pub(crate) fn stop(uid: u64, task_id: u32) -> (Self, Recv<ErrorCode>) {
         let (tx, rx) = channel::<ErrorCode>();
         (
-            Self::Service(ServiceEvent::Stop(uid, task_id, tx)),
+            Self::Service(ServiceEvent::Stop(uid, task_id, false, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to stop a specific task, letting an upload finish
+    /// the file in flight first.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID that owns the task.
+    /// * `task_id` - The ID of the task to stop.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn soft_stop(uid: u64, task_id: u32) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::Stop(uid, task_id, true, tx)),
             Recv::new(rx),
         )
     }
@@ -147,14 +186,69 @@ impl TaskManagerEvent {
     ///
     /// * `uid` - The user ID that owns the task.
     /// * `task_id` - The ID of the task to remove.
+    /// * `grace` - Whether the task stays restorable for the removal grace
+    ///   period instead of being removed at once.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn remove(uid: u64, task_id: u32, grace: bool) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::Remove(uid, task_id, grace, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to restore a task pending removal.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID that owns the task.
+    /// * `task_id` - The ID of the task to restore.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn undo_remove(uid: u64, task_id: u32) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::UndoRemove(uid, task_id, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to remove a task pending removal right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID that owns the task.
+    /// * `task_id` - The ID of the task to purge.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn purge(uid: u64, task_id: u32) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::Purge(uid, task_id, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to change how long removed tasks stay restorable.
+    ///
+    /// # Arguments
+    ///
+    /// * `grace` - The new removal grace period.
     ///
     /// # Returns
     ///
     /// A tuple containing the event and a receiver for the operation result.
-    pub(crate) fn remove(uid: u64, task_id: u32) -> (Self, Recv<ErrorCode>) {
+    pub(crate) fn set_removal_grace(grace: Duration) -> (Self, Recv<ErrorCode>) {
         let (tx, rx) = channel::<ErrorCode>();
         (
-            Self::Service(ServiceEvent::Remove(uid, task_id, tx)),
+            Self::Service(ServiceEvent::SetRemovalGrace(grace, tx)),
             Recv::new(rx),
         )
     }
@@ -223,6 +317,278 @@ impl TaskManagerEvent {
         )
     }
 
+    /// Creates a new event to move a paused download to a new destination.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID that owns the task.
+    /// * `task_id` - The ID of the task to move.
+    /// * `path` - The new destination path of the downloaded file.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn set_destination(uid: u64, task_id: u32, path: String) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::SetDestination(uid, task_id, path, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event reporting whether the user is watching a task's
+    /// notification.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the task whose notification was touched.
+    /// * `attention` - `true` if expanded, `false` if collapsed.
+    ///
+    /// # Returns
+    ///
+    /// The user attention event.
+    pub(crate) fn user_attention(task_id: u32, attention: bool) -> Self {
+        Self::Service(ServiceEvent::UserAttention(task_id, attention))
+    }
+
+    /// Creates a new event to run database maintenance immediately.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the maintenance report.
+    pub(crate) fn maintain() -> (Self, Recv<MaintenanceReport>) {
+        let (tx, rx) = channel::<MaintenanceReport>();
+        (Self::Service(ServiceEvent::Maintain(tx)), Recv::new(rx))
+    }
+
+    /// Creates a new event to count waiting tasks per waiting cause.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - Restricts the count to one application, `None` counts all.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the counts.
+    pub(crate) fn waiting_breakdown(uid: Option<u64>) -> (Self, Recv<WaitingBreakdown>) {
+        let (tx, rx) = channel::<WaitingBreakdown>();
+        (
+            Self::Service(ServiceEvent::WaitingBreakdown(uid, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to count tasks per application and state.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - Restricts the count to one application, `None` counts all.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the counts.
+    pub(crate) fn count_by_state(uid: Option<u64>) -> (Self, Recv<HashMap<u64, StateCounts>>) {
+        let (tx, rx) = channel::<HashMap<u64, StateCounts>>();
+        (
+            Self::Service(ServiceEvent::CountByState(uid, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to query the tasks of an application changed
+    /// since a revision.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The application whose tasks are reported.
+    /// * `revision` - The revision of the last sync of the caller.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the changed tasks.
+    pub(crate) fn changed_since(uid: u64, revision: u64) -> (Self, Recv<ChangedSet>) {
+        let (tx, rx) = channel::<ChangedSet>();
+        (
+            Self::Service(ServiceEvent::ChangedSince(uid, revision, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to search the tasks of all applications.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The tasks searched and the page asked for.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the task summaries.
+    pub(crate) fn search_system(filter: SystemSearchFilter) -> (Self, Recv<Vec<TaskSummary>>) {
+        let (tx, rx) = channel::<Vec<TaskSummary>>();
+        (
+            Self::Service(ServiceEvent::SearchSystem(filter, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to migrate the legacy downloads of an application.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The application whose legacy tasks are migrated.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the migration report.
+    pub(crate) fn migrate_legacy_tasks(uid: u64) -> (Self, Recv<MigrationReport>) {
+        let (tx, rx) = channel::<MigrationReport>();
+        (
+            Self::Service(ServiceEvent::MigrateLegacyTasks(uid, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to issue a signed token for a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The application asking for the token, which must own the task.
+    /// * `task_id` - The task the token grants access to.
+    /// * `scope` - The operations the token grants.
+    /// * `ttl_ms` - The lifetime of the token, 0 for the default.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the token, empty if the
+    /// task is not found.
+    pub(crate) fn issue_token(
+        uid: u64,
+        task_id: u32,
+        scope: TokenScope,
+        ttl_ms: u64,
+    ) -> (Self, Recv<String>) {
+        let (tx, rx) = channel::<String>();
+        (
+            Self::Service(ServiceEvent::IssueToken(uid, task_id, scope, ttl_ms, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to query the speed history of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the task to query.
+    /// * `points` - Maximum number of samples to return, 0 for all.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the samples, `None`
+    /// if the task is neither running nor recently stopped.
+    pub(crate) fn speed_history(
+        task_id: u32,
+        points: usize,
+    ) -> (Self, Recv<Option<Vec<SpeedSample>>>) {
+        let (tx, rx) = channel::<Option<Vec<SpeedSample>>>();
+        (
+            Self::Service(ServiceEvent::SpeedHistory(task_id, points, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to explain why a task is in its current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the task to explain.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the explanation,
+    /// `None` if the task does not exist.
+    pub(crate) fn explain(task_id: u32) -> (Self, Recv<Option<Explanation>>) {
+        let (tx, rx) = channel::<Option<Explanation>>();
+        (
+            Self::Service(ServiceEvent::Explain(task_id, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to make background tasks run only while the
+    /// device is charging, or to lift the requirement.
+    ///
+    /// # Arguments
+    ///
+    /// * `require_charging` - Whether background tasks wait for charging.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn require_charging(require_charging: bool) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::RequireCharging(require_charging, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to check again whether waiting tasks may run.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the number of tasks
+    /// moved into the scheduler's queue.
+    pub(crate) fn reevaluate_waiting() -> (Self, Recv<usize>) {
+        let (tx, rx) = channel::<usize>();
+        (
+            Self::Service(ServiceEvent::ReevaluateWaiting(tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to subscribe to changes of a task list.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID whose tasks are listed.
+    /// * `pid` - The process ID receiving the deltas.
+    /// * `id` - The subscription ID chosen by the client.
+    /// * `filter` - The filter selecting the listed tasks.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn subscribe_task_list(
+        uid: u64,
+        pid: u64,
+        id: u32,
+        filter: TaskFilter,
+    ) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::SubscribeTaskList(uid, pid, id, filter, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to end a task list subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - The process ID owning the subscription.
+    /// * `id` - The subscription ID chosen by the client.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn unsubscribe_task_list(pid: u64, id: u32) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::UnsubscribeTaskList(pid, id, tx)),
+            Recv::new(rx),
+        )
+    }
+
     /// Creates a new event to notify about network state changes.
     ///
     /// # Returns
@@ -250,6 +616,28 @@ impl TaskManagerEvent {
         )
     }
 
+    /// Creates a new event to subscribe to updates for many tasks at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_ids` - The IDs of the tasks to subscribe to.
+    /// * `token_id` - The token ID for subscription identification.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the result of each
+    /// task, in order, holding the current state of the subscribable ones.
+    pub(crate) fn subscribe_batch(
+        task_ids: Vec<u32>,
+        token_id: u64,
+    ) -> (Self, Recv<Vec<Result<NotifyData, ErrorCode>>>) {
+        let (tx, rx) = channel::<Vec<Result<NotifyData, ErrorCode>>>();
+        (
+            Self::Task(TaskEvent::SubscribeBatch(task_ids, token_id, tx)),
+            Recv::new(rx),
+        )
+    }
+
     /// Creates a new event to attach tasks to a group.
     ///
     /// # Arguments
@@ -273,6 +661,50 @@ impl TaskManagerEvent {
         )
     }
 
+    /// Creates a new event to query the membership and aggregate progress of
+    /// a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group to query.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the group, `None` if
+    /// it does not exist.
+    pub(crate) fn query_group(group_id: u32) -> (Self, Recv<Option<GroupInfo>>) {
+        let (tx, rx) = channel::<Option<GroupInfo>>();
+        (
+            Self::Service(ServiceEvent::QueryGroup(group_id, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to stop or remove all tasks of a group and delete
+    /// the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID owning the group.
+    /// * `group_id` - The ID of the group to cancel.
+    /// * `remove_members` - Whether the tasks are removed instead of stopped.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the result of each
+    /// task, `None` if the group does not exist.
+    pub(crate) fn cancel_group(
+        uid: u64,
+        group_id: u32,
+        remove_members: bool,
+    ) -> (Self, Recv<Option<Vec<(u32, ErrorCode)>>>) {
+        let (tx, rx) = channel::<Option<Vec<(u32, ErrorCode)>>>();
+        (
+            Self::Service(ServiceEvent::CancelGroup(uid, group_id, remove_members, tx)),
+            Recv::new(rx),
+        )
+    }
+
     /// Creates a new event to set the maximum speed for a specific task.
     ///
     /// # Arguments
@@ -291,6 +723,42 @@ impl TaskManagerEvent {
             Recv::new(rx),
         )
     }
+
+    /// Creates a new event to set the total speed shared by all tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_speed` - The global speed cap in bytes per second, 0 removes
+    ///   the cap.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn set_global_max_speed(max_speed: u64) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::SetGlobalMaxSpeed(max_speed, tx)),
+            Recv::new(rx),
+        )
+    }
+
+    /// Creates a new event to set how many top-tier run slots are reserved
+    /// for foreground tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `reserve` - The number of reserved slots, 0 disables the reservation.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the event and a receiver for the operation result.
+    pub(crate) fn set_foreground_reserve(reserve: usize) -> (Self, Recv<ErrorCode>) {
+        let (tx, rx) = channel::<ErrorCode>();
+        (
+            Self::Service(ServiceEvent::SetForegroundReserve(reserve, tx)),
+            Recv::new(rx),
+        )
+    }
 }
 
 /// Events for querying task information.
@@ -300,23 +768,34 @@ pub(crate) enum QueryEvent {
     Query(u32, Action, Sender<Option<TaskInfo>>),
     /// Show task information by ID and user ID.
     Show(u32, u64, Sender<Option<TaskInfo>>),
-    /// Touch (update last access time) and get task information.
-    Touch(u32, u64, String, Sender<Option<TaskInfo>>),
+    /// Touch (update last access time) and get task information, the token
+    /// is `None` if the caller presented a verified signed token.
+    Touch(u32, u64, Option<String>, Sender<Option<TaskInfo>>),
 }
 
 /// Service operation events for task management.
 #[derive(Debug)]
 pub(crate) enum ServiceEvent {
     /// Construct a new task with the provided configuration.
-    Construct(Box<ConstructMessage>, Sender<Result<u32, ErrorCode>>),
+    Construct(
+        Box<ConstructMessage>,
+        Sender<Result<CreatedTask, ErrorCode>>,
+    ),
     /// Pause a specific task.
     Pause(u64, u32, Sender<ErrorCode>),
     /// Start a specific task.
     Start(u64, u32, Sender<ErrorCode>),
-    /// Stop a specific task.
-    Stop(u64, u32, Sender<ErrorCode>),
-    /// Remove a specific task.
-    Remove(u64, u32, Sender<ErrorCode>),
+    /// Stop a specific task, optionally letting an upload finish the file in
+    /// flight first.
+    Stop(u64, u32, bool, Sender<ErrorCode>),
+    /// Remove a specific task, optionally keeping it restorable for a while.
+    Remove(u64, u32, bool, Sender<ErrorCode>),
+    /// Restore a task pending removal to the paused state.
+    UndoRemove(u64, u32, Sender<ErrorCode>),
+    /// Remove a task pending removal without waiting for its grace period.
+    Purge(u64, u32, Sender<ErrorCode>),
+    /// Change how long removed tasks stay restorable.
+    SetRemovalGrace(Duration, Sender<ErrorCode>),
     /// Resume a specific task.
     Resume(u64, u32, Sender<ErrorCode>),
     /// Dump information for a specific task.
@@ -325,10 +804,48 @@ pub(crate) enum ServiceEvent {
     DumpAll(Sender<DumpAllInfo>),
     /// Attach multiple tasks to a group.
     AttachGroup(u64, Vec<u32>, u32, Sender<ErrorCode>),
+    /// Query the membership and aggregate progress of a group.
+    QueryGroup(u32, Sender<Option<GroupInfo>>),
+    /// Stop or remove all tasks of a group and delete the group.
+    CancelGroup(u64, u32, bool, Sender<Option<Vec<(u32, ErrorCode)>>>),
     /// Set maximum speed limit for a specific task.
     SetMaxSpeed(u64, u32, i64, Sender<ErrorCode>),
+    /// Set the total speed shared by all tasks.
+    SetGlobalMaxSpeed(u64, Sender<ErrorCode>),
+    /// Set how many top-tier run slots are reserved for foreground tasks.
+    SetForegroundReserve(usize, Sender<ErrorCode>),
     /// Set the execution mode for a specific task.
     SetMode(u64, u32, Mode, Sender<ErrorCode>),
+    /// Move a paused download to a new destination path.
+    SetDestination(u64, u32, String, Sender<ErrorCode>),
+    /// The user expanded or collapsed the notification of a specific task.
+    UserAttention(u32, bool),
+    /// Run database maintenance now.
+    Maintain(Sender<MaintenanceReport>),
+    /// Count waiting tasks per waiting cause, optionally of one application.
+    WaitingBreakdown(Option<u64>, Sender<WaitingBreakdown>),
+    /// Check again whether tasks waiting for network or app state may run.
+    ReevaluateWaiting(Sender<usize>),
+    /// Make background tasks run only while the device is charging.
+    RequireCharging(bool, Sender<ErrorCode>),
+    /// Count tasks per application and state, optionally of one application.
+    CountByState(Option<u64>, Sender<HashMap<u64, StateCounts>>),
+    /// Query the tasks of an application changed since a revision.
+    ChangedSince(u64, u64, Sender<ChangedSet>),
+    /// Search a page of the tasks of all applications for system agents.
+    SearchSystem(SystemSearchFilter, Sender<Vec<TaskSummary>>),
+    /// Rewrite the legacy downloads of an application as API10 tasks.
+    MigrateLegacyTasks(u64, Sender<MigrationReport>),
+    /// Issue a signed token for a task of an application.
+    IssueToken(u64, u32, TokenScope, u64, Sender<String>),
+    /// Query the recent speed samples of a task.
+    SpeedHistory(u32, usize, Sender<Option<Vec<SpeedSample>>>),
+    /// Explain why a task is in its current state.
+    Explain(u32, Sender<Option<Explanation>>),
+    /// Subscribe a process to changes of the tasks matching a filter.
+    SubscribeTaskList(u64, u64, u32, TaskFilter, Sender<ErrorCode>),
+    /// End a task list subscription of a process.
+    UnsubscribeTaskList(u64, u32, Sender<ErrorCode>),
 }
 
 /// Task state and lifecycle events.
@@ -340,10 +857,17 @@ pub(crate) enum TaskEvent {
     Failed(u32, u64, Reason, Mode),
     /// Task has gone offline.
     Offline(u32, u64, Mode),
+    /// Task was paused by a failure it can be resumed from.
+    Paused(u32, u64, Reason),
+    /// Task gave up its run to wait for the delay a rate limiting server
+    /// asked for.
+    RateLimited(u32, u64, Mode, Duration),
     /// Task is currently running.
     Running(u32, u64, Mode),
     /// Subscribe to updates for a specific task.
     Subscribe(u32, u64, Sender<ErrorCode>),
+    /// Subscribe to updates for many tasks, replying their current state.
+    SubscribeBatch(Vec<u32>, u64, Sender<Vec<Result<NotifyData, ErrorCode>>>),
 }
 
 /// System state change events that affect task execution.
@@ -361,6 +885,14 @@ pub(crate) enum StateEvent {
     AppUninstall(u64),
     /// Application has been terminated specially.
     SpecialTerminate(u64),
+    /// Application process has terminated.
+    ProcessTerminate(u64),
+    /// Device has started or stopped charging.
+    Charging(bool),
+    /// Thermal level of the device has changed.
+    ThermalLevel(u8),
+    /// A captive portal probe detected a portal or not.
+    CaptivePortal(bool),
 }
 
 /// Message containing task configuration for task construction.
@@ -402,6 +934,17 @@ pub(crate) enum ScheduleEvent {
     Unload,
     /// Shutdown the service completely.
     Shutdown,
+    /// Start database maintenance if the service has been idle long enough.
+    Maintenance,
+    /// Send pending task list deltas to their subscribers.
+    TaskListFlush,
+    /// Finally remove tasks whose removal grace period has expired.
+    SweepRemovals,
+    /// Restart resumable tasks running on a less preferred network than the
+    /// current one.
+    MigrateToPreferredNetwork,
+    /// The delay a rate limited task waits for has elapsed.
+    RateLimitElapsed(u32),
 }
 
 #[cfg(not(feature = "oh"))]
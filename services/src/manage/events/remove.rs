@@ -15,12 +15,31 @@
 //! 
 //! This module provides the implementation for removing tasks within the `TaskManager`. It handles
 //! task count management and delegates the actual removal operation to the scheduler component.
+//!
+//! A task can also be removed with a grace period. It then enters the
+//! `PendingRemoval` state, is hidden from searches and kept with its partial
+//! file, and can be restored as paused until a periodic sweep, or an explicit
+//! purge, removes it for good.
+//!
+//! The `on_remove_partial` policy of a task decides whether its files are
+//! deleted, see [`RemovedFiles`]. Without one, files are kept on removal and
+//! an unfinished download is deleted when a removal is finalized.
 
+use std::time::Duration;
+
+use crate::config::PartialFilePolicy;
 use crate::error::ErrorCode;
 use crate::info::State;
 use crate::manage::database::RequestDb;
+use crate::manage::removed_files::RemovedFiles;
 use crate::manage::TaskManager;
 
+/// Default time a removed task stays restorable.
+pub(crate) const REMOVAL_GRACE_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// Interval between two sweeps of tasks whose grace period expired.
+pub(crate) const REMOVAL_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
 impl TaskManager {
     /// Removes a task with the specified user ID and task ID.
     ///
@@ -43,6 +62,103 @@ impl TaskManager {
         // Log the remove operation for debugging purposes
         debug!("TaskManager remove,uid{} tid{}", uid, task_id);
         
+        self.release_task_count(uid, task_id);
+        let files = RemovedFiles::capture(task_id, PartialFilePolicy::Keep);
+
+        // Delegate to the scheduler to remove the task
+        match self.scheduler.remove_task(uid, task_id, files) {
+            Ok(_) => ErrorCode::ErrOk,
+            Err(e) => e,
+        }
+    }
+
+    /// Removes a task, keeping it restorable for the removal grace period.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID that owns the task.
+    /// * `task_id` - The ID of the task to remove.
+    ///
+    /// # Returns
+    ///
+    /// * `ErrorCode::ErrOk` - If the task is pending removal.
+    /// * `ErrorCode::TaskNotFound` - If the task does not exist or is removed.
+    /// * `ErrorCode::TaskStateErr` - If the task is already pending removal.
+    pub(crate) fn remove_with_grace(&mut self, uid: u64, task_id: u32) -> ErrorCode {
+        debug!("TaskManager remove with grace,uid{} tid{}", uid, task_id);
+        self.release_task_count(uid, task_id);
+        match self.scheduler.remove_task_with_grace(uid, task_id) {
+            Ok(_) => ErrorCode::ErrOk,
+            Err(e) => e,
+        }
+    }
+
+    /// Restores a task pending removal to the `Paused` state.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID that owns the task.
+    /// * `task_id` - The ID of the task to restore.
+    ///
+    /// # Returns
+    ///
+    /// * `ErrorCode::ErrOk` - If the task was restored.
+    /// * `ErrorCode::TaskStateErr` - If the task is not pending removal.
+    pub(crate) fn undo_remove(&mut self, uid: u64, task_id: u32) -> ErrorCode {
+        debug!("TaskManager undo remove,uid{} tid{}", uid, task_id);
+        match self.scheduler.undo_remove_task(uid, task_id) {
+            Ok(_) => ErrorCode::ErrOk,
+            Err(e) => e,
+        }
+    }
+
+    /// Removes a task pending removal without waiting for its grace period.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID that owns the task.
+    /// * `task_id` - The ID of the task to purge.
+    ///
+    /// # Returns
+    ///
+    /// * `ErrorCode::ErrOk` - If the task was removed.
+    /// * `ErrorCode::TaskStateErr` - If the task is not pending removal.
+    pub(crate) fn purge(&mut self, uid: u64, task_id: u32) -> ErrorCode {
+        debug!("TaskManager purge,uid{} tid{}", uid, task_id);
+        if RequestDb::get_instance().query_task_uid(task_id) != Some(uid) {
+            return ErrorCode::TaskNotFound;
+        }
+        match self.finalize_removal(task_id) {
+            Ok(_) => ErrorCode::ErrOk,
+            Err(e) => e,
+        }
+    }
+
+    /// Removes the tasks whose grace period expired by `now`.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time in milliseconds since the epoch.
+    pub(crate) fn sweep_removals(&mut self, now: u64) {
+        let removed_before = now.saturating_sub(self.removal_grace.as_millis() as u64);
+        for task_id in RequestDb::get_instance().expired_removals(removed_before) {
+            if let Err(e) = self.finalize_removal(task_id) {
+                error!("finalize removal of task {} failed, {:?}", task_id, e);
+            }
+        }
+    }
+
+    /// Removes a task pending removal for good, by default deleting the
+    /// partial file it downloaded.
+    fn finalize_removal(&mut self, task_id: u32) -> Result<(), ErrorCode> {
+        let files = RemovedFiles::capture(task_id, PartialFilePolicy::DeleteIfIncomplete);
+        self.scheduler.finalize_removal(task_id, files)?;
+        info!("task {} removal finalized", task_id);
+        Ok(())
+    }
+
+    /// Releases the slot of a task in the task count of its application.
+    fn release_task_count(&mut self, uid: u64, task_id: u32) {
         // Get database instance to check task status
         let db = RequestDb::get_instance();
         
@@ -52,6 +168,7 @@ impl TaskManager {
             if info.state != State::Failed.repr
                 && info.state != State::Completed.repr
                 && info.state != State::Removed.repr
+                && info.state != State::PendingRemoval.repr
             {
                 // Get the task count for this user and decrement based on mode
                 if let Some(count) = self.task_count.get_mut(&uid) {
@@ -66,11 +183,28 @@ impl TaskManager {
                 }
             }
         }
+    }
+}
 
-        // Delegate to the scheduler to remove the task
-        match self.scheduler.remove_task(uid, task_id) {
-            Ok(_) => ErrorCode::ErrOk,
-            Err(e) => e,
-        }
+impl RequestDb {
+    /// Returns the tasks pending removal since `removed_before` or earlier.
+    ///
+    /// # Arguments
+    ///
+    /// * `removed_before` - Latest removal time in milliseconds since the
+    ///   epoch.
+    pub(crate) fn expired_removals(&self, removed_before: u64) -> Vec<u32> {
+        let sql = format!(
+            "SELECT task_id FROM request_task WHERE state = {} AND mtime <= {}",
+            State::PendingRemoval.repr,
+            removed_before
+        );
+        self.query_integer(&sql)
     }
 }
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_remove {
+    include!("../../../tests/ut/manage/events/ut_remove.rs");
+}
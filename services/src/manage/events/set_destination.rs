@@ -0,0 +1,112 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Destination change implementation for the task manager.
+//!
+//! A paused download can be given a new destination path inside the sandbox
+//! of its application. The partially downloaded file is moved there and the
+//! new path is stored, so the task continues into it once resumed.
+
+use std::path::Path;
+
+use crate::config::Action;
+use crate::error::ErrorCode;
+use crate::info::State;
+use crate::manage::database::RequestDb;
+use crate::manage::TaskManager;
+use crate::task::files::{convert_path, move_download_file, BundleCache};
+use crate::task::request_task::check_path;
+
+impl TaskManager {
+    /// Moves the file of a paused download to a new destination path.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID that owns the task.
+    /// * `task_id` - The ID of the task to move.
+    /// * `path` - The new destination, checked like the paths of new tasks.
+    ///
+    /// # Returns
+    ///
+    /// * `ErrorCode::ErrOk` - If the file was moved and the path stored.
+    /// * `ErrorCode::TaskStateErr` - If the task is not paused.
+    /// * `ErrorCode::ParameterCheck` - If the path is invalid or the task is
+    ///   not a download to a single path.
+    /// * `ErrorCode::FileOperationErr` - If the file could not be moved.
+    pub(crate) fn set_destination(&mut self, uid: u64, task_id: u32, path: &str) -> ErrorCode {
+        debug!("TaskManager set_destination, uid{} tid{}", uid, task_id);
+        match set_destination(task_id, path) {
+            Ok(()) => ErrorCode::ErrOk,
+            Err(e) => e,
+        }
+    }
+}
+
+fn set_destination(task_id: u32, path: &str) -> Result<(), ErrorCode> {
+    let database = RequestDb::get_instance();
+    let info = database
+        .get_task_qos_info(task_id)
+        .ok_or(ErrorCode::TaskNotFound)?;
+    // A running task keeps writing, it has to be paused first.
+    if info.state != State::Paused.repr {
+        error!(
+            "set_destination of task {} in state {}",
+            task_id, info.state
+        );
+        return Err(ErrorCode::TaskStateErr);
+    }
+    if !check_path(path) {
+        return Err(ErrorCode::ParameterCheck);
+    }
+
+    let config = database
+        .get_task_config(task_id)
+        .ok_or(ErrorCode::TaskNotFound)?;
+    let spec = match config.file_specs.as_slice() {
+        [spec] if config.common_data.action == Action::Download && !spec.is_user_file => spec,
+        _ => {
+            error!(
+                "set_destination of task {} without a download path",
+                task_id
+            );
+            return Err(ErrorCode::ParameterCheck);
+        }
+    };
+    if spec.path == path {
+        return Ok(());
+    }
+
+    let bundle_name = BundleCache::new(&config)
+        .get_value()
+        .map_err(|_| ErrorCode::Other)?;
+    let uid = config.common_data.uid;
+    let from = convert_path(uid, &bundle_name, &spec.path);
+    let to = convert_path(uid, &bundle_name, path);
+    if let Err(e) = move_download_file(Path::new(&from), Path::new(&to)) {
+        error!("set_destination of task {} move failed, {}", task_id, e);
+        return Err(ErrorCode::FileOperationErr);
+    }
+    if let Err(e) = database.update_destination(task_id, path) {
+        // Put the file back where the stored config expects it.
+        let _ = move_download_file(Path::new(&to), Path::new(&from));
+        return Err(e);
+    }
+    info!("task {} destination changed", task_id);
+    Ok(())
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_set_destination {
+    include!("../../../tests/ut/manage/events/ut_set_destination.rs");
+}
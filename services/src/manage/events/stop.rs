@@ -15,7 +15,7 @@
 //! 
 //! This module provides the implementation for stopping tasks within the `TaskManager`. It handles
 //! task count decrementation for active tasks and delegates the actual task termination to the
-//! scheduler component.
+//! scheduler component. A soft stop lets a running upload finish the file in flight first.
 
 use crate::error::ErrorCode;
 use crate::info::State;
@@ -43,7 +43,33 @@ impl TaskManager {
     pub(crate) fn stop(&mut self, uid: u64, task_id: u32) -> ErrorCode {
         // Log the task stop operation for debugging purposes
         debug!("TaskManager stop, tid{}", task_id);
-        
+        self.stop_task(uid, task_id, false)
+    }
+
+    /// Stops a task, letting a running upload finish the file in flight
+    /// first.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID that owns the task.
+    /// * `task_id` - The ID of the task to stop.
+    ///
+    /// # Returns
+    ///
+    /// * `ErrorCode::ErrOk` - If the task was successfully stopped.
+    /// * Other `ErrorCode` values - If there was an error stopping the task.
+    ///
+    /// # Notes
+    ///
+    /// The task is `Stopped` at once, but a running upload keeps sending its
+    /// current file for up to `SOFT_STOP_GRACE` so the server is not left with
+    /// a torn object. Downloads stop like with [`stop`](Self::stop).
+    pub(crate) fn soft_stop(&mut self, uid: u64, task_id: u32) -> ErrorCode {
+        debug!("TaskManager soft stop, tid{}", task_id);
+        self.stop_task(uid, task_id, true)
+    }
+
+    fn stop_task(&mut self, uid: u64, task_id: u32, graceful: bool) -> ErrorCode {
         // Get database instance to check task status
         let db = RequestDb::get_instance();
         
@@ -71,7 +97,7 @@ impl TaskManager {
         }
 
         // Delegate to the scheduler to actually stop the task execution
-        match self.scheduler.stop_task(uid, task_id) {
+        match self.scheduler.stop_task(uid, task_id, graceful) {
             Ok(_) => ErrorCode::ErrOk,
             Err(e) => e,
         }
@@ -0,0 +1,32 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! User attention handling for the task manager.
+//! 
+//! This module lets notification bar interactions temporarily raise the QoS
+//! tier of the task the user is looking at.
+
+use crate::manage::TaskManager;
+
+impl TaskManager {
+    /// Boosts or releases a task according to its notification state.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the task whose notification was touched.
+    /// * `attention` - `true` if the notification was expanded.
+    pub(crate) fn user_attention(&mut self, task_id: u32, attention: bool) {
+        debug!("TaskManager user_attention, tid{} attention{}", task_id, attention);
+        self.scheduler.user_attention(task_id, attention);
+    }
+}
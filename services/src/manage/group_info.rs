@@ -0,0 +1,83 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Aggregate information of task groups.
+//!
+//! Clients attaching tasks to a group can ask which tasks it holds and how
+//! far they got as a whole instead of tracking the membership themselves.
+
+use crate::info::{State, TaskInfo};
+use crate::manage::state_counts::StateCounts;
+
+/// Membership and aggregate progress of a task group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GroupInfo {
+    /// The group described.
+    pub(crate) group_id: u32,
+    /// Tasks of the group that were not removed.
+    pub(crate) member_task_ids: Vec<u32>,
+    /// Bytes processed by the members.
+    pub(crate) aggregate_processed: u64,
+    /// Total bytes of the members, -1 if the size of a member is unknown.
+    pub(crate) aggregate_total: i64,
+    /// Number of tasks of the group per state, removed tasks included.
+    pub(crate) counts_by_state: StateCounts,
+    /// Whether the group notification shows the progress.
+    pub(crate) gauge: bool,
+    /// Customized title of the group notification.
+    pub(crate) title: Option<String>,
+}
+
+impl GroupInfo {
+    /// Aggregates the tasks of a group.
+    ///
+    /// Removed tasks are counted by state but are neither members nor part
+    /// of the progress.
+    pub(crate) fn new(
+        group_id: u32,
+        gauge: bool,
+        title: Option<String>,
+        tasks: &[TaskInfo],
+    ) -> Self {
+        let mut info = GroupInfo {
+            group_id,
+            member_task_ids: Vec::new(),
+            aggregate_processed: 0,
+            aggregate_total: 0,
+            counts_by_state: StateCounts::default(),
+            gauge,
+            title,
+        };
+        for task in tasks {
+            let state = task.progress.common_data.state;
+            info.counts_by_state.add(state, 1);
+            if state == State::Removed.repr || state == State::PendingRemoval.repr {
+                continue;
+            }
+            info.member_task_ids.push(task.common_data.task_id);
+            info.aggregate_processed += task.progress.common_data.total_processed as u64;
+            let sizes = &task.progress.sizes;
+            if info.aggregate_total < 0 || sizes.iter().any(|size| *size < 0) {
+                info.aggregate_total = -1;
+            } else {
+                info.aggregate_total += sizes.iter().sum::<i64>();
+            }
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod ut_group_info {
+    include!("../../tests/ut/manage/ut_group_info.rs");
+}
@@ -0,0 +1,172 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-uid diagnostic journal of task events.
+//!
+//! Devices in the field often have no log access. A system application may
+//! enable a journal for another application: lifecycle and error events of
+//! its tasks are then appended as compact records to a file of the service
+//! data directory, rotating at a size cap. Progress is sampled once per
+//! [`PROGRESS_SAMPLE_INTERVAL_MS`] per task, and the journal is flushed at
+//! terminal events so a post-mortem finds them on disk.
+//!
+//! Journals are read back by `request_utils::journal::parse_journal`, see the
+//! dump command.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+
+use request_utils::journal::{JournalEvent, JournalRecord, JournalWriter};
+
+use crate::utils::get_current_timestamp;
+
+/// Directory holding the journals.
+const JOURNAL_DIR: &str = if cfg!(test) {
+    "test_files"
+} else {
+    "/data/service/el1/public/database/request"
+};
+
+/// Shortest time between two journaled progress events of a task.
+pub(crate) const PROGRESS_SAMPLE_INTERVAL_MS: u64 = 5000;
+/// Smallest size cap of a journal.
+pub(crate) const MIN_JOURNAL_CAP: u64 = 4 * 1024;
+/// Largest size cap of a journal.
+pub(crate) const MAX_JOURNAL_CAP: u64 = 16 * 1024 * 1024;
+
+/// Journaled task of an application.
+struct JournaledTask {
+    uid: u64,
+    /// Time of the last journaled progress, 0 if none.
+    sampled: u64,
+}
+
+#[derive(Default)]
+struct Journals {
+    writers: HashMap<u64, JournalWriter>,
+    tasks: HashMap<u32, JournaledTask>,
+}
+
+/// Diagnostic journals of the applications they are enabled for.
+pub(crate) struct DiagnosticJournal {
+    dir: PathBuf,
+    journals: Mutex<Journals>,
+}
+
+impl DiagnosticJournal {
+    /// Returns the journals of the service.
+    pub(crate) fn get_instance() -> &'static Self {
+        static INSTANCE: LazyLock<DiagnosticJournal> =
+            LazyLock::new(|| DiagnosticJournal::new(JOURNAL_DIR));
+        &INSTANCE
+    }
+
+    pub(crate) fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            journals: Mutex::new(Journals::default()),
+        }
+    }
+
+    /// Returns the journal file of `uid`.
+    pub(crate) fn path(&self, uid: u64) -> PathBuf {
+        self.dir.join(format!("request_journal_{}", uid))
+    }
+
+    /// Journals the tasks of `uid` within `cap` bytes, 0 disables the
+    /// journal but keeps its file.
+    pub(crate) fn enable(&self, uid: u64, cap: u64) -> io::Result<()> {
+        let mut journals = self.journals.lock().unwrap();
+        if cap == 0 {
+            info!("journal of uid {} disabled", uid);
+            journals.writers.remove(&uid);
+            journals.tasks.retain(|_, task| task.uid != uid);
+            return Ok(());
+        }
+        let writer = JournalWriter::open(self.path(uid), cap)?;
+        info!("journal of uid {} enabled, cap {}", uid, cap);
+        journals.writers.insert(uid, writer);
+        Ok(())
+    }
+
+    /// Journals an event of a task of `uid`, if its journal is enabled.
+    pub(crate) fn record(
+        &self,
+        uid: u64,
+        task_id: u32,
+        event: JournalEvent,
+        bytes: u64,
+        reason: u8,
+    ) {
+        self.record_at(uid, task_id, event, bytes, reason, get_current_timestamp());
+    }
+
+    /// Journals an event of a task already journaled, whose uid is not known
+    /// by the caller.
+    pub(crate) fn record_task(&self, task_id: u32, event: JournalEvent, reason: u8) {
+        let uid = match self.journals.lock().unwrap().tasks.get(&task_id) {
+            Some(task) => task.uid,
+            None => return,
+        };
+        self.record_at(uid, task_id, event, 0, reason, get_current_timestamp());
+    }
+
+    pub(crate) fn record_at(
+        &self,
+        uid: u64,
+        task_id: u32,
+        event: JournalEvent,
+        bytes: u64,
+        reason: u8,
+        now: u64,
+    ) {
+        let mut journals = self.journals.lock().unwrap();
+        let Journals { writers, tasks } = &mut *journals;
+        let Some(writer) = writers.get_mut(&uid) else {
+            return;
+        };
+        let task = tasks
+            .entry(task_id)
+            .or_insert(JournaledTask { uid, sampled: 0 });
+        if event == JournalEvent::Progress {
+            if task.sampled != 0 && now < task.sampled + PROGRESS_SAMPLE_INTERVAL_MS {
+                return;
+            }
+            task.sampled = now;
+        }
+        let record = JournalRecord {
+            task_id,
+            timestamp: now,
+            event,
+            bytes,
+            reason,
+        };
+        if let Err(e) = writer.append(&record) {
+            error!("journal of uid {} append failed, {}", uid, e);
+            return;
+        }
+        if event.is_terminal() {
+            tasks.remove(&task_id);
+            if let Err(e) = writer.flush() {
+                error!("journal of uid {} flush failed, {}", uid, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_journal {
+    include!("../../tests/ut/manage/ut_journal.rs");
+}
@@ -0,0 +1,208 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Running lock keeping the device awake for critical transfers.
+//!
+//! Tasks with `keep_awake` hold the running lock of the service while they
+//! are running: the lock is acquired when the first of them starts and
+//! released once the last of them left the running state for
+//! [`RELEASE_GRACE`], so tasks rescheduled right away do not release and
+//! acquire it again. The lock is force-released after [`SAFETY_TIMEOUT`]
+//! whatever the tasks still running, and only acquired again once all of
+//! them left. The system releases it by itself after the same timeout should
+//! the service die holding it.
+
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::utils::runtime_spawn;
+
+/// Permission required to keep the device awake while a task runs.
+pub(crate) const KEEP_AWAKE_PERMISSION: &str = "ohos.permission.REQUEST_KEEP_AWAKE";
+/// Time the lock is kept after the last holding task stopped.
+pub(crate) const RELEASE_GRACE: Duration = Duration::from_secs(2);
+/// Longest time the lock is held at once.
+pub(crate) const SAFETY_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Running lock of the system.
+pub(crate) trait RunningLock {
+    /// Keeps the device awake for at most `timeout`, returns whether the
+    /// lock is held.
+    fn acquire(&self, timeout: Duration) -> bool;
+
+    /// Lets the device sleep again.
+    fn release(&self);
+}
+
+/// Running lock of the power service.
+pub(crate) struct SystemRunningLock;
+
+impl RunningLock for SystemRunningLock {
+    #[cfg(feature = "oh")]
+    fn acquire(&self, timeout: Duration) -> bool {
+        crate::utils::acquire_running_lock(timeout)
+    }
+
+    #[cfg(not(feature = "oh"))]
+    fn acquire(&self, _timeout: Duration) -> bool {
+        true
+    }
+
+    #[cfg(feature = "oh")]
+    fn release(&self) {
+        crate::utils::release_running_lock()
+    }
+
+    #[cfg(not(feature = "oh"))]
+    fn release(&self) {}
+}
+
+/// Holders of the running lock.
+pub(crate) struct KeepAwake<L> {
+    lock: L,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Running holders by task, a task restarted before its previous run
+    /// ended holds twice.
+    holders: BTreeMap<u32, usize>,
+    /// When the lock was acquired, `None` while it is released.
+    acquired_at: Option<Instant>,
+    /// When the last holder left, `None` while there are holders.
+    idle_since: Option<Instant>,
+    /// Whether the lock was force-released with holders left.
+    forced: bool,
+}
+
+impl KeepAwake<SystemRunningLock> {
+    /// Returns the holders of the running lock of the service.
+    pub(crate) fn get_instance() -> &'static Self {
+        static KEEP_AWAKE: LazyLock<KeepAwake<SystemRunningLock>> =
+            LazyLock::new(|| KeepAwake::new(SystemRunningLock));
+        &KEEP_AWAKE
+    }
+
+    /// Holds the lock while the returned guard of a running task lives.
+    pub(crate) fn hold(&'static self, task_id: u32) -> AwakeGuard {
+        if self.hold_at(task_id, Instant::now()) {
+            runtime_spawn(async move {
+                ylong_runtime::time::sleep(SAFETY_TIMEOUT).await;
+                self.expire_at(Instant::now());
+            });
+        }
+        AwakeGuard { task_id }
+    }
+}
+
+impl<L: RunningLock> KeepAwake<L> {
+    pub(crate) fn new(lock: L) -> Self {
+        Self {
+            lock,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Adds a running holder, returns whether the lock was acquired.
+    pub(crate) fn hold_at(&self, task_id: u32, now: Instant) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        *inner.holders.entry(task_id).or_default() += 1;
+        inner.idle_since = None;
+        if inner.acquired_at.is_some() || inner.forced {
+            return false;
+        }
+        if !self.lock.acquire(SAFETY_TIMEOUT) {
+            error!("task {} failed to acquire running lock", task_id);
+            return false;
+        }
+        info!("running lock acquired for task {}", task_id);
+        inner.acquired_at = Some(now);
+        true
+    }
+
+    /// Removes a running holder, returns whether it was the last one.
+    pub(crate) fn leave_at(&self, task_id: u32, now: Instant) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(count) = inner.holders.get_mut(&task_id) {
+            *count -= 1;
+            if *count == 0 {
+                inner.holders.remove(&task_id);
+            }
+        }
+        if !inner.holders.is_empty() {
+            return false;
+        }
+        inner.idle_since = Some(now);
+        inner.forced = false;
+        true
+    }
+
+    /// Releases the lock once idle for the grace or held for the safety
+    /// timeout.
+    pub(crate) fn expire_at(&self, now: Instant) {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(acquired_at) = inner.acquired_at else {
+            return;
+        };
+        let idle = inner
+            .idle_since
+            .is_some_and(|since| now.saturating_duration_since(since) >= RELEASE_GRACE);
+        let timed_out = now.saturating_duration_since(acquired_at) >= SAFETY_TIMEOUT;
+        if !idle && !timed_out {
+            return;
+        }
+        if !idle {
+            error!(
+                "running lock force-released after {:?}, held by {:?}",
+                SAFETY_TIMEOUT,
+                inner.holders.keys()
+            );
+            inner.forced = true;
+        } else {
+            info!("running lock released");
+        }
+        inner.acquired_at = None;
+        self.lock.release();
+    }
+
+    /// Returns whether the lock is held and the tasks holding it.
+    pub(crate) fn state(&self) -> (bool, Vec<u32>) {
+        let inner = self.inner.lock().unwrap();
+        let holders = inner.holders.keys().copied().collect();
+        (inner.acquired_at.is_some(), holders)
+    }
+}
+
+/// Holding of the running lock by a running task, left when dropped.
+pub(crate) struct AwakeGuard {
+    task_id: u32,
+}
+
+impl Drop for AwakeGuard {
+    fn drop(&mut self) {
+        let keep_awake = KeepAwake::get_instance();
+        if keep_awake.leave_at(self.task_id, Instant::now()) {
+            runtime_spawn(async move {
+                ylong_runtime::time::sleep(RELEASE_GRACE).await;
+                keep_awake.expire_at(Instant::now());
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_keep_awake {
+    include!("../../tests/ut/manage/ut_keep_awake.rs");
+}
@@ -0,0 +1,243 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Idle-time maintenance of the request database.
+//!
+//! Deleted tasks leave rows behind in the notification tables and free pages
+//! in the database file. Once the service has been idle for a while the
+//! `TaskManager` starts a [`MaintenanceJob`] which prunes those rows, returns
+//! free pages to the file system and refreshes the query planner statistics.
+//! The job runs step by step and stops at the next step boundary as soon as a
+//! new task is constructed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::database::RequestDb;
+use crate::service::active_counter::ActiveCounter;
+use crate::utils::get_current_timestamp;
+
+/// How long the service must stay idle before maintenance starts.
+pub(crate) const MAINTENANCE_IDLE_PERIOD: Duration = Duration::from_secs(30 * 60);
+
+/// Interval between two idle checks of the `TaskManager`.
+pub(crate) const MAINTENANCE_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Free pages returned to the file system by one vacuum step.
+const VACUUM_PAGES_PER_STEP: u64 = 256;
+
+/// Upper bound of vacuum steps in one job.
+const MAX_VACUUM_STEPS: usize = 64;
+
+/// Empty groups younger than this may still get tasks attached, tombstones
+/// of deleted tasks are kept as long for syncing clients.
+const MILLIS_IN_A_WEEK: u64 = 7 * 24 * 60 * 60 * 1000;
+
+/// Tables keyed by `task_id` whose rows are useless once the task is gone.
+const TASK_CHILD_TABLES: [&str; 4] = [
+    "task_config",
+    "group_notification",
+    "task_notification_content",
+    "task_effective_config",
+];
+
+/// Outcome of one maintenance job.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct MaintenanceReport {
+    /// Orphaned rows deleted from child tables.
+    pub(crate) pruned_rows: u64,
+    /// Database pages returned to the file system.
+    pub(crate) reclaimed_pages: u64,
+    /// Whether a new task stopped the job before its last step.
+    pub(crate) interrupted: bool,
+}
+
+/// Tracks service idleness and the cancellation flag of the running job.
+pub(crate) struct Maintenance {
+    active_counter: ActiveCounter,
+    idle_period: Duration,
+    idle_since: Option<Instant>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Maintenance {
+    /// Creates a tracker starting a job after `idle_period` of idleness.
+    pub(crate) fn new(active_counter: ActiveCounter, idle_period: Duration) -> Self {
+        Self {
+            active_counter,
+            idle_period,
+            idle_since: None,
+            cancel: None,
+        }
+    }
+
+    /// Returns a job if the service has been idle for long enough.
+    ///
+    /// `running_tasks` is the number of tasks in the running queue. After a
+    /// job is handed out the idle period starts over.
+    pub(crate) fn poll_idle(
+        &mut self,
+        running_tasks: usize,
+        now: Instant,
+    ) -> Option<MaintenanceJob> {
+        if running_tasks != 0 || self.active_counter.is_active() {
+            self.idle_since = None;
+            return None;
+        }
+        let idle_since = *self.idle_since.get_or_insert(now);
+        if now.duration_since(idle_since) < self.idle_period {
+            return None;
+        }
+        self.idle_since = None;
+        Some(self.start())
+    }
+
+    /// Starts a job regardless of idleness, used by the manual trigger.
+    pub(crate) fn start(&mut self) -> MaintenanceJob {
+        let cancel = Arc::new(AtomicBool::new(false));
+        if let Some(old) = self.cancel.replace(cancel.clone()) {
+            old.store(true, Ordering::Release);
+        }
+        MaintenanceJob { cancel, step: 0 }
+    }
+
+    /// Stops the running job at its next step and restarts the idle period.
+    pub(crate) fn interrupt(&mut self) {
+        self.idle_since = None;
+        if let Some(cancel) = self.cancel.take() {
+            cancel.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// One pass of database maintenance, executed step by step.
+pub(crate) struct MaintenanceJob {
+    cancel: Arc<AtomicBool>,
+    step: usize,
+}
+
+impl MaintenanceJob {
+    /// Runs the remaining steps, checking the cancellation flag in between.
+    pub(crate) fn run(mut self, db: &RequestDb) -> MaintenanceReport {
+        let mut report = MaintenanceReport::default();
+        while !self.is_cancelled() {
+            if !self.step(db, &mut report) {
+                break;
+            }
+        }
+        report.interrupted = self.is_cancelled();
+        info!(
+            "database maintenance pruned {} rows, reclaimed {} pages, interrupted {}",
+            report.pruned_rows, report.reclaimed_pages, report.interrupted
+        );
+        report
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Acquire)
+    }
+
+    /// Executes the next step, returns `false` once all steps are done.
+    pub(crate) fn step(&mut self, db: &RequestDb, report: &mut MaintenanceReport) -> bool {
+        let step = self.step;
+        self.step += 1;
+        match step {
+            i if i < TASK_CHILD_TABLES.len() => {
+                let table = TASK_CHILD_TABLES[i];
+                report.pruned_rows += prune(
+                    db,
+                    table,
+                    "task_id NOT IN (SELECT task_id FROM request_task)",
+                );
+            }
+            i if i == TASK_CHILD_TABLES.len() => {
+                let expired = get_current_timestamp().saturating_sub(MILLIS_IN_A_WEEK);
+                report.pruned_rows += prune(
+                    db,
+                    "group_notification_config",
+                    &format!(
+                        "group_id NOT IN (SELECT group_id FROM group_notification) AND ctime < {}",
+                        expired
+                    ),
+                );
+                report.pruned_rows += prune(
+                    db,
+                    "group_notification_content",
+                    "group_id NOT IN (SELECT group_id FROM group_notification_config)",
+                );
+                report.pruned_rows += db.prune_tombstones(expired);
+            }
+            i if i <= TASK_CHILD_TABLES.len() + MAX_VACUUM_STEPS => {
+                match vacuum_step(db) {
+                    0 => self.step = TASK_CHILD_TABLES.len() + MAX_VACUUM_STEPS + 1,
+                    pages => report.reclaimed_pages += pages,
+                }
+            }
+            i if i == TASK_CHILD_TABLES.len() + MAX_VACUUM_STEPS + 1 => {
+                let _ = db.execute("PRAGMA optimize");
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Deletes the rows of `table` matching `condition`, returns how many.
+fn prune(db: &RequestDb, table: &str, condition: &str) -> u64 {
+    let sql = format!(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = '{}'",
+        table
+    );
+    if db.query_integer::<u64>(&sql).first().copied().unwrap_or(0) == 0 {
+        return 0;
+    }
+    let sql = format!("SELECT COUNT(*) FROM {} WHERE {}", table, condition);
+    let count = db.query_integer::<u64>(&sql).first().copied().unwrap_or(0);
+    if count == 0 {
+        return 0;
+    }
+    match db.execute(&format!("DELETE FROM {} WHERE {}", table, condition)) {
+        Ok(()) => count,
+        Err(_) => 0,
+    }
+}
+
+/// Returns up to `VACUUM_PAGES_PER_STEP` free pages, returns how many.
+///
+/// The database is switched to incremental auto-vacuum when it is opened, a
+/// database still without it keeps its free pages, which ends the vacuum
+/// steps early.
+fn vacuum_step(db: &RequestDb) -> u64 {
+    let freelist = |db: &RequestDb| {
+        db.query_integer::<u64>("PRAGMA freelist_count")
+            .first()
+            .copied()
+            .unwrap_or(0)
+    };
+    let before = freelist(db);
+    if before == 0 {
+        return 0;
+    }
+    let sql = format!("PRAGMA incremental_vacuum({})", VACUUM_PAGES_PER_STEP);
+    if db.execute(&sql).is_err() {
+        return 0;
+    }
+    before.saturating_sub(freelist(db))
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_maintenance {
+    include!("../../tests/ut/manage/ut_maintenance.rs");
+}
@@ -18,14 +18,27 @@ cfg_oh! {
 
 pub(crate) mod account;
 pub(crate) mod app_state;
+pub(crate) mod battery;
+pub(crate) mod body_files;
+pub(crate) mod captive_portal;
 pub(crate) mod database;
 pub(crate) mod events;
+pub(crate) mod group_info;
+pub(crate) mod journal;
+pub(crate) mod keep_awake;
+pub(crate) mod maintenance;
 pub(crate) mod query;
 pub(crate) use task_manager::TaskManager;
 pub(crate) mod network;
 pub(crate) mod network_manager;
 pub(crate) mod notifier;
+pub(crate) mod progress_writer;
+pub(crate) mod removed_files;
+pub(crate) mod revision;
 pub(crate) mod scheduler;
+pub(crate) mod state_counts;
+pub(crate) mod system_search;
+pub(crate) mod task_list;
 pub(crate) mod task_manager;
 
 #[cfg(test)]
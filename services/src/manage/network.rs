@@ -214,6 +214,22 @@ impl NetworkInner {
     }
 }
 
+/// Checks whether the network of a handle is available.
+#[cfg(feature = "oh")]
+pub(crate) fn is_net_available(net_id: i32) -> bool {
+    ffi::IsNetAvailable(net_id)
+}
+
+/// Resolves a host on the network of a handle.
+///
+/// # Returns
+///
+/// The resolved IP addresses, empty if the host could not be resolved.
+#[cfg(feature = "oh")]
+pub(crate) fn resolve_on_net(net_id: i32, host: &str) -> Vec<String> {
+    ffi::GetAddressesByNetId(net_id, host)
+}
+
 // Safety: NetworkRegistry is thread-safe as it's used via FFI with proper synchronization
 unsafe impl Send for NetworkRegistry {}
 unsafe impl Sync for NetworkRegistry {}
@@ -257,5 +273,7 @@ mod ffi {
             notify_online: fn(&NetworkTaskManagerTx),
             notify_offline: fn(&NetworkTaskManagerTx),
         ) -> UniquePtr<NetworkRegistry>;
+        fn IsNetAvailable(net_id: i32) -> bool;
+        fn GetAddressesByNetId(net_id: i32, host: &str) -> Vec<String>;
     }
 }
@@ -95,7 +95,7 @@ impl NetworkManager {
     /// # Panics
     /// 
     /// Panics if the mutex cannot be locked, which typically indicates a deadlock.
-    pub(super) fn query_network() -> NetworkState {
+    pub(crate) fn query_network() -> NetworkState {
         let network_manager = NetworkManager::get_instance().lock().unwrap();
         network_manager.network.state()
     }
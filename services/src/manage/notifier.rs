@@ -16,10 +16,16 @@
 //! This module provides a central notification system for broadcasting various task-related
 //! events to clients and, on OpenHarmony platforms, to the system event infrastructure.
 
+use request_utils::journal::JournalEvent;
+
+use crate::error::ErrorCode;
 use crate::info::State;
+use crate::manage::journal::DiagnosticJournal;
 use crate::service::client::ClientManagerEntry;
 use crate::task::notify::{NotifyData, SubscribeType, WaitingCause};
 use crate::task::reason::Reason;
+#[cfg(feature = "spans")]
+use crate::trace::span;
 /// Central notification dispatcher for task events.
 /// 
 /// Provides methods for sending various types of task-related notifications to clients
@@ -44,6 +50,9 @@ impl Notifier {
             State::Completed.repr as i32,
             notify_data.uid,
         );
+        journal(&notify_data, JournalEvent::Complete);
+        #[cfg(feature = "spans")]
+        finish_span(&notify_data, State::Completed);
         client_manager.send_notify_data(SubscribeType::Complete, notify_data)
     }
 
@@ -64,6 +73,9 @@ impl Notifier {
             State::Failed.repr as i32,
             notify_data.uid,
         );
+        journal(&notify_data, JournalEvent::Fail);
+        #[cfg(feature = "spans")]
+        finish_span(&notify_data, State::Failed);
         client_manager.send_notify_data(SubscribeType::Fail, notify_data)
     }
 
@@ -77,6 +89,7 @@ impl Notifier {
     /// * `client_manager` - The client manager used to dispatch the notification
     /// * `reason` - The reason for the fault
     pub(crate) fn faults(tid: u32, client_manager: &ClientManagerEntry, reason: Reason) {
+        DiagnosticJournal::get_instance().record_task(tid, JournalEvent::Fault, reason.repr);
         client_manager.send_faults(tid, SubscribeType::FaultOccur, reason)
     }
 
@@ -89,6 +102,7 @@ impl Notifier {
     /// * `client_manager` - The client manager used to dispatch the notification
     /// * `notify_data` - The notification data containing task information
     pub(crate) fn pause(client_manager: &ClientManagerEntry, notify_data: NotifyData) {
+        journal(&notify_data, JournalEvent::Pause);
         client_manager.send_notify_data(SubscribeType::Pause, notify_data)
     }
 
@@ -101,6 +115,7 @@ impl Notifier {
     /// * `client_manager` - The client manager used to dispatch the notification
     /// * `notify_data` - The notification data containing task information
     pub(crate) fn resume(client_manager: &ClientManagerEntry, notify_data: NotifyData) {
+        journal(&notify_data, JournalEvent::Resume);
         client_manager.send_notify_data(SubscribeType::Resume, notify_data)
     }
 
@@ -133,6 +148,7 @@ impl Notifier {
         if total_processed == 0 && file_total_size < 0 {
             return;
         }
+        journal(&notify_data, JournalEvent::Progress);
         client_manager.send_notify_data(SubscribeType::Progress, notify_data)
     }
 
@@ -146,10 +162,26 @@ impl Notifier {
     /// * `notify_data` - The notification data containing task information
     pub(crate) fn remove(client_manager: &ClientManagerEntry, notify_data: NotifyData) {
         let task_id = notify_data.task_id;
+        journal(&notify_data, JournalEvent::Remove);
+        #[cfg(feature = "spans")]
+        finish_span(&notify_data, State::Removed);
         client_manager.send_notify_data(SubscribeType::Remove, notify_data);
         client_manager.notify_task_finished(task_id);
     }
 
+    /// Sends a removal notification for a task moved to the trash.
+    ///
+    /// The notification data carries the `PendingRemoval` state. The task is
+    /// not marked as finished, as it may still be restored.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_manager` - The client manager used to dispatch the notification
+    /// * `notify_data` - The notification data containing task information
+    pub(crate) fn pending_removal(client_manager: &ClientManagerEntry, notify_data: NotifyData) {
+        client_manager.send_notify_data(SubscribeType::Remove, notify_data);
+    }
+
     /// Sends a waiting notification for a task.
     /// 
     /// Notifies clients that a task is waiting due to a specific cause.
@@ -160,10 +192,64 @@ impl Notifier {
     /// * `task_id` - The ID of the task that is waiting
     /// * `cause` - The reason why the task is waiting
     pub(crate) fn waiting(client_manager: &ClientManagerEntry, task_id: u32, cause: WaitingCause) {
+        let journal = DiagnosticJournal::get_instance();
+        journal.record_task(task_id, JournalEvent::Waiting, cause.clone() as u8);
         client_manager.send_wait_reason(task_id, cause);
     }
 }
 
+/// Sends a notification once the state change it reports is committed.
+///
+/// Clients may query a task as soon as they are notified, so every
+/// client-visible notification of a state change goes through this helper:
+/// `notify` only runs if `commit` wrote the change to the database, and a
+/// failed write is returned without notifying anyone.
+///
+/// # Arguments
+///
+/// * `commit` - Writes the state change to the database.
+/// * `notify` - Sends the notifications reporting the change.
+pub(crate) fn notify_after_commit<C, N>(commit: C, notify: N) -> Result<(), ErrorCode>
+where
+    C: FnOnce() -> Result<(), ErrorCode>,
+    N: FnOnce(),
+{
+    if let Err(e) = commit() {
+        error!("state change not committed, notification dropped, {:?}", e);
+        return Err(e);
+    }
+    notify();
+    Ok(())
+}
+
+/// Journals an event of the task notified by `notify_data`.
+fn journal(notify_data: &NotifyData, event: JournalEvent) {
+    DiagnosticJournal::get_instance().record(
+        notify_data.uid,
+        notify_data.task_id,
+        event,
+        notify_data.progress.common_data.total_processed as u64,
+        0,
+    );
+}
+
+/// Closes the spans of the task notified by `notify_data`.
+#[cfg(feature = "spans")]
+fn finish_span(notify_data: &NotifyData, state: State) {
+    let reason = notify_data
+        .each_file_status
+        .iter()
+        .map(|status| status.reason)
+        .find(|reason| *reason != Reason::Default)
+        .unwrap_or(Reason::Default);
+    span::task_finished(
+        notify_data.task_id,
+        state,
+        reason,
+        notify_data.progress.common_data.total_processed as u64,
+    );
+}
+
 #[cfg(feature = "oh")]
 /// Publishes a task state change event to the system.
 /// 
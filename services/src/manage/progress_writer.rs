@@ -0,0 +1,300 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batched database writes of task progress.
+//!
+//! Running tasks push snapshots of their progress instead of writing each of
+//! them in its own transaction. A flusher thread keeps the latest snapshot of
+//! each task and writes all of them in one statement every
+//! [`FLUSH_INTERVAL`], or as soon as [`MAX_BATCH_TASKS`] tasks are pending.
+//!
+//! Terminal transitions and queries write at once with
+//! [`ProgressWriter::flush_now`] before anything is reported, and snapshots
+//! of the task still queued are dropped instead of overwriting that write.
+//! The persisted offset of a running task lags its progress by at most the
+//! flush interval and the sampling interval of the task.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::manage::database::RequestDb;
+use crate::task::info::UpdateInfo;
+use crate::utils::hashmap_to_string;
+
+/// Longest time a snapshot waits for the batch it is written in.
+pub(crate) const FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Number of pending tasks writing a batch before the interval elapsed.
+pub(crate) const MAX_BATCH_TASKS: usize = 32;
+
+/// Shortest time between two snapshots pushed by a running task.
+pub(crate) const SNAPSHOT_INTERVAL_MS: u64 = 100;
+
+/// Time the last write of a task is remembered to drop older snapshots.
+const WRITTEN_TTL: Duration = Duration::from_secs(60);
+
+/// Writes a batch of progress updates to the database.
+pub(crate) type Sink = Box<dyn Fn(&[(u32, UpdateInfo)]) + Send + Sync>;
+
+/// Progress of a task at the time it was pushed.
+pub(crate) struct Snapshot {
+    task_id: u32,
+    /// Order of the snapshot among all writes, later writes win.
+    seq: u64,
+    info: UpdateInfo,
+}
+
+/// Last write of a task.
+struct Written {
+    seq: u64,
+    at: Instant,
+}
+
+/// Counters of the batches written, displayed by the dump command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FlushStats {
+    /// Batches written by the flusher.
+    pub(crate) batches: u64,
+    /// Task rows written by the flusher.
+    pub(crate) rows: u64,
+    /// Tasks of the last batch.
+    pub(crate) last_batch: usize,
+    /// Tasks of the largest batch.
+    pub(crate) max_batch: usize,
+    /// Time the last batch took to write, in microseconds.
+    pub(crate) last_latency_us: u64,
+    /// Time the slowest batch took to write, in microseconds.
+    pub(crate) max_latency_us: u64,
+}
+
+/// Aggregator of the progress writes of running tasks.
+pub(crate) struct ProgressWriter {
+    tx: Sender<Snapshot>,
+    seq: AtomicU64,
+    interval: Duration,
+    max_batch: usize,
+    /// Last write of each task, locked for the duration of every write so
+    /// batches and immediate writes are applied in order.
+    written: Mutex<HashMap<u32, Written>>,
+    stats: Mutex<FlushStats>,
+    sink: Sink,
+}
+
+impl ProgressWriter {
+    /// Returns the writer of the service, starting its flusher on first use.
+    pub(crate) fn get_instance() -> &'static Self {
+        static INSTANCE: LazyLock<ProgressWriter> = LazyLock::new(|| {
+            let (tx, rx) = mpsc::channel();
+            let spawned = std::thread::Builder::new()
+                .name("progress_flusher".to_string())
+                .spawn(move || ProgressWriter::get_instance().run(rx));
+            if let Err(e) = spawned {
+                error!("progress flusher spawn failed {}", e);
+            }
+            ProgressWriter::new(tx, FLUSH_INTERVAL, MAX_BATCH_TASKS, Box::new(write_batch))
+        });
+        &INSTANCE
+    }
+
+    /// Creates a writer sending snapshots to `tx`, the flusher reading them
+    /// is started with [`ProgressWriter::run`].
+    pub(crate) fn new(
+        tx: Sender<Snapshot>,
+        interval: Duration,
+        max_batch: usize,
+        sink: Sink,
+    ) -> Self {
+        Self {
+            tx,
+            seq: AtomicU64::new(0),
+            interval,
+            max_batch,
+            written: Mutex::new(HashMap::new()),
+            stats: Mutex::new(FlushStats::default()),
+            sink,
+        }
+    }
+
+    /// Queues the progress of a running task, written with the next batch.
+    pub(crate) fn push(&self, task_id: u32, info: UpdateInfo) {
+        let snapshot = Snapshot {
+            task_id,
+            seq: self.seq.fetch_add(1, Ordering::SeqCst),
+            info,
+        };
+        if let Err(mpsc::SendError(snapshot)) = self.tx.send(snapshot) {
+            error!("progress flusher stopped, task {} written at once", task_id);
+            self.flush_now(task_id, snapshot.info);
+        }
+    }
+
+    /// Writes the progress of a task at once, superseding its queued
+    /// snapshots. Used before terminal transitions are reported.
+    pub(crate) fn flush_now(&self, task_id: u32, info: UpdateInfo) {
+        let seq = self.seq.fetch_add(1, Ordering::SeqCst);
+        let mut written = self.written.lock().unwrap();
+        written.insert(
+            task_id,
+            Written {
+                seq,
+                at: Instant::now(),
+            },
+        );
+        (self.sink)(&[(task_id, info)]);
+    }
+
+    /// Returns the counters of the batches written so far.
+    pub(crate) fn stats(&self) -> FlushStats {
+        *self.stats.lock().unwrap()
+    }
+
+    /// Coalesces the snapshots of `rx` and writes them in batches, until
+    /// the writer is dropped.
+    pub(crate) fn run(&self, rx: Receiver<Snapshot>) {
+        let mut pending = HashMap::new();
+        let mut deadline = None;
+        loop {
+            let received = match deadline {
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                Some(deadline) => {
+                    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+            };
+            match received {
+                Ok(snapshot) => {
+                    deadline.get_or_insert_with(|| Instant::now() + self.interval);
+                    coalesce(&mut pending, snapshot);
+                    if pending.len() < self.max_batch {
+                        continue;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.flush(&mut pending);
+                    return;
+                }
+            }
+            self.flush(&mut pending);
+            deadline = None;
+        }
+    }
+
+    /// Writes the pending snapshots not superseded by a later write.
+    fn flush(&self, pending: &mut HashMap<u32, Snapshot>) {
+        if pending.is_empty() {
+            return;
+        }
+        let start = Instant::now();
+        let mut written = self.written.lock().unwrap();
+        let mut batch = Vec::with_capacity(pending.len());
+        for (task_id, snapshot) in pending.drain() {
+            if matches!(written.get(&task_id), Some(last) if last.seq > snapshot.seq) {
+                continue;
+            }
+            written.insert(
+                task_id,
+                Written {
+                    seq: snapshot.seq,
+                    at: start,
+                },
+            );
+            batch.push((task_id, snapshot.info));
+        }
+        written.retain(|_, last| start.duration_since(last.at) < WRITTEN_TTL);
+        if batch.is_empty() {
+            return;
+        }
+        (self.sink)(&batch);
+        drop(written);
+
+        let latency = start.elapsed().as_micros() as u64;
+        let mut stats = self.stats.lock().unwrap();
+        stats.batches += 1;
+        stats.rows += batch.len() as u64;
+        stats.last_batch = batch.len();
+        stats.max_batch = stats.max_batch.max(batch.len());
+        stats.last_latency_us = latency;
+        stats.max_latency_us = stats.max_latency_us.max(latency);
+    }
+}
+
+/// Keeps the latest snapshot of each task.
+fn coalesce(pending: &mut HashMap<u32, Snapshot>, snapshot: Snapshot) {
+    match pending.get(&snapshot.task_id) {
+        Some(queued) if queued.seq > snapshot.seq => {}
+        _ => {
+            pending.insert(snapshot.task_id, snapshot);
+        }
+    }
+}
+
+fn write_batch(batch: &[(u32, UpdateInfo)]) {
+    if RequestDb::get_instance()
+        .execute(&batch_sql(batch))
+        .is_err()
+    {
+        error!("write progress of {} tasks failed", batch.len());
+    }
+}
+
+/// Builds the statement writing the progress of every task of `batch` in a
+/// single transaction. The batch must not be empty.
+pub(crate) fn batch_sql(batch: &[(u32, UpdateInfo)]) -> String {
+    let column = |name: &str, value: &dyn Fn(&UpdateInfo) -> String| {
+        let cases: String = batch
+            .iter()
+            .map(|(task_id, info)| format!(" WHEN {} THEN {}", task_id, value(info)))
+            .collect();
+        format!("{} = CASE task_id{} END", name, cases)
+    };
+    let ids: Vec<String> = batch
+        .iter()
+        .map(|(task_id, _)| task_id.to_string())
+        .collect();
+    let columns = [
+        column("mtime", &|info| info.mtime.to_string()),
+        column("tries", &|info| info.tries.to_string()),
+        column("mime_type", &|info| quote(&info.mime_type)),
+        column("idx", &|info| info.progress.common_data.index.to_string()),
+        column("total_processed", &|info| {
+            info.progress.common_data.total_processed.to_string()
+        }),
+        column("sizes", &|info| {
+            quote(&format!("{:?}", info.progress.sizes))
+        }),
+        column("processed", &|info| {
+            quote(&format!("{:?}", info.progress.processed))
+        }),
+        column("extras", &|info| {
+            quote(&hashmap_to_string(&info.progress.extras))
+        }),
+    ];
+    format!(
+        "UPDATE request_task SET {} WHERE task_id IN ({})",
+        columns.join(", "),
+        ids.join(", ")
+    )
+}
+
+/// Quotes free text as an SQL string literal.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod ut_progress_writer {
+    include!("../../tests/ut/manage/ut_progress_writer.rs");
+}
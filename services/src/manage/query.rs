@@ -21,7 +21,7 @@ pub(crate) use ffi::TaskFilter;
 use super::events::QueryEvent;
 use super::TaskManager;
 use crate::config::{Action, Mode};
-use crate::manage::database::RequestDb;
+use crate::manage::database::{hex, RequestDb};
 use crate::service::permission::ManagerPermission;
 use crate::task::config::TaskConfig;
 use crate::task::info::{State, TaskInfo};
@@ -31,15 +31,16 @@ use crate::task::info::{State, TaskInfo};
 /// # Arguments
 /// 
 /// * `task_id` - The ID of the task to retrieve
-/// * `token` - The authentication token for the task
+/// * `token` - The authentication token for the task, `None` if a signed
+///   token was verified
 /// 
 /// # Returns
 /// 
 /// Returns `Some(TaskConfig)` if a task with the given ID exists and the token matches,
 /// otherwise `None`.
-pub(crate) fn get_task(task_id: u32, token: String) -> Option<TaskConfig> {
+pub(crate) fn get_task(task_id: u32, token: Option<String>) -> Option<TaskConfig> {
     if let Some(config) = RequestDb::get_instance().get_task_config(task_id) {
-        if config.token.eq(token.as_str()) {
+        if token.map_or(true, |token| config.token == token) {
             return Some(config);
         }
         return None;
@@ -110,12 +111,20 @@ impl TaskManager {
     /// Returns `Some(TaskInfo)` if the task exists and is owned by the specified user,
     /// otherwise `None`.
     pub(crate) fn show(&self, uid: u64, task_id: u32) -> Option<TaskInfo> {
+        let mut effective_speed_limit = None;
         if let Some(task) = self.scheduler.get_task(uid, task_id) {
-            task.update_progress_in_database()
+            task.update_progress_in_database();
+            effective_speed_limit = Some(task.effective_speed_limit());
         }
 
         match RequestDb::get_instance().get_task_info(task_id) {
-            Some(info) if info.uid() == uid => Some(info),
+            Some(mut info) if info.uid() == uid => {
+                if let Some(limit) = effective_speed_limit {
+                    info.effective_speed_limit = limit;
+                }
+                info.response_body = RequestDb::get_instance().query_task_response_body(task_id);
+                Some(info)
+            }
             _ => {
                 info!("TaskManger Show: no task found");
                 None
@@ -132,15 +141,18 @@ impl TaskManager {
     /// 
     /// * `uid` - The user ID to verify ownership
     /// * `task_id` - The ID of the task to retrieve
-    /// * `token` - The authentication token for the task
+    /// * `token` - The authentication token for the task, `None` if a signed
+    ///   token was verified
     /// 
     /// # Returns
     /// 
     /// Returns `Some(TaskInfo)` with the bundle name sanitized if the task exists,
     /// is owned by the specified user, and the token matches, otherwise `None`.
-    pub(crate) fn touch(&self, uid: u64, task_id: u32, token: String) -> Option<TaskInfo> {
+    pub(crate) fn touch(&self, uid: u64, task_id: u32, token: Option<String>) -> Option<TaskInfo> {
+        let mut effective_speed_limit = None;
         if let Some(task) = self.scheduler.get_task(uid, task_id) {
-            task.update_progress_in_database()
+            task.update_progress_in_database();
+            effective_speed_limit = Some(task.effective_speed_limit());
         }
 
         let mut info = match RequestDb::get_instance().get_task_info(task_id) {
@@ -151,8 +163,12 @@ impl TaskManager {
             }
         };
 
-        if info.uid() == uid && info.token() == token {
+        if info.uid() == uid && token.map_or(true, |token| info.token() == token) {
             info.bundle = "".to_string();
+            if let Some(limit) = effective_speed_limit {
+                info.effective_speed_limit = limit;
+            }
+            info.response_body = RequestDb::get_instance().query_task_response_body(task_id);
             Some(info)
         } else {
             info!("TaskManger Touch: no task found");
@@ -176,12 +192,14 @@ impl TaskManager {
     /// Returns `Some(TaskInfo)` with sensitive data sanitized if the task exists and
     /// the action has sufficient permissions, otherwise `None`.
     pub(crate) fn query(&self, task_id: u32, action: Action) -> Option<TaskInfo> {
+        let mut effective_speed_limit = None;
         if let Some(task) = self
             .scheduler
             .tasks()
             .find(|task| task.task_id() == task_id)
         {
-            task.update_progress_in_database()
+            task.update_progress_in_database();
+            effective_speed_limit = Some(task.effective_speed_limit());
         }
 
         let mut info = match RequestDb::get_instance().get_task_info(task_id) {
@@ -196,6 +214,10 @@ impl TaskManager {
         if ManagerPermission::check_action(action, task_action) {
             info.data = "".to_string();
             info.url = "".to_string();
+            if let Some(limit) = effective_speed_limit {
+                info.effective_speed_limit = limit;
+            }
+            info.response_body = RequestDb::get_instance().query_task_response_body(task_id);
             Some(info)
         } else {
             info!("TaskManger Query: no task found");
@@ -204,6 +226,10 @@ impl TaskManager {
     }
 }
 
+/// Orders search results by creation time, newest first, which the ctime
+/// indexes serve without sorting.
+const NEWEST_FIRST: &str = "ORDER BY ctime DESC, task_id DESC";
+
 impl RequestDb {
     /// Searches for tasks belonging to a specific user that match filter criteria.
     /// 
@@ -214,10 +240,12 @@ impl RequestDb {
     /// 
     /// # Returns
     /// 
-    /// Returns a vector of task IDs that match the user and filter criteria.
+    /// Returns a vector of task IDs that match the user and filter criteria,
+    /// newest first.
     pub(crate) fn search_task(&self, filter: TaskFilter, uid: u64) -> Vec<u32> {
         let mut sql = format!("SELECT task_id from request_task WHERE uid = {} AND ", uid);
         Self::search_filter(&mut sql, &filter);
+        sql.push_str(NEWEST_FIRST);
         self.query_integer(&sql)
     }
 
@@ -230,34 +258,40 @@ impl RequestDb {
     /// 
     /// # Returns
     /// 
-    /// Returns a vector of task IDs that match the bundle and filter criteria.
+    /// Returns a vector of task IDs that match the bundle and filter criteria,
+    /// newest first.
     pub(crate) fn system_search_task(&self, filter: TaskFilter, bundle_name: String) -> Vec<u32> {
         let mut sql = "SELECT task_id from request_task WHERE ".to_string();
         if bundle_name != "*" {
             sql.push_str(&format!("bundle = '{}' AND ", bundle_name));
         }
         Self::search_filter(&mut sql, &filter);
+        sql.push_str(NEWEST_FIRST);
         self.query_integer(&sql)
     }
 
     /// Appends filter conditions to an SQL query string.
     /// 
-    /// Adds conditions for time range, state, action, and mode to the provided SQL query.
+    /// Adds conditions for time range, state, action, mode, and client tag to the provided
+    /// SQL query.
     /// 
     /// # Arguments
     /// 
     /// * `sql` - The SQL query string to modify
     /// * `filter` - The filter criteria to apply
-    fn search_filter(sql: &mut String, filter: &TaskFilter) {
-        // Always include time range filtering
+    pub(crate) fn search_filter(sql: &mut String, filter: &TaskFilter) {
+        // Always include time range filtering, both ends inclusive
         sql.push_str(&format!(
             "ctime BETWEEN {} AND {} ",
             filter.after, filter.before
         ));
         
-        // Only add state filter if not matching all states
+        // Only add state filter if not matching all states, tasks pending
+        // removal are only found when asked for explicitly
         if filter.state != State::Any.repr {
             sql.push_str(&format!("AND state = {} ", filter.state));
+        } else {
+            sql.push_str(&format!("AND state != {} ", State::PendingRemoval.repr));
         }
         
         // Only add action filter if not matching all actions
@@ -269,6 +303,12 @@ impl RequestDb {
         if filter.mode != Mode::Any.repr {
             sql.push_str(&format!("AND mode = {} ", filter.mode));
         }
+
+        // Only add client tag filter if a tag is given, it matches exactly
+        if !filter.client_tag.is_empty() {
+            let tag = hex(&filter.client_tag);
+            sql.push_str(&format!("AND client_tag = X'{}' ", tag));
+        }
     }
 }
 
@@ -309,13 +349,14 @@ pub(crate) enum SearchMethod {
 #[cxx::bridge(namespace = "OHOS::Request")]
 mod ffi {
     // Filter criteria for searching tasks
-    #[derive(Debug)]
+    #[derive(Debug, Clone)]
     struct TaskFilter {
         before: i64,
         after: i64,
         state: u8,
         action: u8,
         mode: u8,
+        client_tag: String,
     }
 }
 
@@ -0,0 +1,161 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Files deleted together with removed tasks.
+//!
+//! The [`PartialFilePolicy`] of a task decides whether its download target
+//! and the temporary response body files of an upload are deleted when the
+//! task is removed. Only files the service opened by path are deleted, files
+//! the application passed as descriptors and upload sources are always kept.
+//! What was done is reported in the extras of the removal notification under
+//! [`PARTIAL_FILE_ACTION`].
+
+use std::io;
+
+use super::body_files::is_body_file_name;
+use super::database::RequestDb;
+use crate::config::{Action, PartialFilePolicy, TaskConfig};
+use crate::info::State;
+use crate::task::files::{convert_path, BundleCache};
+
+/// Key in the extras of the removal notification telling what happened to
+/// the files of the task: `kept`, `deleted` or `delete_failed`.
+pub(crate) const PARTIAL_FILE_ACTION: &str = "partial_file";
+
+/// Files a removal deletes.
+#[derive(Debug)]
+pub(crate) struct RemovedFiles {
+    task_id: u32,
+    /// Service side paths to delete, empty if the files are kept.
+    paths: Vec<String>,
+}
+
+impl RemovedFiles {
+    /// Looks up the files of a task before its removal changes its state.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the task being removed.
+    /// * `default` - The policy applied if the task sets none.
+    pub(crate) fn capture(task_id: u32, default: PartialFilePolicy) -> Self {
+        let db = RequestDb::get_instance();
+        let Some(config) = db.get_task_config(task_id) else {
+            return Self::keep(task_id);
+        };
+        let complete = db.query_task_state(task_id) == Some(State::Completed.repr)
+            || db
+                .get_task_info(task_id)
+                .is_some_and(|info| !info.progress.sizes.is_empty() && info.progress.is_finish());
+        Self::new(&config, complete, default)
+    }
+
+    /// Returns the files of a task its policy deletes.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration of the task.
+    /// * `complete` - Whether the download of the task finished.
+    /// * `default` - The policy applied if the task sets none.
+    pub(crate) fn new(config: &TaskConfig, complete: bool, default: PartialFilePolicy) -> Self {
+        let task_id = config.common_data.task_id;
+        let delete = match config.on_remove_partial.unwrap_or(default) {
+            PartialFilePolicy::Keep => false,
+            PartialFilePolicy::Delete => true,
+            PartialFilePolicy::DeleteIfIncomplete => !complete,
+        };
+        if !delete {
+            return Self::keep(task_id);
+        }
+        // Upload sources belong to the application, only download targets
+        // and response bodies are files of the task. Response bodies were
+        // always kept before tasks could set a policy.
+        let targets = match config.common_data.action {
+            Action::Download | Action::LocalLoopback => config.file_specs.as_slice(),
+            _ => &[],
+        };
+        let bodies = match config.on_remove_partial {
+            Some(_) => config.body_file_paths.as_slice(),
+            None => &[],
+        };
+        let paths: Vec<&str> = targets
+            .iter()
+            .filter(|spec| !spec.is_user_file && !spec.path.is_empty())
+            .map(|spec| spec.path.as_str())
+            .chain(
+                bodies
+                    .iter()
+                    .filter(|path| path.rsplit('/').next().is_some_and(is_body_file_name))
+                    .map(String::as_str),
+            )
+            .collect();
+        if paths.is_empty() {
+            return Self::keep(task_id);
+        }
+        let bundle_name = match BundleCache::new(config).get_value() {
+            Ok(bundle_name) => bundle_name,
+            Err(e) => {
+                error!("task {} removed files, bundle not found, {:?}", task_id, e);
+                return Self::keep(task_id);
+            }
+        };
+        let uid = config.common_data.uid;
+        Self {
+            task_id,
+            paths: paths
+                .into_iter()
+                .map(|path| convert_path(uid, &bundle_name, path))
+                .collect(),
+        }
+    }
+
+    fn keep(task_id: u32) -> Self {
+        Self {
+            task_id,
+            paths: Vec::new(),
+        }
+    }
+
+    /// Deletes the files, logging and skipping those that fail.
+    ///
+    /// # Returns
+    ///
+    /// The action to report in the removal notification.
+    pub(crate) fn apply(self) -> &'static str {
+        if self.paths.is_empty() {
+            return "kept";
+        }
+        let mut failed = false;
+        for path in self.paths.iter() {
+            match std::fs::remove_file(path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    error!("task {} remove file failed, {}", self.task_id, e);
+                    failed = true;
+                }
+            }
+        }
+        info!("task {} removed {} files", self.task_id, self.paths.len());
+        if failed {
+            "delete_failed"
+        } else {
+            "deleted"
+        }
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_removed_files {
+    include!("../../tests/ut/manage/ut_removed_files.rs");
+}
@@ -0,0 +1,190 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Revisions of task rows for clients syncing their own model of the tasks.
+//!
+//! Every insert, update and delete of a `request_task` row takes the next
+//! value of a counter kept in the `task_revision` table and stores it in the
+//! `revision` column of the row. Deleted rows leave a tombstone with their
+//! revision in the `task_tombstone` table, which maintenance prunes after a
+//! week. The bookkeeping is done by triggers, so every writer of the table is
+//! covered and the revisions survive restarts.
+//!
+//! A client keeps the revision of its last sync as a cursor and asks for what
+//! changed since then instead of fetching every task again.
+
+use std::sync::Once;
+
+use crate::manage::database::RequestDb;
+use crate::task::info::State;
+use crate::utils::call_once;
+
+/// Bits of a packed row holding the task id.
+const TASK_ID_BITS: u32 = 32;
+
+const CREATE_REVISION_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS task_revision (id INTEGER PRIMARY KEY, revision INTEGER, pruned INTEGER)";
+
+const INIT_REVISION: &str =
+    "INSERT OR IGNORE INTO task_revision (id, revision, pruned) VALUES (0, 0, 0)";
+
+const CREATE_TOMBSTONE_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS task_tombstone (task_id INTEGER PRIMARY KEY, uid INTEGER, revision INTEGER, dtime INTEGER)";
+
+const CREATE_INSERT_TRIGGER: &str =
+    "CREATE TRIGGER IF NOT EXISTS task_revision_insert AFTER INSERT ON request_task BEGIN UPDATE task_revision SET revision = revision + 1 WHERE id = 0; UPDATE request_task SET revision = (SELECT revision FROM task_revision WHERE id = 0) WHERE task_id = NEW.task_id; DELETE FROM task_tombstone WHERE task_id = NEW.task_id; END";
+
+// Rows whose revision was just assigned by a trigger are skipped, so the
+// trigger does not bump its own update.
+const CREATE_UPDATE_TRIGGER: &str =
+    "CREATE TRIGGER IF NOT EXISTS task_revision_update AFTER UPDATE ON request_task WHEN NEW.revision IS OLD.revision BEGIN UPDATE task_revision SET revision = revision + 1 WHERE id = 0; UPDATE request_task SET revision = (SELECT revision FROM task_revision WHERE id = 0) WHERE task_id = NEW.task_id; END";
+
+const CREATE_DELETE_TRIGGER: &str =
+    "CREATE TRIGGER IF NOT EXISTS task_revision_delete AFTER DELETE ON request_task BEGIN UPDATE task_revision SET revision = revision + 1 WHERE id = 0; INSERT OR REPLACE INTO task_tombstone (task_id, uid, revision, dtime) VALUES (OLD.task_id, OLD.uid, (SELECT revision FROM task_revision WHERE id = 0), CAST(strftime('%s', 'now') AS INTEGER) * 1000); END";
+
+/// Tasks of one application that changed since a revision.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct ChangedSet {
+    /// Revision to pass as the cursor of the next query.
+    pub(crate) revision: u64,
+    /// Tasks that were created or changed, with their revision.
+    pub(crate) changed: Vec<(u32, u64)>,
+    /// Tasks that were removed, with the revision of their removal.
+    pub(crate) removed: Vec<(u32, u64)>,
+    /// Whether tombstones newer than the cursor were already pruned, in
+    /// which case the client has to fetch all its tasks again.
+    pub(crate) resync: bool,
+}
+
+impl RequestDb {
+    /// Creates the revision tables and triggers if they do not exist yet.
+    ///
+    /// The task manager calls this before it handles any event, so every
+    /// later change of a task row gets a revision.
+    pub(crate) fn init_revisions(&self) {
+        static ONCE: Once = Once::new();
+        call_once(&ONCE, || {
+            for sql in [
+                CREATE_REVISION_TABLE,
+                INIT_REVISION,
+                CREATE_TOMBSTONE_TABLE,
+                CREATE_INSERT_TRIGGER,
+                CREATE_UPDATE_TRIGGER,
+                CREATE_DELETE_TRIGGER,
+            ] {
+                if let Err(e) = self.execute(sql) {
+                    error!("init task revisions failed {}", e);
+                }
+            }
+        });
+    }
+
+    /// Returns the revision of the latest change of any task row.
+    pub(crate) fn max_revision(&self) -> u64 {
+        self.init_revisions();
+        self.query_integer::<u64>("SELECT revision FROM task_revision WHERE id = 0")
+            .first()
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the tasks of an application that changed after `revision`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The application whose tasks are reported.
+    /// * `revision` - The cursor of the caller, 0 reports every task.
+    ///
+    /// # Notes
+    ///
+    /// The returned cursor is read before the tasks, so a task changing in
+    /// between is reported again by the next query rather than missed. Tasks
+    /// pending removal or removed are reported as removed. `query_integer`
+    /// reads a single column, so each task is packed into one integer: the
+    /// revision above bit 32 and the task id below.
+    pub(crate) fn changed_since(&self, uid: u64, revision: u64) -> ChangedSet {
+        let max_revision = self.max_revision();
+        let unpack = |row: u64| {
+            (
+                (row & ((1 << TASK_ID_BITS) - 1)) as u32,
+                row >> TASK_ID_BITS,
+            )
+        };
+
+        let mut set = ChangedSet {
+            revision: max_revision,
+            ..Default::default()
+        };
+        let removed_states = format!("{}, {}", State::Removed.repr, State::PendingRemoval.repr);
+        let rows = |condition: String| {
+            let sql = format!(
+                "SELECT (IFNULL(revision, 0) << {}) | task_id FROM request_task WHERE uid = {} AND IFNULL(revision, 0) > {} AND {} ORDER BY revision",
+                TASK_ID_BITS, uid, revision, condition
+            );
+            self.query_integer::<u64>(&sql).into_iter().map(unpack)
+        };
+        set.changed
+            .extend(rows(format!("state NOT IN ({})", removed_states)));
+        set.removed
+            .extend(rows(format!("state IN ({})", removed_states)));
+
+        let sql = format!(
+            "SELECT (revision << {}) | task_id FROM task_tombstone WHERE uid = {} AND revision > {}",
+            TASK_ID_BITS, uid, revision
+        );
+        set.removed
+            .extend(self.query_integer::<u64>(&sql).into_iter().map(unpack));
+        set.removed.sort_by_key(|(_, revision)| *revision);
+
+        let sql = "SELECT pruned FROM task_revision WHERE id = 0";
+        let pruned = self.query_integer::<u64>(sql).first().copied().unwrap_or(0);
+        set.resync = revision < pruned;
+        set
+    }
+
+    /// Deletes the tombstones of tasks deleted before `expired`, returns how
+    /// many.
+    ///
+    /// The latest pruned revision is remembered, so a client whose cursor is
+    /// older learns that it missed removals.
+    pub(crate) fn prune_tombstones(&self, expired: u64) -> u64 {
+        self.init_revisions();
+        let condition = format!("dtime < {}", expired);
+        let sql = format!("SELECT COUNT(*) FROM task_tombstone WHERE {}", condition);
+        let count = self
+            .query_integer::<u64>(&sql)
+            .first()
+            .copied()
+            .unwrap_or(0);
+        if count == 0 {
+            return 0;
+        }
+        let sql = format!(
+            "UPDATE task_revision SET pruned = MAX(pruned, (SELECT MAX(revision) FROM task_tombstone WHERE {})) WHERE id = 0",
+            condition
+        );
+        if self.execute(&sql).is_err() {
+            return 0;
+        }
+        match self.execute(&format!("DELETE FROM task_tombstone WHERE {}", condition)) {
+            Ok(()) => count,
+            Err(_) => 0,
+        }
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_revision {
+    include!("../../tests/ut/manage/ut_revision.rs");
+}
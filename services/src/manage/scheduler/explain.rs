@@ -0,0 +1,91 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Explanation of the state of a task.
+//!
+//! Gathers in one report what `check_config_satisfy` evaluates when a task
+//! starts, next to the stored state, reason and retry count, so operators
+//! and applications can tell why a task is not running.
+
+use super::Scheduler;
+use crate::info::State;
+use crate::manage::database::RequestDb;
+use crate::task::notify::WaitingCause;
+use crate::task::reason::Reason;
+
+/// Why a task is in its current state.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Explanation {
+    /// Current state of the task.
+    pub(crate) state: State,
+    /// Reason of the current state.
+    pub(crate) reason: Reason,
+    /// What the task waits for, `None` if it is not waiting.
+    pub(crate) waiting_cause: Option<WaitingCause>,
+    /// Whether the current network suits the task.
+    pub(crate) network: bool,
+    /// Whether the task may run given the state of its application.
+    pub(crate) foreground: bool,
+    /// Whether the task may run given the charging state and thermal level.
+    pub(crate) battery: bool,
+    /// Whether the task has not run out of storage.
+    pub(crate) storage: bool,
+    /// Number of times the task was retried.
+    pub(crate) tries: u32,
+    /// Time in milliseconds the task is next scheduled to act at, 0 if none.
+    pub(crate) next_action_time: u64,
+}
+
+impl Scheduler {
+    /// Explains why a task is in its current state.
+    ///
+    /// # Returns
+    ///
+    /// The explanation, `None` if the task does not exist.
+    ///
+    /// # Notes
+    ///
+    /// The conditions are evaluated against the current system state, the
+    /// stored reason may predate them. Waiting tasks with an unknown reason
+    /// wait in the queue.
+    pub(crate) fn explain(&self, task_id: u32) -> Option<Explanation> {
+        let database = RequestDb::get_instance();
+        let info = database.get_task_info(task_id)?;
+        let config = database.get_task_config(task_id)?;
+
+        let state = State::from(info.progress.common_data.state);
+        let reason = Reason::from(info.common_data.reason);
+        let waiting_cause = (state == State::Waiting).then(|| {
+            WaitingCause::from_reason(info.common_data.reason).unwrap_or(WaitingCause::TaskQueue)
+        });
+        let next_action_time = match reason {
+            Reason::RateLimited => self.rate_limited_until(task_id).unwrap_or(0),
+            _ => 0,
+        };
+        Some(Explanation {
+            state,
+            reason,
+            waiting_cause,
+            network: config.satisfy_network(self.state_handler.network()).is_ok(),
+            foreground: config.satisfy_foreground(self.state_handler.foreground_abilities()),
+            battery: config.satisfy_charging(self.state_handler.charging_gated())
+                && config.satisfy_device_condition(
+                    self.state_handler.thermal_level(),
+                    self.state_handler.charging(),
+                ),
+            storage: reason != Reason::InsufficientSpace,
+            tries: info.common_data.tries,
+            next_action_time,
+        })
+    }
+}
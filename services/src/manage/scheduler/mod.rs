@@ -20,33 +20,47 @@
 
 mod qos;
 mod queue;
+mod rate_limit;
+pub(crate) mod restore;
 pub(crate) mod state;
 use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 
+mod explain;
 mod sql;
-use qos::Qos;
+mod waiting;
+pub(crate) use explain::Explanation;
+use qos::{Qos, ATTENTION_BOOST_DURATION};
 use queue::RunningQueue;
+use rate_limit::RateLimit;
 use state::sql::SqlList;
+pub(crate) use waiting::WaitingBreakdown;
 
 use super::events::TaskManagerEvent;
 use crate::config::Mode;
 use crate::error::ErrorCode;
 use crate::info::TaskInfo;
+use crate::manage::captive_portal;
 use crate::manage::database::RequestDb;
-use crate::manage::notifier::Notifier;
+use crate::manage::network::{NetworkState, NetworkType};
+use crate::manage::network_manager::NetworkManager;
+use crate::manage::notifier::{notify_after_commit, Notifier};
+use crate::manage::removed_files::{RemovedFiles, PARTIAL_FILE_ACTION};
 use crate::manage::task_manager::TaskManagerTx;
 use crate::service::active_counter::ActiveCounter;
 use crate::service::client::ClientManagerEntry;
 use crate::service::notification_bar::NotificationDispatcher;
 use crate::service::run_count::RunCountManagerEntry;
 use crate::task::config::Action;
+use crate::task::debug_log::TaskLog;
 use crate::task::info::State;
 use crate::task::notify::WaitingCause;
 use crate::task::reason::Reason;
 use crate::task::request_task::RequestTask;
-use crate::utils::get_current_timestamp;
+use crate::task::speed_history::{self, SpeedSample};
+use crate::utils::{get_current_timestamp, runtime_spawn};
 
 const MILLISECONDS_IN_ONE_MONTH: u64 = 30 * 24 * 60 * 60 * 1000;
 
@@ -78,6 +92,11 @@ pub(crate) struct Scheduler {
     pub(crate) resort_scheduled: bool,
     /// Transmitter for sending events to the task manager.
     task_manager: TaskManagerTx,
+    /// Failed tasks held waiting for a captive portal probe, with the reasons
+    /// they failed with.
+    portal_suspects: HashMap<u32, Reason>,
+    /// Tasks rate limited by their servers.
+    rate_limits: HashMap<u32, RateLimit>,
 }
 
 impl Scheduler {
@@ -99,6 +118,8 @@ impl Scheduler {
         client_manager: ClientManagerEntry,
         active_counter: ActiveCounter,
     ) -> Scheduler {
+        // Tasks cut off by the previous service instance run again from waiting
+        restore::restore_interrupted_tasks();
         let mut state_handler = state::Handler::new(tx.clone());
         // Initialize state and update database with initial state
         let sql_list = state_handler.init();
@@ -108,6 +129,11 @@ impl Scheduler {
                 error!("TaskManager update network failed {:?}", e);
             };
         }
+        // Tasks restored waiting for a captive portal wait until a probe clears
+        if db.has_captive_portal_tasks() {
+            state_handler.update_captive_portal(true);
+            captive_portal::spawn_reprobe(tx.clone());
+        }
 
         Self {
             qos: Qos::new(),
@@ -121,6 +147,8 @@ impl Scheduler {
             state_handler,
             resort_scheduled: false,
             task_manager: tx,
+            portal_suspects: HashMap::new(),
+            rate_limits: HashMap::new(),
         }
     }
 
@@ -147,6 +175,26 @@ impl Scheduler {
         self.running_queue.tasks()
     }
 
+    /// Returns the speed samples of a running or recently stopped task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the task.
+    /// * `points` - Maximum number of samples to return, 0 for all.
+    ///
+    /// # Returns
+    ///
+    /// The newest samples oldest first, `None` if the task is neither
+    /// running nor recently stopped.
+    pub(crate) fn speed_history(&self, task_id: u32, points: usize) -> Option<Vec<SpeedSample>> {
+        let samples = self
+            .tasks()
+            .find(|task| task.task_id() == task_id)
+            .map(|task| task.speed_history.lock().unwrap().samples())
+            .or_else(|| speed_history::recent(task_id))?;
+        Some(speed_history::last_points(samples, points))
+    }
+
     /// Returns the number of currently running tasks.
     ///
     /// # Returns
@@ -222,36 +270,41 @@ impl Scheduler {
         }
         // Change to Waiting state so the task can be scheduled
         database.change_status(task_id, State::Waiting)?;
-
-        let info = RequestDb::get_instance()
-            .get_task_info(task_id)
-            .ok_or(ErrorCode::TaskNotFound)?;
-        if is_resume {
-            Notifier::resume(&self.client_manager, info.build_notify_data());
-        } else {
+        if !is_resume {
             // For new task starts, reset the task time
             database.update_task_time(task_id, 0);
         }
 
-        // Check if task is already finished
+        // A finished task completes instead of running again
         if info.progress.is_finish() {
-            database.update_task_state(task_id, State::Completed, Reason::Default);
-            if let Some(info) = database.get_task_info(task_id) {
-                Notifier::complete(&self.client_manager, info.build_notify_data());
-            }
+            return notify_after_commit(
+                || database.commit_task_state(task_id, State::Completed, Reason::Default),
+                || {
+                    if let Some(info) = database.get_task_info(task_id) {
+                        Notifier::complete(&self.client_manager, info.build_notify_data());
+                    }
+                },
+            );
         }
 
-        // Check if task configuration requirements are satisfied
-        if !self.check_config_satisfy(task_id)? {
-            return Ok(());
-        };
-        
-        // Add task to QoS system and trigger reschedule
-        let qos_info = database
-            .get_task_qos_info(task_id)
-            .ok_or(ErrorCode::TaskNotFound)?;
-        self.qos.start_task(uid, qos_info);
-        self.schedule_if_not_scheduled();
+        // Check if task configuration requirements are satisfied, otherwise
+        // the task waits for them.
+        if self.check_config_satisfy(task_id)? {
+            // Add task to QoS system and trigger reschedule
+            let qos_info = database
+                .get_task_qos_info(task_id)
+                .ok_or(ErrorCode::TaskNotFound)?;
+            self.qos.start_task(uid, qos_info);
+            self.schedule_if_not_scheduled();
+        }
+
+        // Report the resume once the task is queued or waiting for good
+        if is_resume {
+            let info = database
+                .get_task_info(task_id)
+                .ok_or(ErrorCode::TaskNotFound)?;
+            Notifier::resume(&self.client_manager, info.build_notify_data());
+        }
         Ok(())
     }
 
@@ -270,6 +323,7 @@ impl Scheduler {
         let database = RequestDb::get_instance();
         // Update task state in database
         database.change_status(task_id, State::Paused)?;
+        self.rate_limits.remove(&task_id);
         // Remove from QoS system
         self.qos.remove_task(uid, task_id);
 
@@ -294,15 +348,22 @@ impl Scheduler {
     ///
     /// * `uid` - The user ID of the application that owns the task.
     /// * `task_id` - The unique identifier of the task.
+    /// * `files` - The files of the task to delete once it is removed.
     ///
     /// # Returns
     ///
     /// `Ok(())` if the task was successfully removed, or an error if the task
     /// could not be found.
-    pub(crate) fn remove_task(&mut self, uid: u64, task_id: u32) -> Result<(), ErrorCode> {
+    pub(crate) fn remove_task(
+        &mut self,
+        uid: u64,
+        task_id: u32,
+        files: RemovedFiles,
+    ) -> Result<(), ErrorCode> {
         let database = RequestDb::get_instance();
         // Update task state in database
         database.change_status(task_id, State::Removed)?;
+        self.rate_limits.remove(&task_id);
         // Remove from QoS system
         self.qos.remove_task(uid, task_id);
 
@@ -313,11 +374,126 @@ impl Scheduler {
         
         // Clean up user file task association
         database.remove_user_file_task(task_id);
+        let action = files.apply();
         
         // Notify client of the removal
+        let mut info = database
+            .get_task_info(task_id)
+            .ok_or(ErrorCode::TaskNotFound)?;
+        info.progress
+            .extras
+            .insert(PARTIAL_FILE_ACTION.to_string(), action.to_string());
+        Notifier::remove(&self.client_manager, info.build_notify_data());
+        Ok(())
+    }
+
+    /// Moves a task to the trash, from which it can be restored until it is
+    /// finalized.
+    ///
+    /// The task leaves the QoS system and stops running, but keeps its row
+    /// and its partial file.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID of the application that owns the task.
+    /// * `task_id` - The unique identifier of the task.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the task is pending removal, or an error if the task could
+    /// not be found or is already removed.
+    pub(crate) fn remove_task_with_grace(
+        &mut self,
+        uid: u64,
+        task_id: u32,
+    ) -> Result<(), ErrorCode> {
+        let database = RequestDb::get_instance();
+        database.change_status(task_id, State::PendingRemoval)?;
+        self.qos.remove_task(uid, task_id);
+
+        // An upload restored later continues like a paused one.
+        if self.running_queue.cancel_task(task_id, uid) {
+            self.running_queue.upload_resume.insert(task_id);
+            self.schedule_if_not_scheduled();
+        }
+
         let info = database
             .get_task_info(task_id)
             .ok_or(ErrorCode::TaskNotFound)?;
+        Notifier::pending_removal(&self.client_manager, info.build_notify_data());
+        Ok(())
+    }
+
+    /// Restores a task pending removal to the `Paused` state.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID of the application that owns the task.
+    /// * `task_id` - The unique identifier of the task.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the task was restored, or `ErrorCode::TaskStateErr` if the
+    /// task is not pending removal.
+    pub(crate) fn undo_remove_task(&mut self, uid: u64, task_id: u32) -> Result<(), ErrorCode> {
+        let database = RequestDb::get_instance();
+        let info = database
+            .get_task_info(task_id)
+            .filter(|info| info.uid() == uid)
+            .ok_or(ErrorCode::TaskNotFound)?;
+        if info.progress.common_data.state != State::PendingRemoval.repr {
+            return Err(ErrorCode::TaskStateErr);
+        }
+        database
+            .execute(&sql::undo_remove(task_id))
+            .map_err(|_| ErrorCode::SystemApi)?;
+
+        let info = database
+            .get_task_info(task_id)
+            .ok_or(ErrorCode::TaskNotFound)?;
+        if info.progress.common_data.state != State::Paused.repr {
+            return Err(ErrorCode::TaskStateErr);
+        }
+        task_debug!(TaskLog::load(task_id), "removal undone");
+        Notifier::pause(&self.client_manager, info.build_notify_data());
+        Ok(())
+    }
+
+    /// Finally removes a task pending removal.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The unique identifier of the task.
+    /// * `files` - The files of the task to delete once it is removed.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the task was removed, or `ErrorCode::TaskStateErr` if the
+    /// task is not pending removal.
+    pub(crate) fn finalize_removal(
+        &mut self,
+        task_id: u32,
+        files: RemovedFiles,
+    ) -> Result<(), ErrorCode> {
+        let database = RequestDb::get_instance();
+        if database.query_task_state(task_id) != Some(State::PendingRemoval.repr) {
+            return Err(ErrorCode::TaskStateErr);
+        }
+        database
+            .execute(&sql::finalize_removal(task_id))
+            .map_err(|_| ErrorCode::SystemApi)?;
+        let mut info = database
+            .get_task_info(task_id)
+            .ok_or(ErrorCode::TaskNotFound)?;
+        if info.progress.common_data.state != State::Removed.repr {
+            return Err(ErrorCode::TaskStateErr);
+        }
+        task_debug!(TaskLog::load(task_id), "removal finalized");
+        self.running_queue.upload_resume.remove(&task_id);
+        database.remove_user_file_task(task_id);
+        info.progress
+            .extras
+            .insert(PARTIAL_FILE_ACTION.to_string(), files.apply().to_string());
         Notifier::remove(&self.client_manager, info.build_notify_data());
         Ok(())
     }
@@ -328,20 +504,33 @@ impl Scheduler {
     ///
     /// * `uid` - The user ID of the application that owns the task.
     /// * `task_id` - The unique identifier of the task.
+    /// * `graceful` - Whether a running upload finishes its file in flight
+    ///   before it is cancelled.
     ///
     /// # Returns
     ///
     /// `Ok(())` if the task was successfully stopped, or an error if the task
     /// could not be found.
-    pub(crate) fn stop_task(&mut self, uid: u64, task_id: u32) -> Result<(), ErrorCode> {
+    pub(crate) fn stop_task(
+        &mut self,
+        uid: u64,
+        task_id: u32,
+        graceful: bool,
+    ) -> Result<(), ErrorCode> {
         let database = RequestDb::get_instance();
         // Update task state in database
         database.change_status(task_id, State::Stopped)?;
+        self.rate_limits.remove(&task_id);
         // Remove from QoS system
         self.qos.remove_task(uid, task_id);
 
         // If the task was running, cancel it and schedule a reschedule
-        if self.running_queue.cancel_task(task_id, uid) {
+        let canceled = if graceful {
+            self.running_queue.soft_cancel_task(task_id, uid)
+        } else {
+            self.running_queue.cancel_task(task_id, uid)
+        };
+        if canceled {
             self.schedule_if_not_scheduled();
         }
         Ok(())
@@ -420,6 +609,8 @@ impl Scheduler {
     /// * `task_id` - The unique identifier of the completed task.
     pub(crate) fn task_completed(&mut self, uid: u64, task_id: u32) {
         info!("task {} completed", task_id);
+        task_debug!(TaskLog::load(task_id), "completed");
+        self.rate_limits.remove(&task_id);
         // Mark task as finished in the running queue
         self.running_queue.task_finish(uid, task_id);
 
@@ -445,15 +636,18 @@ impl Scheduler {
             }
         }
 
-        // Mark as completed and clean up
-        database.update_task_state(task_id, State::Completed, Reason::Default);
+        // Mark as completed, then notify and clean up
+        let _ = notify_after_commit(
+            || database.commit_task_state(task_id, State::Completed, Reason::Default),
+            || {
+                if let Some(mut info) = database.get_task_info(task_id) {
+                    info.response_body = database.query_task_response_body(task_id);
+                    Notifier::complete(&self.client_manager, info.build_notify_data());
+                    NotificationDispatcher::get_instance().publish_success_notification(&info);
+                }
+            },
+        );
         database.remove_user_file_task(task_id);
-        
-        // Send completion notifications
-        if let Some(info) = database.get_task_info(task_id) {
-            Notifier::complete(&self.client_manager, info.build_notify_data());
-            NotificationDispatcher::get_instance().publish_success_notification(&info);
-        }
     }
 
     /// Handles task cancellation.
@@ -472,6 +666,7 @@ impl Scheduler {
         task_count: &mut HashMap<u64, (usize, usize)>,
     ) {
         info!("task {} canceled", task_id);
+        task_debug!(TaskLog::load(task_id), "canceled, mode {:?}", mode);
         // Mark task as finished in the running queue
         self.running_queue.task_finish(uid, task_id);
         
@@ -492,12 +687,16 @@ impl Scheduler {
             // If running, move to waiting state due to task limits
             State::Running | State::Retrying => {
                 info!("task {} waiting for task limits", task_id);
-                RequestDb::get_instance().update_task_state(
-                    task_id, 
-                    State::Waiting, 
-                    Reason::RunningTaskMeetLimits,
+                let _ = notify_after_commit(
+                    || {
+                        database.commit_task_state(
+                            task_id,
+                            State::Waiting,
+                            Reason::RunningTaskMeetLimits,
+                        )
+                    },
+                    || Notifier::waiting(&self.client_manager, task_id, WaitingCause::TaskQueue),
                 );
-                Notifier::waiting(&self.client_manager, task_id, WaitingCause::TaskQueue);
             }
             // If failed, notify client and reduce task count
             State::Failed => {
@@ -507,7 +706,7 @@ impl Scheduler {
                 Scheduler::notify_fail(info, &self.client_manager, Reason::from(reason));
             }
             // If stopped or removed, clean up and try restart
-            State::Stopped | State::Removed => {
+            State::Stopped | State::Removed | State::PendingRemoval => {
                 info!("task {} cancel with state Stopped or Removed", task_id);
                 NotificationDispatcher::get_instance().unregister_task(uid, task_id, true);
                 self.running_queue.try_restart(uid, task_id);
@@ -515,22 +714,13 @@ impl Scheduler {
             // For waiting tasks, determine waiting cause based on reason
             State::Waiting => {
                 info!("task {} cancel with state Waiting", task_id);
-                let reason = match info.common_data.reason {
-                    reason if reason == Reason::AppBackgroundOrTerminate.repr => {
-                        WaitingCause::AppState
-                    }
-                    reason
-                        if reason == Reason::NetworkOffline.repr
-                            || reason == Reason::UnsupportedNetworkType.repr =>
-                    {
-                        WaitingCause::Network
-                    }
-                    reason if reason == Reason::RunningTaskMeetLimits.repr => {
-                        WaitingCause::TaskQueue
-                    }
-                    reason if reason == Reason::AccountStopped.repr => WaitingCause::UserState,
-                    reason => {
-                        error!("task {} cancel with other reason {}", task_id, reason);
+                let reason = match WaitingCause::from_reason(info.common_data.reason) {
+                    Some(cause) => cause,
+                    None => {
+                        error!(
+                            "task {} cancel with other reason {}",
+                            task_id, info.common_data.reason
+                        );
                         WaitingCause::TaskQueue
                     }
                 };
@@ -557,6 +747,8 @@ impl Scheduler {
     /// * `reason` - The reason for the task failure.
     pub(crate) fn task_failed(&mut self, uid: u64, task_id: u32, reason: Reason) {
         info!("task {} failed", task_id);
+        task_debug!(TaskLog::load(task_id), "failed, reason {}", reason.repr);
+        self.rate_limits.remove(&task_id);
         // Mark task as finished in the running queue
         self.running_queue.task_finish(uid, task_id);
 
@@ -574,13 +766,133 @@ impl Scheduler {
             }
         }
 
-        // Update task state to failed
-        database.update_task_state(task_id, State::Failed, reason);
-        
-        // Send failure notifications
+        // Tasks failing behind a captive portal wait for the user to sign in
+        if self.hold_for_captive_portal(task_id, reason) {
+            return;
+        }
+
+        // Update task state to failed, then send failure notifications
+        let _ = notify_after_commit(
+            || database.commit_task_state(task_id, State::Failed, reason),
+            || {
+                if let Some(info) = database.get_task_info(task_id) {
+                    let reason = info.common_data.reason;
+                    Scheduler::notify_fail(info, &self.client_manager, Reason::from(reason));
+                }
+            },
+        );
+    }
+
+    /// Moves a failed task to waiting if its failure may be caused by a
+    /// captive portal while the network claims connectivity.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The unique identifier of the task.
+    /// * `reason` - The reason the task failed with.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the task waits instead of failing. Unless a portal is already
+    /// known, the task is held until a probe tells whether there is one.
+    fn hold_for_captive_portal(&mut self, task_id: u32, reason: Reason) -> bool {
+        if !matches!(self.state_handler.network(), NetworkState::Online(_)) {
+            return false;
+        }
+        let database = RequestDb::get_instance();
+        let Some(info) = database.get_task_info(task_id) else {
+            return false;
+        };
+        if !captive_portal::is_portal_symptom(reason, &info.progress.extras) {
+            return false;
+        }
+
+        if database
+            .commit_task_state(task_id, State::Waiting, Reason::CaptivePortal)
+            .is_err()
+        {
+            return false;
+        }
+        if self.state_handler.captive_portal() {
+            info!("task {} waits for the captive portal", task_id);
+            Notifier::waiting(&self.client_manager, task_id, WaitingCause::CaptivePortal);
+        } else {
+            info!(
+                "task {} failed by {:?}, probing for a captive portal",
+                task_id, reason
+            );
+            if self.portal_suspects.is_empty() {
+                captive_portal::spawn_probe(self.task_manager.clone());
+            }
+            self.portal_suspects.insert(task_id, reason);
+        }
+        true
+    }
+
+    /// Handles the result of a captive portal probe.
+    ///
+    /// # Arguments
+    ///
+    /// * `portal` - Whether a captive portal intercepts the traffic.
+    ///
+    /// # Notes
+    ///
+    /// Held tasks wait for the portal if one is detected and fail with their
+    /// original reasons otherwise. A newly detected portal is probed again
+    /// periodically, and tasks waiting for it return to the queue once a
+    /// probe no longer detects it.
+    pub(crate) fn on_captive_portal(&mut self, portal: bool) {
+        let database = RequestDb::get_instance();
+        for (task_id, reason) in std::mem::take(&mut self.portal_suspects) {
+            let Some(info) = database.get_task_info(task_id) else {
+                continue;
+            };
+            // The task may have been paused or removed meanwhile
+            if info.progress.common_data.state != State::Waiting.repr
+                || info.common_data.reason != Reason::CaptivePortal.repr
+            {
+                continue;
+            }
+            if portal {
+                Notifier::waiting(&self.client_manager, task_id, WaitingCause::CaptivePortal);
+            } else {
+                let _ = notify_after_commit(
+                    || database.commit_task_state(task_id, State::Failed, reason),
+                    || {
+                        if let Some(info) = database.get_task_info(task_id) {
+                            Scheduler::notify_fail(info, &self.client_manager, reason);
+                        }
+                    },
+                );
+            }
+        }
+        if portal && !self.state_handler.captive_portal() {
+            captive_portal::spawn_reprobe(self.task_manager.clone());
+        }
+        self.on_state_change(state::Handler::update_captive_portal, portal);
+    }
+
+    /// Handles a task paused by a failure it can be resumed from.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID of the application that owns the task.
+    /// * `task_id` - The unique identifier of the task.
+    /// * `reason` - The reason the task was paused.
+    pub(crate) fn task_paused(&mut self, uid: u64, task_id: u32, reason: Reason) {
+        info!("task {} paused, reason {:?}", task_id, reason);
+        task_debug!(TaskLog::load(task_id), "paused, reason {}", reason.repr);
+        self.running_queue.task_finish(uid, task_id);
+
+        let database = RequestDb::get_instance();
+        if self.qos.remove_task(uid, task_id) {
+            self.schedule_if_not_scheduled();
+        }
+        if !database.pause_interrupted(task_id, reason) {
+            return;
+        }
         if let Some(info) = database.get_task_info(task_id) {
-            let reason = info.common_data.reason;
-            Scheduler::notify_fail(info, &self.client_manager, Reason::from(reason));
+            Notifier::pause(&self.client_manager, info.build_notify_data());
         }
     }
 
@@ -645,6 +957,7 @@ impl Scheduler {
         let action = match info.action() {
             Action::Download => "DOWNLOAD",
             Action::Upload => "UPLOAD",
+            Action::LocalLoopback => "LOOPBACK",
             _ => "UNKNOWN",
         };
         let reason = Reason::from(info.common_data.reason);
@@ -681,6 +994,22 @@ impl Scheduler {
                 error!("TaskManager update network failed {:?}", e);
             };
         }
+
+        // Background tasks released by other state changes keep waiting for charging
+        if self.state_handler.charging_gated() {
+            if let Err(e) = db.execute(&state::sql::charging_unavailable()) {
+                error!("TaskManager update charging failed {:?}", e);
+            }
+        }
+
+        // Tasks released by other state changes keep waiting for the device condition
+        let sql = state::sql::device_condition_unavailable(
+            self.state_handler.thermal_level(),
+            self.state_handler.charging(),
+        );
+        if let Err(e) = db.execute(&sql) {
+            error!("TaskManager update device condition failed {:?}", e);
+        }
         
         // Reload and reschedule all tasks based on new state
         self.reload_all_tasks();
@@ -710,6 +1039,52 @@ impl Scheduler {
         }
     }
 
+    /// Changes the total speed shared by all running tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `cap` - The global speed cap in bytes per second, 0 removes the cap.
+    pub(crate) fn set_global_max_speed(&mut self, cap: u64) {
+        if self.qos.set_global_cap(cap) {
+            self.schedule_if_not_scheduled();
+        }
+    }
+
+    /// Changes how many top-tier run slots are reserved for foreground tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `reserve` - The number of reserved slots, 0 disables the reservation.
+    pub(crate) fn set_foreground_reserve(&mut self, reserve: usize) {
+        if self.qos.set_foreground_reserve(reserve) {
+            self.schedule_if_not_scheduled();
+        }
+    }
+
+    /// Boosts or releases a task the user is watching in the notification bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The unique identifier of the task.
+    /// * `attention` - Whether the task's notification is expanded.
+    ///
+    /// # Notes
+    ///
+    /// Every expansion refreshes the boost and arms a timer that reschedules
+    /// once the boost may have expired, so the task decays back on its own.
+    pub(crate) fn user_attention(&mut self, task_id: u32, attention: bool) {
+        if self.qos.user_attention(task_id, attention, Instant::now()) {
+            self.schedule_if_not_scheduled();
+        }
+        if attention {
+            let task_manager = self.task_manager.clone();
+            runtime_spawn(async move {
+                ylong_runtime::time::sleep(ATTENTION_BOOST_DURATION).await;
+                task_manager.send_event(TaskManagerEvent::Reschedule);
+            });
+        }
+    }
+
     /// Schedules a reschedule operation if one is not already pending.
     ///
     /// This method prevents multiple reschedule operations from being scheduled
@@ -778,8 +1153,10 @@ impl Scheduler {
                 self.state_handler.network()
             );
             // Put task in waiting state due to network
-            database.update_task_state(task_id, State::Waiting, reason);
-            Notifier::waiting(&self.client_manager, task_id, WaitingCause::Network);
+            notify_after_commit(
+                || database.commit_task_state(task_id, State::Waiting, reason),
+                || Notifier::waiting(&self.client_manager, task_id, WaitingCause::Network),
+            )?;
             return Ok(false);
         }
 
@@ -790,8 +1167,39 @@ impl Scheduler {
                 task_id, config.common_data.uid
             );
             // Put task in waiting state due to app state
-            database.update_task_state(task_id, State::Waiting, Reason::AppBackgroundOrTerminate);
-            Notifier::waiting(&self.client_manager, task_id, WaitingCause::AppState);
+            notify_after_commit(
+                || {
+                    database.commit_task_state(
+                        task_id,
+                        State::Waiting,
+                        Reason::AppBackgroundOrTerminate,
+                    )
+                },
+                || Notifier::waiting(&self.client_manager, task_id, WaitingCause::AppState),
+            )?;
+            return Ok(false);
+        }
+
+        // Check if charging requirements are satisfied
+        if !config.satisfy_charging(self.state_handler.charging_gated()) {
+            info!("task {} started, waiting for charging", task_id);
+            notify_after_commit(
+                || database.commit_task_state(task_id, State::Waiting, Reason::NotCharging),
+                || Notifier::waiting(&self.client_manager, task_id, WaitingCause::Battery),
+            )?;
+            return Ok(false);
+        }
+
+        // Check if the thermal level and charging state allow the task to run
+        if !config.satisfy_device_condition(
+            self.state_handler.thermal_level(),
+            self.state_handler.charging(),
+        ) {
+            info!("task {} started, waiting for device condition", task_id);
+            notify_after_commit(
+                || database.commit_task_state(task_id, State::Waiting, Reason::DeviceCondition),
+                || Notifier::waiting(&self.client_manager, task_id, WaitingCause::DeviceCondition),
+            )?;
             return Ok(false);
         }
         
@@ -839,6 +1247,40 @@ impl Scheduler {
         self.running_queue.retry_all_tasks();
     }
 
+    /// Checks whether the device just moved from another network to Wi-Fi,
+    /// the network tasks prefer, without going offline in between.
+    ///
+    /// Must be called before the network state handler is updated.
+    pub(crate) fn gains_preferred_network(&self) -> bool {
+        let was_other = matches!(
+            self.state_handler.network(),
+            NetworkState::Online(info) if info.network_type != NetworkType::Wifi
+        );
+        was_other
+            && matches!(
+                NetworkManager::query_network(),
+                NetworkState::Online(info) if info.network_type == NetworkType::Wifi
+            )
+    }
+
+    /// Moves running tasks to Wi-Fi if the device is on it, by restarting
+    /// them.
+    ///
+    /// Tasks started on another network keep running there unless they
+    /// resume from the bytes they have, see `RunningQueue::migrate`.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the migrated tasks.
+    pub(crate) fn migrate_to_preferred_network(&mut self) -> Vec<u32> {
+        match NetworkManager::query_network() {
+            NetworkState::Online(info) if info.network_type == NetworkType::Wifi => {
+                self.running_queue.migrate(&info)
+            }
+            _ => vec![],
+        }
+    }
+
     /// Shuts down the scheduler and running queue.
     ///
     /// This method properly cleans up resources and stops all running tasks.
@@ -874,6 +1316,10 @@ impl RequestDb {
                 return Err(ErrorCode::TaskStateErr);
             }
         }
+        // A removed task cannot be restored, so it does not enter the trash.
+        if old_state == State::Removed.repr && new_state == State::PendingRemoval {
+            return Err(ErrorCode::TaskNotFound);
+        }
         
         // Generate appropriate SQL for the state change
         let sql = match new_state {
@@ -881,6 +1327,7 @@ impl RequestDb {
             State::Running => sql::start_task(task_id),
             State::Stopped => sql::stop_task(task_id),
             State::Removed => sql::remove_task(task_id),
+            State::PendingRemoval => sql::pend_removal(task_id, get_current_timestamp()),
             State::Waiting => sql::start_task(task_id),
             _ => return Err(ErrorCode::Other),
         };
@@ -897,12 +1344,20 @@ impl RequestDb {
         if info.progress.common_data.state != new_state.repr {
             return Err(ErrorCode::TaskStateErr);
         }
+        task_debug!(
+            TaskLog::load(task_id),
+            "state {:#x} -> {:#x}",
+            old_state,
+            new_state.repr
+        );
 
         // Unregister notifications for certain state transitions
         if (old_state == State::Initialized.repr
             || old_state == State::Waiting.repr
             || old_state == State::Paused.repr)
-            && (new_state == State::Stopped || new_state == State::Removed)
+            && (new_state == State::Stopped
+                || new_state == State::Removed
+                || new_state == State::PendingRemoval)
         {
             NotificationDispatcher::get_instance().unregister_task(info.uid(), task_id, true);
         }
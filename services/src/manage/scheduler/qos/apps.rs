@@ -93,7 +93,7 @@ impl SortedApps {
             uid,
             task_id: task.task_id,
             mode: Mode::from(task.mode),
-            action: Action::from(task.action),
+            action: Action::from(task.action).queue(),
             priority: task.priority,
         };
 
@@ -313,6 +313,11 @@ impl Task {
         self.action
     }
 
+    /// Returns the task's execution mode.
+    pub(crate) fn mode(&self) -> Mode {
+        self.mode
+    }
+
     /// Updates the task's execution mode.
     ///
     /// # Arguments
@@ -419,7 +424,7 @@ fn reload_tasks_of_app_from_database(uid: u64) -> Vec<Task> {
             uid,
             task_id: info.task_id,
             mode: Mode::from(info.mode),
-            action: Action::from(info.action),
+            action: Action::from(info.action).queue(),
             priority: info.priority,
         })
         .collect()
@@ -51,6 +51,9 @@ pub(crate) struct QosDirection {
     task_id: u32,
     /// The new QoS level to apply to the task.
     direction: QosLevel,
+    /// The speed limit to apply to the task in bytes per second, 0 means
+    /// unlimited.
+    speed: u64,
 }
 
 impl QosDirection {
@@ -69,6 +72,18 @@ impl QosDirection {
         self.direction
     }
 
+    /// Returns the speed limit to apply to the task in bytes per second.
+    ///
+    /// This is the speed of the QoS level unless a global speed cap lowered it.
+    pub(crate) fn speed(&self) -> u64 {
+        self.speed
+    }
+
+    /// Lowers the speed limit of the task to its share of a global speed cap.
+    pub(crate) fn limit_speed(&mut self, speed: u64) {
+        self.speed = speed;
+    }
+
     /// Creates a new `QosDirection` instance.
     ///
     /// # Arguments
@@ -81,6 +96,7 @@ impl QosDirection {
             uid,
             task_id,
             direction,
+            speed: direction as u64,
         }
     }
 }
@@ -25,6 +25,9 @@ mod apps;
 mod direction;
 mod rss;
 
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
 use apps::SortedApps;
 pub(crate) use direction::{QosChanges, QosDirection, QosLevel};
 pub(crate) use rss::RssCapacity;
@@ -34,6 +37,25 @@ use crate::config::Mode;
 use crate::manage::database::TaskQosInfo;
 use crate::task::config::Action;
 
+/// How long a task stays boosted after the user last looked at it.
+pub(crate) const ATTENTION_BOOST_DURATION: Duration = Duration::from_secs(5 * 60);
+
+/// Share of the global speed cap a task gets per QoS level, relative to the
+/// other scheduled tasks.
+const fn cap_weight(level: QosLevel) -> u64 {
+    match level {
+        QosLevel::High => 4,
+        QosLevel::Middle => 2,
+        QosLevel::Low => 1,
+    }
+}
+
+/// Factor applied to the cap weight of tasks of foreground applications.
+const FOREGROUND_CAP_FACTOR: u64 = 2;
+
+/// Number of M1 slots reserved for foreground tasks by default.
+const DEFAULT_FOREGROUND_RESERVE: usize = 2;
+
 /// Main QoS scheduler that manages task prioritization and resource allocation.
 ///
 /// This struct coordinates the scheduling of network tasks across applications,
@@ -44,6 +66,13 @@ pub(crate) struct Qos {
     pub(crate) apps: SortedApps,
     /// Current RSS memory capacity level that determines task allocation limits.
     capacity: RssCapacity,
+    /// Tasks the user is watching, mapped to the instant their boost expires.
+    boosts: HashMap<u32, Instant>,
+    /// Total speed in bytes per second shared by all scheduled tasks, 0 means
+    /// unlimited.
+    global_cap: u64,
+    /// Number of M1 slots taken by foreground tasks before any other task.
+    foreground_reserve: usize,
 }
 
 impl Qos {
@@ -55,6 +84,9 @@ impl Qos {
         Self {
             apps: SortedApps::init(),
             capacity: RssCapacity::LEVEL0,
+            boosts: HashMap::new(),
+            global_cap: 0,
+            foreground_reserve: DEFAULT_FOREGROUND_RESERVE,
         }
     }
 
@@ -86,9 +118,38 @@ impl Qos {
     ///
     /// `true` if the task was found and removed, `false` otherwise.
     pub(crate) fn remove_task(&mut self, uid: u64, task_id: u32) -> bool {
+        self.boosts.remove(&task_id);
         self.apps.remove_task(uid, task_id)
     }
 
+    /// Records whether the user is currently paying attention to a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the task the user interacted with.
+    /// * `attention` - `true` when the notification is expanded, `false` when
+    ///   it is collapsed.
+    /// * `now` - The instant of the interaction.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the boost state changed and a reschedule is needed.
+    ///
+    /// # Notes
+    ///
+    /// An expanded notification boosts the task into the M1 tier until
+    /// `ATTENTION_BOOST_DURATION` after the latest interaction. Boost state is
+    /// kept in memory only.
+    pub(crate) fn user_attention(&mut self, task_id: u32, attention: bool, now: Instant) -> bool {
+        if attention {
+            self.boosts
+                .insert(task_id, now + ATTENTION_BOOST_DURATION)
+                .is_none()
+        } else {
+            self.boosts.remove(&task_id).is_some()
+        }
+    }
+
     /// Reloads all tasks from the database into the QoS scheduler.
     ///
     /// This method refreshes the entire task collection, updating the scheduling state
@@ -106,6 +167,46 @@ impl Qos {
         self.capacity = rss;
     }
 
+    /// Updates the total speed shared by all scheduled tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `cap` - The global speed cap in bytes per second, 0 removes the cap.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the cap changed and a reschedule is needed.
+    pub(crate) fn set_global_cap(&mut self, cap: u64) -> bool {
+        if self.global_cap == cap {
+            return false;
+        }
+        self.global_cap = cap;
+        true
+    }
+
+    /// Updates the number of M1 slots reserved for foreground tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `reserve` - The number of reserved slots, 0 disables the reservation.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the reservation changed and a reschedule is needed.
+    ///
+    /// # Notes
+    ///
+    /// Reserved slots are taken after boosted tasks and never exceed the M1
+    /// tier of the current capacity. Whoever they displace falls off the end
+    /// of the tiers and is demoted or stopped as usual.
+    pub(crate) fn set_foreground_reserve(&mut self, reserve: usize) -> bool {
+        if self.foreground_reserve == reserve {
+            return false;
+        }
+        self.foreground_reserve = reserve;
+        true
+    }
+
     /// Changes the execution mode of a specific task.
     ///
     /// # Arguments
@@ -131,6 +232,13 @@ impl Qos {
     ///
     /// A `QosChanges` object containing the updated QoS directions for both download and upload tasks.
     pub(crate) fn reschedule(&mut self, state: &state::Handler) -> QosChanges {
+        self.reschedule_at(state, Instant::now())
+    }
+
+    /// Reschedules all tasks as of `now`, dropping attention boosts that have
+    /// expired by then.
+    fn reschedule_at(&mut self, state: &state::Handler, now: Instant) -> QosChanges {
+        self.boosts.retain(|_, expiry| *expiry > now);
         // Only sort apps before assigning priorities
         self.apps
             .sort(state.foreground_abilities(), state.top_user());
@@ -138,9 +246,60 @@ impl Qos {
         // Generate QoS directions for both download and upload tasks separately
         changes.download = Some(self.reschedule_inner(Action::Download));
         changes.upload = Some(self.reschedule_inner(Action::Upload));
+        if self.global_cap != 0 {
+            let directions = changes
+                .download
+                .iter_mut()
+                .chain(changes.upload.iter_mut())
+                .flatten();
+            Self::distribute_cap(self.global_cap, directions, state.foreground_abilities());
+        }
         changes
     }
 
+    /// Splits the global speed cap among the scheduled tasks of both actions.
+    ///
+    /// # Notes
+    ///
+    /// Every task gets a share proportional to its cap weight, doubled for
+    /// foreground applications, but never more than the speed of its QoS
+    /// level. Whatever a task cannot use is split among the remaining tasks,
+    /// so the sum of all limits stays within the cap. A task always keeps at
+    /// least 1 B/s, since a limit of 0 would mean unlimited.
+    fn distribute_cap<'a>(
+        cap: u64,
+        directions: impl Iterator<Item = &'a mut QosDirection>,
+        foreground_abilities: &HashSet<u64>,
+    ) {
+        let mut directions: Vec<(u64, &mut QosDirection)> = directions
+            .map(|direction| {
+                let mut weight = cap_weight(direction.direction());
+                if foreground_abilities.contains(&direction.uid()) {
+                    weight *= FOREGROUND_CAP_FACTOR;
+                }
+                (weight, direction)
+            })
+            .collect();
+
+        // Tasks whose level caps them lowest relative to their weight are
+        // served first so their leftover goes to the others.
+        let level_limit = |direction: &QosDirection| match direction.speed() {
+            0 => u64::MAX,
+            speed => speed,
+        };
+        directions.sort_by_key(|(weight, direction)| level_limit(direction) / weight);
+
+        let mut remaining = cap;
+        let mut total_weight: u64 = directions.iter().map(|(weight, _)| weight).sum();
+        for (weight, direction) in directions {
+            let share = (remaining as u128 * weight as u128 / total_weight as u128) as u64;
+            let speed = share.min(level_limit(direction)).max(1);
+            direction.limit_speed(speed);
+            remaining = remaining.saturating_sub(speed);
+            total_weight -= weight;
+        }
+    }
+
     /// Inner method that handles the core scheduling algorithm for a specific action type.
     ///
     /// # Arguments
@@ -171,6 +330,40 @@ impl Qos {
 
         let mut qos_vec = Vec::new();
 
+        // Boosted tasks take M1 slots first; whoever falls off the end of the
+        // tiers is demoted as usual.
+        let mut placed = HashSet::new();
+        for task in self.apps.iter().flat_map(|app| app.tasks.iter()) {
+            if count == m1 {
+                break;
+            }
+            if task.action() != action || !self.boosts.contains_key(&task.task_id()) {
+                continue;
+            }
+            qos_vec.push(QosDirection::new(task.uid(), task.task_id(), m1_speed));
+            placed.insert(task.task_id());
+            count += 1;
+        }
+
+        // Foreground tasks take the reserved M1 slots next, so a task moved to
+        // the foreground never queues behind the tasks of other applications.
+        let mut reserved = 0;
+        for task in self.apps.iter().flat_map(|app| app.tasks.iter()) {
+            if count == m1 || reserved == self.foreground_reserve {
+                break;
+            }
+            if task.action() != action
+                || task.mode() != Mode::FrontEnd
+                || placed.contains(&task.task_id())
+            {
+                continue;
+            }
+            qos_vec.push(QosDirection::new(task.uid(), task.task_id(), m1_speed));
+            placed.insert(task.task_id());
+            reserved += 1;
+            count += 1;
+        }
+
         // First pass: Assign highest priority (M1) and second priority (M2) tasks
         // Iterate through all tasks in sorted order by application
         for (i, task) in self.apps.iter().enumerate().flat_map(|(i, app)| {
@@ -180,8 +373,8 @@ impl Qos {
             }
             app.tasks.iter().enumerate()
         }) {
-            // Skip tasks that don't match the current action type
-            if task.action() != action {
+            // Skip tasks that don't match the current action type or were placed
+            if task.action() != action || placed.contains(&task.task_id()) {
                 continue;
             }
             
@@ -222,8 +415,8 @@ impl Qos {
                     None => continue,
                 };
 
-                // Skip tasks that don't match the current action type
-                if task.action() != action {
+                // Skip tasks that don't match the current action type or were placed
+                if task.action() != action || placed.contains(&task.task_id()) {
                     continue;
                 }
 
@@ -253,8 +446,8 @@ impl Qos {
             .take(1)
             .flat_map(|app| app.tasks.iter().skip(task_i + 1))
         {
-            // Skip tasks that don't match the current action type
-            if task.action() != action {
+            // Skip tasks that don't match the current action type or were placed
+            if task.action() != action || placed.contains(&task.task_id()) {
                 continue;
             }
 
@@ -269,3 +462,9 @@ impl Qos {
         qos_vec
     }
 }
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_mod {
+    include!("../../../../tests/ut/manage/scheduler/qos/ut_mod.rs");
+}
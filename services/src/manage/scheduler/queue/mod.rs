@@ -22,6 +22,7 @@ mod running_task;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use keeper::SAKeeper;
 
@@ -34,6 +35,8 @@ use crate::config::Mode;
 use crate::error::ErrorCode;
 use crate::manage::database::RequestDb;
 use crate::manage::events::{TaskEvent, TaskManagerEvent};
+use crate::manage::network::{NetworkInfo, NetworkState, NetworkType};
+use crate::manage::network_manager::NetworkManager;
 use crate::manage::scheduler::qos::{QosChanges, QosDirection};
 use crate::manage::scheduler::queue::running_task::RunningTask;
 use crate::manage::task_manager::TaskManagerTx;
@@ -44,7 +47,10 @@ use crate::task::config::Action;
 use crate::task::info::State;
 use crate::task::reason::Reason;
 use crate::task::request_task::RequestTask;
-use crate::utils::runtime_spawn;
+use crate::utils::{get_current_timestamp, runtime_spawn};
+
+/// Longest time a soft stopped upload may spend finishing its file in flight.
+const SOFT_STOP_GRACE: Duration = Duration::from_secs(10);
 
 /// Task queue manager for running download and upload operations.
 ///
@@ -58,6 +64,8 @@ pub(crate) struct RunningQueue {
     upload_queue: HashMap<(u64, u32), Arc<RequestTask>>,
     /// Map of abort handles for running tasks, allowing cancellation.
     running_tasks: HashMap<(u64, u32), Option<AbortHandle>>,
+    /// Network each running task started on, absent if it started offline.
+    networks: HashMap<(u64, u32), NetworkType>,
     /// Service ability keeper for managing idle timeout and resource cleanup.
     keeper: SAKeeper,
     /// Transmitter for sending task management events.
@@ -95,6 +103,7 @@ impl RunningQueue {
             keeper: SAKeeper::new(tx.clone(), active_counter),
             tx,
             running_tasks: HashMap::new(),
+            networks: HashMap::new(),
             run_count_manager,
             client_manager,
             upload_resume: HashSet::new(),
@@ -142,6 +151,19 @@ impl RunningQueue {
     /// * `task_id` - Unique identifier for the task.
     pub(crate) fn task_finish(&mut self, uid: u64, task_id: u32) {
         self.running_tasks.remove(&(uid, task_id));
+        self.networks.remove(&(uid, task_id));
+    }
+
+    /// Records the network a task starts running on.
+    fn record_network(&mut self, uid: u64, task_id: u32) {
+        match NetworkManager::query_network() {
+            NetworkState::Online(info) => {
+                self.networks.insert((uid, task_id), info.network_type);
+            }
+            NetworkState::Offline => {
+                self.networks.remove(&(uid, task_id));
+            }
+        }
     }
 
     /// Attempts to restart a previously running task.
@@ -178,6 +200,7 @@ impl RunningQueue {
                 (uid, task_id),
                 Some(AbortHandle::new(abort_flag, join_handle)),
             );
+            self.record_network(uid, task_id);
             true
         } else {
             false
@@ -249,7 +272,7 @@ impl RunningQueue {
 
             if let Some(task) = queue.remove(&(uid, task_id)) {
                 // Task exists in current queue - update its speed limit and keep it running
-                task.speed_limit(qos_direction.direction() as u64);
+                task.speed_limit(qos_direction.speed());
                 new_queue.insert((uid, task_id), task);
                 continue;
             }
@@ -285,8 +308,22 @@ impl RunningQueue {
                     continue;
                 }
             };
+            // A task that already tried does not try again after its deadline.
+            if task.tries.load(Ordering::SeqCst) > 0
+                && task.conf.retry_deadline_passed(get_current_timestamp())
+            {
+                info!("task {} retry deadline passed", task_id);
+                self.tx.send_event(TaskManagerEvent::Task(TaskEvent::Failed(
+                    task_id,
+                    uid,
+                    Reason::RetryDeadlineExceeded,
+                    task.conf.common_data.mode,
+                )));
+                qos_remove_queue.push((uid, task_id));
+                continue;
+            }
             // Apply the new QoS speed limit
-            task.speed_limit(qos_direction.direction() as u64);
+            task.speed_limit(qos_direction.speed());
 
             new_queue.insert((uid, task_id), task.clone());
 
@@ -318,6 +355,7 @@ impl RunningQueue {
                 (uid, task_id),
                 Some(AbortHandle::new(abort_flag, join_handle)),
             );
+            self.record_network(uid, task_id);
         }
         // Cancel any tasks that weren't included in the new queue (no longer satisfy QoS)
         for task in queue.values() {
@@ -347,6 +385,48 @@ impl RunningQueue {
         }
     }
 
+    /// Restarts running tasks that started on another network than
+    /// `preferred`, so that their new connections use it.
+    ///
+    /// Only downloads whose config allows `preferred`, that are not bound to
+    /// a network handle and that resume from the bytes they have are
+    /// restarted.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the restarted tasks.
+    pub(crate) fn migrate(&mut self, preferred: &NetworkInfo) -> Vec<u32> {
+        let network = NetworkState::Online(preferred.clone());
+        let mut migrating: Vec<(u64, u32)> = self
+            .running_tasks
+            .iter()
+            .filter(|(key, handle)| {
+                handle.is_some()
+                    && self
+                        .networks
+                        .get(*key)
+                        .map_or(false, |started| *started != preferred.network_type)
+            })
+            .filter_map(|(key, _)| self.download_queue.get(key).map(|task| (key, task)))
+            .filter(|(_, task)| {
+                task.conf.satisfy_network(&network).is_ok()
+                    && task.conf.net_id == 0
+                    && task.is_resumable()
+            })
+            .map(|(key, _)| *key)
+            .collect();
+        migrating.sort();
+        let mut migrated = vec![];
+        for (uid, task_id) in migrating {
+            info!("task {} migrates to {:?}", task_id, preferred.network_type);
+            // The canceled run is restarted once it reports the cancellation.
+            if self.cancel_task(task_id, uid) {
+                migrated.push(task_id);
+            }
+        }
+        migrated
+    }
+
     /// Cancels a specific task by its ID and user ID.
     ///
     /// # Arguments
@@ -392,6 +472,45 @@ impl RunningQueue {
         true
     }
 
+    /// Cancels a specific task, letting an upload finish the file in flight
+    /// first.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - Unique identifier for the task to cancel.
+    /// * `uid` - User ID associated with the task.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the task was found and is canceling, `false` otherwise.
+    ///
+    /// # Notes
+    ///
+    /// The upload checks its soft stop flag before each file and ends once
+    /// it is set. If the file in flight takes longer than `SOFT_STOP_GRACE`,
+    /// the task is canceled like with [`cancel_task`](Self::cancel_task).
+    /// Downloads are canceled at once.
+    pub(crate) fn soft_cancel_task(&mut self, task_id: u32, uid: u64) -> bool {
+        let Some(task) = self.upload_queue.get(&(uid, task_id)) else {
+            return self.cancel_task(task_id, uid);
+        };
+        let Some(handle) = self
+            .running_tasks
+            .get_mut(&(uid, task_id))
+            .and_then(|task| task.take())
+        else {
+            return false;
+        };
+        info!("task {} soft stop", task_id);
+        task.soft_stop.store(true, Ordering::Release);
+        runtime_spawn(async move {
+            ylong_runtime::time::sleep(SOFT_STOP_GRACE).await;
+            // Canceling a task which already ended does nothing.
+            handle.cancel();
+        });
+        true
+    }
+
     /// Shuts down the running queue and cancels any pending service unloading.
     ///
     /// This method calls shutdown on the service ability keeper to prevent any
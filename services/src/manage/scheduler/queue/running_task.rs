@@ -20,20 +20,26 @@
 use std::ops::Deref;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::config::Mode;
 use crate::manage::database::RequestDb;
 use crate::manage::events::{TaskEvent, TaskManagerEvent};
+use crate::manage::keep_awake::{AwakeGuard, KeepAwake};
 use crate::manage::notifier::Notifier;
 use crate::manage::scheduler::queue::keeper::SAKeeper;
 use crate::manage::task_manager::TaskManagerTx;
 use crate::service::notification_bar::NotificationDispatcher;
+use crate::service::progress_brief;
 use crate::task::config::Action;
 use crate::task::download::download;
+use crate::task::loopback;
+use crate::task::progress_sampler::SamplerGuard;
 use crate::task::reason::Reason;
 use crate::task::request_task::RequestTask;
+use crate::task::speed_history;
 use crate::task::upload::upload;
-use crate::utils::get_current_duration;
+use crate::utils::{get_current_duration, get_current_timestamp};
 
 /// A task in the process of being executed.
 ///
@@ -48,6 +54,8 @@ pub(crate) struct RunningTask {
     /// while tasks are running.
     // `_keeper` is never used when executing the task.
     _keeper: SAKeeper,
+    /// Holding of the running lock for tasks keeping the device awake.
+    _awake: Option<AwakeGuard>,
 }
 
 impl RunningTask {
@@ -63,10 +71,18 @@ impl RunningTask {
     ///
     /// A new `RunningTask` ready to be executed.
     pub(crate) fn new(task: Arc<RequestTask>, tx: TaskManagerTx, keeper: SAKeeper) -> Self {
+        // A soft stop only concerns the run it was requested for.
+        task.soft_stop.store(false, Ordering::Release);
+        progress_brief::register_live(&task);
+        let awake = task
+            .conf
+            .keep_awake
+            .then(|| KeepAwake::get_instance().hold(task.task_id()));
         Self {
             task,
             tx,
             _keeper: keeper,
+            _awake: awake,
         }
     }
 
@@ -81,6 +97,18 @@ impl RunningTask {
     /// This method dispatches to either the download or upload implementation
     /// based on the task's action type. It consumes the `RunningTask` instance.
     pub(crate) async fn run(self, abort_flag: Arc<AtomicBool>) {
+        task_debug!(
+            self.log,
+            "running, try {}",
+            self.tries.load(Ordering::SeqCst)
+        );
+        {
+            let mut progress = self.task.progress.lock().unwrap();
+            self.task.record_effective_metered(&mut progress.extras);
+            self.task.record_trace_id(&mut progress.extras);
+        }
+        self.task.record_effective_config();
+        let sampler = SamplerGuard::spawn(self.task.clone());
         match self.conf.common_data.action {
             Action::Download => {
                 download(self.task.clone(), abort_flag).await;
@@ -88,8 +116,14 @@ impl RunningTask {
             Action::Upload => {
                 upload(self.task.clone(), abort_flag).await;
             }
+            Action::LocalLoopback => {
+                loopback::run(self.task.clone(), abort_flag).await;
+            }
             _ => {}
         }
+        // Terminal states see the final totals without waiting for a sample.
+        drop(sampler);
+        self.task.sync_progress();
     }
 
     /// Checks if a download task has completed.
@@ -104,7 +138,7 @@ impl RunningTask {
     /// Returns `false` if the task is not a download or if the total size
     /// is unknown or incomplete.
     fn check_download_complete(&self) -> bool {
-        if self.action() != Action::Download {
+        if self.action().queue() != Action::Download {
             return false;
         }
         let mutex_guard = self.task.progress.lock().unwrap();
@@ -185,11 +219,33 @@ impl Drop for RunningTask {
         // Notify observers of final progress
         Notifier::progress(&self.client_manager, self.build_notify_data());
         
+        // Keep the final speed window queryable after the task stopped
+        let processed = self
+            .task
+            .progress
+            .lock()
+            .unwrap()
+            .common_data
+            .total_processed as u64;
+        let samples = {
+            let mut history = self.task.speed_history.lock().unwrap();
+            history.finish(get_current_timestamp(), processed);
+            history.samples()
+        };
+        speed_history::keep_recent(self.task_id(), samples);
+        progress_brief::unregister_live(self.task_id());
+
         // Get task metadata for event reporting
         let task_id = self.task_id();
         let uid = self.uid();
         let mode = Mode::from(self.mode.load(Ordering::Acquire));
         
+        task_debug!(
+            self.log,
+            "run ended, result {:?}",
+            *self.task.running_result.lock().unwrap()
+        );
+
         // Determine and report the final task state
         match *self.task.running_result.lock().unwrap() {
             Some(res) => match res {
@@ -204,6 +260,22 @@ impl Drop for RunningTask {
                             task_id, uid, mode,
                         )));
                 }
+                // Pause tasks whose completed file could not be made durable
+                Err(e) if e == Reason::DurabilityCheckFailed => {
+                    self.tx
+                        .send_event(TaskManagerEvent::Task(TaskEvent::Paused(task_id, uid, e)));
+                }
+                // Hand the wait of rate limited tasks back to the scheduler
+                Err(e) if e == Reason::RateLimited => {
+                    let delay = self.task.retry_after.load(Ordering::SeqCst);
+                    self.tx
+                        .send_event(TaskManagerEvent::Task(TaskEvent::RateLimited(
+                            task_id,
+                            uid,
+                            mode,
+                            Duration::from_millis(delay),
+                        )));
+                }
                 // Report other failures
                 Err(e) => {
                     self.tx.send_event(TaskManagerEvent::Task(TaskEvent::Failed(
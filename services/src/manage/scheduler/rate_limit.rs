@@ -0,0 +1,134 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Waits of tasks for rate limiting servers.
+//!
+//! A rate limited task gives up its running slot while it waits for the delay
+//! the server asked for, the scheduler puts it back to the queue once the
+//! delay has elapsed.
+
+use std::time::Duration;
+
+use ylong_runtime::time::sleep;
+
+use super::Scheduler;
+use crate::manage::database::RequestDb;
+use crate::manage::events::{ScheduleEvent, TaskManagerEvent};
+use crate::manage::notifier::{notify_after_commit, Notifier};
+use crate::task::info::State;
+use crate::task::notify::WaitingCause;
+use crate::task::reason::Reason;
+use crate::task::request_task::RETRY_TIMES;
+use crate::utils::{get_current_timestamp, runtime_spawn};
+
+/// Rate limit of a task.
+#[derive(Debug, Default, Clone, Copy)]
+pub(super) struct RateLimit {
+    /// Time in milliseconds the task runs again at, 0 if it does not wait.
+    until: u64,
+    /// Number of times the task was rate limited.
+    tries: u32,
+}
+
+impl Scheduler {
+    /// Handles a task that ended its run to wait for a rate limiting server.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The user ID of the application that owns the task.
+    /// * `task_id` - The unique identifier of the task.
+    /// * `delay` - The delay the server asked the task to wait.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the task used up its retries and fails instead of waiting.
+    pub(crate) fn task_rate_limited(&mut self, uid: u64, task_id: u32, delay: Duration) -> bool {
+        self.running_queue.task_finish(uid, task_id);
+        if self.qos.remove_task(uid, task_id) {
+            self.schedule_if_not_scheduled();
+        }
+
+        // The task may have been paused or removed meanwhile
+        let database = RequestDb::get_instance();
+        if let Some(info) = database.get_task_qos_info(task_id) {
+            if info.state != State::Running.repr && info.state != State::Waiting.repr {
+                return true;
+            }
+        }
+        let limit = self.rate_limits.entry(task_id).or_default();
+        limit.tries += 1;
+        if limit.tries > RETRY_TIMES {
+            info!("task {} rate limited {} times", task_id, limit.tries);
+            return false;
+        }
+        info!(
+            "task {} waits {} ms for the rate limit",
+            task_id,
+            delay.as_millis()
+        );
+        limit.until = get_current_timestamp() + delay.as_millis() as u64;
+        let _ = notify_after_commit(
+            || database.commit_task_state(task_id, State::Waiting, Reason::RateLimited),
+            || Notifier::waiting(&self.client_manager, task_id, WaitingCause::RateLimit),
+        );
+
+        let tx = self.task_manager.clone();
+        runtime_spawn(async move {
+            sleep(delay).await;
+            let _ = tx.send_event(TaskManagerEvent::Schedule(ScheduleEvent::RateLimitElapsed(
+                task_id,
+            )));
+        });
+        true
+    }
+
+    /// Puts a rate limited task back to the queue once its delay elapsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The unique identifier of the task.
+    pub(crate) fn rate_limit_elapsed(&mut self, task_id: u32) {
+        let Some(limit) = self.rate_limits.get_mut(&task_id) else {
+            return;
+        };
+        limit.until = 0;
+
+        // The task may have been paused, removed or moved to another wait
+        let database = RequestDb::get_instance();
+        let Some(info) = database.get_task_info(task_id) else {
+            return;
+        };
+        if info.progress.common_data.state != State::Waiting.repr
+            || info.common_data.reason != Reason::RateLimited.repr
+        {
+            return;
+        }
+        database.update_task_state(task_id, State::Waiting, Reason::RunningTaskMeetLimits);
+        if !matches!(self.check_config_satisfy(task_id), Ok(true)) {
+            return;
+        }
+        if let Some(qos_info) = database.get_task_qos_info(task_id) {
+            self.qos.start_task(info.uid(), qos_info);
+            self.schedule_if_not_scheduled();
+        }
+    }
+
+    /// Returns the time in milliseconds a rate limited task runs again at,
+    /// `None` if it does not wait for a rate limiting server.
+    pub(crate) fn rate_limited_until(&self, task_id: u32) -> Option<u64> {
+        self.rate_limits
+            .get(&task_id)
+            .map(|limit| limit.until)
+            .filter(|until| *until != 0)
+    }
+}
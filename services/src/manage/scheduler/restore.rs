@@ -0,0 +1,140 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Restoring tasks interrupted by a service restart.
+//!
+//! Tasks still marked running or retrying when the service starts were cut off
+//! by the previous instance. They are put back to waiting, so the scheduler
+//! runs them again like any task waiting for its turn, while paused and
+//! stopped tasks keep their state.
+
+use std::sync::{Arc, Mutex};
+
+use crate::info::State;
+use crate::manage::database::RequestDb;
+use crate::task::config::Action;
+use crate::task::files::open_download_file;
+use crate::task::reason::Reason;
+
+/// Puts every task interrupted by the previous service instance back to
+/// waiting.
+pub(crate) fn restore_interrupted_tasks() {
+    let db = RequestDb::get_instance();
+    let task_ids = db.query_interrupted_tasks();
+    if !task_ids.is_empty() {
+        info!("restore {} interrupted tasks", task_ids.len());
+    }
+    for task_id in task_ids {
+        restore_task(db, task_id);
+    }
+    // The timers of rate limited tasks ended with the previous service instance
+    let sql = format!(
+        "UPDATE request_task SET reason = {} WHERE state = {} AND reason = {}",
+        Reason::RunningTaskMeetLimits.repr,
+        State::Waiting.repr,
+        Reason::RateLimited.repr,
+    );
+    if let Err(e) = db.execute(&sql) {
+        error!("restore rate limited tasks failed {:?}", e);
+    }
+}
+
+/// Puts an interrupted task back to waiting and reconciles its file.
+///
+/// Bytes written after the last persisted progress may be torn, so the file of
+/// a download is cut back to the persisted offset and the rest is downloaded
+/// again on resume.
+pub(crate) fn restore_task(db: &RequestDb, task_id: u32) {
+    let sql = format!(
+        "UPDATE request_task SET state = {}, reason = {} WHERE task_id = {} AND (state = {} OR state = {})",
+        State::Waiting.repr,
+        Reason::RunningTaskMeetLimits.repr,
+        task_id,
+        State::Running.repr,
+        State::Retrying.repr,
+    );
+    if let Err(e) = db.execute(&sql) {
+        error!("restore task {} failed {:?}", task_id, e);
+        return;
+    }
+    reconcile_download_file(db, task_id);
+}
+
+/// Truncates the file of a download to the progress persisted in the database.
+fn reconcile_download_file(db: &RequestDb, task_id: u32) {
+    let Some(persisted) = db.query_task_total_processed(task_id) else {
+        return;
+    };
+    // Files given by descriptor are only reachable while their task is in memory
+    let file = match db.get_user_file_task(task_id) {
+        Some(task) if task.action().queue() == Action::Download => match task.files.get(0) {
+            Some(file) => file,
+            None => return,
+        },
+        Some(_) => return,
+        None => {
+            let Some(config) = db.get_task_config(task_id) else {
+                return;
+            };
+            if config.common_data.action.queue() != Action::Download {
+                return;
+            }
+            match open_download_file(&config) {
+                Ok(file) => Arc::new(Mutex::new(file)),
+                Err(e) => {
+                    error!("restore task {} open file failed {:?}", task_id, e);
+                    return;
+                }
+            }
+        }
+    };
+
+    let file = file.lock().unwrap();
+    let len = match file.metadata() {
+        Ok(metadata) => metadata.len(),
+        Err(e) => {
+            error!("restore task {} file metadata failed {:?}", task_id, e);
+            return;
+        }
+    };
+    let persisted = persisted.max(0) as u64;
+    if len <= persisted {
+        return;
+    }
+    info!(
+        "restore task {} truncate file from {} to {}",
+        task_id, len, persisted
+    );
+    if let Err(e) = file.set_len(persisted) {
+        error!("restore task {} truncate file failed {:?}", task_id, e);
+    }
+}
+
+impl RequestDb {
+    /// Returns the tasks left running or retrying by the previous service
+    /// instance.
+    fn query_interrupted_tasks(&self) -> Vec<u32> {
+        let sql = format!(
+            "SELECT task_id FROM request_task WHERE state = {} OR state = {}",
+            State::Running.repr,
+            State::Retrying.repr,
+        );
+        self.query_integer(&sql)
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_restore {
+    include!("../../../tests/ut/manage/scheduler/ut_restore.rs");
+}
@@ -140,6 +140,68 @@ pub(super) fn remove_task(task_id: u32) -> String {
     )
 }
 
+/// Generates SQL to move a task to the trash.
+///
+/// # Arguments
+///
+/// * `task_id` - The unique identifier of the task to remove.
+/// * `mtime` - Time of the removal, from which the grace period runs.
+///
+/// # Returns
+///
+/// A SQL UPDATE statement that changes the task state to `PendingRemoval`
+/// unless the task is already removed.
+pub(super) fn pend_removal(task_id: u32, mtime: u64) -> String {
+    format!(
+        "UPDATE request_task SET state = {}, reason = {}, mtime = {} where task_id = {} AND state != {}",
+        State::PendingRemoval.repr,
+        Reason::UserOperation.repr,
+        mtime,
+        task_id,
+        State::Removed.repr,
+    )
+}
+
+/// Generates SQL to restore a task pending removal as paused.
+///
+/// # Arguments
+///
+/// * `task_id` - The unique identifier of the task to restore.
+///
+/// # Returns
+///
+/// A SQL UPDATE statement that changes the task state from `PendingRemoval`
+/// to `Paused`.
+pub(super) fn undo_remove(task_id: u32) -> String {
+    format!(
+        "UPDATE request_task SET state = {}, reason = {} where task_id = {} AND state = {}",
+        State::Paused.repr,
+        Reason::UserOperation.repr,
+        task_id,
+        State::PendingRemoval.repr,
+    )
+}
+
+/// Generates SQL to finally remove a task pending removal.
+///
+/// # Arguments
+///
+/// * `task_id` - The unique identifier of the task to remove.
+///
+/// # Returns
+///
+/// A SQL UPDATE statement that changes the task state from `PendingRemoval`
+/// to `Removed`.
+pub(super) fn finalize_removal(task_id: u32) -> String {
+    format!(
+        "UPDATE request_task SET state = {}, reason = {} where task_id = {} AND state = {}",
+        State::Removed.repr,
+        Reason::UserOperation.repr,
+        task_id,
+        State::PendingRemoval.repr,
+    )
+}
+
 /// Generates SQL to update a task's operation mode.
 ///
 /// # Arguments
@@ -31,7 +31,7 @@ use crate::manage::task_manager::TaskManagerTx;
 use crate::utils::runtime_spawn;
 #[cfg(feature = "oh")]
 #[cfg(not(test))]
-use crate::utils::GetForegroundAbilities;
+use crate::utils::{is_charging, thermal_level, GetForegroundAbilities};
 
 mod recorder;
 pub(crate) mod sql;
@@ -98,6 +98,8 @@ impl Handler {
                     break;
                 }
             }
+            self.recorder.thermal_level = thermal_level();
+            self.recorder.charging = is_charging();
         }
         info!("foreground_abilities: {:?}", foreground_abilities);
         // Convert to Option<HashSet> for recorder
@@ -164,6 +166,61 @@ impl Handler {
             .update_accounts(foreground_account, active_accounts)
     }
 
+    /// Updates the charging state of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `charging` - Whether the device is charging.
+    ///
+    /// # Returns
+    ///
+    /// SQL statements to update the database if the charging state changed.
+    pub(crate) fn update_charging(&mut self, charging: bool) -> Option<SqlList> {
+        self.recorder.update_charging(charging)
+    }
+
+    /// Updates the thermal level of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The new thermal level.
+    ///
+    /// # Returns
+    ///
+    /// SQL statements to update the database if tasks start or stop waiting
+    /// for the device to cool down.
+    pub(crate) fn update_thermal_level(&mut self, level: u8) -> Option<SqlList> {
+        self.recorder.update_thermal_level(level)
+    }
+
+    /// Updates whether background tasks may only run while charging.
+    ///
+    /// # Arguments
+    ///
+    /// * `require_charging` - Whether charging is required.
+    ///
+    /// # Returns
+    ///
+    /// SQL statements to update the database if background tasks start or
+    /// stop waiting for charging.
+    pub(crate) fn update_require_charging(&mut self, require_charging: bool) -> Option<SqlList> {
+        self.recorder.update_require_charging(require_charging)
+    }
+
+    /// Updates whether a captive portal intercepts the traffic of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `captive_portal` - Whether a captive portal is detected.
+    ///
+    /// # Returns
+    ///
+    /// SQL statements to update the database if tasks waiting for the portal
+    /// may run again.
+    pub(crate) fn update_captive_portal(&mut self, captive_portal: bool) -> Option<SqlList> {
+        self.recorder.update_captive_portal(captive_portal)
+    }
+
     /// Updates the top (foreground) UID.
     ///
     /// # Arguments
@@ -284,4 +341,40 @@ impl Handler {
     pub(crate) fn network(&self) -> &NetworkState {
         &self.recorder.network
     }
+
+    /// Checks if background tasks currently wait for the device to charge.
+    ///
+    /// # Returns
+    ///
+    /// `true` if charging is required and the device is not charging.
+    pub(crate) fn charging_gated(&self) -> bool {
+        self.recorder.charging_gated()
+    }
+
+    /// Gets the current thermal level of the device.
+    ///
+    /// # Returns
+    ///
+    /// The last thermal level reported by the system.
+    pub(crate) fn thermal_level(&self) -> u8 {
+        self.recorder.thermal_level
+    }
+
+    /// Checks if the device is charging.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the device was last reported as charging.
+    pub(crate) fn charging(&self) -> bool {
+        self.recorder.charging
+    }
+
+    /// Checks if a captive portal is known to intercept the traffic.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the last captive portal probe detected a portal.
+    pub(crate) fn captive_portal(&self) -> bool {
+        self.recorder.captive_portal
+    }
 }
@@ -36,6 +36,14 @@ pub(super) struct StateRecord {
     pub(super) active_accounts: HashSet<u64>,
     /// Current Resource Scheduling Service level.
     pub(super) rss_level: i32,
+    /// Whether background tasks may only run while the device is charging.
+    pub(super) require_charging: bool,
+    /// Whether the device is charging.
+    pub(super) charging: bool,
+    /// Current thermal level of the device.
+    pub(super) thermal_level: u8,
+    /// Whether a captive portal intercepts the traffic of the device.
+    pub(super) captive_portal: bool,
 }
 
 impl StateRecord {
@@ -51,6 +59,10 @@ impl StateRecord {
             network: NetworkState::Offline,
             active_accounts: HashSet::new(),
             rss_level: 0,
+            require_charging: false,
+            charging: true,
+            thermal_level: 0,
+            captive_portal: false,
         }
     }
 
@@ -78,6 +90,8 @@ impl StateRecord {
         sql_list.add_network_change(&network);
         // Add account change SQL statement
         sql_list.add_account_change(&active_accounts);
+        // Add device condition SQL statement
+        sql_list.add_device_condition_change(self.thermal_level, self.charging);
         
         // Process foreground applications if available
         if let Some(foreground_abilities) = foreground_abilities {
@@ -167,6 +181,126 @@ impl StateRecord {
         Some(sql_list)
     }
 
+    /// Checks if background tasks currently wait for the device to charge.
+    pub(crate) fn charging_gated(&self) -> bool {
+        self.require_charging && !self.charging
+    }
+
+    /// Updates the charging state of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `charging` - Whether the device is charging.
+    ///
+    /// # Returns
+    ///
+    /// SQL statements to update the database if the charging state changed,
+    /// or `None` if no change.
+    pub(crate) fn update_charging(&mut self, charging: bool) -> Option<SqlList> {
+        if charging == self.charging {
+            return None;
+        }
+        let gated = self.charging_gated();
+        self.charging = charging;
+        Some(self.device_change(gated))
+    }
+
+    /// Updates the thermal level of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `thermal_level` - The new thermal level.
+    ///
+    /// # Returns
+    ///
+    /// SQL statements to update the database if the thermal level changed,
+    /// or `None` if no change.
+    pub(crate) fn update_thermal_level(&mut self, thermal_level: u8) -> Option<SqlList> {
+        if thermal_level == self.thermal_level {
+            return None;
+        }
+        info!("update thermal level to {}", thermal_level);
+        self.thermal_level = thermal_level;
+        Some(self.device_change(self.charging_gated()))
+    }
+
+    /// Updates whether background tasks may only run while charging.
+    ///
+    /// # Arguments
+    ///
+    /// * `require_charging` - Whether charging is required.
+    ///
+    /// # Returns
+    ///
+    /// SQL statements to update the database if background tasks start or
+    /// stop waiting for charging, or `None` if no change.
+    pub(crate) fn update_require_charging(&mut self, require_charging: bool) -> Option<SqlList> {
+        let gated = self.charging_gated();
+        self.require_charging = require_charging;
+        self.charging_change(gated)
+    }
+
+    fn charging_change(&self, was_gated: bool) -> Option<SqlList> {
+        let gated = self.charging_gated();
+        if gated == was_gated {
+            return None;
+        }
+
+        info!("background tasks wait for charging: {}", gated);
+        let mut sql_list = SqlList::new();
+        if !gated {
+            // Statements run in reverse order, so the released tasks are
+            // checked against the network and accounts again afterwards.
+            sql_list.add_network_change(&self.network);
+            sql_list.add_account_change(&self.active_accounts);
+        }
+        sql_list.add_charging_change(gated);
+        Some(sql_list)
+    }
+
+    fn device_change(&self, was_gated: bool) -> SqlList {
+        let mut sql_list = SqlList::new();
+        // Statements run in reverse order, so the released tasks are checked
+        // against the network and accounts again afterwards.
+        sql_list.add_network_change(&self.network);
+        sql_list.add_account_change(&self.active_accounts);
+        let gated = self.charging_gated();
+        if gated != was_gated {
+            info!("background tasks wait for charging: {}", gated);
+            sql_list.add_charging_change(gated);
+        }
+        sql_list.add_device_condition_change(self.thermal_level, self.charging);
+        sql_list
+    }
+
+    /// Updates whether a captive portal intercepts the traffic of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `captive_portal` - Whether a captive portal is detected.
+    ///
+    /// # Returns
+    ///
+    /// SQL statements returning tasks waiting for the portal to the queue once
+    /// it is gone, or `None` if there is nothing to update.
+    pub(crate) fn update_captive_portal(&mut self, captive_portal: bool) -> Option<SqlList> {
+        if captive_portal == self.captive_portal {
+            return None;
+        }
+        info!("captive portal detected: {}", captive_portal);
+        self.captive_portal = captive_portal;
+        if captive_portal {
+            return None;
+        }
+        let mut sql_list = SqlList::new();
+        // Statements run in reverse order, so the released tasks are checked
+        // against the network and accounts again afterwards.
+        sql_list.add_network_change(&self.network);
+        sql_list.add_account_change(&self.active_accounts);
+        sql_list.add_captive_portal_cleared();
+        Some(sql_list)
+    }
+
     /// Updates the top (foreground) UID.
     ///
     /// # Arguments
@@ -19,7 +19,7 @@
 
 use std::collections::HashSet;
 
-use crate::config::{Action, Mode, Version};
+use crate::config::{Action, MeteredOverride, Mode, Version};
 use crate::info::State;
 use crate::manage::network::{NetworkInfo, NetworkState, NetworkType};
 use crate::task::reason::Reason;
@@ -43,10 +43,14 @@ const NETWORK_APP: u8 = Reason::NetworkApp.repr;
 const NETWORK_ACCOUNT: u8 = Reason::NetworkAccount.repr;
 const APP_ACCOUNT: u8 = Reason::AppAccount.repr;
 const NETWORK_APP_ACCOUNT: u8 = Reason::NetworkAppAccount.repr;
+const NOT_CHARGING: u8 = Reason::NotCharging.repr;
+const CAPTIVE_PORTAL: u8 = Reason::CaptivePortal.repr;
+const DEVICE_CONDITION: u8 = Reason::DeviceCondition.repr;
 
 // Action constants for SQL statements
 const DOWNLOAD: u8 = Action::Download.repr;
 const UPLOAD: u8 = Action::Upload.repr;
+const LOOPBACK: u8 = Action::LocalLoopback.repr;
 
 // Mode constants for SQL statements
 const BACKGROUND: u8 = Mode::BackGround.repr;
@@ -127,6 +131,40 @@ impl SqlList {
         self.sqls.push(app_state_unavailable(uid));
     }
 
+    /// Adds SQL statements for the charging requirement of background tasks
+    /// coming into or out of effect.
+    ///
+    /// # Arguments
+    ///
+    /// * `gated` - Whether background tasks must wait for charging.
+    pub(crate) fn add_charging_change(&mut self, gated: bool) {
+        if gated {
+            self.sqls.push(charging_unavailable());
+        } else {
+            self.sqls.push(charging_available());
+        }
+    }
+
+    /// Adds SQL statements for tasks that wait for the device to cool down
+    /// or to charge.
+    ///
+    /// # Arguments
+    ///
+    /// * `thermal_level` - The current thermal level of the device.
+    /// * `charging` - Whether the device is charging.
+    pub(crate) fn add_device_condition_change(&mut self, thermal_level: u8, charging: bool) {
+        self.sqls
+            .push(device_condition_available(thermal_level, charging));
+        self.sqls
+            .push(device_condition_unavailable(thermal_level, charging));
+    }
+
+    /// Adds SQL statement for a captive portal that no longer intercepts the
+    /// traffic of the device.
+    pub(crate) fn add_captive_portal_cleared(&mut self) {
+        self.sqls.push(captive_portal_cleared());
+    }
+
     /// Adds SQL statement for application uninstallation.
     ///
     /// # Arguments
@@ -185,14 +223,14 @@ pub(crate) fn app_uninstall(uid: u64) -> String {
 /// # Returns
 ///
 /// SQL statement to update task states and reasons based on application unavailability.
-/// - Downloads are set to waiting state
+/// - Downloads and loopback tasks are set to waiting state
 /// - Uploads are set to failed state
 /// - Existing waiting tasks have their reasons combined with app background reason
 pub(crate) fn app_state_unavailable(uid: u64) -> String {
     format!(
         "UPDATE request_task SET 
             state = CASE
-                WHEN (state = {RUNNING} OR state = {RETRYING}) AND action = {DOWNLOAD} THEN {WAITING}
+                WHEN (state = {RUNNING} OR state = {RETRYING}) AND action IN ({DOWNLOAD}, {LOOPBACK}) THEN {WAITING}
                 WHEN (state = {RUNNING} OR state = {RETRYING}) AND action = {UPLOAD} THEN {FAILED}
                 ELSE state
             END,
@@ -352,6 +390,19 @@ pub(super) fn network_offline() -> String {
     )
 }
 
+/// SQL condition matching tasks that treat the current network as metered.
+///
+/// A task's `metered_override` takes precedence over the reported flag.
+fn treated_as_metered(info: &NetworkInfo) -> String {
+    let metered = MeteredOverride::Metered as u8;
+    let unmetered = MeteredOverride::Unmetered as u8;
+    if info.is_metered {
+        format!("IFNULL(metered_override, 0) != {unmetered}")
+    } else {
+        format!("IFNULL(metered_override, 0) = {metered}")
+    }
+}
+
 /// Generates SQL to update task states for unsupported network conditions.
 ///
 /// # Arguments
@@ -371,10 +422,11 @@ pub(super) fn network_unavailable(info: &NetworkInfo) -> Option<String> {
     // Build condition for tasks that can't run on this network
     let mut unsupported_condition = format!("network != {}", info.network_type.repr);
     
-    // Add metered condition if current network is metered
-    if info.is_metered {
-        unsupported_condition.push_str(" OR metered = 0");
-    }
+    // Add metered condition if the network counts as metered for the task
+    unsupported_condition.push_str(&format!(
+        " OR (metered = 0 AND {})",
+        treated_as_metered(info)
+    ));
     
     // Add roaming condition if current network is roaming
     if info.is_roaming {
@@ -440,10 +492,8 @@ pub(super) fn network_available(info: &NetworkInfo) -> String {
         info.network_type.repr
     ));
     
-    // Add metered condition if current network is metered
-    if info.is_metered {
-        sql.push_str(" AND metered = 1");
-    }
+    // Add metered condition if the network counts as metered for the task
+    sql.push_str(&format!(" AND (metered = 1 OR NOT {})", treated_as_metered(info)));
     
     // Add roaming condition if current network is roaming
     if info.is_roaming {
@@ -454,6 +504,127 @@ pub(super) fn network_available(info: &NetworkInfo) -> String {
     sql
 }
 
+/// Generates SQL to update task states when background tasks must wait for
+/// charging.
+///
+/// # Returns
+///
+/// SQL statement moving running background tasks and background tasks
+/// waiting only for their turn to waiting for charging. Tasks waiting for
+/// other conditions keep their reasons.
+pub(crate) fn charging_unavailable() -> String {
+    format!(
+        "UPDATE request_task SET 
+            state = CASE
+                WHEN state = {RUNNING} OR state = {RETRYING} THEN {WAITING}
+                ELSE state
+            END,
+            reason = CASE
+                WHEN state = {RUNNING} OR state = {RETRYING} THEN {NOT_CHARGING}
+                WHEN state = {WAITING} AND reason = {RUNNING_TASK_MEET_LIMITS} THEN {NOT_CHARGING}
+                ELSE reason
+            END
+        WHERE 
+            mode = {BACKGROUND}",
+    )
+}
+
+/// Generates SQL to update task states when background tasks no longer wait
+/// for charging.
+///
+/// # Returns
+///
+/// SQL statement returning tasks waiting for charging to the queue.
+pub(crate) fn charging_available() -> String {
+    format!(
+        "UPDATE request_task SET 
+            reason = {RUNNING_TASK_MEET_LIMITS}
+        WHERE 
+            state = {WAITING} AND reason = {NOT_CHARGING}",
+    )
+}
+
+/// Builds the condition matching tasks that may not run at `thermal_level`
+/// or while the device is not charging.
+fn device_condition_blocked(thermal_level: u8, charging: bool) -> String {
+    let mut sql = format!(
+        "(pause_on_thermal_level IS NOT NULL AND pause_on_thermal_level <= {})",
+        thermal_level
+    );
+    if !charging {
+        sql.push_str(" OR IFNULL(require_charging_while_running, 0) = 1");
+    }
+    sql
+}
+
+/// Generates SQL to update task states when the device condition blocks
+/// tasks.
+///
+/// # Arguments
+///
+/// * `thermal_level` - The current thermal level of the device.
+/// * `charging` - Whether the device is charging.
+///
+/// # Returns
+///
+/// SQL statement moving running tasks and tasks waiting only for their turn
+/// to waiting for the device condition, if their configuration forbids
+/// running at `thermal_level` or without charging.
+pub(crate) fn device_condition_unavailable(thermal_level: u8, charging: bool) -> String {
+    format!(
+        "UPDATE request_task SET
+            state = CASE
+                WHEN state = {RUNNING} OR state = {RETRYING} THEN {WAITING}
+                ELSE state
+            END,
+            reason = CASE
+                WHEN state = {RUNNING} OR state = {RETRYING} THEN {DEVICE_CONDITION}
+                WHEN state = {WAITING} AND reason = {RUNNING_TASK_MEET_LIMITS} THEN {DEVICE_CONDITION}
+                ELSE reason
+            END
+        WHERE
+            {}",
+        device_condition_blocked(thermal_level, charging)
+    )
+}
+
+/// Generates SQL to update task states when the device condition no longer
+/// blocks tasks.
+///
+/// # Arguments
+///
+/// * `thermal_level` - The current thermal level of the device.
+/// * `charging` - Whether the device is charging.
+///
+/// # Returns
+///
+/// SQL statement returning tasks waiting for the device condition to the
+/// queue once their configuration allows running again.
+pub(crate) fn device_condition_available(thermal_level: u8, charging: bool) -> String {
+    format!(
+        "UPDATE request_task SET
+            reason = {RUNNING_TASK_MEET_LIMITS}
+        WHERE
+            state = {WAITING} AND reason = {DEVICE_CONDITION} AND NOT ({})",
+        device_condition_blocked(thermal_level, charging)
+    )
+}
+
+/// Generates SQL to update task states when a captive portal no longer
+/// intercepts the traffic of the device.
+///
+/// # Returns
+///
+/// SQL statement returning tasks waiting for the captive portal to the queue.
+pub(crate) fn captive_portal_cleared() -> String {
+    format!(
+        "UPDATE request_task SET
+            reason = {RUNNING_TASK_MEET_LIMITS}
+        WHERE
+            state = {WAITING} AND reason = {CAPTIVE_PORTAL}",
+    )
+}
+
 /// Generates SQL to update task states when a special process terminates.
 ///
 /// # Arguments
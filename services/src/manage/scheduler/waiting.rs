@@ -0,0 +1,154 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Inspection and re-evaluation of waiting tasks.
+//!
+//! Operators can ask why tasks are waiting, aggregated per [`WaitingCause`]
+//! from the reasons stored in the database, and force the scheduler to check
+//! again whether tasks waiting for network or app state may run now.
+
+use super::Scheduler;
+use crate::info::State;
+use crate::manage::database::RequestDb;
+use crate::task::notify::WaitingCause;
+use crate::task::reason::Reason;
+
+/// Number of waiting tasks per waiting cause.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct WaitingBreakdown {
+    /// Tasks waiting in the queue for their turn.
+    pub(crate) task_queue: u32,
+    /// Tasks waiting for a suitable network.
+    pub(crate) network: u32,
+    /// Tasks waiting for their application to be in the foreground.
+    pub(crate) app_state: u32,
+    /// Tasks waiting for their user account to be active.
+    pub(crate) user_state: u32,
+    /// Tasks waiting for the delay requested by a rate limiting server.
+    pub(crate) rate_limit: u32,
+    /// Background tasks waiting for the device to be charging.
+    pub(crate) battery: u32,
+    /// Tasks waiting for the user to sign in to a captive portal.
+    pub(crate) captive_portal: u32,
+    /// Tasks waiting for the device to cool down or to be charging.
+    pub(crate) device_condition: u32,
+}
+
+impl WaitingBreakdown {
+    fn add(&mut self, cause: WaitingCause) {
+        match cause {
+            WaitingCause::TaskQueue => self.task_queue += 1,
+            WaitingCause::Network => self.network += 1,
+            WaitingCause::AppState => self.app_state += 1,
+            WaitingCause::UserState => self.user_state += 1,
+            WaitingCause::RateLimit => self.rate_limit += 1,
+            WaitingCause::Battery => self.battery += 1,
+            WaitingCause::CaptivePortal => self.captive_portal += 1,
+            WaitingCause::DeviceCondition => self.device_condition += 1,
+        }
+    }
+}
+
+impl RequestDb {
+    /// Counts waiting tasks per waiting cause.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - Restricts the count to the tasks of one application, `None`
+    ///   counts the tasks of all applications.
+    ///
+    /// # Notes
+    ///
+    /// Tasks waiting for several conditions are counted under the first of
+    /// network, app state and user state. Unknown reasons count as queued.
+    pub(crate) fn waiting_breakdown(&self, uid: Option<u64>) -> WaitingBreakdown {
+        let mut sql = format!(
+            "SELECT reason FROM request_task WHERE state = {}",
+            State::Waiting.repr
+        );
+        if let Some(uid) = uid {
+            sql.push_str(&format!(" AND uid = {}", uid));
+        }
+        let mut breakdown = WaitingBreakdown::default();
+        for reason in self.query_integer::<u8>(&sql) {
+            breakdown.add(WaitingCause::from_reason(reason).unwrap_or(WaitingCause::TaskQueue));
+        }
+        breakdown
+    }
+
+    /// Returns the waiting tasks that are not in the scheduler's queue and do
+    /// not wait for their user account.
+    fn get_blocked_waiting_tasks(&self) -> Vec<u32> {
+        let sql = format!(
+            "SELECT task_id FROM request_task \
+             WHERE state = {} AND reason IN ({}, {}, {}, {}, {}, {})",
+            State::Waiting.repr,
+            Reason::NetworkOffline.repr,
+            Reason::UnsupportedNetworkType.repr,
+            Reason::AppBackgroundOrTerminate.repr,
+            Reason::NetworkApp.repr,
+            Reason::NotCharging.repr,
+            Reason::DeviceCondition.repr,
+        );
+        self.query_integer(&sql)
+    }
+}
+
+impl Scheduler {
+    /// Checks again whether tasks waiting for network, app state or charging
+    /// may run.
+    ///
+    /// # Returns
+    ///
+    /// The number of tasks moved into the scheduler's queue.
+    ///
+    /// # Notes
+    ///
+    /// Each task goes through `check_config_satisfy` again, which refreshes
+    /// the waiting reason of tasks that still cannot run. Tasks waiting for
+    /// their user account are left to the account state handling.
+    pub(crate) fn reevaluate_waiting(&mut self) -> usize {
+        let database = RequestDb::get_instance();
+        let mut count = 0;
+        for task_id in database.get_blocked_waiting_tasks() {
+            match self.check_config_satisfy(task_id) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    error!("reevaluate waiting task {} failed {:?}", task_id, e);
+                    continue;
+                }
+            }
+            let (Some(uid), Some(qos_info)) = (
+                database.query_task_uid(task_id),
+                database.get_task_qos_info(task_id),
+            ) else {
+                continue;
+            };
+            info!("waiting task {} unblocked", task_id);
+            database.update_task_state(task_id, State::Waiting, Reason::RunningTaskMeetLimits);
+            self.qos.start_task(uid, qos_info);
+            count += 1;
+        }
+        if count != 0 {
+            self.schedule_if_not_scheduled();
+        }
+        count
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_waiting {
+    include!("../../../tests/ut/manage/scheduler/ut_waiting.rs");
+}
@@ -0,0 +1,128 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Aggregate task counts per application and state.
+//!
+//! Settings pages show how many tasks of an application are active, waiting
+//! or finished. The counts are computed by a single grouped query served by
+//! the `(uid, state)` index instead of querying every task.
+
+use std::collections::HashMap;
+
+use crate::error::ErrorCode;
+use crate::manage::database::RequestDb;
+use crate::task::info::State;
+
+/// Number of tasks of one application per state.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct StateCounts {
+    pub(crate) initialized: u32,
+    pub(crate) waiting: u32,
+    pub(crate) running: u32,
+    pub(crate) retrying: u32,
+    pub(crate) paused: u32,
+    pub(crate) stopped: u32,
+    pub(crate) completed: u32,
+    pub(crate) failed: u32,
+    pub(crate) removed: u32,
+}
+
+impl StateCounts {
+    pub(crate) fn add(&mut self, state: u8, count: u32) {
+        let field = match State::from(state) {
+            State::Initialized => &mut self.initialized,
+            State::Waiting => &mut self.waiting,
+            State::Running => &mut self.running,
+            State::Retrying => &mut self.retrying,
+            State::Paused => &mut self.paused,
+            State::Stopped => &mut self.stopped,
+            State::Completed => &mut self.completed,
+            State::Failed => &mut self.failed,
+            // Tasks pending removal are hidden like removed ones.
+            State::Removed | State::PendingRemoval => &mut self.removed,
+            _ => {
+                error!("count by state, unknown state {}", state);
+                return;
+            }
+        };
+        *field += count;
+    }
+}
+
+/// Decides which tasks a caller may count.
+///
+/// # Arguments
+///
+/// * `all` - Whether the caller asks for the tasks of all applications.
+/// * `uid` - Application asked for, `None` for the caller's own.
+/// * `calling_uid` - Uid of the caller.
+/// * `system` - Whether the caller may use system APIs.
+///
+/// # Returns
+///
+/// The uid to restrict the count to, `None` to count all applications, or
+/// `ErrorCode::Permission` if a non-system caller asks for other tasks than
+/// its own.
+pub(crate) fn count_scope(
+    all: bool,
+    uid: Option<u64>,
+    calling_uid: u64,
+    system: bool,
+) -> Result<Option<u64>, ErrorCode> {
+    if all {
+        return if system {
+            Ok(None)
+        } else {
+            Err(ErrorCode::Permission)
+        };
+    }
+    let uid = uid.unwrap_or(calling_uid);
+    if uid != calling_uid && !system {
+        return Err(ErrorCode::Permission);
+    }
+    Ok(Some(uid))
+}
+
+impl RequestDb {
+    /// Counts tasks per application and state.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - Restricts the count to the tasks of one application, `None`
+    ///   counts the tasks of all applications.
+    pub(crate) fn count_by_state(&self, uid: Option<u64>) -> HashMap<u64, StateCounts> {
+        let mut sql = "SELECT uid, state, COUNT(*) FROM request_task".to_string();
+        if let Some(uid) = uid {
+            sql.push_str(&format!(" WHERE uid = {}", uid));
+        }
+        sql.push_str(" GROUP BY uid, state");
+
+        let mut counts: HashMap<u64, StateCounts> = HashMap::new();
+        for row in self.query_integer_rows(&sql, 3) {
+            let [uid, state, count] = row[..] else {
+                continue;
+            };
+            counts
+                .entry(uid as u64)
+                .or_default()
+                .add(state as u8, count as u32);
+        }
+        counts
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_state_counts {
+    include!("../../tests/ut/manage/ut_state_counts.rs");
+}
@@ -0,0 +1,287 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Search of task metadata across applications for system agents.
+//!
+//! The backup agent includes pending downloads of all applications in a
+//! backup, which the uid-scoped `search` can not enumerate. This search is
+//! restricted to system callers holding [`SYSTEM_SEARCH_PERMISSION`], is
+//! always paginated and reports tasks as [`TaskSummary`] whose header values
+//! and tokens are redacted. Every invocation is audited with its caller.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+
+use crate::config::Action;
+use crate::error::ErrorCode;
+use crate::manage::database::RequestDb;
+use crate::task::config::TaskConfig;
+use crate::task::info::State;
+use crate::utils::get_current_timestamp;
+
+/// Permission required to search the tasks of all applications.
+pub(crate) const SYSTEM_SEARCH_PERMISSION: &str = "ohos.permission.REQUEST_SYSTEM_SEARCH";
+/// Largest number of tasks of one page.
+pub(crate) const SYSTEM_SEARCH_PAGE_MAX: u32 = 500;
+/// Replacement of redacted header values.
+pub(crate) const REDACTED: &str = "***";
+/// Number of audit records kept for the dump command.
+const AUDIT_CAPACITY: usize = 32;
+
+static AUDITS: LazyLock<Mutex<VecDeque<SearchAudit>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(AUDIT_CAPACITY)));
+
+/// Tasks asked for by a system search.
+///
+/// Empty `states` or `actions` match any state or action. Tasks pending
+/// removal or removed are only found if their state is asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SystemSearchFilter {
+    /// Smallest uid of the applications searched.
+    pub(crate) uid_min: u64,
+    /// Largest uid of the applications searched.
+    pub(crate) uid_max: u64,
+    /// Tasks created before this time in milliseconds are skipped.
+    pub(crate) after: u64,
+    /// Tasks created after this time in milliseconds are skipped.
+    pub(crate) before: u64,
+    pub(crate) states: Vec<u8>,
+    pub(crate) actions: Vec<u8>,
+    /// Number of matching tasks skipped before the page.
+    pub(crate) offset: u32,
+    /// Number of tasks of the page, at most [`SYSTEM_SEARCH_PAGE_MAX`].
+    pub(crate) limit: u32,
+}
+
+impl Default for SystemSearchFilter {
+    fn default() -> Self {
+        Self {
+            uid_min: 0,
+            uid_max: u64::MAX,
+            after: 0,
+            before: u64::MAX,
+            states: Vec::new(),
+            actions: Vec::new(),
+            offset: 0,
+            limit: SYSTEM_SEARCH_PAGE_MAX,
+        }
+    }
+}
+
+/// Essentials of a task configuration without its secrets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TaskSummary {
+    pub(crate) task_id: u32,
+    pub(crate) uid: u64,
+    pub(crate) bundle: String,
+    pub(crate) action: u8,
+    pub(crate) mode: u8,
+    pub(crate) state: u8,
+    pub(crate) ctime: u64,
+    pub(crate) url: String,
+    pub(crate) title: String,
+    pub(crate) description: String,
+    pub(crate) method: String,
+    /// Header names, their values are replaced by [`REDACTED`].
+    pub(crate) headers: HashMap<String, String>,
+    /// Whether the task is protected by a token, the token is not reported.
+    pub(crate) has_token: bool,
+    /// Paths of the files of the task.
+    pub(crate) paths: Vec<String>,
+    pub(crate) client_tag: String,
+}
+
+impl TaskSummary {
+    pub(crate) fn new(config: &TaskConfig, state: u8, ctime: u64) -> Self {
+        Self {
+            task_id: config.common_data.task_id,
+            uid: config.common_data.uid,
+            bundle: config.bundle.clone(),
+            action: config.common_data.action.repr,
+            mode: config.common_data.mode.repr,
+            state,
+            ctime,
+            url: config.url.clone(),
+            title: config.title.clone(),
+            description: config.description.clone(),
+            method: config.method.clone(),
+            headers: config
+                .headers
+                .keys()
+                .map(|name| (name.clone(), REDACTED.to_string()))
+                .collect(),
+            // Clients send "null" for tasks without a token.
+            has_token: !config.token.is_empty() && config.token != "null",
+            paths: config
+                .file_specs
+                .iter()
+                .map(|spec| spec.path.clone())
+                .collect(),
+            client_tag: config.client_tag.clone(),
+        }
+    }
+}
+
+/// Decides whether a caller may run a system search.
+///
+/// # Arguments
+///
+/// * `system` - Whether the caller may use system APIs.
+/// * `permitted` - Whether the caller holds [`SYSTEM_SEARCH_PERMISSION`].
+/// * `limit` - Number of tasks of the asked page.
+///
+/// # Returns
+///
+/// `ErrorCode::SystemApi` for a non-system caller, `ErrorCode::Permission`
+/// without the permission and `ErrorCode::ParameterCheck` if the page is
+/// empty or larger than [`SYSTEM_SEARCH_PAGE_MAX`].
+pub(crate) fn search_access(system: bool, permitted: bool, limit: u32) -> Result<(), ErrorCode> {
+    if !system {
+        return Err(ErrorCode::SystemApi);
+    }
+    if !permitted {
+        return Err(ErrorCode::Permission);
+    }
+    if limit == 0 || limit > SYSTEM_SEARCH_PAGE_MAX {
+        return Err(ErrorCode::ParameterCheck);
+    }
+    Ok(())
+}
+
+/// Record of one system search invocation.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SearchAudit {
+    /// Time of the invocation in milliseconds.
+    pub(crate) time: u64,
+    pub(crate) calling_uid: u64,
+    pub(crate) calling_token_id: u64,
+    pub(crate) filter: SystemSearchFilter,
+    /// Result of the access check.
+    pub(crate) code: ErrorCode,
+    /// Number of tasks reported.
+    pub(crate) count: usize,
+}
+
+/// Audits a system search, whether it was granted or not.
+pub(crate) fn audit(
+    calling_uid: u64,
+    calling_token_id: u64,
+    filter: &SystemSearchFilter,
+    code: ErrorCode,
+    count: usize,
+) {
+    let record = SearchAudit {
+        time: get_current_timestamp(),
+        calling_uid,
+        calling_token_id,
+        filter: filter.clone(),
+        code,
+        count,
+    };
+    info!(
+        "system search by uid {} token {}, code {:?}, {} tasks",
+        calling_uid, calling_token_id, code, count
+    );
+    #[cfg(feature = "oh")]
+    sys_event!(
+        ExecError,
+        DfxCode::SYSTEM_SEARCH_AUDIT,
+        &format!(
+            "System search by uid {} token {}, filter {:?}, code {:?}, {} tasks",
+            calling_uid, calling_token_id, record.filter, code, count
+        )
+    );
+
+    let mut audits = AUDITS.lock().unwrap();
+    if audits.len() == AUDIT_CAPACITY {
+        audits.pop_front();
+    }
+    audits.push_back(record);
+}
+
+/// Returns the latest audit records, oldest first.
+pub(crate) fn recent_audits() -> Vec<SearchAudit> {
+    AUDITS.lock().unwrap().iter().cloned().collect()
+}
+
+impl RequestDb {
+    /// Searches the tasks of all applications matching a filter.
+    ///
+    /// Tasks are ordered by creation time and id so consecutive pages are
+    /// disjoint. The page size is capped at [`SYSTEM_SEARCH_PAGE_MAX`].
+    pub(crate) fn system_search(&self, filter: &SystemSearchFilter) -> Vec<TaskSummary> {
+        // SQLite integers are signed, larger bounds match every task.
+        let bound = |value: u64| value.min(i64::MAX as u64);
+        let mut sql = format!(
+            "SELECT task_id FROM request_task WHERE uid BETWEEN {} AND {} AND ctime BETWEEN {} AND {} ",
+            bound(filter.uid_min),
+            bound(filter.uid_max),
+            bound(filter.after),
+            bound(filter.before)
+        );
+        if filter.states.is_empty() {
+            sql.push_str(&format!(
+                "AND state NOT IN ({}, {}) ",
+                State::PendingRemoval.repr,
+                State::Removed.repr
+            ));
+        } else {
+            sql.push_str(&format!("AND state IN ({}) ", join(&filter.states)));
+        }
+        let actions: Vec<u8> = filter
+            .actions
+            .iter()
+            .copied()
+            .filter(|action| *action != Action::Any.repr)
+            .collect();
+        if !actions.is_empty() {
+            sql.push_str(&format!("AND action IN ({}) ", join(&actions)));
+        }
+        sql.push_str(&format!(
+            "ORDER BY ctime, task_id LIMIT {} OFFSET {}",
+            filter.limit.min(SYSTEM_SEARCH_PAGE_MAX),
+            filter.offset
+        ));
+
+        self.query_integer::<u32>(&sql)
+            .into_iter()
+            .filter_map(|task_id| {
+                let config = self.get_task_config(task_id)?;
+                let state = self.query_task_state(task_id)?;
+                let ctime = self
+                    .query_integer::<u64>(&format!(
+                        "SELECT ctime FROM request_task WHERE task_id = {}",
+                        task_id
+                    ))
+                    .first()
+                    .copied()
+                    .unwrap_or_default();
+                Some(TaskSummary::new(&config, state, ctime))
+            })
+            .collect()
+    }
+}
+
+fn join(values: &[u8]) -> String {
+    values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_system_search {
+    include!("../../tests/ut/manage/ut_system_search.rs");
+}
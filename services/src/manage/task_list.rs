@@ -0,0 +1,213 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Incremental change notifications for task lists.
+//!
+//! A client subscribes with a [`TaskFilter`] and receives [`TaskListDelta`]s
+//! over its UDS channel whenever tasks matching the filter are created,
+//! removed or change state. The first delta of a subscription carries all
+//! tasks currently matching as `added`.
+//!
+//! The `TaskManager` marks the watcher dirty after every event that may
+//! change tasks. Flushes are debounced so that each client receives at most
+//! one delta per [`TASK_LIST_DEBOUNCE`].
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::database::RequestDb;
+use super::query::TaskFilter;
+use crate::config::{Action, Mode};
+use crate::info::State;
+
+/// Minimum interval between two flushes of the watcher.
+pub(crate) const TASK_LIST_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Bytes of a UDS message left for delta entries after the header, the
+/// subscription id and the three entry counts.
+const DELTA_ENTRIES_MAX_SIZE: usize = 4096 - 12 - 16;
+
+/// Compact description of a task in a task list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TaskBrief {
+    pub(crate) task_id: u32,
+    pub(crate) action: Action,
+    pub(crate) mode: Mode,
+    pub(crate) state: State,
+}
+
+/// Changes of a task list since the previous delta.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TaskListDelta {
+    /// Tasks that started matching the filter.
+    pub(crate) added: Vec<TaskBrief>,
+    /// Tasks that were removed or stopped matching the filter.
+    pub(crate) removed: Vec<u32>,
+    /// Tasks still matching whose state changed.
+    pub(crate) state_changed: Vec<(u32, State)>,
+}
+
+impl TaskListDelta {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.state_changed.is_empty()
+    }
+
+    /// Splits the delta into parts each fitting into one UDS message.
+    pub(crate) fn split(self) -> Vec<TaskListDelta> {
+        let mut parts = vec![];
+        let mut part = TaskListDelta::default();
+        let mut size = 0;
+        let mut reserve = |part: &mut TaskListDelta, size: &mut usize, len: usize| {
+            if *size + len > DELTA_ENTRIES_MAX_SIZE {
+                parts.push(std::mem::take(part));
+                *size = 0;
+            }
+            *size += len;
+        };
+        for brief in self.added {
+            reserve(&mut part, &mut size, 16);
+            part.added.push(brief);
+        }
+        for task_id in self.removed {
+            reserve(&mut part, &mut size, 4);
+            part.removed.push(task_id);
+        }
+        for change in self.state_changed {
+            reserve(&mut part, &mut size, 8);
+            part.state_changed.push(change);
+        }
+        if !part.is_empty() {
+            parts.push(part);
+        }
+        parts
+    }
+}
+
+struct Subscription {
+    uid: u64,
+    filter: TaskFilter,
+    known: HashMap<u32, State>,
+}
+
+/// Task list subscriptions of all clients.
+#[derive(Default)]
+pub(crate) struct TaskListWatcher {
+    /// Subscriptions keyed by client pid and the client chosen id.
+    subscriptions: HashMap<(u64, u32), Subscription>,
+    flush_pending: bool,
+    last_flush: Option<Instant>,
+}
+
+impl TaskListWatcher {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a subscription, an existing one with the same id is replaced.
+    pub(crate) fn subscribe(&mut self, uid: u64, pid: u64, id: u32, filter: TaskFilter) {
+        info!("task list subscribe, pid {} id {}", pid, id);
+        self.subscriptions.insert(
+            (pid, id),
+            Subscription {
+                uid,
+                filter,
+                known: HashMap::new(),
+            },
+        );
+    }
+
+    /// Removes a subscription, returns `false` if it does not exist.
+    pub(crate) fn unsubscribe(&mut self, pid: u64, id: u32) -> bool {
+        info!("task list unsubscribe, pid {} id {}", pid, id);
+        self.subscriptions.remove(&(pid, id)).is_some()
+    }
+
+    /// Removes all subscriptions of a terminated process.
+    pub(crate) fn remove_process(&mut self, pid: u64) {
+        self.subscriptions.retain(|(p, _), _| *p != pid);
+    }
+
+    /// Records that tasks may have changed.
+    ///
+    /// Returns the delay after which a flush should be scheduled, or `None`
+    /// if there is nothing to watch or a flush is already scheduled.
+    pub(crate) fn mark_dirty(&mut self, now: Instant) -> Option<Duration> {
+        if self.subscriptions.is_empty() || self.flush_pending {
+            return None;
+        }
+        self.flush_pending = true;
+        let delay = match self.last_flush {
+            Some(last) => TASK_LIST_DEBOUNCE.saturating_sub(now.saturating_duration_since(last)),
+            None => Duration::ZERO,
+        };
+        Some(delay)
+    }
+
+    /// Computes the deltas of all subscriptions since their previous flush.
+    ///
+    /// Returns `(pid, id, delta)` for every subscription whose list changed.
+    pub(crate) fn flush(&mut self, now: Instant, db: &RequestDb) -> Vec<(u64, u32, TaskListDelta)> {
+        self.flush_pending = false;
+        self.last_flush = Some(now);
+        let mut deltas = vec![];
+        for (&(pid, id), subscription) in self.subscriptions.iter_mut() {
+            let delta = subscription.update(db);
+            if !delta.is_empty() {
+                deltas.push((pid, id, delta));
+            }
+        }
+        deltas
+    }
+}
+
+impl Subscription {
+    fn update(&mut self, db: &RequestDb) -> TaskListDelta {
+        let mut sql = format!(
+            "SELECT task_id, action, mode, state, priority FROM request_task WHERE uid = {} AND state != {} AND ",
+            self.uid,
+            State::Removed.repr
+        );
+        RequestDb::search_filter(&mut sql, &self.filter);
+
+        let mut delta = TaskListDelta::default();
+        let mut current = HashMap::new();
+        for info in db.get_app_task_qos_infos_inner(&sql) {
+            let state = State::from(info.state);
+            match self.known.get(&info.task_id) {
+                None => delta.added.push(TaskBrief {
+                    task_id: info.task_id,
+                    action: Action::from(info.action),
+                    mode: Mode::from(info.mode),
+                    state,
+                }),
+                Some(&old) if old != state => delta.state_changed.push((info.task_id, state)),
+                Some(_) => {}
+            }
+            current.insert(info.task_id, state);
+        }
+        delta.removed = self
+            .known
+            .keys()
+            .filter(|task_id| !current.contains_key(task_id))
+            .copied()
+            .collect();
+        self.known = current;
+        delta
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_task_list {
+    include!("../../tests/ut/manage/ut_task_list.rs");
+}
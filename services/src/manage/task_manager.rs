@@ -18,12 +18,14 @@
 //! including creation, starting, pausing, resuming, stopping, and monitoring of tasks.
 
 use std::collections::HashMap;
+use std::io;
 use std::ops::{Deref, DerefMut};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use samgr::definition::COMM_NET_CONN_MANAGER_SYS_ABILITY_ID;
 use ylong_runtime::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use ylong_runtime::sync::oneshot;
+use ylong_runtime::task::JoinHandle;
 use ylong_runtime::time::sleep;
 
 cfg_oh! {
@@ -34,22 +36,36 @@ cfg_oh! {
 use super::account::{remove_account_tasks, AccountEvent};
 use super::database::RequestDb;
 use super::events::{
-    QueryEvent, ScheduleEvent, ServiceEvent, StateEvent, TaskEvent, TaskManagerEvent,
+    max_live_tasks, QueryEvent, ScheduleEvent, ServiceEvent, StateEvent, TaskEvent,
+    TaskManagerEvent, REMOVAL_GRACE_PERIOD, REMOVAL_SWEEP_INTERVAL,
 };
 use crate::config::{Action, Mode};
 use crate::database::clear_database_part;
 use crate::error::ErrorCode;
 use crate::info::{State, TaskInfo};
 use crate::manage::app_state::AppUninstallSubscriber;
+use crate::manage::battery::{
+    ChargingSubscriber, ThermalSubscriber, CHARGING_EVENT, DISCHARGING_EVENT, THERMAL_LEVEL_EVENT,
+};
+use crate::manage::body_files::clear_stale_body_files;
+use crate::manage::maintenance::{
+    Maintenance, MaintenanceJob, MaintenanceReport, MAINTENANCE_CHECK_INTERVAL,
+    MAINTENANCE_IDLE_PERIOD,
+};
 use crate::manage::network::register_network_change;
 use crate::manage::network_manager::NetworkManager;
 use crate::manage::query::TaskFilter;
+use crate::manage::task_list::TaskListWatcher;
 use crate::manage::scheduler::state::Handler;
 use crate::manage::scheduler::Scheduler;
 use crate::service::active_counter::ActiveCounter;
 use crate::service::client::ClientManagerEntry;
 use crate::service::notification_bar::{subscribe_notification_bar, NotificationDispatcher};
 use crate::service::run_count::RunCountManagerEntry;
+use crate::service::task_token::TaskTokens;
+use crate::task::notify::NotifyData;
+use crate::task::reason::Reason;
+use crate::task::task_control::runtime_spawn_blocking;
 use crate::utils::task_event_count::{task_complete_add, task_fail_add, task_unload};
 use crate::utils::{get_current_timestamp, runtime_spawn, subscribe_common_event, update_policy};
 
@@ -80,6 +96,16 @@ pub(crate) struct TaskManager {
     pub(crate) client_manager: ClientManagerEntry,
     /// Tracks task counts per user ID (foreground, background)
     pub(crate) task_count: HashMap<u64, (usize, usize)>,
+    /// Idle tracking and cancellation of database maintenance
+    pub(crate) maintenance: Maintenance,
+    /// Task list subscriptions and their pending deltas
+    pub(crate) task_list: TaskListWatcher,
+    /// How long tasks removed with a grace period stay restorable
+    pub(crate) removal_grace: Duration,
+    /// Largest number of live tasks of one application
+    pub(crate) max_live_tasks: usize,
+    /// Channel sender used to schedule task list flushes and migrations
+    tx: TaskManagerTx,
 }
 
 impl TaskManager {
@@ -147,6 +173,24 @@ impl TaskManager {
             );
         }
 
+        for (event, charging) in [(CHARGING_EVENT, true), (DISCHARGING_EVENT, false)] {
+            if let Err(e) =
+                subscribe_common_event(vec![event], ChargingSubscriber::new(tx.clone(), charging))
+            {
+                error!("Subscribe {} event failed: {}", event, e);
+            }
+        }
+
+        if let Err(e) = subscribe_common_event(
+            vec![THERMAL_LEVEL_EVENT],
+            ThermalSubscriber::new(tx.clone()),
+        ) {
+            error!("Subscribe {} event failed: {}", THERMAL_LEVEL_EVENT, e);
+        }
+
+        // Signed task tokens get a new key at every start.
+        TaskTokens::get_instance();
+
         let task_manager = Self::new(
             tx.clone(),
             rx,
@@ -169,8 +213,14 @@ impl TaskManager {
         // be removed because the scheduler will also be rearranged in the
         // startup logic of the new task.
         runtime_spawn(restore_all_tasks(tx.clone()));
+        runtime_spawn_blocking(|| {
+            clear_stale_body_files();
+            Ok(())
+        });
 
         runtime_spawn(clear_timeout_tasks(tx.clone()));
+        runtime_spawn(check_maintenance(tx.clone()));
+        runtime_spawn(sweep_removals(tx.clone()));
         runtime_spawn(task_manager.run());
         tx
     }
@@ -196,6 +246,7 @@ impl TaskManager {
         active_counter: ActiveCounter,
     ) -> Self {
         Self {
+            maintenance: Maintenance::new(active_counter.clone(), MAINTENANCE_IDLE_PERIOD),
             scheduler: Scheduler::init(
                 tx.clone(),
                 run_count_manager,
@@ -205,6 +256,10 @@ impl TaskManager {
             rx,
             client_manager,
             task_count: HashMap::new(),
+            task_list: TaskListWatcher::new(),
+            removal_grace: REMOVAL_GRACE_PERIOD,
+            max_live_tasks: max_live_tasks(),
+            tx,
         }
     }
 
@@ -214,6 +269,7 @@ impl TaskManager {
     /// handlers based on event type.
     async fn run(mut self) {
         let db = RequestDb::get_instance();
+        db.init_revisions();
         db.clear_invalid_records();
         loop {
             let event = match self.rx.recv().await {
@@ -224,6 +280,13 @@ impl TaskManager {
                 }
             };
 
+            // Queries and flushes themselves leave the task lists unchanged.
+            let dirty = !matches!(
+                event,
+                TaskManagerEvent::Query(_)
+                    | TaskManagerEvent::Schedule(ScheduleEvent::TaskListFlush)
+            );
+
             match event {
                 TaskManagerEvent::Service(event) => self.handle_service_event(event),
                 TaskManagerEvent::State(event) => self.handle_state_event(event),
@@ -242,6 +305,10 @@ impl TaskManager {
                 TaskManagerEvent::Reschedule => self.scheduler.reschedule(),
             }
 
+            if dirty {
+                self.schedule_task_list_flush();
+            }
+
             debug!("TaskManager handles events finished");
         }
     }
@@ -274,13 +341,19 @@ impl TaskManager {
 
         match event {
             ServiceEvent::Construct(msg, tx) => {
-                let _ = tx.send(self.create(msg.config));
+                self.maintenance.interrupt();
+                let _ = tx.send(self.create_task(msg.config));
             }
             ServiceEvent::Start(uid, task_id, tx) => {
                 let _ = tx.send(self.start(uid, task_id));
             }
-            ServiceEvent::Stop(uid, task_id, tx) => {
-                let _ = tx.send(self.stop(uid, task_id));
+            ServiceEvent::Stop(uid, task_id, graceful, tx) => {
+                let ret = if graceful {
+                    self.soft_stop(uid, task_id)
+                } else {
+                    self.stop(uid, task_id)
+                };
+                let _ = tx.send(ret);
             }
             ServiceEvent::Pause(uid, task_id, tx) => {
                 let _ = tx.send(self.pause(uid, task_id));
@@ -288,12 +361,35 @@ impl TaskManager {
             ServiceEvent::Resume(uid, task_id, tx) => {
                 let _ = tx.send(self.resume(uid, task_id));
             }
-            ServiceEvent::Remove(uid, task_id, tx) => {
-                let _ = tx.send(self.remove(uid, task_id));
+            ServiceEvent::Remove(uid, task_id, grace, tx) => {
+                let ret = if grace {
+                    self.remove_with_grace(uid, task_id)
+                } else {
+                    self.remove(uid, task_id)
+                };
+                let _ = tx.send(ret);
+            }
+            ServiceEvent::UndoRemove(uid, task_id, tx) => {
+                let _ = tx.send(self.undo_remove(uid, task_id));
+            }
+            ServiceEvent::Purge(uid, task_id, tx) => {
+                let _ = tx.send(self.purge(uid, task_id));
+            }
+            ServiceEvent::SetRemovalGrace(grace, tx) => {
+                self.removal_grace = grace;
+                let _ = tx.send(ErrorCode::ErrOk);
+            }
+            ServiceEvent::SetForegroundReserve(reserve, tx) => {
+                self.scheduler.set_foreground_reserve(reserve);
+                let _ = tx.send(ErrorCode::ErrOk);
             }
             ServiceEvent::SetMaxSpeed(uid, task_id, max_speed, tx) => {
                 let _ = tx.send(self.set_max_speed(uid, task_id, max_speed));
             }
+            ServiceEvent::SetGlobalMaxSpeed(max_speed, tx) => {
+                self.scheduler.set_global_max_speed(max_speed);
+                let _ = tx.send(ErrorCode::ErrOk);
+            }
             ServiceEvent::DumpAll(tx) => {
                 let _ = tx.send(self.query_all_task());
             }
@@ -303,9 +399,88 @@ impl TaskManager {
             ServiceEvent::AttachGroup(uid, task_ids, group, tx) => {
                 let _ = tx.send(self.attach_group(uid, task_ids, group));
             }
+            ServiceEvent::QueryGroup(group_id, tx) => {
+                let _ = tx.send(self.query_group(group_id));
+            }
+            ServiceEvent::CancelGroup(uid, group_id, remove_members, tx) => {
+                let _ = tx.send(self.cancel_group(uid, group_id, remove_members));
+            }
             ServiceEvent::SetMode(uid, task_id, mode, tx) => {
                 let _ = tx.send(self.set_mode(uid, task_id, mode));
             }
+            ServiceEvent::SetDestination(uid, task_id, path, tx) => {
+                let _ = tx.send(self.set_destination(uid, task_id, &path));
+            }
+            ServiceEvent::UserAttention(task_id, attention) => {
+                self.user_attention(task_id, attention);
+            }
+            ServiceEvent::Maintain(tx) => {
+                let job = self.maintenance.start();
+                runtime_spawn(async move {
+                    if let Ok(Ok(report)) = run_maintenance(job).await {
+                        let _ = tx.send(report);
+                    }
+                });
+            }
+            ServiceEvent::WaitingBreakdown(uid, tx) => {
+                let _ = tx.send(RequestDb::get_instance().waiting_breakdown(uid));
+            }
+            ServiceEvent::CountByState(uid, tx) => {
+                // Counting all applications scans the whole index, so the
+                // query runs off the loop.
+                runtime_spawn(async move {
+                    let counts = runtime_spawn_blocking(move || {
+                        Ok(RequestDb::get_instance().count_by_state(uid))
+                    });
+                    if let Ok(Ok(counts)) = counts.await {
+                        let _ = tx.send(counts);
+                    }
+                });
+            }
+            ServiceEvent::ChangedSince(uid, revision, tx) => {
+                let _ = tx.send(RequestDb::get_instance().changed_since(uid, revision));
+            }
+            ServiceEvent::SearchSystem(filter, tx) => {
+                let _ = tx.send(RequestDb::get_instance().system_search(&filter));
+            }
+            ServiceEvent::MigrateLegacyTasks(uid, tx) => {
+                let _ = tx.send(self.migrate_legacy_tasks(uid));
+            }
+            ServiceEvent::IssueToken(uid, task_id, scope, ttl_ms, tx) => {
+                let token = match RequestDb::get_instance().query_task_uid(task_id) {
+                    Some(owner) if owner == uid => {
+                        TaskTokens::get_instance().issue(task_id, scope, ttl_ms)
+                    }
+                    _ => String::new(),
+                };
+                let _ = tx.send(token);
+            }
+            ServiceEvent::SpeedHistory(task_id, points, tx) => {
+                let _ = tx.send(self.scheduler.speed_history(task_id, points));
+            }
+            ServiceEvent::Explain(task_id, tx) => {
+                let _ = tx.send(self.scheduler.explain(task_id));
+            }
+            ServiceEvent::ReevaluateWaiting(tx) => {
+                let _ = tx.send(self.scheduler.reevaluate_waiting());
+            }
+            ServiceEvent::RequireCharging(require_charging, tx) => {
+                self.scheduler
+                    .on_state_change(Handler::update_require_charging, require_charging);
+                let _ = tx.send(ErrorCode::ErrOk);
+            }
+            ServiceEvent::SubscribeTaskList(uid, pid, id, filter, tx) => {
+                self.task_list.subscribe(uid, pid, id, filter);
+                let _ = tx.send(ErrorCode::ErrOk);
+            }
+            ServiceEvent::UnsubscribeTaskList(pid, id, tx) => {
+                let ret = if self.task_list.unsubscribe(pid, id) {
+                    ErrorCode::ErrOk
+                } else {
+                    ErrorCode::Other
+                };
+                let _ = tx.send(ret);
+            }
         }
     }
 
@@ -322,7 +497,15 @@ impl TaskManager {
 
         match event {
             StateEvent::Network => {
-                self.scheduler.retry_all_tasks();
+                // Connections on the previous network still work when Wi-Fi
+                // appears, only tasks that resume are moved to it.
+                if self.scheduler.gains_preferred_network() {
+                    self.tx.send_event(TaskManagerEvent::Schedule(
+                        ScheduleEvent::MigrateToPreferredNetwork,
+                    ));
+                } else {
+                    self.scheduler.retry_all_tasks();
+                }
                 self.scheduler.on_state_change(Handler::update_network, ());
             }
 
@@ -342,6 +525,14 @@ impl TaskManager {
                 self.scheduler
                     .on_state_change(Handler::special_process_terminate, uid);
             }
+            StateEvent::ProcessTerminate(pid) => self.task_list.remove_process(pid),
+            StateEvent::Charging(charging) => self
+                .scheduler
+                .on_state_change(Handler::update_charging, charging),
+            StateEvent::ThermalLevel(level) => self
+                .scheduler
+                .on_state_change(Handler::update_thermal_level, level),
+            StateEvent::CaptivePortal(portal) => self.scheduler.on_captive_portal(portal),
         }
     }
 
@@ -360,6 +551,9 @@ impl TaskManager {
             TaskEvent::Subscribe(task_id, token_id, tx) => {
                 let _ = tx.send(self.check_subscriber(task_id, token_id));
             }
+            TaskEvent::SubscribeBatch(task_ids, token_id, tx) => {
+                let _ = tx.send(self.subscribe_batch(task_ids, token_id));
+            }
             TaskEvent::Completed(task_id, uid, mode) => {
                 Scheduler::reduce_task_count(uid, mode, &mut self.task_count);
                 task_complete_add();
@@ -378,6 +572,18 @@ impl TaskManager {
                 self.scheduler
                     .task_cancel(uid, task_id, mode, &mut self.task_count);
             }
+            TaskEvent::Paused(task_id, uid, reason) => {
+                self.scheduler.task_paused(uid, task_id, reason);
+            }
+            TaskEvent::RateLimited(task_id, uid, mode, delay) => {
+                // Tasks rate limited too often fail like any other retry
+                if !self.scheduler.task_rate_limited(uid, task_id, delay) {
+                    Scheduler::reduce_task_count(uid, mode, &mut self.task_count);
+                    task_fail_add();
+                    self.scheduler
+                        .task_failed(uid, task_id, Reason::RateLimited);
+                }
+            }
         };
     }
 
@@ -401,6 +607,15 @@ impl TaskManager {
             ScheduleEvent::RestoreAllTasks => self.restore_all_tasks(),
             ScheduleEvent::Unload => return self.unload_sa(),
             ScheduleEvent::Shutdown => self.shutdown(),
+            ScheduleEvent::Maintenance => self.maintain_if_idle(),
+            ScheduleEvent::TaskListFlush => self.flush_task_lists(),
+            ScheduleEvent::SweepRemovals => self.sweep_removals(get_current_timestamp()),
+            ScheduleEvent::MigrateToPreferredNetwork => {
+                self.scheduler.migrate_to_preferred_network();
+            }
+            ScheduleEvent::RateLimitElapsed(task_id) => {
+                self.scheduler.rate_limit_elapsed(task_id);
+            }
         }
         false
     }
@@ -424,6 +639,34 @@ impl TaskManager {
         }
     }
 
+    /// Checks the subscriber of many tasks and takes their current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_ids` - The IDs of the tasks to check
+    /// * `token_id` - The token ID of the subscriber
+    ///
+    /// # Returns
+    ///
+    /// For each task in order, the notification reporting its current state
+    /// if the subscriber has permission, otherwise an appropriate error code
+    pub(crate) fn subscribe_batch(
+        &self,
+        task_ids: Vec<u32>,
+        token_id: u64,
+    ) -> Vec<Result<NotifyData, ErrorCode>> {
+        task_ids
+            .into_iter()
+            .map(|task_id| match self.check_subscriber(task_id, token_id) {
+                ErrorCode::ErrOk => RequestDb::get_instance()
+                    .get_task_info(task_id)
+                    .map(|info| info.build_notify_data())
+                    .ok_or(ErrorCode::TaskNotFound),
+                code => Err(code),
+            })
+            .collect()
+    }
+
     /// Shuts down the scheduler.
     /// 
     /// Terminates all ongoing tasks and prepares for service shutdown.
@@ -446,6 +689,36 @@ impl TaskManager {
         self.scheduler.restore_all_tasks();
     }
 
+    /// Starts database maintenance once the service has been idle long enough.
+    fn maintain_if_idle(&mut self) {
+        let running_tasks = self.scheduler.running_tasks();
+        if let Some(job) = self.maintenance.poll_idle(running_tasks, Instant::now()) {
+            run_maintenance(job);
+        }
+    }
+
+    /// Schedules a flush of the task list deltas, at most one per debounce
+    /// interval.
+    fn schedule_task_list_flush(&mut self) {
+        if let Some(delay) = self.task_list.mark_dirty(Instant::now()) {
+            let tx = self.tx.clone();
+            runtime_spawn(async move {
+                sleep(delay).await;
+                let _ = tx.send_event(TaskManagerEvent::Schedule(ScheduleEvent::TaskListFlush));
+            });
+        }
+    }
+
+    /// Sends the changed task lists to their subscribers.
+    pub(crate) fn flush_task_lists(&mut self) {
+        let deltas = self
+            .task_list
+            .flush(Instant::now(), RequestDb::get_instance());
+        for (pid, id, delta) in deltas {
+            self.client_manager.send_task_list_delta(pid, id, delta);
+        }
+    }
+
     /// Checks if there are any running tasks or pending events.
     /// 
     /// Used before unloading the service to ensure all tasks are completed and no new
@@ -506,6 +779,7 @@ impl TaskManager {
             state: State::Waiting.repr,
             action: Action::Any.repr,
             mode: Mode::Any.repr,
+            client_tag: String::new(),
         };
 
         let bundle_name = "*".to_string();
@@ -633,6 +907,42 @@ impl TaskManagerTx {
         let _ = self.send_event(TaskManagerEvent::State(StateEvent::SpecialTerminate(uid)));
     }
 
+    /// Notifies the task manager that the device started or stopped charging.
+    ///
+    /// # Arguments
+    ///
+    /// * `charging` - Whether the device is charging
+    pub(crate) fn notify_charging_change(&self, charging: bool) {
+        let _ = self.send_event(TaskManagerEvent::State(StateEvent::Charging(charging)));
+    }
+
+    /// Notifies the task manager that the thermal level of the device changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The new thermal level
+    pub(crate) fn notify_thermal_change(&self, level: u8) {
+        let _ = self.send_event(TaskManagerEvent::State(StateEvent::ThermalLevel(level)));
+    }
+
+    /// Notifies the task manager of the result of a captive portal probe.
+    ///
+    /// # Arguments
+    ///
+    /// * `portal` - Whether a captive portal intercepts the traffic
+    pub(crate) fn notify_captive_portal(&self, portal: bool) {
+        let _ = self.send_event(TaskManagerEvent::State(StateEvent::CaptivePortal(portal)));
+    }
+
+    /// Notifies the task manager that a process has terminated.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - The process ID
+    pub(crate) fn notify_process_terminate(&self, pid: u64) {
+        let _ = self.send_event(TaskManagerEvent::State(StateEvent::ProcessTerminate(pid)));
+    }
+
     /// Retrieves task information for a specific user.
     /// 
     /// # Arguments
@@ -687,13 +997,14 @@ impl TaskManagerTx {
     /// 
     /// * `uid` - The user ID to verify ownership
     /// * `task_id` - The ID of the task to retrieve
-    /// * `token` - The authentication token for the task
+    /// * `token` - The authentication token for the task, `None` if a signed
+    ///   token was verified
     /// 
     /// # Returns
     /// 
     /// Returns `Some(TaskInfo)` with the bundle name sanitized if the task exists,
     /// is owned by the specified user, and the token matches, otherwise `None`
-    pub(crate) fn touch(&self, uid: u64, task_id: u32, token: String) -> Option<TaskInfo> {
+    pub(crate) fn touch(&self, uid: u64, task_id: u32, token: Option<String>) -> Option<TaskInfo> {
         let (tx, rx) = oneshot::channel();
         let event = QueryEvent::Touch(task_id, uid, token, tx);
         let _ = self.send_event(TaskManagerEvent::Query(event));
@@ -771,3 +1082,34 @@ async fn clear_timeout_tasks(tx: TaskManagerTx) {
         let _ = tx.send_event(TaskManagerEvent::Schedule(ScheduleEvent::ClearTimeoutTasks));
     }
 }
+
+/// Periodically asks the task manager to check for idle maintenance.
+///
+/// # Arguments
+///
+/// * `tx` - The task manager event sender to use for triggering the check
+async fn check_maintenance(tx: TaskManagerTx) {
+    loop {
+        sleep(MAINTENANCE_CHECK_INTERVAL).await;
+        let _ = tx.send_event(TaskManagerEvent::Schedule(ScheduleEvent::Maintenance));
+    }
+}
+
+/// Periodically asks the task manager to finally remove tasks whose removal
+/// grace period expired.
+///
+/// # Arguments
+///
+/// * `tx` - The task manager event sender to use for triggering the sweep
+async fn sweep_removals(tx: TaskManagerTx) {
+    loop {
+        sleep(REMOVAL_SWEEP_INTERVAL).await;
+        let _ = tx.send_event(TaskManagerEvent::Schedule(ScheduleEvent::SweepRemovals));
+    }
+}
+
+/// Runs a database maintenance job on a blocking thread, off the task
+/// manager loop.
+fn run_maintenance(job: MaintenanceJob) -> JoinHandle<Result<MaintenanceReport, io::Error>> {
+    runtime_spawn_blocking(move || Ok(job.run(RequestDb::get_instance())))
+}
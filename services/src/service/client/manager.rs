@@ -23,14 +23,31 @@ use ylong_runtime::net::UnixDatagram;
 use ylong_runtime::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use ylong_runtime::sync::oneshot::Sender;
 
-use super::{Client, ClientEvent};
+use super::{Client, ClientEvent, ClientQueue};
 
 cfg_oh! {
     use crate::ability::PANIC_INFO;
 }
 use crate::error::ErrorCode;
+use crate::task::notify::{NotifyData, SubscribeType};
 use crate::utils::runtime_spawn;
 
+/// Largest number of open client channels, unless overridden by
+/// [`MAX_CHANNELS_PARAM`].
+pub(crate) const DEFAULT_MAX_CHANNELS: usize = 512;
+
+/// System parameter overriding the largest number of open client channels.
+pub(crate) const MAX_CHANNELS_PARAM: &str = "const.request.max_channels";
+
+/// Returns the largest number of open client channels.
+fn max_channels() -> usize {
+    #[cfg(feature = "oh")]
+    let limit = crate::utils::get_int_parameter(MAX_CHANNELS_PARAM, DEFAULT_MAX_CHANNELS as i64);
+    #[cfg(not(feature = "oh"))]
+    let limit = DEFAULT_MAX_CHANNELS as i64;
+    usize::try_from(limit).unwrap_or(DEFAULT_MAX_CHANNELS)
+}
+
 /// Lightweight handle for sending events to the `ClientManager`.
 ///
 /// This struct provides a thread-safe, cloneable entry point for sending events to the
@@ -96,10 +113,12 @@ impl ClientManagerEntry {
 /// and routes events between the service and its clients.
 pub(crate) struct ClientManager {
     // map from pid to client and fd
-    /// Map of process IDs to client channels and socket connections.
-    clients: HashMap<u64, (UnboundedSender<ClientEvent>, Arc<UnixDatagram>)>,
+    /// Map of process IDs to client queues and socket connections.
+    clients: HashMap<u64, (Arc<ClientQueue>, Arc<UnixDatagram>)>,
     /// Map of task IDs to process IDs for notification routing.
     pid_map: HashMap<u32, u64>,
+    /// Largest number of open client channels.
+    max_channels: usize,
     /// Receiver channel for incoming events to process.
     rx: UnboundedReceiver<ClientEvent>,
 }
@@ -114,11 +133,18 @@ impl ClientManager {
     ///
     /// A new `ClientManagerEntry` that can be used to communicate with the client manager.
     pub(crate) fn init() -> ClientManagerEntry {
-        debug!("ClientManager init");
+        Self::init_with_max_channels(max_channels())
+    }
+
+    /// Initializes a new client manager keeping at most `max_channels`
+    /// client channels open.
+    pub(crate) fn init_with_max_channels(max_channels: usize) -> ClientManagerEntry {
+        debug!("ClientManager init, max channels {}", max_channels);
         let (tx, rx) = unbounded_channel();
         let client_manager = ClientManager {
             clients: HashMap::new(),
             pid_map: HashMap::new(),
+            max_channels,
             rx,
         };
         // Spawn the client manager's main loop in a separate task
@@ -152,15 +178,25 @@ impl ClientManager {
                 ClientEvent::Subscribe(tid, pid, uid, token_id, tx) => {
                     self.handle_subscribe(tid, pid, uid, token_id, tx)
                 }
+                ClientEvent::SubscribeBatch(pid, snapshots, tx) => {
+                    self.handle_subscribe_batch(pid, snapshots, tx)
+                }
                 ClientEvent::Unsubscribe(tid, tx) => self.handle_unsubscribe(tid, tx),
                 ClientEvent::TaskFinished(tid) => self.handle_task_finished(tid),
                 ClientEvent::Terminate(pid, tx) => self.handle_process_terminated(pid, tx),
+                ClientEvent::QueueDrops(tx) => self.handle_queue_drops(tx),
+                ClientEvent::Channels(tx) => {
+                    let _ = tx.send((self.clients.len(), self.max_channels));
+                }
+                ClientEvent::ChannelOpen(pid, tx) => {
+                    let _ = tx.send(self.clients.contains_key(&pid));
+                }
                 
                 // Response event routing
                 ClientEvent::SendResponse(tid, version, status_code, reason, headers) => {
                     if let Some(&pid) = self.pid_map.get(&tid) {
                         if let Some((tx, _fd)) = self.clients.get_mut(&pid) {
-                            if let Err(err) = tx.send(ClientEvent::SendResponse(
+                            if let Err(err) = tx.push(ClientEvent::SendResponse(
                                 tid,
                                 version,
                                 status_code,
@@ -187,7 +223,7 @@ impl ClientManager {
                     if let Some(&pid) = self.pid_map.get(&(notify_data.task_id)) {
                         if let Some((tx, _fd)) = self.clients.get_mut(&pid) {
                             if let Err(err) = 
-                                tx.send(ClientEvent::SendNotifyData(subscribe_type, notify_data))
+                                tx.push(ClientEvent::SendNotifyData(subscribe_type, notify_data))
                             {
                                 error!("send notify data error, {}", err);
                                 sys_event!(
@@ -209,7 +245,7 @@ impl ClientManager {
                     if let Some(&pid) = self.pid_map.get(&tid) {
                         if let Some((tx, _fd)) = self.clients.get_mut(&pid) {
                             if let Err(err) = 
-                                tx.send(ClientEvent::SendFaults(tid, subscribe_type, reason))
+                                tx.push(ClientEvent::SendFaults(tid, subscribe_type, reason))
                             {
                                 error!("send faults error, {}", err);
                                 sys_event!(
@@ -226,7 +262,7 @@ impl ClientManager {
                 ClientEvent::SendWaitNotify(tid, reason) => {
                     if let Some(&pid) = self.pid_map.get(&tid) {
                         if let Some((tx, _fd)) = self.clients.get_mut(&pid) {
-                            if let Err(err) = tx.send(ClientEvent::SendWaitNotify(tid, reason)) {
+                            if let Err(err) = tx.push(ClientEvent::SendWaitNotify(tid, reason)) {
                                 error!("send faults error, {}", err);
                                 sys_event!(
                                     ExecFault,
@@ -238,6 +274,38 @@ impl ClientManager {
                     }
                 }
                 
+                // Redirect routing
+                ClientEvent::SendRedirect(tid, hop) => {
+                    if let Some(&pid) = self.pid_map.get(&tid) {
+                        if let Some((tx, _fd)) = self.clients.get_mut(&pid) {
+                            if let Err(err) = tx.push(ClientEvent::SendRedirect(tid, hop)) {
+                                error!("send redirect error, {}", err);
+                                sys_event!(
+                                    ExecFault,
+                                    DfxCode::UDS_FAULT_02,
+                                    &format!("send redirect error, {}", err)
+                                );
+                            }
+                        }
+                    }
+                }
+
+                // Task list changes are routed by the subscribing process
+                ClientEvent::SendTaskListDelta(pid, id, delta) => {
+                    if let Some((tx, _fd)) = self.clients.get_mut(&pid) {
+                        if let Err(err) = tx.push(ClientEvent::SendTaskListDelta(pid, id, delta)) {
+                            error!("send task list delta error, {}", err);
+                            sys_event!(
+                                ExecFault,
+                                DfxCode::UDS_FAULT_02,
+                                &format!("send task list delta error, {}", err)
+                            );
+                        }
+                    } else {
+                        debug!("task list client not found");
+                    }
+                }
+
                 // Ignore unhandled events
                 _ => {}
             }
@@ -248,7 +316,8 @@ impl ClientManager {
 
     /// Handles client channel opening requests.
     ///
-    /// This method either returns an existing channel for a process or creates a new one.
+    /// This method either returns an existing channel for a process or creates a new one,
+    /// unless as many channels as allowed are already open.
     ///
     /// # Arguments
     ///
@@ -261,11 +330,23 @@ impl ClientManager {
                 let (_, fd) = o.get();
                 let _ = tx.send(Ok(fd.clone()));
             }
+            // Reject new connections once the cap is reached
+            hash_map::Entry::Vacant(_) if self.clients.len() >= self.max_channels => {
+                warn!(
+                    "open channel of pid {} rejected, {} channels open",
+                    pid,
+                    self.clients.len()
+                );
+                let _ = tx.send(Err(ErrorCode::TooManyChannels));
+            }
             // Create new connection if none exists
             hash_map::Entry::Vacant(v) => match Client::constructor(pid) {
                 Some((client, ud_fd)) => {
                     let _ = tx.send(Ok(ud_fd.clone()));
                     v.insert((client, ud_fd));
+                    if self.clients.len() == self.max_channels {
+                        warn!("open channels reached the maximum {}", self.max_channels);
+                    }
                 }
                 None => {
                     let _ = tx.send(Err(ErrorCode::Other));
@@ -303,6 +384,43 @@ impl ClientManager {
         }
     }
 
+    /// Handles subscription requests for many tasks at once.
+    ///
+    /// Maps every task ID to the client process, then queues the current
+    /// state of each task to the client so it is reported before any later
+    /// update.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - Process ID of the subscribing client
+    /// * `snapshots` - Current state of each task being subscribed to
+    /// * `tx` - One-shot sender to confirm the status of each subscription
+    fn handle_subscribe_batch(
+        &mut self,
+        pid: u64,
+        snapshots: Vec<NotifyData>,
+        tx: Sender<Vec<ErrorCode>>,
+    ) {
+        let Some((client, _fd)) = self.clients.get(&pid) else {
+            info!("channel not open, pid {}", pid);
+            let _ = tx.send(vec![ErrorCode::ChannelNotOpen; snapshots.len()]);
+            return;
+        };
+        let mut ret = Vec::with_capacity(snapshots.len());
+        for notify_data in snapshots {
+            self.pid_map.insert(notify_data.task_id, pid);
+            let event = ClientEvent::SendNotifyData(SubscribeType::Progress, notify_data);
+            match client.push(event) {
+                Ok(()) => ret.push(ErrorCode::ErrOk),
+                Err(err) => {
+                    error!("send snapshot error, {}", err);
+                    ret.push(ErrorCode::Other);
+                }
+            }
+        }
+        let _ = tx.send(ret);
+    }
+
     /// Handles task unsubscription requests.
     ///
     /// Removes the mapping between a task ID and client process.
@@ -352,7 +470,7 @@ impl ClientManager {
     fn handle_process_terminated(&mut self, pid: u64, tx: Sender<ErrorCode>) {
         if let Some((tx, _)) = self.clients.get_mut(&pid) {
             // Send shutdown signal to the client handler
-            let _ = tx.send(ClientEvent::Shutdown);
+            let _ = tx.push(ClientEvent::Shutdown);
             // Remove all traces of the client
             self.clients.remove(&pid);
         } else {
@@ -360,4 +478,25 @@ impl ClientManager {
         }
         let _ = tx.send(ErrorCode::ErrOk);
     }
+
+    /// Handles requests for the number of progress events dropped for each
+    /// client.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - One-shot sender to return the process ID and drops of each
+    ///   client
+    fn handle_queue_drops(&self, tx: Sender<Vec<(u64, u64)>>) {
+        let drops = self
+            .clients
+            .iter()
+            .map(|(pid, (queue, _fd))| (*pid, queue.drops()))
+            .collect();
+        let _ = tx.send(drops);
+    }
+}
+
+#[cfg(test)]
+mod ut_manager {
+    include!("../../../tests/ut/service/client/ut_manager.rs");
 }
@@ -18,22 +18,29 @@
 //! receiving various types of events and notifications between the request service and its clients.
 
 mod manager;
+mod queue;
 
 use std::collections::HashMap;
 use std::net::Shutdown;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
 pub(crate) use manager::{ClientManager, ClientManagerEntry};
+use queue::{ClientQueue, CLIENT_QUEUE_CAPACITY};
 use ylong_http_client::Headers;
 use ylong_runtime::net::UnixDatagram;
-use ylong_runtime::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use ylong_runtime::sync::mpsc::UnboundedReceiver;
 use ylong_runtime::sync::oneshot::{channel, Sender};
 
 use crate::config::Version;
 use crate::error::ErrorCode;
+use crate::manage::database::RequestDb;
+use crate::manage::task_list::TaskListDelta;
 use crate::task::notify::{NotifyData, SubscribeType, WaitingCause};
 use crate::task::reason::Reason;
+use crate::task::redirect::RedirectHop;
+use crate::task::request_task::MAX_RESPONSE_HEADERS;
 use crate::utils::{runtime_spawn, Recv};
 
 /// Magic number used to identify request service messages.
@@ -42,9 +49,43 @@ const REQUEST_MAGIC_NUM: u32 = 0x43434646;
 /// Maximum size of headers allowed in message payloads.
 const HEADERS_MAX_SIZE: u16 = 8 * 1024;
 
+/// Pseudo-header appended with the number of response headers dropped.
+const HEADERS_TRUNCATED: &str = "x-request-headers-truncated";
+
+/// Well-known response headers kept ahead of arbitrary ones when truncating.
+const PRIORITY_HEADERS: [&str; 5] = [
+    "content-type",
+    "content-length",
+    "content-disposition",
+    "etag",
+    "location",
+];
+
 /// Position in the message buffer where the length field is stored.
 const POSITION_OF_LENGTH: u32 = 10;
 
+/// Maximum number of response body bytes carried inline in a completion message.
+///
+/// Longer bodies are marked `RESPONSE_BODY_PARTIAL`, the client fetches the
+/// whole body kept by the service with a task query.
+const RESPONSE_BODY_UDS_MAX: usize = 2048;
+
+/// Response body flag, the server sent more than the task keeps.
+const RESPONSE_BODY_TRUNCATED: u8 = 1;
+
+/// Response body flag, the message carries only the beginning of the body
+/// kept by the service.
+const RESPONSE_BODY_PARTIAL: u8 = 1 << 1;
+
+/// Maximum number of bytes of each URL carried in a redirect message.
+const REDIRECT_URL_UDS_MAX: usize = 1024;
+
+/// Version of the extension block appended to notify data messages.
+///
+/// Version 1 holds the estimated remaining time followed by the optional
+/// upload response body, version 2 appends the client tag.
+const NOTIFY_DATA_EXT_VERSION: u32 = 2;
+
 /// Events used for communication between the client manager and client handlers.
 #[derive(Debug)]
 pub(crate) enum ClientEvent {
@@ -66,6 +107,16 @@ pub(crate) enum ClientEvent {
     /// * `3` - Token ID
     /// * `4` - Sender to confirm subscription status
     Subscribe(u32, u64, u64, u64, Sender<ErrorCode>),
+
+    /// Subscribes a client to notifications for many tasks and sends it
+    /// their current state.
+    ///
+    /// # Fields
+    ///
+    /// * `0` - Process ID of the client
+    /// * `1` - Current state of each task
+    /// * `2` - Sender to confirm the subscription status of each task
+    SubscribeBatch(u64, Vec<NotifyData>, Sender<Vec<ErrorCode>>),
     
     /// Unsubscribes a client from task notifications.
     /// 
@@ -125,6 +176,45 @@ pub(crate) enum ClientEvent {
     /// * `0` - Task ID
     /// * `1` - Cause of waiting
     SendWaitNotify(u32, WaitingCause),
+
+    /// Sends task list changes to a client.
+    ///
+    /// # Fields
+    ///
+    /// * `0` - Process ID of the client
+    /// * `1` - Subscription ID chosen by the client
+    /// * `2` - Changes since the previous delta
+    SendTaskListDelta(u64, u32, TaskListDelta),
+
+    /// Sends a redirect followed by a task to a client.
+    ///
+    /// # Fields
+    ///
+    /// * `0` - Task ID
+    /// * `1` - The redirect
+    SendRedirect(u32, RedirectHop),
+
+    /// Reports the number of progress events dropped for each client.
+    ///
+    /// # Fields
+    ///
+    /// * `0` - Sender to return the process ID and drops of each client
+    QueueDrops(Sender<Vec<(u64, u64)>>),
+
+    /// Reports the number of open client channels and their maximum.
+    ///
+    /// # Fields
+    ///
+    /// * `0` - Sender to return the open channels and the maximum
+    Channels(Sender<(usize, usize)>),
+
+    /// Reports whether a client has its channel open.
+    ///
+    /// # Fields
+    ///
+    /// * `0` - Process ID of the client
+    /// * `1` - Sender to return whether the channel is open
+    ChannelOpen(u64, Sender<bool>),
     
     /// Signals to shutdown the client handler.
     Shutdown,
@@ -141,6 +231,10 @@ pub(crate) enum MessageType {
     Faults,
     /// Waiting state notification message.
     Waiting,
+    /// Task list change message.
+    TaskListDelta,
+    /// Redirect followed by a task.
+    Redirect,
 }
 
 impl ClientManagerEntry {
@@ -208,6 +302,41 @@ impl ClientManagerEntry {
         }
     }
 
+    /// Subscribes a client to notifications for many tasks at once.
+    ///
+    /// Each subscribed task immediately reports its current state to the
+    /// client, before any later update.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - Process ID of the client
+    /// * `snapshots` - Current state of each task to subscribe to
+    ///
+    /// # Returns
+    ///
+    /// The subscription status of each task, in order
+    pub(crate) fn subscribe_batch(&self, pid: u64, snapshots: Vec<NotifyData>) -> Vec<ErrorCode> {
+        let count = snapshots.len();
+        let (tx, rx) = channel::<Vec<ErrorCode>>();
+        let event = ClientEvent::SubscribeBatch(pid, snapshots, tx);
+        if !self.send_event(event) {
+            return vec![ErrorCode::Other; count];
+        }
+        let rx = Recv::new(rx);
+        match rx.get() {
+            Some(ret) => ret,
+            None => {
+                error!("subscribe batch fail, recv none");
+                sys_event!(
+                    ExecFault,
+                    DfxCode::UDS_FAULT_03,
+                    "subscribe batch fail, recv none"
+                );
+                vec![ErrorCode::Other; count]
+            }
+        }
+    }
+
     /// Unsubscribes a client from task notifications.
     ///
     /// # Arguments
@@ -302,6 +431,8 @@ impl ClientManagerEntry {
     /// * `subscribe_type` - Type of subscription
     /// * `notify_data` - Notification data
     pub(crate) fn send_notify_data(&self, subscribe_type: SubscribeType, notify_data: NotifyData) {
+        #[cfg(all(feature = "embedded", not(feature = "oh")))]
+        crate::embedded::dispatch_notify(subscribe_type, &notify_data);
         let event = ClientEvent::SendNotifyData(subscribe_type, notify_data);
         let _ = self.send_event(event);
     }
@@ -314,6 +445,8 @@ impl ClientManagerEntry {
     /// * `subscribe_type` - Type of subscription
     /// * `reason` - Reason for the fault
     pub(crate) fn send_faults(&self, tid: u32, subscribe_type: SubscribeType, reason: Reason) {
+        #[cfg(all(feature = "embedded", not(feature = "oh")))]
+        crate::embedded::dispatch_fault(tid, reason);
         let event = ClientEvent::SendFaults(tid, subscribe_type, reason);
         let _ = self.send_event(event);
     }
@@ -328,6 +461,95 @@ impl ClientManagerEntry {
         let event = ClientEvent::SendWaitNotify(tid, reason);
         let _ = self.send_event(event);
     }
+
+    /// Sends task list changes to a client.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - Process ID of the client
+    /// * `id` - Subscription ID chosen by the client
+    /// * `delta` - Changes since the previous delta
+    pub(crate) fn send_task_list_delta(&self, pid: u64, id: u32, delta: TaskListDelta) {
+        let event = ClientEvent::SendTaskListDelta(pid, id, delta);
+        let _ = self.send_event(event);
+    }
+
+    /// Sends a redirect followed by a task to a client.
+    ///
+    /// # Arguments
+    ///
+    /// * `tid` - Task ID
+    /// * `hop` - The redirect
+    pub(crate) fn send_redirect(&self, tid: u32, hop: RedirectHop) {
+        let event = ClientEvent::SendRedirect(tid, hop);
+        let _ = self.send_event(event);
+    }
+
+    /// Returns the number of progress events dropped for each client whose
+    /// queue was full.
+    ///
+    /// # Returns
+    ///
+    /// The process ID and number of dropped events of each client
+    pub(crate) fn queue_drops(&self) -> Vec<(u64, u64)> {
+        let (tx, rx) = channel::<Vec<(u64, u64)>>();
+        let event = ClientEvent::QueueDrops(tx);
+        if !self.send_event(event) {
+            return vec![];
+        }
+        let rx = Recv::new(rx);
+        match rx.get() {
+            Some(ret) => ret,
+            None => {
+                error!("queue drops fail, recv none");
+                vec![]
+            }
+        }
+    }
+
+    /// Returns the number of open client channels and their maximum.
+    ///
+    /// # Returns
+    ///
+    /// The number of open channels and the largest number that may be open,
+    /// `(0, 0)` if the client manager is no longer available
+    pub(crate) fn channels(&self) -> (usize, usize) {
+        let (tx, rx) = channel::<(usize, usize)>();
+        let event = ClientEvent::Channels(tx);
+        if !self.send_event(event) {
+            return (0, 0);
+        }
+        let rx = Recv::new(rx);
+        match rx.get() {
+            Some(ret) => ret,
+            None => {
+                error!("channels fail, recv none");
+                (0, 0)
+            }
+        }
+    }
+
+    /// Returns whether the client `pid` has its channel open.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the channel is not open or the client manager is no longer
+    /// available
+    pub(crate) fn channel_open(&self, pid: u64) -> bool {
+        let (tx, rx) = channel::<bool>();
+        let event = ClientEvent::ChannelOpen(pid, tx);
+        if !self.send_event(event) {
+            return false;
+        }
+        let rx = Recv::new(rx);
+        match rx.get() {
+            Some(ret) => ret,
+            None => {
+                error!("channel open fail, recv none");
+                false
+            }
+        }
+    }
 }
 
 // uid and token_id will be used later
@@ -344,8 +566,10 @@ pub(crate) struct Client {
     pub(crate) server_sock_fd: UnixDatagram,
     /// Client-side socket file descriptor (shared with the client).
     pub(crate) client_sock_fd: Arc<UnixDatagram>,
-    /// Receiver for client events.
-    rx: UnboundedReceiver<ClientEvent>,
+    /// Events waiting to be sent to the client.
+    queue: Arc<ClientQueue>,
+    /// Receiver woken when events are queued.
+    rx: UnboundedReceiver<()>,
 }
 
 impl Client {
@@ -360,11 +584,8 @@ impl Client {
     ///
     /// # Returns
     ///
-    /// `Some((UnboundedSender<ClientEvent>, Arc<UnixDatagram>))` if successful, or `None` if socket creation fails
-    pub(crate) fn constructor(
-        pid: u64,
-    ) -> Option<(UnboundedSender<ClientEvent>, Arc<UnixDatagram>)> {
-        let (tx, rx) = unbounded_channel();
+    /// `Some((Arc<ClientQueue>, Arc<UnixDatagram>))` if successful, or `None` if socket creation fails
+    pub(crate) fn constructor(pid: u64) -> Option<(Arc<ClientQueue>, Arc<UnixDatagram>)> {
         // Create a pair of connected Unix domain sockets
         let (server_sock_fd, client_sock_fd) = match UnixDatagram::pair() {
             Ok((server_sock_fd, client_sock_fd)) => (server_sock_fd, client_sock_fd),
@@ -379,17 +600,20 @@ impl Client {
             }
         };
         let client_sock_fd = Arc::new(client_sock_fd);
+        let (queue, rx) = ClientQueue::new(pid, CLIENT_QUEUE_CAPACITY);
+        let queue = Arc::new(queue);
         let client = Client {
             pid,
             message_id: 1,
             server_sock_fd,
             client_sock_fd: client_sock_fd.clone(),
+            queue: queue.clone(),
             rx,
         };
 
         // Spawn the client handler in a separate task
         runtime_spawn(client.run());
-        Some((tx, client_sock_fd))
+        Some((queue, client_sock_fd))
     }
 
     /// Main message processing loop for the client handler.
@@ -401,28 +625,22 @@ impl Client {
             // for one task, only send last progress message
             let mut progress_index = HashMap::new();
             let mut temp_notify_data: Vec<(SubscribeType, NotifyData)> = Vec::new();
-            let mut len = self.rx.len();
-            if len == 0 {
-                len = 1;
+            if let Err(e) = self.rx.recv().await {
+                error!("ClientManager recv error {:?}", e);
+                sys_event!(
+                    ExecFault,
+                    DfxCode::UDS_FAULT_03,
+                    &format!("ClientManager recv error {:?}", e)
+                );
+                continue;
             }
-            for index in 0..len {
-                let recv = match self.rx.recv().await {
-                    Ok(message) => message,
-                    Err(e) => {
-                        error!("ClientManager recv error {:?}", e);
-                        sys_event!(
-                            ExecFault,
-                            DfxCode::UDS_FAULT_03,
-                            &format!("ClientManager recv error {:?}", e)
-                        );
-                        continue;
-                    }
-                };
+            for (index, recv) in self.queue.take().into_iter().enumerate() {
                 match recv {
                     ClientEvent::Shutdown => {
                         // Clean up resources on shutdown
                         let _ = self.client_sock_fd.shutdown(Shutdown::Both);
                         let _ = self.server_sock_fd.shutdown(Shutdown::Both);
+                        self.queue.close();
                         self.rx.close();
                         info!("client terminate, pid {}", self.pid);
                         return;
@@ -445,6 +663,14 @@ impl Client {
                         self.handle_send_waiting_notify(task_id, waiting_reason)
                             .await;
                     }
+                    ClientEvent::SendTaskListDelta(_pid, id, delta) => {
+                        for part in delta.split() {
+                            self.handle_send_task_list_delta(id, part).await;
+                        }
+                    }
+                    ClientEvent::SendRedirect(tid, hop) => {
+                        self.handle_send_redirect(tid, hop).await;
+                    }
                     _ => {}
                 }
             }
@@ -502,6 +728,10 @@ impl Client {
         // Reason code
         message.extend_from_slice(&(reason.repr as u32).to_le_bytes());
 
+        // Client tag, appended after the fields read by older clients
+        let client_tag = RequestDb::get_instance().query_client_tag(tid);
+        push_client_tag(&mut message, &client_tag);
+
         // Update the message size
         let size = message.len() as u16;
         info!("send faults size, {:?}", size);
@@ -546,6 +776,10 @@ impl Client {
         // Waiting reason code
         message.extend_from_slice(&(waiting_reason.clone() as u32).to_le_bytes());
 
+        // Client tag, appended after the fields read by older clients
+        let client_tag = RequestDb::get_instance().query_client_tag(task_id);
+        push_client_tag(&mut message, &client_tag);
+
         // Update the message size
         let size = message.len() as u16;
         debug!(
@@ -560,6 +794,117 @@ impl Client {
         self.send_message(message).await;
     }
 
+    /// Handles sending task list changes to the client.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Subscription ID chosen by the client
+    /// * `delta` - Changes fitting into one message
+    async fn handle_send_task_list_delta(&mut self, id: u32, delta: TaskListDelta) {
+        let mut message = Vec::<u8>::new();
+
+        // Message header with magic number
+        message.extend_from_slice(&REQUEST_MAGIC_NUM.to_le_bytes());
+
+        // Unique message identifier
+        message.extend_from_slice(&self.message_id.to_le_bytes());
+        self.message_id += 1;
+
+        // Message type for task list changes
+        let message_type = MessageType::TaskListDelta as u16;
+        message.extend_from_slice(&message_type.to_le_bytes());
+
+        // Message body size (initially 0, will be updated later)
+        let message_body_size: u16 = 0;
+        message.extend_from_slice(&message_body_size.to_le_bytes());
+
+        // Subscription ID
+        message.extend_from_slice(&id.to_le_bytes());
+
+        // Added tasks
+        message.extend_from_slice(&(delta.added.len() as u32).to_le_bytes());
+        for brief in delta.added.iter() {
+            message.extend_from_slice(&brief.task_id.to_le_bytes());
+            message.extend_from_slice(&(brief.action.repr as u32).to_le_bytes());
+            message.extend_from_slice(&(brief.mode.repr as u32).to_le_bytes());
+            message.extend_from_slice(&(brief.state.repr as u32).to_le_bytes());
+        }
+
+        // Removed tasks
+        message.extend_from_slice(&(delta.removed.len() as u32).to_le_bytes());
+        for task_id in delta.removed.iter() {
+            message.extend_from_slice(&task_id.to_le_bytes());
+        }
+
+        // Tasks with a new state
+        message.extend_from_slice(&(delta.state_changed.len() as u32).to_le_bytes());
+        for (task_id, state) in delta.state_changed.iter() {
+            message.extend_from_slice(&task_id.to_le_bytes());
+            message.extend_from_slice(&(state.repr as u32).to_le_bytes());
+        }
+
+        // Update the message size
+        let size = message.len() as u16;
+        debug!(
+            "send task list delta, id {} added {} removed {} changed {}",
+            id,
+            delta.added.len(),
+            delta.removed.len(),
+            delta.state_changed.len()
+        );
+        let size = size.to_le_bytes();
+        message[POSITION_OF_LENGTH as usize] = size[0];
+        message[(POSITION_OF_LENGTH + 1) as usize] = size[1];
+
+        // Send the constructed message
+        self.send_message(message).await;
+    }
+
+    /// Handles sending a redirect followed by a task to the client.
+    ///
+    /// # Arguments
+    ///
+    /// * `tid` - Task ID
+    /// * `hop` - The redirect
+    async fn handle_send_redirect(&mut self, tid: u32, hop: RedirectHop) {
+        let mut message = Vec::<u8>::new();
+
+        // Message header with magic number
+        message.extend_from_slice(&REQUEST_MAGIC_NUM.to_le_bytes());
+
+        // Unique message identifier
+        message.extend_from_slice(&self.message_id.to_le_bytes());
+        self.message_id += 1;
+
+        // Message type for redirects
+        let message_type = MessageType::Redirect as u16;
+        message.extend_from_slice(&message_type.to_le_bytes());
+
+        // Message body size (initially 0, will be updated later)
+        let message_body_size: u16 = 0;
+        message.extend_from_slice(&message_body_size.to_le_bytes());
+
+        // Task ID and status code of the redirect response
+        message.extend_from_slice(&tid.to_le_bytes());
+        message.extend_from_slice(&hop.status.to_le_bytes());
+
+        // Source and target URLs (null-terminated)
+        message.extend_from_slice(truncate_url(&hop.from_url).as_bytes());
+        message.push(b'\0');
+        message.extend_from_slice(truncate_url(&hop.to_url).as_bytes());
+        message.push(b'\0');
+
+        // Update the message size
+        let size = message.len() as u16;
+        debug!("send redirect, tid {} size {}", tid, size);
+        let size = size.to_le_bytes();
+        message[POSITION_OF_LENGTH as usize] = size[0];
+        message[(POSITION_OF_LENGTH + 1) as usize] = size[1];
+
+        // Send the constructed message
+        self.send_message(message).await;
+    }
+
     /// Handles sending HTTP responses to the client.
     ///
     /// This method constructs and sends an HTTP response message with the given task ID,
@@ -611,35 +956,27 @@ impl Client {
         response.extend_from_slice(&reason.into_bytes());
         response.push(b'\0');
 
-        // Add HTTP headers, respecting size limit
+        // Add HTTP headers, respecting count and size limits
         // The maximum length of the headers in uds should not exceed 8192
-        let mut buf_size = 0;
-        for (k, v) in headers {
-            buf_size += k.as_bytes().len() + v.iter().map(|f| f.len()).sum::<usize>();
-            if buf_size > HEADERS_MAX_SIZE as usize {
-                break;
-            }
-
-            // Format: key:value1,value2
-            response.extend_from_slice(k.as_bytes());
-            response.push(b':');
-            for (i, sub_value) in v.iter().enumerate() {
-                if i != 0 {
-                    response.push(b',');
-                }
-                response.extend_from_slice(sub_value);
-            }
-            response.push(b'\n');
-        }
+        let headers = headers
+            .into_iter()
+            .map(|(k, v)| {
+                let name = String::from_utf8_lossy(k.as_bytes()).into_owned();
+                (name, v.iter().map(|f| f.to_vec()).collect())
+            })
+            .collect();
+        let max_count = MAX_RESPONSE_HEADERS.load(Ordering::Relaxed);
+        let budget = (HEADERS_MAX_SIZE as usize).saturating_sub(response.len());
+        response.extend_from_slice(&encode_headers(headers, budget, max_count));
 
-        // Truncate if response exceeds size limit
+        // Only reachable if version and reason alone exceed the size limit
         let mut size = response.len() as u16;
-        if size > HEADERS_MAX_SIZE {
+        if response.len() > HEADERS_MAX_SIZE as usize {
             info!("send response too long");
             response.truncate(HEADERS_MAX_SIZE as usize);
             size = HEADERS_MAX_SIZE;
         }
-        
+
         // Update the message size
         debug!("send response size, {:?}", size);
         let size = size.to_le_bytes();
@@ -748,6 +1085,35 @@ impl Client {
             message.push(b'\0');
         }
 
+        // Extension block, older clients stop parsing before this section.
+        message.extend_from_slice(&NOTIFY_DATA_EXT_VERSION.to_le_bytes());
+
+        // Estimated remaining time, flag followed by the milliseconds
+        message.push(notify_data.eta_ms.is_some() as u8);
+        message.extend_from_slice(&notify_data.eta_ms.unwrap_or(0).to_le_bytes());
+
+        // Optional upload response body, only appended to completion messages.
+        match notify_data.response_body {
+            Some(body) if subscribe_type == SubscribeType::Complete => {
+                let len = body.data.len().min(RESPONSE_BODY_UDS_MAX);
+                let mut flags = 0;
+                if body.truncated {
+                    flags |= RESPONSE_BODY_TRUNCATED;
+                }
+                if len < body.data.len() {
+                    flags |= RESPONSE_BODY_PARTIAL;
+                }
+                message.push(1);
+                message.extend_from_slice(&(len as u32).to_le_bytes());
+                message.push(flags);
+                message.extend_from_slice(&body.data[..len]);
+            }
+            _ => message.push(0),
+        }
+
+        // Client tag, length followed by the bytes
+        push_client_tag(&mut message, &notify_data.client_tag);
+
         // Update the message size
         let size = message.len() as u16;
         if subscribe_type == SubscribeType::Progress {
@@ -822,3 +1188,75 @@ impl Client {
         }
     }
 }
+
+/// Appends a client tag to a message as its length followed by its bytes.
+fn push_client_tag(message: &mut Vec<u8>, tag: &str) {
+    message.extend_from_slice(&(tag.len() as u32).to_le_bytes());
+    message.extend_from_slice(tag.as_bytes());
+}
+
+/// Truncates a URL to `REDIRECT_URL_UDS_MAX` bytes at a character boundary.
+fn truncate_url(url: &str) -> &str {
+    let mut len = url.len().min(REDIRECT_URL_UDS_MAX);
+    while !url.is_char_boundary(len) {
+        len -= 1;
+    }
+    &url[..len]
+}
+
+/// Encodes response headers as `key:value1,value2` lines within `budget` bytes
+/// and `max_count` entries.
+///
+/// Well-known headers come first and the rest follow in name order, so the
+/// same header set always truncates the same way. Headers are only dropped
+/// whole; when any are, a `x-request-headers-truncated:N` line carrying the
+/// number dropped is appended.
+fn encode_headers(
+    mut headers: Vec<(String, Vec<Vec<u8>>)>,
+    budget: usize,
+    max_count: usize,
+) -> Vec<u8> {
+    headers.sort_by_cached_key(|(k, _)| {
+        let k = k.to_ascii_lowercase();
+        let rank = PRIORITY_HEADERS
+            .iter()
+            .position(|p| *p == k)
+            .unwrap_or(PRIORITY_HEADERS.len());
+        (rank, k)
+    });
+    let lines = headers
+        .into_iter()
+        .map(|(k, v)| {
+            let mut line = k.into_bytes();
+            line.push(b':');
+            line.extend_from_slice(&v.join(&b','));
+            line.push(b'\n');
+            line
+        })
+        .collect::<Vec<_>>();
+
+    let total = lines.len();
+    if total <= max_count && lines.iter().map(Vec::len).sum::<usize>() <= budget {
+        return lines.concat();
+    }
+
+    // Leave room for the marker, whose count never exceeds `total`.
+    let budget = budget.saturating_sub(HEADERS_TRUNCATED.len() + total.to_string().len() + 2);
+    let mut encoded = Vec::new();
+    let mut kept = 0;
+    for line in lines {
+        if kept < max_count && encoded.len() + line.len() <= budget {
+            encoded.extend_from_slice(&line);
+            kept += 1;
+        }
+    }
+    let dropped = total - kept;
+    warn!("send response headers truncated, {} dropped", dropped);
+    encoded.extend_from_slice(format!("{}:{}\n", HEADERS_TRUNCATED, dropped).as_bytes());
+    encoded
+}
+
+#[cfg(test)]
+mod ut_client {
+    include!("../../../tests/ut/service/client/ut_client.rs");
+}
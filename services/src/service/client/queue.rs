@@ -0,0 +1,161 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bounded queue of the events sent to one client.
+//!
+//! A client which stops reading its socket must not make the service grow
+//! without bound. Once the queue is full the oldest progress events are
+//! dropped, as a later progress event supersedes them. Every other event
+//! reports a change the client can not recover and is never dropped.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use ylong_runtime::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use super::ClientEvent;
+use crate::task::notify::SubscribeType;
+
+/// Number of events queued to a client before progress events are dropped.
+pub(crate) const CLIENT_QUEUE_CAPACITY: usize = 1024;
+
+/// Number of dropped events reported together as a fault.
+const DROPS_REPORT_THRESHOLD: u64 = 1024;
+
+/// Error returned when queuing an event to a client which terminated.
+#[derive(Debug)]
+pub(crate) struct QueueClosed;
+
+impl fmt::Display for QueueClosed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "client queue closed")
+    }
+}
+
+struct Inner {
+    events: VecDeque<ClientEvent>,
+    closed: bool,
+}
+
+/// Events waiting to be sent to one client.
+pub(crate) struct ClientQueue {
+    pid: u64,
+    capacity: usize,
+    inner: Mutex<Inner>,
+    drops: AtomicU64,
+    /// Wakes the client handler when the queue stops being empty.
+    waker: UnboundedSender<()>,
+}
+
+impl ClientQueue {
+    /// Creates the queue of a client, with the receiver waking its handler.
+    pub(crate) fn new(pid: u64, capacity: usize) -> (Self, UnboundedReceiver<()>) {
+        let (waker, rx) = unbounded_channel();
+        let queue = Self {
+            pid,
+            capacity,
+            inner: Mutex::new(Inner {
+                events: VecDeque::new(),
+                closed: false,
+            }),
+            drops: AtomicU64::new(0),
+            waker,
+        };
+        (queue, rx)
+    }
+
+    /// Queues an event to the client.
+    ///
+    /// When the queue is full the oldest progress event is dropped to make
+    /// room. A progress event finding no other one to drop is dropped
+    /// itself, any other event is queued beyond the capacity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `QueueClosed` if the client handler has shut down.
+    pub(crate) fn push(&self, event: ClientEvent) -> Result<(), QueueClosed> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed {
+            return Err(QueueClosed);
+        }
+        if inner.events.len() >= self.capacity {
+            match inner.events.iter().position(is_progress) {
+                Some(index) => {
+                    inner.events.remove(index);
+                    self.record_drop();
+                }
+                None if is_progress(&event) => {
+                    self.record_drop();
+                    return Ok(());
+                }
+                None => {}
+            }
+        }
+        let wake = inner.events.is_empty();
+        inner.events.push_back(event);
+        drop(inner);
+        if wake {
+            let _ = self.waker.send(());
+        }
+        Ok(())
+    }
+
+    /// Takes all the queued events, oldest first.
+    pub(crate) fn take(&self) -> VecDeque<ClientEvent> {
+        std::mem::take(&mut self.inner.lock().unwrap().events)
+    }
+
+    /// Drops the queued events and refuses later ones.
+    pub(crate) fn close(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.closed = true;
+        inner.events.clear();
+    }
+
+    /// Returns the number of progress events dropped so far.
+    pub(crate) fn drops(&self) -> u64 {
+        self.drops.load(Ordering::Relaxed)
+    }
+
+    fn record_drop(&self) {
+        let drops = self.drops.fetch_add(1, Ordering::Relaxed) + 1;
+        if drops % DROPS_REPORT_THRESHOLD == 0 {
+            error!(
+                "client {} stalled, {} progress events dropped",
+                self.pid, drops
+            );
+            sys_event!(
+                ExecFault,
+                DfxCode::UDS_FAULT_05,
+                &format!(
+                    "client {} stalled, {} progress events dropped",
+                    self.pid, drops
+                )
+            );
+        }
+    }
+}
+
+fn is_progress(event: &ClientEvent) -> bool {
+    matches!(
+        event,
+        ClientEvent::SendNotifyData(SubscribeType::Progress, _)
+    )
+}
+
+#[cfg(test)]
+mod ut_queue {
+    include!("../../../tests/ut/service/client/ut_queue.rs");
+}
@@ -0,0 +1,91 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Task changes since a revision for syncing clients.
+//!
+//! This module provides the method returning which tasks of the calling
+//! application were created, changed or removed since the revision of its
+//! last sync, see [`RequestDb::changed_since`].
+//!
+//! [`RequestDb::changed_since`]: crate::manage::database::RequestDb::changed_since
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::events::TaskManagerEvent;
+use crate::service::RequestServiceStub;
+
+impl RequestServiceStub {
+    /// Returns the tasks of the caller changed since a revision.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the revision of the last sync
+    /// * `reply` - Message parcel to write the changes to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the changes were written
+    /// * `Err(IpcStatusCode::Failed)` - If the task manager could not be
+    ///   reached
+    ///
+    /// # Notes
+    ///
+    /// The reply holds the revision to sync from next, whether the caller
+    /// has to fetch all its tasks again, the number of changed tasks followed
+    /// by each task ID and revision, and the same for removed tasks.
+    pub(crate) fn changed_since(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let revision: u64 = data.read()?;
+        let uid = ipc::Skeleton::calling_uid();
+        info!("Service changed since {}, uid {}", revision, uid);
+
+        let (event, rx) = TaskManagerEvent::changed_since(uid, revision);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("End Service changed since, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A59,
+                "End Service changed since, failed: send event failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let Some(set) = rx.get() else {
+            error!("End Service changed since, failed: receives changes failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A59,
+                "End Service changed since, failed: receives changes failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&set.revision)?;
+        reply.write(&set.resync)?;
+        for tasks in [&set.changed, &set.removed] {
+            reply.write(&(tasks.len() as u32))?;
+            for (task_id, revision) in tasks {
+                reply.write(&task_id.to_string())?;
+                reply.write(revision)?;
+            }
+        }
+        Ok(())
+    }
+}
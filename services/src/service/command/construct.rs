@@ -23,10 +23,13 @@ use crate::config::Mode;
 use crate::error::ErrorCode;
 use crate::manage::events::TaskManagerEvent;
 use crate::service::command::{set_code_with_index_other, CONSTRUCT_MAX};
+use crate::service::construct_limiter::{Admission, ConstructLimiter};
+use crate::service::interface::CONSTRUCT_WITH_OPTIONS;
 use crate::service::notification_bar::{NotificationConfig, NotificationDispatcher};
 use crate::service::permission::PermissionChecker;
 use crate::service::RequestServiceStub;
 use crate::task::config::TaskConfig;
+use crate::utils::url_rewrite::UrlRewriter;
 use crate::utils::{check_permission, is_system_api};
 
 impl RequestServiceStub {
@@ -63,8 +66,10 @@ impl RequestServiceStub {
         }
         // Read the number of tasks to construct
         let len: u32 = data.read()?;
+        // Newer clients follow each task with its options
+        let with_options = len & CONSTRUCT_WITH_OPTIONS != 0;
         // Convert to usize for array indexing and comparison
-        let len = len as usize;
+        let len = (len & !CONSTRUCT_WITH_OPTIONS) as usize;
 
         // Validate the number of tasks against the maximum allowed
         if len > CONSTRUCT_MAX {
@@ -75,15 +80,40 @@ impl RequestServiceStub {
 
         // Get caller information for permission checks and task association
         let uid = ipc::Skeleton::calling_uid();
+
+        // Reject apps constructing tasks faster than the configured rate
+        if let Admission::Throttled { report } =
+            ConstructLimiter::get_instance().admit(uid, len as u32)
+        {
+            info!("Service construct: uid {} throttled", uid);
+            if report {
+                sys_event!(
+                    ExecError,
+                    DfxCode::INVALID_IPC_MESSAGE_A51,
+                    &format!("Service construct: uid {} throttled", uid)
+                );
+            }
+            reply.write(&(ErrorCode::ConstructThrottled as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+
         let token_id = ipc::Skeleton::calling_full_token_id();
         let pid = ipc::Skeleton::calling_pid();
         // Initialize results vector with default error values
         let mut vec = vec![(ErrorCode::Other, 0u32); len];
+        // Whether each task is an active duplicate returned by the warn policy
+        let mut duplicates = vec![false; len];
 
         // Check if this is a system API call and if notification permissions exist
         let is_system_api = is_system_api();
         let notification_permission = 
             check_permission("ohos.permission.REQUEST_DISABLE_NOTIFICATION");
+        let keep_awake_permission = PermissionChecker::check_keep_awake();
+        // Tasks are only created while the client can be subscribed to them,
+        // so a client reopening its channel and sending the call again does
+        // not leave the tasks of the first call behind. The channel is only
+        // closed again once the client process is gone.
+        let channel_open = self.client_manager.channel_open(pid);
 
         for i in 0..len {
             // Read both configurations before processing to ensure complete data retrieval
@@ -91,7 +121,7 @@ impl RequestServiceStub {
             let notification_config = data.read::<NotificationConfig>();
 
             // Validate task configuration
-            let task_config = match task_config {
+            let mut task_config = match task_config {
                 Ok(config) => config,
                 Err(e) => {
                     // Set error code for this task and continue to next task
@@ -100,6 +130,16 @@ impl RequestServiceStub {
                     continue;
                 }
             };
+            // Loopback tasks and options such as insecure_skip_verify are
+            // only accepted from system applications
+            task_config.privileged = is_system_api;
+            if with_options {
+                if let Err(e) = task_config.read_options(data) {
+                    set_code_with_index_other(&mut vec, i, ErrorCode::ParameterCheck);
+                    error!("task options read err, {}, {}", i, e);
+                    continue;
+                }
+            }
 
             // Validate notification configuration
             let mut notification_config = match notification_config {
@@ -111,6 +151,22 @@ impl RequestServiceStub {
                 }
             };
 
+            if !channel_open {
+                error!("Service construct: channel not open, pid {}", pid);
+                set_code_with_index_other(&mut vec, i, ErrorCode::ChannelNotOpen);
+                continue;
+            }
+
+            // Only callers allowed to hold the running lock may keep the device awake
+            if task_config.keep_awake && !keep_awake_permission {
+                error!("Service construct: keep_awake without permission, {}", i);
+                set_code_with_index_other(&mut vec, i, ErrorCode::Permission);
+                continue;
+            }
+
+            // Resolve the URL actually requested before the task is verified
+            UrlRewriter::get_instance().apply(&mut task_config);
+
             debug!("Service construct: task_config constructed");
             // Extract task mode for notification configuration
             let mode = task_config.common_data.mode;
@@ -138,7 +194,10 @@ impl RequestServiceStub {
 
             // Extract task ID or handle construction error
             let task_id = match ret {
-                Ok(id) => id,
+                Ok(created) => {
+                    duplicates[i] = created.duplicate;
+                    created.task_id
+                }
                 Err(err_code) => {
                     error!("End Service construct, failed: {:?}", err_code);
                     sys_event!(
@@ -199,6 +258,10 @@ impl RequestServiceStub {
             reply.write(&(c as i32))?;
             reply.write(&tid)?;
         }
+        // Duplicate flags follow all results, so older clients ignore them
+        for duplicate in duplicates {
+            reply.write(&duplicate)?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,123 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Aggregate task counts for the request service.
+//!
+//! This module provides the method returning the number of tasks per
+//! application and state, as shown by quota and settings pages.
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::events::TaskManagerEvent;
+use crate::manage::state_counts::count_scope;
+use crate::service::RequestServiceStub;
+use crate::utils::is_system_api;
+
+impl RequestServiceStub {
+    /// Counts tasks per application and state.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing whether all applications are
+    ///   counted, whether a uid follows and the uid
+    /// * `reply` - Message parcel to write the counts to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the counts were written
+    /// * `Err(IpcStatusCode::Failed)` - If the caller may not count the
+    ///   requested tasks or the task manager could not be reached
+    ///
+    /// # Notes
+    ///
+    /// * Counting all applications or another application than the caller
+    ///   requires a system API caller
+    /// * The reply holds the number of applications followed by each uid and
+    ///   its counts of initialized, waiting, running, retrying, paused,
+    ///   stopped, completed, failed and removed tasks
+    pub(crate) fn count_by_state(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let all: bool = data.read()?;
+        let has_uid: bool = data.read()?;
+        let uid: u64 = data.read()?;
+        let uid = has_uid.then_some(uid);
+        let calling_uid = ipc::Skeleton::calling_uid();
+
+        let uid = match count_scope(all, uid, calling_uid, is_system_api()) {
+            Ok(uid) => uid,
+            Err(code) => {
+                error!(
+                    "Service count by state, failed: uid {} may not count {:?}",
+                    calling_uid, uid
+                );
+                sys_event!(
+                    ExecError,
+                    DfxCode::INVALID_IPC_MESSAGE_A47,
+                    &format!(
+                        "Service count by state, failed: uid {} may not count {:?}",
+                        calling_uid, uid
+                    )
+                );
+                reply.write(&(code as i32))?;
+                return Err(IpcStatusCode::Failed);
+            }
+        };
+
+        let (event, rx) = TaskManagerEvent::count_by_state(uid);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("End Service count by state, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A47,
+                "End Service count by state, failed: send event failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let Some(counts) = rx.get() else {
+            error!("End Service count by state, failed: receives counts failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A47,
+                "End Service count by state, failed: receives counts failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&(counts.len() as u32))?;
+        for (uid, counts) in counts {
+            reply.write(&uid)?;
+            for count in [
+                counts.initialized,
+                counts.waiting,
+                counts.running,
+                counts.retrying,
+                counts.paused,
+                counts.stopped,
+                counts.completed,
+                counts.failed,
+                counts.removed,
+            ] {
+                reply.write(&count)?;
+            }
+        }
+        Ok(())
+    }
+}
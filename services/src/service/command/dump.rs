@@ -18,17 +18,50 @@
 
 use std::fs::File;
 use std::io::Write;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use ipc::IpcResult;
+use request_utils::journal::parse_journal;
 
+use crate::manage::database::RequestDb;
 use crate::manage::events::TaskManagerEvent;
+use crate::manage::journal::DiagnosticJournal;
+use crate::manage::keep_awake::KeepAwake;
+use crate::manage::progress_writer::ProgressWriter;
+use crate::service::construct_limiter::ConstructLimiter;
+use crate::service::notification_bar::NotificationDispatcher;
 use crate::service::RequestServiceStub;
+use crate::task::dns_cache::DnsCache;
+use crate::task::keep_alive::KeepAlivePool;
+use crate::task::request_task::MAX_RESPONSE_HEADERS;
+use crate::task::trace_context;
 
 /// Help message displayed when the dump command is used incorrectly or with `-h` flag.
 const HELP_MSG: &str = "usage:\n\
                          -h                    help text for the tool\n\
                          -t [taskid]           without taskid: display all task summary info; \
-                         taskid: display one task detail info\n";
+                         taskid: display one task detail info\n\
+                         -m                    run database maintenance now\n\
+                         -w [uid]              without uid: count waiting tasks per cause; \
+                         uid: count waiting tasks of one app per cause\n\
+                         -r                    check again whether waiting tasks may run\n\
+                         -l                    display construct rate limits per uid\n\
+                         -c on|off             run background tasks only while charging\n\
+                         -g seconds            keep removed tasks restorable for seconds\n\
+                         -j uid                display the diagnostic journal of one app\n\
+                         -n                    display notifications deferred while the \
+                         notification service was unavailable\n\
+                         -e taskid             display the effective configuration of one task\n\
+                         -x count              process at most count headers of a response\n\
+                         -q                    display progress events dropped for clients \
+                         not reading their messages\n\
+                         -f                    flush the shared DNS cache\n\
+                         -p uid on|off         trace the requests of one app\n\
+                         -s count              reserve count top-tier run slots for \
+                         foreground tasks\n\
+                         -b                    display batched progress writes\n\
+                         -o                    display open client channels\n";
 impl RequestServiceStub {
     /// Dumps task information to a file based on provided arguments.
     ///
@@ -47,6 +80,22 @@ impl RequestServiceStub {
     /// - `-h`: Display help message
     /// - `-t`: Dump summary information for all tasks
     /// - `-t [taskid]`: Dump detailed information for a specific task
+    /// - `-m`: Run database maintenance and dump its report
+    /// - `-w [uid]`: Dump the number of waiting tasks per waiting cause
+    /// - `-r`: Re-evaluate waiting tasks and dump how many may run now
+    /// - `-l`: Dump the construct rate limit state of each application
+    /// - `-c on|off`: Require charging for background tasks or not
+    /// - `-g seconds`: Set the grace period of tasks removed with one
+    /// - `-j uid`: Dump the diagnostic journal of an application
+    /// - `-n`: Dump the counters of deferred notifications
+    /// - `-e taskid`: Dump the effective configuration of a task
+    /// - `-x count`: Set the maximum number of response headers processed
+    /// - `-q`: Dump the number of progress events dropped for each client
+    /// - `-f`: Flush the shared DNS cache
+    /// - `-p uid on|off`: Generate trace contexts for the tasks of an application or not
+    /// - `-s count`: Set the number of top-tier run slots reserved for foreground tasks
+    /// - `-b`: Dump the batch sizes and latencies of progress writes
+    /// - `-o`: Dump the number of open client channels and their maximum
     pub(crate) fn dump(&self, mut file: File, args: Vec<String>) -> IpcResult<()> {
         info!("Service dump");
 
@@ -57,6 +106,130 @@ impl RequestServiceStub {
             return Ok(());
         }
 
+        if args[0] == "-m" {
+            self.dump_maintenance(file);
+            return Ok(());
+        }
+
+        if args[0] == "-w" {
+            match args.get(1).map(|uid| uid.parse::<u64>()) {
+                None => self.dump_waiting_breakdown(file, None),
+                Some(Ok(uid)) => self.dump_waiting_breakdown(file, Some(uid)),
+                Some(Err(_)) => {
+                    let _ = file.write("-w accept a number".as_bytes());
+                }
+            }
+            return Ok(());
+        }
+
+        if args[0] == "-r" {
+            self.dump_reevaluate_waiting(file);
+            return Ok(());
+        }
+
+        if args[0] == "-l" {
+            self.dump_construct_limits(file);
+            return Ok(());
+        }
+
+        if args[0] == "-c" {
+            match args.get(1).map(String::as_str) {
+                Some("on") => self.dump_require_charging(file, true),
+                Some("off") => self.dump_require_charging(file, false),
+                _ => {
+                    let _ = file.write("-c accept on or off".as_bytes());
+                }
+            }
+            return Ok(());
+        }
+
+        if args[0] == "-g" {
+            match args.get(1).map(|secs| secs.parse::<u64>()) {
+                Some(Ok(secs)) => self.dump_removal_grace(file, Duration::from_secs(secs)),
+                _ => {
+                    let _ = file.write("-g accept a number".as_bytes());
+                }
+            }
+            return Ok(());
+        }
+
+        if args[0] == "-j" {
+            match args.get(1).map(|uid| uid.parse::<u64>()) {
+                Some(Ok(uid)) => self.dump_journal(file, uid),
+                _ => {
+                    let _ = file.write("-j accept a number".as_bytes());
+                }
+            }
+            return Ok(());
+        }
+
+        if args[0] == "-n" {
+            self.dump_deferred_notifications(file);
+            return Ok(());
+        }
+
+        if args[0] == "-e" {
+            match args.get(1).map(|task_id| task_id.parse::<u32>()) {
+                Some(Ok(task_id)) => self.dump_effective_config(file, task_id),
+                _ => {
+                    let _ = file.write("-e accept a number".as_bytes());
+                }
+            }
+            return Ok(());
+        }
+
+        if args[0] == "-x" {
+            match args.get(1).map(|count| count.parse::<usize>()) {
+                Some(Ok(count)) if count > 0 => self.dump_max_response_headers(file, count),
+                _ => {
+                    let _ = file.write("-x accept a positive number".as_bytes());
+                }
+            }
+            return Ok(());
+        }
+
+        if args[0] == "-q" {
+            self.dump_queue_drops(file);
+            return Ok(());
+        }
+
+        if args[0] == "-b" {
+            self.dump_progress_writes(file);
+            return Ok(());
+        }
+
+        if args[0] == "-o" {
+            self.dump_channels(file);
+            return Ok(());
+        }
+
+        if args[0] == "-f" {
+            self.dump_flush_dns_cache(file);
+            return Ok(());
+        }
+
+        if args[0] == "-p" {
+            let uid = args.get(1).map(|uid| uid.parse::<u64>());
+            match (uid, args.get(2).map(String::as_str)) {
+                (Some(Ok(uid)), Some("on")) => self.dump_uid_tracing(file, uid, true),
+                (Some(Ok(uid)), Some("off")) => self.dump_uid_tracing(file, uid, false),
+                _ => {
+                    let _ = file.write("-p accept a number and on or off".as_bytes());
+                }
+            }
+            return Ok(());
+        }
+
+        if args[0] == "-s" {
+            match args.get(1).map(|count| count.parse::<usize>()) {
+                Some(Ok(count)) => self.dump_foreground_reserve(file, count),
+                _ => {
+                    let _ = file.write("-s accept a number".as_bytes());
+                }
+            }
+            return Ok(());
+        }
+
         // Validate that the first argument is `-t`
         if args[0] != "-t" {
             let _ = file.write("invalid args".as_bytes());
@@ -93,7 +266,8 @@ impl RequestServiceStub {
     ///
     /// # Notes
     ///
-    /// Writes a table with columns for task ID, action, state, and reason.
+    /// Writes a table with columns for task ID, action, state, and reason,
+    /// followed by the lookup counters of the shared DNS cache.
     fn dump_all_task_info(&self, mut file: File) {
         info!("Service dump all task info");
 
@@ -136,6 +310,35 @@ impl RequestServiceStub {
                 );
             }
         }
+        let stats = DnsCache::get_instance().stats();
+        let _ = file.write(
+            format!(
+                "dns hits: {}\ndns misses: {}\ndns negatives: {}\n",
+                stats.hits.load(Ordering::Relaxed),
+                stats.misses.load(Ordering::Relaxed),
+                stats.negatives.load(Ordering::Relaxed)
+            )
+            .as_bytes(),
+        );
+        let pool = KeepAlivePool::get_instance();
+        let stats = pool.stats();
+        let _ = file.write(
+            format!(
+                "keep-alive idle: {}\nkeep-alive reused: {}\nkeep-alive expired: {}\n",
+                pool.idle(),
+                stats.reused.load(Ordering::Relaxed),
+                stats.expired.load(Ordering::Relaxed)
+            )
+            .as_bytes(),
+        );
+        let (held, holders) = KeepAwake::get_instance().state();
+        let _ = file.write(
+            format!(
+                "keep-awake held: {}\nkeep-awake tasks: {:?}\n",
+                held, holders
+            )
+            .as_bytes(),
+        );
     }
 
     /// Dumps detailed information for a specific task to the provided file.
@@ -186,4 +389,364 @@ impl RequestServiceStub {
             let _ = file.write(format!("invalid task id {}", task_id).as_bytes());
         }
     }
+
+    /// Runs database maintenance and writes its report to the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the maintenance report to.
+    fn dump_maintenance(&self, mut file: File) {
+        info!("Service dump maintenance");
+
+        let (event, rx) = TaskManagerEvent::maintain();
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            return;
+        }
+        let report = match rx.get() {
+            Some(report) => report,
+            None => {
+                error!("Service dump: receives maintenance report failed");
+                sys_event!(
+                    ExecFault,
+                    DfxCode::UDS_FAULT_03,
+                    "Service dump: receives maintenance report failed"
+                );
+                return;
+            }
+        };
+        let _ = file.write(
+            format!(
+                "pruned rows: {}\nreclaimed pages: {}\ninterrupted: {}\n",
+                report.pruned_rows, report.reclaimed_pages, report.interrupted
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Writes the number of waiting tasks per waiting cause to the provided
+    /// file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the counts to.
+    /// * `uid` - Restricts the counts to one application, `None` counts all.
+    fn dump_waiting_breakdown(&self, mut file: File, uid: Option<u64>) {
+        info!("Service dump waiting breakdown");
+
+        let (event, rx) = TaskManagerEvent::waiting_breakdown(uid);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            return;
+        }
+        let Some(breakdown) = rx.get() else {
+            error!("Service dump: receives waiting breakdown failed");
+            return;
+        };
+        let _ = file.write(
+            format!(
+                "task queue: {}\nnetwork: {}\napp state: {}\nuser state: {}\nrate limit: {}\n\
+                 battery: {}\ncaptive portal: {}\ndevice condition: {}\n",
+                breakdown.task_queue,
+                breakdown.network,
+                breakdown.app_state,
+                breakdown.user_state,
+                breakdown.rate_limit,
+                breakdown.battery,
+                breakdown.captive_portal,
+                breakdown.device_condition
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Re-evaluates waiting tasks and writes how many may run now to the
+    /// provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the result to.
+    fn dump_reevaluate_waiting(&self, mut file: File) {
+        info!("Service dump reevaluate waiting");
+
+        let (event, rx) = TaskManagerEvent::reevaluate_waiting();
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            return;
+        }
+        let Some(count) = rx.get() else {
+            error!("Service dump: receives reevaluate waiting result failed");
+            return;
+        };
+        let _ = file.write(format!("unblocked tasks: {}\n", count).as_bytes());
+    }
+
+    /// Requires charging for background tasks or lifts the requirement, and
+    /// writes the result to the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the result to.
+    /// * `require_charging` - Whether background tasks wait for charging.
+    fn dump_require_charging(&self, mut file: File, require_charging: bool) {
+        info!("Service dump require charging {}", require_charging);
+
+        let (event, rx) = TaskManagerEvent::require_charging(require_charging);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            return;
+        }
+        if rx.get().is_none() {
+            error!("Service dump: receives require charging result failed");
+            return;
+        }
+        let state = if require_charging { "on" } else { "off" };
+        let _ = file.write(format!("require charging: {}\n", state).as_bytes());
+    }
+
+    /// Sets how long tasks removed with a grace period stay restorable, and
+    /// writes the result to the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the result to.
+    /// * `grace` - The new removal grace period.
+    fn dump_removal_grace(&self, mut file: File, grace: Duration) {
+        info!("Service dump removal grace {:?}", grace);
+
+        let (event, rx) = TaskManagerEvent::set_removal_grace(grace);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            return;
+        }
+        if rx.get().is_none() {
+            error!("Service dump: receives removal grace result failed");
+            return;
+        }
+        let _ = file.write(format!("removal grace: {}s\n", grace.as_secs()).as_bytes());
+    }
+
+    /// Sets the number of top-tier run slots reserved for foreground tasks
+    /// and writes it to the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the new reservation to.
+    /// * `reserve` - The number of reserved slots, 0 disables the reservation.
+    fn dump_foreground_reserve(&self, mut file: File, reserve: usize) {
+        info!("Service dump foreground reserve {}", reserve);
+
+        let (event, rx) = TaskManagerEvent::set_foreground_reserve(reserve);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            return;
+        }
+        if rx.get().is_none() {
+            error!("Service dump: receives foreground reserve result failed");
+            return;
+        }
+        let _ = file.write(format!("foreground reserve: {}\n", reserve).as_bytes());
+    }
+
+    /// Writes the construct rate limit state of each application to the
+    /// provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the states to.
+    fn dump_construct_limits(&self, mut file: File) {
+        info!("Service dump construct limits");
+
+        let states = ConstructLimiter::get_instance().states();
+        let _ = file.write(
+            format!(
+                "{:<20}{:<12}{:<12}{:<12}\n",
+                "uid", "tokens", "throttled", "throttling"
+            )
+            .as_bytes(),
+        );
+        for (uid, state) in states {
+            let _ = file.write(
+                format!(
+                    "{:<20}{:<12}{:<12}{:<12}\n",
+                    uid, state.tokens as u64, state.throttled, state.throttling
+                )
+                .as_bytes(),
+            );
+        }
+    }
+
+    /// Writes the diagnostic journal of an application to the provided file,
+    /// one event per line, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the journal to.
+    /// * `uid` - Uid of the application.
+    fn dump_journal(&self, mut file: File, uid: u64) {
+        info!("Service dump journal, uid {}", uid);
+
+        let records = parse_journal(DiagnosticJournal::get_instance().path(uid));
+        let _ = file.write(
+            format!(
+                "{:<16}{:<16}{:<12}{:<16}{:<8}\n",
+                "time", "task_id", "event", "bytes", "reason"
+            )
+            .as_bytes(),
+        );
+        for record in records {
+            let _ = file.write(
+                format!(
+                    "{:<16}{:<16}{:<12}{:<16}{:<8}\n",
+                    record.timestamp,
+                    record.task_id,
+                    record.event.name(),
+                    record.bytes,
+                    record.reason
+                )
+                .as_bytes(),
+            );
+        }
+    }
+
+    /// Writes the counters of notifications deferred while the notification
+    /// service was unavailable to the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the counters to.
+    fn dump_deferred_notifications(&self, mut file: File) {
+        info!("Service dump deferred notifications");
+
+        let stats = NotificationDispatcher::get_instance().deferred_stats();
+        let _ = file.write(
+            format!(
+                "deferred: {}\nflushed: {}\ndropped: {}\n",
+                stats.deferred.load(Ordering::Relaxed),
+                stats.flushed.load(Ordering::Relaxed),
+                stats.dropped.load(Ordering::Relaxed)
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Writes the effective configuration of a task to the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the configuration to.
+    /// * `task_id` - ID of the task to dump.
+    fn dump_effective_config(&self, mut file: File, task_id: u32) {
+        info!("Service dump effective config, tid {}", task_id);
+
+        let Some(config) = RequestDb::get_instance().query_effective_config(task_id) else {
+            let _ = file.write(format!("task {} never ran\n", task_id).as_bytes());
+            return;
+        };
+        let _ = file.write(
+            format!(
+                "url: {}\nproxy: {}\nspeed_limit: {}\nresume_strategy: {}\nprotocol: {}\nheaders:\n",
+                config.url,
+                config.proxy,
+                config.speed_limit,
+                config.resume_strategy,
+                config.protocol
+            )
+            .as_bytes(),
+        );
+        for (name, value) in config.headers.iter() {
+            let _ = file.write(format!("  {}: {}\n", name, value).as_bytes());
+        }
+    }
+
+    /// Sets the maximum number of response headers a task processes, and
+    /// writes the result to the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the result to.
+    /// * `count` - The new maximum number of response headers.
+    fn dump_max_response_headers(&self, mut file: File, count: usize) {
+        info!("Service dump max response headers {}", count);
+
+        MAX_RESPONSE_HEADERS.store(count, Ordering::Relaxed);
+        let _ = file.write(format!("max response headers: {}\n", count).as_bytes());
+    }
+
+    /// Writes the number of progress events dropped for each client whose
+    /// queue was full to the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the counts to.
+    fn dump_queue_drops(&self, mut file: File) {
+        info!("Service dump queue drops");
+
+        let drops = self.client_manager.queue_drops();
+        let _ = file.write(format!("{:<20}{:<12}\n", "pid", "dropped").as_bytes());
+        for (pid, dropped) in drops {
+            let _ = file.write(format!("{:<20}{:<12}\n", pid, dropped).as_bytes());
+        }
+    }
+
+    /// Writes the number of open client channels and their maximum to the
+    /// provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the counts to.
+    fn dump_channels(&self, mut file: File) {
+        info!("Service dump channels");
+
+        let (open, max) = self.client_manager.channels();
+        let _ = file.write(format!("open channels: {}\nmax channels: {}\n", open, max).as_bytes());
+    }
+
+    /// Writes the batch sizes and latencies of the batched progress writes to
+    /// the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the counters to.
+    fn dump_progress_writes(&self, mut file: File) {
+        info!("Service dump progress writes");
+
+        let stats = ProgressWriter::get_instance().stats();
+        let _ = file.write(
+            format!(
+                "batches: {}\nrows: {}\nlast batch: {}\nmax batch: {}\n\
+                 last latency: {} us\nmax latency: {} us\n",
+                stats.batches,
+                stats.rows,
+                stats.last_batch,
+                stats.max_batch,
+                stats.last_latency_us,
+                stats.max_latency_us
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Drops all entries of the shared DNS cache, and writes the result to
+    /// the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the result to.
+    fn dump_flush_dns_cache(&self, mut file: File) {
+        info!("Service dump flush dns cache");
+
+        DnsCache::get_instance().flush();
+        let _ = file.write("dns cache flushed\n".as_bytes());
+    }
+
+    /// Enables or disables generated trace contexts for the tasks an
+    /// application creates from now on.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - File to write the new policy to.
+    /// * `uid` - User ID of the application.
+    /// * `enabled` - Whether tasks created without a trace context get one.
+    fn dump_uid_tracing(&self, mut file: File, uid: u64, enabled: bool) {
+        info!("Service dump uid {} tracing {}", uid, enabled);
+
+        trace_context::set_uid_tracing(uid, enabled);
+        let state = if enabled { "on" } else { "off" };
+        let _ = file.write(format!("uid {} tracing: {}\n", uid, state).as_bytes());
+    }
 }
@@ -0,0 +1,114 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Effective configuration query for tasks.
+//!
+//! Returns the configuration a task runs with after the adjustments of the
+//! service, see [`EffectiveConfig`].
+//!
+//! [`EffectiveConfig`]: crate::task::effective::EffectiveConfig
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::database::RequestDb;
+use crate::service::permission::PermissionChecker;
+use crate::service::RequestServiceStub;
+
+impl RequestServiceStub {
+    /// Queries the effective configuration of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the task ID to query
+    /// * `reply` - Message parcel to write the result to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the configuration was written to the reply
+    /// * `Err(IpcStatusCode::Failed)` - If the task can not be queried
+    ///
+    /// # Errors
+    ///
+    /// Returns error codes in the reply parcel:
+    /// * `ErrOk` - Followed by the URL, the number of headers and each header
+    ///   name and value, the proxy, the speed limit, the resume strategy and
+    ///   the protocol
+    /// * `TaskNotFound` - Invalid task ID, task not owned by the caller or
+    ///   task never ran
+    ///
+    /// # Notes
+    ///
+    /// Requires `DOWNLOAD_SESSION_MANAGER` permission to query tasks
+    /// belonging to other UIDs.
+    pub(crate) fn query_effective_config(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let task_id: String = data.read()?;
+        info!("Service query effective config tid {}", task_id);
+
+        let Ok(task_id) = task_id.parse::<u32>() else {
+            error!("End Service query effective config, failed: task_id not valid");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A55,
+                "End Service query effective config, failed: task_id not valid"
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        let uid = ipc::Skeleton::calling_uid();
+        if !PermissionChecker::check_down_permission() && !self.check_task_uid(task_id, uid) {
+            error!(
+                "End Service query effective config, tid: {}, failed: task not belong to uid",
+                task_id
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A55,
+                &format!(
+                    "End Service query effective config, tid: {}, failed: task not belong to uid",
+                    task_id
+                )
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+
+        let Some(config) = RequestDb::get_instance().query_effective_config(task_id) else {
+            error!(
+                "End Service query effective config, tid: {}, failed: task never ran",
+                task_id
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&config.url)?;
+        reply.write(&(config.headers.len() as u32))?;
+        for (name, value) in config.headers.iter() {
+            reply.write(name)?;
+            reply.write(value)?;
+        }
+        reply.write(&config.proxy)?;
+        reply.write(&config.speed_limit)?;
+        reply.write(&config.resume_strategy)?;
+        reply.write(&config.protocol)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Explanation of why a task is in its current state.
+//!
+//! See [`Scheduler::explain`]. Only the application owning the task gets the
+//! conditions, retry count and next action of the task, callers querying it
+//! with the `DOWNLOAD_SESSION_MANAGER` permission get its state and reason.
+//!
+//! [`Scheduler::explain`]: crate::manage::scheduler::Scheduler::explain
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::events::TaskManagerEvent;
+use crate::service::permission::PermissionChecker;
+use crate::service::RequestServiceStub;
+
+impl RequestServiceStub {
+    /// Explains why a task is in its current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the task ID to explain
+    /// * `reply` - Message parcel to write the explanation to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the explanation was written to the reply
+    /// * `Err(IpcStatusCode::Failed)` - If the task can not be explained
+    ///
+    /// # Errors
+    ///
+    /// Returns error codes in the reply parcel:
+    /// * `ErrOk` - Followed by the state, the reason code and description,
+    ///   whether the task waits and for what, and whether details follow:
+    ///   the network, foreground, battery and storage conditions, the retry
+    ///   count and the next action time
+    /// * `TaskNotFound` - Invalid task ID or task not owned by the caller
+    /// * `Other` - The task manager could not be reached
+    pub(crate) fn explain_task(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let task_id: String = data.read()?;
+        info!("Service explain tid {}", task_id);
+
+        let Ok(task_id) = task_id.parse::<u32>() else {
+            error!("End Service explain, failed: task_id not valid");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A58,
+                "End Service explain, failed: task_id not valid"
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        let uid = ipc::Skeleton::calling_uid();
+        let owner = self.check_task_uid(task_id, uid);
+        if !owner && !PermissionChecker::check_down_permission() {
+            error!(
+                "End Service explain, tid: {}, failed: task not belong to uid",
+                task_id
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A58,
+                &format!(
+                    "End Service explain, tid: {}, failed: task not belong to uid",
+                    task_id
+                )
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+
+        let (event, rx) = TaskManagerEvent::explain(task_id);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("End Service explain, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A58,
+                "End Service explain, failed: send event failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let Some(explanation) = rx.get() else {
+            error!("End Service explain, failed: receives explanation failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A58,
+                "End Service explain, failed: receives explanation failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+        let Some(explanation) = explanation else {
+            error!(
+                "End Service explain, tid: {}, failed: task not found",
+                task_id
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&(explanation.state.repr as u32))?;
+        reply.write(&(explanation.reason.repr as u32))?;
+        reply.write(&explanation.reason.to_str().to_string())?;
+        reply.write(&explanation.waiting_cause.is_some())?;
+        if let Some(cause) = explanation.waiting_cause {
+            reply.write(&(cause as u32))?;
+        }
+        reply.write(&owner)?;
+        if owner {
+            reply.write(&explanation.network)?;
+            reply.write(&explanation.foreground)?;
+            reply.write(&explanation.battery)?;
+            reply.write(&explanation.storage)?;
+            reply.write(&explanation.tries)?;
+            reply.write(&explanation.next_action_time)?;
+        }
+        Ok(())
+    }
+}
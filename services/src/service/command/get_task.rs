@@ -20,8 +20,11 @@ use ipc::parcel::MsgParcel;
 use ipc::{IpcResult, IpcStatusCode};
 
 use crate::error::ErrorCode;
+use crate::manage::database::RequestDb;
 use crate::manage::query;
+use crate::service::task_token::{TaskTokens, TokenCheck, TokenScope};
 use crate::service::{serialize_task_config, RequestServiceStub};
+use crate::task::files::check_current_account;
 
 impl RequestServiceStub {
     /// Retrieves task configuration and subscribes client to task updates.
@@ -72,16 +75,34 @@ impl RequestServiceStub {
             return Err(IpcStatusCode::Failed);
         };
 
-        // Verify calling process has permission to access this task
         let uid = ipc::Skeleton::calling_uid();
 
-        if !self.check_task_uid(task_id, uid) {
+        // Read authentication token, a signed token grants access to processes
+        // other than the owner
+        let token: String = data.read()?;
+        let token = match TaskTokens::get_instance().check(token, task_id, TokenScope::Full) {
+            TokenCheck::Granted => None,
+            TokenCheck::Legacy(token) => Some(token),
+            TokenCheck::Denied => {
+                reply.write(&(ErrorCode::TaskNotFound as i32))?;
+                return Err(IpcStatusCode::Failed);
+            }
+        };
+
+        // Verify the caller owns the task, a signed token is shared with other
+        // processes of the account the task belongs to only
+        let allowed = match token {
+            Some(_) => self.check_task_uid(task_id, uid),
+            None => RequestDb::get_instance()
+                .query_task_uid(task_id)
+                .is_some_and(check_current_account),
+        };
+        if !allowed {
             reply.write(&(ErrorCode::TaskNotFound as i32))?;
             return Err(IpcStatusCode::Failed);
         }
 
-        // Read authentication token and retrieve task configuration
-        let token: String = data.read()?;
+        // Retrieve task configuration
         let Some(config) = query::get_task(task_id, token) else {
             error!(
                 "End Service getTask, tid: {}, failed: task_id or token not found",
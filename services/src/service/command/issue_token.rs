@@ -0,0 +1,125 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signed task tokens for the request service.
+//!
+//! This module provides the method issuing a signed token for a task of the
+//! caller, which it may hand to another process to share the task.
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::events::TaskManagerEvent;
+use crate::service::task_token::TokenScope;
+use crate::service::RequestServiceStub;
+
+impl RequestServiceStub {
+    /// Issues a signed token for a task of the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the task ID, the scope and the
+    ///   lifetime of the token in milliseconds
+    /// * `reply` - Message parcel to write the token to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the token was written
+    /// * `Err(IpcStatusCode::Failed)` - If the parameters are invalid or the
+    ///   task is not found
+    ///
+    /// # Notes
+    ///
+    /// * Only the owner of a task may issue tokens for it
+    /// * A lifetime of 0 issues a token valid for an hour
+    pub(crate) fn issue_task_token(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let task_id: String = data.read()?;
+        let scope: u32 = data.read()?;
+        let ttl_ms: u64 = data.read()?;
+        info!("Service issue task token tid {}", task_id);
+
+        let Ok(task_id) = task_id.parse::<u32>() else {
+            error!(
+                "End Service issue task token, failed: tid not valid: {}",
+                task_id
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A62,
+                &format!(
+                    "End Service issue task token, failed: tid not valid: {}",
+                    task_id
+                )
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+        let Some(scope) = u8::try_from(scope).ok().and_then(TokenScope::from_repr) else {
+            error!(
+                "End Service issue task token, failed: scope not valid: {}",
+                scope
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A62,
+                &format!(
+                    "End Service issue task token, failed: scope not valid: {}",
+                    scope
+                )
+            );
+            reply.write(&(ErrorCode::ParameterCheck as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        let uid = ipc::Skeleton::calling_uid();
+        let (event, rx) = TaskManagerEvent::issue_token(uid, task_id, scope, ttl_ms);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("End Service issue task token, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A62,
+                "End Service issue task token, failed: send event failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let token = match rx.get() {
+            Some(token) if !token.is_empty() => token,
+            _ => {
+                error!(
+                    "End Service issue task token, failed: task not found {}",
+                    task_id
+                );
+                sys_event!(
+                    ExecError,
+                    DfxCode::INVALID_IPC_MESSAGE_A62,
+                    &format!(
+                        "End Service issue task token, failed: task not found {}",
+                        task_id
+                    )
+                );
+                reply.write(&(ErrorCode::TaskNotFound as i32))?;
+                return Err(IpcStatusCode::Failed);
+            }
+        };
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&token)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Enabling the diagnostic journal of an application.
+//!
+//! System applications may journal the task events of any application to a
+//! file of the service, for devices without log access. See
+//! [`DiagnosticJournal`].
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::journal::{DiagnosticJournal, MAX_JOURNAL_CAP, MIN_JOURNAL_CAP};
+use crate::service::RequestServiceStub;
+use crate::utils::is_system_api;
+
+impl RequestServiceStub {
+    /// Enables or disables the diagnostic journal of an application.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the uid of the application and
+    ///   the size cap of its journal in bytes, 0 to disable it
+    /// * `reply` - Message parcel to write operation result to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the journal was enabled or disabled
+    /// * `Err(IpcStatusCode::Failed)` - If the caller is not a system
+    ///   application, the cap is out of range or the journal can not be opened
+    ///
+    /// # Errors
+    ///
+    /// Returns error codes in the reply parcel:
+    /// * `ErrOk` - Journal enabled or disabled
+    /// * `SystemApi` - Caller is not a system application
+    /// * `ParameterCheck` - Cap is neither 0 nor within the allowed range
+    /// * `Other` - Journal file can not be opened
+    pub(crate) fn enable_journal(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        info!("Service enable_journal");
+        if !is_system_api() {
+            error!("End Service enable_journal, failed: not system api");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A54,
+                "End Service enable_journal, failed: not system api"
+            );
+            reply.write(&(ErrorCode::SystemApi as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+
+        let uid: u64 = data.read()?;
+        let cap: u64 = data.read()?;
+        if cap != 0 && !(MIN_JOURNAL_CAP..=MAX_JOURNAL_CAP).contains(&cap) {
+            error!("End Service enable_journal, failed: cap not valid: {}", cap);
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A54,
+                &format!("End Service enable_journal, failed: cap not valid: {}", cap)
+            );
+            reply.write(&(ErrorCode::ParameterCheck as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+
+        if let Err(e) = DiagnosticJournal::get_instance().enable(uid, cap) {
+            error!("End Service enable_journal, uid: {}, failed: {}", uid, e);
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A54,
+                &format!("End Service enable_journal, uid: {}, failed: {}", uid, e)
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Migration of legacy downloads for the request service.
+//!
+//! This module provides the method rewriting the API9 downloads of an
+//! application as API10 agent tasks, so they can be controlled through the
+//! agent interface without being created again.
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::events::TaskManagerEvent;
+use crate::service::RequestServiceStub;
+use crate::utils::is_system_api;
+
+impl RequestServiceStub {
+    /// Rewrites the legacy downloads of an application as API10 tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the uid of the application
+    /// * `reply` - Message parcel to write the migration report to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the report was written
+    /// * `Err(IpcStatusCode::Failed)` - If the caller is not a system
+    ///   application or the task manager could not be reached
+    ///
+    /// # Notes
+    ///
+    /// * The reply holds the number of migrated tasks followed by their IDs,
+    ///   then the number of skipped tasks followed by each ID and the reason
+    ///   it was skipped
+    /// * Migrating again reports only the legacy tasks left
+    pub(crate) fn migrate_legacy_tasks(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        info!("Service migrate legacy tasks");
+        if !is_system_api() {
+            error!("End Service migrate legacy tasks, failed: not system api");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A61,
+                "End Service migrate legacy tasks, failed: not system api"
+            );
+            reply.write(&(ErrorCode::SystemApi as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let uid: u64 = data.read()?;
+
+        let (event, rx) = TaskManagerEvent::migrate_legacy_tasks(uid);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("End Service migrate legacy tasks, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A61,
+                "End Service migrate legacy tasks, failed: send event failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let Some(report) = rx.get() else {
+            error!("End Service migrate legacy tasks, failed: receives report failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A61,
+                "End Service migrate legacy tasks, failed: receives report failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&(report.migrated.len() as u32))?;
+        for task_id in report.migrated {
+            reply.write(&task_id.to_string())?;
+        }
+        reply.write(&(report.skipped.len() as u32))?;
+        for (task_id, reason) in report.skipped {
+            reply.write(&task_id.to_string())?;
+            reply.write(&(reason as u32))?;
+        }
+        Ok(())
+    }
+}
@@ -19,9 +19,16 @@
 
 use crate::error::ErrorCode;
 
+mod changed_since;  // Task changes for syncing clients
 mod construct;      // Task creation and configuration
+mod count_by_state; // Per-state task counts
 mod dump;           // Task information dumping utilities
+mod effective_config; // Effective configuration of tasks
+mod explain;        // Why tasks are in their state
 mod get_task;       // Task configuration retrieval
+mod issue_token;    // Signed tokens for sharing tasks
+mod journal;        // Diagnostic journal of task events
+mod migrate_legacy; // Migration of legacy downloads to API10 tasks
 mod notification_bar; // Notification system integration
 mod open_channel;   // Channel establishment for data transfer
 mod pause;          // Task pause operations
@@ -30,13 +37,18 @@ mod query_mime_type; // MIME type detection for resources
 mod remove;         // Task deletion operations
 mod resume;         // Task resumption operations
 mod search;         // Task searching functionality
+mod search_system;  // Search across applications for system agents
+mod set_destination; // Destination change of paused downloads
 mod set_max_speed;  // Bandwidth control for tasks
 mod set_mode;       // Task execution mode configuration
 mod show;           // Task visibility management
+mod speed_history;  // Speed history of tasks
 mod start;          // Task start operations
 mod stop;           // Task termination operations
 mod sub_runcount;   // Running count subscription
 mod subscribe;      // Task event subscription
+mod task_list;      // Task list change subscription
+mod trash;          // Restoring and purging tasks pending removal
 mod touch;          // Task metadata updates
 mod unsub_runcount; // Running count unsubscription
 mod unsubscribe;    // Task event unsubscription
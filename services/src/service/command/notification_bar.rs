@@ -25,7 +25,7 @@ use crate::config::Action;
 use crate::error::ErrorCode;
 use crate::manage::database::RequestDb;
 use crate::manage::events::TaskManagerEvent;
-use crate::service::notification_bar::NotificationDispatcher;
+use crate::service::notification_bar::{GroupProgressStyle, NotificationDispatcher};
 use crate::service::permission::{ManagerPermission, PermissionChecker};
 use crate::service::RequestServiceStub;
 use crate::utils::{check_permission, is_system_api};
@@ -39,7 +39,8 @@ impl RequestServiceStub {
     /// # Arguments
     ///
     /// * `data` - Input parcel containing group configuration (gauge visibility, title,
-    ///   text, intent agent, disable state, and visibility level).
+    ///   text, intent agent, disable state, visibility level and, from newer
+    ///   clients, progress style).
     /// * `reply` - Output parcel to write the newly created group ID.
     ///
     /// # Returns
@@ -89,8 +90,16 @@ impl RequestServiceStub {
 
         let visibility = data.read()?;
 
+        // Older clients end the parcel before the progress style
+        let progress_style = if data.readable() > 0 {
+            GroupProgressStyle::from(data.read::<u32>()?)
+        } else {
+            GroupProgressStyle::Determinate
+        };
+
+        let uid = ipc::Skeleton::calling_uid();
         let new_group_id = NotificationDispatcher::get_instance().create_group(
-            gauge, title, text, want_agent, disable, visibility);
+            gauge, title, text, want_agent, disable, visibility, progress_style, uid);
         reply.write(&new_group_id.to_string())?;
         Ok(())
     }
@@ -241,6 +250,175 @@ impl RequestServiceStub {
         Ok(())
     }
 
+    /// Queries the membership and aggregate progress of a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Input parcel containing the group ID to query.
+    /// * `reply` - Output parcel to write the result code and the group to.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the reply was written.
+    /// * `Err(IpcStatusCode::Failed)` - If sending the event to the task manager failed.
+    ///
+    /// # Notes
+    ///
+    /// Returns `ErrorCode::GroupNotFound` if the group ID is invalid or the
+    /// caller neither created the group nor is a system API. Otherwise the
+    /// reply holds the group ID, the number of members followed by each
+    /// member, the processed and total bytes, the counts of initialized,
+    /// waiting, running, retrying, paused, stopped, completed, failed and
+    /// removed tasks, the gauge flag and the optional title.
+    pub(crate) fn query_group(&self, data: &mut MsgParcel, reply: &mut MsgParcel) -> IpcResult<()> {
+        let Ok(group_id) = data.read::<String>()?.parse::<u32>() else {
+            error!("End Service query_group, failed: group_id not valid");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A52,
+                "End Service query_group, failed: group_id not valid"
+            );
+            reply.write(&(ErrorCode::GroupNotFound as i32))?;
+            return Ok(());
+        };
+        let uid = ipc::Skeleton::calling_uid();
+        if !is_system_api()
+            && NotificationDispatcher::get_instance().group_owner(group_id) != Some(uid)
+        {
+            error!(
+                "End Service query_group, group_id: {}, failed: group not belong to uid",
+                group_id
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A52,
+                &format!(
+                    "End Service query_group, group_id: {}, failed: group not belong to uid",
+                    group_id
+                )
+            );
+            reply.write(&(ErrorCode::GroupNotFound as i32))?;
+            return Ok(());
+        }
+
+        let (event, rx) = TaskManagerEvent::query_group(group_id);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            return Err(IpcStatusCode::Failed);
+        }
+        let Some(Some(info)) = rx.get() else {
+            error!(
+                "End Service query_group, group_id: {}, failed: group not found",
+                group_id
+            );
+            reply.write(&(ErrorCode::GroupNotFound as i32))?;
+            return Ok(());
+        };
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&info.group_id.to_string())?;
+        reply.write(&(info.member_task_ids.len() as u32))?;
+        for task_id in info.member_task_ids.iter() {
+            reply.write(&task_id.to_string())?;
+        }
+        reply.write(&info.aggregate_processed)?;
+        reply.write(&info.aggregate_total)?;
+        let counts = &info.counts_by_state;
+        for count in [
+            counts.initialized,
+            counts.waiting,
+            counts.running,
+            counts.retrying,
+            counts.paused,
+            counts.stopped,
+            counts.completed,
+            counts.failed,
+            counts.removed,
+        ] {
+            reply.write(&count)?;
+        }
+        reply.write(&info.gauge)?;
+        reply.write(&info.title.is_some())?;
+        if let Some(title) = info.title.as_ref() {
+            reply.write(title)?;
+        }
+        Ok(())
+    }
+
+    /// Stops or removes all tasks of a group and deletes the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Input parcel containing the group ID and whether the tasks
+    ///   are removed instead of stopped.
+    /// * `reply` - Output parcel to write the result code and the result of
+    ///   each task to.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the reply was written.
+    /// * `Err(IpcStatusCode::Failed)` - If sending the event to the task manager failed.
+    ///
+    /// # Notes
+    ///
+    /// Returns `ErrorCode::GroupNotFound` if the group ID is invalid or the
+    /// caller did not create the group. Otherwise the reply holds the number
+    /// of tasks followed by the ID and result code of each task.
+    pub(crate) fn cancel_group(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let Ok(group_id) = data.read::<String>()?.parse::<u32>() else {
+            error!("End Service cancel_group, failed: group_id not valid");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A57,
+                "End Service cancel_group, failed: group_id not valid"
+            );
+            reply.write(&(ErrorCode::GroupNotFound as i32))?;
+            return Ok(());
+        };
+        let remove_members: bool = data.read()?;
+        let uid = ipc::Skeleton::calling_uid();
+        if NotificationDispatcher::get_instance().group_owner(group_id) != Some(uid) {
+            error!(
+                "End Service cancel_group, group_id: {}, failed: group not belong to uid",
+                group_id
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A57,
+                &format!(
+                    "End Service cancel_group, group_id: {}, failed: group not belong to uid",
+                    group_id
+                )
+            );
+            reply.write(&(ErrorCode::GroupNotFound as i32))?;
+            return Ok(());
+        }
+
+        let (event, rx) = TaskManagerEvent::cancel_group(uid, group_id, remove_members);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            return Err(IpcStatusCode::Failed);
+        }
+        let Some(Some(results)) = rx.get() else {
+            error!(
+                "End Service cancel_group, group_id: {}, failed: group not found",
+                group_id
+            );
+            reply.write(&(ErrorCode::GroupNotFound as i32))?;
+            return Ok(());
+        };
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&(results.len() as u32))?;
+        for (task_id, ret) in results {
+            reply.write(&task_id.to_string())?;
+            reply.write(&(ret as i32))?;
+        }
+        Ok(())
+    }
+
     /// Disables notifications for multiple tasks.
     ///
     /// Disables user notifications for each task in the provided list, following
@@ -47,6 +47,7 @@ impl RequestServiceStub {
     /// # Errors
     ///
     /// Returns an error code in the reply parcel if:
+    /// * As many channels as allowed are already open (`ErrorCode::TooManyChannels`).
     /// * The channel could not be opened (`ErrorCode::ParameterCheck`).
     ///
     /// # Notes
@@ -81,7 +82,11 @@ impl RequestServiceStub {
                     DfxCode::INVALID_IPC_MESSAGE_A26,
                     &format!("End Service open_channel, failed: {:?}", err)
                 );
-                reply.write(&(ErrorCode::ParameterCheck as i32))?;
+                let code = match err {
+                    ErrorCode::TooManyChannels => err,
+                    _ => ErrorCode::ParameterCheck,
+                };
+                reply.write(&(code as i32))?;
                 Err(IpcStatusCode::Failed)
             }
         }
@@ -53,6 +53,29 @@ impl RequestServiceStub {
     /// Results are returned in the same order as the input task IDs.
     /// For API9, requires either INTERNET permission or download manager permission.
     pub(crate) fn remove(&self, data: &mut MsgParcel, reply: &mut MsgParcel) -> IpcResult<()> {
+        self.remove_tasks(data, reply, false)
+    }
+
+    /// Removes multiple tasks in bulk, keeping them restorable for the
+    /// removal grace period.
+    ///
+    /// The message parcel is the same as for [`remove`](Self::remove). The
+    /// removed tasks enter the `PendingRemoval` state until they are restored,
+    /// purged or their grace period expires.
+    pub(crate) fn remove_with_grace(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        self.remove_tasks(data, reply, true)
+    }
+
+    fn remove_tasks(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+        grace: bool,
+    ) -> IpcResult<()> {
         // Check for download manager permissions
         let permission = PermissionChecker::check_down_permission();
 
@@ -140,7 +163,7 @@ impl RequestServiceStub {
             }
 
             // Create and send removal event to task manager
-            let (event, rx) = TaskManagerEvent::remove(task_uid, task_id);
+            let (event, rx) = TaskManagerEvent::remove(task_uid, task_id, grace);
             if !self.task_manager.lock().unwrap().send_event(event) {
                 error!("Service remove, failed: task_manager err: {}", task_id);
                 sys_event!(
@@ -74,6 +74,13 @@ impl RequestServiceStub {
         let mode: u32 = data.read()?;
         debug!("Service search: mode is {}", mode);
 
+        // Read the optional client tag filter, empty matches any tag
+        let client_tag: String = if data.readable() > 0 {
+            data.read()?
+        } else {
+            String::new()
+        };
+
         // Construct task filter with all search criteria
         let filter = TaskFilter {
             before,
@@ -81,6 +88,7 @@ impl RequestServiceStub {
             state: state as u8,
             action: action as u8,
             mode: mode as u8,
+            client_tag,
         };
 
         // Perform the search operation
@@ -0,0 +1,169 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Search across applications for system agents.
+//!
+//! This module provides the method returning a page of the tasks of all
+//! applications with their secrets redacted, see
+//! [`RequestDb::system_search`].
+//!
+//! [`RequestDb::system_search`]: crate::manage::database::RequestDb::system_search
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::events::TaskManagerEvent;
+use crate::manage::system_search::{audit, search_access, SystemSearchFilter};
+use crate::service::permission::PermissionChecker;
+use crate::service::RequestServiceStub;
+use crate::utils::is_system_api;
+
+impl RequestServiceStub {
+    /// Searches a page of the tasks of all applications.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the smallest and largest uid, the
+    ///   creation time window, the number of states followed by each state,
+    ///   the same for actions, the offset and the size of the page
+    /// * `reply` - Message parcel to write the task summaries to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the summaries were written
+    /// * `Err(IpcStatusCode::Failed)` - If the caller may not search, the
+    ///   page size is invalid or the task manager could not be reached
+    ///
+    /// # Notes
+    ///
+    /// * Requires a system API caller holding the system search permission
+    /// * Every invocation is audited with the uid and token of the caller
+    /// * The reply holds the number of tasks followed by each summary, header
+    ///   values are redacted and only whether a token is set is reported
+    pub(crate) fn search_system(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let uid_min: u64 = data.read()?;
+        let uid_max: u64 = data.read()?;
+        let after: u64 = data.read()?;
+        let before: u64 = data.read()?;
+        let mut states = Vec::new();
+        for _ in 0..data.read::<u32>()? {
+            states.push(data.read::<u32>()? as u8);
+        }
+        let mut actions = Vec::new();
+        for _ in 0..data.read::<u32>()? {
+            actions.push(data.read::<u32>()? as u8);
+        }
+        let offset: u32 = data.read()?;
+        let limit: u32 = data.read()?;
+        let filter = SystemSearchFilter {
+            uid_min,
+            uid_max,
+            after,
+            before,
+            states,
+            actions,
+            offset,
+            limit,
+        };
+
+        let calling_uid = ipc::Skeleton::calling_uid();
+        let token_id = ipc::Skeleton::calling_full_token_id();
+        info!("Service search system, uid {}", calling_uid);
+
+        if let Err(code) = search_access(
+            is_system_api(),
+            PermissionChecker::check_system_search(),
+            filter.limit,
+        ) {
+            audit(calling_uid, token_id, &filter, code, 0);
+            error!(
+                "End Service search system, failed: uid {} denied {:?}",
+                calling_uid, code
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A60,
+                &format!(
+                    "End Service search system, failed: uid {} denied {:?}",
+                    calling_uid, code
+                )
+            );
+            reply.write(&(code as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+
+        let (event, rx) = TaskManagerEvent::search_system(filter.clone());
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            audit(calling_uid, token_id, &filter, ErrorCode::Other, 0);
+            error!("End Service search system, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A60,
+                "End Service search system, failed: send event failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let Some(summaries) = rx.get() else {
+            audit(calling_uid, token_id, &filter, ErrorCode::Other, 0);
+            error!("End Service search system, failed: receives summaries failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A60,
+                "End Service search system, failed: receives summaries failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+        audit(
+            calling_uid,
+            token_id,
+            &filter,
+            ErrorCode::ErrOk,
+            summaries.len(),
+        );
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&(summaries.len() as u32))?;
+        for summary in summaries {
+            reply.write(&summary.task_id.to_string())?;
+            reply.write(&summary.uid)?;
+            reply.write(&summary.bundle)?;
+            reply.write(&(summary.action as u32))?;
+            reply.write(&(summary.mode as u32))?;
+            reply.write(&(summary.state as u32))?;
+            reply.write(&summary.ctime)?;
+            reply.write(&summary.url)?;
+            reply.write(&summary.title)?;
+            reply.write(&summary.description)?;
+            reply.write(&summary.method)?;
+            reply.write(&(summary.headers.len() as u32))?;
+            for (name, value) in summary.headers.iter() {
+                reply.write(name)?;
+                reply.write(value)?;
+            }
+            reply.write(&summary.has_token)?;
+            reply.write(&(summary.paths.len() as u32))?;
+            for path in summary.paths.iter() {
+                reply.write(path)?;
+            }
+            reply.write(&summary.client_tag)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Destination change functionality for paused download tasks.
+//!
+//! This module implements the method moving the file of a paused download to
+//! a new path, with ownership validation and proper error handling.
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::database::RequestDb;
+use crate::manage::events::TaskManagerEvent;
+use crate::service::permission::PermissionChecker;
+use crate::service::RequestServiceStub;
+use crate::task::files::check_current_account;
+
+impl RequestServiceStub {
+    /// Moves a paused download to a new destination path.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the task ID and the new path
+    /// * `reply` - Message parcel to write operation result to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the destination was changed
+    /// * `Err(IpcStatusCode::Failed)` - If the task is not found, the caller
+    ///   does not own it or the task manager rejects the change
+    ///
+    /// # Errors
+    ///
+    /// Returns error codes in the reply parcel:
+    /// * `ErrOk` - Destination changed successfully
+    /// * `TaskNotFound` - Task ID invalid, doesn't exist, or caller lacks permission
+    /// * `TaskStateErr` - Task is not paused
+    /// * `ParameterCheck` - Invalid path or task without a download path
+    /// * `FileOperationErr` - The downloaded file could not be moved
+    /// * `Other` - Failed to communicate with task manager
+    pub(crate) fn set_destination(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let permission = PermissionChecker::check_down_permission();
+        let task_id: String = data.read()?;
+        let path: String = data.read()?;
+        info!("Service set_destination tid {}", task_id);
+
+        let Ok(task_id) = task_id.parse::<u32>() else {
+            error!("Service set_destination, failed: tid not valid: {}", task_id);
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A50,
+                &format!("Service set_destination, failed: tid not valid: {}", task_id)
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        // Ensure caller owns the task or has management permissions
+        let ipc_uid = ipc::Skeleton::calling_uid();
+        let task_uid = match RequestDb::get_instance().query_task_uid(task_id) {
+            Some(uid) if check_current_account(uid) && (uid == ipc_uid || permission) => uid,
+            _ => {
+                error!(
+                    "Service set_destination, failed: check task uid. tid: {}, uid: {}",
+                    task_id, ipc_uid
+                );
+                sys_event!(
+                    ExecError,
+                    DfxCode::INVALID_IPC_MESSAGE_A50,
+                    &format!(
+                        "Service set_destination, failed: check task uid. tid: {}, uid: {}",
+                        task_id, ipc_uid
+                    )
+                );
+                reply.write(&(ErrorCode::TaskNotFound as i32))?;
+                return Err(IpcStatusCode::Failed);
+            }
+        };
+
+        let (event, rx) = TaskManagerEvent::set_destination(task_uid, task_id, path);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!(
+                "Service set_destination, failed: task_manager err: {}",
+                task_id
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A50,
+                &format!(
+                    "Service set_destination, failed: task_manager err: {}",
+                    task_id
+                )
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let ret = rx.get().unwrap_or(ErrorCode::Other);
+        reply.write(&(ret as i32))?;
+        if ret != ErrorCode::ErrOk {
+            error!(
+                "Service set_destination, tid: {}, failed: {}",
+                task_id, ret as i32
+            );
+            return Err(IpcStatusCode::Failed);
+        }
+        Ok(())
+    }
+}
@@ -14,7 +14,8 @@
 //! Task speed limitation functionality for download tasks.
 //! 
 //! This module provides methods to set maximum download speed limits for tasks,
-//! with validation, permission checking, and bulk operation support.
+//! with validation, permission checking, and bulk operation support, as well
+//! as a speed cap shared by all tasks.
 
 use ipc::parcel::MsgParcel;
 use ipc::{IpcResult, IpcStatusCode};
@@ -211,4 +212,80 @@ impl RequestServiceStub {
         }
         Ok(())
     }
+
+    /// Sets the total speed shared by all running tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the global speed cap
+    /// * `reply` - Message parcel to write operation result to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the cap was applied
+    /// * `Err(IpcStatusCode::Failed)` - If there was a permission issue, an
+    ///   invalid cap or a task manager error
+    ///
+    /// # Notes
+    ///
+    /// * Requires `DOWNLOAD_SESSION_MANAGER` permission
+    /// * A cap of 0 removes the limit, other values must be at least 16KB/s
+    /// * The cap is kept in memory only and split among running tasks by the
+    ///   QoS scheduler
+    pub(crate) fn set_global_max_speed(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        info!("Service set_global_max_speed");
+        // Minimum speed limit: 16KB/s
+        const MIN_SPEED_LIMIT: i64 = 16 * 1024;
+
+        if !PermissionChecker::check_down_permission() {
+            error!("Service set_global_max_speed: no DOWNLOAD_SESSION_MANAGER permission.");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A49,
+                "Service set_global_max_speed: no DOWNLOAD_SESSION_MANAGER permission."
+            );
+            reply.write(&(ErrorCode::Permission as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+
+        let max_speed: i64 = data.read()?;
+        if max_speed != 0 && max_speed < MIN_SPEED_LIMIT {
+            error!(
+                "Service set_global_max_speed, failed: speed not valid: {}",
+                max_speed
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A49,
+                &format!(
+                    "Service set_global_max_speed, failed: speed not valid: {}",
+                    max_speed
+                )
+            );
+            reply.write(&(ErrorCode::ParameterCheck as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+
+        let (event, rx) = TaskManagerEvent::set_global_max_speed(max_speed as u64);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("Service set_global_max_speed, failed: task_manager err");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A49,
+                "Service set_global_max_speed, failed: task_manager err"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let ret = rx.get().unwrap_or(ErrorCode::Other);
+        reply.write(&(ret as i32))?;
+        if ret != ErrorCode::ErrOk {
+            return Err(IpcStatusCode::Failed);
+        }
+        Ok(())
+    }
 }
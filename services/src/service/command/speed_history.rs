@@ -0,0 +1,132 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Speed history query for tasks.
+//!
+//! Returns the recent processed bytes samples of a running or recently
+//! stopped task, see [`SpeedHistory`].
+//!
+//! [`SpeedHistory`]: crate::task::speed_history::SpeedHistory
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::events::TaskManagerEvent;
+use crate::service::permission::PermissionChecker;
+use crate::service::RequestServiceStub;
+
+impl RequestServiceStub {
+    /// Queries the speed history of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the task ID to query and the
+    ///   maximum number of samples, 0 for all
+    /// * `reply` - Message parcel to write the result to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the samples were written to the reply
+    /// * `Err(IpcStatusCode::Failed)` - If the task can not be queried
+    ///
+    /// # Errors
+    ///
+    /// Returns error codes in the reply parcel:
+    /// * `ErrOk` - Followed by the number of samples and the timestamp in
+    ///   milliseconds and processed bytes of each sample, oldest first
+    /// * `TaskNotFound` - Invalid task ID, task not owned by the caller or
+    ///   task neither running nor recently stopped
+    /// * `Other` - The task manager could not be reached
+    ///
+    /// # Notes
+    ///
+    /// Requires `DOWNLOAD_SESSION_MANAGER` permission to query tasks
+    /// belonging to other UIDs.
+    pub(crate) fn query_speed_history(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let task_id: String = data.read()?;
+        let points: u32 = data.read()?;
+        info!("Service query speed history tid {}", task_id);
+
+        let Ok(task_id) = task_id.parse::<u32>() else {
+            error!("End Service query speed history, failed: task_id not valid");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A56,
+                "End Service query speed history, failed: task_id not valid"
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        let uid = ipc::Skeleton::calling_uid();
+        if !PermissionChecker::check_down_permission() && !self.check_task_uid(task_id, uid) {
+            error!(
+                "End Service query speed history, tid: {}, failed: task not belong to uid",
+                task_id
+            );
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A56,
+                &format!(
+                    "End Service query speed history, tid: {}, failed: task not belong to uid",
+                    task_id
+                )
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+
+        let (event, rx) = TaskManagerEvent::speed_history(task_id, points as usize);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("End Service query speed history, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A56,
+                "End Service query speed history, failed: send event failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let Some(samples) = rx.get() else {
+            error!("End Service query speed history, failed: receives samples failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A56,
+                "End Service query speed history, failed: receives samples failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+        let Some(samples) = samples else {
+            error!(
+                "End Service query speed history, tid: {}, failed: task not running",
+                task_id
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        reply.write(&(ErrorCode::ErrOk as i32))?;
+        reply.write(&(samples.len() as u32))?;
+        for sample in samples {
+            reply.write(&sample.time)?;
+            reply.write(&sample.processed)?;
+        }
+        Ok(())
+    }
+}
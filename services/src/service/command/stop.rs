@@ -54,7 +54,26 @@ impl RequestServiceStub {
     /// * Input is limited to `CONTROL_MAX` number of tasks
     /// * Performs account and UID validation to ensure proper access control
     pub(crate) fn stop(&self, data: &mut MsgParcel, reply: &mut MsgParcel) -> IpcResult<()> {
-        info!("Service stop");
+        self.stop_tasks(data, reply, false)
+    }
+
+    /// Stops execution of multiple tasks, letting uploads finish the file in
+    /// flight first.
+    ///
+    /// The message parcel is the same as for [`stop`](Self::stop). Running
+    /// uploads stop once their current file is sent, or are cancelled if that
+    /// takes longer than the soft stop grace period. Downloads stop at once.
+    pub(crate) fn soft_stop(&self, data: &mut MsgParcel, reply: &mut MsgParcel) -> IpcResult<()> {
+        self.stop_tasks(data, reply, true)
+    }
+
+    fn stop_tasks(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+        graceful: bool,
+    ) -> IpcResult<()> {
+        info!("Service stop, graceful {}", graceful);
         // Check if caller has download permission (needed for privileged operations)
         let permission = PermissionChecker::check_down_permission();
         
@@ -127,7 +146,10 @@ impl RequestServiceStub {
             }
 
             // Create and send task stop event to task manager
-            let (event, rx) = TaskManagerEvent::stop(task_uid, task_id);
+            let (event, rx) = match graceful {
+                true => TaskManagerEvent::soft_stop(task_uid, task_id),
+                false => TaskManagerEvent::stop(task_uid, task_id),
+            };
             if !self.task_manager.lock().unwrap().send_event(event) {
                 error!("Service stop, failed: task_manager err: {}", task_id);
                 set_code_with_index(&mut vec, i, ErrorCode::Other);
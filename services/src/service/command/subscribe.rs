@@ -150,4 +150,92 @@ impl RequestServiceStub {
             Err(IpcStatusCode::Failed)
         }
     }
+
+    /// Subscribes a client to notifications for many tasks at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the task ID strings
+    /// * `reply` - Message parcel to write the result of each task to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the result of each task was written
+    /// * `Err(IpcStatusCode::Failed)` - If the task manager or the client
+    ///   manager could not be reached
+    ///
+    /// # Notes
+    ///
+    /// * The reply holds an error code for each task in the order of the
+    ///   request, some tasks may be subscribed while others fail
+    /// * Each subscribed task immediately reports its current state to the
+    ///   client
+    pub(crate) fn subscribe_batch(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let task_ids: Vec<String> = data.read()?;
+        debug!("Service subscribe batch tids {:?}", task_ids);
+
+        let uid = ipc::Skeleton::calling_uid();
+        let pid = ipc::Skeleton::calling_pid();
+        let token_id = ipc::Skeleton::calling_full_token_id();
+
+        // Tasks that do not parse or belong to the caller fail on their own
+        let mut ret = vec![ErrorCode::TaskNotFound; task_ids.len()];
+        let (indexes, checked): (Vec<usize>, Vec<u32>) = task_ids
+            .iter()
+            .enumerate()
+            .filter_map(|(index, task_id)| Some((index, task_id.parse::<u32>().ok()?)))
+            .filter(|(_, task_id)| self.check_task_uid(*task_id, uid))
+            .unzip();
+
+        let (event, rx) = TaskManagerEvent::subscribe_batch(checked, token_id);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("End Service subscribe batch, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A28,
+                "End Service subscribe batch, failed: send event failed"
+            );
+            return Err(IpcStatusCode::Failed);
+        }
+        let Some(checked) = rx.get() else {
+            error!("End Service subscribe batch, failed: receives ret failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A28,
+                "End Service subscribe batch, failed: receives ret failed"
+            );
+            return Err(IpcStatusCode::Failed);
+        };
+
+        let mut subscribed = Vec::new();
+        let mut snapshots = Vec::new();
+        for (index, res) in indexes.into_iter().zip(checked) {
+            match res {
+                Ok(snapshot) => {
+                    subscribed.push(index);
+                    snapshots.push(snapshot);
+                }
+                Err(code) => ret[index] = code,
+            }
+        }
+        let codes = self.client_manager.subscribe_batch(pid, snapshots);
+        for (index, code) in subscribed.into_iter().zip(codes) {
+            ret[index] = code;
+        }
+
+        for (task_id, code) in task_ids.iter().zip(ret) {
+            if code != ErrorCode::ErrOk {
+                error!(
+                    "End Service subscribe batch, tid: {}, failed: {:?}",
+                    task_id, code
+                );
+            }
+            reply.write(&(code as i32))?;
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,134 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Task list subscription functionality for request service.
+//!
+//! This module provides methods to subscribe to incremental changes of the
+//! caller's tasks matching a search filter. Changes are delivered over the
+//! channel opened by the caller.
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::events::TaskManagerEvent;
+use crate::manage::query::TaskFilter;
+use crate::service::RequestServiceStub;
+
+impl RequestServiceStub {
+    /// Subscribes the caller to changes of its tasks matching a filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the time range, state, action and
+    ///   mode of the filter followed by the subscription ID
+    /// * `reply` - Message parcel to write operation result to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the subscription was added
+    /// * `Err(IpcStatusCode::Failed)` - If the task manager could not be reached
+    pub(crate) fn subscribe_task_list(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let before: i64 = data.read()?;
+        let after: i64 = data.read()?;
+        let state: u32 = data.read()?;
+        let action: u32 = data.read()?;
+        let mode: u32 = data.read()?;
+        let id: u32 = data.read()?;
+
+        let filter = TaskFilter {
+            before,
+            after,
+            state: state as u8,
+            action: action as u8,
+            mode: mode as u8,
+            client_tag: String::new(),
+        };
+        let uid = ipc::Skeleton::calling_uid();
+        let pid = ipc::Skeleton::calling_pid();
+        info!("Service subscribe task list, pid {} id {}", pid, id);
+
+        let (event, rx) = TaskManagerEvent::subscribe_task_list(uid, pid, id, filter);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("End Service subscribe task list, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A47,
+                "End Service subscribe task list, failed: send event failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let ret = match rx.get() {
+            Some(ret) => ret,
+            None => {
+                error!("End Service subscribe task list, failed: receives ret failed");
+                sys_event!(
+                    ExecError,
+                    DfxCode::INVALID_IPC_MESSAGE_A47,
+                    "End Service subscribe task list, failed: receives ret failed"
+                );
+                reply.write(&(ErrorCode::Other as i32))?;
+                return Err(IpcStatusCode::Failed);
+            }
+        };
+        reply.write(&(ret as i32))?;
+        if ret != ErrorCode::ErrOk {
+            return Err(IpcStatusCode::Failed);
+        }
+        Ok(())
+    }
+
+    /// Ends a task list subscription of the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the subscription ID
+    /// * `reply` - Message parcel to write operation result to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the subscription was removed
+    /// * `Err(IpcStatusCode::Failed)` - If the subscription does not exist
+    pub(crate) fn unsubscribe_task_list(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        let id: u32 = data.read()?;
+        let pid = ipc::Skeleton::calling_pid();
+        info!("Service unsubscribe task list, pid {} id {}", pid, id);
+
+        let (event, rx) = TaskManagerEvent::unsubscribe_task_list(pid, id);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("End Service unsubscribe task list, failed: send event failed");
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A48,
+                "End Service unsubscribe task list, failed: send event failed"
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let ret = rx.get().unwrap_or(ErrorCode::Other);
+        reply.write(&(ret as i32))?;
+        if ret != ErrorCode::ErrOk {
+            return Err(IpcStatusCode::Failed);
+        }
+        Ok(())
+    }
+}
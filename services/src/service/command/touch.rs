@@ -24,6 +24,7 @@ use crate::info::TaskInfo;
 use crate::manage::database::RequestDb;
 use crate::service::command::{set_code_with_index_other, GET_INFO_MAX};
 use crate::service::permission::PermissionChecker;
+use crate::service::task_token::{TaskTokens, TokenCheck, TokenScope};
 use crate::service::{serialize_task_info, RequestServiceStub};
 use crate::task::files::check_current_account;
 
@@ -100,8 +101,19 @@ impl RequestServiceStub {
                 continue;
             }
 
+            // A signed token grants access to processes other than the owner
+            let checked = TaskTokens::get_instance().check(token, task_id, TokenScope::Query);
+            let token = match checked {
+                TokenCheck::Granted => None,
+                TokenCheck::Legacy(token) => Some(token),
+                TokenCheck::Denied => {
+                    set_code_with_index_other(&mut vec, i, ErrorCode::TaskNotFound);
+                    continue;
+                }
+            };
+
             // Check permission for cross-UID access
-            if (task_uid != ipc_uid) && !permission {
+            if token.is_some() && (task_uid != ipc_uid) && !permission {
                 set_code_with_index_other(&mut vec, i, ErrorCode::TaskNotFound);
                 error!(
                     "Service touch, failed: check task uid. tid: {}, uid: {}",
@@ -118,7 +130,7 @@ impl RequestServiceStub {
                 continue;
             }
 
-            // Attempt to touch the task with the provided token
+            // Attempt to touch the task with the provided legacy token
             let info = self
                 .task_manager
                 .lock()
@@ -0,0 +1,149 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Restoring and purging of tasks pending removal.
+//!
+//! Tasks removed with a grace period stay in the `PendingRemoval` state for
+//! a while. This module implements the methods restoring such a task as
+//! paused and removing it for good before its grace period expires.
+
+use ipc::parcel::MsgParcel;
+use ipc::{IpcResult, IpcStatusCode};
+
+use crate::error::ErrorCode;
+use crate::manage::database::RequestDb;
+use crate::manage::events::TaskManagerEvent;
+use crate::service::permission::PermissionChecker;
+use crate::service::RequestServiceStub;
+use crate::task::files::check_current_account;
+use crate::utils::Recv;
+
+impl RequestServiceStub {
+    /// Restores a task pending removal to the paused state.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the task ID
+    /// * `reply` - Message parcel to write operation result to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the task was restored
+    /// * `Err(IpcStatusCode::Failed)` - If the task is not found, the caller
+    ///   does not own it or the task is not pending removal
+    ///
+    /// # Errors
+    ///
+    /// Returns error codes in the reply parcel:
+    /// * `ErrOk` - Task restored successfully
+    /// * `TaskNotFound` - Task ID invalid, doesn't exist, or caller lacks permission
+    /// * `TaskStateErr` - Task is not pending removal
+    /// * `Other` - Failed to communicate with task manager
+    pub(crate) fn undo_remove(&self, data: &mut MsgParcel, reply: &mut MsgParcel) -> IpcResult<()> {
+        self.trash_command(data, reply, "undo_remove", TaskManagerEvent::undo_remove)
+    }
+
+    /// Removes a task pending removal without waiting for its grace period.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Message parcel containing the task ID
+    /// * `reply` - Message parcel to write operation result to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the task was removed
+    /// * `Err(IpcStatusCode::Failed)` - If the task is not found, the caller
+    ///   does not own it or the task is not pending removal
+    ///
+    /// # Errors
+    ///
+    /// Returns error codes in the reply parcel:
+    /// * `ErrOk` - Task removed successfully
+    /// * `TaskNotFound` - Task ID invalid, doesn't exist, or caller lacks permission
+    /// * `TaskStateErr` - Task is not pending removal
+    /// * `Other` - Failed to communicate with task manager
+    pub(crate) fn purge_removed(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+    ) -> IpcResult<()> {
+        self.trash_command(data, reply, "purge_removed", TaskManagerEvent::purge)
+    }
+
+    /// Checks the caller may change the task read from `data`, then sends
+    /// the event built by `event` and replies its result.
+    fn trash_command(
+        &self,
+        data: &mut MsgParcel,
+        reply: &mut MsgParcel,
+        name: &str,
+        event: fn(u64, u32) -> (TaskManagerEvent, Recv<ErrorCode>),
+    ) -> IpcResult<()> {
+        let permission = PermissionChecker::check_down_permission();
+        let task_id: String = data.read()?;
+        info!("Service {} tid {}", name, task_id);
+
+        let Ok(task_id) = task_id.parse::<u32>() else {
+            error!("Service {}, failed: tid not valid: {}", name, task_id);
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A53,
+                &format!("Service {}, failed: tid not valid: {}", name, task_id)
+            );
+            reply.write(&(ErrorCode::TaskNotFound as i32))?;
+            return Err(IpcStatusCode::Failed);
+        };
+
+        // Ensure caller owns the task or has management permissions
+        let ipc_uid = ipc::Skeleton::calling_uid();
+        let task_uid = match RequestDb::get_instance().query_task_uid(task_id) {
+            Some(uid) if check_current_account(uid) && (uid == ipc_uid || permission) => uid,
+            _ => {
+                error!(
+                    "Service {}, failed: check task uid. tid: {}, uid: {}",
+                    name, task_id, ipc_uid
+                );
+                sys_event!(
+                    ExecError,
+                    DfxCode::INVALID_IPC_MESSAGE_A53,
+                    &format!(
+                        "Service {}, failed: check task uid. tid: {}, uid: {}",
+                        name, task_id, ipc_uid
+                    )
+                );
+                reply.write(&(ErrorCode::TaskNotFound as i32))?;
+                return Err(IpcStatusCode::Failed);
+            }
+        };
+
+        let (event, rx) = event(task_uid, task_id);
+        if !self.task_manager.lock().unwrap().send_event(event) {
+            error!("Service {}, failed: task_manager err: {}", name, task_id);
+            sys_event!(
+                ExecError,
+                DfxCode::INVALID_IPC_MESSAGE_A53,
+                &format!("Service {}, failed: task_manager err: {}", name, task_id)
+            );
+            reply.write(&(ErrorCode::Other as i32))?;
+            return Err(IpcStatusCode::Failed);
+        }
+        let ret = rx.get().unwrap_or(ErrorCode::Other);
+        reply.write(&(ret as i32))?;
+        if ret != ErrorCode::ErrOk {
+            error!("Service {}, tid: {}, failed: {}", name, task_id, ret as i32);
+            return Err(IpcStatusCode::Failed);
+        }
+        Ok(())
+    }
+}
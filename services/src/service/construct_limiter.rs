@@ -0,0 +1,166 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-uid rate limiting of construct requests.
+//!
+//! Each application owns a token bucket refilled at a sustained rate up to a
+//! burst size. Every task an application asks to construct takes one token,
+//! requests finding too few tokens are rejected with
+//! `ErrorCode::ConstructThrottled` so a runaway application cannot starve the
+//! others. System uids are never throttled.
+//!
+//! The rate and burst default to [`DEFAULT_RATE`] and [`DEFAULT_BURST`] and
+//! may be overridden by the [`RATE_PARAM`] and [`BURST_PARAM`] system
+//! parameters.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Tasks an application may construct per second in the long run.
+pub(crate) const DEFAULT_RATE: u32 = 20;
+/// Tasks an application may construct at once.
+pub(crate) const DEFAULT_BURST: u32 = 200;
+/// System parameter overriding the sustained rate.
+pub(crate) const RATE_PARAM: &str = "const.request.construct_rate";
+/// System parameter overriding the burst size.
+pub(crate) const BURST_PARAM: &str = "const.request.construct_burst";
+/// Uids below this value belong to system services.
+const SYSTEM_UID_MAX: u64 = 10000;
+/// Shortest time between two throttle reports of the same uid.
+const REPORT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Outcome of a construct request checked by the limiter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Admission {
+    /// The request may proceed.
+    Allowed,
+    /// The request is rejected, `report` is set for the first rejection of
+    /// the uid within `REPORT_INTERVAL`.
+    Throttled { report: bool },
+}
+
+/// Throttle state of an application, as shown by the dump command.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ThrottleState {
+    /// Tokens left in the bucket.
+    pub(crate) tokens: f64,
+    /// Requests rejected since the service started.
+    pub(crate) throttled: u64,
+    /// Whether the last request was rejected.
+    pub(crate) throttling: bool,
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+    throttled: u64,
+    throttling: bool,
+    reported: Option<Instant>,
+}
+
+/// Token buckets of all applications having constructed tasks.
+pub(crate) struct ConstructLimiter {
+    rate: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<u64, Bucket>>,
+}
+
+impl ConstructLimiter {
+    /// Returns the limiter configured by the system parameters.
+    pub(crate) fn get_instance() -> &'static Self {
+        static INSTANCE: LazyLock<ConstructLimiter> = LazyLock::new(|| {
+            #[cfg(feature = "oh")]
+            let (rate, burst) = (
+                crate::utils::get_int_parameter(RATE_PARAM, DEFAULT_RATE as i64),
+                crate::utils::get_int_parameter(BURST_PARAM, DEFAULT_BURST as i64),
+            );
+            #[cfg(not(feature = "oh"))]
+            let (rate, burst) = (DEFAULT_RATE as i64, DEFAULT_BURST as i64);
+            let rate = u32::try_from(rate).unwrap_or(DEFAULT_RATE);
+            let burst = u32::try_from(burst).unwrap_or(DEFAULT_BURST);
+            info!("construct rate limit {}/s, burst {}", rate, burst);
+            ConstructLimiter::new(rate, burst)
+        });
+        &INSTANCE
+    }
+
+    pub(crate) fn new(rate: u32, burst: u32) -> Self {
+        Self {
+            rate: rate as f64,
+            burst: burst as f64,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Checks whether `uid` may construct `count` tasks now.
+    pub(crate) fn admit(&self, uid: u64, count: u32) -> Admission {
+        self.admit_at(uid, count, Instant::now())
+    }
+
+    pub(crate) fn admit_at(&self, uid: u64, count: u32, now: Instant) -> Admission {
+        if uid < SYSTEM_UID_MAX {
+            return Admission::Allowed;
+        }
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(uid).or_insert(Bucket {
+            tokens: self.burst,
+            refilled: now,
+            throttled: 0,
+            throttling: false,
+            reported: None,
+        });
+        let elapsed = now.saturating_duration_since(bucket.refilled);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.rate).min(self.burst);
+        bucket.refilled = now;
+
+        if bucket.tokens >= count as f64 {
+            bucket.tokens -= count as f64;
+            bucket.throttling = false;
+            return Admission::Allowed;
+        }
+        bucket.throttled += 1;
+        bucket.throttling = true;
+        let report = match bucket.reported {
+            Some(reported) => now.saturating_duration_since(reported) >= REPORT_INTERVAL,
+            None => true,
+        };
+        if report {
+            bucket.reported = Some(now);
+        }
+        Admission::Throttled { report }
+    }
+
+    /// Returns the throttle state of every application, ordered by uid.
+    pub(crate) fn states(&self) -> Vec<(u64, ThrottleState)> {
+        let buckets = self.buckets.lock().unwrap();
+        let mut states: Vec<_> = buckets
+            .iter()
+            .map(|(uid, bucket)| {
+                let state = ThrottleState {
+                    tokens: bucket.tokens,
+                    throttled: bucket.throttled,
+                    throttling: bucket.throttling,
+                };
+                (*uid, state)
+            })
+            .collect();
+        states.sort_by_key(|(uid, _)| *uid);
+        states
+    }
+}
+
+#[cfg(test)]
+mod ut_construct_limiter {
+    include!("../../tests/ut/service/ut_construct_limiter.rs");
+}
@@ -19,6 +19,9 @@
 
 /// Constructs a new request.
 pub const CONSTRUCT: u32 = 0;
+/// Set in the task count of a `CONSTRUCT` request whose tasks are each
+/// followed by their options.
+pub const CONSTRUCT_WITH_OPTIONS: u32 = 1 << 31;
 /// Pauses a specific request.
 pub const PAUSE: u32 = 1;
 /// Queries information about a request.
@@ -63,10 +66,50 @@ pub const DELETE_GROUP: u32 = 20;
 pub const SET_MAX_SPEED: u32 = 21;
 /// Shows the progress of a task.
 pub const SHOW_PROGRESS: u32 = 22;
+/// Subscribes to changes of a task list.
+pub const SUBSCRIBE_TASK_LIST: u32 = 23;
+/// Unsubscribes from changes of a task list.
+pub const UNSUBSCRIBE_TASK_LIST: u32 = 24;
+/// Counts tasks per application and state.
+pub const COUNT_BY_STATE: u32 = 25;
 /// Changes the mode of a task.
 pub const SET_MODE: u32 = 100;
 /// Disables notifications for a specific task.
 pub const DISABLE_TASK_NOTIFICATION: u32 = 101;
+/// Sets the total speed shared by all tasks.
+pub const SET_GLOBAL_MAX_SPEED: u32 = 102;
+/// Moves a paused download to a new destination path.
+pub const SET_DESTINATION: u32 = 103;
+/// Queries the membership and aggregate progress of a group.
+pub const QUERY_GROUP: u32 = 104;
+/// Subscribes to notifications of many tasks at once.
+pub const SUBSCRIBE_BATCH: u32 = 105;
+/// Removes tasks, keeping them restorable for a grace period.
+pub const REMOVE_WITH_GRACE: u32 = 106;
+/// Restores a task pending removal.
+pub const UNDO_REMOVE: u32 = 107;
+/// Removes a task pending removal without waiting for its grace period.
+pub const PURGE_REMOVED: u32 = 108;
+/// Enables the diagnostic journal of an application.
+pub const ENABLE_JOURNAL: u32 = 109;
+/// Queries the effective configuration a task runs with.
+pub const QUERY_EFFECTIVE_CONFIG: u32 = 110;
+/// Stops tasks, letting uploads finish the file in flight first.
+pub const SOFT_STOP: u32 = 111;
+/// Queries the recent speed samples of a task.
+pub const QUERY_SPEED_HISTORY: u32 = 112;
+/// Stops or removes all tasks of a group and deletes the group.
+pub const CANCEL_GROUP: u32 = 113;
+/// Explains why a task is in its current state.
+pub const EXPLAIN_TASK: u32 = 114;
+/// Queries the tasks changed since a revision.
+pub const CHANGED_SINCE: u32 = 115;
+/// Searches a page of the tasks of all applications for system agents.
+pub const SEARCH_SYSTEM: u32 = 116;
+/// Rewrites the legacy downloads of an application as API10 tasks.
+pub const MIGRATE_LEGACY_TASKS: u32 = 117;
+/// Issues a signed token sharing a task with another process.
+pub const ISSUE_TASK_TOKEN: u32 = 118;
 
 /// Function code for the request notification interface to notify run count changes.
 pub(crate) const NOTIFY_RUN_COUNT: u32 = 2;
@@ -97,7 +140,27 @@ mod test {
         assert_eq!(18, CREATE_GROUP);
         assert_eq!(19, ATTACH_GROUP);
         assert_eq!(20, DELETE_GROUP);
+        assert_eq!(23, SUBSCRIBE_TASK_LIST);
+        assert_eq!(24, UNSUBSCRIBE_TASK_LIST);
+        assert_eq!(25, COUNT_BY_STATE);
         assert_eq!(100, SET_MODE);
         assert_eq!(101, DISABLE_TASK_NOTIFICATION);
+        assert_eq!(102, SET_GLOBAL_MAX_SPEED);
+        assert_eq!(103, SET_DESTINATION);
+        assert_eq!(104, QUERY_GROUP);
+        assert_eq!(105, SUBSCRIBE_BATCH);
+        assert_eq!(106, REMOVE_WITH_GRACE);
+        assert_eq!(107, UNDO_REMOVE);
+        assert_eq!(108, PURGE_REMOVED);
+        assert_eq!(109, ENABLE_JOURNAL);
+        assert_eq!(110, QUERY_EFFECTIVE_CONFIG);
+        assert_eq!(111, SOFT_STOP);
+        assert_eq!(112, QUERY_SPEED_HISTORY);
+        assert_eq!(113, CANCEL_GROUP);
+        assert_eq!(114, EXPLAIN_TASK);
+        assert_eq!(115, CHANGED_SINCE);
+        assert_eq!(116, SEARCH_SYSTEM);
+        assert_eq!(117, MIGRATE_LEGACY_TASKS);
+        assert_eq!(118, ISSUE_TASK_TOKEN);
     }
 }
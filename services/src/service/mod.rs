@@ -22,6 +22,12 @@
 pub(crate) mod active_counter;
 /// Client interface for interacting with the request service.
 pub(crate) mod client;
+/// Per-uid rate limiting of construct requests.
+pub(crate) mod construct_limiter;
+/// Progress polling for in-process C++ callers.
+pub(crate) mod progress_brief;
+/// Signed capability tokens for sharing a task with another process.
+pub(crate) mod task_token;
 
 /// IPC interface definitions for the request server service.
 pub mod interface;
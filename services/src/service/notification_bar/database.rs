@@ -18,7 +18,7 @@
 //! It handles creation, updates, queries, and cleanup operations.
 
 use crate::database::REQUEST_DB;
-use crate::service::notification_bar::NotificationConfig;
+use crate::service::notification_bar::{GroupProgressStyle, NotificationConfig};
 use super::NotificationDispatcher;
 
 const CREATE_TASK_CONFIG_TABLE: &str = 
@@ -51,6 +51,12 @@ const TASK_CONTENT_TABLE_ADD_WANT_AGENT: &str =
 const GROUP_CONTENT_TABLE_ADD_WANT_AGENT: &str = 
     "ALTER TABLE group_notification_content ADD COLUMN want_agent TEXT";
 
+const GROUP_CONFIG_TABLE_ADD_UID: &str =
+    "ALTER TABLE group_notification_config ADD COLUMN uid INTEGER";
+
+const GROUP_CONFIG_TABLE_ADD_PROGRESS_STYLE: &str =
+    "ALTER TABLE group_notification_config ADD COLUMN progress_style INTEGER";
+
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const MILLIS_IN_A_WEEK: u64 = 7 * 24 * 60 * 60 * 1000;
@@ -174,6 +180,30 @@ impl NotificationDb {
         } else {
             debug!("Successfully added want_agent column to group_notification_content table");
         }
+
+        // Add uid column to group_notification_config table
+        if let Err(e) = self.inner.execute(GROUP_CONFIG_TABLE_ADD_UID, ()) {
+            error!("Failed to add uid column to group_notification_config table: {}", e);
+            sys_event!(
+                ExecFault,
+                DfxCode::RDB_FAULT_04,
+                &format!("Failed to add uid column to group_notification_config table: {}", e)
+            );
+        } else {
+            debug!("Successfully added uid column to group_notification_config table");
+        }
+
+        // Add progress_style column to group_notification_config table
+        if let Err(e) = self.inner.execute(GROUP_CONFIG_TABLE_ADD_PROGRESS_STYLE, ()) {
+            error!("Failed to add progress_style column to group_notification_config table: {}", e);
+            sys_event!(
+                ExecFault,
+                DfxCode::RDB_FAULT_04,
+                &format!("Failed to add progress_style column to group_notification_config table: {}", e)
+            );
+        } else {
+            debug!("Successfully added progress_style column to group_notification_config table");
+        }
     }
 
     /// Clears all notification information for a specific task.
@@ -556,6 +586,97 @@ impl NotificationDb {
         }
     }
 
+    /// Records the application that created a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group to update
+    /// * `uid` - The uid of the creating application
+    pub(crate) fn update_group_owner(&self, group_id: u32, uid: u64) {
+        if let Err(e) = self.inner.execute(
+            "UPDATE group_notification_config SET uid = ? WHERE group_id = ?",
+            (uid, group_id),
+        ) {
+            error!("Failed to update group {} owner: {}", group_id, e);
+            sys_event!(
+                ExecFault,
+                DfxCode::RDB_FAULT_04,
+                &format!("Failed to update group {} owner: {}", group_id, e)
+            );
+        }
+    }
+
+    /// Retrieves the application that created a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group to check
+    ///
+    /// # Returns
+    ///
+    /// * `Some(u64)` - The uid of the creating application
+    /// * `None` - If the group doesn't exist or was created before owners
+    ///   were recorded
+    pub(crate) fn query_group_owner(&self, group_id: u32) -> Option<u64> {
+        let mut set = match self.inner.query::<Option<i64>>(
+            "SELECT uid FROM group_notification_config where group_id = ?",
+            group_id,
+        ) {
+            Ok(set) => set,
+            Err(e) => {
+                error!("Failed to query group {} owner: {}", group_id, e);
+                return None;
+            }
+        };
+        set.next().flatten().map(|uid| uid as u64)
+    }
+
+    /// Records how the progress bar of a group is drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group to update
+    /// * `style` - The progress style of the group
+    pub(crate) fn update_group_progress_style(&self, group_id: u32, style: GroupProgressStyle) {
+        if let Err(e) = self.inner.execute(
+            "UPDATE group_notification_config SET progress_style = ? WHERE group_id = ?",
+            (style as u32, group_id),
+        ) {
+            error!("Failed to update group {} progress style: {}", group_id, e);
+            sys_event!(
+                ExecFault,
+                DfxCode::RDB_FAULT_04,
+                &format!("Failed to update group {} progress style: {}", group_id, e)
+            );
+        }
+    }
+
+    /// Retrieves how the progress bar of a group is drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group to check
+    ///
+    /// # Returns
+    ///
+    /// The progress style of the group, `GroupProgressStyle::Determinate` if
+    /// the group doesn't exist or was created before styles were recorded
+    pub(crate) fn query_group_progress_style(&self, group_id: u32) -> GroupProgressStyle {
+        let mut set = match self.inner.query::<Option<u32>>(
+            "SELECT progress_style FROM group_notification_config where group_id = ?",
+            group_id,
+        ) {
+            Ok(set) => set,
+            Err(e) => {
+                error!("Failed to query group {} progress style: {}", group_id, e);
+                return GroupProgressStyle::Determinate;
+            }
+        };
+        set.next()
+            .flatten()
+            .map_or(GroupProgressStyle::Determinate, GroupProgressStyle::from)
+    }
+
     /// Checks if a group exists in the database.
     /// 
     /// # Arguments
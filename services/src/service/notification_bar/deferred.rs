@@ -0,0 +1,182 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deferral of notifications while the notification service is unavailable.
+//!
+//! Early in boot the notification service may not be up yet. Notifications
+//! that fail for that reason are kept in a bounded queue and published again
+//! with exponential backoff once the service answers.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::ffi::NotifyContent;
+
+/// Maximum number of notifications kept while the service is unavailable.
+pub(crate) const MAX_DEFERRED: usize = 64;
+
+/// Delay before the first retry.
+const RETRY_BASE: Duration = Duration::from_secs(1);
+
+/// Upper bound of the delay between retries.
+const RETRY_MAX: Duration = Duration::from_secs(60);
+
+/// Counters of deferred notifications, shared with the dump command.
+#[derive(Default)]
+pub(crate) struct DeferredStats {
+    /// Notifications queued because the service was unavailable
+    pub(crate) deferred: AtomicU64,
+    /// Queued notifications published once the service came back
+    pub(crate) flushed: AtomicU64,
+    /// Queued notifications discarded as stale or because the queue was full
+    pub(crate) dropped: AtomicU64,
+}
+
+/// A notification waiting for the service to become available.
+struct Deferred {
+    content: NotifyContent,
+    /// Whether this reports a final outcome rather than progress
+    terminal: bool,
+}
+
+/// Bounded queue of notifications that could not be published yet.
+pub(crate) struct DeferredNotify {
+    queue: VecDeque<Deferred>,
+    backoff: Duration,
+    stats: Arc<DeferredStats>,
+}
+
+impl DeferredNotify {
+    /// Creates an empty queue.
+    pub(crate) fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            backoff: RETRY_BASE,
+            stats: Arc::new(DeferredStats::default()),
+        }
+    }
+
+    /// Returns the counters of this queue.
+    pub(crate) fn stats(&self) -> Arc<DeferredStats> {
+        self.stats.clone()
+    }
+
+    /// Checks whether no notification is waiting.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns the delay until the next retry.
+    pub(crate) fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// Doubles the delay until the next retry, up to `RETRY_MAX`.
+    pub(crate) fn back_off(&mut self) {
+        self.backoff = (self.backoff * 2).min(RETRY_MAX);
+    }
+
+    /// Publishes a notification, queueing it if the service is unavailable.
+    ///
+    /// Notifications are queued without being attempted while older ones
+    /// are still waiting, so they reach the service in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - Notification to publish
+    /// * `terminal` - Whether it reports a final outcome rather than progress
+    /// * `publish` - Publishes a notification, returning `false` if the service
+    ///   is unavailable
+    pub(crate) fn publish<P>(&mut self, content: NotifyContent, terminal: bool, mut publish: P)
+    where
+        P: FnMut(&NotifyContent) -> bool,
+    {
+        if self.queue.is_empty() && publish(&content) {
+            return;
+        }
+        self.push(content, terminal);
+    }
+
+    /// Publishes the queued terminal notifications in order.
+    ///
+    /// Queued progress notifications are outdated by now and are dropped.
+    /// Stops at the first notification the service still refuses.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the queue has been emptied
+    pub(crate) fn flush<P>(&mut self, mut publish: P) -> bool
+    where
+        P: FnMut(&NotifyContent) -> bool,
+    {
+        let len = self.queue.len();
+        self.queue.retain(|deferred| deferred.terminal);
+        let stale = (len - self.queue.len()) as u64;
+        self.stats.dropped.fetch_add(stale, Ordering::Relaxed);
+
+        while let Some(deferred) = self.queue.front() {
+            if !publish(&deferred.content) {
+                self.back_off();
+                return false;
+            }
+            self.queue.pop_front();
+            self.stats.flushed.fetch_add(1, Ordering::Relaxed);
+        }
+        info!("deferred notifications flushed");
+        self.backoff = RETRY_BASE;
+        true
+    }
+
+    /// Drops the queued notifications of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request_id` - Task or group whose notifications are dropped
+    pub(crate) fn discard(&mut self, request_id: u32) {
+        let len = self.queue.len();
+        self.queue
+            .retain(|deferred| deferred.content.request_id != request_id);
+        let dropped = (len - self.queue.len()) as u64;
+        self.stats.dropped.fetch_add(dropped, Ordering::Relaxed);
+    }
+
+    fn push(&mut self, content: NotifyContent, terminal: bool) {
+        self.stats.deferred.fetch_add(1, Ordering::Relaxed);
+        // A newer progress notification replaces the queued one of the same request
+        if !terminal {
+            if let Some(deferred) = self
+                .queue
+                .iter_mut()
+                .find(|d| !d.terminal && d.content.request_id == content.request_id)
+            {
+                deferred.content = content;
+                self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        }
+        if self.queue.len() >= MAX_DEFERRED {
+            // Prefer losing progress over losing a final outcome
+            let index = self.queue.iter().position(|d| !d.terminal).unwrap_or(0);
+            self.queue.remove(index);
+            self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        self.queue.push_back(Deferred { content, terminal });
+    }
+}
+
+#[cfg(test)]
+mod ut_deferred {
+    include!("../../../tests/ut/service/notification_bar/ut_deferred.rs");
+}
@@ -19,15 +19,17 @@
 //! infrastructure.
 
 mod database;
+mod deferred;
 mod notification_config;
 mod notify_flow;
 mod progress_size;
 mod publish;
 mod task_handle;
+mod throttle;
 mod typology;
 
 // Re-export for internal use within the service
-pub(crate) use notification_config::NotificationConfig;
+pub(crate) use notification_config::{GroupProgressStyle, NotificationConfig};
 
 /// Notification dispatcher for managing and publishing download task notifications.
 /// 
@@ -61,6 +63,8 @@ mod ffi {
         live_view: bool,
         progress_circle: ProgressCircle,
         x_mark: bool,
+        attention_button: bool,
+        attended: bool,
     }
 
     /// Progress circle information for notifications.
@@ -114,6 +118,19 @@ mod ffi {
         /// * `true` - If the task was successfully stopped
         /// * `false` - If the task stop failed
         fn stop_task(self: &TaskManagerWrapper, task_id: u32) -> bool;
+
+        /// Reports that the user expanded or collapsed a task's notification.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `task_id` - The ID of the task whose notification was touched
+        /// * `attention` - `true` if expanded, `false` if collapsed
+        /// 
+        /// # Returns
+        /// 
+        /// * `true` - If the event was delivered to the task manager
+        /// * `false` - If the task manager is unavailable
+        fn user_attention(self: &TaskManagerWrapper, task_id: u32, attention: bool) -> bool;
     }
 
     // C++ functions exposed to Rust
@@ -167,6 +184,23 @@ mod ffi {
         /// # Arguments
         /// 
         /// * `task_manager` - The task manager wrapper to handle notification interactions
-        fn SubscribeNotification(task_manager: Box<TaskManagerWrapper>);
+        ///
+        /// # Returns
+        ///
+        /// * `0` - If the subscription succeeded
+        /// * Error code - If the subscription failed
+        fn SubscribeNotification(task_manager: Box<TaskManagerWrapper>) -> i32;
+
+        /// Checks whether an error code means the notification service is not up yet.
+        ///
+        /// # Arguments
+        ///
+        /// * `errCode` - Error code returned by a notification call
+        ///
+        /// # Returns
+        ///
+        /// * `true` - If the call may succeed once the service becomes available
+        /// * `false` - Otherwise
+        fn IsServiceUnavailable(errCode: i32) -> bool;
     }
 }
@@ -43,6 +43,28 @@ pub(crate) struct NotificationConfig {
     pub(crate) visibility: u32,
 }
 
+/// How the progress bar of a group notification is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub(crate) enum GroupProgressStyle {
+    /// Always shows a progress bar
+    Determinate = 0,
+    /// Never shows a progress bar, for groups of unknown total size
+    Indeterminate,
+    /// Shows no progress bar until the sizes of all tasks are known
+    AutoSwitch,
+}
+
+impl From<u32> for GroupProgressStyle {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => GroupProgressStyle::Indeterminate,
+            2 => GroupProgressStyle::AutoSwitch,
+            _ => GroupProgressStyle::Determinate,
+        }
+    }
+}
+
 #[cfg(test)]
 impl NotificationConfig {
     /// Creates a new notification configuration with specified parameters.
@@ -19,15 +19,18 @@
 //! ensuring appropriate notifications are displayed based on task state changes.
 
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use ylong_runtime::sync::mpsc::{self, UnboundedReceiver};
+use ylong_runtime::time::timeout;
 
 use super::database::{CustomizedNotification, NotificationDb};
-use super::ffi::{NotifyContent, PublishNotification};
+use super::deferred::{DeferredNotify, DeferredStats};
+use super::ffi::{IsServiceUnavailable, NotifyContent, ProgressCircle, PublishNotification};
 use super::task_handle::cancel_notification;
-use super::NotificationDispatcher;
+use super::throttle::{PublishThrottle, FLUSH_INTERVAL, PUBLISH_LIMIT, PUBLISH_WINDOW};
+use super::{GroupProgressStyle, NotificationDispatcher};
 use crate::config::Action;
 use crate::info::State;
 use crate::manage::database::RequestDb;
@@ -63,6 +66,16 @@ pub(crate) struct NotifyFlow {
     group_completion_visibility: HashMap<u32, bool>,
     progress_visibility: HashMap<u32, bool>,
     completion_visibility: HashMap<u32, bool>,
+    // Notifications waiting for the notification service to become available
+    deferred: DeferredNotify,
+    // Notifications waiting to stay within the publishing rate limit
+    throttle: PublishThrottle,
+    // Whether subscribing to the notification bar still has to be retried
+    resubscribe: bool,
+    // Groups cancelled together with their tasks, whose updates are ignored
+    cancelled_groups: HashSet<u32>,
+    // Tasks whose notification the user expanded
+    attended: HashSet<u32>,
     // Channel for receiving notification events
     rx: mpsc::UnboundedReceiver<NotifyInfo>,
 }
@@ -78,6 +91,12 @@ pub(crate) struct GroupProgress {
     successful: usize,
     // Count of failed tasks
     failed: usize,
+    // Sizes reported by the tasks
+    task_size: HashMap<u32, u64>,
+    // How the progress bar is drawn
+    style: GroupProgressStyle,
+    // Whether the progress bar is drawn, switched on once under AutoSwitch
+    determinate: bool,
 }
 
 impl GroupProgress {
@@ -89,6 +108,76 @@ impl GroupProgress {
             task_state: HashMap::new(),
             successful: 0,
             failed: 0,
+            task_size: HashMap::new(),
+            style: GroupProgressStyle::Determinate,
+            determinate: true,
+        }
+    }
+
+    /// Sets how the progress bar of the group is drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The progress style of the group
+    pub(crate) fn set_style(&mut self, style: GroupProgressStyle) {
+        self.style = style;
+        self.determinate = style == GroupProgressStyle::Determinate;
+        self.settle();
+    }
+
+    /// Records the size of a task within the group, once it is known.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the task to update
+    /// * `total` - The size of the task, `None` while unknown
+    pub(crate) fn update_task_size(&mut self, task_id: u32, total: Option<u64>) {
+        if let Some(total) = total {
+            self.task_size.insert(task_id, total);
+            self.settle();
+        }
+    }
+
+    /// Returns the total size of the group, `None` while a task that has not
+    /// finished has not reported its size.
+    pub(crate) fn total_size(&self) -> Option<u64> {
+        if self.task_state.is_empty() {
+            return None;
+        }
+        self.task_state
+            .iter()
+            .map(|(task_id, state)| match self.task_size.get(task_id) {
+                Some(size) => Some(*size),
+                // A finished task has processed all it ever will
+                None if *state == State::Completed || *state == State::Failed => {
+                    Some(self.task_progress.get(task_id).copied().unwrap_or(0))
+                }
+                None => None,
+            })
+            .sum()
+    }
+
+    /// Returns the progress bar of the group notification.
+    ///
+    /// Counts finished tasks until the total size of the group is known.
+    pub(crate) fn progress_circle(&self) -> ProgressCircle {
+        if !self.determinate {
+            return ProgressCircle::close();
+        }
+        match self.total_size() {
+            Some(total) if total > 0 => ProgressCircle::open(self.total_progress, total),
+            _ => ProgressCircle::open((self.successful + self.failed) as u64, self.total() as u64),
+        }
+    }
+
+    /// Switches an `AutoSwitch` group to a progress bar once its total size
+    /// is known. It stays switched whatever is reported later.
+    fn settle(&mut self) {
+        if self.style == GroupProgressStyle::AutoSwitch
+            && !self.determinate
+            && self.total_size().is_some()
+        {
+            self.determinate = true;
         }
     }
 
@@ -106,6 +195,7 @@ impl GroupProgress {
         // Update total progress by the delta between new and previous values
         self.total_progress += processed - *prev;
         *prev = processed;
+        self.settle();
     }
 
     /// Updates the state for a specific task within the group.
@@ -126,6 +216,7 @@ impl GroupProgress {
                 } else if state == State::Failed {
                     self.failed += 1;
                 }
+                self.settle();
                 return;
             }
         };
@@ -153,6 +244,7 @@ impl GroupProgress {
             self.successful -= 1;
         }
         *prev = state;
+        self.settle();
     }
 
     /// Returns the number of successfully completed tasks.
@@ -204,6 +296,8 @@ pub struct ProgressNotify {
     pub(crate) file_name: String,
     /// API version in use
     pub(crate) version: Version,
+    /// Estimated remaining time in milliseconds (if available)
+    pub(crate) eta_ms: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
@@ -234,6 +328,12 @@ pub(crate) enum NotifyInfo {
     Unregister(u64, u32, u32),
     /// Group completion notification
     GroupEventual(u32, u64),
+    /// Retry subscribing to the notification bar until the service is up
+    Resubscribe,
+    /// Clear the notification of a cancelled group
+    CancelGroup(u32),
+    /// The user expanded or collapsed the notification of a task
+    Attention(u32, bool),
 }
 
 #[derive(Clone, Copy)]
@@ -244,6 +344,27 @@ enum NotifyType {
     Task,
 }
 
+/// Publishes a notification to the notification bar.
+///
+/// # Returns
+///
+/// `false` if the notification service is unavailable and publishing should be
+/// retried later
+fn publish(content: &NotifyContent) -> bool {
+    let ret = PublishNotification(content);
+    if IsServiceUnavailable(ret) {
+        info!(
+            "notification service unavailable, defer {}",
+            content.request_id
+        );
+        return false;
+    }
+    if ret != 0 {
+        error!("publish notification {} failed {}", content.request_id, ret);
+    }
+    true
+}
+
 impl NotifyFlow {
     /// Creates a new notification flow manager.
     /// 
@@ -267,10 +388,20 @@ impl NotifyFlow {
             progress_visibility: HashMap::new(),
             group_completion_visibility: HashMap::new(),
             group_progress_visibility: HashMap::new(),
+            deferred: DeferredNotify::new(),
+            throttle: PublishThrottle::new(PUBLISH_LIMIT, PUBLISH_WINDOW, NOTIFY_PROGRESS_INTERVAL),
+            resubscribe: false,
+            cancelled_groups: HashSet::new(),
+            attended: HashSet::new(),
             rx,
         }
     }
 
+    /// Returns the counters of notifications deferred by this flow.
+    pub(crate) fn deferred_stats(&self) -> Arc<DeferredStats> {
+        self.deferred.stats()
+    }
+
     /// Starts the notification flow processing loop.
     /// 
     /// Spawns an asynchronous task that processes incoming notification events
@@ -278,7 +409,29 @@ impl NotifyFlow {
     pub(crate) fn run(mut self) {
         runtime_spawn(async move {
             loop {
-                let info = match self.rx.recv().await {
+                // Wake up for a retry while the notification service is unavailable,
+                // or to publish the notifications held back by the rate limit
+                let wake = if self.resubscribe || !self.deferred.is_empty() {
+                    Some(self.deferred.backoff())
+                } else if !self.throttle.is_empty() {
+                    Some(FLUSH_INTERVAL)
+                } else {
+                    None
+                };
+                let recv = match wake {
+                    Some(wake) => match timeout(wake, self.rx.recv()).await {
+                        Ok(recv) => recv,
+                        Err(_) => {
+                            if self.resubscribe || !self.deferred.is_empty() {
+                                self.retry();
+                            }
+                            self.flush_throttle();
+                            continue;
+                        }
+                    },
+                    None => self.rx.recv().await,
+                };
+                let info = match recv {
                     Ok(message) => message,
                     Err(e) => {
                         error!("Notification flow channel error: {:?}", e);
@@ -291,6 +444,12 @@ impl NotifyFlow {
                     }
                 };
 
+                if self.is_cancelled(&info) {
+                    continue;
+                }
+                // Outdated progress need not survive an unavailable service
+                let terminal =
+                    !matches!(info, NotifyInfo::Progress(_) | NotifyInfo::AttachGroup(..));
                 if let Some(content) = match info {
                     NotifyInfo::Eventual(info) => self.publish_completed_notify(&info),
                     NotifyInfo::Progress(info) => self.publish_progress_notification(info),
@@ -301,13 +460,119 @@ impl NotifyFlow {
                     NotifyInfo::Unregister(uid, task_id, group_id) => {
                         self.unregister_task(uid, task_id, group_id)
                     }
+                    NotifyInfo::Resubscribe => {
+                        self.resubscribe = true;
+                        None
+                    }
+                    NotifyInfo::CancelGroup(group_id) => {
+                        self.cancel_group(group_id);
+                        None
+                    }
+                    NotifyInfo::Attention(task_id, attention) => {
+                        self.user_attention(task_id, attention);
+                        None
+                    }
                 } {
-                    PublishNotification(&content);
+                    let deferred = &mut self.deferred;
+                    self.throttle.submit(
+                        content,
+                        terminal,
+                        get_current_timestamp(),
+                        |content, terminal| deferred.publish(content, terminal, publish),
+                    );
                 }
             }
         });
     }
 
+    /// Retries what failed while the notification service was unavailable.
+    ///
+    /// Subscribes to the notification bar first, then flushes the deferred
+    /// notifications.
+    fn retry(&mut self) {
+        if self.resubscribe {
+            if !NotificationDispatcher::get_instance().try_subscribe() {
+                self.deferred.back_off();
+                return;
+            }
+            self.resubscribe = false;
+        }
+        self.deferred.flush(publish);
+    }
+
+    /// Publishes the notifications held back by the rate limit once the
+    /// notification service is available again.
+    fn flush_throttle(&mut self) {
+        if self.resubscribe || !self.deferred.is_empty() {
+            return;
+        }
+        let deferred = &mut self.deferred;
+        self.throttle
+            .flush(get_current_timestamp(), |content, terminal| {
+                deferred.publish(content, terminal, publish)
+            });
+    }
+
+    /// Checks whether a notification event belongs to a cancelled group.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - Notification event to check
+    fn is_cancelled(&mut self, info: &NotifyInfo) -> bool {
+        if self.cancelled_groups.is_empty() {
+            return false;
+        }
+        let group_id = match info {
+            NotifyInfo::Progress(ProgressNotify { task_id, .. })
+            | NotifyInfo::Eventual(EventualNotify { task_id, .. }) => {
+                match self.get_request_id(*task_id) {
+                    NotifyType::Group(group_id) => group_id,
+                    NotifyType::Task => return false,
+                }
+            }
+            NotifyInfo::AttachGroup(group_id, ..)
+            | NotifyInfo::Unregister(_, _, group_id)
+            | NotifyInfo::GroupEventual(group_id, _) => *group_id,
+            NotifyInfo::Resubscribe | NotifyInfo::CancelGroup(_) | NotifyInfo::Attention(..) => {
+                return false
+            }
+        };
+        self.cancelled_groups.contains(&group_id)
+    }
+
+    /// Records whether the user expanded the notification of a task, so its
+    /// next progress offers the opposite button.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - Task whose notification was touched
+    /// * `attention` - Whether the notification was expanded
+    fn user_attention(&mut self, task_id: u32, attention: bool) {
+        if attention {
+            self.attended.insert(task_id);
+        } else {
+            self.attended.remove(&task_id);
+        }
+    }
+
+    /// Clears the notification and the cached state of a cancelled group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - Group that was cancelled
+    fn cancel_group(&mut self, group_id: u32) {
+        info!("Cancel group notification {}", group_id);
+        self.cancelled_groups.insert(group_id);
+        self.group_notify_progress.remove(&group_id);
+        self.group_customized_notify.remove(&group_id);
+        self.group_progress_visibility.remove(&group_id);
+        self.group_completion_visibility.remove(&group_id);
+        self.last_notify_map.remove(&group_id);
+        self.deferred.discard(group_id);
+        self.throttle.discard(group_id);
+        cancel_notification(group_id);
+    }
+
     /// Handles task unregistration from notifications.
     /// 
     /// # Arguments
@@ -376,7 +641,7 @@ impl NotifyFlow {
         let Some(state) = RequestDb::get_instance().query_task_state(task_id) else {
             return;
         };
-        if state == State::Removed.repr {
+        if state == State::Removed.repr || state == State::PendingRemoval.repr {
             return;
         }
         group_progress.update_task_state(task_id, State::from(state));
@@ -395,6 +660,7 @@ impl NotifyFlow {
     /// A `GroupProgress` instance with current state from database
     fn get_group_progress(database: &NotificationDb, group_id: u32) -> GroupProgress {
         let mut group_progress = GroupProgress::new();
+        group_progress.set_style(database.query_group_progress_style(group_id));
         for task_id in database.query_group_tasks(group_id) {
             Self::update_db_task_state_and_progress(&mut group_progress, task_id);
         }
@@ -575,6 +841,7 @@ impl NotifyFlow {
                     }
                 };
                 progress.update_task_progress(info.task_id, info.processed);
+                progress.update_task_size(info.task_id, info.total);
 
                 if !progress_interval_check {
                     return None;
@@ -601,10 +868,12 @@ impl NotifyFlow {
                 if !self.check_progress_visibility(info.task_id) {
                     return None;
                 }
-                NotifyContent::task_progress_notify(
+                let mut content = NotifyContent::task_progress_notify(
                     self.task_customized_notify(info.task_id),
                     &info,
-                )
+                );
+                content.attended = self.attended.contains(&info.task_id);
+                content
             }
         };
         Some(content)
@@ -650,6 +919,7 @@ impl NotifyFlow {
     /// * `Some(NotifyContent)` - If a notification should be published
     /// * `None` - If no notification is needed
     fn publish_completed_notify(&mut self, info: &EventualNotify) -> Option<NotifyContent> {
+        self.attended.remove(&info.task_id);
         let content = match self.get_request_id(info.task_id) {
             NotifyType::Group(group_id) => {
                 let is_progress_visible = self.check_progress_visibility_from_group(group_id);
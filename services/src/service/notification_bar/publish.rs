@@ -26,10 +26,12 @@ use ylong_runtime::fastrand::fast_random;
 use ylong_runtime::sync::mpsc::{self, unbounded_channel};
 
 use super::database::NotificationDb;
+use super::deferred::DeferredStats;
 use super::notify_flow::{EventualNotify, NotifyFlow, NotifyInfo, ProgressNotify};
-use super::task_handle::{cancel_notification, NotificationCheck};
+use super::task_handle::{cancel_notification, subscribe, NotificationCheck};
 use crate::info::TaskInfo;
-use crate::service::notification_bar::NotificationConfig;
+use crate::manage::task_manager::TaskManagerTx;
+use crate::service::notification_bar::{GroupProgressStyle, NotificationConfig};
 use crate::task::request_task::RequestTask;
 use crate::utils::get_current_duration;
 
@@ -47,6 +49,10 @@ pub struct NotificationDispatcher {
     task_gauge: Mutex<HashMap<u32, Arc<AtomicBool>>>,
     /// Channel for sending notification information to the notification flow.
     flow: mpsc::UnboundedSender<NotifyInfo>,
+    /// Counters of notifications deferred while the notification service is unavailable.
+    deferred: Arc<DeferredStats>,
+    /// Task manager to subscribe with once the notification service is available.
+    unsubscribed: Mutex<Option<TaskManagerTx>>,
 }
 
 impl NotificationDispatcher {
@@ -60,12 +66,16 @@ impl NotificationDispatcher {
         // Set up channel for notification messages
         let (tx, rx) = unbounded_channel();
         // Start notification flow processor
-        NotifyFlow::new(rx, database.clone()).run();
+        let flow = NotifyFlow::new(rx, database.clone());
+        let deferred = flow.deferred_stats();
+        flow.run();
         
         Self {
             database: database.clone(),
             task_gauge: Mutex::new(HashMap::new()),
             flow: tx,
+            deferred,
+            unsubscribed: Mutex::new(None),
         }
     }
 
@@ -82,6 +92,39 @@ impl NotificationDispatcher {
         &INSTANCE
     }
 
+    /// Returns the counters of deferred, flushed and dropped notifications.
+    pub(crate) fn deferred_stats(&self) -> Arc<DeferredStats> {
+        self.deferred.clone()
+    }
+
+    /// Retries subscribing to the notification bar until the service is up.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_manager` - Channel for sending task management events
+    pub(crate) fn defer_subscribe(&self, task_manager: TaskManagerTx) {
+        *self.unsubscribed.lock().unwrap() = Some(task_manager);
+        let _ = self.flow.send(NotifyInfo::Resubscribe);
+    }
+
+    /// Subscribes to the notification bar if an earlier attempt was deferred.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the notification service is still unavailable
+    pub(crate) fn try_subscribe(&self) -> bool {
+        let mut unsubscribed = self.unsubscribed.lock().unwrap();
+        let Some(task_manager) = unsubscribed.take() else {
+            return true;
+        };
+        if subscribe(task_manager.clone()) {
+            info!("notification bar subscribed");
+            return true;
+        }
+        *unsubscribed = Some(task_manager);
+        false
+    }
+
     /// Clears notification information for a specific task.
     /// 
     /// # Arguments
@@ -267,6 +310,7 @@ impl NotificationDispatcher {
             total,
             multi_upload,
             version: task.conf.version,
+            eta_ms: task.eta_ms(&progress),
         };
         
         // Send notification through the channel
@@ -393,6 +437,44 @@ impl NotificationDispatcher {
         true
     }
 
+    /// Returns the application that created a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - ID of the group
+    ///
+    /// # Returns
+    ///
+    /// The uid of the creating application, `None` if the group does not
+    /// exist or was created before owners were recorded
+    pub(crate) fn group_owner(&self, group_id: u32) -> Option<u64> {
+        self.database.query_group_owner(group_id)
+    }
+
+    /// Returns the gauge flag, customized title and tasks of a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - ID of the group
+    ///
+    /// # Returns
+    ///
+    /// `None` if the group does not exist
+    pub(crate) fn group_members(&self, group_id: u32) -> Option<(bool, Option<String>, Vec<u32>)> {
+        if !self.database.contains_group(group_id) {
+            return None;
+        }
+        let title = self
+            .database
+            .query_group_customized_notification(group_id)
+            .and_then(|content| content.title);
+        Some((
+            self.database.is_gauge(group_id),
+            title,
+            self.database.query_group_tasks(group_id),
+        ))
+    }
+
     /// Deletes a notification group and disables further attachments.
     /// 
     /// Marks the group as disabled and sends a notification to update the UI.
@@ -426,6 +508,37 @@ impl NotificationDispatcher {
         true
     }
 
+    /// Deletes a notification group together with its notifications.
+    ///
+    /// Unlike [`delete_group`](Self::delete_group), no completion notification
+    /// is published for the group, and later updates of its tasks are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - ID of the group to cancel
+    /// * `task_ids` - Tasks of the group
+    pub(crate) fn cancel_group(&self, group_id: u32, task_ids: &[u32]) {
+        info!("Cancel group {}", group_id);
+        self.database.disable_attach_group(group_id);
+        let task_gauge = self.task_gauge.lock().unwrap();
+        for task_id in task_ids {
+            if let Some(gauge) = task_gauge.get(task_id) {
+                gauge.store(false, Ordering::Release);
+            }
+        }
+        let _ = self.flow.send(NotifyInfo::CancelGroup(group_id));
+    }
+
+    /// Records that the user expanded or collapsed the notification of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - Task whose notification was touched
+    /// * `attention` - Whether the notification was expanded
+    pub(crate) fn user_attention(&self, task_id: u32, attention: bool) {
+        let _ = self.flow.send(NotifyInfo::Attention(task_id, attention));
+    }
+
     /// Creates a new notification group with the specified configuration.
     /// 
     /// Generates a unique group ID, stores the group configuration in the database,
@@ -439,6 +552,8 @@ impl NotificationDispatcher {
     /// * `want_agent` - Optional agent identifier
     /// * `disable` - Whether to disable notifications for this group
     /// * `visibility` - Visibility level of the notifications
+    /// * `progress_style` - How the progress bar of the group is drawn
+    /// * `uid` - User ID of the application creating the group
     /// 
     /// # Returns
     /// 
@@ -451,6 +566,8 @@ impl NotificationDispatcher {
         want_agent: Option<String>,
         disable: bool,
         visibility: u32,
+        progress_style: GroupProgressStyle,
+        uid: u64,
     ) -> u32 {
         // Generate a unique group ID using random number generation
         let new_group_id = loop {
@@ -461,8 +578,8 @@ impl NotificationDispatcher {
         };
         
         info!(
-            "Create group {} gauge {} customized_title {:?} customized_text {:?} want_agent {:?} disable {} visibility {}",
-            new_group_id, gauge, title, text, want_agent, disable, visibility
+            "Create group {} gauge {} customized_title {:?} customized_text {:?} want_agent {:?} disable {} visibility {} progress_style {:?}",
+            new_group_id, gauge, title, text, want_agent, disable, visibility, progress_style
         );
 
         // Get current time for group creation timestamp
@@ -471,6 +588,9 @@ impl NotificationDispatcher {
         // Store group configuration
         self.database
             .update_group_config(new_group_id, gauge, current_time, !disable, visibility);
+        self.database.update_group_owner(new_group_id, uid);
+        self.database
+            .update_group_progress_style(new_group_id, progress_style);
             
         // Set up customized notification if provided
         if title.is_some() || text.is_some() || want_agent.is_some() {
@@ -20,13 +20,14 @@
 use std::sync::atomic::Ordering;
 
 use super::database::NotificationDb;
-use super::ffi::{self, SubscribeNotification};
+use super::ffi::{self, IsServiceUnavailable, SubscribeNotification};
 use super::NotificationDispatcher;
 use crate::config::{Mode, Version};
 use crate::error::ErrorCode;
 use crate::info::{State, TaskInfo};
 use crate::manage::database::RequestDb;
 use crate::manage::events::TaskManagerEvent;
+use crate::manage::group_info::GroupInfo;
 use crate::manage::task_manager::TaskManagerTx;
 use crate::manage::TaskManager;
 use crate::task::request_task::RequestTask;
@@ -91,6 +92,66 @@ impl TaskManager {
         
         ErrorCode::ErrOk
     }
+
+    /// Queries the membership and aggregate progress of a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - ID of the group to query
+    ///
+    /// # Returns
+    ///
+    /// `None` if the group does not exist
+    pub(crate) fn query_group(&self, group_id: u32) -> Option<GroupInfo> {
+        let (gauge, title, task_ids) =
+            NotificationDispatcher::get_instance().group_members(group_id)?;
+        let tasks: Vec<TaskInfo> = task_ids
+            .into_iter()
+            .filter_map(|task_id| RequestDb::get_instance().get_task_info(task_id))
+            .collect();
+        Some(GroupInfo::new(group_id, gauge, title, &tasks))
+    }
+
+    /// Stops or removes all tasks of a group and deletes the group.
+    ///
+    /// The notifications of the group are cleared before the tasks are
+    /// touched, so that their final updates do not publish them again.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - User ID owning the group and its tasks
+    /// * `group_id` - ID of the group to cancel
+    /// * `remove_members` - Whether the tasks are removed instead of stopped
+    ///
+    /// # Returns
+    ///
+    /// The result of stopping or removing each task, `None` if the group does
+    /// not exist
+    pub(crate) fn cancel_group(
+        &mut self,
+        uid: u64,
+        group_id: u32,
+        remove_members: bool,
+    ) -> Option<Vec<(u32, ErrorCode)>> {
+        let (_, _, task_ids) = NotificationDispatcher::get_instance().group_members(group_id)?;
+        info!(
+            "Cancel group {} tasks {:?} remove {}",
+            group_id, task_ids, remove_members
+        );
+        NotificationDispatcher::get_instance().cancel_group(group_id, &task_ids);
+        let results = task_ids
+            .into_iter()
+            .map(|task_id| {
+                let ret = if remove_members {
+                    self.remove(uid, task_id)
+                } else {
+                    self.stop(uid, task_id)
+                };
+                (task_id, ret)
+            })
+            .collect();
+        Some(results)
+    }
 }
 
 /// Trait for checking if a task is eligible for notifications.
@@ -246,6 +307,22 @@ impl TaskManagerWrapper {
         self.event_inner(task_id, TaskManagerEvent::stop)
     }
 
+    /// Reports whether the user is watching a task's notification.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `task_id` - ID of the task whose notification was touched
+    /// * `attention` - Whether the notification was expanded
+    /// 
+    /// # Returns
+    /// 
+    /// Boolean indicating whether the event was delivered
+    pub(crate) fn user_attention(&self, task_id: u32, attention: bool) -> bool {
+        NotificationDispatcher::get_instance().user_attention(task_id, attention);
+        self.task_manager
+            .send_event(TaskManagerEvent::user_attention(task_id, attention))
+    }
+
     /// Internal function for sending task events and handling responses.
     /// 
     /// # Type Parameters
@@ -288,13 +365,33 @@ impl TaskManagerWrapper {
 /// Subscribes to notification bar events and connects them to task management.
 /// 
 /// Creates a TaskManagerWrapper and registers it with the notification system
-/// to handle user interactions with notifications.
+/// to handle user interactions with notifications. If the notification service
+/// is not up yet, the subscription is retried in the background.
 /// 
 /// # Arguments
 /// 
 /// * `task_manager` - Channel for sending task management events
 pub(crate) fn subscribe_notification_bar(task_manager: TaskManagerTx) {
-    SubscribeNotification(Box::new(TaskManagerWrapper::new(task_manager)));
+    if !subscribe(task_manager.clone()) {
+        info!("notification service unavailable, subscribe later");
+        NotificationDispatcher::get_instance().defer_subscribe(task_manager);
+    }
+}
+
+/// Subscribes to notification bar events once.
+///
+/// # Returns
+///
+/// `false` if the notification service is unavailable
+pub(super) fn subscribe(task_manager: TaskManagerTx) -> bool {
+    let ret = SubscribeNotification(Box::new(TaskManagerWrapper::new(task_manager)));
+    if IsServiceUnavailable(ret) {
+        return false;
+    }
+    if ret != 0 {
+        error!("subscribe notification failed {}", ret);
+    }
+    true
 }
 
 impl RequestDb {
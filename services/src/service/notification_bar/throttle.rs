@@ -0,0 +1,213 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rate limiting of published notifications.
+//!
+//! The notification service drops notifications published faster than it
+//! allows. With many tasks running, their progress updates easily exceed
+//! that rate and final outcomes get dropped with them. Publishing is
+//! therefore limited per request and in total. Updates over the limit wait,
+//! a newer progress update replacing the waiting one of the same request,
+//! and are published once there is room again. Final outcomes always wait
+//! instead of being replaced.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+use super::ffi::NotifyContent;
+
+/// Maximum number of notifications published within `PUBLISH_WINDOW`.
+pub(crate) const PUBLISH_LIMIT: usize = 10;
+
+/// Window in milliseconds over which `PUBLISH_LIMIT` applies.
+pub(crate) const PUBLISH_WINDOW: u64 = 1000;
+
+/// Interval between two flushes of the waiting notifications.
+pub(crate) const FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A notification waiting for room to be published.
+struct Pending {
+    content: NotifyContent,
+    /// Whether this reports a final outcome rather than progress
+    terminal: bool,
+}
+
+/// Limits how often notifications are published, in total and per request.
+pub(crate) struct PublishThrottle {
+    /// Maximum number of notifications published within `window`
+    limit: usize,
+    window: u64,
+    /// Minimum time between two progress notifications of a request
+    interval: u64,
+    /// Times of the notifications published within the last `window`
+    published: VecDeque<u64>,
+    /// Time of the last notification published for each request
+    last: HashMap<u32, u64>,
+    pending: VecDeque<Pending>,
+}
+
+impl PublishThrottle {
+    /// Creates a throttle publishing at most `limit` notifications per
+    /// `window` milliseconds and progress of a request at most every
+    /// `interval` milliseconds.
+    pub(crate) fn new(limit: usize, window: u64, interval: u64) -> Self {
+        Self {
+            limit,
+            window,
+            interval,
+            published: VecDeque::new(),
+            last: HashMap::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Checks whether no notification is waiting.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Publishes a notification if the limits allow it, otherwise keeps it
+    /// until they do.
+    ///
+    /// Waiting notifications are flushed first. A notification waits as long
+    /// as an older one of the same request is waiting, so the updates of a
+    /// request are published in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - Notification to publish
+    /// * `terminal` - Whether it reports a final outcome rather than progress
+    /// * `now` - Current time in milliseconds
+    /// * `publish` - Publishes a notification and whether it is terminal
+    pub(crate) fn submit<P>(
+        &mut self,
+        content: NotifyContent,
+        terminal: bool,
+        now: u64,
+        mut publish: P,
+    ) where
+        P: FnMut(NotifyContent, bool),
+    {
+        self.flush(now, &mut publish);
+
+        let request_id = content.request_id;
+        if terminal {
+            // A final outcome makes the waiting progress of the request outdated
+            self.pending
+                .retain(|pending| pending.terminal || pending.content.request_id != request_id);
+        } else if let Some(pending) = self
+            .pending
+            .iter_mut()
+            .find(|pending| !pending.terminal && pending.content.request_id == request_id)
+        {
+            pending.content = content;
+            return;
+        }
+
+        let waiting = self
+            .pending
+            .iter()
+            .any(|pending| pending.content.request_id == request_id);
+        if !waiting && self.admits(request_id, terminal, now) {
+            self.record(request_id, terminal, now);
+            publish(content, terminal);
+        } else {
+            self.pending.push_back(Pending { content, terminal });
+        }
+    }
+
+    /// Publishes the waiting notifications the limits allow, final outcomes
+    /// first.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time in milliseconds
+    /// * `publish` - Publishes a notification and whether it is terminal
+    pub(crate) fn flush<P>(&mut self, now: u64, mut publish: P)
+    where
+        P: FnMut(NotifyContent, bool),
+    {
+        self.drain(now, true, &mut publish);
+        self.drain(now, false, &mut publish);
+    }
+
+    /// Drops the waiting notifications of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request_id` - Task or group whose notifications are dropped
+    pub(crate) fn discard(&mut self, request_id: u32) {
+        self.pending
+            .retain(|pending| pending.content.request_id != request_id);
+        self.last.remove(&request_id);
+    }
+
+    /// Publishes the waiting notifications the limits allow in order, only
+    /// the terminal ones if `terminal_only`.
+    fn drain<P>(&mut self, now: u64, terminal_only: bool, publish: &mut P)
+    where
+        P: FnMut(NotifyContent, bool),
+    {
+        // Requests with an older notification still waiting
+        let mut held = HashSet::new();
+        let mut index = 0;
+        while index < self.pending.len() {
+            let pending = &self.pending[index];
+            let (request_id, terminal) = (pending.content.request_id, pending.terminal);
+            if (terminal || !terminal_only)
+                && !held.contains(&request_id)
+                && self.admits(request_id, terminal, now)
+            {
+                let pending = self.pending.remove(index).unwrap();
+                self.record(request_id, terminal, now);
+                publish(pending.content, terminal);
+            } else {
+                held.insert(request_id);
+                index += 1;
+            }
+        }
+    }
+
+    /// Checks whether a notification of a request may be published now.
+    fn admits(&mut self, request_id: u32, terminal: bool, now: u64) -> bool {
+        while self
+            .published
+            .front()
+            .is_some_and(|time| time + self.window <= now)
+        {
+            self.published.pop_front();
+        }
+        if self.published.len() >= self.limit {
+            return false;
+        }
+        terminal
+            || !self
+                .last
+                .get(&request_id)
+                .is_some_and(|last| now < last + self.interval)
+    }
+
+    fn record(&mut self, request_id: u32, terminal: bool, now: u64) {
+        self.published.push_back(now);
+        if terminal {
+            self.last.remove(&request_id);
+        } else {
+            self.last.insert(request_id, now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_throttle {
+    include!("../../../tests/ut/service/notification_bar/ut_throttle.rs");
+}
@@ -28,12 +28,16 @@ const UPLOAD_SUCCESS: &str = "request_agent_upload_success\0";        // Templat
 const UPLOAD_FAIL: &str = "request_agent_upload_fail\0";            // Template for upload failure notification title
 const TASK_COUNT: &str = "request_agent_task_count\0";            // Template for task count text (successful/failed)
 const DOWNLOAD_COMPLETE: &str = "request_agent_download_complete\0"; // Template for download complete notification title
+const ETA_UNDER_MINUTE: &str = "request_agent_eta_under_minute\0"; // Remaining time below a minute
+const ETA_MINUTES: &str = "request_agent_eta_minutes\0";          // Template for remaining time in minutes
+const ETA_OVER_HOUR: &str = "request_agent_eta_over_hour\0";      // Remaining time of an hour or more
 
 use super::database::CustomizedNotification;
 use super::ffi::{GetSystemResourceString, NotifyContent, ProgressCircle};
 use super::notify_flow::{GroupProgress, ProgressNotify};
 use super::progress_size;
 use crate::config::Action;
+use crate::task::eta::{eta_bucket, EtaBucket};
 
 /// Formats progress as a percentage string with two decimal places.
 /// 
@@ -58,6 +62,25 @@ fn progress_percentage(current: u64, total: u64) -> String {
     )
 }
 
+/// Formats an estimated remaining time with the system resource strings.
+/// 
+/// # Arguments
+/// 
+/// * `eta_ms` - Estimated remaining time in milliseconds
+/// 
+/// # Returns
+/// 
+/// Localized remaining time string (e.g., "about 3 min")
+fn eta_text(eta_ms: u64) -> String {
+    match eta_bucket(eta_ms) {
+        EtaBucket::UnderMinute => GetSystemResourceString(ETA_UNDER_MINUTE),
+        EtaBucket::Minutes(minutes) => {
+            GetSystemResourceString(ETA_MINUTES).replace("%d", &minutes.to_string())
+        }
+        EtaBucket::OverHour => GetSystemResourceString(ETA_OVER_HOUR),
+    }
+}
+
 /// Formats progress size as a human-readable string.
 /// 
 /// Uses the progress_size module to convert bytes to a human-readable format.
@@ -104,7 +127,7 @@ impl NotifyContent {
             .as_mut()
             .and_then(|c| c.title.take())
             .unwrap_or_else(|| match action {
-                Action::Download => {
+                Action::Download | Action::LocalLoopback => {
                     if is_successful {
                         GetSystemResourceString(DOWNLOAD_SUCCESS)
                     } else {
@@ -134,6 +157,8 @@ impl NotifyContent {
             live_view: false,       // Not a live updating notification
             progress_circle: ProgressCircle::close(),
             x_mark: false,          // No close button needed for completed task
+            attention_button: false,
+            attended: false,
         }
     }
 
@@ -159,7 +184,7 @@ impl NotifyContent {
             .as_mut()
             .and_then(|c| c.title.take())
             .unwrap_or_else(|| match info.action {
-                Action::Download => {
+                Action::Download | Action::LocalLoopback => {
                     let title = GetSystemResourceString(DOWNLOAD_FILE);
                     match info.total {
                         Some(total) => {
@@ -184,10 +209,13 @@ impl NotifyContent {
                 _ => unreachable!(),
             });
 
-        // Use custom text if provided, otherwise use file name
+        // Use custom text if provided, otherwise use file name and remaining time
         let text = customized.as_mut()
             .and_then(|c| c.text.clone())
-            .unwrap_or_else(|| info.file_name.clone());
+            .unwrap_or_else(|| match info.eta_ms {
+                Some(eta_ms) => format!("{} · {}", info.file_name, eta_text(eta_ms)),
+                None => info.file_name.clone(),
+            });
         
         let want_agent = customized.and_then(|c| c.want_agent).unwrap_or_default();
         
@@ -206,6 +234,9 @@ impl NotifyContent {
             live_view: true,
             progress_circle,
             x_mark: true,
+            // Expanding the notification boosts the task, see `NotifyFlow`
+            attention_button: true,
+            attended: false,
         }
     }
 
@@ -245,7 +276,7 @@ impl NotifyContent {
             .as_mut()
             .and_then(|c| c.title.take())
             .unwrap_or_else(|| match action {
-                Action::Download => text_download,
+                Action::Download | Action::LocalLoopback => text_download,
                 Action::Upload => format!("上传完成 {}", progress_size(current_size)),
                 _ => unreachable!(),
             });
@@ -275,6 +306,8 @@ impl NotifyContent {
             live_view: false,
             progress_circle: ProgressCircle::close(),
             x_mark: false,
+            attention_button: false,
+            attended: false,
         }
     }
 
@@ -305,7 +338,7 @@ impl NotifyContent {
             .as_mut()
             .and_then(|c| c.title.take())
             .unwrap_or_else(|| match action {
-                Action::Download => {
+                Action::Download | Action::LocalLoopback => {
                     let title = GetSystemResourceString(DOWNLOAD_FILE);
                     title.replace("%s", &progress_size(group_progress.processed()))
                 }
@@ -335,8 +368,7 @@ impl NotifyContent {
         let text = customized.as_mut().and_then(|c| c.text.take()).unwrap_or(text_count);
         let want_agent = customized.and_then(|c| c.want_agent).unwrap_or_default();
 
-        let progress_circle =
-            ProgressCircle::open((successful + failed) as u64, group_progress.total() as u64);
+        let progress_circle = group_progress.progress_circle();
         Self {
             title,
             text,
@@ -346,6 +378,8 @@ impl NotifyContent {
             live_view: true,
             progress_circle,
             x_mark: false,
+            attention_button: false,
+            attended: false,
         }
     }
 }
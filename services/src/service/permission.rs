@@ -19,6 +19,8 @@
 //! capabilities.
 
 use crate::config::Action;
+use crate::manage::keep_awake::KEEP_AWAKE_PERMISSION;
+use crate::manage::system_search::SYSTEM_SEARCH_PERMISSION;
 use crate::utils::check_permission;
 
 /// Permission string for internet access.
@@ -53,6 +55,24 @@ impl PermissionChecker {
         check_permission(MANAGER_DOWNLOAD)
     }
 
+    /// Checks if the caller may search the tasks of all applications.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the caller has the system search permission, `false` otherwise.
+    pub(crate) fn check_system_search() -> bool {
+        check_permission(SYSTEM_SEARCH_PERMISSION)
+    }
+
+    /// Checks if the caller may keep the device awake while its tasks run.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the caller has the keep awake permission, `false` otherwise.
+    pub(crate) fn check_keep_awake() -> bool {
+        check_permission(KEEP_AWAKE_PERMISSION)
+    }
+
     /// Checks the caller's management permissions for download and upload operations.
     /// 
     /// # Returns
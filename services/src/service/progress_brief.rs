@@ -0,0 +1,180 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lightweight progress polling for in-process C++ callers.
+//!
+//! System widgets polling a few tasks several times per second read their
+//! progress here instead of subscribing through the UDS channel. Reads never
+//! go through the task manager event loop: running tasks are looked up in a
+//! table they join and leave themselves, other tasks fall back to their
+//! database row.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+
+pub(crate) use ffi::ProgressBrief;
+
+use crate::error::ErrorCode;
+use crate::manage::database::RequestDb;
+use crate::task::files::check_current_account;
+use crate::task::notify::Progress;
+use crate::task::request_task::RequestTask;
+use crate::utils::get_current_timestamp;
+
+/// Maximum number of tasks queried by one batched call.
+pub(crate) const PROGRESS_BRIEF_MAX: usize = 32;
+
+/// Tasks currently running, keyed by task ID.
+static LIVE_TASKS: Mutex<Option<HashMap<u32, Weak<RequestTask>>>> = Mutex::new(None);
+
+/// Makes the progress of a running task readable without the database.
+pub(crate) fn register_live(task: &Arc<RequestTask>) {
+    LIVE_TASKS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(task.task_id(), Arc::downgrade(task));
+}
+
+/// Removes a task that stopped running from the live tasks.
+pub(crate) fn unregister_live(task_id: u32) {
+    if let Some(tasks) = LIVE_TASKS.lock().unwrap().as_mut() {
+        tasks.remove(&task_id);
+    }
+}
+
+fn live_task(task_id: u32) -> Option<Arc<RequestTask>> {
+    LIVE_TASKS
+        .lock()
+        .unwrap()
+        .as_ref()?
+        .get(&task_id)
+        .and_then(Weak::upgrade)
+}
+
+/// Returns the progress of a task.
+///
+/// # Arguments
+///
+/// * `task_id` - The ID of the task.
+/// * `uid` - The uid of the caller.
+/// * `manager` - Whether the caller may query tasks of other uids.
+///
+/// # Returns
+///
+/// The progress with `code` set to `ErrOk`, or only `code` set to
+/// `TaskNotFound` if the task does not exist, belongs to another uid the
+/// caller may not query or to an inactive account.
+pub(crate) fn progress_brief(task_id: u32, uid: u64, manager: bool) -> ProgressBrief {
+    let (owner, brief) = match live_task(task_id) {
+        Some(task) => {
+            let speed = task.eta.lock().unwrap().speed(get_current_timestamp());
+            let brief = brief_of(&task.progress.lock().unwrap(), speed);
+            (task.uid(), brief)
+        }
+        None => match RequestDb::get_instance().get_task_info(task_id) {
+            Some(info) => (info.uid(), brief_of(&info.progress, 0)),
+            None => return not_found(),
+        },
+    };
+    if (owner != uid && !manager) || !check_current_account(owner) {
+        error!(
+            "Progress brief, failed: check task uid. tid: {}, uid: {}",
+            task_id, uid
+        );
+        return not_found();
+    }
+    brief
+}
+
+/// Returns the progress of up to [`PROGRESS_BRIEF_MAX`] tasks in the order
+/// of `task_ids`.
+///
+/// Longer batches are rejected with `Other` for every task.
+pub(crate) fn progress_briefs(task_ids: &[u32], uid: u64, manager: bool) -> Vec<ProgressBrief> {
+    if task_ids.len() > PROGRESS_BRIEF_MAX {
+        info!("Progress brief: out of size: {}", task_ids.len());
+        let rejected = ProgressBrief {
+            code: ErrorCode::Other as i32,
+            ..Default::default()
+        };
+        return vec![rejected; task_ids.len()];
+    }
+    task_ids
+        .iter()
+        .map(|task_id| progress_brief(*task_id, uid, manager))
+        .collect()
+}
+
+fn brief_of(progress: &Progress, speed: u64) -> ProgressBrief {
+    // A single unknown size makes the total unknown.
+    let total = if progress.sizes.iter().any(|size| *size < 0) {
+        -1
+    } else {
+        progress.sizes.iter().sum()
+    };
+    ProgressBrief {
+        code: ErrorCode::ErrOk as i32,
+        state: progress.common_data.state,
+        processed: progress.common_data.total_processed as u64,
+        total,
+        speed,
+    }
+}
+
+fn not_found() -> ProgressBrief {
+    ProgressBrief {
+        code: ErrorCode::TaskNotFound as i32,
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "oh")]
+fn check_down_permission() -> bool {
+    crate::service::permission::PermissionChecker::check_down_permission()
+}
+
+#[cfg(not(feature = "oh"))]
+fn check_down_permission() -> bool {
+    false
+}
+
+fn get_task_progress_brief(task_id: u32, uid: u64) -> ProgressBrief {
+    progress_brief(task_id, uid, check_down_permission())
+}
+
+fn get_task_progress_briefs(task_ids: &[u32], uid: u64) -> Vec<ProgressBrief> {
+    progress_briefs(task_ids, uid, check_down_permission())
+}
+
+#[allow(unreachable_pub)]
+#[cxx::bridge(namespace = "OHOS::Request")]
+mod ffi {
+    // Progress of a task for callers polling it
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    struct ProgressBrief {
+        code: i32,
+        state: u8,
+        processed: u64,
+        total: i64,
+        speed: u64,
+    }
+
+    // Rust functions exposed to C++, safe to call from any thread
+    extern "Rust" {
+        #[cxx_name = "GetTaskProgressBrief"]
+        fn get_task_progress_brief(task_id: u32, uid: u64) -> ProgressBrief;
+        #[cxx_name = "GetTaskProgressBriefs"]
+        fn get_task_progress_briefs(task_ids: &[u32], uid: u64) -> Vec<ProgressBrief>;
+    }
+}
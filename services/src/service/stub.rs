@@ -183,8 +183,28 @@ impl RemoteStub for RequestServiceStub {
             interface::ATTACH_GROUP => self.attach_group(data, reply),
             interface::DELETE_GROUP => self.delete_group(data, reply),
             interface::SET_MAX_SPEED => self.set_max_speed(data, reply),
+            interface::SUBSCRIBE_TASK_LIST => self.subscribe_task_list(data, reply),
+            interface::UNSUBSCRIBE_TASK_LIST => self.unsubscribe_task_list(data, reply),
+            interface::COUNT_BY_STATE => self.count_by_state(data, reply),
             interface::SET_MODE => self.set_mode(data, reply),
             interface::DISABLE_TASK_NOTIFICATION => self.disable_task_notifications(data, reply),
+            interface::SET_GLOBAL_MAX_SPEED => self.set_global_max_speed(data, reply),
+            interface::SET_DESTINATION => self.set_destination(data, reply),
+            interface::QUERY_GROUP => self.query_group(data, reply),
+            interface::SUBSCRIBE_BATCH => self.subscribe_batch(data, reply),
+            interface::REMOVE_WITH_GRACE => self.remove_with_grace(data, reply),
+            interface::UNDO_REMOVE => self.undo_remove(data, reply),
+            interface::PURGE_REMOVED => self.purge_removed(data, reply),
+            interface::ENABLE_JOURNAL => self.enable_journal(data, reply),
+            interface::QUERY_EFFECTIVE_CONFIG => self.query_effective_config(data, reply),
+            interface::SOFT_STOP => self.soft_stop(data, reply),
+            interface::QUERY_SPEED_HISTORY => self.query_speed_history(data, reply),
+            interface::CANCEL_GROUP => self.cancel_group(data, reply),
+            interface::EXPLAIN_TASK => self.explain_task(data, reply),
+            interface::CHANGED_SINCE => self.changed_since(data, reply),
+            interface::SEARCH_SYSTEM => self.search_system(data, reply),
+            interface::MIGRATE_LEGACY_TASKS => self.migrate_legacy_tasks(data, reply),
+            interface::ISSUE_TASK_TOKEN => self.issue_task_token(data, reply),
             _ => Err(IpcStatusCode::Failed),
         };
 
@@ -296,6 +316,15 @@ pub(crate) fn serialize_task_info(tf: TaskInfo, reply: &mut MsgParcel) -> IpcRes
         reply.write(&(item.reason.repr as u32))?;
         reply.write(&(item.message))?;
     }
+    reply.write(&(tf.effective_speed_limit))?;
+
+    // Serialize the captured upload response body, if any
+    reply.write(&(tf.response_body.is_some()))?;
+    if let Some(body) = tf.response_body {
+        reply.write(&(body.truncated))?;
+        reply.write(&(body.data))?;
+    }
+    reply.write(&(tf.client_tag))?;
     Ok(())
 }
 
@@ -385,5 +414,8 @@ pub(crate) fn serialize_task_config(config: TaskConfig, reply: &mut MsgParcel) -
     // Serialize minimum speed requirements
     reply.write(&(config.common_data.min_speed.speed))?;
     reply.write(&(config.common_data.min_speed.duration))?;
+
+    // Serialize per-task options
+    config.write_options(reply)?;
     Ok(())
 }
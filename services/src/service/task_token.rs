@@ -0,0 +1,342 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signed capability tokens for sharing a task with another process.
+//!
+//! The token of a task used to be a secret chosen by the application at
+//! construct time and compared with the copy stored in the database. The
+//! owner of a task may now ask the service to issue a capability token
+//! instead:
+//!
+//! ```text
+//! rt1.<task_id>.<scope>.<expiry>.<key_id>.<mac>
+//! ```
+//!
+//! where `mac` is the HMAC-SHA256 of everything before it under a key only
+//! the service holds. A process given such a token may touch the task, or
+//! also get it depending on the [`TokenScope`], until the expiry. Pausing,
+//! resuming, stopping and removing the task stay with its owner. The service
+//! verifies the token without looking the task up, and a token altered in
+//! any field is rejected. Tokens are not checked against the caller, the
+//! handlers accept them from processes of the account of the task only.
+//!
+//! A new key is generated at every service start. Tokens signed by the
+//! previous key stay valid for [`ROTATION_GRACE_MS`] so tasks shared before
+//! a restart keep working. Tokens chosen by applications are still accepted
+//! unless the [`LEGACY_TOKENS_PARAM`] system parameter is set to 0.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+
+use request_utils::hash::{hmac_sha256, HMAC_SHA256_LEN};
+
+use crate::utils::get_current_timestamp;
+
+/// Directory holding the signing key.
+const KEY_DIR: &str = if cfg!(test) {
+    "test_files"
+} else {
+    "/data/service/el1/public/database/request"
+};
+/// Name of the signing key file.
+const KEY_FILE: &str = "request_token_key";
+/// First field of every signed token.
+const TOKEN_PREFIX: &str = "rt1";
+
+/// Time tokens of the previous key stay valid after a rotation.
+pub(crate) const ROTATION_GRACE_MS: u64 = 24 * 60 * 60 * 1000;
+/// Lifetime of a token issued without one.
+pub(crate) const DEFAULT_TTL_MS: u64 = 60 * 60 * 1000;
+/// Longest lifetime of a token.
+pub(crate) const MAX_TTL_MS: u64 = 7 * 24 * 60 * 60 * 1000;
+/// System parameter disabling application chosen tokens when set to 0.
+pub(crate) const LEGACY_TOKENS_PARAM: &str = "const.request.legacy_task_tokens";
+
+/// Operations a signed token grants on its task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum TokenScope {
+    /// Touch the task only.
+    Query = 0,
+    /// Touch and get the task.
+    Full = 1,
+}
+
+impl TokenScope {
+    pub(crate) fn from_repr(repr: u8) -> Option<Self> {
+        match repr {
+            0 => Some(Self::Query),
+            1 => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    fn grants(self, needed: TokenScope) -> bool {
+        self as u8 >= needed as u8
+    }
+}
+
+/// Outcome of checking the token presented for a task.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum TokenCheck {
+    /// A signed token granting the scope needed.
+    Granted,
+    /// A token chosen by the application, to compare with the stored one.
+    Legacy(String),
+    /// A signed token which is altered, expired or does not grant the
+    /// operation, or a legacy token while those are disabled.
+    Denied,
+}
+
+/// Size of a signing key in bytes.
+const KEY_LEN: usize = HMAC_SHA256_LEN;
+
+#[derive(Clone)]
+struct SigningKey {
+    id: u32,
+    bytes: [u8; KEY_LEN],
+}
+
+impl SigningKey {
+    /// Generates a key from the system random source, `None` if it cannot
+    /// be read. No weaker source is used instead.
+    fn generate(id: u32) -> Option<Self> {
+        let mut bytes = [0; KEY_LEN];
+        match File::open("/dev/urandom").and_then(|mut f| f.read_exact(&mut bytes)) {
+            Ok(()) => Some(Self { id, bytes }),
+            Err(e) => {
+                error!("task token key from urandom failed {:?}", e);
+                None
+            }
+        }
+    }
+
+    /// Returns the hex MAC of `payload`, `None` if it could not be computed.
+    fn sign(&self, payload: &str) -> Option<String> {
+        let mac = hmac_sha256(&self.bytes, payload.as_bytes())?;
+        Some(mac.iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+
+    fn load(path: &Path) -> Option<Self> {
+        let stored = fs::read(path).ok()?;
+        if stored.len() != 4 + KEY_LEN {
+            error!("task token key of {} bytes ignored", stored.len());
+            return None;
+        }
+        let id = u32::from_be_bytes([stored[0], stored[1], stored[2], stored[3]]);
+        let mut bytes = [0; KEY_LEN];
+        bytes.copy_from_slice(&stored[4..]);
+        Some(Self { id, bytes })
+    }
+
+    fn store(&self, path: &Path) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        file.write_all(&self.id.to_be_bytes())?;
+        file.write_all(&self.bytes)?;
+        file.sync_all()
+    }
+}
+
+struct Keys {
+    /// Key signing new tokens, `None` if no key could be generated, in which
+    /// case no token is issued.
+    current: Option<SigningKey>,
+    /// Key replaced by `current` and the time of the rotation.
+    previous: Option<(SigningKey, u64)>,
+}
+
+/// Issues and verifies the signed task tokens of the service.
+pub(crate) struct TaskTokens {
+    keys: Mutex<Keys>,
+    legacy: bool,
+}
+
+impl TaskTokens {
+    /// Returns the tokens of the service, rotating the stored key.
+    pub(crate) fn get_instance() -> &'static Self {
+        static INSTANCE: LazyLock<TaskTokens> = LazyLock::new(|| {
+            #[cfg(feature = "oh")]
+            let legacy = crate::utils::get_int_parameter(LEGACY_TOKENS_PARAM, 1) != 0;
+            #[cfg(not(feature = "oh"))]
+            let legacy = true;
+            info!("task tokens, legacy tokens accepted {}", legacy);
+            TaskTokens::restore(Path::new(KEY_DIR), legacy, get_current_timestamp())
+        });
+        &INSTANCE
+    }
+
+    pub(crate) fn new(legacy: bool) -> Self {
+        Self {
+            keys: Mutex::new(Keys {
+                current: SigningKey::generate(0),
+                previous: None,
+            }),
+            legacy,
+        }
+    }
+
+    /// Loads the key stored in `dir`, replaces it by a new one and stores
+    /// the new key.
+    pub(crate) fn restore(dir: &Path, legacy: bool, now: u64) -> Self {
+        let path = dir.join(KEY_FILE);
+        let tokens = Self::new(legacy);
+        if let Some(stored) = SigningKey::load(&path) {
+            tokens.keys.lock().unwrap().current = Some(stored);
+            tokens.rotate_at(now);
+        }
+        if let Some(current) = &tokens.keys.lock().unwrap().current {
+            if let Err(e) = current.store(&path) {
+                error!("task token key store failed {:?}", e);
+            }
+        }
+        tokens
+    }
+
+    /// Replaces the signing key, keeping the current one for the grace
+    /// period. The current key is kept if no new one can be generated.
+    pub(crate) fn rotate_at(&self, now: u64) {
+        let mut keys = self.keys.lock().unwrap();
+        let id = keys
+            .current
+            .as_ref()
+            .map_or(0, |key| key.id.wrapping_add(1));
+        let Some(next) = SigningKey::generate(id) else {
+            error!("task token key not rotated");
+            return;
+        };
+        info!("task token key rotated to {}", id);
+        if let Some(previous) = keys.current.replace(next) {
+            keys.previous = Some((previous, now));
+        }
+    }
+
+    /// Issues a token granting `scope` on `task_id` for `ttl_ms`, 0 for
+    /// [`DEFAULT_TTL_MS`].
+    pub(crate) fn issue(&self, task_id: u32, scope: TokenScope, ttl_ms: u64) -> String {
+        self.issue_at(task_id, scope, ttl_ms, get_current_timestamp())
+    }
+
+    pub(crate) fn issue_at(
+        &self,
+        task_id: u32,
+        scope: TokenScope,
+        ttl_ms: u64,
+        now: u64,
+    ) -> String {
+        let ttl_ms = match ttl_ms {
+            0 => DEFAULT_TTL_MS,
+            ttl => ttl.min(MAX_TTL_MS),
+        };
+        let keys = self.keys.lock().unwrap();
+        let Some(current) = &keys.current else {
+            error!("task token of tid {} not issued, no key", task_id);
+            return String::new();
+        };
+        let payload = format!(
+            "{}.{}.{}.{}.{}",
+            TOKEN_PREFIX,
+            task_id,
+            scope as u8,
+            now + ttl_ms,
+            current.id
+        );
+        match current.sign(&payload) {
+            Some(mac) => format!("{}.{}", payload, mac),
+            None => {
+                error!("task token of tid {} not signed", task_id);
+                String::new()
+            }
+        }
+    }
+
+    /// Checks whether `token` grants `needed` on `task_id`.
+    pub(crate) fn check(&self, token: String, task_id: u32, needed: TokenScope) -> TokenCheck {
+        self.check_at(token, task_id, needed, get_current_timestamp())
+    }
+
+    pub(crate) fn check_at(
+        &self,
+        token: String,
+        task_id: u32,
+        needed: TokenScope,
+        now: u64,
+    ) -> TokenCheck {
+        // Application chosen tokens may start with the prefix itself, only
+        // the prefix and its separator mark a signed token.
+        let signed = token
+            .strip_prefix(TOKEN_PREFIX)
+            .is_some_and(|rest| rest.starts_with('.'));
+        if !signed {
+            return match self.legacy {
+                true => TokenCheck::Legacy(token),
+                false => TokenCheck::Denied,
+            };
+        }
+        match self.verify(&token, task_id, needed, now) {
+            true => TokenCheck::Granted,
+            false => {
+                info!("task token of tid {} denied", task_id);
+                TokenCheck::Denied
+            }
+        }
+    }
+
+    fn verify(&self, token: &str, task_id: u32, needed: TokenScope, now: u64) -> bool {
+        let Some((payload, mac)) = token.rsplit_once('.') else {
+            return false;
+        };
+        let fields = payload.split('.').collect::<Vec<_>>();
+        let [TOKEN_PREFIX, id, scope, expiry, key_id] = fields[..] else {
+            return false;
+        };
+        let (Ok(id), Some(scope), Ok(expiry), Ok(key_id)) = (
+            id.parse::<u32>(),
+            scope.parse::<u8>().ok().and_then(TokenScope::from_repr),
+            expiry.parse::<u64>(),
+            key_id.parse::<u32>(),
+        ) else {
+            return false;
+        };
+        let keys = self.keys.lock().unwrap();
+        let key = match (&keys.current, &keys.previous) {
+            (Some(key), _) if key.id == key_id => key,
+            (_, Some((key, rotated))) if key.id == key_id && now < rotated + ROTATION_GRACE_MS => {
+                key
+            }
+            _ => return false,
+        };
+        key.sign(payload)
+            .is_some_and(|expected| constant_time_eq(expected.as_bytes(), mac.as_bytes()))
+            && id == task_id
+            && scope.grants(needed)
+            && now < expiry
+    }
+}
+
+/// Compares two byte strings in a time independent of where they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod ut_task_token {
+    include!("../../tests/ut/service/ut_task_token.rs");
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `Accept-Encoding` negotiation of tasks.
+//!
+//! A task configured with `accept_encoding` advertises exactly those content
+//! codings, replacing any `Accept-Encoding` given by the application headers
+//! or the default headers of the application. `identity` asks the server not
+//! to compress the body at all.
+//!
+//! The service does not decode response bodies: whatever coding the server
+//! picked, the bytes are written to the file as they were received.
+
+/// Name of the header carrying the content codings a task accepts.
+const ACCEPT_ENCODING: &str = "Accept-Encoding";
+
+/// Content codings a task may advertise.
+pub(crate) const SUPPORTED_ENCODINGS: [&str; 4] = ["identity", "gzip", "deflate", "br"];
+
+/// Returns whether `value` is a valid `accept_encoding`, a comma separated
+/// list of supported codings each with an optional `q` weight. An empty value
+/// is valid and leaves the negotiation to the headers of the task.
+pub(crate) fn is_supported(value: &str) -> bool {
+    value.is_empty()
+        || value.split(',').all(|item| {
+            let mut params = item.split(';');
+            let coding = params.next().unwrap_or_default().trim();
+            SUPPORTED_ENCODINGS
+                .iter()
+                .any(|supported| supported.eq_ignore_ascii_case(coding))
+                && params.all(is_weight)
+        })
+}
+
+/// Returns whether `param` is a `q` weight between 0 and 1.
+fn is_weight(param: &str) -> bool {
+    param
+        .trim()
+        .strip_prefix("q=")
+        .and_then(|weight| weight.parse::<f32>().ok())
+        .is_some_and(|weight| (0.0..=1.0).contains(&weight))
+}
+
+/// Replaces the `Accept-Encoding` of `headers`, sorted by name, with `value`.
+/// An empty `value` leaves `headers` untouched.
+pub(crate) fn apply(headers: &mut Vec<(String, String)>, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    headers.retain(|(name, _)| !name.eq_ignore_ascii_case(ACCEPT_ENCODING));
+    headers.push((ACCEPT_ENCODING.to_string(), value.to_string()));
+    headers.sort();
+}
+
+#[cfg(test)]
+mod ut_accept_encoding {
+    include!("../../tests/ut/task/ut_accept_encoding.rs");
+}
@@ -26,6 +26,7 @@
 //! - Connection timeout and speed monitoring
 
 use std::error::Error;
+use std::time::Duration;
 
 use ylong_http_client::async_impl::{Client, Request};
 use ylong_http_client::{
@@ -38,8 +39,12 @@ cfg_oh! {
 }
 
 use super::files::BundleCache;
-use crate::task::config::{Action, TaskConfig};
+use crate::task::config::{Action, TaskConfig, TlsProtocol};
+use crate::task::debug_log::TaskLog;
 use crate::task::files::convert_path;
+use crate::task::ip_preference::{PreferenceResolver, UsedFamily};
+use crate::task::net_binding::NetResolver;
+use crate::task::redirect::{CrossHostLimit, RedirectRecorder};
 
 /// Builds an HTTP client with configuration based on the provided task settings.
 ///
@@ -48,6 +53,7 @@ use crate::task::files::convert_path;
 /// * `config` - The task configuration containing connection parameters, certificates,
 ///             proxy settings, and other client options.
 /// * `total_timeout` - The total timeout in seconds for the entire client operation.
+/// * `used_family` - The slot the resolver records the address family it used in.
 /// * `system` - [Only in OHOS] System configuration containing system-wide settings.
 ///
 /// # Returns
@@ -82,6 +88,7 @@ use crate::task::files::convert_path;
 pub(crate) fn build_client(
     config: &TaskConfig,
     total_timeout: u64,
+    used_family: UsedFamily,
     #[cfg(feature = "oh")] mut system: SystemConfig,
 ) -> Result<Client, Box<dyn Error + Send + Sync>> {
     const DEFAULT_CONNECTION_TIMEOUT: u64 = 60;
@@ -92,12 +99,24 @@ pub(crate) fn build_client(
         connection_timeout = DEFAULT_CONNECTION_TIMEOUT;
     }
 
+    let Some((min_tls, max_tls)) = tls_versions(config) else {
+        return Err(Box::new(HttpClientError::other("Invalid TLS settings")));
+    };
+
     // Set up basic client configuration with required timeouts and TLS version
     // Ensure connections are established within a reasonable time and operations complete promptly
     let mut client = Client::builder()
         .connect_timeout(Timeout::from_secs(connection_timeout))  // Time to establish connection
         .total_timeout(Timeout::from_secs(total_timeout))         // Total time limit for entire request
-        .min_tls_version(TlsVersion::TLS_1_2);                    // Enforce secure TLS version
+        .min_tls_version(min_tls.version());                      // Enforce secure TLS version
+
+    // Restrict the TLS versions and ciphers as configured by the task
+    if let Some(max_tls) = max_tls {
+        client = client.max_tls_version(max_tls.version());
+    }
+    if !config.tls_ciphers.is_empty() {
+        client = client.set_cipher_list(&config.tls_ciphers);
+    }
     
     // Set socket ownership for proper resource management
     client = client.sockets_owner(config.common_data.uid as u32, config.common_data.uid as u32);
@@ -111,6 +130,25 @@ pub(crate) fn build_client(
         client = client.redirect(Redirect::none());
     }
 
+    // Resolve hosts on the network the task is bound to, or through the
+    // shared DNS cache by the address family preference of the task
+    let log = TaskLog::new(config.common_data.task_id, config.debug);
+    if config.net_id != 0 {
+        client = client.dns_resolver(NetResolver::new(
+            log,
+            config.net_id,
+            config.ip_preference,
+            used_family,
+        ));
+    } else {
+        client = client.dns_resolver(PreferenceResolver::new(
+            log,
+            config.ip_preference,
+            config.dns_ttl_override_ms.map(Duration::from_millis),
+            used_family,
+        ));
+    }
+
     // Configure minimum speed requirements if specified to detect stalled connections
     if config.common_data.min_speed.speed > 0 && config.common_data.min_speed.duration > 0 {
         client = client
@@ -152,29 +190,46 @@ pub(crate) fn build_client(
         client = client.add_public_key_pins(pinned_key);
     }
 
+    // Skip certificate verification of tasks allowed to, leaving a trace of it
+    if config.insecure_skip_verify {
+        warn!(
+            "task {} skips certificate verification, uid {}, url {}",
+            config.common_data.task_id,
+            config.common_data.uid,
+            config.request_url()
+        );
+        client = client.danger_accept_invalid_certs(true);
+    }
+
+    // Checks of redirects handed to the redirect recorder of the task
+    #[allow(unused_mut)]
+    let mut redirect_check: Option<Box<dyn Interceptor + Send + Sync>> = None;
+
     // Apply domain policy checks for atomic services (system-specific security check)
     const ATOMIC_SERVICE: u32 = 1;
     if config.bundle_type == ATOMIC_SERVICE {
         let domain_type = action_to_domain_type(config.common_data.action);
         info!(
             "ApiPolicy Domain check, tid {}, bundle {}, domain_type {}, url {}",
-            config.common_data.task_id, &config.bundle, &domain_type, &config.url
+            config.common_data.task_id, &config.bundle, &domain_type, config.request_url()
         );
         
         #[cfg(feature = "oh")]
-        if let Some(is_accessed) = check_url_domain(&config.bundle, &domain_type, &config.url) {
+        if let Some(is_accessed) =
+            check_url_domain(&config.bundle, &domain_type, config.request_url())
+        {
             if !is_accessed {
                 // Log policy violation and return error
                 error!(
                     "Intercept request by domain check, tid {}, bundle {}, domain_type {}, url {}",
-                    config.common_data.task_id, &config.bundle, &domain_type, &config.url
+                    config.common_data.task_id, &config.bundle, &domain_type, config.request_url()
                 );
                 sys_event!(
                     ExecFault,
                     DfxCode::URL_POLICY_FAULT_00,
                     &format!(
                     "Intercept request by domain check, tid {}, bundle {}, domain_type {}, url {}",
-                config.common_data.task_id, &config.bundle, &domain_type, &config.url)
+                config.common_data.task_id, &config.bundle, &domain_type, config.request_url())
                 );
 
                 // Wrap the HttpClientError in a Box to fit the function's return type requirement
@@ -186,7 +241,7 @@ pub(crate) fn build_client(
         } else {
             info!(
                 "Intercept request by domain check, tid {}, domain_type {}, url {}",
-                config.common_data.task_id, &domain_type, &config.url
+                config.common_data.task_id, &domain_type, config.request_url()
             );
         }
 
@@ -196,7 +251,11 @@ pub(crate) fn build_client(
         #[cfg(feature = "oh")]
         {
             let interceptors = DomainInterceptor::new(config.bundle.clone(), domain_type);
-            client = client.interceptor(interceptors);
+            if config.report_redirects || config.max_cross_host_redirects.is_some() {
+                redirect_check = Some(Box::new(interceptors));
+            } else {
+                client = client.interceptor(interceptors);
+            }
         }
 
         info!(
@@ -205,6 +264,30 @@ pub(crate) fn build_client(
         );
     }
 
+    // Cap the redirects to another host, checking them first
+    if let Some(max) = config.max_cross_host_redirects {
+        let limit = CrossHostLimit::new(
+            config.common_data.task_id,
+            config.request_url(),
+            max,
+            redirect_check.take(),
+        );
+        if config.report_redirects {
+            redirect_check = Some(Box::new(limit));
+        } else {
+            client = client.interceptor(limit);
+        }
+    }
+
+    // Record the redirects of tasks reporting them, checking them first
+    if config.report_redirects {
+        client = client.interceptor(RedirectRecorder::new(
+            config.common_data.task_id,
+            config.request_url(),
+            redirect_check,
+        ));
+    }
+
     // Finalize client construction
     // All configuration steps are complete including timeouts, redirect policy,
     // proxy settings, certificates, public key pinning, and domain policy enforcement
@@ -216,6 +299,65 @@ pub(crate) fn build_client(
     ))
 }
 
+/// Honours `insecure_skip_verify` for privileged callers only.
+///
+/// # Returns
+///
+/// Whether the task skips certificate verification, the flag of an
+/// unprivileged caller is cleared.
+pub(crate) fn check_insecure_skip_verify(config: &mut TaskConfig, privileged: bool) -> bool {
+    if config.insecure_skip_verify && !privileged {
+        error!(
+            "insecure_skip_verify ignored, caller uid {} not privileged",
+            config.common_data.uid
+        );
+        config.insecure_skip_verify = false;
+    }
+    config.insecure_skip_verify
+}
+
+/// Returns the lowest and highest TLS versions the task negotiates.
+///
+/// The highest version is `None` if the task leaves it to the system.
+///
+/// # Returns
+///
+/// `None` if the settings contradict each other: the lowest version is above
+/// the highest one, or a cipher list is set that is malformed or cannot apply
+/// because TLS 1.2 is excluded, whose cipher suites are the only ones that
+/// can be constrained.
+pub(crate) fn tls_versions(config: &TaskConfig) -> Option<(TlsProtocol, Option<TlsProtocol>)> {
+    let min = config.min_tls_version.unwrap_or(TlsProtocol::Tls12);
+    if config.max_tls_version.is_some_and(|max| max < min) {
+        return None;
+    }
+    if !config.tls_ciphers.is_empty()
+        && (min > TlsProtocol::Tls12 || !is_cipher_list(&config.tls_ciphers))
+    {
+        return None;
+    }
+    Some((min, config.max_tls_version))
+}
+
+/// Checks an OpenSSL cipher list has no empty or malformed entry.
+fn is_cipher_list(ciphers: &str) -> bool {
+    ciphers.split(':').all(|cipher| {
+        !cipher.is_empty()
+            && cipher
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_+!@=".contains(c))
+    })
+}
+
+impl TlsProtocol {
+    fn version(self) -> TlsVersion {
+        match self {
+            TlsProtocol::Tls12 => TlsVersion::TLS_1_2,
+            TlsProtocol::Tls13 => TlsVersion::TLS_1_3,
+        }
+    }
+}
+
 /// Creates a proxy configuration from task settings.
 ///
 /// # Arguments
@@ -293,7 +435,7 @@ fn build_task_certificate_pins(
     // Create public key pinning for the target URL
     Ok(Some(cvt_res_error!(
         PubKeyPins::builder()
-            .add(&config.url, &config.certificate_pins)
+            .add(config.request_url(), &config.certificate_pins)
             .build()
             .map_err(Box::new),
         "Create task certificate pinned_key failed",
@@ -514,3 +656,8 @@ impl Interceptor for DomainInterceptor {
         }
     }
 }
+
+#[cfg(test)]
+mod ut_client {
+    include!("../../tests/ut/task/ut_client.rs");
+}
@@ -11,6 +11,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::os::fd::{FromRawFd, IntoRawFd, RawFd};
@@ -24,13 +25,16 @@ cfg_oh! {
     use ipc::parcel::Deserialize;
 }
 
+use super::client::check_insecure_skip_verify;
+use super::loopback::{LOOPBACK_FAIL_AT, LOOPBACK_RATE, LOOPBACK_SIZE};
 use super::reason::Reason;
+use super::retry_after::DEFAULT_RETRY_AFTER_CAP_MS;
 use super::ATOMIC_SERVICE;
 use crate::manage::account::GetOhosAccountUid;
-use crate::manage::network::{NetworkState, NetworkType};
+use crate::manage::network::{NetworkInfo, NetworkState, NetworkType};
 use crate::utils::c_wrapper::{CFileSpec, CFormItem, CStringWrapper};
-use crate::utils::form_item::{FileSpec, FormItem};
-use crate::utils::{hashmap_to_string, query_calling_bundle};
+use crate::utils::form_item::{FileSpec, FormItem, FORM_URLENCODED};
+use crate::utils::{get_current_timestamp, hashmap_to_string, query_calling_bundle};
 
 // C++ bridge for exposing Rust types to C++
 #[cxx::bridge(namespace = "OHOS::Request")]
@@ -45,6 +49,8 @@ mod ffi {
         Upload,
         /// Wildcard action that matches any operation type.
         Any,
+        /// Developer-mode action writing generated data instead of downloading.
+        LocalLoopback,
     }
 
     /// Determines the execution context for a task.
@@ -82,6 +88,95 @@ pub enum NetworkConfig {
     Cellular,
 }
 
+/// Per-task override of the metered flag reported by the platform.
+///
+/// Some Wi-Fi networks are phone hotspots the platform reports as unmetered,
+/// tasks may state how the current network should be treated instead.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum MeteredOverride {
+    /// Treat the network as metered.
+    Metered = 1,
+    /// Treat the network as unmetered.
+    Unmetered,
+}
+
+/// What happens to the files of a task when it is removed.
+///
+/// Only files the service opened by path are deleted, files passed as
+/// descriptors by the application are always kept.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum PartialFilePolicy {
+    /// Keep the files.
+    Keep = 1,
+    /// Delete the files, even of a finished download.
+    Delete,
+    /// Delete the files unless the download finished.
+    DeleteIfIncomplete,
+}
+
+/// How creating a download that is already active is handled.
+///
+/// A download is a duplicate of an active task of the same application with
+/// the same URL and destination.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum DuplicatePolicy {
+    /// Create the task regardless of active duplicates.
+    Allow = 0,
+    /// Warn and return the active task instead of creating another one.
+    Warn,
+    /// Fail the creation of a duplicate.
+    Reject,
+}
+
+/// Address families a task connects to and their order.
+///
+/// Some carrier networks have broken IPv6 where connections hang for seconds
+/// before falling back, tasks may restrict or order the families instead.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum IpPreference {
+    /// Connect to the addresses in the order they are resolved.
+    Default = 0,
+    /// Connect to IPv4 addresses only.
+    V4Only,
+    /// Connect to IPv6 addresses only.
+    V6Only,
+    /// Try IPv4 first and fall back to IPv6.
+    V4Preferred,
+    /// Try IPv6 first and fall back to IPv4.
+    V6Preferred,
+}
+
+/// How a download resumes from the bytes it already has.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ResumeStrategy {
+    /// Asks for the remaining bytes with a `Range` header.
+    RangeHeader,
+    /// Asks for the remaining bytes with the query parameter `name` set to
+    /// the byte offset, for servers ignoring `Range`.
+    QueryParam {
+        /// Name of the query parameter carrying the offset.
+        name: String,
+    },
+    /// Restarts from zero on every resume.
+    None,
+}
+
+/// TLS protocol versions a task may restrict its connections to.
+///
+/// Versions older than TLS 1.2 are not supported by the service.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[repr(u8)]
+pub enum TlsProtocol {
+    /// TLS 1.2.
+    Tls12 = 0,
+    /// TLS 1.3.
+    Tls13,
+}
+
 /// Minimum speed requirements for a network task.
 /// 
 /// If the network speed falls below the specified threshold for the given duration,
@@ -104,6 +199,15 @@ pub struct Timeout {
     pub(crate) total_timeout: u64,
 }
 
+/// W3C trace context propagated with every request of a task.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TraceContext {
+    /// `traceparent` header value, its span id replaced on every attempt.
+    pub(crate) traceparent: String,
+    /// `tracestate` header value, sent as it is if present.
+    pub(crate) tracestate: Option<String>,
+}
+
 /// Core configuration shared by all types of network tasks.
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
@@ -152,6 +256,29 @@ pub(crate) struct CommonTaskConfig {
     pub(crate) timeout: Timeout,
 }
 
+/// Maximum length in bytes of a task's client tag.
+pub(crate) const MAX_CLIENT_TAG_LEN: usize = 256;
+
+/// Bytes of the upload response body kept unless the client sets a limit.
+pub(crate) const DEFAULT_RESPONSE_BODY_LIMIT: u32 = 64 * 1024;
+
+/// Largest response body limit a client may set, the kept body is returned
+/// in a single task query reply.
+pub(crate) const MAX_RESPONSE_BODY_LIMIT: u32 = 1024 * 1024;
+
+/// Standard methods, sent in upper case whatever case they were given in.
+const STANDARD_METHODS: [&str; 8] = [
+    "GET", "HEAD", "POST", "PUT", "DELETE", "PATCH", "OPTIONS", "TRACE",
+];
+
+/// Checks a method is an HTTP token of RFC 9110, a non-empty run of `tchar`.
+pub(crate) fn is_http_token(method: &str) -> bool {
+    !method.is_empty()
+        && method
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
 /// Complete configuration for a network task.
 /// 
 /// Contains all necessary parameters to execute a download or upload operation,
@@ -195,6 +322,98 @@ pub struct TaskConfig {
     pub(crate) body_file_paths: Vec<String>,
     /// Paths to custom certificates.
     pub(crate) certs_path: Vec<String>,
+    /// Number of concurrent range connections used for a download.
+    pub(crate) segments: u32,
+    /// Fail a resumed download whose range the server rejects instead of
+    /// downloading the file again from the beginning.
+    pub(crate) strict_resume: bool,
+    /// Reserve disk space for the whole file once the download size is known.
+    pub(crate) preallocate: bool,
+    /// Upper bound in milliseconds of a delay requested by `Retry-After`,
+    /// 0 fails rate limited requests instead of waiting.
+    pub(crate) retry_after_cap_ms: u64,
+    /// Log the lifecycle of this task in detail.
+    pub(crate) debug: bool,
+    /// Sync the downloaded file and its directory to disk before the task
+    /// is reported completed.
+    pub(crate) durable_completion: bool,
+    /// Handling of an active download of the same URL and destination.
+    pub(crate) duplicate_policy: DuplicatePolicy,
+    /// Maximum bytes of the upload response body kept for the client.
+    pub(crate) response_body_limit: u32,
+    /// URL actually requested after rewrite rules, empty if not rewritten.
+    pub(crate) effective_url: String,
+    /// How the current network is treated regardless of its reported metered flag.
+    pub(crate) treat_network_as: Option<MeteredOverride>,
+    /// Opaque tag set by the client at creation and echoed in every
+    /// notification and query of the task.
+    pub(crate) client_tag: String,
+    /// Address families the task connects to.
+    pub(crate) ip_preference: IpPreference,
+    /// Lowest TLS version negotiated, `None` for TLS 1.2.
+    pub(crate) min_tls_version: Option<TlsProtocol>,
+    /// Highest TLS version negotiated, `None` for the system default.
+    pub(crate) max_tls_version: Option<TlsProtocol>,
+    /// OpenSSL cipher list allowed for TLS 1.2 connections, empty for the
+    /// system default.
+    pub(crate) tls_ciphers: String,
+    /// Whether each redirect followed by the task is reported to the client.
+    pub(crate) report_redirects: bool,
+    /// Content types a download accepts, `type/*` matching any subtype,
+    /// empty to accept any.
+    pub(crate) expected_mime_types: Vec<String>,
+    /// ETag the remote object must still have for an upload to replace it,
+    /// sent as `If-Match`, empty for unconditional uploads.
+    pub(crate) if_match: String,
+    /// Whether the task skips certificate verification, honoured for
+    /// privileged callers only.
+    pub(crate) insecure_skip_verify: bool,
+    /// How the download resumes from the bytes it already has.
+    pub(crate) resume_strategy: ResumeStrategy,
+    /// Time in milliseconds since the epoch after which the task fails
+    /// instead of retrying, 0 retries until the retry budget is used.
+    pub(crate) retry_deadline_ms: u64,
+    /// Largest size in bytes of each file of the task, `None` for no cap.
+    pub(crate) max_size_bytes: Option<u64>,
+    /// Largest size in bytes of all files of the task, `None` for no cap.
+    pub(crate) max_total_bytes: Option<u64>,
+    /// Content codings advertised in `Accept-Encoding`, empty to keep the
+    /// header of the task as it is.
+    pub(crate) accept_encoding: String,
+    /// Handle of the network the task goes out of, 0 for the default network.
+    pub(crate) net_id: i32,
+    /// What happens to the files of the task when it is removed, `None` to
+    /// keep them on removal and delete an unfinished download once a removal
+    /// grace period expires.
+    pub(crate) on_remove_partial: Option<PartialFilePolicy>,
+    /// Whether a download named after its URL takes the file name of the
+    /// `Content-Disposition` header of the response instead.
+    pub(crate) content_disposition_name: bool,
+    /// Largest number of redirects to another host a request of the task
+    /// follows, `None` for no cap besides the redirect policy of the task.
+    pub(crate) max_cross_host_redirects: Option<u32>,
+    /// Longest time in milliseconds the task accepts a cached address of a
+    /// host, `None` for the lifetime of the shared DNS cache.
+    pub(crate) dns_ttl_override_ms: Option<u64>,
+    /// Trace context sent as `traceparent` and `tracestate`, `None` unless
+    /// set by the client or generated by the tracing policy of its uid.
+    pub(crate) trace_context: Option<TraceContext>,
+    /// Whether a completed download records the size and content type of its
+    /// file in the progress `extras`.
+    pub(crate) record_final_metadata: bool,
+    /// Whether an upload sends its files one after another as the body of a
+    /// single request.
+    pub(crate) concatenated: bool,
+    /// Thermal level at which the task waits until the device cools down,
+    /// `None` to run at any level.
+    pub(crate) pause_on_thermal_level: Option<u8>,
+    /// Whether the task waits while the device is not charging.
+    pub(crate) require_charging_while_running: bool,
+    /// Whether the device is kept awake while the task runs.
+    pub(crate) keep_awake: bool,
+    /// Whether the task was created by a system caller, set when the task is
+    /// constructed and for tasks restored from the database.
+    pub(crate) privileged: bool,
     /// Core configuration shared across task types.
     pub(crate) common_data: CommonTaskConfig,
 }
@@ -212,7 +431,7 @@ impl TaskConfig {
                 _ => {
                     // Check roaming and metered status constraints
                     if (self.common_data.roaming || !info.is_roaming)
-                        && (self.common_data.metered || !info.is_metered)
+                        && (self.common_data.metered || !self.is_metered(info))
                     {
                         Ok(())
                     } else {
@@ -223,6 +442,17 @@ impl TaskConfig {
         }
     }
 
+    /// Whether this task treats `info` as a metered network.
+    ///
+    /// The per-task override takes precedence over the reported flag.
+    pub(crate) fn is_metered(&self, info: &NetworkInfo) -> bool {
+        match self.treat_network_as {
+            Some(MeteredOverride::Metered) => true,
+            Some(MeteredOverride::Unmetered) => false,
+            None => info.is_metered,
+        }
+    }
+
     /// Determines if a task satisfies foreground execution requirements.
     /// 
     /// A task can run in the foreground if it's configured for background execution
@@ -231,6 +461,92 @@ impl TaskConfig {
         self.common_data.mode == Mode::BackGround
             || foreground_abilities.contains(&self.common_data.uid)
     }
+
+    /// Whether the charging requirement allows this task to run.
+    ///
+    /// Only background tasks wait for charging while `gated` is set.
+    pub(crate) fn satisfy_charging(&self, gated: bool) -> bool {
+        !gated || self.common_data.mode != Mode::BackGround
+    }
+
+    /// Whether the device conditions of the task allow it to run.
+    ///
+    /// The task waits at or above its thermal level and, if it requires
+    /// charging, while the device is not charging.
+    pub(crate) fn satisfy_device_condition(&self, thermal_level: u8, charging: bool) -> bool {
+        self.pause_on_thermal_level
+            .map_or(true, |level| thermal_level < level)
+            && (charging || !self.require_charging_while_running)
+    }
+
+    /// Returns the largest size in bytes the single file of a download may
+    /// have, the lower of both caps.
+    pub(crate) fn download_size_cap(&self) -> Option<u64> {
+        match (self.max_size_bytes, self.max_total_bytes) {
+            (Some(file), Some(total)) => Some(file.min(total)),
+            (file, total) => file.or(total),
+        }
+    }
+
+    /// Whether the retry deadline of this task passed at `now`, in
+    /// milliseconds since the epoch.
+    pub(crate) fn retry_deadline_passed(&self, now: u64) -> bool {
+        self.retry_deadline_ms != 0 && now >= self.retry_deadline_ms
+    }
+
+    /// Value of the configured header `name`, whatever case it was given in.
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether this upload sends only its form items as an urlencoded body.
+    ///
+    /// Selected by a `Content-Type: application/x-www-form-urlencoded` header.
+    pub(crate) fn is_form_urlencoded(&self) -> bool {
+        self.common_data.action == Action::Upload
+            && self.header("Content-Type").is_some_and(|value| {
+                value
+                    .split(';')
+                    .next()
+                    .is_some_and(|media| media.trim().eq_ignore_ascii_case(FORM_URLENCODED))
+            })
+    }
+
+    /// Method the request is sent with.
+    ///
+    /// Without a method, downloads use `GET` and uploads `PUT`, or `POST`
+    /// for API9. Other methods are sent as given.
+    pub(crate) fn request_method(&self) -> String {
+        if self.method.is_empty() {
+            let method = match (self.common_data.action, self.version) {
+                (Action::Upload, Version::API10) => "PUT",
+                (Action::Upload, _) => "POST",
+                _ => "GET",
+            };
+            return method.to_string();
+        }
+        match STANDARD_METHODS
+            .iter()
+            .find(|standard| standard.eq_ignore_ascii_case(&self.method))
+        {
+            Some(standard) => standard.to_string(),
+            None => self.method.clone(),
+        }
+    }
+
+    /// URL the request is sent to, after any rewrite rules were applied.
+    ///
+    /// `url` keeps the address given by the application for display.
+    pub(crate) fn request_url(&self) -> &str {
+        if self.effective_url.is_empty() {
+            &self.url
+        } else {
+            &self.effective_url
+        }
+    }
 }
 
 /// Internal representation of a task configuration optimized for C FFI.
@@ -303,11 +619,25 @@ impl From<u8> for Action {
         match value {
             0 => Action::Download,
             1 => Action::Upload,
+            3 => Action::LocalLoopback,
             _ => Action::Any, // Default for unknown values
         }
     }
 }
 
+impl Action {
+    /// Returns the action whose queue tasks of this action are scheduled in.
+    ///
+    /// Loopback tasks stand in for downloads, so they share the download
+    /// queue, its QoS share and the restoring of partial files.
+    pub(crate) fn queue(self) -> Action {
+        match self {
+            Action::LocalLoopback => Action::Download,
+            action => action,
+        }
+    }
+}
+
 impl From<u8> for Version {
     /// Converts a raw u8 value to a Version enum.
     /// 
@@ -321,6 +651,123 @@ impl From<u8> for Version {
     }
 }
 
+impl MeteredOverride {
+    /// Converts a stored value, 0 or unknown values mean no override.
+    pub(crate) fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(MeteredOverride::Metered),
+            2 => Some(MeteredOverride::Unmetered),
+            _ => None,
+        }
+    }
+}
+
+impl TlsProtocol {
+    /// Converts a stored value, `None` for unknown values.
+    pub(crate) fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(TlsProtocol::Tls12),
+            1 => Some(TlsProtocol::Tls13),
+            _ => None,
+        }
+    }
+}
+
+impl IpPreference {
+    /// Converts a stored value, `None` for unknown values.
+    pub(crate) fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(IpPreference::Default),
+            1 => Some(IpPreference::V4Only),
+            2 => Some(IpPreference::V6Only),
+            3 => Some(IpPreference::V4Preferred),
+            4 => Some(IpPreference::V6Preferred),
+            _ => None,
+        }
+    }
+}
+
+impl DuplicatePolicy {
+    /// Converts a stored value, `None` for unknown values.
+    pub(crate) fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(DuplicatePolicy::Allow),
+            1 => Some(DuplicatePolicy::Warn),
+            2 => Some(DuplicatePolicy::Reject),
+            _ => None,
+        }
+    }
+}
+
+impl ResumeStrategy {
+    /// Returns the strategy as the value of its option.
+    pub(crate) fn to_option(&self) -> String {
+        match self {
+            ResumeStrategy::RangeHeader => "range_header".to_string(),
+            ResumeStrategy::QueryParam { name } => format!("query_param:{}", name),
+            ResumeStrategy::None => "none".to_string(),
+        }
+    }
+
+    /// Converts the value of its option, `None` for unknown values or an
+    /// empty parameter name.
+    pub(crate) fn from_option(value: &str) -> Option<Self> {
+        match value {
+            "range_header" => Some(ResumeStrategy::RangeHeader),
+            "none" => Some(ResumeStrategy::None),
+            _ => {
+                let name = value.strip_prefix("query_param:")?;
+                if name.is_empty() {
+                    return None;
+                }
+                Some(ResumeStrategy::QueryParam {
+                    name: name.to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl TraceContext {
+    /// Returns the context as the value of its option, the `traceparent`
+    /// followed by a line feed and the `tracestate` if any.
+    pub(crate) fn to_option(&self) -> String {
+        let mut value = self.traceparent.clone();
+        if let Some(state) = &self.tracestate {
+            value.push('\n');
+            value.push_str(state);
+        }
+        value
+    }
+
+    /// Converts the value of its option, `None` without a `traceparent`.
+    pub(crate) fn from_option(value: &str) -> Option<Self> {
+        let (traceparent, tracestate) = match value.split_once('\n') {
+            Some((parent, state)) => (parent, Some(state.to_string())),
+            None => (value, None),
+        };
+        if traceparent.is_empty() {
+            return None;
+        }
+        Some(TraceContext {
+            traceparent: traceparent.to_string(),
+            tracestate,
+        })
+    }
+}
+
+impl PartialFilePolicy {
+    /// Converts a stored value, 0 or unknown values mean the default policy.
+    pub(crate) fn from_repr(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(PartialFilePolicy::Keep),
+            2 => Some(PartialFilePolicy::Delete),
+            3 => Some(PartialFilePolicy::DeleteIfIncomplete),
+            _ => None,
+        }
+    }
+}
+
 impl From<u8> for NetworkConfig {
     /// Converts a raw u8 value to a NetworkConfig enum.
     /// 
@@ -356,6 +803,24 @@ impl TaskConfig {
         }
     }
 
+    /// Orders the files by descending priority, files of equal priority
+    /// keeping their order.
+    ///
+    /// The response body file of each file, if any, moves along with it.
+    pub(crate) fn order_file_specs(&mut self) {
+        let mut order = (0..self.file_specs.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| Reverse(self.file_specs[index].priority));
+        if order.iter().copied().eq(0..order.len()) {
+            return;
+        }
+        let specs = std::mem::take(&mut self.file_specs);
+        self.file_specs = order.iter().map(|&index| specs[index].clone()).collect();
+        if self.body_file_paths.len() == specs.len() {
+            let paths = std::mem::take(&mut self.body_file_paths);
+            self.body_file_paths = order.iter().map(|&index| paths[index].clone()).collect();
+        }
+    }
+
     /// Checks if the task configuration includes any user files.
     /// 
     /// Returns true if any file specification in the configuration
@@ -395,6 +860,42 @@ impl Default for TaskConfig {
             body_file_paths: vec![],
             certs_path: vec![],
             certificate_pins: "".to_string(),
+            segments: 0,
+            strict_resume: false,
+            preallocate: false,
+            retry_after_cap_ms: DEFAULT_RETRY_AFTER_CAP_MS,
+            debug: false,
+            durable_completion: false,
+            duplicate_policy: DuplicatePolicy::Allow,
+            response_body_limit: DEFAULT_RESPONSE_BODY_LIMIT,
+            effective_url: String::new(),
+            treat_network_as: None,
+            client_tag: String::new(),
+            ip_preference: IpPreference::Default,
+            min_tls_version: None,
+            max_tls_version: None,
+            tls_ciphers: String::new(),
+            report_redirects: false,
+            expected_mime_types: vec![],
+            if_match: String::new(),
+            insecure_skip_verify: false,
+            resume_strategy: ResumeStrategy::RangeHeader,
+            retry_deadline_ms: 0,
+            max_size_bytes: None,
+            max_total_bytes: None,
+            accept_encoding: String::new(),
+            net_id: 0,
+            on_remove_partial: None,
+            content_disposition_name: false,
+            max_cross_host_redirects: None,
+            dns_ttl_override_ms: None,
+            trace_context: None,
+            record_final_metadata: false,
+            concatenated: false,
+            pause_on_thermal_level: None,
+            require_charging_while_running: false,
+            keep_awake: false,
+            privileged: false,
             common_data: CommonTaskConfig {
                 task_id: 0,
                 uid: 0,
@@ -456,6 +957,14 @@ impl ConfigBuilder {
         self
     }
 
+    /// Adds a user file processed before the files of lower priority.
+    pub fn prioritized_file_spec(&mut self, file: File, priority: u32) -> &mut Self {
+        let mut spec = FileSpec::user_file(file);
+        spec.priority = priority;
+        self.inner.file_specs.push(spec);
+        self
+    }
+
     /// Sets the operation type (download or upload).
     pub fn action(&mut self, action: Action) -> &mut Self {
         self.inner.common_data.action = action;
@@ -532,6 +1041,273 @@ impl ConfigBuilder {
         self.inner.common_data.retry = retry;
         self
     }
+
+    /// Sets the number of concurrent range connections for a download.
+    pub fn segments(&mut self, segments: u32) -> &mut Self {
+        self.inner.segments = segments;
+        self
+    }
+
+    /// Sets whether a resumed download fails when the server rejects its
+    /// range, instead of restarting from the beginning.
+    pub fn strict_resume(&mut self, strict_resume: bool) -> &mut Self {
+        self.inner.strict_resume = strict_resume;
+        self
+    }
+
+    /// Sets whether disk space for the whole file is reserved before a
+    /// download of known size starts writing.
+    pub fn preallocate(&mut self, preallocate: bool) -> &mut Self {
+        self.inner.preallocate = preallocate;
+        self
+    }
+
+    /// Sets the longest delay in milliseconds a task waits when the server
+    /// rate limits it with `Retry-After`, 0 fails the task instead.
+    pub fn retry_after_cap(&mut self, cap_ms: u64) -> &mut Self {
+        self.inner.retry_after_cap_ms = cap_ms;
+        self
+    }
+
+    /// Sets whether state changes, headers and retry decisions of this task
+    /// are logged in detail.
+    pub fn debug(&mut self, debug: bool) -> &mut Self {
+        self.inner.debug = debug;
+        self
+    }
+
+    /// Sets whether a download is synced to disk and checked before it is
+    /// reported completed.
+    pub fn durable_completion(&mut self, durable: bool) -> &mut Self {
+        self.inner.durable_completion = durable;
+        self
+    }
+
+    /// Sets how creating a download already active for the same URL and
+    /// destination is handled.
+    pub fn duplicate_policy(&mut self, policy: DuplicatePolicy) -> &mut Self {
+        self.inner.duplicate_policy = policy;
+        self
+    }
+
+    /// Sets how many bytes of the upload response body are kept, 0 disables it.
+    pub fn response_body_limit(&mut self, limit: u32) -> &mut Self {
+        self.inner.response_body_limit = limit;
+        self
+    }
+
+
+    /// Overrides the metered flag of the network for this task, API10 only.
+    pub fn treat_network_as(&mut self, treat_as: MeteredOverride) -> &mut Self {
+        self.inner.treat_network_as = Some(treat_as);
+        self
+    }
+
+    /// Sets the client tag of the task, at most `MAX_CLIENT_TAG_LEN` bytes.
+    pub fn client_tag(&mut self, tag: &str) -> &mut Self {
+        self.inner.client_tag = tag.to_string();
+        self
+    }
+
+    /// Sets the address families the task connects to.
+    pub fn ip_preference(&mut self, preference: IpPreference) -> &mut Self {
+        self.inner.ip_preference = preference;
+        self
+    }
+
+    /// Sets the lowest TLS version the task negotiates.
+    pub fn min_tls_version(&mut self, version: TlsProtocol) -> &mut Self {
+        self.inner.min_tls_version = Some(version);
+        self
+    }
+
+    /// Sets the highest TLS version the task negotiates.
+    pub fn max_tls_version(&mut self, version: TlsProtocol) -> &mut Self {
+        self.inner.max_tls_version = Some(version);
+        self
+    }
+
+    /// Sets the ciphers allowed for TLS 1.2 connections, as an OpenSSL
+    /// cipher list such as `ECDHE-RSA-AES128-GCM-SHA256:ECDHE-RSA-AES256-GCM-SHA384`.
+    pub fn tls_ciphers(&mut self, ciphers: &str) -> &mut Self {
+        self.inner.tls_ciphers = ciphers.to_string();
+        self
+    }
+
+    /// Reports each redirect followed by the task to the client.
+    pub fn report_redirects(&mut self, report: bool) -> &mut Self {
+        self.inner.report_redirects = report;
+        self
+    }
+
+    /// Adds a content type the download accepts, such as `image/png` or
+    /// `image/*`. Downloads of other content types fail at once.
+    pub fn expected_mime_type(&mut self, mime_type: &str) -> &mut Self {
+        self.inner.expected_mime_types.push(mime_type.to_string());
+        self
+    }
+
+    /// Uploads only if the remote object still has the given ETag. The
+    /// upload fails with `Reason::PreconditionFailed` once it changed.
+    pub fn if_match(&mut self, etag: &str) -> &mut Self {
+        self.inner.if_match = etag.to_string();
+        self
+    }
+
+    /// Skips certificate verification, for test servers with self-signed
+    /// certificates. Ignored unless the caller is a system application.
+    pub fn insecure_skip_verify(&mut self, skip: bool) -> &mut Self {
+        self.inner.insecure_skip_verify = skip;
+        self
+    }
+
+    /// Sets how the download resumes from the bytes it already has.
+    pub fn resume_strategy(&mut self, strategy: ResumeStrategy) -> &mut Self {
+        self.inner.resume_strategy = strategy;
+        self
+    }
+
+    /// Stops retrying at the given time in milliseconds since the epoch. A
+    /// task that would retry later fails with `Reason::RetryDeadlineExceeded`,
+    /// even if retries are left.
+    pub fn retry_deadline_at(&mut self, deadline_ms: u64) -> &mut Self {
+        self.inner.retry_deadline_ms = deadline_ms;
+        self
+    }
+
+    /// Stops retrying `after_ms` milliseconds from now, see
+    /// [`retry_deadline_at`](Self::retry_deadline_at).
+    pub fn retry_deadline_after(&mut self, after_ms: u64) -> &mut Self {
+        self.inner.retry_deadline_ms = get_current_timestamp().saturating_add(after_ms);
+        self
+    }
+
+    /// Caps the size of each file of the task. A larger file fails the task
+    /// with `Reason::SizeLimitExceeded`, a download removes what it wrote.
+    pub fn max_size_bytes(&mut self, bytes: u64) -> &mut Self {
+        self.inner.max_size_bytes = Some(bytes);
+        self
+    }
+
+    /// Caps the size of all files of the task together, see
+    /// [`max_size_bytes`](Self::max_size_bytes).
+    pub fn max_total_bytes(&mut self, bytes: u64) -> &mut Self {
+        self.inner.max_total_bytes = Some(bytes);
+        self
+    }
+
+    /// Sets the content codings advertised in `Accept-Encoding`, replacing
+    /// the header given by `headers`. `identity` disables compression.
+    pub fn accept_encoding(&mut self, encoding: &str) -> &mut Self {
+        self.inner.accept_encoding = encoding.to_string();
+        self
+    }
+
+    /// Binds the task to the network of the given handle, resolving its hosts
+    /// on that network even if another one is the default. A task whose
+    /// network is unavailable when it runs fails with
+    /// `Reason::BoundNetworkUnavailable`.
+    pub fn net_id(&mut self, net_id: i32) -> &mut Self {
+        self.inner.net_id = net_id;
+        self
+    }
+
+    /// Sets what happens to the files of the task when it is removed.
+    pub fn on_remove_partial(&mut self, policy: PartialFilePolicy) -> &mut Self {
+        self.inner.on_remove_partial = Some(policy);
+        self
+    }
+
+    /// Renames the downloaded file after the `Content-Disposition` header of
+    /// the response. Set it only if the file name was derived from the URL.
+    pub fn content_disposition_name(&mut self, enabled: bool) -> &mut Self {
+        self.inner.content_disposition_name = enabled;
+        self
+    }
+
+    /// Caps the redirects to another host a request of the task follows,
+    /// however many redirects within the same host it is allowed. Exceeding
+    /// the cap fails the task with `Reason::CrossHostRedirectLimit`.
+    pub fn max_cross_host_redirects(&mut self, count: u32) -> &mut Self {
+        self.inner.max_cross_host_redirects = Some(count);
+        self
+    }
+
+    /// Accepts addresses of hosts cached up to `ms` milliseconds ago instead
+    /// of the lifetime of the shared DNS cache, 0 resolves on every
+    /// connection. Cached failed lookups are accepted no longer than usual.
+    pub fn dns_ttl_override_ms(&mut self, ms: u64) -> &mut Self {
+        self.inner.dns_ttl_override_ms = Some(ms);
+        self
+    }
+
+    /// Propagates a W3C trace context with every request of the task. The
+    /// `traceparent` must be valid, and a header of the same name set by the
+    /// task is sent instead.
+    pub fn trace_context(&mut self, traceparent: &str, tracestate: Option<&str>) -> &mut Self {
+        self.inner.trace_context = Some(TraceContext {
+            traceparent: traceparent.to_string(),
+            tracestate: tracestate.map(str::to_string),
+        });
+        self
+    }
+
+    /// Records the size of the downloaded file on disk and its content type,
+    /// as served or sniffed from its first bytes, once the task completes.
+    pub fn record_final_metadata(&mut self, enabled: bool) -> &mut Self {
+        self.inner.record_final_metadata = enabled;
+        self
+    }
+
+    /// Sends the files of an upload in order as the body of one request,
+    /// without framing. The body is chunked if the size of a file is unknown.
+    pub fn concatenated(&mut self, concatenated: bool) -> &mut Self {
+        self.inner.concatenated = concatenated;
+        self
+    }
+
+    /// Makes the task wait while the thermal level of the device is `level`
+    /// or higher.
+    pub fn pause_on_thermal_level(&mut self, level: u8) -> &mut Self {
+        self.inner.pause_on_thermal_level = Some(level);
+        self
+    }
+
+    /// Makes the task wait while the device is not charging, whatever its
+    /// mode.
+    pub fn require_charging_while_running(&mut self, required: bool) -> &mut Self {
+        self.inner.require_charging_while_running = required;
+        self
+    }
+
+    /// Keeps the device awake while the task runs, for callers holding the
+    /// running lock permission.
+    pub fn keep_awake(&mut self, keep_awake: bool) -> &mut Self {
+        self.inner.keep_awake = keep_awake;
+        self
+    }
+
+    /// Marks the task as created by a system caller, as required by
+    /// `Action::LocalLoopback`.
+    pub fn privileged(&mut self, privileged: bool) -> &mut Self {
+        self.inner.privileged = privileged;
+        self
+    }
+
+    /// Makes the task a loopback task writing `size` bytes of pattern data
+    /// at `rate` bytes per second, 0 for no rate besides its speed limits.
+    /// With `fail_at`, the task fails once its data reaches that offset.
+    pub fn loopback(&mut self, size: u64, rate: u64, fail_at: Option<u64>) -> &mut Self {
+        self.inner.common_data.action = Action::LocalLoopback;
+        let extras = &mut self.inner.extras;
+        extras.insert(LOOPBACK_SIZE.to_string(), size.to_string());
+        extras.insert(LOOPBACK_RATE.to_string(), rate.to_string());
+        match fail_at {
+            Some(offset) => extras.insert(LOOPBACK_FAIL_AT.to_string(), offset.to_string()),
+            None => extras.remove(LOOPBACK_FAIL_AT),
+        };
+        self
+    }
 }
 
 #[cfg(feature = "oh")]
@@ -750,6 +1526,7 @@ impl Deserialize for TaskConfig {
                 mime_type,
                 is_user_file,
                 fd,
+                priority: 0,
             });
         }
 
@@ -834,6 +1611,42 @@ impl Deserialize for TaskConfig {
             file_specs,
             body_file_paths,
             certs_path,
+            segments: 0,
+            strict_resume: false,
+            preallocate: false,
+            retry_after_cap_ms: DEFAULT_RETRY_AFTER_CAP_MS,
+            debug: false,
+            durable_completion: false,
+            duplicate_policy: DuplicatePolicy::Allow,
+            response_body_limit: DEFAULT_RESPONSE_BODY_LIMIT,
+            effective_url: String::new(),
+            treat_network_as: None,
+            client_tag: String::new(),
+            ip_preference: IpPreference::Default,
+            min_tls_version: None,
+            max_tls_version: None,
+            tls_ciphers: String::new(),
+            report_redirects: false,
+            expected_mime_types: vec![],
+            if_match: String::new(),
+            insecure_skip_verify: false,
+            resume_strategy: ResumeStrategy::RangeHeader,
+            retry_deadline_ms: 0,
+            max_size_bytes: None,
+            max_total_bytes: None,
+            accept_encoding: String::new(),
+            net_id: 0,
+            on_remove_partial: None,
+            content_disposition_name: false,
+            max_cross_host_redirects: None,
+            dns_ttl_override_ms: None,
+            trace_context: None,
+            record_final_metadata: false,
+            concatenated: false,
+            pause_on_thermal_level: None,
+            require_charging_while_running: false,
+            keep_awake: false,
+            privileged: false,
             common_data: CommonTaskConfig {
                 task_id: 0,
                 uid,
@@ -868,6 +1681,255 @@ impl Deserialize for TaskConfig {
     }
 }
 
+#[cfg(feature = "oh")]
+impl TaskConfig {
+    /// Reads the options following a task of a construct request.
+    ///
+    /// Options the request does not name keep their defaults and options of
+    /// newer clients are ignored.
+    pub(crate) fn read_options(
+        &mut self,
+        parcel: &mut ipc::parcel::MsgParcel,
+    ) -> ipc::IpcResult<()> {
+        let size: u32 = parcel.read()?;
+        if size > parcel.readable() as u32 {
+            error!("deserialize failed: options size too large");
+            sys_event!(
+                ExecFault,
+                DfxCode::INVALID_IPC_MESSAGE_A00,
+                "deserialize failed: options size too large"
+            );
+            return Err(IpcStatusCode::Failed);
+        }
+        for _ in 0..size {
+            let name: String = parcel.read()?;
+            let value: String = parcel.read()?;
+            if self.set_option(&name, &value).is_none() {
+                error!("deserialize failed: invalid option {}", name);
+                sys_event!(
+                    ExecFault,
+                    DfxCode::INVALID_IPC_MESSAGE_A00,
+                    &format!("deserialize failed: invalid option {}", name)
+                );
+                return Err(IpcStatusCode::Failed);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the options of the task the way `read_options` reads them.
+    pub(crate) fn write_options(&self, parcel: &mut ipc::parcel::MsgParcel) -> ipc::IpcResult<()> {
+        let options = self.options();
+        parcel.write(&(options.len() as u32))?;
+        for (name, value) in options.iter() {
+            parcel.write(&name.to_string())?;
+            parcel.write(value)?;
+        }
+        Ok(())
+    }
+}
+
+impl TaskConfig {
+    /// Sets the option `name` of the task, `None` if `value` is invalid.
+    pub(crate) fn set_option(&mut self, name: &str, value: &str) -> Option<()> {
+        match name {
+            "segments" => self.segments = value.parse().ok()?,
+            "response_body_limit" => {
+                let limit = value.parse().ok()?;
+                if limit > MAX_RESPONSE_BODY_LIMIT {
+                    return None;
+                }
+                self.response_body_limit = limit;
+            }
+            "treat_network_as" => {
+                self.treat_network_as = Some(MeteredOverride::from_repr(value.parse().ok()?)?)
+            }
+            "strict_resume" => self.strict_resume = value.parse().ok()?,
+            "preallocate" => self.preallocate = value.parse().ok()?,
+            "retry_after_cap_ms" => self.retry_after_cap_ms = value.parse().ok()?,
+            "debug" => self.debug = value.parse().ok()?,
+            "durable_completion" => self.durable_completion = value.parse().ok()?,
+            "duplicate_policy" => {
+                self.duplicate_policy = DuplicatePolicy::from_repr(value.parse().ok()?)?
+            }
+            "client_tag" => self.client_tag = value.to_string(),
+            "ip_preference" => self.ip_preference = IpPreference::from_repr(value.parse().ok()?)?,
+            "min_tls_version" => {
+                self.min_tls_version = Some(TlsProtocol::from_repr(value.parse().ok()?)?)
+            }
+            "max_tls_version" => {
+                self.max_tls_version = Some(TlsProtocol::from_repr(value.parse().ok()?)?)
+            }
+            "tls_ciphers" => self.tls_ciphers = value.to_string(),
+            "report_redirects" => self.report_redirects = value.parse().ok()?,
+            "expected_mime_types" => {
+                self.expected_mime_types = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|mime_type| !mime_type.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            }
+            "if_match" => self.if_match = value.to_string(),
+            "insecure_skip_verify" => {
+                self.insecure_skip_verify = value.parse().ok()?;
+                // Only system applications may skip certificate verification.
+                check_insecure_skip_verify(self, self.privileged);
+            }
+            "resume_strategy" => self.resume_strategy = ResumeStrategy::from_option(value)?,
+            "retry_deadline_ms" => self.retry_deadline_ms = value.parse().ok()?,
+            "max_size_bytes" => self.max_size_bytes = Some(value.parse().ok()?),
+            "max_total_bytes" => self.max_total_bytes = Some(value.parse().ok()?),
+            "accept_encoding" => self.accept_encoding = value.to_string(),
+            "net_id" => self.net_id = value.parse().ok()?,
+            "on_remove_partial" => {
+                self.on_remove_partial = Some(PartialFilePolicy::from_repr(value.parse().ok()?)?)
+            }
+            "content_disposition_name" => self.content_disposition_name = value.parse().ok()?,
+            "max_cross_host_redirects" => self.max_cross_host_redirects = Some(value.parse().ok()?),
+            "dns_ttl_override_ms" => self.dns_ttl_override_ms = Some(value.parse().ok()?),
+            "trace_context" => self.trace_context = Some(TraceContext::from_option(value)?),
+            "record_final_metadata" => self.record_final_metadata = value.parse().ok()?,
+            "concatenated" => self.concatenated = value.parse().ok()?,
+            "pause_on_thermal_level" => self.pause_on_thermal_level = Some(value.parse().ok()?),
+            "require_charging_while_running" => {
+                self.require_charging_while_running = value.parse().ok()?
+            }
+            "keep_awake" => self.keep_awake = value.parse().ok()?,
+            "file_priorities" => {
+                let priorities = value
+                    .split(',')
+                    .filter(|priority| !priority.is_empty())
+                    .map(|priority| priority.parse().ok())
+                    .collect::<Option<Vec<u32>>>()?;
+                // Priorities follow the files the task was sent with.
+                if priorities.len() != self.file_specs.len() {
+                    return None;
+                }
+                for (spec, priority) in self.file_specs.iter_mut().zip(priorities) {
+                    spec.priority = priority;
+                }
+            }
+            _ => {}
+        }
+        Some(())
+    }
+
+    /// Returns the options of the task as name and value pairs.
+    pub(crate) fn options(&self) -> Vec<(&'static str, String)> {
+        let mut options = vec![
+            ("segments", self.segments.to_string()),
+            ("response_body_limit", self.response_body_limit.to_string()),
+        ];
+        if let Some(treat_as) = self.treat_network_as {
+            options.push(("treat_network_as", (treat_as as u8).to_string()));
+        }
+        options.push(("strict_resume", self.strict_resume.to_string()));
+        options.push(("preallocate", self.preallocate.to_string()));
+        options.push(("retry_after_cap_ms", self.retry_after_cap_ms.to_string()));
+        options.push(("debug", self.debug.to_string()));
+        options.push(("durable_completion", self.durable_completion.to_string()));
+        let policy = self.duplicate_policy as u8;
+        options.push(("duplicate_policy", policy.to_string()));
+        options.push(("client_tag", self.client_tag.clone()));
+        let preference = self.ip_preference as u8;
+        options.push(("ip_preference", preference.to_string()));
+        if let Some(version) = self.min_tls_version {
+            options.push(("min_tls_version", (version as u8).to_string()));
+        }
+        if let Some(version) = self.max_tls_version {
+            options.push(("max_tls_version", (version as u8).to_string()));
+        }
+        options.push(("tls_ciphers", self.tls_ciphers.clone()));
+        options.push(("report_redirects", self.report_redirects.to_string()));
+        let mime_types = self.expected_mime_types.join(",");
+        options.push(("expected_mime_types", mime_types));
+        options.push(("if_match", self.if_match.clone()));
+        let skip = self.insecure_skip_verify;
+        options.push(("insecure_skip_verify", skip.to_string()));
+        let strategy = self.resume_strategy.to_option();
+        options.push(("resume_strategy", strategy));
+        options.push(("retry_deadline_ms", self.retry_deadline_ms.to_string()));
+        if let Some(bytes) = self.max_size_bytes {
+            options.push(("max_size_bytes", bytes.to_string()));
+        }
+        if let Some(bytes) = self.max_total_bytes {
+            options.push(("max_total_bytes", bytes.to_string()));
+        }
+        options.push(("accept_encoding", self.accept_encoding.clone()));
+        options.push(("net_id", self.net_id.to_string()));
+        if let Some(policy) = self.on_remove_partial {
+            options.push(("on_remove_partial", (policy as u8).to_string()));
+        }
+        let disposition_name = self.content_disposition_name;
+        options.push(("content_disposition_name", disposition_name.to_string()));
+        if let Some(count) = self.max_cross_host_redirects {
+            options.push(("max_cross_host_redirects", count.to_string()));
+        }
+        if let Some(ms) = self.dns_ttl_override_ms {
+            options.push(("dns_ttl_override_ms", ms.to_string()));
+        }
+        if let Some(context) = &self.trace_context {
+            options.push(("trace_context", context.to_option()));
+        }
+        let record = self.record_final_metadata;
+        options.push(("record_final_metadata", record.to_string()));
+        options.push(("concatenated", self.concatenated.to_string()));
+        if let Some(level) = self.pause_on_thermal_level {
+            options.push(("pause_on_thermal_level", level.to_string()));
+        }
+        let charging = self.require_charging_while_running;
+        options.push(("require_charging_while_running", charging.to_string()));
+        options.push(("keep_awake", self.keep_awake.to_string()));
+        if self.file_specs.iter().any(|spec| spec.priority != 0) {
+            let priorities = self
+                .file_specs
+                .iter()
+                .map(|spec| spec.priority.to_string())
+                .collect::<Vec<_>>();
+            options.push(("file_priorities", priorities.join(",")));
+        }
+        options
+    }
+
+    /// Serializes the options of the task to be stored with it.
+    ///
+    /// Each name and value is written as its length in little-endian `u32`
+    /// followed by its bytes.
+    pub(crate) fn encode_options(&self) -> Vec<u8> {
+        let mut data = vec![];
+        for (name, value) in self.options() {
+            for field in [name.as_bytes(), value.as_bytes()] {
+                data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+                data.extend_from_slice(field);
+            }
+        }
+        data
+    }
+
+    /// Restores the options serialized by `encode_options`.
+    ///
+    /// Invalid options keep the value the task already has.
+    pub(crate) fn decode_options(&mut self, mut data: &[u8]) {
+        let mut next = || {
+            let rest = data;
+            let len = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+            let field = std::str::from_utf8(rest.get(4..4 + len)?).ok()?;
+            data = &rest[4 + len..];
+            Some(field)
+        };
+        while let Some(name) = next() {
+            let Some(value) = next() else {
+                error!("decode options failed: option {} has no value", name);
+                return;
+            };
+            if self.set_option(name, value).is_none() {
+                error!("decode options failed: invalid option {}", name);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod ut_config {
     include!("../../tests/ut/task/ut_config.rs");
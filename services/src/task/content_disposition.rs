@@ -0,0 +1,232 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! File names of downloads from the `Content-Disposition` header.
+//!
+//! A download configured with `content_disposition_name` was named after its
+//! URL by the client. Once the response headers arrive, the file name of the
+//! `Content-Disposition` header replaces the last component of its path. The
+//! file is renamed next to where it is and the new path is stored as the
+//! destination of the task, so queries and later resumes use it. An existing
+//! file is never replaced, the download keeps its name then.
+//!
+//! Server names are reduced to a single path component without control or
+//! reserved characters, so they cannot leave the directory of the download.
+
+use std::path::Path;
+
+use ylong_http_client::async_impl::Response;
+
+use crate::config::Action;
+use crate::manage::database::RequestDb;
+use crate::task::files::{convert_path, move_download_file, BundleCache};
+use crate::task::request_task::RequestTask;
+
+/// Longest file name in bytes kept from a server.
+const MAX_FILE_NAME_LEN: usize = 255;
+
+/// Returns the sanitized file name of a `Content-Disposition` header value.
+///
+/// `filename*` is preferred over `filename` as RFC 6266 recommends. `None`
+/// if the header has no usable file name.
+pub(crate) fn disposition_file_name(value: &str) -> Option<String> {
+    let mut plain = None;
+    let mut extended = None;
+    // The first parameter is the disposition type.
+    for param in split_params(value).into_iter().skip(1) {
+        let Some((key, value)) = param.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "filename*" => extended = decode_ext_value(value.trim()),
+            "filename" => plain = Some(unquote(value.trim())),
+            _ => {}
+        }
+    }
+    extended
+        .and_then(|name| sanitize_file_name(&name))
+        .or_else(|| plain.and_then(|name| sanitize_file_name(&name)))
+}
+
+/// Reduces a server provided name to a file name safe to create.
+///
+/// Directories are dropped, control and reserved characters are replaced
+/// by `_`, leading dots and surrounding spaces are removed and the name is
+/// cut to `MAX_FILE_NAME_LEN` bytes. `None` if nothing is left.
+pub(crate) fn sanitize_file_name(name: &str) -> Option<String> {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let mut name: String = name
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    name = name
+        .trim()
+        .trim_start_matches('.')
+        .trim_end_matches(['.', ' '])
+        .to_string();
+    while name.len() > MAX_FILE_NAME_LEN {
+        name.pop();
+    }
+    (!name.is_empty()).then_some(name)
+}
+
+/// Splits a header value at the semicolons outside quoted strings.
+fn split_params(value: &str) -> Vec<&str> {
+    let mut params = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                params.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    params.push(&value[start..]);
+    params
+}
+
+/// Removes the quotes and escapes of a quoted string.
+fn unquote(value: &str) -> String {
+    let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    else {
+        return value.to_string();
+    };
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unquoted.extend(chars.next()),
+            c => unquoted.push(c),
+        }
+    }
+    unquoted
+}
+
+/// Decodes an RFC 5987 `charset'language'value`, in UTF-8 or ISO-8859-1.
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let charset = parts.next()?.to_ascii_lowercase();
+    let encoded = parts.nth(1)?;
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut iter = encoded.bytes();
+    while let Some(b) = iter.next() {
+        if b != b'%' {
+            bytes.push(b);
+            continue;
+        }
+        let hex = [iter.next()?, iter.next()?];
+        bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+    }
+    match charset.as_str() {
+        "utf-8" => String::from_utf8(bytes).ok(),
+        "iso-8859-1" => Some(bytes.into_iter().map(char::from).collect()),
+        _ => None,
+    }
+}
+
+impl RequestTask {
+    /// Returns the path of the downloaded file, `None` for user files.
+    ///
+    /// The path follows a rename after the `Content-Disposition` header.
+    pub(crate) fn download_file_path(&self) -> Option<String> {
+        let spec = self.conf.file_specs.first()?;
+        if spec.is_user_file {
+            return None;
+        }
+        match self.disposition_path.lock().unwrap().as_ref() {
+            Some(path) => Some(path.clone()),
+            None => Some(spec.path.clone()),
+        }
+    }
+
+    /// Renames a download after the `Content-Disposition` header of its
+    /// response, if the task asks for it.
+    ///
+    /// Failures are logged and leave the file where it is.
+    pub(crate) fn adopt_disposition_name(&self, response: &Response) {
+        if !self.conf.content_disposition_name
+            || self.conf.common_data.action != Action::Download
+            || self.conf.file_specs.len() != 1
+        {
+            return;
+        }
+        let Some(value) = response
+            .headers()
+            .get("content-disposition")
+            .and_then(|value| value.to_string().ok())
+        else {
+            return;
+        };
+        let Some(name) = disposition_file_name(&value) else {
+            info!(
+                "task {} no file name in content disposition {:?}",
+                self.task_id(),
+                value
+            );
+            return;
+        };
+        let Some(from) = self.download_file_path() else {
+            return;
+        };
+        let to = match from.rsplit_once('/') {
+            Some((dir, _)) => format!("{}/{}", dir, name),
+            None => name,
+        };
+        if to == from {
+            return;
+        }
+
+        let bundle_name = match BundleCache::new(&self.conf).get_value() {
+            Ok(bundle_name) => bundle_name,
+            Err(e) => {
+                error!("task {} rename, bundle not found, {:?}", self.task_id(), e);
+                return;
+            }
+        };
+        let uid = self.conf.common_data.uid;
+        let real_from = convert_path(uid, &bundle_name, &from);
+        let real_to = convert_path(uid, &bundle_name, &to);
+        if let Err(e) = move_download_file(Path::new(&real_from), Path::new(&real_to)) {
+            error!("task {} rename to {:?} failed, {}", self.task_id(), name, e);
+            return;
+        }
+        if RequestDb::get_instance()
+            .update_destination(self.task_id(), &to)
+            .is_err()
+        {
+            // Put the file back where the stored config expects it.
+            let _ = move_download_file(Path::new(&real_to), Path::new(&real_from));
+            return;
+        }
+        info!("task {} renamed after content disposition", self.task_id());
+        *self.disposition_path.lock().unwrap() = Some(to);
+    }
+}
+
+#[cfg(test)]
+mod ut_content_disposition {
+    include!("../../tests/ut/task/ut_content_disposition.rs");
+}
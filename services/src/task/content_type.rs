@@ -0,0 +1,103 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Content type allowlists of downloads.
+//!
+//! Downloads configured with `expected_mime_types` check the `Content-Type`
+//! of their response before writing anything. A response of another type,
+//! such as the HTML login page of a captive portal, fails the task with
+//! `Reason::UnexpectedContentType`, and its content type is recorded under
+//! `unexpected_content_type` in the progress `extras`.
+//!
+//! Responses without a content type fail too, the service does not sniff
+//! the type from the body.
+
+use std::sync::Arc;
+
+use crate::task::reason::Reason;
+use crate::task::request_task::{RequestTask, TaskError};
+use crate::task::task_control;
+
+/// Key in `extras` holding the content type a download was rejected for.
+pub(crate) const UNEXPECTED_CONTENT_TYPE: &str = "unexpected_content_type";
+
+/// Returns whether `content_type` matches one of the `expected` types.
+///
+/// Parameters such as `charset` are ignored and types are compared case
+/// insensitively. A `type/*` entry matches every subtype of `type`.
+pub(crate) fn matches_mime_type(expected: &[String], content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let Some((kind, _)) = mime.split_once('/') else {
+        return false;
+    };
+    expected.iter().any(|pattern| {
+        let pattern = pattern.trim().to_ascii_lowercase();
+        match pattern.strip_suffix("/*") {
+            Some(prefix) => prefix == kind || prefix == "*",
+            None => pattern == mime,
+        }
+    })
+}
+
+impl RequestTask {
+    /// Checks the content type of a download response.
+    ///
+    /// # Returns
+    ///
+    /// The content type of the response, empty if it has none, when the task
+    /// expects other types. `None` if the response may be downloaded.
+    pub(crate) fn unexpected_content_type(&self, content_type: Option<&str>) -> Option<String> {
+        let expected = &self.conf.expected_mime_types;
+        if expected.is_empty() {
+            return None;
+        }
+        match content_type {
+            Some(content_type) if matches_mime_type(expected, content_type) => None,
+            content_type => Some(content_type.unwrap_or_default().to_string()),
+        }
+    }
+
+    /// Fails a download whose response has an unexpected content type.
+    ///
+    /// The bytes already written to the file are removed and the content
+    /// type is recorded in the progress `extras`.
+    pub(crate) async fn reject_content_type(
+        self: &Arc<Self>,
+        content_type: String,
+    ) -> Result<(), TaskError> {
+        error!(
+            "task {} unexpected content type {:?}, expected {:?}",
+            self.task_id(),
+            content_type,
+            self.conf.expected_mime_types
+        );
+        task_control::clear_downloaded_file(self.clone()).await?;
+        self.progress
+            .lock()
+            .unwrap()
+            .extras
+            .insert(UNEXPECTED_CONTENT_TYPE.to_string(), content_type);
+        self.update_progress_in_database();
+        Err(TaskError::Failed(Reason::UnexpectedContentType))
+    }
+}
+
+#[cfg(test)]
+mod ut_content_type {
+    include!("../../tests/ut/task/ut_content_type.rs");
+}
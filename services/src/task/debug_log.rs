@@ -0,0 +1,100 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-task debug logging.
+//!
+//! Tasks configured with `debug` log their state changes, headers and retry
+//! decisions through [`task_debug!`] while other tasks stay at the normal
+//! verbosity. A loaded task carries its [`TaskLog`], scheduler code that only
+//! holds a task ID loads it from the stored configuration.
+
+#[cfg(test)]
+use std::sync::Mutex;
+
+use crate::manage::database::RequestDb;
+
+/// Headers whose values are not logged.
+const SENSITIVE_HEADERS: [&str; 4] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// Lines written by debugged tasks, read back by tests.
+#[cfg(test)]
+pub(crate) static CAPTURED: Mutex<Vec<(u32, String)>> = Mutex::new(Vec::new());
+
+/// Debug log context of a task.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TaskLog {
+    task_id: u32,
+    enabled: bool,
+}
+
+impl TaskLog {
+    /// Creates the log context of `task_id`.
+    pub(crate) fn new(task_id: u32, enabled: bool) -> Self {
+        Self { task_id, enabled }
+    }
+
+    /// Loads the log context of `task_id` from its stored configuration.
+    pub(crate) fn load(task_id: u32) -> Self {
+        Self::new(task_id, RequestDb::get_instance().query_debug(task_id))
+    }
+
+    /// Returns the ID of the task.
+    pub(crate) fn task_id(&self) -> u32 {
+        self.task_id
+    }
+
+    /// Returns whether the task logs in detail.
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Writes a debug line of the task, use [`task_debug!`] instead.
+    pub(crate) fn write(&self, msg: &str) {
+        info!("task {} debug: {}", self.task_id, msg);
+        #[cfg(test)]
+        CAPTURED
+            .lock()
+            .unwrap()
+            .push((self.task_id, msg.to_string()));
+    }
+}
+
+/// Returns whether the value of the header `name` is a credential.
+pub(crate) fn is_sensitive_header(name: &str) -> bool {
+    SENSITIVE_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// Formats headers for a debug line, hiding the values of credentials.
+pub(crate) fn format_headers<'a>(headers: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    let mut lines: Vec<String> = headers
+        .map(|(name, value)| {
+            if is_sensitive_header(name) {
+                format!("{}: ***", name)
+            } else {
+                format!("{}: {}", name, value)
+            }
+        })
+        .collect();
+    lines.sort();
+    lines.join(", ")
+}
+
+#[cfg(test)]
+mod ut_debug_log {
+    include!("../../tests/ut/task/ut_debug_log.rs");
+}
@@ -0,0 +1,226 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shared cache of resolved hosts.
+//!
+//! Tasks on the default network resolve hosts through the [`DnsCache`], so
+//! a host looked up by one task is served to the others until its entry
+//! expires. Failed lookups are cached as well, for a much shorter time, so
+//! tasks retrying against a flaky resolver fail at once instead of flooding
+//! it. Concurrent lookups of a host not in the cache are merged: the first
+//! one resolves it and the others wait for its result.
+//!
+//! The lifetimes of entries default to [`DEFAULT_TTL`] and
+//! [`DEFAULT_NEGATIVE_TTL`] and may be overridden by the [`TTL_PARAM`] and
+//! [`NEGATIVE_TTL_PARAM`] system parameters. A task configured with
+//! `dns_ttl_override_ms` accepts entries up to that age instead, an override
+//! of 0 always resolves again.
+//!
+//! Tasks bound to a network resolve on it and bypass the cache.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Time a resolved host is served from the cache.
+pub(crate) const DEFAULT_TTL: Duration = Duration::from_secs(60);
+/// Time a failed lookup is served from the cache.
+pub(crate) const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(5);
+/// System parameter overriding the time resolved hosts are cached, in ms.
+pub(crate) const TTL_PARAM: &str = "const.request.dns_ttl_ms";
+/// System parameter overriding the time failed lookups are cached, in ms.
+pub(crate) const NEGATIVE_TTL_PARAM: &str = "const.request.dns_negative_ttl_ms";
+
+/// Result of a lookup, with the error kept cloneable for waiting lookups.
+type Lookup = Result<Vec<SocketAddr>, (io::ErrorKind, String)>;
+
+/// A lookup in progress, the lookups of the same host wait for its result.
+#[derive(Default)]
+struct Flight {
+    result: Mutex<Option<Lookup>>,
+    done: Condvar,
+}
+
+impl Flight {
+    fn wait(&self) -> Lookup {
+        let mut result = self.result.lock().unwrap();
+        loop {
+            match result.as_ref() {
+                Some(lookup) => return lookup.clone(),
+                None => result = self.done.wait(result).unwrap(),
+            }
+        }
+    }
+
+    fn finish(&self, lookup: Lookup) {
+        *self.result.lock().unwrap() = Some(lookup);
+        self.done.notify_all();
+    }
+}
+
+enum Entry {
+    Resolved(Lookup, Instant),
+    Pending(Arc<Flight>),
+}
+
+/// Lookup counters, as shown by the dump command.
+#[derive(Default)]
+pub(crate) struct DnsStats {
+    /// Lookups served from the cache or by a lookup in progress.
+    pub(crate) hits: AtomicU64,
+    /// Lookups that reached the resolver.
+    pub(crate) misses: AtomicU64,
+    /// Lookups failed by a cached failure.
+    pub(crate) negatives: AtomicU64,
+}
+
+/// Cache of the addresses of hosts, shared by all tasks.
+pub(crate) struct DnsCache {
+    ttl: Duration,
+    negative_ttl: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+    stats: DnsStats,
+}
+
+impl DnsCache {
+    /// Returns the cache configured by the system parameters.
+    pub(crate) fn get_instance() -> &'static Self {
+        static INSTANCE: LazyLock<DnsCache> = LazyLock::new(|| {
+            #[cfg(feature = "oh")]
+            let (ttl, negative_ttl) = (
+                crate::utils::get_int_parameter(TTL_PARAM, DEFAULT_TTL.as_millis() as i64),
+                crate::utils::get_int_parameter(
+                    NEGATIVE_TTL_PARAM,
+                    DEFAULT_NEGATIVE_TTL.as_millis() as i64,
+                ),
+            );
+            #[cfg(not(feature = "oh"))]
+            let (ttl, negative_ttl) = (
+                DEFAULT_TTL.as_millis() as i64,
+                DEFAULT_NEGATIVE_TTL.as_millis() as i64,
+            );
+            let ttl = u64::try_from(ttl).map_or(DEFAULT_TTL, Duration::from_millis);
+            let negative_ttl =
+                u64::try_from(negative_ttl).map_or(DEFAULT_NEGATIVE_TTL, Duration::from_millis);
+            info!("dns cache ttl {:?}, negative ttl {:?}", ttl, negative_ttl);
+            DnsCache::new(ttl, negative_ttl)
+        });
+        &INSTANCE
+    }
+
+    pub(crate) fn new(ttl: Duration, negative_ttl: Duration) -> Self {
+        Self {
+            ttl,
+            negative_ttl,
+            entries: Mutex::new(HashMap::new()),
+            stats: DnsStats::default(),
+        }
+    }
+
+    /// Returns the addresses of an authority, resolving it with `resolve`
+    /// unless the cache holds a fresh entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the lookup, or of the cached failed lookup.
+    pub(crate) fn lookup<F>(
+        &self,
+        authority: &str,
+        ttl_override: Option<Duration>,
+        resolve: F,
+    ) -> io::Result<Vec<SocketAddr>>
+    where
+        F: FnOnce() -> io::Result<Vec<SocketAddr>>,
+    {
+        self.lookup_at(authority, ttl_override, Instant::now(), resolve)
+    }
+
+    pub(crate) fn lookup_at<F>(
+        &self,
+        authority: &str,
+        ttl_override: Option<Duration>,
+        now: Instant,
+        resolve: F,
+    ) -> io::Result<Vec<SocketAddr>>
+    where
+        F: FnOnce() -> io::Result<Vec<SocketAddr>>,
+    {
+        let mut entries = self.entries.lock().unwrap();
+        let flight = match entries.get(authority) {
+            Some(Entry::Resolved(lookup, resolved)) => {
+                let ttl = match lookup {
+                    Ok(_) => ttl_override.unwrap_or(self.ttl),
+                    Err(_) => {
+                        ttl_override.map_or(self.negative_ttl, |ttl| ttl.min(self.negative_ttl))
+                    }
+                };
+                if now.saturating_duration_since(*resolved) < ttl {
+                    let counter = match lookup {
+                        Ok(_) => &self.stats.hits,
+                        Err(_) => &self.stats.negatives,
+                    };
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    return into_result(lookup.clone());
+                }
+                None
+            }
+            Some(Entry::Pending(flight)) => Some(flight.clone()),
+            None => None,
+        };
+        if let Some(flight) = flight {
+            drop(entries);
+            self.stats.hits.fetch_add(1, Ordering::Relaxed);
+            return into_result(flight.wait());
+        }
+
+        let flight = Arc::new(Flight::default());
+        entries.insert(authority.to_string(), Entry::Pending(flight.clone()));
+        drop(entries);
+
+        self.stats.misses.fetch_add(1, Ordering::Relaxed);
+        let lookup = resolve().map_err(|e| (e.kind(), e.to_string()));
+        if let Err((_, e)) = &lookup {
+            info!("dns lookup of {} failed, {}", authority, e);
+        }
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(authority.to_string(), Entry::Resolved(lookup.clone(), now));
+        flight.finish(lookup.clone());
+        into_result(lookup)
+    }
+
+    /// Drops all entries, lookups in progress still complete.
+    pub(crate) fn flush(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        info!("flush dns cache, {} entries", entries.len());
+        entries.retain(|_, entry| matches!(entry, Entry::Pending(_)));
+    }
+
+    /// Returns the lookup counters since the service started.
+    pub(crate) fn stats(&self) -> &DnsStats {
+        &self.stats
+    }
+}
+
+fn into_result(lookup: Lookup) -> io::Result<Vec<SocketAddr>> {
+    lookup.map_err(|(kind, e)| io::Error::new(kind, e))
+}
+
+#[cfg(test)]
+mod ut_dns_cache {
+    include!("../../tests/ut/task/ut_dns_cache.rs");
+}
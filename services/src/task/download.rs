@@ -34,22 +34,30 @@ use ylong_http_client::{ErrorKind, HttpClientError, SpeedLimit, Timeout};
 use super::operator::TaskOperator;
 use super::reason::Reason;
 use super::request_task::{TaskError, TaskPhase};
+use super::{handshake, redirect, resume, segment, sparse, trailers};
 use crate::manage::database::RequestDb;
 use crate::task::info::State;
+use crate::task::redirect::{CROSS_HOST_LIMIT_EXCEEDED, FINAL_URL};
 use crate::task::request_task::RequestTask;
+use crate::task::size_limit::SIZE_LIMIT_EXCEEDED;
 use crate::task::task_control;
+#[cfg(feature = "spans")]
+use crate::trace::span;
 #[cfg(feature = "oh")]
 use crate::trace::Trace;
 use crate::utils::get_current_duration;
 
+/// `errno` of a file system without space left.
+const ENOSPC: i32 = 28;
+
 /// Maximum download timeout duration (one week in seconds).
-const SECONDS_IN_ONE_WEEK: u64 = 7 * 24 * 60 * 60;
+pub(crate) const SECONDS_IN_ONE_WEEK: u64 = 7 * 24 * 60 * 60;
 
 /// Minimum time (in seconds) to consider a connection as low speed.
-const LOW_SPEED_TIME: u64 = 60;
+pub(crate) const LOW_SPEED_TIME: u64 = 60;
 
 /// Minimum download speed (in bytes per second) before considering connection stalled.
-const LOW_SPEED_LIMIT: u64 = 1;
+pub(crate) const LOW_SPEED_LIMIT: u64 = 1;
 
 /// Implementation of the `DownloadOperator` trait for `TaskOperator`.
 ///
@@ -61,7 +69,19 @@ impl DownloadOperator for TaskOperator {
         cx: &mut Context<'_>,
         data: &[u8],
     ) -> Poll<Result<usize, HttpClientError>> {
-        self.poll_write_file(cx, data, 0)
+        // Discards the bytes a response resumed by a query parameter repeats.
+        let skip = match resume::skip_overlap(self.task.task_id(), data) {
+            Some(skip) => skip,
+            None => {
+                return Poll::Ready(Err(HttpClientError::other(
+                    "resumed response does not match the file",
+                )))
+            }
+        };
+        if skip == data.len() {
+            return Poll::Ready(Ok(skip));
+        }
+        self.poll_write_file(cx, &data[skip..], skip)
     }
 
     fn poll_progress(
@@ -152,13 +172,19 @@ pub(crate) fn build_downloader(
 pub(crate) async fn download(task: Arc<RequestTask>, abort_flag: Arc<AtomicBool>) {
     // Initialize retry counter
     task.tries.store(0, Ordering::SeqCst);
+    task.refresh_client_timeout().await;
     
     // Main download loop with retry logic
     loop {
         let begin_time = Instant::now();
         
         // Execute the actual download logic
-        if let Err(e) = download_inner(task.clone(), abort_flag.clone()).await {
+        let result = download_inner(task.clone(), abort_flag.clone()).await;
+        // Attempts end with their final bytes in the progress.
+        task.sync_progress();
+        #[cfg(feature = "spans")]
+        span::end(task.task_id(), "retry", &[]);
+        if let Err(e) = result {
             match e {
                 TaskError::Waiting(phase) => match phase {
                     // Handle retry case: update timeout and continue the loop
@@ -166,16 +192,29 @@ pub(crate) async fn download(task: Arc<RequestTask>, abort_flag: Arc<AtomicBool>
                         // Update the remaining time based on elapsed download time
                         let download_time = begin_time.elapsed().as_secs();
                         task.rest_time.fetch_sub(download_time, Ordering::SeqCst);
+                        task_debug!(
+                            task.log,
+                            "retry download, {} s left",
+                            task.rest_time.load(Ordering::SeqCst)
+                        );
                         
                         // Adjust client timeout to match remaining task time
                         let mut client = task.client.lock().await;
                         client.total_timeout(Timeout::from_secs(
                             task.rest_time.load(Ordering::SeqCst),
                         ));
+                        #[cfg(feature = "spans")]
+                        retry_span(&task, "need_retry");
                         
                         // Continue to next iteration for retry
                         continue;
                     }
+                    // Hand the delay the server asked for back to the scheduler
+                    TaskPhase::RateLimited(delay) => {
+                        #[cfg(feature = "spans")]
+                        retry_span(&task, "rate_limited");
+                        task.end_rate_limited(delay);
+                    }
                     // Handle user abort: break the loop without setting an error
                     TaskPhase::UserAbort => {},
                     // Handle network offline: record the error
@@ -189,8 +228,16 @@ pub(crate) async fn download(task: Arc<RequestTask>, abort_flag: Arc<AtomicBool>
                 }
             }
         } else {
-            // Download completed successfully
-            *task.running_result.lock().unwrap() = Some(Ok(()));
+            // Download completed successfully, report it once the file is on disk
+            let result = if task.config().durable_completion {
+                task.complete_durably().await
+            } else {
+                Ok(())
+            };
+            if result.is_ok() && task.config().record_final_metadata {
+                task.record_final_metadata().await;
+            }
+            *task.running_result.lock().unwrap() = Some(result);
         }
         
         // Exit the loop after handling success or non-retryable errors
@@ -198,8 +245,23 @@ pub(crate) async fn download(task: Arc<RequestTask>, abort_flag: Arc<AtomicBool>
     }
 }
 
+/// Opens the span of the retry of a download, closed when the retried
+/// attempt ends.
+#[cfg(feature = "spans")]
+fn retry_span(task: &RequestTask, cause: &str) {
+    let tries = task.tries.load(Ordering::SeqCst) as i64;
+    span::start(
+        task.task_id(),
+        "retry",
+        &[
+            ("cause", span::Value::Str(cause.to_string())),
+            ("tries", span::Value::Int(tries)),
+        ],
+    );
+}
+
 impl RequestTask {
-    async fn prepare_download(&self) -> Result<(), TaskError> {
+    pub(crate) async fn prepare_download(&self) -> Result<(), TaskError> {
         if let Some(file) = self.files.get(0) {
             // Seek to the end of the file to get the current size (for resuming downloads)
             task_control::file_seek(file.clone(), SeekFrom::End(0)).await?;
@@ -209,6 +271,7 @@ impl RequestTask {
 
             // Update progress tracking information
             let mut progress = self.progress.lock().unwrap();
+            self.counter.take();  // Bytes counted before are part of the file size
             progress.common_data.index = 0;  // Set file index
             progress.common_data.total_processed = downloaded;  // Set bytes already downloaded
             progress.common_data.state = State::Running.repr;  // Set task state to running
@@ -220,6 +283,59 @@ impl RequestTask {
         }
         Ok(())
     }
+
+    /// Reserves disk space for the whole file once its size is known.
+    ///
+    /// Lack of space fails the task before the body is downloaded, other
+    /// failures leave the file sparse.
+    pub(crate) async fn preallocate(&self) -> Result<(), TaskError> {
+        let total = self
+            .progress
+            .lock()
+            .unwrap()
+            .sizes
+            .first()
+            .copied()
+            .unwrap_or(-1);
+        let Some(file) = self.files.get(0) else {
+            return Ok(());
+        };
+        if total <= 0 {
+            return Ok(());
+        }
+        match task_control::file_preallocate(file, total as u64).await {
+            Ok(()) => {
+                debug!("task {} preallocated {} bytes", self.task_id(), total);
+                Ok(())
+            }
+            Err(e) if e.raw_os_error() == Some(ENOSPC) => {
+                error!("task {} preallocate {} bytes failed", self.task_id(), total);
+                sys_event!(
+                    ExecFault,
+                    DfxCode::TASK_FAULT_09,
+                    &format!("task {} preallocate {} bytes failed", self.task_id(), total)
+                );
+                Err(TaskError::Failed(Reason::InsufficientSpace))
+            }
+            Err(e) => {
+                info!("task {} preallocate unsupported, {:?}", self.task_id(), e);
+                Ok(())
+            }
+        }
+    }
+
+    /// Frees the space preallocated after the end of a stopped download.
+    async fn release_preallocated(&self) -> Result<(), TaskError> {
+        let Some(file) = self.files.get(0) else {
+            return Ok(());
+        };
+        // Truncating to the same length drops blocks allocated past it.
+        let len = task_control::file_metadata(file.clone()).await?.len();
+        task_control::file_set_len(file.clone(), len).await?;
+        let file = file.lock().unwrap();
+        sparse::record_file_sizes(&file, &mut self.progress.lock().unwrap().extras);
+        Ok(())
+    }
 }
 
 /// Performs the core download operation including request handling and file writing.
@@ -253,20 +369,71 @@ pub(crate) async fn download_inner(
     // Prepare the download task by initializing file pointers and progress tracking
     task.prepare_download().await?;
 
+    // Fail at once if the network the task is bound to is gone
+    task.check_bound_network()?;
+
     // Log that the download has started
     info!("{} downloading", task.task_id());
 
+    // Segmented downloads stopped early refetch only the ranges left unwritten.
+    let holes = RequestDb::get_instance().query_unwritten_ranges(task.task_id());
+    if !holes.is_empty() {
+        if segment::resume_segmented(task.clone(), abort_flag.clone(), holes).await? {
+            info!("{} downloaded", task.task_id());
+            return Ok(());
+        }
+        task_control::clear_downloaded_file(task.clone()).await?;
+    }
+
     // Build the HTTP request for downloading
-    let request = RequestTask::build_download_request(task.clone()).await?;
+    let mut request = RequestTask::build_download_request(task.clone()).await?;
 
     // Record the start time for tracking
     let start_time = get_current_duration().as_secs() as u64;
     task.start_time.store(start_time as u64, Ordering::SeqCst);
 
+    // Fresh downloads may opt in to several range connections.
+    if task.conf.segments > 1
+        && !task.require_range()
+        && task.progress.lock().unwrap().common_data.total_processed == 0
+        && segment::download_segmented(task.clone(), abort_flag.clone()).await?
+    {
+        info!("{} downloaded", task.task_id());
+        return Ok(());
+    }
+
     // Acquire the client lock and send the request
     // Send HTTP request and handle response with detailed error categorization
     let client = task.client.lock().await;
-    let response = client.request(request).await;
+    redirect::reset_host_changes(task.task_id());
+    #[cfg(feature = "spans")]
+    {
+        span::start(task.task_id(), "connect", &[]);
+        span::start(task.task_id(), "first_byte", &[]);
+    }
+    // Transient handshake failures are retried without using a task retry.
+    let mut retried = 0;
+    let response = loop {
+        let response = client.request(request).await;
+        let fault = response.as_ref().err().and_then(handshake::tls_fault);
+        if !task.retry_handshake(fault, &mut retried).await {
+            break response;
+        }
+        request = RequestTask::build_download_request(task.clone()).await?;
+    };
+    #[cfg(feature = "spans")]
+    match response.as_ref() {
+        Ok(response) => {
+            let status = span::Value::Int(response.status().as_u16() as i64);
+            span::end(task.task_id(), "connect", &[("status", status)]);
+        }
+        Err(e) => {
+            let error = [("error", span::Value::Str(format!("{:?}", e.error_kind())))];
+            span::end(task.task_id(), "connect", &error);
+            span::end(task.task_id(), "first_byte", &error);
+        }
+    }
+    let final_url = task.report_redirects();
 
     // Handle response and categorize errors based on status codes and error types
     match response.as_ref() {
@@ -279,6 +446,33 @@ pub(crate) async fn download_inner(
                 "{} response {}",
                 task.conf.common_data.task_id, status_code
             );
+            task.debug_response(response);
+            task.record_protocol(response);
+
+            // A response resumed by a query parameter from another offset
+            if !task.check_resumed_response(response) {
+                resume::finish_overlap(task.task_id());
+                task_control::clear_downloaded_file(task.clone()).await?;
+                return Err(TaskError::Waiting(TaskPhase::NeedRetry));
+            }
+
+            // A resume range past the end of a file that shrank on the server
+            if status_code.as_u16() == 416
+                && !task.conf.strict_resume
+                && task.progress.lock().unwrap().common_data.total_processed > 0
+            {
+                info!(
+                    "task {} resume range not satisfiable, restart from zero",
+                    task.task_id()
+                );
+                task_control::clear_downloaded_file(task.clone()).await?;
+                return Err(TaskError::Waiting(TaskPhase::NeedRetry));
+            }
+
+            if let Some(delay) = task.rate_limit_delay(response) {
+                task.check_rate_limit(delay)?;
+                return Err(TaskError::Waiting(TaskPhase::RateLimited(delay)));
+            }
 
             // Handle protocol errors (server errors, most client errors, and redirects)
             if status_code.is_server_error()
@@ -290,12 +484,15 @@ pub(crate) async fn download_inner(
 
             // Handle timeout errors with retry logic
             if status_code.as_u16() == 408 {
+                task.check_retry_deadline()?;
                 if task.timeout_tries.load(Ordering::SeqCst) < 2 {
                     // Retry up to 2 times for timeout errors
                     task.timeout_tries.fetch_add(1, Ordering::SeqCst);
+                    task_debug!(task.log, "request timeout, retry");
                     return Err(TaskError::Waiting(TaskPhase::NeedRetry));
                 } else {
                     // Too many timeout retries, consider it a failure
+                    task_debug!(task.log, "request timeout, retries used");
                     return Err(TaskError::Failed(Reason::ProtocolError));
                 }
             } else {
@@ -314,8 +511,9 @@ pub(crate) async fn download_inner(
                 // Verify and prepare the download file
                 if let Some(file) = task.files.get(0) {
                     // Check if file already has content (which shouldn't happen for new downloads)
-                    let has_downloaded = task_control::file_metadata(file).await?.len() > 0;
-                    if has_downloaded {
+                    let len = task_control::file_metadata(file).await?.len();
+                    // Servers resuming by a query parameter answer with 200 too.
+                    if len > 0 && !task.resumes_by_query(len) {
                         error!("task {} file not cleared", task.task_id());
                         sys_event!(
                             ExecFault,
@@ -335,6 +533,11 @@ pub(crate) async fn download_inner(
             // Log the error for debugging purposes
             error!("Task {} {:?}", task.task_id(), e);
 
+            // Redirects to another host beyond the cap of the task
+            if format!("{}", e).contains(CROSS_HOST_LIMIT_EXCEEDED) {
+                return Err(TaskError::Failed(Reason::CrossHostRedirectLimit));
+            }
+
             // Categorize errors based on their type for appropriate handling
             match e.error_kind() {
                 ErrorKind::Timeout => {
@@ -342,7 +545,7 @@ pub(crate) async fn download_inner(
                     sys_event!(
                         ExecFault,
                         DfxCode::TASK_FAULT_01,
-                        &format!("Task {} {:?}", task.task_id(), e)
+                        &format!("Task {} {:?}", task.fault_label(), e)
                     );
                     return Err(TaskError::Failed(Reason::ContinuousTaskTimeout));
                 }
@@ -351,7 +554,7 @@ pub(crate) async fn download_inner(
                     sys_event!(
                         ExecFault,
                         DfxCode::TASK_FAULT_02,
-                        &format!("Task {} {:?}", task.task_id(), e)
+                        &format!("Task {} {:?}", task.fault_label(), e)
                     );
                     return Err(TaskError::Failed(Reason::RequestError));
                 }
@@ -360,19 +563,19 @@ pub(crate) async fn download_inner(
                     sys_event!(
                         ExecFault,
                         DfxCode::TASK_FAULT_08,
-                        &format!("Task {} {:?}", task.task_id(), e)
+                        &format!("Task {} {:?}", task.fault_label(), e)
                     );
                     return Err(TaskError::Failed(Reason::RedirectError));
                 }
                 ErrorKind::Connect | ErrorKind::ConnectionUpgrade => {
                     // Handle connection errors with network retry and further categorization
-                    task.network_retry().await?;
+                    task.connect_retry(handshake::tls_fault(e)).await?;
                     if e.is_dns_error() {
                         // DNS resolution errors
                         sys_event!(
                             ExecFault,
                             DfxCode::TASK_FAULT_05,
-                            &format!("Task {} {:?}", task.task_id(), e)
+                            &format!("Task {} {:?}", task.fault_label(), e)
                         );
                         return Err(TaskError::Failed(Reason::Dns));
                     } else if e.is_tls_error() {
@@ -380,7 +583,7 @@ pub(crate) async fn download_inner(
                         sys_event!(
                             ExecFault,
                             DfxCode::TASK_FAULT_07,
-                            &format!("Task {} {:?}", task.task_id(), e)
+                            &format!("Task {} {:?}", task.fault_label(), e)
                         );
                         return Err(TaskError::Failed(Reason::Ssl));
                     } else {
@@ -388,7 +591,7 @@ pub(crate) async fn download_inner(
                         sys_event!(
                             ExecFault,
                             DfxCode::TASK_FAULT_06,
-                            &format!("Task {} {:?}", task.task_id(), e)
+                            &format!("Task {} {:?}", task.fault_label(), e)
                         );
                         return Err(TaskError::Failed(Reason::Tcp));
                     }
@@ -399,7 +602,7 @@ pub(crate) async fn download_inner(
                     sys_event!(
                         ExecFault,
                         DfxCode::TASK_FAULT_09,
-                        &format!("Task {} {:?}", task.task_id(), e)
+                        &format!("Task {} {:?}", task.fault_label(), e)
                     );
                     return Err(TaskError::Failed(Reason::OthersError));
                 }
@@ -410,7 +613,7 @@ pub(crate) async fn download_inner(
                         sys_event!(
                             ExecFault,
                             DfxCode::TASK_FAULT_09,
-                            &format!("Task {} {:?}", task.task_id(), e)
+                            &format!("Task {} {:?}", task.fault_label(), e)
                         );
                         return Err(TaskError::Failed(Reason::InsufficientSpace));
                     } else {
@@ -418,7 +621,7 @@ pub(crate) async fn download_inner(
                         sys_event!(
                             ExecFault,
                             DfxCode::TASK_FAULT_09,
-                            &format!("Task {} {:?}", task.task_id(), e)
+                            &format!("Task {} {:?}", task.fault_label(), e)
                         );
                         return Err(TaskError::Failed(Reason::OthersError));
                     }
@@ -431,13 +634,32 @@ pub(crate) async fn download_inner(
     {
         let mut guard = task.progress.lock().unwrap();
         guard.extras.clear();
-        for (k, v) in response.headers() {
-            if let Ok(value) = v.to_string() {
-                guard.extras.insert(k.to_string().to_lowercase(), value);
-            }
+        for (k, v) in task.response_headers(&response) {
+            guard.extras.insert(k.to_lowercase(), v);
+        }
+        task.record_effective_metered(&mut guard.extras);
+        task.record_address_family(&mut guard.extras);
+        task.record_handshake_retries(&mut guard.extras);
+        if let Some(url) = final_url {
+            guard.extras.insert(FINAL_URL.to_string(), url);
         }
     }
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|value| value.to_string().ok());
+    if let Some(content_type) = task.unexpected_content_type(content_type.as_deref()) {
+        return task.reject_content_type(content_type).await;
+    }
+    task.adopt_disposition_name(&response);
     task.get_file_info(&response)?;
+    task.discount_overlap();
+    if let Some(size) = task.oversize_declared() {
+        return task.reject_oversize(size).await;
+    }
+    if task.conf.preallocate {
+        task.preallocate().await?;
+    }
     task.update_progress_in_database();
     RequestDb::get_instance()
         .update_task_sizes(task.task_id(), &task.progress.lock().unwrap().sizes);
@@ -456,9 +678,37 @@ pub(crate) async fn download_inner(
                 &0
             })
     ));
-    let mut downloader = build_downloader(task.clone(), response, abort_flag);
-
-    if let Err(e) = downloader.download().await {
+    // The downloader discards trailers, responses announcing them are read
+    // without it.
+    let result = if trailers::announces_trailers(&response) {
+        trailers::download_with_trailers(task.clone(), response, abort_flag).await
+    } else {
+        let mut downloader = build_downloader(task.clone(), response, abort_flag);
+        downloader.download().await
+    };
+    // Buffered chunks land before the file is checked, cleared or synced.
+    let written = task.writes.drain().await.map_err(HttpClientError::other);
+    task.sync_progress();
+    let result = result.and(written);
+    let aborted = matches!(&result, Err(e) if e.error_kind() == ErrorKind::UserAborted);
+    // Restarts from zero if the resumed response did not start as expected.
+    if !resume::finish_overlap(task.task_id()) && !aborted {
+        info!(
+            "task {} resumed response mismatched, restart from zero",
+            task.task_id()
+        );
+        task_control::clear_downloaded_file(task.clone()).await?;
+        return Err(TaskError::Waiting(TaskPhase::NeedRetry));
+    }
+    if let Err(e) = result {
+        if task.conf.preallocate {
+            task.release_preallocated().await?;
+        }
+        // Responses of unknown length are cut off once they outgrow the cap.
+        if format!("{}", e).contains(SIZE_LIMIT_EXCEEDED) {
+            let written = task.progress.lock().unwrap().common_data.total_processed;
+            return task.reject_oversize(written as u64).await;
+        }
         return task.handle_download_error(e).await;
     }
 
@@ -524,8 +774,8 @@ fn check_file_exist(task: &Arc<RequestTask>) -> Result<(), TaskError> {
     let real_path = convert_path(
         config.common_data.uid,
         &bundle_name,
-        match &config.file_specs.first() {
-            Some(spec) => &spec.path,
+        &match task.download_file_path() {
+            Some(path) => path,
             None => {
                 error!("Failed to get the first file_spec from an empty vector in TaskConfig");
                 Default::default()
@@ -0,0 +1,181 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Durable completion of downloads.
+//!
+//! Tasks configured with `durable_completion` sync the downloaded file and
+//! its directory to disk and check the file again before they are reported
+//! completed, so a completed task survives a power loss. A failed check
+//! pauses the task with `Reason::DurabilityCheckFailed` so it can be resumed.
+//!
+//! Tasks carry no checksum, so the file is checked by its length.
+
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+#[cfg(test)]
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::info::State;
+use crate::manage::database::RequestDb;
+use crate::task::files::{convert_path, BundleCache};
+use crate::task::reason::Reason;
+use crate::task::request_task::RequestTask;
+use crate::task::task_control;
+
+/// Longest time the durability check may delay the completion of a task.
+pub(crate) const DURABILITY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tasks whose sync fails, injected by tests.
+#[cfg(test)]
+pub(crate) static FAIL_SYNC: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+#[cfg(test)]
+fn injected_fault(task_id: u32) -> io::Result<()> {
+    if FAIL_SYNC.lock().unwrap().contains(&task_id) {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "injected sync failure",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(test))]
+fn injected_fault(_task_id: u32) -> io::Result<()> {
+    Ok(())
+}
+
+/// Syncs a downloaded file and its directory to disk and checks its length.
+///
+/// # Arguments
+///
+/// * `task_id` - The task the file belongs to.
+/// * `file` - The downloaded file.
+/// * `path` - Path of the file, `None` if the service cannot access it by
+///   path, in which case only the file itself is synced.
+/// * `expected` - Number of bytes downloaded into the file.
+pub(crate) fn make_durable(
+    task_id: u32,
+    file: &File,
+    path: Option<&Path>,
+    expected: u64,
+) -> io::Result<()> {
+    injected_fault(task_id)?;
+    file.sync_all()?;
+    if let Some(dir) = path.and_then(Path::parent) {
+        File::open(dir)?.sync_all()?;
+    }
+    let len = match path {
+        Some(path) => std::fs::metadata(path)?.len(),
+        None => file.metadata()?.len(),
+    };
+    if len != expected {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("file has {} bytes, {} downloaded", len, expected),
+        ));
+    }
+    Ok(())
+}
+
+impl RequestTask {
+    /// Makes the downloaded file durable before the task is reported
+    /// completed.
+    ///
+    /// The check is given up after `DURABILITY_TIMEOUT`, its duration is
+    /// logged with the task.
+    pub(crate) async fn complete_durably(&self) -> Result<(), Reason> {
+        let task_id = self.task_id();
+        let Some(file) = self.files.get(0) else {
+            return Ok(());
+        };
+        let expected = self
+            .progress
+            .lock()
+            .unwrap()
+            .processed
+            .first()
+            .copied()
+            .unwrap_or(0) as u64;
+        let path = self.download_path();
+
+        let begin = Instant::now();
+        let handle = task_control::runtime_spawn_blocking(move || {
+            let file = file.lock().unwrap();
+            make_durable(task_id, &file, path.as_deref(), expected)
+        });
+        let res = match ylong_runtime::time::timeout(DURABILITY_TIMEOUT, handle).await {
+            Ok(Ok(res)) => res,
+            Ok(Err(e)) => Err(io::Error::new(io::ErrorKind::Other, e)),
+            Err(_) => Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")),
+        };
+        let elapsed = begin.elapsed().as_millis();
+        task_debug!(self.log, "durability check took {} ms, {:?}", elapsed, res);
+
+        match res {
+            Ok(()) => {
+                info!("task {} synced to disk in {} ms", task_id, elapsed);
+                Ok(())
+            }
+            Err(e) => {
+                error!(
+                    "task {} durability check failed in {} ms, {}",
+                    task_id, elapsed, e
+                );
+                Err(Reason::DurabilityCheckFailed)
+            }
+        }
+    }
+
+    /// Returns the path of the downloaded file if the service can access it.
+    fn download_path(&self) -> Option<PathBuf> {
+        let config = self.config();
+        let path = self.download_file_path()?;
+        let bundle_name = BundleCache::new(config).get_value().ok()?;
+        Some(PathBuf::from(convert_path(
+            config.common_data.uid,
+            &bundle_name,
+            &path,
+        )))
+    }
+}
+
+impl RequestDb {
+    /// Pauses a task interrupted by a resumable failure.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the task was running or waiting and is now paused with the
+    /// given reason, `false` if its state was left unchanged or could not be
+    /// written.
+    pub(crate) fn pause_interrupted(&self, task_id: u32, reason: Reason) -> bool {
+        let Some(info) = self.get_task_qos_info(task_id) else {
+            return false;
+        };
+        if info.state != State::Running.repr
+            && info.state != State::Retrying.repr
+            && info.state != State::Waiting.repr
+        {
+            return false;
+        }
+        self.commit_task_state(task_id, State::Paused, reason).is_ok()
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_durable {
+    include!("../../tests/ut/task/ut_durable.rs");
+}
@@ -0,0 +1,247 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Snapshot of the configuration a task actually runs with.
+//!
+//! Before the first request the service adjusts the configuration given by
+//! the application: URL rewrite rules, default headers of the application,
+//! the system proxy and the speed limits in force. The outcome is recorded in
+//! the `task_effective_config` table when the task first runs, the protocol
+//! once the first response arrived. Secret values are redacted before they are
+//! recorded, their names are kept.
+
+use std::sync::Once;
+
+use ylong_http_client::async_impl::Response;
+
+cfg_oh! {
+    use crate::ability::SYSTEM_CONFIG_MANAGER;
+}
+
+use super::accept_encoding;
+use super::config::{ResumeStrategy, TaskConfig};
+use super::debug_log::is_sensitive_header;
+use super::request_task::RequestTask;
+use crate::manage::database::{hex, RequestDb};
+use crate::utils::call_once;
+use crate::utils::default_headers::DefaultHeaders;
+
+/// Value recorded in place of a secret.
+pub(crate) const REDACTED: &str = "<redacted>";
+
+const CREATE_EFFECTIVE_CONFIG_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS task_effective_config (task_id INTEGER PRIMARY KEY, url BLOB, headers BLOB, proxy BLOB, speed_limit INTEGER, resume_strategy BLOB, protocol BLOB)";
+
+/// Configuration a task runs with after all adjustments of the service.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct EffectiveConfig {
+    /// URL requested after rewrite rules.
+    pub(crate) url: String,
+    /// Headers sent with the requests, sorted by name.
+    pub(crate) headers: Vec<(String, String)>,
+    /// Proxy the requests go through, empty if none.
+    pub(crate) proxy: String,
+    /// Speed limit in bytes per second, 0 for unlimited.
+    pub(crate) speed_limit: u64,
+    /// How a download resumes from the bytes it already has.
+    pub(crate) resume_strategy: String,
+    /// HTTP version of the first response, empty until one arrived.
+    pub(crate) protocol: String,
+}
+
+impl EffectiveConfig {
+    /// Assembles the snapshot of a task configured by `config`, redacting the
+    /// secrets of `headers` and `proxy`.
+    pub(crate) fn build(
+        config: &TaskConfig,
+        headers: Vec<(String, String)>,
+        proxy: &str,
+        speed_limit: u64,
+    ) -> Self {
+        let headers = headers
+            .into_iter()
+            .map(|(name, value)| {
+                if is_sensitive_header(&name) {
+                    (name, REDACTED.to_string())
+                } else {
+                    (name, value)
+                }
+            })
+            .collect();
+        let resume_strategy = match &config.resume_strategy {
+            ResumeStrategy::RangeHeader => "range".to_string(),
+            ResumeStrategy::QueryParam { name } => format!("query:{}", name),
+            ResumeStrategy::None => "none".to_string(),
+        };
+        Self {
+            url: redact_userinfo(config.request_url()),
+            headers,
+            proxy: redact_userinfo(proxy),
+            speed_limit,
+            resume_strategy,
+            protocol: String::new(),
+        }
+    }
+}
+
+/// Replaces the password of the user info of `url` with [`REDACTED`].
+fn redact_userinfo(url: &str) -> String {
+    let start = url.find("://").map_or(0, |i| i + 3);
+    let end = url[start..].find('/').map_or(url.len(), |i| start + i);
+    let Some(at) = url[start..end].rfind('@').map(|i| start + i) else {
+        return url.to_string();
+    };
+    match url[start..at].find(':').map(|i| start + i) {
+        Some(colon) => format!("{}{}{}", &url[..colon + 1], REDACTED, &url[at..]),
+        None => url.to_string(),
+    }
+}
+
+/// Returns the proxy the requests of a task go through, empty if none.
+fn effective_proxy(config: &TaskConfig) -> String {
+    if !config.proxy.is_empty() {
+        return config.proxy.clone();
+    }
+    system_proxy()
+}
+
+#[cfg(feature = "oh")]
+fn system_proxy() -> String {
+    unsafe { SYSTEM_CONFIG_MANAGER.assume_init_ref().proxy_url() }
+}
+
+#[cfg(not(feature = "oh"))]
+fn system_proxy() -> String {
+    String::new()
+}
+
+impl RequestTask {
+    /// Returns the headers sent with the requests of the task.
+    pub(crate) fn request_headers(&self) -> Vec<(String, String)> {
+        let mut headers = DefaultHeaders::get_instance().merge(self.uid(), &self.conf.headers);
+        accept_encoding::apply(&mut headers, &self.conf.accept_encoding);
+        headers
+    }
+
+    /// Records the effective configuration the first time the task runs.
+    pub(crate) fn record_effective_config(&self) {
+        let db = RequestDb::get_instance();
+        if db.contains_effective_config(self.task_id()) {
+            return;
+        }
+        let config = EffectiveConfig::build(
+            &self.conf,
+            self.request_headers(),
+            &effective_proxy(&self.conf),
+            self.effective_speed_limit(),
+        );
+        db.insert_effective_config(self.task_id(), &config);
+    }
+
+    /// Records the protocol of the first response of the task.
+    pub(crate) fn record_protocol(&self, response: &Response) {
+        RequestDb::get_instance()
+            .update_effective_protocol(self.task_id(), response.version().as_str());
+    }
+}
+
+impl RequestDb {
+    fn create_effective_config_table(&self) {
+        static ONCE: Once = Once::new();
+        call_once(&ONCE, || {
+            if let Err(e) = self.execute(CREATE_EFFECTIVE_CONFIG_TABLE) {
+                error!("create effective config table failed {}", e);
+            }
+        });
+    }
+
+    fn contains_effective_config(&self, task_id: u32) -> bool {
+        self.create_effective_config_table();
+        let sql = format!(
+            "SELECT COUNT(*) FROM task_effective_config WHERE task_id = {}",
+            task_id
+        );
+        matches!(self.query_integer::<u32>(&sql).first(), Some(count) if *count > 0)
+    }
+
+    /// Stores the effective configuration of a task, keeping an existing one.
+    pub(crate) fn insert_effective_config(&self, task_id: u32, config: &EffectiveConfig) {
+        self.create_effective_config_table();
+        let headers = config
+            .headers
+            .iter()
+            .map(|(name, value)| format!("{}\t{}", name, value))
+            .collect::<Vec<_>>()
+            .join("\r\n");
+        let sql = format!(
+            "INSERT OR IGNORE INTO task_effective_config (task_id, url, headers, proxy, speed_limit, resume_strategy, protocol) VALUES ({}, X'{}', X'{}', X'{}', {}, X'{}', X'{}')",
+            task_id,
+            hex(&config.url),
+            hex(&headers),
+            hex(&config.proxy),
+            config.speed_limit,
+            hex(&config.resume_strategy),
+            hex(&config.protocol),
+        );
+        let _ = self.execute(&sql);
+    }
+
+    /// Stores the protocol of a task unless one is known already.
+    pub(crate) fn update_effective_protocol(&self, task_id: u32, protocol: &str) {
+        self.create_effective_config_table();
+        let sql = format!(
+            "UPDATE task_effective_config SET protocol = X'{}' WHERE task_id = {} AND length(protocol) = 0",
+            hex(protocol),
+            task_id
+        );
+        let _ = self.execute(&sql);
+    }
+
+    /// Returns the effective configuration of a task, `None` if it never ran.
+    pub(crate) fn query_effective_config(&self, task_id: u32) -> Option<EffectiveConfig> {
+        self.create_effective_config_table();
+        let sql = format!(
+            "SELECT speed_limit FROM task_effective_config WHERE task_id = {}",
+            task_id
+        );
+        let speed_limit = *self.query_integer::<u64>(&sql).first()?;
+        let text = |column: &str| {
+            let sql = format!(
+                "SELECT {} FROM task_effective_config WHERE task_id = {}",
+                column, task_id
+            );
+            self.query_blob(&sql)
+                .and_then(|value| String::from_utf8(value).ok())
+                .unwrap_or_default()
+        };
+        let headers = text("headers")
+            .split("\r\n")
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Some(EffectiveConfig {
+            url: text("url"),
+            headers,
+            proxy: text("proxy"),
+            speed_limit,
+            resume_strategy: text("resume_strategy"),
+            protocol: text("protocol"),
+        })
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_effective {
+    include!("../../tests/ut/task/ut_effective.rs");
+}
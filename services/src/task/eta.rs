@@ -0,0 +1,137 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Estimated remaining time of running tasks.
+//!
+//! The throughput of a task is tracked as an exponentially weighted moving
+//! average with a time constant of a third of [`ETA_WINDOW_MS`], so samples
+//! older than the window contribute less than five percent. Time passing without progress counts
+//! as zero throughput, which makes the estimate grow during stalls instead of
+//! freezing at the last value.
+
+/// Time in milliseconds the throughput average mostly depends on.
+pub(crate) const ETA_WINDOW_MS: u64 = 30_000;
+
+/// Minimum time between two throughput samples in milliseconds.
+const MIN_SAMPLE_INTERVAL_MS: u64 = 500;
+
+const MINUTE_MS: u64 = 60_000;
+const HOUR_MS: u64 = 60 * MINUTE_MS;
+
+/// Moving average of the throughput of a task.
+#[derive(Debug, Default)]
+pub(crate) struct EtaEstimator {
+    /// Time of the last sample in milliseconds, 0 before the first sample.
+    last_time: u64,
+    /// Processed bytes at the last sample.
+    last_processed: u64,
+    /// Average throughput in bytes per millisecond, `None` until the first
+    /// throughput sample.
+    rate: Option<f64>,
+}
+
+impl EtaEstimator {
+    /// Records the processed bytes of the task at `now`.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current timestamp in milliseconds.
+    /// * `processed` - Total bytes processed so far.
+    ///
+    /// # Notes
+    ///
+    /// Progress going backwards, e.g. after a restart from the beginning,
+    /// discards the history.
+    pub(crate) fn update(&mut self, now: u64, processed: u64) {
+        if self.last_time == 0 || processed < self.last_processed || now < self.last_time {
+            *self = Self {
+                last_time: now,
+                last_processed: processed,
+                rate: None,
+            };
+            return;
+        }
+        let elapsed = now - self.last_time;
+        if elapsed < MIN_SAMPLE_INTERVAL_MS {
+            return;
+        }
+        let sample = (processed - self.last_processed) as f64 / elapsed as f64;
+        self.rate = Some(match self.rate {
+            Some(rate) => rate + Self::weight(elapsed) * (sample - rate),
+            None => sample,
+        });
+        self.last_time = now;
+        self.last_processed = processed;
+    }
+
+    /// Returns the estimated time in milliseconds to transfer `remaining`
+    /// bytes, or `None` if no progress has been observed yet.
+    ///
+    /// The time since the last sample counts as a stall, so the estimate
+    /// keeps growing while no progress is recorded.
+    pub(crate) fn eta_ms(&self, now: u64, remaining: u64) -> Option<u64> {
+        if remaining == 0 {
+            return Some(0);
+        }
+        let rate = self.rate.filter(|rate| *rate > 0.0)?;
+        let stalled = now.saturating_sub(self.last_time);
+        let rate = rate * (1.0 - Self::weight(stalled));
+        // Float to integer casts saturate, so an endless stall ends at u64::MAX.
+        Some((remaining as f64 / rate) as u64)
+    }
+
+    /// Returns the average throughput in bytes per second at `now`, 0 if no
+    /// progress has been observed yet.
+    ///
+    /// Like for [`eta_ms`](Self::eta_ms), the time since the last sample
+    /// counts as a stall.
+    pub(crate) fn speed(&self, now: u64) -> u64 {
+        let Some(rate) = self.rate else {
+            return 0;
+        };
+        let stalled = now.saturating_sub(self.last_time);
+        (rate * (1.0 - Self::weight(stalled)) * 1000.0) as u64
+    }
+
+    /// Weight of a sample covering `elapsed` milliseconds.
+    fn weight(elapsed: u64) -> f64 {
+        1.0 - (-3.0 * elapsed as f64 / ETA_WINDOW_MS as f64).exp()
+    }
+}
+
+/// Rounded estimated remaining time shown in the notification bar.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum EtaBucket {
+    /// Less than a minute.
+    UnderMinute,
+    /// About the given number of minutes, from 1 to 59.
+    Minutes(u64),
+    /// An hour or more.
+    OverHour,
+}
+
+/// Rounds an estimated remaining time for the notification bar.
+pub(crate) fn eta_bucket(eta_ms: u64) -> EtaBucket {
+    if eta_ms < MINUTE_MS {
+        EtaBucket::UnderMinute
+    } else if eta_ms < HOUR_MS {
+        EtaBucket::Minutes(((eta_ms + MINUTE_MS / 2) / MINUTE_MS).min(59))
+    } else {
+        EtaBucket::OverHour
+    }
+}
+
+#[cfg(test)]
+mod ut_eta {
+    include!("../../tests/ut/task/ut_eta.rs");
+}
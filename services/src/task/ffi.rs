@@ -17,11 +17,12 @@
 //! between Rust and C code for task configuration, information, and progress updates.
 
 use super::config::{
-    Action, CommonTaskConfig, ConfigSet, MinSpeed, Mode, NetworkConfig, TaskConfig, Timeout,
-    Version,
+    Action, CommonTaskConfig, ConfigSet, DuplicatePolicy, IpPreference, MinSpeed, Mode,
+    NetworkConfig, ResumeStrategy, TaskConfig, Timeout, Version, DEFAULT_RESPONSE_BODY_LIMIT,
 };
-use super::info::{CommonTaskInfo, InfoSet, TaskInfo, UpdateInfo};
+use super::info::{CommonTaskInfo, InfoSet, TaskInfo};
 use super::notify::{CommonProgress, Progress};
+use super::retry_after::DEFAULT_RETRY_AFTER_CAP_MS;
 use crate::task::info::State;
 use crate::utils::c_wrapper::{CFileSpec, CFormItem, CStringWrapper};
 
@@ -350,6 +351,10 @@ impl TaskInfo {
             common_data: c_struct.common_data,
             max_speed: c_struct.max_speed,
             task_time: c_struct.task_time,
+            // No QoS tier applies to a task that is not running.
+            effective_speed_limit: c_struct.max_speed.max(0) as u64,
+            response_body: None,
+            client_tag: String::new(),
         };
 
         #[cfg(feature = "oh")]
@@ -361,47 +366,6 @@ impl TaskInfo {
     }
 }
 
-/// C-compatible representation of task update information.
-///
-/// This struct provides a way to pass task update details between Rust and C code,
-/// including modification time, update reason, retry attempts, MIME type, and progress.
-#[repr(C)]
-pub(crate) struct CUpdateInfo {
-    /// Last modification timestamp (milliseconds since epoch).
-    pub(crate) mtime: u64,
-    /// Reason code for the update.
-    pub(crate) reason: u8,
-    /// Number of retry attempts made.
-    pub(crate) tries: u32,
-    /// MIME type of the updated content.
-    pub(crate) mime_type: CStringWrapper,
-    /// Current progress information for the task.
-    pub(crate) progress: CProgress,
-}
-
-impl UpdateInfo {
-    /// Converts a Rust UpdateInfo struct to its C-compatible representation.
-    ///
-    /// # Arguments
-    ///
-    /// * `sizes` - Comma-separated string of file sizes.
-    /// * `processed` - Comma-separated string of processed bytes.
-    /// * `extras` - JSON string with additional progress information.
-    ///
-    /// # Returns
-    ///
-    /// Returns a `CUpdateInfo` struct ready to be passed across the FFI boundary.
-    pub(crate) fn to_c_struct(&self, sizes: &str, processed: &str, extras: &str) -> CUpdateInfo {
-        CUpdateInfo {
-            mtime: self.mtime,
-            reason: self.reason,
-            tries: self.tries,
-            mime_type: CStringWrapper::from(self.mime_type.as_str()),
-            progress: self.progress.to_c_struct(sizes, processed, extras),
-        }
-    }
-}
-
 impl TaskConfig {
     /// Converts a Rust TaskConfig struct to its C-compatible representation.
     ///
@@ -552,6 +516,42 @@ impl TaskConfig {
                 c_struct.certs_path_len as usize,
                 CStringWrapper::to_string, // Conversion function for each element
             ),
+            segments: 0,
+            strict_resume: false,
+            preallocate: false,
+            retry_after_cap_ms: DEFAULT_RETRY_AFTER_CAP_MS,
+            debug: false,
+            durable_completion: false,
+            duplicate_policy: DuplicatePolicy::Allow,
+            response_body_limit: DEFAULT_RESPONSE_BODY_LIMIT,
+            effective_url: String::new(),
+            treat_network_as: None,
+            client_tag: String::new(),
+            ip_preference: IpPreference::Default,
+            min_tls_version: None,
+            max_tls_version: None,
+            tls_ciphers: String::new(),
+            report_redirects: false,
+            expected_mime_types: vec![],
+            if_match: String::new(),
+            insecure_skip_verify: false,
+            resume_strategy: ResumeStrategy::RangeHeader,
+            retry_deadline_ms: 0,
+            max_size_bytes: None,
+            max_total_bytes: None,
+            accept_encoding: String::new(),
+            net_id: 0,
+            on_remove_partial: None,
+            content_disposition_name: false,
+            max_cross_host_redirects: None,
+            dns_ttl_override_ms: None,
+            trace_context: None,
+            record_final_metadata: false,
+            concatenated: false,
+            pause_on_thermal_level: None,
+            require_charging_while_running: false,
+            keep_awake: false,
+            privileged: false,
 
             // Common task configuration data
             common_data: CommonTaskConfig {
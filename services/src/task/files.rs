@@ -12,8 +12,9 @@
 // limitations under the License.
 
 use std::fs::{File, OpenOptions};
-use std::io;
+use std::io::{self, Read};
 use std::os::fd::FromRawFd;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 use crate::error::{ErrorCode, ServiceError};
@@ -97,22 +98,35 @@ fn open_task_files(config: &TaskConfig) -> Result<(Files, Vec<i64>), ServiceErro
                         .map_err(ServiceError::IoError)?
                 };
                 // Get file size for upload progress tracking
-                let size = cvt_res_error!(
-                    file.metadata()
-                        .map(|data| data.len())
-                        .map_err(ServiceError::IoError),
+                let metadata = cvt_res_error!(
+                    file.metadata().map_err(ServiceError::IoError),
                     "Cannot get upload file's size - task_id: {}, idx: {}",
                     tid, idx
                 );
+                let size = if config.concatenated {
+                    // An empty read fails on a descriptor not open for reading.
+                    cvt_res_error!(
+                        (&file).read(&mut []).map_err(ServiceError::IoError),
+                        "Upload file not readable - task_id: {}, idx: {}",
+                        tid, idx
+                    );
+                    // The size of a pipe or socket is unknown until read.
+                    match metadata.is_file() {
+                        true => metadata.len() as i64,
+                        false => -1,
+                    }
+                } else {
+                    metadata.len() as i64
+                };
                 // Use Arc<Mutex<File>> to ensure thread-safe access
                 files.push(Arc::new(Mutex::new(file)));
                 debug!(
                     "Get file size succeed - task_id: {}, idx: {}, size: {}",
                     tid, idx, size
                 );
-                sizes.push(size as i64);
+                sizes.push(size);
             }
-            Action::Download => {
+            Action::Download | Action::LocalLoopback => {
                 let file = if fs.is_user_file {
                     // For user-provided files, use the file descriptor directly
                     match fs.fd {
@@ -228,6 +242,43 @@ pub(crate) fn convert_path(uid: u64, bundle_name: &str, path: &str) -> String {
         .replacen("base", &base_replace, 1)
 }
 
+/// Moves the file of a download to a new path.
+///
+/// The file is copied and removed when it cannot be renamed, e.g. across file
+/// systems. An existing file at the new path is never replaced.
+///
+/// # Errors
+/// Returns an `io::Error` if the new path exists or the file cannot be moved.
+pub(crate) fn move_download_file(from: &Path, to: &Path) -> io::Result<()> {
+    if to.exists() {
+        return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+    }
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    if let Err(e) = std::fs::remove_file(from) {
+        let _ = std::fs::remove_file(to);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Opens the destination file of a download from the app's storage.
+///
+/// # Errors
+/// Returns a `ServiceError` if the task has no file in the app's storage or the
+/// file cannot be opened.
+pub(crate) fn open_download_file(config: &TaskConfig) -> Result<File, ServiceError> {
+    let fs = match config.file_specs.first() {
+        Some(fs) if !fs.is_user_file => fs,
+        _ => return Err(ServiceError::ErrorCode(ErrorCode::Other)),
+    };
+    let bundle_name = BundleCache::new(config).get_value()?;
+    open_file_readwrite(config.common_data.uid, &bundle_name, &fs.path)
+        .map_err(ServiceError::IoError)
+}
+
 /// Extracts the UUID from a UID by dividing by 200000.
 /// 
 /// This is a standard way to map user identifiers to unique account identifiers.
@@ -0,0 +1,144 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metadata of completed downloads.
+//!
+//! Downloads configured with `record_final_metadata` record, once complete,
+//! the size of their file on disk under `final_size` and its content type
+//! under `final_mime_type` in the progress `extras`, so applications need
+//! not stat the file themselves. The content type is the one served with the
+//! file, or the one sniffed from its first bytes if the server sent none or
+//! a generic `application/octet-stream`.
+//!
+//! The size is read from the file itself rather than from the progress of
+//! the task, so it is the size of the bytes actually on disk whatever the
+//! content coding of the response.
+
+use std::os::unix::fs::FileExt;
+
+use crate::task::request_task::RequestTask;
+use crate::task::task_control;
+
+/// Key in `extras` holding the size in bytes of the completed file.
+pub(crate) const FINAL_SIZE: &str = "final_size";
+
+/// Key in `extras` holding the content type of the completed file.
+pub(crate) const FINAL_MIME_TYPE: &str = "final_mime_type";
+
+/// Content type of files whose type is unknown.
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Number of leading bytes of a file its content type is sniffed from.
+const SNIFF_LEN: usize = 512;
+
+/// Signatures recognized at the start of a file, with their content type.
+const SIGNATURES: [(&[u8], &str); 9] = [
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"OggS", "application/ogg"),
+    (b"ID3", "audio/mpeg"),
+];
+
+/// Returns the content type of a file starting with `head`, `None` if it is
+/// not recognized.
+pub(crate) fn sniff_mime_type(head: &[u8]) -> Option<&'static str> {
+    if let Some((_, mime_type)) = SIGNATURES
+        .iter()
+        .find(|(signature, _)| head.starts_with(signature))
+    {
+        return Some(mime_type);
+    }
+    if head.len() >= 12 && head.starts_with(b"RIFF") && &head[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    if head.len() >= 8 && &head[4..8] == b"ftyp" {
+        return Some("video/mp4");
+    }
+    let text = head.trim_ascii_start().to_ascii_lowercase();
+    if text.starts_with(b"<!doctype html") || text.starts_with(b"<html") {
+        return Some("text/html");
+    }
+    if text.starts_with(b"<?xml") {
+        return Some("text/xml");
+    }
+    if !head.is_empty() && std::str::from_utf8(head).is_ok() && !head.contains(&0) {
+        return Some("text/plain");
+    }
+    None
+}
+
+/// Returns the content type recorded for a file served with `served`, empty
+/// if none, and starting with `head`.
+pub(crate) fn final_mime_type(served: &str, head: &[u8]) -> String {
+    let served = served.trim();
+    let essence = served.split(';').next().unwrap_or_default().trim();
+    if !essence.is_empty() && !essence.eq_ignore_ascii_case(OCTET_STREAM) {
+        return served.to_string();
+    }
+    sniff_mime_type(head).unwrap_or(OCTET_STREAM).to_string()
+}
+
+impl RequestTask {
+    /// Records the size on disk and the content type of the downloaded file
+    /// in the progress `extras`.
+    ///
+    /// A file the service cannot read back is typed after the response only.
+    pub(crate) async fn record_final_metadata(&self) {
+        let Some(file) = self.files.get(0) else {
+            return;
+        };
+        let handle = task_control::runtime_spawn_blocking(move || {
+            let file = file.lock().unwrap();
+            let size = file.metadata()?.len();
+            let mut head = vec![0; SNIFF_LEN];
+            let read = file.read_at(&mut head, 0).unwrap_or(0);
+            head.truncate(read);
+            Ok((size, head))
+        });
+        let (size, head) = match handle.await {
+            Ok(Ok(res)) => res,
+            Ok(Err(e)) => {
+                error!("task {} final metadata failed, {}", self.task_id(), e);
+                return;
+            }
+            Err(e) => {
+                error!("task {} final metadata failed, {:?}", self.task_id(), e);
+                return;
+            }
+        };
+        let mime_type = final_mime_type(&self.mime_type(), &head);
+        debug!(
+            "task {} final size {}, type {}",
+            self.task_id(),
+            size,
+            mime_type
+        );
+        let mut progress = self.progress.lock().unwrap();
+        progress
+            .extras
+            .insert(FINAL_SIZE.to_string(), size.to_string());
+        progress
+            .extras
+            .insert(FINAL_MIME_TYPE.to_string(), mime_type);
+    }
+}
+
+#[cfg(test)]
+mod ut_final_metadata {
+    include!("../../tests/ut/task/ut_final_metadata.rs");
+}
@@ -0,0 +1,139 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Retries of transient TLS handshake failures.
+//!
+//! Handshakes cut off or timed out, as during a network handover, usually
+//! succeed right away on another try. A request failing that way is sent
+//! again up to `HANDSHAKE_RETRIES` times within the same attempt, without
+//! using a retry of the task. These retries are counted apart and reported
+//! in `extras` under `HANDSHAKE_RETRIES_KEY`.
+//!
+//! Certificate validation and pinning failures are never retried, neither
+//! here nor as a retry of the task.
+
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use ylong_http_client::{ErrorKind, HttpClientError};
+
+use crate::task::request_task::{RequestTask, TaskError};
+
+/// Retries of a transient handshake failure within one attempt.
+pub(crate) const HANDSHAKE_RETRIES: u32 = 2;
+
+/// Delay between two tries of a handshake.
+pub(crate) const HANDSHAKE_RETRY_INTERVAL: Duration =
+    Duration::from_millis(if cfg!(test) { 5 } else { 500 });
+
+/// Key in `extras` holding the number of handshake retries of a task.
+pub(crate) const HANDSHAKE_RETRIES_KEY: &str = "tls_handshake_retries";
+
+/// Error messages of handshakes cut off or timed out.
+const TRANSIENT: [&str; 3] = ["timed out", "timeout", "unexpected eof"];
+
+/// Error messages of certificates or pins rejected, taking precedence.
+const REJECTED: [&str; 4] = ["certificate", "verify failed", "pinning", "pinned"];
+
+/// Kind of a failed TLS handshake.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TlsFault {
+    /// Cut off or timed out, retried within the attempt
+    Transient,
+    /// Certificate or pin rejected, never retried
+    Rejected,
+    /// Any other failure, retried as a retry of the task
+    Other,
+}
+
+/// Returns the kind of a TLS handshake failure, `None` for other errors.
+pub(crate) fn tls_fault(error: &HttpClientError) -> Option<TlsFault> {
+    if !matches!(
+        error.error_kind(),
+        ErrorKind::Connect | ErrorKind::ConnectionUpgrade
+    ) || !error.is_tls_error()
+    {
+        return None;
+    }
+    Some(classify(&format!("{:?}", error)))
+}
+
+/// Classifies a TLS handshake failure by its error message.
+pub(crate) fn classify(message: &str) -> TlsFault {
+    let message = message.to_lowercase();
+    if REJECTED.iter().any(|marker| message.contains(marker)) {
+        TlsFault::Rejected
+    } else if TRANSIENT.iter().any(|marker| message.contains(marker)) {
+        TlsFault::Transient
+    } else {
+        TlsFault::Other
+    }
+}
+
+impl RequestTask {
+    /// Waits before sending a request again if it failed with a transient
+    /// handshake failure and retries are left for the attempt.
+    ///
+    /// # Arguments
+    ///
+    /// * `fault` - Kind of the handshake failure, `None` for other errors
+    /// * `retried` - Handshake retries used by the attempt so far
+    ///
+    /// # Returns
+    ///
+    /// `true` if the request is to be sent again.
+    pub(crate) async fn retry_handshake(&self, fault: Option<TlsFault>, retried: &mut u32) -> bool {
+        if fault != Some(TlsFault::Transient) || *retried >= HANDSHAKE_RETRIES {
+            return false;
+        }
+        *retried += 1;
+        self.handshake_retries.fetch_add(1, Ordering::SeqCst);
+        task_debug!(
+            self.log,
+            "tls handshake failed, retry {} of {}",
+            *retried,
+            HANDSHAKE_RETRIES
+        );
+        ylong_runtime::time::sleep(HANDSHAKE_RETRY_INTERVAL).await;
+        true
+    }
+
+    /// Uses a retry of the task after a connection failure, unless a
+    /// certificate or pin was rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `fault` - Kind of the handshake failure, `None` for other errors
+    pub(crate) async fn connect_retry(&self, fault: Option<TlsFault>) -> Result<(), TaskError> {
+        if fault == Some(TlsFault::Rejected) {
+            task_debug!(self.log, "tls certificate rejected, no retry");
+            return Ok(());
+        }
+        self.network_retry().await
+    }
+
+    /// Records the handshake retries of the task in `extras`, if any.
+    pub(crate) fn record_handshake_retries(&self, extras: &mut HashMap<String, String>) {
+        let retries = self.handshake_retries.load(Ordering::SeqCst);
+        if retries > 0 {
+            extras.insert(HANDSHAKE_RETRIES_KEY.to_string(), retries.to_string());
+        }
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_handshake {
+    include!("../../tests/ut/task/ut_handshake.rs");
+}
@@ -16,7 +16,7 @@ use std::collections::HashMap;
 /// Task state enumeration.
 pub use ffi::State;
 
-use super::notify::{EachFileStatus, NotifyData, Progress};
+use super::notify::{EachFileStatus, NotifyData, Progress, ResponseBody};
 use crate::task::config::{Action, Version};
 use crate::task::reason::Reason;
 use crate::utils::c_wrapper::{CFileSpec, CFormItem};
@@ -54,6 +54,13 @@ pub(crate) struct TaskInfo {
     pub(crate) max_speed: i64,
     /// Time when the task was created.
     pub(crate) task_time: u64,
+    /// Speed limit actually applied to the task in bytes per second, the
+    /// lower of the QoS tier and `max_speed`. 0 means unlimited.
+    pub(crate) effective_speed_limit: u64,
+    /// Upload response body captured on completion, if any.
+    pub(crate) response_body: Option<ResponseBody>,
+    /// Client tag set at creation, empty if none.
+    pub(crate) client_tag: String,
 }
 
 impl TaskInfo {
@@ -75,6 +82,9 @@ impl TaskInfo {
             common_data: CommonTaskInfo::new(),
             max_speed: 0,
             task_time: 0,
+            effective_speed_limit: 0,
+            response_body: None,
+            client_tag: String::new(),
         }
     }
 
@@ -188,6 +198,9 @@ mod ffi {
         Failed = 0x41,
         /// Task has been removed from the system.
         Removed = 0x50,
+        /// Task has been removed but can still be restored until its grace
+        /// period expires.
+        PendingRemoval = 0x51,
         /// Wildcard value used for filtering any state.
         Any = 0x61,
     }
@@ -224,6 +237,7 @@ impl From<u8> for State {
             64 => State::Completed,
             65 => State::Failed,
             80 => State::Removed,
+            81 => State::PendingRemoval,
             _ => State::Any,
         }
     }
@@ -262,6 +276,9 @@ impl TaskInfo {
             each_file_status: self.build_each_file_status(),
             task_id: self.common_data.task_id,
             uid: self.common_data.uid,
+            response_body: self.response_body.clone(),
+            eta_ms: None,
+            client_tag: self.client_tag.clone(),
         }
     }
 }
@@ -0,0 +1,335 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Address family preference of task connections.
+//!
+//! Tasks on the default network resolve hosts through [`PreferenceResolver`],
+//! which takes them from the shared DNS cache and, for an [`IpPreference`]
+//! other than `Default`, filters and orders the addresses handed to the HTTP
+//! client. The client connects to them one
+//! after the other, so for the `*Preferred` preferences the resolver races a
+//! connection to the preferred family against one to the other family
+//! started `FALLBACK_STAGGER` later, Happy Eyeballs style, and puts the
+//! address that connected first in front.
+//!
+//! The connections of the race only probe the families, the client connects
+//! again. To pay for a race once per host rather than once per connection,
+//! the family that won is remembered for the host for [`WINNER_TTL`], and
+//! resolutions in that time put its address in front without racing.
+//!
+//! The family of the address in front is recorded in the [`UsedFamily`] of
+//! the task, and under `address_family` in its progress `extras`.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ylong_http_client::async_impl::{Addrs, Resolver, SocketFuture};
+
+use crate::task::config::IpPreference;
+use crate::task::debug_log::TaskLog;
+use crate::task::dns_cache::DnsCache;
+use crate::task::request_task::RequestTask;
+use crate::task::task_control;
+
+/// Delay before a connection to the fallback family is started.
+pub(crate) const FALLBACK_STAGGER: Duration = Duration::from_millis(300);
+
+/// Longest time the connection race may delay a request.
+pub(crate) const RACE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time the family that won a race is preferred for its host.
+pub(crate) const WINNER_TTL: Duration = Duration::from_secs(600);
+
+/// Key in `extras` holding the address family a task connected to.
+pub(crate) const ADDRESS_FAMILY: &str = "address_family";
+
+/// Family of the addresses last resolved for a task, shared by the task and
+/// the resolver of its client.
+pub(crate) type UsedFamily = Arc<Mutex<Option<&'static str>>>;
+
+/// Whether IPv4 won the last race to a host, and when.
+static WINNERS: LazyLock<Mutex<HashMap<String, (bool, Instant)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the address of the family that won the last race to `host`, if
+/// that race is at most `WINNER_TTL` old.
+fn last_winner(host: &str, addrs: &[SocketAddr]) -> Option<SocketAddr> {
+    let mut winners = WINNERS.lock().unwrap();
+    let (ipv4, won) = *winners.get(host)?;
+    if won.elapsed() >= WINNER_TTL {
+        winners.remove(host);
+        return None;
+    }
+    addrs.iter().find(|addr| addr.is_ipv4() == ipv4).copied()
+}
+
+impl IpPreference {
+    /// Returns whether an address may be connected to.
+    fn allows(&self, addr: &SocketAddr) -> bool {
+        match self {
+            IpPreference::V4Only => addr.is_ipv4(),
+            IpPreference::V6Only => addr.is_ipv6(),
+            _ => true,
+        }
+    }
+
+    /// Returns whether an address is of the preferred family, `None` if no
+    /// family is preferred.
+    fn prefers(&self, addr: &SocketAddr) -> Option<bool> {
+        match self {
+            IpPreference::V4Preferred => Some(addr.is_ipv4()),
+            IpPreference::V6Preferred => Some(addr.is_ipv6()),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the name of the family of an address as recorded in `extras`.
+fn family(addr: &SocketAddr) -> &'static str {
+    if addr.is_ipv4() {
+        "ipv4"
+    } else {
+        "ipv6"
+    }
+}
+
+/// Filters and orders resolved addresses by a preference.
+///
+/// The addresses of the preferred family are interleaved with the others,
+/// starting with the preferred one, so a client trying them in order falls
+/// back to the other family after a single failed attempt.
+pub(crate) fn order_addrs<I>(addrs: I, preference: IpPreference) -> Vec<SocketAddr>
+where
+    I: IntoIterator<Item = SocketAddr>,
+{
+    let (preferred, others): (Vec<_>, Vec<_>) = addrs
+        .into_iter()
+        .filter(|addr| preference.allows(addr))
+        .partition(|addr| preference.prefers(addr).unwrap_or(true));
+
+    let mut ordered = Vec::with_capacity(preferred.len() + others.len());
+    let mut preferred = preferred.into_iter();
+    let mut others = others.into_iter();
+    loop {
+        match (preferred.next(), others.next()) {
+            (None, None) => break,
+            (first, second) => ordered.extend(first.into_iter().chain(second)),
+        }
+    }
+    ordered
+}
+
+/// Races connections to the first address of each family.
+///
+/// The connection to `addrs[0]` is started at once, the one to the first
+/// address of the other family after `FALLBACK_STAGGER` or as soon as the
+/// first one fails.
+///
+/// # Returns
+///
+/// The address connected to first, `None` if both connections fail or the
+/// race times out.
+pub(crate) fn race<C>(addrs: &[SocketAddr], connect: C) -> Option<SocketAddr>
+where
+    C: Fn(SocketAddr) -> io::Result<()> + Send + Sync + 'static,
+{
+    let first = *addrs.first()?;
+    let mut fallback = addrs
+        .iter()
+        .find(|addr| addr.is_ipv4() != first.is_ipv4())
+        .copied();
+
+    let connect = Arc::new(connect);
+    let (tx, rx) = mpsc::channel();
+    let start = |addr: SocketAddr| {
+        let connect = connect.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let _ = tx.send((addr, connect(addr).is_ok()));
+        });
+    };
+
+    let deadline = Instant::now() + RACE_TIMEOUT;
+    start(first);
+    let mut pending = 1;
+    loop {
+        let wait = match fallback {
+            Some(_) => FALLBACK_STAGGER,
+            None => deadline.saturating_duration_since(Instant::now()),
+        };
+        match rx.recv_timeout(wait) {
+            Ok((addr, true)) => return Some(addr),
+            Ok((addr, false)) => {
+                info!("connect to {} family failed", family(&addr));
+                pending -= 1;
+            }
+            Err(RecvTimeoutError::Timeout) if fallback.is_none() => return None,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return None,
+        }
+        if let Some(addr) = fallback.take() {
+            start(addr);
+            pending += 1;
+        } else if pending == 0 {
+            return None;
+        }
+    }
+}
+
+/// Resolves the addresses of a task to `host` by its preference.
+///
+/// # Errors
+///
+/// Returns an error if no resolved address is allowed by the preference.
+pub(crate) fn resolve<C>(
+    log: TaskLog,
+    host: &str,
+    addrs: Vec<SocketAddr>,
+    preference: IpPreference,
+    used_family: &UsedFamily,
+    connect: C,
+) -> io::Result<Vec<SocketAddr>>
+where
+    C: Fn(SocketAddr) -> io::Result<()> + Send + Sync + 'static,
+{
+    let mut addrs = order_addrs(addrs, preference);
+    let Some(first) = addrs.first().copied() else {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            format!("no address allowed by {:?}", preference),
+        ));
+    };
+
+    let both = addrs.iter().any(|addr| addr.is_ipv4() != first.is_ipv4());
+    let used = if preference.prefers(&first).is_some() && both {
+        let winner = match last_winner(host, &addrs) {
+            Some(winner) => Some(winner),
+            None => {
+                let begin = Instant::now();
+                let winner = race(&addrs, connect);
+                task_debug!(
+                    log,
+                    "connection race took {} ms, {:?}",
+                    begin.elapsed().as_millis(),
+                    winner
+                );
+                if let Some(winner) = winner {
+                    WINNERS
+                        .lock()
+                        .unwrap()
+                        .insert(host.to_string(), (winner.is_ipv4(), Instant::now()));
+                }
+                winner
+            }
+        };
+        if let Some(winner) = winner {
+            addrs.retain(|addr| *addr != winner);
+            addrs.insert(0, winner);
+        }
+        winner
+    } else {
+        Some(first)
+    };
+
+    if let Some(used) = used {
+        *used_family.lock().unwrap() = Some(family(&used));
+    }
+    Ok(addrs)
+}
+
+/// Returns whether the platform has an IPv6 address besides loopback.
+pub(crate) fn has_ipv6() -> bool {
+    std::fs::read_to_string("/proc/net/if_inet6").is_ok_and(|table| has_ipv6_in(&table))
+}
+
+/// Returns whether an `if_inet6` table lists a non-loopback interface.
+pub(crate) fn has_ipv6_in(table: &str) -> bool {
+    table
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .any(|interface| interface != "lo")
+}
+
+/// Resolver of the HTTP client of a task on the default network.
+pub(crate) struct PreferenceResolver {
+    log: TaskLog,
+    preference: IpPreference,
+    ttl_override: Option<Duration>,
+    used_family: UsedFamily,
+}
+
+impl PreferenceResolver {
+    pub(crate) fn new(
+        log: TaskLog,
+        preference: IpPreference,
+        ttl_override: Option<Duration>,
+        used_family: UsedFamily,
+    ) -> Self {
+        Self {
+            log,
+            preference,
+            ttl_override,
+            used_family,
+        }
+    }
+}
+
+impl Resolver for PreferenceResolver {
+    fn resolve(&self, authority: &str) -> SocketFuture {
+        let authority = authority.to_string();
+        let log = self.log;
+        let preference = self.preference;
+        let ttl_override = self.ttl_override;
+        let used_family = self.used_family.clone();
+        Box::pin(async move {
+            let handle = task_control::runtime_spawn_blocking(move || {
+                let addrs = DnsCache::get_instance().lookup(&authority, ttl_override, || {
+                    Ok(authority.to_socket_addrs()?.collect())
+                })?;
+                if preference == IpPreference::Default {
+                    return Ok(addrs);
+                }
+                resolve(log, &authority, addrs, preference, &used_family, |addr| {
+                    TcpStream::connect_timeout(&addr, RACE_TIMEOUT).map(drop)
+                })
+            });
+            let addrs = match handle.await {
+                Ok(res) => res?,
+                Err(e) => return Err(io::Error::new(io::ErrorKind::Other, e).into()),
+            };
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+impl RequestTask {
+    /// Records the address family the task connected to.
+    ///
+    /// Only tasks with an address family preference record it, under
+    /// `address_family` in `extras`.
+    pub(crate) fn record_address_family(&self, extras: &mut HashMap<String, String>) {
+        if let Some(family) = self.used_family.lock().unwrap().take() {
+            extras.insert(ADDRESS_FAMILY.to_string(), family.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_ip_preference {
+    include!("../../tests/ut/task/ut_ip_preference.rs");
+}
@@ -0,0 +1,263 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keep-alive pool of HTTP clients shared by sequential tasks.
+//!
+//! Every task owns an HTTP client, and the connections a client keeps alive
+//! only serve its own requests. Tasks whose client holds nothing specific to
+//! them hand it to the [`KeepAlivePool`] when they are dropped, and the next
+//! task of the same app to the same origin with the same connection settings
+//! takes it over with its idle HTTP/1.1 connections, so bursts of small tasks
+//! to one host skip the TCP and TLS handshakes.
+//!
+//! Idle clients are dropped, closing their connections, once unused for
+//! [`DEFAULT_IDLE_TIMEOUT`], and at most [`DEFAULT_MAX_PER_HOST`] are kept
+//! per origin. Both may be overridden by the [`IDLE_TIMEOUT_PARAM`] and
+//! [`MAX_PER_HOST_PARAM`] system parameters, a maximum of 0 disables the
+//! pool.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use ylong_http_client::async_impl::Client;
+use ylong_http_client::Timeout;
+
+use crate::task::config::{IpPreference, TaskConfig};
+use crate::task::request_task::RequestTask;
+
+/// Time an idle client is kept with its connections.
+pub(crate) const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Idle clients kept per origin.
+pub(crate) const DEFAULT_MAX_PER_HOST: usize = 4;
+/// System parameter overriding the time idle clients are kept, in ms.
+pub(crate) const IDLE_TIMEOUT_PARAM: &str = "const.request.keep_alive_idle_ms";
+/// System parameter overriding the idle clients kept per origin.
+pub(crate) const MAX_PER_HOST_PARAM: &str = "const.request.keep_alive_max_per_host";
+
+/// HTTP client of a task, shared with the pool once the task is dropped.
+pub(crate) type SharedClient = Arc<ylong_runtime::sync::Mutex<Client>>;
+
+/// Tasks whose clients may serve each other.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct PoolKey {
+    origin: String,
+    uid: u64,
+    settings: String,
+}
+
+/// Returns the key of the clients a task may take over, `None` if its
+/// client holds state of the task and cannot be shared.
+pub(crate) fn pool_key(config: &TaskConfig) -> Option<PoolKey> {
+    const ATOMIC_SERVICE: u32 = 1;
+    if config.net_id != 0
+        || config.ip_preference != IpPreference::Default
+        || config.report_redirects
+        || config.max_cross_host_redirects.is_some()
+        || config.bundle_type == ATOMIC_SERVICE
+        || config.insecure_skip_verify
+    {
+        return None;
+    }
+    let common = &config.common_data;
+    // Everything else the client is built from
+    let settings = format!(
+        "{:?}",
+        (
+            common.timeout.connection_timeout,
+            common.redirect,
+            (common.min_speed.speed, common.min_speed.duration),
+            (config.min_tls_version, config.max_tls_version),
+            &config.tls_ciphers,
+            (&config.proxy, &config.certs_path, &config.certificate_pins),
+            config.dns_ttl_override_ms,
+        )
+    );
+    Some(PoolKey {
+        origin: origin_of(config.request_url())?,
+        uid: common.uid,
+        settings,
+    })
+}
+
+/// Returns the scheme and lower case authority of an HTTP(S) URL.
+fn origin_of(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let scheme = scheme.to_ascii_lowercase();
+    if scheme != "http" && scheme != "https" {
+        return None;
+    }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    if host.is_empty() {
+        return None;
+    }
+    Some(format!("{}://{}", scheme, host.to_ascii_lowercase()))
+}
+
+/// Client counters, as shown by the dump command.
+#[derive(Default)]
+pub(crate) struct KeepAliveStats {
+    /// Clients taken over from an earlier task.
+    pub(crate) reused: AtomicU64,
+    /// Clients dropped after idling too long or to make room.
+    pub(crate) expired: AtomicU64,
+}
+
+struct Idle<C> {
+    key: PoolKey,
+    client: C,
+    since: Instant,
+}
+
+/// Idle clients by origin, shared by all tasks.
+pub(crate) struct KeepAlivePool<C> {
+    idle_timeout: Duration,
+    max_per_host: usize,
+    idle: Mutex<HashMap<String, VecDeque<Idle<C>>>>,
+    stats: KeepAliveStats,
+}
+
+impl KeepAlivePool<SharedClient> {
+    /// Returns the pool configured by the system parameters.
+    pub(crate) fn get_instance() -> &'static Self {
+        static INSTANCE: LazyLock<KeepAlivePool<SharedClient>> = LazyLock::new(|| {
+            #[cfg(feature = "oh")]
+            let (idle_timeout, max_per_host) = (
+                crate::utils::get_int_parameter(
+                    IDLE_TIMEOUT_PARAM,
+                    DEFAULT_IDLE_TIMEOUT.as_millis() as i64,
+                ),
+                crate::utils::get_int_parameter(MAX_PER_HOST_PARAM, DEFAULT_MAX_PER_HOST as i64),
+            );
+            #[cfg(not(feature = "oh"))]
+            let (idle_timeout, max_per_host) = (
+                DEFAULT_IDLE_TIMEOUT.as_millis() as i64,
+                DEFAULT_MAX_PER_HOST as i64,
+            );
+            let idle_timeout =
+                u64::try_from(idle_timeout).map_or(DEFAULT_IDLE_TIMEOUT, Duration::from_millis);
+            let max_per_host = usize::try_from(max_per_host).unwrap_or(DEFAULT_MAX_PER_HOST);
+            info!(
+                "keep-alive idle timeout {:?}, max per host {}",
+                idle_timeout, max_per_host
+            );
+            KeepAlivePool::new(idle_timeout, max_per_host)
+        });
+        &INSTANCE
+    }
+}
+
+impl<C> KeepAlivePool<C> {
+    pub(crate) fn new(idle_timeout: Duration, max_per_host: usize) -> Self {
+        Self {
+            idle_timeout,
+            max_per_host,
+            idle: Mutex::new(HashMap::new()),
+            stats: KeepAliveStats::default(),
+        }
+    }
+
+    /// Takes the most recently idled client of `key`, if any.
+    pub(crate) fn take(&self, key: &PoolKey) -> Option<C> {
+        self.take_at(key, Instant::now())
+    }
+
+    pub(crate) fn take_at(&self, key: &PoolKey, now: Instant) -> Option<C> {
+        let mut idle = self.idle.lock().unwrap();
+        let clients = idle.get_mut(&key.origin)?;
+        self.expire(clients, now);
+        let position = clients.iter().rposition(|idle| idle.key == *key);
+        let client = position.and_then(|position| clients.remove(position));
+        if clients.is_empty() {
+            idle.remove(&key.origin);
+        }
+        let client = client?;
+        self.stats.reused.fetch_add(1, Ordering::Relaxed);
+        Some(client.client)
+    }
+
+    /// Keeps the client of a dropped task for the next task of `key`,
+    /// dropping the oldest idle client of the origin if it is full.
+    pub(crate) fn give(&self, key: PoolKey, client: C) {
+        self.give_at(key, client, Instant::now())
+    }
+
+    pub(crate) fn give_at(&self, key: PoolKey, client: C, now: Instant) {
+        if self.max_per_host == 0 {
+            return;
+        }
+        let mut idle = self.idle.lock().unwrap();
+        let clients = idle.entry(key.origin.clone()).or_default();
+        self.expire(clients, now);
+        while clients.len() >= self.max_per_host {
+            clients.pop_front();
+            self.stats.expired.fetch_add(1, Ordering::Relaxed);
+        }
+        clients.push_back(Idle {
+            key,
+            client,
+            since: now,
+        });
+    }
+
+    /// Drops the clients of an origin idle for longer than the timeout.
+    fn expire(&self, clients: &mut VecDeque<Idle<C>>, now: Instant) {
+        while clients
+            .front()
+            .is_some_and(|idle| now.saturating_duration_since(idle.since) >= self.idle_timeout)
+        {
+            clients.pop_front();
+            self.stats.expired.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the client counters since the service started.
+    pub(crate) fn stats(&self) -> &KeepAliveStats {
+        &self.stats
+    }
+
+    /// Returns the number of clients waiting for a task.
+    pub(crate) fn idle(&self) -> usize {
+        self.idle.lock().unwrap().values().map(VecDeque::len).sum()
+    }
+}
+
+impl RequestTask {
+    /// Gives the total timeout left to a client taken over from another task.
+    pub(crate) async fn refresh_client_timeout(&self) {
+        if self.pool_key.is_some() {
+            let rest_time = self.rest_time.load(Ordering::SeqCst);
+            self.client
+                .lock()
+                .await
+                .total_timeout(Timeout::from_secs(rest_time));
+        }
+    }
+}
+
+impl Drop for RequestTask {
+    fn drop(&mut self) {
+        if let Some(key) = self.pool_key.take() {
+            KeepAlivePool::get_instance().give(key, self.client.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_keep_alive {
+    include!("../../tests/ut/task/ut_keep_alive.rs");
+}
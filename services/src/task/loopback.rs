@@ -0,0 +1,164 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Developer-mode loopback tasks.
+//!
+//! A loopback task generates its data instead of downloading it, and writes
+//! it through the same progress, speed limit and write paths as a download.
+//! System callers use it to exercise the task pipeline on devices without
+//! depending on a network. The data of the task is described by `extras`
+//! of its configuration.
+
+use std::collections::HashMap;
+use std::future::poll_fn;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ylong_http_client::HttpClientError;
+
+use super::operator::TaskOperator;
+use super::reason::Reason;
+use super::request_task::{TaskError, TaskPhase};
+use crate::manage::database::RequestDb;
+use crate::task::request_task::RequestTask;
+use crate::task::task_control;
+
+/// Key in `extras` holding the number of bytes a loopback task writes.
+pub(crate) const LOOPBACK_SIZE: &str = "loopback.size";
+
+/// Key in `extras` holding the rate in bytes per second a loopback task
+/// writes at, 0 or missing for no rate besides the speed limits of the task.
+pub(crate) const LOOPBACK_RATE: &str = "loopback.rate";
+
+/// Key in `extras` holding the offset a loopback task fails at, missing for
+/// a task running to completion.
+pub(crate) const LOOPBACK_FAIL_AT: &str = "loopback.fail_at";
+
+/// Bytes generated by each write of a loopback task.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Data written by a loopback task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct LoopbackSpec {
+    /// Total number of bytes of the file.
+    pub(crate) size: u64,
+    /// Bytes written per second, 0 for no rate.
+    pub(crate) rate: u64,
+    /// Offset the task fails at once its data reaches it.
+    pub(crate) fail_at: Option<u64>,
+}
+
+impl LoopbackSpec {
+    /// Reads the spec from `extras` of a task configuration, `None` if the
+    /// size is missing or a value is not a number.
+    pub(crate) fn from_extras(extras: &HashMap<String, String>) -> Option<Self> {
+        let size = extras.get(LOOPBACK_SIZE)?.parse().ok()?;
+        let rate = match extras.get(LOOPBACK_RATE) {
+            Some(rate) => rate.parse().ok()?,
+            None => 0,
+        };
+        let fail_at = match extras.get(LOOPBACK_FAIL_AT) {
+            Some(offset) => Some(offset.parse().ok()?),
+            None => None,
+        };
+        Some(Self {
+            size,
+            rate,
+            fail_at,
+        })
+    }
+}
+
+/// Returns the byte a loopback task writes at `offset` of its file.
+///
+/// The pattern does not depend on where a run started, so a paused and
+/// resumed task writes the same file as one running at once.
+pub(crate) fn pattern(offset: u64) -> u8 {
+    (offset % 251) as u8
+}
+
+/// Runs a loopback task, recording its result like a download does.
+pub(crate) async fn run(task: Arc<RequestTask>, abort_flag: Arc<AtomicBool>) {
+    let result = match run_inner(task.clone(), abort_flag).await {
+        Ok(()) => Ok(()),
+        Err(TaskError::Failed(reason)) => Err(reason),
+        // A paused or removed task is not given a result.
+        Err(TaskError::Waiting(TaskPhase::UserAbort)) => return,
+        Err(TaskError::Waiting(_)) => Err(Reason::OthersError),
+    };
+    *task.running_result.lock().unwrap() = Some(result);
+}
+
+async fn run_inner(task: Arc<RequestTask>, abort_flag: Arc<AtomicBool>) -> Result<(), TaskError> {
+    let Some(spec) = LoopbackSpec::from_extras(&task.conf.extras) else {
+        error!("loopback task {} without a valid spec", task.task_id());
+        return Err(TaskError::Failed(Reason::OthersError));
+    };
+    // Continues after the bytes written by a previous run.
+    task.prepare_download().await?;
+    task.progress.lock().unwrap().sizes = vec![spec.size as i64];
+    task.update_progress_in_database();
+    RequestDb::get_instance()
+        .update_task_sizes(task.task_id(), &task.progress.lock().unwrap().sizes);
+
+    let end = spec
+        .fail_at
+        .map_or(spec.size, |offset| offset.min(spec.size));
+    let mut operator = TaskOperator::new(task.clone(), abort_flag);
+    let result = generate(&mut operator, end, spec.rate).await;
+    let written = task.writes.drain().await.map_err(HttpClientError::other);
+    task.sync_progress();
+    if let Err(e) = result.and(written) {
+        return task.handle_download_error(e).await;
+    }
+
+    let file_mutex = task.files.get(0).unwrap();
+    task_control::file_sync_all(file_mutex).await?;
+    if end < spec.size {
+        info!("loopback task {} failed at {}", task.task_id(), end);
+        return Err(TaskError::Failed(Reason::OthersError));
+    }
+    info!("{} loopback written", task.task_id());
+    Ok(())
+}
+
+/// Writes the pattern from the progress of the task up to `end`, no faster
+/// than `rate` bytes per second.
+async fn generate(operator: &mut TaskOperator, end: u64, rate: u64) -> Result<(), HttpClientError> {
+    let begin = operator.task.progress.lock().unwrap().processed[0] as u64;
+    let start = Instant::now();
+    let mut chunk = [0u8; CHUNK_SIZE];
+    let mut offset = begin;
+    while offset < end {
+        poll_fn(|cx| operator.poll_progress_common(cx)).await?;
+        let len = (end - offset).min(CHUNK_SIZE as u64) as usize;
+        for (i, byte) in chunk[..len].iter_mut().enumerate() {
+            *byte = pattern(offset + i as u64);
+        }
+        poll_fn(|cx| operator.poll_write_file(cx, &chunk[..len], 0)).await?;
+        offset += len as u64;
+        if rate > 0 {
+            let due = Duration::from_secs_f64((offset - begin) as f64 / rate as f64);
+            if let Some(wait) = due.checked_sub(start.elapsed()) {
+                ylong_runtime::time::sleep(wait).await;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod ut_loopback {
+    include!("../../tests/ut/task/ut_loopback.rs");
+}
@@ -23,12 +23,37 @@ pub mod config;
 pub mod info;
 
 // Internal modules for task implementation
+pub(crate) mod accept_encoding; // Content codings advertised by tasks
+pub(crate) mod content_disposition; // File names from Content-Disposition
+pub(crate) mod content_type; // Expected content types of downloads
+pub(crate) mod debug_log;    // Per-task debug logging
+pub(crate) mod dns_cache;    // Shared cache of resolved hosts
 pub(crate) mod download;     // Download task handling
+pub(crate) mod durable;      // Durable completion of downloads
+pub(crate) mod effective;    // Effective configuration snapshots
+pub(crate) mod eta;          // Remaining time estimation
 pub(crate) mod files;         // File management utilities
+pub(crate) mod final_metadata; // Metadata of completed downloads
+pub(crate) mod handshake;     // Retries of transient TLS handshake failures
+pub(crate) mod ip_preference; // Address family preference of connections
+pub(crate) mod keep_alive;    // Clients kept alive across tasks
+pub(crate) mod loopback;      // Developer-mode loopback tasks
+pub(crate) mod net_binding;   // Network binding of tasks
 pub(crate) mod notify;        // Notification and event handling
 mod operator;                 // Task operation implementations
+pub(crate) mod progress_sampler; // Timer-driven progress sampling
 pub(crate) mod reason;        // Error and state reason codes
+pub(crate) mod redirect;      // Redirect chain reporting
 pub(crate) mod request_task;  // Core task abstraction
+pub(crate) mod resume;        // Resuming by a query parameter
+pub(crate) mod retry_after;   // Retry-After header parsing
+pub(crate) mod segment;       // Range-chunked download handling
+pub(crate) mod size_limit;    // Size caps of task files
+pub(crate) mod sparse;        // Releasing space of unwritten ranges
+pub(crate) mod speed_history; // Speed history for graphing
+pub(crate) mod trace_context; // W3C trace context propagation
+pub(crate) mod trailers;      // Trailers of download responses
+pub(crate) mod write_buffer;  // Bounded buffering of download writes
 
 /// Constant representing atomic service identifier.
 pub(crate) const ATOMIC_SERVICE: u32 = 1;
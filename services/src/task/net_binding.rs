@@ -0,0 +1,186 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Network binding of tasks.
+//!
+//! On devices with several networks, a task configured with a `net_id`
+//! resolves its hosts on the network of that handle through [`NetResolver`],
+//! even if another network is the default. A `net_id` of 0 keeps the default
+//! network, negative handles are rejected when the task is created.
+//!
+//! The network must be available whenever the task runs, otherwise the task
+//! fails with `Reason::BoundNetworkUnavailable`.
+
+use std::io;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+#[cfg(not(feature = "oh"))]
+use std::sync::Mutex;
+
+use ylong_http_client::async_impl::{Addrs, Resolver, SocketFuture};
+
+use crate::task::config::IpPreference;
+use crate::task::debug_log::TaskLog;
+use crate::task::ip_preference::{self, UsedFamily, RACE_TIMEOUT};
+use crate::task::reason::Reason;
+use crate::task::request_task::{RequestTask, TaskError};
+use crate::task::task_control;
+
+/// Networks available to bound tasks, there is no netstack off the device.
+#[cfg(not(feature = "oh"))]
+static ATTACHED_NETS: Mutex<Vec<i32>> = Mutex::new(Vec::new());
+
+/// Makes the network of a handle available to bound tasks or not.
+#[cfg(not(feature = "oh"))]
+pub(crate) fn attach_net(net_id: i32, attached: bool) {
+    let mut nets = ATTACHED_NETS.lock().unwrap();
+    nets.retain(|id| *id != net_id);
+    if attached {
+        nets.push(net_id);
+    }
+}
+
+/// Returns whether a task may be bound to the network of `net_id`.
+pub(crate) fn is_valid_net_id(net_id: i32) -> bool {
+    net_id >= 0
+}
+
+/// Returns whether the network of a handle is available.
+pub(crate) fn net_available(net_id: i32) -> bool {
+    #[cfg(feature = "oh")]
+    {
+        crate::manage::network::is_net_available(net_id)
+    }
+    #[cfg(not(feature = "oh"))]
+    {
+        ATTACHED_NETS.lock().unwrap().contains(&net_id)
+    }
+}
+
+/// Splits an authority into its host and port, removing the brackets of an
+/// IPv6 host.
+pub(crate) fn split_authority(authority: &str) -> Option<(&str, u16)> {
+    let (host, port) = authority.rsplit_once(':')?;
+    let port = port.parse::<u16>().ok()?;
+    let host = match host.strip_prefix('[') {
+        Some(host) => host.strip_suffix(']')?,
+        None => host,
+    };
+    Some((host, port))
+}
+
+/// Resolves an authority on the network of `net_id`.
+///
+/// # Errors
+///
+/// Returns an error if the authority is malformed or the host has no
+/// address on the network.
+pub(crate) fn lookup(net_id: i32, authority: &str) -> io::Result<Vec<SocketAddr>> {
+    let Some((host, port)) = split_authority(authority) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid authority {}", authority),
+        ));
+    };
+    #[cfg(feature = "oh")]
+    let addrs: Vec<SocketAddr> = crate::manage::network::resolve_on_net(net_id, host)
+        .iter()
+        .filter_map(|ip| ip.parse().ok())
+        .map(|ip| SocketAddr::new(ip, port))
+        .collect();
+    #[cfg(not(feature = "oh"))]
+    let addrs: Vec<SocketAddr> = if net_available(net_id) {
+        (host, port).to_socket_addrs()?.collect()
+    } else {
+        vec![]
+    };
+    if addrs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} has no address on net {}", host, net_id),
+        ));
+    }
+    Ok(addrs)
+}
+
+/// Resolver of the HTTP client of a task bound to a network.
+///
+/// The address family preference of the task applies to the addresses
+/// resolved on the network.
+pub(crate) struct NetResolver {
+    log: TaskLog,
+    net_id: i32,
+    preference: IpPreference,
+    used_family: UsedFamily,
+}
+
+impl NetResolver {
+    pub(crate) fn new(
+        log: TaskLog,
+        net_id: i32,
+        preference: IpPreference,
+        used_family: UsedFamily,
+    ) -> Self {
+        Self {
+            log,
+            net_id,
+            preference,
+            used_family,
+        }
+    }
+}
+
+impl Resolver for NetResolver {
+    fn resolve(&self, authority: &str) -> SocketFuture {
+        let authority = authority.to_string();
+        let log = self.log;
+        let net_id = self.net_id;
+        let preference = self.preference;
+        let used_family = self.used_family.clone();
+        Box::pin(async move {
+            let handle = task_control::runtime_spawn_blocking(move || {
+                let addrs = lookup(net_id, &authority)?;
+                if preference == IpPreference::Default {
+                    return Ok(addrs);
+                }
+                // Families reachable on the network are remembered apart
+                let host = format!("{}@{}", authority, net_id);
+                ip_preference::resolve(log, &host, addrs, preference, &used_family, |addr| {
+                    TcpStream::connect_timeout(&addr, RACE_TIMEOUT).map(drop)
+                })
+            });
+            let addrs = match handle.await {
+                Ok(res) => res?,
+                Err(e) => return Err(io::Error::new(io::ErrorKind::Other, e).into()),
+            };
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+impl RequestTask {
+    /// Fails the task with `Reason::BoundNetworkUnavailable` if the network
+    /// it is bound to is unavailable.
+    pub(crate) fn check_bound_network(&self) -> Result<(), TaskError> {
+        let net_id = self.conf.net_id;
+        if net_id != 0 && !net_available(net_id) {
+            error!("task {} bound net {} unavailable", self.task_id(), net_id);
+            return Err(TaskError::Failed(Reason::BoundNetworkUnavailable));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ut_net_binding {
+    include!("../../tests/ut/task/ut_net_binding.rs");
+}
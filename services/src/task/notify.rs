@@ -52,6 +52,39 @@ pub(crate) enum WaitingCause {
     AppState,
     /// Task is waiting due to user state constraints.
     UserState,
+    /// Task is waiting for the delay requested by a rate limiting server.
+    RateLimit,
+    /// Background task is waiting for the device to be charging.
+    Battery,
+    /// Task is waiting for the user to sign in to a captive portal.
+    CaptivePortal,
+    /// Task is waiting for the device to cool down or to be charging.
+    DeviceCondition,
+}
+
+impl WaitingCause {
+    /// Maps the reason stored for a waiting task to its waiting cause.
+    ///
+    /// Reasons combining several conditions map to the first of network, app
+    /// state and user state. Returns `None` for reasons a waiting task should
+    /// not have.
+    pub(crate) fn from_reason(reason: u8) -> Option<Self> {
+        match Reason::from(reason) {
+            Reason::RunningTaskMeetLimits => Some(WaitingCause::TaskQueue),
+            Reason::NetworkOffline
+            | Reason::UnsupportedNetworkType
+            | Reason::NetworkApp
+            | Reason::NetworkAccount
+            | Reason::NetworkAppAccount => Some(WaitingCause::Network),
+            Reason::AppBackgroundOrTerminate | Reason::AppAccount => Some(WaitingCause::AppState),
+            Reason::AccountStopped => Some(WaitingCause::UserState),
+            Reason::RateLimited => Some(WaitingCause::RateLimit),
+            Reason::NotCharging => Some(WaitingCause::Battery),
+            Reason::CaptivePortal => Some(WaitingCause::CaptivePortal),
+            Reason::DeviceCondition => Some(WaitingCause::DeviceCondition),
+            _ => None,
+        }
+    }
 }
 
 /// Contains task notification data sent to subscribers.
@@ -71,6 +104,13 @@ pub(crate) struct NotifyData {
     pub(crate) task_id: u32,
     /// User ID that owns the task.
     pub(crate) uid: u64,
+    /// Captured upload response body, only set on completion.
+    pub(crate) response_body: Option<ResponseBody>,
+    /// Estimated remaining time in milliseconds, only set for running tasks
+    /// of known total size.
+    pub(crate) eta_ms: Option<u64>,
+    /// Client tag of the task, empty if none.
+    pub(crate) client_tag: String,
 }
 
 /// Core progress information shared across different components.
@@ -99,6 +139,15 @@ pub(crate) struct Progress {
     pub(crate) extras: HashMap<String, String>,
 }
 
+/// Beginning of an upload response body kept for the client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ResponseBody {
+    /// Body bytes, at most `TaskConfig::response_body_limit` long.
+    pub(crate) data: Vec<u8>,
+    /// Whether the server sent more than `data` holds.
+    pub(crate) truncated: bool,
+}
+
 /// Status information for an individual file in a multi-file task.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[repr(C)]
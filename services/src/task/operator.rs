@@ -16,23 +16,19 @@
 //! This module provides core functionality for managing task operations including
 //! progress tracking, notifications, and file writing operations.
 
-use std::cmp::min;
-use std::io::Write;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use ylong_http_client::HttpClientError;
 
-use crate::manage::notifier::Notifier;
-use crate::service::notification_bar::{NotificationDispatcher, NOTIFY_PROGRESS_INTERVAL};
 use crate::task::request_task::RequestTask;
+use crate::task::size_limit::SIZE_LIMIT_EXCEEDED;
 use crate::task::speed_limiter::SpeedLimiter;
+#[cfg(feature = "spans")]
+use crate::trace::span;
 use crate::utils::get_current_timestamp;
 
-/// Interval in milliseconds for frontend progress notifications.
-const FRONT_NOTIFY_INTERVAL: u64 = 1000;
-
 /// Task operator that handles task execution operations.
 /// 
 /// This struct manages the execution of download and upload tasks,
@@ -61,10 +57,10 @@ impl TaskOperator {
         }
     }
 
-    /// Polls for common progress updates and handles notifications.
+    /// Polls for task abortion and the speed limit of the task.
     /// 
-    /// This method checks for task abortion, sends progress notifications at appropriate
-    /// intervals, and applies speed limiting.
+    /// Progress notifications are sent by the progress sampler of the
+    /// running task rather than per polled chunk.
     /// 
     /// # Arguments
     /// 
@@ -84,77 +80,44 @@ impl TaskOperator {
             return Poll::Ready(Err(HttpClientError::user_aborted()));
         }
         
-        let current = get_current_timestamp();
-
-        // Check if it's time to send frontend notification
-        let next_notify_time = self.task.last_notify.load(Ordering::SeqCst) + FRONT_NOTIFY_INTERVAL;
-
-        if current >= next_notify_time {
-            // Build and send notification data
-            let notify_data = self.task.build_notify_data();
-            self.task.last_notify.store(current, Ordering::SeqCst);
-            Notifier::progress(&self.task.client_manager, notify_data);
-        }
-
-        // Check if background notification should be sent
-        if self.task.background_notify.load(Ordering::Acquire)
-            && current
-                > self.task.background_notify_time.load(Ordering::SeqCst) + NOTIFY_PROGRESS_INTERVAL
-        {
-            self.task
-                .background_notify_time
-                .store(current, Ordering::SeqCst);
-            NotificationDispatcher::get_instance().publish_progress_notification(&self.task);
-        }
-
-        // Apply speed limiting
-        let total_processed = self
-            .task
-            .progress
-            .lock()
-            .unwrap()
-            .common_data
-            .total_processed as u64;
-
-        let rate_limiting = self.task.rate_limiting.load(Ordering::SeqCst);
-        let max_speed = self.task.max_speed.load(Ordering::SeqCst) as u64;
-
-        // Determine effective speed limit based on configured values
-        let speed_limit = match (rate_limiting, max_speed) {
-            (0, max_speed) => max_speed,       // Only max_speed is set
-            (rate_limiting, 0) => rate_limiting, // Only rate_limiting is set
-            (rate_limiting, max_speed) => min(rate_limiting, max_speed), // Use the lower value
-        };
-
+        // Notifications and database writes follow the progress sampler,
+        // only limiting the speed needs the processed bytes per chunk.
+        let speed_limit = self.task.effective_speed_limit();
         self.speed_limiter.update_speed_limit(speed_limit);
+        if speed_limit == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        let total_processed = self.task.processed_bytes() as u64;
         self.speed_limiter
-            .poll_check_limit(cx, current, total_processed)
+            .poll_check_limit(cx, get_current_timestamp(), total_processed)
     }
 
     /// Polls for file writing operations.
     /// 
-    /// This method writes data to the first file associated with the task
-    /// and updates progress tracking information.
+    /// This method queues data to be written to the first file associated
+    /// with the task and counts it for the progress sampler.
     /// 
     /// # Arguments
     /// 
-    /// * `_cx` - The task context (currently unused).
+    /// * `cx` - The task context, woken once the write buffer drains.
     /// * `data` - The data to write to the file.
     /// * `skip_size` - Size to add to the reported written size (for resume operations).
     /// 
     /// # Returns
     /// 
     /// - `Poll::Ready(Ok(usize))` with the total bytes written (including skip_size).
+    /// - `Poll::Pending` while the write buffer of the task or the service is full.
     /// - `Poll::Ready(Err(HttpClientError))` if an error occurs.
     /// 
     /// # Errors
     /// 
     /// - Returns an error if no files are associated with the task.
     /// - Returns an error if the task was aborted.
-    /// - Returns an error if writing to the file fails.
+    /// - Returns an error if the data would exceed the size cap of the task.
+    /// - Returns an error if a previous write to the file failed.
     pub(crate) fn poll_write_file(
         &self,
-        _cx: &mut Context<'_>,
+        cx: &mut Context<'_>,
         data: &[u8],
         skip_size: usize,
     ) -> Poll<Result<usize, HttpClientError>> {
@@ -165,24 +128,32 @@ impl TaskOperator {
             error!("poll_write_file err, no file in the `task`");
             return Poll::Ready(Err(HttpClientError::other("error msg")));
         };
-        
-        let mut file = file_mutex.lock().unwrap();
 
         // Check for task abortion before writing
         if self.abort_flag.load(Ordering::Acquire) {
             return Poll::Ready(Err(HttpClientError::user_aborted()));
         }
-        
-        // Perform the write operation
-        match file.write(data) {
-            Ok(size) => {
-                // Update progress tracking
-                let mut progress_guard = self.task.progress.lock().unwrap();
-                progress_guard.processed[0] += size;
-                progress_guard.common_data.total_processed += size;
-                Poll::Ready(Ok(size + skip_size))
+
+        // Cut off downloads outgrowing their size cap
+        if self.task.conf.download_size_cap().is_some() {
+            let progress = self.task.progress.lock().unwrap();
+            let written = progress.processed[0] + self.task.counter.pending();
+            if self.task.exceeds_size_cap((written + data.len()) as u64) {
+                return Poll::Ready(Err(HttpClientError::other(SIZE_LIMIT_EXCEEDED)));
+            }
+        }
+
+        // Queue the data, the buffer writes it on the blocking pool
+        match self.task.writes.poll_write(cx, file_mutex, data) {
+            Poll::Ready(Ok(())) => {
+                // Count the bytes, the progress sampler moves them into the progress
+                self.task.counter.add(data.len());
+                #[cfg(feature = "spans")]
+                span::end(self.task.task_id(), "first_byte", &[]);
+                Poll::Ready(Ok(data.len() + skip_size))
             }
-            Err(e) => Poll::Ready(Err(HttpClientError::other(e))),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(HttpClientError::other(e))),
+            Poll::Pending => Poll::Pending,
         }
     }
 }
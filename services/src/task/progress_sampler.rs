@@ -0,0 +1,189 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Timer-driven sampling of the progress of running tasks.
+//!
+//! Writing a downloaded chunk only adds its length to a [`ProgressCounter`].
+//! While the task runs, a sampler wakes up every [`SAMPLE_INTERVAL`], takes
+//! the progress lock once to move the counted bytes into the [`Progress`] of
+//! the task, and then feeds the remaining time estimate, the batched database
+//! writes and the progress notifications. Reaching a terminal state syncs the
+//! counter at once, so final totals never wait for the next sample.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ylong_runtime::task::JoinHandle;
+
+use super::notify::Progress;
+use crate::manage::notifier::Notifier;
+use crate::manage::progress_writer::SNAPSHOT_INTERVAL_MS;
+use crate::service::notification_bar::{NotificationDispatcher, NOTIFY_PROGRESS_INTERVAL};
+use crate::task::request_task::RequestTask;
+use crate::utils::{get_current_timestamp, runtime_spawn};
+
+/// Time between two progress samples of a running task.
+pub(crate) const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Interval in milliseconds for frontend progress notifications.
+const FRONT_NOTIFY_INTERVAL: u64 = 1000;
+
+/// Bytes processed by a task but not yet moved into its progress.
+#[derive(Debug, Default)]
+pub(crate) struct ProgressCounter {
+    /// Bytes counted since the last materialization.
+    pending: AtomicUsize,
+    /// Number of times the progress lock was taken to materialize.
+    locks: AtomicUsize,
+}
+
+impl ProgressCounter {
+    /// Counts `bytes` processed for the first file of the task.
+    pub(crate) fn add(&self, bytes: usize) {
+        self.pending.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Returns the bytes counted since the last materialization.
+    pub(crate) fn pending(&self) -> usize {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// Takes the counted bytes, leaving the counter empty.
+    ///
+    /// Resetting the progress takes the counted bytes while holding its lock,
+    /// so bytes of a discarded attempt are not added to the new one.
+    pub(crate) fn take(&self) -> usize {
+        self.pending.swap(0, Ordering::Relaxed)
+    }
+
+    /// Moves the counted bytes into `progress`, whose lock the caller holds.
+    pub(crate) fn materialize(&self, progress: &mut Progress) {
+        self.locks.fetch_add(1, Ordering::Relaxed);
+        let bytes = self.take();
+        if let Some(processed) = progress.processed.get_mut(0) {
+            *processed += bytes;
+        }
+        progress.common_data.total_processed += bytes;
+    }
+
+    /// Moves the counted bytes into `progress`, taking its lock only if any
+    /// bytes were counted.
+    pub(crate) fn sync(&self, progress: &Mutex<Progress>) {
+        if self.pending() == 0 {
+            return;
+        }
+        // `unwrap` for propagating panics among threads.
+        self.materialize(&mut progress.lock().unwrap());
+    }
+
+    /// Returns how many times the progress lock was taken to materialize.
+    #[cfg(test)]
+    pub(crate) fn locks(&self) -> usize {
+        self.locks.load(Ordering::Relaxed)
+    }
+}
+
+impl RequestTask {
+    /// Moves the bytes counted while running into the progress of the task.
+    pub(crate) fn sync_progress(&self) {
+        self.counter.sync(&self.progress);
+    }
+
+    /// Returns the total bytes processed, including those not yet sampled.
+    pub(crate) fn processed_bytes(&self) -> usize {
+        let progress = self.progress.lock().unwrap();
+        progress.common_data.total_processed + self.counter.pending()
+    }
+}
+
+/// Sampler of a running task, stopped when dropped.
+pub(crate) struct SamplerGuard {
+    handle: JoinHandle<()>,
+}
+
+impl SamplerGuard {
+    /// Starts sampling the progress of `task` every [`SAMPLE_INTERVAL`].
+    pub(crate) fn spawn(task: Arc<RequestTask>) -> Self {
+        Self {
+            handle: runtime_spawn(run(task)),
+        }
+    }
+}
+
+impl Drop for SamplerGuard {
+    fn drop(&mut self) {
+        self.handle.cancel();
+    }
+}
+
+async fn run(task: Arc<RequestTask>) {
+    let mut sampler = Sampler::default();
+    loop {
+        ylong_runtime::time::sleep(SAMPLE_INTERVAL).await;
+        sampler.sample(&task, get_current_timestamp());
+    }
+}
+
+#[derive(Default)]
+struct Sampler {
+    /// Time the progress was last queued for the database.
+    last_snapshot: u64,
+    /// Total processed bytes at the previous sample.
+    last_total: Option<usize>,
+}
+
+impl Sampler {
+    fn sample(&mut self, task: &RequestTask, now: u64) {
+        let total = {
+            let mut progress = task.progress.lock().unwrap();
+            task.counter.materialize(&mut progress);
+            progress.common_data.total_processed
+        };
+        // Stalls count as zero throughput for the estimate and the history.
+        task.eta.lock().unwrap().update(now, total as u64);
+        task.speed_history.lock().unwrap().record(now, total as u64);
+
+        // Like chunks used to, only progress made triggers the updates below.
+        if self.last_total == Some(total) {
+            return;
+        }
+        self.last_total = Some(total);
+
+        // Queue the progress for the next batched database write
+        if now >= self.last_snapshot + SNAPSHOT_INTERVAL_MS {
+            self.last_snapshot = now;
+            task.queue_progress_update();
+        }
+
+        // Check if it's time to send frontend notification
+        if now >= task.last_notify.load(Ordering::SeqCst) + FRONT_NOTIFY_INTERVAL {
+            let notify_data = task.build_notify_data();
+            task.last_notify.store(now, Ordering::SeqCst);
+            Notifier::progress(&task.client_manager, notify_data);
+        }
+
+        // Check if background notification should be sent
+        if task.background_notify.load(Ordering::Acquire)
+            && now > task.background_notify_time.load(Ordering::SeqCst) + NOTIFY_PROGRESS_INTERVAL
+        {
+            task.background_notify_time.store(now, Ordering::SeqCst);
+            NotificationDispatcher::get_instance().publish_progress_notification(task);
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_progress_sampler {
+    include!("../../tests/ut/task/ut_progress_sampler.rs");
+}
@@ -80,6 +80,28 @@ mod ffi {
         NetworkAppAccount = 30,
         /// Transfer speed below configured minimum threshold.
         LowSpeed = 31,
+        /// Server asked the client to retry later.
+        RateLimited = 32,
+        /// The completed file could not be synced to disk or checked.
+        DurabilityCheckFailed = 33,
+        /// The response content type is not one the task expects.
+        UnexpectedContentType = 34,
+        /// The upload precondition of the task does not hold on the server.
+        PreconditionFailed = 35,
+        /// Background tasks wait for the device to be charging.
+        NotCharging = 36,
+        /// The retry deadline of the task passed before it succeeded.
+        RetryDeadlineExceeded = 37,
+        /// A file of the task is larger than its configured size cap.
+        SizeLimitExceeded = 38,
+        /// The network intercepts traffic with a sign-in page.
+        CaptivePortal = 39,
+        /// The network the task is bound to is unavailable.
+        BoundNetworkUnavailable = 40,
+        /// A request followed more redirects to another host than allowed.
+        CrossHostRedirectLimit = 41,
+        /// The device is too hot or not charging for the task to run.
+        DeviceCondition = 42,
     }
 }
 
@@ -121,6 +143,17 @@ impl From<u8> for Reason {
             29 => Reason::AppAccount,
             30 => Reason::NetworkAppAccount,
             31 => Reason::LowSpeed,
+            32 => Reason::RateLimited,
+            33 => Reason::DurabilityCheckFailed,
+            34 => Reason::UnexpectedContentType,
+            35 => Reason::PreconditionFailed,
+            36 => Reason::NotCharging,
+            37 => Reason::RetryDeadlineExceeded,
+            38 => Reason::SizeLimitExceeded,
+            39 => Reason::CaptivePortal,
+            40 => Reason::BoundNetworkUnavailable,
+            41 => Reason::CrossHostRedirectLimit,
+            42 => Reason::DeviceCondition,
             _ => Reason::OthersError, // Fallback for unrecognized values
         }
     }
@@ -161,6 +194,17 @@ impl Reason {
             Reason::AppAccount => "The app is background or terminate and the account is stopped",
             Reason::NetworkAppAccount => "NetWork is offline and the app is background or terminate and the account is stopped",
             Reason::LowSpeed => "Below low speed limit",
+            Reason::RateLimited => "Rate limited by the server",
+            Reason::DurabilityCheckFailed => "Failed to sync the file to disk",
+            Reason::UnexpectedContentType => "Unexpected content type",
+            Reason::PreconditionFailed => "Upload precondition failed",
+            Reason::NotCharging => "The device is not charging",
+            Reason::RetryDeadlineExceeded => "Retry deadline exceeded",
+            Reason::SizeLimitExceeded => "Size limit exceeded",
+            Reason::CaptivePortal => "The network requires signing in",
+            Reason::BoundNetworkUnavailable => "The bound network is unavailable",
+            Reason::CrossHostRedirectLimit => "Too many redirects to another host",
+            Reason::DeviceCondition => "The device is too hot or not charging",
             _ => "unknown error",
         }
     }
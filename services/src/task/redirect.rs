@@ -0,0 +1,282 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redirect chains of tasks.
+//!
+//! Tasks configured with `report_redirects` get a [`RedirectRecorder`]
+//! interceptor recording every redirect their requests follow. Once the
+//! response arrives the hops are sent to the client in the order they were
+//! followed, and the URL the response came from is recorded under
+//! `final_url` in the progress `extras` of the task.
+//!
+//! Tasks configured with `max_cross_host_redirects` get a [`CrossHostLimit`]
+//! interceptor failing a request once its redirects changed host more often
+//! than allowed, whatever the number of redirects within the same host.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use ylong_http_client::async_impl::{Request, Response};
+use ylong_http_client::{HttpClientError, Interceptor};
+
+use crate::task::request_task::RequestTask;
+
+/// Key in `extras` holding the URL the response of a task came from.
+pub(crate) const FINAL_URL: &str = "final_url";
+
+/// Message of the error failing a redirect beyond the cross-host cap.
+pub(crate) const CROSS_HOST_LIMIT_EXCEEDED: &str = "cross-host redirect limit exceeded";
+
+/// Redirects followed by each task and not reported yet.
+static CHAINS: Mutex<Option<HashMap<u32, Chain>>> = Mutex::new(None);
+
+/// Current host and host changes of the redirects each task is following.
+static HOST_CHANGES: Mutex<Option<HashMap<u32, (String, u32)>>> = Mutex::new(None);
+
+/// A redirect followed by a task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RedirectHop {
+    /// URL that answered with the redirect.
+    pub(crate) from_url: String,
+    /// URL the redirect pointed to.
+    pub(crate) to_url: String,
+    /// Status code of the redirect response.
+    pub(crate) status: u32,
+}
+
+#[derive(Default)]
+struct Chain {
+    /// Status of the last redirect response, used by the following hop.
+    status: u32,
+    hops: Vec<RedirectHop>,
+}
+
+/// Records the status of a redirect response of a task.
+pub(crate) fn record_status(task_id: u32, status: u32) {
+    let mut chains = CHAINS.lock().unwrap();
+    let chain = chains
+        .get_or_insert_with(HashMap::new)
+        .entry(task_id)
+        .or_default();
+    chain.status = status;
+}
+
+/// Records a redirect of a task to `to_url`.
+///
+/// The hop starts from the URL the previous hop pointed to, or from
+/// `origin` for the first one.
+pub(crate) fn record_hop(task_id: u32, origin: &str, to_url: String) {
+    let mut chains = CHAINS.lock().unwrap();
+    let chain = chains
+        .get_or_insert_with(HashMap::new)
+        .entry(task_id)
+        .or_default();
+    let from_url = match chain.hops.last() {
+        Some(hop) => hop.to_url.clone(),
+        None => origin.to_string(),
+    };
+    let status = std::mem::take(&mut chain.status);
+    chain.hops.push(RedirectHop {
+        from_url,
+        to_url,
+        status,
+    });
+}
+
+/// Takes the redirects followed by a task since they were last taken.
+pub(crate) fn take_hops(task_id: u32) -> Vec<RedirectHop> {
+    CHAINS
+        .lock()
+        .unwrap()
+        .as_mut()
+        .and_then(|chains| chains.remove(&task_id))
+        .map(|chain| chain.hops)
+        .unwrap_or_default()
+}
+
+/// Interceptor recording the redirects followed by a task.
+///
+/// Wraps the interceptor the client would have had otherwise, which still
+/// checks each redirect before it is recorded.
+pub(crate) struct RedirectRecorder {
+    task_id: u32,
+    /// URL the requests of the task are sent to.
+    origin: String,
+    inner: Option<Box<dyn Interceptor + Send + Sync>>,
+}
+
+impl RedirectRecorder {
+    pub(crate) fn new(
+        task_id: u32,
+        origin: &str,
+        inner: Option<Box<dyn Interceptor + Send + Sync>>,
+    ) -> Self {
+        Self {
+            task_id,
+            origin: origin.to_string(),
+            inner,
+        }
+    }
+}
+
+impl Interceptor for RedirectRecorder {
+    fn intercept_redirect_response(&self, response: &Response) -> Result<(), HttpClientError> {
+        if let Some(inner) = self.inner.as_ref() {
+            inner.intercept_redirect_response(response)?;
+        }
+        record_status(self.task_id, response.status().as_u16() as u32);
+        Ok(())
+    }
+
+    fn intercept_redirect_request(&self, request: &Request) -> Result<(), HttpClientError> {
+        if let Some(inner) = self.inner.as_ref() {
+            inner.intercept_redirect_request(request)?;
+        }
+        record_hop(self.task_id, &self.origin, request.uri().to_string());
+        Ok(())
+    }
+}
+
+impl Drop for RedirectRecorder {
+    fn drop(&mut self) {
+        take_hops(self.task_id);
+    }
+}
+
+/// Returns the host of `url` in lower case, with its port if any.
+fn host_of(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    host.to_ascii_lowercase()
+}
+
+/// Counts a redirect of a task to `url`, returning how often the redirects
+/// changed host since the chain started at `origin`.
+pub(crate) fn count_host_change(task_id: u32, origin: &str, url: &str) -> u32 {
+    let host = host_of(url);
+    let mut changes = HOST_CHANGES.lock().unwrap();
+    let (current, count) = changes
+        .get_or_insert_with(HashMap::new)
+        .entry(task_id)
+        .or_insert_with(|| (host_of(origin), 0));
+    if *current != host {
+        *current = host;
+        *count += 1;
+    }
+    *count
+}
+
+/// Forgets the host changes of a task, the next redirect starts a new chain.
+pub(crate) fn reset_host_changes(task_id: u32) {
+    if let Some(changes) = HOST_CHANGES.lock().unwrap().as_mut() {
+        changes.remove(&task_id);
+    }
+}
+
+/// Interceptor failing the redirects of a task beyond its cross-host cap.
+///
+/// Wraps the interceptor the client would have had otherwise, which still
+/// checks each redirect before it is counted.
+pub(crate) struct CrossHostLimit {
+    task_id: u32,
+    /// URL the requests of the task are sent to.
+    origin: String,
+    max: u32,
+    inner: Option<Box<dyn Interceptor + Send + Sync>>,
+}
+
+impl CrossHostLimit {
+    pub(crate) fn new(
+        task_id: u32,
+        origin: &str,
+        max: u32,
+        inner: Option<Box<dyn Interceptor + Send + Sync>>,
+    ) -> Self {
+        Self {
+            task_id,
+            origin: origin.to_string(),
+            max,
+            inner,
+        }
+    }
+}
+
+impl Interceptor for CrossHostLimit {
+    fn intercept_redirect_response(&self, response: &Response) -> Result<(), HttpClientError> {
+        match self.inner.as_ref() {
+            Some(inner) => inner.intercept_redirect_response(response),
+            None => Ok(()),
+        }
+    }
+
+    fn intercept_redirect_request(&self, request: &Request) -> Result<(), HttpClientError> {
+        if let Some(inner) = self.inner.as_ref() {
+            inner.intercept_redirect_request(request)?;
+        }
+        let url = request.uri().to_string();
+        let changes = count_host_change(self.task_id, &self.origin, &url);
+        if changes > self.max {
+            error!(
+                "task {} redirect to {} changes host {} times, cap {}",
+                self.task_id, url, changes, self.max
+            );
+            return Err(HttpClientError::other(CROSS_HOST_LIMIT_EXCEEDED));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for CrossHostLimit {
+    fn drop(&mut self) {
+        reset_host_changes(self.task_id);
+    }
+}
+
+impl RequestTask {
+    /// Reports the redirects followed since the last report to the client.
+    ///
+    /// # Returns
+    ///
+    /// The URL the response came from for tasks configured with
+    /// `report_redirects`, `None` for the others.
+    pub(crate) fn report_redirects(&self) -> Option<String> {
+        if !self.conf.report_redirects {
+            return None;
+        }
+        let task_id = self.task_id();
+        let hops = take_hops(task_id);
+        let final_url = match hops.last() {
+            Some(hop) => hop.to_url.clone(),
+            None => self.conf.request_url().to_string(),
+        };
+        for hop in hops {
+            task_debug!(
+                self.log,
+                "redirected {} {} -> {}",
+                hop.status,
+                hop.from_url,
+                hop.to_url
+            );
+            self.client_manager.send_redirect(task_id, hop);
+        }
+        Some(final_url)
+    }
+}
+
+#[cfg(test)]
+mod ut_redirect {
+    include!("../../tests/ut/task/ut_redirect.rs");
+}
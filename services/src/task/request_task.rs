@@ -18,44 +18,76 @@
 //! It defines the main `RequestTask` structure and associated components for
 //! controlling the lifecycle of network operations.
 
+use std::collections::HashMap;
 use std::io::{self};
 use std::sync::atomic::{
-    AtomicBool, AtomicI64, AtomicU32, AtomicU64, AtomicU8, Ordering,
+    AtomicBool, AtomicI64, AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering,
 };
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use request_utils::file_control::{belong_app_base, check_standardized_path};
-use ylong_http_client::async_impl::{Body, Client, Request, RequestBuilder, Response};
+use ylong_http_client::async_impl::{Body, Request, RequestBuilder, Response};
+#[cfg(feature = "oh")]
+use ylong_http_client::Headers;
 use ylong_http_client::{ErrorKind, HttpClientError};
 
 cfg_oh! {
     use crate::manage::SystemConfig;
 }
 
+use super::accept_encoding;
 use super::config::Version;
+use super::debug_log::{format_headers, TaskLog};
+use super::eta::EtaEstimator;
 use super::info::{CommonTaskInfo, State, TaskInfo, UpdateInfo};
-use super::notify::{EachFileStatus, NotifyData, Progress};
+use super::notify::{EachFileStatus, NotifyData, Progress, ResponseBody};
+use super::progress_sampler::ProgressCounter;
 use super::reason::Reason;
+use super::retry_after::{is_rate_limited, retry_after_delay};
+use super::speed_history::SpeedHistory;
 use crate::error::ErrorCode;
 use crate::manage::database::RequestDb;
+use crate::manage::network::NetworkState;
 use crate::manage::network_manager::NetworkManager;
 use crate::manage::notifier::Notifier;
+use crate::manage::progress_writer::ProgressWriter;
 use crate::service::client::ClientManagerEntry;
 use crate::service::notification_bar::NotificationDispatcher;
-use crate::task::client::build_client;
-use crate::task::config::{Action, TaskConfig};
+use crate::task::client::{build_client, tls_versions};
+use crate::task::config::{
+    is_http_token, Action, IpPreference, ResumeStrategy, TaskConfig, MAX_CLIENT_TAG_LEN,
+};
 use crate::task::files::{AttachedFiles, Files};
+use crate::task::ip_preference::{has_ipv6, UsedFamily};
+use crate::task::keep_alive::{self, KeepAlivePool, PoolKey, SharedClient};
+use crate::task::loopback::LoopbackSpec;
+use crate::task::net_binding;
+use crate::task::redirect::{CROSS_HOST_LIMIT_EXCEEDED, FINAL_URL};
+use crate::task::resume;
 use crate::task::task_control;
-use crate::utils::form_item::FileSpec;
+use crate::task::trace_context;
+use crate::task::write_buffer::{BufferBudget, WriteBuffer};
+use crate::utils::form_item::{form_urlencode, FileSpec};
 use crate::utils::{get_current_duration, get_current_timestamp};
 
 /// Maximum number of network retry attempts.
-const RETRY_TIMES: u32 = 4;
+pub(crate) const RETRY_TIMES: u32 = 4;
 
 /// Interval between retry attempts in milliseconds.
 const RETRY_INTERVAL: u64 = 400;
 
+/// Key in `extras` holding the meteredness used for an overriding task.
+pub(crate) const EFFECTIVE_METERED: &str = "effective_metered";
+
+/// Default maximum number of response headers processed by a task.
+pub(crate) const DEFAULT_MAX_RESPONSE_HEADERS: usize = 100;
+
+/// Maximum number of response headers processed by a task, the headers
+/// beyond it are dropped.
+pub(crate) static MAX_RESPONSE_HEADERS: AtomicUsize =
+    AtomicUsize::new(DEFAULT_MAX_RESPONSE_HEADERS);
+
 /// Represents an HTTP request task.
 ///
 /// This struct encapsulates all the information and state needed to execute and manage
@@ -65,8 +97,15 @@ pub(crate) struct RequestTask {
     /// Task configuration containing request parameters, headers, and metadata.
     pub(crate) conf: TaskConfig,
     
-    /// HTTP client used to execute the request.
-    pub(crate) client: ylong_runtime::sync::Mutex<Client>,
+    /// HTTP client used to execute the request, possibly taken over from an
+    /// earlier task.
+    pub(crate) client: SharedClient,
+
+    /// Address family the client connected to, set by its resolver.
+    pub(crate) used_family: UsedFamily,
+
+    /// Key of the tasks that may take over the client, `None` if it is not shared.
+    pub(crate) pool_key: Option<PoolKey>,
     
     /// Files associated with the task (for download or upload operations).
     pub(crate) files: Files,
@@ -118,9 +157,19 @@ pub(crate) struct RequestTask {
     
     /// Number of timeout attempts.
     pub(crate) timeout_tries: AtomicU32,
+
+    /// Number of transient TLS handshake failures retried.
+    pub(crate) handshake_retries: AtomicU32,
+
+    /// Delay in milliseconds a rate limiting server asked the task to wait
+    /// before its next run.
+    pub(crate) retry_after: AtomicU64,
     
     /// Flag indicating whether upload resume is enabled.
     pub(crate) upload_resume: AtomicBool,
+
+    /// Flag asking a running upload to end before its next file.
+    pub(crate) soft_stop: AtomicBool,
     
     /// Task mode representation.
     pub(crate) mode: AtomicU8,
@@ -133,6 +182,27 @@ pub(crate) struct RequestTask {
     
     /// Remaining time until task timeout.
     pub(crate) rest_time: AtomicU64,
+    
+    /// Beginning of the last upload response body.
+    pub(crate) response_body: Mutex<Option<ResponseBody>>,
+    
+    /// Throughput average used to estimate the remaining time.
+    pub(crate) eta: Mutex<EtaEstimator>,
+    
+    /// Recent processed bytes samples for graphing the speed.
+    pub(crate) speed_history: Mutex<SpeedHistory>,
+
+    /// Path the download was renamed to after its `Content-Disposition`.
+    pub(crate) disposition_path: Mutex<Option<String>>,
+
+    /// Debug log context, registering the task if it logs in detail.
+    pub(crate) log: TaskLog,
+
+    /// Downloaded chunks waiting to be written to the file.
+    pub(crate) writes: WriteBuffer,
+
+    /// Bytes written while running, moved into `progress` when sampled.
+    pub(crate) counter: ProgressCounter,
 }
 
 impl RequestTask {
@@ -176,6 +246,40 @@ impl RequestTask {
         }
     }
 
+    /// Returns the speed limit currently applied to the task.
+    /// 
+    /// Combines the QoS tier limit with the user-set `max_speed`, either of
+    /// which may be 0 for unlimited.
+    /// 
+    /// # Returns
+    /// 
+    /// The lower of the two limits in bytes per second, or 0 if neither is set.
+    pub(crate) fn effective_speed_limit(&self) -> u64 {
+        let rate_limiting = self.rate_limiting.load(Ordering::SeqCst);
+        let max_speed = self.max_speed.load(Ordering::SeqCst).max(0) as u64;
+        match (rate_limiting, max_speed) {
+            (0, max_speed) => max_speed,
+            (rate_limiting, 0) => rate_limiting,
+            (rate_limiting, max_speed) => rate_limiting.min(max_speed),
+        }
+    }
+
+    /// Records whether the current network counts as metered for the task.
+    ///
+    /// Only tasks overriding the reported metered flag record it, under
+    /// `effective_metered` in `extras`.
+    pub(crate) fn record_effective_metered(&self, extras: &mut HashMap<String, String>) {
+        if self.conf.treat_network_as.is_none() {
+            return;
+        }
+        if let NetworkState::Online(info) = NetworkManager::query_network() {
+            extras.insert(
+                EFFECTIVE_METERED.to_string(),
+                self.conf.is_metered(&info).to_string(),
+            );
+        }
+    }
+
     /// Attempts to retry the task after a network error.
     /// 
     /// # Returns
@@ -183,19 +287,126 @@ impl RequestTask {
     /// * `Ok(())` if the retry limit has been reached.
     /// * `Err(TaskError::Waiting(TaskPhase::NetworkOffline))` if the network is offline.
     /// * `Err(TaskError::Waiting(TaskPhase::NeedRetry))` if a retry should be attempted after a delay.
+    /// * `Err(TaskError::Failed(Reason::RetryDeadlineExceeded))` if the retry deadline passed.
     pub(crate) async fn network_retry(&self) -> Result<(), TaskError> {
-        if self.tries.load(Ordering::SeqCst) < RETRY_TIMES {
+        self.check_retry_deadline()?;
+        let tries = self.tries.load(Ordering::SeqCst);
+        if tries < RETRY_TIMES {
             self.tries.fetch_add(1, Ordering::SeqCst);
             if !NetworkManager::is_online() {
+                task_debug!(self.log, "network offline, wait for network");
                 return Err(TaskError::Waiting(TaskPhase::NetworkOffline));
             } else {
+                task_debug!(
+                    self.log,
+                    "network error, retry {} of {}",
+                    tries + 1,
+                    RETRY_TIMES
+                );
                 // Wait before retrying to avoid overwhelming the network
                 ylong_runtime::time::sleep(Duration::from_millis(RETRY_INTERVAL)).await;
                 return Err(TaskError::Waiting(TaskPhase::NeedRetry));
             }
         }
+        task_debug!(self.log, "network error, {} retries used", tries);
+        Ok(())
+    }
+
+    /// Fails the task with `Reason::RetryDeadlineExceeded` if its retry
+    /// deadline passed, whatever retries are left.
+    pub(crate) fn check_retry_deadline(&self) -> Result<(), TaskError> {
+        if self.conf.retry_deadline_passed(get_current_timestamp()) {
+            task_debug!(self.log, "retry deadline passed");
+            return Err(TaskError::Failed(Reason::RetryDeadlineExceeded));
+        }
+        Ok(())
+    }
+
+    /// Returns the delay a rate limiting response asks the task to wait.
+    ///
+    /// `None` if the status is neither 429 nor 503, the response carries no
+    /// valid `Retry-After` or the task fails rate limited requests.
+    pub(crate) fn rate_limit_delay(&self, response: &Response) -> Option<Duration> {
+        if self.conf.retry_after_cap_ms == 0 || !is_rate_limited(response.status().as_u16()) {
+            return None;
+        }
+        let value = response.headers().get("retry-after")?.to_string().ok()?;
+        let cap = Duration::from_millis(self.conf.retry_after_cap_ms);
+        retry_after_delay(&value, SystemTime::now(), cap)
+    }
+
+    /// Returns the headers of a response, at most `MAX_RESPONSE_HEADERS` of
+    /// them.
+    ///
+    /// Headers beyond the cap are dropped, so a server sending thousands of
+    /// headers does not blow up the progress of the task.
+    pub(crate) fn response_headers(&self, response: &Response) -> Vec<(String, String)> {
+        let max = MAX_RESPONSE_HEADERS.load(Ordering::Relaxed);
+        let mut iter = response.headers().into_iter();
+        let headers = iter
+            .by_ref()
+            .take(max)
+            .filter_map(|(k, v)| Some((k.to_string(), v.to_string().ok()?)))
+            .collect();
+        if iter.next().is_some() {
+            warn!(
+                "task {} response headers beyond {} dropped",
+                self.task_id(),
+                max
+            );
+        }
+        headers
+    }
+
+    /// Logs the status and headers of a response if the task logs in detail.
+    pub(crate) fn debug_response(&self, response: &Response) {
+        if !self.log.enabled() {
+            return;
+        }
+        let headers = self.response_headers(response);
+        task_debug!(
+            self.log,
+            "response {}, headers {}",
+            response.status(),
+            format_headers(headers.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+        );
+    }
+
+    /// Checks whether the task may wait for the delay a rate limiting server
+    /// asked for.
+    ///
+    /// The wait counts against the total timeout and the retry deadline of
+    /// the task, it fails if either runs out before the delay elapsed.
+    pub(crate) fn check_rate_limit(&self, delay: Duration) -> Result<(), TaskError> {
+        let resume_at = get_current_timestamp() + delay.as_millis() as u64;
+        if self.conf.retry_deadline_passed(resume_at) {
+            task_debug!(self.log, "retry deadline passed while rate limited");
+            return Err(TaskError::Failed(Reason::RetryDeadlineExceeded));
+        }
+        if delay.as_secs() >= self.rest_time.load(Ordering::SeqCst) {
+            task_debug!(self.log, "total timeout passed while rate limited");
+            return Err(TaskError::Failed(Reason::RateLimited));
+        }
         Ok(())
     }
+
+    /// Ends the run of the task for the delay a rate limiting server asked
+    /// for.
+    ///
+    /// The task gives up its running slot, the scheduler runs it again once
+    /// the delay has elapsed.
+    pub(crate) fn end_rate_limited(&self, delay: Duration) {
+        info!(
+            "task {} rate limited, retry after {} ms",
+            self.task_id(),
+            delay.as_millis()
+        );
+        // The wait counts against the total timeout of the task
+        self.task_time.fetch_add(delay.as_secs(), Ordering::SeqCst);
+        self.retry_after
+            .store(delay.as_millis() as u64, Ordering::SeqCst);
+        *self.running_result.lock().unwrap() = Some(Err(Reason::RateLimited));
+    }
 }
 
 /// Calculates the effective size of a range for upload operations.
@@ -232,35 +443,47 @@ impl RequestTask {
     /// * `config` - The task configuration.
     /// * `files` - The files to be processed.
     /// * `client` - The HTTP client to use for the request.
+    /// * `used_family` - The address family slot of the resolver of the client.
     /// * `client_manager` - The client manager for handling client-specific operations.
     /// * `upload_resume` - Whether to enable upload resume functionality.
     /// * `rest_time` - Remaining time until task timeout.
     pub(crate) fn new(
         config: TaskConfig,
         files: AttachedFiles,
-        client: Client,
+        client: SharedClient,
+        used_family: UsedFamily,
         client_manager: ClientManagerEntry,
         upload_resume: bool,
         rest_time: u64,
     ) -> RequestTask {
-        let file_len = files.files.len();
         let action = config.common_data.action;
 
+        // An urlencoded form is tracked like a single file of the body size.
+        let mut sizes = if config.is_form_urlencoded() {
+            vec![form_urlencode(&config.form_items).len() as i64]
+        } else {
+            files.sizes.clone()
+        };
+        let file_len = sizes.len();
+
         let file_total_size = match action {
             Action::Upload => {
                 let mut file_total_size = 0i64;
                 // If the total size overflows, ignore it.
-                for size in files.sizes.iter() {
+                for size in sizes.iter() {
+                    // A file of unknown size leaves the total unknown.
+                    if *size < 0 {
+                        file_total_size = -1;
+                        break;
+                    }
                     file_total_size += *size;
                 }
                 file_total_size
             }
-            Action::Download => -1,
+            Action::Download | Action::LocalLoopback => -1,
             _ => unreachable!("Action::Any in RequestTask::new never reach"),
         };
 
-        let mut sizes = files.sizes.clone();
-
         if action == Action::Upload && config.common_data.index < sizes.len() as u32 {
             sizes[config.common_data.index as usize] = change_upload_size(
                 config.common_data.begins,
@@ -273,10 +496,14 @@ impl RequestTask {
         let status = TaskStatus::new(time);
         let progress = Progress::new(sizes);
         let mode = AtomicU8::new(config.common_data.mode.repr);
+        let log = TaskLog::new(config.common_data.task_id, config.debug);
+        let pool_key = keep_alive::pool_key(&config);
 
         RequestTask {
             conf: config,
-            client: ylong_runtime::sync::Mutex::new(client),
+            client,
+            used_family,
+            pool_key,
             files: files.files,
             body_files: files.body_files,
             ctime: time,
@@ -294,11 +521,21 @@ impl RequestTask {
             client_manager,
             running_result: Mutex::new(None),
             timeout_tries: AtomicU32::new(0),
+            handshake_retries: AtomicU32::new(0),
+            retry_after: AtomicU64::new(0),
             upload_resume: AtomicBool::new(upload_resume),
+            soft_stop: AtomicBool::new(false),
             mode,
             start_time: AtomicU64::new(get_current_duration().as_secs()),
             task_time: AtomicU64::new(0),
             rest_time: AtomicU64::new(rest_time),
+            response_body: Mutex::new(None),
+            eta: Mutex::new(EtaEstimator::default()),
+            speed_history: Mutex::new(SpeedHistory::default()),
+            disposition_path: Mutex::new(None),
+            log,
+            writes: WriteBuffer::new(BufferBudget::get_instance()),
+            counter: ProgressCounter::default(),
         }
     }
 
@@ -325,9 +562,9 @@ impl RequestTask {
     ) -> Result<RequestTask, ErrorCode> {
         let rest_time = get_rest_time(&config, info.task_time);
         #[cfg(feature = "oh")]
-        let (files, client) = check_config(&config, rest_time, system)?;
+        let (files, client, used_family) = check_config(&config, rest_time, system)?;
         #[cfg(not(feature = "oh"))]
-        let (files, client) = check_config(&config, rest_time)?;
+        let (files, client, used_family) = check_config(&config, rest_time)?;
 
         let file_len = files.files.len();
         let action = config.common_data.action;
@@ -338,11 +575,16 @@ impl RequestTask {
                 let mut file_total_size = 0i64;
                 // If the total size overflows, ignore it.
                 for size in files.sizes.iter() {
+                    // A file of unknown size leaves the total unknown.
+                    if *size < 0 {
+                        file_total_size = -1;
+                        break;
+                    }
                     file_total_size += *size;
                 }
                 file_total_size
             }
-            Action::Download => *info.progress.sizes.first().unwrap_or(&-1),
+            Action::Download | Action::LocalLoopback => *info.progress.sizes.first().unwrap_or(&-1),
             _ => unreachable!("Action::Any in RequestTask::new never reach"),
         };
 
@@ -357,10 +599,14 @@ impl RequestTask {
         };
         let progress = info.progress;
         let mode = AtomicU8::new(config.common_data.mode.repr);
+        let log = TaskLog::new(config.common_data.task_id, config.debug);
+        let pool_key = keep_alive::pool_key(&config);
 
         let mut task = RequestTask {
             conf: config,
-            client: ylong_runtime::sync::Mutex::new(client),
+            client,
+            used_family,
+            pool_key,
             files: files.files,
             body_files: files.body_files,
             ctime,
@@ -378,11 +624,21 @@ impl RequestTask {
             client_manager,
             running_result: Mutex::new(None),
             timeout_tries: AtomicU32::new(0),
+            handshake_retries: AtomicU32::new(0),
+            retry_after: AtomicU64::new(0),
             upload_resume: AtomicBool::new(upload_resume),
+            soft_stop: AtomicBool::new(false),
             mode,
             start_time: AtomicU64::new(get_current_duration().as_secs()),
             task_time: AtomicU64::new(info.task_time),
             rest_time: AtomicU64::new(rest_time),
+            response_body: Mutex::new(None),
+            eta: Mutex::new(EtaEstimator::default()),
+            speed_history: Mutex::new(SpeedHistory::default()),
+            disposition_path: Mutex::new(None),
+            log,
+            writes: WriteBuffer::new(BufferBudget::get_instance()),
+            counter: ProgressCounter::default(),
         };
         let background_notify = NotificationDispatcher::get_instance().register_task(&task);
         task.background_notify = background_notify;
@@ -396,33 +652,70 @@ impl RequestTask {
     /// A `NotifyData` struct containing the current state of the task for notification purposes.
     pub(crate) fn build_notify_data(&self) -> NotifyData {
         let vec = self.get_each_file_status();
+        self.sync_progress();
+        // `unwrap` for propagating panics among threads.
+        let progress = self.progress.lock().unwrap().clone();
         NotifyData {
             bundle: self.conf.bundle.clone(),
-            // `unwrap` for propagating panics among threads.
-            progress: self.progress.lock().unwrap().clone(),
+            eta_ms: self.eta_ms(&progress),
+            progress,
             action: self.conf.common_data.action,
             version: self.conf.version,
             each_file_status: vec,
             task_id: self.conf.common_data.task_id,
             uid: self.conf.common_data.uid,
+            response_body: None,
+            client_tag: self.conf.client_tag.clone(),
+        }
+    }
+
+    /// Returns the estimated remaining time of the task in milliseconds.
+    ///
+    /// Returns `None` if the task is not running, the total size is unknown
+    /// or no progress has been observed yet.
+    pub(crate) fn eta_ms(&self, progress: &Progress) -> Option<u64> {
+        if progress.common_data.state != State::Running.repr {
+            return None;
         }
+        let mut total = 0u64;
+        for size in progress.sizes.iter() {
+            if *size < 0 {
+                return None;
+            }
+            total += *size as u64;
+        }
+        let remaining = total.saturating_sub(progress.common_data.total_processed as u64);
+        self.eta
+            .lock()
+            .unwrap()
+            .eta_ms(get_current_timestamp(), remaining)
     }
 
     /// Updates the task progress in the database.
     /// 
-    /// This method saves the current state of the task to persistent storage.
+    /// This method saves the current state of the task to persistent storage
+    /// at once, superseding progress queued by `queue_progress_update`.
     pub(crate) fn update_progress_in_database(&self) {
+        ProgressWriter::get_instance().flush_now(self.task_id(), self.update_info());
+    }
+
+    /// Queues the task progress for the next batched database write.
+    pub(crate) fn queue_progress_update(&self) {
+        ProgressWriter::get_instance().push(self.task_id(), self.update_info());
+    }
+
+    fn update_info(&self) -> UpdateInfo {
         let mtime = self.status.lock().unwrap().mtime;
         let reason = self.status.lock().unwrap().reason;
+        self.sync_progress();
         let progress = self.progress.lock().unwrap().clone();
-        let update_info = UpdateInfo {
+        UpdateInfo {
             mtime,
             reason: reason.repr,
             progress,
             tries: self.tries.load(Ordering::SeqCst),
             mime_type: self.mime_type(),
-        };
-        RequestDb::get_instance().update_task(self.task_id(), update_info);
+        }
     }
 
     /// Builds an HTTP request builder based on the task configuration.
@@ -436,10 +729,15 @@ impl RequestTask {
     /// 
     /// Returns an error if the URL percent encoding fails.
     pub(crate) fn build_request_builder(&self) -> Result<RequestBuilder, HttpClientError> {
+        self.build_request_builder_to(self.conf.request_url())
+    }
+
+    /// Builds an HTTP request builder sending the request to `url` instead of
+    /// the URL of the task.
+    fn build_request_builder_to(&self, url: &str) -> Result<RequestBuilder, HttpClientError> {
         use ylong_http_client::async_impl::PercentEncoder;
 
-        let url = self.conf.url.clone();
-        let url = match PercentEncoder::encode(url.as_str()) {
+        let url = match PercentEncoder::encode(url) {
             Ok(value) => value,
             Err(e) => {
                 error!("url percent encoding error is {:?}", e);
@@ -452,26 +750,27 @@ impl RequestTask {
             }
         };
 
-        let method = match self.conf.method.to_uppercase().as_str() {
-            "PUT" => "PUT",
-            "POST" => "POST",
-            "GET" => "GET",
-            _ => match self.conf.common_data.action {
-                Action::Upload => {
-                    if self.conf.version == Version::API10 {
-                        "PUT"
-                    } else {
-                        "POST"
-                    }
-                }
-                Action::Download => "GET",
-                _ => "",
-            },
-        };
-        let mut request = RequestBuilder::new().method(method).url(url.as_str());
-        for (key, value) in self.conf.headers.iter() {
+        let method = self.conf.request_method();
+        let mut request = RequestBuilder::new()
+            .method(method.as_str())
+            .url(url.as_str());
+        let mut headers = self.request_headers();
+        if let Some(context) = &self.conf.trace_context {
+            trace_context::inject(&mut headers, context);
+        }
+        for (key, value) in headers.iter() {
             request = request.header(key.as_str(), value.as_str());
         }
+        if self.conf.common_data.action == Action::Upload && !self.conf.if_match.is_empty() {
+            request = request.header("If-Match", self.conf.if_match.as_str());
+        }
+        task_debug!(
+            self.log,
+            "request {} {}, headers {}",
+            method,
+            url,
+            format_headers(headers.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+        );
         Ok(request)
     }
 
@@ -493,8 +792,6 @@ impl RequestTask {
     pub(crate) async fn build_download_request(
         task: Arc<RequestTask>,
     ) -> Result<Request, TaskError> {
-        let mut request_builder = task.build_request_builder()?;
-
         let file = if let Some(mutex) = task.files.get(0) {
             mutex
         } else {
@@ -506,6 +803,16 @@ impl RequestTask {
         let resume_download = has_downloaded > 0;
         let require_range = task.require_range();
 
+        resume::finish_overlap(task.task_id());
+        let resume_by_query = task.resumes_by_query(has_downloaded);
+        let mut request_builder = if resume_by_query {
+            let url = task.resume_url(has_downloaded).await;
+            task.build_request_builder_to(&url)?
+        } else {
+            task.build_request_builder()?
+        };
+        let restart = task.conf.resume_strategy == ResumeStrategy::None;
+
         let begins = task.conf.common_data.begins;
         let ends = task.conf.common_data.ends;
 
@@ -516,6 +823,14 @@ impl RequestTask {
             require_range
         );
         match (resume_download, require_range) {
+            // The offset is in the URL already.
+            (true, false) if resume_by_query => {}
+            (true, _) if restart => {
+                task_control::clear_downloaded_file(task.clone()).await?;
+                if require_range {
+                    request_builder = task.range_request(request_builder, begins, ends);
+                }
+            }
             (true, false) => {
                 let (builder, support_range) = task.support_range(request_builder);
                 request_builder = builder;
@@ -671,6 +986,9 @@ impl RequestTask {
         if err.error_kind() != ErrorKind::UserAborted {
             error!("Task {} {:?}", self.task_id(), err);
         }
+        if format!("{}", err).contains(CROSS_HOST_LIMIT_EXCEEDED) {
+            return Err(TaskError::Failed(Reason::CrossHostRedirectLimit));
+        }
         match err.error_kind() {
             ErrorKind::Timeout => {
                 sys_event!(
@@ -750,6 +1068,30 @@ impl RequestTask {
             .send_response(tid, version, status_code, status_message, headers)
     }
 
+    /// Notifies the client of the HTTP response again with its trailers
+    /// merged into the headers, trailers taking precedence (OH platform only).
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The HTTP response whose body was read.
+    /// * `trailers` - The trailers sent after the body.
+    #[cfg(feature = "oh")]
+    pub(crate) fn notify_response_trailers(&self, response: &Response, trailers: &Headers) {
+        let mut headers = response.headers().clone();
+        for (k, v) in trailers {
+            if let Ok(v) = v.to_string() {
+                let _ = headers.insert(k.to_string().as_str(), v.as_str());
+            }
+        }
+        self.client_manager.send_response(
+            self.task_id(),
+            response.version().as_str().into(),
+            response.status().as_u16() as u32,
+            response.status().reason().unwrap_or_default().into(),
+            headers,
+        )
+    }
+
     /// Determines if the task requires range requests.
     /// 
     /// # Returns
@@ -770,22 +1112,37 @@ impl RequestTask {
         index: usize,
         response: Result<Response, HttpClientError>,
     ) {
+        let final_url = self.report_redirects();
         if let Ok(mut r) = response {
             {
                 let mut guard = self.progress.lock().unwrap();
                 guard.extras.clear();
-                for (k, v) in r.headers() {
-                    if let Ok(value) = v.to_string() {
-                        guard.extras.insert(k.to_string().to_lowercase(), value);
-                    }
+                for (k, v) in self.response_headers(&r) {
+                    guard.extras.insert(k.to_lowercase(), v);
+                }
+                self.record_effective_metered(&mut guard.extras);
+                self.record_address_family(&mut guard.extras);
+                self.record_handshake_retries(&mut guard.extras);
+                if let Some(url) = final_url {
+                    guard.extras.insert(FINAL_URL.to_string(), url);
                 }
             }
 
-            let file = match self.body_files.get(index) {
-                Some(file) => file,
-                None => return,
+            let file = self.body_files.get(index);
+            if let Some(file) = file.as_ref() {
+                let _ = task_control::file_set_len(file.clone(), 0).await;
+            }
+
+            // API10 clients get the beginning of the body back, at most `limit`
+            // bytes of it are ever held in memory.
+            let limit = match self.conf.version {
+                Version::API10 => self.conf.response_body_limit as usize,
+                Version::API9 => 0,
             };
-            let _ = task_control::file_set_len(file.clone(), 0).await;
+            if file.is_none() && limit == 0 {
+                return;
+            }
+            let mut body = ResponseBody::default();
             loop {
                 let mut buf = [0u8; 1024];
                 let size = r.data(&mut buf).await;
@@ -797,10 +1154,26 @@ impl RequestTask {
                 if size == 0 {
                     break;
                 }
-                let _ = task_control::file_write_all(file.clone(), &buf[..size]).await;
+                if let Some(file) = file.as_ref() {
+                    let _ = task_control::file_write_all(file.clone(), &buf[..size]).await;
+                }
+                if !body.truncated {
+                    let room = limit - body.data.len();
+                    body.data.extend_from_slice(&buf[..size.min(room)]);
+                    body.truncated = size > room;
+                }
+                // Without a body file nothing needs the rest of the stream.
+                if file.is_none() && body.truncated {
+                    break;
+                }
+            }
+            if let Some(file) = file {
+                // Makes sure all the data has been written to the target file.
+                let _ = task_control::file_sync_all(file).await;
+            }
+            if limit != 0 {
+                *self.response_body.lock().unwrap() = Some(body);
             }
-            // Makes sure all the data has been written to the target file.
-            let _ = task_control::file_sync_all(file).await;
         }
     }
 
@@ -873,6 +1246,9 @@ impl RequestTask {
             },
             max_speed: self.max_speed.load(Ordering::SeqCst),
             task_time: self.task_time.load(Ordering::SeqCst),
+            effective_speed_limit: self.effective_speed_limit(),
+            response_body: self.response_body.lock().unwrap().clone(),
+            client_tag: self.conf.client_tag.clone(),
         }
     }
 
@@ -940,7 +1316,7 @@ fn check_file_specs(file_specs: &[FileSpec]) -> bool {
     true
 }
 
-fn check_path(path: &str) -> bool {
+pub(crate) fn check_path(path: &str) -> bool {
     if !check_standardized_path(path) {
         error!("File path err - path: {}", path);
         return false;
@@ -967,16 +1343,79 @@ fn check_path(path: &str) -> bool {
 /// 
 /// # Returns
 /// 
-/// * `Ok((AttachedFiles, Client))` - The attached files and configured client.
+/// * `Ok((AttachedFiles, SharedClient, UsedFamily))` - The attached files,
+///   configured client, the idle client of an earlier task if one can be taken
+///   over, and the address family slot of its resolver.
 /// * `Err(ErrorCode)` - If the configuration is invalid or files cannot be opened.
 pub(crate) fn check_config(
     config: &TaskConfig,
     total_timeout: u64,
     #[cfg(feature = "oh")] system: SystemConfig,
-) -> Result<(AttachedFiles, Client), ErrorCode> {
+) -> Result<(AttachedFiles, SharedClient, UsedFamily), ErrorCode> {
     if !check_file_specs(&config.file_specs) {
         return Err(ErrorCode::Other);
     }
+    if config.common_data.action == Action::LocalLoopback {
+        if !config.privileged {
+            error!("loopback task of {} not privileged", config.common_data.uid);
+            return Err(ErrorCode::Permission);
+        }
+        if LoopbackSpec::from_extras(&config.extras).is_none() {
+            error!("loopback task without a valid spec");
+            return Err(ErrorCode::ParameterCheck);
+        }
+    }
+    if config.is_form_urlencoded() && !config.file_specs.is_empty() {
+        error!("urlencoded form upload does not accept files");
+        return Err(ErrorCode::ParameterCheck);
+    }
+    if config.concatenated
+        && (config.common_data.action != Action::Upload
+            || config.common_data.multipart
+            || config.file_specs.is_empty())
+    {
+        error!("concatenated body requires an upload of files");
+        return Err(ErrorCode::ParameterCheck);
+    }
+    if config.treat_network_as.is_some() && config.version != Version::API10 {
+        error!("metered override requires API10");
+        return Err(ErrorCode::ParameterCheck);
+    }
+    if !config.method.is_empty() && !is_http_token(&config.method) {
+        error!("invalid method {:?}", config.method);
+        return Err(ErrorCode::ParameterCheck);
+    }
+    let method = config.request_method();
+    let has_body = config.common_data.action == Action::Upload || !config.data.is_empty();
+    if has_body && (method == "GET" || method == "HEAD") {
+        warn!("{} request with a body", method);
+    }
+    if config.client_tag.len() > MAX_CLIENT_TAG_LEN {
+        error!("client tag exceeds {} bytes", MAX_CLIENT_TAG_LEN);
+        return Err(ErrorCode::ParameterCheck);
+    }
+    if !accept_encoding::is_supported(&config.accept_encoding) {
+        error!("unsupported accept encoding {}", config.accept_encoding);
+        return Err(ErrorCode::ParameterCheck);
+    }
+    if !net_binding::is_valid_net_id(config.net_id) {
+        error!("invalid net id {}", config.net_id);
+        return Err(ErrorCode::ParameterCheck);
+    }
+    if config.ip_preference == IpPreference::V6Only && !has_ipv6() {
+        error!("IPv6 only task without IPv6 on the platform");
+        return Err(ErrorCode::ParameterCheck);
+    }
+    if tls_versions(config).is_none() {
+        error!("invalid TLS version range or cipher list");
+        return Err(ErrorCode::ParameterCheck);
+    }
+    if let Some(context) = &config.trace_context {
+        if !trace_context::is_valid(context) {
+            error!("invalid trace context {}", context.traceparent);
+            return Err(ErrorCode::ParameterCheck);
+        }
+    }
     if !config.body_file_paths.iter().all(|path| check_path(path)) {
         return Err(ErrorCode::Other);
     }
@@ -984,12 +1423,27 @@ pub(crate) fn check_config(
         return Err(ErrorCode::Other);
     }
     let files = AttachedFiles::open(config).map_err(|_| ErrorCode::FileOperationErr)?;
+    let pooled = keep_alive::pool_key(config)
+        .and_then(|key| KeepAlivePool::get_instance().take(&key));
+    // Pooled clients resolve without a preference and never set the family
+    let used_family = UsedFamily::default();
+    if let Some(client) = pooled {
+        debug!("task {} takes over an idle client", config.common_data.task_id);
+        return Ok((files, client, used_family));
+    }
+
     #[cfg(feature = "oh")]
-    let client = build_client(config, total_timeout, system).map_err(|_| ErrorCode::Other)?;
+    let client = build_client(config, total_timeout, used_family.clone(), system)
+        .map_err(|_| ErrorCode::Other)?;
 
     #[cfg(not(feature = "oh"))]
-    let client = build_client(config, total_timeout).map_err(|_| ErrorCode::Other)?;
-    Ok((files, client))
+    let client =
+        build_client(config, total_timeout, used_family.clone()).map_err(|_| ErrorCode::Other)?;
+    Ok((
+        files,
+        Arc::new(ylong_runtime::sync::Mutex::new(client)),
+        used_family,
+    ))
 }
 
 /// Calculates the remaining time until task timeout.
@@ -1054,6 +1508,9 @@ pub enum TaskPhase {
     
     /// The network is offline.
     NetworkOffline,
+
+    /// The server asked to retry after the given delay.
+    RateLimited(Duration),
 }
 
 /// Represents errors that can occur during task execution.
@@ -0,0 +1,261 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resuming downloads by a query parameter.
+//!
+//! Some servers ignore `Range` but accept the byte offset to resume from as
+//! a query parameter. Tasks configured with `ResumeStrategy::QueryParam` set
+//! that parameter instead of sending `Range` when they resume.
+//!
+//! Such servers answer with `200 OK`, so the response is checked to start at
+//! the requested offset. A `Content-Range` is checked when the server sends
+//! one. Besides, the offset is backed off by up to `RESUME_OVERLAP` bytes and
+//! the start of the response is compared with the tail of the local file,
+//! the overlapping bytes are discarded. A response starting elsewhere
+//! restarts the download from zero.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use ylong_http_client::async_impl::Response;
+
+use crate::task::config::{Action, ResumeStrategy};
+use crate::task::request_task::RequestTask;
+use crate::task::task_control;
+
+/// Most bytes of the local file a resumed response is checked against.
+pub(crate) const RESUME_OVERLAP: u64 = 4096;
+
+/// Offsets requested by tasks resuming by a query parameter.
+static OVERLAPS: Mutex<Option<HashMap<u32, Overlap>>> = Mutex::new(None);
+
+/// Start a resumed response is expected to have.
+struct Overlap {
+    /// Offset requested from the server.
+    offset: u64,
+    /// Bytes of the local file the response must start with.
+    expected: Vec<u8>,
+    /// Number of expected bytes received so far.
+    matched: usize,
+    /// Whether the response started with other bytes.
+    mismatched: bool,
+}
+
+/// Sets the query parameter `name` of `url` to `offset`, replacing any
+/// previous value.
+pub(crate) fn with_offset(url: &str, name: &str, offset: u64) -> String {
+    let param = format!("{}={}", name, offset);
+    let (url, fragment) = match url.find('#') {
+        Some(index) => url.split_at(index),
+        None => (url, ""),
+    };
+    let (base, query) = match url.split_once('?') {
+        Some((base, query)) => (base, query),
+        None => (url, ""),
+    };
+    let mut params: Vec<&str> = query
+        .split('&')
+        .filter(|p| !p.is_empty() && p.split('=').next() != Some(name))
+        .collect();
+    params.push(&param);
+    format!("{}?{}{}", base, params.join("&"), fragment)
+}
+
+/// Parses the first byte position of a `Content-Range` value such as
+/// `bytes 100-199/200`.
+pub(crate) fn content_range_start(value: &str) -> Option<u64> {
+    let range = value.trim().strip_prefix("bytes")?.trim_start();
+    range.split('-').next()?.trim().parse().ok()
+}
+
+/// Expects the next response of a task to start at `offset` with the bytes
+/// `expected`.
+pub(crate) fn expect_overlap(task_id: u32, offset: u64, expected: Vec<u8>) {
+    OVERLAPS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(
+            task_id,
+            Overlap {
+                offset,
+                expected,
+                matched: 0,
+                mismatched: false,
+            },
+        );
+}
+
+/// Returns the offset the pending response of a task was requested from.
+pub(crate) fn requested_offset(task_id: u32) -> Option<u64> {
+    OVERLAPS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|overlaps| overlaps.get(&task_id))
+        .map(|overlap| overlap.offset)
+}
+
+/// Checks the next bytes of a response against the expected overlap.
+///
+/// # Returns
+///
+/// The number of leading bytes of `data` to discard, `None` if the response
+/// does not start with the expected bytes.
+pub(crate) fn skip_overlap(task_id: u32, data: &[u8]) -> Option<usize> {
+    let mut overlaps = OVERLAPS.lock().unwrap();
+    let Some(overlap) = overlaps
+        .as_mut()
+        .and_then(|overlaps| overlaps.get_mut(&task_id))
+    else {
+        return Some(0);
+    };
+    if overlap.mismatched {
+        return None;
+    }
+    let remaining = &overlap.expected[overlap.matched..];
+    let len = remaining.len().min(data.len());
+    if remaining[..len] != data[..len] {
+        overlap.mismatched = true;
+        return None;
+    }
+    overlap.matched += len;
+    Some(len)
+}
+
+/// Stops expecting an overlap for a task.
+///
+/// # Returns
+///
+/// `false` if the response did not start with all the expected bytes.
+pub(crate) fn finish_overlap(task_id: u32) -> bool {
+    let overlap = OVERLAPS
+        .lock()
+        .unwrap()
+        .as_mut()
+        .and_then(|overlaps| overlaps.remove(&task_id));
+    match overlap {
+        Some(overlap) => !overlap.mismatched && overlap.matched == overlap.expected.len(),
+        None => true,
+    }
+}
+
+impl RequestTask {
+    /// Checks if a download restarted now would continue from the bytes it
+    /// has rather than from zero.
+    ///
+    /// Resuming by `Range` needs a validator of the response, as the request
+    /// is only sent with `If-Range`.
+    pub(crate) fn is_resumable(&self) -> bool {
+        if self.conf.common_data.action != Action::Download {
+            return false;
+        }
+        match self.conf.resume_strategy {
+            ResumeStrategy::RangeHeader => {
+                let progress = self.progress.lock().unwrap();
+                progress.extras.contains_key("etag")
+                    || progress.extras.contains_key("last-modified")
+            }
+            ResumeStrategy::QueryParam { .. } => true,
+            ResumeStrategy::None => false,
+        }
+    }
+
+    /// Checks if the task resumes by a query parameter from the bytes it has.
+    pub(crate) fn resumes_by_query(&self, has_downloaded: u64) -> bool {
+        has_downloaded > 0
+            && !self.require_range()
+            && matches!(self.conf.resume_strategy, ResumeStrategy::QueryParam { .. })
+    }
+
+    /// Builds the URL resuming the download by its query parameter.
+    ///
+    /// The start of the response is expected to overlap the tail of the
+    /// local file. If the tail cannot be read, the exact offset is requested
+    /// and only a `Content-Range` of the response is checked.
+    pub(crate) async fn resume_url(&self, has_downloaded: u64) -> String {
+        let url = self.conf.request_url();
+        let ResumeStrategy::QueryParam { name } = &self.conf.resume_strategy else {
+            return url.to_string();
+        };
+        let task_id = self.task_id();
+        let overlap = has_downloaded.min(RESUME_OVERLAP);
+        let offset = has_downloaded - overlap;
+        let tail = match self.files.get(0) {
+            Some(file) => task_control::file_read_at(file, offset, overlap as usize).await,
+            None => Err(std::io::ErrorKind::NotFound.into()),
+        };
+        let offset = match tail {
+            Ok(tail) => {
+                expect_overlap(task_id, offset, tail);
+                offset
+            }
+            Err(e) => {
+                info!("task {} cannot read the file tail, {:?}", task_id, e);
+                expect_overlap(task_id, has_downloaded, vec![]);
+                has_downloaded
+            }
+        };
+        info!("task {} resumes from {} by {}", task_id, offset, name);
+        with_offset(url, name, offset)
+    }
+
+    /// Checks a response resuming by a query parameter against the offset it
+    /// was requested from.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the response has a `Content-Range` starting elsewhere.
+    pub(crate) fn check_resumed_response(&self, response: &Response) -> bool {
+        let Some(offset) = requested_offset(self.task_id()) else {
+            return true;
+        };
+        let content_range = response
+            .headers()
+            .get("content-range")
+            .and_then(|value| value.to_string().ok());
+        match content_range.as_deref().map(content_range_start) {
+            Some(start) if start != Some(offset) => {
+                info!(
+                    "task {} resumed from {:?}, {} requested",
+                    self.task_id(),
+                    start,
+                    offset
+                );
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Removes the bytes the resumed response repeats from the size of the
+    /// file the server reported.
+    pub(crate) fn discount_overlap(&self) {
+        let task_id = self.task_id();
+        let Some(offset) = requested_offset(task_id) else {
+            return;
+        };
+        let mut progress = self.progress.lock().unwrap();
+        let processed = progress.processed.first().copied().unwrap_or(0) as i64;
+        if let Some(size) = progress.sizes.first_mut() {
+            if *size >= 0 {
+                *size -= processed - offset as i64;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_resume {
+    include!("../../tests/ut/task/ut_resume.rs");
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing of the `Retry-After` response header.
+//!
+//! Servers rejecting a request with 429 or 503 may ask the client to wait,
+//! either for a number of seconds or until an HTTP date. The resulting delay
+//! is capped by the task's `retry_after_cap_ms` so a misconfigured server
+//! cannot park a task for days.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default upper bound of a delay requested by `Retry-After`.
+pub(crate) const DEFAULT_RETRY_AFTER_CAP_MS: u64 = 10 * 60 * 1000;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Returns whether a response with `status` may carry a `Retry-After` the
+/// task waits for.
+pub(crate) fn is_rate_limited(status: u16) -> bool {
+    status == 429 || status == 503
+}
+
+/// Returns the delay requested by a `Retry-After` header value, at most
+/// `cap`.
+///
+/// # Arguments
+///
+/// * `value` - Header value, either delay seconds or an IMF-fixdate such as
+///   `Sun, 06 Nov 1994 08:49:37 GMT`.
+/// * `now` - Current time, dates in the past give a zero delay.
+/// * `cap` - Upper bound of the returned delay.
+///
+/// # Returns
+///
+/// `None` if the value is neither form.
+pub(crate) fn retry_after_delay(value: &str, now: SystemTime, cap: Duration) -> Option<Duration> {
+    let value = value.trim();
+    let delay = if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        // Overlong digit strings are absurd waits, not malformed values.
+        Duration::from_secs(value.parse().unwrap_or(u64::MAX))
+    } else {
+        parse_http_date(value)?
+            .duration_since(now)
+            .unwrap_or(Duration::ZERO)
+    };
+    Some(delay.min(cap))
+}
+
+/// Parses an IMF-fixdate, the preferred HTTP date format.
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    let (weekday, rest) = value.split_once(", ")?;
+    if weekday.len() != 3 {
+        return None;
+    }
+    let parts: Vec<&str> = rest.split(' ').collect();
+    let [day, month, year, time, "GMT"] = parts[..] else {
+        return None;
+    };
+    let day: u64 = parse_digits(day, 2)?;
+    let month = MONTHS.iter().position(|m| *m == month)? as u64 + 1;
+    let year: u64 = parse_digits(year, 4)?;
+
+    let time: Vec<&str> = time.split(':').collect();
+    let [hour, minute, second] = time[..] else {
+        return None;
+    };
+    let hour = parse_digits(hour, 2)?;
+    let minute = parse_digits(minute, 2)?;
+    let second = parse_digits(second, 2)?;
+    if !(1..=31).contains(&day) || year < 1970 || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let secs = days_since_epoch(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+fn parse_digits(value: &str, len: usize) -> Option<u64> {
+    if value.len() != len || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// Days from 1970-01-01 to the given date of the proleptic Gregorian
+/// calendar, for years from 1970 on.
+fn days_since_epoch(year: u64, month: u64, day: u64) -> u64 {
+    // Counting years from March puts the leap day at the end of a year.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month_from_march = (month + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    // 719_468 days lie between 0000-03-01 and 1970-01-01.
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod ut_retry_after {
+    include!("../../tests/ut/task/ut_retry_after.rs");
+}
@@ -0,0 +1,439 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Multi-connection download of a single file.
+//!
+//! A download opts in through `TaskConfig::segments`. The server is probed
+//! with a one-byte range request to learn the total size and whether ranges
+//! are honored; if so, the file is split into contiguous byte ranges that are
+//! fetched concurrently and written in place. Any other probe outcome falls
+//! back to the single-stream download path.
+
+use std::fs::File;
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use ylong_http_client::async_impl::{Body, DownloadOperator, Downloader};
+use ylong_http_client::{HttpClientError, SpeedLimit, Timeout};
+
+use super::download::{LOW_SPEED_LIMIT, LOW_SPEED_TIME, SECONDS_IN_ONE_WEEK};
+use super::operator::TaskOperator;
+use super::reason::Reason;
+use super::request_task::{TaskError, TaskPhase};
+use crate::manage::database::RequestDb;
+use crate::task::request_task::RequestTask;
+use crate::task::{sparse, task_control};
+
+/// Upper bound of concurrent connections for one task.
+pub(crate) const MAX_SEGMENTS: u32 = 8;
+
+/// Files smaller than this per connection are not worth splitting.
+const MIN_SEGMENT_SIZE: u64 = 64 * 1024;
+
+/// Splits `total` bytes into at most `segments` inclusive byte ranges.
+pub(crate) fn split_ranges(total: u64, segments: u32) -> Vec<(u64, u64)> {
+    let count = (segments.min(MAX_SEGMENTS) as u64)
+        .min(total / MIN_SEGMENT_SIZE)
+        .max(1);
+    let size = total / count;
+    (0..count)
+        .map(|i| {
+            let begin = i * size;
+            let end = if i == count - 1 {
+                total - 1
+            } else {
+                begin + size - 1
+            };
+            (begin, end)
+        })
+        .collect()
+}
+
+/// Parses the total length out of a `Content-Range: bytes a-b/total` value.
+fn parse_content_range_total(value: &str) -> Option<u64> {
+    value.rsplit_once('/')?.1.trim().parse().ok()
+}
+
+/// Parses the byte range out of a `Content-Range: bytes a-b/total` value.
+fn parse_content_range(value: &str) -> Option<(u64, u64)> {
+    let range = value.trim().strip_prefix("bytes")?.trim_start();
+    let (begin, end) = range.split_once('/')?.0.split_once('-')?;
+    Some((begin.trim().parse().ok()?, end.trim().parse().ok()?))
+}
+
+/// Error of a segment response carrying more bytes than its range.
+const SEGMENT_OVERRUN: &str = "segment response longer than its range";
+
+/// Writes one byte range of the task file at its own offset.
+struct SegmentOperator {
+    inner: TaskOperator,
+    file: File,
+    offset: u64,
+    end: u64,
+    written: Arc<AtomicU64>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl DownloadOperator for SegmentOperator {
+    fn poll_download(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        data: &[u8],
+    ) -> Poll<Result<usize, HttpClientError>> {
+        let this = self.get_mut();
+        if this.cancel.load(Ordering::Acquire)
+            || this.inner.abort_flag.load(Ordering::Acquire)
+        {
+            return Poll::Ready(Err(HttpClientError::user_aborted()));
+        }
+        // Bytes past the range belong to another segment, keep them out.
+        if this.offset + data.len() as u64 > this.end + 1 {
+            let e = io::Error::new(io::ErrorKind::InvalidData, SEGMENT_OVERRUN);
+            return Poll::Ready(Err(HttpClientError::other(e)));
+        }
+        match this.file.write_at(data, this.offset) {
+            Ok(size) => {
+                this.offset += size as u64;
+                this.written.fetch_add(size as u64, Ordering::SeqCst);
+                this.inner.task.counter.add(size);
+                Poll::Ready(Ok(size))
+            }
+            Err(e) => Poll::Ready(Err(HttpClientError::other(e))),
+        }
+    }
+
+    fn poll_progress(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        _downloaded: u64,
+        _total: Option<u64>,
+    ) -> Poll<Result<(), HttpClientError>> {
+        self.get_mut().inner.poll_progress_common(cx)
+    }
+}
+
+impl RequestTask {
+    /// Probes the total size with a one-byte range request.
+    ///
+    /// Returns `None` if the server does not answer with a usable
+    /// `206 Partial Content`.
+    async fn probe_range_size(&self) -> Result<Option<u64>, TaskError> {
+        let request = self
+            .build_request_builder()?
+            .header("Range", "bytes=0-0")
+            .body(Body::empty())?;
+        let response = {
+            let client = self.client.lock().await;
+            client.request(request).await
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                info!("task {} range probe failed, {:?}", self.task_id(), e);
+                return Ok(None);
+            }
+        };
+        if response.status().as_u16() != 206 {
+            return Ok(None);
+        }
+        // The single stream rejects responses of unexpected types.
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_string().ok());
+        if self
+            .unexpected_content_type(content_type.as_deref())
+            .is_some()
+        {
+            return Ok(None);
+        }
+        Ok(response
+            .headers()
+            .get("content-range")
+            .and_then(|value| value.to_string().ok())
+            .and_then(|value| parse_content_range_total(&value)))
+    }
+
+    /// Fetches `begin..=end` into `file` at the same offset.
+    ///
+    /// Fails with `ProtocolError` unless the response covers exactly the
+    /// range, the bytes not written are left as a hole.
+    async fn fetch_segment(
+        self: Arc<Self>,
+        abort_flag: Arc<AtomicBool>,
+        file: File,
+        (begin, end): (u64, u64),
+        written: Arc<AtomicU64>,
+        cancel: Arc<AtomicBool>,
+    ) -> Result<(), TaskError> {
+        let request = self
+            .build_request_builder()?
+            .header("Range", format!("bytes={begin}-{end}").as_str())
+            .body(Body::empty())?;
+        let response = {
+            let client = self.client.lock().await;
+            client.request(request).await
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => return self.handle_download_error(e).await,
+        };
+        if response.status().as_u16() != 206 {
+            error!(
+                "task {} segment {}-{} response {}",
+                self.task_id(),
+                begin,
+                end,
+                response.status()
+            );
+            return Err(TaskError::Failed(Reason::ProtocolError));
+        }
+        // Bytes of another range would be written at the wrong offset.
+        let range = response
+            .headers()
+            .get("content-range")
+            .and_then(|value| value.to_string().ok())
+            .and_then(|value| parse_content_range(&value));
+        if range != Some((begin, end)) {
+            error!(
+                "task {} segment {}-{} content range {:?}",
+                self.task_id(),
+                begin,
+                end,
+                range
+            );
+            return Err(TaskError::Failed(Reason::ProtocolError));
+        }
+
+        let operator = SegmentOperator {
+            inner: TaskOperator::new(self.clone(), abort_flag),
+            file,
+            offset: begin,
+            end,
+            written: written.clone(),
+            cancel,
+        };
+        let mut downloader = Downloader::builder()
+            .body(response)
+            .operator(operator)
+            .timeout(Timeout::from_secs(SECONDS_IN_ONE_WEEK))
+            .speed_limit(SpeedLimit::new().min_speed(LOW_SPEED_LIMIT, LOW_SPEED_TIME))
+            .build();
+        if let Err(e) = downloader.download().await {
+            if format!("{}", e).contains(SEGMENT_OVERRUN) {
+                error!("task {} segment {}-{} overruns", self.task_id(), begin, end);
+                return Err(TaskError::Failed(Reason::ProtocolError));
+            }
+            return self.handle_download_error(e).await;
+        }
+        // A body ending early leaves the rest of the range as a hole.
+        let received = written.load(Ordering::SeqCst);
+        if received != end - begin + 1 {
+            error!(
+                "task {} segment {}-{} ends after {} bytes",
+                self.task_id(),
+                begin,
+                end,
+                received
+            );
+            return Err(TaskError::Failed(Reason::ProtocolError));
+        }
+        Ok(())
+    }
+}
+
+/// Downloads the first file of `task` over several range connections.
+///
+/// Returns `Ok(false)` without touching the file if the server cannot serve
+/// ranges, so the caller continues with a single stream. On failure the
+/// ranges left unwritten are released and recorded, so a later resume only
+/// refetches them.
+pub(crate) async fn download_segmented(
+    task: Arc<RequestTask>,
+    abort_flag: Arc<AtomicBool>,
+) -> Result<bool, TaskError> {
+    let total = match task.probe_range_size().await? {
+        Some(total) if total > 0 => total,
+        _ => {
+            info!("task {} segmented download falls back", task.task_id());
+            return Ok(false);
+        }
+    };
+    if task.exceeds_size_cap(total) {
+        task.reject_oversize(total).await?;
+    }
+    let ranges = split_ranges(total, task.conf.segments);
+    if ranges.len() < 2 {
+        return Ok(false);
+    }
+    info!(
+        "task {} downloads {} bytes in {} segments",
+        task.task_id(),
+        total,
+        ranges.len()
+    );
+
+    let file = match task.files.get(0) {
+        Some(file) => file,
+        None => {
+            error!("download_segmented err, no file in the `task`");
+            return Err(TaskError::Failed(Reason::OthersError));
+        }
+    };
+    task_control::file_set_len(file.clone(), total).await?;
+    {
+        let mut progress = task.progress.lock().unwrap();
+        progress.sizes = vec![total as i64];
+        task.file_total_size.store(total as i64, Ordering::SeqCst);
+    }
+    if task.conf.preallocate {
+        task.preallocate().await?;
+    }
+    task.update_progress_in_database();
+    RequestDb::get_instance()
+        .update_task_sizes(task.task_id(), &task.progress.lock().unwrap().sizes);
+
+    fetch_ranges(&task, &abort_flag, &file, &ranges).await?;
+    Ok(true)
+}
+
+/// Refetches the ranges a stopped segmented download left unwritten.
+///
+/// Returns `Ok(false)` without touching the file if the server no longer
+/// serves ranges of a file of the same size, so the caller restarts the
+/// download from zero.
+pub(crate) async fn resume_segmented(
+    task: Arc<RequestTask>,
+    abort_flag: Arc<AtomicBool>,
+    holes: Vec<(u64, u64)>,
+) -> Result<bool, TaskError> {
+    let size = task.file_total_size.load(Ordering::SeqCst);
+    match task.probe_range_size().await? {
+        Some(total) if total as i64 == size => {}
+        _ => {
+            info!("task {} holes cannot be resumed", task.task_id());
+            return Ok(false);
+        }
+    }
+    let file = match task.files.get(0) {
+        Some(file) => file,
+        None => {
+            error!("resume_segmented err, no file in the `task`");
+            return Err(TaskError::Failed(Reason::OthersError));
+        }
+    };
+    let missing: u64 = holes.iter().map(|(begin, end)| end - begin + 1).sum();
+    let written = (size as u64).saturating_sub(missing) as usize;
+    info!(
+        "task {} resumes {} bytes in {} holes",
+        task.task_id(),
+        missing,
+        holes.len()
+    );
+    {
+        let mut progress = task.progress.lock().unwrap();
+        // Bytes counted by a previous attempt are part of `written`.
+        task.counter.take();
+        progress.processed = vec![written];
+        progress.common_data.total_processed = written;
+    }
+
+    fetch_ranges(&task, &abort_flag, &file, &holes).await?;
+    Ok(true)
+}
+
+/// Fetches `ranges` of `file` concurrently.
+///
+/// On failure the space of the bytes left unwritten is released and the
+/// ranges are recorded in the database.
+async fn fetch_ranges(
+    task: &Arc<RequestTask>,
+    abort_flag: &Arc<AtomicBool>,
+    file: &Arc<Mutex<File>>,
+    ranges: &[(u64, u64)],
+) -> Result<(), TaskError> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let mut handles = Vec::with_capacity(ranges.len());
+    let mut counters = Vec::with_capacity(ranges.len());
+    for range in ranges.iter() {
+        let segment_file = file.lock().unwrap().try_clone()?;
+        let written = Arc::new(AtomicU64::new(0));
+        counters.push(written.clone());
+        handles.push(ylong_runtime::spawn(task.clone().fetch_segment(
+            abort_flag.clone(),
+            segment_file,
+            *range,
+            written,
+            cancel.clone(),
+        )));
+    }
+
+    let mut result = Ok(());
+    for handle in handles {
+        let ret = match handle.await {
+            Ok(ret) => ret,
+            Err(e) => {
+                error!("task {} segment join failed, {:?}", task.task_id(), e);
+                Err(TaskError::Failed(Reason::OthersError))
+            }
+        };
+        if let Err(e) = ret {
+            cancel.store(true, Ordering::Release);
+            // Siblings cancelled by us report a user abort, keep the cause.
+            if result.is_ok() || result == Err(TaskError::Waiting(TaskPhase::UserAbort)) {
+                result = Err(e);
+            }
+        }
+    }
+
+    if let Err(e) = result {
+        let holes = unwritten_ranges(ranges, &counters);
+        let punched = task_control::file_release_unwritten(file.clone(), holes.clone()).await?;
+        info!(
+            "task {} left {} holes, punched {}",
+            task.task_id(),
+            holes.len(),
+            punched
+        );
+        RequestDb::get_instance().update_unwritten_ranges(task.task_id(), &holes);
+        let file = file.lock().unwrap();
+        sparse::record_file_sizes(&file, &mut task.progress.lock().unwrap().extras);
+        return Err(e);
+    }
+
+    task_control::file_sync_all(file.clone()).await?;
+    RequestDb::get_instance().update_unwritten_ranges(task.task_id(), &[]);
+    Ok(())
+}
+
+/// Returns the inclusive byte ranges of `ranges` not fully written.
+fn unwritten_ranges(ranges: &[(u64, u64)], counters: &[Arc<AtomicU64>]) -> Vec<(u64, u64)> {
+    ranges
+        .iter()
+        .zip(counters)
+        .filter_map(|((begin, end), written)| {
+            let written = written.load(Ordering::SeqCst);
+            (begin + written <= *end).then_some((begin + written, *end))
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_segment {
+    include!("../../tests/ut/task/ut_segment.rs");
+}
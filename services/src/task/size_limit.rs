@@ -0,0 +1,102 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Size caps of task files.
+//!
+//! Tasks configured with `max_size_bytes` or `max_total_bytes` fail with
+//! `Reason::SizeLimitExceeded` once a file is larger than allowed. A download
+//! is rejected as soon as its `Content-Length`, counted from the resume
+//! offset, exceeds the cap, and a response of unknown length is cut off once
+//! the bytes written would exceed it. Either way the partial file is removed.
+//!
+//! Uploads check the sizes of their files, each and in total, before sending
+//! anything.
+
+use std::sync::Arc;
+
+use crate::task::reason::Reason;
+use crate::task::request_task::{RequestTask, TaskError};
+use crate::task::task_control;
+
+/// Message of the write error cutting off a download that outgrew its cap.
+pub(crate) const SIZE_LIMIT_EXCEEDED: &str = "size limit exceeded";
+
+/// Returns whether `size` bytes exceed `cap`, `None` for no cap.
+pub(crate) fn exceeds(cap: Option<u64>, size: u64) -> bool {
+    cap.is_some_and(|cap| size > cap)
+}
+
+/// Returns whether uploading files of `sizes` exceeds the cap of each file
+/// or the cap of all files.
+pub(crate) fn upload_exceeds(
+    max_size_bytes: Option<u64>,
+    max_total_bytes: Option<u64>,
+    sizes: &[i64],
+) -> bool {
+    let sizes = sizes.iter().map(|size| (*size).max(0) as u64);
+    sizes.clone().any(|size| exceeds(max_size_bytes, size)) || exceeds(max_total_bytes, sizes.sum())
+}
+
+impl RequestTask {
+    /// Returns whether a download of `size` bytes, including the bytes it
+    /// resumes from, exceeds the caps of the task.
+    pub(crate) fn exceeds_size_cap(&self, size: u64) -> bool {
+        exceeds(self.conf.download_size_cap(), size)
+    }
+
+    /// Returns the size of the file declared by the response, including the
+    /// bytes the download resumes from, if it exceeds the caps of the task.
+    pub(crate) fn oversize_declared(&self) -> Option<u64> {
+        let size = *self.progress.lock().unwrap().sizes.first()?;
+        let size = u64::try_from(size).ok()?;
+        self.exceeds_size_cap(size).then_some(size)
+    }
+
+    /// Fails a download larger than its cap, removing what it wrote.
+    pub(crate) async fn reject_oversize(self: &Arc<Self>, size: u64) -> Result<(), TaskError> {
+        let msg = format!(
+            "task {} size {} exceeds cap {:?}",
+            self.task_id(),
+            size,
+            self.conf.download_size_cap()
+        );
+        error!("{}", msg);
+        sys_event!(ExecFault, DfxCode::TASK_FAULT_09, &msg);
+        task_control::clear_downloaded_file(self.clone()).await?;
+        self.update_progress_in_database();
+        Err(TaskError::Failed(Reason::SizeLimitExceeded))
+    }
+
+    /// Fails an upload whose files exceed the caps of the task.
+    pub(crate) fn check_upload_size_caps(&self) -> Result<(), TaskError> {
+        let sizes = self.progress.lock().unwrap().sizes.clone();
+        if !upload_exceeds(self.conf.max_size_bytes, self.conf.max_total_bytes, &sizes) {
+            return Ok(());
+        }
+        let msg = format!(
+            "task {} upload sizes {:?} exceed caps {:?} {:?}",
+            self.task_id(),
+            sizes,
+            self.conf.max_size_bytes,
+            self.conf.max_total_bytes
+        );
+        error!("{}", msg);
+        sys_event!(ExecFault, DfxCode::TASK_FAULT_09, &msg);
+        Err(TaskError::Failed(Reason::SizeLimitExceeded))
+    }
+}
+
+#[cfg(test)]
+mod ut_size_limit {
+    include!("../../tests/ut/task/ut_size_limit.rs");
+}
@@ -0,0 +1,121 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Releasing disk space of byte ranges a download left unwritten.
+//!
+//! A segmented or preallocated download that stops early may own blocks that
+//! hold no data. Where the file system supports it, those ranges are punched
+//! out and the file keeps its logical size, so ranges written after a gap
+//! survive. Elsewhere only the unwritten space after the last written byte is
+//! truncated away.
+
+use std::collections::HashMap;
+use std::ffi::c_int;
+use std::fs::File;
+use std::io;
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::MetadataExt;
+
+/// `fallocate` mode leaving the file size unchanged.
+const FALLOC_FL_KEEP_SIZE: c_int = 0x01;
+
+/// `fallocate` mode deallocating a range, requires `FALLOC_FL_KEEP_SIZE`.
+const FALLOC_FL_PUNCH_HOLE: c_int = 0x02;
+
+/// Error of `fallocate` modes the file system does not support.
+const EOPNOTSUPP: i32 = 95;
+
+/// Error of `fallocate` on kernels without it.
+const ENOSYS: i32 = 38;
+
+/// Unit of `st_blocks`, independent of the file system block size.
+const STAT_BLOCK_SIZE: u64 = 512;
+
+/// Key in `extras` holding the logical size of a file with holes.
+pub(crate) const LOGICAL_SIZE: &str = "logical_size";
+
+/// Key in `extras` holding the disk space used by a file with holes.
+pub(crate) const PHYSICAL_SIZE: &str = "physical_size";
+
+/// Deallocates `len` bytes at `offset` of `file`, keeping its size.
+pub(crate) fn punch_hole(file: &File, offset: u64, len: u64) -> io::Result<()> {
+    let mode = FALLOC_FL_PUNCH_HOLE | FALLOC_FL_KEEP_SIZE;
+    // The descriptor stays valid while `file` is borrowed.
+    let ret = unsafe { fallocate(file.as_raw_fd(), mode, offset as i64, len as i64) };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Returns the disk space used by `file` in bytes.
+pub(crate) fn disk_usage(file: &File) -> io::Result<u64> {
+    Ok(file.metadata()?.blocks() * STAT_BLOCK_SIZE)
+}
+
+/// Releases the space of the inclusive byte ranges `holes` of `file`.
+///
+/// Returns `true` if the holes were punched. Without hole punching the file
+/// is truncated after its last written byte instead, which keeps the ranges
+/// before it allocated.
+pub(crate) fn release_unwritten(file: &File, holes: &[(u64, u64)]) -> io::Result<bool> {
+    for (begin, end) in holes {
+        match punch_hole(file, *begin, end - begin + 1) {
+            Ok(()) => {}
+            Err(e) if unsupported(&e) => {
+                info!("punch hole unsupported, {:?}", e);
+                let len = file.metadata()?.len();
+                file.set_len(written_end(holes, len))?;
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+/// Returns where the unwritten space at the end of a file of `len` bytes
+/// begins.
+fn written_end(holes: &[(u64, u64)], len: u64) -> u64 {
+    match holes.last() {
+        Some((begin, end)) if *end + 1 >= len => (*begin).min(len),
+        _ => len,
+    }
+}
+
+/// Returns whether `e` reports a file system without hole punching.
+fn unsupported(e: &io::Error) -> bool {
+    matches!(e.raw_os_error(), Some(EOPNOTSUPP) | Some(ENOSYS))
+}
+
+/// Records the logical and physical size of `file` in `extras`.
+pub(crate) fn record_file_sizes(file: &File, extras: &mut HashMap<String, String>) {
+    let Ok(metadata) = file.metadata() else {
+        return;
+    };
+    extras.insert(LOGICAL_SIZE.to_string(), metadata.len().to_string());
+    extras.insert(
+        PHYSICAL_SIZE.to_string(),
+        (metadata.blocks() * STAT_BLOCK_SIZE).to_string(),
+    );
+}
+
+extern "C" {
+    fn fallocate(fd: c_int, mode: c_int, offset: i64, len: i64) -> c_int;
+}
+
+#[cfg(test)]
+mod ut_sparse {
+    include!("../../tests/ut/task/ut_sparse.rs");
+}
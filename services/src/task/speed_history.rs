@@ -0,0 +1,137 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Download and upload speed history of tasks.
+//!
+//! A running task samples its processed bytes at most once per
+//! [`SAMPLE_INTERVAL_MS`] into a ring buffer of [`SPEED_HISTORY_LEN`]
+//! samples, so clients can graph its recent throughput. When a task stops
+//! running its last window is kept for the [`RECENT_TASKS`] most recently
+//! stopped tasks.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Maximum number of samples kept per task.
+pub(crate) const SPEED_HISTORY_LEN: usize = 60;
+
+/// Minimum time between two samples in milliseconds.
+const SAMPLE_INTERVAL_MS: u64 = 1000;
+
+/// Number of stopped tasks whose last window is kept.
+const RECENT_TASKS: usize = 32;
+
+/// Last windows of recently stopped tasks, oldest first.
+static RECENT: Mutex<VecDeque<(u32, Vec<SpeedSample>)>> = Mutex::new(VecDeque::new());
+
+/// Processed bytes of a task at a point in time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SpeedSample {
+    /// Timestamp of the sample in milliseconds.
+    pub(crate) time: u64,
+    /// Total bytes processed at `time`.
+    pub(crate) processed: u64,
+}
+
+/// Ring buffer of the recent samples of a task.
+#[derive(Debug, Default)]
+pub(crate) struct SpeedHistory {
+    samples: VecDeque<SpeedSample>,
+}
+
+impl SpeedHistory {
+    /// Records the processed bytes of the task at `now`.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current timestamp in milliseconds.
+    /// * `processed` - Total bytes processed so far.
+    ///
+    /// # Notes
+    ///
+    /// Samples closer than [`SAMPLE_INTERVAL_MS`] to the previous one are
+    /// dropped. Progress going backwards, e.g. after a restart from the
+    /// beginning, discards the history.
+    pub(crate) fn record(&mut self, now: u64, processed: u64) {
+        if let Some(last) = self.samples.back() {
+            if processed < last.processed || now < last.time {
+                self.samples.clear();
+            } else if now - last.time < SAMPLE_INTERVAL_MS {
+                return;
+            }
+        }
+        self.push(now, processed);
+    }
+
+    /// Records the last sample of a run regardless of the sample interval,
+    /// unless nothing was processed since the previous sample.
+    pub(crate) fn finish(&mut self, now: u64, processed: u64) {
+        match self.samples.back() {
+            Some(last) if processed == last.processed => {}
+            Some(last) if processed < last.processed || now < last.time => {
+                self.samples.clear();
+                self.push(now, processed);
+            }
+            _ => self.push(now, processed),
+        }
+    }
+
+    /// Returns the samples, oldest first.
+    pub(crate) fn samples(&self) -> Vec<SpeedSample> {
+        self.samples.iter().copied().collect()
+    }
+
+    fn push(&mut self, now: u64, processed: u64) {
+        if self.samples.len() == SPEED_HISTORY_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(SpeedSample {
+            time: now,
+            processed,
+        });
+    }
+}
+
+/// Keeps the last window of a task that stopped running, evicting the
+/// window of the least recently stopped task if [`RECENT_TASKS`] are kept.
+pub(crate) fn keep_recent(task_id: u32, samples: Vec<SpeedSample>) {
+    let mut recent = RECENT.lock().unwrap();
+    recent.retain(|(id, _)| *id != task_id);
+    if recent.len() == RECENT_TASKS {
+        recent.pop_front();
+    }
+    recent.push_back((task_id, samples));
+}
+
+/// Returns the last window of a recently stopped task.
+pub(crate) fn recent(task_id: u32) -> Option<Vec<SpeedSample>> {
+    RECENT
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(id, _)| *id == task_id)
+        .map(|(_, samples)| samples.clone())
+}
+
+/// Returns the last `points` of `samples`, all of them if `points` is 0.
+pub(crate) fn last_points(mut samples: Vec<SpeedSample>, points: usize) -> Vec<SpeedSample> {
+    if points != 0 && samples.len() > points {
+        samples.drain(..samples.len() - points);
+    }
+    samples
+}
+
+#[cfg(test)]
+mod ut_speed_history {
+    include!("../../tests/ut/task/ut_speed_history.rs");
+}
@@ -16,13 +16,21 @@
 //! This module provides utility functions for spawning blocking operations in async context
 //! and performing file operations in a thread-safe manner, primarily used for HTTP request tasks.
 
+use std::ffi::c_int;
 use std::fs::{File, Metadata};
 use std::io::{self, Seek, SeekFrom, Write};
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::FileExt;
 use std::sync::{Arc, Mutex};
 
 use ylong_runtime::task::JoinHandle;
 
+use crate::manage::database::RequestDb;
 use crate::task::request_task::RequestTask;
+use crate::task::sparse;
+
+/// `fallocate` mode allocating space without changing the file size.
+const FALLOC_FL_KEEP_SIZE: c_int = 0x01;
 
 /// Spawns a blocking operation that returns a result.
 /// 
@@ -143,6 +151,38 @@ pub(crate) async fn file_metadata(file: Arc<Mutex<File>>) -> io::Result<Metadata
     .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
 }
 
+/// Reads bytes at a position of a file asynchronously, leaving the file
+/// position unchanged.
+/// 
+/// # Arguments
+/// 
+/// * `file` - A thread-safe reference to the file.
+/// * `pos` - The position of the first byte to read.
+/// * `len` - The number of bytes to read.
+/// 
+/// # Returns
+/// 
+/// The bytes read.
+/// 
+/// # Errors
+/// 
+/// Returns an error if the file cannot be read, ends before `len` bytes, or
+/// if the blocking task fails.
+pub(crate) async fn file_read_at(
+    file: Arc<Mutex<File>>,
+    pos: u64,
+    len: usize,
+) -> io::Result<Vec<u8>> {
+    runtime_spawn_blocking(move || {
+        let file = file.lock().unwrap();
+        let mut buf = vec![0; len];
+        file.read_exact_at(&mut buf, pos)?;
+        Ok(buf)
+    })
+    .await
+    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+}
+
 /// Sets the length of a file asynchronously.
 /// 
 /// # Arguments
@@ -167,6 +207,68 @@ pub(crate) async fn file_set_len(file: Arc<Mutex<File>>, size: u64) -> io::Resul
     .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
 }
 
+/// Allocates disk space for the first `len` bytes of a file asynchronously.
+/// 
+/// The file size is left unchanged, since downloads resume from the end of
+/// the file.
+/// 
+/// # Arguments
+/// 
+/// * `file` - A thread-safe reference to the file.
+/// * `len` - The number of bytes to allocate.
+/// 
+/// # Returns
+/// 
+/// `Ok(())` if the operation succeeds.
+/// 
+/// # Errors
+/// 
+/// Returns the OS error if the space cannot be allocated, e.g. `ENOSPC` if the
+/// storage is full or `EOPNOTSUPP` if the file system does not support it.
+pub(crate) async fn file_preallocate(file: Arc<Mutex<File>>, len: u64) -> io::Result<()> {
+    runtime_spawn_blocking(move || {
+        let file = file.lock().unwrap();
+        // The descriptor stays valid while the file lock is held.
+        let ret = unsafe { fallocate(file.as_raw_fd(), FALLOC_FL_KEEP_SIZE, 0, len as i64) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    })
+    .await
+    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+}
+
+/// Releases the disk space of unwritten byte ranges of a file asynchronously.
+///
+/// # Arguments
+///
+/// * `file` - A thread-safe reference to the file.
+/// * `holes` - The inclusive byte ranges left unwritten.
+///
+/// # Returns
+///
+/// `Ok(true)` if the ranges were punched out, `Ok(false)` if the file system
+/// cannot punch holes and the file was truncated after its last written byte.
+///
+/// # Errors
+///
+/// Returns an error if the space cannot be released or if the blocking task
+/// fails.
+pub(crate) async fn file_release_unwritten(
+    file: Arc<Mutex<File>>,
+    holes: Vec<(u64, u64)>,
+) -> io::Result<bool> {
+    runtime_spawn_blocking(move || {
+        let mut file = file.lock().unwrap();
+        file.flush()?;
+        sparse::release_unwritten(&file, &holes)
+    })
+    .await
+    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+}
+
 /// Writes all bytes from a buffer to a file asynchronously.
 /// 
 /// # Arguments
@@ -226,10 +328,12 @@ pub(crate) async fn clear_downloaded_file(task: Arc<RequestTask>) -> Result<(),
             file.set_len(0)?; // Truncate the file to zero length
             file.seek(SeekFrom::Start(0))?; // Reset file position
         }
+        RequestDb::get_instance().update_unwritten_ranges(task.task_id(), &[]);
         
         // Reset progress tracking
         {
             let mut progress_guard = task.progress.lock().unwrap();
+            task.counter.take(); // Drop bytes counted for the cleared file
             progress_guard.common_data.total_processed = 0;
             if let Some(elem) = progress_guard.processed.get_mut(0) {
                 *elem = 0; // Reset individual file progress
@@ -242,3 +346,7 @@ pub(crate) async fn clear_downloaded_file(task: Arc<RequestTask>) -> Result<(),
     .await
     .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
 }
+
+extern "C" {
+    fn fallocate(fd: c_int, mode: c_int, offset: i64, len: i64) -> c_int;
+}
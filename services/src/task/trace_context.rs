@@ -0,0 +1,195 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! W3C trace context propagation of tasks.
+//!
+//! A task configured with `trace_context` sends its `traceparent`, and its
+//! `tracestate` if any, with every request it makes. Each attempt gets a new
+//! span id under the trace id of the task, so that retries show up as sibling
+//! spans of one trace, and the redirects followed within an attempt carry the
+//! headers of that attempt. The task headers take precedence: none of the
+//! context is sent if they hold a `traceparent`, and their `tracestate` is
+//! sent instead of the one of the context.
+//!
+//! Tasks created without a context by an application whose uid has tracing
+//! enabled get a generated one. The trace id, never the span id, is recorded
+//! under `trace_id` in the progress `extras` of the task and in its fault
+//! reports.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use ylong_runtime::fastrand::fast_random;
+
+use crate::config::{TaskConfig, TraceContext};
+use crate::task::request_task::RequestTask;
+
+/// Name of the header carrying the trace and span ids of a request.
+pub(crate) const TRACEPARENT: &str = "traceparent";
+
+/// Name of the header carrying vendor specific trace data.
+pub(crate) const TRACESTATE: &str = "tracestate";
+
+/// Key in `extras` holding the trace id of a task.
+pub(crate) const TRACE_ID: &str = "trace_id";
+
+/// Longest `tracestate` accepted, as recommended by the specification.
+const MAX_TRACESTATE_LEN: usize = 512;
+
+/// Uids whose tasks get a generated trace context.
+static TRACED_UIDS: Mutex<Option<HashSet<u64>>> = Mutex::new(None);
+
+/// Returns whether `context` has a valid `traceparent` and a `tracestate`
+/// that may be sent as a header.
+pub(crate) fn is_valid(context: &TraceContext) -> bool {
+    is_valid_traceparent(&context.traceparent)
+        && context.tracestate.as_deref().map_or(true, |state| {
+            state.len() <= MAX_TRACESTATE_LEN && state.bytes().all(|b| (0x20..0x7f).contains(&b))
+        })
+}
+
+/// Returns whether `value` is a valid `traceparent`, a version, a trace id, a
+/// parent id and flags in lowercase hex separated by dashes. Ids must not be
+/// all zeros, version `ff` is invalid and versions after `00` may append
+/// fields.
+pub(crate) fn is_valid_traceparent(value: &str) -> bool {
+    let fields: Vec<&str> = value.split('-').collect();
+    if fields.len() < 4 || !is_hex(fields[0], 2) || fields[0] == "ff" {
+        return false;
+    }
+    if fields[0] == "00" && fields.len() != 4 {
+        return false;
+    }
+    is_hex(fields[1], 32)
+        && is_hex(fields[2], 16)
+        && is_hex(fields[3], 2)
+        && !is_zero(fields[1])
+        && !is_zero(fields[2])
+}
+
+fn is_hex(field: &str, len: usize) -> bool {
+    field.len() == len
+        && field
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn is_zero(field: &str) -> bool {
+    field.bytes().all(|b| b == b'0')
+}
+
+/// Returns the trace id of a valid `traceparent`.
+pub(crate) fn trace_id(traceparent: &str) -> &str {
+    traceparent.split('-').nth(1).unwrap_or_default()
+}
+
+/// Returns `traceparent` with a new random span id.
+pub(crate) fn with_new_span(traceparent: &str) -> String {
+    let span = format!("{:016x}", fast_random().max(1));
+    let mut fields: Vec<&str> = traceparent.split('-').collect();
+    if let Some(field) = fields.get_mut(2) {
+        *field = &span;
+    }
+    fields.join("-")
+}
+
+/// Generates a sampled `traceparent` with a random trace id and span id.
+pub(crate) fn generate_traceparent() -> String {
+    format!(
+        "00-{:016x}{:016x}-{:016x}-01",
+        fast_random(),
+        fast_random().max(1),
+        fast_random().max(1)
+    )
+}
+
+/// Enables or disables generated trace contexts for the tasks of `uid`.
+pub(crate) fn set_uid_tracing(uid: u64, enabled: bool) {
+    let mut uids = TRACED_UIDS.lock().unwrap();
+    let uids = uids.get_or_insert_with(HashSet::new);
+    if enabled {
+        uids.insert(uid);
+    } else {
+        uids.remove(&uid);
+    }
+}
+
+/// Gives `config` a generated trace context if it has none and tracing is
+/// enabled for its uid.
+pub(crate) fn apply_policy(config: &mut TaskConfig) {
+    if config.trace_context.is_some() {
+        return;
+    }
+    let traced = TRACED_UIDS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|uids| uids.contains(&config.common_data.uid));
+    if traced {
+        config.trace_context = Some(TraceContext {
+            traceparent: generate_traceparent(),
+            tracestate: None,
+        });
+    }
+}
+
+/// Adds the trace context headers of one attempt to `headers`, sorted by
+/// name, unless they already hold a `traceparent`. A `tracestate` already in
+/// `headers` is kept.
+pub(crate) fn inject(headers: &mut Vec<(String, String)>, context: &TraceContext) {
+    if headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(TRACEPARENT))
+    {
+        return;
+    }
+    let has_state = headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(TRACESTATE));
+    headers.push((TRACEPARENT.to_string(), with_new_span(&context.traceparent)));
+    match &context.tracestate {
+        Some(state) if !has_state => headers.push((TRACESTATE.to_string(), state.clone())),
+        _ => {}
+    }
+    headers.sort();
+}
+
+impl RequestTask {
+    /// Records the trace id of the task in its progress `extras`.
+    pub(crate) fn record_trace_id(&self, extras: &mut HashMap<String, String>) {
+        if let Some(context) = &self.conf.trace_context {
+            extras.insert(
+                TRACE_ID.to_string(),
+                trace_id(&context.traceparent).to_string(),
+            );
+        }
+    }
+
+    /// Returns the task id, followed by the trace id if the task has one, to
+    /// identify the task in fault reports.
+    pub(crate) fn fault_label(&self) -> String {
+        match &self.conf.trace_context {
+            Some(context) => format!(
+                "{} trace {}",
+                self.task_id(),
+                trace_id(&context.traceparent)
+            ),
+            None => self.task_id().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_trace_context {
+    include!("../../tests/ut/task/ut_trace_context.rs");
+}
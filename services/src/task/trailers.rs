@@ -0,0 +1,101 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Trailers of download responses.
+//!
+//! Some servers send metadata such as checksums in trailers after a chunked
+//! body. The `Downloader` consumes the response and discards them, so
+//! responses announcing trailers with a `Trailer` header are read here
+//! instead. Once the body is read, the trailers are merged into the progress
+//! `extras` and, on OH devices, the response is notified again with the
+//! trailers merged into its headers. Responses that announce trailers but send none complete as
+//! any other response.
+
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ylong_http_client::async_impl::{Body, DownloadOperator, Response};
+use ylong_http_client::{Headers, HttpClientError};
+
+use super::download::LOW_SPEED_TIME;
+use super::operator::TaskOperator;
+use crate::task::request_task::{RequestTask, MAX_RESPONSE_HEADERS};
+
+/// Size of the buffer the body is read into.
+const READ_SIZE: usize = 16 * 1024;
+
+/// Checks if a response announces trailers.
+pub(crate) fn announces_trailers(response: &Response) -> bool {
+    response.headers().get("trailer").is_some()
+}
+
+/// Downloads the body of a response announcing trailers and delivers the
+/// trailers once it is read.
+///
+/// The body is written by a `TaskOperator` as with the `Downloader`, and a
+/// connection sending nothing for `LOW_SPEED_TIME` seconds fails the same
+/// way as one below its minimum speed.
+pub(crate) async fn download_with_trailers(
+    task: Arc<RequestTask>,
+    mut response: Response,
+    abort_flag: Arc<AtomicBool>,
+) -> Result<(), HttpClientError> {
+    let mut operator = TaskOperator::new(task.clone(), abort_flag);
+    let mut buf = vec![0u8; READ_SIZE];
+    loop {
+        poll_fn(|cx| Pin::new(&mut operator).poll_progress(cx, 0, None)).await?;
+        let read = ylong_runtime::time::timeout(
+            Duration::from_secs(LOW_SPEED_TIME),
+            response.data(&mut buf),
+        )
+        .await;
+        let size = match read {
+            Ok(size) => size?,
+            Err(_) => return Err(HttpClientError::other("download speed too low")),
+        };
+        if size == 0 {
+            break;
+        }
+        let mut data = &buf[..size];
+        while !data.is_empty() {
+            let written = poll_fn(|cx| Pin::new(&mut operator).poll_download(cx, data)).await?;
+            data = &data[written..];
+        }
+    }
+
+    if let Some(trailers) = response.trailer().await? {
+        record_trailers(&task, &trailers);
+        #[cfg(feature = "oh")]
+        task.notify_response_trailers(&response, &trailers);
+    }
+    Ok(())
+}
+
+/// Merges `trailers` into the progress `extras`, at most
+/// `MAX_RESPONSE_HEADERS` of them.
+fn record_trailers(task: &RequestTask, trailers: &Headers) {
+    let max = MAX_RESPONSE_HEADERS.load(Ordering::Relaxed);
+    let mut guard = task.progress.lock().unwrap();
+    let mut count = 0;
+    for (k, v) in trailers.into_iter().take(max) {
+        if let Ok(v) = v.to_string() {
+            guard.extras.insert(k.to_string().to_lowercase(), v);
+            count += 1;
+        }
+    }
+    info!("task {} response sent {} trailers", task.task_id(), count);
+}
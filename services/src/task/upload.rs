@@ -14,8 +14,8 @@
 //! Upload functionality for HTTP request tasks.
 //! 
 //! This module provides the implementation for file upload operations, including stream uploads,
-//! multipart form data uploads, and batch uploads. It handles file reading, progress tracking,
-//! request construction, and error handling for upload tasks.
+//! multipart form data uploads, batch uploads and concatenated uploads. It handles file reading,
+//! progress tracking, request construction, and error handling for upload tasks.
 
 use std::future::Future;
 use std::io::{Read, SeekFrom};
@@ -33,11 +33,12 @@ use super::info::State;
 use super::operator::TaskOperator;
 use super::reason::Reason;
 use super::request_task::{TaskError, TaskPhase};
-use super::task_control;
+use super::{handshake, task_control};
 use crate::manage::database::RequestDb;
 use crate::task::request_task::RequestTask;
 #[cfg(feature = "oh")]
 use crate::trace::Trace;
+use crate::utils::form_item::form_urlencode;
 use crate::utils::get_current_duration;
 
 /// A reader that reads data from a task's file for upload operations.
@@ -184,6 +185,103 @@ impl ReusableReader for TaskReader {
     }
 }
 
+/// A reader that reads the files of a task one after another, for uploads
+/// sending them as a single body.
+struct ConcatReader {
+    /// The request task containing the files to read.
+    task: Arc<RequestTask>,
+    /// The index of the file currently read.
+    index: usize,
+    /// Bytes read from the current file.
+    read: usize,
+    /// Whether the body is sent again, which is not counted as progress.
+    reused: bool,
+}
+
+impl ConcatReader {
+    /// Creates a new `ConcatReader` starting at the first file of the task.
+    fn new(task: Arc<RequestTask>) -> Self {
+        Self {
+            task,
+            index: 0,
+            read: 0,
+            reused: false,
+        }
+    }
+}
+
+impl AsyncRead for ConcatReader {
+    /// Reads from the current file, moving to the next one once it reached
+    /// its size or, for a size unknown, its end.
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        loop {
+            // All files read, the body ends.
+            let Some(file) = self.task.files.get(self.index) else {
+                return Poll::Ready(Ok(()));
+            };
+            let index = self.index;
+            let size = self.task.progress.lock().unwrap().sizes[index];
+            let rest = match size {
+                size if size < 0 => usize::MAX,
+                size => size as usize - self.read.min(size as usize),
+            };
+
+            let filled = buf.filled().len();
+            let unfilled = buf.initialize_unfilled();
+            let want = unfilled.len().min(rest);
+            let read = match want {
+                0 => 0,
+                want => file.lock().unwrap().read(&mut unfilled[..want])?,
+            };
+            if read == 0 {
+                if unfilled.is_empty() {
+                    return Poll::Ready(Ok(()));
+                }
+                self.index += 1;
+                self.read = 0;
+                continue;
+            }
+            buf.set_filled(filled + read);
+            self.read += read;
+
+            if !self.reused {
+                let mut progress = self.task.progress.lock().unwrap();
+                progress.processed[index] += read;
+                progress.common_data.total_processed += read;
+                progress.common_data.index = index;
+            }
+            return Poll::Ready(Ok(()));
+        }
+    }
+}
+
+impl ReusableReader for ConcatReader {
+    /// Rewinds all files to send the body again from its start.
+    fn reuse<'a>(
+        &'a mut self,
+    ) -> Pin<Box<dyn Future<Output = std::io::Result<()>> + Send + Sync + 'a>>
+    where
+        Self: 'a,
+    {
+        self.index = 0;
+        self.read = 0;
+        self.reused = true;
+        let files: Vec<_> = (0..self.task.files.len())
+            .filter_map(|index| self.task.files.get(index))
+            .collect();
+        Box::pin(async move {
+            for file in files {
+                task_control::file_rewind(file).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
 impl UploadOperator for TaskOperator {
     /// Polls for progress updates during upload operations.
     /// 
@@ -264,6 +362,91 @@ fn build_stream_request(
     }
 }
 
+/// Builds an upload request whose body is all files of the task in order.
+///
+/// The body has a `Content-Length` of the summed file sizes, or is chunked if
+/// the size of a file is unknown.
+///
+/// # Arguments
+///
+/// * `task` - The request task containing the files to upload.
+/// * `_index` - Unused index parameter, the body always starts at file 0.
+/// * `abort_flag` - Atomic flag to signal upload cancellation.
+///
+/// # Returns
+///
+/// A `Request` if successful, or `None` if construction fails.
+fn build_concatenated_request(
+    task: Arc<RequestTask>,
+    _index: usize,
+    abort_flag: Arc<AtomicBool>,
+) -> Option<Request> {
+    debug!("build concatenated request");
+    let task_reader = ConcatReader::new(task.clone());
+    let task_operator = TaskOperator::new(task.clone(), abort_flag);
+
+    match task.build_request_builder() {
+        Ok(mut request_builder) => {
+            if !task.conf.headers.contains_key("Content-Type") {
+                request_builder =
+                    request_builder.header("Content-Type", "application/octet-stream");
+            }
+
+            let sizes = task.progress.lock().unwrap().sizes.clone();
+            let upload_length = match sizes.iter().all(|size| *size >= 0) {
+                true => Some(sizes.iter().sum::<i64>() as u64),
+                false => None,
+            };
+            debug!("upload length is {:?}", upload_length);
+
+            request_builder = match upload_length {
+                Some(length) => {
+                    request_builder.header("Content-Length", length.to_string().as_str())
+                }
+                None => request_builder.header("Transfer-Encoding", "chunked"),
+            };
+
+            let uploader = Uploader::builder()
+                .reader(task_reader)
+                .operator(task_operator)
+                .total_bytes(upload_length)
+                .build();
+            let request = request_builder.body(Body::stream(uploader));
+            build_request_common(&task, 0, request)
+        }
+        Err(err) => build_request_common(&task, 0, Err(err)),
+    }
+}
+
+/// Builds an upload request whose body is the urlencoded form items.
+/// 
+/// # Arguments
+/// 
+/// * `task` - The request task containing the form items.
+/// * `_index` - Unused index parameter, the form is always tracked as index 0.
+/// * `_abort_flag` - Unused, the body is sent in one piece.
+/// 
+/// # Returns
+/// 
+/// A `Request` if successful, or `None` if construction fails.
+fn build_form_urlencoded_request(
+    task: Arc<RequestTask>,
+    _index: usize,
+    _abort_flag: Arc<AtomicBool>,
+) -> Option<Request> {
+    debug!("build urlencoded form request");
+    let body = form_urlencode(&task.conf.form_items);
+    match task.build_request_builder() {
+        Ok(request_builder) => {
+            let request = request_builder
+                .header("Content-Length", body.len().to_string().as_str())
+                .body(Body::slice(body));
+            build_request_common(&task, 0, request)
+        }
+        Err(err) => build_request_common(&task, 0, Err(err)),
+    }
+}
+
 /// Builds a multipart form-data upload request for a single file.
 /// 
 /// Constructs an HTTP request with multipart form data for file uploads,
@@ -433,6 +616,18 @@ fn build_request_common(
 }
 
 impl RequestTask {
+    /// Ends the upload if a soft stop was requested.
+    ///
+    /// Checked between files only, so a soft stop never tears the file in
+    /// flight.
+    fn check_soft_stop(&self) -> Result<(), TaskError> {
+        if self.soft_stop.load(Ordering::Acquire) {
+            info!("task {} soft stopped", self.task_id());
+            return Err(TaskError::Waiting(TaskPhase::UserAbort));
+        }
+        Ok(())
+    }
+
     /// Prepares a single file for upload.
     /// 
     /// Resets progress tracking if not resuming, sets the current file index,
@@ -492,6 +687,41 @@ impl RequestTask {
         .is_ok()
     }
 
+    /// Prepares the files of a concatenated upload.
+    ///
+    /// The body cannot be resumed in the middle, so progress is reset and all
+    /// files of a known size are rewound to their start. Files of an unknown
+    /// size, like pipes, cannot be rewound and are read on from where they are.
+    ///
+    /// # Returns
+    ///
+    /// `true` if preparation succeeded for all files, `false` otherwise.
+    async fn prepare_concatenated_upload(&self) -> bool {
+        {
+            let mut progress = self.progress.lock().unwrap();
+            self.upload_resume.store(false, Ordering::SeqCst);
+            progress.processed.fill(0);
+            progress.common_data.index = 0;
+            progress.common_data.total_processed = 0;
+        }
+
+        let sizes = self.progress.lock().unwrap().sizes.clone();
+        for (index, size) in sizes.iter().enumerate() {
+            let Some(file) = self.files.get(index) else {
+                error!("task {} file {} not found", self.task_id(), index);
+                return false;
+            };
+            if *size < 0 {
+                continue;
+            }
+            if let Err(e) = task_control::file_rewind(file).await {
+                error!("file rewind err:{:}", e);
+                return false;
+            }
+        }
+        true
+    }
+
     /// Prepares multiple files for batch upload.
     /// 
     /// Determines the current file index based on total processed bytes,
@@ -587,6 +817,7 @@ pub(crate) async fn upload(task: Arc<RequestTask>, abort_flag: Arc<AtomicBool>)
     // Set task state to running
     task.progress.lock().unwrap().common_data.state = State::Running.repr;
     task.tries.store(0, Ordering::SeqCst);
+    task.refresh_client_timeout().await;
     
     // Main upload loop with retry logic
     loop {
@@ -599,8 +830,13 @@ pub(crate) async fn upload(task: Arc<RequestTask>, abort_flag: Arc<AtomicBool>)
                 TaskError::Waiting(phase) => match phase {
                     TaskPhase::NeedRetry => {
                         // Retry the upload
+                        task_debug!(task.log, "retry upload");
                         continue;
                     }
+                    TaskPhase::RateLimited(delay) => {
+                        // The scheduler retries once the server's delay elapsed
+                        task.end_rate_limited(delay);
+                    }
                     TaskPhase::UserAbort => {
                         // User requested abort, end without setting error
                     }
@@ -611,7 +847,10 @@ pub(crate) async fn upload(task: Arc<RequestTask>, abort_flag: Arc<AtomicBool>)
                 },
             }
         } else {
-            // Upload succeeded
+            // Upload succeeded, keep the response body for later queries
+            if let Some(body) = task.response_body.lock().unwrap().as_ref() {
+                RequestDb::get_instance().update_task_response_body(task.task_id(), body);
+            }
             *task.running_result.lock().unwrap() = Some(Ok(()));
         }
         break;
@@ -636,6 +875,9 @@ async fn upload_inner(
     abort_flag: Arc<AtomicBool>,
 ) -> Result<(), TaskError> {
     info!("upload task {} running", task.task_id());
+    task.check_soft_stop()?;
+    task.check_upload_size_caps()?;
+    task.check_bound_network()?;
 
     #[cfg(feature = "oh")]
     let _trace = Trace::new(&format!(
@@ -652,7 +894,44 @@ async fn upload_inner(
     task.start_time.store(start_time as u64, Ordering::SeqCst);
 
     // Handle different upload modes
-    if task.conf.common_data.multipart {
+    if task.conf.is_form_urlencoded() {
+        // Form fields only, sent as one urlencoded body
+        {
+            let mut progress = task.progress.lock().unwrap();
+            progress.processed[0] = 0;
+            progress.common_data.index = 0;
+            progress.common_data.total_processed = 0;
+        }
+        upload_one_file(task.clone(), 0, abort_flag.clone(), build_form_urlencoded_request).await?;
+
+        let mut progress = task.progress.lock().unwrap();
+        let size = progress.sizes[0] as usize;
+        progress.processed[0] = size;
+        progress.common_data.total_processed = size;
+    } else if task.conf.concatenated {
+        // All files sent as one body
+        #[cfg(feature = "oh")]
+        let _trace = Trace::new(&format!("upload concatenated:{}", task.task_id()));
+
+        if !task.prepare_concatenated_upload().await {
+            return Err(TaskError::Failed(Reason::OthersError));
+        }
+        upload_one_file(
+            task.clone(),
+            0,
+            abort_flag.clone(),
+            build_concatenated_request,
+        )
+        .await?;
+
+        // Files of unknown size are known once read to their end.
+        let mut progress = task.progress.lock().unwrap();
+        for index in 0..progress.sizes.len() {
+            if progress.sizes[index] < 0 {
+                progress.sizes[index] = progress.processed[index] as i64;
+            }
+        }
+    } else if task.conf.common_data.multipart {
         // Batch multipart upload mode
         #[cfg(feature = "oh")]
         let _trace = Trace::new(&format!("upload file:{} index:{}", task.task_id(), start));
@@ -679,6 +958,8 @@ async fn upload_inner(
         
         // Upload files one by one
         for index in start..size {
+            task.check_soft_stop()?;
+
             #[cfg(feature = "oh")]
             let _trace = Trace::new(&format!("upload file:{} index:{}", task.task_id(), index));
 
@@ -796,13 +1077,24 @@ where
     );
 
     // Build the upload request
-    let Some(request) = build_upload_request(task.clone(), index, abort_flag) else {
+    let Some(mut request) = build_upload_request(task.clone(), index, abort_flag.clone()) else {
         return Err(TaskError::Failed(Reason::BuildRequestFailed));
     };
 
-    // Execute the request
+    // Execute the request, retrying transient handshake failures
     let client = task.client.lock().await;
-    let response = client.request(request).await;
+    let mut retried = 0;
+    let response = loop {
+        let response = client.request(request).await;
+        let fault = response.as_ref().err().and_then(handshake::tls_fault);
+        if !task.retry_handshake(fault, &mut retried).await {
+            break response;
+        }
+        request = match build_upload_request(task.clone(), index, abort_flag.clone()) {
+            Some(request) => request,
+            None => return Err(TaskError::Failed(Reason::BuildRequestFailed)),
+        };
+    };
     
     // Process the response
     match response.as_ref() {
@@ -814,7 +1106,20 @@ where
                 "{} response {}",
                 task.conf.common_data.task_id, status_code,
             );
+            task.debug_response(response);
+            task.record_protocol(response);
             
+            if let Some(delay) = task.rate_limit_delay(response) {
+                task.check_rate_limit(delay)?;
+                return Err(TaskError::Waiting(TaskPhase::RateLimited(delay)));
+            }
+
+            // The remote object changed since the client last saw it.
+            if status_code.as_u16() == 412 {
+                info!("task {} upload precondition failed", task.task_id());
+                return Err(TaskError::Failed(Reason::PreconditionFailed));
+            }
+
             // Handle various HTTP status codes
             if status_code.is_server_error()
                 || (status_code.as_u16() != 408 && status_code.is_client_error())
@@ -825,12 +1130,15 @@ where
             
             // Special handling for timeout status (408)
             if status_code.as_u16() == 408 {
+                task.check_retry_deadline()?;
                 if task.timeout_tries.load(Ordering::SeqCst) < 2 {
                     // Retry on timeout, but limit retry attempts
                     task.timeout_tries.fetch_add(1, Ordering::SeqCst);
+                    task_debug!(task.log, "request timeout, retry");
                     return Err(TaskError::Waiting(TaskPhase::NeedRetry));
                 } else {
                     // Too many timeout retries, fail permanently
+                    task_debug!(task.log, "request timeout, retries used");
                     return Err(TaskError::Failed(Reason::ProtocolError));
                 }
             } else {
@@ -851,7 +1159,7 @@ where
                 ErrorKind::Redirect => return Err(TaskError::Failed(Reason::RedirectError)),
                 ErrorKind::Connect | ErrorKind::ConnectionUpgrade => {
                     // Handle connection errors with retry logic
-                    task.network_retry().await?;
+                    task.connect_retry(handshake::tls_fault(e)).await?;
                     if e.is_dns_error() {
                         return Err(TaskError::Failed(Reason::Dns));
                     } else if e.is_tls_error() {
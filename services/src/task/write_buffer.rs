@@ -0,0 +1,236 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bounded buffering of download writes.
+//!
+//! Body chunks of a download are written to its file on the blocking pool,
+//! so a slow disk does not stall the runtime. Chunks accepted but not yet
+//! written count against a per-task and a service-wide limit of in-flight
+//! bytes; once either is reached the download stops reading its body until
+//! writes drain. A task with nothing in flight always gets one chunk
+//! through, so a chunk larger than the limits cannot block a task forever.
+//!
+//! The limits default to [`DEFAULT_TASK_LIMIT`] and [`DEFAULT_LIMIT`] and may
+//! be overridden by the [`TASK_LIMIT_PARAM`] and [`LIMIT_PARAM`] system
+//! parameters.
+
+use std::collections::VecDeque;
+use std::future::poll_fn;
+use std::io::{self, Write};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use crate::task::task_control::runtime_spawn_blocking;
+
+/// In-flight bytes a task may have.
+pub(crate) const DEFAULT_TASK_LIMIT: usize = 4 * 1024 * 1024;
+/// In-flight bytes all tasks may have together.
+pub(crate) const DEFAULT_LIMIT: usize = 32 * 1024 * 1024;
+/// System parameter overriding the per-task limit.
+pub(crate) const TASK_LIMIT_PARAM: &str = "const.request.task_buffer_bytes";
+/// System parameter overriding the service-wide limit.
+pub(crate) const LIMIT_PARAM: &str = "const.request.buffer_bytes";
+
+/// Limits of in-flight bytes and the bytes in flight in the whole service.
+pub(crate) struct BufferBudget {
+    task_limit: usize,
+    limit: usize,
+    in_flight: Mutex<(usize, Vec<Waker>)>,
+}
+
+impl BufferBudget {
+    /// Returns the budget configured by the system parameters.
+    pub(crate) fn get_instance() -> &'static Self {
+        static INSTANCE: LazyLock<BufferBudget> = LazyLock::new(|| {
+            #[cfg(feature = "oh")]
+            let (task_limit, limit) = (
+                crate::utils::get_int_parameter(TASK_LIMIT_PARAM, DEFAULT_TASK_LIMIT as i64),
+                crate::utils::get_int_parameter(LIMIT_PARAM, DEFAULT_LIMIT as i64),
+            );
+            #[cfg(not(feature = "oh"))]
+            let (task_limit, limit) = (DEFAULT_TASK_LIMIT as i64, DEFAULT_LIMIT as i64);
+            let task_limit = usize::try_from(task_limit).unwrap_or(DEFAULT_TASK_LIMIT);
+            let limit = usize::try_from(limit).unwrap_or(DEFAULT_LIMIT);
+            info!(
+                "write buffer limit {} per task, {} in all",
+                task_limit, limit
+            );
+            BufferBudget::new(task_limit, limit)
+        });
+        &INSTANCE
+    }
+
+    pub(crate) fn new(task_limit: usize, limit: usize) -> Self {
+        Self {
+            task_limit,
+            limit,
+            in_flight: Mutex::new((0, vec![])),
+        }
+    }
+
+    /// Returns the bytes in flight in the whole service.
+    pub(crate) fn in_flight(&self) -> usize {
+        self.in_flight.lock().unwrap().0
+    }
+
+    /// Takes `len` bytes from the budget, or registers `waker` to be woken
+    /// once bytes are released if the service is over its limit.
+    fn reserve(&self, len: usize, waker: &Waker) -> bool {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight.0 > 0 && in_flight.0 + len > self.limit {
+            in_flight.1.push(waker.clone());
+            return false;
+        }
+        in_flight.0 += len;
+        true
+    }
+
+    fn release(&self, len: usize) {
+        let wakers = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            in_flight.0 -= len;
+            std::mem::take(&mut in_flight.1)
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+/// Chunks of a task waiting to be written.
+#[derive(Default)]
+struct Inner {
+    chunks: VecDeque<Vec<u8>>,
+    in_flight: usize,
+    writing: bool,
+    error: Option<io::Error>,
+    wakers: Vec<Waker>,
+}
+
+impl Inner {
+    fn wake(&mut self) {
+        std::mem::take(&mut self.wakers)
+            .into_iter()
+            .for_each(Waker::wake);
+    }
+}
+
+/// Write buffer of a download, holding the chunks accepted but not written.
+pub(crate) struct WriteBuffer {
+    budget: &'static BufferBudget,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl WriteBuffer {
+    pub(crate) fn new(budget: &'static BufferBudget) -> Self {
+        Self {
+            budget,
+            inner: Arc::new(Mutex::new(Inner::default())),
+        }
+    }
+
+    /// Returns the bytes of this buffer in flight.
+    pub(crate) fn in_flight(&self) -> usize {
+        self.inner.lock().unwrap().in_flight
+    }
+
+    /// Queues `data` to be appended to `file`.
+    ///
+    /// Returns `Poll::Pending` while the task or the service is over its
+    /// limit, or the error of a previous write which failed.
+    pub(crate) fn poll_write<W>(
+        &self,
+        cx: &mut Context<'_>,
+        file: &Arc<Mutex<W>>,
+        data: &[u8],
+    ) -> Poll<io::Result<()>>
+    where
+        W: Write + Send + 'static,
+    {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(e) = inner.error.take() {
+            return Poll::Ready(Err(e));
+        }
+        if inner.in_flight > 0 && inner.in_flight + data.len() > self.budget.task_limit {
+            inner.wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        if !self.budget.reserve(data.len(), cx.waker()) {
+            return Poll::Pending;
+        }
+        inner.in_flight += data.len();
+        inner.chunks.push_back(data.to_vec());
+        if !inner.writing {
+            inner.writing = true;
+            let (buffer, file, budget) = (self.inner.clone(), file.clone(), self.budget);
+            runtime_spawn_blocking(move || {
+                write_chunks(&buffer, &file, budget);
+                Ok(())
+            });
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    /// Waits until all queued chunks are written.
+    ///
+    /// Returns the error of the first write which failed, the chunks queued
+    /// after it are dropped.
+    pub(crate) async fn drain(&self) -> io::Result<()> {
+        poll_fn(|cx| {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.writing {
+                inner.wakers.push(cx.waker().clone());
+                return Poll::Pending;
+            }
+            Poll::Ready(inner.error.take().map_or(Ok(()), Err))
+        })
+        .await
+    }
+}
+
+/// Writes the queued chunks of a buffer until it is empty.
+fn write_chunks<W: Write>(buffer: &Mutex<Inner>, file: &Mutex<W>, budget: &BufferBudget) {
+    loop {
+        let chunk = {
+            let mut inner = buffer.lock().unwrap();
+            match inner.chunks.pop_front() {
+                Some(chunk) => chunk,
+                None => {
+                    inner.writing = false;
+                    inner.wake();
+                    return;
+                }
+            }
+        };
+        let result = file.lock().unwrap().write_all(&chunk);
+
+        let mut inner = buffer.lock().unwrap();
+        let mut written = chunk.len();
+        if let Err(e) = result {
+            error!("write buffered chunk failed, {:?}", e);
+            written += inner
+                .chunks
+                .drain(..)
+                .map(|chunk| chunk.len())
+                .sum::<usize>();
+            inner.error = Some(e);
+        }
+        inner.in_flight -= written;
+        inner.wake();
+        drop(inner);
+        budget.release(written);
+    }
+}
+
+#[cfg(test)]
+mod ut_write_buffer {
+    include!("../../tests/ut/task/ut_write_buffer.rs");
+}
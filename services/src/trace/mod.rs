@@ -11,12 +11,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "spans")]
+pub mod span;
+
 /// Hitrace adapter which provides timing capability.
 ///
 /// The timing will end automatically when the structure drops. Users should
 /// take care that the lifetime of this structure.
+#[cfg(feature = "oh")]
 pub(crate) struct Trace;
 
+#[cfg(feature = "oh")]
 impl Trace {
     // Copies from `Hitrace`.
     const HITRACE_TAG_MISC: u64 = 1u64 << 41;
@@ -28,6 +33,7 @@ impl Trace {
     }
 }
 
+#[cfg(feature = "oh")]
 impl Drop for Trace {
     /// Stops tracing.
     fn drop(&mut self) {
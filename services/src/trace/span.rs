@@ -0,0 +1,193 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Task lifecycle spans emitted to a pluggable tracer.
+//!
+//! Each task gets a `task` span from its creation to its terminal state,
+//! with `connect`, `first_byte` and `retry` child spans for each attempt of
+//! a download. Spans are only recorded once a tracer is installed with
+//! [`set_tracer`], which lets an OpenTelemetry exporter or any other
+//! backend receive them without the service depending on it.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::task::info::State;
+use crate::task::reason::Reason;
+
+/// Attribute value of a span.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    /// Text value, such as a host name.
+    Str(String),
+    /// Integer value, such as a byte count.
+    Int(i64),
+}
+
+/// Receiver of the spans of task lifecycles.
+pub trait Tracer: Send + Sync {
+    /// Opens span `id` named `name`, as a child of `parent` if any.
+    fn start(&self, id: u64, parent: Option<u64>, name: &str, attributes: &[(&str, Value)]);
+
+    /// Closes span `id` with the attributes only known at its end.
+    fn end(&self, id: u64, attributes: &[(&str, Value)]);
+}
+
+/// Tracer receiving spans, none until one is installed.
+static TRACER: RwLock<Option<Arc<dyn Tracer>>> = RwLock::new(None);
+
+/// Spans currently open, by task ID.
+static SPANS: Mutex<Option<HashMap<u32, TaskSpans>>> = Mutex::new(None);
+
+/// ID of the next span opened.
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Open spans of a task.
+struct TaskSpans {
+    /// ID of the `task` span.
+    task: u64,
+    /// IDs of the open child spans, by name.
+    children: HashMap<&'static str, u64>,
+}
+
+/// Installs the tracer receiving spans, or stops emitting them with `None`.
+///
+/// Spans open in the previous tracer are not closed in the new one.
+pub fn set_tracer(tracer: Option<Arc<dyn Tracer>>) {
+    *TRACER.write().unwrap() = tracer;
+    *SPANS.lock().unwrap() = None;
+}
+
+fn tracer() -> Option<Arc<dyn Tracer>> {
+    TRACER.read().unwrap().clone()
+}
+
+/// Opens the `task` span of a task just created.
+pub(crate) fn task_created(task_id: u32, url: &str, traceparent: Option<&str>) {
+    let Some(tracer) = tracer() else {
+        return;
+    };
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut attributes = vec![
+        ("task_id", Value::Int(task_id as i64)),
+        ("url.host", Value::Str(host_of(url).to_string())),
+    ];
+    if let Some(traceparent) = traceparent {
+        attributes.push(("traceparent", Value::Str(traceparent.to_string())));
+    }
+    tracer.start(id, None, "task", &attributes);
+    let task = TaskSpans {
+        task: id,
+        children: HashMap::new(),
+    };
+    if let Some(old) = SPANS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(task_id, task)
+    {
+        close(tracer.as_ref(), old, &[]);
+    }
+}
+
+/// Opens child span `name` of a task, closing the one already open if any.
+///
+/// A task created before the tracer was installed gets its `task` span here.
+pub(crate) fn start(task_id: u32, name: &'static str, attributes: &[(&str, Value)]) {
+    let Some(tracer) = tracer() else {
+        return;
+    };
+    let mut spans = SPANS.lock().unwrap();
+    let task = spans
+        .get_or_insert_with(HashMap::new)
+        .entry(task_id)
+        .or_insert_with(|| {
+            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+            tracer.start(id, None, "task", &[("task_id", Value::Int(task_id as i64))]);
+            TaskSpans {
+                task: id,
+                children: HashMap::new(),
+            }
+        });
+    if let Some(old) = task.children.remove(name) {
+        tracer.end(old, &[]);
+    }
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    tracer.start(id, Some(task.task), name, attributes);
+    task.children.insert(name, id);
+}
+
+/// Closes child span `name` of a task if it is open.
+pub(crate) fn end(task_id: u32, name: &'static str, attributes: &[(&str, Value)]) {
+    let Some(tracer) = tracer() else {
+        return;
+    };
+    let id = SPANS
+        .lock()
+        .unwrap()
+        .as_mut()
+        .and_then(|spans| spans.get_mut(&task_id))
+        .and_then(|task| task.children.remove(name));
+    if let Some(id) = id {
+        tracer.end(id, attributes);
+    }
+}
+
+/// Closes the spans of a task which reached a terminal state.
+pub(crate) fn task_finished(task_id: u32, state: State, reason: Reason, bytes: u64) {
+    let Some(tracer) = tracer() else {
+        return;
+    };
+    let task = SPANS
+        .lock()
+        .unwrap()
+        .as_mut()
+        .and_then(|spans| spans.remove(&task_id));
+    if let Some(task) = task {
+        let attributes = [
+            ("state", Value::Str(format!("{:?}", state))),
+            ("reason", Value::Int(reason.repr as i64)),
+            ("bytes", Value::Int(bytes as i64)),
+        ];
+        close(tracer.as_ref(), task, &attributes);
+    }
+}
+
+/// Closes the child spans of a task, then its `task` span.
+fn close(tracer: &dyn Tracer, task: TaskSpans, attributes: &[(&str, Value)]) {
+    let mut children = task.children.into_values().collect::<Vec<_>>();
+    children.sort_unstable();
+    for id in children.into_iter().rev() {
+        tracer.end(id, &[]);
+    }
+    tracer.end(task.task, attributes);
+}
+
+/// Returns the host of a URL, without user info or port.
+fn host_of(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    match host.find(']') {
+        Some(end) if host.starts_with('[') => &host[..=end],
+        _ => host.split(':').next().unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod ut_span {
+    include!("../../tests/ut/trace/ut_span.rs");
+}
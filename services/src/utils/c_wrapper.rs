@@ -84,6 +84,8 @@ impl FileSpec {
             mime_type: c_struct.mime_type.to_string(),
             is_user_file: c_struct.is_user_file,
             fd: None,
+            // Files are stored in the order they are processed
+            priority: 0,
         }
     }
 }
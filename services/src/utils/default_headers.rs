@@ -0,0 +1,119 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Default request headers configured per application.
+//!
+//! Headers are read once from [`DEFAULT_HEADERS_CONFIG`], one header per
+//! line prefixed by the uid of the application it applies to:
+//!
+//! ```text
+//! # comment
+//! 20010001 X-Tenant: example
+//! ```
+//!
+//! The headers are added to every request of the application's tasks, a
+//! header set by the task itself takes precedence over a default one of the
+//! same name.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+/// Location of the default headers, a missing file means no defaults.
+const DEFAULT_HEADERS_CONFIG: &str = "/system/etc/request/default_headers.conf";
+
+/// Default headers of every configured application, keyed by uid.
+pub(crate) struct DefaultHeaders {
+    headers: RwLock<HashMap<u64, Vec<(String, String)>>>,
+}
+
+impl DefaultHeaders {
+    /// Returns the default headers loaded from the system configuration.
+    pub(crate) fn get_instance() -> &'static Self {
+        static INSTANCE: LazyLock<DefaultHeaders> = LazyLock::new(|| {
+            let headers = match std::fs::read_to_string(DEFAULT_HEADERS_CONFIG) {
+                Ok(text) => DefaultHeaders::parse(&text),
+                Err(_) => HashMap::new(),
+            };
+            info!("default headers loaded, uid count {}", headers.len());
+            DefaultHeaders::new(headers)
+        });
+        &INSTANCE
+    }
+
+    pub(crate) fn new(headers: HashMap<u64, Vec<(String, String)>>) -> Self {
+        Self {
+            headers: RwLock::new(headers),
+        }
+    }
+
+    /// Parses header lines, malformed lines are logged and skipped.
+    pub(crate) fn parse(text: &str) -> HashMap<u64, Vec<(String, String)>> {
+        let mut headers: HashMap<u64, Vec<(String, String)>> = HashMap::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match parse_line(line) {
+                Some((uid, name, value)) => headers.entry(uid).or_default().push((name, value)),
+                None => error!("invalid default header: {}", line),
+            }
+        }
+        headers
+    }
+
+    /// Replaces the default headers of all applications.
+    pub(crate) fn set_headers(&self, headers: HashMap<u64, Vec<(String, String)>>) {
+        *self.headers.write().unwrap() = headers;
+    }
+
+    /// Returns the headers of a task of `uid`, its own `headers` merged with
+    /// the defaults of the application and sorted by name.
+    pub(crate) fn merge(
+        &self,
+        uid: u64,
+        headers: &HashMap<String, String>,
+    ) -> Vec<(String, String)> {
+        let mut merged: Vec<(String, String)> = headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        if let Some(defaults) = self.headers.read().unwrap().get(&uid) {
+            for (name, value) in defaults {
+                if !headers.keys().any(|key| key.eq_ignore_ascii_case(name)) {
+                    merged.push((name.clone(), value.clone()));
+                }
+            }
+        }
+        merged.sort();
+        merged
+    }
+}
+
+/// Parses a `uid name: value` line.
+fn parse_line(line: &str) -> Option<(u64, String, String)> {
+    let (uid, header) = line.split_once(char::is_whitespace)?;
+    let uid = uid.parse::<u64>().ok()?;
+    let (name, value) = header.split_once(':')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    Some((uid, name.to_string(), value.trim().to_string()))
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_default_headers {
+    include!("../../tests/ut/utils/ut_default_headers.rs");
+}
@@ -32,6 +32,9 @@ pub struct FileSpec {
     pub is_user_file: bool,
     /// File descriptor for the opened file, only valid when `is_user_file` is true.
     pub fd: Option<RawFd>,
+    /// Files of higher priority are processed first, files of equal priority
+    /// in list order. 0 by default.
+    pub priority: u32,
 }
 
 impl FileSpec {
@@ -75,6 +78,7 @@ impl FileSpec {
             mime_type: "".to_string(),
             is_user_file: true,
             fd: Some(file.into_raw_fd()),
+            priority: 0,
         }
     }
 }
@@ -89,3 +93,46 @@ pub(crate) struct FormItem {
     /// The value associated with the form field.
     pub(crate) value: String,
 }
+
+/// Media type of uploads sending only form fields as an urlencoded body.
+pub(crate) const FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+
+/// Serializes `items` as an `application/x-www-form-urlencoded` body.
+///
+/// Names and values are percent-encoded, spaces become `+` and the pairs are
+/// joined with `&` in their original order.
+pub(crate) fn form_urlencode(items: &[FormItem]) -> String {
+    let mut body = String::new();
+    for (i, item) in items.iter().enumerate() {
+        if i != 0 {
+            body.push('&');
+        }
+        percent_encode(&item.name, &mut body);
+        body.push('=');
+        percent_encode(&item.value, &mut body);
+    }
+    body
+}
+
+/// Appends `input` to `out`, escaping everything but `A-Z a-z 0-9 * - . _`.
+fn percent_encode(input: &str, out: &mut String) {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                out.push(byte as char)
+            }
+            b' ' => out.push('+'),
+            _ => {
+                out.push('%');
+                out.push(HEX[(byte >> 4) as usize] as char);
+                out.push(HEX[(byte & 0xf) as usize] as char);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_form_item {
+    include!("../../tests/ut/utils/ut_form_item.rs");
+}
@@ -19,7 +19,9 @@
 
 pub(crate) mod c_wrapper;
 pub(crate) mod common_event;
+pub(crate) mod default_headers;
 pub(crate) mod form_item;
+pub(crate) mod url_rewrite;
 use std::collections::HashMap;
 use std::future::Future;
 use std::io::Write;
@@ -439,6 +441,79 @@ pub(crate) fn is_called_by_hap() -> bool {
     ffi::IsCalledByHAP(token_id)
 }
 
+/// Reads an integer system parameter.
+///
+/// # Returns
+///
+/// Returns the value of the parameter, or `default` if it is unset or not an
+/// integer.
+///
+/// # Availability
+///
+/// This function is only available when the `oh` feature is enabled.
+#[cfg(feature = "oh")]
+pub(crate) fn get_int_parameter(key: &str, default: i64) -> i64 {
+    ffi::GetIntParameter(key, default)
+}
+
+/// Reads the current thermal level of the device.
+///
+/// # Returns
+///
+/// Returns the thermal level, `0` if the thermal service is unavailable.
+///
+/// # Availability
+///
+/// This function is only available when the `oh` feature is enabled.
+#[cfg(feature = "oh")]
+pub(crate) fn thermal_level() -> u8 {
+    ffi::GetThermalLevel().clamp(0, u8::MAX as i32) as u8
+}
+
+/// Reads whether the device is charging.
+///
+/// # Returns
+///
+/// Returns `true` if the device is charging or the battery service is
+/// unavailable.
+///
+/// # Availability
+///
+/// This function is only available when the `oh` feature is enabled.
+#[cfg(feature = "oh")]
+pub(crate) fn is_charging() -> bool {
+    ffi::IsCharging()
+}
+
+/// Keeps the device awake with the running lock of the service.
+///
+/// # Arguments
+///
+/// - `timeout`: Time after which the system releases the lock by itself
+///
+/// # Returns
+///
+/// Returns `true` if the lock is held, `false` if the power service is
+/// unavailable or refused it.
+///
+/// # Availability
+///
+/// This function is only available when the `oh` feature is enabled.
+#[cfg(feature = "oh")]
+pub(crate) fn acquire_running_lock(timeout: Duration) -> bool {
+    ffi::AcquireRunningLock(timeout.as_millis().min(i32::MAX as u128) as i64)
+}
+
+/// Releases the running lock of the service.
+///
+/// # Availability
+///
+/// This function is only available when the `oh` feature is enabled.
+#[cfg(feature = "oh")]
+pub(crate) fn release_running_lock() {
+    ffi::ReleaseRunningLock()
+}
+
 /// CXX FFI bridge to C++ utilities.
 ///
 /// This module defines the interface to C++ utility functions used throughout
@@ -475,6 +550,21 @@ mod ffi {
         
         /// Checks if a token ID belongs to a HarmonyOS Ability Package.
         fn IsCalledByHAP(token_id: u32) -> bool;
+
+        /// Reads an integer system parameter, `default` if it is unset.
+        fn GetIntParameter(key: &str, default: i64) -> i64;
+
+        /// Reads the current thermal level of the device.
+        fn GetThermalLevel() -> i32;
+
+        /// Checks if the device is charging.
+        fn IsCharging() -> bool;
+
+        /// Keeps the device awake for at most `timeout_ms`.
+        fn AcquireRunningLock(timeout_ms: i64) -> bool;
+
+        /// Lets the device sleep again.
+        fn ReleaseRunningLock();
     }
 }
 
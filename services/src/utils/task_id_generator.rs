@@ -12,13 +12,36 @@
 // limitations under the License.
 
 //! Provides utilities for generating unique task identifiers.
+//!
+//! Candidates mix the clock with the uptime of the service and a sequence
+//! number, so candidates generated in the same clock tick or right after a
+//! restart differ. After long uptime a candidate may still collide with a
+//! stored task, [`TaskIdGenerator::allocate`] checks candidates against the
+//! stored tasks and gives up after [`MAX_ID_ATTEMPTS`] of them.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::LazyLock;
+use std::time::Instant;
 
 cfg_oh! {
-    use std::sync::atomic::{AtomicU32, Ordering};
     use std::time::{SystemTime, UNIX_EPOCH};
-    use crate::manage::database::RequestDb;
 }
 
+/// Largest number of candidates tried to allocate one task id.
+pub(crate) const MAX_ID_ATTEMPTS: usize = 16;
+
+/// Task ids are kept positive when read as `i32`, as notification ids are.
+const ID_MASK: u32 = 0x7FFF_FFFF;
+
+/// Multiplier spreading consecutive sequence numbers over the id space.
+const SEQUENCE_MIX: u32 = 0x9E37_79B9;
+
+/// Time the service started generating ids.
+static START: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+/// Sequence number of the next candidate.
+static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+
 /// Generator for unique task identifiers.
 ///
 /// This struct provides functionality to generate unique 32-bit identifiers
@@ -26,56 +49,86 @@ cfg_oh! {
 pub(crate) struct TaskIdGenerator;
 
 impl TaskIdGenerator {
-    /// Generates a unique task identifier.
+    /// Generates a candidate task identifier.
     ///
-    /// # Feature Dependencies
-    /// This function has two different implementations:
-    /// - For `oh` feature: Uses system time nanoseconds with an atomic fallback
-    /// - For other cases: Uses random number generation
+    /// The candidate is not checked against the stored tasks, use
+    /// [`allocate`](Self::allocate) for the id of a new task.
     ///
     /// # Examples
     /// ```rust
-    /// // Generate a new unique task ID
+    /// // Generate a new candidate task ID
     /// let task_id = TaskIdGenerator::generate();
     /// ```
-    #[cfg(feature = "oh")]
     pub(crate) fn generate() -> u32 {
-        loop {
-            debug!("generate task_id");
-            // Try to use system time's nanoseconds for unique ID generation
-            let task_id = match SystemTime::now().duration_since(UNIX_EPOCH) {
-                Ok(time) => time.subsec_nanos(),
-                Err(e) => {
-                    // Fallback to atomic counter if system time fails
-                    static ID: AtomicU32 = AtomicU32::new(0);
-                    error!("Generate task id from system time failed {:?}", e);
-                    sys_event!(
-                        ExecFault,
-                        DfxCode::SA_ERROR_00,
-                        &format!("Generate task id from system time failed {:?}", e)
-                    );
-                    // Increment and return atomic counter (relaxed ordering sufficient here)
-                    ID.fetch_add(1, Ordering::Relaxed)
+        let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let uptime = START.elapsed().as_millis() as u32;
+        Self::mix(Self::entropy(), uptime, sequence)
+    }
+
+    /// Mixes the clock, the uptime in milliseconds and a sequence number
+    /// into a candidate, never 0.
+    pub(crate) fn mix(entropy: u32, uptime: u32, sequence: u32) -> u32 {
+        let id = (entropy ^ uptime.rotate_left(16) ^ sequence.wrapping_mul(SEQUENCE_MIX)) & ID_MASK;
+        id.max(1)
+    }
+
+    /// Allocates an id not used by any stored task.
+    ///
+    /// # Arguments
+    ///
+    /// * `taken` - Returns whether a candidate is used by a stored task.
+    ///
+    /// # Returns
+    ///
+    /// The id, or `None` if [`MAX_ID_ATTEMPTS`] candidates are all taken.
+    pub(crate) fn allocate(taken: impl FnMut(u32) -> bool) -> Option<u32> {
+        Self::allocate_from(std::iter::repeat_with(Self::generate), taken)
+    }
+
+    /// Allocates the first id of `candidates` not used by any stored task,
+    /// trying at most [`MAX_ID_ATTEMPTS`] of them.
+    pub(crate) fn allocate_from(
+        candidates: impl IntoIterator<Item = u32>,
+        mut taken: impl FnMut(u32) -> bool,
+    ) -> Option<u32> {
+        candidates
+            .into_iter()
+            .take(MAX_ID_ATTEMPTS)
+            .find(|task_id| {
+                if taken(*task_id) {
+                    info!("task id {} is in use, trying another", task_id);
+                    return false;
                 }
-            };
-            // Ensure generated ID is unique by checking database
-            if !RequestDb::get_instance().contains_task(task_id) {
-                return task_id;
+                true
+            })
+    }
+
+    /// Returns the nanoseconds of the system time.
+    #[cfg(feature = "oh")]
+    fn entropy() -> u32 {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(time) => time.subsec_nanos(),
+            Err(e) => {
+                // The sequence number alone still tells candidates apart.
+                error!("Generate task id from system time failed {:?}", e);
+                sys_event!(
+                    ExecFault,
+                    DfxCode::SA_ERROR_00,
+                    &format!("Generate task id from system time failed {:?}", e)
+                );
+                0
             }
         }
     }
-    
-    /// Generates a unique task identifier using random number generation.
-    ///
-    /// This implementation is used when the `oh` feature is not enabled.
-    ///
-    /// # Examples
-    /// ```rust
-    /// // Generate a random task ID
-    /// let task_id = TaskIdGenerator::generate();
-    /// ```
+
+    /// Returns random bits in place of the clock.
     #[cfg(not(feature = "oh"))]
-    pub(crate) fn generate() -> u32 {
+    fn entropy() -> u32 {
         rand::random()
     }
 }
+
+#[cfg(test)]
+mod ut_task_id_generator {
+    include!("../../tests/ut/utils/ut_task_id_generator.rs");
+}
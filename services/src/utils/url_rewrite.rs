@@ -0,0 +1,150 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! URL rewriting applied to tasks before they are constructed.
+//!
+//! Rules are read once from [`URL_REWRITE_CONFIG`], one rule per line:
+//!
+//! ```text
+//! # comment
+//! prefix http://old.example.com/ https://new.example.com/
+//! regex ^http://(\w+)\.cdn\.example\.com/ https://cdn.example.com/$1/
+//! ```
+//!
+//! The first matching rule wins. The rewritten URL is stored in
+//! `TaskConfig::effective_url` and used for the request, while `url` keeps
+//! the address given by the application for display.
+
+use std::sync::{LazyLock, RwLock};
+
+use regex::Regex;
+
+use crate::task::config::TaskConfig;
+
+/// Location of the rewrite rules, a missing file means no rewriting.
+const URL_REWRITE_CONFIG: &str = "/system/etc/request/url_rewrite.conf";
+
+/// A single rewrite rule.
+#[derive(Debug)]
+pub(crate) enum RewriteRule {
+    /// Replaces a leading `from` with `to`.
+    Prefix { from: String, to: String },
+    /// Replaces the first match of `pattern`, `$n` refers to capture groups.
+    Regex { pattern: Regex, replacement: String },
+}
+
+impl RewriteRule {
+    fn rewrite(&self, url: &str) -> Option<String> {
+        match self {
+            RewriteRule::Prefix { from, to } => url
+                .strip_prefix(from.as_str())
+                .map(|rest| format!("{}{}", to, rest)),
+            RewriteRule::Regex {
+                pattern,
+                replacement,
+            } => pattern
+                .is_match(url)
+                .then(|| pattern.replace(url, replacement.as_str()).into_owned()),
+        }
+    }
+}
+
+/// Ordered set of rewrite rules shared by all tasks.
+pub(crate) struct UrlRewriter {
+    rules: RwLock<Vec<RewriteRule>>,
+}
+
+impl UrlRewriter {
+    /// Returns the rewriter loaded from the system configuration.
+    pub(crate) fn get_instance() -> &'static Self {
+        static INSTANCE: LazyLock<UrlRewriter> = LazyLock::new(|| {
+            let rules = match std::fs::read_to_string(URL_REWRITE_CONFIG) {
+                Ok(text) => UrlRewriter::parse(&text),
+                Err(_) => Vec::new(),
+            };
+            info!("url rewrite rules loaded, count {}", rules.len());
+            UrlRewriter::new(rules)
+        });
+        &INSTANCE
+    }
+
+    pub(crate) fn new(rules: Vec<RewriteRule>) -> Self {
+        Self {
+            rules: RwLock::new(rules),
+        }
+    }
+
+    /// Parses rule lines, malformed lines are logged and skipped.
+    pub(crate) fn parse(text: &str) -> Vec<RewriteRule> {
+        let mut rules = Vec::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let rule = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some("prefix"), Some(from), Some(to), None) => Some(RewriteRule::Prefix {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                }),
+                (Some("regex"), Some(pattern), Some(replacement), None) => Regex::new(pattern)
+                    .ok()
+                    .map(|pattern| RewriteRule::Regex {
+                        pattern,
+                        replacement: replacement.to_string(),
+                    }),
+                _ => None,
+            };
+            match rule {
+                Some(rule) => rules.push(rule),
+                None => error!("invalid url rewrite rule: {}", line),
+            }
+        }
+        rules
+    }
+
+    /// Replaces all rules.
+    pub(crate) fn set_rules(&self, rules: Vec<RewriteRule>) {
+        *self.rules.write().unwrap() = rules;
+    }
+
+    /// Returns the rewritten URL of the first matching rule.
+    pub(crate) fn rewrite(&self, url: &str) -> Option<String> {
+        self.rules
+            .read()
+            .unwrap()
+            .iter()
+            .find_map(|rule| rule.rewrite(url))
+    }
+
+    /// Sets the effective URL of `config`, leaving its original URL intact.
+    pub(crate) fn apply(&self, config: &mut TaskConfig) {
+        match self.rewrite(&config.url) {
+            Some(url) if url != config.url => {
+                info!(
+                    "task {} url rewritten to {}",
+                    config.common_data.task_id, url
+                );
+                config.effective_url = url;
+            }
+            _ => config.effective_url.clear(),
+        }
+    }
+}
+
+#[cfg(not(feature = "oh"))]
+#[cfg(test)]
+mod ut_url_rewrite {
+    include!("../../tests/ut/utils/ut_url_rewrite.rs");
+}
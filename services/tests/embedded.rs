@@ -0,0 +1,218 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "embedded", not(feature = "oh")))]
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use download_server::config::{Action, ConfigBuilder, Mode, TaskConfig};
+use download_server::embedded::{
+    EmbeddedCallback, EmbeddedHandle, EmbeddedRequestService, TaskProgress,
+};
+use download_server::info::State;
+
+const CONFIG_DIR: &str = "test_files/embedded";
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Boots the embedded service once, on a fresh database.
+fn handle() -> EmbeddedHandle {
+    static HANDLE: OnceLock<EmbeddedHandle> = OnceLock::new();
+    HANDLE
+        .get_or_init(|| {
+            let _ = std::fs::remove_dir_all(CONFIG_DIR);
+            EmbeddedRequestService::new(CONFIG_DIR)
+        })
+        .clone()
+}
+
+/// Starts a server serving `body` with `Range` support, `chunk` bytes at a
+/// time with `delay` in between.
+fn start_server(body: Vec<u8>, chunk: usize, delay: Duration) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let body = Arc::new(body);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let body = body.clone();
+            std::thread::spawn(move || {
+                let mut start = 0;
+                for line in BufReader::new(&mut stream).lines() {
+                    let Ok(line) = line else {
+                        return;
+                    };
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(range) = line.strip_prefix("Range: bytes=") {
+                        start = range.trim_end_matches('-').parse().unwrap_or(0);
+                    }
+                }
+                let head = match start {
+                    0 => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nAccept-Ranges: bytes\r\nLast-Modified: Wed, 21 Oct 2015 07:28:00 GMT\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    ),
+                    _ => format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nLast-Modified: Wed, 21 Oct 2015 07:28:00 GMT\r\nConnection: close\r\n\r\n",
+                        body.len() - start,
+                        start,
+                        body.len() - 1,
+                        body.len()
+                    ),
+                };
+                if stream.write_all(head.as_bytes()).is_err() {
+                    return;
+                }
+                for piece in body[start..].chunks(chunk) {
+                    if stream.write_all(piece).is_err() {
+                        return;
+                    }
+                    std::thread::sleep(delay);
+                }
+            });
+        }
+    });
+    url
+}
+
+fn download_config(url: &str, path: &str) -> TaskConfig {
+    let file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .unwrap();
+    ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .version(2)
+        .file_spec(file)
+        .url(url)
+        .build()
+}
+
+fn test_body(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i % 251) as u8).collect()
+}
+
+/// Records the states reported to the callback.
+#[derive(Default)]
+struct Recorder {
+    states: Mutex<Vec<State>>,
+}
+
+impl Recorder {
+    fn record(&self, progress: &TaskProgress) {
+        self.states.lock().unwrap().push(progress.state);
+    }
+
+    fn await_state(&self, state: State) -> bool {
+        let deadline = Instant::now() + TIMEOUT;
+        while Instant::now() < deadline {
+            if self.states.lock().unwrap().contains(&state) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+}
+
+impl EmbeddedCallback for Recorder {
+    fn on_completed(&self, progress: &TaskProgress) {
+        self.record(progress);
+    }
+
+    fn on_pause(&self, progress: &TaskProgress) {
+        self.record(progress);
+    }
+
+    fn on_resume(&self, progress: &TaskProgress) {
+        self.record(progress);
+    }
+}
+
+// @tc.name: sdv_embedded_download
+// @tc.desc: Test a download runs to completion in the embedded service
+// @tc.precon: NA
+// @tc.step: 1. Boot the embedded service and start a local server
+//           2. Create and start a download through an embedded client
+//           3. Wait for the completion callback
+// @tc.expect: The callback reports completion, the task is queried as
+//             completed and the file holds the served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn sdv_embedded_download() {
+    let client = handle().client(6000);
+    let body = test_body(256 * 1024);
+    let url = start_server(body.clone(), 64 * 1024, Duration::ZERO);
+    let path = "test_files/sdv_embedded_download.txt";
+
+    let task_id = client.create_task(download_config(&url, path)).unwrap();
+    let recorder = Arc::new(Recorder::default());
+    client.register_callback(task_id, recorder.clone());
+    client.start(task_id).unwrap();
+
+    assert!(recorder.await_state(State::Completed));
+    let progress = client.query(task_id).unwrap();
+    assert_eq!(progress.state, State::Completed);
+    assert_eq!(progress.processed, body.len() as u64);
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    assert!(handle().client(6001).query(task_id).is_err());
+}
+
+// @tc.name: sdv_embedded_pause_resume
+// @tc.desc: Test a download is paused and resumed in the embedded service
+// @tc.precon: NA
+// @tc.step: 1. Boot the embedded service and start a throttled server
+//           2. Start a download and pause it once bytes arrived
+//           3. Resume the download and wait for its completion
+// @tc.expect: Pause, resume and completion are reported to the callback and
+//             the file holds the served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn sdv_embedded_pause_resume() {
+    let client = handle().client(6002);
+    let body = test_body(512 * 1024);
+    let url = start_server(body.clone(), 16 * 1024, Duration::from_millis(50));
+    let path = "test_files/sdv_embedded_pause_resume.txt";
+
+    let task_id = client.create_task(download_config(&url, path)).unwrap();
+    let recorder = Arc::new(Recorder::default());
+    client.register_callback(task_id, recorder.clone());
+    client.start(task_id).unwrap();
+
+    let deadline = Instant::now() + TIMEOUT;
+    while client.query(task_id).unwrap().processed == 0 {
+        assert!(Instant::now() < deadline);
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    client.pause(task_id).unwrap();
+    assert!(recorder.await_state(State::Paused));
+    assert_eq!(client.query(task_id).unwrap().state, State::Paused);
+
+    client.resume(task_id).unwrap();
+    assert!(recorder.await_state(State::Completed));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    client.unregister_callback(task_id);
+}
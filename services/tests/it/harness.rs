@@ -0,0 +1,831 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Hermetic harness running the whole `TaskManager` stack on a Linux host.
+//
+// The harness wires the real `TaskManager`, `Scheduler`, `RunningQueue` and
+// `ClientManager` together with the in-memory database backend, a synthetic
+// network state, a fake client speaking the UDS frame protocol and a local
+// HTTP server, so scenarios can be driven end to end without a device.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use ylong_runtime::net::UnixDatagram;
+
+use crate::config::TaskConfig;
+use crate::error::ErrorCode;
+use crate::info::State;
+use crate::manage::database::RequestDb;
+use crate::manage::events::TaskManagerEvent;
+use crate::manage::network::{NetworkInfo, NetworkType};
+use crate::manage::network_manager::NetworkManager;
+use crate::manage::task_manager::TaskManagerTx;
+use crate::manage::TaskManager;
+use crate::service::active_counter::ActiveCounter;
+use crate::service::client::{ClientManager, ClientManagerEntry};
+use crate::service::run_count::{RunCountManager, RunCountManagerEntry};
+use crate::task::notify::SubscribeType;
+use crate::utils::runtime_spawn;
+
+const REQUEST_MAGIC_NUM: u32 = 0x43434646;
+const FRAME_HEADER_SIZE: usize = 12;
+/// Validator sent by the local server, so that tasks may resume with a range.
+const LAST_MODIFIED: &str = "Last-Modified: Wed, 21 Oct 2015 07:28:00 GMT\r\n";
+
+static CLIENT: Lazy<ClientManagerEntry> = Lazy::new(ClientManager::init);
+static RUN_COUNT_MANAGER: Lazy<RunCountManagerEntry> = Lazy::new(RunCountManager::init);
+
+/// Shared `TaskManager` booted once for all scenarios of the test binary.
+pub(crate) static TASK_MANAGER: Lazy<TaskManagerTx> = Lazy::new(|| {
+    let _ = env_logger::builder().is_test(true).try_init();
+    let _ = std::fs::create_dir("test_files/");
+    let network = NetworkManager::get_instance()
+        .lock()
+        .unwrap()
+        .network
+        .clone();
+    TaskManager::init(
+        RUN_COUNT_MANAGER.clone(),
+        CLIENT.clone(),
+        ActiveCounter::new(),
+        network,
+    )
+});
+
+/// Serializes scenarios, since they share the network state.
+pub(crate) fn serial() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Switches the synthetic network to an unmetered wifi connection.
+pub(crate) fn network_online() {
+    let notified = NetworkManager::get_instance()
+        .lock()
+        .unwrap()
+        .network
+        .inner
+        .notify_online(NetworkInfo {
+            network_type: NetworkType::Wifi,
+            is_metered: false,
+            is_roaming: false,
+        });
+    if notified {
+        TASK_MANAGER.send_event(TaskManagerEvent::network());
+    }
+}
+
+/// Switches the synthetic network offline and lets the scheduler react.
+pub(crate) fn network_offline() {
+    NetworkManager::get_instance()
+        .lock()
+        .unwrap()
+        .network
+        .inner
+        .notify_offline();
+    TASK_MANAGER.send_event(TaskManagerEvent::network());
+}
+
+/// Polls the database until `task_id` reaches `state` or `timeout` elapses.
+///
+/// Returns `true` if the state was observed in time.
+pub(crate) fn await_state(task_id: u32, state: State, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if RequestDb::get_instance().query_task_state(task_id) == Some(state.repr) {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Constructs a task, subscribes `client` to it and starts it.
+pub(crate) fn construct_and_start(config: TaskConfig, client: &FakeClient) -> u32 {
+    let uid = config.common_data.uid;
+    let (event, rx) = TaskManagerEvent::construct(config);
+    TASK_MANAGER.send_event(event);
+    let task_id = rx.get().unwrap().unwrap().task_id;
+    client.subscribe(task_id, uid);
+    let (event, rx) = TaskManagerEvent::start(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    task_id
+}
+
+/// A frame decoded from the UDS channel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Frame {
+    /// `MessageType::HttpResponse` for a task.
+    Response { task_id: u32 },
+    /// `MessageType::NotifyData` with its subscribe type and reported state.
+    Notify {
+        subscribe_type: u32,
+        task_id: u32,
+        state: u32,
+        processed: u64,
+    },
+    /// `MessageType::Faults` with the fault reason.
+    Faults { task_id: u32, reason: u32 },
+    /// `MessageType::Waiting` with the waiting cause.
+    Waiting { task_id: u32, cause: u32 },
+}
+
+/// Fake client process consuming frames the service writes to its channel.
+pub(crate) struct FakeClient {
+    pid: u64,
+    frames: Arc<Mutex<Vec<Frame>>>,
+    states: Arc<Mutex<Vec<Option<u8>>>>,
+}
+
+impl FakeClient {
+    /// Opens a channel for a fresh pid and starts draining frames from it.
+    pub(crate) fn open() -> Self {
+        static PID: AtomicU64 = AtomicU64::new(10000);
+        Lazy::force(&TASK_MANAGER);
+
+        let pid = PID.fetch_add(1, Ordering::SeqCst);
+        let sock = CLIENT.open_channel(pid).unwrap();
+        let frames = Arc::new(Mutex::new(Vec::new()));
+        let states = Arc::new(Mutex::new(Vec::new()));
+        runtime_spawn(drain(sock, frames.clone(), states.clone()));
+        Self {
+            pid,
+            frames,
+            states,
+        }
+    }
+
+    /// Subscribes this client to notifications of `task_id`.
+    pub(crate) fn subscribe(&self, task_id: u32, uid: u64) {
+        assert_eq!(
+            CLIENT.subscribe(task_id, self.pid, uid, 0),
+            ErrorCode::ErrOk
+        );
+    }
+
+    /// Subscribes this client to notifications of many tasks at once.
+    ///
+    /// Returns the subscription status of each task, in order.
+    pub(crate) fn subscribe_batch(&self, task_ids: Vec<u32>) -> Vec<ErrorCode> {
+        let (event, rx) = TaskManagerEvent::subscribe_batch(task_ids, 0);
+        TASK_MANAGER.send_event(event);
+        let checked = rx.get().unwrap();
+        let mut ret = Vec::with_capacity(checked.len());
+        let mut snapshots = Vec::new();
+        for res in checked {
+            match res {
+                Ok(snapshot) => {
+                    ret.push(ErrorCode::ErrOk);
+                    snapshots.push(snapshot);
+                }
+                Err(code) => ret.push(code),
+            }
+        }
+        let mut codes = CLIENT.subscribe_batch(self.pid, snapshots).into_iter();
+        for code in ret.iter_mut().filter(|code| **code == ErrorCode::ErrOk) {
+            *code = codes.next().unwrap();
+        }
+        ret
+    }
+
+    /// Waits until a frame matching `f` has been received.
+    pub(crate) fn await_frame<F>(&self, timeout: Duration, f: F) -> Option<Frame>
+    where
+        F: Fn(&Frame) -> bool,
+    {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(frame) = self.frames.lock().unwrap().iter().find(|frame| f(frame)) {
+                return Some(frame.clone());
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    /// Returns the frames received so far, each with the state its task had
+    /// in the database when the frame arrived.
+    pub(crate) fn frames_with_states(&self) -> Vec<(Frame, Option<u8>)> {
+        let frames = self.frames.lock().unwrap().clone();
+        let states = self.states.lock().unwrap().clone();
+        frames.into_iter().zip(states).collect()
+    }
+
+    /// Waits for a notify frame of `subscribe_type` for `task_id`.
+    pub(crate) fn await_notify(
+        &self,
+        task_id: u32,
+        subscribe_type: SubscribeType,
+        timeout: Duration,
+    ) -> Option<Frame> {
+        self.await_frame(timeout, |frame| {
+            matches!(frame, Frame::Notify { subscribe_type: t, task_id: id, .. }
+                if *t == subscribe_type as u32 && *id == task_id)
+        })
+    }
+}
+
+impl Drop for FakeClient {
+    fn drop(&mut self) {
+        let _ = CLIENT.notify_process_terminate(self.pid);
+    }
+}
+
+async fn drain(
+    sock: Arc<UnixDatagram>,
+    frames: Arc<Mutex<Vec<Frame>>>,
+    states: Arc<Mutex<Vec<Option<u8>>>>,
+) {
+    let mut buf = vec![0u8; 16 * 1024];
+    loop {
+        let len = match sock.recv(&mut buf).await {
+            Ok(0) | Err(_) => return,
+            Ok(len) => len,
+        };
+        // The service waits for the frame length as acknowledgment.
+        let _ = sock.send(&(len as u32).to_le_bytes()).await;
+        if let Some(frame) = decode(&buf[..len]) {
+            // Queries the task as a client would right on the callback.
+            let task_id = match frame {
+                Frame::Response { task_id }
+                | Frame::Notify { task_id, .. }
+                | Frame::Faults { task_id, .. }
+                | Frame::Waiting { task_id, .. } => task_id,
+            };
+            let state = RequestDb::get_instance().query_task_state(task_id);
+            // Both are pushed under the frames lock to stay aligned.
+            let mut frames = frames.lock().unwrap();
+            states.lock().unwrap().push(state);
+            frames.push(frame);
+        }
+    }
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> Option<u32> {
+    buf.get(pos..pos + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u64(buf: &[u8], pos: usize) -> Option<u64> {
+    buf.get(pos..pos + 8).map(|b| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(b);
+        u64::from_le_bytes(bytes)
+    })
+}
+
+fn decode(buf: &[u8]) -> Option<Frame> {
+    if read_u32(buf, 0)? != REQUEST_MAGIC_NUM {
+        return None;
+    }
+    let message_type = u16::from_le_bytes([*buf.get(8)?, *buf.get(9)?]);
+    let body = FRAME_HEADER_SIZE;
+    match message_type {
+        0 => Some(Frame::Response {
+            task_id: read_u32(buf, body)?,
+        }),
+        1 => Some(Frame::Notify {
+            subscribe_type: read_u32(buf, body)?,
+            task_id: read_u32(buf, body + 4)?,
+            state: read_u32(buf, body + 8)?,
+            processed: read_u64(buf, body + 16)?,
+        }),
+        2 => Some(Frame::Faults {
+            task_id: read_u32(buf, body)?,
+            reason: read_u32(buf, body + 8)?,
+        }),
+        3 => Some(Frame::Waiting {
+            task_id: read_u32(buf, body)?,
+            cause: read_u32(buf, body + 4)?,
+        }),
+        _ => None,
+    }
+}
+
+/// Behaviour of the local HTTP server.
+#[derive(Clone, Copy)]
+pub(crate) enum ServerMode {
+    /// Serves the body at full speed.
+    Normal,
+    /// Serves the body in `chunk` sized pieces, sleeping `delay` in between.
+    Throttled { chunk: usize, delay: Duration },
+    /// Answers every request with `404 Not Found`.
+    NotFound,
+    /// Announces the whole requested range but closes the connection after
+    /// half of it.
+    Flaky,
+    /// Serves and announces only the first half of requested ranges longer
+    /// than a byte.
+    HalfRange,
+    /// Serves the body at full speed with the given `Content-Type`.
+    ContentType(&'static str),
+    /// Accepts uploads whose `If-Match` is the given ETag and answers the
+    /// others with `412 Precondition Failed`.
+    IfMatch(&'static str),
+    /// Ignores `Range` and serves the body throttled like `Throttled`. If
+    /// `honored`, the body is served from the `offset` query parameter of the
+    /// request, still with `200 OK` and without a `Content-Range`.
+    QueryOffset {
+        honored: bool,
+        chunk: usize,
+        delay: Duration,
+    },
+    /// Serves the body at full speed with the given number of filler headers.
+    ManyHeaders(usize),
+    /// Serves the whole body with chunked transfer encoding, without
+    /// `Content-Length` and ignoring `Range`.
+    Chunked,
+    /// Serves the whole body like `Chunked`, announcing and sending the given
+    /// trailer name and value after it.
+    Trailer(&'static str, &'static str),
+    /// Reads uploads in `chunk` sized pieces, sleeping `delay` in between,
+    /// and answers `200 OK` once the whole body arrived.
+    SlowSink { chunk: usize, delay: Duration },
+    /// Serves the body at full speed, labelled `Content-Encoding: gzip` if
+    /// the request accepts gzip. The body is sent as it is either way.
+    Gzip,
+    /// Answers every request with a `text/html` sign-in page while the portal
+    /// is on. Otherwise answers `/generate_204` with `204 No Content` and
+    /// serves the body at full speed as `application/octet-stream`.
+    Portal,
+    /// Serves the body at full speed with the given `Content-Disposition`.
+    Disposition(&'static str),
+    /// Reads uploads whole, by `Content-Length` or chunked, records their
+    /// bodies and answers `200 OK`.
+    Capture,
+    /// Serves the whole body to every request, keeping the connection open
+    /// for further requests.
+    KeepAlive,
+}
+
+/// Local HTTP server serving a fixed body with `Range` support.
+pub(crate) struct TestServer {
+    /// Base url of the server.
+    pub(crate) url: String,
+    ranges: Arc<Mutex<Vec<Option<u64>>>>,
+    encodings: Arc<Mutex<Vec<Option<String>>>>,
+    traceparents: Arc<Mutex<Vec<Option<String>>>>,
+    uploads: Arc<Mutex<Vec<Vec<u8>>>>,
+    methods: Arc<Mutex<Vec<String>>>,
+    body: Arc<Mutex<Arc<Vec<u8>>>>,
+    portal: Arc<AtomicBool>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl TestServer {
+    /// Starts a server answering every connection according to `mode`.
+    pub(crate) fn start(body: Vec<u8>, mode: ServerMode) -> Self {
+        let listener = bind();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let recorded = ranges.clone();
+        let encodings = Arc::new(Mutex::new(Vec::new()));
+        let accepted = encodings.clone();
+        let traceparents = Arc::new(Mutex::new(Vec::new()));
+        let traced = traceparents.clone();
+        let uploads = Arc::new(Mutex::new(Vec::new()));
+        let captured = uploads.clone();
+        let methods = Arc::new(Mutex::new(Vec::new()));
+        let requested = methods.clone();
+        let body = Arc::new(Mutex::new(Arc::new(body)));
+        let served = body.clone();
+        let portal = Arc::new(AtomicBool::new(false));
+        let intercepting = portal.clone();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let connected = connections.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                connected.fetch_add(1, Ordering::SeqCst);
+                let body = served.lock().unwrap().clone();
+                let recorded = recorded.clone();
+                let accepted = accepted.clone();
+                let traced = traced.clone();
+                let captured = captured.clone();
+                let requested = requested.clone();
+                if matches!(mode, ServerMode::Portal) && intercepting.load(Ordering::SeqCst) {
+                    std::thread::spawn(move || sign_in_page(stream));
+                    continue;
+                }
+                if let ServerMode::KeepAlive = mode {
+                    std::thread::spawn(move || serve_keep_alive(stream, &body, &requested));
+                    continue;
+                }
+                std::thread::spawn(move || {
+                    serve(
+                        stream, &body, mode, &recorded, &accepted, &traced, &captured, &requested,
+                    )
+                });
+            }
+        });
+        Self {
+            url,
+            ranges,
+            encodings,
+            traceparents,
+            uploads,
+            methods,
+            body,
+            portal,
+            connections,
+        }
+    }
+
+    /// Turns the sign-in page of `Portal` mode on or off.
+    pub(crate) fn set_portal(&self, portal: bool) {
+        self.portal.store(portal, Ordering::SeqCst);
+    }
+
+    /// Replaces the body served to later connections.
+    pub(crate) fn set_body(&self, body: Vec<u8>) {
+        *self.body.lock().unwrap() = Arc::new(body);
+    }
+
+    /// Returns the `Range` start offsets requested so far, in order. In
+    /// `QueryOffset` mode, returns the `offset` query parameters instead, and
+    /// in `SlowSink` mode the number of body bytes received per upload.
+    pub(crate) fn ranges(&self) -> Vec<Option<u64>> {
+        self.ranges.lock().unwrap().clone()
+    }
+
+    /// Returns the `Accept-Encoding` of the requests so far, in order.
+    pub(crate) fn accept_encodings(&self) -> Vec<Option<String>> {
+        self.encodings.lock().unwrap().clone()
+    }
+
+    /// Returns the `traceparent` of the requests so far, in order.
+    pub(crate) fn traceparents(&self) -> Vec<Option<String>> {
+        self.traceparents.lock().unwrap().clone()
+    }
+
+    /// Returns the bodies of the uploads read in `Capture` mode, in order.
+    pub(crate) fn uploads(&self) -> Vec<Vec<u8>> {
+        self.uploads.lock().unwrap().clone()
+    }
+
+    /// Returns the methods of the requests so far, in order.
+    pub(crate) fn methods(&self) -> Vec<String> {
+        self.methods.lock().unwrap().clone()
+    }
+
+    /// Returns the number of connections accepted so far.
+    pub(crate) fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+fn bind() -> TcpListener {
+    let mut port = 7878;
+    loop {
+        match TcpListener::bind(("127.0.0.1", port)) {
+            Ok(listener) => break listener,
+            Err(_) => port += 1,
+        }
+    }
+}
+
+fn serve(
+    mut stream: TcpStream,
+    body: &[u8],
+    mode: ServerMode,
+    ranges: &Mutex<Vec<Option<u64>>>,
+    encodings: &Mutex<Vec<Option<String>>>,
+    traceparents: &Mutex<Vec<Option<String>>>,
+    uploads: &Mutex<Vec<Vec<u8>>>,
+    methods: &Mutex<Vec<String>>,
+) {
+    let mut range: Option<(u64, Option<u64>)> = None;
+    let mut accept_encoding = None;
+    let mut traceparent = None;
+    let mut if_match = None;
+    let mut content_length = 0;
+    let mut chunked = false;
+    let mut offset = None;
+    let mut reader = BufReader::new(&mut stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    if let Some(method) = request_line.split_whitespace().next() {
+        methods.lock().unwrap().push(method.to_string());
+    }
+    let generate_204 = request_line
+        .split_whitespace()
+        .nth(1)
+        .is_some_and(|path| path.ends_with("/generate_204"));
+    if let Some((_, query)) = request_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|path| path.split_once('?'))
+    {
+        offset = query
+            .split('&')
+            .find_map(|param| param.strip_prefix("offset="))
+            .and_then(|value| value.parse::<u64>().ok());
+    }
+    for line in reader.by_ref().lines() {
+        let Ok(line) = line else {
+            return;
+        };
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            if key.eq_ignore_ascii_case("range") {
+                range = value
+                    .trim()
+                    .trim_start_matches("bytes=")
+                    .split_once('-')
+                    .and_then(|(start, end)| {
+                        Some((start.parse::<u64>().ok()?, end.parse::<u64>().ok()))
+                    });
+            } else if key.eq_ignore_ascii_case("if-match") {
+                if_match = Some(value.trim().trim_matches('"').to_string());
+            } else if key.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>().unwrap_or(0);
+            } else if key.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.trim().eq_ignore_ascii_case("chunked");
+            } else if key.eq_ignore_ascii_case("accept-encoding") {
+                accept_encoding = Some(value.trim().to_string());
+            } else if key.eq_ignore_ascii_case("traceparent") {
+                traceparent = Some(value.trim().to_string());
+            }
+        }
+    }
+    let gzip = accept_encoding
+        .as_deref()
+        .is_some_and(|value| value.contains("gzip"));
+    encodings.lock().unwrap().push(accept_encoding);
+    traceparents.lock().unwrap().push(traceparent);
+    if let ServerMode::IfMatch(etag) = mode {
+        let mut uploaded = vec![0; content_length];
+        let _ = reader.read_exact(&mut uploaded);
+        let head = match if_match {
+            Some(if_match) if if_match == etag => format!(
+                "HTTP/1.1 200 OK\r\nETag: \"{}-next\"\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                etag
+            ),
+            _ => "HTTP/1.1 412 Precondition Failed\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                .to_string(),
+        };
+        let _ = stream.write_all(head.as_bytes());
+        return;
+    }
+    if let ServerMode::Capture = mode {
+        let uploaded = match chunked {
+            true => read_chunked(&mut reader),
+            false => {
+                let mut uploaded = vec![0; content_length];
+                reader.read_exact(&mut uploaded).ok().map(|_| uploaded)
+            }
+        };
+        let Some(uploaded) = uploaded else {
+            return;
+        };
+        uploads.lock().unwrap().push(uploaded);
+        let _ =
+            stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        return;
+    }
+    if let ServerMode::SlowSink { chunk, delay } = mode {
+        let mut received = 0;
+        let mut buf = vec![0; chunk];
+        while received < content_length {
+            let want = chunk.min(content_length - received);
+            match reader.read(&mut buf[..want]) {
+                Ok(0) | Err(_) => break,
+                Ok(size) => received += size,
+            }
+            std::thread::sleep(delay);
+        }
+        ranges.lock().unwrap().push(Some(received as u64));
+        if received == content_length {
+            let head = "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+            let _ = stream.write_all(head.as_bytes());
+        }
+        return;
+    }
+    if let ServerMode::QueryOffset { honored, .. } = mode {
+        ranges.lock().unwrap().push(offset);
+        range = match offset {
+            Some(offset) if honored => Some((offset, None)),
+            _ => None,
+        };
+        // Serves the offset like a range, but with `200 OK`.
+        let start = range.map_or(0, |(start, _)| (start as usize).min(body.len()));
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len() - start
+        );
+        if stream.write_all(head.as_bytes()).is_err() {
+            return;
+        }
+        write_body(&mut stream, &body[start..], mode);
+        return;
+    }
+    ranges.lock().unwrap().push(range.map(|(start, _)| start));
+
+    if let (ServerMode::Portal, true) = (mode, generate_204) {
+        let _ = stream.write_all(
+            b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+        return;
+    }
+
+    if let ServerMode::NotFound = mode {
+        let _ = stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        return;
+    }
+
+    if let ServerMode::Chunked | ServerMode::Trailer(..) = mode {
+        let announced = match mode {
+            ServerMode::Trailer(name, _) => format!("Trailer: {}\r\n", name),
+            _ => String::new(),
+        };
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n{}{}Connection: close\r\n\r\n",
+            announced, LAST_MODIFIED
+        );
+        if stream.write_all(head.as_bytes()).is_err() {
+            return;
+        }
+        for piece in body.chunks(8 * 1024) {
+            let size = format!("{:x}\r\n", piece.len());
+            let written = stream
+                .write_all(size.as_bytes())
+                .and_then(|_| stream.write_all(piece))
+                .and_then(|_| stream.write_all(b"\r\n"));
+            if written.is_err() {
+                return;
+            }
+        }
+        let last = match mode {
+            ServerMode::Trailer(name, value) => format!("0\r\n{}: {}\r\n\r\n", name, value),
+            _ => "0\r\n\r\n".to_string(),
+        };
+        let _ = stream.write_all(last.as_bytes());
+        return;
+    }
+
+    if matches!(range, Some((start, _)) if start >= body.len() as u64) {
+        let head = format!(
+            "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            body.len()
+        );
+        let _ = stream.write_all(head.as_bytes());
+        return;
+    }
+
+    let (start, end) = match range {
+        Some((start, end)) => (
+            (start as usize).min(body.len()),
+            end.map_or(body.len(), |end| (end as usize + 1).min(body.len())),
+        ),
+        None => (0, body.len()),
+    };
+    let end = match mode {
+        ServerMode::HalfRange if range.is_some() && end - start > 1 => start + (end - start) / 2,
+        _ => end,
+    };
+    let mut content = &body[start..end.max(start)];
+    let extra_headers = match mode {
+        ServerMode::ContentType(content_type) => format!("Content-Type: {}\r\n", content_type),
+        ServerMode::ManyHeaders(count) => (0..count)
+            .map(|i| format!("X-Filler-{}: v\r\n", i))
+            .collect(),
+        ServerMode::Gzip if gzip => "Content-Encoding: gzip\r\n".to_string(),
+        ServerMode::Portal => "Content-Type: application/octet-stream\r\n".to_string(),
+        ServerMode::Disposition(value) => format!("Content-Disposition: {}\r\n", value),
+        _ => String::new(),
+    };
+    let head = match range {
+        Some(_) => format!(
+            "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nAccept-Ranges: bytes\r\n{}{}Connection: close\r\n\r\n",
+            content.len(),
+            start,
+            end.max(start + 1) - 1,
+            body.len(),
+            LAST_MODIFIED,
+            extra_headers
+        ),
+        None => format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nAccept-Ranges: bytes\r\n{}{}Connection: close\r\n\r\n",
+            content.len(),
+            LAST_MODIFIED,
+            extra_headers
+        ),
+    };
+    if stream.write_all(head.as_bytes()).is_err() {
+        return;
+    }
+    if let ServerMode::Flaky = mode {
+        content = &content[..content.len() / 2];
+    }
+    write_body(&mut stream, content, mode);
+}
+
+/// Reads a chunked request body, `None` if it is malformed or cut off.
+fn read_chunked(reader: &mut impl BufRead) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let size = usize::from_str_radix(line.trim().split(';').next()?, 16).ok()?;
+        // The chunk is followed by its CRLF, the last one by the final CRLF.
+        let mut chunk = vec![0; size + 2];
+        reader.read_exact(&mut chunk).ok()?;
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(&chunk[..size]);
+    }
+}
+
+fn serve_keep_alive(mut stream: TcpStream, body: &[u8], methods: &Mutex<Vec<String>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    loop {
+        let mut request_line = String::new();
+        match reader.read_line(&mut request_line) {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+        if let Some(method) = request_line.split_whitespace().next() {
+            methods.lock().unwrap().push(method.to_string());
+        }
+        for line in reader.by_ref().lines() {
+            match line {
+                Ok(line) if !line.is_empty() => {}
+                Ok(_) => break,
+                Err(_) => return,
+            }
+        }
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n{}\r\n",
+            body.len(),
+            LAST_MODIFIED
+        );
+        if stream.write_all(head.as_bytes()).is_err() || stream.write_all(body).is_err() {
+            return;
+        }
+    }
+}
+
+fn sign_in_page(mut stream: TcpStream) {
+    let mut reader = BufReader::new(&mut stream);
+    for line in reader.by_ref().lines() {
+        match line {
+            Ok(line) if !line.is_empty() => {}
+            _ => break,
+        }
+    }
+    let page = "<html>sign in</html>";
+    let head = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        page.len()
+    );
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(page.as_bytes());
+}
+
+fn write_body(stream: &mut TcpStream, content: &[u8], mode: ServerMode) {
+    match mode {
+        ServerMode::Throttled { chunk, delay } | ServerMode::QueryOffset { chunk, delay, .. } => {
+            for piece in content.chunks(chunk) {
+                if stream.write_all(piece).is_err() {
+                    return;
+                }
+                std::thread::sleep(delay);
+            }
+        }
+        _ => {
+            let _ = stream.write_all(content);
+        }
+    }
+}
+
+/// Builds a deterministic body of `size` bytes.
+pub(crate) fn test_body(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i % 251) as u8).collect()
+}
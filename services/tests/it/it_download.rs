@@ -0,0 +1,1491 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::os::unix::fs::MetadataExt;
+use std::time::{Duration, Instant};
+
+use super::harness::{
+    await_state, construct_and_start, network_offline, network_online, serial, test_body,
+    FakeClient, Frame, ServerMode, TestServer, TASK_MANAGER,
+};
+use crate::config::{Action, ConfigBuilder, Mode, ResumeStrategy, TaskConfig, TraceContext};
+use crate::error::ErrorCode;
+use crate::info::State;
+use crate::manage::captive_portal;
+use crate::manage::database::RequestDb;
+use crate::manage::events::TaskManagerEvent;
+use crate::manage::scheduler::restore::restore_task;
+use crate::service::progress_brief::{progress_brief, progress_briefs, PROGRESS_BRIEF_MAX};
+use crate::task::final_metadata::{FINAL_MIME_TYPE, FINAL_SIZE};
+use crate::task::keep_alive::KeepAlivePool;
+use crate::task::net_binding::attach_net;
+use crate::task::notify::{SubscribeType, WaitingCause};
+use crate::task::reason::Reason;
+use crate::task::request_task::DEFAULT_MAX_RESPONSE_HEADERS;
+use crate::utils::form_item::FileSpec;
+use crate::utils::get_current_timestamp;
+
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+fn download_config(url: &str, path: &str, uid: u64) -> TaskConfig {
+    let file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .unwrap();
+    ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .version(2)
+        .file_spec(file)
+        .url(url)
+        .redirect(true)
+        .uid(uid)
+        .build()
+}
+
+// @tc.name: it_download_complete
+// @tc.desc: Test a download runs through construct, schedule, notify and complete
+// @tc.precon: NA
+// @tc.step: 1. Start a local server and a fake client
+//           2. Construct and start a download task
+//           3. Wait for the task to complete
+// @tc.expect: Task reaches Completed, client receives the complete frame and
+//             the file content matches the served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_complete() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Normal);
+    let client = FakeClient::open();
+    let path = "test_files/it_download_complete.txt";
+
+    let task_id = construct_and_start(download_config(&server.url, path, 5001), &client);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert!(client
+        .await_notify(task_id, SubscribeType::Complete, TIMEOUT)
+        .is_some());
+    assert_eq!(std::fs::read(path).unwrap(), body);
+}
+
+// @tc.name: it_download_pause_resume
+// @tc.desc: Test pausing a running download and resuming it with a range request
+// @tc.precon: NA
+// @tc.step: 1. Start a throttled local server and a fake client
+//           2. Start a download task and pause it mid-transfer
+//           3. Resume the task and wait for completion
+// @tc.expect: The resumed request carries a non-zero range start and the
+//             assembled file matches the served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_pause_resume() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(256 * 1024);
+    let server = TestServer::start(
+        body.clone(),
+        ServerMode::Throttled {
+            chunk: 8 * 1024,
+            delay: Duration::from_millis(50),
+        },
+    );
+    let client = FakeClient::open();
+    let path = "test_files/it_download_pause_resume.txt";
+    let uid = 5002;
+
+    let task_id = construct_and_start(download_config(&server.url, path, uid), &client);
+    assert!(await_state(task_id, State::Running, TIMEOUT));
+    client
+        .await_frame(TIMEOUT, |frame| {
+            matches!(frame, Frame::Notify { task_id: id, processed, .. }
+                if *id == task_id && *processed > 0)
+        })
+        .unwrap();
+
+    let (event, rx) = TaskManagerEvent::pause(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(await_state(task_id, State::Paused, TIMEOUT));
+
+    let (event, rx) = TaskManagerEvent::resume(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+
+    let ranges = server.ranges();
+    assert!(ranges.len() >= 2);
+    assert!(matches!(ranges.last(), Some(Some(begin)) if *begin > 0));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+}
+
+// @tc.name: it_download_network_recover
+// @tc.desc: Test a download waits while offline and recovers once online
+// @tc.precon: NA
+// @tc.step: 1. Start a throttled local server and a fake client
+//           2. Start a download task and drop the network mid-transfer
+//           3. Bring the network back and wait for completion
+// @tc.expect: Task enters Waiting while offline and then completes with the
+//             full body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_network_recover() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(256 * 1024);
+    let server = TestServer::start(
+        body.clone(),
+        ServerMode::Throttled {
+            chunk: 8 * 1024,
+            delay: Duration::from_millis(50),
+        },
+    );
+    let client = FakeClient::open();
+    let path = "test_files/it_download_network_recover.txt";
+
+    let task_id = construct_and_start(download_config(&server.url, path, 5003), &client);
+    assert!(await_state(task_id, State::Running, TIMEOUT));
+
+    network_offline();
+    assert!(await_state(task_id, State::Waiting, TIMEOUT));
+
+    network_online();
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+}
+
+// @tc.name: it_download_fail
+// @tc.desc: Test a download answered with 404 fails and notifies the client
+// @tc.precon: NA
+// @tc.step: 1. Start a local server answering 404 and a fake client
+//           2. Start a download task
+//           3. Wait for the task to fail
+// @tc.expect: Task reaches Failed and the client receives the fail frame
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_fail() {
+    let _serial = serial();
+    network_online();
+    let server = TestServer::start(vec![], ServerMode::NotFound);
+    let client = FakeClient::open();
+    let path = "test_files/it_download_fail.txt";
+
+    let task_id = construct_and_start(download_config(&server.url, path, 5004), &client);
+
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    assert!(client
+        .await_notify(task_id, SubscribeType::Fail, TIMEOUT)
+        .is_some());
+}
+
+/// Pauses a throttled download once more than `shrunk` bytes arrived, then
+/// shrinks the served file to `shrunk` bytes and resumes the task.
+fn resume_after_shrink(
+    path: &str,
+    uid: u64,
+    shrunk: usize,
+    strict_resume: bool,
+) -> (TestServer, u32) {
+    let server = TestServer::start(
+        test_body(256 * 1024),
+        ServerMode::Throttled {
+            chunk: 8 * 1024,
+            delay: Duration::from_millis(50),
+        },
+    );
+    let client = FakeClient::open();
+    let mut config = download_config(&server.url, path, uid);
+    config.strict_resume = strict_resume;
+
+    let task_id = construct_and_start(config, &client);
+    client
+        .await_frame(TIMEOUT, |frame| {
+            matches!(frame, Frame::Notify { task_id: id, processed, .. }
+                if *id == task_id && *processed > shrunk as u64)
+        })
+        .unwrap();
+    let (event, rx) = TaskManagerEvent::pause(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(await_state(task_id, State::Paused, TIMEOUT));
+
+    server.set_body(test_body(shrunk));
+    let (event, rx) = TaskManagerEvent::resume(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    (server, task_id)
+}
+
+// @tc.name: it_download_resume_shrunk_file
+// @tc.desc: Test a resume rejected with 416 restarts the download from zero
+// @tc.precon: NA
+// @tc.step: 1. Pause a download after more bytes than the new file size
+//           2. Shrink the served file and resume the task
+// @tc.expect: The stale range is answered with 416, the task downloads the
+//             whole file again without a range and completes with the new
+//             content
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_resume_shrunk_file() {
+    let _serial = serial();
+    network_online();
+    let path = "test_files/it_download_resume_shrunk_file.txt";
+    let shrunk = 16 * 1024;
+
+    let (server, task_id) = resume_after_shrink(path, 5005, shrunk, false);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    let ranges = server.ranges();
+    assert!(matches!(ranges[ranges.len() - 2], Some(begin) if begin > shrunk as u64));
+    assert_eq!(ranges.last(), Some(&None));
+    assert_eq!(std::fs::read(path).unwrap(), test_body(shrunk));
+}
+
+// @tc.name: it_download_strict_resume_shrunk_file
+// @tc.desc: Test a strict resume rejected with 416 fails the task
+// @tc.precon: NA
+// @tc.step: 1. Pause a strict resume download after more bytes than the new
+//              file size
+//           2. Shrink the served file and resume the task
+// @tc.expect: The task fails after the stale range request without
+//             downloading the file again
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_strict_resume_shrunk_file() {
+    let _serial = serial();
+    network_online();
+    let path = "test_files/it_download_strict_resume_shrunk_file.txt";
+    let shrunk = 16 * 1024;
+
+    let (server, task_id) = resume_after_shrink(path, 5006, shrunk, true);
+
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    assert!(matches!(server.ranges().last(), Some(Some(begin)) if *begin > shrunk as u64));
+}
+
+// @tc.name: it_download_preallocate
+// @tc.desc: Test a preallocating download reserves the whole file upfront
+// @tc.precon: NA
+// @tc.step: 1. Start a throttled local server and a fake client
+//           2. Start a download task with preallocation enabled
+//           3. Check the file once the first progress arrives
+// @tc.expect: Disk space for the whole body is allocated while the download
+//             is still running, the file size only grows with the written
+//             bytes and the completed file matches the served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_preallocate() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(1024 * 1024);
+    let server = TestServer::start(
+        body.clone(),
+        ServerMode::Throttled {
+            chunk: 64 * 1024,
+            delay: Duration::from_millis(50),
+        },
+    );
+    let client = FakeClient::open();
+    let path = "test_files/it_download_preallocate.txt";
+    let mut config = download_config(&server.url, path, 5007);
+    config.preallocate = true;
+
+    let task_id = construct_and_start(config, &client);
+    client
+        .await_frame(TIMEOUT, |frame| {
+            matches!(frame, Frame::Notify { task_id: id, processed, .. }
+                if *id == task_id && *processed > 0)
+        })
+        .unwrap();
+    let metadata = std::fs::metadata(path).unwrap();
+    assert!(metadata.blocks() * 512 >= body.len() as u64);
+    assert!(metadata.len() < body.len() as u64);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+}
+
+// @tc.name: it_download_unexpected_content_type
+// @tc.desc: Test a download expecting images fails on an HTML login page
+// @tc.precon: NA
+// @tc.step: 1. Start a local server answering with `text/html`, and a
+//              captive portal probe detecting no portal
+//           2. Start a download expecting `image/*`
+//           3. Wait for the task to fail
+// @tc.expect: Task fails with `UnexpectedContentType` and nothing is left in
+//             the file
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_unexpected_content_type() {
+    let _serial = serial();
+    network_online();
+    let probe = TestServer::start(vec![], ServerMode::Portal);
+    captive_portal::set_probe_url(&format!("{}generate_204", probe.url));
+    let server = TestServer::start(
+        b"<html>login</html>".to_vec(),
+        ServerMode::ContentType("text/html; charset=utf-8"),
+    );
+    let client = FakeClient::open();
+    let path = "test_files/it_download_unexpected_content_type.txt";
+    let mut config = download_config(&server.url, path, 5008);
+    config.expected_mime_types = vec!["image/*".to_string()];
+
+    let task_id = construct_and_start(config, &client);
+
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    assert!(client
+        .await_frame(TIMEOUT, |frame| {
+            *frame
+                == Frame::Faults {
+                    task_id,
+                    reason: Reason::UnexpectedContentType.repr as u32,
+                }
+        })
+        .is_some());
+    assert_eq!(std::fs::metadata(path).unwrap().len(), 0);
+    captive_portal::set_probe_url("");
+}
+
+// @tc.name: it_download_expected_content_type
+// @tc.desc: Test a download of an expected content type proceeds normally
+// @tc.precon: NA
+// @tc.step: 1. Start a local server answering with `image/png`
+//           2. Start a download expecting `image/*`
+//           3. Wait for the task to complete
+// @tc.expect: Task reaches Completed and the file matches the served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_expected_content_type() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::ContentType("image/png"));
+    let client = FakeClient::open();
+    let path = "test_files/it_download_expected_content_type.txt";
+    let mut config = download_config(&server.url, path, 5009);
+    config.expected_mime_types = vec!["image/*".to_string()];
+
+    let task_id = construct_and_start(config, &client);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+}
+
+// @tc.name: it_download_subscribe_batch
+// @tc.desc: Test many tasks are subscribed at once and report their state
+// @tc.precon: NA
+// @tc.step: 1. Construct several downloads without starting them
+//           2. Subscribe to all of them and to an unknown task at once
+// @tc.expect: Every download is subscribed and reports its initialized state
+//             to the client, the unknown task fails with `TaskNotFound`
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_subscribe_batch() {
+    let _serial = serial();
+    let server = TestServer::start(test_body(1024), ServerMode::Normal);
+    let client = FakeClient::open();
+
+    let mut task_ids = Vec::new();
+    for i in 0..8 {
+        let path = format!("test_files/it_download_subscribe_batch_{}.txt", i);
+        let (event, rx) = TaskManagerEvent::construct(download_config(&server.url, &path, 5012));
+        TASK_MANAGER.send_event(event);
+        task_ids.push(rx.get().unwrap().unwrap().task_id);
+    }
+    let mut requested = task_ids.clone();
+    requested.insert(3, u32::MAX);
+
+    let codes = client.subscribe_batch(requested);
+    assert_eq!(codes[3], ErrorCode::TaskNotFound);
+    assert!(codes
+        .iter()
+        .enumerate()
+        .all(|(i, code)| i == 3 || *code == ErrorCode::ErrOk));
+    let initialized = State::Initialized.repr as u32;
+    for task_id in task_ids {
+        let frame = client.await_notify(task_id, SubscribeType::Progress, TIMEOUT);
+        assert!(
+            matches!(frame, Some(Frame::Notify { state, .. }) if state == initialized),
+            "{:?}",
+            frame
+        );
+    }
+}
+
+/// Pauses a download from a server taking the resume offset as a query
+/// parameter, then resumes it with `strategy` and waits for completion.
+///
+/// The body does not repeat, so a response starting elsewhere than the
+/// requested offset cannot match the tail of the file.
+fn resume_by_query(path: &str, uid: u64, honored: bool, strategy: ResumeStrategy) -> TestServer {
+    let body: Vec<u8> = (0..256 * 1024u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+        .collect();
+    let server = TestServer::start(
+        body.clone(),
+        ServerMode::QueryOffset {
+            honored,
+            chunk: 8 * 1024,
+            delay: Duration::from_millis(50),
+        },
+    );
+    let client = FakeClient::open();
+    let mut config = download_config(&server.url, path, uid);
+    config.resume_strategy = strategy;
+
+    let task_id = construct_and_start(config, &client);
+    client
+        .await_frame(TIMEOUT, |frame| {
+            matches!(frame, Frame::Notify { task_id: id, processed, .. }
+                if *id == task_id && *processed > 16 * 1024)
+        })
+        .unwrap();
+    let (event, rx) = TaskManagerEvent::pause(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(await_state(task_id, State::Paused, TIMEOUT));
+
+    let (event, rx) = TaskManagerEvent::resume(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    server
+}
+
+fn offset_param() -> ResumeStrategy {
+    ResumeStrategy::QueryParam {
+        name: "offset".to_string(),
+    }
+}
+
+// @tc.name: it_download_resume_query_offset
+// @tc.desc: Test a download resumes by a query parameter offset
+// @tc.precon: NA
+// @tc.step: 1. Pause a download from a server honoring the offset parameter
+//           2. Resume the task with the query parameter strategy
+// @tc.expect: The resumed request carries a non-zero offset overlapping the
+//             file, the task completes with the served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_resume_query_offset() {
+    let _serial = serial();
+    network_online();
+    let path = "test_files/it_download_resume_query_offset.txt";
+
+    let server = resume_by_query(path, 5013, true, offset_param());
+
+    let offsets = server.ranges();
+    assert_eq!(offsets.len(), 2);
+    assert_eq!(offsets[0], None);
+    assert!(matches!(offsets[1], Some(offset) if offset > 0));
+}
+
+// @tc.name: it_download_resume_query_offset_ignored
+// @tc.desc: Test a resume answered from another offset restarts from zero
+// @tc.precon: NA
+// @tc.step: 1. Pause a download from a server ignoring the offset parameter
+//           2. Resume the task with the query parameter strategy
+// @tc.expect: The response does not match the tail of the file, the task
+//             downloads again without an offset and completes with the
+//             served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_resume_query_offset_ignored() {
+    let _serial = serial();
+    network_online();
+    let path = "test_files/it_download_resume_query_offset_ignored.txt";
+
+    let server = resume_by_query(path, 5014, false, offset_param());
+
+    let offsets = server.ranges();
+    assert!(offsets.len() >= 3);
+    assert!(matches!(offsets[offsets.len() - 2], Some(offset) if offset > 0));
+    assert_eq!(offsets.last(), Some(&None));
+}
+
+// @tc.name: it_download_resume_restart
+// @tc.desc: Test a download without a resume strategy restarts from zero
+// @tc.precon: NA
+// @tc.step: 1. Pause a download from a server honoring the offset parameter
+//           2. Resume the task with no resume strategy
+// @tc.expect: No request carries an offset, the task completes with the
+//             served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_resume_restart() {
+    let _serial = serial();
+    network_online();
+    let path = "test_files/it_download_resume_restart.txt";
+
+    let server = resume_by_query(path, 5015, true, ResumeStrategy::None);
+
+    let offsets = server.ranges();
+    assert!(offsets.len() >= 2);
+    assert!(offsets.iter().all(Option::is_none));
+}
+
+// @tc.name: it_download_wait_for_charging
+// @tc.desc: Test background downloads wait for charging when it is required
+// @tc.precon: NA
+// @tc.step: 1. Require charging for background tasks and stop charging
+//           2. Start a background download task
+//           3. Start charging
+// @tc.expect: The task waits with the battery cause while not charging and
+//             completes with the served body once charging
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_wait_for_charging() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Normal);
+    let client = FakeClient::open();
+    let path = "test_files/it_download_wait_for_charging.txt";
+    let (event, rx) = TaskManagerEvent::require_charging(true);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    TASK_MANAGER.notify_charging_change(false);
+
+    let task_id = construct_and_start(download_config(&server.url, path, 5016), &client);
+    assert!(await_state(task_id, State::Waiting, TIMEOUT));
+    let battery = WaitingCause::Battery as u32;
+    let frame = client.await_frame(TIMEOUT, |frame| {
+        matches!(frame, Frame::Waiting { task_id: id, cause }
+            if *id == task_id && *cause == battery)
+    });
+    assert!(frame.is_some());
+
+    TASK_MANAGER.notify_charging_change(true);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+
+    let (event, rx) = TaskManagerEvent::require_charging(false);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+}
+
+/// Starts a download from a server dropping every connection halfway,
+/// retrying until `deadline_ms` from now, 0 for no deadline.
+fn flaky_download(path: &str, uid: u64, deadline_ms: u64) -> (TestServer, FakeClient, u32) {
+    let server = TestServer::start(test_body(64 * 1024), ServerMode::Flaky);
+    let client = FakeClient::open();
+    let mut config = download_config(&server.url, path, uid);
+    if deadline_ms != 0 {
+        config.retry_deadline_ms = get_current_timestamp() + deadline_ms;
+    }
+    let task_id = construct_and_start(config, &client);
+    (server, client, task_id)
+}
+
+// @tc.name: it_download_retry_deadline
+// @tc.desc: Test a download stops retrying once its retry deadline passed
+// @tc.precon: NA
+// @tc.step: 1. Start a server dropping every connection halfway
+//           2. Start a download with a deadline shorter than its retries
+//           3. Wait for the task to fail
+// @tc.expect: Task fails with `RetryDeadlineExceeded` before its retries
+//             are used
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_retry_deadline() {
+    let _serial = serial();
+    network_online();
+    let path = "test_files/it_download_retry_deadline.txt";
+    let (server, client, task_id) = flaky_download(path, 5017, 300);
+
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    let reason = Reason::RetryDeadlineExceeded.repr as u32;
+    assert!(client
+        .await_frame(TIMEOUT, |frame| {
+            *frame == Frame::Faults { task_id, reason }
+        })
+        .is_some());
+    assert!(server.ranges().len() < 5);
+}
+
+// @tc.name: it_download_retry_deadline_after_retries
+// @tc.desc: Test a download whose retries are used before its deadline fails
+//           like one without deadline
+// @tc.precon: NA
+// @tc.step: 1. Start a server dropping every connection halfway
+//           2. Start a download with a deadline far in the future
+//           3. Wait for the task to fail
+// @tc.expect: Task fails with `OthersError` once its retries are used
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_retry_deadline_after_retries() {
+    let _serial = serial();
+    network_online();
+    let path = "test_files/it_download_retry_deadline_after_retries.txt";
+    let (server, client, task_id) = flaky_download(path, 5018, 3_600_000);
+
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    let reason = Reason::OthersError.repr as u32;
+    assert!(client
+        .await_frame(TIMEOUT, |frame| {
+            *frame == Frame::Faults { task_id, reason }
+        })
+        .is_some());
+    assert!(server.ranges().len() >= 5);
+}
+
+// @tc.name: it_download_restore_interrupted
+// @tc.desc: Test a download cut off by a service restart resumes cleanly
+// @tc.precon: NA
+// @tc.step: 1. Construct a download whose file holds the persisted bytes and
+//              a torn tail, and mark it running in the database
+//           2. Restore the task as on service start
+//           3. Let the scheduler reload the tasks and wait for completion
+// @tc.expect: The task waits again with its file cut back to the persisted
+//             offset, resumes with a range from that offset and the completed
+//             file matches the served body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_restore_interrupted() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(128 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Normal);
+    let path = "test_files/it_download_restore_interrupted.txt";
+    let persisted = 40 * 1024;
+
+    let (event, rx) = TaskManagerEvent::construct(download_config(&server.url, path, 5019));
+    TASK_MANAGER.send_event(event);
+    let task_id = rx.get().unwrap().unwrap().task_id;
+    let mut partial = body[..persisted].to_vec();
+    partial.extend_from_slice(&[0xff; 4096]);
+    std::fs::write(path, partial).unwrap();
+    let db = RequestDb::get_instance();
+    db.execute(&format!(
+        "UPDATE request_task SET state = {}, total_processed = {} WHERE task_id = {}",
+        State::Running.repr,
+        persisted,
+        task_id
+    ))
+    .unwrap();
+
+    restore_task(db, task_id);
+    assert_eq!(db.query_task_state(task_id), Some(State::Waiting.repr));
+    assert_eq!(std::fs::metadata(path).unwrap().len(), persisted as u64);
+
+    TASK_MANAGER.send_event(TaskManagerEvent::network());
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(server.ranges().first(), Some(&Some(persisted as u64)));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+}
+
+// @tc.name: it_download_many_headers
+// @tc.desc: Test a download whose response carries a flood of headers
+// @tc.precon: NA
+// @tc.step: 1. Start a server answering with 10000 filler headers
+//           2. Construct and start a download task
+//           3. Wait for the task to complete
+// @tc.expect: The task completes with the served body and keeps at most
+//             `DEFAULT_MAX_RESPONSE_HEADERS` of the headers
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_many_headers() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::ManyHeaders(10000));
+    let client = FakeClient::open();
+    let path = "test_files/it_download_many_headers.txt";
+    let uid = 5020;
+
+    let (event, rx) = TaskManagerEvent::construct(download_config(&server.url, path, uid));
+    TASK_MANAGER.send_event(event);
+    let task_id = rx.get().unwrap().unwrap().task_id;
+    let task = RequestDb::get_instance()
+        .get_user_file_task(task_id)
+        .unwrap();
+    client.subscribe(task_id, uid);
+    let (event, rx) = TaskManagerEvent::start(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    let progress = task.progress.lock().unwrap();
+    let fillers = progress
+        .extras
+        .keys()
+        .filter(|key| key.starts_with("x-filler-"))
+        .count();
+    assert!(fillers > 0);
+    assert!(fillers <= DEFAULT_MAX_RESPONSE_HEADERS);
+}
+
+/// Waits for a capped download to fail with `SizeLimitExceeded` and checks
+/// nothing of it is left in its file.
+fn assert_size_limit_exceeded(client: &FakeClient, task_id: u32, path: &str) {
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    let reason = Reason::SizeLimitExceeded.repr as u32;
+    assert!(client
+        .await_frame(TIMEOUT, |frame| {
+            *frame == Frame::Faults { task_id, reason }
+        })
+        .is_some());
+    assert_eq!(std::fs::metadata(path).unwrap().len(), 0);
+}
+
+// @tc.name: it_download_size_cap_declared
+// @tc.desc: Test a download whose declared length exceeds its cap fails
+// @tc.precon: NA
+// @tc.step: 1. Start a server serving a body larger than the cap
+//           2. Start a download capped below the body size
+// @tc.expect: Task fails with `SizeLimitExceeded` and its file is empty
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_size_cap_declared() {
+    let _serial = serial();
+    network_online();
+    let server = TestServer::start(test_body(64 * 1024), ServerMode::Normal);
+    let client = FakeClient::open();
+    let path = "test_files/it_download_size_cap_declared.txt";
+    let mut config = download_config(&server.url, path, 5023);
+    config.max_size_bytes = Some(32 * 1024);
+
+    let task_id = construct_and_start(config, &client);
+    assert_size_limit_exceeded(&client, task_id, path);
+}
+
+// @tc.name: it_download_size_cap_streaming
+// @tc.desc: Test a download of unknown length is cut off at its cap
+// @tc.precon: NA
+// @tc.step: 1. Start a server serving a chunked body larger than the cap
+//           2. Start a download capped below the body size
+// @tc.expect: Task fails with `SizeLimitExceeded` and its file is empty
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_size_cap_streaming() {
+    let _serial = serial();
+    network_online();
+    let server = TestServer::start(test_body(64 * 1024), ServerMode::Chunked);
+    let client = FakeClient::open();
+    let path = "test_files/it_download_size_cap_streaming.txt";
+    let mut config = download_config(&server.url, path, 5024);
+    config.max_total_bytes = Some(32 * 1024);
+
+    let task_id = construct_and_start(config, &client);
+    assert_size_limit_exceeded(&client, task_id, path);
+}
+
+// @tc.name: it_download_size_cap_resume
+// @tc.desc: Test the bytes a download resumes from count toward its cap
+// @tc.precon: NA
+// @tc.step: 1. Construct a download capped between the remaining and the
+//              whole body size, with half of the body already in its file
+//           2. Start the task
+// @tc.expect: The task resumes from the half, then fails with
+//             `SizeLimitExceeded` and its file is empty
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_size_cap_resume() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Normal);
+    let client = FakeClient::open();
+    let path = "test_files/it_download_size_cap_resume.txt";
+    let uid = 5025;
+    let mut config = download_config(&server.url, path, uid);
+    config.max_size_bytes = Some(48 * 1024);
+
+    let (event, rx) = TaskManagerEvent::construct(config);
+    TASK_MANAGER.send_event(event);
+    let task_id = rx.get().unwrap().unwrap().task_id;
+    std::fs::write(path, &body[..32 * 1024]).unwrap();
+    client.subscribe(task_id, uid);
+    let (event, rx) = TaskManagerEvent::start(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+
+    assert_size_limit_exceeded(&client, task_id, path);
+    assert_eq!(server.ranges().first(), Some(&Some(32 * 1024)));
+}
+
+// @tc.name: it_download_accept_encoding
+// @tc.desc: Test the advertised `Accept-Encoding` follows the task config
+// @tc.precon: NA
+// @tc.step: 1. Start a server labelling gzip accepting responses as gzip
+//           2. Download with `accept_encoding` set to gzip, overriding the
+//              `Accept-Encoding` of the task headers
+//           3. Download with `accept_encoding` set to identity
+// @tc.expect: Each request advertises the configured codings, and both files
+//             hold the bytes as served, without decoding
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_accept_encoding() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Gzip);
+    let client = FakeClient::open();
+
+    let path = "test_files/it_download_accept_encoding_gzip.txt";
+    let mut config = download_config(&server.url, path, 5026);
+    config
+        .headers
+        .insert("Accept-Encoding".to_string(), "br".to_string());
+    config.accept_encoding = "gzip".to_string();
+    let task_id = construct_and_start(config, &client);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+
+    let path = "test_files/it_download_accept_encoding_identity.txt";
+    let mut config = download_config(&server.url, path, 5027);
+    config.accept_encoding = "identity".to_string();
+    let task_id = construct_and_start(config, &client);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+
+    assert_eq!(
+        server.accept_encodings(),
+        vec![Some("gzip".to_string()), Some("identity".to_string())]
+    );
+}
+
+// @tc.name: it_download_captive_portal
+// @tc.desc: Test a download behind a captive portal waits until the user
+//           signs in
+// @tc.precon: NA
+// @tc.step: 1. Start a server answering with a sign-in page, and probe it
+//              for a captive portal
+//           2. Start a download expecting `application/octet-stream`
+//           3. Turn the sign-in page off
+// @tc.expect: The task waits with the captive portal cause instead of
+//             failing, and completes with the served body once the probe
+//             no longer detects the portal
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_captive_portal() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Portal);
+    server.set_portal(true);
+    captive_portal::set_probe_url(&format!("{}generate_204", server.url));
+    captive_portal::set_reprobe_interval(Duration::from_millis(200));
+    let client = FakeClient::open();
+    let path = "test_files/it_download_captive_portal.txt";
+    let mut config = download_config(&server.url, path, 5028);
+    config.expected_mime_types = vec!["application/octet-stream".to_string()];
+
+    let task_id = construct_and_start(config, &client);
+    let portal = WaitingCause::CaptivePortal as u32;
+    let frame = client.await_frame(TIMEOUT, |frame| {
+        matches!(frame, Frame::Waiting { task_id: id, cause }
+            if *id == task_id && *cause == portal)
+    });
+    assert!(frame.is_some());
+    assert!(await_state(task_id, State::Waiting, TIMEOUT));
+
+    server.set_portal(false);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+
+    captive_portal::set_probe_url("");
+    captive_portal::set_reprobe_interval(Duration::from_secs(30));
+}
+
+// @tc.name: it_download_captive_portal_server_error
+// @tc.desc: Test server errors fail normally while a portal would be detected
+// @tc.precon: NA
+// @tc.step: 1. Start a captive portal probe detecting a portal
+//           2. Start a download answered with 404
+//           3. Wait for the task to fail
+// @tc.expect: Task fails and notifies the client without waiting for the
+//             captive portal
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_captive_portal_server_error() {
+    let _serial = serial();
+    network_online();
+    let probe = TestServer::start(vec![], ServerMode::Portal);
+    probe.set_portal(true);
+    captive_portal::set_probe_url(&format!("{}generate_204", probe.url));
+    let server = TestServer::start(vec![], ServerMode::NotFound);
+    let client = FakeClient::open();
+    let path = "test_files/it_download_captive_portal_server_error.txt";
+
+    let task_id = construct_and_start(download_config(&server.url, path, 5029), &client);
+
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    assert!(client
+        .await_notify(task_id, SubscribeType::Fail, TIMEOUT)
+        .is_some());
+    let portal = WaitingCause::CaptivePortal as u32;
+    let frame = client.await_frame(
+        Duration::from_millis(200),
+        |frame| matches!(frame, Frame::Waiting { cause, .. } if *cause == portal),
+    );
+    assert!(frame.is_none());
+    captive_portal::set_probe_url("");
+}
+
+// @tc.name: it_download_bound_network
+// @tc.desc: Test downloads bound to a network run only while it is available
+// @tc.precon: NA
+// @tc.step: 1. Construct a download bound to a negative network handle
+//           2. Attach a network and download bound to it
+//           3. Detach the network and download bound to it again
+// @tc.expect: The negative handle is rejected, the first bound download
+//             completes and the second fails with `BoundNetworkUnavailable`
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_bound_network() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Normal);
+    let client = FakeClient::open();
+    let net_id = 7;
+
+    let path = "test_files/it_download_bound_network_invalid.txt";
+    let mut config = download_config(&server.url, path, 5030);
+    config.net_id = -1;
+    let (event, rx) = TaskManagerEvent::construct(config);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), Err(ErrorCode::ParameterCheck));
+
+    attach_net(net_id, true);
+    let path = "test_files/it_download_bound_network.txt";
+    let mut config = download_config(&server.url, path, 5030);
+    config.net_id = net_id;
+    let task_id = construct_and_start(config, &client);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+
+    attach_net(net_id, false);
+    let path = "test_files/it_download_bound_network_detached.txt";
+    let mut config = download_config(&server.url, path, 5031);
+    config.net_id = net_id;
+    let task_id = construct_and_start(config, &client);
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    assert!(client
+        .await_frame(TIMEOUT, |frame| {
+            *frame
+                == Frame::Faults {
+                    task_id,
+                    reason: Reason::BoundNetworkUnavailable.repr as u32,
+                }
+        })
+        .is_some());
+}
+
+// @tc.name: it_download_speed_history
+// @tc.desc: Test the speed history of a download is sampled and queryable
+// @tc.precon: NA
+// @tc.step: 1. Start a throttled download and query its history while
+//              running
+//           2. Wait for completion and query the history again
+// @tc.expect: Samples accumulate with growing processed bytes while running
+//             and the final window ends at the full body size
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_speed_history() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(512 * 1024);
+    let server = TestServer::start(
+        body.clone(),
+        ServerMode::Throttled {
+            chunk: 8 * 1024,
+            delay: Duration::from_millis(50),
+        },
+    );
+    let client = FakeClient::open();
+    let path = "test_files/it_download_speed_history.txt";
+
+    let task_id = construct_and_start(download_config(&server.url, path, 5032), &client);
+    assert!(await_state(task_id, State::Running, TIMEOUT));
+    std::thread::sleep(Duration::from_millis(2500));
+
+    let (event, rx) = TaskManagerEvent::speed_history(task_id, 0);
+    TASK_MANAGER.send_event(event);
+    let samples = rx.get().unwrap().unwrap();
+    assert!(samples.len() >= 2);
+    assert!(samples
+        .windows(2)
+        .all(|pair| pair[0].time < pair[1].time && pair[0].processed <= pair[1].processed));
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    let (event, rx) = TaskManagerEvent::speed_history(task_id, 1);
+    TASK_MANAGER.send_event(event);
+    let last = rx.get().unwrap().unwrap();
+    assert_eq!(last.len(), 1);
+    assert_eq!(last[0].processed, body.len() as u64);
+
+    let (event, rx) = TaskManagerEvent::speed_history(u32::MAX, 0);
+    TASK_MANAGER.send_event(event);
+    assert!(rx.get().unwrap().is_none());
+}
+
+// @tc.name: it_download_progress_brief
+// @tc.desc: Test polling the progress of running and finished downloads
+// @tc.precon: NA
+// @tc.step: 1. Start a throttled download and poll it while running
+//           2. Poll it as another uid with and without manager permission
+//           3. Wait for completion and poll it again, also batched
+// @tc.expect: A running task reports its live progress and speed, other
+//             uids are rejected unless managers, a finished task reports its
+//             database row and oversized batches are rejected
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_progress_brief() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(512 * 1024);
+    let server = TestServer::start(
+        body.clone(),
+        ServerMode::Throttled {
+            chunk: 8 * 1024,
+            delay: Duration::from_millis(50),
+        },
+    );
+    let client = FakeClient::open();
+    let path = "test_files/it_download_progress_brief.txt";
+    let uid = 5033;
+
+    let task_id = construct_and_start(download_config(&server.url, path, uid), &client);
+    assert!(await_state(task_id, State::Running, TIMEOUT));
+    std::thread::sleep(Duration::from_millis(1500));
+    let brief = progress_brief(task_id, uid, false);
+    assert_eq!(brief.code, ErrorCode::ErrOk as i32);
+    assert_eq!(brief.state, State::Running.repr);
+    assert!(brief.processed > 0);
+    assert_eq!(brief.total, body.len() as i64);
+    assert!(brief.speed > 0);
+
+    let brief = progress_brief(task_id, uid + 1, false);
+    assert_eq!(brief.code, ErrorCode::TaskNotFound as i32);
+    let brief = progress_brief(task_id, uid + 1, true);
+    assert_eq!(brief.code, ErrorCode::ErrOk as i32);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    let briefs = progress_briefs(&[task_id, u32::MAX], uid, false);
+    assert_eq!(briefs[0].code, ErrorCode::ErrOk as i32);
+    assert_eq!(briefs[0].state, State::Completed.repr);
+    assert_eq!(briefs[0].processed, body.len() as u64);
+    assert_eq!(briefs[0].speed, 0);
+    assert_eq!(briefs[1].code, ErrorCode::TaskNotFound as i32);
+
+    let task_ids = vec![task_id; PROGRESS_BRIEF_MAX + 1];
+    let briefs = progress_briefs(&task_ids, uid, false);
+    assert!(briefs
+        .iter()
+        .all(|brief| brief.code == ErrorCode::Other as i32));
+}
+
+// @tc.name: it_download_content_disposition
+// @tc.desc: Test a download named after its URL takes the server file name
+// @tc.precon: NA
+// @tc.step: 1. Start a local server sending a `Content-Disposition` file name
+//              with directories and reserved characters
+//           2. Download to a path the service opens, with and without
+//              `content_disposition_name`
+// @tc.expect: With the option the file is renamed to the sanitized server
+//             name in the same directory and the database records the new
+//             path, without it the file keeps its name
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_content_disposition() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(
+        body.clone(),
+        ServerMode::Disposition("attachment; filename=\"../../re:port?.pdf\""),
+    );
+    let client = FakeClient::open();
+    let uid = 5034;
+    let renamed = "test_files/re_port_.pdf";
+    let _ = std::fs::remove_file(renamed);
+
+    for adopt in [true, false] {
+        let path = format!("test_files/it_download_content_disposition_{}", adopt);
+        File::create(&path).unwrap();
+        let mut config = ConfigBuilder::new()
+            .action(Action::Download)
+            .mode(Mode::BackGround)
+            .version(2)
+            .url(&server.url)
+            .uid(uid)
+            .content_disposition_name(adopt)
+            .build();
+        config.file_specs.push(FileSpec {
+            name: "file".to_string(),
+            path: path.clone(),
+            file_name: path.rsplit('/').next().unwrap().to_string(),
+            mime_type: String::new(),
+            is_user_file: false,
+            fd: None,
+            priority: 0,
+        });
+
+        let task_id = construct_and_start(config, &client);
+        assert!(await_state(task_id, State::Completed, TIMEOUT));
+        let destination = RequestDb::get_instance().query_destination(task_id);
+        if adopt {
+            assert!(!std::path::Path::new(&path).exists());
+            assert_eq!(std::fs::read(renamed).unwrap(), body);
+            assert_eq!(destination.as_deref(), Some(renamed));
+        } else {
+            assert_eq!(std::fs::read(&path).unwrap(), body);
+            assert_eq!(destination, None);
+        }
+    }
+}
+
+// @tc.name: it_download_trace_context
+// @tc.desc: Test a download sends its trace context on every attempt
+// @tc.precon: NA
+// @tc.step: 1. Start a server dropping every connection halfway
+//           2. Start a download with a trace context and wait for it to fail
+// @tc.expect: Every attempt carries a `traceparent` of the trace of the task
+//             with a span id of its own
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_trace_context() {
+    let _serial = serial();
+    network_online();
+    let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    let server = TestServer::start(test_body(64 * 1024), ServerMode::Flaky);
+    let client = FakeClient::open();
+    let path = "test_files/it_download_trace_context.txt";
+    let mut config = download_config(&server.url, path, 5035);
+    config.trace_context = Some(TraceContext {
+        traceparent: traceparent.to_string(),
+        tracestate: None,
+    });
+    let task_id = construct_and_start(config, &client);
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+
+    let sent = server.traceparents();
+    assert!(sent.len() > 1);
+    let mut spans = Vec::new();
+    for parent in sent {
+        let parent = parent.unwrap();
+        let fields: Vec<&str> = parent.split('-').collect();
+        assert_eq!(fields[1], "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_ne!(fields[2], "00f067aa0ba902b7");
+        assert!(!spans.contains(&fields[2].to_string()));
+        spans.push(fields[2].to_string());
+    }
+}
+
+// @tc.name: it_download_final_metadata
+// @tc.desc: Test a completed download records the size and type of its file
+// @tc.precon: NA
+// @tc.step: 1. Start a server serving the body with a content type
+//           2. Download with `record_final_metadata` and wait for completion
+// @tc.expect: The progress extras of the task hold the size of the file on
+//             disk and the served content type
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_final_metadata() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::ContentType("image/png"));
+    let client = FakeClient::open();
+    let path = "test_files/it_download_final_metadata.txt";
+    let mut config = download_config(&server.url, path, 5036);
+    config.record_final_metadata = true;
+    let task_id = construct_and_start(config, &client);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+
+    let size = std::fs::metadata(path).unwrap().len();
+    assert_eq!(size, body.len() as u64);
+    let info = RequestDb::get_instance().get_task_info(task_id).unwrap();
+    let extras = &info.progress.extras;
+    assert_eq!(extras.get(FINAL_SIZE), Some(&size.to_string()));
+    assert_eq!(
+        extras.get(FINAL_MIME_TYPE).map(String::as_str),
+        Some("image/png")
+    );
+}
+
+// @tc.name: it_download_notify_after_commit
+// @tc.desc: Test every state notification is sent once the state it reports
+//           is in the database
+// @tc.precon: NA
+// @tc.step: 1. Start a throttled download, pause and resume it and let it
+//              complete
+//           2. Start a download failing with 404 and remove it
+//           3. On every notification, query the state of its task at once
+// @tc.expect: The queried state already reflects each pause, resume,
+//             complete, fail and remove notification
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_notify_after_commit() {
+    let _serial = serial();
+    network_online();
+    let server = TestServer::start(
+        test_body(256 * 1024),
+        ServerMode::Throttled {
+            chunk: 8 * 1024,
+            delay: Duration::from_millis(50),
+        },
+    );
+    let client = FakeClient::open();
+    let uid = 5037;
+
+    let path = "test_files/it_download_notify_after_commit.txt";
+    let task_id = construct_and_start(download_config(&server.url, path, uid), &client);
+    assert!(await_state(task_id, State::Running, TIMEOUT));
+    let (event, rx) = TaskManagerEvent::pause(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(client
+        .await_notify(task_id, SubscribeType::Pause, TIMEOUT)
+        .is_some());
+    let (event, rx) = TaskManagerEvent::resume(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(client
+        .await_notify(task_id, SubscribeType::Complete, TIMEOUT)
+        .is_some());
+
+    let failing = TestServer::start(vec![], ServerMode::NotFound);
+    let path = "test_files/it_download_notify_after_commit_fail.txt";
+    let task_id = construct_and_start(download_config(&failing.url, path, uid), &client);
+    assert!(client
+        .await_notify(task_id, SubscribeType::Fail, TIMEOUT)
+        .is_some());
+    let (event, rx) = TaskManagerEvent::remove(uid, task_id, false);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(client
+        .await_notify(task_id, SubscribeType::Remove, TIMEOUT)
+        .is_some());
+
+    let mut checked = 0;
+    for (frame, state) in client.frames_with_states() {
+        let Frame::Notify { subscribe_type, .. } = frame else {
+            continue;
+        };
+        let expected: &[State] = match subscribe_type {
+            t if t == SubscribeType::Pause as u32 => &[State::Paused],
+            t if t == SubscribeType::Resume as u32 => &[State::Waiting, State::Running],
+            t if t == SubscribeType::Complete as u32 => &[State::Completed],
+            t if t == SubscribeType::Fail as u32 => &[State::Failed],
+            t if t == SubscribeType::Remove as u32 => &[State::Removed],
+            _ => continue,
+        };
+        assert!(
+            expected.iter().any(|expected| Some(expected.repr) == state),
+            "notify {} observed state {:?}",
+            subscribe_type,
+            state
+        );
+        checked += 1;
+    }
+    assert_eq!(checked, 5);
+}
+
+// @tc.name: it_download_pause_on_thermal_level
+// @tc.desc: Test downloads configured with a thermal level wait while the
+//           device is that hot
+// @tc.precon: NA
+// @tc.step: 1. Raise the thermal level of the device
+//           2. Start a download pausing at that level and one without a level
+//           3. Lower the thermal level
+// @tc.expect: The download with a level waits with the device condition cause
+//             while the other completes, and completes once the level drops
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_pause_on_thermal_level() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Normal);
+    let client = FakeClient::open();
+    TASK_MANAGER.notify_thermal_change(3);
+
+    let path = "test_files/it_download_pause_on_thermal_level.txt";
+    let mut config = download_config(&server.url, path, 5042);
+    config.pause_on_thermal_level = Some(3);
+    let task_id = construct_and_start(config, &client);
+    let other_path = "test_files/it_download_pause_on_thermal_level_other.txt";
+    let other = construct_and_start(download_config(&server.url, other_path, 5042), &client);
+
+    assert!(await_state(task_id, State::Waiting, TIMEOUT));
+    let device_condition = WaitingCause::DeviceCondition as u32;
+    let frame = client.await_frame(TIMEOUT, |frame| {
+        matches!(frame, Frame::Waiting { task_id: id, cause }
+            if *id == task_id && *cause == device_condition)
+    });
+    assert!(frame.is_some());
+    assert!(await_state(other, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(other_path).unwrap(), body);
+
+    TASK_MANAGER.notify_thermal_change(0);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+}
+
+// @tc.name: it_download_require_charging_while_running
+// @tc.desc: Test downloads requiring charging wait while the device is not
+//           charging
+// @tc.precon: NA
+// @tc.step: 1. Stop charging
+//           2. Start a download requiring charging and one without
+//           3. Start charging
+// @tc.expect: The download requiring charging waits with the device condition
+//             cause while the other completes, and completes once charging
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_require_charging_while_running() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Normal);
+    let client = FakeClient::open();
+    TASK_MANAGER.notify_charging_change(false);
+
+    let path = "test_files/it_download_require_charging_while_running.txt";
+    let mut config = download_config(&server.url, path, 5043);
+    config.require_charging_while_running = true;
+    let task_id = construct_and_start(config, &client);
+    let other_path = "test_files/it_download_require_charging_while_running_other.txt";
+    let other = construct_and_start(download_config(&server.url, other_path, 5043), &client);
+
+    assert!(await_state(task_id, State::Waiting, TIMEOUT));
+    let device_condition = WaitingCause::DeviceCondition as u32;
+    let frame = client.await_frame(TIMEOUT, |frame| {
+        matches!(frame, Frame::Waiting { task_id: id, cause }
+            if *id == task_id && *cause == device_condition)
+    });
+    assert!(frame.is_some());
+    assert!(await_state(other, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(other_path).unwrap(), body);
+
+    TASK_MANAGER.notify_charging_change(true);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+}
+
+// @tc.name: it_download_trailers
+// @tc.desc: Test trailers sent after a chunked body reach the task extras
+// @tc.precon: NA
+// @tc.step: 1. Start a server announcing and sending a checksum trailer
+//           2. Download from it and wait for completion
+//           3. Download from a server sending no trailers
+// @tc.expect: Both downloads complete with the served body, and only the
+//             first holds the trailer in its progress extras
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_trailers() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(64 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::Trailer("X-Checksum", "abc123"));
+    let client = FakeClient::open();
+    let path = "test_files/it_download_trailers.txt";
+    let task_id = construct_and_start(download_config(&server.url, path, 5044), &client);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    let info = RequestDb::get_instance().get_task_info(task_id).unwrap();
+    assert_eq!(
+        info.progress.extras.get("x-checksum").map(String::as_str),
+        Some("abc123")
+    );
+
+    let server = TestServer::start(body.clone(), ServerMode::Chunked);
+    let path = "test_files/it_download_trailers_none.txt";
+    let task_id = construct_and_start(download_config(&server.url, path, 5045), &client);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    let info = RequestDb::get_instance().get_task_info(task_id).unwrap();
+    assert!(!info.progress.extras.contains_key("x-checksum"));
+}
+
+// @tc.name: it_download_keep_alive
+// @tc.desc: Test sequential downloads from one host share the connection
+// @tc.precon: NA
+// @tc.step: 1. Start a server keeping connections open
+//           2. Download from it and wait until the client is pooled
+//           3. Download from it again
+// @tc.expect: Both downloads complete with the served body over a single
+//             connection
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_download_keep_alive() {
+    let _serial = serial();
+    network_online();
+    let body = test_body(16 * 1024);
+    let server = TestServer::start(body.clone(), ServerMode::KeepAlive);
+    let client = FakeClient::open();
+    let pool = KeepAlivePool::get_instance();
+    let idle = pool.idle();
+
+    let path = "test_files/it_download_keep_alive.txt";
+    let task_id = construct_and_start(download_config(&server.url, path, 5047), &client);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    let deadline = Instant::now() + TIMEOUT;
+    while pool.idle() <= idle && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(pool.idle() > idle);
+
+    let path = "test_files/it_download_keep_alive_again.txt";
+    let task_id = construct_and_start(download_config(&server.url, path, 5047), &client);
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    assert_eq!(server.methods().len(), 2);
+    assert_eq!(server.connections(), 1);
+}
@@ -0,0 +1,156 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::time::Duration;
+
+use super::harness::{await_state, construct_and_start, serial, FakeClient, Frame, TASK_MANAGER};
+use crate::config::{ConfigBuilder, Mode, TaskConfig};
+use crate::error::ErrorCode;
+use crate::info::State;
+use crate::manage::events::TaskManagerEvent;
+use crate::task::loopback::pattern;
+use crate::task::notify::SubscribeType;
+
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+fn loopback_config(path: &str, uid: u64, size: u64, fail_at: Option<u64>) -> TaskConfig {
+    let file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .unwrap();
+    ConfigBuilder::new()
+        .loopback(size, 128 * 1024, fail_at)
+        .mode(Mode::BackGround)
+        .version(2)
+        .file_spec(file)
+        .uid(uid)
+        .privileged(true)
+        .build()
+}
+
+fn expected_file(len: u64) -> Vec<u8> {
+    (0..len).map(pattern).collect()
+}
+
+// @tc.name: it_loopback_pause_resume
+// @tc.desc: Test a loopback task runs through pause and resume like a download
+// @tc.precon: NA
+// @tc.step: 1. Start a rate limited loopback task and a fake client
+//           2. Pause the task once it made progress
+//           3. Resume the task and wait for completion
+// @tc.expect: The file holds the whole pattern and the client receives the
+//             pause, resume and complete notifications in order
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_loopback_pause_resume() {
+    let _serial = serial();
+    let client = FakeClient::open();
+    let path = "test_files/it_loopback_pause_resume.txt";
+    let uid = 5038;
+    let size = 256 * 1024;
+
+    let task_id = construct_and_start(loopback_config(path, uid, size, None), &client);
+    assert!(await_state(task_id, State::Running, TIMEOUT));
+    client
+        .await_frame(TIMEOUT, |frame| {
+            matches!(frame, Frame::Notify { task_id: id, processed, .. }
+                if *id == task_id && *processed > 0)
+        })
+        .unwrap();
+
+    let (event, rx) = TaskManagerEvent::pause(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(await_state(task_id, State::Paused, TIMEOUT));
+
+    let (event, rx) = TaskManagerEvent::resume(uid, task_id);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    assert!(client
+        .await_notify(task_id, SubscribeType::Complete, TIMEOUT)
+        .is_some());
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+
+    assert_eq!(std::fs::read(path).unwrap(), expected_file(size));
+    let sequence: Vec<u32> = client
+        .frames_with_states()
+        .into_iter()
+        .filter_map(|(frame, _)| match frame {
+            Frame::Notify {
+                subscribe_type,
+                task_id: id,
+                ..
+            } if id == task_id && subscribe_type != SubscribeType::Progress as u32 => {
+                Some(subscribe_type)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        sequence,
+        vec![
+            SubscribeType::Pause as u32,
+            SubscribeType::Resume as u32,
+            SubscribeType::Complete as u32,
+        ]
+    );
+}
+
+// @tc.name: it_loopback_fail_at
+// @tc.desc: Test a loopback task fails once its data reaches the set offset
+// @tc.precon: NA
+// @tc.step: 1. Start a loopback task failing at an offset within its size
+//           2. Wait for the fail notification
+// @tc.expect: Task reaches Failed and the file holds the pattern up to the
+//             offset only
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_loopback_fail_at() {
+    let _serial = serial();
+    let client = FakeClient::open();
+    let path = "test_files/it_loopback_fail_at.txt";
+    let fail_at = 10_000;
+
+    let config = loopback_config(path, 5039, 64 * 1024, Some(fail_at));
+    let task_id = construct_and_start(config, &client);
+    assert!(client
+        .await_notify(task_id, SubscribeType::Fail, TIMEOUT)
+        .is_some());
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    assert_eq!(std::fs::read(path).unwrap(), expected_file(fail_at));
+}
+
+// @tc.name: it_loopback_unprivileged
+// @tc.desc: Test a loopback task of a non-system caller is rejected
+// @tc.precon: NA
+// @tc.step: 1. Construct a loopback task not marked as privileged
+// @tc.expect: Construction fails with a permission error
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_loopback_unprivileged() {
+    let _serial = serial();
+    let path = "test_files/it_loopback_unprivileged.txt";
+    let mut config = loopback_config(path, 5040, 1024, None);
+    config.privileged = false;
+
+    let (event, rx) = TaskManagerEvent::construct(config);
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), Err(ErrorCode::Permission));
+}
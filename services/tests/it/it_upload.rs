@@ -0,0 +1,310 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use super::harness::{
+    await_state, construct_and_start, network_online, serial, test_body, FakeClient, Frame,
+    ServerMode, TestServer, TASK_MANAGER,
+};
+use crate::config::{Action, ConfigBuilder, Mode, TaskConfig};
+use crate::error::ErrorCode;
+use crate::info::State;
+use crate::manage::events::TaskManagerEvent;
+use crate::task::reason::Reason;
+
+const TIMEOUT: Duration = Duration::from_secs(20);
+const ETAG: &str = "v1";
+
+fn upload_config(url: &str, path: &str, uid: u64, if_match: &str) -> TaskConfig {
+    let mut file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .unwrap();
+    file.write_all(&test_body(16 * 1024)).unwrap();
+    ConfigBuilder::new()
+        .action(Action::Upload)
+        .method("PUT")
+        .mode(Mode::BackGround)
+        .version(2)
+        .file_spec(File::open(path).unwrap())
+        .url(url)
+        .uid(uid)
+        .if_match(if_match)
+        .build()
+}
+
+// @tc.name: it_upload_if_match_stale
+// @tc.desc: Test an upload whose remote object changed fails without retrying
+// @tc.precon: NA
+// @tc.step: 1. Start a local server expecting another ETag
+//           2. Start an upload with a stale `If-Match`
+//           3. Wait for the task to fail
+// @tc.expect: Task fails with `PreconditionFailed`
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_upload_if_match_stale() {
+    let _serial = serial();
+    network_online();
+    let server = TestServer::start(vec![], ServerMode::IfMatch(ETAG));
+    let client = FakeClient::open();
+    let path = "test_files/it_upload_if_match_stale.txt";
+
+    let task_id = construct_and_start(upload_config(&server.url, path, 5010, "v0"), &client);
+
+    assert!(await_state(task_id, State::Failed, TIMEOUT));
+    assert!(client
+        .await_frame(TIMEOUT, |frame| {
+            *frame
+                == Frame::Faults {
+                    task_id,
+                    reason: Reason::PreconditionFailed.repr as u32,
+                }
+        })
+        .is_some());
+}
+
+// @tc.name: it_upload_if_match_current
+// @tc.desc: Test an upload whose remote object is unchanged replaces it
+// @tc.precon: NA
+// @tc.step: 1. Start a local server expecting an ETag
+//           2. Start an upload with that ETag as `If-Match`
+//           3. Wait for the task to complete
+// @tc.expect: Task reaches Completed
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_upload_if_match_current() {
+    let _serial = serial();
+    network_online();
+    let server = TestServer::start(vec![], ServerMode::IfMatch(ETAG));
+    let client = FakeClient::open();
+    let path = "test_files/it_upload_if_match_current.txt";
+
+    let task_id = construct_and_start(upload_config(&server.url, path, 5011, ETAG), &client);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+}
+
+const FILE_SIZE: usize = 32 * 1024 * 1024;
+
+/// Starts a PUT upload of two files to a server reading them slowly, and
+/// stops it softly or not once the first file is in flight.
+fn stop_two_file_upload(name: &str, uid: u64, graceful: bool) -> (TestServer, u32) {
+    let server = TestServer::start(
+        vec![],
+        ServerMode::SlowSink {
+            chunk: 1024 * 1024,
+            delay: Duration::from_millis(100),
+        },
+    );
+    let client = FakeClient::open();
+    let mut builder = ConfigBuilder::new();
+    builder
+        .action(Action::Upload)
+        .method("PUT")
+        .mode(Mode::BackGround)
+        .version(2)
+        .url(&server.url)
+        .uid(uid);
+    for i in 0..2 {
+        let path = format!("test_files/{}_{}.txt", name, i);
+        std::fs::write(&path, test_body(FILE_SIZE)).unwrap();
+        builder.file_spec(File::open(&path).unwrap());
+    }
+
+    let task_id = construct_and_start(builder.build(), &client);
+    assert!(await_state(task_id, State::Running, TIMEOUT));
+    std::thread::sleep(Duration::from_millis(300));
+    let (event, rx) = match graceful {
+        true => TaskManagerEvent::soft_stop(uid, task_id),
+        false => TaskManagerEvent::stop(uid, task_id),
+    };
+    TASK_MANAGER.send_event(event);
+    assert_eq!(rx.get().unwrap(), ErrorCode::ErrOk);
+    (server, task_id)
+}
+
+/// Waits until the server ended one upload, then a little longer for any
+/// upload which should not have started.
+fn await_uploads(server: &TestServer) -> Vec<Option<u64>> {
+    let deadline = Instant::now() + TIMEOUT;
+    while server.ranges().is_empty() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    std::thread::sleep(Duration::from_secs(1));
+    server.ranges()
+}
+
+// @tc.name: it_upload_soft_stop
+// @tc.desc: Test a soft stopped upload finishes the file in flight only
+// @tc.precon: NA
+// @tc.step: 1. Start a server reading uploads slowly
+//           2. Start a PUT upload of two files
+//           3. Softly stop the task while the first file is in flight
+// @tc.expect: Task is Stopped and the server received exactly the whole
+//             first file and nothing of the second one
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_upload_soft_stop() {
+    let _serial = serial();
+    network_online();
+    let (server, task_id) = stop_two_file_upload("it_upload_soft_stop", 5021, true);
+
+    assert!(await_state(task_id, State::Stopped, TIMEOUT));
+    assert_eq!(await_uploads(&server), vec![Some(FILE_SIZE as u64)]);
+}
+
+// @tc.name: it_upload_hard_stop
+// @tc.desc: Test a stopped upload is cut off within the file in flight
+// @tc.precon: NA
+// @tc.step: 1. Start a server reading uploads slowly
+//           2. Start a PUT upload of two files
+//           3. Stop the task while the first file is in flight
+// @tc.expect: Task is Stopped and the server received only part of the
+//             first file
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_upload_hard_stop() {
+    let _serial = serial();
+    network_online();
+    let (server, task_id) = stop_two_file_upload("it_upload_hard_stop", 5022, false);
+
+    assert!(await_state(task_id, State::Stopped, TIMEOUT));
+    let uploads = await_uploads(&server);
+    assert_eq!(uploads.len(), 1);
+    assert!(uploads[0].unwrap() < FILE_SIZE as u64);
+}
+
+// @tc.name: it_upload_concatenated
+// @tc.desc: Test a concatenated upload sends its files as one body
+// @tc.precon: NA
+// @tc.step: 1. Start a local server recording upload bodies
+//           2. Start a concatenated PUT upload of two files
+//           3. Wait for the task to complete
+// @tc.expect: Task reaches Completed and the server received one body
+//             holding the first file followed by the second one
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_upload_concatenated() {
+    let _serial = serial();
+    network_online();
+    let server = TestServer::start(vec![], ServerMode::Capture);
+    let client = FakeClient::open();
+    let first = test_body(3 * 1024);
+    let second: Vec<u8> = test_body(5 * 1024).iter().map(|b| !b).collect();
+    let mut builder = ConfigBuilder::new();
+    builder
+        .action(Action::Upload)
+        .method("PUT")
+        .mode(Mode::BackGround)
+        .version(2)
+        .url(&server.url)
+        .uid(5041)
+        .concatenated(true);
+    for (i, content) in [&first, &second].iter().enumerate() {
+        let path = format!("test_files/it_upload_concatenated_{}.txt", i);
+        std::fs::write(&path, content).unwrap();
+        builder.file_spec(File::open(&path).unwrap());
+    }
+
+    let task_id = construct_and_start(builder.build(), &client);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(server.uploads(), vec![[first, second].concat()]);
+}
+
+// @tc.name: it_upload_patch_method
+// @tc.desc: Test an upload is sent with a PATCH method and its body
+// @tc.precon: NA
+// @tc.step: 1. Start a local server recording methods and upload bodies
+//           2. Start an upload of a file with the method `patch`
+//           3. Wait for the task to complete
+// @tc.expect: Task reaches Completed and the server received one PATCH
+//             request with the file as its body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_upload_patch_method() {
+    let _serial = serial();
+    network_online();
+    let server = TestServer::start(vec![], ServerMode::Capture);
+    let client = FakeClient::open();
+    let path = "test_files/it_upload_patch_method.txt";
+    let content = test_body(4 * 1024);
+    std::fs::write(path, &content).unwrap();
+    let config = ConfigBuilder::new()
+        .action(Action::Upload)
+        .method("patch")
+        .mode(Mode::BackGround)
+        .version(2)
+        .file_spec(File::open(path).unwrap())
+        .url(&server.url)
+        .uid(5046)
+        .build();
+
+    let task_id = construct_and_start(config, &client);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    assert_eq!(server.methods(), vec!["PATCH"]);
+    assert_eq!(server.uploads(), vec![content]);
+}
+
+// @tc.name: it_upload_file_priority
+// @tc.desc: Test files of an upload are sent by descending priority
+// @tc.precon: NA
+// @tc.step: 1. Start a local server capturing uploads
+//           2. Start a PUT upload of files listed out of priority order
+//           3. Wait for the task to complete
+// @tc.expect: Task reaches Completed and the server received the files by
+//             descending priority, files of equal priority in list order
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn it_upload_file_priority() {
+    let _serial = serial();
+    network_online();
+    let server = TestServer::start(vec![], ServerMode::Capture);
+    let client = FakeClient::open();
+    let mut builder = ConfigBuilder::new();
+    builder
+        .action(Action::Upload)
+        .method("PUT")
+        .mode(Mode::BackGround)
+        .version(2)
+        .url(&server.url)
+        .uid(5048);
+    let mut contents = vec![];
+    for (i, priority) in [0, 5, 5, 9].into_iter().enumerate() {
+        let path = format!("test_files/it_upload_file_priority_{}.txt", i);
+        let content = test_body((i + 1) * 1024);
+        std::fs::write(&path, &content).unwrap();
+        builder.prioritized_file_spec(File::open(&path).unwrap(), priority);
+        contents.push(content);
+    }
+
+    let task_id = construct_and_start(builder.build(), &client);
+
+    assert!(await_state(task_id, State::Completed, TIMEOUT));
+    let expected = [3, 1, 2, 0].map(|i| contents[i].clone());
+    assert_eq!(server.uploads(), expected);
+}
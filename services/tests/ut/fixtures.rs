@@ -0,0 +1,59 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::manage::database::RequestDb;
+use crate::task::config::{Action, Mode};
+use crate::task::info::State;
+use crate::task::reason::Reason;
+
+/// Row of `request_task` holding the columns tests inserting raw tasks set.
+#[derive(Clone, Copy)]
+pub(crate) struct TaskRow {
+    pub(crate) task_id: u32,
+    pub(crate) uid: u64,
+    pub(crate) action: Action,
+    pub(crate) mode: Mode,
+    pub(crate) state: State,
+    pub(crate) reason: Reason,
+    pub(crate) ctime: u64,
+}
+
+impl TaskRow {
+    /// Returns an initialized foreground download of uid 0.
+    pub(crate) fn new(task_id: u32) -> Self {
+        Self {
+            task_id,
+            uid: 0,
+            action: Action::Download,
+            mode: Mode::FrontEnd,
+            state: State::Initialized,
+            reason: Reason::Default,
+            ctime: 1,
+        }
+    }
+
+    /// Inserts the row, replacing any task with the same ID.
+    pub(crate) fn insert(&self, db: &RequestDb) {
+        db.execute(&format!(
+            "INSERT OR REPLACE INTO request_task (task_id, uid, action, mode, state, reason, ctime, priority) VALUES ({}, {}, {}, {}, {}, {}, {}, 0)",
+            self.task_id,
+            self.uid,
+            self.action.repr,
+            self.mode.repr,
+            self.state.repr,
+            self.reason.repr,
+            self.ctime,
+        ))
+        .unwrap();
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+
+use ylong_runtime::sync::mpsc::unbounded_channel;
+
+use super::*;
+use crate::config::{ConfigBuilder, ResumeStrategy};
+use crate::fixtures::TaskRow;
+use crate::manage::network::{NetworkInfo, NetworkInner, NetworkType};
+use crate::manage::query::TaskFilter;
+use crate::manage::task_manager::{TaskManagerRx, TaskManagerTx};
+use crate::service::active_counter::ActiveCounter;
+use crate::service::client::ClientManagerEntry;
+use crate::service::run_count::RunCountManagerEntry;
+use crate::tests::{lock_database, test_init};
+use crate::utils::task_id_generator::{TaskIdGenerator, MAX_ID_ATTEMPTS};
+
+fn task_manager() -> TaskManager {
+    let (tx, rx) = unbounded_channel();
+    let task_manager_tx = TaskManagerTx::new(tx);
+    let rx = TaskManagerRx::new(rx);
+    let inner = NetworkInner::new();
+    inner.notify_online(NetworkInfo {
+        network_type: NetworkType::Wifi,
+        is_metered: false,
+        is_roaming: false,
+    });
+    let (tx, _rx) = unbounded_channel();
+    let run_count = RunCountManagerEntry::new(tx);
+    let (tx, _rx) = unbounded_channel();
+    let client = ClientManagerEntry::new(tx);
+    TaskManager::new(task_manager_tx, rx, run_count, client, ActiveCounter::new())
+}
+
+fn config(name: &str, policy: DuplicatePolicy) -> TaskConfig {
+    let file = File::create(format!("test_files/{}.txt", name)).unwrap();
+    ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .file_spec(file)
+        .url(&format!(
+            "https://www.example.com/{}/{}.txt",
+            name,
+            TaskIdGenerator::generate()
+        ))
+        .duplicate_policy(policy)
+        .build()
+}
+
+// @tc.name: ut_construct_duplicate_warn
+// @tc.desc: Test the warn policy returns the active duplicate of a download
+// @tc.precon: NA
+// @tc.step: 1. Create a download
+//           2. Create it again with the warn policy
+//           3. Create it again to another destination with the warn policy
+// @tc.expect: The same download returns the active task flagged as a
+// duplicate, the download to another destination creates a new task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_duplicate_warn() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let mut config = config("ut_construct_duplicate_warn", DuplicatePolicy::Allow);
+
+    let task_id = manager.create(config.clone()).unwrap();
+    config.duplicate_policy = DuplicatePolicy::Warn;
+    assert_eq!(
+        manager.create_task(config.clone()),
+        Ok(CreatedTask {
+            task_id,
+            duplicate: true,
+        })
+    );
+
+    config.file_specs[0].path = "other.txt".to_string();
+    let other = manager.create_task(config).unwrap();
+    assert_ne!(other.task_id, task_id);
+    assert!(!other.duplicate);
+}
+
+// @tc.name: ut_construct_duplicate_reject
+// @tc.desc: Test the reject policy fails duplicates of active downloads only
+// @tc.precon: NA
+// @tc.step: 1. Create a download
+//           2. Create it again with the reject and the allow policy
+//           3. Remove the first task and create the download again with the
+//              reject policy
+// @tc.expect: The rejected creation fails with `TaskEnqueueErr`, allowed
+// duplicates and downloads without an active duplicate are created
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_duplicate_reject() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let mut config = config("ut_construct_duplicate_reject", DuplicatePolicy::Reject);
+    let uid = config.common_data.uid;
+
+    let task_id = manager.create(config.clone()).unwrap();
+    assert_eq!(
+        manager.create(config.clone()),
+        Err(ErrorCode::TaskEnqueueErr)
+    );
+
+    config.duplicate_policy = DuplicatePolicy::Allow;
+    let allowed = manager.create(config.clone()).unwrap();
+    assert_ne!(allowed, task_id);
+
+    assert_eq!(manager.remove(uid, task_id), ErrorCode::ErrOk);
+    assert_eq!(manager.remove(uid, allowed), ErrorCode::ErrOk);
+    config.duplicate_policy = DuplicatePolicy::Reject;
+    let created = manager.create(config).unwrap();
+    assert_ne!(created, task_id);
+    assert_ne!(created, allowed);
+}
+
+// @tc.name: ut_construct_duplicate_index
+// @tc.desc: Test active duplicates are looked up by the uid and url index
+// @tc.precon: NA
+// @tc.step: 1. Explain the query plan of a duplicate lookup
+// @tc.expect: The plan uses `uid_url_index`
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_duplicate_index() {
+    let db = RequestDb::get_instance();
+    let mut stmt = db
+        .inner
+        .prepare(
+            "EXPLAIN QUERY PLAN SELECT task_id FROM request_task WHERE uid = 1 AND url = 'a' \
+             AND action = 1 AND state IN (0, 1)",
+        )
+        .unwrap();
+    let plan: Vec<String> = stmt
+        .query_map([], |row| row.get(3))
+        .unwrap()
+        .map(|detail| detail.unwrap())
+        .collect();
+    assert!(
+        plan.iter().any(|detail| detail.contains("uid_url_index")),
+        "{:?}",
+        plan
+    );
+}
+
+// @tc.name: ut_construct_client_tag
+// @tc.desc: Test the client tag of a task is kept from creation to search
+// @tc.precon: NA
+// @tc.step: 1. Create a download with a client tag
+//           2. Query the task and build its notification
+//           3. Search tasks by the tag and by another tag
+// @tc.expect: The tag is returned by the query and the notification, the
+// search by the tag finds the task only
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_client_tag() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let mut config = config("ut_construct_client_tag", DuplicatePolicy::Allow);
+    let tag = format!("order-{}", TaskIdGenerator::generate());
+    config.client_tag = tag.clone();
+    let uid = config.common_data.uid;
+
+    let task_id = manager.create(config).unwrap();
+    let db = RequestDb::get_instance();
+    let info = manager.show(uid, task_id).unwrap();
+    assert_eq!(info.client_tag, tag);
+    assert_eq!(info.build_notify_data().client_tag, tag);
+    assert_eq!(db.get_task_config(task_id).unwrap().client_tag, tag);
+
+    let filter = |client_tag: &str| TaskFilter {
+        before: i64::MAX,
+        after: 0,
+        state: State::Any.repr,
+        action: Action::Any.repr,
+        mode: Mode::Any.repr,
+        client_tag: client_tag.to_string(),
+    };
+    assert_eq!(db.search_task(filter(&tag), uid), vec![task_id]);
+    assert!(db.search_task(filter("other"), uid).is_empty());
+}
+
+// @tc.name: ut_construct_resume_strategy
+// @tc.desc: Test the resume strategy of a task is kept in the database
+// @tc.precon: NA
+// @tc.step: 1. Create a download resuming by a query parameter
+//           2. Read the config of the task back from the database
+// @tc.expect: The config read back resumes by the same query parameter
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_resume_strategy() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let mut config = config("ut_construct_resume_strategy", DuplicatePolicy::Allow);
+    let strategy = ResumeStrategy::QueryParam {
+        name: "offset".to_string(),
+    };
+    config.resume_strategy = strategy.clone();
+
+    let task_id = manager.create(config).unwrap();
+    let restored = RequestDb::get_instance().get_task_config(task_id).unwrap();
+    assert_eq!(restored.resume_strategy, strategy);
+}
+
+// @tc.name: ut_construct_privileged
+// @tc.desc: Test whether the caller of a task was privileged is kept
+// @tc.precon: NA
+// @tc.step: 1. Create a download of a privileged caller and another one
+//           2. Read the configs of the tasks back from the database
+// @tc.expect: Only the config of the privileged caller is privileged
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_privileged() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let db = RequestDb::get_instance();
+    for privileged in [true, false] {
+        let mut config = config("ut_construct_privileged", DuplicatePolicy::Allow);
+        config.privileged = privileged;
+        let task_id = manager.create(config).unwrap();
+        assert_eq!(db.get_task_config(task_id).unwrap().privileged, privileged);
+    }
+}
+
+// @tc.name: ut_construct_id_collision
+// @tc.desc: Test task ids used by stored tasks are skipped transparently
+// @tc.precon: NA
+// @tc.step: 1. Store tasks with the next two candidate ids
+//           2. Create a download trying these candidates first
+//           3. Create a download whose candidates are all used
+// @tc.expect: The first download gets the third candidate, the second one
+// fails with `ErrorCode::Other` without being stored
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_id_collision() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let db = RequestDb::get_instance();
+    let candidates: Vec<u32> = (0..3).map(|_| TaskIdGenerator::generate()).collect();
+    for task_id in &candidates[..2] {
+        TaskRow {
+            state: State::Completed,
+            ..TaskRow::new(*task_id)
+        }
+        .insert(db);
+    }
+
+    let config = config("ut_construct_id_collision", DuplicatePolicy::Allow);
+    assert_eq!(
+        manager
+            .create_with_ids(config.clone(), candidates.clone())
+            .map(|created| created.task_id),
+        Ok(candidates[2])
+    );
+    assert!(db.contains_task(candidates[2]));
+
+    let taken = vec![candidates[0]; MAX_ID_ATTEMPTS + 1];
+    assert_eq!(
+        manager.create_with_ids(config, taken),
+        Err(ErrorCode::Other)
+    );
+}
+
+// @tc.name: ut_construct_too_many_tasks
+// @tc.desc: Test tasks beyond the live task limit of an application fail
+// @tc.precon: NA
+// @tc.step: 1. Limit applications to two live tasks
+//           2. Create three downloads of one application
+//           3. Create a download of another application
+// @tc.expect: The third download fails with `ErrorCode::TooManyTasks`
+// before allocating an id, the other application is not limited
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_too_many_tasks() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    manager.max_live_tasks = 2;
+    let mut config = config("ut_construct_too_many_tasks", DuplicatePolicy::Allow);
+    config.common_data.uid = TaskIdGenerator::generate() as u64;
+
+    manager.create(config.clone()).unwrap();
+    manager.create(config.clone()).unwrap();
+    let mut tried = false;
+    let candidates = std::iter::from_fn(|| {
+        tried = true;
+        Some(TaskIdGenerator::generate())
+    });
+    assert_eq!(
+        manager.create_with_ids(config.clone(), candidates),
+        Err(ErrorCode::TooManyTasks)
+    );
+    assert!(!tried);
+
+    config.common_data.uid += 1;
+    assert!(manager.create(config).is_ok());
+}
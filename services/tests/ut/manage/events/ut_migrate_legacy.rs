@@ -0,0 +1,253 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::os::fd::IntoRawFd;
+
+use ylong_runtime::sync::mpsc::unbounded_channel;
+
+use super::*;
+use crate::config::{ConfigBuilder, TaskConfig};
+use crate::error::ErrorCode;
+use crate::info::TaskInfo;
+use crate::manage::network::{NetworkInfo, NetworkInner, NetworkType};
+use crate::manage::task_manager::{TaskManagerRx, TaskManagerTx};
+use crate::service::active_counter::ActiveCounter;
+use crate::service::client::ClientManagerEntry;
+use crate::service::run_count::RunCountManagerEntry;
+use crate::tests::{lock_database, test_init};
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+fn task_manager() -> TaskManager {
+    let (tx, rx) = unbounded_channel();
+    let task_manager_tx = TaskManagerTx::new(tx);
+    let rx = TaskManagerRx::new(rx);
+    let inner = NetworkInner::new();
+    inner.notify_online(NetworkInfo {
+        network_type: NetworkType::Wifi,
+        is_metered: false,
+        is_roaming: false,
+    });
+    let (tx, _rx) = unbounded_channel();
+    let run_count = RunCountManagerEntry::new(tx);
+    let (tx, _rx) = unbounded_channel();
+    let client = ClientManagerEntry::new(tx);
+    TaskManager::new(task_manager_tx, rx, run_count, client, ActiveCounter::new())
+}
+
+fn legacy(name: &str, uid: u64, background: bool) -> TaskConfig {
+    let file = File::create(format!("test_files/{}.txt", name)).unwrap();
+    let mut config = ConfigBuilder::new()
+        .action(Action::Download)
+        .version(Version::API9 as u8)
+        .file_spec(file)
+        .url(&format!(
+            "https://www.example.com/{}/{}.txt",
+            name,
+            TaskIdGenerator::generate()
+        ))
+        .uid(uid)
+        .build();
+    config.common_data.background = background;
+    config
+}
+
+fn update(task_id: u32, set: &str) {
+    RequestDb::get_instance()
+        .execute(&format!(
+            "UPDATE request_task SET {} WHERE task_id = {}",
+            set, task_id
+        ))
+        .unwrap();
+}
+
+fn info(task_id: u32) -> TaskInfo {
+    RequestDb::get_instance().get_task_info(task_id).unwrap()
+}
+
+/// Legacy tasks of one application, one per way a task can be migrated or
+/// skipped, and an API10 task.
+struct Seeded {
+    uid: u64,
+    initialized: u32,
+    paused: u32,
+    upload: u32,
+    user_file: u32,
+    removed: u32,
+    api10: u32,
+}
+
+fn seed(manager: &mut TaskManager, name: &str) -> Seeded {
+    let uid = TaskIdGenerator::generate() as u64;
+    let initialized = manager
+        .create(legacy(&format!("{}_initialized", name), uid, false))
+        .unwrap();
+
+    let paused = manager
+        .create(legacy(&format!("{}_paused", name), uid, true))
+        .unwrap();
+    update(
+        paused,
+        &format!("state = {}, total_processed = 512", State::Paused.repr),
+    );
+
+    let upload = manager
+        .create(legacy(&format!("{}_upload", name), uid, false))
+        .unwrap();
+    update(upload, &format!("action = {}", Action::Upload.repr));
+
+    let mut config = legacy(&format!("{}_user_file", name), uid, false);
+    let file = File::create(format!("test_files/{}_user_file_fd.txt", name)).unwrap();
+    config.file_specs[0].is_user_file = true;
+    config.file_specs[0].fd = Some(file.into_raw_fd());
+    let user_file = manager.create(config).unwrap();
+
+    let removed = manager
+        .create(legacy(&format!("{}_removed", name), uid, false))
+        .unwrap();
+    update(removed, &format!("state = {}", State::Removed.repr));
+
+    let mut config = legacy(&format!("{}_api10", name), uid, false);
+    config.version = Version::API10;
+    let api10 = manager.create(config).unwrap();
+
+    Seeded {
+        uid,
+        initialized,
+        paused,
+        upload,
+        user_file,
+        removed,
+        api10,
+    }
+}
+
+fn expected(seeded: &Seeded) -> MigrationReport {
+    let mut migrated = vec![seeded.initialized, seeded.paused];
+    migrated.sort();
+    let mut skipped = vec![
+        (seeded.upload, MigrationSkip::Upload),
+        (seeded.user_file, MigrationSkip::UserFile),
+        (seeded.removed, MigrationSkip::Removed),
+    ];
+    skipped.sort_by_key(|(task_id, _)| *task_id);
+    MigrationReport { migrated, skipped }
+}
+
+// @tc.name: ut_migrate_legacy_report
+// @tc.desc: Test legacy downloads are rewritten as API10 tasks and every
+// legacy task is reported
+// @tc.precon: NA
+// @tc.step: 1. Create legacy tasks in several states, a legacy upload, a
+//              legacy download to a user file and an API10 task
+//           2. Migrate the legacy tasks of the application
+// @tc.expect: The initialized and paused downloads are migrated with their
+// mode mapped, gauge off, default priority, progress and path kept, the
+// other legacy tasks are skipped with their reason and left untouched, the
+// API10 task is not reported
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_migrate_legacy_report() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let seeded = seed(&mut manager, "ut_migrate_legacy_report");
+    let path = info(seeded.paused).file_specs[0].path.clone();
+
+    let report = manager.migrate_legacy_tasks(seeded.uid);
+    assert_eq!(report, expected(&seeded));
+
+    for (task_id, mode) in [
+        (seeded.initialized, Mode::FrontEnd),
+        (seeded.paused, Mode::BackGround),
+    ] {
+        let info = info(task_id);
+        assert_eq!(info.common_data.version, Version::API10 as u8);
+        assert_eq!(info.common_data.mode, mode.repr);
+        assert!(!info.common_data.gauge);
+        assert_eq!(info.common_data.priority, 0);
+    }
+    assert_eq!(
+        info(seeded.paused).progress.common_data.state,
+        State::Paused.repr
+    );
+    assert_eq!(
+        RequestDb::get_instance().query_task_total_processed(seeded.paused),
+        Some(512)
+    );
+    assert_eq!(info(seeded.paused).file_specs[0].path, path);
+
+    for task_id in [seeded.upload, seeded.user_file, seeded.removed] {
+        assert_eq!(info(task_id).common_data.version, Version::API9 as u8);
+    }
+    assert_eq!(info(seeded.api10).common_data.version, Version::API10 as u8);
+}
+
+// @tc.name: ut_migrate_legacy_control
+// @tc.desc: Test migrated tasks are controlled like API10 tasks
+// @tc.precon: NA
+// @tc.step: 1. Create legacy tasks and migrate them
+//           2. Start and pause the initialized task, resume the paused task
+// @tc.expect: Every operation succeeds and the states follow
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_migrate_legacy_control() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let seeded = seed(&mut manager, "ut_migrate_legacy_control");
+    manager.migrate_legacy_tasks(seeded.uid);
+
+    assert_eq!(
+        manager.start(seeded.uid, seeded.initialized),
+        ErrorCode::ErrOk
+    );
+    assert_eq!(
+        manager.pause(seeded.uid, seeded.initialized),
+        ErrorCode::ErrOk
+    );
+    assert_eq!(
+        info(seeded.initialized).progress.common_data.state,
+        State::Paused.repr
+    );
+
+    assert_eq!(manager.resume(seeded.uid, seeded.paused), ErrorCode::ErrOk);
+    assert_ne!(
+        info(seeded.paused).progress.common_data.state,
+        State::Paused.repr
+    );
+}
+
+// @tc.name: ut_migrate_legacy_idempotent
+// @tc.desc: Test migrating again changes nothing
+// @tc.precon: NA
+// @tc.step: 1. Create legacy tasks and migrate them twice
+// @tc.expect: The second report migrates nothing and skips the same tasks,
+// the migrated tasks keep their mode
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_migrate_legacy_idempotent() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let seeded = seed(&mut manager, "ut_migrate_legacy_idempotent");
+
+    assert_eq!(manager.migrate_legacy_tasks(seeded.uid), expected(&seeded));
+    let report = manager.migrate_legacy_tasks(seeded.uid);
+    assert!(report.migrated.is_empty());
+    assert_eq!(report.skipped, expected(&seeded).skipped);
+    assert_eq!(info(seeded.paused).common_data.mode, Mode::BackGround.repr);
+}
@@ -96,7 +96,7 @@ fn ut_task_manager_start() {
     .build();
     let (event, rx) = TaskManagerEvent::construct(config.clone());
     TASK_MANGER.send_event(event);
-    let task_id = rx.get().unwrap().unwrap();
+    let task_id = rx.get().unwrap().unwrap().task_id;
     let (event, rx) = TaskManagerEvent::start(uid, task_id);
     TASK_MANGER.send_event(event);
     let res = rx.get().unwrap();
@@ -133,7 +133,7 @@ fn ut_task_manager_pause_resume() {
     .build();
     let (event, rx) = TaskManagerEvent::construct(config.clone());
     TASK_MANGER.send_event(event);
-    let task_id = rx.get().unwrap().unwrap();
+    let task_id = rx.get().unwrap().unwrap().task_id;
     let (event, _rx) = TaskManagerEvent::start(uid, task_id);
     TASK_MANGER.send_event(event);
     let (event, _rx) = TaskManagerEvent::pause(uid, task_id);
@@ -172,7 +172,7 @@ fn ut_task_manager_stop_resume() {
     .build();
     let (event, rx) = TaskManagerEvent::construct(config.clone());
     TASK_MANGER.send_event(event);
-    let task_id = rx.get().unwrap().unwrap();
+    let task_id = rx.get().unwrap().unwrap().task_id;
     let (event, _rx) = TaskManagerEvent::start(uid, task_id);
     TASK_MANGER.send_event(event);
     let (event, _rx) = TaskManagerEvent::stop(uid, task_id);
@@ -0,0 +1,184 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+
+use ylong_runtime::sync::mpsc::unbounded_channel;
+
+use super::*;
+use crate::config::{ConfigBuilder, Mode, TaskConfig};
+use crate::manage::network::{NetworkInfo, NetworkInner, NetworkType};
+use crate::manage::query::TaskFilter;
+use crate::manage::task_manager::{TaskManagerRx, TaskManagerTx};
+use crate::service::active_counter::ActiveCounter;
+use crate::service::client::ClientManagerEntry;
+use crate::service::run_count::RunCountManagerEntry;
+use crate::tests::{lock_database, test_init};
+use crate::utils::get_current_timestamp;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+fn task_manager() -> TaskManager {
+    let (tx, rx) = unbounded_channel();
+    let task_manager_tx = TaskManagerTx::new(tx);
+    let rx = TaskManagerRx::new(rx);
+    let inner = NetworkInner::new();
+    inner.notify_online(NetworkInfo {
+        network_type: NetworkType::Wifi,
+        is_metered: false,
+        is_roaming: false,
+    });
+    let (tx, _rx) = unbounded_channel();
+    let run_count = RunCountManagerEntry::new(tx);
+    let (tx, _rx) = unbounded_channel();
+    let client = ClientManagerEntry::new(tx);
+    TaskManager::new(task_manager_tx, rx, run_count, client, ActiveCounter::new())
+}
+
+fn config(name: &str) -> TaskConfig {
+    let file = File::create(format!("test_files/{}.txt", name)).unwrap();
+    ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .file_spec(file)
+        .url(&format!(
+            "https://www.example.com/{}/{}.txt",
+            name,
+            TaskIdGenerator::generate()
+        ))
+        .build()
+}
+
+fn state(task_id: u32) -> Option<u8> {
+    RequestDb::get_instance().query_task_state(task_id)
+}
+
+// @tc.name: ut_remove_with_grace_undo
+// @tc.desc: Test a task removed with a grace period can be restored
+// @tc.precon: NA
+// @tc.step: 1. Create a download and remove it with a grace period
+//           2. Undo the removal twice
+// @tc.expect: The task is pending removal, the first undo pauses it and the
+// second fails with `TaskStateErr`
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_remove_with_grace_undo() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let config = config("ut_remove_with_grace_undo");
+    let uid = config.common_data.uid;
+
+    let task_id = manager.create(config).unwrap();
+    assert_eq!(manager.remove_with_grace(uid, task_id), ErrorCode::ErrOk);
+    assert_eq!(state(task_id), Some(State::PendingRemoval.repr));
+    assert_eq!(
+        manager.remove_with_grace(uid, task_id),
+        ErrorCode::TaskStateErr
+    );
+
+    assert_eq!(manager.undo_remove(uid, task_id), ErrorCode::ErrOk);
+    assert_eq!(state(task_id), Some(State::Paused.repr));
+    assert_eq!(manager.undo_remove(uid, task_id), ErrorCode::TaskStateErr);
+}
+
+// @tc.name: ut_remove_with_grace_sweep
+// @tc.desc: Test a task pending removal is removed once its grace period
+// expires
+// @tc.precon: NA
+// @tc.step: 1. Create a download and remove it with a grace period
+//           2. Sweep removals now and after the grace period
+// @tc.expect: The task is pending removal after the first sweep and removed
+// after the second, it can not be restored anymore
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_remove_with_grace_sweep() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let config = config("ut_remove_with_grace_sweep");
+    let uid = config.common_data.uid;
+
+    let task_id = manager.create(config).unwrap();
+    assert_eq!(manager.remove_with_grace(uid, task_id), ErrorCode::ErrOk);
+    let now = get_current_timestamp();
+
+    manager.sweep_removals(now);
+    assert_eq!(state(task_id), Some(State::PendingRemoval.repr));
+
+    let grace = manager.removal_grace.as_millis() as u64;
+    manager.sweep_removals(now + grace + 1);
+    assert_eq!(state(task_id), Some(State::Removed.repr));
+    assert_eq!(manager.undo_remove(uid, task_id), ErrorCode::TaskStateErr);
+}
+
+// @tc.name: ut_remove_with_grace_purge
+// @tc.desc: Test a task pending removal can be removed before its grace
+// period expires by its owner only
+// @tc.precon: NA
+// @tc.step: 1. Create a download and remove it with a grace period
+//           2. Purge it with another uid and with its own uid
+// @tc.expect: The purge with another uid fails with `TaskNotFound`, the
+// purge with its own uid removes the task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_remove_with_grace_purge() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let config = config("ut_remove_with_grace_purge");
+    let uid = config.common_data.uid;
+
+    let task_id = manager.create(config).unwrap();
+    assert_eq!(manager.remove_with_grace(uid, task_id), ErrorCode::ErrOk);
+    assert_eq!(manager.purge(uid + 1, task_id), ErrorCode::TaskNotFound);
+    assert_eq!(manager.purge(uid, task_id), ErrorCode::ErrOk);
+    assert_eq!(state(task_id), Some(State::Removed.repr));
+    assert_eq!(manager.purge(uid, task_id), ErrorCode::TaskStateErr);
+}
+
+// @tc.name: ut_remove_with_grace_search
+// @tc.desc: Test tasks pending removal are hidden from searches of any state
+// @tc.precon: NA
+// @tc.step: 1. Create a download and remove it with a grace period
+//           2. Search tasks of any state and tasks pending removal
+// @tc.expect: Only the search of tasks pending removal finds the task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_remove_with_grace_search() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let config = config("ut_remove_with_grace_search");
+    let uid = config.common_data.uid;
+
+    let task_id = manager.create(config).unwrap();
+    assert_eq!(manager.remove_with_grace(uid, task_id), ErrorCode::ErrOk);
+
+    let filter = |state: State| TaskFilter {
+        before: i64::MAX,
+        after: 0,
+        state: state.repr,
+        action: Action::Any.repr,
+        mode: Mode::Any.repr,
+        client_tag: String::new(),
+    };
+    let db = RequestDb::get_instance();
+    assert!(!db.search_task(filter(State::Any), uid).contains(&task_id));
+    assert!(db
+        .search_task(filter(State::PendingRemoval), uid)
+        .contains(&task_id));
+}
@@ -0,0 +1,144 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{Read, Write};
+
+use super::*;
+use crate::fixtures::TaskRow;
+use crate::utils::form_item::FileSpec;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+const DESTINATION: &str = "/data/storage/el2/base/files/moved.txt";
+
+fn insert(db: &RequestDb, task_id: u32, state: State) {
+    TaskRow {
+        action: Action::Upload,
+        state,
+        ..TaskRow::new(task_id)
+    }
+    .insert(db);
+}
+
+// @tc.name: ut_set_destination_move
+// @tc.desc: Test the file of a download is moved without replacing files
+// @tc.precon: NA
+// @tc.step: 1. Move a partially written file to a new path
+//           2. Move another file onto the moved one
+//           3. Move a missing file
+// @tc.expect: The content is found at the new path only, moving onto an
+// existing file fails with `AlreadyExists` and leaves both files, moving a
+// missing file fails
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_set_destination_move() {
+    let task_id = TaskIdGenerator::generate();
+    let _ = std::fs::create_dir("test_files/");
+    let from = format!("test_files/ut_set_destination_from_{}.txt", task_id);
+    let to = format!("test_files/ut_set_destination_to_{}.txt", task_id);
+    std::fs::File::create(&from)
+        .unwrap()
+        .write_all(b"partial")
+        .unwrap();
+
+    move_download_file(Path::new(&from), Path::new(&to)).unwrap();
+    assert!(!Path::new(&from).exists());
+    let mut content = String::new();
+    std::fs::File::open(&to)
+        .unwrap()
+        .read_to_string(&mut content)
+        .unwrap();
+    assert_eq!(content, "partial");
+
+    std::fs::File::create(&from).unwrap();
+    let err = move_download_file(Path::new(&from), Path::new(&to)).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    assert!(Path::new(&from).exists());
+    assert_eq!(std::fs::metadata(&to).unwrap().len(), 7);
+
+    let _ = std::fs::remove_file(&from);
+    assert!(move_download_file(Path::new(&from), Path::new(&format!("{}.new", to))).is_err());
+    let _ = std::fs::remove_file(&to);
+}
+
+// @tc.name: ut_set_destination_rejected
+// @tc.desc: Test the destination of a task is changed only while paused
+// @tc.precon: NA
+// @tc.step: 1. Change the destination of a running task
+//           2. Change the destination of a paused task to a path outside
+//              the sandbox
+//           3. Change the destination of an unknown task
+// @tc.expect: The running task fails with `TaskStateErr`, the invalid path
+// with `ParameterCheck`, the unknown task with `TaskNotFound`, and no
+// destination is stored
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_set_destination_rejected() {
+    let db = RequestDb::get_instance();
+    let task_id = TaskIdGenerator::generate();
+    insert(db, task_id, State::Running);
+    assert_eq!(
+        set_destination(task_id, DESTINATION),
+        Err(ErrorCode::TaskStateErr)
+    );
+
+    insert(db, task_id, State::Paused);
+    for path in [
+        "/data/local/tmp/moved.txt",
+        "/data/storage/el2/base/../moved.txt",
+    ] {
+        assert_eq!(
+            set_destination(task_id, path),
+            Err(ErrorCode::ParameterCheck)
+        );
+    }
+    assert_eq!(db.query_destination(task_id), None);
+
+    assert_eq!(
+        set_destination(TaskIdGenerator::generate(), DESTINATION),
+        Err(ErrorCode::TaskNotFound)
+    );
+}
+
+// @tc.name: ut_set_destination_stored
+// @tc.desc: Test a stored destination replaces the path of the task
+// @tc.precon: NA
+// @tc.step: 1. Store a destination for a paused task
+//           2. Query it and apply it to the file specs of the task
+// @tc.expect: The destination is returned and becomes the path of the first
+// file, tasks without a destination keep their path
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_set_destination_stored() {
+    let db = RequestDb::get_instance();
+    let task_id = TaskIdGenerator::generate();
+    insert(db, task_id, State::Paused);
+    let mut specs = vec![FileSpec {
+        name: String::new(),
+        path: String::new(),
+        file_name: String::new(),
+        mime_type: String::new(),
+        is_user_file: false,
+        fd: None,
+        priority: 0,
+    }];
+    db.apply_destination(task_id, &mut specs);
+    assert!(specs[0].path.is_empty());
+
+    db.update_destination(task_id, DESTINATION).unwrap();
+    assert_eq!(db.query_destination(task_id).as_deref(), Some(DESTINATION));
+    db.apply_destination(task_id, &mut specs);
+    assert_eq!(specs[0].path, DESTINATION);
+}
@@ -0,0 +1,254 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{Duration, Instant};
+
+use ylong_runtime::sync::mpsc::unbounded_channel;
+
+use super::*;
+use crate::manage::task_manager::TaskManagerTx;
+
+const UID: u64 = 9_100_000;
+
+fn qos_with_tasks(first_task_id: u32, count: u32) -> Qos {
+    let mut qos = Qos::new();
+    qos.change_rss(RssCapacity::LEVEL7);
+    for i in 0..count {
+        qos.start_task(
+            UID,
+            TaskQosInfo {
+                task_id: first_task_id + i,
+                action: Action::Download.repr,
+                mode: Mode::BackGround.repr,
+                state: 0,
+                priority: i,
+            },
+        );
+    }
+    qos
+}
+
+fn level_of(changes: &QosChanges, task_id: u32) -> Option<QosLevel> {
+    changes
+        .download
+        .as_ref()
+        .unwrap()
+        .iter()
+        .find(|direction| direction.task_id() == task_id)
+        .map(|direction| direction.direction())
+}
+
+fn high_count(changes: &QosChanges) -> usize {
+    changes
+        .download
+        .as_ref()
+        .unwrap()
+        .iter()
+        .filter(|direction| direction.direction() == QosLevel::High)
+        .count()
+}
+
+fn handler() -> state::Handler {
+    let (tx, _rx) = unbounded_channel();
+    state::Handler::new(TaskManagerTx::new(tx))
+}
+
+// @tc.name: ut_qos_user_attention_boost
+// @tc.desc: Test an attended task is moved to the top tier and decays back
+// @tc.precon: NA
+// @tc.step: 1. Fill the M1 tier with higher priority tasks
+//           2. Report attention on the lowest priority task and reschedule
+//           3. Reschedule again after the boost duration has passed
+// @tc.expect: The task is High while boosted, M1 stays at capacity and the
+//             task loses High once the boost expires
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_qos_user_attention_boost() {
+    let state = handler();
+    let mut qos = qos_with_tasks(91_000, 6);
+    let watched = 91_005;
+    let now = Instant::now();
+
+    let changes = qos.reschedule_at(&state, now);
+    assert_ne!(level_of(&changes, watched), Some(QosLevel::High));
+
+    assert!(qos.user_attention(watched, true, now));
+    let changes = qos.reschedule_at(&state, now);
+    assert_eq!(level_of(&changes, watched), Some(QosLevel::High));
+    assert_eq!(high_count(&changes), RssCapacity::LEVEL7.m1());
+
+    let later = now + ATTENTION_BOOST_DURATION + Duration::from_secs(1);
+    let changes = qos.reschedule_at(&state, later);
+    assert_ne!(level_of(&changes, watched), Some(QosLevel::High));
+}
+
+// @tc.name: ut_qos_user_attention_refresh_and_collapse
+// @tc.desc: Test attention refreshes the boost and collapsing drops it
+// @tc.precon: NA
+// @tc.step: 1. Report attention twice, the second shortly before expiry
+//           2. Reschedule past the first expiry
+//           3. Report the notification collapsed and reschedule
+// @tc.expect: The refreshed boost survives the first expiry and collapsing
+//             reverts the task immediately
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_qos_user_attention_refresh_and_collapse() {
+    let state = handler();
+    let mut qos = qos_with_tasks(92_000, 6);
+    let watched = 92_005;
+    let now = Instant::now();
+
+    assert!(qos.user_attention(watched, true, now));
+    let refresh = now + ATTENTION_BOOST_DURATION - Duration::from_secs(1);
+    assert!(!qos.user_attention(watched, true, refresh));
+
+    let changes = qos.reschedule_at(&state, now + ATTENTION_BOOST_DURATION);
+    assert_eq!(level_of(&changes, watched), Some(QosLevel::High));
+
+    assert!(qos.user_attention(watched, false, refresh));
+    assert!(!qos.user_attention(watched, false, refresh));
+    let changes = qos.reschedule_at(&state, refresh);
+    assert_ne!(level_of(&changes, watched), Some(QosLevel::High));
+}
+
+fn speeds(changes: &QosChanges) -> Vec<(QosLevel, u64)> {
+    changes
+        .download
+        .iter()
+        .chain(changes.upload.iter())
+        .flatten()
+        .map(|direction| (direction.direction(), direction.speed()))
+        .collect()
+}
+
+// @tc.name: ut_qos_global_cap
+// @tc.desc: Test a global speed cap is split among tasks within the cap
+// @tc.precon: NA
+// @tc.step: 1. Schedule several tasks across two tiers under a small cap
+//           2. Raise the cap beyond what the lower tier may use
+//           3. Remove the cap
+// @tc.expect: The summed limits never exceed the cap, higher tiers get more,
+//             lower tiers keep their level speed and removing the cap
+//             restores the level speeds
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_qos_global_cap() {
+    let state = handler();
+    let mut qos = qos_with_tasks(93_000, 6);
+    let now = Instant::now();
+
+    let cap = 1024 * 1024;
+    assert!(qos.set_global_cap(cap));
+    assert!(!qos.set_global_cap(cap));
+    let speeds = speeds(&qos.reschedule_at(&state, now));
+    assert!(!speeds.is_empty());
+    assert!(speeds.iter().map(|(_, speed)| speed).sum::<u64>() <= cap);
+    assert!(speeds.iter().all(|(_, speed)| *speed > 0));
+    let high_min = speeds
+        .iter()
+        .filter(|(level, _)| *level == QosLevel::High)
+        .map(|(_, speed)| *speed)
+        .min()
+        .unwrap();
+    let low_max = speeds
+        .iter()
+        .filter(|(level, _)| *level == QosLevel::Low)
+        .map(|(_, speed)| *speed)
+        .max()
+        .unwrap();
+    assert!(high_min > low_max);
+
+    let cap = 100 * 1024 * 1024;
+    assert!(qos.set_global_cap(cap));
+    let speeds = speeds(&qos.reschedule_at(&state, now));
+    assert!(speeds.iter().map(|(_, speed)| speed).sum::<u64>() <= cap);
+    for (level, speed) in speeds.iter() {
+        if *level == QosLevel::Low {
+            assert_eq!(*speed, QosLevel::Low as u64);
+        } else {
+            assert!(*speed > QosLevel::Low as u64);
+        }
+    }
+
+    assert!(qos.set_global_cap(0));
+    let speeds = speeds(&qos.reschedule_at(&state, now));
+    assert!(speeds.iter().all(|(level, speed)| *speed == *level as u64));
+}
+
+// @tc.name: ut_qos_global_cap_foreground
+// @tc.desc: Test foreground tasks get a larger share of the global speed cap
+// @tc.precon: NA
+// @tc.step: 1. Split a cap among two tasks of the same tier, one of them
+//              belonging to a foreground application
+// @tc.expect: The foreground task gets twice the share of the other task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_qos_global_cap_foreground() {
+    let foreground = UID + 1;
+    let mut directions = vec![
+        QosDirection::new(UID, 94_000, QosLevel::High),
+        QosDirection::new(foreground, 94_001, QosLevel::High),
+    ];
+    let cap = 300 * 1024;
+    Qos::distribute_cap(cap, directions.iter_mut(), &HashSet::from([foreground]));
+    assert_eq!(directions[0].speed(), 100 * 1024);
+    assert_eq!(directions[1].speed(), 200 * 1024);
+}
+
+// @tc.name: ut_qos_foreground_reserve
+// @tc.desc: Test a task moved to the foreground takes a reserved slot
+// @tc.precon: NA
+// @tc.step: 1. Fill every tier with background tasks of several apps
+//           2. Move a task left without a slot to the foreground
+//           3. Reschedule with and without the reservation
+// @tc.expect: The foregrounded task runs at High right away and displaces a
+//             background task, and queues again once nothing is reserved
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_qos_foreground_reserve() {
+    let state = handler();
+    let mut qos = qos_with_tasks(93_000, 8);
+    let last_background = 93_007;
+    for i in 0..3 {
+        qos.start_task(
+            UID + 1 + i,
+            TaskQosInfo {
+                task_id: 93_100 + i as u32,
+                action: Action::Download.repr,
+                mode: Mode::BackGround.repr,
+                state: 0,
+                priority: 0,
+            },
+        );
+    }
+    let foregrounded = 93_102;
+
+    let changes = qos.reschedule(&state);
+    assert_eq!(level_of(&changes, foregrounded), None);
+    assert!(level_of(&changes, last_background).is_some());
+
+    assert!(qos.task_set_mode(UID + 3, foregrounded, Mode::FrontEnd));
+    let changes = qos.reschedule(&state);
+    assert_eq!(level_of(&changes, foregrounded), Some(QosLevel::High));
+    assert_eq!(level_of(&changes, last_background), None);
+
+    assert!(qos.set_foreground_reserve(0));
+    assert!(!qos.set_foreground_reserve(0));
+    let changes = qos.reschedule(&state);
+    assert_eq!(level_of(&changes, foregrounded), None);
+}
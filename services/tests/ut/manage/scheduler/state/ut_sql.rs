@@ -12,7 +12,7 @@
 // limitations under the License.
 
 use super::*;
-use crate::config::NetworkConfig;
+use crate::config::{MeteredOverride, NetworkConfig};
 use crate::manage::database::RequestDb;
 use crate::tests::{lock_database, test_init};
 use crate::utils::get_current_timestamp;
@@ -280,6 +280,65 @@ fn ut_network_online() {
     assert_eq!(reason, RUNNING_TASK_MEET_LIMITS);
 }
 
+// @tc.name: ut_network_metered_override
+// @tc.desc: Test the per-task metered override when the metered flag flips
+// @tc.precon: NA
+// @tc.step: 1. Insert two running Wi-Fi tasks disallowing metered networks,
+//              one of them with the Unmetered override
+//           2. Report the Wi-Fi as metered
+//           3. Report the Wi-Fi as unmetered with a Metered override task
+// @tc.expect: The overriding task keeps running while the identical task
+// waits, and the Metered override makes a task wait on an unmetered Wi-Fi
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_network_metered_override() {
+    test_init();
+    let _lock = lock_database();
+    let db = RequestDb::get_instance();
+    let metered = MeteredOverride::Metered as u8;
+    let unmetered = MeteredOverride::Unmetered as u8;
+    let mut info = NetworkInfo {
+        network_type: NetworkType::Wifi,
+        is_metered: true,
+        is_roaming: false,
+    };
+
+    let plain = TaskIdGenerator::generate();
+    let overridden = TaskIdGenerator::generate();
+    for (task_id, treat_as) in [(plain, 0), (overridden, unmetered)] {
+        db.execute(&format!(
+            "INSERT OR REPLACE INTO request_task (task_id, state, reason, network, metered, roaming, version, mode, retry, metered_override) VALUES ({task_id}, {RUNNING}, {RUNNING_TASK_MEET_LIMITS}, {WIFI}, 0, 0, {API10}, {BACKGROUND}, 1, {treat_as})",
+        ))
+        .unwrap();
+    }
+    db.execute(&network_unavailable(&info).unwrap()).unwrap();
+    assert_eq!(
+        query_state_and_reason(plain),
+        (WAITING, UNSUPPORTED_NETWORK_TYPE)
+    );
+    assert_eq!(
+        query_state_and_reason(overridden),
+        (RUNNING, RUNNING_TASK_MEET_LIMITS)
+    );
+
+    info.is_metered = false;
+    db.execute(&format!(
+        "UPDATE request_task SET state = {RUNNING}, reason = {RUNNING_TASK_MEET_LIMITS}, metered_override = {metered} WHERE task_id = {overridden}",
+    ))
+    .unwrap();
+    db.execute(&network_unavailable(&info).unwrap()).unwrap();
+    db.execute(&network_available(&info)).unwrap();
+    assert_eq!(
+        query_state_and_reason(plain),
+        (WAITING, RUNNING_TASK_MEET_LIMITS)
+    );
+    assert_eq!(
+        query_state_and_reason(overridden),
+        (WAITING, UNSUPPORTED_NETWORK_TYPE)
+    );
+}
+
 // @tc.name: ut_app_state_unavailable
 // @tc.desc: Test task state handling when application state is unavailable
 // @tc.precon: NA
@@ -785,4 +844,224 @@ fn ut_multi_reason_unailable() {
     let (state, reason) = query_state_and_reason(task_id);
     assert_eq!(state, WAITING);
     assert_eq!(reason, NETWORK_APP_ACCOUNT);
-}
\ No newline at end of file
+}
+// @tc.name: ut_charging_unavailable
+// @tc.desc: Test background tasks wait for charging when charging is required
+// @tc.precon: NA
+// @tc.step: 1. Initialize test database
+//           2. Lock database
+//           3. Apply the charging gate to background and frontend tasks
+// @tc.expect: Running and queued background tasks wait for charging, frontend
+// tasks and tasks waiting for other conditions are unchanged
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_charging_unavailable() {
+    test_init();
+    let _lock = lock_database();
+    let db = RequestDb::get_instance();
+    let task_id = TaskIdGenerator::generate();
+
+    for (state, reason) in [
+        (RUNNING, RUNNING_TASK_MEET_LIMITS),
+        (RETRYING, RUNNING_TASK_MEET_LIMITS),
+        (WAITING, RUNNING_TASK_MEET_LIMITS),
+    ] {
+        db.execute(&format!(
+            "INSERT OR REPLACE INTO request_task (task_id, state, reason, mode) VALUES ({task_id}, {state}, {reason}, {BACKGROUND})"
+        )).unwrap();
+        db.execute(&charging_unavailable()).unwrap();
+        assert_eq!(query_state_and_reason(task_id), (WAITING, NOT_CHARGING));
+    }
+
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason, mode) VALUES ({task_id}, {WAITING}, {NETWORK_OFFLINE}, {BACKGROUND})"
+    )).unwrap();
+    db.execute(&charging_unavailable()).unwrap();
+    assert_eq!(query_state_and_reason(task_id), (WAITING, NETWORK_OFFLINE));
+
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason, mode) VALUES ({task_id}, {RUNNING}, {RUNNING_TASK_MEET_LIMITS}, {FRONTEND})"
+    )).unwrap();
+    db.execute(&charging_unavailable()).unwrap();
+    assert_eq!(
+        query_state_and_reason(task_id),
+        (RUNNING, RUNNING_TASK_MEET_LIMITS)
+    );
+}
+
+// @tc.name: ut_charging_available
+// @tc.desc: Test tasks waiting for charging return to the queue
+// @tc.precon: NA
+// @tc.step: 1. Initialize test database
+//           2. Lock database
+//           3. Lift the charging gate of a task waiting for charging
+// @tc.expect: The task waits in the queue again
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_charging_available() {
+    test_init();
+    let _lock = lock_database();
+    let db = RequestDb::get_instance();
+    let task_id = TaskIdGenerator::generate();
+
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason, mode) VALUES ({task_id}, {WAITING}, {NOT_CHARGING}, {BACKGROUND})"
+    )).unwrap();
+    db.execute(&charging_available()).unwrap();
+    assert_eq!(
+        query_state_and_reason(task_id),
+        (WAITING, RUNNING_TASK_MEET_LIMITS)
+    );
+}
+
+// @tc.name: ut_device_condition_unavailable
+// @tc.desc: Test tasks wait for the device condition their configuration asks
+// for
+// @tc.precon: NA
+// @tc.step: 1. Initialize test database
+//           2. Lock database
+//           3. Apply a thermal level and a discharging device to tasks with
+//              and without device conditions
+// @tc.expect: Only running and queued tasks whose condition is not met wait
+// for the device condition
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_device_condition_unavailable() {
+    test_init();
+    let _lock = lock_database();
+    let db = RequestDb::get_instance();
+    let task_id = TaskIdGenerator::generate();
+
+    for (state, reason) in [
+        (RUNNING, RUNNING_TASK_MEET_LIMITS),
+        (RETRYING, RUNNING_TASK_MEET_LIMITS),
+        (WAITING, RUNNING_TASK_MEET_LIMITS),
+    ] {
+        db.execute(&format!(
+            "INSERT OR REPLACE INTO request_task (task_id, state, reason, pause_on_thermal_level) VALUES ({task_id}, {state}, {reason}, 3)"
+        )).unwrap();
+        db.execute(&device_condition_unavailable(3, true)).unwrap();
+        assert_eq!(query_state_and_reason(task_id), (WAITING, DEVICE_CONDITION));
+    }
+
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason, pause_on_thermal_level) VALUES ({task_id}, {RUNNING}, {RUNNING_TASK_MEET_LIMITS}, 3)"
+    )).unwrap();
+    db.execute(&device_condition_unavailable(2, true)).unwrap();
+    assert_eq!(
+        query_state_and_reason(task_id),
+        (RUNNING, RUNNING_TASK_MEET_LIMITS)
+    );
+
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason, require_charging_while_running) VALUES ({task_id}, {RUNNING}, {RUNNING_TASK_MEET_LIMITS}, 1)"
+    )).unwrap();
+    db.execute(&device_condition_unavailable(0, true)).unwrap();
+    assert_eq!(
+        query_state_and_reason(task_id),
+        (RUNNING, RUNNING_TASK_MEET_LIMITS)
+    );
+    db.execute(&device_condition_unavailable(0, false)).unwrap();
+    assert_eq!(query_state_and_reason(task_id), (WAITING, DEVICE_CONDITION));
+
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason) VALUES ({task_id}, {RUNNING}, {RUNNING_TASK_MEET_LIMITS})"
+    )).unwrap();
+    db.execute(&device_condition_unavailable(u8::MAX, false))
+        .unwrap();
+    assert_eq!(
+        query_state_and_reason(task_id),
+        (RUNNING, RUNNING_TASK_MEET_LIMITS)
+    );
+}
+
+// @tc.name: ut_device_condition_available
+// @tc.desc: Test tasks waiting for the device condition return to the queue
+// once it is met
+// @tc.precon: NA
+// @tc.step: 1. Initialize test database
+//           2. Lock database
+//           3. Release tasks waiting for the device condition at different
+//              thermal levels and charging states
+// @tc.expect: Tasks return to the queue only when their condition is met
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_device_condition_available() {
+    test_init();
+    let _lock = lock_database();
+    let db = RequestDb::get_instance();
+    let thermal_task = TaskIdGenerator::generate();
+    let charging_task = TaskIdGenerator::generate();
+
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason, pause_on_thermal_level) VALUES ({thermal_task}, {WAITING}, {DEVICE_CONDITION}, 3)"
+    )).unwrap();
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason, require_charging_while_running) VALUES ({charging_task}, {WAITING}, {DEVICE_CONDITION}, 1)"
+    )).unwrap();
+
+    db.execute(&device_condition_available(3, false)).unwrap();
+    assert_eq!(
+        query_state_and_reason(thermal_task),
+        (WAITING, DEVICE_CONDITION)
+    );
+    assert_eq!(
+        query_state_and_reason(charging_task),
+        (WAITING, DEVICE_CONDITION)
+    );
+
+    db.execute(&device_condition_available(2, false)).unwrap();
+    assert_eq!(
+        query_state_and_reason(thermal_task),
+        (WAITING, RUNNING_TASK_MEET_LIMITS)
+    );
+    assert_eq!(
+        query_state_and_reason(charging_task),
+        (WAITING, DEVICE_CONDITION)
+    );
+
+    db.execute(&device_condition_available(2, true)).unwrap();
+    assert_eq!(
+        query_state_and_reason(charging_task),
+        (WAITING, RUNNING_TASK_MEET_LIMITS)
+    );
+}
+
+// @tc.name: ut_captive_portal_cleared
+// @tc.desc: Test tasks waiting for a captive portal return to the queue
+// @tc.precon: NA
+// @tc.step: 1. Initialize test database
+//           2. Lock database
+//           3. Clear the captive portal with one task waiting for it and one
+//              waiting for the network
+// @tc.expect: Only the task waiting for the portal waits in the queue again
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_captive_portal_cleared() {
+    test_init();
+    let _lock = lock_database();
+    let db = RequestDb::get_instance();
+    let portal_task = TaskIdGenerator::generate();
+    let offline_task = TaskIdGenerator::generate();
+
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason, mode) VALUES ({portal_task}, {WAITING}, {CAPTIVE_PORTAL}, {BACKGROUND})"
+    )).unwrap();
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, state, reason, mode) VALUES ({offline_task}, {WAITING}, {NETWORK_OFFLINE}, {BACKGROUND})"
+    )).unwrap();
+    db.execute(&captive_portal_cleared()).unwrap();
+    assert_eq!(
+        query_state_and_reason(portal_task),
+        (WAITING, RUNNING_TASK_MEET_LIMITS)
+    );
+    assert_eq!(
+        query_state_and_reason(offline_task),
+        (WAITING, NETWORK_OFFLINE)
+    );
+}
@@ -0,0 +1,98 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::fixtures::TaskRow;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+fn insert(db: &RequestDb, state: State, reason: Reason) -> u32 {
+    let task_id = TaskIdGenerator::generate();
+    TaskRow {
+        uid: (1 << 42) + task_id as u64,
+        action: Action::Upload,
+        state,
+        reason,
+        ..TaskRow::new(task_id)
+    }
+    .insert(db);
+    task_id
+}
+
+fn reason(db: &RequestDb, task_id: u32) -> u8 {
+    let sql = format!(
+        "SELECT reason FROM request_task WHERE task_id = {}",
+        task_id
+    );
+    db.query_integer(&sql)[0]
+}
+
+// @tc.name: ut_restore_interrupted_task
+// @tc.desc: Test interrupted tasks are restored to waiting
+// @tc.precon: NA
+// @tc.step: 1. Create running, retrying, paused and stopped tasks
+//           2. Restore every task
+// @tc.expect: Running and retrying tasks wait for their turn, paused and
+// stopped tasks are unchanged
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_restore_interrupted_task() {
+    let db = RequestDb::get_instance();
+    let running = insert(db, State::Running, Reason::Default);
+    let retrying = insert(db, State::Retrying, Reason::ContinuousTaskTimeout);
+    let paused = insert(db, State::Paused, Reason::UserOperation);
+    let stopped = insert(db, State::Stopped, Reason::UserOperation);
+
+    let interrupted = db.query_interrupted_tasks();
+    assert!(interrupted.contains(&running));
+    assert!(interrupted.contains(&retrying));
+    assert!(!interrupted.contains(&paused));
+    assert!(!interrupted.contains(&stopped));
+
+    for task_id in [running, retrying, paused, stopped] {
+        restore_task(db, task_id);
+    }
+
+    for task_id in [running, retrying] {
+        assert_eq!(db.query_task_state(task_id), Some(State::Waiting.repr));
+        assert_eq!(reason(db, task_id), Reason::RunningTaskMeetLimits.repr);
+    }
+    assert_eq!(db.query_task_state(paused), Some(State::Paused.repr));
+    assert_eq!(reason(db, paused), Reason::UserOperation.repr);
+    assert_eq!(db.query_task_state(stopped), Some(State::Stopped.repr));
+    assert_eq!(reason(db, stopped), Reason::UserOperation.repr);
+    assert!(!db.query_interrupted_tasks().contains(&running));
+}
+
+// @tc.name: ut_restore_rate_limited_task
+// @tc.desc: Test tasks waiting for a rate limit are queued again on restart
+// @tc.precon: NA
+// @tc.step: 1. Create a task waiting for a rate limit and one waiting for
+//           the network
+//           2. Restore the interrupted tasks
+// @tc.expect: The rate limited task waits for its turn, the other one keeps
+// its reason
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_restore_rate_limited_task() {
+    let db = RequestDb::get_instance();
+    let limited = insert(db, State::Waiting, Reason::RateLimited);
+    let offline = insert(db, State::Waiting, Reason::NetworkOffline);
+
+    restore_interrupted_tasks();
+
+    assert_eq!(db.query_task_state(limited), Some(State::Waiting.repr));
+    assert_eq!(reason(db, limited), Reason::RunningTaskMeetLimits.repr);
+    assert_eq!(reason(db, offline), Reason::NetworkOffline.repr);
+}
@@ -0,0 +1,74 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::fixtures::TaskRow;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+fn insert(db: &RequestDb, uid: u64, state: State, reason: Reason) {
+    TaskRow {
+        uid,
+        state,
+        reason,
+        ..TaskRow::new(TaskIdGenerator::generate())
+    }
+    .insert(db);
+}
+
+// @tc.name: ut_waiting_breakdown
+// @tc.desc: Test waiting tasks are counted per waiting cause
+// @tc.precon: NA
+// @tc.step: 1. Create waiting tasks of one app for every cause, including
+//              combined reasons, and a running task
+//           2. Count the waiting tasks of the app
+// @tc.expect: Each task is counted once under its first unmet condition and
+// the running task is not counted
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_waiting_breakdown() {
+    let db = RequestDb::get_instance();
+    let uid = (1 << 41) + TaskIdGenerator::generate() as u64;
+
+    insert(db, uid, State::Waiting, Reason::RunningTaskMeetLimits);
+    insert(db, uid, State::Waiting, Reason::RunningTaskMeetLimits);
+    insert(db, uid, State::Waiting, Reason::NetworkOffline);
+    insert(db, uid, State::Waiting, Reason::UnsupportedNetworkType);
+    insert(db, uid, State::Waiting, Reason::NetworkAppAccount);
+    insert(db, uid, State::Waiting, Reason::AppBackgroundOrTerminate);
+    insert(db, uid, State::Waiting, Reason::AppAccount);
+    insert(db, uid, State::Waiting, Reason::AccountStopped);
+    insert(db, uid, State::Waiting, Reason::RateLimited);
+    insert(db, uid, State::Waiting, Reason::NotCharging);
+    insert(db, uid, State::Waiting, Reason::CaptivePortal);
+    insert(db, uid, State::Waiting, Reason::DeviceCondition);
+    insert(db, uid, State::Running, Reason::Default);
+
+    assert_eq!(
+        db.waiting_breakdown(Some(uid)),
+        WaitingBreakdown {
+            task_queue: 2,
+            network: 3,
+            app_state: 2,
+            user_state: 1,
+            rate_limit: 1,
+            battery: 1,
+            captive_portal: 1,
+            device_condition: 1,
+        }
+    );
+    assert_eq!(
+        db.waiting_breakdown(Some(uid + 1)),
+        WaitingBreakdown::default()
+    );
+}
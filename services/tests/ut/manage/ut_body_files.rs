@@ -0,0 +1,93 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+
+use super::*;
+
+const OLD: Duration = Duration::from_secs(48 * 60 * 60);
+
+/// Creates `name` in `dir`, last modified `age` ago.
+fn create(dir: &Path, name: &str, age: Duration) -> PathBuf {
+    let path = dir.join(name);
+    let file = File::create(&path).unwrap();
+    file.set_modified(SystemTime::now() - age).unwrap();
+    path
+}
+
+// @tc.name: ut_body_files_name
+// @tc.desc: Test only exact body file names are recognized
+// @tc.precon: NA
+// @tc.step: 1. Check names with and without the body file pattern
+// @tc.expect: Only `tmp_body_<index>_<nanos>` names are accepted
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_body_files_name() {
+    assert!(is_body_file_name("tmp_body_0_1700000000000000000"));
+    assert!(is_body_file_name("tmp_body_12_3"));
+    assert!(!is_body_file_name("tmp_body_0_"));
+    assert!(!is_body_file_name("tmp_body__1"));
+    assert!(!is_body_file_name("tmp_body_0_1.txt"));
+    assert!(!is_body_file_name("tmp_body_a_1"));
+    assert!(!is_body_file_name("xtmp_body_0_1"));
+    assert!(!is_body_file_name("tmp_body_0"));
+}
+
+// @tc.name: ut_body_files_sweep
+// @tc.desc: Test the sweep deletes only old unreferenced body files in known
+// cache directories
+// @tc.precon: NA
+// @tc.step: 1. Create referenced, orphaned, recent and foreign files with old
+//              and new modification times in a cache directory
+//           2. Create an old orphaned body file outside the cache directory
+//           3. Sweep with the referenced body file
+// @tc.expect: Only the old orphaned body file in the cache directory is
+// deleted
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_body_files_sweep() {
+    let root = std::env::temp_dir().join("ut_body_files_sweep");
+    let _ = fs::remove_dir_all(&root);
+    let cache = root.join("cache");
+    let other = root.join("files");
+    fs::create_dir_all(&cache).unwrap();
+    fs::create_dir_all(&other).unwrap();
+
+    let referenced = create(&cache, "tmp_body_0_100", OLD);
+    let orphan = create(&cache, "tmp_body_1_200", OLD);
+    let recent = create(&cache, "tmp_body_2_300", Duration::from_secs(60));
+    let foreign = create(&cache, "tmp_body_3_400.bak", OLD);
+    let unrelated = create(&cache, "image.png", OLD);
+    let outside = create(&other, "tmp_body_4_500", OLD);
+    let dir = cache.join("tmp_body_5_600");
+    fs::create_dir_all(&dir).unwrap();
+
+    let set = HashSet::from([referenced.clone()]);
+    assert_eq!(
+        sweep_body_files(&set, BODY_FILE_MAX_AGE, SystemTime::now()),
+        1
+    );
+    assert!(!orphan.exists());
+    for path in [referenced, recent, foreign, unrelated, outside, dir] {
+        assert!(path.exists(), "{:?}", path);
+    }
+
+    // Without referenced body files no directory is known.
+    assert_eq!(
+        sweep_body_files(&HashSet::new(), Duration::ZERO, SystemTime::now()),
+        0
+    );
+    let _ = fs::remove_dir_all(&root);
+}
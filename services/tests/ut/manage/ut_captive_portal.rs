@@ -0,0 +1,64 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn extras(content_type: &str) -> HashMap<String, String> {
+    HashMap::from([(
+        UNEXPECTED_CONTENT_TYPE.to_string(),
+        content_type.to_string(),
+    )])
+}
+
+// @tc.name: ut_captive_portal_symptom
+// @tc.desc: Test which task failures may be caused by a captive portal
+// @tc.precon: NA
+// @tc.step: 1. Classify TLS errors, rejected content types and other
+//              failures
+// @tc.expect: TLS errors and rejected HTML pages are portal symptoms, other
+//             content types and server errors are not
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_captive_portal_symptom() {
+    let none = HashMap::new();
+    assert!(is_portal_symptom(Reason::Ssl, &none));
+    assert!(is_portal_symptom(
+        Reason::UnexpectedContentType,
+        &extras("text/html; charset=utf-8")
+    ));
+    assert!(is_portal_symptom(
+        Reason::UnexpectedContentType,
+        &extras("TEXT/HTML")
+    ));
+    assert!(is_portal_symptom(
+        Reason::UnexpectedContentType,
+        &extras("application/xhtml+xml")
+    ));
+
+    assert!(!is_portal_symptom(Reason::UnexpectedContentType, &none));
+    assert!(!is_portal_symptom(
+        Reason::UnexpectedContentType,
+        &extras("application/json")
+    ));
+    assert!(!is_portal_symptom(
+        Reason::UnexpectedContentType,
+        &extras("")
+    ));
+    assert!(!is_portal_symptom(
+        Reason::ProtocolError,
+        &extras("text/html")
+    ));
+    assert!(!is_portal_symptom(Reason::Tcp, &none));
+    assert!(!is_portal_symptom(Reason::Dns, &none));
+}
@@ -14,6 +14,7 @@
 use super::RequestDb;
 use crate::config::{Action, Mode};
 use crate::task::info::State;
+use crate::task::notify::ResponseBody;
 use crate::tests::{lock_database, test_init};
 use crate::utils::get_current_timestamp;
 use crate::utils::task_id_generator::TaskIdGenerator;
@@ -124,4 +125,33 @@ fn ut_database_app_task_qos_info() {
     assert_eq!(info.mode, Mode::FrontEnd.repr);
     assert_eq!(info.state, State::Completed.repr);
     assert_eq!(info.priority, priority);
-}
\ No newline at end of file
+}
+// @tc.name: ut_database_response_body
+// @tc.desc: Test storing and reading the upload response body of a task
+// @tc.precon: NA
+// @tc.step: 1. Insert a task without a response body
+//           2. Store a truncated binary response body
+//           3. Query the response body back
+// @tc.expect: Nothing is returned before storing, the stored body after
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_database_response_body() {
+    test_init();
+    let _lock = lock_database();
+    let task_id = TaskIdGenerator::generate();
+    let db = RequestDb::get_instance();
+    db.execute(&format!(
+        "INSERT INTO request_task (task_id, bundle) VALUES ({}, 'example_bundle')",
+        task_id
+    ))
+    .unwrap();
+    assert!(db.query_task_response_body(task_id).is_none());
+
+    let body = ResponseBody {
+        data: vec![0x7b, 0x00, 0xff, 0x27, 0x7d],
+        truncated: true,
+    };
+    db.update_task_response_body(task_id, &body);
+    assert_eq!(db.query_task_response_body(task_id), Some(body));
+}
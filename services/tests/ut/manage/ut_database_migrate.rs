@@ -0,0 +1,69 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn columns(inner: &Connection) -> Vec<String> {
+    let mut stmt = inner.prepare("PRAGMA table_info(request_task)").unwrap();
+    stmt.query_map([], |row| row.get(1))
+        .unwrap()
+        .map(|name| name.unwrap())
+        .collect()
+}
+
+// @tc.name: ut_database_migrate_columns
+// @tc.desc: Test a database of an older version gets the missing columns
+// @tc.precon: NA
+// @tc.step: 1. Create a table of a few columns holding a task
+//           2. Create the table of the current version and add the missing
+//              columns twice
+// @tc.expect: The table has the columns of the current version and still
+// holds the task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_database_migrate_columns() {
+    let inner = Connection::open_in_memory().unwrap();
+    inner
+        .execute(
+            "CREATE TABLE request_task (task_id INTEGER PRIMARY KEY, uid INTEGER, url TEXT)",
+            (),
+        )
+        .unwrap();
+    inner
+        .execute(
+            "INSERT INTO request_task (task_id, uid, url) VALUES (1, 2, 'url')",
+            (),
+        )
+        .unwrap();
+
+    inner.execute(CREATE_TABLE, ()).unwrap();
+    add_missing_columns(&inner).unwrap();
+    add_missing_columns(&inner).unwrap();
+
+    let expected = Connection::open_in_memory().unwrap();
+    expected.execute(CREATE_TABLE, ()).unwrap();
+    let mut added = columns(&inner);
+    let mut current = columns(&expected);
+    added.sort();
+    current.sort();
+    assert_eq!(added, current);
+    let url: String = inner
+        .query_row(
+            "SELECT url FROM request_task WHERE task_id = 1",
+            (),
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(url, "url");
+}
@@ -0,0 +1,78 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn task(task_id: u32, state: State, processed: usize, sizes: Vec<i64>) -> TaskInfo {
+    let mut info = TaskInfo::new();
+    info.common_data.task_id = task_id;
+    info.progress.sizes = sizes;
+    info.progress.common_data.state = state.repr;
+    info.progress.common_data.total_processed = processed;
+    info
+}
+
+// @tc.name: ut_group_info_aggregate
+// @tc.desc: Test the progress and state counts of a group are aggregated
+// @tc.precon: NA
+// @tc.step: 1. Aggregate three tasks at different progress points
+//           2. Aggregate them again after one was removed
+// @tc.expect: Processed and total bytes sum up over the members, each state
+// is counted, the removed task is counted but neither a member nor progress
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_group_info_aggregate() {
+    let mut tasks = vec![
+        task(1, State::Running, 100, vec![1000]),
+        task(2, State::Paused, 500, vec![1000]),
+        task(3, State::Completed, 2000, vec![2000]),
+    ];
+    let info = GroupInfo::new(7, true, Some("photos".to_string()), &tasks);
+    assert_eq!(info.group_id, 7);
+    assert_eq!(info.member_task_ids, vec![1, 2, 3]);
+    assert_eq!(info.aggregate_processed, 2600);
+    assert_eq!(info.aggregate_total, 4000);
+    assert_eq!(info.counts_by_state.running, 1);
+    assert_eq!(info.counts_by_state.paused, 1);
+    assert_eq!(info.counts_by_state.completed, 1);
+    assert!(info.gauge);
+    assert_eq!(info.title.as_deref(), Some("photos"));
+
+    tasks[1].progress.common_data.state = State::Removed.repr;
+    let info = GroupInfo::new(7, true, None, &tasks);
+    assert_eq!(info.member_task_ids, vec![1, 3]);
+    assert_eq!(info.aggregate_processed, 2100);
+    assert_eq!(info.aggregate_total, 3000);
+    assert_eq!(info.counts_by_state.paused, 0);
+    assert_eq!(info.counts_by_state.removed, 1);
+}
+
+// @tc.name: ut_group_info_unknown_size
+// @tc.desc: Test the total of a group is unknown once a member size is
+// @tc.precon: NA
+// @tc.step: 1. Aggregate a task of known size and one of unknown size
+// @tc.expect: The processed bytes sum up and the total is -1
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_group_info_unknown_size() {
+    let tasks = vec![
+        task(1, State::Running, 100, vec![-1]),
+        task(2, State::Running, 200, vec![1000]),
+    ];
+    let info = GroupInfo::new(8, false, None, &tasks);
+    assert_eq!(info.aggregate_processed, 300);
+    assert_eq!(info.aggregate_total, -1);
+    assert!(GroupInfo::new(8, false, None, &[]).member_task_ids.is_empty());
+}
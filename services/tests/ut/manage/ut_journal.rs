@@ -0,0 +1,98 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use request_utils::journal::{parse_journal, rotated_path};
+
+use super::*;
+
+fn journal(uid: u64) -> DiagnosticJournal {
+    let journal = DiagnosticJournal::new("test_files");
+    let _ = std::fs::remove_file(journal.path(uid));
+    let _ = std::fs::remove_file(rotated_path(journal.path(uid)));
+    journal
+}
+
+fn events(journal: &DiagnosticJournal, uid: u64) -> Vec<(u32, u64, JournalEvent, u64, u8)> {
+    parse_journal(journal.path(uid))
+        .into_iter()
+        .map(|r| (r.task_id, r.timestamp, r.event, r.bytes, r.reason))
+        .collect()
+}
+
+// @tc.name: ut_journal_lifecycle
+// @tc.desc: Test the events of a journaled task are written with sampled
+// progress
+// @tc.precon: NA
+// @tc.step: 1. Enable the journal of a uid
+//           2. Record progress within and after the sample interval, a
+//              waiting cause and a failure with its fault
+// @tc.expect: Progress within the sample interval is dropped, the other
+// events are read back in order with their bytes and reasons
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_journal_lifecycle() {
+    let uid = 1001;
+    let journal = journal(uid);
+    journal.enable(uid, MIN_JOURNAL_CAP).unwrap();
+
+    journal.record_at(uid, 1, JournalEvent::Resume, 0, 0, 1000);
+    journal.record_at(uid, 1, JournalEvent::Progress, 10, 0, 1000);
+    journal.record_at(uid, 1, JournalEvent::Progress, 20, 0, 1000 + 1);
+    let sampled = 1000 + PROGRESS_SAMPLE_INTERVAL_MS;
+    journal.record_at(uid, 1, JournalEvent::Progress, 30, 0, sampled);
+    journal.record_at(uid, 1, JournalEvent::Fail, 30, 0, sampled + 1);
+    journal.record_task(1, JournalEvent::Fault, 24);
+
+    let events = events(&journal, uid);
+    assert_eq!(events.len(), 5);
+    assert_eq!(events[0], (1, 1000, JournalEvent::Resume, 0, 0));
+    assert_eq!(events[1], (1, 1000, JournalEvent::Progress, 10, 0));
+    assert_eq!(events[2], (1, sampled, JournalEvent::Progress, 30, 0));
+    assert_eq!(events[3], (1, sampled + 1, JournalEvent::Fail, 30, 0));
+    assert_eq!(
+        (events[4].0, events[4].2, events[4].4),
+        (1, JournalEvent::Fault, 24)
+    );
+}
+
+// @tc.name: ut_journal_disabled
+// @tc.desc: Test tasks of uids without journal are not journaled
+// @tc.precon: NA
+// @tc.step: 1. Record events of a uid never enabled and of a task unknown to
+//              the journal
+//           2. Enable the journal of a uid, record an event and disable it
+//           3. Record another event of the uid
+// @tc.expect: Only the event recorded while the journal was enabled is read
+// back
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_journal_disabled() {
+    let uid = 1002;
+    let journal = journal(uid);
+    journal.record_at(uid, 2, JournalEvent::Resume, 0, 0, 1000);
+    journal.record_task(2, JournalEvent::Waiting, 1);
+    assert!(!journal.path(uid).exists());
+
+    journal.enable(uid, MIN_JOURNAL_CAP).unwrap();
+    journal.record_at(uid, 2, JournalEvent::Pause, 5, 0, 2000);
+    journal.enable(uid, 0).unwrap();
+    journal.record_at(uid, 2, JournalEvent::Resume, 5, 0, 3000);
+    journal.record_task(2, JournalEvent::Waiting, 1);
+
+    assert_eq!(
+        events(&journal, uid),
+        vec![(2, 2000, JournalEvent::Pause, 5, 0)]
+    );
+}
@@ -0,0 +1,147 @@
+// Copyright (C) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use super::*;
+
+const TASK_A: u32 = 1;
+const TASK_B: u32 = 2;
+
+/// Stub of the running lock recording the calls it receives.
+#[derive(Clone, Default)]
+struct StubLock {
+    calls: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl StubLock {
+    fn calls(&self) -> Vec<&'static str> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+impl RunningLock for StubLock {
+    fn acquire(&self, timeout: Duration) -> bool {
+        assert_eq!(timeout, SAFETY_TIMEOUT);
+        self.calls.lock().unwrap().push("acquire");
+        true
+    }
+
+    fn release(&self) {
+        self.calls.lock().unwrap().push("release");
+    }
+}
+
+// @tc.name: ut_keep_awake_overlapping_tasks
+// @tc.desc: Test the running lock is held once for overlapping tasks
+// @tc.precon: NA
+// @tc.step: 1. Start two keep-awake tasks
+//           2. Pause and resume the first, then complete both
+//           3. Expire the lock before and after the release grace
+// @tc.expect: The lock is acquired once when the first task starts and
+//             released once the grace after the last task completed passed
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_keep_awake_overlapping_tasks() {
+    let lock = StubLock::default();
+    let keep_awake = KeepAwake::new(lock.clone());
+    let now = Instant::now();
+
+    assert!(keep_awake.hold_at(TASK_A, now));
+    assert!(!keep_awake.hold_at(TASK_B, now));
+    assert_eq!(keep_awake.state(), (true, vec![TASK_A, TASK_B]));
+
+    // Pause and resume the first task
+    assert!(!keep_awake.leave_at(TASK_A, now));
+    keep_awake.expire_at(now + RELEASE_GRACE);
+    assert!(!keep_awake.hold_at(TASK_A, now));
+
+    // Complete both tasks
+    assert!(!keep_awake.leave_at(TASK_A, now));
+    assert!(keep_awake.leave_at(TASK_B, now));
+    assert_eq!(keep_awake.state(), (true, vec![]));
+    keep_awake.expire_at(now + RELEASE_GRACE - Duration::from_millis(1));
+    assert_eq!(lock.calls(), vec!["acquire"]);
+    keep_awake.expire_at(now + RELEASE_GRACE);
+    keep_awake.expire_at(now + RELEASE_GRACE);
+    assert_eq!(lock.calls(), vec!["acquire", "release"]);
+    assert_eq!(keep_awake.state(), (false, vec![]));
+}
+
+// @tc.name: ut_keep_awake_reschedule
+// @tc.desc: Test a task rescheduled within the grace keeps the lock held
+// @tc.precon: NA
+// @tc.step: 1. Start a keep-awake task and stop it
+//           2. Restart it within the release grace, before its previous run
+//              ended a second time
+// @tc.expect: The lock is neither released nor acquired again until the
+//             task stopped for the whole grace
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_keep_awake_reschedule() {
+    let lock = StubLock::default();
+    let keep_awake = KeepAwake::new(lock.clone());
+    let now = Instant::now();
+
+    keep_awake.hold_at(TASK_A, now);
+    assert!(keep_awake.leave_at(TASK_A, now));
+    let later = now + RELEASE_GRACE / 2;
+    assert!(!keep_awake.hold_at(TASK_A, later));
+    assert!(!keep_awake.hold_at(TASK_A, later));
+    assert!(!keep_awake.leave_at(TASK_A, later));
+    keep_awake.expire_at(now + RELEASE_GRACE);
+    assert_eq!(keep_awake.state(), (true, vec![TASK_A]));
+
+    let end = later + RELEASE_GRACE;
+    assert!(keep_awake.leave_at(TASK_A, end));
+    keep_awake.expire_at(end + RELEASE_GRACE);
+    assert_eq!(lock.calls(), vec!["acquire", "release"]);
+}
+
+// @tc.name: ut_keep_awake_safety_timeout
+// @tc.desc: Test the safety timeout force-releases the running lock
+// @tc.precon: NA
+// @tc.step: 1. Start a keep-awake task and expire the lock after the
+//              safety timeout
+//           2. Start another keep-awake task
+//           3. Complete both tasks and start one again
+// @tc.expect: The lock is released at the timeout with the task running,
+//             is not acquired again while tasks still run, and is acquired
+//             again once all of them left
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_keep_awake_safety_timeout() {
+    let lock = StubLock::default();
+    let keep_awake = KeepAwake::new(lock.clone());
+    let now = Instant::now();
+
+    keep_awake.hold_at(TASK_A, now);
+    keep_awake.expire_at(now + SAFETY_TIMEOUT - Duration::from_millis(1));
+    assert_eq!(lock.calls(), vec!["acquire"]);
+    keep_awake.expire_at(now + SAFETY_TIMEOUT);
+    assert_eq!(lock.calls(), vec!["acquire", "release"]);
+    assert_eq!(keep_awake.state(), (false, vec![TASK_A]));
+
+    let later = now + SAFETY_TIMEOUT;
+    assert!(!keep_awake.hold_at(TASK_B, later));
+    keep_awake.leave_at(TASK_A, later);
+    keep_awake.leave_at(TASK_B, later);
+    keep_awake.expire_at(later + RELEASE_GRACE);
+    assert_eq!(lock.calls(), vec!["acquire", "release"]);
+
+    assert!(keep_awake.hold_at(TASK_A, later + RELEASE_GRACE));
+    assert_eq!(lock.calls(), vec!["acquire", "release", "acquire"]);
+}
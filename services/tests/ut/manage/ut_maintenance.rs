@@ -0,0 +1,156 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::*;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+static CHILD_TABLES: Mutex<()> = Mutex::new(());
+
+/// Creates the child tables and inserts a live task plus rows of a live and
+/// an orphaned task into each of them. Returns `(live, orphan)`.
+fn prepare(db: &RequestDb) -> (MutexGuard<'static, ()>, u32, u32) {
+    let guard = CHILD_TABLES.lock().unwrap_or_else(|e| e.into_inner());
+    db.execute("CREATE TABLE IF NOT EXISTS task_config (task_id INTEGER PRIMARY KEY, display BOOLEAN)")
+        .unwrap();
+    db.execute("CREATE TABLE IF NOT EXISTS task_notification_content (task_id INTEGER PRIMARY KEY, title TEXT, text TEXT)")
+        .unwrap();
+
+    let live = TaskIdGenerator::generate();
+    let orphan = TaskIdGenerator::generate();
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, bundle) VALUES ({}, 'example_bundle')",
+        live
+    ))
+    .unwrap();
+    for table in ["task_config", "task_notification_content"] {
+        for task_id in [live, orphan] {
+            db.execute(&format!(
+                "INSERT OR REPLACE INTO {} (task_id) VALUES ({})",
+                table, task_id
+            ))
+            .unwrap();
+        }
+    }
+    (guard, live, orphan)
+}
+
+fn contains(db: &RequestDb, table: &str, task_id: u32) -> bool {
+    let sql = format!("SELECT COUNT(*) FROM {} WHERE task_id = {}", table, task_id);
+    db.query_integer::<u32>(&sql).first().copied().unwrap_or(0) == 1
+}
+
+// @tc.name: ut_maintenance_prune_orphans
+// @tc.desc: Test maintenance deletes child rows of deleted tasks only
+// @tc.precon: NA
+// @tc.step: 1. Insert child rows of a live and of a deleted task
+//           2. Run a maintenance job to the end
+// @tc.expect: Rows of the deleted task are gone, rows of the live task remain
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_maintenance_prune_orphans() {
+    let db = RequestDb::get_instance();
+    let (_guard, live, orphan) = prepare(db);
+
+    let mut maintenance = Maintenance::new(ActiveCounter::new(), MAINTENANCE_IDLE_PERIOD);
+    let report = maintenance.start().run(db);
+    assert!(!report.interrupted);
+    assert!(report.pruned_rows >= 2);
+    for table in ["task_config", "task_notification_content"] {
+        assert!(contains(db, table, live));
+        assert!(!contains(db, table, orphan));
+    }
+}
+
+// @tc.name: ut_maintenance_construct_interrupts
+// @tc.desc: Test a constructed task stops maintenance between two steps
+// @tc.precon: NA
+// @tc.step: 1. Insert child rows of a deleted task in two tables
+//           2. Run the first step of a maintenance job
+//           3. Interrupt as the construct event does and run the rest
+// @tc.expect: The first table is pruned, the job reports the interruption and
+// the later table keeps its orphaned row
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_maintenance_construct_interrupts() {
+    let db = RequestDb::get_instance();
+    let (_guard, _live, orphan) = prepare(db);
+
+    let mut maintenance = Maintenance::new(ActiveCounter::new(), MAINTENANCE_IDLE_PERIOD);
+    let mut job = maintenance.start();
+    let mut report = MaintenanceReport::default();
+    assert!(job.step(db, &mut report));
+    assert!(!contains(db, "task_config", orphan));
+
+    maintenance.interrupt();
+    let report = job.run(db);
+    assert!(report.interrupted);
+    assert_eq!(report.pruned_rows, 0);
+    assert!(contains(db, "task_notification_content", orphan));
+}
+
+// @tc.name: ut_maintenance_idle_period
+// @tc.desc: Test maintenance only starts after an uninterrupted idle period
+// @tc.precon: NA
+// @tc.step: 1. Poll with running tasks, an active counter and while idle
+//           2. Poll again after the idle period
+// @tc.expect: A job is handed out only once the service was idle long enough
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_maintenance_idle_period() {
+    let counter = ActiveCounter::new();
+    let period = Duration::from_secs(60);
+    let mut maintenance = Maintenance::new(counter.clone(), period);
+    let now = Instant::now();
+
+    assert!(maintenance.poll_idle(1, now).is_none());
+    counter.increment();
+    assert!(maintenance.poll_idle(0, now).is_none());
+    counter.decrement();
+    assert!(maintenance.poll_idle(0, now).is_none());
+    assert!(maintenance.poll_idle(0, now + period / 2).is_none());
+    maintenance.interrupt();
+    assert!(maintenance.poll_idle(0, now + period).is_none());
+    assert!(maintenance.poll_idle(0, now + period * 2).is_some());
+}
+
+// @tc.name: ut_maintenance_vacuum
+// @tc.desc: Test maintenance returns free pages of the database
+// @tc.precon: NA
+// @tc.step: 1. Check the auto vacuum mode of the database
+//           2. Fill a table and drop it
+//           3. Run a vacuum step
+// @tc.expect: The database uses incremental auto vacuum and the step
+// returns free pages
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_maintenance_vacuum() {
+    let db = RequestDb::get_instance();
+    let _guard = CHILD_TABLES.lock().unwrap_or_else(|e| e.into_inner());
+    assert_eq!(db.query_integer::<u64>("PRAGMA auto_vacuum"), vec![2]);
+
+    db.execute("CREATE TABLE IF NOT EXISTS maintenance_vacuum (data BLOB)")
+        .unwrap();
+    for _ in 0..64 {
+        db.execute("INSERT INTO maintenance_vacuum (data) VALUES (zeroblob(4096))")
+            .unwrap();
+    }
+    db.execute("DROP TABLE maintenance_vacuum").unwrap();
+    assert!(vacuum_step(db) > 0);
+}
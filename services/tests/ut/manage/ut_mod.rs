@@ -13,21 +13,31 @@
 
 use std::fs::File;
 
-use ylong_runtime::sync::mpsc::unbounded_channel;
+use ylong_runtime::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
 use super::database::RequestDb;
 use super::network::{NetworkInfo, NetworkInner, NetworkType};
+use super::network_manager::NetworkManager;
 use super::TaskManager;
-use crate::config::{Action, ConfigBuilder, Mode};
+use crate::config::{Action, ConfigBuilder, Mode, NetworkConfig, ResumeStrategy};
 use crate::error::ErrorCode;
 use crate::info::{State, TaskInfo};
+use crate::manage::query::TaskFilter;
+use crate::manage::task_list::TaskListDelta;
 use crate::manage::task_manager::{TaskManagerRx, TaskManagerTx};
 use crate::service::active_counter::ActiveCounter;
-use crate::service::client::ClientManagerEntry;
+use crate::service::client::{ClientEvent, ClientManagerEntry};
+use crate::service::notification_bar::{GroupProgressStyle, NotificationDispatcher};
 use crate::service::run_count::RunCountManagerEntry;
+use crate::task::notify::WaitingCause;
+use crate::task::reason::Reason;
 use crate::tests::{lock_database, test_init};
 
 fn task_manager() -> TaskManager {
+    task_manager_with_client().0
+}
+
+fn task_manager_with_client() -> (TaskManager, UnboundedReceiver<ClientEvent>) {
     let (tx, rx) = unbounded_channel();
     let task_manager_tx = TaskManagerTx::new(tx);
     let rx = TaskManagerRx::new(rx);
@@ -39,9 +49,12 @@ fn task_manager() -> TaskManager {
     });
     let (tx, _rx) = unbounded_channel();
     let run_count = RunCountManagerEntry::new(tx);
-    let (tx, _rx) = unbounded_channel();
+    let (tx, client_rx) = unbounded_channel();
     let client = ClientManagerEntry::new(tx);
-    TaskManager::new(task_manager_tx, rx, run_count, client, ActiveCounter::new())
+    (
+        TaskManager::new(task_manager_tx, rx, run_count, client, ActiveCounter::new()),
+        client_rx,
+    )
 }
 
 fn task_into(task_id: u32) -> TaskInfo {
@@ -145,4 +158,294 @@ fn ut_manager_state_change_error() {
         task_into(task_id).progress.common_data.state,
         State::Removed.repr
     );
-}
\ No newline at end of file
+}
+
+/// Flushes the task lists and returns the delta sent to the client.
+fn next_task_list_delta(
+    manager: &mut TaskManager,
+    client_rx: &mut UnboundedReceiver<ClientEvent>,
+) -> TaskListDelta {
+    manager.flush_task_lists();
+    ylong_runtime::block_on(async {
+        loop {
+            if let ClientEvent::SendTaskListDelta(pid, id, delta) = client_rx.recv().await.unwrap() {
+                assert_eq!((pid, id), (1, 1));
+                return delta;
+            }
+        }
+    })
+}
+
+// @tc.name: ut_manager_task_list_subscription
+// @tc.desc: Test task list deltas reach the client for create, start and remove
+// @tc.precon: NA
+// @tc.step: 1. Subscribe to the task list and create a download task
+//           2. Start the task and remove it, flushing after each step
+// @tc.expect: The client receives the task as added, then its new state and
+// finally its removal
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_manager_task_list_subscription() {
+    test_init();
+    let _lock = lock_database();
+    let (mut manager, mut client_rx) = task_manager_with_client();
+    let file_path = "test_files/ut_manager_task_list_subscription.txt";
+
+    let file = File::create(file_path).unwrap();
+    let config = ConfigBuilder::new()
+    .action(Action::Download)
+    .mode(Mode::BackGround)
+    .file_spec(file)
+    .url("https://www.gitee.com/tiga-ultraman/downloadTests/releases/download/v1.01/test.txt")
+    .build();
+    let uid = config.common_data.uid;
+    let filter = TaskFilter {
+        before: i64::MAX,
+        after: 0,
+        state: State::Any.repr,
+        action: Action::Any.repr,
+        mode: Mode::Any.repr,
+        client_tag: String::new(),
+    };
+    manager.task_list.subscribe(uid, 1, 1, filter);
+
+    let task_id = manager.create(config).unwrap();
+    let delta = next_task_list_delta(&mut manager, &mut client_rx);
+    assert_eq!(delta.added.len(), 1);
+    assert_eq!(delta.added[0].task_id, task_id);
+    assert_eq!(delta.added[0].state, State::Initialized);
+
+    assert_eq!(manager.start(uid, task_id), ErrorCode::ErrOk);
+    let delta = next_task_list_delta(&mut manager, &mut client_rx);
+    assert!(delta.added.is_empty());
+    assert_eq!(delta.state_changed.len(), 1);
+    assert_eq!(delta.state_changed[0].0, task_id);
+    assert_ne!(delta.state_changed[0].1, State::Initialized);
+
+    assert_eq!(manager.remove(uid, task_id), ErrorCode::ErrOk);
+    let delta = next_task_list_delta(&mut manager, &mut client_rx);
+    assert_eq!(delta.removed, vec![task_id]);
+    assert!(delta.state_changed.is_empty());
+}
+
+// @tc.name: ut_manager_cancel_group
+// @tc.desc: Test cancelling a group stops or removes all its tasks
+// @tc.precon: NA
+// @tc.step: 1. Create two groups with several started download tasks each
+//           2. Cancel the first group keeping its tasks
+//           3. Cancel the second group removing its tasks
+//           4. Attach a new task to the first group
+// @tc.expect: Every task is reported, tasks of the first group are stopped,
+//             tasks of the second group are removed and the cancelled group
+//             accepts no more tasks
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_manager_cancel_group() {
+    test_init();
+    let _lock = lock_database();
+    let mut manager = task_manager();
+    let file_path = "test_files/ut_manager_cancel_group.txt";
+
+    let file = File::create(file_path).unwrap();
+    let config = ConfigBuilder::new()
+    .action(Action::Download)
+    .mode(Mode::BackGround)
+    .file_spec(file)
+    .url("https://www.gitee.com/tiga-ultraman/downloadTests/releases/download/v1.01/test.txt")
+    .build();
+    let uid = config.common_data.uid;
+
+    for (remove_members, state) in [(false, State::Stopped), (true, State::Removed)] {
+        let group_id = NotificationDispatcher::get_instance().create_group(
+            true,
+            None,
+            None,
+            None,
+            false,
+            0b11,
+            GroupProgressStyle::Determinate,
+            uid,
+        );
+        let mut task_ids: Vec<u32> = (0..3)
+            .map(|_| manager.create(config.clone()).unwrap())
+            .collect();
+        task_ids.sort();
+        assert_eq!(
+            manager.attach_group(uid, task_ids.clone(), group_id),
+            ErrorCode::ErrOk
+        );
+        for task_id in task_ids.iter().copied() {
+            assert_eq!(manager.start(uid, task_id), ErrorCode::ErrOk);
+        }
+
+        let mut results = manager.cancel_group(uid, group_id, remove_members).unwrap();
+        results.sort_by_key(|(task_id, _)| *task_id);
+        assert_eq!(
+            results,
+            task_ids
+                .iter()
+                .map(|task_id| (*task_id, ErrorCode::ErrOk))
+                .collect::<Vec<_>>()
+        );
+        for task_id in task_ids.iter().copied() {
+            assert_eq!(task_into(task_id).progress.common_data.state, state.repr);
+        }
+
+        let task_id = manager.create(config.clone()).unwrap();
+        assert_eq!(
+            manager.attach_group(uid, vec![task_id], group_id),
+            ErrorCode::GroupNotFound
+        );
+        assert_eq!(manager.remove(uid, task_id), ErrorCode::ErrOk);
+    }
+    assert!(manager.cancel_group(uid, u32::MAX, false).is_none());
+}
+
+fn set_network(network_type: NetworkType) {
+    let network_manager = NetworkManager::get_instance().lock().unwrap();
+    network_manager.network.inner.notify_online(NetworkInfo {
+        network_type,
+        is_metered: network_type == NetworkType::Cellular,
+        is_roaming: false,
+    });
+}
+
+// @tc.name: ut_manager_migrate_to_preferred_network
+// @tc.desc: Test running tasks move to Wi-Fi once it becomes available
+// @tc.precon: NA
+// @tc.step: 1. Start downloads on a cellular network, resumable by a query
+//              parameter, by a range or not resumable, and bound to a network
+//           2. Switch the network to Wi-Fi
+//           3. Migrate the running tasks to the preferred network
+// @tc.expect: Only resumable unbound tasks are restarted, a range resume
+//             needs a validator, and tasks are migrated once
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_manager_migrate_to_preferred_network() {
+    test_init();
+    let _lock = lock_database();
+    set_network(NetworkType::Cellular);
+    let mut manager = task_manager();
+    let file_path = "test_files/ut_manager_migrate_to_preferred_network.txt";
+
+    let config = |strategy: ResumeStrategy, net_id: i32| {
+        let file = File::create(file_path).unwrap();
+        ConfigBuilder::new()
+            .action(Action::Download)
+            .mode(Mode::BackGround)
+            .file_spec(file)
+            .url("https://www.gitee.com/tiga-ultraman/downloadTests/releases/download/v1.01/test.txt")
+            .resume_strategy(strategy)
+            .net_id(net_id)
+            .build()
+    };
+    let by_query = ResumeStrategy::QueryParam {
+        name: "offset".to_string(),
+    };
+    let query = manager.create(config(by_query.clone(), 0)).unwrap();
+    let range = manager
+        .create(config(ResumeStrategy::RangeHeader, 0))
+        .unwrap();
+    let validated = manager
+        .create(config(ResumeStrategy::RangeHeader, 0))
+        .unwrap();
+    let restart = manager.create(config(ResumeStrategy::None, 0)).unwrap();
+    let bound = manager.create(config(by_query, 100)).unwrap();
+    let uid = config(ResumeStrategy::None, 0).common_data.uid;
+    let task_ids = [query, range, validated, restart, bound];
+    for task_id in task_ids {
+        assert_eq!(manager.start(uid, task_id), ErrorCode::ErrOk);
+    }
+    manager.scheduler.reschedule();
+    for task_id in task_ids {
+        assert!(manager.scheduler.get_task(uid, task_id).is_some());
+    }
+    manager
+        .scheduler
+        .get_task(uid, validated)
+        .unwrap()
+        .progress
+        .lock()
+        .unwrap()
+        .extras
+        .insert("etag".to_string(), "\"v1\"".to_string());
+
+    // Still on cellular, nothing moves.
+    assert!(!manager.scheduler.gains_preferred_network());
+    assert!(manager.scheduler.migrate_to_preferred_network().is_empty());
+
+    set_network(NetworkType::Wifi);
+    assert!(manager.scheduler.gains_preferred_network());
+    let mut migrated = manager.scheduler.migrate_to_preferred_network();
+    let mut expected = vec![query, validated];
+    migrated.sort();
+    expected.sort();
+    assert_eq!(migrated, expected);
+    assert!(manager.scheduler.migrate_to_preferred_network().is_empty());
+
+    for task_id in task_ids {
+        assert_eq!(manager.remove(uid, task_id), ErrorCode::ErrOk);
+    }
+    NetworkManager::get_instance()
+        .lock()
+        .unwrap()
+        .network
+        .inner
+        .notify_offline();
+}
+
+// @tc.name: ut_manager_explain_waiting_for_network
+// @tc.desc: Test the explanation of a task waiting for network
+// @tc.precon: NA
+// @tc.step: 1. Start a Wi-Fi only download on a cellular network
+//           2. Explain the task
+// @tc.expect: The task waits for network, the network condition is the
+//             only unsatisfied one
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_manager_explain_waiting_for_network() {
+    test_init();
+    let _lock = lock_database();
+    set_network(NetworkType::Cellular);
+    let mut manager = task_manager();
+    let file_path = "test_files/ut_manager_explain_waiting_for_network.txt";
+
+    let file = File::create(file_path).unwrap();
+    let config = ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .file_spec(file)
+        .url("https://www.gitee.com/tiga-ultraman/downloadTests/releases/download/v1.01/test.txt")
+        .network(NetworkConfig::Wifi)
+        .build();
+    let uid = config.common_data.uid;
+    let task_id = manager.create(config).unwrap();
+
+    let explanation = manager.scheduler.explain(task_id).unwrap();
+    assert_eq!(explanation.state, State::Initialized);
+    assert_eq!(explanation.waiting_cause, None);
+
+    assert_eq!(manager.start(uid, task_id), ErrorCode::ErrOk);
+    let explanation = manager.scheduler.explain(task_id).unwrap();
+    assert_eq!(explanation.state, State::Waiting);
+    assert_eq!(explanation.reason, Reason::UnsupportedNetworkType);
+    assert_eq!(explanation.waiting_cause, Some(WaitingCause::Network));
+    assert!(!explanation.network);
+    assert!(explanation.foreground);
+    assert!(explanation.battery);
+    assert!(explanation.storage);
+    assert_eq!(explanation.tries, 0);
+    assert_eq!(explanation.next_action_time, 0);
+
+    assert_eq!(manager.remove(uid, task_id), ErrorCode::ErrOk);
+    NetworkManager::get_instance()
+        .lock()
+        .unwrap()
+        .network
+        .inner
+        .notify_offline();
+}
@@ -510,4 +510,34 @@ fn ut_notify_pause_resume_failed() {
         assert_eq!(subscribe_type, SubscribeType::Resume);
         assert!(client_rx.is_empty());
     })
-}
\ No newline at end of file
+}
+// @tc.name: ut_notify_after_commit
+// @tc.desc: Test a notification is only sent once its state change is written
+// @tc.precon: NA
+// @tc.step: 1. Notify after a commit that succeeds
+//           2. Notify after a commit that fails
+// @tc.expect: The notification follows a successful commit, and a failed
+//             commit returns its error without notifying
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_notify_after_commit() {
+    let mut events = Vec::new();
+    let res = crate::manage::notifier::notify_after_commit(
+        || {
+            events.push("commit");
+            Ok(())
+        },
+        || events.push("notify"),
+    );
+    assert_eq!(res, Ok(()));
+    assert_eq!(events, vec!["commit", "notify"]);
+
+    let mut notified = false;
+    let res = crate::manage::notifier::notify_after_commit(
+        || Err(ErrorCode::SystemApi),
+        || notified = true,
+    );
+    assert_eq!(res, Err(ErrorCode::SystemApi));
+    assert!(!notified);
+}
@@ -0,0 +1,173 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use super::*;
+use crate::task::info::State;
+use crate::task::notify::Progress;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+type Batches = Arc<Mutex<Vec<Vec<(u32, usize)>>>>;
+
+/// Starts a writer recording the total processed of every written task.
+fn recording_writer(interval: Duration, max_batch: usize) -> (Arc<ProgressWriter>, Batches) {
+    let (tx, rx) = mpsc::channel();
+    let batches = Batches::default();
+    let record = batches.clone();
+    let sink: Sink = Box::new(move |batch| {
+        let mut rows: Vec<(u32, usize)> = batch
+            .iter()
+            .map(|(task_id, info)| (*task_id, info.progress.common_data.total_processed))
+            .collect();
+        rows.sort();
+        record.lock().unwrap().push(rows);
+    });
+    let writer = Arc::new(ProgressWriter::new(tx, interval, max_batch, sink));
+    let flusher = writer.clone();
+    std::thread::spawn(move || flusher.run(rx));
+    (writer, batches)
+}
+
+fn update_info(total_processed: usize) -> UpdateInfo {
+    let mut progress = Progress::new(vec![1024]);
+    progress.common_data.total_processed = total_processed;
+    progress.processed = vec![total_processed];
+    UpdateInfo {
+        mtime: 1,
+        reason: 0,
+        tries: 0,
+        mime_type: String::new(),
+        progress,
+    }
+}
+
+/// Waits until `count` batches were written, returning all of them.
+fn await_batches(batches: &Batches, count: usize) -> Vec<Vec<(u32, usize)>> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while batches.lock().unwrap().len() < count && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    batches.lock().unwrap().clone()
+}
+
+// @tc.name: ut_progress_writer_coalesce
+// @tc.desc: Test snapshots of a task are coalesced into its latest one
+// @tc.precon: NA
+// @tc.step: 1. Push three snapshots of one task and one of another
+//           2. Wait for the flush interval
+// @tc.expect: One batch holds the latest snapshot of each task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_progress_writer_coalesce() {
+    let (writer, batches) = recording_writer(Duration::from_millis(200), MAX_BATCH_TASKS);
+    writer.push(1, update_info(10));
+    writer.push(1, update_info(20));
+    writer.push(2, update_info(5));
+    writer.push(1, update_info(30));
+
+    assert_eq!(await_batches(&batches, 1), vec![vec![(1, 30), (2, 5)]]);
+    let stats = writer.stats();
+    assert_eq!(stats.batches, 1);
+    assert_eq!(stats.rows, 2);
+    assert_eq!(stats.max_batch, 2);
+}
+
+// @tc.name: ut_progress_writer_max_batch
+// @tc.desc: Test a full batch is written before the flush interval elapsed
+// @tc.precon: NA
+// @tc.step: 1. Create a writer with a long interval and batches of 4 tasks
+//           2. Push snapshots of 4 tasks
+// @tc.expect: The batch is written at once
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_progress_writer_max_batch() {
+    let (writer, batches) = recording_writer(Duration::from_secs(60), 4);
+    for task_id in 1..=4 {
+        writer.push(task_id, update_info(task_id as usize));
+    }
+    assert_eq!(
+        await_batches(&batches, 1),
+        vec![vec![(1, 1), (2, 2), (3, 3), (4, 4)]]
+    );
+}
+
+// @tc.name: ut_progress_writer_flush_now
+// @tc.desc: Test a terminal write is immediate and supersedes queued snapshots
+// @tc.precon: NA
+// @tc.step: 1. Push a snapshot of a task, then write its progress at once
+//           2. Push a snapshot of another task to fill the batch
+// @tc.expect: The immediate write lands before the call returns, and the
+//             batch only holds the other task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_progress_writer_flush_now() {
+    let (writer, batches) = recording_writer(Duration::from_secs(60), 2);
+    writer.push(1, update_info(10));
+    writer.flush_now(1, update_info(50));
+    assert_eq!(batches.lock().unwrap().clone(), vec![vec![(1, 50)]]);
+
+    writer.push(2, update_info(5));
+    assert_eq!(
+        await_batches(&batches, 2),
+        vec![vec![(1, 50)], vec![(2, 5)]]
+    );
+    assert_eq!(writer.stats().rows, 1);
+}
+
+// @tc.name: ut_progress_writer_batch_sql
+// @tc.desc: Test the batch statement writes the progress of every task
+// @tc.precon: NA
+// @tc.step: 1. Insert three tasks
+//           2. Execute the statement of a batch of two of them, one with a
+//              quote in its extras
+// @tc.expect: Both tasks of the batch hold their own progress and extras,
+//             the third task is unchanged
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_progress_writer_batch_sql() {
+    let db = RequestDb::get_instance();
+    let ids: Vec<u32> = (0..3).map(|_| TaskIdGenerator::generate()).collect();
+    for task_id in ids.iter() {
+        db.execute(&format!(
+            "INSERT OR REPLACE INTO request_task (task_id, uid, action, mode, state, reason, ctime, priority, total_processed) VALUES ({}, 1, 0, 1, {}, 0, 1, 0, 0)",
+            task_id,
+            State::Running.repr,
+        ))
+        .unwrap();
+    }
+    let first = update_info(100);
+    let mut second = update_info(200);
+    second
+        .progress
+        .extras
+        .insert("etag".to_string(), "it's".to_string());
+
+    db.execute(&batch_sql(&[(ids[0], first), (ids[1], second)]))
+        .unwrap();
+
+    assert_eq!(db.query_task_total_processed(ids[0]), Some(100));
+    assert_eq!(db.query_task_total_processed(ids[1]), Some(200));
+    assert_eq!(db.query_task_total_processed(ids[2]), Some(0));
+    let extras = db
+        .query_blob(&format!(
+            "SELECT CAST(extras AS BLOB) FROM request_task WHERE task_id = {}",
+            ids[1]
+        ))
+        .unwrap();
+    assert!(String::from_utf8(extras).unwrap().contains("it's"));
+}
@@ -39,6 +39,7 @@ fn ut_search_user() {
         state: State::Completed.repr,
         action: Action::Any.repr,
         mode: Mode::Any.repr,
+        client_tag: String::new(),
     };
     let res = db.search_task(filter, uid);
     assert_eq!(res, vec![]);
@@ -49,6 +50,7 @@ fn ut_search_user() {
         state: State::Any.repr,
         action: Action::Download.repr,
         mode: Mode::Any.repr,
+        client_tag: String::new(),
     };
     let res = db.search_task(filter, uid);
     assert_eq!(res, vec![]);
@@ -59,6 +61,7 @@ fn ut_search_user() {
         state: State::Any.repr,
         action: Action::Any.repr,
         mode: Mode::FrontEnd.repr,
+        client_tag: String::new(),
     };
     let res = db.search_task(filter, uid);
     assert_eq!(res, vec![]);
@@ -69,6 +72,7 @@ fn ut_search_user() {
         state: State::Removed.repr,
         action: Action::Upload.repr,
         mode: Mode::BackGround.repr,
+        client_tag: String::new(),
     };
     let res = db.search_task(filter, uid);
     assert_eq!(res, vec![task_id as u32]);
@@ -79,6 +83,7 @@ fn ut_search_user() {
         state: State::Any.repr,
         action: Action::Any.repr,
         mode: Mode::Any.repr,
+        client_tag: String::new(),
     };
     let res = db.search_task(filter, uid);
     assert_eq!(res, vec![task_id as u32]);
@@ -89,6 +94,7 @@ fn ut_search_user() {
         state: State::Any.repr,
         action: Action::Upload.repr,
         mode: Mode::BackGround.repr,
+        client_tag: String::new(),
     };
     let res = db.search_task(filter, uid);
     assert_eq!(res, vec![task_id as u32]);
@@ -117,6 +123,7 @@ fn ut_search_system() {
         state: State::Completed.repr,
         action: Action::Any.repr,
         mode: Mode::Any.repr,
+        client_tag: String::new(),
     };
     let res = db.system_search_task(filter, bundle_name.to_string());
     assert_eq!(res, vec![]);
@@ -127,6 +134,7 @@ fn ut_search_system() {
         state: State::Any.repr,
         action: Action::Any.repr,
         mode: Mode::Any.repr,
+        client_tag: String::new(),
     };
     let res = db.system_search_task(filter, bundle_name.to_string());
     assert_eq!(res, vec![task_id as u32]);
@@ -137,7 +145,135 @@ fn ut_search_system() {
         state: State::Any.repr,
         action: Action::Download.repr,
         mode: Mode::BackGround.repr,
+        client_tag: String::new(),
     };
     let res = db.system_search_task(filter, "*".to_string());
     assert_eq!(res, vec![task_id as u32]);
-}
\ No newline at end of file
+}
+fn window(after: i64, before: i64, state: u8) -> TaskFilter {
+    TaskFilter {
+        before,
+        after,
+        state,
+        action: Action::Any.repr,
+        mode: Mode::Any.repr,
+        client_tag: String::new(),
+    }
+}
+
+fn insert_at(db: &RequestDb, uid: u64, ctime: i64, state: State) -> u32 {
+    let task_id = TaskIdGenerator::generate();
+    db.execute(&format!(
+        "INSERT INTO request_task (task_id, uid, state, ctime, action, mode) VALUES ({}, {}, {}, {}, {}, {})",
+        task_id,
+        uid,
+        state.repr,
+        ctime,
+        Action::Download.repr,
+        Mode::BackGround.repr
+    ))
+    .unwrap();
+    task_id
+}
+
+// @tc.name: ut_search_time_bounds
+// @tc.desc: Test the creation time window includes tasks exactly at its bounds
+// @tc.precon: NA
+// @tc.step: 1. Insert tasks before, at and after both bounds of a window
+//           2. Search the window
+// @tc.expect: Tasks at or inside the bounds are returned newest first
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_search_time_bounds() {
+    test_init();
+    let _lock = lock_database();
+    let db = RequestDb::get_instance();
+    let uid = get_current_timestamp();
+
+    let _early = insert_at(db, uid, 999, State::Completed);
+    let first = insert_at(db, uid, 1000, State::Completed);
+    let middle = insert_at(db, uid, 1500, State::Completed);
+    let last = insert_at(db, uid, 2000, State::Completed);
+    let _late = insert_at(db, uid, 2001, State::Completed);
+
+    let res = db.search_task(window(1000, 2000, State::Any.repr), uid);
+    assert_eq!(res, vec![last, middle, first]);
+
+    let res = db.search_task(window(2000, 3000, State::Any.repr), uid);
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1], last);
+}
+
+// @tc.name: ut_search_time_and_state
+// @tc.desc: Test the creation time window combines with the state filter
+// @tc.precon: NA
+// @tc.step: 1. Insert tasks in different states inside and outside a window
+//           2. Search the window for one state, then for any state
+// @tc.expect: Only tasks in both the window and the state are returned,
+// newest first, and any state leaves tasks pending removal out
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_search_time_and_state() {
+    test_init();
+    let _lock = lock_database();
+    let db = RequestDb::get_instance();
+    let uid = get_current_timestamp();
+
+    let old_failed = insert_at(db, uid, 100, State::Failed);
+    let failed = insert_at(db, uid, 200, State::Failed);
+    let completed = insert_at(db, uid, 300, State::Completed);
+    let newer_failed = insert_at(db, uid, 400, State::Failed);
+    let _pending = insert_at(db, uid, 350, State::PendingRemoval);
+
+    let res = db.search_task(window(200, 400, State::Failed.repr), uid);
+    assert_eq!(res, vec![newer_failed, failed]);
+
+    let res = db.search_task(window(200, 400, State::Any.repr), uid);
+    assert_eq!(res, vec![newer_failed, completed, failed]);
+
+    let res = db.search_task(window(0, 199, State::Failed.repr), uid);
+    assert_eq!(res, vec![old_failed]);
+}
+
+// @tc.name: ut_search_time_index
+// @tc.desc: Test searches by creation time are served by the ctime indexes
+// @tc.precon: NA
+// @tc.step: 1. Explain the query plan of a user and a system search
+// @tc.expect: The plans use `uid_ctime_index` and `ctime_index` without
+// sorting
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[cfg(not(feature = "oh"))]
+#[test]
+fn ut_search_time_index() {
+    let db = RequestDb::get_instance();
+    for (sql, index) in [
+        (
+            "SELECT task_id from request_task WHERE uid = 1 AND ",
+            "uid_ctime_index",
+        ),
+        ("SELECT task_id from request_task WHERE ", "ctime_index"),
+    ] {
+        let mut sql = format!("EXPLAIN QUERY PLAN {}", sql);
+        RequestDb::search_filter(&mut sql, &window(0, 100, State::Any.repr));
+        sql.push_str(NEWEST_FIRST);
+        let mut stmt = db.inner.prepare(&sql).unwrap();
+        let plan: Vec<String> = stmt
+            .query_map([], |row| row.get(3))
+            .unwrap()
+            .map(|detail| detail.unwrap())
+            .collect();
+        assert!(
+            plan.iter().any(|detail| detail.contains(&format!("INDEX {} ", index))),
+            "{:?}",
+            plan
+        );
+        assert!(
+            !plan.iter().any(|detail| detail.contains("TEMP B-TREE")),
+            "{:?}",
+            plan
+        );
+    }
+}
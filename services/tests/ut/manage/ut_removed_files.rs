@@ -0,0 +1,144 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use super::*;
+use crate::config::ConfigBuilder;
+use crate::utils::form_item::FileSpec;
+
+const SIZE: usize = 1024;
+
+/// Creates a download target in `dir` holding `percent` of the task size.
+fn target(dir: &Path, name: &str, percent: usize) -> PathBuf {
+    let path = dir.join(name);
+    fs::write(&path, vec![0u8; SIZE * percent / 100]).unwrap();
+    path
+}
+
+/// Builds a download task writing to `path`.
+fn download(path: &Path, policy: Option<PartialFilePolicy>) -> TaskConfig {
+    let mut config = ConfigBuilder::new().action(Action::Download).build();
+    config.file_specs.push(FileSpec {
+        name: "file".to_string(),
+        path: path.to_str().unwrap().to_string(),
+        file_name: "file".to_string(),
+        mime_type: "".to_string(),
+        is_user_file: false,
+        fd: None,
+        priority: 0,
+    });
+    config.on_remove_partial = policy;
+    config
+}
+
+// @tc.name: ut_removed_files_policy
+// @tc.desc: Test the files of tasks removed at 0%, 50% and 100% under each
+// policy
+// @tc.precon: NA
+// @tc.step: 1. Create download targets holding 0%, 50% and 100% of the task
+//           2. Remove them under Keep, Delete and DeleteIfIncomplete
+// @tc.expect: Keep never deletes, Delete always deletes and
+// DeleteIfIncomplete deletes only unfinished targets
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_removed_files_policy() {
+    let dir = std::env::temp_dir().join("ut_removed_files_policy");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let cases = [
+        (PartialFilePolicy::Keep, [true, true, true]),
+        (PartialFilePolicy::Delete, [false, false, false]),
+        (PartialFilePolicy::DeleteIfIncomplete, [false, false, true]),
+    ];
+    for (policy, kept) in cases {
+        for (percent, kept) in [0, 50, 100].into_iter().zip(kept) {
+            let path = target(&dir, &format!("{:?}_{}", policy, percent), percent);
+            let config = download(&path, Some(policy));
+            let action =
+                RemovedFiles::new(&config, percent == 100, PartialFilePolicy::Keep).apply();
+            assert_eq!(path.exists(), kept, "{:?} at {}%", policy, percent);
+            assert_eq!(action, if kept { "kept" } else { "deleted" });
+        }
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// @tc.name: ut_removed_files_default
+// @tc.desc: Test the default policy applies to tasks setting none
+// @tc.precon: NA
+// @tc.step: 1. Remove unfinished targets without a policy under the Keep and
+//              DeleteIfIncomplete defaults
+// @tc.expect: Only the DeleteIfIncomplete default deletes the target
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_removed_files_default() {
+    let dir = std::env::temp_dir().join("ut_removed_files_default");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let path = target(&dir, "file", 50);
+    let config = download(&path, None);
+    assert_eq!(
+        RemovedFiles::new(&config, false, PartialFilePolicy::Keep).apply(),
+        "kept"
+    );
+    assert!(path.exists());
+    assert_eq!(
+        RemovedFiles::new(&config, false, PartialFilePolicy::DeleteIfIncomplete).apply(),
+        "deleted"
+    );
+    assert!(!path.exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// @tc.name: ut_removed_files_user_file
+// @tc.desc: Test files passed by the application are never deleted
+// @tc.precon: NA
+// @tc.step: 1. Remove a download into a user file and an upload of a
+//              service opened file under the Delete policy
+// @tc.expect: Both files still exist
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_removed_files_user_file() {
+    let dir = std::env::temp_dir().join("ut_removed_files_user_file");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let user = target(&dir, "user", 50);
+    let mut config = ConfigBuilder::new()
+        .action(Action::Download)
+        .file_spec(File::open(&user).unwrap())
+        .build();
+    config.on_remove_partial = Some(PartialFilePolicy::Delete);
+    assert_eq!(
+        RemovedFiles::new(&config, false, PartialFilePolicy::Keep).apply(),
+        "kept"
+    );
+    assert!(user.exists());
+
+    let source = target(&dir, "source", 100);
+    let mut config = download(&source, Some(PartialFilePolicy::Delete));
+    config.common_data.action = Action::Upload;
+    assert_eq!(
+        RemovedFiles::new(&config, false, PartialFilePolicy::Keep).apply(),
+        "kept"
+    );
+    assert!(source.exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,120 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::fixtures::TaskRow;
+use crate::task::reason::Reason;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+fn insert(db: &RequestDb, uid: u64) -> u32 {
+    let task_id = TaskIdGenerator::generate();
+    TaskRow {
+        uid,
+        ..TaskRow::new(task_id)
+    }
+    .insert(db);
+    task_id
+}
+
+fn ids(tasks: &[(u32, u64)]) -> Vec<u32> {
+    let mut ids: Vec<u32> = tasks.iter().map(|(task_id, _)| *task_id).collect();
+    ids.sort();
+    ids
+}
+
+// @tc.name: ut_revision_changed_since
+// @tc.desc: Test only the tasks changed after a cursor are reported
+// @tc.precon: NA
+// @tc.step: 1. Create four tasks and take the current revision as cursor
+//           2. Update one task, remove one and delete one
+//           3. Query the changes since the cursor and since the new cursor
+// @tc.expect: The updated task is changed, the removed and deleted tasks are
+//             removed with revisions after the cursor, the untouched task is
+//             not reported and nothing changed since the new cursor
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_revision_changed_since() {
+    let db = RequestDb::get_instance();
+    db.init_revisions();
+    let uid = TaskIdGenerator::generate() as u64;
+    let untouched = insert(db, uid);
+    let updated = insert(db, uid);
+    let removed = insert(db, uid);
+    let deleted = insert(db, uid);
+
+    let all = db.changed_since(uid, 0);
+    assert_eq!(ids(&all.changed), {
+        let mut ids = vec![untouched, updated, removed, deleted];
+        ids.sort();
+        ids
+    });
+    assert!(all.removed.is_empty());
+
+    let cursor = db.max_revision();
+    db.update_task_state(updated, State::Running, Reason::Default);
+    db.update_task_state(removed, State::Removed, Reason::Default);
+    db.execute(&format!(
+        "DELETE FROM request_task WHERE task_id = {}",
+        deleted
+    ))
+    .unwrap();
+
+    let set = db.changed_since(uid, cursor);
+    assert_eq!(ids(&set.changed), vec![updated]);
+    assert_eq!(ids(&set.removed), {
+        let mut ids = vec![removed, deleted];
+        ids.sort();
+        ids
+    });
+    for (_, revision) in set.changed.iter().chain(set.removed.iter()) {
+        assert!(*revision > cursor);
+        assert!(*revision <= set.revision);
+    }
+
+    let set = db.changed_since(uid, set.revision);
+    assert!(set.changed.is_empty());
+    assert!(set.removed.is_empty());
+}
+
+// @tc.name: ut_revision_prune_tombstones
+// @tc.desc: Test expired tombstones are pruned and older cursors resync
+// @tc.precon: NA
+// @tc.step: 1. Record a tombstone deleted long ago
+//           2. Prune the tombstones older than a second after it
+//           3. Query with cursors before and after the pruned revision
+// @tc.expect: The tombstone is pruned, the older cursor has to resync and
+//             the current one does not
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_revision_prune_tombstones() {
+    let db = RequestDb::get_instance();
+    db.init_revisions();
+    let uid = TaskIdGenerator::generate() as u64;
+    let task_id = insert(db, uid);
+    let revision = db.max_revision();
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO task_tombstone (task_id, uid, revision, dtime) VALUES ({}, {}, {}, 1)",
+        task_id, uid, revision
+    ))
+    .unwrap();
+
+    assert_eq!(db.prune_tombstones(1000), 1);
+    assert_eq!(db.prune_tombstones(1000), 0);
+
+    let set = db.changed_since(uid, revision - 1);
+    assert!(set.resync);
+    assert!(set.removed.is_empty());
+    assert!(!db.changed_since(uid, db.max_revision()).resync);
+}
@@ -0,0 +1,147 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::fixtures::TaskRow;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+fn insert(db: &RequestDb, uid: u64, state: State, count: usize) {
+    for _ in 0..count {
+        TaskRow {
+            uid,
+            state,
+            ..TaskRow::new(TaskIdGenerator::generate())
+        }
+        .insert(db);
+    }
+}
+
+/// Returns a uid followed by three unused ones, wider than 32 bits.
+fn unique_uid() -> u64 {
+    (TaskIdGenerator::generate() as u64) << 30
+}
+
+// @tc.name: ut_state_counts_aggregate
+// @tc.desc: Test tasks are counted per application and state
+// @tc.precon: NA
+// @tc.step: 1. Create tasks of three applications in several states
+//           2. Count the tasks of all applications and of one application
+// @tc.expect: Each application has the counts of its own tasks and the
+// restricted count only holds the requested application
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_state_counts_aggregate() {
+    let db = RequestDb::get_instance();
+    let uid = unique_uid();
+    let (a, b, c) = (uid, uid + 1, uid + 2);
+
+    insert(db, a, State::Running, 2);
+    insert(db, a, State::Waiting, 3);
+    insert(db, a, State::Completed, 5);
+    insert(db, b, State::Paused, 1);
+    insert(db, b, State::Failed, 4);
+    insert(db, c, State::Initialized, 1);
+    insert(db, c, State::Removed, 2);
+    insert(db, c, State::Any, 1);
+
+    let all = db.count_by_state(None);
+    assert_eq!(
+        all[&a],
+        StateCounts {
+            running: 2,
+            waiting: 3,
+            completed: 5,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        all[&b],
+        StateCounts {
+            paused: 1,
+            failed: 4,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        all[&c],
+        StateCounts {
+            initialized: 1,
+            removed: 2,
+            ..Default::default()
+        }
+    );
+
+    let one = db.count_by_state(Some(b));
+    assert_eq!(one.len(), 1);
+    assert_eq!(one[&b], all[&b]);
+    assert!(db.count_by_state(Some(uid + 3)).is_empty());
+}
+
+// @tc.name: ut_state_counts_index
+// @tc.desc: Test the grouped query is served by the uid and state index
+// @tc.precon: NA
+// @tc.step: 1. Explain the query plan of counting all and one application
+// @tc.expect: Both plans use `uid_state_index`
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_state_counts_index() {
+    let db = RequestDb::get_instance();
+    for sql in [
+        "SELECT COUNT(*) FROM request_task GROUP BY uid, state",
+        "SELECT COUNT(*) FROM request_task WHERE uid = 1 GROUP BY uid, state",
+    ] {
+        let mut stmt = db
+            .inner
+            .prepare(&format!("EXPLAIN QUERY PLAN {}", sql))
+            .unwrap();
+        let plan: Vec<String> = stmt
+            .query_map([], |row| row.get(3))
+            .unwrap()
+            .map(|detail| detail.unwrap())
+            .collect();
+        assert!(
+            plan.iter().any(|detail| detail.contains("uid_state_index")),
+            "{:?}",
+            plan
+        );
+    }
+}
+
+// @tc.name: ut_state_counts_scope
+// @tc.desc: Test non-system callers may only count their own tasks
+// @tc.precon: NA
+// @tc.step: 1. Decide the scope for system and non-system callers asking
+//              for all, their own and another application
+// @tc.expect: Non-system callers are restricted to their own uid, system
+// callers may count any application or all
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_state_counts_scope() {
+    assert_eq!(count_scope(false, None, 100, false), Ok(Some(100)));
+    assert_eq!(count_scope(false, Some(100), 100, false), Ok(Some(100)));
+    assert_eq!(
+        count_scope(false, Some(101), 100, false),
+        Err(ErrorCode::Permission)
+    );
+    assert_eq!(
+        count_scope(true, None, 100, false),
+        Err(ErrorCode::Permission)
+    );
+
+    assert_eq!(count_scope(false, None, 100, true), Ok(Some(100)));
+    assert_eq!(count_scope(false, Some(101), 100, true), Ok(Some(101)));
+    assert_eq!(count_scope(true, None, 100, true), Ok(None));
+}
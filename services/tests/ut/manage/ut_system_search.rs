@@ -0,0 +1,183 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::config::ConfigBuilder;
+use crate::fixtures::TaskRow;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+fn insert(db: &RequestDb, uid: u64, ctime: u64, state: State) -> u32 {
+    let task_id = TaskIdGenerator::generate();
+    TaskRow {
+        uid,
+        state,
+        ctime,
+        ..TaskRow::new(task_id)
+    }
+    .insert(db);
+    db.execute(&format!(
+        "UPDATE request_task SET url = 'http://example.com', title = '', description = '', method = 'GET', data = '', token = 'null', version = 1 WHERE task_id = {}",
+        task_id,
+    ))
+    .unwrap();
+    task_id
+}
+
+fn filter(uid: u64, offset: u32, limit: u32) -> SystemSearchFilter {
+    SystemSearchFilter {
+        uid_min: uid,
+        uid_max: uid,
+        offset,
+        limit,
+        ..Default::default()
+    }
+}
+
+fn ids(summaries: &[TaskSummary]) -> Vec<u32> {
+    summaries.iter().map(|summary| summary.task_id).collect()
+}
+
+// @tc.name: ut_system_search_access
+// @tc.desc: Test only permitted system callers may search with a valid page
+// @tc.precon: NA
+// @tc.step: 1. Check the access of callers with and without system API and
+//              permission
+//           2. Check empty, largest and too large pages
+// @tc.expect: Non-system callers get SystemApi, callers without permission
+//             get Permission and invalid pages get ParameterCheck
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_system_search_access() {
+    assert_eq!(search_access(false, true, 10), Err(ErrorCode::SystemApi));
+    assert_eq!(search_access(false, false, 10), Err(ErrorCode::SystemApi));
+    assert_eq!(search_access(true, false, 10), Err(ErrorCode::Permission));
+    assert_eq!(search_access(true, true, 10), Ok(()));
+    assert_eq!(search_access(true, true, SYSTEM_SEARCH_PAGE_MAX), Ok(()));
+    assert_eq!(search_access(true, true, 0), Err(ErrorCode::ParameterCheck));
+    assert_eq!(
+        search_access(true, true, SYSTEM_SEARCH_PAGE_MAX + 1),
+        Err(ErrorCode::ParameterCheck)
+    );
+}
+
+// @tc.name: ut_system_search_redaction
+// @tc.desc: Test summaries do not contain header values and tokens
+// @tc.precon: NA
+// @tc.step: 1. Build a config with an authorization header and a token
+//           2. Summarize it and a config without token
+// @tc.expect: Header names are kept with redacted values, only whether a
+//             token is set is reported and no secret is in the summary
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_system_search_redaction() {
+    let mut config = ConfigBuilder::new()
+        .url("http://example.com/file")
+        .bundle_name("com.example.app")
+        .uid(20)
+        .build();
+    config
+        .headers
+        .insert("Authorization".to_string(), "Bearer secret".to_string());
+    config.token = "secret-token".to_string();
+
+    let summary = TaskSummary::new(&config, State::Waiting.repr, 7);
+    assert_eq!(summary.uid, 20);
+    assert_eq!(summary.bundle, "com.example.app");
+    assert_eq!(summary.url, "http://example.com/file");
+    assert_eq!(summary.state, State::Waiting.repr);
+    assert_eq!(summary.ctime, 7);
+    assert_eq!(summary.headers.get("Authorization").unwrap(), REDACTED);
+    assert!(summary.has_token);
+    assert!(!format!("{:?}", summary).contains("secret"));
+
+    config.token = "null".to_string();
+    assert!(!TaskSummary::new(&config, State::Waiting.repr, 7).has_token);
+}
+
+// @tc.name: ut_system_search_pagination
+// @tc.desc: Test the search is paginated over a uid range
+// @tc.precon: NA
+// @tc.step: 1. Create five tasks and a removed task of a unique uid
+//           2. Search pages of two tasks
+//           3. Search removed tasks and a too large page
+// @tc.expect: Pages are disjoint in creation order, removed tasks are only
+//             found when asked for and the page size is capped
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_system_search_pagination() {
+    let db = RequestDb::get_instance();
+    let uid = TaskIdGenerator::generate() as u64;
+    let tasks: Vec<u32> = (1..=5)
+        .map(|ctime| insert(db, uid, ctime, State::Waiting))
+        .collect();
+    let removed = insert(db, uid, 6, State::Removed);
+
+    let mut pages = Vec::new();
+    for offset in [0, 2, 4, 6] {
+        pages.push(ids(&db.system_search(&filter(uid, offset, 2))));
+    }
+    assert_eq!(pages[0], tasks[0..2]);
+    assert_eq!(pages[1], tasks[2..4]);
+    assert_eq!(pages[2], tasks[4..5]);
+    assert!(pages[3].is_empty());
+
+    let mut removed_filter = filter(uid, 0, 2);
+    removed_filter.states = vec![State::Removed.repr];
+    assert_eq!(ids(&db.system_search(&removed_filter)), vec![removed]);
+
+    let mut window = filter(uid, 0, 2);
+    window.after = 2;
+    window.before = 3;
+    assert_eq!(ids(&db.system_search(&window)), tasks[1..3]);
+
+    let all = db.system_search(&filter(uid, 0, SYSTEM_SEARCH_PAGE_MAX * 2));
+    assert_eq!(ids(&all), tasks);
+    assert_eq!(all[0].ctime, 1);
+    assert_eq!(all[0].state, State::Waiting.repr);
+}
+
+// @tc.name: ut_system_search_audit
+// @tc.desc: Test every search invocation is audited with its caller
+// @tc.precon: NA
+// @tc.step: 1. Audit a denied and a granted search of a unique caller
+//           2. Audit more searches than records are kept
+// @tc.expect: Both records hold the caller, token, result and count, and
+//             the number of kept records is bounded
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_system_search_audit() {
+    let uid = TaskIdGenerator::generate() as u64;
+    let search = filter(0, 0, 10);
+    audit(uid, 11, &search, ErrorCode::Permission, 0);
+    audit(uid, 11, &search, ErrorCode::ErrOk, 3);
+
+    let records: Vec<SearchAudit> = recent_audits()
+        .into_iter()
+        .filter(|record| record.calling_uid == uid)
+        .collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].code, ErrorCode::Permission);
+    assert_eq!(records[1].code, ErrorCode::ErrOk);
+    assert_eq!(records[1].count, 3);
+    assert_eq!(records[1].calling_token_id, 11);
+    assert_eq!(records[1].filter, search);
+
+    for _ in 0..AUDIT_CAPACITY + 1 {
+        audit(uid, 11, &search, ErrorCode::ErrOk, 0);
+    }
+    assert_eq!(recent_audits().len(), AUDIT_CAPACITY);
+}
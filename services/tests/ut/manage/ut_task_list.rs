@@ -0,0 +1,234 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::fixtures::TaskRow;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+const PID: u64 = 1;
+
+fn filter_all() -> TaskFilter {
+    TaskFilter {
+        before: i64::MAX,
+        after: 0,
+        state: State::Any.repr,
+        action: Action::Any.repr,
+        mode: Mode::Any.repr,
+        client_tag: String::new(),
+    }
+}
+
+/// Returns a uid no other test owns tasks of.
+fn unique_uid() -> u64 {
+    (1 << 40) + TaskIdGenerator::generate() as u64
+}
+
+fn insert(db: &RequestDb, uid: u64, action: Action, state: State) -> u32 {
+    let task_id = TaskIdGenerator::generate();
+    TaskRow {
+        uid,
+        action,
+        mode: Mode::BackGround,
+        state,
+        ..TaskRow::new(task_id)
+    }
+    .insert(db);
+    task_id
+}
+
+fn set_state(db: &RequestDb, task_id: u32, state: State) {
+    db.execute(&format!(
+        "UPDATE request_task SET state = {} WHERE task_id = {}",
+        state.repr, task_id
+    ))
+    .unwrap();
+}
+
+// @tc.name: ut_task_list_delta
+// @tc.desc: Test deltas follow creation, state changes and removal of tasks
+// @tc.precon: NA
+// @tc.step: 1. Subscribe and create two tasks plus a task of another uid
+//           2. Flush, start one task and flush again
+//           3. Remove one task and flush again
+// @tc.expect: The first delta adds both tasks of the uid, later deltas only
+// carry the state change and the removal
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_list_delta() {
+    let db = RequestDb::get_instance();
+    let uid = unique_uid();
+    let mut watcher = TaskListWatcher::new();
+    watcher.subscribe(uid, PID, 1, filter_all());
+
+    let download = insert(db, uid, Action::Download, State::Initialized);
+    let upload = insert(db, uid, Action::Upload, State::Initialized);
+    insert(db, unique_uid(), Action::Download, State::Initialized);
+
+    let now = Instant::now();
+    let mut deltas = watcher.flush(now, db);
+    assert_eq!(deltas.len(), 1);
+    let (pid, id, mut delta) = deltas.remove(0);
+    assert_eq!((pid, id), (PID, 1));
+    delta.added.sort_by_key(|brief| brief.task_id);
+    let mut expected = vec![
+        TaskBrief {
+            task_id: download,
+            action: Action::Download,
+            mode: Mode::BackGround,
+            state: State::Initialized,
+        },
+        TaskBrief {
+            task_id: upload,
+            action: Action::Upload,
+            mode: Mode::BackGround,
+            state: State::Initialized,
+        },
+    ];
+    expected.sort_by_key(|brief| brief.task_id);
+    assert_eq!(delta.added, expected);
+    assert!(delta.removed.is_empty());
+    assert!(delta.state_changed.is_empty());
+
+    assert!(watcher.flush(now, db).is_empty());
+
+    set_state(db, download, State::Running);
+    let deltas = watcher.flush(now, db);
+    assert_eq!(deltas.len(), 1);
+    assert_eq!(
+        deltas[0].2,
+        TaskListDelta {
+            state_changed: vec![(download, State::Running)],
+            ..Default::default()
+        }
+    );
+
+    set_state(db, upload, State::Removed);
+    let deltas = watcher.flush(now, db);
+    assert_eq!(deltas.len(), 1);
+    assert_eq!(
+        deltas[0].2,
+        TaskListDelta {
+            removed: vec![upload],
+            ..Default::default()
+        }
+    );
+}
+
+// @tc.name: ut_task_list_filter
+// @tc.desc: Test tasks leaving the filter are reported as removed
+// @tc.precon: NA
+// @tc.step: 1. Subscribe to running tasks only
+//           2. Start a task, flush, then pause it and flush again
+// @tc.expect: The task is added once running and removed once paused
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_list_filter() {
+    let db = RequestDb::get_instance();
+    let uid = unique_uid();
+    let mut watcher = TaskListWatcher::new();
+    let mut filter = filter_all();
+    filter.state = State::Running.repr;
+    watcher.subscribe(uid, PID, 2, filter);
+
+    let task_id = insert(db, uid, Action::Download, State::Waiting);
+    assert!(watcher.flush(Instant::now(), db).is_empty());
+
+    set_state(db, task_id, State::Running);
+    let deltas = watcher.flush(Instant::now(), db);
+    assert_eq!(deltas[0].2.added.len(), 1);
+    assert_eq!(deltas[0].2.added[0].task_id, task_id);
+
+    set_state(db, task_id, State::Paused);
+    let deltas = watcher.flush(Instant::now(), db);
+    assert_eq!(deltas[0].2.removed, vec![task_id]);
+
+    assert!(watcher.unsubscribe(PID, 2));
+    assert!(!watcher.unsubscribe(PID, 2));
+}
+
+// @tc.name: ut_task_list_debounce
+// @tc.desc: Test flushes are scheduled at most once per debounce interval
+// @tc.precon: NA
+// @tc.step: 1. Mark dirty without and with a subscription
+//           2. Mark dirty again before and after flushing
+// @tc.expect: Nothing is scheduled without subscriptions or while a flush is
+// pending, the next flush waits for the rest of the debounce interval
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_list_debounce() {
+    let db = RequestDb::get_instance();
+    let mut watcher = TaskListWatcher::new();
+    let now = Instant::now();
+    assert_eq!(watcher.mark_dirty(now), None);
+
+    watcher.subscribe(unique_uid(), PID, 3, filter_all());
+    assert_eq!(watcher.mark_dirty(now), Some(Duration::ZERO));
+    assert_eq!(watcher.mark_dirty(now), None);
+
+    watcher.flush(now, db);
+    let later = now + Duration::from_millis(100);
+    assert_eq!(
+        watcher.mark_dirty(later),
+        Some(TASK_LIST_DEBOUNCE - Duration::from_millis(100))
+    );
+    assert_eq!(watcher.mark_dirty(later), None);
+
+    watcher.flush(now + TASK_LIST_DEBOUNCE, db);
+    let idle = now + TASK_LIST_DEBOUNCE * 3;
+    assert_eq!(watcher.mark_dirty(idle), Some(Duration::ZERO));
+
+    watcher.remove_process(PID);
+    watcher.flush(idle, db);
+    assert_eq!(watcher.mark_dirty(idle), None);
+}
+
+// @tc.name: ut_task_list_split
+// @tc.desc: Test large deltas are split into parts fitting one message
+// @tc.precon: NA
+// @tc.step: 1. Build a delta larger than one message and split it
+// @tc.expect: Every part fits into a message and no entry is lost
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_list_split() {
+    let delta = TaskListDelta {
+        added: vec![
+            TaskBrief {
+                task_id: 0,
+                action: Action::Download,
+                mode: Mode::BackGround,
+                state: State::Waiting,
+            };
+            300
+        ],
+        removed: (0..1000).collect(),
+        state_changed: vec![(0, State::Running); 10],
+    };
+    let parts = delta.clone().split();
+    assert!(parts.len() > 1);
+    for part in parts.iter() {
+        let size = part.added.len() * 16 + part.removed.len() * 4 + part.state_changed.len() * 8;
+        assert!(size <= DELTA_ENTRIES_MAX_SIZE);
+    }
+    let mut merged = TaskListDelta::default();
+    for part in parts {
+        merged.added.extend(part.added);
+        merged.removed.extend(part.removed);
+        merged.state_changed.extend(part.state_changed);
+    }
+    assert_eq!(merged, delta);
+    assert!(TaskListDelta::default().split().is_empty());
+}
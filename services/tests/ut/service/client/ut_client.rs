@@ -0,0 +1,95 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn headers(count: usize) -> Vec<(String, Vec<Vec<u8>>)> {
+    let mut headers = (0..count - 5)
+        .map(|i| (format!("x-custom-{:03}", i), vec![vec![b'v'; 64]]))
+        .collect::<Vec<_>>();
+    for name in PRIORITY_HEADERS.iter().rev() {
+        headers.push((name.to_string(), vec![b"keep".to_vec()]));
+    }
+    headers
+}
+
+fn lines(encoded: &[u8]) -> Vec<String> {
+    String::from_utf8(encoded.to_vec())
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+// @tc.name: ut_client_encode_headers_truncate
+// @tc.desc: Test oversized header sets are truncated at whole-header boundaries
+// @tc.precon: NA
+// @tc.step: 1. Encode 200 headers, well-known ones last, within the uds limit
+// @tc.expect: Every encoded line is a complete header, well-known headers are
+// kept first and the marker counts the dropped headers
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_encode_headers_truncate() {
+    let budget = HEADERS_MAX_SIZE as usize - 64;
+    let encoded = encode_headers(headers(200), budget, 200);
+    assert!(encoded.len() <= budget);
+    assert_eq!(encoded.last(), Some(&b'\n'));
+
+    let lines = lines(&encoded);
+    for (line, name) in lines.iter().zip(PRIORITY_HEADERS.iter()) {
+        assert_eq!(line, &format!("{}:keep", name));
+    }
+    let kept = &lines[PRIORITY_HEADERS.len()..lines.len() - 1];
+    for (i, line) in kept.iter().enumerate() {
+        assert_eq!(line, &format!("x-custom-{:03}:{}", i, "v".repeat(64)));
+    }
+    let dropped = 200 - PRIORITY_HEADERS.len() - kept.len();
+    assert!(dropped > 0);
+    assert_eq!(
+        lines.last().unwrap(),
+        &format!("{}:{}", HEADERS_TRUNCATED, dropped)
+    );
+}
+
+// @tc.name: ut_client_encode_headers_count
+// @tc.desc: Test headers beyond the count limit are reported as dropped
+// @tc.precon: NA
+// @tc.step: 1. Encode 200 headers with a count limit of 10
+// @tc.expect: The well-known headers and 5 others are kept, the marker reports
+// 190 dropped
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_encode_headers_count() {
+    let lines = lines(&encode_headers(headers(200), usize::MAX, 10));
+    assert_eq!(lines.len(), 11);
+    assert!(lines[..5].iter().all(|line| line.ends_with(":keep")));
+    assert_eq!(lines[10], format!("{}:190", HEADERS_TRUNCATED));
+}
+
+// @tc.name: ut_client_encode_headers_fit
+// @tc.desc: Test header sets within the limits are encoded without a marker
+// @tc.precon: NA
+// @tc.step: 1. Encode 20 headers within the uds limit
+// @tc.expect: All 20 headers are encoded and no marker is appended
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_encode_headers_fit() {
+    let lines = lines(&encode_headers(headers(20), HEADERS_MAX_SIZE as usize, 200));
+    assert_eq!(lines.len(), 20);
+    assert!(lines
+        .iter()
+        .all(|line| !line.starts_with(HEADERS_TRUNCATED)));
+}
@@ -0,0 +1,47 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const PID: u64 = 20020001;
+
+// @tc.name: ut_client_manager_max_channels
+// @tc.desc: Test client channels are rejected beyond the maximum
+// @tc.precon: NA
+// @tc.step: 1. Open channels up to the maximum and query the count
+//           2. Open a channel for another process, then again for an open one
+//           3. Terminate a process and open the rejected channel again
+// @tc.expect: Channels beyond the maximum fail with
+//             `ErrorCode::TooManyChannels`, open channels are still returned,
+//             and a terminated process frees its channel
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_manager_max_channels() {
+    let entry = ClientManager::init_with_max_channels(2);
+    assert_eq!(entry.channels(), (0, 2));
+
+    assert!(entry.open_channel(PID).is_ok());
+    assert!(entry.open_channel(PID + 1).is_ok());
+    assert_eq!(entry.channels(), (2, 2));
+
+    let rejected = entry.open_channel(PID + 2);
+    assert_eq!(rejected.err(), Some(ErrorCode::TooManyChannels));
+    assert!(entry.open_channel(PID).is_ok());
+    assert_eq!(entry.channels(), (2, 2));
+
+    assert_eq!(entry.notify_process_terminate(PID), ErrorCode::ErrOk);
+    assert_eq!(entry.channels(), (1, 2));
+    assert!(entry.open_channel(PID + 2).is_ok());
+    assert_eq!(entry.channels(), (2, 2));
+}
@@ -0,0 +1,134 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::config::{Action, Version};
+use crate::task::notify::{NotifyData, Progress};
+use crate::task::reason::Reason;
+
+const PID: u64 = 20010001;
+
+fn notify(subscribe_type: SubscribeType, task_id: u32) -> ClientEvent {
+    let data = NotifyData {
+        bundle: String::new(),
+        progress: Progress::new(vec![]),
+        action: Action::Download,
+        version: Version::API10,
+        each_file_status: vec![],
+        task_id,
+        uid: PID,
+        response_body: None,
+        eta_ms: None,
+        client_tag: String::new(),
+    };
+    ClientEvent::SendNotifyData(subscribe_type, data)
+}
+
+fn summary(events: VecDeque<ClientEvent>) -> Vec<(SubscribeType, u32)> {
+    events
+        .into_iter()
+        .map(|event| match event {
+            ClientEvent::SendNotifyData(subscribe_type, data) => (subscribe_type, data.task_id),
+            ClientEvent::SendFaults(task_id, subscribe_type, _) => (subscribe_type, task_id),
+            event => panic!("unexpected event {:?}", event),
+        })
+        .collect()
+}
+
+// @tc.name: ut_client_queue_drop_oldest_progress
+// @tc.desc: Test a full client queue drops its oldest progress events first
+// @tc.precon: NA
+// @tc.step: 1. Fill the queue of a client which never drains it with
+//              progress events and a state change
+//           2. Queue more progress events
+// @tc.expect: The oldest progress events are dropped and counted, the state
+// change keeps its place
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_queue_drop_oldest_progress() {
+    let (queue, _rx) = ClientQueue::new(PID, 4);
+    queue.push(notify(SubscribeType::Progress, 1)).unwrap();
+    queue.push(notify(SubscribeType::Pause, 1)).unwrap();
+    queue.push(notify(SubscribeType::Progress, 2)).unwrap();
+    queue.push(notify(SubscribeType::Progress, 3)).unwrap();
+    assert_eq!(queue.drops(), 0);
+
+    queue.push(notify(SubscribeType::Progress, 4)).unwrap();
+    queue.push(notify(SubscribeType::Progress, 5)).unwrap();
+    assert_eq!(queue.drops(), 2);
+    assert_eq!(
+        summary(queue.take()),
+        vec![
+            (SubscribeType::Pause, 1),
+            (SubscribeType::Progress, 3),
+            (SubscribeType::Progress, 4),
+            (SubscribeType::Progress, 5),
+        ]
+    );
+}
+
+// @tc.name: ut_client_queue_keep_state_changes
+// @tc.desc: Test a full client queue never drops state changes
+// @tc.precon: NA
+// @tc.step: 1. Fill the queue of a client which never drains it with state
+//              changes
+//           2. Queue a progress event, a completion and a fault
+// @tc.expect: The progress event is dropped and counted, the completion and
+// the fault are queued beyond the capacity
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_queue_keep_state_changes() {
+    let (queue, _rx) = ClientQueue::new(PID, 2);
+    queue.push(notify(SubscribeType::Pause, 1)).unwrap();
+    queue.push(notify(SubscribeType::Resume, 1)).unwrap();
+
+    queue.push(notify(SubscribeType::Progress, 1)).unwrap();
+    assert_eq!(queue.drops(), 1);
+    queue.push(notify(SubscribeType::Complete, 1)).unwrap();
+    queue
+        .push(ClientEvent::SendFaults(
+            2,
+            SubscribeType::FaultOccur,
+            Reason::NetworkOffline,
+        ))
+        .unwrap();
+    assert_eq!(queue.drops(), 1);
+    assert_eq!(
+        summary(queue.take()),
+        vec![
+            (SubscribeType::Pause, 1),
+            (SubscribeType::Resume, 1),
+            (SubscribeType::Complete, 1),
+            (SubscribeType::FaultOccur, 2),
+        ]
+    );
+}
+
+// @tc.name: ut_client_queue_closed
+// @tc.desc: Test events can not be queued to a client which shut down
+// @tc.precon: NA
+// @tc.step: 1. Queue an event, then close the queue
+//           2. Queue another event
+// @tc.expect: The queued event is discarded and queuing fails without panic
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_queue_closed() {
+    let (queue, _rx) = ClientQueue::new(PID, CLIENT_QUEUE_CAPACITY);
+    queue.push(notify(SubscribeType::Progress, 1)).unwrap();
+    queue.close();
+    assert!(queue.push(notify(SubscribeType::Complete, 1)).is_err());
+    assert!(queue.take().is_empty());
+}
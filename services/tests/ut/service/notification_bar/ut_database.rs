@@ -160,6 +160,53 @@ fn ut_notify_database_group_config() {
     assert!(!db.is_gauge(group_id));
 }
 
+// @tc.name: ut_notify_database_group_progress_style
+// @tc.desc: Test the progress style of a group is persisted
+// @tc.precon: NA
+// @tc.step: 1. Create a group config without a progress style
+//           2. Record the AutoSwitch style for the group
+// @tc.expect: The group is determinate until a style is recorded and keeps the
+// recorded style afterwards
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_notify_database_group_progress_style() {
+    let db = NotificationDb::new();
+    let group_id = fast_random() as u32;
+
+    db.update_group_config(group_id, true, 0, false, 0b01);
+    assert_eq!(
+        db.query_group_progress_style(group_id),
+        GroupProgressStyle::Determinate
+    );
+    db.update_group_progress_style(group_id, GroupProgressStyle::AutoSwitch);
+    assert_eq!(
+        db.query_group_progress_style(group_id),
+        GroupProgressStyle::AutoSwitch
+    );
+}
+
+// @tc.name: ut_notify_database_group_owner
+// @tc.desc: Test the application creating a group is recorded
+// @tc.precon: NA
+// @tc.step: 1. Create a group config without an owner
+//           2. Record the owner of the group
+// @tc.expect: The group has no owner before it is recorded and the recorded
+// uid afterwards, unknown groups have no owner
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_notify_database_group_owner() {
+    let db = NotificationDb::new();
+    let group_id = fast_random() as u32;
+
+    assert_eq!(db.query_group_owner(group_id), None);
+    db.update_group_config(group_id, true, 0, true, 0b01);
+    assert_eq!(db.query_group_owner(group_id), None);
+    db.update_group_owner(group_id, 20020);
+    assert_eq!(db.query_group_owner(group_id), Some(20020));
+}
+
 // @tc.name: ut_clear_task_info
 // @tc.desc: Test clearing task notification information
 // @tc.precon: NA
@@ -0,0 +1,108 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::service::notification_bar::ffi::ProgressCircle;
+
+fn content(request_id: u32, text: &str) -> NotifyContent {
+    NotifyContent {
+        title: String::from("title"),
+        text: text.to_string(),
+        want_agent: String::new(),
+        request_id,
+        uid: 0,
+        live_view: false,
+        progress_circle: ProgressCircle::close(),
+        x_mark: false,
+        attention_button: false,
+        attended: false,
+    }
+}
+
+// @tc.name: ut_deferred_flush_terminal
+// @tc.desc: Test terminal notifications are delivered once the service is up
+// @tc.precon: NA
+// @tc.step: 1. Publish progress and terminal notifications through a stub
+//              failing the first 4 calls
+//           2. Flush until the queue is empty
+// @tc.expect: Both terminal notifications are delivered in order, the stale
+//             progress ones are dropped and the counters match
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_deferred_flush_terminal() {
+    let mut deferred = DeferredNotify::new();
+    let mut calls = 0;
+    let mut delivered = vec![];
+    let mut publish = |content: &NotifyContent| {
+        calls += 1;
+        if calls <= 4 {
+            return false;
+        }
+        delivered.push(content.text.clone());
+        true
+    };
+
+    deferred.publish(content(1, "progress 1"), false, &mut publish);
+    deferred.publish(content(1, "progress 2"), false, &mut publish);
+    deferred.publish(content(1, "done 1"), true, &mut publish);
+    deferred.publish(content(2, "progress 3"), false, &mut publish);
+    deferred.publish(content(2, "done 2"), true, &mut publish);
+    assert!(!deferred.is_empty());
+
+    let mut retries = 0;
+    while !deferred.flush(&mut publish) {
+        retries += 1;
+    }
+    assert_eq!(retries, 3);
+    assert!(deferred.is_empty());
+    assert_eq!(delivered, vec!["done 1", "done 2"]);
+
+    let stats = deferred.stats();
+    assert_eq!(stats.deferred.load(Ordering::Relaxed), 5);
+    assert_eq!(stats.flushed.load(Ordering::Relaxed), 2);
+    assert_eq!(stats.dropped.load(Ordering::Relaxed), 3);
+}
+
+// @tc.name: ut_deferred_bounded
+// @tc.desc: Test the queue stays bounded and keeps terminal notifications
+// @tc.precon: NA
+// @tc.step: 1. Queue one progress and MAX_DEFERRED terminal notifications
+//              while the service is unavailable
+//           2. Flush with the service available
+// @tc.expect: The progress notification is evicted first, the oldest terminal
+//             one next, and the backoff doubles until the flush succeeds
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_deferred_bounded() {
+    let mut deferred = DeferredNotify::new();
+    deferred.publish(content(0, "progress"), false, |_| false);
+    for i in 0..MAX_DEFERRED as u32 + 1 {
+        deferred.publish(content(i + 1, "done"), true, |_| false);
+    }
+    assert_eq!(deferred.stats().dropped.load(Ordering::Relaxed), 2);
+
+    let backoff = deferred.backoff();
+    assert!(!deferred.flush(|_| false));
+    assert_eq!(deferred.backoff(), backoff * 2);
+
+    let mut delivered = vec![];
+    assert!(deferred.flush(|content| {
+        delivered.push(content.request_id);
+        true
+    }));
+    assert_eq!(delivered.len(), MAX_DEFERRED);
+    assert_eq!(delivered[0], 2);
+    assert_eq!(deferred.backoff(), backoff);
+}
@@ -69,6 +69,46 @@ fn ut_notify_flow_group() {
     assert_eq!(group_progress.total(), 100);
 }
 
+// @tc.name: ut_notify_flow_group_auto_switch
+// @tc.desc: Test an AutoSwitch group switches to a progress bar exactly once
+// @tc.precon: NA
+// @tc.step: 1. Create a GroupProgress with the AutoSwitch style
+//           2. Run two tasks whose sizes are reported late
+//           3. Build the group notification after every update
+// @tc.expect: The progress bar stays closed until both sizes are known, then
+// stays open with the byte progress of the group
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_notify_flow_group_auto_switch() {
+    let mut group_progress = GroupProgress::new();
+    group_progress.set_style(GroupProgressStyle::AutoSwitch);
+    let mut opened = vec![];
+    let mut record = |group_progress: &GroupProgress| {
+        let content =
+            NotifyContent::group_progress_notify(None, Action::Download, 1, 1, group_progress);
+        opened.push(content.progress_circle.open);
+        content.progress_circle
+    };
+
+    group_progress.update_task_state(0, State::Running);
+    group_progress.update_task_state(1, State::Running);
+    record(&group_progress);
+    group_progress.update_task_progress(0, 10);
+    group_progress.update_task_size(0, Some(100));
+    record(&group_progress);
+    group_progress.update_task_progress(1, 20);
+    record(&group_progress);
+    group_progress.update_task_size(1, Some(200));
+    let circle = record(&group_progress);
+    assert_eq!((circle.current, circle.total), (30, 300));
+    // A later unknown size does not switch back
+    group_progress.update_task_state(2, State::Running);
+    record(&group_progress);
+
+    assert_eq!(opened, vec![false, false, false, true, true]);
+}
+
 // @tc.name: ut_notify_flow_task_progress
 // @tc.desc: Test task progress notification generation
 // @tc.precon: NA
@@ -100,6 +140,7 @@ fn ut_notify_flow_task_progress() {
         multi_upload: None,
         file_name: "test".to_string(),
         version: Version::API10,
+        eta_ms: None,
     };
     let content_default = NotifyContent::task_progress_notify(None, &progress);
     let content = flow
@@ -258,6 +299,7 @@ fn ut_group_visibility_progress() {
         multi_upload: None,
         file_name: "test".to_string(),
         version: Version::API10,
+        eta_ms: None,
     };
     let content = flow.publish_progress_notification(progress.clone());
     assert!(content.is_some());
@@ -328,3 +370,69 @@ fn ut_group_visibility_completion() {
     let content = flow.publish_completed_notify(&info);
     assert!(content.is_none());
 }
+
+// @tc.name: ut_notify_flow_cancel_group
+// @tc.desc: Test updates of a cancelled group are ignored
+// @tc.precon: NA
+// @tc.step: 1. Create a group with two tasks and publish their progress
+//           2. Defer a notification of the group and cancel the group
+//           3. Check progress, completion and unregister events of the tasks
+// @tc.expect: The cached group state and the deferred notification are
+//             dropped, events of the group are ignored and events of other
+//             tasks are not
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_notify_flow_cancel_group() {
+    let (_, rx) = mpsc::unbounded_channel();
+    let db = Arc::new(NotificationDb::new());
+    let mut flow = NotifyFlow::new(rx, db.clone());
+    let group_id = fast_random() as u32;
+    let task_ids = [fast_random() as u32, fast_random() as u32];
+    let other = fast_random() as u32;
+    let uid = fast_random();
+    let current_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    db.update_group_config(group_id, true, current_time, true, 0b11);
+    let progress = |task_id| ProgressNotify {
+        action: Action::Download,
+        task_id,
+        uid,
+        processed: 50,
+        total: Some(100),
+        multi_upload: None,
+        file_name: "test".to_string(),
+        version: Version::API10,
+        eta_ms: None,
+    };
+    for task_id in task_ids {
+        db.update_task_group(task_id, group_id);
+        flow.publish_progress_notification(progress(task_id));
+    }
+    assert!(flow.group_notify_progress.contains_key(&group_id));
+    let mut content = NotifyContent::task_progress_notify(None, &progress(task_ids[0]));
+    content.request_id = group_id;
+    flow.deferred.publish(content, true, |_| false);
+    assert!(!flow.deferred.is_empty());
+
+    flow.cancel_group(group_id);
+    assert!(!flow.group_notify_progress.contains_key(&group_id));
+    assert!(flow.deferred.is_empty());
+    for task_id in task_ids {
+        assert!(flow.is_cancelled(&NotifyInfo::Progress(progress(task_id))));
+        assert!(flow.is_cancelled(&NotifyInfo::Eventual(EventualNotify {
+            action: Action::Download,
+            task_id,
+            processed: 100,
+            uid,
+            file_name: "test".to_string(),
+            is_successful: true,
+        })));
+        assert!(flow.is_cancelled(&NotifyInfo::Unregister(uid, task_id, group_id)));
+    }
+    assert!(flow.is_cancelled(&NotifyInfo::GroupEventual(group_id, uid)));
+    assert!(!flow.is_cancelled(&NotifyInfo::Progress(progress(other))));
+}
@@ -0,0 +1,156 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use super::*;
+use crate::service::notification_bar::ffi::ProgressCircle;
+
+fn content(request_id: u32, text: &str) -> NotifyContent {
+    NotifyContent {
+        title: String::from("title"),
+        text: text.to_string(),
+        want_agent: String::new(),
+        request_id,
+        uid: 0,
+        live_view: false,
+        progress_circle: ProgressCircle::close(),
+        x_mark: false,
+        attention_button: false,
+        attended: false,
+    }
+}
+
+// @tc.name: ut_throttle_rate_limit
+// @tc.desc: Test rapid progress of many tasks stays under the publishing limit
+// @tc.precon: NA
+// @tc.step: 1. Submit progress of 30 requests every 10ms for 3 seconds
+//           2. Submit the final outcome of every request
+//           3. Flush every 100ms until nothing is waiting
+// @tc.expect: No window holds more publishes than the limit, progress of a
+//             request respects the interval and every request ends with its
+//             final outcome
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_throttle_rate_limit() {
+    const REQUESTS: u32 = 30;
+    let mut throttle = PublishThrottle::new(PUBLISH_LIMIT, PUBLISH_WINDOW, 500);
+    let mut published = vec![];
+
+    let mut now = 0;
+    while now < 3000 {
+        for request_id in 0..REQUESTS {
+            let progress = content(request_id, &format!("progress {}", now));
+            throttle.submit(progress, false, now, |content, terminal| {
+                published.push((now, content.request_id, content.text, terminal))
+            });
+        }
+        now += 10;
+    }
+    for request_id in 0..REQUESTS {
+        let done = content(request_id, "done");
+        throttle.submit(done, true, now, |content, terminal| {
+            published.push((now, content.request_id, content.text, terminal))
+        });
+    }
+    while !throttle.is_empty() {
+        now += FLUSH_INTERVAL.as_millis() as u64;
+        throttle.flush(now, |content, terminal| {
+            published.push((now, content.request_id, content.text, terminal))
+        });
+    }
+
+    for (start, ..) in published.iter() {
+        let within = published
+            .iter()
+            .filter(|(time, ..)| *time >= *start && *time < start + PUBLISH_WINDOW)
+            .count();
+        assert!(within <= PUBLISH_LIMIT);
+    }
+    let mut last = HashMap::new();
+    for (time, request_id, text, terminal) in published.iter() {
+        if let Some((previous, false)) = last.insert(*request_id, (*time, *terminal)) {
+            assert!(*terminal || previous + 500 <= *time);
+        }
+        if *terminal {
+            assert_eq!(text, "done");
+        }
+    }
+    assert_eq!(last.len(), REQUESTS as usize);
+    assert!(last.values().all(|(_, terminal)| *terminal));
+    let terminals = published.iter().filter(|(.., terminal)| *terminal).count();
+    assert_eq!(terminals, REQUESTS as usize);
+}
+
+// @tc.name: ut_throttle_coalesce
+// @tc.desc: Test waiting notifications are coalesced per request
+// @tc.precon: NA
+// @tc.step: 1. Exhaust a limit of 1 publish per window
+//           2. Submit two progress updates of one request and a progress
+//              update followed by a final outcome of another
+//           3. Flush once per window
+// @tc.expect: Only the newest progress and the final outcome are published
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_throttle_coalesce() {
+    let mut throttle = PublishThrottle::new(1, 1000, 0);
+    let mut published = vec![];
+    let mut record = |content: NotifyContent, terminal: bool| {
+        published.push((content.request_id, content.text, terminal))
+    };
+
+    throttle.submit(content(1, "first"), false, 0, &mut record);
+    throttle.submit(content(2, "old"), false, 10, &mut record);
+    throttle.submit(content(2, "new"), false, 20, &mut record);
+    throttle.submit(content(3, "progress"), false, 30, &mut record);
+    throttle.submit(content(3, "done"), true, 40, &mut record);
+    throttle.flush(1000, &mut record);
+    throttle.flush(2000, &mut record);
+    assert!(throttle.is_empty());
+
+    assert_eq!(
+        published,
+        vec![
+            (1, String::from("first"), false),
+            (3, String::from("done"), true),
+            (2, String::from("new"), false),
+        ]
+    );
+}
+
+// @tc.name: ut_throttle_discard
+// @tc.desc: Test discarding drops the waiting notifications of a request
+// @tc.precon: NA
+// @tc.step: 1. Exhaust a limit of 1 publish per window
+//           2. Submit notifications of two requests and discard one
+//           3. Flush after the window
+// @tc.expect: Only the notification of the remaining request is published
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_throttle_discard() {
+    let mut throttle = PublishThrottle::new(1, 1000, 0);
+    let mut published = vec![];
+    let mut record = |content: NotifyContent, _: bool| published.push(content.request_id);
+
+    throttle.submit(content(1, "first"), false, 0, &mut record);
+    throttle.submit(content(2, "done"), true, 10, &mut record);
+    throttle.submit(content(3, "done"), true, 20, &mut record);
+    throttle.discard(2);
+    throttle.flush(1000, &mut record);
+    assert!(throttle.is_empty());
+
+    assert_eq!(published, vec![1, 3]);
+}
@@ -105,6 +105,7 @@ fn ut_notify_typology_default_progress() {
         total: Some(10),
         multi_upload: None,
         version: Version::API10,
+        eta_ms: None,
     };
     let content = NotifyContent::task_progress_notify(None, &progress_info);
     assert_eq!(content.title, "下载文件 10.00%");
@@ -112,8 +113,15 @@ fn ut_notify_typology_default_progress() {
     assert_eq!(content.live_view, true);
     assert_eq!(content.progress_circle.open, true);
     assert_eq!(content.x_mark, true);
+    assert_eq!(content.attention_button, true);
     assert_eq!(content.request_id, TASK_ID);
 
+    progress_info.eta_ms = Some(150_000);
+    let content = NotifyContent::task_progress_notify(None, &progress_info);
+    let eta = GetSystemResourceString(ETA_MINUTES).replace("%d", "3");
+    assert_eq!(content.text, format!("{} · {}", EXAMPLE_FILE, eta));
+    progress_info.eta_ms = None;
+
     progress_info.processed = 1001;
     progress_info.total = Some(10000);
     let content = NotifyContent::task_progress_notify(None, &progress_info);
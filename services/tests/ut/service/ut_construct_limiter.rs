@@ -0,0 +1,106 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const APP: u64 = 20010001;
+const OTHER_APP: u64 = 20010002;
+
+// @tc.name: ut_construct_limiter_burst
+// @tc.desc: Test construct requests of one uid are throttled after the burst
+// @tc.precon: NA
+// @tc.step: 1. Construct one task at a time from one uid beyond the burst
+//           2. Construct a task from another uid
+// @tc.expect: The uid is throttled right after the burst and reported once,
+// the other uid is unaffected
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_limiter_burst() {
+    let limiter = ConstructLimiter::new(1, 5);
+    let now = Instant::now();
+    for _ in 0..5 {
+        assert_eq!(limiter.admit_at(APP, 1, now), Admission::Allowed);
+    }
+    assert_eq!(
+        limiter.admit_at(APP, 1, now),
+        Admission::Throttled { report: true }
+    );
+    assert_eq!(
+        limiter.admit_at(APP, 1, now),
+        Admission::Throttled { report: false }
+    );
+    assert_eq!(limiter.admit_at(OTHER_APP, 1, now), Admission::Allowed);
+
+    let states = limiter.states();
+    assert_eq!(states.len(), 2);
+    assert_eq!(states[0].0, APP);
+    assert_eq!(states[0].1.throttled, 2);
+    assert!(states[0].1.throttling);
+    assert_eq!(states[1].0, OTHER_APP);
+    assert!(!states[1].1.throttling);
+}
+
+// @tc.name: ut_construct_limiter_refill
+// @tc.desc: Test throttled uids recover at the sustained rate
+// @tc.precon: NA
+// @tc.step: 1. Exhaust the burst of a uid
+//           2. Construct again after one and after ten seconds
+//           3. Get throttled again after an hour
+// @tc.expect: One task is allowed per second, the bucket never exceeds the
+// burst and the throttle is reported again after an hour
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_limiter_refill() {
+    let limiter = ConstructLimiter::new(1, 5);
+    let now = Instant::now();
+    assert_eq!(limiter.admit_at(APP, 5, now), Admission::Allowed);
+    assert!(matches!(
+        limiter.admit_at(APP, 1, now),
+        Admission::Throttled { .. }
+    ));
+
+    let now = now + Duration::from_secs(1);
+    assert_eq!(limiter.admit_at(APP, 1, now), Admission::Allowed);
+    let now = now + Duration::from_secs(10);
+    assert_eq!(
+        limiter.admit_at(APP, 6, now),
+        Admission::Throttled { report: false }
+    );
+    assert_eq!(limiter.admit_at(APP, 5, now), Admission::Allowed);
+
+    let now = now + REPORT_INTERVAL;
+    assert_eq!(limiter.admit_at(APP, 5, now), Admission::Allowed);
+    assert_eq!(
+        limiter.admit_at(APP, 1, now),
+        Admission::Throttled { report: true }
+    );
+}
+
+// @tc.name: ut_construct_limiter_system_uid
+// @tc.desc: Test system uids are never throttled
+// @tc.precon: NA
+// @tc.step: 1. Construct more tasks than the burst from a system uid
+// @tc.expect: Every request is allowed and no state is kept for the uid
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_construct_limiter_system_uid() {
+    let limiter = ConstructLimiter::new(1, 5);
+    let now = Instant::now();
+    for _ in 0..10 {
+        assert_eq!(limiter.admit_at(1000, 1, now), Admission::Allowed);
+    }
+    assert!(limiter.states().is_empty());
+}
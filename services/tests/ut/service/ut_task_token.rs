@@ -0,0 +1,229 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const TASK_ID: u32 = 123;
+const NOW: u64 = 1_000_000;
+
+// @tc.name: ut_task_token_scope
+// @tc.desc: Test a signed token grants its scope on its task only
+// @tc.precon: NA
+// @tc.step: 1. Issue a query token and a full token for a task
+//           2. Check them for query and full operations on the task and on
+//           another task
+// @tc.expect: The query token only grants query, the full token grants both,
+// neither grants anything on another task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_token_scope() {
+    let tokens = TaskTokens::new(true);
+    let query = tokens.issue_at(TASK_ID, TokenScope::Query, 0, NOW);
+    let full = tokens.issue_at(TASK_ID, TokenScope::Full, 0, NOW);
+
+    let check = |token: &str, task_id, needed| tokens.check_at(token.into(), task_id, needed, NOW);
+    assert_eq!(
+        check(&query, TASK_ID, TokenScope::Query),
+        TokenCheck::Granted
+    );
+    assert_eq!(check(&query, TASK_ID, TokenScope::Full), TokenCheck::Denied);
+    assert_eq!(
+        check(&full, TASK_ID, TokenScope::Query),
+        TokenCheck::Granted
+    );
+    assert_eq!(check(&full, TASK_ID, TokenScope::Full), TokenCheck::Granted);
+    assert_eq!(
+        check(&full, TASK_ID + 1, TokenScope::Query),
+        TokenCheck::Denied
+    );
+}
+
+// @tc.name: ut_task_token_expiry
+// @tc.desc: Test a signed token is rejected once expired
+// @tc.precon: NA
+// @tc.step: 1. Issue tokens with a short, a default and an overlong lifetime
+//           2. Check them before and after their expiry
+// @tc.expect: Tokens are granted until their expiry, the lifetime defaults
+// to DEFAULT_TTL_MS and is capped at MAX_TTL_MS
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_token_expiry() {
+    let tokens = TaskTokens::new(true);
+    let check =
+        |token: &String, now| tokens.check_at(token.clone(), TASK_ID, TokenScope::Query, now);
+
+    let short = tokens.issue_at(TASK_ID, TokenScope::Query, 1000, NOW);
+    assert_eq!(check(&short, NOW + 999), TokenCheck::Granted);
+    assert_eq!(check(&short, NOW + 1000), TokenCheck::Denied);
+
+    let default = tokens.issue_at(TASK_ID, TokenScope::Query, 0, NOW);
+    assert_eq!(
+        check(&default, NOW + DEFAULT_TTL_MS - 1),
+        TokenCheck::Granted
+    );
+    assert_eq!(check(&default, NOW + DEFAULT_TTL_MS), TokenCheck::Denied);
+
+    let capped = tokens.issue_at(TASK_ID, TokenScope::Query, u64::MAX, NOW);
+    assert_eq!(check(&capped, NOW + MAX_TTL_MS), TokenCheck::Denied);
+}
+
+// @tc.name: ut_task_token_rotation_grace
+// @tc.desc: Test tokens of the previous key are accepted for the grace period
+// @tc.precon: NA
+// @tc.step: 1. Issue a token, rotate the key and issue another token
+//           2. Check both within and after the grace period
+//           3. Rotate again
+// @tc.expect: The old token is granted until ROTATION_GRACE_MS after the
+// rotation, the new one afterwards too, and the old one never again after
+// the second rotation
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_token_rotation_grace() {
+    let tokens = TaskTokens::new(true);
+    let ttl = MAX_TTL_MS;
+    let old = tokens.issue_at(TASK_ID, TokenScope::Query, ttl, NOW);
+    tokens.rotate_at(NOW);
+    let new = tokens.issue_at(TASK_ID, TokenScope::Query, ttl, NOW);
+    let check =
+        |token: &String, now| tokens.check_at(token.clone(), TASK_ID, TokenScope::Query, now);
+
+    let end = NOW + ROTATION_GRACE_MS;
+    assert_eq!(check(&old, end - 1), TokenCheck::Granted);
+    assert_eq!(check(&old, end), TokenCheck::Denied);
+    assert_eq!(check(&new, end), TokenCheck::Granted);
+
+    tokens.rotate_at(NOW + 1);
+    assert_eq!(check(&old, NOW + 1), TokenCheck::Denied);
+    assert_eq!(check(&new, NOW + 1), TokenCheck::Granted);
+}
+
+// @tc.name: ut_task_token_restore
+// @tc.desc: Test the key is rotated and stored when the service starts
+// @tc.precon: NA
+// @tc.step: 1. Restore the tokens twice from the same directory
+//           2. Check a token issued before the second restore
+// @tc.expect: The token is granted through the grace period of the stored
+// key and tokens are signed by a new key after the restore
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_token_restore() {
+    let dir = Path::new("test_files/ut_task_token_restore");
+    fs::create_dir_all(dir).unwrap();
+    let _ = fs::remove_file(dir.join(KEY_FILE));
+
+    let first = TaskTokens::restore(dir, true, NOW);
+    let token = first.issue_at(TASK_ID, TokenScope::Full, 0, NOW);
+    let second = TaskTokens::restore(dir, true, NOW);
+    let check = second.check_at(token.clone(), TASK_ID, TokenScope::Full, NOW);
+    assert_eq!(check, TokenCheck::Granted);
+    assert_ne!(second.issue_at(TASK_ID, TokenScope::Full, 0, NOW), token);
+}
+
+// @tc.name: ut_task_token_tampered
+// @tc.desc: Test a signed token altered in any field is rejected
+// @tc.precon: NA
+// @tc.step: 1. Issue a query token
+//           2. Change its task id, scope, expiry, key id or mac
+//           3. Check a token signed by another service
+// @tc.expect: Every altered or foreign token is denied
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_token_tampered() {
+    let tokens = TaskTokens::new(true);
+    let token = tokens.issue_at(TASK_ID, TokenScope::Query, 1000, NOW);
+    let fields = token.split('.').collect::<Vec<_>>();
+    for (index, value) in [
+        (1, "124"),
+        (2, "1"),
+        (3, "99999999999"),
+        (4, "7"),
+        (5, "00"),
+    ] {
+        let mut altered = fields.clone();
+        altered[index] = value;
+        let altered = altered.join(".");
+        let check = tokens.check_at(altered, TASK_ID, TokenScope::Query, NOW);
+        assert_eq!(check, TokenCheck::Denied);
+    }
+    let check = tokens.check_at(format!("{}.", token), TASK_ID, TokenScope::Query, NOW);
+    assert_eq!(check, TokenCheck::Denied);
+
+    let foreign = TaskTokens::new(true).issue_at(TASK_ID, TokenScope::Query, 1000, NOW);
+    let check = tokens.check_at(foreign, TASK_ID, TokenScope::Query, NOW);
+    assert_eq!(check, TokenCheck::Denied);
+}
+
+// @tc.name: ut_task_token_legacy
+// @tc.desc: Test application chosen tokens behind the migration flag
+// @tc.precon: NA
+// @tc.step: 1. Check application chosen tokens with legacy tokens enabled,
+//           one of them starting like a signed token
+//           2. Check one with legacy tokens disabled
+// @tc.expect: The token is handed back for comparison when enabled and
+// denied when disabled
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_token_legacy() {
+    let enabled = TaskTokens::new(true);
+    let check = enabled.check_at("my_token".into(), TASK_ID, TokenScope::Full, NOW);
+    assert_eq!(check, TokenCheck::Legacy("my_token".into()));
+    let check = enabled.check_at("rt1abc".into(), TASK_ID, TokenScope::Full, NOW);
+    assert_eq!(check, TokenCheck::Legacy("rt1abc".into()));
+
+    let disabled = TaskTokens::new(false);
+    let check = disabled.check_at("my_token".into(), TASK_ID, TokenScope::Full, NOW);
+    assert_eq!(check, TokenCheck::Denied);
+}
+
+// @tc.name: ut_task_token_no_key
+// @tc.desc: Test no token is issued or granted without a signing key
+// @tc.precon: NA
+// @tc.step: 1. Issue a token, then drop the signing key
+//           2. Issue another token and check the first one
+// @tc.expect: No token is issued and the first token is denied
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_token_no_key() {
+    let tokens = TaskTokens::new(false);
+    let token = tokens.issue_at(TASK_ID, TokenScope::Full, 0, NOW);
+    tokens.keys.lock().unwrap().current = None;
+
+    assert!(tokens
+        .issue_at(TASK_ID, TokenScope::Full, 0, NOW)
+        .is_empty());
+    assert_eq!(
+        tokens.check_at(token, TASK_ID, TokenScope::Query, NOW),
+        TokenCheck::Denied
+    );
+}
+
+// @tc.name: ut_task_token_constant_time_eq
+// @tc.desc: Test byte string comparison used for MAC verification
+// @tc.precon: NA
+// @tc.step: 1. Compare equal, differing and different-length inputs
+// @tc.expect: Only equal inputs compare equal
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_task_token_constant_time_eq() {
+    assert!(constant_time_eq(b"abc", b"abc"));
+    assert!(!constant_time_eq(b"abc", b"abd"));
+    assert!(!constant_time_eq(b"abc", b"ab"));
+}
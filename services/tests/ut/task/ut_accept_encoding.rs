@@ -0,0 +1,67 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+// @tc.name: ut_accept_encoding_supported
+// @tc.desc: Test only supported codings are accepted
+// @tc.precon: NA
+// @tc.step: 1. Check empty, single, weighted and listed codings
+//           2. Check unknown codings and malformed weights
+// @tc.expect: Supported codings with valid weights are accepted, the others
+//             are rejected
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_accept_encoding_supported() {
+    assert!(is_supported(""));
+    assert!(is_supported("identity"));
+    assert!(is_supported("gzip, deflate, br"));
+    assert!(is_supported("GZIP;q=1.0, identity;q=0.5"));
+    assert!(!is_supported("zstd"));
+    assert!(!is_supported("gzip, *"));
+    assert!(!is_supported("gzip;q=2"));
+    assert!(!is_supported("gzip;level=9"));
+    assert!(!is_supported("gzip,"));
+}
+
+// @tc.name: ut_accept_encoding_apply
+// @tc.desc: Test the configured codings replace the header of the task
+// @tc.precon: NA
+// @tc.step: 1. Apply a coding to headers carrying an `accept-encoding`
+//           2. Apply an empty coding
+// @tc.expect: The configured coding replaces the header and the headers stay
+//             sorted, an empty coding changes nothing
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_accept_encoding_apply() {
+    let mut headers = vec![
+        ("Accept".to_string(), "*/*".to_string()),
+        ("accept-encoding".to_string(), "gzip".to_string()),
+        ("User-Agent".to_string(), "test".to_string()),
+    ];
+    let origin = headers.clone();
+    apply(&mut headers, "");
+    assert_eq!(headers, origin);
+
+    apply(&mut headers, "identity");
+    assert_eq!(
+        headers,
+        vec![
+            ("Accept".to_string(), "*/*".to_string()),
+            ("Accept-Encoding".to_string(), "identity".to_string()),
+            ("User-Agent".to_string(), "test".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,103 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::config::ConfigBuilder;
+
+fn config(min: Option<TlsProtocol>, max: Option<TlsProtocol>) -> TaskConfig {
+    let mut config = ConfigBuilder::new()
+        .action(Action::Download)
+        .url("https://127.0.0.1/ut_client")
+        .build();
+    config.min_tls_version = min;
+    config.max_tls_version = max;
+    config
+}
+
+// @tc.name: ut_client_tls_versions
+// @tc.desc: Test the TLS versions of a task are passed to the client builder
+// @tc.precon: NA
+// @tc.step: 1. Compute the TLS versions of a default config
+//           2. Compute them with TLS 1.2 as lowest and TLS 1.3 as highest
+//           3. Build clients of both configs
+// @tc.expect: TLS 1.2 is the lowest version of both, the highest is only set
+// when configured and the clients are built
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_tls_versions() {
+    let default = config(None, None);
+    assert_eq!(tls_versions(&default), Some((TlsProtocol::Tls12, None)));
+
+    let pinned = config(Some(TlsProtocol::Tls12), Some(TlsProtocol::Tls13));
+    assert_eq!(
+        tls_versions(&pinned),
+        Some((TlsProtocol::Tls12, Some(TlsProtocol::Tls13)))
+    );
+
+    #[cfg(not(feature = "oh"))]
+    {
+        assert!(build_client(&default, 60, UsedFamily::default()).is_ok());
+        assert!(build_client(&pinned, 60, UsedFamily::default()).is_ok());
+    }
+}
+
+// @tc.name: ut_client_tls_ciphers
+// @tc.desc: Test cipher lists are checked against the TLS version range
+// @tc.precon: NA
+// @tc.step: 1. Compute the TLS versions with well formed and malformed
+//              cipher lists, and with a cipher list restricted to TLS 1.3
+// @tc.expect: Only the well formed list with TLS 1.2 allowed is accepted
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_tls_ciphers() {
+    let mut config = config(None, None);
+    config.tls_ciphers = "ECDHE-RSA-AES128-GCM-SHA256:!aNULL".to_string();
+    assert!(tls_versions(&config).is_some());
+
+    for malformed in [":", "AES128-SHA:", "AES128 SHA"] {
+        config.tls_ciphers = malformed.to_string();
+        assert!(tls_versions(&config).is_none(), "{}", malformed);
+    }
+
+    config.tls_ciphers = "AES128-SHA".to_string();
+    config.min_tls_version = Some(TlsProtocol::Tls13);
+    assert!(tls_versions(&config).is_none());
+}
+
+// @tc.name: ut_client_insecure_skip_verify
+// @tc.desc: Test only privileged callers may skip certificate verification
+// @tc.precon: NA
+// @tc.step: 1. Check a task skipping verification for a privileged caller
+//           2. Check it for an unprivileged caller
+//           3. Build a client of the privileged task
+// @tc.expect: The privileged task keeps the flag and its client is built, the
+// flag of the unprivileged task is cleared
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_insecure_skip_verify() {
+    let mut privileged = config(None, None);
+    privileged.insecure_skip_verify = true;
+    assert!(check_insecure_skip_verify(&mut privileged, true));
+    assert!(privileged.insecure_skip_verify);
+
+    let mut unprivileged = config(None, None);
+    unprivileged.insecure_skip_verify = true;
+    assert!(!check_insecure_skip_verify(&mut unprivileged, false));
+    assert!(!unprivileged.insecure_skip_verify);
+
+    #[cfg(not(feature = "oh"))]
+    assert!(build_client(&privileged, 60, UsedFamily::default()).is_ok());
+}
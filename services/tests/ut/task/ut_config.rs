@@ -77,3 +77,312 @@ fn ut_enum_network_config() {
     assert_eq!(NetworkConfig::Wifi as u32, 1);
     assert_eq!(NetworkConfig::Cellular as u32, 2);
 }
+
+// @tc.name: ut_config_metered_override
+// @tc.desc: Test the metered override takes precedence over the reported flag
+// @tc.precon: NA
+// @tc.step: 1. Check a Wi-Fi task disallowing metered networks on a metered
+//              Wi-Fi with and without the Unmetered override
+//           2. Flip the network to unmetered and check the Metered override
+// @tc.expect: The Unmetered override satisfies the metered Wi-Fi, the Metered
+// override rejects the unmetered one and tasks without override follow the flag
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_config_metered_override() {
+    let mut info = NetworkInfo {
+        network_type: NetworkType::Wifi,
+        is_metered: true,
+        is_roaming: false,
+    };
+    let mut config = ConfigBuilder::new()
+        .network(NetworkConfig::Wifi)
+        .metered(false)
+        .build();
+    let mut overridden = config.clone();
+    overridden.treat_network_as = Some(MeteredOverride::Unmetered);
+
+    let network = NetworkState::Online(info.clone());
+    assert_eq!(
+        config.satisfy_network(&network),
+        Err(Reason::UnsupportedNetworkType)
+    );
+    assert!(overridden.satisfy_network(&network).is_ok());
+    assert!(!overridden.is_metered(&info));
+
+    info.is_metered = false;
+    let network = NetworkState::Online(info.clone());
+    assert!(config.satisfy_network(&network).is_ok());
+    config.treat_network_as = Some(MeteredOverride::Metered);
+    assert_eq!(
+        config.satisfy_network(&network),
+        Err(Reason::UnsupportedNetworkType)
+    );
+    assert!(config.is_metered(&info));
+}
+
+// @tc.name: ut_config_retry_deadline
+// @tc.desc: Test the retry deadline set absolute and relative to now
+// @tc.precon: NA
+// @tc.step: 1. Build configs without deadline, with an absolute deadline and
+//              with a deadline relative to now
+//           2. Check whether each deadline passed before and at its time
+// @tc.expect: A config without deadline never passes it, the others pass it
+// from their deadline on
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_config_retry_deadline() {
+    let config = ConfigBuilder::new().build();
+    assert!(!config.retry_deadline_passed(u64::MAX));
+
+    let config = ConfigBuilder::new().retry_deadline_at(1000).build();
+    assert!(!config.retry_deadline_passed(999));
+    assert!(config.retry_deadline_passed(1000));
+
+    let now = get_current_timestamp();
+    let config = ConfigBuilder::new().retry_deadline_after(60_000).build();
+    assert!(!config.retry_deadline_passed(now));
+    assert!(config.retry_deadline_passed(now + 60_000 + 1000));
+}
+
+// @tc.name: ut_config_order_file_specs
+// @tc.desc: Test files are ordered by descending priority
+// @tc.precon: NA
+// @tc.step: 1. Build a config of files listed out of priority order, each
+//              with a response body file
+//           2. Order the files
+// @tc.expect: Files are ordered by descending priority, files of equal
+// priority keep their order and body files follow their file
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_config_order_file_specs() {
+    let mut config = ConfigBuilder::new().build();
+    for (name, priority) in [("a", 0), ("b", 5), ("c", 5), ("d", 9)] {
+        let mut spec = FileSpec::user_file(File::open("/dev/null").unwrap());
+        spec.name = name.to_string();
+        spec.priority = priority;
+        config.file_specs.push(spec);
+        config.body_file_paths.push(format!("{}.body", name));
+    }
+
+    config.order_file_specs();
+    let names = config
+        .file_specs
+        .iter()
+        .map(|spec| spec.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["d", "b", "c", "a"]);
+    let paths = ["d.body", "b.body", "c.body", "a.body"];
+    assert_eq!(config.body_file_paths, paths);
+}
+
+// @tc.name: ut_config_options
+// @tc.desc: Test task options are set by name and listed back
+// @tc.precon: NA
+// @tc.step: 1. Set options of a config by name, invalid and unknown ones
+//              included
+//           2. Set the listed options of the config on another config
+// @tc.expect: Invalid values are rejected, unknown options are ignored and
+// the second config has the options of the first
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[cfg(feature = "oh")]
+#[test]
+fn ut_config_options() {
+    let mut config = ConfigBuilder::new().build();
+    assert_eq!(config.set_option("segments", "4"), Some(()));
+    assert_eq!(config.set_option("segments", "four"), None);
+    assert_eq!(config.set_option("unknown", "1"), Some(()));
+    assert_eq!(config.segments, 4);
+    assert_eq!(config.set_option("response_body_limit", "0"), Some(()));
+    let limit = (MAX_RESPONSE_BODY_LIMIT + 1).to_string();
+    assert_eq!(config.set_option("response_body_limit", &limit), None);
+    assert_eq!(config.response_body_limit, 0);
+    assert_eq!(config.set_option("treat_network_as", "3"), None);
+    assert_eq!(config.set_option("treat_network_as", "2"), Some(()));
+    assert_eq!(config.set_option("strict_resume", "yes"), None);
+    assert_eq!(config.set_option("strict_resume", "true"), Some(()));
+    assert_eq!(config.set_option("preallocate", "true"), Some(()));
+    assert_eq!(config.set_option("retry_after_cap_ms", "0"), Some(()));
+    assert_eq!(config.set_option("debug", "true"), Some(()));
+    assert_eq!(config.set_option("durable_completion", "true"), Some(()));
+    assert_eq!(config.set_option("duplicate_policy", "3"), None);
+    assert_eq!(config.set_option("duplicate_policy", "2"), Some(()));
+    assert_eq!(config.set_option("client_tag", "order=1"), Some(()));
+    assert_eq!(config.set_option("ip_preference", "5"), None);
+    assert_eq!(config.set_option("ip_preference", "4"), Some(()));
+    assert_eq!(config.set_option("min_tls_version", "2"), None);
+    assert_eq!(config.set_option("min_tls_version", "0"), Some(()));
+    assert_eq!(config.set_option("max_tls_version", "1"), Some(()));
+    assert_eq!(
+        config.set_option("tls_ciphers", "ECDHE-RSA-AES128-GCM-SHA256"),
+        Some(())
+    );
+    assert_eq!(config.set_option("report_redirects", "true"), Some(()));
+    assert_eq!(
+        config.set_option("expected_mime_types", "image/png, image/*,"),
+        Some(())
+    );
+    assert_eq!(config.set_option("if_match", "\"v1\""), Some(()));
+    assert_eq!(config.set_option("insecure_skip_verify", "true"), Some(()));
+    assert!(!config.insecure_skip_verify);
+    config.privileged = true;
+    assert_eq!(config.set_option("insecure_skip_verify", "true"), Some(()));
+    assert_eq!(config.set_option("resume_strategy", "query_param:"), None);
+    assert_eq!(config.set_option("resume_strategy", "offset"), None);
+    assert_eq!(
+        config.set_option("resume_strategy", "query_param:start"),
+        Some(())
+    );
+    assert_eq!(config.set_option("retry_deadline_ms", "-1"), None);
+    assert_eq!(
+        config.set_option("retry_deadline_ms", "1700000000000"),
+        Some(())
+    );
+    assert_eq!(config.set_option("max_size_bytes", "1MB"), None);
+    assert_eq!(config.set_option("max_size_bytes", "1048576"), Some(()));
+    assert_eq!(config.set_option("max_total_bytes", "4194304"), Some(()));
+    assert_eq!(config.set_option("accept_encoding", "gzip, br"), Some(()));
+    assert_eq!(config.set_option("net_id", "100"), Some(()));
+    assert_eq!(config.set_option("on_remove_partial", "0"), None);
+    assert_eq!(config.set_option("on_remove_partial", "3"), Some(()));
+    assert_eq!(
+        config.set_option("content_disposition_name", "true"),
+        Some(())
+    );
+    assert_eq!(config.set_option("max_cross_host_redirects", "2"), Some(()));
+    assert_eq!(config.set_option("dns_ttl_override_ms", "0"), Some(()));
+    assert_eq!(config.set_option("trace_context", ""), None);
+    let context = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01\nvendor=1";
+    assert_eq!(config.set_option("trace_context", context), Some(()));
+    assert_eq!(config.set_option("record_final_metadata", "true"), Some(()));
+    assert_eq!(config.set_option("concatenated", "true"), Some(()));
+    assert_eq!(config.set_option("pause_on_thermal_level", "256"), None);
+    assert_eq!(config.set_option("pause_on_thermal_level", "4"), Some(()));
+    assert_eq!(
+        config.set_option("require_charging_while_running", "true"),
+        Some(())
+    );
+    assert_eq!(config.set_option("keep_awake", "true"), Some(()));
+
+    let mut restored = ConfigBuilder::new().privileged(true).build();
+    for (name, value) in config.options() {
+        assert_eq!(restored.set_option(name, &value), Some(()));
+    }
+    assert_eq!(restored.segments, 4);
+    assert_eq!(restored.response_body_limit, 0);
+    assert_eq!(restored.treat_network_as, Some(MeteredOverride::Unmetered));
+    assert!(restored.strict_resume);
+    assert!(restored.preallocate);
+    assert_eq!(restored.retry_after_cap_ms, 0);
+    assert!(restored.debug);
+    assert!(restored.durable_completion);
+    assert_eq!(restored.duplicate_policy, DuplicatePolicy::Reject);
+    assert_eq!(restored.client_tag, "order=1");
+    assert_eq!(restored.ip_preference, IpPreference::V6Preferred);
+    assert_eq!(restored.min_tls_version, Some(TlsProtocol::Tls12));
+    assert_eq!(restored.max_tls_version, Some(TlsProtocol::Tls13));
+    assert_eq!(restored.tls_ciphers, "ECDHE-RSA-AES128-GCM-SHA256");
+    assert!(restored.report_redirects);
+    assert_eq!(restored.expected_mime_types, vec!["image/png", "image/*"]);
+    assert_eq!(restored.if_match, "\"v1\"");
+    assert!(restored.insecure_skip_verify);
+    assert_eq!(
+        restored.resume_strategy,
+        ResumeStrategy::QueryParam {
+            name: "start".to_string()
+        }
+    );
+    assert_eq!(restored.retry_deadline_ms, 1_700_000_000_000);
+    assert_eq!(restored.max_size_bytes, Some(1_048_576));
+    assert_eq!(restored.max_total_bytes, Some(4_194_304));
+    assert_eq!(restored.accept_encoding, "gzip, br");
+    assert_eq!(restored.net_id, 100);
+    assert_eq!(
+        restored.on_remove_partial,
+        Some(PartialFilePolicy::DeleteIfIncomplete)
+    );
+    assert!(restored.content_disposition_name);
+    assert_eq!(restored.max_cross_host_redirects, Some(2));
+    assert_eq!(restored.dns_ttl_override_ms, Some(0));
+    let context = restored.trace_context.as_ref().unwrap();
+    assert_eq!(
+        context.traceparent,
+        "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+    );
+    assert_eq!(context.tracestate.as_deref(), Some("vendor=1"));
+    assert!(restored.record_final_metadata);
+    assert!(restored.concatenated);
+    assert_eq!(restored.pause_on_thermal_level, Some(4));
+    assert!(restored.require_charging_while_running);
+    assert!(restored.keep_awake);
+}
+
+// @tc.name: ut_config_encode_options
+// @tc.desc: Test the options of a task are restored from their encoding
+// @tc.precon: NA
+// @tc.step: 1. Encode the options of a config with options set
+//           2. Decode them into another config, then a truncated encoding
+// @tc.expect: The second config has the options of the first, the truncated
+// encoding restores all options but the cut one
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_config_encode_options() {
+    let mut config = ConfigBuilder::new().build();
+    config.segments = 4;
+    config.keep_awake = true;
+    config.client_tag = "order=1".to_string();
+    config.resume_strategy = ResumeStrategy::None;
+    let data = config.encode_options();
+
+    let mut restored = ConfigBuilder::new().build();
+    restored.decode_options(&data);
+    assert_eq!(restored.options(), config.options());
+
+    let mut truncated = ConfigBuilder::new().build();
+    truncated.decode_options(&data[..data.len() - 1]);
+    assert_eq!(truncated.segments, 4);
+    assert!(!truncated.keep_awake);
+}
+
+// @tc.name: ut_config_file_priorities
+// @tc.desc: Test the priorities of the files of a task are set by option
+// @tc.precon: NA
+// @tc.step: 1. Set priorities of a config of three files, with a wrong count
+//              included
+//           2. Set the listed options of the config on another config of
+//              three files
+// @tc.expect: A wrong count is rejected and both configs have the priorities
+// in the order of their files
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[cfg(feature = "oh")]
+#[test]
+fn ut_config_file_priorities() {
+    let with_files = || {
+        let mut config = ConfigBuilder::new().build();
+        for _ in 0..3 {
+            let spec = FileSpec::user_file(File::open("/dev/null").unwrap());
+            config.file_specs.push(spec);
+        }
+        config
+    };
+    let mut config = with_files();
+    assert_eq!(config.set_option("file_priorities", "1,2"), None);
+    assert_eq!(config.set_option("file_priorities", "1,x,3"), None);
+    assert_eq!(config.set_option("file_priorities", "0,5,9"), Some(()));
+
+    let mut restored = with_files();
+    for (name, value) in config.options() {
+        assert_eq!(restored.set_option(name, &value), Some(()));
+    }
+    let priorities = restored
+        .file_specs
+        .iter()
+        .map(|spec| spec.priority)
+        .collect::<Vec<_>>();
+    assert_eq!(priorities, [0, 5, 9]);
+}
@@ -0,0 +1,96 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn name(value: &str) -> Option<String> {
+    disposition_file_name(value)
+}
+
+// @tc.name: ut_content_disposition_file_name
+// @tc.desc: Test file names are read from Content-Disposition values
+// @tc.precon: NA
+// @tc.step: 1. Parse plain, quoted and extended file names
+//           2. Parse values with both forms and without a file name
+// @tc.expect: Quotes and percent encoding are decoded, `filename*` wins over
+// `filename` and values without a usable name give `None`
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_content_disposition_file_name() {
+    assert_eq!(
+        name("attachment; filename=report.pdf").unwrap(),
+        "report.pdf"
+    );
+    assert_eq!(
+        name("attachment; filename=\"a \\\"b\\\"; c.txt\"").unwrap(),
+        "a _b_; c.txt"
+    );
+    assert_eq!(
+        name("attachment; FILENAME*=UTF-8''%E2%82%AC%20rates.txt").unwrap(),
+        "\u{20ac} rates.txt"
+    );
+    assert_eq!(
+        name("attachment; filename*=iso-8859-1'en'%A3%20rates.txt").unwrap(),
+        "\u{a3} rates.txt"
+    );
+    assert_eq!(
+        name("attachment; filename=\"fallback.txt\"; filename*=UTF-8''pr%C3%A9f%C3%A9r%C3%A9.txt")
+            .unwrap(),
+        "pr\u{e9}f\u{e9}r\u{e9}.txt"
+    );
+    assert_eq!(
+        name("attachment; filename*=UTF-8''%FF; filename=fallback.txt").unwrap(),
+        "fallback.txt"
+    );
+    assert!(name("attachment").is_none());
+    assert!(name("inline; size=12").is_none());
+    assert!(name("attachment; filename=\"\"").is_none());
+    assert!(name("attachment; filename*=UTF-8''%4").is_none());
+}
+
+// @tc.name: ut_content_disposition_sanitize
+// @tc.desc: Test server file names cannot leave the download directory
+// @tc.precon: NA
+// @tc.step: 1. Sanitize names with directories, traversal, reserved and
+//              control characters, dots and long names
+// @tc.expect: Only a single safe file name of at most 255 bytes is left, or
+// `None` if nothing usable remains
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_content_disposition_sanitize() {
+    assert_eq!(sanitize_file_name("../../etc/passwd").unwrap(), "passwd");
+    assert_eq!(sanitize_file_name("..\\..\\win.ini").unwrap(), "win.ini");
+    assert_eq!(
+        sanitize_file_name("/abs/path/file.bin").unwrap(),
+        "file.bin"
+    );
+    assert_eq!(
+        sanitize_file_name("a<b>c:d|e?f*g.txt").unwrap(),
+        "a_b_c_d_e_f_g.txt"
+    );
+    assert_eq!(
+        sanitize_file_name("new\nline\0.txt").unwrap(),
+        "new_line_.txt"
+    );
+    assert_eq!(sanitize_file_name(" .hidden. ").unwrap(), "hidden");
+    assert!(sanitize_file_name("..").is_none());
+    assert!(sanitize_file_name("dir/").is_none());
+    assert!(sanitize_file_name("   ").is_none());
+
+    let long = format!("{}.txt", "\u{e9}".repeat(200));
+    let name = sanitize_file_name(&long).unwrap();
+    assert!(name.len() <= MAX_FILE_NAME_LEN);
+    assert!(long.starts_with(&name));
+}
@@ -0,0 +1,59 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn expected(types: &[&str]) -> Vec<String> {
+    types.iter().map(|mime| mime.to_string()).collect()
+}
+
+// @tc.name: ut_content_type_exact
+// @tc.desc: Test content types are matched by type and subtype
+// @tc.precon: NA
+// @tc.step: 1. Match content types with parameters and other cases
+//           2. Match content types against other types
+// @tc.expect: Parameters and case are ignored, other types do not match
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_content_type_exact() {
+    let types = expected(&["application/zip", "image/png"]);
+    assert!(matches_mime_type(&types, "image/png"));
+    assert!(matches_mime_type(&types, "Image/PNG"));
+    assert!(matches_mime_type(&types, "application/zip; name=fw.zip"));
+    assert!(!matches_mime_type(&types, "image/jpeg"));
+    assert!(!matches_mime_type(&types, "text/html; charset=utf-8"));
+    assert!(!matches_mime_type(&types, ""));
+    assert!(!matches_mime_type(&[], "image/png"));
+}
+
+// @tc.name: ut_content_type_wildcard
+// @tc.desc: Test wildcard entries match every subtype of their type
+// @tc.precon: NA
+// @tc.step: 1. Match content types against `image/*` and `*/*`
+// @tc.expect: `image/*` matches images only, `*/*` matches any valid type
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_content_type_wildcard() {
+    let images = expected(&["image/*"]);
+    assert!(matches_mime_type(&images, "image/png"));
+    assert!(matches_mime_type(&images, "image/svg+xml"));
+    assert!(!matches_mime_type(&images, "text/html"));
+    assert!(!matches_mime_type(&images, "imagery/png"));
+    assert!(!matches_mime_type(&images, "image"));
+
+    let any = expected(&["*/*"]);
+    assert!(matches_mime_type(&any, "text/html"));
+    assert!(!matches_mime_type(&any, "garbage"));
+}
@@ -0,0 +1,91 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+fn captured(task_id: u32) -> Vec<String> {
+    CAPTURED
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(id, _)| *id == task_id)
+        .map(|(_, msg)| msg.clone())
+        .collect()
+}
+
+// @tc.name: ut_debug_log_task_only
+// @tc.desc: Test only debugged tasks write debug lines
+// @tc.precon: NA
+// @tc.step: 1. Create the log context of a debugged and a normal task
+//           2. Log a line for both tasks
+// @tc.expect: Only the debugged task's line is captured
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_debug_log_task_only() {
+    let debugged = TaskIdGenerator::generate();
+    let normal = debugged.wrapping_add(1);
+    let log = TaskLog::new(debugged, true);
+    let normal_log = TaskLog::new(normal, false);
+    assert!(log.enabled());
+    assert!(!normal_log.enabled());
+
+    task_debug!(log, "state {:#x} -> {:#x}", 0x10, 0x20);
+    task_debug!(normal_log, "state {:#x} -> {:#x}", 0x10, 0x20);
+    assert_eq!(captured(debugged), vec!["state 0x10 -> 0x20".to_string()]);
+    assert!(captured(normal).is_empty());
+}
+
+// @tc.name: ut_debug_log_load
+// @tc.desc: Test the log context of a stored task follows its configuration
+// @tc.precon: NA
+// @tc.step: 1. Store a debugged task
+//           2. Load the log context of the task and of an unknown task
+// @tc.expect: Only the stored debugged task logs in detail
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_debug_log_load() {
+    let db = RequestDb::get_instance();
+    let task_id = TaskIdGenerator::generate();
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, uid, debug) VALUES ({}, 1, 1)",
+        task_id
+    ))
+    .unwrap();
+    assert!(TaskLog::load(task_id).enabled());
+    assert!(!TaskLog::load(task_id.wrapping_add(1)).enabled());
+}
+
+// @tc.name: ut_debug_log_headers
+// @tc.desc: Test headers are logged sorted with credentials hidden
+// @tc.precon: NA
+// @tc.step: 1. Format headers including authorization and cookie headers
+// @tc.expect: Values of credential headers are replaced by `***`
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_debug_log_headers() {
+    let headers = [
+        ("Range", "bytes=0-"),
+        ("Authorization", "Bearer secret"),
+        ("Cookie", "session=1"),
+        ("Accept", "*/*"),
+    ];
+    assert_eq!(
+        format_headers(headers.into_iter()),
+        "Accept: */*, Authorization: ***, Cookie: ***, Range: bytes=0-"
+    );
+    assert_eq!(format_headers(std::iter::empty()), "");
+}
@@ -0,0 +1,184 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+use std::thread;
+
+use super::*;
+
+const HOST: &str = "example.com:443";
+
+fn addrs() -> Vec<SocketAddr> {
+    vec!["192.0.2.1:443".parse().unwrap()]
+}
+
+/// Stub resolver counting its lookups, resolving after `delay` or failing
+/// with `NotFound` if `fail` is set.
+fn resolver(
+    calls: &AtomicUsize,
+    delay: Duration,
+    fail: bool,
+) -> impl FnOnce() -> io::Result<Vec<SocketAddr>> + '_ {
+    move || {
+        calls.fetch_add(1, Ordering::SeqCst);
+        thread::sleep(delay);
+        if fail {
+            Err(io::Error::new(io::ErrorKind::NotFound, "NXDOMAIN"))
+        } else {
+            Ok(addrs())
+        }
+    }
+}
+
+// @tc.name: ut_dns_cache_single_flight
+// @tc.desc: Test concurrent lookups of one host resolve it once
+// @tc.precon: NA
+// @tc.step: 1. Look up one host from several threads with a slow resolver
+//           2. Look up the host again
+// @tc.expect: The resolver is called once, all lookups get its addresses
+//             and later lookups are hits
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_dns_cache_single_flight() {
+    let cache = DnsCache::new(DEFAULT_TTL, DEFAULT_NEGATIVE_TTL);
+    let calls = AtomicUsize::new(0);
+    thread::scope(|s| {
+        let lookups: Vec<_> = (0..8)
+            .map(|_| {
+                s.spawn(|| {
+                    cache.lookup(
+                        HOST,
+                        None,
+                        resolver(&calls, Duration::from_millis(200), false),
+                    )
+                })
+            })
+            .collect();
+        for lookup in lookups {
+            assert_eq!(lookup.join().unwrap().unwrap(), addrs());
+        }
+    });
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let res = cache.lookup(HOST, None, resolver(&calls, Duration::ZERO, false));
+    assert_eq!(res.unwrap(), addrs());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(cache.stats().misses.load(Ordering::SeqCst), 1);
+    assert_eq!(cache.stats().hits.load(Ordering::SeqCst), 9);
+}
+
+// @tc.name: ut_dns_cache_negative
+// @tc.desc: Test failed lookups are cached for the negative lifetime only
+// @tc.precon: NA
+// @tc.step: 1. Fail a lookup
+//           2. Look the host up again within and after the negative lifetime
+// @tc.expect: The failure is served from the cache within the lifetime, the
+//             host is resolved again after it
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_dns_cache_negative() {
+    let cache = DnsCache::new(DEFAULT_TTL, DEFAULT_NEGATIVE_TTL);
+    let calls = AtomicUsize::new(0);
+    let now = Instant::now();
+    let err = cache
+        .lookup_at(HOST, None, now, resolver(&calls, Duration::ZERO, true))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    let later = now + DEFAULT_NEGATIVE_TTL - Duration::from_millis(1);
+    let err = cache
+        .lookup_at(HOST, None, later, resolver(&calls, Duration::ZERO, false))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(cache.stats().negatives.load(Ordering::SeqCst), 1);
+
+    // A longer override does not keep failures longer.
+    let ttl_override = Some(Duration::from_secs(600));
+    let expired = now + DEFAULT_NEGATIVE_TTL;
+    let res = cache.lookup_at(
+        HOST,
+        ttl_override,
+        expired,
+        resolver(&calls, Duration::ZERO, false),
+    );
+    assert_eq!(res.unwrap(), addrs());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+// @tc.name: ut_dns_cache_ttl_override
+// @tc.desc: Test a task accepts cached addresses up to its own lifetime
+// @tc.precon: NA
+// @tc.step: 1. Resolve a host
+//           2. Look it up with a shorter override, a zero override and none
+// @tc.expect: Entries older than the override are resolved again, a zero
+//             override always resolves
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_dns_cache_ttl_override() {
+    let cache = DnsCache::new(DEFAULT_TTL, DEFAULT_NEGATIVE_TTL);
+    let calls = AtomicUsize::new(0);
+    let now = Instant::now();
+    let lookup = |ttl_override, at| {
+        cache
+            .lookup_at(
+                HOST,
+                ttl_override,
+                at,
+                resolver(&calls, Duration::ZERO, false),
+            )
+            .unwrap()
+    };
+    lookup(None, now);
+    let later = now + Duration::from_secs(2);
+    lookup(Some(Duration::from_secs(10)), later);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    lookup(Some(Duration::from_secs(1)), later);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    lookup(Some(Duration::ZERO), later);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    lookup(None, later + DEFAULT_TTL - Duration::from_millis(1));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    lookup(None, later + DEFAULT_TTL);
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+
+// @tc.name: ut_dns_cache_flush
+// @tc.desc: Test flushing drops resolved and failed entries
+// @tc.precon: NA
+// @tc.step: 1. Resolve one host and fail another
+//           2. Flush the cache and look both up again
+// @tc.expect: Both hosts are resolved again
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_dns_cache_flush() {
+    let cache = DnsCache::new(DEFAULT_TTL, DEFAULT_NEGATIVE_TTL);
+    let calls = AtomicUsize::new(0);
+    let failing = "missing.example.com:443";
+    let _ = cache.lookup(HOST, None, resolver(&calls, Duration::ZERO, false));
+    let _ = cache.lookup(failing, None, resolver(&calls, Duration::ZERO, true));
+    let _ = cache.lookup(HOST, None, resolver(&calls, Duration::ZERO, false));
+    let _ = cache.lookup(failing, None, resolver(&calls, Duration::ZERO, true));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    cache.flush();
+    let res = cache.lookup(HOST, None, resolver(&calls, Duration::ZERO, false));
+    assert_eq!(res.unwrap(), addrs());
+    let res = cache.lookup(failing, None, resolver(&calls, Duration::ZERO, false));
+    assert_eq!(res.unwrap(), addrs());
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
@@ -42,12 +42,13 @@ fn build_task(config: TaskConfig) -> Arc<RequestTask> {
     static TASK_MANGER: Lazy<TaskManagerTx> = Lazy::new(|| {
         TaskManager::init(RUN_COUNT_MANAGER.clone(), CLIENT.clone(), NETWORK.clone())
     });
-    let (files, client) = check_config(&config, 0).unwrap();
+    let (files, client, used_family) = check_config(&config, 0).unwrap();
 
     let task = Arc::new(RequestTask::new(
         config,
         files,
         client,
+        used_family,
         CLIENT.clone(),
         NETWORK.clone(),
     ));
@@ -0,0 +1,93 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use super::*;
+use crate::fixtures::TaskRow;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+fn insert(db: &RequestDb, task_id: u32, state: State) {
+    TaskRow {
+        state,
+        ..TaskRow::new(task_id)
+    }
+    .insert(db);
+}
+
+fn reason(db: &RequestDb, task_id: u32) -> u8 {
+    db.query_integer(&format!(
+        "SELECT reason FROM request_task WHERE task_id = {}",
+        task_id
+    ))[0]
+}
+
+// @tc.name: ut_durable_sync
+// @tc.desc: Test completed files are synced and checked by their length
+// @tc.precon: NA
+// @tc.step: 1. Write a file in a directory and make it durable
+//           2. Make it durable expecting another length
+// @tc.expect: The check passes for the written length and fails otherwise
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_durable_sync() {
+    let task_id = TaskIdGenerator::generate();
+    let _ = std::fs::create_dir("test_files/");
+    let path = PathBuf::from(format!("test_files/ut_durable_sync_{}.txt", task_id));
+    let mut file = File::create(&path).unwrap();
+    file.write_all(&[0; 1024]).unwrap();
+
+    make_durable(task_id, &file, Some(&path), 1024).unwrap();
+    make_durable(task_id, &file, None, 1024).unwrap();
+    let err = make_durable(task_id, &file, Some(&path), 2048).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let _ = std::fs::remove_file(path);
+}
+
+// @tc.name: ut_durable_sync_failed
+// @tc.desc: Test a failed sync pauses the task with its reason recorded
+// @tc.precon: NA
+// @tc.step: 1. Inject a sync failure for a running task
+//           2. Make its file durable and pause the task on failure
+//           3. Pause a completed task
+// @tc.expect: The running task is paused with `DurabilityCheckFailed` and
+// can be resumed, the completed task is left unchanged
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_durable_sync_failed() {
+    let db = RequestDb::get_instance();
+    let task_id = TaskIdGenerator::generate();
+    let _ = std::fs::create_dir("test_files/");
+    let path = PathBuf::from(format!("test_files/ut_durable_failed_{}.txt", task_id));
+    let file = File::create(&path).unwrap();
+    insert(db, task_id, State::Running);
+
+    FAIL_SYNC.lock().unwrap().push(task_id);
+    assert!(make_durable(task_id, &file, Some(&path), 0).is_err());
+    FAIL_SYNC.lock().unwrap().retain(|id| *id != task_id);
+    assert!(db.pause_interrupted(task_id, Reason::DurabilityCheckFailed));
+
+    let info = db.get_task_qos_info(task_id).unwrap();
+    assert_eq!(info.state, State::Paused.repr);
+    assert_eq!(reason(db, task_id), Reason::DurabilityCheckFailed.repr);
+
+    let completed = TaskIdGenerator::generate();
+    insert(db, completed, State::Completed);
+    assert!(!db.pause_interrupted(completed, Reason::DurabilityCheckFailed));
+    let info = db.get_task_qos_info(completed).unwrap();
+    assert_eq!(info.state, State::Completed.repr);
+    assert_eq!(reason(db, completed), Reason::Default.repr);
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,168 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const START: u64 = 1_000_000;
+
+/// Feeds one sample per second at `speed` bytes per second for `secs`
+/// seconds, returning the time and processed bytes afterwards.
+fn feed(
+    estimator: &mut EtaEstimator,
+    now: u64,
+    processed: u64,
+    speed: u64,
+    secs: u64,
+) -> (u64, u64) {
+    let (mut now, mut processed) = (now, processed);
+    for _ in 0..secs {
+        now += 1000;
+        processed += speed;
+        estimator.update(now, processed);
+    }
+    (now, processed)
+}
+
+// @tc.name: ut_eta_steady
+// @tc.desc: Test the estimate for a steady throughput
+// @tc.precon: NA
+// @tc.step: 1. Feed samples of a constant throughput
+//           2. Query the estimate for a known remaining size
+// @tc.expect: No estimate before the first throughput sample, afterwards the
+// estimate matches the remaining size divided by the throughput
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_eta_steady() {
+    let mut estimator = EtaEstimator::default();
+    assert_eq!(estimator.eta_ms(START, 1000), None);
+    estimator.update(START, 0);
+    assert_eq!(estimator.eta_ms(START, 1000), None);
+
+    let (now, _) = feed(&mut estimator, START, 0, 100_000, 10);
+    assert_eq!(estimator.eta_ms(now, 1_000_000), Some(10_000));
+    assert_eq!(estimator.eta_ms(now, 0), Some(0));
+}
+
+// @tc.name: ut_eta_speed_change
+// @tc.desc: Test the estimate follows throughput changes within the window
+// @tc.precon: NA
+// @tc.step: 1. Feed a fast throughput, then a slow one for 10 and for 70
+//              seconds
+// @tc.expect: The estimate lies between both speeds after 10 seconds and is
+// close to the slow speed once the fast samples left the window
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_eta_speed_change() {
+    let mut estimator = EtaEstimator::default();
+    estimator.update(START, 0);
+    let (now, processed) = feed(&mut estimator, START, 0, 1_000_000, 30);
+    let (now, processed) = feed(&mut estimator, now, processed, 100_000, 10);
+
+    // 10 MB take 10 s at the fast and 100 s at the slow speed.
+    let eta = estimator.eta_ms(now, 10_000_000).unwrap();
+    assert!(eta > 10_000 && eta < 100_000, "eta {}", eta);
+
+    let (now, _) = feed(&mut estimator, now, processed, 100_000, 60);
+    let eta = estimator.eta_ms(now, 10_000_000).unwrap();
+    assert!(eta > 80_000 && eta <= 100_000, "eta {}", eta);
+}
+
+// @tc.name: ut_eta_stall
+// @tc.desc: Test the estimate grows while the transfer is stalled
+// @tc.precon: NA
+// @tc.step: 1. Feed a steady throughput
+//           2. Query the estimate during a stall with and without samples
+//              recording no progress
+// @tc.expect: The estimate grows with the stall duration, samples without
+// progress and the mere passing of time give the same estimate
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_eta_stall() {
+    let mut estimator = EtaEstimator::default();
+    estimator.update(START, 0);
+    let (now, processed) = feed(&mut estimator, START, 0, 100_000, 10);
+
+    let before = estimator.eta_ms(now, 1_000_000).unwrap();
+    let during = estimator.eta_ms(now + 10_000, 1_000_000).unwrap();
+    let later = estimator.eta_ms(now + 60_000, 1_000_000).unwrap();
+    assert!(before < during && during < later);
+
+    let (stalled, _) = feed(&mut estimator, now, processed, 0, 10);
+    let sampled = estimator.eta_ms(stalled, 1_000_000).unwrap();
+    assert!(sampled.abs_diff(during) <= 1, "{} {}", sampled, during);
+
+    assert_eq!(estimator.eta_ms(u64::MAX, 1_000_000), Some(u64::MAX));
+}
+
+// @tc.name: ut_eta_restart
+// @tc.desc: Test progress going backwards discards the history
+// @tc.precon: NA
+// @tc.step: 1. Feed a steady throughput
+//           2. Record less processed bytes than before
+// @tc.expect: No estimate until the next throughput sample, which is used
+// as is
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_eta_restart() {
+    let mut estimator = EtaEstimator::default();
+    estimator.update(START, 0);
+    let (now, _) = feed(&mut estimator, START, 0, 1_000_000, 10);
+
+    estimator.update(now + 1000, 0);
+    assert_eq!(estimator.eta_ms(now + 1000, 1000), None);
+    feed(&mut estimator, now + 1000, 0, 100_000, 1);
+    assert_eq!(estimator.eta_ms(now + 2000, 1_000_000), Some(10_000));
+}
+
+// @tc.name: ut_eta_speed
+// @tc.desc: Test the reported throughput of a task
+// @tc.precon: NA
+// @tc.step: 1. Query the speed before any sample
+//           2. Feed a steady throughput and query the speed
+//           3. Query the speed after a stall
+// @tc.expect: 0 before progress, the fed throughput afterwards and a lower
+// throughput during the stall
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_eta_speed() {
+    let mut estimator = EtaEstimator::default();
+    assert_eq!(estimator.speed(START), 0);
+    estimator.update(START, 0);
+    let (now, _) = feed(&mut estimator, START, 0, 100_000, 10);
+    assert_eq!(estimator.speed(now), 100_000);
+    assert!(estimator.speed(now + 10_000) < 100_000);
+}
+
+// @tc.name: ut_eta_bucket
+// @tc.desc: Test the bucketing of estimates for the notification bar
+// @tc.precon: NA
+// @tc.step: 1. Render estimates around the bucket boundaries
+// @tc.expect: Below a minute, rounded minutes up to an hour and a fixed text
+// above
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_eta_bucket() {
+    assert_eq!(eta_bucket(0), EtaBucket::UnderMinute);
+    assert_eq!(eta_bucket(59_999), EtaBucket::UnderMinute);
+    assert_eq!(eta_bucket(60_000), EtaBucket::Minutes(1));
+    assert_eq!(eta_bucket(150_000), EtaBucket::Minutes(3));
+    assert_eq!(eta_bucket(3_599_999), EtaBucket::Minutes(59));
+    assert_eq!(eta_bucket(3_600_000), EtaBucket::OverHour);
+    assert_eq!(eta_bucket(u64::MAX), EtaBucket::OverHour);
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+// @tc.name: ut_final_metadata_sniff
+// @tc.desc: Test the content type of a file is sniffed from its first bytes
+// @tc.precon: NA
+// @tc.step: 1. Sniff files starting with known signatures
+//           2. Sniff markup, text and binary files
+// @tc.expect: Known signatures and markup give their type, valid text gives
+//             `text/plain` and unknown binary content none
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_final_metadata_sniff() {
+    assert_eq!(sniff_mime_type(b"\x89PNG\r\n\x1a\n\0\0"), Some("image/png"));
+    assert_eq!(sniff_mime_type(b"\xff\xd8\xff\xe0"), Some("image/jpeg"));
+    assert_eq!(sniff_mime_type(b"%PDF-1.7"), Some("application/pdf"));
+    assert_eq!(sniff_mime_type(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+    assert_eq!(sniff_mime_type(b"\0\0\0\x18ftypmp42"), Some("video/mp4"));
+    assert_eq!(
+        sniff_mime_type(b"  <!DOCTYPE html><html>"),
+        Some("text/html")
+    );
+    assert_eq!(
+        sniff_mime_type(b"<?xml version=\"1.0\"?>"),
+        Some("text/xml")
+    );
+    assert_eq!(sniff_mime_type(b"plain words"), Some("text/plain"));
+    assert_eq!(sniff_mime_type(b"\0\x01\x02\xfe"), None);
+    assert_eq!(sniff_mime_type(b""), None);
+}
+
+// @tc.name: ut_final_metadata_mime_type
+// @tc.desc: Test the served content type is preferred to the sniffed one
+// @tc.precon: NA
+// @tc.step: 1. Type a PNG file served with a specific type
+//           2. Type it served without a type and as a generic binary
+//           3. Type an unknown binary file served without a type
+// @tc.expect: A specific served type is kept as it is, otherwise the sniffed
+//             type is used, and unknown content is a generic binary
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_final_metadata_mime_type() {
+    let png = b"\x89PNG\r\n\x1a\n";
+    assert_eq!(
+        final_mime_type("text/html; charset=utf-8", png),
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(final_mime_type("", png), "image/png");
+    assert_eq!(
+        final_mime_type("Application/Octet-Stream", png),
+        "image/png"
+    );
+    assert_eq!(final_mime_type("", b"\0\x01"), OCTET_STREAM);
+}
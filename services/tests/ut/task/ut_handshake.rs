@@ -0,0 +1,154 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::sync::Arc;
+
+use ylong_runtime::sync::mpsc::unbounded_channel;
+
+use super::*;
+use crate::config::{Action, ConfigBuilder, Mode, TaskConfig};
+use crate::service::client::ClientManagerEntry;
+use crate::task::request_task::{check_config, get_rest_time};
+
+fn build_task(config: TaskConfig) -> Arc<RequestTask> {
+    let (tx, _) = unbounded_channel();
+    let client_manager = ClientManagerEntry::new(tx);
+    let rest_time = get_rest_time(&config, 0);
+    let (files, client, used_family) = check_config(&config, rest_time).unwrap();
+    Arc::new(RequestTask::new(
+        config,
+        files,
+        client,
+        used_family,
+        client_manager,
+        false,
+        rest_time,
+    ))
+}
+
+fn handshake_task(path: &str) -> Arc<RequestTask> {
+    let _ = std::fs::create_dir("test_files/");
+    let file = File::create(path).unwrap();
+    let config = ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .file_spec(file)
+        .url("https://127.0.0.1/handshake")
+        .build();
+    build_task(config)
+}
+
+/// Sends a request as the download does, each try failing with the next
+/// injected fault until none is left.
+///
+/// Returns the number of tries and the fault of the last one, `None` if it
+/// succeeded.
+async fn send(task: &RequestTask, faults: &[TlsFault]) -> (usize, Option<TlsFault>) {
+    let mut faults: VecDeque<TlsFault> = faults.iter().copied().collect();
+    let mut retried = 0;
+    let mut tries = 0;
+    loop {
+        tries += 1;
+        let fault = faults.pop_front();
+        if !task.retry_handshake(fault, &mut retried).await {
+            return (tries, fault);
+        }
+    }
+}
+
+// @tc.name: ut_handshake_classify
+// @tc.desc: Test TLS handshake failures are classified by their message
+// @tc.precon: NA
+// @tc.step: 1. Classify messages of timeouts, cut off handshakes, rejected
+//              certificates and pins, and other failures
+// @tc.expect: Only timeouts and cut off handshakes are transient, rejections
+//             take precedence
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_handshake_classify() {
+    let cases = [
+        ("SSL routines: handshake timed out", TlsFault::Transient),
+        ("Connect timeout", TlsFault::Transient),
+        ("unexpected eof while reading", TlsFault::Transient),
+        ("certificate verify failed", TlsFault::Rejected),
+        ("Public key pinning verification failed", TlsFault::Rejected),
+        ("certificate has expired, timed out", TlsFault::Rejected),
+        ("SSL routines::wrong version number", TlsFault::Other),
+    ];
+    for (message, fault) in cases {
+        assert_eq!(classify(message), fault, "{}", message);
+    }
+}
+
+// @tc.name: ut_handshake_transient_budget
+// @tc.desc: Test transient handshake failures are retried apart from the
+//           retries of the task
+// @tc.precon: NA
+// @tc.step: 1. Inject a transient failure followed by success
+//           2. Inject more transient failures than retried within an attempt
+//           3. Use a retry of the task after the last failure
+// @tc.expect: Handshakes are retried at most twice per attempt and counted
+//             apart, only the exhausted attempt uses a retry of the task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_handshake_transient_budget() {
+    let task = handshake_task("test_files/ut_handshake_transient_budget.txt");
+    ylong_runtime::block_on(async {
+        assert_eq!(send(&task, &[TlsFault::Transient]).await, (2, None));
+        assert_eq!(task.handshake_retries.load(Ordering::SeqCst), 1);
+        assert_eq!(task.tries.load(Ordering::SeqCst), 0);
+
+        let faults = [TlsFault::Transient; 4];
+        let (tries, fault) = send(&task, &faults).await;
+        assert_eq!((tries, fault), (3, Some(TlsFault::Transient)));
+        assert_eq!(task.handshake_retries.load(Ordering::SeqCst), 3);
+        assert_eq!(task.tries.load(Ordering::SeqCst), 0);
+
+        assert!(task.connect_retry(fault).await.is_err());
+        assert_eq!(task.tries.load(Ordering::SeqCst), 1);
+    });
+
+    let mut extras = HashMap::new();
+    task.record_handshake_retries(&mut extras);
+    assert_eq!(extras.get(HANDSHAKE_RETRIES_KEY).unwrap(), "3");
+}
+
+// @tc.name: ut_handshake_rejected_no_retry
+// @tc.desc: Test rejected certificates and pins are never retried
+// @tc.precon: NA
+// @tc.step: 1. Inject a rejected certificate
+//           2. Handle the connection failure as the download does
+// @tc.expect: The request is tried once, no handshake retry or retry of the
+//             task is used and nothing is recorded
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_handshake_rejected_no_retry() {
+    let task = handshake_task("test_files/ut_handshake_rejected_no_retry.txt");
+    ylong_runtime::block_on(async {
+        let faults = [TlsFault::Rejected, TlsFault::Transient];
+        let (tries, fault) = send(&task, &faults).await;
+        assert_eq!((tries, fault), (1, Some(TlsFault::Rejected)));
+        assert!(task.connect_retry(fault).await.is_ok());
+    });
+    assert_eq!(task.handshake_retries.load(Ordering::SeqCst), 0);
+    assert_eq!(task.tries.load(Ordering::SeqCst), 0);
+
+    let mut extras = HashMap::new();
+    task.record_handshake_retries(&mut extras);
+    assert!(extras.is_empty());
+}
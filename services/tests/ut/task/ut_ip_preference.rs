@@ -0,0 +1,248 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+const V4_A: &str = "192.0.2.1:443";
+const V4_B: &str = "192.0.2.2:443";
+const V6_A: &str = "[2001:db8::1]:443";
+const V6_B: &str = "[2001:db8::2]:443";
+
+fn addrs(addrs: &[&str]) -> Vec<SocketAddr> {
+    addrs.iter().map(|addr| addr.parse().unwrap()).collect()
+}
+
+/// Stub connector recording the attempts, connecting to `ok` addresses
+/// after `delay` and failing the others at once.
+fn connector(
+    ok: Vec<SocketAddr>,
+    delay: Duration,
+) -> (
+    Arc<Mutex<Vec<SocketAddr>>>,
+    impl Fn(SocketAddr) -> io::Result<()> + Send + Sync + 'static,
+) {
+    let attempts = Arc::new(Mutex::new(vec![]));
+    let recorded = attempts.clone();
+    let connect = move |addr| {
+        recorded.lock().unwrap().push(addr);
+        if !ok.contains(&addr) {
+            return Err(io::Error::from(io::ErrorKind::ConnectionRefused));
+        }
+        thread::sleep(delay);
+        Ok(())
+    };
+    (attempts, connect)
+}
+
+// @tc.name: ut_ip_preference_order
+// @tc.desc: Test resolved addresses are filtered and ordered by preference
+// @tc.precon: NA
+// @tc.step: 1. Order mixed IPv4 and IPv6 addresses by every preference
+// @tc.expect: Only preferences keep one family, preferred ones interleave
+// the families starting with the preferred one, the default keeps the order
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_ip_preference_order() {
+    let resolved = addrs(&[V6_A, V6_B, V4_A, V4_B]);
+    let order = |preference| order_addrs(resolved.clone(), preference);
+
+    assert_eq!(order(IpPreference::Default), resolved);
+    assert_eq!(order(IpPreference::V4Only), addrs(&[V4_A, V4_B]));
+    assert_eq!(order(IpPreference::V6Only), addrs(&[V6_A, V6_B]));
+    assert_eq!(
+        order(IpPreference::V4Preferred),
+        addrs(&[V4_A, V6_A, V4_B, V6_B])
+    );
+    assert_eq!(
+        order(IpPreference::V6Preferred),
+        addrs(&[V6_A, V4_A, V6_B, V4_B])
+    );
+    assert!(order_addrs(addrs(&[V4_A]), IpPreference::V6Only).is_empty());
+}
+
+// @tc.name: ut_ip_preference_race_preferred
+// @tc.desc: Test the preferred family wins the race when it connects in time
+// @tc.precon: NA
+// @tc.step: 1. Race addresses whose preferred family connects at once
+// @tc.expect: The preferred address wins without a fallback attempt
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_ip_preference_race_preferred() {
+    let ordered = order_addrs(addrs(&[V4_A, V6_A]), IpPreference::V6Preferred);
+    let (attempts, connect) = connector(ordered.clone(), Duration::ZERO);
+    assert_eq!(race(&ordered, connect), Some(ordered[0]));
+    assert_eq!(*attempts.lock().unwrap(), addrs(&[V6_A]));
+}
+
+// @tc.name: ut_ip_preference_race_stagger
+// @tc.desc: Test the fallback family is tried after the stagger delay
+// @tc.precon: NA
+// @tc.step: 1. Race addresses whose preferred family hangs
+// @tc.expect: The fallback is started after `FALLBACK_STAGGER` and wins
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_ip_preference_race_stagger() {
+    let ordered = order_addrs(addrs(&[V4_A, V6_A]), IpPreference::V6Preferred);
+    let v4 = ordered[1];
+    let (attempts, connect) = connector(vec![v4], Duration::ZERO);
+    let hanging = move |addr: SocketAddr| {
+        if addr.is_ipv6() {
+            thread::sleep(Duration::from_secs(2));
+        }
+        connect(addr)
+    };
+
+    let begin = Instant::now();
+    assert_eq!(race(&ordered, hanging), Some(v4));
+    let elapsed = begin.elapsed();
+    assert!(elapsed >= FALLBACK_STAGGER, "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(2), "{:?}", elapsed);
+    assert_eq!(*attempts.lock().unwrap(), vec![v4]);
+}
+
+// @tc.name: ut_ip_preference_race_failed
+// @tc.desc: Test a failed preferred connection starts the fallback at once
+// @tc.precon: NA
+// @tc.step: 1. Race addresses whose preferred family is refused
+//           2. Race addresses whose families are both refused
+// @tc.expect: The fallback wins before the stagger delay, the race without
+// a reachable address has no winner
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_ip_preference_race_failed() {
+    let ordered = order_addrs(addrs(&[V4_A, V6_A]), IpPreference::V4Preferred);
+    let (attempts, connect) = connector(addrs(&[V6_A]), Duration::ZERO);
+    let begin = Instant::now();
+    assert_eq!(race(&ordered, connect), Some(ordered[1]));
+    assert!(begin.elapsed() < FALLBACK_STAGGER);
+    assert_eq!(*attempts.lock().unwrap(), addrs(&[V4_A, V6_A]));
+
+    let (_, connect) = connector(vec![], Duration::ZERO);
+    assert_eq!(race(&ordered, connect), None);
+}
+
+// @tc.name: ut_ip_preference_resolve
+// @tc.desc: Test resolving puts the winner first and records its family
+// @tc.precon: NA
+// @tc.step: 1. Resolve mixed addresses preferring IPv6 with IPv6 refused
+//           2. Resolve IPv4 addresses with the IPv6 only preference
+// @tc.expect: The IPv4 winner is first and `ipv4` is recorded in the slot
+// of the task, the IPv6 only resolution fails without recording
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_ip_preference_resolve() {
+    let log = TaskLog::new(TaskIdGenerator::generate(), false);
+    let used_family = UsedFamily::default();
+    let (_, connect) = connector(addrs(&[V4_A, V4_B]), Duration::ZERO);
+    let resolved = resolve(
+        log,
+        "ut_ip_preference_resolve",
+        addrs(&[V6_A, V6_B, V4_A, V4_B]),
+        IpPreference::V6Preferred,
+        &used_family,
+        connect,
+    )
+    .unwrap();
+    assert_eq!(resolved, addrs(&[V4_A, V6_A, V6_B, V4_B]));
+    assert_eq!(used_family.lock().unwrap().take(), Some("ipv4"));
+
+    let (_, connect) = connector(vec![], Duration::ZERO);
+    let err = resolve(
+        log,
+        "ut_ip_preference_resolve",
+        addrs(&[V4_A]),
+        IpPreference::V6Only,
+        &used_family,
+        connect,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AddrNotAvailable);
+    assert_eq!(used_family.lock().unwrap().take(), None);
+}
+
+// @tc.name: ut_ip_preference_resolve_last_winner
+// @tc.desc: Test the family that won a race is used for the host without
+// racing again
+// @tc.precon: NA
+// @tc.step: 1. Resolve a host preferring IPv6 with IPv6 refused
+//           2. Resolve the host again and resolve another host
+// @tc.expect: The second resolution of the host puts IPv4 first without a
+// connection attempt, the other host races
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_ip_preference_resolve_last_winner() {
+    let log = TaskLog::new(TaskIdGenerator::generate(), false);
+    let used_family = UsedFamily::default();
+    let resolved = addrs(&[V6_A, V4_A]);
+    let host = "ut_ip_preference_resolve_last_winner";
+
+    let (attempts, connect) = connector(addrs(&[V4_A]), Duration::ZERO);
+    let first = resolve(
+        log,
+        host,
+        resolved.clone(),
+        IpPreference::V6Preferred,
+        &used_family,
+        connect,
+    );
+    assert_eq!(first.unwrap(), addrs(&[V4_A, V6_A]));
+    assert_eq!(attempts.lock().unwrap().len(), 2);
+
+    let (attempts, connect) = connector(addrs(&[V4_A]), Duration::ZERO);
+    let again = resolve(
+        log,
+        host,
+        resolved.clone(),
+        IpPreference::V6Preferred,
+        &used_family,
+        connect,
+    );
+    assert_eq!(again.unwrap(), addrs(&[V4_A, V6_A]));
+    assert!(attempts.lock().unwrap().is_empty());
+    assert_eq!(used_family.lock().unwrap().take(), Some("ipv4"));
+
+    let (attempts, connect) = connector(addrs(&[V6_A]), Duration::ZERO);
+    let other = resolve(
+        log,
+        "ut_ip_preference_resolve_other",
+        resolved,
+        IpPreference::V6Preferred,
+        &used_family,
+        connect,
+    );
+    assert_eq!(other.unwrap(), addrs(&[V6_A, V4_A]));
+    assert_eq!(*attempts.lock().unwrap(), addrs(&[V6_A]));
+}
+
+// @tc.name: ut_ip_preference_has_ipv6
+// @tc.desc: Test IPv6 is detected by the interfaces of `if_inet6`
+// @tc.precon: NA
+// @tc.step: 1. Check tables with loopback only and with another interface
+// @tc.expect: Only the table listing another interface has IPv6
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_ip_preference_has_ipv6() {
+    let lo = "00000000000000000000000000000001 01 80 10 80       lo\n";
+    let wlan = "20010db8000000000000000000000001 02 40 00 00    wlan0\n";
+    assert!(!has_ipv6_in(""));
+    assert!(!has_ipv6_in(lo));
+    assert!(has_ipv6_in(&format!("{}{}", lo, wlan)));
+}
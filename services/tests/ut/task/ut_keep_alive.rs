@@ -0,0 +1,174 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+
+use super::*;
+use crate::config::ConfigBuilder;
+
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn config(url: &str) -> TaskConfig {
+    ConfigBuilder::new().url(url).uid(1000).build()
+}
+
+/// Mock client counting the connections it opened.
+struct MockClient {
+    connects: Arc<AtomicUsize>,
+}
+
+impl MockClient {
+    fn connect(connects: &Arc<AtomicUsize>) -> Self {
+        connects.fetch_add(1, Ordering::SeqCst);
+        Self {
+            connects: connects.clone(),
+        }
+    }
+}
+
+/// Runs a request for `key`, taking a pooled client if there is one, and
+/// gives the client back once it is done.
+fn request(
+    pool: &KeepAlivePool<MockClient>,
+    key: &PoolKey,
+    connects: &Arc<AtomicUsize>,
+    now: Instant,
+) {
+    let client = pool
+        .take_at(key, now)
+        .unwrap_or_else(|| MockClient::connect(connects));
+    pool.give_at(key.clone(), client, now);
+}
+
+// @tc.name: ut_keep_alive_reuse
+// @tc.desc: Test sequential requests to one host reuse the connection
+// @tc.precon: NA
+// @tc.step: 1. Issue three sequential requests to the same host
+//           2. Issue a request to another host
+// @tc.expect: The same host is connected once, the other host connects anew
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_keep_alive_reuse() {
+    let pool = KeepAlivePool::new(IDLE_TIMEOUT, DEFAULT_MAX_PER_HOST);
+    let connects = Arc::new(AtomicUsize::new(0));
+    let key = pool_key(&config("https://example.com/a")).unwrap();
+    let now = Instant::now();
+
+    for _ in 0..3 {
+        request(&pool, &key, &connects, now);
+    }
+    assert_eq!(connects.load(Ordering::SeqCst), 1);
+    assert_eq!(pool.stats().reused.load(Ordering::SeqCst), 2);
+
+    let other = pool_key(&config("https://example.org/a")).unwrap();
+    assert!(pool.take_at(&other, now).is_none());
+    let client = pool.take_at(&key, now).unwrap();
+    assert_eq!(client.connects.load(Ordering::SeqCst), 1);
+}
+
+// @tc.name: ut_keep_alive_idle_timeout
+// @tc.desc: Test clients idle for longer than the timeout are dropped
+// @tc.precon: NA
+// @tc.step: 1. Give a client to the pool
+//           2. Take it just before and just after the idle timeout
+// @tc.expect: The client is reused before the timeout and expired after it
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_keep_alive_idle_timeout() {
+    let pool = KeepAlivePool::new(IDLE_TIMEOUT, DEFAULT_MAX_PER_HOST);
+    let connects = Arc::new(AtomicUsize::new(0));
+    let key = pool_key(&config("http://example.com")).unwrap();
+    let now = Instant::now();
+
+    request(&pool, &key, &connects, now);
+    let before = now + IDLE_TIMEOUT - Duration::from_millis(1);
+    assert!(pool.take_at(&key, before).is_some());
+
+    request(&pool, &key, &connects, now);
+    assert_eq!(connects.load(Ordering::SeqCst), 2);
+    assert!(pool.take_at(&key, now + IDLE_TIMEOUT).is_none());
+    assert_eq!(pool.stats().expired.load(Ordering::SeqCst), 1);
+    assert_eq!(pool.idle(), 0);
+}
+
+// @tc.name: ut_keep_alive_max_per_host
+// @tc.desc: Test the idle clients kept per host are bounded
+// @tc.precon: NA
+// @tc.step: 1. Give more clients of one host than the maximum
+//           2. Give a client to a pool with a maximum of 0
+// @tc.expect: The oldest clients are dropped, and no client is kept when
+//             the maximum is 0
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_keep_alive_max_per_host() {
+    let pool = KeepAlivePool::new(IDLE_TIMEOUT, 2);
+    let connects = Arc::new(AtomicUsize::new(0));
+    let key = pool_key(&config("https://example.com")).unwrap();
+    let now = Instant::now();
+    for _ in 0..3 {
+        pool.give_at(key.clone(), MockClient::connect(&connects), now);
+    }
+    assert_eq!(pool.idle(), 2);
+    assert_eq!(pool.stats().expired.load(Ordering::SeqCst), 1);
+
+    let disabled = KeepAlivePool::new(IDLE_TIMEOUT, 0);
+    disabled.give_at(key.clone(), MockClient::connect(&connects), now);
+    assert_eq!(disabled.idle(), 0);
+}
+
+// @tc.name: ut_keep_alive_pool_key
+// @tc.desc: Test which tasks may share clients
+// @tc.precon: NA
+// @tc.step: 1. Compute the keys of tasks to the same origin with other paths
+//           2. Compute the keys of tasks with other apps or settings
+//           3. Compute the keys of tasks whose client holds task state
+// @tc.expect: The origin ignores case, path and user info, other apps and
+//             settings get other keys, and tasks with task state get none
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_keep_alive_pool_key() {
+    let key = pool_key(&config("https://Example.com/a?b=c")).unwrap();
+    let same = pool_key(&config("HTTPS://user@example.COM/d")).unwrap();
+    assert_eq!(key, same);
+    assert_ne!(key, pool_key(&config("http://example.com/a")).unwrap());
+    let other_port = pool_key(&config("https://example.com:8443/a")).unwrap();
+    assert_ne!(key, other_port);
+
+    let other_app = ConfigBuilder::new()
+        .url("https://example.com/a")
+        .uid(1001)
+        .build();
+    assert_ne!(key, pool_key(&other_app).unwrap());
+    let other_settings = ConfigBuilder::new()
+        .url("https://example.com/a")
+        .uid(1000)
+        .redirect(false)
+        .build();
+    assert_ne!(key, pool_key(&other_settings).unwrap());
+
+    let preference = ConfigBuilder::new()
+        .url("https://example.com/a")
+        .ip_preference(IpPreference::V4Only)
+        .build();
+    assert!(pool_key(&preference).is_none());
+    let reporting = ConfigBuilder::new()
+        .url("https://example.com/a")
+        .report_redirects(true)
+        .build();
+    assert!(pool_key(&reporting).is_none());
+    assert!(pool_key(&config("ftp://example.com/a")).is_none());
+}
@@ -0,0 +1,92 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn extras(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+// @tc.name: ut_loopback_spec
+// @tc.desc: Test the loopback spec is read from the extras of a task
+// @tc.precon: NA
+// @tc.step: 1. Read a spec with a size only
+//           2. Read a spec with a size, a rate and a failing offset
+// @tc.expect: Missing values default to no rate and no failure
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_loopback_spec() {
+    assert_eq!(
+        LoopbackSpec::from_extras(&extras(&[(LOOPBACK_SIZE, "1024")])),
+        Some(LoopbackSpec {
+            size: 1024,
+            rate: 0,
+            fail_at: None,
+        })
+    );
+    let full = extras(&[
+        (LOOPBACK_SIZE, "4096"),
+        (LOOPBACK_RATE, "512"),
+        (LOOPBACK_FAIL_AT, "100"),
+    ]);
+    assert_eq!(
+        LoopbackSpec::from_extras(&full),
+        Some(LoopbackSpec {
+            size: 4096,
+            rate: 512,
+            fail_at: Some(100),
+        })
+    );
+}
+
+// @tc.name: ut_loopback_spec_invalid
+// @tc.desc: Test extras without a valid loopback spec are rejected
+// @tc.precon: NA
+// @tc.step: 1. Read a spec without a size
+//           2. Read specs with values that are not numbers
+// @tc.expect: No spec is read
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_loopback_spec_invalid() {
+    assert_eq!(LoopbackSpec::from_extras(&extras(&[])), None);
+    assert_eq!(
+        LoopbackSpec::from_extras(&extras(&[(LOOPBACK_RATE, "10")])),
+        None
+    );
+    assert_eq!(
+        LoopbackSpec::from_extras(&extras(&[(LOOPBACK_SIZE, "-1")])),
+        None
+    );
+    let bad_offset = extras(&[(LOOPBACK_SIZE, "10"), (LOOPBACK_FAIL_AT, "x")]);
+    assert_eq!(LoopbackSpec::from_extras(&bad_offset), None);
+}
+
+// @tc.name: ut_loopback_pattern
+// @tc.desc: Test the loopback pattern only depends on the offset
+// @tc.precon: NA
+// @tc.step: 1. Generate the pattern around its period
+// @tc.expect: The pattern repeats every 251 bytes
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_loopback_pattern() {
+    assert_eq!(pattern(0), 0);
+    assert_eq!(pattern(250), 250);
+    assert_eq!(pattern(251), 0);
+    assert_eq!(pattern(251 * 1000 + 7), 7);
+}
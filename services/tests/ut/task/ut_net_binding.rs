@@ -0,0 +1,65 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+// @tc.name: ut_net_binding_split_authority
+// @tc.desc: Test authorities are split into host and port
+// @tc.precon: NA
+// @tc.step: 1. Split host names, IPv4 and bracketed IPv6 authorities
+//           2. Split authorities without a valid port
+// @tc.expect: Hosts come without brackets, malformed authorities are None
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_net_binding_split_authority() {
+    assert_eq!(
+        split_authority("example.com:443"),
+        Some(("example.com", 443))
+    );
+    assert_eq!(split_authority("127.0.0.1:80"), Some(("127.0.0.1", 80)));
+    assert_eq!(split_authority("[::1]:8080"), Some(("::1", 8080)));
+    assert_eq!(split_authority("example.com"), None);
+    assert_eq!(split_authority("example.com:http"), None);
+    assert_eq!(split_authority("[::1:80"), None);
+}
+
+// @tc.name: ut_net_binding_lookup
+// @tc.desc: Test hosts are only resolved on available networks
+// @tc.precon: NA
+// @tc.step: 1. Resolve localhost on a detached network
+//           2. Attach the network and resolve again
+//           3. Check the validity of network handles
+// @tc.expect: Lookups fail on the detached network and resolve the port on
+//             the attached one, negative handles are invalid
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_net_binding_lookup() {
+    let net_id = 9001;
+    attach_net(net_id, false);
+    assert!(!net_available(net_id));
+    assert!(lookup(net_id, "localhost:80").is_err());
+
+    attach_net(net_id, true);
+    assert!(net_available(net_id));
+    let addrs = lookup(net_id, "localhost:80").unwrap();
+    assert!(!addrs.is_empty());
+    assert!(addrs.iter().all(|addr| addr.port() == 80));
+    assert!(lookup(net_id, "localhost").is_err());
+    attach_net(net_id, false);
+
+    assert!(is_valid_net_id(0));
+    assert!(is_valid_net_id(100));
+    assert!(!is_valid_net_id(-1));
+}
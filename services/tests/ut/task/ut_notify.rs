@@ -54,6 +54,10 @@ fn ut_waiting_cause_enum_values() {
     assert_eq!(WaitingCause::Network as u8, 1);
     assert_eq!(WaitingCause::AppState as u8, 2);
     assert_eq!(WaitingCause::UserState as u8, 3);
+    assert_eq!(WaitingCause::RateLimit as u8, 4);
+    assert_eq!(WaitingCause::Battery as u8, 5);
+    assert_eq!(WaitingCause::CaptivePortal as u8, 6);
+    assert_eq!(WaitingCause::DeviceCondition as u8, 7);
 }
 
 // @tc.name: ut_each_file_status_create_empty_files
@@ -90,6 +94,7 @@ fn ut_each_file_status_create_single_file() {
         mime_type: "text/plain".to_string(),
         is_user_file: false,
         fd: None,
+        priority: 0,
     }];
     let result = EachFileStatus::create_each_file_status(&file_specs, 0, Reason::IoError);
     assert_eq!(result.len(), 1);
@@ -117,6 +122,7 @@ fn ut_each_file_status_create_multiple_files_index_zero() {
             mime_type: "text/plain".to_string(),
             is_user_file: false,
             fd: None,
+            priority: 0,
         },
         FileSpec {
             name: "file2.txt".to_string(),
@@ -125,6 +131,7 @@ fn ut_each_file_status_create_multiple_files_index_zero() {
             mime_type: "text/plain".to_string(),
             is_user_file: false,
             fd: None,
+            priority: 0,
         },
     ];
     let result = EachFileStatus::create_each_file_status(&file_specs, 0, Reason::NetworkOffline);
@@ -152,6 +159,7 @@ fn ut_each_file_status_create_index_middle() {
             mime_type: "text/plain".to_string(),
             is_user_file: false,
             fd: None,
+            priority: 0,
         },
         FileSpec {
             name: "file2.txt".to_string(),
@@ -160,6 +168,7 @@ fn ut_each_file_status_create_index_middle() {
             mime_type: "text/plain".to_string(),
             is_user_file: false,
             fd: None,
+            priority: 0,
         },
         FileSpec {
             name: "file3.txt".to_string(),
@@ -168,6 +177,7 @@ fn ut_each_file_status_create_index_middle() {
             mime_type: "text/plain".to_string(),
             is_user_file: false,
             fd: None,
+            priority: 0,
         },
     ];
     let result = EachFileStatus::create_each_file_status(&file_specs, 2, Reason::RequestError);
@@ -196,6 +206,7 @@ fn ut_each_file_status_create_index_beyond_length() {
             mime_type: "text/plain".to_string(),
             is_user_file: false,
             fd: None,
+            priority: 0,
         },
         FileSpec {
             name: "file2.txt".to_string(),
@@ -204,6 +215,7 @@ fn ut_each_file_status_create_index_beyond_length() {
             mime_type: "text/plain".to_string(),
             is_user_file: false,
             fd: None,
+            priority: 0,
         },
     ];
     let result = EachFileStatus::create_each_file_status(&file_specs, 5, Reason::UploadFileError);
@@ -424,6 +436,7 @@ fn ut_notify_data_creation() {
             mime_type: "text/plain".to_string(),
             is_user_file: false,
             fd: None,
+            priority: 0,
         },
     ];
     let each_file_status = EachFileStatus::create_each_file_status(&file_specs, 0, Reason::Default);
@@ -438,6 +451,9 @@ fn ut_notify_data_creation() {
         each_file_status,
         task_id,
         uid,
+        response_body: None,
+        eta_ms: None,
+        client_tag: String::new(),
     };
 
     assert_eq!(notify_data.bundle, bundle);
@@ -537,6 +553,7 @@ fn ut_notify_data_clone() {
         mime_type: "text/plain".to_string(),
         is_user_file: false,
         fd: None,
+        priority: 0,
     }];
     let each_file_status = EachFileStatus::create_each_file_status(&file_specs, 0, Reason::Default);
 
@@ -548,6 +565,9 @@ fn ut_notify_data_clone() {
         each_file_status,
         task_id: 999,
         uid: 1001,
+        response_body: None,
+        eta_ms: None,
+        client_tag: String::new(),
     };
 
     let cloned = original.clone();
@@ -609,6 +629,7 @@ fn ut_each_file_status_empty_path() {
         mime_type: "".to_string(),
         is_user_file: false,
         fd: None,
+        priority: 0,
     }];
     let result = EachFileStatus::create_each_file_status(&file_specs, 0, Reason::Default);
     assert_eq!(result[0].path, "");
@@ -656,6 +677,7 @@ fn ut_each_file_status_unicode_path() {
         mime_type: "text/plain".to_string(),
         is_user_file: false,
         fd: None,
+        priority: 0,
     }];
     let result = EachFileStatus::create_each_file_status(&file_specs, 0, Reason::Default);
     assert_eq!(result[0].path, "/tmp/测试文件.txt");
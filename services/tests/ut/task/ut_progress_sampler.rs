@@ -0,0 +1,163 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::thread;
+
+use super::*;
+
+const MB: usize = 1024 * 1024;
+const CHUNK: usize = 64 * 1024;
+
+/// Writes `size` bytes in chunks, materializing the counter every
+/// `chunks_per_sample` chunks, and returns the number of chunks.
+fn write(
+    counter: &ProgressCounter,
+    progress: &Mutex<Progress>,
+    size: usize,
+    chunks_per_sample: usize,
+) -> usize {
+    let chunks = size / CHUNK;
+    for chunk in 1..=chunks {
+        counter.add(CHUNK);
+        if chunk % chunks_per_sample == 0 {
+            counter.materialize(&mut progress.lock().unwrap());
+        }
+    }
+    counter.sync(progress);
+    chunks
+}
+
+// @tc.name: ut_progress_sampler_locks_per_mb
+// @tc.desc: Test the progress lock acquisitions per MB of a download
+// @tc.precon: NA
+// @tc.step: 1. Write 8 MB locking the progress for every chunk
+//           2. Write 8 MB materializing once per sample of 32 chunks
+// @tc.expect: Per-chunk updates lock once per chunk, sampling locks at most
+// once per 16 chunks, both reaching the same totals
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_progress_sampler_locks_per_mb() {
+    let before = ProgressCounter::default();
+    let before_progress = Mutex::new(Progress::new(vec![(8 * MB) as i64]));
+    let chunks = write(&before, &before_progress, 8 * MB, 1);
+    assert_eq!(before.locks(), chunks);
+
+    let after = ProgressCounter::default();
+    let after_progress = Mutex::new(Progress::new(vec![(8 * MB) as i64]));
+    let chunks = write(&after, &after_progress, 8 * MB, 32);
+    let locks_per_mb = after.locks() as f64 / 8.0;
+    let chunks_per_mb = chunks as f64 / 8.0;
+    assert!(locks_per_mb * 16.0 <= chunks_per_mb);
+
+    let before = before_progress.lock().unwrap();
+    let after = after_progress.lock().unwrap();
+    assert_eq!(after.processed, before.processed);
+    assert_eq!(after.common_data.total_processed, 8 * MB);
+}
+
+// @tc.name: ut_progress_sampler_sync_idle
+// @tc.desc: Test syncing a counter without counted bytes
+// @tc.precon: NA
+// @tc.step: 1. Sync an empty counter
+//           2. Count bytes and sync again
+// @tc.expect: The empty counter does not take the lock, the second sync moves
+// the bytes into the progress and leaves the counter empty
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_progress_sampler_sync_idle() {
+    let counter = ProgressCounter::default();
+    let progress = Mutex::new(Progress::new(vec![100]));
+    counter.sync(&progress);
+    assert_eq!(counter.locks(), 0);
+
+    counter.add(40);
+    counter.sync(&progress);
+    assert_eq!(counter.locks(), 1);
+    assert_eq!(counter.pending(), 0);
+    let progress = progress.lock().unwrap();
+    assert_eq!(progress.processed, vec![40]);
+    assert_eq!(progress.common_data.total_processed, 40);
+}
+
+// @tc.name: ut_progress_sampler_concurrent_totals
+// @tc.desc: Test the final totals of bytes counted while sampling
+// @tc.precon: NA
+// @tc.step: 1. Count bytes from several threads while another one samples
+//           2. Sync the counter after all threads finished
+// @tc.expect: The progress holds exactly the bytes counted
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_progress_sampler_concurrent_totals() {
+    const WRITERS: usize = 4;
+    const CHUNKS: usize = 10_000;
+    let counter = Arc::new(ProgressCounter::default());
+    let progress = Arc::new(Mutex::new(Progress::new(vec![-1])));
+
+    let writers: Vec<_> = (0..WRITERS)
+        .map(|i| {
+            let counter = counter.clone();
+            thread::spawn(move || {
+                for _ in 0..CHUNKS {
+                    counter.add(i + 1);
+                }
+            })
+        })
+        .collect();
+    let sampler = {
+        let (counter, progress) = (counter.clone(), progress.clone());
+        thread::spawn(move || {
+            for _ in 0..1000 {
+                counter.materialize(&mut progress.lock().unwrap());
+            }
+        })
+    };
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    sampler.join().unwrap();
+    counter.sync(&progress);
+
+    let expected = CHUNKS * (1..=WRITERS).sum::<usize>();
+    let progress = progress.lock().unwrap();
+    assert_eq!(progress.processed, vec![expected]);
+    assert_eq!(progress.common_data.total_processed, expected);
+}
+
+// @tc.name: ut_progress_sampler_take
+// @tc.desc: Test dropping counted bytes on a progress reset
+// @tc.precon: NA
+// @tc.step: 1. Count bytes, then take them while resetting the progress
+//           2. Count more bytes and sync
+// @tc.expect: Only the bytes counted after the reset reach the progress
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_progress_sampler_take() {
+    let counter = ProgressCounter::default();
+    let progress = Mutex::new(Progress::new(vec![100]));
+    counter.add(30);
+    {
+        let mut progress = progress.lock().unwrap();
+        assert_eq!(counter.take(), 30);
+        progress.processed = vec![0];
+        progress.common_data.total_processed = 0;
+    }
+    counter.add(20);
+    counter.sync(&progress);
+    let progress = progress.lock().unwrap();
+    assert_eq!(progress.processed, vec![20]);
+    assert_eq!(progress.common_data.total_processed, 20);
+}
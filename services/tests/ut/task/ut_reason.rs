@@ -49,6 +49,17 @@ fn ut_reason_enum_values() {
     assert_eq!(Reason::AppAccount.repr, 29);
     assert_eq!(Reason::NetworkAppAccount.repr, 30);
     assert_eq!(Reason::LowSpeed.repr, 31);
+    assert_eq!(Reason::RateLimited.repr, 32);
+    assert_eq!(Reason::DurabilityCheckFailed.repr, 33);
+    assert_eq!(Reason::UnexpectedContentType.repr, 34);
+    assert_eq!(Reason::PreconditionFailed.repr, 35);
+    assert_eq!(Reason::NotCharging.repr, 36);
+    assert_eq!(Reason::RetryDeadlineExceeded.repr, 37);
+    assert_eq!(Reason::SizeLimitExceeded.repr, 38);
+    assert_eq!(Reason::CaptivePortal.repr, 39);
+    assert_eq!(Reason::BoundNetworkUnavailable.repr, 40);
+    assert_eq!(Reason::CrossHostRedirectLimit.repr, 41);
+    assert_eq!(Reason::DeviceCondition.repr, 42);
 }
 
 // @tc.name: ut_reason_from_u8_valid_values
@@ -89,6 +100,17 @@ fn ut_reason_from_u8_valid_values() {
     assert_eq!(Reason::from(29), Reason::AppAccount);
     assert_eq!(Reason::from(30), Reason::NetworkAppAccount);
     assert_eq!(Reason::from(31), Reason::LowSpeed);
+    assert_eq!(Reason::from(32), Reason::RateLimited);
+    assert_eq!(Reason::from(33), Reason::DurabilityCheckFailed);
+    assert_eq!(Reason::from(34), Reason::UnexpectedContentType);
+    assert_eq!(Reason::from(35), Reason::PreconditionFailed);
+    assert_eq!(Reason::from(36), Reason::NotCharging);
+    assert_eq!(Reason::from(37), Reason::RetryDeadlineExceeded);
+    assert_eq!(Reason::from(38), Reason::SizeLimitExceeded);
+    assert_eq!(Reason::from(39), Reason::CaptivePortal);
+    assert_eq!(Reason::from(40), Reason::BoundNetworkUnavailable);
+    assert_eq!(Reason::from(41), Reason::CrossHostRedirectLimit);
+    assert_eq!(Reason::from(42), Reason::DeviceCondition);
 }
 
 // @tc.name: ut_reason_from_u8_invalid_values
@@ -102,7 +124,7 @@ fn ut_reason_from_u8_valid_values() {
 // @tc.level: Level 2
 #[test]
 fn ut_reason_from_u8_invalid_values() {
-    let invalid_values = vec![2, 3, 9, 13, 22, 32, 100, 200, 255];
+    let invalid_values = vec![2, 3, 9, 13, 22, 43, 100, 200, 255];
     for value in invalid_values {
         assert_eq!(Reason::from(value), Reason::OthersError);
     }
@@ -146,6 +168,41 @@ fn ut_reason_to_str_all_variants() {
     assert_eq!(Reason::AppAccount.to_str(), "The app is background or terminate and the account is stopped");
     assert_eq!(Reason::NetworkAppAccount.to_str(), "NetWork is offline and the app is background or terminate and the account is stopped");
     assert_eq!(Reason::LowSpeed.to_str(), "Below low speed limit");
+    assert_eq!(Reason::RateLimited.to_str(), "Rate limited by the server");
+    assert_eq!(
+        Reason::DurabilityCheckFailed.to_str(),
+        "Failed to sync the file to disk"
+    );
+    assert_eq!(
+        Reason::UnexpectedContentType.to_str(),
+        "Unexpected content type"
+    );
+    assert_eq!(
+        Reason::PreconditionFailed.to_str(),
+        "Upload precondition failed"
+    );
+    assert_eq!(Reason::NotCharging.to_str(), "The device is not charging");
+    assert_eq!(
+        Reason::RetryDeadlineExceeded.to_str(),
+        "Retry deadline exceeded"
+    );
+    assert_eq!(Reason::SizeLimitExceeded.to_str(), "Size limit exceeded");
+    assert_eq!(
+        Reason::CaptivePortal.to_str(),
+        "The network requires signing in"
+    );
+    assert_eq!(
+        Reason::BoundNetworkUnavailable.to_str(),
+        "The bound network is unavailable"
+    );
+    assert_eq!(
+        Reason::CrossHostRedirectLimit.to_str(),
+        "Too many redirects to another host"
+    );
+    assert_eq!(
+        Reason::DeviceCondition.to_str(),
+        "The device is too hot or not charging"
+    );
 }
 
 // @tc.name: ut_reason_partial_eq
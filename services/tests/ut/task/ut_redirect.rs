@@ -0,0 +1,164 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ylong_http_client::async_impl::{Body, RequestBuilder};
+
+use super::*;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+const ORIGIN: &str = "http://example.com/file";
+
+fn redirect_to(url: &str) -> Request {
+    RequestBuilder::new()
+        .method("GET")
+        .url(url)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn hop(from_url: &str, to_url: &str, status: u32) -> RedirectHop {
+    RedirectHop {
+        from_url: from_url.to_string(),
+        to_url: to_url.to_string(),
+        status,
+    }
+}
+
+// @tc.name: ut_redirect_chain
+// @tc.desc: Test the hops of a multi-hop redirect chain are taken in order
+// @tc.precon: NA
+// @tc.step: 1. Record a chain of three redirects with their statuses
+//           2. Take the hops of the task twice
+// @tc.expect: Each hop starts from the previous target with its own status,
+// the hops are taken once
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_redirect_chain() {
+    let task_id = TaskIdGenerator::generate();
+    let chain = [
+        ("https://example.com/file", 301),
+        ("https://cdn.example.com/file", 302),
+        ("https://edge.example.com/file?sig=1", 307),
+    ];
+    for (to_url, status) in chain {
+        record_status(task_id, status);
+        record_hop(task_id, ORIGIN, to_url.to_string());
+    }
+
+    assert_eq!(
+        take_hops(task_id),
+        vec![
+            hop(ORIGIN, "https://example.com/file", 301),
+            hop(
+                "https://example.com/file",
+                "https://cdn.example.com/file",
+                302
+            ),
+            hop(
+                "https://cdn.example.com/file",
+                "https://edge.example.com/file?sig=1",
+                307
+            ),
+        ]
+    );
+    assert!(take_hops(task_id).is_empty());
+}
+
+// @tc.name: ut_redirect_next_request
+// @tc.desc: Test a request after the hops were taken starts a new chain
+// @tc.precon: NA
+// @tc.step: 1. Record and take a redirect of a task
+//           2. Record another redirect of the task and one of another task
+// @tc.expect: The new chain starts from the origin, chains of tasks are kept
+// apart
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_redirect_next_request() {
+    let task_id = TaskIdGenerator::generate();
+    let other = TaskIdGenerator::generate();
+    record_status(task_id, 302);
+    record_hop(task_id, ORIGIN, "http://a.example.com/".to_string());
+    assert_eq!(take_hops(task_id).len(), 1);
+
+    record_status(task_id, 308);
+    record_hop(task_id, ORIGIN, "http://b.example.com/".to_string());
+    record_status(other, 301);
+    record_hop(other, ORIGIN, "http://c.example.com/".to_string());
+    assert_eq!(
+        take_hops(task_id),
+        vec![hop(ORIGIN, "http://b.example.com/", 308)]
+    );
+    assert_eq!(
+        take_hops(other),
+        vec![hop(ORIGIN, "http://c.example.com/", 301)]
+    );
+}
+
+// @tc.name: ut_redirect_recorder_drop
+// @tc.desc: Test dropping the recorder of a task discards its hops
+// @tc.precon: NA
+// @tc.step: 1. Record a redirect of a task and drop the recorder of the task
+// @tc.expect: No hop is left for the task
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_redirect_recorder_drop() {
+    let task_id = TaskIdGenerator::generate();
+    let recorder = RedirectRecorder::new(task_id, ORIGIN, None);
+    record_status(task_id, 302);
+    record_hop(task_id, ORIGIN, "http://a.example.com/".to_string());
+    drop(recorder);
+    assert!(take_hops(task_id).is_empty());
+}
+
+// @tc.name: ut_redirect_cross_host_limit
+// @tc.desc: Test redirects beyond the cross-host cap of a task fail
+// @tc.precon: NA
+// @tc.step: 1. Redirect many times within the host of the task
+//           2. Redirect to other hosts until the cap of two is exceeded
+// @tc.expect: Same-host redirects pass, the third host change fails with
+// the cross-host error
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_redirect_cross_host_limit() {
+    let task_id = TaskIdGenerator::generate();
+    let limit = CrossHostLimit::new(task_id, ORIGIN, 2, None);
+    for i in 0..10 {
+        let url = format!("http://EXAMPLE.com/hop{}", i);
+        assert!(limit.intercept_redirect_request(&redirect_to(&url)).is_ok());
+    }
+    let chain = [
+        "http://cdn.example.com/file",
+        "http://cdn.example.com/file?sig=1",
+        "http://user@edge.example.com/file",
+    ];
+    for url in chain {
+        assert!(limit.intercept_redirect_request(&redirect_to(url)).is_ok());
+    }
+    let err = limit
+        .intercept_redirect_request(&redirect_to("http://tracker.example.net/file"))
+        .unwrap_err();
+    assert!(format!("{}", err).contains(CROSS_HOST_LIMIT_EXCEEDED));
+
+    // A new request of the task starts a new chain.
+    reset_host_changes(task_id);
+    assert!(limit
+        .intercept_redirect_request(&redirect_to("http://cdn.example.com/file"))
+        .is_ok());
+    drop(limit);
+    assert_eq!(count_host_change(task_id, ORIGIN, ORIGIN), 0);
+    reset_host_changes(task_id);
+}
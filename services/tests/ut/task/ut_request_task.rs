@@ -11,7 +11,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::task::request_task::change_upload_size;
+use std::fs::File;
+use std::sync::atomic::Ordering;
+
+use ylong_runtime::sync::mpsc::unbounded_channel;
+
+use crate::config::{
+    Action, ConfigBuilder, MeteredOverride, Mode, TlsProtocol, MAX_CLIENT_TAG_LEN,
+};
+use crate::error::ErrorCode;
+use crate::service::client::ClientManagerEntry;
+use crate::task::request_task::{change_upload_size, check_config, get_rest_time, RequestTask};
 
 // @tc.name: ut_upload_size
 // @tc.desc: Test the change_upload_size function with various parameters
@@ -35,4 +45,203 @@ fn ut_upload_size() {
     assert_eq!(change_upload_size(0, 30, 30), 30);
     assert_eq!(change_upload_size(0, 0, 0), 0);
     assert_eq!(change_upload_size(10, 9, 100), 100);
-}
\ No newline at end of file
+}
+// @tc.name: ut_effective_speed_limit
+// @tc.desc: Test the effective speed limit combines QoS and user limits
+// @tc.precon: NA
+// @tc.step: 1. Build a download task
+//           2. Set the QoS tier limit and the user max speed in turn
+//           3. Check the effective limit and the reported task info
+// @tc.expect: The lower non-zero limit is applied and 0 means unlimited
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_effective_speed_limit() {
+    let _ = std::fs::create_dir("test_files/");
+    let file = File::create("test_files/ut_effective_speed_limit.txt").unwrap();
+    let config = ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .file_spec(file)
+        .url("http://127.0.0.1/ut_effective_speed_limit")
+        .build();
+    let (tx, _rx) = unbounded_channel();
+    let rest_time = get_rest_time(&config, 0);
+    let (files, client, used_family) = check_config(&config, rest_time).unwrap();
+    let task = RequestTask::new(
+        config,
+        files,
+        client,
+        used_family,
+        ClientManagerEntry::new(tx),
+        false,
+        rest_time,
+    );
+
+    assert_eq!(task.effective_speed_limit(), 0);
+
+    task.speed_limit(800 * 1024);
+    assert_eq!(task.effective_speed_limit(), 800 * 1024);
+
+    task.max_speed.store(100 * 1024, Ordering::SeqCst);
+    assert_eq!(task.effective_speed_limit(), 100 * 1024);
+    assert_eq!(task.info().effective_speed_limit, 100 * 1024);
+
+    task.speed_limit(0);
+    assert_eq!(task.effective_speed_limit(), 100 * 1024);
+
+    task.max_speed.store(1024 * 1024, Ordering::SeqCst);
+    task.speed_limit(400 * 1024);
+    assert_eq!(task.info().effective_speed_limit, 400 * 1024);
+}
+
+// @tc.name: ut_metered_override_requires_api10
+// @tc.desc: Test the metered override is only accepted for API10 tasks
+// @tc.precon: NA
+// @tc.step: 1. Check an API9 and an API10 config overriding the metered flag
+// @tc.expect: The API9 config fails the parameter check, the API10 one passes
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_metered_override_requires_api10() {
+    let _ = std::fs::create_dir("test_files/");
+    let config = |version: u8| {
+        let file = File::create("test_files/ut_metered_override_requires_api10.txt").unwrap();
+        ConfigBuilder::new()
+            .action(Action::Download)
+            .mode(Mode::BackGround)
+            .file_spec(file)
+            .url("http://127.0.0.1/ut_metered_override_requires_api10")
+            .version(version)
+            .treat_network_as(MeteredOverride::Unmetered)
+            .build()
+    };
+
+    let api9 = config(1);
+    assert!(matches!(
+        check_config(&api9, get_rest_time(&api9, 0)),
+        Err(ErrorCode::ParameterCheck)
+    ));
+    let api10 = config(2);
+    assert!(check_config(&api10, get_rest_time(&api10, 0)).is_ok());
+}
+
+// @tc.name: ut_client_tag_too_long
+// @tc.desc: Test client tags longer than the limit are rejected
+// @tc.precon: NA
+// @tc.step: 1. Check configs with a tag of the maximum length and one byte more
+// @tc.expect: The longer tag fails the parameter check, the other one passes
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_client_tag_too_long() {
+    let _ = std::fs::create_dir("test_files/");
+    let config = |len: usize| {
+        let file = File::create("test_files/ut_client_tag_too_long.txt").unwrap();
+        ConfigBuilder::new()
+            .action(Action::Download)
+            .mode(Mode::BackGround)
+            .file_spec(file)
+            .url("http://127.0.0.1/ut_client_tag_too_long")
+            .client_tag(&"t".repeat(len))
+            .build()
+    };
+
+    let longest = config(MAX_CLIENT_TAG_LEN);
+    assert!(check_config(&longest, get_rest_time(&longest, 0)).is_ok());
+    let too_long = config(MAX_CLIENT_TAG_LEN + 1);
+    assert!(matches!(
+        check_config(&too_long, get_rest_time(&too_long, 0)),
+        Err(ErrorCode::ParameterCheck)
+    ));
+}
+
+// @tc.name: ut_tls_versions_invalid
+// @tc.desc: Test contradicting TLS settings are rejected at creation
+// @tc.precon: NA
+// @tc.step: 1. Check a config with the lowest TLS version above the highest
+//           2. Check a config with a cipher list and TLS 1.3 as lowest version
+//           3. Check a config with a malformed cipher list
+//           4. Check a config with a TLS 1.2 range and a cipher list
+// @tc.expect: The contradicting configs fail the parameter check, the last one
+// passes
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_tls_versions_invalid() {
+    let _ = std::fs::create_dir("test_files/");
+    let config = |min: TlsProtocol, max: TlsProtocol, ciphers: &str| {
+        let file = File::create("test_files/ut_tls_versions_invalid.txt").unwrap();
+        ConfigBuilder::new()
+            .action(Action::Download)
+            .mode(Mode::BackGround)
+            .file_spec(file)
+            .url("https://127.0.0.1/ut_tls_versions_invalid")
+            .min_tls_version(min)
+            .max_tls_version(max)
+            .tls_ciphers(ciphers)
+            .build()
+    };
+    let rejected = |config| {
+        matches!(
+            check_config(&config, get_rest_time(&config, 0)),
+            Err(ErrorCode::ParameterCheck)
+        )
+    };
+
+    assert!(rejected(config(TlsProtocol::Tls13, TlsProtocol::Tls12, "")));
+    assert!(rejected(config(
+        TlsProtocol::Tls13,
+        TlsProtocol::Tls13,
+        "ECDHE-RSA-AES128-GCM-SHA256"
+    )));
+    assert!(rejected(config(
+        TlsProtocol::Tls12,
+        TlsProtocol::Tls13,
+        "ECDHE-RSA-AES128-GCM-SHA256::"
+    )));
+    let valid = config(
+        TlsProtocol::Tls12,
+        TlsProtocol::Tls12,
+        "ECDHE-RSA-AES128-GCM-SHA256:ECDHE-RSA-AES256-GCM-SHA384",
+    );
+    assert!(check_config(&valid, get_rest_time(&valid, 0)).is_ok());
+}
+
+// @tc.name: ut_method_invalid
+// @tc.desc: Test methods are checked against the HTTP token rules
+// @tc.precon: NA
+// @tc.step: 1. Check configs with methods holding illegal characters
+//           2. Check configs with standard, custom and no methods
+//           3. Check the methods the requests are sent with
+// @tc.expect: Methods with illegal characters fail the parameter check,
+//             standard methods are sent in upper case and custom ones as given
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_method_invalid() {
+    let _ = std::fs::create_dir("test_files/");
+    let config = |method: &str| {
+        let file = File::create("test_files/ut_method_invalid.txt").unwrap();
+        ConfigBuilder::new()
+            .action(Action::Download)
+            .mode(Mode::BackGround)
+            .file_spec(file)
+            .url("http://127.0.0.1/ut_method_invalid")
+            .method(method)
+            .build()
+    };
+
+    for method in ["GET /", "PO ST", "PATCH\r\n", "DELETE:", "M\u{e9}THOD"] {
+        let config = config(method);
+        assert!(matches!(
+            check_config(&config, get_rest_time(&config, 0)),
+            Err(ErrorCode::ParameterCheck)
+        ));
+    }
+    for (method, sent) in [("", "GET"), ("patch", "PATCH"), ("PROPFIND", "PROPFIND")] {
+        let config = config(method);
+        assert!(check_config(&config, get_rest_time(&config, 0)).is_ok());
+        assert_eq!(config.request_method(), sent);
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::utils::task_id_generator::TaskIdGenerator;
+
+// @tc.name: ut_resume_with_offset
+// @tc.desc: Test the offset parameter is appended or replaced in a URL
+// @tc.precon: NA
+// @tc.step: 1. Set the offset of URLs without, with and with another query
+//           2. Set the offset of a URL with a fragment
+// @tc.expect: The parameter is set once, other parameters and the fragment
+// are kept
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_resume_with_offset() {
+    assert_eq!(
+        with_offset("http://example.com/file", "offset", 10),
+        "http://example.com/file?offset=10"
+    );
+    assert_eq!(
+        with_offset("http://example.com/file?offset=5&sig=1", "offset", 10),
+        "http://example.com/file?sig=1&offset=10"
+    );
+    assert_eq!(
+        with_offset("http://example.com/file?sig=1", "start", 0),
+        "http://example.com/file?sig=1&start=0"
+    );
+    assert_eq!(
+        with_offset("http://example.com/file?offsets=1#part", "offset", 7),
+        "http://example.com/file?offsets=1&offset=7#part"
+    );
+}
+
+// @tc.name: ut_resume_content_range_start
+// @tc.desc: Test the first byte position is parsed from a Content-Range
+// @tc.precon: NA
+// @tc.step: 1. Parse valid and invalid Content-Range values
+// @tc.expect: Valid values give their first byte position, others give None
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_resume_content_range_start() {
+    assert_eq!(content_range_start("bytes 100-199/200"), Some(100));
+    assert_eq!(content_range_start(" bytes 0-9/*"), Some(0));
+    assert_eq!(content_range_start("bytes */200"), None);
+    assert_eq!(content_range_start("items 1-2/3"), None);
+}
+
+// @tc.name: ut_resume_skip_overlap
+// @tc.desc: Test the expected overlap is discarded across several chunks
+// @tc.precon: NA
+// @tc.step: 1. Expect an overlap of four bytes
+//           2. Check chunks holding part of the overlap and the new bytes
+// @tc.expect: The overlapping bytes are skipped and the overlap finishes
+// matched
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_resume_skip_overlap() {
+    let task_id = TaskIdGenerator::generate();
+    assert_eq!(skip_overlap(task_id, b"abc"), Some(0));
+
+    expect_overlap(task_id, 6, b"wxyz".to_vec());
+    assert_eq!(requested_offset(task_id), Some(6));
+    assert_eq!(skip_overlap(task_id, b"wx"), Some(2));
+    assert_eq!(skip_overlap(task_id, b"yz01"), Some(2));
+    assert_eq!(skip_overlap(task_id, b"23"), Some(0));
+    assert!(finish_overlap(task_id));
+    assert_eq!(requested_offset(task_id), None);
+}
+
+// @tc.name: ut_resume_overlap_mismatch
+// @tc.desc: Test a response not starting with the overlap is detected
+// @tc.precon: NA
+// @tc.step: 1. Expect an overlap and check a chunk starting elsewhere
+//           2. Expect an overlap and receive only part of it
+// @tc.expect: Mismatched and incomplete overlaps do not finish matched
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_resume_overlap_mismatch() {
+    let task_id = TaskIdGenerator::generate();
+    expect_overlap(task_id, 0, b"wxyz".to_vec());
+    assert_eq!(skip_overlap(task_id, b"abcd"), None);
+    assert_eq!(skip_overlap(task_id, b"wxyz"), None);
+    assert!(!finish_overlap(task_id));
+
+    expect_overlap(task_id, 0, b"wxyz".to_vec());
+    assert_eq!(skip_overlap(task_id, b"wx"), Some(2));
+    assert!(!finish_overlap(task_id));
+    assert!(finish_overlap(task_id));
+}
@@ -0,0 +1,119 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const CAP: Duration = Duration::from_millis(DEFAULT_RETRY_AFTER_CAP_MS);
+
+/// Sun, 06 Nov 1994 08:49:37 GMT
+const DATE_SECS: u64 = 784_111_777;
+
+fn at(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+// @tc.name: ut_retry_after_seconds
+// @tc.desc: Test delay seconds are used as the delay
+// @tc.precon: NA
+// @tc.step: 1. Parse `Retry-After: 120` and a zero delay
+// @tc.expect: The delays are 120 and 0 seconds
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_retry_after_seconds() {
+    let now = SystemTime::now();
+    assert_eq!(
+        retry_after_delay("120", now, CAP),
+        Some(Duration::from_secs(120))
+    );
+    assert_eq!(retry_after_delay(" 0 ", now, CAP), Some(Duration::ZERO));
+}
+
+// @tc.name: ut_retry_after_http_date
+// @tc.desc: Test HTTP dates give the time left until the date
+// @tc.precon: NA
+// @tc.step: 1. Parse an HTTP date 90 seconds in the future
+//           2. Parse an HTTP date in the past
+// @tc.expect: The delay is 90 seconds for the future date and zero for the
+// past date
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_retry_after_http_date() {
+    let date = "Sun, 06 Nov 1994 08:49:37 GMT";
+    assert_eq!(
+        retry_after_delay(date, at(DATE_SECS - 90), CAP),
+        Some(Duration::from_secs(90))
+    );
+    assert_eq!(
+        retry_after_delay(date, at(DATE_SECS + 90), CAP),
+        Some(Duration::ZERO)
+    );
+    // Leap day and the first day of the epoch
+    assert_eq!(
+        parse_http_date("Thu, 29 Feb 2024 00:00:00 GMT"),
+        Some(at(1_709_164_800))
+    );
+    assert_eq!(
+        parse_http_date("Thu, 01 Jan 1970 00:00:00 GMT"),
+        Some(UNIX_EPOCH)
+    );
+}
+
+// @tc.name: ut_retry_after_cap
+// @tc.desc: Test delays above the cap are clamped
+// @tc.precon: NA
+// @tc.step: 1. Parse delay seconds and a date beyond the cap
+// @tc.expect: Both delays equal the cap
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_retry_after_cap() {
+    let now = at(DATE_SECS - 86_400);
+    assert_eq!(retry_after_delay("86400", now, CAP), Some(CAP));
+    assert_eq!(
+        retry_after_delay("99999999999999999999999", now, CAP),
+        Some(CAP)
+    );
+    assert_eq!(
+        retry_after_delay("Sun, 06 Nov 1994 08:49:37 GMT", now, CAP),
+        Some(CAP)
+    );
+}
+
+// @tc.name: ut_retry_after_invalid
+// @tc.desc: Test malformed values are rejected
+// @tc.precon: NA
+// @tc.step: 1. Parse negative, fractional and malformed date values
+// @tc.expect: No delay is returned
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_retry_after_invalid() {
+    let now = SystemTime::now();
+    for value in [
+        "",
+        "-1",
+        "1.5",
+        "soon",
+        "Sun, 06 Nov 1994 08:49:37 UTC",
+        "Sun, 6 Nov 1994 08:49:37 GMT",
+        "Sun, 06 Foo 1994 08:49:37 GMT",
+        "Sun, 06 Nov 1994 24:49:37 GMT",
+        "Sunday, 06-Nov-94 08:49:37 GMT",
+    ] {
+        assert_eq!(retry_after_delay(value, now, CAP), None, "{}", value);
+    }
+    assert!(is_rate_limited(429) && is_rate_limited(503));
+    assert!(!is_rate_limited(500));
+}
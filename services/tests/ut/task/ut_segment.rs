@@ -0,0 +1,256 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use ylong_runtime::sync::mpsc::unbounded_channel;
+
+use super::*;
+use crate::config::{Action, ConfigBuilder, Mode, TaskConfig};
+use crate::it::harness::{test_body, ServerMode, TestServer};
+use crate::service::client::ClientManagerEntry;
+use crate::task::request_task::{check_config, get_rest_time};
+
+fn build_task(config: TaskConfig) -> Arc<RequestTask> {
+    let (tx, _) = unbounded_channel();
+    let client_manager = ClientManagerEntry::new(tx);
+    let rest_time = get_rest_time(&config, 0);
+    let (files, client, used_family) = check_config(&config, rest_time).unwrap();
+    Arc::new(RequestTask::new(
+        config,
+        files,
+        client,
+        used_family,
+        client_manager,
+        false,
+        rest_time,
+    ))
+}
+
+fn segment_config(url: &str, path: &str, segments: u32) -> TaskConfig {
+    let file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .unwrap();
+    ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .file_spec(file)
+        .url(url)
+        .segments(segments)
+        .build()
+}
+
+// @tc.name: ut_segment_split_ranges
+// @tc.desc: Test splitting a file into contiguous byte ranges
+// @tc.precon: NA
+// @tc.step: 1. Split sizes with different segment counts
+// @tc.expect: Ranges cover the file exactly and respect the size bounds
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_segment_split_ranges() {
+    let total = 10 * MIN_SEGMENT_SIZE + 3;
+    let ranges = split_ranges(total, 4);
+    assert_eq!(ranges.len(), 4);
+    assert_eq!(ranges.first().unwrap().0, 0);
+    assert_eq!(ranges.last().unwrap().1, total - 1);
+    for pair in ranges.windows(2) {
+        assert_eq!(pair[0].1 + 1, pair[1].0);
+    }
+
+    assert_eq!(split_ranges(total, 100).len(), MAX_SEGMENTS as usize);
+    assert_eq!(split_ranges(MIN_SEGMENT_SIZE, 4), vec![(0, MIN_SEGMENT_SIZE - 1)]);
+    assert_eq!(parse_content_range_total("bytes 0-0/1234"), Some(1234));
+    assert_eq!(parse_content_range_total("bytes 0-0/*"), None);
+    assert_eq!(parse_content_range("bytes 5-9/1234"), Some((5, 9)));
+    assert_eq!(parse_content_range("bytes */1234"), None);
+}
+
+// @tc.name: ut_segment_download_assemble
+// @tc.desc: Test a segmented download assembles ranges into the file
+// @tc.precon: NA
+// @tc.step: 1. Start a range-capable local server
+//           2. Run a download configured with four segments
+//           3. Compare the file with the served body
+// @tc.expect: Several ranges are requested and the file matches the body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_segment_download_assemble() {
+    let _ = std::fs::create_dir("test_files/");
+    let body = test_body(4 * MIN_SEGMENT_SIZE as usize + 17);
+    let server = TestServer::start(body.clone(), ServerMode::Normal);
+    let path = "test_files/ut_segment_download_assemble.txt";
+    let task = build_task(segment_config(&server.url, path, 4));
+
+    ylong_runtime::block_on(async {
+        task.progress.lock().unwrap().processed = vec![0];
+        let done = download_segmented(task.clone(), Arc::new(AtomicBool::new(false)))
+            .await
+            .unwrap();
+        assert!(done);
+    });
+
+    let ranges = server.ranges();
+    assert_eq!(ranges.len(), 5);
+    assert_eq!(ranges[0], Some(0));
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    assert_eq!(
+        task.progress.lock().unwrap().common_data.total_processed,
+        body.len()
+    );
+}
+
+// @tc.name: ut_segment_download_fallback
+// @tc.desc: Test a server without range support falls back to one stream
+// @tc.precon: NA
+// @tc.step: 1. Start a local server answering 404 to the probe
+//           2. Run a segmented download
+// @tc.expect: The segmented path reports fallback and leaves the file empty
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_segment_download_fallback() {
+    let _ = std::fs::create_dir("test_files/");
+    let server = TestServer::start(vec![], ServerMode::NotFound);
+    let path = "test_files/ut_segment_download_fallback.txt";
+    let task = build_task(segment_config(&server.url, path, 4));
+
+    ylong_runtime::block_on(async {
+        let done = download_segmented(task.clone(), Arc::new(AtomicBool::new(false)))
+            .await
+            .unwrap();
+        assert!(!done);
+    });
+    assert_eq!(std::fs::metadata(path).unwrap().len(), 0);
+}
+
+// @tc.name: ut_segment_download_short_range
+// @tc.desc: Test a segment answered with a shorter range is not written
+// @tc.precon: NA
+// @tc.step: 1. Start a server serving half of every requested range
+//           2. Run a download configured with four segments
+// @tc.expect: The download fails with a protocol error and every range is
+//             recorded as a hole
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_segment_download_short_range() {
+    let _ = std::fs::create_dir("test_files/");
+    let body = test_body(4 * MIN_SEGMENT_SIZE as usize);
+    let server = TestServer::start(body.clone(), ServerMode::HalfRange);
+    let path = "test_files/ut_segment_download_short_range.txt";
+    let task = build_task(segment_config(&server.url, path, 4));
+    let db = RequestDb::get_instance();
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, uid, action, mode, state, ctime, priority) VALUES ({}, 0, 1, 1, 0, 1, 0)",
+        task.task_id()
+    ))
+    .unwrap();
+
+    ylong_runtime::block_on(async {
+        task.progress.lock().unwrap().processed = vec![0];
+        let ret = download_segmented(task.clone(), Arc::new(AtomicBool::new(false))).await;
+        assert_eq!(ret, Err(TaskError::Failed(Reason::ProtocolError)));
+    });
+    let ranges = split_ranges(body.len() as u64, 4);
+    assert_eq!(db.query_unwritten_ranges(task.task_id()), ranges);
+}
+
+// @tc.name: ut_segment_download_resume_holes
+// @tc.desc: Test a failed segmented download releases and refetches its holes
+// @tc.precon: NA
+// @tc.step: 1. Run a preallocated download whose segments stop halfway
+//           2. Resume the recorded holes from a server serving whole ranges
+// @tc.expect: The holes are recorded and released, only they are requested
+//             on resume and the file matches the body
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_segment_download_resume_holes() {
+    let _ = std::fs::create_dir("test_files/");
+    let body = test_body(4 * MIN_SEGMENT_SIZE as usize);
+    let flaky = TestServer::start(body.clone(), ServerMode::Flaky);
+    let path = "test_files/ut_segment_download_resume_holes.txt";
+    let mut config = segment_config(&flaky.url, path, 4);
+    config.preallocate = true;
+    let task = build_task(config);
+    let db = RequestDb::get_instance();
+    db.execute(&format!(
+        "INSERT OR REPLACE INTO request_task (task_id, uid, action, mode, state, ctime, priority) VALUES ({}, 0, 1, 1, 0, 1, 0)",
+        task.task_id()
+    ))
+    .unwrap();
+
+    ylong_runtime::block_on(async {
+        task.progress.lock().unwrap().processed = vec![0];
+        let ret = download_segmented(task.clone(), Arc::new(AtomicBool::new(false))).await;
+        assert!(ret.is_err());
+    });
+    let holes = db.query_unwritten_ranges(task.task_id());
+    assert_eq!(holes.len(), 4);
+    let missing: u64 = holes.iter().map(|(begin, end)| end - begin + 1).sum();
+    assert_eq!(
+        task.progress.lock().unwrap().common_data.total_processed as u64,
+        body.len() as u64 - missing
+    );
+    let physical = sparse::disk_usage(&File::open(path).unwrap()).unwrap();
+    assert!(physical < body.len() as u64);
+    let extras = task.progress.lock().unwrap().extras.clone();
+    assert_eq!(
+        extras.get(sparse::PHYSICAL_SIZE),
+        Some(&physical.to_string())
+    );
+
+    let server = TestServer::start(body.clone(), ServerMode::Normal);
+    let file = File::options().read(true).write(true).open(path).unwrap();
+    let config = ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .file_spec(file)
+        .url(&server.url)
+        .segments(4)
+        .build();
+    let resumed = build_task(config);
+    resumed
+        .file_total_size
+        .store(body.len() as i64, Ordering::SeqCst);
+    ylong_runtime::block_on(async {
+        let done = resume_segmented(
+            resumed.clone(),
+            Arc::new(AtomicBool::new(false)),
+            holes.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(done);
+    });
+
+    let ranges = server.ranges();
+    assert_eq!(ranges.len(), holes.len() + 1);
+    for (begin, _) in holes.iter() {
+        assert!(ranges.contains(&Some(*begin)));
+    }
+    assert_eq!(std::fs::read(path).unwrap(), body);
+    assert_eq!(
+        resumed.progress.lock().unwrap().common_data.total_processed,
+        body.len()
+    );
+    db.update_unwritten_ranges(task.task_id(), &[]);
+}
@@ -0,0 +1,72 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::task::config::ConfigBuilder;
+
+// @tc.name: ut_size_limit_exceeds
+// @tc.desc: Test a size exceeds a cap only when it is larger
+// @tc.precon: NA
+// @tc.step: 1. Compare sizes below, at and above a cap
+//           2. Compare a size without cap
+// @tc.expect: Only sizes above the cap exceed it, nothing exceeds no cap
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_size_limit_exceeds() {
+    assert!(!exceeds(Some(100), 99));
+    assert!(!exceeds(Some(100), 100));
+    assert!(exceeds(Some(100), 101));
+    assert!(!exceeds(None, u64::MAX));
+}
+
+// @tc.name: ut_size_limit_download_cap
+// @tc.desc: Test the cap of a download is the lower of both caps
+// @tc.precon: NA
+// @tc.step: 1. Build configurations with no, either and both caps
+// @tc.expect: The download cap is the configured cap, the lower one if both
+//             are set, and `None` without any
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_size_limit_download_cap() {
+    assert_eq!(ConfigBuilder::new().build().download_size_cap(), None);
+    let config = ConfigBuilder::new().max_size_bytes(100).build();
+    assert_eq!(config.download_size_cap(), Some(100));
+    let config = ConfigBuilder::new().max_total_bytes(50).build();
+    assert_eq!(config.download_size_cap(), Some(50));
+    let config = ConfigBuilder::new()
+        .max_size_bytes(100)
+        .max_total_bytes(50)
+        .build();
+    assert_eq!(config.download_size_cap(), Some(50));
+}
+
+// @tc.name: ut_size_limit_upload
+// @tc.desc: Test upload files are capped each and in total
+// @tc.precon: NA
+// @tc.step: 1. Check files within and above the per file cap
+//           2. Check files each within but together above the total cap
+// @tc.expect: Files exceed the caps if one file or their sum is too large
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_size_limit_upload() {
+    let sizes = [60, 40];
+    assert!(!upload_exceeds(None, None, &sizes));
+    assert!(!upload_exceeds(Some(60), None, &sizes));
+    assert!(upload_exceeds(Some(59), None, &sizes));
+    assert!(!upload_exceeds(None, Some(100), &sizes));
+    assert!(upload_exceeds(Some(60), Some(99), &sizes));
+    assert!(!upload_exceeds(Some(100), Some(100), &[-1, 100]));
+}
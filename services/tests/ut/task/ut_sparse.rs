@@ -0,0 +1,102 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+use std::os::unix::fs::FileExt;
+
+use super::*;
+
+const LEN: usize = 1024 * 1024;
+
+fn written_file(path: &str) -> File {
+    let _ = std::fs::create_dir("test_files/");
+    let mut file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .unwrap();
+    file.write_all(&vec![1u8; LEN]).unwrap();
+    file.sync_all().unwrap();
+    file
+}
+
+// @tc.name: ut_sparse_punch_hole
+// @tc.desc: Test unwritten ranges are punched out of a file
+// @tc.precon: NA
+// @tc.step: 1. Write a file of 1 MiB
+//           2. Release the second quarter and the last quarter
+// @tc.expect: The disk usage shrinks, the size is kept and the holes read
+//             as zeros, or the file is truncated without hole punching
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_sparse_punch_hole() {
+    let path = "test_files/ut_sparse_punch_hole.txt";
+    let file = written_file(path);
+    let before = disk_usage(&file).unwrap();
+    let quarter = LEN as u64 / 4;
+    let holes = [(quarter, 2 * quarter - 1), (3 * quarter, LEN as u64 - 1)];
+
+    let punched = release_unwritten(&file, &holes).unwrap();
+    let after = disk_usage(&file).unwrap();
+    assert!(after < before);
+    if !punched {
+        assert_eq!(file.metadata().unwrap().len(), 3 * quarter);
+        return;
+    }
+    assert!(after <= before / 2);
+    assert_eq!(file.metadata().unwrap().len(), LEN as u64);
+    let mut buf = vec![1u8; quarter as usize];
+    file.read_exact_at(&mut buf, quarter).unwrap();
+    assert!(buf.iter().all(|b| *b == 0));
+    file.read_exact_at(&mut buf, 2 * quarter).unwrap();
+    assert!(buf.iter().all(|b| *b == 1));
+}
+
+// @tc.name: ut_sparse_written_end
+// @tc.desc: Test where the trailing unwritten space of a file begins
+// @tc.precon: NA
+// @tc.step: 1. Compute the end for holes inside and at the end of a file
+// @tc.expect: Only a hole reaching the end of the file moves the end
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_sparse_written_end() {
+    assert_eq!(written_end(&[], 100), 100);
+    assert_eq!(written_end(&[(10, 19)], 100), 100);
+    assert_eq!(written_end(&[(10, 19), (60, 99)], 100), 60);
+    assert_eq!(written_end(&[(10, 19), (60, 149)], 100), 60);
+    assert_eq!(written_end(&[(120, 149)], 100), 100);
+}
+
+// @tc.name: ut_sparse_record_file_sizes
+// @tc.desc: Test the logical and physical size of a file are recorded
+// @tc.precon: NA
+// @tc.step: 1. Write a file and record its sizes in extras
+// @tc.expect: Both keys hold the sizes reported by the file system
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_sparse_record_file_sizes() {
+    let path = "test_files/ut_sparse_record_file_sizes.txt";
+    let file = written_file(path);
+    let mut extras = HashMap::new();
+    record_file_sizes(&file, &mut extras);
+    assert_eq!(extras.get(LOGICAL_SIZE).unwrap(), &LEN.to_string());
+    assert_eq!(
+        extras.get(PHYSICAL_SIZE).unwrap(),
+        &disk_usage(&file).unwrap().to_string()
+    );
+}
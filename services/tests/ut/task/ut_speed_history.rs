@@ -0,0 +1,108 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const START: u64 = 1_000_000;
+
+// @tc.name: ut_speed_history_bounded
+// @tc.desc: Test the history keeps a bounded number of spaced samples
+// @tc.precon: NA
+// @tc.step: 1. Record samples closer than the sample interval
+//           2. Record more spaced samples than the history holds
+// @tc.expect: Close samples are dropped and only the latest
+//             `SPEED_HISTORY_LEN` samples are kept, oldest first
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_speed_history_bounded() {
+    let mut history = SpeedHistory::default();
+    history.record(START, 0);
+    history.record(START + 100, 10);
+    assert_eq!(
+        history.samples(),
+        vec![SpeedSample {
+            time: START,
+            processed: 0
+        }]
+    );
+
+    for i in 1..=SPEED_HISTORY_LEN as u64 * 2 {
+        history.record(START + i * 1000, i * 100);
+    }
+    let samples = history.samples();
+    assert_eq!(samples.len(), SPEED_HISTORY_LEN);
+    assert_eq!(
+        samples.last().unwrap().processed,
+        SPEED_HISTORY_LEN as u64 * 200
+    );
+    assert!(samples.windows(2).all(|pair| pair[0].time < pair[1].time));
+}
+
+// @tc.name: ut_speed_history_reset_and_finish
+// @tc.desc: Test backwards progress and the final sample of a run
+// @tc.precon: NA
+// @tc.step: 1. Record samples and then a smaller processed size
+//           2. Finish the run shortly after the last sample
+// @tc.expect: Backwards progress keeps only the new sample and the final
+//             sample is recorded despite the sample interval
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_speed_history_reset_and_finish() {
+    let mut history = SpeedHistory::default();
+    history.record(START, 500);
+    history.record(START + 1000, 1000);
+    history.record(START + 2000, 0);
+    assert_eq!(history.samples().len(), 1);
+
+    history.finish(START + 2100, 300);
+    history.finish(START + 2200, 300);
+    let samples = history.samples();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[1].processed, 300);
+}
+
+// @tc.name: ut_speed_history_recent
+// @tc.desc: Test the windows of stopped tasks and the point limit
+// @tc.precon: NA
+// @tc.step: 1. Keep the windows of more tasks than are kept
+//           2. Query the first and the last task
+//           3. Limit a window to its last points
+// @tc.expect: The first window is evicted, the last is returned and the
+//             limit keeps the newest samples
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_speed_history_recent() {
+    let base = 0x7f00_0000;
+    for i in 0..=RECENT_TASKS as u32 {
+        let sample = SpeedSample {
+            time: START,
+            processed: i as u64,
+        };
+        keep_recent(base + i, vec![sample]);
+    }
+    assert!(recent(base).is_none());
+    let last = recent(base + RECENT_TASKS as u32).unwrap();
+    assert_eq!(last[0].processed, RECENT_TASKS as u64);
+
+    let samples = (0..5)
+        .map(|i| SpeedSample {
+            time: START + i * 1000,
+            processed: i,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(last_points(samples.clone(), 2), samples[3..]);
+    assert_eq!(last_points(samples.clone(), 0), samples);
+}
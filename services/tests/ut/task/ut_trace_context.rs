@@ -0,0 +1,139 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::config::{Action, ConfigBuilder};
+
+const PARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+// @tc.name: ut_trace_context_valid
+// @tc.desc: Test only well formed trace contexts are accepted
+// @tc.precon: NA
+// @tc.step: 1. Check a version 00 and a later version `traceparent`
+//           2. Check malformed versions, ids, flags and `tracestate`
+// @tc.expect: Well formed contexts are accepted, the others are rejected
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_trace_context_valid() {
+    let context = |traceparent: &str, tracestate: Option<&str>| TraceContext {
+        traceparent: traceparent.to_string(),
+        tracestate: tracestate.map(str::to_string),
+    };
+    assert!(is_valid(&context(PARENT, None)));
+    assert!(is_valid(&context(PARENT, Some("vendor=value"))));
+    assert!(is_valid_traceparent(
+        "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"
+    ));
+    assert!(!is_valid_traceparent(
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"
+    ));
+    assert!(!is_valid_traceparent(
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+    ));
+    assert!(!is_valid_traceparent(
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01"
+    ));
+    assert!(!is_valid_traceparent(
+        "00-00000000000000000000000000000000-00f067aa0ba902b7-01"
+    ));
+    assert!(!is_valid_traceparent(
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01"
+    ));
+    assert!(!is_valid_traceparent(
+        "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01"
+    ));
+    assert!(!is_valid_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736"));
+    assert!(!is_valid(&context(PARENT, Some("vendor=a\r\nx: y"))));
+    assert!(is_valid_traceparent(&generate_traceparent()));
+}
+
+// @tc.name: ut_trace_context_inject
+// @tc.desc: Test every attempt gets a new span of the same trace
+// @tc.precon: NA
+// @tc.step: 1. Inject a context into headers twice
+//           2. Inject a context into headers holding a `traceparent`
+// @tc.expect: Each injection sends the trace id of the context with a new
+//             span id and the `tracestate`, the headers stay sorted, and a
+//             `traceparent` of the task is kept without the context
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_trace_context_inject() {
+    let context = TraceContext {
+        traceparent: PARENT.to_string(),
+        tracestate: Some("vendor=value".to_string()),
+    };
+    let base = vec![("Accept".to_string(), "*/*".to_string())];
+    let mut spans = Vec::new();
+    for _ in 0..2 {
+        let mut headers = base.clone();
+        inject(&mut headers, &context);
+        assert_eq!(headers.len(), 3);
+        assert_eq!(headers[0], base[0]);
+        assert_eq!(headers[1].0, TRACEPARENT);
+        assert_eq!(
+            headers[2],
+            (TRACESTATE.to_string(), "vendor=value".to_string())
+        );
+        assert!(is_valid_traceparent(&headers[1].1));
+        assert_eq!(trace_id(&headers[1].1), trace_id(PARENT));
+        spans.push(headers[1].1.split('-').nth(2).unwrap().to_string());
+    }
+    assert_ne!(spans[0], spans[1]);
+
+    let mut headers = vec![("TraceParent".to_string(), "user".to_string())];
+    let origin = headers.clone();
+    inject(&mut headers, &context);
+    assert_eq!(headers, origin);
+
+    let mut headers = vec![("tracestate".to_string(), "user=1".to_string())];
+    inject(&mut headers, &context);
+    assert_eq!(headers.len(), 2);
+    assert!(headers.contains(&("tracestate".to_string(), "user=1".to_string())));
+}
+
+// @tc.name: ut_trace_context_policy
+// @tc.desc: Test the tracing policy of a uid generates missing contexts
+// @tc.precon: NA
+// @tc.step: 1. Apply the policy to a task of a uid without tracing
+//           2. Enable tracing for the uid and apply the policy again
+//           3. Apply the policy to a task with its own context
+// @tc.expect: Only tasks of traced uids without a context get a generated
+//             valid context
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_trace_context_policy() {
+    let uid = 4269;
+    let mut config = ConfigBuilder::new()
+        .action(Action::Download)
+        .uid(uid)
+        .build();
+    apply_policy(&mut config);
+    assert!(config.trace_context.is_none());
+
+    set_uid_tracing(uid, true);
+    apply_policy(&mut config);
+    let generated = config.trace_context.clone().unwrap();
+    assert!(is_valid(&generated));
+
+    let mut own = ConfigBuilder::new()
+        .action(Action::Download)
+        .uid(uid)
+        .trace_context(PARENT, None)
+        .build();
+    apply_policy(&mut own);
+    assert_eq!(own.trace_context.unwrap().traceparent, PARENT);
+    set_uid_tracing(uid, false);
+}
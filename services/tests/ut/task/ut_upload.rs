@@ -42,7 +42,7 @@ fn build_task(config: TaskConfig) -> Arc<RequestTask> {
 
     let rest_time = get_rest_time(&config, 0);
 
-    let (files, client) = check_config(
+    let (files, client, used_family) = check_config(
         &config,
         rest_time,
         #[cfg(feature = "oh")]
@@ -54,6 +54,7 @@ fn build_task(config: TaskConfig) -> Arc<RequestTask> {
         config,
         files,
         client,
+        used_family,
         client_manager,
         false,
         rest_time,
@@ -306,4 +307,113 @@ fn ut_upload_index_range() {
         upload(task.clone(), Arc::new(AtomicBool::new(false))).await;
     });
     assert!(task.running_result.lock().unwrap().unwrap().is_ok());
-}
\ No newline at end of file
+}
+fn response_server(reply: &'static str) -> String {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut lines = BufReader::new(&mut stream).lines();
+            // Skips the request head and the multipart body.
+            for line in lines.by_ref() {
+                if line.unwrap().ends_with("--") {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                reply.len(),
+                reply
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    addr
+}
+
+// @tc.name: ut_upload_response_body
+// @tc.desc: Test API10 uploads keep the server response body
+// @tc.precon: NA
+// @tc.step: 1. Start a server answering with a JSON body
+//           2. Upload with the default and with a 4 byte body limit
+//           3. Check the captured body of each task
+// @tc.expect: The whole body is kept by default, 4 bytes and the truncation
+// marker with the small limit
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_upload_response_body() {
+    test_init();
+    const REPLY: &str = "{\"id\":\"12345\"}";
+    for (limit, expect, truncated) in [(64 * 1024, REPLY, false), (4, &REPLY[..4], true)] {
+        let server = response_server(REPLY);
+        let mut file = create_file("test_files/ut_upload_response_body.txt");
+        file.write_all(TEST_CONTENT.as_bytes()).unwrap();
+
+        let mut config = config(server, vec![file]);
+        config.version = crate::config::Version::API10;
+        config.response_body_limit = limit;
+        let task = build_task(config);
+        ylong_runtime::block_on(async {
+            upload(task.clone(), Arc::new(AtomicBool::new(false))).await;
+        });
+        assert!(task.running_result.lock().unwrap().unwrap().is_ok());
+        let body = task.info().response_body.unwrap();
+        assert_eq!(body.data, expect.as_bytes());
+        assert_eq!(body.truncated, truncated);
+    }
+}
+
+// @tc.name: ut_upload_form_urlencoded_with_files
+// @tc.desc: Test urlencoded form uploads reject file specs
+// @tc.precon: NA
+// @tc.step: 1. Configure an urlencoded upload with a file
+//           2. Check the configuration
+// @tc.expect: The configuration is rejected with a parameter error
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_upload_form_urlencoded_with_files() {
+    test_init();
+    let file = create_file("test_files/ut_upload_form_urlencoded.txt");
+    let mut config = config("127.0.0.1:7878".to_string(), vec![file]);
+    config.headers.insert(
+        "Content-Type".to_string(),
+        "application/x-www-form-urlencoded".to_string(),
+    );
+    assert!(config.is_form_urlencoded());
+
+    #[cfg(feature = "oh")]
+    let system_config = unsafe { SYSTEM_CONFIG_MANAGER.assume_init_ref().system_config() };
+    let ret = check_config(
+        &config,
+        get_rest_time(&config, 0),
+        #[cfg(feature = "oh")]
+        system_config,
+    );
+    assert_eq!(ret.err(), Some(crate::error::ErrorCode::ParameterCheck));
+}
+
+// @tc.name: ut_upload_form_urlencoded_header_case
+// @tc.desc: Test urlencoded form uploads are selected whatever the case of
+// the Content-Type header
+// @tc.precon: NA
+// @tc.step: 1. Configure uploads with Content-Type header names and values
+//              in different cases
+//           2. Check the configurations
+// @tc.expect: Every configuration is an urlencoded form upload
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_upload_form_urlencoded_header_case() {
+    test_init();
+    for (name, value) in [
+        ("content-type", "application/x-www-form-urlencoded"),
+        ("CONTENT-TYPE", "Application/X-WWW-Form-Urlencoded; q=1"),
+    ] {
+        let mut config = config("127.0.0.1:7878".to_string(), vec![]);
+        config.headers.insert(name.to_string(), value.to_string());
+        assert!(config.is_form_urlencoded());
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use super::*;
+
+const CHUNK: usize = 16 * 1024;
+
+/// Disk taking 2 ms for each write.
+#[derive(Default)]
+struct SlowDisk {
+    data: Vec<u8>,
+}
+
+impl Write for SlowDisk {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        std::thread::sleep(Duration::from_millis(2));
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Disk failing every write.
+struct FullDisk;
+
+impl Write for FullDisk {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::from_raw_os_error(28))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn budget(task_limit: usize, limit: usize) -> &'static BufferBudget {
+    Box::leak(Box::new(BufferBudget::new(task_limit, limit)))
+}
+
+/// Writes `count` chunks as fast as the buffer accepts them, returning how
+/// many times the writer was held back.
+async fn feed<W: Write + Send + 'static>(
+    buffer: &WriteBuffer,
+    file: &Arc<Mutex<W>>,
+    count: usize,
+    check: impl Fn(&WriteBuffer),
+) -> usize {
+    let mut throttled = 0;
+    for i in 0..count {
+        let chunk = vec![i as u8; CHUNK];
+        poll_fn(|cx| {
+            let poll = buffer.poll_write(cx, file, &chunk);
+            if poll.is_pending() {
+                throttled += 1;
+            }
+            poll
+        })
+        .await
+        .unwrap();
+        check(buffer);
+    }
+    throttled
+}
+
+// @tc.name: ut_write_buffer_task_limit
+// @tc.desc: Test a fast download to a slow disk is throttled by its task limit
+// @tc.precon: NA
+// @tc.step: 1. Feed 64 chunks of 16 KiB to a buffer limited to 4 chunks
+//           2. Drain the buffer
+// @tc.expect: The feeding is held back, the bytes in flight never exceed the
+// limit and the disk receives all chunks in order
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_write_buffer_task_limit() {
+    let budget = budget(4 * CHUNK, 64 * CHUNK);
+    let buffer = WriteBuffer::new(budget);
+    let file = Arc::new(Mutex::new(SlowDisk::default()));
+
+    let throttled = ylong_runtime::block_on(async {
+        let throttled = feed(&buffer, &file, 64, |buffer| {
+            assert!(buffer.in_flight() <= 4 * CHUNK);
+        })
+        .await;
+        buffer.drain().await.unwrap();
+        throttled
+    });
+    assert!(throttled > 0);
+    assert_eq!(buffer.in_flight(), 0);
+    assert_eq!(budget.in_flight(), 0);
+
+    let data = &file.lock().unwrap().data;
+    assert_eq!(data.len(), 64 * CHUNK);
+    for (i, chunk) in data.chunks(CHUNK).enumerate() {
+        assert!(chunk.iter().all(|b| *b == i as u8));
+    }
+}
+
+// @tc.name: ut_write_buffer_service_limit
+// @tc.desc: Test concurrent downloads are throttled by the service limit
+// @tc.precon: NA
+// @tc.step: 1. Feed 32 chunks to each of two buffers sharing a budget of
+//              4 chunks, each allowed 4 chunks on its own
+//           2. Drain both buffers
+// @tc.expect: The bytes in flight in the service never exceed the limit
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_write_buffer_service_limit() {
+    let budget = budget(4 * CHUNK, 4 * CHUNK);
+    let check = |_: &WriteBuffer| assert!(budget.in_flight() <= 4 * CHUNK);
+
+    let handles = (0..2)
+        .map(|_| {
+            ylong_runtime::spawn(async move {
+                let buffer = WriteBuffer::new(budget);
+                let file = Arc::new(Mutex::new(SlowDisk::default()));
+                feed(&buffer, &file, 32, check).await;
+                buffer.drain().await.unwrap();
+                let len = file.lock().unwrap().data.len();
+                len
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        assert_eq!(ylong_runtime::block_on(handle).unwrap(), 32 * CHUNK);
+    }
+    assert_eq!(budget.in_flight(), 0);
+}
+
+// @tc.name: ut_write_buffer_error
+// @tc.desc: Test a failed write is reported and releases the buffered bytes
+// @tc.precon: NA
+// @tc.step: 1. Queue a chunk to a disk failing every write
+//           2. Drain the buffer
+// @tc.expect: Draining returns the write error and nothing is left in flight
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_write_buffer_error() {
+    let budget = budget(4 * CHUNK, 4 * CHUNK);
+    let buffer = WriteBuffer::new(budget);
+    let file = Arc::new(Mutex::new(FullDisk));
+
+    let result = ylong_runtime::block_on(async {
+        feed(&buffer, &file, 1, |_| {}).await;
+        buffer.drain().await
+    });
+    assert_eq!(result.unwrap_err().raw_os_error(), Some(28));
+    assert_eq!(buffer.in_flight(), 0);
+    assert_eq!(budget.in_flight(), 0);
+}
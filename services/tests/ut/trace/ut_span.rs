@@ -0,0 +1,165 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use super::*;
+
+const TASK_ID: u32 = 20100001;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Start(u64, Option<u64>, String, Vec<(String, Value)>),
+    End(u64, Vec<(String, Value)>),
+}
+
+#[derive(Default)]
+struct MockTracer {
+    events: Mutex<Vec<Event>>,
+}
+
+fn owned(attributes: &[(&str, Value)]) -> Vec<(String, Value)> {
+    attributes
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect()
+}
+
+impl Tracer for MockTracer {
+    fn start(&self, id: u64, parent: Option<u64>, name: &str, attributes: &[(&str, Value)]) {
+        let event = Event::Start(id, parent, name.to_string(), owned(attributes));
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn end(&self, id: u64, attributes: &[(&str, Value)]) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(Event::End(id, owned(attributes)));
+    }
+}
+
+impl MockTracer {
+    /// Returns the events of the spans of `TASK_ID`, as tasks run by other
+    /// tests may emit spans too.
+    fn task_events(&self) -> Vec<Event> {
+        let mut ids = HashSet::new();
+        let mut events = vec![];
+        for event in self.events.lock().unwrap().drain(..) {
+            let ours = match &event {
+                Event::Start(id, None, _, attributes) => {
+                    let ours = attributes.contains(&attr("task_id", Value::Int(TASK_ID as i64)));
+                    if ours {
+                        ids.insert(*id);
+                    }
+                    ours
+                }
+                Event::Start(id, Some(parent), _, _) => {
+                    let ours = ids.contains(parent);
+                    if ours {
+                        ids.insert(*id);
+                    }
+                    ours
+                }
+                Event::End(id, _) => ids.contains(id),
+            };
+            if ours {
+                events.push(event);
+            }
+        }
+        events
+    }
+}
+
+fn attr(key: &str, value: Value) -> (String, Value) {
+    (key.to_string(), value)
+}
+
+// @tc.name: ut_span_task_lifecycle
+// @tc.desc: Test the spans of a task open and close in lifecycle order
+// @tc.precon: NA
+// @tc.step: 1. Install a mock tracer
+//           2. Create a task, connect, receive the first byte and retry
+//           3. Finish the task during the retried connection
+// @tc.expect: The task span carries the host and trace parent, child spans
+// are parented to it with their attributes, and the spans left open are
+// closed before the task span
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_span_task_lifecycle() {
+    let tracer = Arc::new(MockTracer::default());
+    set_tracer(Some(tracer.clone()));
+
+    let traceparent = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+    task_created(
+        TASK_ID,
+        "https://user@Example.com:8080/file?a=1",
+        Some(traceparent),
+    );
+    start(TASK_ID, "connect", &[]);
+    start(TASK_ID, "first_byte", &[]);
+    end(TASK_ID, "connect", &[("status", Value::Int(200))]);
+    end(TASK_ID, "first_byte", &[]);
+    // Closing a span twice emits nothing.
+    end(TASK_ID, "first_byte", &[]);
+    start(TASK_ID, "retry", &[("tries", Value::Int(1))]);
+    start(TASK_ID, "connect", &[]);
+    task_finished(TASK_ID, State::Completed, Reason::Default, 1024);
+    set_tracer(None);
+
+    let events = tracer.task_events();
+    let Event::Start(task, None, ref name, ref attributes) = events[0] else {
+        panic!("unexpected event {:?}", events[0]);
+    };
+    assert_eq!(name, "task");
+    assert_eq!(
+        attributes,
+        &vec![
+            attr("task_id", Value::Int(TASK_ID as i64)),
+            attr("url.host", Value::Str("example.com".to_string())),
+            attr("traceparent", Value::Str(traceparent.to_string())),
+        ]
+    );
+
+    let id = |index: usize| match &events[index] {
+        Event::Start(id, ..) => *id,
+        event => panic!("unexpected event {:?}", event),
+    };
+    let (connect, first_byte, retry, reconnect) = (id(1), id(2), id(5), id(6));
+    let expected = vec![
+        Event::Start(task, None, "task".to_string(), attributes.clone()),
+        Event::Start(connect, Some(task), "connect".to_string(), vec![]),
+        Event::Start(first_byte, Some(task), "first_byte".to_string(), vec![]),
+        Event::End(connect, vec![attr("status", Value::Int(200))]),
+        Event::End(first_byte, vec![]),
+        Event::Start(
+            retry,
+            Some(task),
+            "retry".to_string(),
+            vec![attr("tries", Value::Int(1))],
+        ),
+        Event::Start(reconnect, Some(task), "connect".to_string(), vec![]),
+        Event::End(reconnect, vec![]),
+        Event::End(retry, vec![]),
+        Event::End(
+            task,
+            vec![
+                attr("state", Value::Str("Completed".to_string())),
+                attr("reason", Value::Int(Reason::Default.repr as i64)),
+                attr("bytes", Value::Int(1024)),
+            ],
+        ),
+    ];
+    assert_eq!(events, expected);
+}
@@ -33,4 +33,7 @@ fn ut_enum_error_code() {
     assert_eq!(ErrorCode::TaskEnqueueErr as i32, 21900004);
     assert_eq!(ErrorCode::TaskNotFound as i32, 21900006);
     assert_eq!(ErrorCode::TaskStateErr as i32, 21900007);
+    assert_eq!(ErrorCode::ConstructThrottled as i32, 21900009);
+    assert_eq!(ErrorCode::TooManyTasks as i32, 21900010);
+    assert_eq!(ErrorCode::TooManyChannels as i32, 21900011);
 }
\ No newline at end of file
@@ -0,0 +1,52 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+const HEADERS: &str = "
+# tenants
+1000 X-Tenant: example
+1000 Accept-Language: en
+2000 X-Tenant: other
+1000 broken
+";
+
+// @tc.name: ut_default_headers_merge
+// @tc.desc: Test default headers are added to the headers of a task
+// @tc.precon: NA
+// @tc.step: 1. Parse default headers with a comment and a malformed line
+//           2. Merge them with task headers of the configured uid and of
+//              another uid
+// @tc.expect: The malformed line is skipped, a task header wins over the
+// default of the same name and other uids get no defaults
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_default_headers_merge() {
+    let defaults = DefaultHeaders::new(DefaultHeaders::parse(HEADERS));
+    assert_eq!(defaults.headers.read().unwrap().len(), 2);
+
+    let mut headers = HashMap::new();
+    headers.insert("accept-language".to_string(), "de".to_string());
+    assert_eq!(
+        defaults.merge(1000, &headers),
+        vec![
+            ("X-Tenant".to_string(), "example".to_string()),
+            ("accept-language".to_string(), "de".to_string()),
+        ]
+    );
+    assert_eq!(
+        defaults.merge(3000, &headers),
+        vec![("accept-language".to_string(), "de".to_string())]
+    );
+}
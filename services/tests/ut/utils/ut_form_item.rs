@@ -0,0 +1,44 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+fn item(name: &str, value: &str) -> FormItem {
+    FormItem {
+        name: name.to_string(),
+        value: value.to_string(),
+    }
+}
+
+// @tc.name: ut_form_urlencode_special_chars
+// @tc.desc: Test urlencoded serialization of reserved and non-ASCII characters
+// @tc.precon: NA
+// @tc.step: 1. Encode form items containing spaces, reserved and UTF-8 characters
+// @tc.expect: Unreserved characters are kept, spaces become `+` and everything
+// else is percent-encoded with upper case hex digits
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_form_urlencode_special_chars() {
+    let items = vec![
+        item("name", "a b&c=d+e"),
+        item("path/key", "50%~*-._"),
+        item("city", "Zürich\n"),
+    ];
+    assert_eq!(
+        form_urlencode(&items),
+        "name=a+b%26c%3Dd%2Be&path%2Fkey=50%25%7E*-._&city=Z%C3%BCrich%0A"
+    );
+    assert_eq!(form_urlencode(&[]), "");
+    assert_eq!(form_urlencode(&[item("", "")]), "=");
+}
@@ -11,7 +11,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::utils::task_id_generator::TaskIdGenerator;
+use super::*;
 
 // @tc.name: ut_task_id_generator_generate_basic
 // @tc.desc: Test basic functionality of task ID generation
@@ -125,6 +125,59 @@ fn ut_task_id_generator_concurrent_safety() {
     assert_eq!(final_ids.len(), 400);
 }
 
+// @tc.name: ut_task_id_generator_mix
+// @tc.desc: Test candidates differ by sequence number and uptime
+// @tc.precon: NA
+// @tc.step: 1. Mix the same clock with consecutive sequence numbers
+//           2. Mix the same clock and sequence number with another uptime
+//           3. Mix values cancelling each other out
+// @tc.expect: All candidates differ, are positive as an i32 and never 0
+// @tc.type: FUNC
+// @tc.require: issue#ICODZX
+// @tc.level: Level 1
+#[test]
+fn ut_task_id_generator_mix() {
+    use std::collections::HashSet;
+
+    let mut ids = HashSet::new();
+    for sequence in 0..1000 {
+        assert!(ids.insert(TaskIdGenerator::mix(12345, 0, sequence)));
+    }
+    for uptime in 1..1000 {
+        assert!(ids.insert(TaskIdGenerator::mix(12345, uptime << 16, 0)));
+    }
+    assert!(ids.iter().all(|id| *id != 0 && *id <= i32::MAX as u32));
+    assert_eq!(TaskIdGenerator::mix(0, 0, 0), 1);
+}
+
+// @tc.name: ut_task_id_generator_allocate_taken
+// @tc.desc: Test allocation skips candidates used by stored tasks
+// @tc.precon: NA
+// @tc.step: 1. Allocate from candidates whose first two are taken
+//           2. Allocate from more taken candidates than are tried
+// @tc.expect: The third candidate is allocated, then no id is allocated
+//             after `MAX_ID_ATTEMPTS` candidates were tried
+// @tc.type: FUNC
+// @tc.require: issue#ICODZX
+// @tc.level: Level 1
+#[test]
+fn ut_task_id_generator_allocate_taken() {
+    let taken = [10, 11];
+    assert_eq!(
+        TaskIdGenerator::allocate_from([10, 11, 12], |id| taken.contains(&id)),
+        Some(12)
+    );
+
+    let mut tried = 0;
+    let allocated = TaskIdGenerator::allocate_from(1.., |_| {
+        tried += 1;
+        true
+    });
+    assert_eq!(allocated, None);
+    assert_eq!(tried, MAX_ID_ATTEMPTS);
+    assert!(TaskIdGenerator::allocate(|_| false).is_some());
+}
+
 // Conditional tests for OH feature
 #[cfg(feature = "oh")]
 mod oh_tests {
@@ -135,7 +188,7 @@ mod oh_tests {
     // @tc.precon: OH feature is enabled
     // @tc.step: 1. Generate task ID
     //           2. Verify ID is reasonable
-    // @tc.expect: ID is generated successfully
+    // @tc.expect: ID is generated successfully and positive as an i32
     // @tc.type: FUNC
     // @tc.require: issue#ICODZX
     // @tc.level: Level 1
@@ -143,7 +196,7 @@ mod oh_tests {
     fn ut_task_id_generator_oh_system_time() {
         let task_id = TaskIdGenerator::generate();
         assert_ne!(task_id, 0);
-        assert!(task_id < 2_000_000_000);
+        assert!(task_id <= i32::MAX as u32);
     }
 }
 
@@ -0,0 +1,118 @@
+// Copyright (C) 2025 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+
+use ylong_runtime::sync::mpsc::unbounded_channel;
+
+use super::*;
+use crate::config::{Action, ConfigBuilder, Mode};
+use crate::service::client::ClientManagerEntry;
+use crate::task::request_task::{check_config, get_rest_time, RequestTask};
+
+const RULES: &str = "
+# mirrors
+prefix http://old.example.com/ https://new.example.com/
+regex ^http://(\\w+)\\.cdn\\.example\\.com/(.*)$ https://cdn.example.com/$1/$2
+prefix broken
+";
+
+// @tc.name: ut_url_rewrite_prefix
+// @tc.desc: Test prefix rules replace only the leading part of the URL
+// @tc.precon: NA
+// @tc.step: 1. Parse rules with a comment and a malformed line
+//           2. Rewrite a matching and a non-matching URL
+// @tc.expect: The malformed line is skipped, the matching URL keeps its path
+// and the other URL is not rewritten
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_url_rewrite_prefix() {
+    let rewriter = UrlRewriter::new(UrlRewriter::parse(RULES));
+    assert_eq!(rewriter.rules.read().unwrap().len(), 2);
+    assert_eq!(
+        rewriter.rewrite("http://old.example.com/a/b.txt?x=1").as_deref(),
+        Some("https://new.example.com/a/b.txt?x=1")
+    );
+    assert_eq!(rewriter.rewrite("http://other.example.com/old.example.com/"), None);
+}
+
+// @tc.name: ut_url_rewrite_regex
+// @tc.desc: Test regex rules substitute capture groups
+// @tc.precon: NA
+// @tc.step: 1. Parse a regex rule
+//           2. Rewrite a matching and a non-matching URL
+// @tc.expect: Capture groups are placed into the replacement, non-matching URLs
+// are not rewritten
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_url_rewrite_regex() {
+    let rewriter = UrlRewriter::new(UrlRewriter::parse(RULES));
+    assert_eq!(
+        rewriter.rewrite("http://img.cdn.example.com/logo.png").as_deref(),
+        Some("https://cdn.example.com/img/logo.png")
+    );
+    assert_eq!(rewriter.rewrite("https://img.cdn.example.com/logo.png"), None);
+
+    rewriter.set_rules(vec![]);
+    assert_eq!(rewriter.rewrite("http://img.cdn.example.com/logo.png"), None);
+}
+
+// @tc.name: ut_url_rewrite_task_info
+// @tc.desc: Test a rewritten task requests the effective URL but reports the
+// original one
+// @tc.precon: NA
+// @tc.step: 1. Apply prefix rules to a download config
+//           2. Construct a task from it
+// @tc.expect: The task requests the rewritten URL and its TaskInfo keeps the
+// original URL
+// @tc.type: FUNC
+// @tc.require: issues#ICN16H
+#[test]
+fn ut_url_rewrite_task_info() {
+    let _ = std::fs::create_dir("test_files/");
+    let file = File::create("test_files/ut_url_rewrite_task_info.txt").unwrap();
+    let original = "http://old.example.com/file.txt";
+    let mut config = ConfigBuilder::new()
+        .action(Action::Download)
+        .mode(Mode::BackGround)
+        .file_spec(file)
+        .url(original)
+        .build();
+
+    let rewriter = UrlRewriter::new(UrlRewriter::parse(RULES));
+    rewriter.apply(&mut config);
+    assert_eq!(config.url, original);
+    assert_eq!(config.request_url(), "https://new.example.com/file.txt");
+
+    let (tx, _) = unbounded_channel();
+    let rest_time = get_rest_time(&config, 0);
+    let (files, client, used_family) = check_config(&config, rest_time).unwrap();
+    let task = RequestTask::new(
+        config,
+        files,
+        client,
+        used_family,
+        ClientManagerEntry::new(tx),
+        false,
+        rest_time,
+    );
+    assert_eq!(task.conf.request_url(), "https://new.example.com/file.txt");
+    assert_eq!(task.info().url, original);
+
+    rewriter.set_rules(vec![]);
+    let mut config = task.conf.clone();
+    rewriter.apply(&mut config);
+    assert_eq!(config.request_url(), original);
+}